@@ -71,6 +71,8 @@ pub async fn execute(body: &str) -> flow_like_types::Result<()> {
         runtime_variables: payload.runtime_variables,
         user_context: payload.user_context,
         profile: payload.profile,
+        on_node_error: Default::default(),
+        deterministic: false,
     };
 
     let config = ExecutorConfig::from_env();
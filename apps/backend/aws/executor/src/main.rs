@@ -61,6 +61,7 @@ async fn main() -> Result<(), Error> {
 
     // Create executor state from environment
     let state = ExecutorState::from_env();
+    state.readiness.mark_catalog_initialized();
 
     // Build router with all execution endpoints
     let app = executor_router(state);
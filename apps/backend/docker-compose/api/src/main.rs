@@ -11,7 +11,7 @@ use axum::{
 };
 use flow_like_api::{construct_router, state::State};
 use flow_like_catalog::get_catalog;
-use std::{sync::Arc, time::Instant};
+use std::{net::SocketAddr, sync::Arc, time::Instant};
 use tower_http::cors::CorsLayer;
 
 mod config;
@@ -86,6 +86,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let listener = tokio::net::TcpListener::bind(&addr).await?;
     let metrics_listener = tokio::net::TcpListener::bind(&metrics_addr).await?;
 
+    // The rate limiter falls back to the client address for anonymous requests
+    let app = app.into_make_service_with_connect_info::<SocketAddr>();
+
     tokio::select! {
         res = axum::serve(listener, app) => res?,
         res = axum::serve(metrics_listener, metrics_app) => res?,
@@ -14,6 +14,7 @@ use flow_like_catalog::initialize as initialize_catalog;
 use flow_like_executor::{
     execute, executor_router, ExecutionRequest, ExecutorConfig, ExecutorState,
 };
+use std::sync::Arc;
 use std::time::Instant;
 
 mod config;
@@ -69,6 +70,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     );
 
     let executor_config = ExecutorConfig::from_env();
+    let state = ExecutorState::new(executor_config.clone());
+    let readiness = state.readiness.clone();
+    readiness.mark_catalog_initialized();
 
     // Start queue worker if enabled
     if config.queue_worker_enabled {
@@ -80,13 +84,21 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             queue_name: config.redis_queue_name.clone(),
             concurrency: config.max_concurrent_executions,
             poll_timeout_secs: 30,
+            max_attempts: config.queue_max_attempts,
         };
 
         let worker_executor_config = executor_config.clone();
 
-        tokio::spawn(async move {
-            match QueueWorker::new(queue_config).await {
-                Ok(worker) => {
+        match QueueWorker::new(queue_config).await {
+            Ok(worker) => {
+                let worker = Arc::new(worker);
+                let probe = worker.clone();
+                readiness.add_check("redis", move || {
+                    let probe = probe.clone();
+                    async move { probe.ping().await.map_err(|e| e.to_string()) }
+                });
+
+                tokio::spawn(async move {
                     tracing::info!("Queue worker started");
                     let _ = worker
                         .run(move |job: QueuedJob| {
@@ -94,16 +106,20 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                             async move { process_queued_job(job, executor_config).await }
                         })
                         .await;
-                }
-                Err(e) => {
-                    tracing::error!(error = %e, "Failed to start queue worker");
-                }
+                });
+            }
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to start queue worker");
+                let error = e.to_string();
+                readiness.add_check("redis", move || {
+                    let error = error.clone();
+                    async move { Err(error) }
+                });
             }
-        });
+        }
     }
 
-    // Create executor state with metrics middleware
-    let state = ExecutorState::new(executor_config);
+    // Create executor router with metrics middleware
     let app = executor_router(state)
         .route("/metrics", get(metrics::handler))
         .layer(middleware::from_fn(metrics_middleware));
@@ -141,6 +157,8 @@ async fn process_queued_job(job: QueuedJob, executor_config: ExecutorConfig) ->
         runtime_variables: job.runtime_variables,
         user_context: job.user_context,
         profile: job.profile,
+        on_node_error: Default::default(),
+        deterministic: false,
     };
 
     let result = execute(exec_request, executor_config).await;
@@ -155,6 +173,7 @@ async fn process_queued_job(job: QueuedJob, executor_config: ExecutorConfig) ->
                 "Job completed"
             );
             metrics::record_execution("success", duration_secs);
+            metrics::record_node_timings(&exec_result.node_timings);
         }
         Err(e) => {
             tracing::error!(job_id = %job.job_id, error = %e, "Job failed");
@@ -1,8 +1,10 @@
 use axum::response::IntoResponse;
+use flow_like_executor::NodeTiming;
 use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
 use opentelemetry::trace::TracerProvider as _;
 use opentelemetry_otlp::WithExportConfig;
 use opentelemetry_sdk::{runtime, trace::TracerProvider};
+use std::collections::BTreeMap;
 use std::sync::OnceLock;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
@@ -57,6 +59,16 @@ fn init_metrics() {
             &[0.1, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0, 300.0],
         )
         .unwrap()
+        .set_buckets_for_metric(
+            Matcher::Full("flow_node_duration_seconds".to_string()),
+            &[0.001, 0.01, 0.05, 0.1, 0.5, 1.0, 5.0, 30.0, 120.0],
+        )
+        .unwrap()
+        .set_buckets_for_metric(
+            Matcher::Full(flow_like_wasm::metrics::EXECUTION_DURATION_SECONDS.to_string()),
+            flow_like_wasm::metrics::DURATION_BUCKETS,
+        )
+        .unwrap()
         .install_recorder()
         .expect("failed to install Prometheus recorder");
 
@@ -69,7 +81,12 @@ fn init_metrics() {
         "flow_execution_duration_seconds",
         "Flow execution duration in seconds"
     );
+    metrics::describe_histogram!(
+        "flow_node_duration_seconds",
+        "Time a node spent executing within one run, summed over its executions"
+    );
     metrics::describe_gauge!("executor_active_jobs", "Number of currently executing jobs");
+    flow_like_wasm::metrics::describe_metrics();
     metrics::describe_counter!("http_requests_total", "Total HTTP requests");
     metrics::describe_histogram!(
         "http_request_duration_seconds",
@@ -90,6 +107,16 @@ pub fn record_execution(status: &str, duration_secs: f64) {
         .record(duration_secs);
 }
 
+pub fn record_node_timings(timings: &BTreeMap<String, NodeTiming>) {
+    for timing in timings.values() {
+        metrics::histogram!("flow_node_duration_seconds",
+            "node_type" => timing.node_type.clone(),
+            "status" => if timing.failed > 0 { "failure" } else { "success" }
+        )
+        .record(timing.total_ms / 1000.0);
+    }
+}
+
 pub fn increment_active_jobs() {
     metrics::gauge!("executor_active_jobs").increment(1.0);
 }
@@ -0,0 +1,193 @@
+//! AMQP (RabbitMQ) consumers for Flow-Like sink services
+//!
+//! Runs one queue consumer per active AMQP sink. Sinks are synced from the API and
+//! consumers are restarted when their config changes. Deliveries are only acked once the
+//! API started the run, see `flow_like_sinks::amqp`.
+
+use crate::api_client::{ApiClient, SinkConfigInfo};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+#[cfg(feature = "amqp")]
+use flow_like_sinks::{
+    amqp::{consumer, AmqpSink},
+    Executor, SinkContext, SinkError, SinkExecution, SinkRegistration, SinkResult, SinkType,
+};
+
+/// Wait before reconnecting a consumer whose connection failed or closed
+#[cfg(feature = "amqp")]
+const RECONNECT_DELAY: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Starts the runs of consumed deliveries through the service trigger endpoint
+#[cfg(feature = "amqp")]
+struct ApiExecutor {
+    api_client: Arc<ApiClient>,
+}
+
+#[cfg(feature = "amqp")]
+#[async_trait::async_trait]
+impl Executor for ApiExecutor {
+    async fn execute_event(
+        &self,
+        _app_id: &str,
+        _board_id: &str,
+        event_id: &str,
+        payload: Option<serde_json::Value>,
+        _personal_access_token: Option<&str>,
+    ) -> SinkResult<String> {
+        self.api_client
+            .start_sink_run(event_id, "amqp", payload.unwrap_or_default())
+            .await
+            .map(Option::unwrap_or_default)
+            .map_err(|e| SinkError::ExecutionFailed(e.to_string()))
+    }
+}
+
+/// Manages one consumer per AMQP sink
+pub struct AmqpConsumerManager {
+    api_client: Arc<ApiClient>,
+    /// Running consumers: event_id -> (serialized config, consumer task)
+    consumers: RwLock<HashMap<String, (String, tokio::task::JoinHandle<()>)>>,
+}
+
+impl AmqpConsumerManager {
+    pub fn new(api_client: Arc<ApiClient>) -> Self {
+        Self {
+            api_client,
+            consumers: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Sync consumers with the active AMQP sinks of the API
+    pub async fn sync_consumers(
+        &self,
+    ) -> Result<SyncResult, Box<dyn std::error::Error + Send + Sync>> {
+        let sinks: HashMap<String, SinkConfigInfo> = self
+            .api_client
+            .get_sink_configs("amqp")
+            .await?
+            .into_iter()
+            .filter(|sink| sink.active)
+            .map(|sink| (sink.event_id.clone(), sink))
+            .collect();
+
+        let mut consumers = self.consumers.write().await;
+        let mut result = SyncResult::default();
+
+        // Stop consumers of removed sinks and of sinks whose config changed
+        consumers.retain(|event_id, (config, handle)| {
+            let current = sinks.get(event_id).map(config_key);
+            if current.as_ref() == Some(config) {
+                return true;
+            }
+            info!(event_id = %event_id, "Stopping AMQP consumer");
+            handle.abort();
+            result.stopped += 1;
+            false
+        });
+
+        for (event_id, sink) in sinks {
+            if consumers.contains_key(&event_id) {
+                continue;
+            }
+            info!(event_id = %event_id, "Starting AMQP consumer");
+            let key = config_key(&sink);
+            let handle = tokio::spawn(run_consumer(Arc::clone(&self.api_client), sink));
+            consumers.insert(event_id, (key, handle));
+            result.started += 1;
+        }
+
+        Ok(result)
+    }
+
+    pub async fn get_active_consumer_count(&self) -> usize {
+        self.consumers.read().await.len()
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct SyncResult {
+    pub started: usize,
+    pub stopped: usize,
+}
+
+fn config_key(sink: &SinkConfigInfo) -> String {
+    sink.config
+        .as_ref()
+        .map(|config| config.to_string())
+        .unwrap_or_default()
+}
+
+/// Consumes the queue of a sink, reconnecting until the task is aborted
+#[cfg(feature = "amqp")]
+async fn run_consumer(api_client: Arc<ApiClient>, sink: SinkConfigInfo) {
+    let registration = SinkRegistration {
+        id: sink.event_id.clone(),
+        event_id: sink.event_id.clone(),
+        board_id: String::new(),
+        app_id: sink.app_id.clone(),
+        sink_type: SinkType::Amqp,
+        config: sink.config.clone().unwrap_or_default(),
+        execution: SinkExecution::Remote,
+        active: sink.active,
+        auth_token: None,
+        path: None,
+        method: None,
+        cron_expression: None,
+        default_payload: None,
+        personal_access_token: None,
+        oauth_tokens: None,
+    };
+    let ctx = SinkContext::new(Arc::new(ApiExecutor { api_client }));
+    let amqp = AmqpSink::new();
+
+    loop {
+        match consumer::consume(&amqp, &ctx, &registration).await {
+            Ok(()) => warn!(event_id = %sink.event_id, "AMQP connection closed, reconnecting"),
+            Err(SinkError::InvalidConfig(e)) => {
+                // Retrying cannot fix the config, the next sync restarts the consumer once it changed
+                warn!(event_id = %sink.event_id, error = %e, "Invalid AMQP sink config");
+                return;
+            }
+            Err(e) => warn!(event_id = %sink.event_id, error = %e, "AMQP consumer failed"),
+        }
+        tokio::time::sleep(RECONNECT_DELAY).await;
+    }
+}
+
+#[cfg(not(feature = "amqp"))]
+async fn run_consumer(_api_client: Arc<ApiClient>, sink: SinkConfigInfo) {
+    warn!(event_id = %sink.event_id, "AMQP feature not enabled");
+}
+
+/// Start the AMQP consumer manager
+pub async fn start_amqp_consumers(
+    api_client: Arc<ApiClient>,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let manager = AmqpConsumerManager::new(api_client);
+
+    info!("Starting AMQP consumer manager");
+
+    loop {
+        match manager.sync_consumers().await {
+            Ok(result) => {
+                if result.started > 0 || result.stopped > 0 {
+                    let active = manager.get_active_consumer_count().await;
+                    info!(
+                        started = result.started,
+                        stopped = result.stopped,
+                        active = active,
+                        "AMQP consumer sync"
+                    );
+                }
+            }
+            Err(e) => {
+                warn!("AMQP consumer sync failed: {}", e);
+            }
+        }
+
+        tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+    }
+}
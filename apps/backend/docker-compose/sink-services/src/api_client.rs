@@ -133,6 +133,88 @@ impl ApiClient {
         }
     }
 
+    /// Trigger a sink event via the async trigger endpoint and return the id of the started run.
+    /// Fails when the API did not start a run, e.g. because the sink is throttled.
+    pub async fn start_sink_run(
+        &self,
+        event_id: &str,
+        sink_type: &str,
+        payload: serde_json::Value,
+    ) -> Result<Option<String>, ApiError> {
+        let url = format!("{}/api/v1/sink/trigger/async", self.base_url);
+
+        let body = serde_json::json!({
+            "event_id": event_id,
+            "sink_type": sink_type,
+            "payload": payload,
+        });
+
+        let response = self
+            .client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", self.jwt))
+            .header("Content-Type", "application/json")
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| ApiError::Request(e.to_string()))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            return Err(ApiError::Response {
+                status: status.as_u16(),
+                message: text,
+            });
+        }
+
+        let result: ServiceTriggerResult = response
+            .json()
+            .await
+            .map_err(|e| ApiError::Parse(e.to_string()))?;
+        if result.success {
+            Ok(result.run_id)
+        } else {
+            Err(ApiError::Response {
+                status: status.as_u16(),
+                message: result
+                    .error
+                    .unwrap_or_else(|| "Event was not triggered".to_string()),
+            })
+        }
+    }
+
+    /// Get the active sinks of a type with their event config
+    pub async fn get_sink_configs(&self, sink_type: &str) -> Result<Vec<SinkConfigInfo>, ApiError> {
+        let url = format!(
+            "{}/api/v1/sink/configs?sink_type={}",
+            self.base_url, sink_type
+        );
+
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", self.jwt))
+            .send()
+            .await
+            .map_err(|e| ApiError::Request(e.to_string()))?;
+
+        if response.status().is_success() {
+            let configs: Vec<SinkConfigInfo> = response
+                .json()
+                .await
+                .map_err(|e| ApiError::Parse(e.to_string()))?;
+            Ok(configs)
+        } else {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            Err(ApiError::Response {
+                status: status.as_u16(),
+                message: text,
+            })
+        }
+    }
+
     /// Get bot configurations grouped by token
     /// Returns bots with their tokens and associated event handlers
     pub async fn get_bot_configs(&self, sink_type: &str) -> Result<Vec<BotConfig>, ApiError> {
@@ -177,6 +259,22 @@ pub struct BotHandler {
     pub config: serde_json::Value,
 }
 
+#[derive(Debug, Deserialize)]
+struct ServiceTriggerResult {
+    success: bool,
+    run_id: Option<String>,
+    error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SinkConfigInfo {
+    pub event_id: String,
+    pub app_id: String,
+    pub sink_type: String,
+    pub active: bool,
+    pub config: Option<serde_json::Value>,
+}
+
 #[derive(Debug)]
 pub enum ApiError {
     Request(String),
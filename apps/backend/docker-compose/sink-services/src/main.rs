@@ -1,3 +1,4 @@
+mod amqp;
 mod api_client;
 mod cron;
 mod discord;
@@ -24,6 +25,8 @@ struct SupportedSinks {
     discord: bool,
     #[serde(default)]
     telegram: bool,
+    #[serde(default)]
+    amqp: bool,
 }
 
 fn load_config() -> Result<Config, Box<dyn std::error::Error>> {
@@ -55,10 +58,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     };
 
     info!(
-        "Loaded config: cron={}, discord={}, telegram={}",
+        "Loaded config: cron={}, discord={}, telegram={}, amqp={}",
         config.supported_sinks.cron,
         config.supported_sinks.discord,
-        config.supported_sinks.telegram
+        config.supported_sinks.telegram,
+        config.supported_sinks.amqp
     );
 
     let api_base_url =
@@ -132,6 +136,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         info!("Telegram bot task spawned");
     }
 
+    if config.supported_sinks.amqp {
+        let api_client_amqp = Arc::clone(&api_client);
+        handles.push(tokio::spawn(async move {
+            if let Err(e) = amqp::start_amqp_consumers(api_client_amqp).await {
+                error!("AMQP consumer error: {}", e);
+            }
+        }));
+        info!("AMQP consumer task spawned");
+    }
+
     if handles.is_empty() {
         warn!("No sinks enabled - service will idle");
     }
@@ -11,7 +11,10 @@ use tokio::sync::RwLock;
 use tracing::{info, warn};
 
 #[cfg(feature = "telegram")]
-use teloxide::{prelude::*, types::Message};
+use teloxide::{
+    prelude::*,
+    types::{CallbackQuery, Message},
+};
 
 /// Event handler configuration for a Telegram bot
 #[derive(Debug, Clone)]
@@ -294,6 +297,40 @@ impl From<&Message> for TelegramMessage {
     }
 }
 
+/// Serializable data of an inline keyboard button click
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct TelegramCallbackQuery {
+    pub id: String,
+    pub data: Option<String>,
+    pub from_id: i64,
+    pub from_username: Option<String>,
+    pub from_first_name: String,
+    /// Chat and message of the button, missing for messages sent in inline mode
+    pub chat_id: Option<i64>,
+    pub message_id: Option<i32>,
+    pub message_text: Option<String>,
+}
+
+#[cfg(feature = "telegram")]
+impl From<&CallbackQuery> for TelegramCallbackQuery {
+    fn from(query: &CallbackQuery) -> Self {
+        let message = query.message.as_ref();
+        Self {
+            id: query.id.clone(),
+            data: query.data.clone(),
+            from_id: query.from.id.0 as i64,
+            from_username: query.from.username.clone(),
+            from_first_name: query.from.first_name.clone(),
+            chat_id: message.map(|m| m.chat().id.0),
+            message_id: message.map(|m| m.id().0),
+            message_text: message
+                .and_then(|m| m.regular_message())
+                .and_then(|m| m.text())
+                .map(String::from),
+        }
+    }
+}
+
 #[cfg(feature = "telegram")]
 async fn run_telegram_bot(
     bot_id: String,
@@ -320,7 +357,7 @@ async fn run_telegram_bot(
     let api_client_clone = api_client.clone();
     let handlers_clone = handlers.clone();
 
-    let handler = Update::filter_message().endpoint(move |_bot: Bot, msg: Message| {
+    let message_handler = Update::filter_message().endpoint(move |_bot: Bot, msg: Message| {
         let bot_id = bot_id_clone.clone();
         let api_client = api_client_clone.clone();
         let handlers = handlers_clone.clone();
@@ -331,6 +368,24 @@ async fn run_telegram_bot(
         }
     });
 
+    let bot_id_clone = bot_id.clone();
+    let callback_handler =
+        Update::filter_callback_query().endpoint(move |bot: Bot, query: CallbackQuery| {
+            let bot_id = bot_id_clone.clone();
+            let api_client = api_client.clone();
+            let handlers = handlers.clone();
+
+            async move {
+                handle_telegram_callback(&bot_id, bot.token(), &api_client, &handlers, &query)
+                    .await;
+                respond(())
+            }
+        });
+
+    let handler = dptree::entry()
+        .branch(message_handler)
+        .branch(callback_handler);
+
     let mut dispatcher = Dispatcher::builder(bot, handler).build();
 
     // Get shutdown token before dispatching
@@ -407,6 +462,66 @@ async fn handle_telegram_message(
     }
 }
 
+/// Fires the handlers of the chat for a button click. Command filters do not apply, the
+/// click answers a message the bot sent itself. The `local_session` lets the flow answer
+/// the callback and edit the message.
+#[cfg(feature = "telegram")]
+async fn handle_telegram_callback(
+    bot_id: &str,
+    token: &str,
+    api_client: &ApiClient,
+    handlers: &Arc<RwLock<HashMap<String, Vec<TelegramEventHandler>>>>,
+    query: &CallbackQuery,
+) {
+    let callback = TelegramCallbackQuery::from(query);
+    let chat = query.message.as_ref().map(|m| m.chat());
+
+    let handlers_guard = handlers.read().await;
+    let Some(bot_handlers) = handlers_guard.get(bot_id) else {
+        return;
+    };
+
+    for handler in bot_handlers {
+        if let Some(required_chat) = handler.chat_id {
+            if callback.chat_id != Some(required_chat) {
+                continue;
+            }
+        }
+
+        let payload = serde_json::json!({
+            "source": "telegram",
+            "bot_id": bot_id,
+            "event_id": handler.event_id,
+            "local_session": {
+                "bot_token": token,
+                "chat_id": callback.chat_id.map(|id| id.to_string()).unwrap_or_default(),
+                "chat_type": chat.map(|c| format!("{:?}", c.kind)).unwrap_or_default(),
+                "chat_title": chat.and_then(|c| c.title()),
+                "message_id": callback.message_id.map(|id| id.to_string()).unwrap_or_default(),
+                "user": {
+                    "id": callback.from_id.to_string(),
+                    "name": callback.from_first_name,
+                    "username": callback.from_username,
+                    "is_bot": query.from.is_bot,
+                },
+            },
+            "callback_query": callback,
+        });
+
+        match api_client
+            .trigger_sink(&handler.event_id, "telegram", payload)
+            .await
+        {
+            Ok(_) => {
+                debug!(event_id = %handler.event_id, "Telegram callback triggered");
+            }
+            Err(e) => {
+                error!(event_id = %handler.event_id, error = %e, "Failed to trigger event");
+            }
+        }
+    }
+}
+
 /// Start the Telegram bot manager
 #[cfg(feature = "telegram")]
 pub async fn start_telegram_bot(
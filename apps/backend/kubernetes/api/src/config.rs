@@ -39,7 +39,10 @@ pub struct R2StorageConfig {
 #[derive(Clone, Debug, Deserialize)]
 pub struct AzureStorageConfig {
     pub account_name: String,
+    /// Falls back to the managed identity of the pod when unset
     pub account_key: Option<String>,
+    /// User assigned managed identity, the system assigned one is used when unset
+    pub client_id: Option<String>,
     pub content_container: String,
 }
 
@@ -47,6 +50,8 @@ pub struct AzureStorageConfig {
 pub struct GcpStorageConfig {
     pub project_id: String,
     pub service_account_key: Option<String>,
+    /// Key file, e.g. a mounted secret. Workload Identity is used when neither is set
+    pub service_account_path: Option<String>,
     pub content_bucket: String,
 }
 
@@ -136,19 +141,27 @@ impl Config {
                     .map_err(|_| ConfigError::MissingVar("AZURE_STORAGE_ACCOUNT_NAME"))?;
                 Ok(StorageConfig::Azure(AzureStorageConfig {
                     account_name,
-                    account_key: env::var("AZURE_STORAGE_ACCOUNT_KEY").ok(),
+                    account_key: env::var("AZURE_STORAGE_ACCOUNT_KEY")
+                        .ok()
+                        .filter(|key| !key.is_empty()),
+                    client_id: env::var("AZURE_CLIENT_ID").ok().filter(|id| !id.is_empty()),
                     content_container: env::var("CONTENT_BUCKET")
                         .or_else(|_| env::var("AZURE_CONTENT_CONTAINER"))
                         .unwrap_or_else(|_| "flow-like-content".to_string()),
                 }))
             }
 
-            "gcp" => {
+            "gcp" | "gcs" | "google" => {
                 let project_id = env::var("GCP_PROJECT_ID")
                     .map_err(|_| ConfigError::MissingVar("GCP_PROJECT_ID"))?;
                 Ok(StorageConfig::Gcp(GcpStorageConfig {
                     project_id,
-                    service_account_key: env::var("GOOGLE_APPLICATION_CREDENTIALS_JSON").ok(),
+                    service_account_key: env::var("GOOGLE_APPLICATION_CREDENTIALS_JSON")
+                        .ok()
+                        .filter(|key| !key.is_empty()),
+                    service_account_path: env::var("GOOGLE_SERVICE_ACCOUNT_PATH")
+                        .ok()
+                        .filter(|path| !path.is_empty()),
                     content_bucket: env::var("CONTENT_BUCKET")
                         .or_else(|_| env::var("GCP_CONTENT_BUCKET"))
                         .unwrap_or_else(|_| "flow-like-content".to_string()),
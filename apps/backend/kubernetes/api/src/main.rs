@@ -4,7 +4,7 @@ static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
 use axum::Router;
 use flow_like_api::{construct_router, state::State};
 use flow_like_catalog::get_catalog;
-use std::sync::Arc;
+use std::{net::SocketAddr, sync::Arc};
 use tower_http::cors::CorsLayer;
 
 mod config;
@@ -55,6 +55,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let listener = tokio::net::TcpListener::bind(&addr).await?;
     let metrics_listener = tokio::net::TcpListener::bind(&metrics_addr).await?;
 
+    // The rate limiter falls back to the client address for anonymous requests
+    let app = app.into_make_service_with_connect_info::<SocketAddr>();
+
     tokio::select! {
         res = axum::serve(listener, app) => res?,
         res = axum::serve(metrics_listener, metrics_app) => res?,
@@ -1,10 +1,6 @@
 use crate::config::Config;
-use flow_like_storage::files::store::FlowLikeStore;
-use flow_like_storage::object_store::{
-    aws::AmazonS3Builder,
-    azure::{AzureConfigKey, MicrosoftAzureBuilder},
-    gcp::GoogleCloudStorageBuilder,
-};
+use flow_like_storage::files::store::{AzureCredential, FlowLikeStore, GoogleCredential};
+use flow_like_storage::object_store::aws::AmazonS3Builder;
 use std::sync::Arc;
 
 pub fn create_content_store(config: &Config) -> Result<FlowLikeStore, StorageError> {
@@ -47,31 +43,27 @@ fn build_aws_store(cfg: &crate::config::AwsStorageConfig) -> Result<FlowLikeStor
 fn build_azure_store(
     cfg: &crate::config::AzureStorageConfig,
 ) -> Result<FlowLikeStore, StorageError> {
-    let mut builder = MicrosoftAzureBuilder::new()
-        .with_account(&cfg.account_name)
-        .with_container_name(&cfg.content_container);
-
-    if let Some(key) = &cfg.account_key {
-        builder = builder.with_config(AzureConfigKey::AccessKey, key);
-    }
-
-    let store = builder
-        .build()
-        .map_err(|e| StorageError::Build(format!("Azure: {}", e)))?;
-    Ok(FlowLikeStore::Azure(Arc::new(store)))
+    let credential = match &cfg.account_key {
+        Some(key) => AzureCredential::AccessKey(key.clone()),
+        None => AzureCredential::ManagedIdentity {
+            client_id: cfg.client_id.clone(),
+            msi_endpoint: None,
+        },
+    };
+
+    FlowLikeStore::azure(&cfg.account_name, &cfg.content_container, credential)
+        .map_err(|e| StorageError::Build(format!("Azure: {}", e)))
 }
 
 fn build_gcp_store(cfg: &crate::config::GcpStorageConfig) -> Result<FlowLikeStore, StorageError> {
-    let mut builder = GoogleCloudStorageBuilder::new().with_bucket_name(&cfg.content_bucket);
-
-    if let Some(key) = &cfg.service_account_key {
-        builder = builder.with_service_account_key(key);
-    }
-
-    let store = builder
-        .build()
-        .map_err(|e| StorageError::Build(format!("GCP: {}", e)))?;
-    Ok(FlowLikeStore::Google(Arc::new(store)))
+    let credential = match (&cfg.service_account_key, &cfg.service_account_path) {
+        (Some(key), _) => GoogleCredential::ServiceAccountKey(key.clone()),
+        (None, Some(path)) => GoogleCredential::ServiceAccountPath(path.clone()),
+        (None, None) => GoogleCredential::WorkloadIdentity,
+    };
+
+    FlowLikeStore::google(&cfg.content_bucket, credential)
+        .map_err(|e| StorageError::Build(format!("GCP: {}", e)))
 }
 
 fn build_r2_store(cfg: &crate::config::R2StorageConfig) -> Result<FlowLikeStore, StorageError> {
@@ -127,3 +119,28 @@ impl std::fmt::Display for StorageError {
 }
 
 impl std::error::Error for StorageError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{GcpStorageConfig, StorageConfig};
+
+    #[test]
+    fn test_gcp_provider_builds_google_store() {
+        let config = Config {
+            port: 8080,
+            storage_config: StorageConfig::Gcp(GcpStorageConfig {
+                project_id: "flow-like".to_string(),
+                service_account_key: None,
+                service_account_path: None,
+                content_bucket: "flow-like-content".to_string(),
+            }),
+        };
+
+        assert_eq!(config.storage_provider(), "gcp");
+        assert!(matches!(
+            create_content_store(&config),
+            Ok(FlowLikeStore::Google(_))
+        ));
+    }
+}
@@ -10,6 +10,8 @@
 //! - `POST /execute/stream` - Execute with NDJSON streaming
 //! - `POST /execute/sse` - Execute with Server-Sent Events
 //! - `GET /health` - Health check
+//! - `GET /health/live` - Liveness probe
+//! - `GET /health/ready` - Readiness probe, 503 until the catalog is initialized
 //! - `GET /metrics` - Prometheus metrics
 
 #[global_allocator]
@@ -25,9 +27,6 @@ mod metrics;
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     metrics::init_telemetry();
 
-    // Initialize catalog runtime (ONNX execution providers, etc.)
-    initialize_catalog();
-
     let server_mode = std::env::var("EXECUTOR_SERVER_MODE")
         .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
         .unwrap_or(false);
@@ -35,6 +34,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     if server_mode {
         run_server().await
     } else {
+        // Initialize catalog runtime (ONNX execution providers, etc.)
+        initialize_catalog();
         run_job_once().await
     }
 }
@@ -46,6 +47,15 @@ async fn run_server() -> Result<(), Box<dyn std::error::Error>> {
     // Create executor state from environment
     let state = ExecutorState::from_env();
 
+    // Initialize the catalog runtime (ONNX execution providers, etc.) while already serving
+    // the probes, the pod only becomes ready once it is done
+    let readiness = state.readiness.clone();
+    tokio::task::spawn_blocking(move || {
+        initialize_catalog();
+        readiness.mark_catalog_initialized();
+        tracing::info!("Catalog initialized, executor is ready");
+    });
+
     // Use the standard executor router with all endpoints
     let app = executor_router(state).route("/metrics", get(metrics::handler));
 
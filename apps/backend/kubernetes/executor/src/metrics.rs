@@ -57,6 +57,11 @@ fn init_metrics() {
             &[0.1, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0, 300.0],
         )
         .unwrap()
+        .set_buckets_for_metric(
+            Matcher::Full(flow_like_wasm::metrics::EXECUTION_DURATION_SECONDS.to_string()),
+            flow_like_wasm::metrics::DURATION_BUCKETS,
+        )
+        .unwrap()
         .install_recorder()
         .expect("failed to install Prometheus recorder");
 
@@ -70,6 +75,7 @@ fn init_metrics() {
         "Flow execution duration in seconds"
     );
     metrics::describe_gauge!("executor_active_jobs", "Number of currently executing jobs");
+    flow_like_wasm::metrics::describe_metrics();
 
     tracing::info!("Prometheus metrics initialized");
 }
@@ -81,7 +81,12 @@ async fn main() {
             return;
         }
     };
-    axum::serve(listener, app).await.unwrap();
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await
+    .unwrap();
 }
 
 fn create_listener<A: ToSocketAddrs>(
@@ -10,6 +10,8 @@ pub struct Config {
     pub redis_url: Option<String>,
     /// Redis queue name
     pub redis_queue_name: String,
+    /// Failed attempts before a queued job is moved to the dead-letter queue
+    pub queue_max_attempts: u32,
 }
 
 impl Config {
@@ -30,6 +32,10 @@ impl Config {
             redis_url: env::var("REDIS_URL").ok(),
             redis_queue_name: env::var("REDIS_EXECUTION_QUEUE")
                 .unwrap_or_else(|_| "exec:jobs".to_string()),
+            queue_max_attempts: env::var("QUEUE_MAX_ATTEMPTS")
+                .unwrap_or_else(|_| "3".to_string())
+                .parse()
+                .map_err(|_| ConfigError::InvalidValue("QUEUE_MAX_ATTEMPTS".to_string()))?,
         })
     }
 }
@@ -9,6 +9,7 @@ use flow_like_catalog::initialize as initialize_catalog;
 use flow_like_executor::{
     execute, executor_router, ExecutionRequest, ExecutorConfig, ExecutorState,
 };
+use std::sync::Arc;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 mod config;
@@ -36,6 +37,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     );
 
     let executor_config = ExecutorConfig::from_env();
+    let state = ExecutorState::new(executor_config.clone());
+    let readiness = state.readiness.clone();
+    readiness.mark_catalog_initialized();
 
     // Start queue worker if enabled
     if config.queue_worker_enabled {
@@ -47,13 +51,21 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             queue_name: config.redis_queue_name.clone(),
             concurrency: config.max_concurrent_executions,
             poll_timeout_secs: 30,
+            max_attempts: config.queue_max_attempts,
         };
 
         let worker_executor_config = executor_config.clone();
 
-        tokio::spawn(async move {
-            match QueueWorker::new(queue_config).await {
-                Ok(worker) => {
+        match QueueWorker::new(queue_config).await {
+            Ok(worker) => {
+                let worker = Arc::new(worker);
+                let probe = worker.clone();
+                readiness.add_check("redis", move || {
+                    let probe = probe.clone();
+                    async move { probe.ping().await.map_err(|e| e.to_string()) }
+                });
+
+                tokio::spawn(async move {
                     tracing::info!("Queue worker started");
                     let _ = worker
                         .run(move |job: QueuedJob| {
@@ -61,16 +73,20 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                             async move { process_queued_job(job, executor_config).await }
                         })
                         .await;
-                }
-                Err(e) => {
-                    tracing::error!(error = %e, "Failed to start queue worker");
-                }
+                });
+            }
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to start queue worker");
+                let error = e.to_string();
+                readiness.add_check("redis", move || {
+                    let error = error.clone();
+                    async move { Err(error) }
+                });
             }
-        });
+        }
     }
 
     // Use the executor's router
-    let state = ExecutorState::new(executor_config);
     let app = executor_router(state);
 
     let addr = format!("0.0.0.0:{}", config.port);
@@ -104,6 +120,8 @@ async fn process_queued_job(job: QueuedJob, executor_config: ExecutorConfig) ->
         runtime_variables: job.runtime_variables,
         user_context: job.user_context,
         profile: job.profile,
+        on_node_error: Default::default(),
+        deterministic: false,
     };
 
     let result = execute(exec_request, executor_config).await;
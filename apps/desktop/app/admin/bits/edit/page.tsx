@@ -79,7 +79,12 @@ export default function EditPage() {
 		[debouncedSearch, selectedBitTypes, currentPage, itemsPerPage],
 	);
 
-	const bits = useApi<IBit[]>("POST", "bit", queryParams, true);
+	const bits = useApi<{ items: IBit[]; next_cursor?: string | null }>(
+		"POST",
+		"bit",
+		queryParams,
+		true,
+	);
 
 	useEffect(() => {
 		console.dir(bits.data, "Bits data fetched in EditPage");
@@ -106,7 +111,7 @@ export default function EditPage() {
 	};
 
 	const totalItems = useMemo(() => {
-		return bits.data?.length || 0;
+		return bits.data?.items.length || 0;
 	}, [bits.data]);
 
 	const hasMorePages = useMemo(() => {
@@ -116,7 +121,7 @@ export default function EditPage() {
 
 	const paginatedBits = useMemo(() => {
 		if (!bits.data) return [];
-		return bits.data.filter((bit) => bit.meta["en"]);
+		return bits.data.items.filter((bit) => bit.meta["en"]);
 	}, [bits.data]);
 
 	const handleNextPage = () => {
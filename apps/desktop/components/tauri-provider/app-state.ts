@@ -174,12 +174,23 @@ export class AppState implements IAppState {
 		const mergedData = new Map<string, [IApp, IMetadata | undefined]>();
 
 		try {
-			const remoteData = await fetcher<[IApp, IMetadata | undefined][]>(
-				this.backend.profile,
-				"apps",
-				undefined,
-				this.backend.auth,
-			);
+			const remoteData: [IApp, IMetadata | undefined][] = [];
+			let cursor: string | undefined;
+			do {
+				const page = await fetcher<{
+					items: [IApp, IMetadata | undefined][];
+					next_cursor?: string | null;
+				}>(
+					this.backend.profile,
+					cursor
+						? `apps?limit=100&cursor=${encodeURIComponent(cursor)}`
+						: "apps?limit=100",
+					undefined,
+					this.backend.auth,
+				);
+				remoteData.push(...page.items);
+				cursor = page.next_cursor ?? undefined;
+			} while (cursor);
 
 			for (const [app, meta] of remoteData) {
 				mergedData.set(app.id, [app, meta]);
@@ -21,6 +21,7 @@ import {
 	isEqual,
 	showProgressToast,
 } from "@tm9657/flow-like-ui";
+import type { IValidationMessage } from "@tm9657/flow-like-ui/state/backend-state/event-state";
 import { toast } from "sonner";
 import { fetcher, streamFetcher } from "../../lib/api";
 import { oauthConsentStore, oauthTokenStore } from "../../lib/oauth-db";
@@ -308,10 +309,10 @@ export class EventState implements IEventState {
 		appId: string,
 		eventId: string,
 		version?: [number, number, number],
-	): Promise<void> {
+	): Promise<IValidationMessage[]> {
 		const isOffline = await this.backend.isOffline(appId);
 		if (isOffline) {
-			return await invoke("validate_event", {
+			return await invoke<IValidationMessage[]>("validate_event", {
 				appId: appId,
 				eventId: eventId,
 				version: version,
@@ -328,7 +329,7 @@ export class EventState implements IEventState {
 			);
 		}
 
-		return await fetcher(
+		return await fetcher<IValidationMessage[]>(
 			this.backend.profile,
 			`apps/${appId}/events/${eventId}/validate`,
 			{
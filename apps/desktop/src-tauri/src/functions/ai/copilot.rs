@@ -2,7 +2,7 @@ use crate::state::{TauriFlowLikeState, TauriSettingsState};
 use async_trait::async_trait;
 use flow_like::a2ui::SurfaceComponent;
 use flow_like::copilot::{
-    CopilotScope, UIActionContext, UnifiedChatMessage, UnifiedContext, UnifiedCopilot,
+    CopilotScope, StopReason, UIActionContext, UnifiedChatMessage, UnifiedContext, UnifiedCopilot,
     UnifiedCopilotResponse,
 };
 use flow_like::flow::board::Board;
@@ -236,6 +236,7 @@ pub async fn copilot_chat(
     // Extended context
     run_context: Option<RunContext>,
     action_context: Option<UIActionContext>,
+    max_iterations: Option<u32>,
     // Streaming channel
     channel: Channel<String>,
 ) -> Result<UnifiedCopilotResponse, String> {
@@ -289,11 +290,12 @@ pub async fn copilot_chat(
     });
 
     // Build unified context
-    let context = if run_context.is_some() || action_context.is_some() {
+    let context = if run_context.is_some() || action_context.is_some() || max_iterations.is_some() {
         Some(UnifiedContext {
             scope,
             run_context,
             action_context,
+            max_iterations,
         })
     } else {
         None
@@ -572,6 +574,10 @@ async fn copilot_sdk_chat_internal(
         canvas_settings: extracted_canvas_settings,
         root_component_id: extracted_root_component_id,
         active_scope: scope,
+        // The Copilot SDK session does not report token usage
+        usage: Default::default(),
+        // The session runs its own agent loop and does not report why it ended
+        stop_reason: StopReason::Completed,
     })
 }
 
@@ -254,6 +254,8 @@ AddNode: {"command_type": "AddNode", "node_type": "category::subcategory::name",
 ConnectPins: {"command_type": "ConnectPins", "from_node": "$0", "from_pin": "exec_out", "to_node": "$1", "to_pin": "exec_in", "summary": "Connect execution flow"}
 UpdateNodePin: {"command_type": "UpdateNodePin", "node_id": "$0", "pin_id": "url", "value": "https://example.com", "summary": "Set URL"}
 RemoveNode: {"command_type": "RemoveNode", "node_id": "existing_node_id", "summary": "Remove node"}
+RenameNode: {"command_type": "RenameNode", "node_id": "existing_node_id", "new_name": "Fetch Orders", "summary": "Rename node"}
+UpdateComment: {"command_type": "UpdateComment", "comment_id": "existing_comment_id", "content": "New text", "summary": "Update comment"}
 
 POSITIONING:
 - Place new nodes NEAR related nodes (within 250px)
@@ -352,6 +354,11 @@ EXAMPLE - HTTP request with JSON parsing:
                 BoardCommand::RemoveNode { node_id, .. } => {
                     format!("  - Remove node: {}", node_id)
                 }
+                BoardCommand::RenameNode {
+                    node_id, new_name, ..
+                } => {
+                    format!("  - Rename node: {} → \"{}\"", node_id, new_name)
+                }
                 BoardCommand::UpdateNodePin {
                     node_id, pin_id, ..
                 } => {
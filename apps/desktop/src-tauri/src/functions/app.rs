@@ -45,10 +45,14 @@ async fn presign_meta(
     Ok(())
 }
 
+/// Local apps sorted by id. With a `limit` only that many apps after the `cursor`, the id of
+/// the last app of the previous call, are loaded.
 #[tauri::command(async)]
 pub async fn get_apps(
     app_handle: AppHandle,
     language: Option<String>,
+    limit: Option<usize>,
+    cursor: Option<String>,
 ) -> Result<Vec<(App, Option<Metadata>)>, TauriFunctionError> {
     let mut app_list: Vec<(App, Option<Metadata>)> = vec![];
 
@@ -63,10 +67,18 @@ pub async fn get_apps(
         .list_with_delimiter(Some(&app_dir))
         .await
         .map_err(|e| TauriFunctionError::new(&format!("Failed to list apps: {}", e)))?;
-    let apps = apps.common_prefixes;
-
-    for app in apps {
-        let app_id = app.parts().last().unwrap_or_default().as_ref().to_string();
+    let mut app_ids: Vec<String> = apps
+        .common_prefixes
+        .iter()
+        .map(|app| app.parts().last().unwrap_or_default().as_ref().to_string())
+        .filter(|app_id| cursor.as_ref().is_none_or(|cursor| app_id > cursor))
+        .collect();
+    app_ids.sort();
+
+    for app_id in app_ids {
+        if limit.is_some_and(|limit| app_list.len() >= limit) {
+            break;
+        }
         if let Ok(app) = App::load(app_id.clone(), flow_like_state.clone()).await {
             let app = app;
             let metadata = App::get_meta(
@@ -7,7 +7,7 @@ use crate::{
 };
 use flow_like::{
     bit::{Bit, BitPack},
-    hub::BitSearchQuery,
+    hub::{BitSearchPage, BitSearchQuery},
 };
 use flow_like_types::intercom::BufferedInterComHandler;
 use tauri::AppHandle;
@@ -64,6 +64,22 @@ pub async fn search_bits(
     Ok(bits)
 }
 
+/// One page of the bits of the home hub, pass `next_cursor` as `query.cursor` to continue.
+#[tauri::command(async)]
+pub async fn search_bits_page(
+    app_handle: AppHandle,
+    query: BitSearchQuery,
+) -> Result<BitSearchPage, TauriFunctionError> {
+    let profile = TauriSettingsState::current_profile(&app_handle).await?;
+    let http_client = TauriFlowLikeState::http_client(&app_handle).await?;
+    let page = profile
+        .hub_profile
+        .search_bits_page(&query, http_client)
+        .await?;
+
+    Ok(page)
+}
+
 #[tauri::command(async)]
 pub async fn download_bit(app_handle: AppHandle, bit: Bit) -> Result<Vec<Bit>, TauriFunctionError> {
     println!("Downloading bit: {}", bit.id);
@@ -4,7 +4,7 @@ use crate::{
 };
 use flow_like::{
     app::App,
-    flow::board::{Board, VersionType, commands::GenericCommand},
+    flow::board::{Board, VersionType, commands::GenericCommand, diff::BoardDiff},
 };
 use std::collections::HashMap;
 use tauri::AppHandle;
@@ -83,6 +83,26 @@ pub async fn get_board_versions(
     Err(TauriFunctionError::new("Board not found"))
 }
 
+#[tauri::command(async)]
+pub async fn diff_board_versions(
+    handler: AppHandle,
+    app_id: String,
+    board_id: String,
+    from: Option<(u32, u32, u32)>,
+    to: Option<(u32, u32, u32)>,
+) -> Result<BoardDiff, TauriFunctionError> {
+    let flow_like_state = TauriFlowLikeState::construct(&handler).await?;
+    let diff = flow_like::flow::board::diff::diff_board_versions(
+        flow_like_state,
+        &app_id,
+        &board_id,
+        from,
+        to,
+    )
+    .await?;
+    Ok(diff)
+}
+
 #[tauri::command(async)]
 pub async fn get_board(
     handler: AppHandle,
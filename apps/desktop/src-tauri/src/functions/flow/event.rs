@@ -1,6 +1,8 @@
 use flow_like::{
     app::App,
-    flow::{board::VersionType, event::Event, oauth::OAuthToken},
+    flow::{
+        board::VersionType, event::Event, node::validation::ValidationMessage, oauth::OAuthToken,
+    },
 };
 use std::collections::HashMap;
 use tauri::AppHandle;
@@ -160,12 +162,12 @@ pub async fn validate_event(
     app_id: String,
     event_id: String,
     version: Option<(u32, u32, u32)>,
-) -> Result<(), TauriFunctionError> {
+) -> Result<Vec<ValidationMessage>, TauriFunctionError> {
     let flow_like_state = TauriFlowLikeState::construct(&handler).await?;
 
     if let Ok(app) = App::load(app_id.clone(), flow_like_state).await {
-        app.validate_event(&event_id, version).await?;
-        return Ok(());
+        let messages = app.validate_event(&event_id, version).await?;
+        return Ok(messages);
     }
 
     Err(TauriFunctionError::new("Failed to validate event"))
@@ -4,7 +4,7 @@ use anyhow::anyhow;
 use flow_like::{
     flow_like_storage::{
         Path,
-        files::store::{FlowLikeStore, StorageItem},
+        files::store::{FlowLikeStore, StorageItem, StoragePage},
     },
     utils::storage::construct_storage,
 };
@@ -119,6 +119,34 @@ pub async fn storage_list(
     Ok(items)
 }
 
+/// Pages through a folder for UIs that load lazily. Pass the continuation of the previous
+/// page to get the next one, folders are returned as items with `is_dir` set.
+#[tauri::command(async)]
+pub async fn storage_list_page(
+    app_handle: AppHandle,
+    app_id: String,
+    prefix: String,
+    continuation: Option<String>,
+    limit: Option<usize>,
+) -> Result<StoragePage, TauriFunctionError> {
+    let state = TauriFlowLikeState::construct(&app_handle).await?;
+    let (store, path) = construct_storage(&state, &app_id, &prefix).await?;
+    let mut page = store
+        .list_page(Some(&path), continuation.as_deref(), limit.unwrap_or(100))
+        .await
+        .map_err(|e| anyhow!("Failed to list items: {}", e))?;
+    for item in page.items.iter_mut() {
+        // Skip the first three parts of the location, the UI works relative to the app
+        item.location = item
+            .location
+            .split('/')
+            .skip(3)
+            .collect::<Vec<_>>()
+            .join("/");
+    }
+    Ok(page)
+}
+
 #[tauri::command(async)]
 pub async fn storage_get(
     app_handle: AppHandle,
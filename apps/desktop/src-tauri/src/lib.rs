@@ -835,10 +835,12 @@ pub fn run() {
             functions::bit::get_bit_size,
             functions::bit::get_pack_from_bit,
             functions::bit::search_bits,
+            functions::bit::search_bits_page,
             functions::bit::download_bit,
             functions::bit::delete_bit,
             functions::bit::get_installed_bit,
             functions::flow::storage::storage_list,
+            functions::flow::storage::storage_list_page,
             functions::flow::storage::storage_add,
             functions::flow::storage::storage_remove,
             functions::flow::storage::storage_rename,
@@ -847,6 +849,7 @@ pub fn run() {
             functions::flow::catalog::get_catalog,
             functions::flow::board::create_board_version,
             functions::flow::board::get_board_versions,
+            functions::flow::board::diff_board_versions,
             functions::flow::board::close_board,
             functions::flow::board::get_board,
             functions::flow::board::get_open_boards,
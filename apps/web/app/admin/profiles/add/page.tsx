@@ -62,7 +62,7 @@ export default function AddProfilePage() {
 	const thumbnailInputRef = useRef<HTMLInputElement>(null);
 	const backend = useBackend();
 
-	const bits = useApi<IBit[]>(
+	const bits = useApi<{ items: IBit[]; next_cursor?: string | null }>(
 		"POST",
 		"bit",
 		{
@@ -393,7 +393,7 @@ export default function AddProfilePage() {
 					</CardHeader>
 					<CardContent className="space-y-4">
 						{/* Dropdown Bit Selection */}
-						{bits.data && bits.data.length > 0 && (
+						{bits.data && bits.data.items.length > 0 && (
 							<div className="space-y-3">
 								<Label>Add Bit from Available</Label>
 								<Select
@@ -408,8 +408,8 @@ export default function AddProfilePage() {
 										<SelectValue placeholder="Select a bit to add..." />
 									</SelectTrigger>
 									<SelectContent>
-										{bits.data
-											?.filter(
+										{bits.data?.items
+											.filter(
 												(bit) =>
 													!profile.bits?.includes(`${bit.hub}:${bit.id}`),
 											)
@@ -449,7 +449,7 @@ export default function AddProfilePage() {
 								<Label>Selected Bit IDs</Label>
 								<div className="flex flex-wrap gap-2">
 									{profile.bits?.map((bitId) => {
-										const bit = bits.data?.find(
+										const bit = bits.data?.items.find(
 											(b) => `${b.hub}:${b.id}` === bitId,
 										);
 										const displayName = bit?.meta?.en?.name || bitId;
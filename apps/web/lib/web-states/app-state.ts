@@ -96,10 +96,22 @@ export class WebAppState implements IAppState {
 
 	async getApps(): Promise<[IApp, IMetadata | undefined][]> {
 		try {
-			return await apiGet<[IApp, IMetadata | undefined][]>(
-				"apps",
-				this.backend.auth,
-			);
+			const apps: [IApp, IMetadata | undefined][] = [];
+			let cursor: string | undefined;
+			do {
+				const page = await apiGet<{
+					items: [IApp, IMetadata | undefined][];
+					next_cursor?: string | null;
+				}>(
+					cursor
+						? `apps?limit=100&cursor=${encodeURIComponent(cursor)}`
+						: "apps?limit=100",
+					this.backend.auth,
+				);
+				apps.push(...page.items);
+				cursor = page.next_cursor ?? undefined;
+			} while (cursor);
+			return apps;
 		} catch {
 			return [];
 		}
@@ -66,8 +66,12 @@ export class WebBitState implements IBitState {
 
 	async searchBits(query: IBitSearchQuery): Promise<IBit[]> {
 		try {
-			const result = await apiPost<IBit[]>("bit", query, this.backend.auth);
-			return result ?? [];
+			const result = await apiPost<{ items: IBit[] }>(
+				"bit",
+				query,
+				this.backend.auth,
+			);
+			return result?.items ?? [];
 		} catch {
 			return [];
 		}
@@ -14,7 +14,10 @@ import {
 	finishAllProgressToasts,
 	showProgressToast,
 } from "@tm9657/flow-like-ui";
-import type { IOAuthCheckResult } from "@tm9657/flow-like-ui/state/backend-state/event-state";
+import type {
+	IOAuthCheckResult,
+	IValidationMessage,
+} from "@tm9657/flow-like-ui/state/backend-state/event-state";
 import type { IPrerunEventResponse } from "@tm9657/flow-like-ui/state/backend-state/types";
 import { toast } from "sonner";
 import { oauthConsentStore, oauthTokenStore } from "../oauth-db";
@@ -224,9 +227,9 @@ export class WebEventState implements IEventState {
 		appId: string,
 		eventId: string,
 		version?: [number, number, number],
-	): Promise<void> {
+	): Promise<IValidationMessage[]> {
 		const params = version ? `?version=${version.join(".")}` : "";
-		await apiPost(
+		return await apiPost<IValidationMessage[]>(
 			`apps/${appId}/events/${eventId}/validate${params}`,
 			undefined,
 			this.backend.auth,
@@ -28,7 +28,10 @@ pub use jwt::{
 };
 #[cfg(feature = "redis")]
 pub use queue::QueueWorker;
-pub use queue::{OAuthTokenInput, QueueConfig, QueueError, QueuedJob};
+pub use queue::{
+    DeadLetterEntry, JobLedger, JobOutcome, OAuthTokenInput, QueueConfig, QueueError, QueuedJob,
+    settle_job,
+};
 pub use sse_proxy::proxy_sse_response;
 pub use state::{
     CreateEventInput, CreateRunInput, EventQuery, ExecutionEventRecord, ExecutionRunRecord,
@@ -22,8 +22,18 @@
 //! REDIS_EXECUTION_QUEUE=exec:jobs
 //! QUEUE_WORKER_CONCURRENCY=10
 //! QUEUE_POLL_TIMEOUT_SECS=30
+//! QUEUE_MAX_ATTEMPTS=3
 //! ```
+//!
+//! ## Dead-letter queue
+//!
+//! A failing job is pushed back onto the queue until it failed `max_attempts` times, then
+//! it is moved to the `{queue_name}:dlq` list together with the last error. The failed
+//! attempts are counted per `job_id` in the `{queue_name}:attempts` hash. Dead letters can
+//! be listed with `QueueWorker::dead_letters` and put back onto the queue with
+//! `QueueWorker::replay_dead_letter`.
 
+use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -40,6 +50,8 @@ pub struct QueueConfig {
     pub concurrency: usize,
     /// BRPOP timeout in seconds (0 = infinite)
     pub poll_timeout_secs: u64,
+    /// Failed attempts after which a job is moved to the dead-letter queue
+    pub max_attempts: u32,
 }
 
 impl Default for QueueConfig {
@@ -63,10 +75,26 @@ impl QueueConfig {
                 .ok()
                 .and_then(|s| s.parse().ok())
                 .unwrap_or(30),
+            max_attempts: std::env::var("QUEUE_MAX_ATTEMPTS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(DEFAULT_MAX_ATTEMPTS),
         }
     }
+
+    /// List holding the jobs that exhausted their attempts
+    pub fn dead_letter_queue(&self) -> String {
+        format!("{}:dlq", self.queue_name)
+    }
+
+    /// Hash counting the failed attempts per `job_id`
+    pub fn attempts_key(&self) -> String {
+        format!("{}:attempts", self.queue_name)
+    }
 }
 
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+
 /// Job payload from the queue (matches build_executor_payload in dispatch.rs)
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct QueuedJob {
@@ -111,6 +139,78 @@ pub enum QueueError {
     Execution(String),
 }
 
+/// A job that failed on every attempt and is no longer retried
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DeadLetterEntry {
+    pub job: QueuedJob,
+    /// Error of the last attempt
+    pub reason: String,
+    pub attempts: u32,
+    /// Unix timestamp in seconds of the last attempt
+    pub failed_at: u64,
+}
+
+/// What happened to a job after its handler returned
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JobOutcome {
+    Completed,
+    /// Pushed back onto the queue after failing `attempts` times
+    Retried {
+        attempts: u32,
+    },
+    /// Moved to the dead-letter queue after failing `attempts` times
+    DeadLettered {
+        attempts: u32,
+    },
+}
+
+/// Storage of the retry bookkeeping, Redis for the worker
+#[async_trait]
+pub trait JobLedger: Send + Sync {
+    /// Counts a failed attempt of `job_id` and returns the attempts so far
+    async fn record_failure(&self, job_id: &str) -> Result<u32, QueueError>;
+    async fn clear_attempts(&self, job_id: &str) -> Result<(), QueueError>;
+    async fn requeue(&self, job: &QueuedJob) -> Result<(), QueueError>;
+    async fn push_dead_letter(&self, entry: &DeadLetterEntry) -> Result<(), QueueError>;
+}
+
+/// Records the result of a job: failures are retried until `max_attempts` is reached,
+/// then the job goes to the dead-letter queue.
+pub async fn settle_job(
+    ledger: &dyn JobLedger,
+    job: QueuedJob,
+    result: Result<(), String>,
+    max_attempts: u32,
+) -> Result<JobOutcome, QueueError> {
+    let reason = match result {
+        Ok(()) => {
+            ledger.clear_attempts(&job.job_id).await?;
+            return Ok(JobOutcome::Completed);
+        }
+        Err(reason) => reason,
+    };
+
+    let attempts = ledger.record_failure(&job.job_id).await?;
+    if attempts < max_attempts.max(1) {
+        ledger.requeue(&job).await?;
+        return Ok(JobOutcome::Retried { attempts });
+    }
+
+    let job_id = job.job_id.clone();
+    let entry = DeadLetterEntry {
+        job,
+        reason,
+        attempts,
+        failed_at: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default(),
+    };
+    ledger.push_dead_letter(&entry).await?;
+    ledger.clear_attempts(&job_id).await?;
+    Ok(JobOutcome::DeadLettered { attempts })
+}
+
 #[cfg(feature = "redis")]
 mod worker {
     use super::*;
@@ -118,11 +218,67 @@ mod worker {
     use std::sync::Arc;
     use tokio::sync::Semaphore;
 
+    /// Keeps the attempt counters and the dead-letter list next to the queue
+    #[derive(Clone)]
+    struct RedisLedger {
+        client: Client,
+        config: QueueConfig,
+    }
+
+    impl RedisLedger {
+        async fn connection(&self) -> Result<redis::aio::MultiplexedConnection, QueueError> {
+            self.client
+                .get_multiplexed_async_connection()
+                .await
+                .map_err(|e| QueueError::Redis(e.to_string()))
+        }
+    }
+
+    #[async_trait]
+    impl JobLedger for RedisLedger {
+        async fn record_failure(&self, job_id: &str) -> Result<u32, QueueError> {
+            self.connection()
+                .await?
+                .hincr(self.config.attempts_key(), job_id, 1)
+                .await
+                .map_err(|e| QueueError::Redis(e.to_string()))
+        }
+
+        async fn clear_attempts(&self, job_id: &str) -> Result<(), QueueError> {
+            self.connection()
+                .await?
+                .hdel::<_, _, ()>(self.config.attempts_key(), job_id)
+                .await
+                .map_err(|e| QueueError::Redis(e.to_string()))
+        }
+
+        async fn requeue(&self, job: &QueuedJob) -> Result<(), QueueError> {
+            let job_json =
+                serde_json::to_string(job).map_err(|e| QueueError::Serialization(e.to_string()))?;
+            self.connection()
+                .await?
+                .lpush::<_, _, ()>(&self.config.queue_name, job_json)
+                .await
+                .map_err(|e| QueueError::Redis(e.to_string()))
+        }
+
+        async fn push_dead_letter(&self, entry: &DeadLetterEntry) -> Result<(), QueueError> {
+            let entry_json = serde_json::to_string(entry)
+                .map_err(|e| QueueError::Serialization(e.to_string()))?;
+            self.connection()
+                .await?
+                .lpush::<_, _, ()>(self.config.dead_letter_queue(), entry_json)
+                .await
+                .map_err(|e| QueueError::Redis(e.to_string()))
+        }
+    }
+
     /// Redis queue worker
     pub struct QueueWorker {
         config: QueueConfig,
         client: Client,
         semaphore: Arc<Semaphore>,
+        ledger: RedisLedger,
     }
 
     impl QueueWorker {
@@ -131,11 +287,16 @@ mod worker {
                 .map_err(|e| QueueError::Redis(e.to_string()))?;
 
             let semaphore = Arc::new(Semaphore::new(config.concurrency));
+            let ledger = RedisLedger {
+                client: client.clone(),
+                config: config.clone(),
+            };
 
             Ok(Self {
                 config,
                 client,
                 semaphore,
+                ledger,
             })
         }
 
@@ -148,6 +309,7 @@ mod worker {
             tracing::info!(
                 queue = %self.config.queue_name,
                 concurrency = %self.config.concurrency,
+                max_attempts = %self.config.max_attempts,
                 "Starting Redis queue worker"
             );
 
@@ -180,6 +342,8 @@ mod worker {
                     Ok(Some((_queue, job_json))) => {
                         let handler = handler.clone();
                         let permit = permit.unwrap();
+                        let ledger = self.ledger.clone();
+                        let max_attempts = self.config.max_attempts;
 
                         // Spawn task to handle job
                         tokio::spawn(async move {
@@ -190,15 +354,43 @@ mod worker {
 
                                     tracing::info!(job_id = %job_id, run_id = %run_id, "Processing job");
 
-                                    if let Err(e) = handler(job).await {
+                                    let result = handler(job.clone()).await;
+                                    if let Err(e) = &result {
                                         tracing::error!(
                                             job_id = %job_id,
                                             run_id = %run_id,
                                             error = %e,
                                             "Job execution failed"
                                         );
-                                    } else {
-                                        tracing::info!(job_id = %job_id, run_id = %run_id, "Job completed");
+                                    }
+
+                                    match settle_job(&ledger, job, result, max_attempts).await {
+                                        Ok(JobOutcome::Completed) => {
+                                            tracing::info!(job_id = %job_id, run_id = %run_id, "Job completed");
+                                        }
+                                        Ok(JobOutcome::Retried { attempts }) => {
+                                            tracing::warn!(
+                                                job_id = %job_id,
+                                                run_id = %run_id,
+                                                attempts,
+                                                "Job requeued for another attempt"
+                                            );
+                                        }
+                                        Ok(JobOutcome::DeadLettered { attempts }) => {
+                                            tracing::error!(
+                                                job_id = %job_id,
+                                                run_id = %run_id,
+                                                attempts,
+                                                "Job moved to the dead-letter queue"
+                                            );
+                                        }
+                                        Err(e) => {
+                                            tracing::error!(
+                                                job_id = %job_id,
+                                                error = %e,
+                                                "Failed to record job result"
+                                            );
+                                        }
                                     }
                                 }
                                 Err(e) => {
@@ -242,6 +434,15 @@ mod worker {
             }
         }
 
+        /// Checks that Redis is reachable
+        pub async fn ping(&self) -> Result<(), QueueError> {
+            let mut conn = self.ledger.connection().await?;
+            redis::cmd("PING")
+                .query_async::<()>(&mut conn)
+                .await
+                .map_err(|e| QueueError::Redis(e.to_string()))
+        }
+
         /// Get the number of jobs currently in the queue
         pub async fn queue_length(&self) -> Result<usize, QueueError> {
             let mut conn = self
@@ -257,6 +458,74 @@ mod worker {
 
             Ok(len)
         }
+
+        /// Lists up to `count` dead letters, newest first, starting at `offset`
+        pub async fn dead_letters(
+            &self,
+            offset: usize,
+            count: usize,
+        ) -> Result<Vec<DeadLetterEntry>, QueueError> {
+            if count == 0 {
+                return Ok(Vec::new());
+            }
+            let mut conn = self.ledger.connection().await?;
+            let entries: Vec<String> = conn
+                .lrange(
+                    self.config.dead_letter_queue(),
+                    offset as isize,
+                    (offset + count - 1) as isize,
+                )
+                .await
+                .map_err(|e| QueueError::Redis(e.to_string()))?;
+
+            entries
+                .iter()
+                .map(|entry| {
+                    serde_json::from_str(entry)
+                        .map_err(|e| QueueError::Serialization(e.to_string()))
+                })
+                .collect()
+        }
+
+        /// Get the number of jobs in the dead-letter queue
+        pub async fn dead_letter_length(&self) -> Result<usize, QueueError> {
+            let mut conn = self.ledger.connection().await?;
+            conn.llen(self.config.dead_letter_queue())
+                .await
+                .map_err(|e| QueueError::Redis(e.to_string()))
+        }
+
+        /// Moves the dead letter of `job_id` back onto the queue with fresh attempts.
+        /// Returns `false` if the dead-letter queue holds no such job.
+        pub async fn replay_dead_letter(&self, job_id: &str) -> Result<bool, QueueError> {
+            let mut conn = self.ledger.connection().await?;
+            let dlq = self.config.dead_letter_queue();
+            let entries: Vec<String> = conn
+                .lrange(&dlq, 0, -1)
+                .await
+                .map_err(|e| QueueError::Redis(e.to_string()))?;
+
+            let Some((raw, entry)) = entries.into_iter().find_map(|raw| {
+                let entry: DeadLetterEntry = serde_json::from_str(&raw).ok()?;
+                (entry.job.job_id == job_id).then_some((raw, entry))
+            }) else {
+                return Ok(false);
+            };
+
+            // Another worker may have replayed the entry in the meantime
+            let removed: usize = conn
+                .lrem(&dlq, 1, &raw)
+                .await
+                .map_err(|e| QueueError::Redis(e.to_string()))?;
+            if removed == 0 {
+                return Ok(false);
+            }
+
+            self.ledger.clear_attempts(job_id).await?;
+            self.ledger.requeue(&entry.job).await?;
+            tracing::info!(job_id = %job_id, "Replayed job from the dead-letter queue");
+            Ok(true)
+        }
     }
 }
 
@@ -272,3 +541,162 @@ impl QueueWorker {
         Err(QueueError::Redis("Redis feature not enabled".into()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+    use std::sync::Mutex;
+
+    /// In-memory stand-in for the Redis lists and hash
+    #[derive(Default)]
+    struct MemoryLedger {
+        attempts: Mutex<HashMap<String, u32>>,
+        queue: Mutex<VecDeque<QueuedJob>>,
+        dead_letters: Mutex<Vec<DeadLetterEntry>>,
+    }
+
+    #[async_trait]
+    impl JobLedger for MemoryLedger {
+        async fn record_failure(&self, job_id: &str) -> Result<u32, QueueError> {
+            let mut attempts = self.attempts.lock().unwrap();
+            let count = attempts.entry(job_id.to_string()).or_default();
+            *count += 1;
+            Ok(*count)
+        }
+
+        async fn clear_attempts(&self, job_id: &str) -> Result<(), QueueError> {
+            self.attempts.lock().unwrap().remove(job_id);
+            Ok(())
+        }
+
+        async fn requeue(&self, job: &QueuedJob) -> Result<(), QueueError> {
+            self.queue.lock().unwrap().push_back(job.clone());
+            Ok(())
+        }
+
+        async fn push_dead_letter(&self, entry: &DeadLetterEntry) -> Result<(), QueueError> {
+            self.dead_letters.lock().unwrap().push(entry.clone());
+            Ok(())
+        }
+    }
+
+    impl MemoryLedger {
+        /// Works the queue like the worker loop until it is empty
+        async fn drain(
+            &self,
+            max_attempts: u32,
+            handler: impl Fn(&QueuedJob) -> Result<(), String>,
+        ) -> Vec<JobOutcome> {
+            let mut outcomes = Vec::new();
+            loop {
+                let Some(job) = self.queue.lock().unwrap().pop_front() else {
+                    break;
+                };
+                let result = handler(&job);
+                outcomes.push(settle_job(self, job, result, max_attempts).await.unwrap());
+            }
+            outcomes
+        }
+    }
+
+    fn job(job_id: &str) -> QueuedJob {
+        serde_json::from_value(serde_json::json!({
+            "job_id": job_id,
+            "run_id": format!("run-{job_id}"),
+            "app_id": "app",
+            "board_id": "board",
+            "board_version": null,
+            "node_id": "node",
+            "event_json": null,
+            "payload": null,
+            "user_id": "user",
+            "credentials": "{}",
+            "executor_jwt": "jwt",
+            "callback_url": "http://localhost/callback",
+            "token": null,
+            "oauth_tokens": null
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_failing_job_lands_in_dead_letter_queue() {
+        let ledger = MemoryLedger::default();
+        ledger.queue.lock().unwrap().push_back(job("failing"));
+
+        let outcomes = ledger
+            .drain(3, |_| Err("board not found".to_string()))
+            .await;
+
+        assert_eq!(
+            outcomes,
+            vec![
+                JobOutcome::Retried { attempts: 1 },
+                JobOutcome::Retried { attempts: 2 },
+                JobOutcome::DeadLettered { attempts: 3 },
+            ]
+        );
+
+        let dead_letters = ledger.dead_letters.lock().unwrap();
+        assert_eq!(dead_letters.len(), 1);
+        assert_eq!(dead_letters[0].job.job_id, "failing");
+        assert_eq!(dead_letters[0].job.run_id, "run-failing");
+        assert_eq!(dead_letters[0].reason, "board not found");
+        assert_eq!(dead_letters[0].attempts, 3);
+        assert!(dead_letters[0].failed_at > 0);
+        assert!(ledger.attempts.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_succeeding_job_is_never_dead_lettered() {
+        let ledger = MemoryLedger::default();
+        ledger.queue.lock().unwrap().push_back(job("flaky"));
+        ledger.queue.lock().unwrap().push_back(job("healthy"));
+
+        // The flaky job recovers on its second attempt
+        let outcomes = ledger
+            .drain(2, |job| {
+                let failures = ledger.attempts.lock().unwrap();
+                match failures.get(&job.job_id) {
+                    None if job.job_id == "flaky" => Err("timeout".to_string()),
+                    _ => Ok(()),
+                }
+            })
+            .await;
+
+        assert_eq!(
+            outcomes,
+            vec![
+                JobOutcome::Retried { attempts: 1 },
+                JobOutcome::Completed,
+                JobOutcome::Completed,
+            ]
+        );
+        assert!(ledger.dead_letters.lock().unwrap().is_empty());
+        assert!(ledger.attempts.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_zero_max_attempts_still_runs_once() {
+        let ledger = MemoryLedger::default();
+        ledger.queue.lock().unwrap().push_back(job("once"));
+
+        let outcomes = ledger.drain(0, |_| Err("failed".to_string())).await;
+
+        assert_eq!(outcomes, vec![JobOutcome::DeadLettered { attempts: 1 }]);
+    }
+
+    #[test]
+    fn test_dead_letter_keys_follow_queue_name() {
+        let config = QueueConfig {
+            redis_url: "redis://localhost:6379".into(),
+            queue_name: "exec:jobs".into(),
+            concurrency: 1,
+            poll_timeout_secs: 1,
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+        };
+        assert_eq!(config.dead_letter_queue(), "exec:jobs:dlq");
+        assert_eq!(config.attempts_key(), "exec:jobs:attempts");
+    }
+}
@@ -9,6 +9,7 @@ use error::InternalError;
 use flow_like_types::Value;
 use middleware::error_reporting::error_reporting_middleware;
 use middleware::jwt::jwt_middleware;
+use middleware::rate_limit::{RateLimiter, rate_limit_middleware};
 use state::{AppState, State};
 use tower::ServiceBuilder;
 use tower_http::{
@@ -23,6 +24,7 @@ use utoipa_swagger_ui::SwaggerUi;
 pub mod entity;
 mod middleware;
 pub mod openapi;
+mod pagination;
 mod routes;
 
 pub mod alerting;
@@ -63,6 +65,8 @@ pub fn warn_env_filter() -> EnvFilter {
 }
 
 pub fn construct_router(state: Arc<State>) -> Router {
+    let rate_limiter = Arc::new(RateLimiter::from_env());
+
     let router = Router::new()
         .route("/", get(hub_info))
         .nest("/health", routes::health::routes())
@@ -88,10 +92,13 @@ pub fn construct_router(state: Arc<State>) -> Router {
         .route("/webhook/stripe", post(routes::webhook::stripe_webhook))
         .with_state(state.clone())
         .route("/version", get(|| async { "0.0.0" }))
+        .route("/openapi.json", get(openapi::openapi_json))
         .layer(from_fn_with_state(
             state.clone(),
             error_reporting_middleware,
         ))
+        // Runs after the JWT middleware so it can key on the authenticated user
+        .layer(from_fn_with_state(rate_limiter, rate_limit_middleware))
         .layer(from_fn_with_state(state.clone(), jwt_middleware))
         .layer(CorsLayer::permissive())
         .layer(
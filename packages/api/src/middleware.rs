@@ -1,3 +1,4 @@
 pub mod jwt;
 
 pub mod error_reporting;
+pub mod rate_limit;
@@ -11,6 +11,7 @@ use crate::{
 use axum::{
     body::Body,
     extract::{Request, State},
+    http::HeaderMap,
     middleware::Next,
     response::Response,
 };
@@ -110,6 +111,79 @@ pub struct ApiKey {
     pub app_id: String,
 }
 
+impl ApiKey {
+    /// API keys are issued for a single app and never reach into other apps.
+    pub fn covers(&self, app_id: &str) -> bool {
+        self.app_id == app_id
+    }
+}
+
+/// Prefix of app API keys, `flk_{app_id}.{key_id}.{secret}`
+pub const API_KEY_PREFIX: &str = "flk_";
+
+#[derive(Debug, PartialEq, Eq)]
+struct ParsedApiKey<'a> {
+    app_id: &'a str,
+    key_id: &'a str,
+    secret: &'a str,
+}
+
+fn parse_api_key(key: &str) -> Option<ParsedApiKey<'_>> {
+    let mut parts = key.strip_prefix(API_KEY_PREFIX)?.split('.');
+    let (Some(app_id), Some(key_id), Some(secret), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return None;
+    };
+    if app_id.is_empty() || key_id.is_empty() || secret.is_empty() {
+        return None;
+    }
+    Some(ParsedApiKey {
+        app_id,
+        key_id,
+        secret,
+    })
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum ApiKeyError {
+    Invalid,
+    Expired,
+}
+
+/// Checks the presented key against its stored record. Only the blake3 hash of the secret
+/// is stored, `blake3::Hash` compares in constant time.
+fn verify_api_key(
+    key: &ParsedApiKey,
+    record: &technical_user::Model,
+    now: chrono::NaiveDateTime,
+) -> Result<(), ApiKeyError> {
+    let stored = blake3::Hash::from_hex(record.key.as_bytes()).map_err(|_| ApiKeyError::Invalid)?;
+    if blake3::hash(key.secret.as_bytes()) != stored || record.app_id != key.app_id {
+        return Err(ApiKeyError::Invalid);
+    }
+    if record
+        .valid_until
+        .is_some_and(|valid_until| valid_until < now)
+    {
+        return Err(ApiKeyError::Expired);
+    }
+    Ok(())
+}
+
+/// The API key of the request, sent as `X-API-Key` or as `Authorization: Bearer flk_...`.
+fn api_key_from_headers(headers: &HeaderMap) -> Option<&str> {
+    if let Some(key) = headers.get("x-api-key") {
+        return key.to_str().ok();
+    }
+    let token = bearer_token(headers.get(AUTHORIZATION)?.to_str().ok()?);
+    token.starts_with(API_KEY_PREFIX).then_some(token)
+}
+
+fn bearer_token(value: &str) -> &str {
+    value.strip_prefix("Bearer ").unwrap_or(value).trim()
+}
+
 #[derive(Debug, Clone)]
 pub struct ExecutorUser {
     pub sub: String,
@@ -375,12 +449,16 @@ impl AppUser {
         }
 
         if let AppUser::APIKey(api_key) = self {
+            if !api_key.covers(app_id) {
+                return Err(ApiError::FORBIDDEN);
+            }
+
             let role_model = role::Entity::find()
                 .join(JoinType::InnerJoin, role::Relation::TechnicalUser.def())
                 .filter(
                     technical_user::Column::AppId
                         .eq(&api_key.app_id)
-                        .and(technical_user::Column::Key.eq(&api_key.api_key)),
+                        .and(technical_user::Column::Id.eq(&api_key.key_id)),
                 )
                 .one(&state.db)
                 .await?
@@ -404,6 +482,70 @@ impl AppUser {
     }
 }
 
+/// Resolves an API key to the app it was issued for, `None` for malformed, unknown or
+/// deleted keys. Results are cached in `auth_cache`, deleting a key clears the cache.
+async fn resolve_api_key(
+    db: &impl sea_orm::ConnectionTrait,
+    auth_cache: &moka::sync::Cache<String, CachedAuth>,
+    api_key: &str,
+    now: chrono::NaiveDateTime,
+) -> Result<Option<ApiKey>, AuthorizationError> {
+    let cache_key = hash_token(api_key);
+
+    // Check cache first
+    match auth_cache.get(&cache_key) {
+        Some(CachedAuth::ApiKey { key_id, app_id }) => {
+            return Ok(Some(ApiKey {
+                key_id,
+                api_key: api_key.to_string(),
+                app_id,
+            }));
+        }
+        Some(CachedAuth::Invalid) => return Ok(None),
+        _ => {}
+    }
+
+    // Cache miss - parse and validate API key
+    let Some(parsed) = parse_api_key(api_key) else {
+        auth_cache.insert(cache_key, CachedAuth::Invalid);
+        return Ok(None);
+    };
+
+    // Looked up by id only, the secret is compared in constant time afterwards
+    let db_key = TechnicalUser::find_by_id(parsed.key_id).one(db).await?;
+
+    let verified = match &db_key {
+        Some(db_key) => verify_api_key(&parsed, db_key, now),
+        None => Err(ApiKeyError::Invalid),
+    };
+    let db_key = match (verified, db_key) {
+        (Ok(()), Some(db_key)) => db_key,
+        (Err(ApiKeyError::Expired), _) => {
+            auth_cache.insert(cache_key, CachedAuth::Invalid);
+            return Err(AuthorizationError::from(anyhow!("API Key is expired")));
+        }
+        _ => {
+            auth_cache.insert(cache_key, CachedAuth::Invalid);
+            return Ok(None);
+        }
+    };
+
+    // Cache valid API key
+    auth_cache.insert(
+        cache_key,
+        CachedAuth::ApiKey {
+            key_id: db_key.id.clone(),
+            app_id: db_key.app_id.clone(),
+        },
+    );
+
+    Ok(Some(ApiKey {
+        key_id: db_key.id,
+        api_key: api_key.to_string(),
+        app_id: db_key.app_id,
+    }))
+}
+
 fn hash_token(token: &str) -> String {
     let mut hasher = blake3::Hasher::new();
     hasher.update(token.as_bytes());
@@ -421,13 +563,9 @@ pub async fn jwt_middleware(
     if let Some(auth_header) = request.headers().get(AUTHORIZATION)
         && let Ok(token) = auth_header.to_str()
         && !token.starts_with("pat_")
+        && !bearer_token(token).starts_with(API_KEY_PREFIX)
     {
-        let token = if token.starts_with("Bearer ") {
-            &token[7..]
-        } else {
-            token
-        };
-        let token = token.trim();
+        let token = bearer_token(token);
         let cache_key = hash_token(token);
 
         // Check cache first
@@ -581,101 +719,195 @@ pub async fn jwt_middleware(
     }
 
     // Try API key auth
-    if let Some(api_key_header) = request.headers().get("x-api-key")
-        && let Ok(api_key_str) = api_key_header.to_str()
-    {
-        let cache_key = hash_token(api_key_str);
+    if let Some(api_key_str) = api_key_from_headers(request.headers()).map(str::to_string) {
+        let user = match resolve_api_key(
+            &state.db,
+            &state.auth_cache,
+            &api_key_str,
+            chrono::Utc::now().naive_utc(),
+        )
+        .await?
+        {
+            Some(api_key) => AppUser::APIKey(api_key),
+            None => AppUser::Unauthorized,
+        };
+        request.extensions_mut().insert::<AppUser>(user);
+        return Ok(next.run(request).await);
+    }
 
-        // Check cache first
-        if let Some(cached) = state.auth_cache.get(&cache_key) {
-            match cached {
-                CachedAuth::ApiKey { key_id, app_id } => {
-                    let app_user = AppUser::APIKey(ApiKey {
-                        key_id,
-                        api_key: api_key_str.to_string(),
-                        app_id,
-                    });
-                    request.extensions_mut().insert::<AppUser>(app_user);
-                    return Ok(next.run(request).await);
-                }
-                CachedAuth::Invalid => {
-                    request
-                        .extensions_mut()
-                        .insert::<AppUser>(AppUser::Unauthorized);
-                    return Ok(next.run(request).await);
-                }
-                _ => {}
-            }
-        }
+    request
+        .extensions_mut()
+        .insert::<AppUser>(AppUser::Unauthorized);
+    Ok(next.run(request).await)
+}
 
-        // Cache miss - parse and validate API key
-        // Format: flk_{app_id}.{key_id}.{secret}
-        if !api_key_str.starts_with("flk_") {
-            state.auth_cache.insert(cache_key, CachedAuth::Invalid);
-            request
-                .extensions_mut()
-                .insert::<AppUser>(AppUser::Unauthorized);
-            return Ok(next.run(request).await);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    const SECRET: &str = "c2VjcmV0LXNlY3JldC1zZWNyZXQ";
+
+    fn at(seconds: i64) -> chrono::NaiveDateTime {
+        chrono::DateTime::from_timestamp(seconds, 0)
+            .unwrap()
+            .naive_utc()
+    }
+
+    fn record(valid_until: Option<chrono::NaiveDateTime>) -> technical_user::Model {
+        technical_user::Model {
+            id: "key".to_string(),
+            name: "CI".to_string(),
+            description: None,
+            key: blake3::hash(SECRET.as_bytes()).to_hex().to_string(),
+            role_id: Some("role".to_string()),
+            app_id: "app".to_string(),
+            valid_until,
+            created_at: at(0),
+            updated_at: at(0),
         }
+    }
 
-        let key_parts = &api_key_str[4..];
-        let parts: Vec<&str> = key_parts.split('.').collect();
-        if parts.len() != 3 {
-            state.auth_cache.insert(cache_key, CachedAuth::Invalid);
-            request
-                .extensions_mut()
-                .insert::<AppUser>(AppUser::Unauthorized);
-            return Ok(next.run(request).await);
+    fn headers(entries: &[(&'static str, &str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in entries {
+            headers.insert(*name, HeaderValue::from_str(value).unwrap());
         }
+        headers
+    }
 
-        let _app_id_from_key = parts[0];
-        let key_id = parts[1];
-        let key_secret = parts[2];
+    #[test]
+    fn test_parse_api_key() {
+        let key = format!("flk_app.key.{}", SECRET);
+        assert_eq!(
+            parse_api_key(&key),
+            Some(ParsedApiKey {
+                app_id: "app",
+                key_id: "key",
+                secret: SECRET,
+            })
+        );
+        assert_eq!(parse_api_key("flk_app.key"), None);
+        assert_eq!(parse_api_key("flk_app.key.secret.extra"), None);
+        assert_eq!(parse_api_key("flk_app..secret"), None);
+        assert_eq!(parse_api_key("pat_key.secret"), None);
+    }
 
-        // Hash the secret for lookup
-        let mut hasher = blake3::Hasher::new();
-        hasher.update(key_secret.as_bytes());
-        let secret_hash = hasher.finalize().to_hex().to_string().to_lowercase();
+    #[test]
+    fn test_valid_key() {
+        let key = format!("flk_app.key.{}", SECRET);
+        let parsed = parse_api_key(&key).unwrap();
+        assert_eq!(verify_api_key(&parsed, &record(None), at(100)), Ok(()));
+        assert_eq!(
+            verify_api_key(&parsed, &record(Some(at(200))), at(100)),
+            Ok(())
+        );
+    }
 
-        let db_app = TechnicalUser::find()
-            .filter(
-                technical_user::Column::Id
-                    .eq(key_id)
-                    .and(technical_user::Column::Key.eq(secret_hash)),
-            )
-            .one(&state.db)
-            .await?;
+    #[test]
+    fn test_rejected_keys() {
+        let wrong_secret = parse_api_key("flk_app.key.guessed").unwrap();
+        assert_eq!(
+            verify_api_key(&wrong_secret, &record(None), at(100)),
+            Err(ApiKeyError::Invalid)
+        );
+
+        // The app in the key has to be the app the key was issued for
+        let key = format!("flk_other.key.{}", SECRET);
+        let other_app = parse_api_key(&key).unwrap();
+        assert_eq!(
+            verify_api_key(&other_app, &record(None), at(100)),
+            Err(ApiKeyError::Invalid)
+        );
+
+        let key = format!("flk_app.key.{}", SECRET);
+        let expired = parse_api_key(&key).unwrap();
+        assert_eq!(
+            verify_api_key(&expired, &record(Some(at(50))), at(100)),
+            Err(ApiKeyError::Expired)
+        );
+    }
 
-        if let Some(app) = db_app {
-            if let Some(valid_until) = app.valid_until {
-                let now = chrono::Utc::now().naive_utc();
-                if valid_until < now {
-                    state.auth_cache.insert(cache_key, CachedAuth::Invalid);
-                    return Err(AuthorizationError::from(anyhow!("API Key is expired")));
-                }
-            }
+    #[test]
+    fn test_api_key_is_scoped_to_its_app() {
+        let user = AppUser::APIKey(ApiKey {
+            key_id: "key".to_string(),
+            api_key: format!("flk_app.key.{}", SECRET),
+            app_id: "app".to_string(),
+        });
+        let AppUser::APIKey(api_key) = &user else {
+            unreachable!()
+        };
+        assert!(api_key.covers("app"));
+        assert!(!api_key.covers("other"));
+        // No user behind the key, so no user scoped routes either
+        assert!(user.sub().is_err());
+    }
 
-            // Cache valid API key
-            state.auth_cache.insert(
-                cache_key,
-                CachedAuth::ApiKey {
-                    key_id: app.id.clone(),
-                    app_id: app.app_id.clone(),
-                },
-            );
+    #[test]
+    fn test_api_key_headers() {
+        let key = format!("flk_app.key.{}", SECRET);
+        assert_eq!(
+            api_key_from_headers(&headers(&[("x-api-key", &key)])),
+            Some(key.as_str())
+        );
+        assert_eq!(
+            api_key_from_headers(&headers(&[("authorization", &format!("Bearer {}", key))])),
+            Some(key.as_str())
+        );
+        assert_eq!(api_key_from_headers(&headers(&[])), None);
+    }
 
-            let app_user = AppUser::APIKey(ApiKey {
-                key_id: app.id.clone(),
-                api_key: api_key_str.to_string(),
-                app_id: app.app_id.clone(),
-            });
-            request.extensions_mut().insert::<AppUser>(app_user);
-            return Ok(next.run(request).await);
-        }
+    #[test]
+    fn test_jwt_and_pat_are_not_api_keys() {
+        let jwt = "Bearer eyJhbGciOiJSUzI1NiJ9.eyJzdWIiOiJ1c2VyIn0.c2ln";
+        assert_eq!(
+            api_key_from_headers(&headers(&[("authorization", jwt)])),
+            None
+        );
+        assert_eq!(
+            bearer_token(jwt),
+            "eyJhbGciOiJSUzI1NiJ9.eyJzdWIiOiJ1c2VyIn0.c2ln"
+        );
+        assert_eq!(
+            api_key_from_headers(&headers(&[("authorization", "pat_key.secret")])),
+            None
+        );
     }
 
-    request
-        .extensions_mut()
-        .insert::<AppUser>(AppUser::Unauthorized);
-    Ok(next.run(request).await)
+    #[tokio::test]
+    async fn test_deleted_key_is_rejected_on_next_use() {
+        use sea_orm::{DatabaseBackend, MockDatabase};
+
+        let key = format!("flk_app.key.{}", SECRET);
+        // The key exists on the first lookup and is gone after it was deleted
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([vec![record(None)], vec![]])
+            .into_connection();
+        let auth_cache = moka::sync::Cache::new(100);
+
+        let resolved = resolve_api_key(&db, &auth_cache, &key, at(100))
+            .await
+            .unwrap()
+            .expect("key should be valid before it is deleted");
+        assert_eq!(resolved.key_id, "key");
+        assert_eq!(resolved.app_id, "app");
+
+        // `delete_api_key` removes the row and clears the auth cache
+        auth_cache.invalidate_all();
+
+        assert!(
+            resolve_api_key(&db, &auth_cache, &key, at(100))
+                .await
+                .unwrap()
+                .is_none()
+        );
+        // The rejection is cached, so the deleted key does not hit the database again
+        assert!(
+            resolve_api_key(&db, &auth_cache, &key, at(100))
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
 }
@@ -0,0 +1,512 @@
+//! Per client rate limiting
+//!
+//! Every request takes a token from the bucket of its client. Signed in users are keyed
+//! by their sub, API keys by their key id and executors by their run, everything else by
+//! the client IP. A bucket holds up to `burst` tokens and refills with
+//! `requests_per_window` tokens per `window`. Empty buckets answer with 429 and a
+//! `Retry-After` header.
+//!
+//! ## Configuration
+//!
+//! - **`RATE_LIMIT_REQUESTS`**: Tokens refilled per window (default 600, 0 disables the limit)
+//! - **`RATE_LIMIT_WINDOW_SECS`**: Window length in seconds (default 60)
+//! - **`RATE_LIMIT_BURST`**: Bucket size (default 100)
+//! - **`RATE_LIMIT_BACKEND`**: `memory` (default) or `redis` to share buckets between
+//!   API instances. Redis uses `RATE_LIMIT_REDIS_URL`, falling back to `REDIS_URL`
+//! - **`RATE_LIMIT_TRUST_PROXY`**: Take the client IP from `X-Forwarded-For` instead of the
+//!   socket. Only enable it behind a proxy that overwrites the header
+//!
+//! The health and version routes are never limited. Anonymous requests need the client
+//! address, serve the router with `into_make_service_with_connect_info::<SocketAddr>()`
+//! or trust the proxy headers, otherwise they pass unlimited.
+
+use std::{
+    net::SocketAddr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{HeaderValue, header::RETRY_AFTER},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use parking_lot::Mutex;
+
+use crate::{error::ApiError, middleware::jwt::AppUser};
+
+#[cfg(feature = "redis")]
+const KEY_PREFIX: &str = "ratelimit:";
+
+#[derive(Clone, Debug)]
+pub struct RateLimitConfig {
+    pub requests_per_window: u32,
+    pub window: Duration,
+    pub burst: u32,
+    pub trust_proxy: bool,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            requests_per_window: 600,
+            window: Duration::from_secs(60),
+            burst: 100,
+            trust_proxy: false,
+        }
+    }
+}
+
+impl RateLimitConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let parse = |name: &str| std::env::var(name).ok().and_then(|v| v.parse::<u64>().ok());
+        Self {
+            requests_per_window: parse("RATE_LIMIT_REQUESTS")
+                .map(|v| v as u32)
+                .unwrap_or(defaults.requests_per_window),
+            window: parse("RATE_LIMIT_WINDOW_SECS")
+                .filter(|v| *v > 0)
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.window),
+            burst: parse("RATE_LIMIT_BURST")
+                .map(|v| v as u32)
+                .unwrap_or(defaults.burst),
+            trust_proxy: std::env::var("RATE_LIMIT_TRUST_PROXY")
+                .map(|v| matches!(v.to_lowercase().as_str(), "1" | "true" | "yes"))
+                .unwrap_or(defaults.trust_proxy),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.requests_per_window > 0
+    }
+
+    fn capacity(&self) -> f64 {
+        self.burst.max(1) as f64
+    }
+
+    /// Tokens refilled per second
+    fn refill_rate(&self) -> f64 {
+        self.requests_per_window as f64 / self.window.as_secs_f64()
+    }
+
+    /// Time an untouched bucket needs to fill up again, after that it can be forgotten
+    fn idle_ttl(&self) -> Duration {
+        Duration::from_secs_f64(self.capacity() / self.refill_rate()).max(self.window)
+    }
+}
+
+/// Where the buckets are kept
+#[async_trait]
+pub trait RateLimitBackend: Send + Sync {
+    /// Takes a token from the bucket of `key`.
+    /// Returns `None` if the request may pass, otherwise how long to wait for the next token.
+    async fn acquire(
+        &self,
+        key: &str,
+        config: &RateLimitConfig,
+    ) -> flow_like_types::Result<Option<Duration>>;
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl Bucket {
+    fn take(&mut self, now: Instant, capacity: f64, rate: f64) -> Option<Duration> {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * rate).min(capacity);
+        self.updated = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            return None;
+        }
+        Some(Duration::from_secs_f64((1.0 - self.tokens) / rate))
+    }
+}
+
+/// Buckets of this API instance only
+pub struct InMemoryRateLimitBackend {
+    buckets: moka::sync::Cache<String, Arc<Mutex<Bucket>>>,
+}
+
+impl InMemoryRateLimitBackend {
+    pub fn new(config: &RateLimitConfig) -> Self {
+        Self {
+            buckets: moka::sync::Cache::builder()
+                .max_capacity(100_000)
+                .time_to_idle(config.idle_ttl())
+                .build(),
+        }
+    }
+}
+
+#[async_trait]
+impl RateLimitBackend for InMemoryRateLimitBackend {
+    async fn acquire(
+        &self,
+        key: &str,
+        config: &RateLimitConfig,
+    ) -> flow_like_types::Result<Option<Duration>> {
+        let now = Instant::now();
+        let bucket = self.buckets.get_with_by_ref(key, || {
+            Arc::new(Mutex::new(Bucket {
+                tokens: config.capacity(),
+                updated: now,
+            }))
+        });
+        Ok(bucket
+            .lock()
+            .take(now, config.capacity(), config.refill_rate()))
+    }
+}
+
+/// Buckets shared by every API instance connected to the same Redis
+#[cfg(feature = "redis")]
+pub struct RedisRateLimitBackend {
+    client: redis::Client,
+    conn: tokio::sync::OnceCell<redis::aio::MultiplexedConnection>,
+    script: redis::Script,
+}
+
+/// Refills and takes from the bucket in one step, using the Redis clock so instances
+/// with skewed clocks agree. Returns the milliseconds to wait, 0 if a token was taken.
+#[cfg(feature = "redis")]
+const TOKEN_BUCKET_SCRIPT: &str = r#"
+local capacity = tonumber(ARGV[1])
+local rate = tonumber(ARGV[2])
+local ttl = tonumber(ARGV[3])
+local time = redis.call('TIME')
+local now = tonumber(time[1]) * 1000 + math.floor(tonumber(time[2]) / 1000)
+local state = redis.call('HMGET', KEYS[1], 'tokens', 'ts')
+local tokens = tonumber(state[1]) or capacity
+local ts = tonumber(state[2]) or now
+tokens = math.min(capacity, tokens + math.max(0, now - ts) * rate)
+local wait = 0
+if tokens >= 1 then
+    tokens = tokens - 1
+else
+    wait = math.ceil((1 - tokens) / rate)
+end
+redis.call('HSET', KEYS[1], 'tokens', tostring(tokens), 'ts', now)
+redis.call('PEXPIRE', KEYS[1], ttl)
+return wait
+"#;
+
+#[cfg(feature = "redis")]
+impl RedisRateLimitBackend {
+    pub fn new(url: &str) -> flow_like_types::Result<Self> {
+        Ok(Self {
+            client: redis::Client::open(url)?,
+            conn: tokio::sync::OnceCell::new(),
+            script: redis::Script::new(TOKEN_BUCKET_SCRIPT),
+        })
+    }
+}
+
+#[cfg(feature = "redis")]
+#[async_trait]
+impl RateLimitBackend for RedisRateLimitBackend {
+    async fn acquire(
+        &self,
+        key: &str,
+        config: &RateLimitConfig,
+    ) -> flow_like_types::Result<Option<Duration>> {
+        let mut conn = self
+            .conn
+            .get_or_try_init(|| self.client.get_multiplexed_async_connection())
+            .await?
+            .clone();
+
+        let wait_ms: u64 = self
+            .script
+            .key(format!("{KEY_PREFIX}{key}"))
+            .arg(config.capacity())
+            .arg(config.refill_rate() / 1000.0)
+            .arg(config.idle_ttl().as_millis() as u64)
+            .invoke_async(&mut conn)
+            .await?;
+
+        Ok((wait_ms > 0).then(|| Duration::from_millis(wait_ms)))
+    }
+}
+
+pub struct RateLimiter {
+    config: RateLimitConfig,
+    backend: Arc<dyn RateLimitBackend>,
+}
+
+impl RateLimiter {
+    pub fn new(config: RateLimitConfig, backend: Arc<dyn RateLimitBackend>) -> Self {
+        Self { config, backend }
+    }
+
+    pub fn in_memory(config: RateLimitConfig) -> Self {
+        let backend = Arc::new(InMemoryRateLimitBackend::new(&config));
+        Self::new(config, backend)
+    }
+
+    pub fn from_env() -> Self {
+        let config = RateLimitConfig::from_env();
+        let backend = std::env::var("RATE_LIMIT_BACKEND")
+            .unwrap_or_default()
+            .to_lowercase();
+
+        if backend == "redis" {
+            #[cfg(feature = "redis")]
+            {
+                let url = std::env::var("RATE_LIMIT_REDIS_URL")
+                    .or_else(|_| std::env::var("REDIS_URL"))
+                    .unwrap_or_else(|_| "redis://localhost:6379".to_string());
+                match RedisRateLimitBackend::new(&url) {
+                    Ok(backend) => return Self::new(config, Arc::new(backend)),
+                    Err(e) => tracing::warn!(
+                        "Failed to set up the Redis rate limit backend, using memory: {}",
+                        e
+                    ),
+                }
+            }
+            #[cfg(not(feature = "redis"))]
+            tracing::warn!("Redis rate limit backend requested but redis feature not enabled");
+        }
+
+        Self::in_memory(config)
+    }
+}
+
+fn is_exempt(path: &str) -> bool {
+    path == "/version" || path == "/health" || path.starts_with("/health/")
+}
+
+/// `None` for anonymous requests whose address is unknown, they are not limited rather
+/// than all sharing one bucket
+fn client_key(request: &Request, trust_proxy: bool) -> Option<String> {
+    match request.extensions().get::<AppUser>() {
+        Some(AppUser::OpenID(user)) => return Some(format!("user:{}", user.sub)),
+        Some(AppUser::PAT(user)) => return Some(format!("user:{}", user.sub)),
+        Some(AppUser::APIKey(key)) => return Some(format!("key:{}", key.key_id)),
+        Some(AppUser::Executor(user)) => return Some(format!("run:{}", user.run_id)),
+        Some(AppUser::Unauthorized) | None => {}
+    }
+
+    let forwarded = trust_proxy
+        .then(|| {
+            request
+                .headers()
+                .get("x-forwarded-for")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.split(',').next())
+                .map(|ip| ip.trim().to_string())
+                .filter(|ip| !ip.is_empty())
+        })
+        .flatten();
+
+    let ip = forwarded.or_else(|| {
+        request
+            .extensions()
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip().to_string())
+    });
+
+    ip.map(|ip| format!("ip:{}", ip))
+}
+
+/// Has to run after the JWT middleware, it reads the `AppUser` that one attaches
+pub async fn rate_limit_middleware(
+    State(limiter): State<Arc<RateLimiter>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !limiter.config.is_enabled() || is_exempt(request.uri().path()) {
+        return next.run(request).await;
+    }
+
+    let Some(key) = client_key(&request, limiter.config.trust_proxy) else {
+        return next.run(request).await;
+    };
+    let wait = match limiter.backend.acquire(&key, &limiter.config).await {
+        Ok(wait) => wait,
+        Err(e) => {
+            // Rather let traffic through than take the API down with the backend
+            tracing::warn!("Rate limit backend failed, letting the request pass: {}", e);
+            None
+        }
+    };
+
+    let Some(wait) = wait else {
+        return next.run(request).await;
+    };
+
+    let retry_after = wait.as_secs_f64().ceil().max(1.0) as u64;
+    let mut response = ApiError::too_many_requests(format!(
+        "Rate limit exceeded, retry in {} seconds",
+        retry_after
+    ))
+    .into_response();
+    response
+        .headers_mut()
+        .insert(RETRY_AFTER, HeaderValue::from(retry_after));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::jwt::{OpenIDUser, PATUser};
+    use axum::{
+        Router,
+        body::Body,
+        http::{Request as HttpRequest, StatusCode},
+        middleware::from_fn_with_state,
+        routing::get,
+    };
+    use tower::ServiceExt;
+
+    fn router(config: RateLimitConfig) -> Router {
+        let limiter = Arc::new(RateLimiter::in_memory(config));
+        Router::new()
+            .route("/apps", get(|| async { "ok" }))
+            .route("/version", get(|| async { "0.0.0" }))
+            .layer(from_fn_with_state(limiter, rate_limit_middleware))
+    }
+
+    fn config(burst: u32) -> RateLimitConfig {
+        RateLimitConfig {
+            requests_per_window: 1,
+            window: Duration::from_secs(60),
+            burst,
+            trust_proxy: false,
+        }
+    }
+
+    fn request(path: &str, user: Option<AppUser>) -> HttpRequest<Body> {
+        let mut request = HttpRequest::builder()
+            .uri(path)
+            .body(Body::empty())
+            .unwrap();
+        request
+            .extensions_mut()
+            .insert(ConnectInfo(SocketAddr::from(([10, 0, 0, 1], 4000))));
+        if let Some(user) = user {
+            request.extensions_mut().insert(user);
+        }
+        request
+    }
+
+    fn user(sub: &str) -> AppUser {
+        AppUser::OpenID(OpenIDUser {
+            sub: sub.to_string(),
+            access_token: String::new(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_requests_past_the_burst_get_429_with_retry_after() {
+        let app = router(config(3));
+
+        for _ in 0..3 {
+            let response = app
+                .clone()
+                .oneshot(request("/apps", Some(user("alice"))))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+
+        let response = app
+            .oneshot(request("/apps", Some(user("alice"))))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        let retry_after: u64 = response.headers()[RETRY_AFTER]
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        // One token per minute
+        assert!((1..=60).contains(&retry_after));
+    }
+
+    #[tokio::test]
+    async fn test_users_have_independent_buckets() {
+        let app = router(config(1));
+
+        let first = app
+            .clone()
+            .oneshot(request("/apps", Some(user("alice"))))
+            .await
+            .unwrap();
+        assert_eq!(first.status(), StatusCode::OK);
+        let limited = app
+            .clone()
+            .oneshot(request("/apps", Some(user("alice"))))
+            .await
+            .unwrap();
+        assert_eq!(limited.status(), StatusCode::TOO_MANY_REQUESTS);
+
+        // Same IP, but signed in as someone else
+        let pat = AppUser::PAT(PATUser {
+            pat: String::new(),
+            sub: "bob".to_string(),
+        });
+        let other = app
+            .clone()
+            .oneshot(request("/apps", Some(pat)))
+            .await
+            .unwrap();
+        assert_eq!(other.status(), StatusCode::OK);
+
+        // Anonymous requests fall back to the IP
+        let anonymous = app
+            .clone()
+            .oneshot(request("/apps", Some(AppUser::Unauthorized)))
+            .await
+            .unwrap();
+        assert_eq!(anonymous.status(), StatusCode::OK);
+        let anonymous = app
+            .oneshot(request("/apps", Some(AppUser::Unauthorized)))
+            .await
+            .unwrap();
+        assert_eq!(anonymous.status(), StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    #[tokio::test]
+    async fn test_version_route_is_exempt() {
+        let app = router(config(1));
+
+        for _ in 0..5 {
+            let response = app
+                .clone()
+                .oneshot(request("/version", None))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+    }
+
+    #[test]
+    fn test_bucket_refills_over_time() {
+        let start = Instant::now();
+        let mut bucket = Bucket {
+            tokens: 2.0,
+            updated: start,
+        };
+
+        assert!(bucket.take(start, 2.0, 1.0).is_none());
+        assert!(bucket.take(start, 2.0, 1.0).is_none());
+        let wait = bucket.take(start, 2.0, 1.0).unwrap();
+        assert_eq!(wait, Duration::from_secs(1));
+
+        // Refilling never exceeds the burst
+        let later = start + Duration::from_secs(10);
+        assert!(bucket.take(later, 2.0, 1.0).is_none());
+        assert!(bucket.take(later, 2.0, 1.0).is_none());
+        assert!(bucket.take(later, 2.0, 1.0).is_some());
+    }
+}
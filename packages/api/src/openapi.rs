@@ -1,3 +1,5 @@
+use axum::Json;
+use std::sync::LazyLock;
 use utoipa::{
     Modify, OpenApi,
     openapi::security::{
@@ -61,9 +63,32 @@ impl Modify for SecurityAddon {
                     .build(),
             ),
         );
+
+        // User JWT (poll_token returned by the invoke endpoints, scoped to one run)
+        components.add_security_scheme(
+            "user_jwt",
+            SecurityScheme::Http(
+                Http::builder()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .bearer_format("JWT")
+                    .description(Some("JWT token for polling a single execution run"))
+                    .build(),
+            ),
+        );
     }
 }
 
+/// Generated once, the document only changes with the binary
+static OPENAPI: LazyLock<utoipa::openapi::OpenApi> = LazyLock::new(ApiDoc::openapi);
+
+/// GET /openapi.json
+///
+/// The same document the Swagger UI shows, served next to the routes it describes.
+#[tracing::instrument(name = "GET /openapi.json")]
+pub async fn openapi_json() -> Json<utoipa::openapi::OpenApi> {
+    Json(OPENAPI.clone())
+}
+
 #[derive(OpenApi)]
 #[openapi(
     modifiers(&SecurityAddon),
@@ -240,6 +265,7 @@ impl Modify for SecurityAddon {
         crate::routes::app::data::upload_files::upload_files,
         crate::routes::app::data::download_files::download_files,
         crate::routes::app::data::list_files::list_files,
+        crate::routes::app::data::list_files::list_files_page,
         crate::routes::app::data::delete_files::delete_files,
         crate::routes::app::data::presign_data_access::presign_data_access,
         // Realtime board routes
@@ -269,6 +295,7 @@ impl Modify for SecurityAddon {
         crate::routes::execution::progress::push_events,
         crate::routes::execution::progress::poll_status,
         crate::routes::execution::progress::get_run_status,
+        crate::routes::execution::ws::run_events_socket,
         crate::routes::execution::public_key::get_execution_jwks,
         // Registry routes
         crate::routes::registry::publish::publish,
@@ -282,6 +309,8 @@ impl Modify for SecurityAddon {
         crate::routes::sink::trigger::trigger_http,
         crate::routes::sink::trigger::trigger_telegram,
         crate::routes::sink::trigger::trigger_discord,
+        crate::routes::sink::trigger::trigger_twilio,
+        crate::routes::sink::trigger::trigger_github,
         crate::routes::sink::trigger::trigger_service,
         crate::routes::sink::trigger::get_cron_sinks,
         crate::routes::sink::trigger::get_sink_configs,
@@ -301,6 +330,7 @@ impl Modify for SecurityAddon {
         crate::routes::solution::track_solution,
         // Tmp routes
         crate::routes::tmp::get_temporary_upload,
+        crate::routes::tmp::get_presigned_url,
         // Admin routes
         crate::routes::admin::solutions::list_solutions::list_solutions,
         crate::routes::admin::packages::get_stats::get_stats,
@@ -396,7 +426,93 @@ impl Modify for SecurityAddon {
         crate::routes::app::data::upload_files::UploadFilesPayload,
         crate::routes::app::data::download_files::DownloadFilesPayload,
         crate::routes::app::data::list_files::ListFilesPayload,
+        crate::routes::app::data::list_files::ListFilesPagePayload,
         crate::routes::app::data::delete_files::DeleteFilesPayload,
     ))
 )]
 pub struct ApiDoc;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+    use std::collections::HashSet;
+
+    const METHODS: [&str; 8] = [
+        "get", "put", "post", "delete", "options", "head", "patch", "trace",
+    ];
+
+    fn document() -> Value {
+        serde_json::to_value(ApiDoc::openapi()).unwrap()
+    }
+
+    fn collect_refs<'a>(value: &'a Value, refs: &mut Vec<&'a str>) {
+        match value {
+            Value::Object(map) => {
+                if let Some(Value::String(reference)) = map.get("$ref") {
+                    refs.push(reference);
+                }
+                map.values().for_each(|value| collect_refs(value, refs));
+            }
+            Value::Array(items) => items.iter().for_each(|value| collect_refs(value, refs)),
+            _ => {}
+        }
+    }
+
+    #[test]
+    fn test_document_is_consistent() {
+        let doc = document();
+        assert!(doc["openapi"].as_str().unwrap().starts_with("3.1"));
+        assert!(doc["info"]["title"].is_string());
+        assert!(doc["info"]["version"].is_string());
+
+        let schemes = doc["components"]["securitySchemes"].as_object().unwrap();
+        let mut operation_ids = HashSet::new();
+        for (path, item) in doc["paths"].as_object().unwrap() {
+            assert!(path.starts_with('/'), "{path} is not absolute");
+            for (method, operation) in item.as_object().unwrap() {
+                if !METHODS.contains(&method.as_str()) {
+                    continue;
+                }
+                let responses = operation["responses"].as_object().unwrap();
+                assert!(!responses.is_empty(), "{method} {path} has no responses");
+
+                let id = operation["operationId"].as_str().unwrap();
+                assert!(operation_ids.insert(id), "operationId {id} is used twice");
+
+                for requirement in operation["security"].as_array().into_iter().flatten() {
+                    for scheme in requirement.as_object().unwrap().keys() {
+                        assert!(
+                            schemes.contains_key(scheme),
+                            "{method} {path} uses the undefined security scheme {scheme}"
+                        );
+                    }
+                }
+            }
+        }
+
+        let mut refs = Vec::new();
+        collect_refs(&doc, &mut refs);
+        for reference in refs {
+            let pointer = reference
+                .strip_prefix('#')
+                .unwrap_or_else(|| panic!("{reference} is not a local reference"));
+            assert!(
+                doc.pointer(pointer).is_some(),
+                "{reference} does not resolve"
+            );
+        }
+    }
+
+    #[test]
+    fn test_document_includes_known_routes() {
+        let doc = document();
+        let poll = &doc["paths"]["/execution/poll"]["get"];
+        assert_eq!(poll["security"][0]["user_jwt"], serde_json::json!([]));
+        assert!(poll["responses"]["200"]["content"]["application/json"]["schema"].is_object());
+
+        let health = &doc["paths"]["/health"]["get"];
+        assert!(health.is_object());
+        assert_eq!(doc["servers"][0]["url"], "/api/v1");
+    }
+}
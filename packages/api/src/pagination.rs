@@ -0,0 +1,201 @@
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::NaiveDateTime;
+use sea_orm::{ColumnTrait, Condition};
+use serde::{Deserialize, Serialize};
+
+use crate::error::ApiError;
+
+pub const DEFAULT_PAGE_LIMIT: u64 = 50;
+pub const MAX_PAGE_LIMIT: u64 = 100;
+
+pub fn page_limit(limit: Option<u64>) -> u64 {
+    limit.unwrap_or(DEFAULT_PAGE_LIMIT).clamp(1, MAX_PAGE_LIMIT)
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Pass as `cursor` to get the next page, `None` on the last page
+    pub next_cursor: Option<String>,
+}
+
+impl<T> Page<T> {
+    /// Builds the page from rows queried with `limit + 1`, the extra row only tells
+    /// whether another page follows.
+    pub fn from_rows(mut rows: Vec<T>, limit: u64, cursor: impl Fn(&T) -> Cursor) -> Self {
+        let has_more = rows.len() as u64 > limit;
+        rows.truncate(limit as usize);
+        let next_cursor = if has_more {
+            rows.last().map(|row| cursor(row).encode())
+        } else {
+            None
+        };
+        Self {
+            items: rows,
+            next_cursor,
+        }
+    }
+
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U> {
+        Page {
+            items: self.items.into_iter().map(f).collect(),
+            next_cursor: self.next_cursor,
+        }
+    }
+}
+
+/// Position in a listing sorted by creation time and id, both descending. The id breaks
+/// ties, so rows created in the same microsecond are neither skipped nor repeated.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cursor {
+    pub created_at: NaiveDateTime,
+    pub id: String,
+}
+
+impl Cursor {
+    pub fn new(created_at: NaiveDateTime, id: &str) -> Self {
+        Self {
+            created_at,
+            id: id.to_string(),
+        }
+    }
+
+    pub fn encode(&self) -> String {
+        let micros = self.created_at.and_utc().timestamp_micros();
+        URL_SAFE_NO_PAD.encode(format!("{}:{}", micros, self.id))
+    }
+
+    pub fn decode(cursor: &str) -> Result<Self, ApiError> {
+        URL_SAFE_NO_PAD
+            .decode(cursor)
+            .ok()
+            .and_then(|bytes| String::from_utf8(bytes).ok())
+            .and_then(|decoded| {
+                let (micros, id) = decoded.split_once(':')?;
+                let created_at = chrono::DateTime::from_timestamp_micros(micros.parse().ok()?)?;
+                (!id.is_empty()).then(|| Self::new(created_at.naive_utc(), id))
+            })
+            .ok_or_else(|| ApiError::bad_request("Invalid cursor"))
+    }
+
+    pub fn parse(cursor: Option<&str>) -> Result<Option<Self>, ApiError> {
+        cursor
+            .filter(|cursor| !cursor.is_empty())
+            .map(Self::decode)
+            .transpose()
+    }
+
+    /// Rows after the cursor in the order `created_at DESC, id DESC`.
+    pub fn after<C: ColumnTrait>(&self, created_at: C, id: C) -> Condition {
+        Condition::any().add(created_at.lt(self.created_at)).add(
+            Condition::all()
+                .add(created_at.eq(self.created_at))
+                .add(id.lt(self.id.clone())),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{http::StatusCode, response::IntoResponse};
+
+    fn at(seconds: i64) -> NaiveDateTime {
+        chrono::DateTime::from_timestamp(seconds, 0)
+            .unwrap()
+            .naive_utc()
+    }
+
+    /// Mirrors the query of the routes: sorted descending, filtered by [`Cursor::after`]
+    /// and fetched with one extra row.
+    fn query(rows: &[(NaiveDateTime, String)], cursor: Option<&str>, limit: u64) -> Page<String> {
+        let cursor = Cursor::parse(cursor).unwrap();
+        let mut rows: Vec<(NaiveDateTime, String)> = rows
+            .iter()
+            .filter(|(created_at, id)| match &cursor {
+                Some(cursor) => {
+                    *created_at < cursor.created_at
+                        || (*created_at == cursor.created_at && *id < cursor.id)
+                }
+                None => true,
+            })
+            .cloned()
+            .collect();
+        rows.sort_by(|a, b| b.cmp(a));
+        rows.truncate(limit as usize + 1);
+        Page::from_rows(rows, limit, |(created_at, id)| Cursor::new(*created_at, id))
+            .map(|(_, id)| id)
+    }
+
+    fn rows() -> Vec<(NaiveDateTime, String)> {
+        // Several rows share a timestamp, only the id orders them
+        (0..23)
+            .map(|index| (at(1_700_000_000 + index / 3), format!("app-{:02}", index)))
+            .collect()
+    }
+
+    #[test]
+    fn test_cursor_roundtrip() {
+        let cursor = Cursor::new(at(1_700_000_000), "app:with:colons");
+        assert_eq!(Cursor::decode(&cursor.encode()).unwrap(), cursor);
+        assert_eq!(Cursor::parse(None).unwrap(), None);
+        assert_eq!(Cursor::parse(Some("")).unwrap(), None);
+    }
+
+    #[test]
+    fn test_invalid_cursor_is_bad_request() {
+        for cursor in ["not base64!", "bm90LWEtY3Vyc29y", "MTIzOg"] {
+            let error = Cursor::decode(cursor).unwrap_err();
+            assert_eq!(error.into_response().status(), StatusCode::BAD_REQUEST);
+        }
+    }
+
+    #[test]
+    fn test_page_limit() {
+        assert_eq!(page_limit(None), DEFAULT_PAGE_LIMIT);
+        assert_eq!(page_limit(Some(0)), 1);
+        assert_eq!(page_limit(Some(10_000)), MAX_PAGE_LIMIT);
+    }
+
+    #[test]
+    fn test_pages_cover_all_rows_once() {
+        let rows = rows();
+        let rows_prefix = rows[..10].to_vec();
+        let mut seen = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let page = query(&rows, cursor.as_deref(), 5);
+            assert!(page.items.len() <= 5);
+            seen.extend(page.items);
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+
+        let mut expected: Vec<String> = rows.into_iter().map(|(_, id)| id).collect();
+        expected.reverse();
+        assert_eq!(seen, expected);
+
+        let exact = query(&rows_prefix, None, 10);
+        assert_eq!(exact.items.len(), 10);
+        assert!(exact.next_cursor.is_none());
+    }
+
+    #[test]
+    fn test_pages_are_stable_under_insertion() {
+        let mut rows = rows();
+        let first = query(&rows, None, 5);
+        let cursor = first.next_cursor.clone().unwrap();
+        let expected_second = query(&rows, Some(&cursor), 5);
+
+        // Newer rows land on the first page, a row with the timestamp of the cursor and a
+        // larger id sorts before it as well
+        rows.push((at(1_800_000_000), "app-new".to_string()));
+        rows.push((at(1_700_000_006), "app-99".to_string()));
+
+        let second = query(&rows, Some(&cursor), 5);
+        assert_eq!(second.items, expected_second.items);
+        assert!(second.items.iter().all(|id| !first.items.contains(id)));
+    }
+}
@@ -27,6 +27,7 @@ pub struct LanguageParams {
     pub language: Option<String>,
     pub limit: Option<u64>,
     pub offset: Option<u64>,
+    pub cursor: Option<String>,
 }
 
 #[derive(Clone, Deserialize, Serialize, Debug)]
@@ -60,6 +60,10 @@ pub struct CopilotChatRequest {
     #[serde(default)]
     pub action_context: Option<UIActionContext>,
 
+    /// Limit for the model iterations of the turn, the copilot default if unset
+    #[serde(default)]
+    pub max_iterations: Option<u32>,
+
     /// Whether to stream the response
     #[serde(default)]
     pub stream: bool,
@@ -326,11 +330,15 @@ pub async fn copilot_chat(
 
     let token = user_access_token(&user);
 
-    let context = if payload.run_context.is_some() || payload.action_context.is_some() {
+    let context = if payload.run_context.is_some()
+        || payload.action_context.is_some()
+        || payload.max_iterations.is_some()
+    {
         Some(flow_like::copilot::UnifiedContext {
             scope: payload.scope,
             run_context: payload.run_context.clone(),
             action_context: payload.action_context.clone(),
+            max_iterations: payload.max_iterations,
         })
     } else {
         None
@@ -69,6 +69,7 @@ fn generate_encryption_key() -> String {
     path = "/apps/{app_id}/board/{board_id}/realtime",
     tag = "boards",
     description = "Get JWKS for realtime collaboration.",
+    operation_id = "get_realtime_jwks",
     params(
         ("app_id" = String, Path, description = "Application ID"),
         ("board_id" = String, Path, description = "Board ID")
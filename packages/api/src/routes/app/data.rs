@@ -20,4 +20,5 @@ pub fn routes() -> Router<AppState> {
         .route("/presign", post(presign_data_access::presign_data_access))
         .route("/download", post(download_files::download_files))
         .route("/list", post(list_files::list_files))
+        .route("/list/page", post(list_files::list_files_page))
 }
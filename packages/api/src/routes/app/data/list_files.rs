@@ -6,7 +6,7 @@ use axum::{
     Extension, Json,
     extract::{Path, State},
 };
-use flow_like_storage::files::store::StorageItem;
+use flow_like_storage::files::store::{StorageItem, StoragePage};
 use flow_like_types::anyhow;
 use utoipa::ToSchema;
 
@@ -15,6 +15,15 @@ pub struct ListFilesPayload {
     pub prefix: String,
 }
 
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+pub struct ListFilesPagePayload {
+    pub prefix: String,
+    /// Continuation of the previous page, omit for the first page
+    pub continuation: Option<String>,
+    /// Items per page, defaults to 100 and is capped at 1000
+    pub limit: Option<usize>,
+}
+
 #[utoipa::path(
     post,
     path = "/apps/{app_id}/data/list",
@@ -76,3 +85,56 @@ pub async fn list_files(
 
     Ok(Json(items))
 }
+
+#[utoipa::path(
+    post,
+    path = "/apps/{app_id}/data/list/page",
+    tag = "data",
+    description = "List one page of the files and folders under a prefix. Pass the returned continuation to get the next page.",
+    params(
+        ("app_id" = String, Path, description = "Application ID")
+    ),
+    request_body = ListFilesPagePayload,
+    responses(
+        (status = 200, description = "Page of files and folders", body = String, content_type = "application/json"),
+        (status = 400, description = "Bad request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden")
+    ),
+    security(
+        ("bearer_auth" = []),
+        ("api_key" = []),
+        ("pat" = [])
+    )
+)]
+#[tracing::instrument(name = "POST /apps/{app_id}/data/list/page", skip(state, user))]
+pub async fn list_files_page(
+    State(state): State<AppState>,
+    Extension(user): Extension<AppUser>,
+    Path(app_id): Path<String>,
+    Json(payload): Json<ListFilesPagePayload>,
+) -> Result<Json<StoragePage>, ApiError> {
+    ensure_permission!(user, &app_id, &state, RolePermissions::ReadFiles);
+
+    let sub = user.sub()?;
+
+    let project_dir = state
+        .scoped_credentials(
+            &sub,
+            &app_id,
+            crate::credentials::CredentialsAccess::ReadApp,
+        )
+        .await?;
+    let project_dir = project_dir.to_store(false).await?;
+    let path = project_dir
+        .construct_upload(&app_id, &payload.prefix)
+        .await?;
+
+    let limit = payload.limit.unwrap_or(100).clamp(1, 1000);
+    let page = project_dir
+        .list_page(Some(&path), payload.continuation.as_deref(), limit)
+        .await
+        .map_err(|e| anyhow!("Failed to list items: {}", e))?;
+
+    Ok(Json(page))
+}
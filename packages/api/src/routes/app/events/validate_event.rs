@@ -6,6 +6,7 @@ use axum::{
     Extension, Json,
     extract::{Path, Query, State},
 };
+use flow_like::flow::node::validation::ValidationMessage;
 use flow_like_types::anyhow;
 use serde::Deserialize;
 use utoipa::ToSchema;
@@ -27,7 +28,7 @@ pub struct VersionQuery {
         ("version" = Option<String>, Query, description = "Version in MAJOR_MINOR_PATCH format")
     ),
     responses(
-        (status = 200, description = "Validation succeeded", body = ()),
+        (status = 200, description = "Node validation messages, empty if the event is valid", body = String, content_type = "application/json"),
         (status = 400, description = "Bad request"),
         (status = 401, description = "Unauthorized"),
         (status = 403, description = "Forbidden")
@@ -47,7 +48,7 @@ pub async fn validate_event(
     Extension(user): Extension<AppUser>,
     Path((app_id, event_id)): Path<(String, String)>,
     Query(query): Query<VersionQuery>,
-) -> Result<Json<()>, ApiError> {
+) -> Result<Json<Vec<ValidationMessage>>, ApiError> {
     let permission = ensure_permission!(user, &app_id, &state, RolePermissions::WriteEvents);
     let sub = permission.sub()?;
 
@@ -76,7 +77,7 @@ pub async fn validate_event(
             crate::credentials::CredentialsAccess::EditApp,
         )
         .await?;
-    app.validate_event(&event_id, version_opt).await?;
+    let messages = app.validate_event(&event_id, version_opt).await?;
 
-    Ok(Json(()))
+    Ok(Json(messages))
 }
@@ -1,7 +1,10 @@
+use std::collections::HashMap;
+
 use crate::{
     entity::{app, membership, meta},
     error::ApiError,
     middleware::jwt::AppUser,
+    pagination::{Cursor, Page, page_limit},
     routes::LanguageParams,
     state::AppState,
 };
@@ -20,11 +23,12 @@ use sea_orm::{
     tag = "apps",
     params(
         ("language" = Option<String>, Query, description = "Language code (default: en)"),
-        ("limit" = Option<u64>, Query, description = "Maximum number of results (max 100)"),
-        ("offset" = Option<u64>, Query, description = "Offset for pagination")
+        ("limit" = Option<u64>, Query, description = "Maximum number of results (default 50, max 100)"),
+        ("cursor" = Option<String>, Query, description = "next_cursor of the previous page")
     ),
     responses(
-        (status = 200, description = "List of user applications with metadata", body = Vec<Object>),
+        (status = 200, description = "Page of user applications with metadata, newest first", body = Object),
+        (status = 400, description = "Invalid cursor"),
         (status = 401, description = "Unauthorized")
     )
 )]
@@ -33,38 +37,54 @@ pub async fn get_apps(
     State(state): State<AppState>,
     Extension(user): Extension<AppUser>,
     Query(query): Query<LanguageParams>,
-) -> Result<Json<Vec<(App, Option<Metadata>)>>, ApiError> {
+) -> Result<Json<Page<(App, Option<Metadata>)>>, ApiError> {
     let language = query.language.clone().unwrap_or_else(|| "en".to_string());
-
-    let limit = query.limit.unwrap_or(100).min(100);
+    let limit = page_limit(query.limit);
+    let cursor = Cursor::parse(query.cursor.as_deref())?;
 
     let sub = user.sub()?;
 
-    let apps_with_meta = app::Entity::find()
-        .order_by_desc(app::Column::UpdatedAt)
+    let mut apps = app::Entity::find()
         .join(JoinType::InnerJoin, app::Relation::Membership.def())
-        .find_with_related(meta::Entity)
+        .filter(membership::Column::UserId.eq(sub))
+        .order_by_desc(app::Column::CreatedAt)
+        .order_by_desc(app::Column::Id)
+        .limit(Some(limit + 1));
+    if let Some(cursor) = &cursor {
+        apps = apps.filter(cursor.after(app::Column::CreatedAt, app::Column::Id));
+    }
+    let apps = apps.all(&state.db).await?;
+    let page = Page::from_rows(apps, limit, |app| Cursor::new(app.created_at, &app.id));
+
+    // Loaded separately, a joined limit would count metadata rows instead of apps
+    let app_ids: Vec<String> = page.items.iter().map(|app| app.id.clone()).collect();
+    let mut meta_models: HashMap<String, Vec<meta::Model>> = HashMap::new();
+    for meta in meta::Entity::find()
+        .filter(meta::Column::AppId.is_in(app_ids))
         .filter(
             meta::Column::Lang
                 .eq(&language)
                 .or(meta::Column::Lang.eq("en")),
         )
-        .filter(membership::Column::UserId.eq(sub))
-        .limit(Some(limit.min(100)))
-        .offset(query.offset)
         .all(&state.db)
-        .await?;
+        .await?
+    {
+        if let Some(app_id) = meta.app_id.clone() {
+            meta_models.entry(app_id).or_default().push(meta);
+        }
+    }
 
     let master_store = state.master_credentials().await?;
     let store = master_store.to_store(false).await?;
 
-    let mut apps = Vec::new();
+    let mut items = Vec::with_capacity(page.items.len());
 
-    for (app_model, meta_models) in apps_with_meta {
-        let metadata = if let Some(meta) = meta_models
+    for app_model in page.items {
+        let metas = meta_models.remove(&app_model.id).unwrap_or_default();
+        let metadata = if let Some(meta) = metas
             .iter()
             .find(|meta| meta.lang == language)
-            .or_else(|| meta_models.first())
+            .or_else(|| metas.first())
         {
             let mut metadata = Metadata::from(meta.clone());
             let prefix = flow_like_storage::Path::from("media")
@@ -76,8 +96,11 @@ pub async fn get_apps(
             None
         };
 
-        apps.push((App::from(app_model), metadata));
+        items.push((App::from(app_model), metadata));
     }
 
-    Ok(Json(apps))
+    Ok(Json(Page {
+        items,
+        next_cursor: page.next_cursor,
+    }))
 }
@@ -2,6 +2,7 @@ use crate::{
     entity::{bit, meta, sea_orm_active_enums::BitType},
     error::ApiError,
     middleware::jwt::AppUser,
+    pagination::{Cursor, Page, page_limit},
     routes::LanguageParams,
     state::AppState,
 };
@@ -10,7 +11,8 @@ use axum::{
     extract::{Query, State},
 };
 use flow_like::{bit::Bit, hub::BitSearchQuery};
-use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QuerySelect};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
+use std::collections::HashMap;
 
 use super::get_bit::temporary_bit;
 
@@ -23,7 +25,8 @@ use super::get_bit::temporary_bit;
     ),
     request_body = BitSearchQuery,
     responses(
-        (status = 200, description = "Search results", body = Vec<Bit>)
+        (status = 200, description = "Page of search results, newest first", body = Object),
+        (status = 400, description = "Invalid cursor")
     )
 )]
 #[tracing::instrument(name = "POST /bit", skip(state, user, bit_query, lang_query))]
@@ -32,12 +35,14 @@ pub async fn search_bits(
     Extension(user): Extension<AppUser>,
     Query(lang_query): Query<LanguageParams>,
     Json(bit_query): Json<BitSearchQuery>,
-) -> Result<Json<Vec<Bit>>, ApiError> {
+) -> Result<Json<Page<Bit>>, ApiError> {
     if !state.platform_config.features.unauthorized_read {
         user.sub()?;
     }
 
     let language = lang_query.language.as_deref().unwrap_or("en");
+    let limit = page_limit(bit_query.limit);
+    let cursor = Cursor::parse(bit_query.cursor.as_deref())?;
 
     let cache_key = format!("search_bits:{:?}:{:?}", bit_query, language);
 
@@ -45,18 +50,23 @@ pub async fn search_bits(
         return Ok(Json(cached));
     }
 
-    let limit = std::cmp::min(bit_query.limit.unwrap_or(50), 100);
     let mut qb = bit::Entity::find()
-        .limit(Some(limit))
+        .left_join(meta::Entity)
+        .group_by(bit::Column::Id)
+        .order_by_desc(bit::Column::CreatedAt)
+        .order_by_desc(bit::Column::Id)
+        .limit(Some(limit + 1))
         .offset(bit_query.offset);
 
+    if let Some(cursor) = &cursor {
+        qb = qb.filter(cursor.after(bit::Column::CreatedAt, bit::Column::Id));
+    }
+
     if let Some(types) = bit_query.bit_types {
         let types: Vec<BitType> = types.into_iter().map(Into::into).collect();
         qb = qb.filter(bit::Column::Type.is_in(types));
     }
 
-    // qb = qb.left_join(meta::Entity);
-
     if let Some(search_str) = bit_query.search {
         qb = qb.filter(
             meta::Column::Description
@@ -72,45 +82,59 @@ pub async fn search_bits(
             .or(meta::Column::Lang.eq("en")),
     );
 
-    let models = qb
-        .find_with_related(meta::Entity)
+    let models = qb.all(&state.db).await.map_err(ApiError::from)?;
+    let page = Page::from_rows(models, limit, |bit| Cursor::new(bit.created_at, &bit.id));
+
+    // Loaded separately, a joined limit would count metadata rows instead of bits
+    let bit_ids: Vec<String> = page.items.iter().map(|bit| bit.id.clone()).collect();
+    let mut meta_models: HashMap<String, Vec<meta::Model>> = HashMap::new();
+    for meta in meta::Entity::find()
+        .filter(meta::Column::BitId.is_in(bit_ids))
+        .filter(
+            meta::Column::Lang
+                .eq(language)
+                .or(meta::Column::Lang.eq("en")),
+        )
         .all(&state.db)
         .await
-        .map_err(ApiError::from)?;
-
-    let mut bits = models
-        .into_iter()
-        .map(|(bit_model, meta_models)| {
-            let mut bit: Bit = Bit::from(bit_model);
-            if meta_models.len() == 1 {
-                // If there's only one metadata, use it directly
-                bit.meta
-                    .insert(language.to_string(), meta_models[0].clone().into());
-            }
-
-            let requested_lang_or_en = meta_models
-                .iter()
-                .find(|meta| meta.lang == language)
-                .or_else(|| meta_models.first())
-                .cloned();
-
-            if let Some(requested_lang_or_en) = requested_lang_or_en {
-                bit.meta.insert(
-                    requested_lang_or_en.lang.clone(),
-                    requested_lang_or_en.into(),
-                );
-            }
-            bit
-        })
-        .collect::<Vec<_>>();
+        .map_err(ApiError::from)?
+    {
+        if let Some(bit_id) = meta.bit_id.clone() {
+            meta_models.entry(bit_id).or_default().push(meta);
+        }
+    }
+
+    let mut page = page.map(|bit_model| {
+        let meta_models = meta_models.remove(&bit_model.id).unwrap_or_default();
+        let mut bit: Bit = Bit::from(bit_model);
+        if meta_models.len() == 1 {
+            // If there's only one metadata, use it directly
+            bit.meta
+                .insert(language.to_string(), meta_models[0].clone().into());
+        }
+
+        let requested_lang_or_en = meta_models
+            .iter()
+            .find(|meta| meta.lang == language)
+            .or_else(|| meta_models.first())
+            .cloned();
+
+        if let Some(requested_lang_or_en) = requested_lang_or_en {
+            bit.meta.insert(
+                requested_lang_or_en.lang.clone(),
+                requested_lang_or_en.into(),
+            );
+        }
+        bit
+    });
 
     if !state.platform_config.features.unauthorized_read {
-        for bit in bits.iter_mut() {
+        for bit in page.items.iter_mut() {
             *bit = temporary_bit(bit.clone(), &state.cdn_bucket).await?;
         }
     }
 
-    state.set_cache(cache_key, &bits);
+    state.set_cache(cache_key, &page);
 
-    Ok(Json(bits))
+    Ok(Json(page))
 }
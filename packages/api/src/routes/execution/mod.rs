@@ -2,7 +2,7 @@
 //!
 //! Contains routes for:
 //! - Executor → API: progress reporting, event pushing
-//! - User → API: long polling, status queries
+//! - User → API: long polling, status queries, WebSocket event stream
 //! - Public: JWKS for JWT verification
 
 use crate::state::AppState;
@@ -13,6 +13,7 @@ use axum::{
 
 pub mod progress;
 pub mod public_key;
+pub mod ws;
 
 pub fn routes() -> Router<AppState> {
     Router::new()
@@ -23,6 +24,8 @@ pub fn routes() -> Router<AppState> {
         .route("/poll", get(progress::poll_status))
         // App-auth endpoints (require normal app access)
         .route("/run/{run_id}", get(progress::get_run_status))
+        // User endpoint, the user JWT comes as query parameter or first frame
+        .route("/run/{run_id}/ws", get(ws::run_events_socket))
         // Public endpoints
         .route(
             "/.well-known/jwks.json",
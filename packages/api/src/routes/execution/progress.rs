@@ -11,8 +11,8 @@ use crate::{
     error::ApiError,
     execution::{
         state::{
-            CreateEventInput, EventQuery, ExecutionStateStore, RunStatus as StateRunStatus,
-            UpdateRunInput,
+            CreateEventInput, EventQuery, ExecutionEventRecord, ExecutionStateStore,
+            RunStatus as StateRunStatus, UpdateRunInput,
         },
         verify_execution_jwt, verify_user_jwt,
     },
@@ -182,7 +182,13 @@ pub async fn report_progress(
         .await
         .map_err(|e| ApiError::internal_error(anyhow!("Failed to update run: {}", e)))?;
 
+    // No receiver just means nobody watches the run over a WebSocket
+    let _ = state.run_events.send(claims.run_id.clone());
+
     if updated.status.is_terminal() {
+        // Frees the concurrency slot if a throttled sink started the run
+        state.sink_throttles.release(&claims.run_id);
+
         let duration_us = match (updated.started_at, updated.completed_at) {
             (Some(start), Some(end)) => (end - start) * 1000,
             _ => 0,
@@ -283,6 +289,8 @@ pub async fn push_events(
         .await
         .map_err(|e| ApiError::internal_error(anyhow!("Failed to push events: {}", e)))?;
 
+    let _ = state.run_events.send(claims.run_id.clone());
+
     Ok(Json(PushEventsResponse {
         accepted,
         next_sequence: next_seq,
@@ -324,6 +332,19 @@ pub struct ExecutionEventOutput {
     pub created_at: String,
 }
 
+impl From<ExecutionEventRecord> for ExecutionEventOutput {
+    fn from(event: ExecutionEventRecord) -> Self {
+        Self {
+            sequence: event.sequence,
+            event_type: event.event_type,
+            payload: event.payload,
+            created_at: chrono::DateTime::from_timestamp_millis(event.created_at)
+                .map(|dt| dt.to_rfc3339())
+                .unwrap_or_default(),
+        }
+    }
+}
+
 /// GET /execution/poll
 ///
 /// Long poll for run status and events. Requires user JWT in Authorization header.
@@ -393,17 +414,7 @@ pub async fn poll_status(
                 progress: run.progress,
                 current_step: run.current_step,
                 error: run.error_message,
-                events: events
-                    .into_iter()
-                    .map(|e| ExecutionEventOutput {
-                        sequence: e.sequence,
-                        event_type: e.event_type,
-                        payload: e.payload,
-                        created_at: chrono::DateTime::from_timestamp_millis(e.created_at)
-                            .map(|dt| dt.to_rfc3339())
-                            .unwrap_or_default(),
-                    })
-                    .collect(),
+                events: events.into_iter().map(ExecutionEventOutput::from).collect(),
                 started_at: run.started_at.and_then(|t| {
                     chrono::DateTime::from_timestamp_millis(t).map(|dt| dt.to_rfc3339())
                 }),
@@ -547,7 +558,9 @@ fn extract_bearer_token(headers: &HeaderMap) -> Result<&str, ApiError> {
 }
 
 /// Get or create the execution state store from app state
-async fn get_state_store(state: &AppState) -> Result<Arc<dyn ExecutionStateStore>, ApiError> {
+pub(super) async fn get_state_store(
+    state: &AppState,
+) -> Result<Arc<dyn ExecutionStateStore>, ApiError> {
     // Build config with available AppState components
     let mut config =
         crate::execution::state::StateStoreConfig::default().with_db(Arc::new(state.db.clone()));
@@ -0,0 +1,550 @@
+//! WebSocket stream of the events of a run
+//!
+//! Replays the stored events after `after_sequence` on connect and forwards new events as
+//! the executor pushes them. The socket closes with the final status once the run is done.
+
+use super::progress::{ExecutionEventOutput, get_state_store};
+use crate::{
+    error::ApiError,
+    execution::{
+        ExecutionClaims,
+        state::{EventQuery, ExecutionStateStore, StateStoreError},
+        verify_user_jwt,
+    },
+    state::AppState,
+};
+use axum::{
+    extract::{
+        Path, Query, State,
+        ws::{CloseFrame, Message, WebSocket, WebSocketUpgrade, close_code},
+    },
+    response::Response,
+};
+use flow_like_types::tokio::{
+    self,
+    sync::broadcast::{Receiver, error::RecvError},
+};
+use serde::Deserialize;
+use std::{sync::Arc, time::Duration};
+use utoipa::IntoParams;
+
+/// How long a client without `token` query parameter has to send the token frame.
+const AUTH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Events pushed to another API instance never reach the local broadcast, the store is
+/// checked in this interval as well.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Clone, Debug, Deserialize, IntoParams)]
+pub struct RunSocketParams {
+    /// User JWT of the run (poll_token). Can be sent as the first frame instead
+    pub token: Option<String>,
+    /// Last event sequence the client already has, the replay starts after it
+    pub after_sequence: Option<i32>,
+}
+
+#[derive(Deserialize)]
+struct AuthFrame {
+    token: String,
+}
+
+/// GET /execution/run/{run_id}/ws
+///
+/// Streams the events of a run as JSON text frames, each an `ExecutionEventOutput`.
+/// The user JWT goes into the `token` query parameter or into the first frame, either
+/// as the bare token or as `{"token": "..."}`.
+#[utoipa::path(
+    get,
+    path = "/execution/run/{run_id}/ws",
+    tag = "execution",
+    params(
+        ("run_id" = String, Path, description = "Run ID"),
+        RunSocketParams
+    ),
+    responses(
+        (status = 101, description = "Switching to the WebSocket protocol"),
+        (status = 400, description = "Invalid user JWT"),
+        (status = 403, description = "Token belongs to another run")
+    ),
+    security(
+        ("user_jwt" = [])
+    )
+)]
+#[tracing::instrument(name = "GET /execution/run/{run_id}/ws", skip(state, params, ws))]
+pub async fn run_events_socket(
+    State(state): State<AppState>,
+    Path(run_id): Path<String>,
+    Query(params): Query<RunSocketParams>,
+    ws: WebSocketUpgrade,
+) -> Result<Response, ApiError> {
+    // A token in the query is checked before the upgrade, so the client gets a plain error
+    let claims = params
+        .token
+        .as_deref()
+        .map(|token| authorize(token, &run_id))
+        .transpose()?;
+
+    let store = get_state_store(&state).await?;
+    // Subscribe before the replay, so no notification falls in between
+    let notifications = state.run_events.subscribe();
+    let after_sequence = params.after_sequence.unwrap_or(0);
+
+    Ok(ws.on_upgrade(move |mut socket| async move {
+        let claims = match claims {
+            Some(claims) => claims,
+            None => {
+                let Some(token) = receive_token(&mut socket).await else {
+                    close(socket, close_code::POLICY, "Missing token").await;
+                    return;
+                };
+                match authorize(&token, &run_id) {
+                    Ok(claims) => claims,
+                    Err(_) => {
+                        close(socket, close_code::POLICY, "Invalid token").await;
+                        return;
+                    }
+                }
+            }
+        };
+
+        stream_run_events(
+            socket,
+            store,
+            notifications,
+            run_id,
+            claims.app_id,
+            after_sequence,
+        )
+        .await;
+    }))
+}
+
+fn authorize(token: &str, run_id: &str) -> Result<ExecutionClaims, ApiError> {
+    let claims = verify_user_jwt(token)
+        .map_err(|e| ApiError::bad_request(format!("Invalid user JWT: {}", e)))?;
+    if claims.run_id != run_id {
+        return Err(ApiError::FORBIDDEN);
+    }
+    Ok(claims)
+}
+
+async fn receive_token(socket: &mut WebSocket) -> Option<String> {
+    let Ok(Some(Ok(Message::Text(text)))) = tokio::time::timeout(AUTH_TIMEOUT, socket.recv()).await
+    else {
+        return None;
+    };
+    let text = text.as_str().trim();
+    match serde_json::from_str::<AuthFrame>(text) {
+        Ok(frame) => Some(frame.token),
+        Err(_) => Some(text.to_string()),
+    }
+}
+
+/// Forwards the events of the run until it is done or the client goes away. Dropping the
+/// receiver on return ends the subscription.
+pub(crate) async fn stream_run_events(
+    mut socket: WebSocket,
+    store: Arc<dyn ExecutionStateStore>,
+    mut notifications: Receiver<String>,
+    run_id: String,
+    app_id: String,
+    after_sequence: i32,
+) {
+    let mut last_sequence = after_sequence;
+    let mut poll = tokio::time::interval(POLL_INTERVAL);
+    poll.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        // The status is read before the events, events pushed ahead of the final status
+        // update are therefore always forwarded before the socket closes
+        let run = match store.get_run_for_app(&run_id, &app_id).await {
+            Ok(Some(run)) => run,
+            Ok(None) => {
+                close(socket, close_code::POLICY, "Run not found").await;
+                return;
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, run_id = %run_id, "Failed to get run");
+                close(socket, close_code::ERROR, "Failed to get run").await;
+                return;
+            }
+        };
+
+        match forward_events(&mut socket, store.as_ref(), &run_id, &mut last_sequence).await {
+            Ok(true) => {}
+            Ok(false) => return,
+            Err(e) => {
+                tracing::warn!(error = %e, run_id = %run_id, "Failed to get events");
+                close(socket, close_code::ERROR, "Failed to get events").await;
+                return;
+            }
+        }
+
+        if run.status.is_terminal() {
+            close(socket, close_code::NORMAL, &format!("{:?}", run.status)).await;
+            return;
+        }
+
+        tokio::select! {
+            _ = next_notification(&mut notifications, &run_id) => {}
+            _ = poll.tick() => {}
+            message = socket.recv() => match message {
+                // Pings are answered by axum, anything else from the client is ignored
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
+
+/// Sends the events after `last_sequence` in order. Returns `false` if the client is gone.
+async fn forward_events(
+    socket: &mut WebSocket,
+    store: &dyn ExecutionStateStore,
+    run_id: &str,
+    last_sequence: &mut i32,
+) -> Result<bool, StateStoreError> {
+    let mut events = store
+        .get_events(EventQuery {
+            run_id: run_id.to_string(),
+            after_sequence: Some(*last_sequence),
+            only_undelivered: false,
+            limit: None,
+        })
+        .await?;
+    // Not every backend returns the events sorted
+    events.sort_by_key(|event| event.sequence);
+
+    for event in events {
+        let sequence = event.sequence;
+        let frame = serde_json::to_string(&ExecutionEventOutput::from(event))
+            .map_err(|e| StateStoreError::Serialization(e.to_string()))?;
+        if socket.send(Message::Text(frame.into())).await.is_err() {
+            return Ok(false);
+        }
+        *last_sequence = sequence;
+    }
+
+    Ok(true)
+}
+
+async fn next_notification(notifications: &mut Receiver<String>, run_id: &str) {
+    loop {
+        match notifications.recv().await {
+            Ok(id) if id == run_id => return,
+            Ok(_) => {}
+            // The skipped notifications may have been for this run
+            Err(RecvError::Lagged(_)) => return,
+            Err(RecvError::Closed) => std::future::pending::<()>().await,
+        }
+    }
+}
+
+async fn close(mut socket: WebSocket, code: u16, reason: &str) {
+    let frame = CloseFrame {
+        code,
+        reason: reason.to_string().into(),
+    };
+    let _ = socket.send(Message::Close(Some(frame))).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution::state::{
+        CreateEventInput, CreateRunInput, ExecutionEventRecord, ExecutionRunRecord, RunMode,
+        RunStatus, UpdateRunInput,
+    };
+    use async_trait::async_trait;
+    use axum::{Router, routing::get};
+    use flow_like_types::tokio::sync::{Mutex, broadcast};
+    use futures::StreamExt;
+    use tokio_tungstenite::tungstenite;
+
+    #[derive(Debug, Default)]
+    struct MemoryStateStore {
+        runs: Mutex<Vec<ExecutionRunRecord>>,
+        events: Mutex<Vec<ExecutionEventRecord>>,
+    }
+
+    #[async_trait]
+    impl ExecutionStateStore for MemoryStateStore {
+        fn backend_name(&self) -> &'static str {
+            "memory"
+        }
+
+        async fn create_run(
+            &self,
+            input: CreateRunInput,
+        ) -> Result<ExecutionRunRecord, StateStoreError> {
+            let run = ExecutionRunRecord {
+                id: input.id,
+                board_id: input.board_id,
+                version: input.version,
+                event_id: input.event_id,
+                status: RunStatus::Pending,
+                mode: input.mode,
+                input_payload_len: input.input_payload_len,
+                output_payload_len: 0,
+                error_message: None,
+                progress: 0,
+                current_step: None,
+                started_at: None,
+                completed_at: None,
+                expires_at: input.expires_at,
+                user_id: input.user_id,
+                app_id: input.app_id,
+                created_at: 0,
+                updated_at: 0,
+            };
+            self.runs.lock().await.push(run.clone());
+            Ok(run)
+        }
+
+        async fn get_run(
+            &self,
+            run_id: &str,
+        ) -> Result<Option<ExecutionRunRecord>, StateStoreError> {
+            let runs = self.runs.lock().await;
+            Ok(runs.iter().find(|run| run.id == run_id).cloned())
+        }
+
+        async fn get_run_for_app(
+            &self,
+            run_id: &str,
+            app_id: &str,
+        ) -> Result<Option<ExecutionRunRecord>, StateStoreError> {
+            let run = self.get_run(run_id).await?;
+            Ok(run.filter(|run| run.app_id == app_id))
+        }
+
+        async fn update_run(
+            &self,
+            run_id: &str,
+            input: UpdateRunInput,
+        ) -> Result<ExecutionRunRecord, StateStoreError> {
+            let mut runs = self.runs.lock().await;
+            let run = runs
+                .iter_mut()
+                .find(|run| run.id == run_id)
+                .ok_or(StateStoreError::NotFound)?;
+            if let Some(status) = input.status {
+                run.status = status;
+            }
+            Ok(run.clone())
+        }
+
+        async fn list_runs_for_app(
+            &self,
+            _app_id: &str,
+            _limit: i32,
+            _cursor: Option<&str>,
+        ) -> Result<Vec<ExecutionRunRecord>, StateStoreError> {
+            unimplemented!()
+        }
+
+        async fn delete_expired_runs(&self) -> Result<i64, StateStoreError> {
+            unimplemented!()
+        }
+
+        async fn push_events(&self, events: Vec<CreateEventInput>) -> Result<i32, StateStoreError> {
+            let count = events.len() as i32;
+            // Newest first, the stream has to sort them itself
+            let mut stored = self.events.lock().await;
+            for event in events {
+                stored.insert(
+                    0,
+                    ExecutionEventRecord {
+                        id: event.id,
+                        run_id: event.run_id,
+                        sequence: event.sequence,
+                        event_type: event.event_type,
+                        payload: event.payload,
+                        delivered: false,
+                        expires_at: event.expires_at,
+                        created_at: 0,
+                    },
+                );
+            }
+            Ok(count)
+        }
+
+        async fn get_events(
+            &self,
+            query: EventQuery,
+        ) -> Result<Vec<ExecutionEventRecord>, StateStoreError> {
+            let after = query.after_sequence.unwrap_or(0);
+            let events = self.events.lock().await;
+            Ok(events
+                .iter()
+                .filter(|event| event.run_id == query.run_id && event.sequence > after)
+                .cloned()
+                .collect())
+        }
+
+        async fn get_max_sequence(&self, _run_id: &str) -> Result<i32, StateStoreError> {
+            unimplemented!()
+        }
+
+        async fn mark_events_delivered(
+            &self,
+            _event_ids: &[String],
+        ) -> Result<(), StateStoreError> {
+            unimplemented!()
+        }
+
+        async fn delete_expired_events(&self) -> Result<i64, StateStoreError> {
+            unimplemented!()
+        }
+    }
+
+    async fn push(store: &MemoryStateStore, sequences: std::ops::RangeInclusive<i32>) {
+        let events = sequences
+            .map(|sequence| CreateEventInput {
+                id: format!("event-{}", sequence),
+                run_id: "run-1".to_string(),
+                sequence,
+                event_type: "log".to_string(),
+                payload: serde_json::json!({ "line": sequence }),
+                expires_at: 0,
+            })
+            .collect();
+        store.push_events(events).await.unwrap();
+    }
+
+    type Client = tokio_tungstenite::WebSocketStream<
+        tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>,
+    >;
+
+    /// Sequence of the next event frame, `None` once the server closed the socket.
+    async fn next_sequence(client: &mut Client) -> Option<i64> {
+        let message = tokio::time::timeout(Duration::from_secs(5), client.next())
+            .await
+            .expect("no frame within 5s")?
+            .unwrap();
+        match message {
+            tungstenite::Message::Text(text) => {
+                let event: serde_json::Value = serde_json::from_str(text.as_str()).unwrap();
+                assert_eq!(event["event_type"], "log");
+                assert_eq!(event["payload"]["line"], event["sequence"]);
+                event["sequence"].as_i64()
+            }
+            tungstenite::Message::Close(frame) => {
+                assert_eq!(frame.unwrap().reason.as_str(), "Completed");
+                None
+            }
+            other => panic!("unexpected frame {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_socket_replays_and_forwards_events_in_order() {
+        let store = Arc::new(MemoryStateStore::default());
+        store
+            .create_run(CreateRunInput {
+                id: "run-1".to_string(),
+                board_id: "board".to_string(),
+                version: None,
+                event_id: None,
+                mode: RunMode::Http,
+                input_payload_len: 0,
+                user_id: None,
+                app_id: "app".to_string(),
+                expires_at: None,
+            })
+            .await
+            .unwrap();
+        push(&store, 1..=3).await;
+
+        let (sender, _) = broadcast::channel::<String>(16);
+        let app = {
+            let store = store.clone();
+            let sender = sender.clone();
+            Router::new().route(
+                "/run/{run_id}/ws",
+                get(
+                    move |Path(run_id): Path<String>,
+                          Query(params): Query<RunSocketParams>,
+                          ws: WebSocketUpgrade| {
+                        let store: Arc<dyn ExecutionStateStore> = store.clone();
+                        let notifications = sender.subscribe();
+                        async move {
+                            ws.on_upgrade(move |socket| {
+                                stream_run_events(
+                                    socket,
+                                    store,
+                                    notifications,
+                                    run_id,
+                                    "app".to_string(),
+                                    params.after_sequence.unwrap_or(0),
+                                )
+                            })
+                        }
+                    },
+                ),
+            )
+        };
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let url = format!("ws://{}/run/run-1/ws?after_sequence=1", address);
+        let (mut client, _) = tokio_tungstenite::connect_async(url).await.unwrap();
+
+        // Catch-up replay of what was stored before the connect
+        assert_eq!(next_sequence(&mut client).await, Some(2));
+        assert_eq!(next_sequence(&mut client).await, Some(3));
+
+        push(&store, 4..=6).await;
+        sender.send("run-1".to_string()).unwrap();
+        for sequence in 4..=6 {
+            assert_eq!(next_sequence(&mut client).await, Some(sequence));
+        }
+
+        push(&store, 7..=7).await;
+        store
+            .update_run(
+                "run-1",
+                UpdateRunInput {
+                    status: Some(RunStatus::Completed),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        sender.send("run-1".to_string()).unwrap();
+        assert_eq!(next_sequence(&mut client).await, Some(7));
+        assert_eq!(next_sequence(&mut client).await, None);
+
+        // The handler returned and dropped its subscription
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while sender.receiver_count() > 0 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("subscription was not cleaned up");
+    }
+
+    #[test]
+    fn test_token_must_belong_to_the_run() {
+        if !crate::execution::is_jwt_configured() {
+            return;
+        }
+        let token = crate::execution::sign_execution_jwt(crate::execution::ExecutionJwtParams {
+            user_id: "user".to_string(),
+            run_id: "run-1".to_string(),
+            app_id: "app".to_string(),
+            board_id: "board".to_string(),
+            event_id: None,
+            callback_url: String::new(),
+            token_type: crate::execution::TokenType::User,
+            ttl_seconds: None,
+        })
+        .unwrap();
+
+        assert_eq!(authorize(&token, "run-1").unwrap().app_id, "app");
+        assert!(authorize(&token, "run-2").is_err());
+        assert!(authorize("not-a-token", "run-1").is_err());
+    }
+}
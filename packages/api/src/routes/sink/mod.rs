@@ -5,7 +5,9 @@
 //! - HTTP sink triggers (/sink/trigger/http/{app_id}/{path})
 //! - Telegram webhook triggers (/sink/trigger/telegram/{event_id})
 //! - Discord interactions webhook triggers (/sink/trigger/discord/{event_id})
-//! - Service-to-service triggers (/sink/trigger/async) - for cron, discord bot, telegram bot
+//! - Twilio inbound SMS webhook triggers (/sink/trigger/twilio/{event_id})
+//! - GitHub repository webhook triggers (/sink/trigger/github/{event_id})
+//! - Service-to-service triggers (/sink/trigger/async) - for cron, discord bot, telegram bot, S3 notifications
 //! - Listing all active sinks for user's apps
 //!
 //! Note: Sink config comes from the Event itself. We only store sink-specific
@@ -31,7 +33,7 @@ pub fn routes() -> Router<AppState> {
         .route("/{event_id}", get(management::get_sink))
         .route("/{event_id}", patch(management::update_sink))
         .route("/{event_id}/toggle", post(management::toggle_sink))
-        // Service-to-service trigger (for internal sink services: cron, discord bot, telegram bot)
+        // Service-to-service trigger (for internal sink services: cron, discord bot, telegram bot, S3 notifications)
         .route("/trigger/async", post(trigger::trigger_service))
         // List cron schedules (for docker-compose sink service to sync)
         .route("/schedules", get(trigger::get_cron_sinks))
@@ -61,4 +63,8 @@ pub fn routes() -> Router<AppState> {
             "/trigger/discord/{event_id}",
             post(trigger::trigger_discord),
         )
+        // Twilio inbound SMS webhook trigger - async execution with X-Twilio-Signature verification
+        .route("/trigger/twilio/{event_id}", post(trigger::trigger_twilio))
+        // GitHub repository webhook trigger - async execution with X-Hub-Signature-256 verification
+        .route("/trigger/github/{event_id}", post(trigger::trigger_github))
 }
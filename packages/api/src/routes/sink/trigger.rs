@@ -2,8 +2,11 @@
 //!
 //! Provides:
 //! - `trigger_event` - Utility function for programmatic event triggering (Lambda, SQS, etc.)
+//! - `trigger_s3_notification` - Runs an S3 sink once per object of a bucket notification
 //! - `http_trigger` - HTTP endpoint for HTTP sinks
 //! - `telegram_trigger` - Telegram webhook endpoint with secret token & IP verification
+//! - `trigger_twilio` - Twilio inbound SMS webhook endpoint with signature verification
+//! - `trigger_github` - GitHub repository webhook endpoint with signature verification
 //! - `service_trigger` - Service-to-service trigger for internal services (cron, discord bot, etc.)
 
 use crate::{
@@ -22,7 +25,7 @@ use crate::{
 use axum::{
     Json,
     body::Body,
-    extract::{ConnectInfo, Path, Query, State},
+    extract::{ConnectInfo, Path, Query, RawQuery, State},
     http::{HeaderMap, Method, StatusCode},
     response::{IntoResponse, Response},
 };
@@ -107,6 +110,10 @@ pub struct TriggerResponse {
 /// If the sink has stored PAT and/or OAuth tokens, they will be decrypted and
 /// passed to the executor, enabling access to models and personal files.
 ///
+/// The run goes through [`flow_like_sinks::SinkContext::dispatch`] with the throttles of
+/// [`AppState`], so events beyond the rate limit or concurrency cap of the sink are dropped
+/// (`triggered` is false) or wait for their turn.
+///
 /// # Example
 /// ```ignore
 /// // In a Lambda handler
@@ -120,6 +127,82 @@ pub async fn trigger_event(
     state: &AppState,
     input: TriggerEventInput,
 ) -> FlResult<TriggerResponse> {
+    use flow_like_sinks::SinkError;
+
+    let sink = event_sink::Entity::find()
+        .filter(event_sink::Column::EventId.eq(&input.event_id))
+        .filter(event_sink::Column::Active.eq(true))
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| anyhow!("No active sink found for event {}", input.event_id))?;
+    let event = get_event_from_db(&state.db, &sink.event_id).await?;
+    let registration = sink_registration(&sink, &event)?;
+
+    let ctx = sink_context(state, input.user_id.unwrap_or_else(|| "system".to_string()));
+    match ctx.dispatch(&registration, input.payload).await {
+        Ok(run_id) => Ok(TriggerResponse {
+            triggered: true,
+            run_id: Some(run_id),
+            message: "Event triggered successfully".to_string(),
+        }),
+        Err(e @ (SinkError::Throttled(_) | SinkError::ExecutionFailed(_))) => Ok(TriggerResponse {
+            triggered: false,
+            run_id: None,
+            message: e.to_string(),
+        }),
+        Err(e) => Err(anyhow!(e)),
+    }
+}
+
+/// The [`flow_like_sinks::SinkRegistration`] of a stored sink, its config lives in the event
+fn sink_registration(
+    sink: &event_sink::Model,
+    event: &flow_like::flow::event::Event,
+) -> flow_like_sinks::SinkResult<flow_like_sinks::SinkRegistration> {
+    use flow_like_sinks::{SinkError, SinkExecution, SinkRegistration};
+
+    let config = if event.config.is_empty() {
+        serde_json::json!({})
+    } else {
+        serde_json::from_slice(&event.config)
+            .map_err(|e| SinkError::InvalidConfig(format!("Invalid sink config: {}", e)))?
+    };
+
+    Ok(SinkRegistration {
+        id: sink.id.clone(),
+        event_id: sink.event_id.clone(),
+        board_id: event.board_id.clone(),
+        app_id: sink.app_id.clone(),
+        sink_type: sink.sink_type.parse().map_err(SinkError::InvalidConfig)?,
+        config,
+        execution: SinkExecution::Remote,
+        active: sink.active,
+        auth_token: None,
+        path: sink.path.clone(),
+        method: None,
+        cron_expression: sink.cron_expression.clone(),
+        default_payload: None,
+        personal_access_token: None,
+        oauth_tokens: None,
+    })
+}
+
+/// Context of the sink handlers, the throttles are shared by all trigger routes
+fn sink_context(
+    state: &AppState,
+    user_id: impl Into<String>,
+) -> flow_like_sinks::SinkContext<TriggerEventExecutor> {
+    flow_like_sinks::SinkContext::with_throttles(
+        Arc::new(TriggerEventExecutor {
+            state: state.clone(),
+            user_id: user_id.into(),
+        }),
+        state.sink_throttles.clone(),
+    )
+}
+
+/// Creates the run record of an event and dispatches it, without any throttling
+async fn start_run(state: &AppState, input: TriggerEventInput) -> FlResult<TriggerResponse> {
     use crate::routes::app::events::db::decrypt_token;
 
     // Look up sink by event_id
@@ -255,6 +338,77 @@ pub async fn trigger_event(
     }
 }
 
+/// Trigger an S3 sink for a bucket notification received from SQS or SNS.
+///
+/// Every object that passes the bucket/prefix/suffix filters of the event config
+/// starts its own run with the object metadata as payload. Test events start nothing,
+/// an invalid event config starts nothing and is returned as error.
+///
+/// Bucket notifications arrive through [`trigger_service`] with sink type `s3` and the
+/// notification as payload.
+///
+/// # Example
+/// ```ignore
+/// // In an SQS-triggered Lambda handler
+/// let results = trigger_s3_notification(&state, "event_123", &sqs_event).await?;
+/// ```
+pub async fn trigger_s3_notification(
+    state: &AppState,
+    event_id: &str,
+    notification: &serde_json::Value,
+) -> FlResult<Vec<TriggerResponse>> {
+    let event = get_event_from_db(&state.db, event_id).await?;
+    let objects = s3_objects_to_trigger(&event.config, notification).inspect_err(|e| {
+        tracing::error!(event_id = %event_id, error = %e, "S3 notification not triggered");
+    })?;
+
+    let mut results = Vec::new();
+    for object in objects {
+        tracing::info!(
+            event_id = %event_id,
+            bucket = %object.bucket,
+            key = %object.key,
+            "S3 notification: triggering event"
+        );
+        results.push(
+            trigger_event(
+                state,
+                TriggerEventInput {
+                    event_id: event_id.to_string(),
+                    payload: Some(serde_json::to_value(&object)?),
+                    user_id: Some("service:s3".to_string()),
+                },
+            )
+            .await?,
+        );
+    }
+
+    Ok(results)
+}
+
+/// The objects of a notification that pass the filters of the S3 sink config in `event_config`
+fn s3_objects_to_trigger(
+    event_config: &[u8],
+    notification: &serde_json::Value,
+) -> FlResult<Vec<flow_like_sinks::s3::S3Object>> {
+    use flow_like_sinks::{S3EventSinkConfig, s3::S3EventSink};
+
+    let config: S3EventSinkConfig = if event_config.is_empty() {
+        S3EventSinkConfig::default()
+    } else {
+        serde_json::from_slice(event_config)
+            .map_err(|e| anyhow!("Invalid S3 sink config: {}", e))?
+    };
+
+    let objects = S3EventSink::parse_notification(notification)
+        .map_err(|e| anyhow!("Invalid S3 notification: {}", e))?;
+
+    Ok(objects
+        .into_iter()
+        .filter(|object| object.matches(&config))
+        .collect())
+}
+
 /// POST/GET/etc /sink/trigger/{app_id}/{path}
 /// HTTP endpoint for HTTP sinks
 #[utoipa::path(
@@ -375,6 +529,27 @@ pub async fn trigger_http(
         .await
         .map_err(|e| ApiError::internal_error(anyhow!("Failed to get event: {}", e)))?;
 
+    // The run streams its result back, the throttle slot is held as long as the response body
+    let registration =
+        sink_registration(&sink, &event).map_err(|e| ApiError::bad_request(e.to_string()))?;
+    let permit = match sink_context(&state, "http_sink")
+        .acquire(&registration)
+        .await
+    {
+        Ok(permit) => permit,
+        Err(e) => {
+            return Ok((
+                StatusCode::from_u16(e.status_code()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+                Json(TriggerResponse {
+                    triggered: false,
+                    run_id: None,
+                    message: e.to_string(),
+                }),
+            )
+                .into_response());
+        }
+    };
+
     // Check JWT configured
     if !is_jwt_configured() {
         return Err(ApiError::internal_error(anyhow!(
@@ -510,12 +685,15 @@ pub async fn trigger_http(
             match dispatch_result {
                 Ok((_dispatch_response, byte_stream)) => {
                     tracing::info!(run_id = %run_id, "Got Lambda response, starting stream");
-                    Ok(proxy_lambda_sse_response(
-                        byte_stream,
-                        run_id,
-                        Some(Arc::new(state.db.clone())),
-                    )
-                    .into_response())
+                    Ok(hold_permit(
+                        proxy_lambda_sse_response(
+                            byte_stream,
+                            run_id,
+                            Some(Arc::new(state.db.clone())),
+                        )
+                        .into_response(),
+                        permit,
+                    ))
                 }
                 Err(e) => {
                     tracing::error!(error = %e, "Failed to dispatch Lambda streaming");
@@ -542,12 +720,15 @@ pub async fn trigger_http(
             match dispatch_result {
                 Ok((_dispatch_response, executor_response)) => {
                     tracing::info!(run_id = %run_id, "Got executor response, starting stream");
-                    Ok(proxy_sse_response(
-                        executor_response,
-                        run_id,
-                        Some(Arc::new(state.db.clone())),
-                    )
-                    .into_response())
+                    Ok(hold_permit(
+                        proxy_sse_response(
+                            executor_response,
+                            run_id,
+                            Some(Arc::new(state.db.clone())),
+                        )
+                        .into_response(),
+                        permit,
+                    ))
                 }
                 Err(e) => {
                     tracing::error!(error = %e, "Failed to dispatch");
@@ -566,6 +747,24 @@ pub async fn trigger_http(
     }
 }
 
+/// Keeps the throttle slot of a streamed run until its response body is dropped
+fn hold_permit(
+    response: Response,
+    permit: Option<flow_like_sinks::throttle::ThrottlePermit>,
+) -> Response {
+    use futures::StreamExt;
+
+    let Some(permit) = permit else {
+        return response;
+    };
+    let (parts, body) = response.into_parts();
+    let body = body.into_data_stream().map(move |chunk| {
+        let _held = &permit;
+        chunk
+    });
+    Response::from_parts(parts, Body::from_stream(body))
+}
+
 /// Query params for Telegram webhook (optional secret_token as query param)
 #[derive(Debug, Deserialize)]
 pub struct TelegramQueryParams {
@@ -602,8 +801,6 @@ pub async fn trigger_telegram(
     ConnectInfo(connect_info): ConnectInfo<SocketAddr>,
     body: Body,
 ) -> Result<Response, ApiError> {
-    use crate::routes::app::events::db::decrypt_token;
-
     let client_ip = connect_info.ip();
 
     tracing::info!(
@@ -708,148 +905,346 @@ pub async fn trigger_telegram(
         None
     };
 
-    // Get the event from database
-    let event = get_event_from_db(&state.db, &sink.event_id)
-        .await
-        .map_err(|e| ApiError::internal_error(anyhow!("Failed to get event: {}", e)))?;
+    tracing::info!(event_id = %sink.event_id, "Dispatching Telegram webhook (async)");
 
-    // Check JWT configured
-    if !is_jwt_configured() {
-        return Err(ApiError::internal_error(anyhow!(
-            "Execution JWT signing not configured"
-        )));
-    }
-
-    // Create run
-    let run_id = create_id();
-    let expires_at = chrono::Utc::now().naive_utc() + chrono::Duration::hours(24);
+    // Telegram expects a fast acknowledgement, so the run is dispatched in the background
+    let event_id = sink.event_id.clone();
+    tokio::spawn(async move {
+        let result = trigger_event(
+            &state,
+            TriggerEventInput {
+                event_id: event_id.clone(),
+                payload,
+                user_id: Some("telegram_webhook".to_string()),
+            },
+        )
+        .await;
+        if let Err(e) = result {
+            tracing::error!(event_id = %event_id, error = %e, "Telegram webhook dispatch failed");
+        }
+    });
 
-    let input_payload_len = payload
-        .as_ref()
-        .map(|p| {
-            serde_json::to_string(p)
-                .map(|s| s.len() as i64)
-                .unwrap_or(0)
-        })
-        .unwrap_or(0);
+    // Return immediately - Telegram expects fast acknowledgement
+    Ok((
+        StatusCode::OK,
+        Json(TriggerResponse {
+            triggered: true,
+            run_id: None,
+            message: "Webhook received and processing".to_string(),
+        }),
+    )
+        .into_response())
+}
 
-    let event_json = serde_json::to_string(&event)
-        .map_err(|e| ApiError::internal_error(anyhow!("Failed to serialize event: {}", e)))?;
+/// POST /sink/trigger/twilio/{event_id}
+/// Twilio inbound SMS webhook - async execution with `X-Twilio-Signature` verification.
+/// The sink's webhook secret holds the auth token of the Twilio account.
+#[utoipa::path(
+    post,
+    path = "/sink/trigger/twilio/{event_id}",
+    tag = "sink",
+    params(
+        ("event_id" = String, Path, description = "Event ID")
+    ),
+    responses(
+        (status = 200, description = "Message received, empty TwiML response"),
+        (status = 400, description = "Body is not a Twilio message"),
+        (status = 403, description = "Invalid or missing signature"),
+        (status = 404, description = "Webhook not found or inactive")
+    )
+)]
+#[tracing::instrument(
+    name = "POST /sink/trigger/twilio/{event_id}",
+    skip(state, headers, body)
+)]
+pub async fn trigger_twilio(
+    State(state): State<AppState>,
+    Path(event_id): Path<String>,
+    RawQuery(query): RawQuery,
+    headers: HeaderMap,
+    body: Body,
+) -> Result<Response, ApiError> {
+    use flow_like_sinks::twilio::{EMPTY_TWIML, TwilioWebhookRequest};
 
-    // Get credentials
-    let credentials = state
-        .master_credentials()
+    let sink = event_sink::Entity::find()
+        .filter(event_sink::Column::EventId.eq(&event_id))
+        .filter(event_sink::Column::Active.eq(true))
+        .filter(event_sink::Column::SinkType.eq("twilio"))
+        .one(&state.db)
         .await
-        .map_err(|e| ApiError::internal_error(anyhow!("Failed to get credentials: {}", e)))?;
-
-    let shared_credentials = credentials.into_shared_credentials();
-    let credentials_json = serde_json::to_string(&shared_credentials)
-        .map_err(|e| ApiError::internal_error(anyhow!("Failed to serialize credentials: {}", e)))?;
-
-    let callback_url =
-        std::env::var("API_BASE_URL").unwrap_or_else(|_| "http://localhost:8080".to_string());
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            ApiError::internal_error(anyhow!("Database error"))
+        })?;
 
-    // Sign JWT
-    let executor_jwt = sign_execution_jwt(ExecutionJwtParams {
-        user_id: "telegram_webhook".to_string(),
-        run_id: run_id.clone(),
-        app_id: sink.app_id.clone(),
-        board_id: event.board_id.clone(),
-        event_id: Some(sink.event_id.clone()),
-        callback_url: callback_url.clone(),
-        token_type: TokenType::Executor,
-        ttl_seconds: Some(24 * 60 * 60),
-    })
-    .map_err(|e| ApiError::internal_error(anyhow!("Failed to sign JWT: {}", e)))?;
+    let Some(sink) = sink else {
+        tracing::warn!("No active Twilio sink found for event {}", event_id);
+        return Ok((StatusCode::NOT_FOUND, "Webhook not found or inactive").into_response());
+    };
+    // The auth token of the Twilio account lives in the webhook secret of the sink
+    let Some(auth_token) = sink
+        .webhook_secret
+        .as_deref()
+        .filter(|auth_token| !auth_token.is_empty())
+    else {
+        tracing::warn!("Twilio sink for event {} has no auth token", event_id);
+        return Ok((
+            StatusCode::FORBIDDEN,
+            "Webhook has no auth token configured",
+        )
+            .into_response());
+    };
 
-    // Decrypt PAT from sink if available
-    let token = sink
-        .pat_encrypted
-        .as_ref()
-        .and_then(|encrypted| decrypt_token(encrypted));
+    let body_bytes = axum::body::to_bytes(body, 1024 * 1024).await.map_err(|e| {
+        tracing::error!("Failed to read body: {}", e);
+        ApiError::bad_request("Failed to read request body")
+    })?;
 
-    // Decrypt OAuth tokens from sink if available
-    let oauth_tokens: Option<std::collections::HashMap<String, serde_json::Value>> = sink
-        .oauth_tokens_encrypted
-        .as_ref()
-        .and_then(|encrypted| decrypt_token(encrypted))
-        .and_then(|json| serde_json::from_str(&json).ok());
+    // Twilio signs the public URL it called, query string included
+    let api_base_url =
+        std::env::var("API_BASE_URL").unwrap_or_else(|_| "http://localhost:8080".to_string());
+    let mut url = format!(
+        "{}/sink/trigger/twilio/{}",
+        api_base_url.trim_end_matches('/'),
+        event_id
+    );
+    if let Some(query) = query.filter(|query| !query.is_empty()) {
+        url = format!("{}?{}", url, query);
+    }
 
-    // Build dispatch request (async - no streaming)
-    let request = DispatchRequest {
-        run_id: run_id.clone(),
-        app_id: sink.app_id.clone(),
-        board_id: event.board_id.clone(),
-        board_version: event.board_version,
-        node_id: event.node_id.clone(),
-        event_json: Some(event_json),
-        payload,
-        user_id: "telegram_webhook".to_string(),
-        credentials_json,
-        jwt: executor_jwt,
-        callback_url,
-        token,        // PAT from sink (if configured)
-        oauth_tokens, // OAuth tokens from sink (if configured)
-        stream_state: false,
-        runtime_variables: None,
-        user_context: None, // Telegram webhook triggers don't have user context
-        profile: sink.profile_json.clone(),
+    let request = TwilioWebhookRequest {
+        url,
+        signature: headers
+            .get("X-Twilio-Signature")
+            .and_then(|v| v.to_str().ok())
+            .map(|s| s.to_string()),
+        body: body_bytes.to_vec(),
     };
 
-    // Create run record
-    let run = execution_run::ActiveModel {
-        id: Set(run_id.clone()),
-        board_id: Set(event.board_id.clone()),
-        version: Set(None),
-        event_id: Set(Some(sink.event_id.clone())),
-        node_id: Set(Some(event.id.clone())),
-        status: Set(RunStatus::Pending),
-        mode: Set(RunMode::Http),
-        log_level: Set(0),
-        input_payload_len: Set(input_payload_len),
-        input_payload_key: Set(None),
-        output_payload_len: Set(0),
-        error_message: Set(None),
-        progress: Set(0),
-        current_step: Set(None),
-        started_at: Set(None),
-        completed_at: Set(None),
-        expires_at: Set(Some(expires_at)),
-        user_id: Set(None),
-        app_id: Set(sink.app_id.clone()),
-        created_at: Set(chrono::Utc::now().naive_utc()),
-        updated_at: Set(chrono::Utc::now().naive_utc()),
-    };
+    if let Err(e) = request.verify_signature(auth_token) {
+        tracing::warn!("Rejected Twilio webhook for event {}: {}", event_id, e);
+        return Ok((StatusCode::FORBIDDEN, "Invalid signature").into_response());
+    }
 
-    tracing::info!(run_id = %run_id, "Dispatching Telegram webhook (async)");
+    let message = match request.parse_message() {
+        Ok(message) => message,
+        Err(e) => return Ok((StatusCode::BAD_REQUEST, e.to_string()).into_response()),
+    };
 
-    // Insert run record
-    run.insert(&state.db).await.map_err(|e| {
-        tracing::error!(error = %e, "Failed to create run record");
-        ApiError::internal_error(anyhow!("Failed to create run record"))
-    })?;
+    let payload = serde_json::to_value(&message)
+        .map_err(|e| ApiError::internal_error(anyhow!("Failed to serialize message: {}", e)))?;
 
-    // Dispatch async (fire and forget) - Telegram expects fast response
-    let dispatcher = state.dispatcher.clone();
-    let run_id_for_log = run_id.clone();
+    // Twilio expects an answer within 15 seconds, so the run is dispatched in the background
     tokio::spawn(async move {
-        if let Err(e) = dispatcher.dispatch_async(request).await {
-            tracing::error!(run_id = %run_id_for_log, error = %e, "Telegram webhook dispatch failed");
+        let result = trigger_event(
+            &state,
+            TriggerEventInput {
+                event_id: event_id.clone(),
+                payload: Some(payload),
+                user_id: Some("twilio_webhook".to_string()),
+            },
+        )
+        .await;
+        if let Err(e) = result {
+            tracing::error!(event_id = %event_id, error = %e, "Twilio webhook dispatch failed");
         }
     });
 
-    // Return immediately - Telegram expects fast acknowledgement
     Ok((
         StatusCode::OK,
-        Json(TriggerResponse {
-            triggered: true,
-            run_id: Some(run_id),
-            message: "Webhook received and processing".to_string(),
-        }),
+        [(axum::http::header::CONTENT_TYPE, "text/xml")],
+        EMPTY_TWIML,
     )
         .into_response())
 }
 
+/// POST /sink/trigger/github/{event_id}
+/// GitHub repository webhook - verified with `X-Hub-Signature-256` against the webhook secret
+/// of the sink and filtered by `X-GitHub-Event` against the event config before the run is
+/// dispatched.
+#[utoipa::path(
+    post,
+    path = "/sink/trigger/github/{event_id}",
+    tag = "sink",
+    params(
+        ("event_id" = String, Path, description = "Event ID")
+    ),
+    responses(
+        (status = 200, description = "Delivery accepted, triggered is false for filtered events"),
+        (status = 400, description = "Invalid sink config or delivery body"),
+        (status = 401, description = "Invalid or missing signature, or no webhook secret configured"),
+        (status = 404, description = "Webhook not found or inactive")
+    )
+)]
+#[tracing::instrument(
+    name = "POST /sink/trigger/github/{event_id}",
+    skip(state, headers, body)
+)]
+pub async fn trigger_github(
+    State(state): State<AppState>,
+    Path(event_id): Path<String>,
+    headers: HeaderMap,
+    body: Body,
+) -> Result<Response, ApiError> {
+    let sink = event_sink::Entity::find()
+        .filter(event_sink::Column::EventId.eq(&event_id))
+        .filter(event_sink::Column::Active.eq(true))
+        .filter(event_sink::Column::SinkType.eq("github"))
+        .one(&state.db)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            ApiError::internal_error(anyhow!("Database error"))
+        })?;
+
+    let Some(sink) = sink else {
+        tracing::warn!("No active GitHub sink found for event {}", event_id);
+        return Ok((
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({
+                "error": "Webhook not found or inactive"
+            })),
+        )
+            .into_response());
+    };
+    let Some(secret) = sink
+        .webhook_secret
+        .clone()
+        .filter(|secret| !secret.is_empty())
+    else {
+        tracing::warn!("GitHub sink for event {} has no webhook secret", event_id);
+        return Ok((
+            StatusCode::UNAUTHORIZED,
+            Json(serde_json::json!({
+                "error": "Webhook has no secret configured"
+            })),
+        )
+            .into_response());
+    };
+
+    let event = get_event_from_db(&state.db, &sink.event_id)
+        .await
+        .map_err(|e| ApiError::internal_error(anyhow!("Failed to get event: {}", e)))?;
+    let registration = sink_registration(&sink, &event)
+        .map_err(|_| ApiError::bad_request("Invalid GitHub sink config"))?;
+
+    // GitHub caps deliveries at 25MB
+    let body_bytes = axum::body::to_bytes(body, 25 * 1024 * 1024)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to read body: {}", e);
+            ApiError::bad_request("Failed to read request body")
+        })?;
+
+    let ctx = sink_context(&state, "github_webhook");
+    Ok(github_delivery_response(&ctx, &registration, &secret, &headers, body_bytes.to_vec()).await)
+}
+
+/// Hands a GitHub delivery to [`flow_like_sinks::github::GitHubSink::handle_webhook`]
+/// and maps the outcome to the HTTP answer GitHub gets
+async fn github_delivery_response<E: flow_like_sinks::Executor>(
+    ctx: &flow_like_sinks::SinkContext<E>,
+    registration: &flow_like_sinks::SinkRegistration,
+    secret: &str,
+    headers: &HeaderMap,
+    body: Vec<u8>,
+) -> Response {
+    use flow_like_sinks::github::{GitHubSink, GitHubWebhookRequest};
+
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(|s| s.to_string())
+    };
+    let request = GitHubWebhookRequest {
+        event: header("X-GitHub-Event").unwrap_or_default(),
+        delivery: header("X-GitHub-Delivery"),
+        signature: header("X-Hub-Signature-256"),
+        body,
+    };
+
+    match GitHubSink::new()
+        .handle_webhook(ctx, registration, secret, &request)
+        .await
+    {
+        Ok(response) => (
+            StatusCode::OK,
+            Json(TriggerResponse {
+                triggered: response.triggered,
+                message: if response.triggered {
+                    "Webhook received and processing".to_string()
+                } else {
+                    format!("Ignored '{}' event", request.event)
+                },
+                run_id: response.run_id,
+            }),
+        )
+            .into_response(),
+        Err(e) => {
+            tracing::warn!(
+                "Rejected GitHub delivery {} for event {}: {}",
+                request.delivery.as_deref().unwrap_or("-"),
+                registration.event_id,
+                e
+            );
+            let status = match &e {
+                flow_like_sinks::SinkError::AuthFailed(_) => StatusCode::UNAUTHORIZED,
+                _ => StatusCode::from_u16(e.status_code())
+                    .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+            };
+            (
+                status,
+                Json(serde_json::json!({
+                    "error": e.to_string()
+                })),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Starts the runs of [`flow_like_sinks::SinkContext::dispatch`]. Runs are only queued, the
+/// progress callback of the run frees its throttle slot.
+struct TriggerEventExecutor {
+    state: AppState,
+    user_id: String,
+}
+
+#[flow_like_types::async_trait]
+impl flow_like_sinks::Executor for TriggerEventExecutor {
+    async fn execute_event(
+        &self,
+        _app_id: &str,
+        _board_id: &str,
+        event_id: &str,
+        payload: Option<serde_json::Value>,
+        _personal_access_token: Option<&str>,
+    ) -> flow_like_sinks::SinkResult<String> {
+        use flow_like_sinks::SinkError;
+
+        let response = start_run(
+            &self.state,
+            TriggerEventInput {
+                event_id: event_id.to_string(),
+                payload,
+                user_id: Some(self.user_id.clone()),
+            },
+        )
+        .await
+        .map_err(|e| SinkError::ExecutionFailed(e.to_string()))?;
+
+        match response.run_id {
+            Some(run_id) if response.triggered => Ok(run_id),
+            _ => Err(SinkError::ExecutionFailed(response.message)),
+        }
+    }
+
+    fn returns_before_completion(&self) -> bool {
+        true
+    }
+}
+
 /// Verify Discord Ed25519 signature
 /// Discord sends: X-Signature-Ed25519 (signature) and X-Signature-Timestamp (timestamp)
 /// The message to verify is: timestamp + body
@@ -925,8 +1320,6 @@ pub async fn trigger_discord(
     headers: HeaderMap,
     body: Body,
 ) -> Result<Response, ApiError> {
-    use crate::routes::app::events::db::decrypt_token;
-
     tracing::info!("Discord webhook trigger for event {}", event_id);
 
     // Read body first (needed for signature verification)
@@ -1020,129 +1413,23 @@ pub async fn trigger_discord(
             .into_response());
     }
 
-    // For other interaction types (commands, components, etc.), dispatch async
-    // Get the event from database
-    let event = get_event_from_db(&state.db, &sink.event_id)
-        .await
-        .map_err(|e| ApiError::internal_error(anyhow!("Failed to get event: {}", e)))?;
-
-    // Check JWT configured
-    if !is_jwt_configured() {
-        return Err(ApiError::internal_error(anyhow!(
-            "Execution JWT signing not configured"
-        )));
-    }
-
-    // Create run
-    let run_id = create_id();
-    let expires_at = chrono::Utc::now().naive_utc() + chrono::Duration::hours(24);
-
-    let input_payload_len = serde_json::to_string(&interaction)
-        .map(|s| s.len() as i64)
-        .unwrap_or(0);
-
-    let event_json = serde_json::to_string(&event)
-        .map_err(|e| ApiError::internal_error(anyhow!("Failed to serialize event: {}", e)))?;
-
-    // Get credentials
-    let credentials = state
-        .master_credentials()
-        .await
-        .map_err(|e| ApiError::internal_error(anyhow!("Failed to get credentials: {}", e)))?;
-
-    let shared_credentials = credentials.into_shared_credentials();
-    let credentials_json = serde_json::to_string(&shared_credentials)
-        .map_err(|e| ApiError::internal_error(anyhow!("Failed to serialize credentials: {}", e)))?;
-
-    let callback_url =
-        std::env::var("API_BASE_URL").unwrap_or_else(|_| "http://localhost:8080".to_string());
-
-    // Sign JWT
-    let executor_jwt = sign_execution_jwt(ExecutionJwtParams {
-        user_id: "discord_webhook".to_string(),
-        run_id: run_id.clone(),
-        app_id: sink.app_id.clone(),
-        board_id: event.board_id.clone(),
-        event_id: Some(sink.event_id.clone()),
-        callback_url: callback_url.clone(),
-        token_type: TokenType::Executor,
-        ttl_seconds: Some(24 * 60 * 60),
-    })
-    .map_err(|e| ApiError::internal_error(anyhow!("Failed to sign JWT: {}", e)))?;
-
-    // Decrypt PAT from sink if available
-    let token = sink
-        .pat_encrypted
-        .as_ref()
-        .and_then(|encrypted| decrypt_token(encrypted));
-
-    // Decrypt OAuth tokens from sink if available
-    let oauth_tokens: Option<std::collections::HashMap<String, serde_json::Value>> = sink
-        .oauth_tokens_encrypted
-        .as_ref()
-        .and_then(|encrypted| decrypt_token(encrypted))
-        .and_then(|json| serde_json::from_str(&json).ok());
-
-    // Build dispatch request (async - no streaming)
-    let request = DispatchRequest {
-        run_id: run_id.clone(),
-        app_id: sink.app_id.clone(),
-        board_id: event.board_id.clone(),
-        board_version: event.board_version,
-        node_id: event.node_id.clone(),
-        event_json: Some(event_json),
-        payload: Some(interaction.clone()),
-        user_id: "discord_webhook".to_string(),
-        credentials_json,
-        jwt: executor_jwt,
-        callback_url,
-        token,        // PAT from sink (if configured)
-        oauth_tokens, // OAuth tokens from sink (if configured)
-        stream_state: false,
-        runtime_variables: None,
-        user_context: None, // Discord webhook triggers don't have user context
-        profile: sink.profile_json.clone(),
-    };
-
-    // Create run record
-    let run = execution_run::ActiveModel {
-        id: Set(run_id.clone()),
-        board_id: Set(event.board_id.clone()),
-        version: Set(None),
-        event_id: Set(Some(sink.event_id.clone())),
-        node_id: Set(Some(event.id.clone())),
-        status: Set(RunStatus::Pending),
-        mode: Set(RunMode::Http),
-        log_level: Set(0),
-        input_payload_len: Set(input_payload_len),
-        input_payload_key: Set(None),
-        output_payload_len: Set(0),
-        error_message: Set(None),
-        progress: Set(0),
-        current_step: Set(None),
-        started_at: Set(None),
-        completed_at: Set(None),
-        expires_at: Set(Some(expires_at)),
-        user_id: Set(None),
-        app_id: Set(sink.app_id.clone()),
-        created_at: Set(chrono::Utc::now().naive_utc()),
-        updated_at: Set(chrono::Utc::now().naive_utc()),
-    };
-
-    tracing::info!(run_id = %run_id, "Dispatching Discord webhook (async)");
-
-    // Insert run record
-    run.insert(&state.db).await.map_err(|e| {
-        tracing::error!(error = %e, "Failed to create run record");
-        ApiError::internal_error(anyhow!("Failed to create run record"))
-    })?;
+    // For other interaction types (commands, components, etc.), dispatch async.
+    // Discord expects a response within 3 seconds, so the run starts in the background
+    tracing::info!(event_id = %sink.event_id, "Dispatching Discord webhook (async)");
 
-    // Dispatch async (fire and forget) - Discord expects response within 3 seconds
-    let dispatcher = state.dispatcher.clone();
-    let run_id_for_log = run_id.clone();
+    let event_id = sink.event_id.clone();
     tokio::spawn(async move {
-        if let Err(e) = dispatcher.dispatch_async(request).await {
-            tracing::error!(run_id = %run_id_for_log, error = %e, "Discord webhook dispatch failed");
+        let result = trigger_event(
+            &state,
+            TriggerEventInput {
+                event_id: event_id.clone(),
+                payload: Some(interaction),
+                user_id: Some("discord_webhook".to_string()),
+            },
+        )
+        .await;
+        if let Err(e) = result {
+            tracing::error!(event_id = %event_id, error = %e, "Discord webhook dispatch failed");
         }
     });
 
@@ -1261,6 +1548,8 @@ async fn is_token_revoked(db: &sea_orm::DatabaseConnection, jti: &str) -> Result
 /// - Cron service gets JWT with `sink_types: ["cron"]`
 /// - Discord bot gets JWT with `sink_types: ["discord"]`
 /// - Telegram bot gets JWT with `sink_types: ["telegram"]`
+/// - S3 notification consumers get JWT with `sink_types: ["s3"]`, the payload is the
+///   SQS or SNS notification and goes through [`trigger_s3_notification`]
 ///
 /// If a service is compromised, it can only trigger events of its own type.
 /// Tokens can be individually revoked via /admin/sinks/{jti}.
@@ -1359,6 +1648,28 @@ pub async fn trigger_service(
         )));
     }
 
+    // Bucket notifications run the flow once per object instead of once per request
+    if sink.sink_type == "s3" {
+        let notification = request.payload.unwrap_or_default();
+        return Ok(Json(
+            match trigger_s3_notification(&state, &request.event_id, &notification).await {
+                Ok(results) => ServiceTriggerResponse {
+                    success: results.iter().all(|result| result.triggered),
+                    run_id: results.iter().find_map(|result| result.run_id.clone()),
+                    error: results
+                        .iter()
+                        .find(|result| !result.triggered)
+                        .map(|result| result.message.clone()),
+                },
+                Err(e) => ServiceTriggerResponse {
+                    success: false,
+                    run_id: None,
+                    error: Some(e.to_string()),
+                },
+            },
+        ));
+    }
+
     // Get the event to access its config for additional payload
     let event = get_event_from_db(&state.db, &request.event_id)
         .await
@@ -1713,3 +2024,267 @@ fn extract_sse_event(buffer: &mut Vec<u8>) -> Option<ParsedSseEvent> {
     }
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{Router, body::Bytes, http::Request as HttpRequest, routing::post};
+    use flow_like_sinks::{
+        Executor, SinkContext, SinkExecution, SinkRegistration, SinkResult, SinkType,
+        throttle::Throttles,
+    };
+    use hmac::{Hmac, Mac};
+    use sha2::Sha256;
+    use std::sync::Mutex;
+    use tower::ServiceExt;
+
+    const SECRET: &str = "It's a Secret to Everybody";
+
+    #[derive(Default)]
+    struct RecordingExecutor {
+        payloads: Mutex<Vec<Option<serde_json::Value>>>,
+    }
+
+    #[flow_like_types::async_trait]
+    impl Executor for RecordingExecutor {
+        async fn execute_event(
+            &self,
+            _app_id: &str,
+            _board_id: &str,
+            _event_id: &str,
+            payload: Option<serde_json::Value>,
+            _personal_access_token: Option<&str>,
+        ) -> SinkResult<String> {
+            let mut payloads = self.payloads.lock().unwrap();
+            payloads.push(payload);
+            Ok(format!("run-{}", payloads.len()))
+        }
+
+        fn returns_before_completion(&self) -> bool {
+            true
+        }
+    }
+
+    fn router(events: &[&str], executor: Arc<RecordingExecutor>) -> Router {
+        let config = serde_json::json!({
+            "repository": "octo-org/hello-world",
+            "events": events,
+        });
+        router_with(config, SECRET, executor, Arc::new(Throttles::new()))
+    }
+
+    /// Every delivery gets its own context like in `trigger_github`, the throttles are shared
+    fn router_with(
+        config: serde_json::Value,
+        secret: &str,
+        executor: Arc<RecordingExecutor>,
+        throttles: Arc<Throttles>,
+    ) -> Router {
+        let registration = Arc::new(SinkRegistration {
+            id: "sink-1".to_string(),
+            event_id: "event-1".to_string(),
+            board_id: "board-1".to_string(),
+            app_id: "app-1".to_string(),
+            sink_type: SinkType::GitHub,
+            config,
+            execution: SinkExecution::Remote,
+            active: true,
+            auth_token: None,
+            path: None,
+            method: None,
+            cron_expression: None,
+            default_payload: None,
+            personal_access_token: None,
+            oauth_tokens: None,
+        });
+        let secret = secret.to_string();
+
+        Router::new().route(
+            "/sink/trigger/github/{event_id}",
+            post(move |headers: HeaderMap, body: Bytes| {
+                let ctx = SinkContext::with_throttles(executor.clone(), throttles.clone());
+                let registration = registration.clone();
+                let secret = secret.clone();
+                async move {
+                    github_delivery_response(&ctx, &registration, &secret, &headers, body.to_vec())
+                        .await
+                }
+            }),
+        )
+    }
+
+    fn push_request(secret: &str) -> HttpRequest<Body> {
+        let body = serde_json::to_vec(&serde_json::json!({
+            "ref": "refs/heads/main",
+            "repository": {
+                "id": 1296269,
+                "name": "hello-world",
+                "full_name": "octo-org/hello-world",
+                "private": false,
+                "html_url": "https://github.com/octo-org/hello-world",
+                "default_branch": "main"
+            },
+            "sender": {
+                "id": 583231,
+                "login": "octocat",
+                "html_url": "https://github.com/octocat"
+            },
+            "commits": []
+        }))
+        .unwrap();
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(&body);
+        let signature = format!("sha256={}", hex::encode(mac.finalize().into_bytes()));
+
+        HttpRequest::builder()
+            .method("POST")
+            .uri("/sink/trigger/github/event-1")
+            .header("X-GitHub-Event", "push")
+            .header("X-GitHub-Delivery", "72d3162e-cc78-11e3-81ab-4c9367dc0958")
+            .header("X-Hub-Signature-256", signature)
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    async fn json_body(response: Response) -> serde_json::Value {
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_github_push_event_triggers_run() {
+        let executor = Arc::new(RecordingExecutor::default());
+        let response = router(&["push"], executor.clone())
+            .oneshot(push_request(SECRET))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = json_body(response).await;
+        assert_eq!(body["triggered"], true);
+        assert_eq!(body["run_id"], "run-1");
+
+        let payloads = executor.payloads.lock().unwrap();
+        assert_eq!(payloads.len(), 1);
+        let payload = payloads[0].as_ref().unwrap();
+        assert_eq!(payload["event"], "push");
+        assert_eq!(payload["delivery"], "72d3162e-cc78-11e3-81ab-4c9367dc0958");
+    }
+
+    #[tokio::test]
+    async fn test_github_filtered_event_does_not_trigger() {
+        let executor = Arc::new(RecordingExecutor::default());
+        let response = router(&["pull_request"], executor.clone())
+            .oneshot(push_request(SECRET))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = json_body(response).await;
+        assert_eq!(body["triggered"], false);
+        assert!(body["run_id"].is_null());
+        assert!(executor.payloads.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_github_bad_signature_is_unauthorized() {
+        let executor = Arc::new(RecordingExecutor::default());
+        let response = router(&[], executor.clone())
+            .oneshot(push_request("wrong secret"))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert!(executor.payloads.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_github_empty_secret_is_unauthorized() {
+        let executor = Arc::new(RecordingExecutor::default());
+        let config = serde_json::json!({ "events": ["push"] });
+        let response = router_with(config, "", executor.clone(), Arc::new(Throttles::new()))
+            .oneshot(push_request(""))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert!(executor.payloads.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_github_deliveries_share_the_concurrency_cap() {
+        let executor = Arc::new(RecordingExecutor::default());
+        let throttles = Arc::new(Throttles::new());
+        let config = serde_json::json!({
+            "events": ["push"],
+            "max_concurrent": 2,
+        });
+        let app = router_with(config, SECRET, executor.clone(), throttles.clone());
+
+        let responses =
+            futures::future::join_all((0..3).map(|_| app.clone().oneshot(push_request(SECRET))))
+                .await;
+        let mut statuses: Vec<StatusCode> = responses
+            .into_iter()
+            .map(|response| response.unwrap().status())
+            .collect();
+        statuses.sort();
+        assert_eq!(
+            statuses,
+            vec![
+                StatusCode::OK,
+                StatusCode::OK,
+                StatusCode::TOO_MANY_REQUESTS
+            ]
+        );
+        assert_eq!(executor.payloads.lock().unwrap().len(), 2);
+
+        // The queued runs keep their slots until the progress callback reports them finished
+        let response = app.clone().oneshot(push_request(SECRET)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+        assert!(throttles.release("run-1"));
+        let response = app.clone().oneshot(push_request(SECRET)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = app.oneshot(push_request(SECRET)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    fn s3_notification() -> serde_json::Value {
+        serde_json::json!({
+            "Records": [{
+                "eventName": "ObjectCreated:Put",
+                "eventTime": "2024-05-01T12:00:00.000Z",
+                "s3": {
+                    "bucket": { "name": "uploads" },
+                    "object": { "key": "incoming/report.csv", "size": 1024 }
+                }
+            }]
+        })
+    }
+
+    #[test]
+    fn test_s3_notification_passes_config_filters() {
+        let config = br#"{ "bucket": "uploads", "suffix": ".csv" }"#;
+        let objects = s3_objects_to_trigger(config, &s3_notification()).unwrap();
+        assert_eq!(objects.len(), 1);
+        assert_eq!(objects[0].key, "incoming/report.csv");
+
+        let config = br#"{ "suffix": ".json" }"#;
+        assert!(
+            s3_objects_to_trigger(config, &s3_notification())
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_s3_notification_with_invalid_config_does_not_trigger() {
+        for config in [&b"{ \"bucket\": 42 }"[..], &b"not json"[..]] {
+            assert!(s3_objects_to_trigger(config, &s3_notification()).is_err());
+        }
+    }
+}
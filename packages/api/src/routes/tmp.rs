@@ -7,11 +7,12 @@ use axum::{
     routing::get,
 };
 use chrono::{Duration as ChronoDuration, Utc};
-use flow_like_storage::Path as FLPath;
+use flow_like_storage::{Path as FLPath, files::store::MAX_PRESIGNED_EXPIRY};
 use flow_like_types::tokio::try_join;
 use flow_like_types::{
     create_id,
     mime_guess::{self, mime},
+    reqwest::Method,
 };
 use mime::Mime;
 use serde::{Deserialize, Serialize};
@@ -50,8 +51,29 @@ pub struct ExtensionParams {
     pub filename: Option<String>,
 }
 
+#[derive(Clone, Deserialize, Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PresignedUrlResponse {
+    pub key: String,
+    pub method: String,
+    pub url: String,
+    pub expires_at: String,
+}
+
+#[derive(Deserialize, Debug, ToSchema, utoipa::IntoParams)]
+pub struct PresignParams {
+    /// Key of a temporary file as returned by `GET /tmp`. Must belong to the caller.
+    pub key: String,
+    /// PUT or POST for an upload URL, GET or HEAD for a download URL. Defaults to GET.
+    pub method: Option<String>,
+    /// Optional lifetime in seconds (defaults to 15 minutes, capped at 7 days).
+    pub ttl_secs: Option<u64>,
+}
+
 pub fn routes() -> Router<AppState> {
-    Router::new().route("/", get(get_temporary_upload))
+    Router::new()
+        .route("/", get(get_temporary_upload))
+        .route("/presign", get(get_presigned_url))
 }
 
 #[utoipa::path(
@@ -146,6 +168,80 @@ pub async fn get_temporary_upload(
     Ok(Json(response))
 }
 
+#[utoipa::path(
+    get,
+    path = "/tmp/presign",
+    tag = "tmp",
+    params(PresignParams),
+    responses(
+        (status = 200, description = "Presigned URL generated successfully", body = PresignedUrlResponse),
+        (status = 400, description = "Invalid method or expiry"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Key belongs to another user"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[tracing::instrument(
+    name = "GET /tmp/presign",
+    skip(state, user),
+    fields(user_sub = tracing::field::Empty, key = tracing::field::Empty, method = tracing::field::Empty)
+)]
+pub async fn get_presigned_url(
+    State(state): State<AppState>,
+    Extension(user): Extension<AppUser>,
+    Query(params): Query<PresignParams>,
+) -> Result<Json<PresignedUrlResponse>, ApiError> {
+    let sub = user.sub()?;
+
+    let key = params.key.trim().trim_start_matches('/');
+    if !key.starts_with(&format!("tmp/user/{sub}/")) || key.split('/').any(|s| s == "..") {
+        return Err(ApiError::forbidden("Key does not belong to the caller"));
+    }
+
+    let method = params
+        .method
+        .as_deref()
+        .unwrap_or("GET")
+        .to_ascii_uppercase()
+        .parse::<Method>()
+        .map_err(|_| ApiError::bad_request("Invalid HTTP method"))?;
+    let ttl = params
+        .ttl_secs
+        .unwrap_or(UPLOAD_TTL_SECS)
+        .clamp(1, MAX_PRESIGNED_EXPIRY.as_secs());
+
+    let master = state.master_credentials().await?;
+    let store = master.to_store(false).await?;
+    let path = FLPath::from(key);
+    let expires_after = Duration::from_secs(ttl);
+
+    let url = if method == Method::PUT || method == Method::POST {
+        store
+            .generate_presigned_upload_url(&path, expires_after, method.clone())
+            .await?
+    } else if method == Method::GET || method == Method::HEAD {
+        store
+            .generate_presigned_download_url(&path, expires_after, method.clone())
+            .await?
+    } else {
+        return Err(ApiError::bad_request(
+            "Method must be PUT, POST, GET or HEAD",
+        ));
+    };
+
+    let expires_at = (Utc::now() + ChronoDuration::seconds(ttl as i64)).to_rfc3339();
+
+    Ok(Json(PresignedUrlResponse {
+        key: key.to_string(),
+        method: method.to_string(),
+        url: url.to_string(),
+        expires_at,
+    }))
+}
+
 fn sanitize_ext(input: Option<&str>) -> Option<String> {
     let mut s = input?.trim().trim_start_matches('.').to_ascii_lowercase();
     if s.is_empty() || s.len() > 16 || !s.chars().all(|c| c.is_ascii_alphanumeric()) {
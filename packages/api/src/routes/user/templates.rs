@@ -19,6 +19,7 @@ use sea_orm::{
 #[utoipa::path(
     get,
     path = "/user/templates",
+    operation_id = "get_user_templates",
     tag = "user",
     params(
         ("language" = Option<String>, Query, description = "Language code"),
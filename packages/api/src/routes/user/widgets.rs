@@ -21,6 +21,7 @@ use sea_orm::{
 #[utoipa::path(
     get,
     path = "/user/widgets",
+    operation_id = "get_user_widgets",
     tag = "user",
     params(
         ("language" = Option<String>, Query, description = "Language code"),
@@ -77,6 +77,11 @@ pub struct State {
     pub wasm_registry: Option<Arc<ServerRegistry>>,
     /// Sink scheduler for cron events (AWS EventBridge, K8s CronJobs, or in-memory)
     pub sink_scheduler: Option<Arc<dyn flow_like_sinks::SchedulerBackend>>,
+    /// Rate limits and concurrency caps of the sinks, shared by all trigger routes
+    pub sink_throttles: Arc<flow_like_sinks::throttle::Throttles>,
+    /// Ids of runs that received events or a status update on this instance,
+    /// wakes up the WebSocket subscribers of the run
+    pub run_events: flow_like_types::tokio::sync::broadcast::Sender<String>,
 }
 
 impl State {
@@ -256,6 +261,8 @@ impl State {
                 .build(),
             wasm_registry,
             sink_scheduler,
+            sink_throttles: Arc::new(flow_like_sinks::throttle::Throttles::new()),
+            run_events: flow_like_types::tokio::sync::broadcast::channel(1024).0,
         }
     }
 
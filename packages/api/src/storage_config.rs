@@ -3,12 +3,10 @@
 //! This module provides a unified way to configure and create FlowLikeStore instances
 //! from environment variables across all deployment backends.
 
-use flow_like::flow_like_storage::files::store::FlowLikeStore;
-use flow_like_storage::object_store::{
-    aws::AmazonS3Builder,
-    azure::{AzureConfigKey, MicrosoftAzureBuilder},
-    gcp::GoogleCloudStorageBuilder,
+use flow_like::flow_like_storage::files::store::{
+    AzureCredential, FlowLikeStore, GoogleCredential,
 };
+use flow_like_storage::object_store::aws::AmazonS3Builder;
 use flow_like_types::Result;
 use serde::{Deserialize, Serialize};
 use std::{fmt::Display, sync::Arc};
@@ -112,10 +110,16 @@ impl S3Config {
 }
 
 /// Azure Blob Storage configuration
+///
+/// Authentication options:
+/// 1. Account key (AZURE_STORAGE_ACCOUNT_KEY)
+/// 2. Managed identity when no key is set (AZURE_CLIENT_ID selects a user assigned identity)
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct AzureConfig {
     pub account: String,
-    pub access_key: String,
+    pub access_key: Option<String>,
+    pub client_id: Option<String>,
+    pub msi_endpoint: Option<String>,
 }
 
 impl AzureConfig {
@@ -124,26 +128,43 @@ impl AzureConfig {
             account: std::env::var("AZURE_STORAGE_ACCOUNT_NAME")
                 .map_err(|_| flow_like_types::anyhow!("AZURE_STORAGE_ACCOUNT_NAME not set"))?,
             access_key: std::env::var("AZURE_STORAGE_ACCOUNT_KEY")
-                .map_err(|_| flow_like_types::anyhow!("AZURE_STORAGE_ACCOUNT_KEY not set"))?,
+                .ok()
+                .filter(|key| !key.is_empty()),
+            client_id: std::env::var("AZURE_CLIENT_ID")
+                .ok()
+                .filter(|id| !id.is_empty()),
+            msi_endpoint: std::env::var("AZURE_MSI_ENDPOINT")
+                .ok()
+                .filter(|endpoint| !endpoint.is_empty()),
         })
     }
 
+    pub fn credential(&self) -> AzureCredential {
+        match &self.access_key {
+            Some(key) => AzureCredential::AccessKey(key.clone()),
+            None => AzureCredential::ManagedIdentity {
+                client_id: self.client_id.clone(),
+                msi_endpoint: self.msi_endpoint.clone(),
+            },
+        }
+    }
+
     pub fn build_store(&self, container: &str) -> Result<FlowLikeStore> {
-        let store = MicrosoftAzureBuilder::new()
-            .with_account(&self.account)
-            .with_container_name(container)
-            .with_config(AzureConfigKey::AccessKey, &self.access_key)
-            .build()
-            .map_err(|e| flow_like_types::anyhow!("Failed to build Azure store: {}", e))?;
-        Ok(FlowLikeStore::Azure(Arc::new(store)))
+        FlowLikeStore::azure(&self.account, container, self.credential())
     }
 }
 
 /// GCP Cloud Storage configuration
+///
+/// Authentication options:
+/// 1. Service account key JSON (GOOGLE_APPLICATION_CREDENTIALS_JSON)
+/// 2. Service account key file (GOOGLE_SERVICE_ACCOUNT_PATH)
+/// 3. Workload Identity / application default credentials when neither is set
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct GcpConfig {
     pub project_id: String,
     pub credentials_json: Option<String>,
+    pub credentials_path: Option<String>,
 }
 
 impl GcpConfig {
@@ -151,21 +172,25 @@ impl GcpConfig {
         Ok(GcpConfig {
             project_id: std::env::var("GCP_PROJECT_ID")
                 .map_err(|_| flow_like_types::anyhow!("GCP_PROJECT_ID not set"))?,
-            credentials_json: std::env::var("GOOGLE_APPLICATION_CREDENTIALS_JSON").ok(),
+            credentials_json: std::env::var("GOOGLE_APPLICATION_CREDENTIALS_JSON")
+                .ok()
+                .filter(|json| !json.is_empty()),
+            credentials_path: std::env::var("GOOGLE_SERVICE_ACCOUNT_PATH")
+                .ok()
+                .filter(|path| !path.is_empty()),
         })
     }
 
-    pub fn build_store(&self, bucket: &str) -> Result<FlowLikeStore> {
-        let mut builder = GoogleCloudStorageBuilder::new().with_bucket_name(bucket);
-
-        if let Some(creds) = &self.credentials_json {
-            builder = builder.with_service_account_key(creds);
+    pub fn credential(&self) -> GoogleCredential {
+        match (&self.credentials_json, &self.credentials_path) {
+            (Some(json), _) => GoogleCredential::ServiceAccountKey(json.clone()),
+            (None, Some(path)) => GoogleCredential::ServiceAccountPath(path.clone()),
+            (None, None) => GoogleCredential::WorkloadIdentity,
         }
+    }
 
-        let store = builder
-            .build()
-            .map_err(|e| flow_like_types::anyhow!("Failed to build GCS store: {}", e))?;
-        Ok(FlowLikeStore::Google(Arc::new(store)))
+    pub fn build_store(&self, bucket: &str) -> Result<FlowLikeStore> {
+        FlowLikeStore::google(bucket, self.credential())
     }
 }
 
@@ -340,4 +365,55 @@ mod tests {
         assert_eq!(StorageProvider::Azure.to_string(), "azure");
         assert_eq!(StorageProvider::Gcp.to_string(), "gcp");
     }
+
+    #[test]
+    fn test_gcp_credential_selection() {
+        let mut config = GcpConfig {
+            project_id: "flow-like".to_string(),
+            credentials_json: None,
+            credentials_path: Some("/var/secrets/gcs.json".to_string()),
+        };
+        assert_eq!(
+            config.credential(),
+            GoogleCredential::ServiceAccountPath("/var/secrets/gcs.json".to_string())
+        );
+
+        config.credentials_path = None;
+        assert_eq!(config.credential(), GoogleCredential::WorkloadIdentity);
+        assert!(matches!(
+            StorageConfig::Gcp(config).build_store("content"),
+            Ok(FlowLikeStore::Google(_))
+        ));
+    }
+
+    #[test]
+    fn test_azure_credential_selection() {
+        let mut config = AzureConfig {
+            account: "flowlike".to_string(),
+            access_key: Some("ZmxvdyBsaWtlIHRlc3Qga2V5".to_string()),
+            client_id: Some("client".to_string()),
+            msi_endpoint: None,
+        };
+        assert_eq!(
+            config.credential(),
+            AzureCredential::AccessKey("ZmxvdyBsaWtlIHRlc3Qga2V5".to_string())
+        );
+        assert!(matches!(
+            config.build_store("content"),
+            Ok(FlowLikeStore::Azure(_))
+        ));
+
+        config.access_key = None;
+        assert_eq!(
+            config.credential(),
+            AzureCredential::ManagedIdentity {
+                client_id: Some("client".to_string()),
+                msi_endpoint: None,
+            }
+        );
+        assert!(matches!(
+            StorageConfig::Azure(config).build_store("content"),
+            Ok(FlowLikeStore::Azure(_))
+        ));
+    }
 }
@@ -66,7 +66,7 @@ pub fn register_node(attr: TokenStream, item: TokenStream) -> TokenStream {
 
         ::inventory::submit! {
             #[allow(clippy::redundant_closure)]
-            crate::NodeConstructor::new(|| {
+            crate::NodeConstructor::for_package(env!("CARGO_PKG_NAME"), || {
                 ::std::sync::Arc::new(#struct_name::default()) as ::std::sync::Arc<dyn ::flow_like::flow::node::NodeLogic>
             })
         }
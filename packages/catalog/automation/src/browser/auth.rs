@@ -15,7 +15,7 @@ pub struct BasicAuthCredentials {
     pub password: String,
 }
 
-#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug)]
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq)]
 pub struct CookieData {
     pub name: String,
     pub value: String,
@@ -214,8 +214,6 @@ impl NodeLogic for BrowserSaveCookiesNode {
 
     #[cfg(feature = "execute")]
     async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
-        use thirtyfour::cookie::SameSite;
-
         context.deactivate_exec_pin("exec_out").await?;
 
         let session: AutomationSession = context.evaluate_pin("session").await?;
@@ -229,20 +227,7 @@ impl NodeLogic for BrowserSaveCookiesNode {
 
         let cookie_data: Vec<CookieData> = cookies
             .into_iter()
-            .map(|c| CookieData {
-                name: c.name.clone(),
-                value: c.value.clone(),
-                domain: c.domain.clone(),
-                path: c.path.clone(),
-                secure: c.secure,
-                http_only: None, // thirtyfour Cookie doesn't have http_only field
-                same_site: c.same_site.map(|ss| match ss {
-                    SameSite::Strict => "Strict".to_string(),
-                    SameSite::Lax => "Lax".to_string(),
-                    SameSite::None => "None".to_string(),
-                }),
-                expiry: c.expiry,
-            })
+            .map(super::cookies::from_webdriver_cookie)
             .collect();
 
         let cookie_json = flow_like_types::json::to_string_pretty(&cookie_data)
@@ -354,8 +339,6 @@ impl NodeLogic for BrowserLoadCookiesNode {
 
     #[cfg(feature = "execute")]
     async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
-        use thirtyfour::cookie::{Cookie, SameSite};
-
         context.deactivate_exec_pin("exec_out").await?;
         context.deactivate_exec_pin("exec_error").await?;
 
@@ -387,31 +370,9 @@ impl NodeLogic for BrowserLoadCookiesNode {
         let driver = session.get_browser_driver(context).await?;
 
         for cd in &cookie_data {
-            let mut cookie = Cookie::new(&cd.name, &cd.value);
-
-            if let Some(domain) = &cd.domain {
-                cookie.set_domain(domain.clone());
-            }
-            if let Some(path) = &cd.path {
-                cookie.set_path(path.clone());
-            }
-            if let Some(secure) = cd.secure {
-                cookie.set_secure(secure);
-            }
-            // Note: http_only is not directly supported by thirtyfour Cookie
-            if let Some(same_site) = &cd.same_site {
-                let ss = match same_site.as_str() {
-                    "Strict" => SameSite::Strict,
-                    "Lax" => SameSite::Lax,
-                    _ => SameSite::None,
-                };
-                cookie.set_same_site(ss);
-            }
-            if let Some(expiry) = cd.expiry {
-                cookie.set_expiry(expiry);
-            }
-
-            let _ = driver.add_cookie(cookie).await;
+            let _ = driver
+                .add_cookie(super::cookies::to_webdriver_cookie(cd))
+                .await;
         }
 
         context.set_pin_value("session_out", json!(session)).await?;
@@ -0,0 +1,577 @@
+//! Cookie nodes. Chromium browsers go through the CDP `Network`/`Storage` domains, which see
+//! every cookie of the browser including `HttpOnly` ones. Other browsers fall back to WebDriver,
+//! which only sees the cookies of the current page and hides the `HttpOnly` flag.
+
+use crate::browser::auth::CookieData;
+use crate::types::handles::AutomationSession;
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic},
+    pin::ValueType,
+    variable::VariableType,
+};
+use flow_like_types::{Value, async_trait, json::json};
+
+#[cfg(feature = "execute")]
+use thirtyfour::WebDriver;
+
+/// Reads a cookie as reported by CDP `Network.getCookies` and `Storage.getCookies`.
+/// Session cookies report `expires: -1` and come back without an expiry.
+pub fn cookie_from_cdp(cookie: &Value) -> Option<CookieData> {
+    let text = |key: &str| {
+        cookie[key]
+            .as_str()
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+    let session = cookie["session"].as_bool().unwrap_or(false);
+    let expires = cookie["expires"].as_f64().filter(|expires| *expires > 0.0);
+
+    Some(CookieData {
+        name: cookie["name"].as_str()?.to_string(),
+        value: cookie["value"].as_str().unwrap_or_default().to_string(),
+        domain: text("domain"),
+        path: text("path"),
+        secure: cookie["secure"].as_bool(),
+        http_only: cookie["httpOnly"].as_bool(),
+        same_site: text("sameSite"),
+        expiry: expires.filter(|_| !session).map(|expires| expires as i64),
+    })
+}
+
+/// Builds a `Network.CookieParam`. CDP needs a domain or URL to scope a cookie, cookies
+/// without a domain are set for `page_url`.
+pub fn cookie_to_cdp(cookie: &CookieData, page_url: &str) -> Value {
+    let mut param = json!({
+        "name": cookie.name,
+        "value": cookie.value,
+    });
+    match &cookie.domain {
+        Some(domain) => param["domain"] = json!(domain),
+        None => param["url"] = json!(page_url),
+    }
+    if let Some(path) = &cookie.path {
+        param["path"] = json!(path);
+    }
+    if let Some(secure) = cookie.secure {
+        param["secure"] = json!(secure);
+    }
+    if let Some(http_only) = cookie.http_only {
+        param["httpOnly"] = json!(http_only);
+    }
+    if let Some(same_site) = cookie.same_site.as_deref().and_then(normalize_same_site) {
+        param["sameSite"] = json!(same_site);
+    }
+    if let Some(expiry) = cookie.expiry {
+        param["expires"] = json!(expiry);
+    }
+    param
+}
+
+fn normalize_same_site(same_site: &str) -> Option<&'static str> {
+    match same_site.to_ascii_lowercase().as_str() {
+        "strict" => Some("Strict"),
+        "lax" => Some("Lax"),
+        "none" | "no_restriction" => Some("None"),
+        _ => None,
+    }
+}
+
+/// Whether `cookie_domain` (as stored, possibly with a leading dot) belongs to `domain`
+pub fn cookie_matches_domain(cookie_domain: &str, domain: &str) -> bool {
+    let cookie_domain = cookie_domain.trim_start_matches('.').to_ascii_lowercase();
+    let domain = domain.trim().trim_start_matches('.').to_ascii_lowercase();
+    cookie_domain == domain || cookie_domain.ends_with(&format!(".{}", domain))
+}
+
+#[cfg(feature = "execute")]
+fn uses_cdp(session: &AutomationSession) -> bool {
+    use crate::types::handles::BrowserType;
+
+    matches!(
+        session.browser_type,
+        Some(BrowserType::Chrome) | Some(BrowserType::Edge)
+    )
+}
+
+/// All cookies of the browser, or only those sent to the current page
+#[cfg(feature = "execute")]
+pub async fn get_cdp_cookies(
+    driver: &WebDriver,
+    current_page_only: bool,
+) -> flow_like_types::Result<Vec<CookieData>> {
+    use thirtyfour::extensions::cdp::ChromeDevTools;
+
+    let dev_tools = ChromeDevTools::new(driver.handle.clone());
+    let result = if current_page_only {
+        let url = driver
+            .current_url()
+            .await
+            .map_err(|e| flow_like_types::anyhow!("Failed to read page URL: {}", e))?;
+        dev_tools
+            .execute_cdp_with_params("Network.getCookies", json!({ "urls": [url.to_string()] }))
+            .await
+    } else {
+        dev_tools
+            .execute_cdp_with_params("Storage.getCookies", json!({}))
+            .await
+    }
+    .map_err(|e| flow_like_types::anyhow!("Failed to get cookies: {}", e))?;
+
+    Ok(result["cookies"]
+        .as_array()
+        .map(|cookies| cookies.iter().filter_map(cookie_from_cdp).collect())
+        .unwrap_or_default())
+}
+
+#[cfg(feature = "execute")]
+pub async fn set_cdp_cookies(
+    driver: &WebDriver,
+    cookies: &[CookieData],
+) -> flow_like_types::Result<()> {
+    use thirtyfour::extensions::cdp::ChromeDevTools;
+
+    let page_url = driver
+        .current_url()
+        .await
+        .map(|url| url.to_string())
+        .unwrap_or_default();
+    let params: Vec<Value> = cookies
+        .iter()
+        .map(|cookie| cookie_to_cdp(cookie, &page_url))
+        .collect();
+
+    ChromeDevTools::new(driver.handle.clone())
+        .execute_cdp_with_params("Network.setCookies", json!({ "cookies": params }))
+        .await
+        .map_err(|e| flow_like_types::anyhow!("Failed to set cookies: {}", e))?;
+    Ok(())
+}
+
+/// Deletes the cookies of `domain` and its subdomains, or every cookie when it is empty.
+/// Returns how many cookies were deleted.
+#[cfg(feature = "execute")]
+pub async fn clear_cdp_cookies(driver: &WebDriver, domain: &str) -> flow_like_types::Result<usize> {
+    use thirtyfour::extensions::cdp::ChromeDevTools;
+
+    let cookies = get_cdp_cookies(driver, false).await?;
+    let dev_tools = ChromeDevTools::new(driver.handle.clone());
+
+    if domain.trim().is_empty() {
+        dev_tools
+            .execute_cdp("Network.clearBrowserCookies")
+            .await
+            .map_err(|e| flow_like_types::anyhow!("Failed to clear cookies: {}", e))?;
+        return Ok(cookies.len());
+    }
+
+    let mut deleted = 0;
+    for cookie in cookies.iter().filter(|cookie| {
+        cookie
+            .domain
+            .as_deref()
+            .is_some_and(|cookie_domain| cookie_matches_domain(cookie_domain, domain))
+    }) {
+        dev_tools
+            .execute_cdp_with_params(
+                "Network.deleteCookies",
+                json!({
+                    "name": cookie.name,
+                    "domain": cookie.domain,
+                    "path": cookie.path,
+                }),
+            )
+            .await
+            .map_err(|e| {
+                flow_like_types::anyhow!("Failed to delete cookie '{}': {}", cookie.name, e)
+            })?;
+        deleted += 1;
+    }
+    Ok(deleted)
+}
+
+#[cfg(feature = "execute")]
+pub(crate) fn from_webdriver_cookie(cookie: thirtyfour::cookie::Cookie) -> CookieData {
+    use thirtyfour::cookie::SameSite;
+
+    CookieData {
+        name: cookie.name,
+        value: cookie.value,
+        domain: cookie.domain,
+        path: cookie.path,
+        secure: cookie.secure,
+        // WebDriver cookies in thirtyfour don't expose the HttpOnly flag
+        http_only: None,
+        same_site: cookie.same_site.map(|same_site| match same_site {
+            SameSite::Strict => "Strict".to_string(),
+            SameSite::Lax => "Lax".to_string(),
+            SameSite::None => "None".to_string(),
+        }),
+        expiry: cookie.expiry,
+    }
+}
+
+#[cfg(feature = "execute")]
+pub(crate) fn to_webdriver_cookie(data: &CookieData) -> thirtyfour::cookie::Cookie {
+    use thirtyfour::cookie::{Cookie, SameSite};
+
+    let mut cookie = Cookie::new(&data.name, &data.value);
+    if let Some(domain) = &data.domain {
+        cookie.set_domain(domain.clone());
+    }
+    if let Some(path) = &data.path {
+        cookie.set_path(path.clone());
+    }
+    if let Some(secure) = data.secure {
+        cookie.set_secure(secure);
+    }
+    if let Some(same_site) = data.same_site.as_deref().and_then(normalize_same_site) {
+        cookie.set_same_site(match same_site {
+            "Strict" => SameSite::Strict,
+            "Lax" => SameSite::Lax,
+            _ => SameSite::None,
+        });
+    }
+    if let Some(expiry) = data.expiry {
+        cookie.set_expiry(expiry);
+    }
+    cookie
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct BrowserGetCookiesNode {}
+
+impl BrowserGetCookiesNode {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for BrowserGetCookiesNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "browser_get_cookies",
+            "Get Cookies",
+            "Gets the browser cookies as a list, e.g. to save a logged in session and restore it with Set Cookies",
+            "Automation/Browser/Auth",
+        );
+        node.add_icon("/flow/icons/browser.svg");
+
+        node.set_scores(
+            flow_like::flow::node::NodeScores::new()
+                .set_privacy(2)
+                .set_security(3)
+                .set_performance(9)
+                .set_governance(4)
+                .set_reliability(9)
+                .set_cost(10)
+                .build(),
+        );
+        node.set_only_offline(true);
+
+        node.add_input_pin("exec_in", "▶", "Trigger", VariableType::Execution);
+
+        node.add_input_pin(
+            "session",
+            "Session",
+            "Automation session",
+            VariableType::Struct,
+        )
+        .set_schema::<AutomationSession>();
+
+        node.add_input_pin(
+            "current_page_only",
+            "Current Page Only",
+            "Only return cookies sent to the current page instead of all cookies of the browser",
+            VariableType::Boolean,
+        )
+        .set_default_value(Some(json!(false)));
+
+        node.add_output_pin("exec_out", "▶", "Continue", VariableType::Execution);
+
+        node.add_output_pin(
+            "session_out",
+            "Session",
+            "Automation session (pass-through)",
+            VariableType::Struct,
+        )
+        .set_schema::<AutomationSession>();
+
+        node.add_output_pin(
+            "cookies",
+            "Cookies",
+            "Browser cookies",
+            VariableType::Struct,
+        )
+        .set_schema::<CookieData>()
+        .set_value_type(ValueType::Array);
+
+        node.add_output_pin("count", "Count", "Number of cookies", VariableType::Integer);
+
+        node
+    }
+
+    #[cfg(feature = "execute")]
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+
+        let session: AutomationSession = context.evaluate_pin("session").await?;
+        let current_page_only: bool = context.evaluate_pin("current_page_only").await?;
+
+        let driver = session.get_browser_driver_and_switch(context).await?;
+
+        let cookies = if uses_cdp(&session) {
+            get_cdp_cookies(&driver, current_page_only).await?
+        } else {
+            driver
+                .get_all_cookies()
+                .await
+                .map_err(|e| flow_like_types::anyhow!("Failed to get cookies: {}", e))?
+                .into_iter()
+                .map(from_webdriver_cookie)
+                .collect()
+        };
+
+        context.set_pin_value("session_out", json!(session)).await?;
+        context
+            .set_pin_value("count", json!(cookies.len() as i64))
+            .await?;
+        context.set_pin_value("cookies", json!(cookies)).await?;
+        context.activate_exec_pin("exec_out").await?;
+
+        Ok(())
+    }
+
+    #[cfg(not(feature = "execute"))]
+    async fn run(&self, _context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        Err(flow_like_types::anyhow!(
+            "Browser automation requires the 'execute' feature"
+        ))
+    }
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct BrowserSetCookiesNode {}
+
+impl BrowserSetCookiesNode {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for BrowserSetCookiesNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "browser_set_cookies",
+            "Set Cookies",
+            "Adds or replaces browser cookies, e.g. the ones returned by Get Cookies in an earlier run",
+            "Automation/Browser/Auth",
+        );
+        node.add_icon("/flow/icons/browser.svg");
+
+        node.set_scores(
+            flow_like::flow::node::NodeScores::new()
+                .set_privacy(2)
+                .set_security(3)
+                .set_performance(9)
+                .set_governance(4)
+                .set_reliability(8)
+                .set_cost(10)
+                .build(),
+        );
+        node.set_only_offline(true);
+
+        node.add_input_pin("exec_in", "▶", "Trigger", VariableType::Execution);
+
+        node.add_input_pin(
+            "session",
+            "Session",
+            "Automation session",
+            VariableType::Struct,
+        )
+        .set_schema::<AutomationSession>();
+
+        node.add_input_pin(
+            "cookies",
+            "Cookies",
+            "Cookies to set. Cookies without a domain are set for the current page",
+            VariableType::Struct,
+        )
+        .set_schema::<CookieData>()
+        .set_value_type(ValueType::Array)
+        .set_default_value(Some(json!([])));
+
+        node.add_output_pin("exec_out", "▶", "Continue", VariableType::Execution);
+
+        node.add_output_pin(
+            "session_out",
+            "Session",
+            "Automation session (pass-through)",
+            VariableType::Struct,
+        )
+        .set_schema::<AutomationSession>();
+
+        node
+    }
+
+    #[cfg(feature = "execute")]
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+
+        let session: AutomationSession = context.evaluate_pin("session").await?;
+        let cookies: Vec<CookieData> = context.evaluate_pin("cookies").await?;
+
+        let driver = session.get_browser_driver_and_switch(context).await?;
+
+        if uses_cdp(&session) {
+            set_cdp_cookies(&driver, &cookies).await?;
+        } else {
+            // WebDriver only accepts cookies for the domain of the current page
+            for cookie in &cookies {
+                if let Err(e) = driver.add_cookie(to_webdriver_cookie(cookie)).await {
+                    context.log_message(
+                        &format!("Skipped cookie '{}': {}", cookie.name, e),
+                        flow_like::flow::execution::LogLevel::Warn,
+                    );
+                }
+            }
+        }
+
+        context.set_pin_value("session_out", json!(session)).await?;
+        context.activate_exec_pin("exec_out").await?;
+
+        Ok(())
+    }
+
+    #[cfg(not(feature = "execute"))]
+    async fn run(&self, _context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        Err(flow_like_types::anyhow!(
+            "Browser automation requires the 'execute' feature"
+        ))
+    }
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct BrowserClearCookiesNode {}
+
+impl BrowserClearCookiesNode {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for BrowserClearCookiesNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "browser_clear_cookies",
+            "Clear Cookies",
+            "Deletes browser cookies, e.g. to log out before restoring another session",
+            "Automation/Browser/Auth",
+        );
+        node.add_icon("/flow/icons/browser.svg");
+
+        node.set_scores(
+            flow_like::flow::node::NodeScores::new()
+                .set_privacy(3)
+                .set_security(4)
+                .set_performance(9)
+                .set_governance(5)
+                .set_reliability(9)
+                .set_cost(10)
+                .build(),
+        );
+        node.set_only_offline(true);
+
+        node.add_input_pin("exec_in", "▶", "Trigger", VariableType::Execution);
+
+        node.add_input_pin(
+            "session",
+            "Session",
+            "Automation session",
+            VariableType::Struct,
+        )
+        .set_schema::<AutomationSession>();
+
+        node.add_input_pin(
+            "domain",
+            "Domain",
+            "Only delete cookies of this domain and its subdomains, leave empty for all",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("")));
+
+        node.add_output_pin("exec_out", "▶", "Continue", VariableType::Execution);
+
+        node.add_output_pin(
+            "session_out",
+            "Session",
+            "Automation session (pass-through)",
+            VariableType::Struct,
+        )
+        .set_schema::<AutomationSession>();
+
+        node.add_output_pin(
+            "deleted",
+            "Deleted",
+            "Number of deleted cookies",
+            VariableType::Integer,
+        );
+
+        node
+    }
+
+    #[cfg(feature = "execute")]
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+
+        let session: AutomationSession = context.evaluate_pin("session").await?;
+        let domain: String = context.evaluate_pin("domain").await?;
+
+        let driver = session.get_browser_driver_and_switch(context).await?;
+
+        let deleted = if uses_cdp(&session) {
+            clear_cdp_cookies(&driver, &domain).await?
+        } else {
+            let cookies = driver
+                .get_all_cookies()
+                .await
+                .map_err(|e| flow_like_types::anyhow!("Failed to get cookies: {}", e))?;
+            let mut deleted = 0;
+            for cookie in cookies {
+                let matches = domain.trim().is_empty()
+                    || cookie
+                        .domain
+                        .as_deref()
+                        .is_some_and(|cookie_domain| cookie_matches_domain(cookie_domain, &domain));
+                if matches {
+                    driver.delete_cookie(&cookie.name).await.map_err(|e| {
+                        flow_like_types::anyhow!("Failed to delete cookie '{}': {}", cookie.name, e)
+                    })?;
+                    deleted += 1;
+                }
+            }
+            deleted
+        };
+
+        context.set_pin_value("session_out", json!(session)).await?;
+        context
+            .set_pin_value("deleted", json!(deleted as i64))
+            .await?;
+        context.activate_exec_pin("exec_out").await?;
+
+        Ok(())
+    }
+
+    #[cfg(not(feature = "execute"))]
+    async fn run(&self, _context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        Err(flow_like_types::anyhow!(
+            "Browser automation requires the 'execute' feature"
+        ))
+    }
+}
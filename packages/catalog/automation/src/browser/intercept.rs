@@ -0,0 +1,746 @@
+//! Network interception for the active page.
+//!
+//! WebDriver gives no access to the CDP `Fetch` event stream, so requests are intercepted
+//! inside the page by wrapping `fetch` and `XMLHttpRequest`. On Chromium the script is also
+//! registered with `Page.addScriptToEvaluateOnNewDocument`, which keeps the rules active
+//! across navigations of the same window.
+
+use crate::types::handles::AutomationSession;
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic},
+    pin::ValueType,
+    variable::VariableType,
+};
+use flow_like_types::{async_trait, json::json};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Captured responses kept per page before the oldest are dropped
+pub const MAX_INTERCEPTED_RESPONSES: usize = 500;
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum InterceptAction {
+    /// Let the request through and record its response
+    #[default]
+    Capture,
+    /// Answer with the rule's canned response without contacting the server
+    Fulfill,
+}
+
+fn default_status() -> u16 {
+    200
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug)]
+pub struct InterceptRule {
+    /// URL glob, `*` matches any characters and `?` a single one
+    pub url_pattern: String,
+    /// HTTP method to match, empty for any
+    #[serde(default)]
+    pub method: String,
+    #[serde(default)]
+    pub action: InterceptAction,
+    /// Status of the canned response
+    #[serde(default = "default_status")]
+    pub status: u16,
+    /// Headers of the canned response
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    /// Body of the canned response
+    #[serde(default)]
+    pub body: String,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug)]
+pub struct InterceptedResponse {
+    pub url: String,
+    pub method: String,
+    pub status: u16,
+    pub headers: HashMap<String, String>,
+    /// Response text, empty for binary XHR responses
+    pub body: String,
+    /// Whether the response came from a fulfill rule
+    pub stubbed: bool,
+    pub timestamp: i64,
+}
+
+/// Converts a URL glob into an anchored regular expression source.
+pub fn glob_to_regex(pattern: &str) -> String {
+    let mut regex = String::with_capacity(pattern.len() + 8);
+    regex.push('^');
+    for c in pattern.chars() {
+        match c {
+            '*' => regex.push_str(".*"),
+            '?' => regex.push('.'),
+            '\\' | '.' | '+' | '(' | ')' | '|' | '[' | ']' | '{' | '}' | '^' | '$' => {
+                regex.push('\\');
+                regex.push(c);
+            }
+            _ => regex.push(c),
+        }
+    }
+    regex.push('$');
+    regex
+}
+
+fn compile_rules(rules: &[InterceptRule]) -> flow_like_types::Result<Vec<flow_like_types::Value>> {
+    rules
+        .iter()
+        .map(|rule| {
+            if rule.url_pattern.is_empty() {
+                return Err(flow_like_types::anyhow!(
+                    "Intercept rule needs a URL pattern, use \"*\" to match every request"
+                ));
+            }
+            if rule.action == InterceptAction::Fulfill && !(200..=599).contains(&rule.status) {
+                return Err(flow_like_types::anyhow!(
+                    "Status {} of the rule for \"{}\" must be between 200 and 599",
+                    rule.status,
+                    rule.url_pattern
+                ));
+            }
+            Ok(json!({
+                "regex": glob_to_regex(&rule.url_pattern),
+                "method": rule.method.trim().to_uppercase(),
+                "action": rule.action,
+                "status": rule.status,
+                "headers": rule.headers,
+                "body": rule.body,
+            }))
+        })
+        .collect()
+}
+
+/// Defines `__flowlikeIntercept(rules, replace)`, which patches `fetch` and `XMLHttpRequest`
+/// once per document and either replaces the rules or adds the missing ones.
+const INTERCEPT_RUNTIME: &str = r#"
+function __flowlikeIntercept(rules, replace) {
+    const state = window.__flowlike_intercept || (window.__flowlike_intercept = { rules: [], responses: [] });
+    const compiled = rules.map(rule => Object.assign({}, rule, { pattern: new RegExp(rule.regex) }));
+    if (replace) {
+        state.rules = compiled;
+    } else {
+        for (const rule of compiled) {
+            if (!state.rules.some(r => r.regex === rule.regex && r.method === rule.method)) {
+                state.rules.push(rule);
+            }
+        }
+    }
+    if (state.installed) return state;
+    state.installed = true;
+
+    const absolute = url => { try { return new URL(url, location.href).href; } catch (e) { return String(url); } };
+    const match = (url, method) => state.rules.find(rule =>
+        rule.pattern.test(url) && (!rule.method || rule.method === method));
+    const record = entry => {
+        state.responses.push(Object.assign({ timestamp: Date.now() }, entry));
+        if (state.responses.length > __MAX_RESPONSES__) state.responses.shift();
+    };
+    const parseHeaders = text => {
+        const headers = {};
+        for (const line of (text || '').trim().split(/[\r\n]+/)) {
+            const index = line.indexOf(':');
+            if (index > 0) headers[line.slice(0, index).trim().toLowerCase()] = line.slice(index + 1).trim();
+        }
+        return headers;
+    };
+    const stub = (rule, url, method) => {
+        record({ url, method, status: rule.status, headers: rule.headers, body: rule.body, stubbed: true });
+    };
+
+    const originalFetch = window.fetch;
+    window.fetch = function (input, init) {
+        const isRequest = typeof Request !== 'undefined' && input instanceof Request;
+        const url = absolute(isRequest ? input.url : input);
+        const method = String((init && init.method) || (isRequest ? input.method : 'GET')).toUpperCase();
+        const rule = match(url, method);
+        if (rule && rule.action === 'fulfill') {
+            stub(rule, url, method);
+            const noBody = [204, 205, 304].includes(rule.status);
+            return Promise.resolve(new Response(noBody ? null : rule.body, { status: rule.status, headers: rule.headers }));
+        }
+        const pending = originalFetch.apply(this, arguments);
+        if (!rule) return pending;
+        return pending.then(response => {
+            const headers = {};
+            response.headers.forEach((value, key) => { headers[key] = value; });
+            response.clone().text().then(body => {
+                record({ url, method, status: response.status, headers, body, stubbed: false });
+            }).catch(() => {});
+            return response;
+        });
+    };
+
+    const XHR = XMLHttpRequest.prototype;
+    const originalOpen = XHR.open;
+    const originalSend = XHR.send;
+    XHR.open = function (method, url) {
+        this.__flowlike = { method: String(method).toUpperCase(), url: absolute(url) };
+        return originalOpen.apply(this, arguments);
+    };
+    XHR.send = function () {
+        const info = this.__flowlike;
+        const rule = info && match(info.url, info.method);
+        if (!rule) return originalSend.apply(this, arguments);
+        if (rule.action === 'fulfill') {
+            stub(rule, info.url, info.method);
+            const headers = {};
+            for (const key of Object.keys(rule.headers)) headers[key.toLowerCase()] = rule.headers[key];
+            let response = rule.body;
+            if (this.responseType === 'json') {
+                try { response = JSON.parse(rule.body); } catch (e) { response = null; }
+            }
+            const define = (name, value) => Object.defineProperty(this, name, { configurable: true, value });
+            define('readyState', 4);
+            define('status', rule.status);
+            define('statusText', '');
+            define('responseURL', info.url);
+            define('responseText', rule.body);
+            define('response', response);
+            this.getAllResponseHeaders = () => Object.entries(headers).map(([k, v]) => k + ': ' + v).join('\r\n');
+            this.getResponseHeader = name => headers[String(name).toLowerCase()] ?? null;
+            setTimeout(() => {
+                for (const type of ['readystatechange', 'load', 'loadend']) this.dispatchEvent(new Event(type));
+            });
+            return;
+        }
+        this.addEventListener('loadend', () => {
+            const text = this.responseType === '' || this.responseType === 'text';
+            record({
+                url: info.url,
+                method: info.method,
+                status: this.status,
+                headers: parseHeaders(this.getAllResponseHeaders()),
+                body: text ? this.responseText : '',
+                stubbed: false
+            });
+        });
+        return originalSend.apply(this, arguments);
+    };
+    return state;
+}
+"#;
+
+fn runtime() -> String {
+    INTERCEPT_RUNTIME.replace("__MAX_RESPONSES__", &MAX_INTERCEPTED_RESPONSES.to_string())
+}
+
+/// Script that installs interception with exactly these rules. It has no top level
+/// `return`, so it can run both through WebDriver and before a new document loads.
+pub fn install_script(rules: &[InterceptRule]) -> flow_like_types::Result<String> {
+    let rules = flow_like_types::json::to_string(&compile_rules(rules)?)?;
+    Ok(format!(
+        "(function () {{ {} __flowlikeIntercept({}, true); }})();",
+        runtime(),
+        rules
+    ))
+}
+
+/// Script returning the recorded responses, optionally emptying the buffer.
+pub fn take_responses_script(clear: bool) -> String {
+    format!(
+        r#"
+        const state = window.__flowlike_intercept;
+        if (!state) return [];
+        const responses = state.responses.slice();
+        if ({}) state.responses.length = 0;
+        return responses;
+        "#,
+        clear
+    )
+}
+
+/// Script that makes sure responses matching the pattern are captured, then removes and
+/// returns the first recorded match, or `null` if none has completed yet.
+pub fn wait_for_response_script(
+    url_pattern: &str,
+    method: &str,
+) -> flow_like_types::Result<String> {
+    let rule = InterceptRule {
+        url_pattern: url_pattern.to_string(),
+        method: method.to_string(),
+        action: InterceptAction::Capture,
+        status: default_status(),
+        headers: HashMap::new(),
+        body: String::new(),
+    };
+    let compiled = compile_rules(std::slice::from_ref(&rule))?;
+    let rules = flow_like_types::json::to_string(&compiled)?;
+    Ok(format!(
+        r#"
+        {}
+        const state = __flowlikeIntercept({}, false);
+        const pattern = new RegExp({});
+        const method = {};
+        const index = state.responses.findIndex(r => pattern.test(r.url) && (!method || r.method === method));
+        return index < 0 ? null : state.responses.splice(index, 1)[0];
+        "#,
+        runtime(),
+        rules,
+        flow_like_types::json::to_string(&compiled[0]["regex"])?,
+        flow_like_types::json::to_string(&compiled[0]["method"])?,
+    ))
+}
+
+/// Identifier of the script registered for new documents of one window.
+#[cfg(feature = "execute")]
+struct PersistedInterceptScript {
+    identifier: String,
+}
+
+#[cfg(feature = "execute")]
+impl flow_like_types::Cacheable for PersistedInterceptScript {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+/// Replaces the script Chromium runs on every new document of the active window.
+#[cfg(feature = "execute")]
+async fn persist_on_navigation(
+    context: &ExecutionContext,
+    session: &AutomationSession,
+    driver: &thirtyfour::WebDriver,
+    script: Option<&str>,
+) -> flow_like_types::Result<()> {
+    use std::sync::Arc;
+    use thirtyfour::extensions::cdp::ChromeDevTools;
+
+    let cache_key = format!(
+        "{}_intercept_{}",
+        session.session_ref,
+        session.current_window_handle.as_deref().unwrap_or_default()
+    );
+    let dev_tools = ChromeDevTools::new(driver.handle.clone());
+
+    let previous = context.get_cache(&cache_key).await.and_then(|cached| {
+        cached
+            .as_any()
+            .downcast_ref::<PersistedInterceptScript>()
+            .map(|persisted| persisted.identifier.clone())
+    });
+    if let Some(identifier) = previous {
+        dev_tools
+            .execute_cdp_with_params(
+                "Page.removeScriptToEvaluateOnNewDocument",
+                json!({ "identifier": identifier }),
+            )
+            .await
+            .map_err(|e| flow_like_types::anyhow!("Failed to remove intercept script: {}", e))?;
+        context.cache.write().await.remove(&cache_key);
+    }
+
+    let Some(script) = script else {
+        return Ok(());
+    };
+    let result = dev_tools
+        .execute_cdp_with_params(
+            "Page.addScriptToEvaluateOnNewDocument",
+            json!({ "source": script }),
+        )
+        .await
+        .map_err(|e| flow_like_types::anyhow!("Failed to register intercept script: {}", e))?;
+    let identifier = result
+        .get("identifier")
+        .and_then(|identifier| identifier.as_str())
+        .ok_or_else(|| flow_like_types::anyhow!("CDP returned no script identifier"))?;
+    context
+        .set_cache(
+            &cache_key,
+            Arc::new(PersistedInterceptScript {
+                identifier: identifier.to_string(),
+            }),
+        )
+        .await;
+    Ok(())
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct BrowserInterceptNetworkNode {}
+
+impl BrowserInterceptNetworkNode {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for BrowserInterceptNetworkNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "browser_intercept_network",
+            "Intercept Network",
+            "Intercepts fetch and XHR requests of the active page. Matching requests are either captured with their response or fulfilled with a canned response. Rules replace the previous ones, an empty list stops interception",
+            "Automation/Browser/Observe",
+        );
+        node.add_icon("/flow/icons/browser.svg");
+
+        node.set_scores(
+            flow_like::flow::node::NodeScores::new()
+                .set_privacy(4)
+                .set_security(4)
+                .set_performance(7)
+                .set_governance(5)
+                .set_reliability(7)
+                .set_cost(10)
+                .build(),
+        );
+        node.set_only_offline(true);
+
+        node.add_input_pin("exec_in", "▶", "Trigger", VariableType::Execution);
+
+        node.add_input_pin(
+            "session",
+            "Session",
+            "Automation session",
+            VariableType::Struct,
+        )
+        .set_schema::<AutomationSession>();
+
+        node.add_input_pin(
+            "rules",
+            "Rules",
+            "Rules checked in order, e.g. { \"url_pattern\": \"*/api/users*\", \"action\": \"fulfill\", \"status\": 200, \"body\": \"[]\" }",
+            VariableType::Struct,
+        )
+        .set_schema::<InterceptRule>()
+        .set_value_type(ValueType::Array)
+        .set_default_value(Some(json!([])));
+
+        node.add_output_pin("exec_out", "▶", "Continue", VariableType::Execution);
+
+        node.add_output_pin(
+            "session_out",
+            "Session",
+            "Automation session (pass-through)",
+            VariableType::Struct,
+        )
+        .set_schema::<AutomationSession>();
+
+        node.add_output_pin(
+            "persistent",
+            "Persistent",
+            "Whether the rules survive navigations (Chromium only)",
+            VariableType::Boolean,
+        );
+
+        node
+    }
+
+    #[cfg(feature = "execute")]
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+
+        let session: AutomationSession = context.evaluate_pin("session").await?;
+        let rules: Vec<InterceptRule> = context.evaluate_pin("rules").await?;
+
+        let script = install_script(&rules)?;
+        let driver = session.get_browser_driver_and_switch(context).await?;
+
+        driver.execute(&script, vec![]).await.map_err(|e| {
+            flow_like_types::anyhow!("Failed to install network interception: {}", e)
+        })?;
+
+        let persisted = (!rules.is_empty()).then_some(script.as_str());
+        let persistent = match persist_on_navigation(context, &session, &driver, persisted).await {
+            Ok(()) => !rules.is_empty(),
+            Err(e) => {
+                context.log_message(
+                    &format!("Interception only applies to the current document: {}", e),
+                    flow_like::flow::execution::LogLevel::Warn,
+                );
+                false
+            }
+        };
+
+        context.set_pin_value("session_out", json!(session)).await?;
+        context
+            .set_pin_value("persistent", json!(persistent))
+            .await?;
+        context.activate_exec_pin("exec_out").await?;
+
+        Ok(())
+    }
+
+    #[cfg(not(feature = "execute"))]
+    async fn run(&self, _context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        Err(flow_like_types::anyhow!(
+            "Browser automation requires the 'execute' feature"
+        ))
+    }
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct BrowserGetInterceptedResponsesNode {}
+
+impl BrowserGetInterceptedResponsesNode {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for BrowserGetInterceptedResponsesNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "browser_get_intercepted_responses",
+            "Get Intercepted Responses",
+            "Returns the responses recorded by network interception on the active page",
+            "Automation/Browser/Observe",
+        );
+        node.add_icon("/flow/icons/browser.svg");
+
+        node.set_scores(
+            flow_like::flow::node::NodeScores::new()
+                .set_privacy(4)
+                .set_security(5)
+                .set_performance(8)
+                .set_governance(5)
+                .set_reliability(8)
+                .set_cost(10)
+                .build(),
+        );
+        node.set_only_offline(true);
+
+        node.add_input_pin("exec_in", "▶", "Trigger", VariableType::Execution);
+
+        node.add_input_pin(
+            "session",
+            "Session",
+            "Automation session",
+            VariableType::Struct,
+        )
+        .set_schema::<AutomationSession>();
+
+        node.add_input_pin(
+            "clear",
+            "Clear",
+            "Empty the buffer after reading",
+            VariableType::Boolean,
+        )
+        .set_default_value(Some(json!(true)));
+
+        node.add_output_pin("exec_out", "▶", "Continue", VariableType::Execution);
+
+        node.add_output_pin(
+            "session_out",
+            "Session",
+            "Automation session (pass-through)",
+            VariableType::Struct,
+        )
+        .set_schema::<AutomationSession>();
+
+        node.add_output_pin(
+            "responses",
+            "Responses",
+            "Recorded responses, oldest first",
+            VariableType::Struct,
+        )
+        .set_schema::<InterceptedResponse>()
+        .set_value_type(ValueType::Array);
+
+        node.add_output_pin(
+            "count",
+            "Count",
+            "Number of responses",
+            VariableType::Integer,
+        );
+
+        node
+    }
+
+    #[cfg(feature = "execute")]
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+
+        let session: AutomationSession = context.evaluate_pin("session").await?;
+        let clear: bool = context.evaluate_pin("clear").await?;
+
+        let driver = session.get_browser_driver_and_switch(context).await?;
+
+        let result = driver
+            .execute(&take_responses_script(clear), vec![])
+            .await
+            .map_err(|e| flow_like_types::anyhow!("Failed to get intercepted responses: {}", e))?;
+        let responses: Vec<InterceptedResponse> =
+            flow_like_types::json::from_value(result.json().clone())?;
+
+        context.set_pin_value("session_out", json!(session)).await?;
+        context
+            .set_pin_value("count", json!(responses.len() as i64))
+            .await?;
+        context.set_pin_value("responses", json!(responses)).await?;
+        context.activate_exec_pin("exec_out").await?;
+
+        Ok(())
+    }
+
+    #[cfg(not(feature = "execute"))]
+    async fn run(&self, _context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        Err(flow_like_types::anyhow!(
+            "Browser automation requires the 'execute' feature"
+        ))
+    }
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct BrowserWaitForResponseNode {}
+
+impl BrowserWaitForResponseNode {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for BrowserWaitForResponseNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "browser_wait_for_response",
+            "Wait For Response",
+            "Waits until a fetch or XHR request matching the URL pattern completes on the active page. Responses recorded before the wait also count, each one is returned only once",
+            "Automation/Browser/Wait",
+        );
+        node.add_icon("/flow/icons/browser.svg");
+
+        node.set_scores(
+            flow_like::flow::node::NodeScores::new()
+                .set_privacy(4)
+                .set_security(5)
+                .set_performance(6)
+                .set_governance(5)
+                .set_reliability(7)
+                .set_cost(10)
+                .build(),
+        );
+        node.set_only_offline(true);
+
+        node.add_input_pin("exec_in", "▶", "Trigger", VariableType::Execution);
+
+        node.add_input_pin(
+            "session",
+            "Session",
+            "Automation session",
+            VariableType::Struct,
+        )
+        .set_schema::<AutomationSession>();
+
+        node.add_input_pin(
+            "url_pattern",
+            "URL Pattern",
+            "URL glob, `*` matches any characters and `?` a single one",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("*")));
+
+        node.add_input_pin(
+            "method",
+            "Method",
+            "HTTP method to match, empty for any",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("")));
+
+        node.add_input_pin(
+            "timeout_ms",
+            "Timeout (ms)",
+            "Maximum time to wait",
+            VariableType::Integer,
+        )
+        .set_default_value(Some(json!(30000)));
+
+        node.add_output_pin("exec_out", "▶", "Continue", VariableType::Execution);
+
+        node.add_output_pin(
+            "exec_timeout",
+            "Timeout",
+            "No matching response completed in time",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "session_out",
+            "Session",
+            "Automation session (pass-through)",
+            VariableType::Struct,
+        )
+        .set_schema::<AutomationSession>();
+
+        node.add_output_pin(
+            "response",
+            "Response",
+            "The matching response",
+            VariableType::Struct,
+        )
+        .set_schema::<InterceptedResponse>();
+
+        node
+    }
+
+    #[cfg(feature = "execute")]
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        use std::time::Duration;
+
+        context.deactivate_exec_pin("exec_out").await?;
+        context.deactivate_exec_pin("exec_timeout").await?;
+
+        let session: AutomationSession = context.evaluate_pin("session").await?;
+        let url_pattern: String = context.evaluate_pin("url_pattern").await?;
+        let method: String = context.evaluate_pin("method").await?;
+        let timeout_ms: i64 = context.evaluate_pin("timeout_ms").await?;
+
+        let script = wait_for_response_script(&url_pattern, &method)?;
+        let driver = session.get_browser_driver_and_switch(context).await?;
+
+        let result = tokio::time::timeout(Duration::from_millis(timeout_ms.max(0) as u64), async {
+            loop {
+                // Navigations make single polls fail, the script reinstalls itself afterwards
+                let polled = driver
+                    .execute(&script, vec![])
+                    .await
+                    .ok()
+                    .and_then(|result| {
+                        flow_like_types::json::from_value::<InterceptedResponse>(
+                            result.json().clone(),
+                        )
+                        .ok()
+                    });
+                if let Some(response) = polled {
+                    return response;
+                }
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+        })
+        .await;
+
+        context.set_pin_value("session_out", json!(session)).await?;
+        match result {
+            Ok(response) => {
+                context.set_pin_value("response", json!(response)).await?;
+                context.activate_exec_pin("exec_out").await?;
+            }
+            Err(_) => {
+                context.activate_exec_pin("exec_timeout").await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg(not(feature = "execute"))]
+    async fn run(&self, _context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        Err(flow_like_types::anyhow!(
+            "Browser automation requires the 'execute' feature"
+        ))
+    }
+}
@@ -1,9 +1,11 @@
 pub mod auth;
 pub mod capture;
 pub mod context;
+pub mod cookies;
 pub mod extract;
 pub mod files;
 pub mod input;
+pub mod intercept;
 pub mod interact;
 pub mod navigation;
 pub mod observe;
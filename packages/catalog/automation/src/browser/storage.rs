@@ -686,3 +686,160 @@ impl NodeLogic for BrowserGetAllStorageNode {
         ))
     }
 }
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct BrowserSetAllStorageNode {}
+
+impl BrowserSetAllStorageNode {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for BrowserSetAllStorageNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "browser_set_all_storage",
+            "Set All Storage",
+            "Writes key-value pairs into localStorage or sessionStorage, e.g. to restore data read with Get All Storage",
+            "Automation/Browser/Storage",
+        );
+        node.add_icon("/flow/icons/browser.svg");
+
+        node.set_scores(
+            flow_like::flow::node::NodeScores::new()
+                .set_privacy(3)
+                .set_security(4)
+                .set_performance(8)
+                .set_governance(5)
+                .set_reliability(9)
+                .set_cost(10)
+                .build(),
+        );
+        node.set_only_offline(true);
+
+        node.add_input_pin("exec_in", "▶", "Trigger", VariableType::Execution);
+
+        node.add_input_pin(
+            "session",
+            "Session",
+            "Automation session",
+            VariableType::Struct,
+        )
+        .set_schema::<AutomationSession>();
+
+        node.add_input_pin(
+            "storage_type",
+            "Storage Type",
+            "Which storage to write",
+            VariableType::String,
+        )
+        .set_options(
+            flow_like::flow::pin::PinOptions::new()
+                .set_valid_values(vec!["local".to_string(), "session".to_string()])
+                .build(),
+        )
+        .set_default_value(Some(json!("local")));
+
+        node.add_input_pin(
+            "data",
+            "Data",
+            "JSON object of keys and values. Values that are not strings are stored as JSON",
+            VariableType::Struct,
+        )
+        .set_default_value(Some(json!({})));
+
+        node.add_input_pin(
+            "clear_first",
+            "Clear First",
+            "Remove existing items before writing, so the storage matches the data exactly",
+            VariableType::Boolean,
+        )
+        .set_default_value(Some(json!(false)));
+
+        node.add_output_pin("exec_out", "▶", "Continue", VariableType::Execution);
+
+        node.add_output_pin(
+            "session_out",
+            "Session",
+            "Automation session (pass-through)",
+            VariableType::Struct,
+        )
+        .set_schema::<AutomationSession>();
+
+        node.add_output_pin(
+            "count",
+            "Count",
+            "Number of items written",
+            VariableType::Integer,
+        );
+
+        node
+    }
+
+    #[cfg(feature = "execute")]
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+
+        let session: AutomationSession = context.evaluate_pin("session").await?;
+        let storage_type: String = context.evaluate_pin("storage_type").await?;
+        let data: Value = context.evaluate_pin("data").await?;
+        let clear_first: bool = context.evaluate_pin("clear_first").await?;
+
+        let Value::Object(data) = data else {
+            return Err(flow_like_types::anyhow!(
+                "Storage data must be a JSON object of keys and values"
+            ));
+        };
+        let items: flow_like_types::json::Map<String, Value> = data
+            .into_iter()
+            .map(|(key, value)| {
+                let value = match value {
+                    Value::String(text) => text,
+                    other => other.to_string(),
+                };
+                (key, Value::String(value))
+            })
+            .collect();
+        let count = items.len() as i64;
+
+        let driver = session.get_browser_driver_and_switch(context).await?;
+
+        let storage_name = if storage_type == "session" {
+            "sessionStorage"
+        } else {
+            "localStorage"
+        };
+
+        // Keys and values are passed as arguments, so they need no escaping
+        let script = format!(
+            r#"
+            if (arguments[1]) {{ {storage_name}.clear(); }}
+            for (const [key, value] of Object.entries(arguments[0])) {{
+                {storage_name}.setItem(key, value);
+            }}
+            "#,
+            storage_name = storage_name
+        );
+
+        driver
+            .execute(&script, vec![Value::Object(items), json!(clear_first)])
+            .await
+            .map_err(|e| flow_like_types::anyhow!("Failed to write storage: {}", e))?;
+
+        context.set_pin_value("session_out", json!(session)).await?;
+        context.set_pin_value("count", json!(count)).await?;
+        context.activate_exec_pin("exec_out").await?;
+
+        Ok(())
+    }
+
+    #[cfg(not(feature = "execute"))]
+    async fn run(&self, _context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        Err(flow_like_types::anyhow!(
+            "Browser automation requires the 'execute' feature"
+        ))
+    }
+}
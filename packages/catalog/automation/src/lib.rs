@@ -24,5 +24,5 @@ pub mod session;
 pub mod vision;
 
 pub fn get_catalog() -> Vec<Arc<dyn NodeLogic>> {
-    flow_like_catalog_core::get_catalog()
+    flow_like_catalog_core::get_package_catalog(env!("CARGO_PKG_NAME"))
 }
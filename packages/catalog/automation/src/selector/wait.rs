@@ -0,0 +1,379 @@
+use crate::types::fingerprints::{ElementFingerprint, TargetRef};
+use crate::types::handles::AutomationSession;
+use crate::types::selectors::{Selector, SelectorKind};
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic},
+    pin::PinOptions,
+    variable::VariableType,
+};
+use flow_like_types::{async_trait, json::json};
+
+/// How far an element has to be loaded before the wait ends.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ElementReadiness {
+    /// Attached to the DOM
+    Present,
+    /// Displayed and with a non-empty size
+    Visible,
+    /// Visible and enabled
+    Clickable,
+}
+
+impl ElementReadiness {
+    pub fn parse(value: &str) -> flow_like_types::Result<Self> {
+        match value {
+            "Present" => Ok(Self::Present),
+            "Visible" => Ok(Self::Visible),
+            "Clickable" => Ok(Self::Clickable),
+            other => Err(flow_like_types::anyhow!(
+                "Unknown readiness state \"{}\"",
+                other
+            )),
+        }
+    }
+
+    pub fn is_met(&self, displayed: bool, enabled: bool, width: f64, height: f64) -> bool {
+        let visible = displayed && width > 0.0 && height > 0.0;
+        match self {
+            Self::Present => true,
+            Self::Visible => visible,
+            Self::Clickable => visible && enabled,
+        }
+    }
+}
+
+fn xpath_literal(value: &str) -> String {
+    if !value.contains('\'') {
+        return format!("'{}'", value);
+    }
+    if !value.contains('"') {
+        return format!("\"{}\"", value);
+    }
+    let parts: Vec<String> = value
+        .split('\'')
+        .map(|part| format!("'{}'", part))
+        .collect();
+    format!("concat({})", parts.join(", \"'\", "))
+}
+
+/// Translates a selector into a WebDriver locator, as `(is_xpath, expression)`.
+/// Image selectors have no DOM equivalent and yield `None`.
+pub fn to_locator(selector: &Selector) -> Option<(bool, String)> {
+    let value = selector.value.as_str();
+    let attribute = |name: &str| Some((true, format!("//*[@{}={}]", name, xpath_literal(value))));
+    match selector.kind {
+        SelectorKind::Css => Some((false, value.to_string())),
+        SelectorKind::Xpath => Some((true, value.to_string())),
+        SelectorKind::TestId => attribute("data-testid"),
+        SelectorKind::AriaLabel => attribute("aria-label"),
+        SelectorKind::Placeholder => attribute("placeholder"),
+        SelectorKind::AltText => attribute("alt"),
+        SelectorKind::Title => attribute("title"),
+        SelectorKind::Role => attribute("role"),
+        SelectorKind::TextExact => Some((
+            true,
+            format!("//*[normalize-space(.)={}]", xpath_literal(value)),
+        )),
+        // The deepest element containing the text, not all of its ancestors
+        SelectorKind::Text => Some((
+            true,
+            format!(
+                "//*[contains(., {0}) and not(*[contains(., {0})])]",
+                xpath_literal(value)
+            ),
+        )),
+        SelectorKind::Image => None,
+    }
+}
+
+/// Polls the selectors in order until one resolves to an element in the requested state.
+/// Returns `None` once the timeout has elapsed.
+#[cfg(feature = "execute")]
+pub async fn wait_for_element(
+    driver: &thirtyfour::WebDriver,
+    selectors: &[Selector],
+    readiness: ElementReadiness,
+    poll_interval: std::time::Duration,
+    timeout: std::time::Duration,
+) -> Option<TargetRef> {
+    use crate::types::fingerprints::MatchStrategy;
+    use flow_like_catalog_core::BoundingBox;
+    use std::time::Instant;
+    use thirtyfour::By;
+
+    let start = Instant::now();
+    let mut attempts = 0u32;
+    loop {
+        for selector in selectors {
+            let Some((is_xpath, expression)) = to_locator(selector) else {
+                continue;
+            };
+            let by = if is_xpath {
+                By::XPath(expression)
+            } else {
+                By::Css(expression)
+            };
+            attempts += 1;
+
+            // The element may be replaced between the lookup and the checks, which
+            // surfaces as an error and simply counts as not ready yet
+            let Ok(element) = driver.find(by).await else {
+                continue;
+            };
+            let displayed = element.is_displayed().await.unwrap_or(false);
+            let enabled = element.is_enabled().await.unwrap_or(false);
+            let Ok(rect) = element.rect().await else {
+                continue;
+            };
+            if !readiness.is_met(displayed, enabled, rect.width, rect.height) {
+                continue;
+            }
+
+            return Some(TargetRef {
+                fingerprint_id: String::new(),
+                resolved_selector: selector.clone(),
+                bounding_box: BoundingBox {
+                    x1: rect.x as f32,
+                    y1: rect.y as f32,
+                    x2: (rect.x + rect.width) as f32,
+                    y2: (rect.y + rect.height) as f32,
+                    ..Default::default()
+                },
+                confidence: selector.confidence.unwrap_or(1.0),
+                strategy_used: MatchStrategy::Dom,
+                fallback_attempts: attempts - 1,
+                resolution_time_ms: start.elapsed().as_millis() as u64,
+            });
+        }
+
+        let elapsed = start.elapsed();
+        if elapsed >= timeout {
+            return None;
+        }
+        tokio::time::sleep(poll_interval.min(timeout - elapsed)).await;
+    }
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct WaitForElementNode {}
+
+impl WaitForElementNode {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for WaitForElementNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "selector_wait_for_element",
+            "Wait For Element",
+            "Polls until an element matching a CSS or XPath selector, or any selector of a fingerprint, is present, visible or clickable. Continues on the Timeout pin if it never gets there",
+            "Automation/Selector",
+        );
+        node.add_icon("/flow/icons/selector.svg");
+
+        node.set_scores(
+            flow_like::flow::node::NodeScores::new()
+                .set_privacy(5)
+                .set_security(6)
+                .set_performance(6)
+                .set_governance(6)
+                .set_reliability(9)
+                .set_cost(10)
+                .build(),
+        );
+        node.set_only_offline(true);
+
+        node.add_input_pin("exec_in", "▶", "Trigger", VariableType::Execution);
+
+        node.add_input_pin(
+            "session",
+            "Session",
+            "Automation session",
+            VariableType::Struct,
+        )
+        .set_schema::<AutomationSession>();
+
+        node.add_input_pin(
+            "selector_kind",
+            "Selector Kind",
+            "How to find the element",
+            VariableType::String,
+        )
+        .set_options(
+            PinOptions::new()
+                .set_valid_values(vec![
+                    "Css".to_string(),
+                    "Xpath".to_string(),
+                    "Fingerprint".to_string(),
+                ])
+                .build(),
+        )
+        .set_default_value(Some(json!("Css")));
+
+        node.add_input_pin(
+            "selector",
+            "Selector",
+            "CSS selector or XPath expression, unused for fingerprints",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("")));
+
+        node.add_input_pin(
+            "fingerprint",
+            "Fingerprint",
+            "Fingerprint whose selectors are tried in priority order",
+            VariableType::Struct,
+        )
+        .set_schema::<ElementFingerprint>();
+
+        node.add_input_pin(
+            "state",
+            "State",
+            "Present: in the DOM. Visible: displayed with a size. Clickable: visible and enabled",
+            VariableType::String,
+        )
+        .set_options(
+            PinOptions::new()
+                .set_valid_values(vec![
+                    "Present".to_string(),
+                    "Visible".to_string(),
+                    "Clickable".to_string(),
+                ])
+                .build(),
+        )
+        .set_default_value(Some(json!("Visible")));
+
+        node.add_input_pin(
+            "poll_interval_ms",
+            "Poll Interval (ms)",
+            "Time between two checks",
+            VariableType::Integer,
+        )
+        .set_default_value(Some(json!(100)));
+
+        node.add_input_pin(
+            "timeout_ms",
+            "Timeout (ms)",
+            "Maximum time to wait",
+            VariableType::Integer,
+        )
+        .set_default_value(Some(json!(10000)));
+
+        node.add_output_pin("exec_out", "▶", "Ready", VariableType::Execution);
+
+        node.add_output_pin(
+            "timeout",
+            "Timeout",
+            "The element did not reach the state in time",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "session_out",
+            "Session",
+            "Automation session (pass-through)",
+            VariableType::Struct,
+        )
+        .set_schema::<AutomationSession>();
+
+        node.add_output_pin(
+            "element",
+            "Element",
+            "The resolved element with the selector that matched and its bounding box",
+            VariableType::Struct,
+        )
+        .set_schema::<TargetRef>();
+
+        node.add_output_pin(
+            "waited_ms",
+            "Waited (ms)",
+            "How long the wait took",
+            VariableType::Integer,
+        );
+
+        node
+    }
+
+    #[cfg(feature = "execute")]
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        use std::time::{Duration, Instant};
+
+        context.deactivate_exec_pin("exec_out").await?;
+        context.deactivate_exec_pin("timeout").await?;
+
+        let session: AutomationSession = context.evaluate_pin("session").await?;
+        let selector_kind: String = context.evaluate_pin("selector_kind").await?;
+        let state: String = context.evaluate_pin("state").await?;
+        let poll_interval_ms: i64 = context.evaluate_pin("poll_interval_ms").await?;
+        let timeout_ms: i64 = context.evaluate_pin("timeout_ms").await?;
+
+        let readiness = ElementReadiness::parse(&state)?;
+        let (selectors, fingerprint_id) = match selector_kind.as_str() {
+            "Fingerprint" => {
+                let fingerprint: ElementFingerprint = context.evaluate_pin("fingerprint").await?;
+                let selectors: Vec<Selector> =
+                    fingerprint.selectors.iter_by_priority().cloned().collect();
+                (selectors, fingerprint.id)
+            }
+            kind => {
+                let selector: String = context.evaluate_pin("selector").await?;
+                if selector.trim().is_empty() {
+                    return Err(flow_like_types::anyhow!("Selector is empty"));
+                }
+                let selector = match kind {
+                    "Xpath" => Selector::xpath(selector),
+                    _ => Selector::css(selector),
+                };
+                (vec![selector], String::new())
+            }
+        };
+        if selectors
+            .iter()
+            .all(|selector| to_locator(selector).is_none())
+        {
+            return Err(flow_like_types::anyhow!(
+                "None of the selectors can be looked up in the DOM"
+            ));
+        }
+
+        let driver = session.get_browser_driver_and_switch(context).await?;
+
+        let start = Instant::now();
+        let element = wait_for_element(
+            &driver,
+            &selectors,
+            readiness,
+            Duration::from_millis(poll_interval_ms.max(10) as u64),
+            Duration::from_millis(timeout_ms.max(0) as u64),
+        )
+        .await;
+        let waited_ms = start.elapsed().as_millis() as i64;
+
+        context.set_pin_value("session_out", json!(session)).await?;
+        context.set_pin_value("waited_ms", json!(waited_ms)).await?;
+        match element {
+            Some(mut element) => {
+                element.fingerprint_id = fingerprint_id;
+                context.set_pin_value("element", json!(element)).await?;
+                context.activate_exec_pin("exec_out").await?;
+            }
+            None => {
+                context.activate_exec_pin("timeout").await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg(not(feature = "execute"))]
+    async fn run(&self, _context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        Err(flow_like_types::anyhow!(
+            "Browser automation requires the 'execute' feature"
+        ))
+    }
+}
@@ -1,3 +1,5 @@
+pub mod tabs;
+
 use crate::types::handles::AutomationSession;
 use flow_like::flow::{
     execution::context::ExecutionContext,
@@ -0,0 +1,610 @@
+use crate::browser::intercept::glob_to_regex;
+use crate::types::handles::AutomationSession;
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic},
+    pin::ValueType,
+    variable::VariableType,
+};
+use flow_like_types::{async_trait, json::json, regex::Regex};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "execute")]
+use crate::types::handles::TabList;
+#[cfg(feature = "execute")]
+use std::sync::Arc;
+#[cfg(feature = "execute")]
+use thirtyfour::{WebDriver, WindowHandle};
+
+/// A tab of the browser attached to the session
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq)]
+pub struct BrowserTab {
+    /// Position in opening order, stable until an earlier tab closes
+    pub index: usize,
+    pub handle: String,
+    pub url: String,
+    pub title: String,
+    /// Whether browser nodes currently act on this tab
+    pub active: bool,
+}
+
+/// Picks a tab by index. A non-empty URL glob like `*/checkout*` first narrows the tabs down
+/// to the matching ones. Negative indices count from the end, so -1 is the newest tab.
+pub fn find_tab(tabs: &[BrowserTab], index: i64, url_pattern: &str) -> Option<usize> {
+    let candidates: Vec<&BrowserTab> = if url_pattern.trim().is_empty() {
+        tabs.iter().collect()
+    } else {
+        let pattern = Regex::new(&glob_to_regex(url_pattern.trim())).ok()?;
+        tabs.iter()
+            .filter(|tab| pattern.is_match(&tab.url))
+            .collect()
+    };
+
+    let position = if index < 0 {
+        candidates
+            .len()
+            .checked_sub(index.unsigned_abs() as usize)?
+    } else {
+        index as usize
+    };
+    candidates.get(position).map(|tab| tab.index)
+}
+
+/// Tab to activate once `closed` is gone: the one opened before it, which is usually the
+/// page that opened it, or the new first tab.
+pub fn next_active_after_close(closed: usize, remaining: usize) -> Option<usize> {
+    if remaining == 0 {
+        return None;
+    }
+    Some(closed.saturating_sub(1).min(remaining - 1))
+}
+
+/// Reconciles the tracked tabs with the windows the browser reports and returns the handles
+/// of tabs opened by the page since the last call.
+#[cfg(feature = "execute")]
+pub async fn sync_tabs(
+    driver: &WebDriver,
+    tabs: &mut TabList,
+) -> flow_like_types::Result<Vec<String>> {
+    let open: Vec<String> = driver
+        .windows()
+        .await
+        .map_err(|e| flow_like_types::anyhow!("Failed to list browser windows: {}", e))?
+        .into_iter()
+        .map(|handle| handle.to_string())
+        .collect();
+    Ok(tabs.sync(&open))
+}
+
+/// Reads URL and title of every tab. WebDriver only sees the focused window, so this visits
+/// each tab and focuses `active` again afterwards.
+#[cfg(feature = "execute")]
+pub async fn describe_tabs(
+    driver: &WebDriver,
+    tabs: &TabList,
+    active: &str,
+) -> flow_like_types::Result<Vec<BrowserTab>> {
+    let mut described = Vec::with_capacity(tabs.handles().len());
+    for (index, handle) in tabs.handles().iter().enumerate() {
+        switch_to(driver, handle).await?;
+        let url = driver
+            .current_url()
+            .await
+            .map(|url| url.to_string())
+            .unwrap_or_default();
+        let title = driver.title().await.unwrap_or_default();
+        described.push(BrowserTab {
+            index,
+            handle: handle.clone(),
+            url,
+            title,
+            active: handle == active,
+        });
+    }
+
+    if tabs.position(active).is_some() {
+        switch_to(driver, active).await?;
+    }
+    Ok(described)
+}
+
+#[cfg(feature = "execute")]
+async fn switch_to(driver: &WebDriver, handle: &str) -> flow_like_types::Result<()> {
+    driver
+        .switch_to_window(WindowHandle::from(handle.to_string()))
+        .await
+        .map_err(|e| flow_like_types::anyhow!("Failed to switch to tab {}: {}", handle, e))
+}
+
+/// Syncs the tracked tabs of a session and describes them. When the active tab was closed
+/// behind the session's back, the first remaining tab becomes active.
+#[cfg(feature = "execute")]
+async fn load_tabs(
+    context: &mut ExecutionContext,
+    session: &mut AutomationSession,
+) -> flow_like_types::Result<(Arc<WebDriver>, Vec<BrowserTab>)> {
+    let driver = session.get_browser_driver(context).await?;
+    let tabs = session.get_tabs(context).await?;
+    let mut tabs = tabs.lock().await;
+
+    let adopted = sync_tabs(&driver, &mut tabs).await?;
+    if !adopted.is_empty() {
+        context.log_message(
+            &format!("Tracking {} tab(s) opened by the page", adopted.len()),
+            flow_like::flow::execution::LogLevel::Debug,
+        );
+    }
+
+    let focused = match &session.current_window_handle {
+        Some(handle) => Some(handle.clone()),
+        None => driver.window().await.ok().map(|handle| handle.to_string()),
+    };
+    let (active, fallback) = match focused.filter(|handle| tabs.position(handle).is_some()) {
+        Some(handle) => (handle, false),
+        None => {
+            let first = tabs
+                .handles()
+                .first()
+                .cloned()
+                .ok_or_else(|| flow_like_types::anyhow!("The browser has no open tabs"))?;
+            (first, true)
+        }
+    };
+
+    let described = describe_tabs(&driver, &tabs, &active).await?;
+    drop(tabs);
+
+    if fallback {
+        activate(context, session, &driver, &active).await?;
+    }
+    Ok((driver, described))
+}
+
+/// Focuses a tab and makes it the one later browser nodes act on
+#[cfg(feature = "execute")]
+async fn activate(
+    context: &mut ExecutionContext,
+    session: &mut AutomationSession,
+    driver: &WebDriver,
+    handle: &str,
+) -> flow_like_types::Result<()> {
+    switch_to(driver, handle).await?;
+    session
+        .set_current_page(context, WindowHandle::from(handle.to_string()))
+        .await
+}
+
+fn add_session_input(node: &mut Node) {
+    node.add_input_pin(
+        "session",
+        "Session",
+        "Automation session with browser attached",
+        VariableType::Struct,
+    )
+    .set_schema::<AutomationSession>();
+}
+
+fn add_tab_selector_inputs(node: &mut Node, action: &str) {
+    node.add_input_pin(
+        "index",
+        "Index",
+        &format!(
+            "Tab to {} in opening order, -1 for the newest. Counts among the matching tabs when a URL pattern is set",
+            action
+        ),
+        VariableType::Integer,
+    )
+    .set_default_value(Some(json!(0)));
+
+    node.add_input_pin(
+        "url_pattern",
+        "URL Pattern",
+        "Optional URL glob like */checkout*, * matches anything",
+        VariableType::String,
+    )
+    .set_default_value(Some(json!("")));
+}
+
+fn add_session_output(node: &mut Node, description: &str) {
+    node.add_output_pin("exec_out", "▶", "Continue", VariableType::Execution);
+
+    node.add_output_pin("session_out", "Session", description, VariableType::Struct)
+        .set_schema::<AutomationSession>();
+}
+
+fn set_tab_scores(node: &mut Node) {
+    node.set_scores(
+        flow_like::flow::node::NodeScores::new()
+            .set_privacy(4)
+            .set_security(5)
+            .set_performance(8)
+            .set_governance(6)
+            .set_reliability(8)
+            .set_cost(9)
+            .build(),
+    );
+    node.set_only_offline(true);
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct ListTabsNode {}
+
+impl ListTabsNode {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for ListTabsNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "browser_list_tabs",
+            "List Tabs",
+            "Lists the open tabs of the browser, including tabs the page opened itself",
+            "Automation/Browser/Tabs",
+        );
+        node.add_icon("/flow/icons/browser.svg");
+        set_tab_scores(&mut node);
+
+        node.add_input_pin("exec_in", "▶", "Trigger", VariableType::Execution);
+        add_session_input(&mut node);
+
+        add_session_output(&mut node, "Automation session (pass-through)");
+
+        node.add_output_pin(
+            "tabs",
+            "Tabs",
+            "Open tabs in opening order",
+            VariableType::Struct,
+        )
+        .set_schema::<BrowserTab>()
+        .set_value_type(ValueType::Array);
+
+        node.add_output_pin(
+            "active_index",
+            "Active Index",
+            "Index of the tab browser nodes act on",
+            VariableType::Integer,
+        );
+
+        node.add_output_pin(
+            "count",
+            "Count",
+            "Number of open tabs",
+            VariableType::Integer,
+        );
+
+        node
+    }
+
+    #[cfg(feature = "execute")]
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+
+        let mut session: AutomationSession = context.evaluate_pin("session").await?;
+        let (_, tabs) = load_tabs(context, &mut session).await?;
+
+        let active_index = tabs.iter().position(|tab| tab.active).unwrap_or(0);
+        context
+            .set_pin_value("active_index", json!(active_index))
+            .await?;
+        context.set_pin_value("count", json!(tabs.len())).await?;
+        context.set_pin_value("tabs", json!(tabs)).await?;
+        context.set_pin_value("session_out", json!(session)).await?;
+        context.activate_exec_pin("exec_out").await?;
+
+        Ok(())
+    }
+
+    #[cfg(not(feature = "execute"))]
+    async fn run(&self, _context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        Err(flow_like_types::anyhow!(
+            "Browser automation requires the 'execute' feature"
+        ))
+    }
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct SwitchTabNode {}
+
+impl SwitchTabNode {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for SwitchTabNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "browser_switch_tab",
+            "Switch Tab",
+            "Makes another tab the one browser nodes act on. Waits for tabs the page is still opening, e.g. after clicking a link with target=\"_blank\"",
+            "Automation/Browser/Tabs",
+        );
+        node.add_icon("/flow/icons/browser.svg");
+        set_tab_scores(&mut node);
+
+        node.add_input_pin("exec_in", "▶", "Trigger", VariableType::Execution);
+        add_session_input(&mut node);
+        add_tab_selector_inputs(&mut node, "switch to");
+
+        node.add_input_pin(
+            "timeout_ms",
+            "Timeout (ms)",
+            "How long to wait for a matching tab to open",
+            VariableType::Integer,
+        )
+        .set_default_value(Some(json!(5000)));
+
+        add_session_output(&mut node, "Automation session acting on the selected tab");
+
+        node.add_output_pin("tab", "Tab", "The now active tab", VariableType::Struct)
+            .set_schema::<BrowserTab>();
+
+        node
+    }
+
+    #[cfg(feature = "execute")]
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        use std::time::{Duration, Instant};
+
+        context.deactivate_exec_pin("exec_out").await?;
+
+        let mut session: AutomationSession = context.evaluate_pin("session").await?;
+        let index: i64 = context.evaluate_pin("index").await?;
+        let url_pattern: String = context.evaluate_pin("url_pattern").await?;
+        let timeout_ms: i64 = context.evaluate_pin("timeout_ms").await?;
+
+        let deadline = Instant::now() + Duration::from_millis(timeout_ms.max(0) as u64);
+        let (driver, mut tab) = loop {
+            let (driver, tabs) = load_tabs(context, &mut session).await?;
+            if let Some(found) = find_tab(&tabs, index, &url_pattern) {
+                break (driver, tabs[found].clone());
+            }
+            if Instant::now() >= deadline {
+                return Err(flow_like_types::anyhow!(
+                    "No tab #{} matching '{}' among: {}",
+                    index,
+                    url_pattern,
+                    tabs.iter()
+                        .map(|tab| tab.url.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+            }
+            flow_like_types::tokio::time::sleep(Duration::from_millis(250)).await;
+        };
+
+        activate(context, &mut session, &driver, &tab.handle).await?;
+        tab.active = true;
+
+        context.set_pin_value("tab", json!(tab)).await?;
+        context.set_pin_value("session_out", json!(session)).await?;
+        context.activate_exec_pin("exec_out").await?;
+
+        Ok(())
+    }
+
+    #[cfg(not(feature = "execute"))]
+    async fn run(&self, _context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        Err(flow_like_types::anyhow!(
+            "Browser automation requires the 'execute' feature"
+        ))
+    }
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct OpenTabNode {}
+
+impl OpenTabNode {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for OpenTabNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "browser_open_tab",
+            "Open Tab",
+            "Opens a new tab, optionally navigates it, and makes it active",
+            "Automation/Browser/Tabs",
+        );
+        node.add_icon("/flow/icons/browser.svg");
+        set_tab_scores(&mut node);
+
+        node.add_input_pin("exec_in", "▶", "Trigger", VariableType::Execution);
+        add_session_input(&mut node);
+
+        node.add_input_pin(
+            "url",
+            "URL",
+            "Page to open, leave empty for a blank tab",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("")));
+
+        add_session_output(&mut node, "Automation session acting on the new tab");
+
+        node.add_output_pin("tab", "Tab", "The new tab", VariableType::Struct)
+            .set_schema::<BrowserTab>();
+
+        node
+    }
+
+    #[cfg(feature = "execute")]
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+
+        let mut session: AutomationSession = context.evaluate_pin("session").await?;
+        let url: String = context.evaluate_pin("url").await?;
+
+        // Adopt tabs opened so far, so the new one is indexed after them
+        load_tabs(context, &mut session).await?;
+
+        let driver = session.get_browser_driver(context).await?;
+        let handle = driver
+            .new_tab()
+            .await
+            .map_err(|e| flow_like_types::anyhow!("Failed to open tab: {}", e))?
+            .to_string();
+        let index = {
+            let tabs = session.get_tabs(context).await?;
+            let mut tabs = tabs.lock().await;
+            tabs.push(&handle);
+            tabs.handles().len() - 1
+        };
+        activate(context, &mut session, &driver, &handle).await?;
+
+        if !url.trim().is_empty() {
+            driver
+                .goto(url.trim())
+                .await
+                .map_err(|e| flow_like_types::anyhow!("Failed to navigate new tab: {}", e))?;
+        }
+
+        let tab = BrowserTab {
+            index,
+            handle,
+            url: driver
+                .current_url()
+                .await
+                .map(|url| url.to_string())
+                .unwrap_or_default(),
+            title: driver.title().await.unwrap_or_default(),
+            active: true,
+        };
+
+        context.set_pin_value("tab", json!(tab)).await?;
+        context.set_pin_value("session_out", json!(session)).await?;
+        context.activate_exec_pin("exec_out").await?;
+
+        Ok(())
+    }
+
+    #[cfg(not(feature = "execute"))]
+    async fn run(&self, _context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        Err(flow_like_types::anyhow!(
+            "Browser automation requires the 'execute' feature"
+        ))
+    }
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct CloseTabNode {}
+
+impl CloseTabNode {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for CloseTabNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "browser_close_tab",
+            "Close Tab",
+            "Closes a tab. If it was active, the tab opened before it becomes active",
+            "Automation/Browser/Tabs",
+        );
+        node.add_icon("/flow/icons/browser.svg");
+        set_tab_scores(&mut node);
+
+        node.add_input_pin("exec_in", "▶", "Trigger", VariableType::Execution);
+        add_session_input(&mut node);
+
+        node.add_input_pin(
+            "active_tab",
+            "Active Tab",
+            "Close the active tab instead of selecting one by index or URL pattern",
+            VariableType::Boolean,
+        )
+        .set_default_value(Some(json!(true)));
+
+        add_tab_selector_inputs(&mut node, "close");
+
+        add_session_output(&mut node, "Automation session acting on the remaining tabs");
+
+        node.add_output_pin(
+            "remaining",
+            "Remaining",
+            "Number of tabs still open",
+            VariableType::Integer,
+        );
+
+        node
+    }
+
+    #[cfg(feature = "execute")]
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+
+        let mut session: AutomationSession = context.evaluate_pin("session").await?;
+        let active_tab: bool = context.evaluate_pin("active_tab").await?;
+        let index: i64 = context.evaluate_pin("index").await?;
+        let url_pattern: String = context.evaluate_pin("url_pattern").await?;
+
+        let (driver, tabs) = load_tabs(context, &mut session).await?;
+        let active = tabs
+            .iter()
+            .find(|tab| tab.active)
+            .map(|tab| tab.handle.clone());
+        let closing = if active_tab {
+            tabs.iter().position(|tab| tab.active)
+        } else {
+            find_tab(&tabs, index, &url_pattern)
+        }
+        .ok_or_else(|| {
+            flow_like_types::anyhow!("No tab #{} matching '{}' to close", index, url_pattern)
+        })?;
+        let closing = tabs[closing].handle.clone();
+
+        switch_to(&driver, &closing).await?;
+        driver
+            .close_window()
+            .await
+            .map_err(|e| flow_like_types::anyhow!("Failed to close tab: {}", e))?;
+
+        let (removed, remaining) = {
+            let tabs = session.get_tabs(context).await?;
+            let mut tabs = tabs.lock().await;
+            let removed = tabs.remove(&closing);
+            (removed, tabs.handles().to_vec())
+        };
+
+        let next = if active.as_deref() == Some(closing.as_str()) {
+            removed
+                .and_then(|closed| next_active_after_close(closed, remaining.len()))
+                .map(|next| remaining[next].clone())
+        } else {
+            active
+        };
+        if let Some(next) = next {
+            activate(context, &mut session, &driver, &next).await?;
+        }
+
+        context
+            .set_pin_value("remaining", json!(remaining.len()))
+            .await?;
+        context.set_pin_value("session_out", json!(session)).await?;
+        context.activate_exec_pin("exec_out").await?;
+
+        Ok(())
+    }
+
+    #[cfg(not(feature = "execute"))]
+    async fn run(&self, _context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        Err(flow_like_types::anyhow!(
+            "Browser automation requires the 'execute' feature"
+        ))
+    }
+}
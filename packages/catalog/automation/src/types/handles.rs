@@ -93,11 +93,55 @@ pub struct AutomationSession {
     pub current_window_handle: Option<String>,
 }
 
+/// Window handles of the browser tabs, in the order they were opened
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TabList {
+    handles: Vec<String>,
+}
+
+impl TabList {
+    pub fn handles(&self) -> &[String] {
+        &self.handles
+    }
+
+    pub fn position(&self, handle: &str) -> Option<usize> {
+        self.handles.iter().position(|known| known == handle)
+    }
+
+    pub fn push(&mut self, handle: &str) {
+        if self.position(handle).is_none() {
+            self.handles.push(handle.to_string());
+        }
+    }
+
+    /// Forgets a tab, returning the index it had
+    pub fn remove(&mut self, handle: &str) -> Option<usize> {
+        let index = self.position(handle)?;
+        self.handles.remove(index);
+        Some(index)
+    }
+
+    /// Reconciles with the handles the browser reports. Tabs the page opened itself, e.g.
+    /// through `window.open` or `target="_blank"` links, are appended and returned, closed
+    /// ones are dropped.
+    pub fn sync(&mut self, open: &[String]) -> Vec<String> {
+        self.handles.retain(|handle| open.contains(handle));
+        let adopted: Vec<String> = open
+            .iter()
+            .filter(|handle| !self.handles.contains(handle))
+            .cloned()
+            .collect();
+        self.handles.extend(adopted.iter().cloned());
+        adopted
+    }
+}
+
 #[cfg(feature = "execute")]
 pub struct AutomationSessionWrapper {
     pub autogui: Arc<flow_like_types::sync::Mutex<rustautogui::RustAutoGui>>,
     pub browser_driver: Option<Arc<thirtyfour::WebDriver>>,
     pub current_window_handle: Option<thirtyfour::WindowHandle>,
+    pub tabs: Arc<flow_like_types::sync::Mutex<TabList>>,
 }
 
 #[cfg(feature = "execute")]
@@ -135,6 +179,7 @@ impl AutomationSession {
             autogui: Arc::new(flow_like_types::sync::Mutex::new(autogui)),
             browser_driver: None,
             current_window_handle: None,
+            tabs: Arc::new(flow_like_types::sync::Mutex::new(TabList::default())),
         };
         ctx.cache
             .write()
@@ -239,6 +284,25 @@ impl AutomationSession {
             .ok_or_else(|| flow_like_types::anyhow!("No browser attached to this session"))
     }
 
+    /// Get the tabs tracked for the attached browser
+    #[cfg(feature = "execute")]
+    pub async fn get_tabs(
+        &self,
+        ctx: &ExecutionContext,
+    ) -> flow_like_types::Result<Arc<flow_like_types::sync::Mutex<TabList>>> {
+        let cache = ctx.cache.read().await;
+        let wrapper = cache
+            .get(&self.session_ref)
+            .ok_or_else(|| flow_like_types::anyhow!("Automation session not found in cache"))?;
+        let wrapper = wrapper
+            .as_any()
+            .downcast_ref::<AutomationSessionWrapper>()
+            .ok_or_else(|| {
+                flow_like_types::anyhow!("Could not downcast to AutomationSessionWrapper")
+            })?;
+        Ok(wrapper.tabs.clone())
+    }
+
     /// Check if browser is attached
     pub fn has_browser(&self) -> bool {
         self.browser_type.is_some()
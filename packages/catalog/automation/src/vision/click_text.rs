@@ -0,0 +1,525 @@
+use crate::types::handles::AutomationSession;
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic},
+    pin::PinOptions,
+    variable::VariableType,
+};
+use flow_like_catalog_onnx::{
+    NodeOnnxSession,
+    ocr::{OcrRegion, TextRegion},
+};
+use flow_like_types::{async_trait, json::json};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Text found on screen that matches the query
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq)]
+pub struct TextMatch {
+    /// Full text of the OCR region the query was found in
+    pub text: String,
+    /// Recognition confidence of that region
+    pub confidence: f32,
+    /// Box around the matched characters [x, y, width, height], in captured pixels
+    pub bbox: [f32; 4],
+}
+
+/// Finds `query` in the OCR regions, in reading order (top to bottom, then left to right).
+/// Case and repeated whitespace are ignored. A query found inside a longer region gets a box
+/// narrowed to its characters, assuming roughly even character widths.
+pub fn find_text_matches(
+    regions: &[OcrRegion],
+    query: &str,
+    exact: bool,
+    min_confidence: f32,
+) -> Vec<TextMatch> {
+    let query = normalize(query);
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    let mut matches: Vec<TextMatch> = reading_order(regions)
+        .into_iter()
+        .filter(|ocr| ocr.text.confidence >= min_confidence)
+        .filter_map(|ocr| {
+            let text = normalize(&ocr.text.text);
+            let [x, y, width, height] = ocr.region.bbox;
+            let bbox = if exact {
+                if text != query {
+                    return None;
+                }
+                ocr.region.bbox
+            } else {
+                let start = text.find(&query)?;
+                let total = text.chars().count() as f32;
+                let before = text[..start].chars().count() as f32;
+                let length = query.chars().count() as f32;
+                [
+                    x + width * before / total,
+                    y,
+                    width * length / total,
+                    height,
+                ]
+            };
+            Some(TextMatch {
+                text: ocr.text.text.clone(),
+                confidence: ocr.text.confidence,
+                bbox,
+            })
+        })
+        .collect();
+    matches.dedup();
+    matches
+}
+
+fn normalize(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Regions whose vertical centers are within half a line height share a row
+fn reading_order(regions: &[OcrRegion]) -> Vec<&OcrRegion> {
+    let center_y = |region: &TextRegion| region.bbox[1] + region.bbox[3] / 2.0;
+
+    let mut sorted: Vec<&OcrRegion> = regions.iter().collect();
+    sorted.sort_by(|a, b| center_y(&a.region).total_cmp(&center_y(&b.region)));
+
+    let mut rows: Vec<Vec<&OcrRegion>> = Vec::new();
+    for ocr in sorted {
+        match rows.last_mut() {
+            Some(row)
+                if center_y(&ocr.region) - center_y(&row[0].region)
+                    <= row[0].region.bbox[3] / 2.0 =>
+            {
+                row.push(ocr)
+            }
+            _ => rows.push(vec![ocr]),
+        }
+    }
+
+    rows.into_iter()
+        .flat_map(|mut row| {
+            row.sort_by(|a, b| a.region.bbox[0].total_cmp(&b.region.bbox[0]));
+            row
+        })
+        .collect()
+}
+
+/// Screen coordinate to click for a match. `origin` is where the captured area starts in
+/// physical pixels, `scale` converts physical pixels to the logical points the mouse uses.
+pub fn click_point(bbox: [f32; 4], origin: (u32, u32), scale: (f64, f64)) -> (i32, i32) {
+    let [x, y, width, height] = bbox;
+    let center_x = origin.0 as f64 + x as f64 + width as f64 / 2.0;
+    let center_y = origin.1 as f64 + y as f64 + height as f64 / 2.0;
+    (
+        (center_x * scale.0).round() as i32,
+        (center_y * scale.1).round() as i32,
+    )
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct ClickTextNode {}
+
+impl ClickTextNode {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for ClickTextNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "vision_click_text",
+            "Click Text",
+            "Finds text on screen with OCR and clicks it. Works for apps that draw text on a canvas, where selectors find nothing",
+            "Automation/Vision",
+        );
+        node.add_icon("/flow/icons/vision.svg");
+
+        node.set_scores(
+            flow_like::flow::node::NodeScores::new()
+                .set_privacy(2)
+                .set_security(3)
+                .set_performance(4)
+                .set_governance(5)
+                .set_reliability(6)
+                .set_cost(8)
+                .build(),
+        );
+        node.set_only_offline(true);
+
+        node.add_input_pin("exec_in", "▶", "Trigger", VariableType::Execution);
+
+        node.add_input_pin(
+            "session",
+            "Session",
+            "Automation session handle",
+            VariableType::Struct,
+        )
+        .set_schema::<AutomationSession>();
+
+        node.add_input_pin(
+            "detection_model",
+            "Detection Model",
+            "ONNX text detection model, as used by Text Detection",
+            VariableType::Struct,
+        )
+        .set_schema::<NodeOnnxSession>()
+        .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node.add_input_pin(
+            "recognition_model",
+            "Recognition Model",
+            "ONNX text recognition model, as used by Text Recognition",
+            VariableType::Struct,
+        )
+        .set_schema::<NodeOnnxSession>()
+        .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node.add_input_pin("query", "Text", "Text to click", VariableType::String);
+
+        node.add_input_pin(
+            "exact",
+            "Exact",
+            "Only match whole text regions instead of text contained in them",
+            VariableType::Boolean,
+        )
+        .set_default_value(Some(json!(false)));
+
+        node.add_input_pin(
+            "min_confidence",
+            "Min Confidence",
+            "Minimum recognition confidence (0.0-1.0) of a match",
+            VariableType::Float,
+        )
+        .set_default_value(Some(json!(0.6)));
+
+        node.add_input_pin(
+            "nth",
+            "Nth Match",
+            "Which match to click in reading order, 0 is the first",
+            VariableType::Integer,
+        )
+        .set_default_value(Some(json!(0)));
+
+        node.add_input_pin(
+            "region_x",
+            "Region X",
+            "Left edge of the area to search, in screen coordinates",
+            VariableType::Integer,
+        )
+        .set_default_value(Some(json!(0)));
+        node.add_input_pin(
+            "region_y",
+            "Region Y",
+            "Top edge of the area to search, in screen coordinates",
+            VariableType::Integer,
+        )
+        .set_default_value(Some(json!(0)));
+        node.add_input_pin(
+            "region_width",
+            "Region Width",
+            "Width of the area to search, 0 for the whole screen",
+            VariableType::Integer,
+        )
+        .set_default_value(Some(json!(0)));
+        node.add_input_pin(
+            "region_height",
+            "Region Height",
+            "Height of the area to search, 0 for the whole screen",
+            VariableType::Integer,
+        )
+        .set_default_value(Some(json!(0)));
+
+        node.add_input_pin(
+            "button",
+            "Button",
+            "Mouse button to click",
+            VariableType::String,
+        )
+        .set_options(
+            PinOptions::new()
+                .set_valid_values(vec![
+                    "left".to_string(),
+                    "right".to_string(),
+                    "middle".to_string(),
+                ])
+                .build(),
+        )
+        .set_default_value(Some(json!("left")));
+
+        node.add_output_pin(
+            "exec_out",
+            "▶",
+            "Continue after clicking",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "not_found",
+            "Not Found",
+            "Triggered without clicking when no confident match exists, e.g. to fall back to another locator",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "session_out",
+            "Session",
+            "Automation session handle (pass-through)",
+            VariableType::Struct,
+        )
+        .set_schema::<AutomationSession>();
+
+        node.add_output_pin("match", "Match", "The clicked match", VariableType::Struct)
+            .set_schema::<TextMatch>();
+
+        node.add_output_pin("x", "X", "Clicked X coordinate", VariableType::Integer);
+        node.add_output_pin("y", "Y", "Clicked Y coordinate", VariableType::Integer);
+
+        node.add_output_pin(
+            "match_count",
+            "Match Count",
+            "Number of confident matches on screen",
+            VariableType::Integer,
+        );
+
+        node
+    }
+
+    #[cfg(feature = "execute")]
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        use enigo::{Button, Coordinate, Mouse};
+        use flow_like_catalog_onnx::ocr::{DEFAULT_CHARSET, detect_text, recognize_text};
+        use flow_like_types::image::DynamicImage;
+
+        // Settings of the default OCR models, see the Text Detection and Text Recognition nodes
+        const DETECTION_THRESHOLD: f32 = 0.3;
+        const DETECTION_SIZE: u32 = 640;
+        const RECOGNITION_HEIGHT: u32 = 32;
+
+        context.deactivate_exec_pin("exec_out").await?;
+        context.deactivate_exec_pin("not_found").await?;
+
+        let session: AutomationSession = context.evaluate_pin("session").await?;
+        let detection_model: NodeOnnxSession = context.evaluate_pin("detection_model").await?;
+        let recognition_model: NodeOnnxSession = context.evaluate_pin("recognition_model").await?;
+        let query: String = context.evaluate_pin("query").await?;
+        let exact: bool = context.evaluate_pin("exact").await?;
+        let min_confidence: f64 = context.evaluate_pin("min_confidence").await?;
+        let nth: i64 = context.evaluate_pin("nth").await?;
+        let region_x: i64 = context.evaluate_pin("region_x").await?;
+        let region_y: i64 = context.evaluate_pin("region_y").await?;
+        let region_width: i64 = context.evaluate_pin("region_width").await?;
+        let region_height: i64 = context.evaluate_pin("region_height").await?;
+        let button_str: String = context.evaluate_pin("button").await?;
+
+        context.set_pin_value("session_out", json!(session)).await?;
+
+        let monitor = xcap::Monitor::all()
+            .ok()
+            .and_then(|monitors| monitors.into_iter().next())
+            .ok_or_else(|| flow_like_types::anyhow!("No monitor found"))?;
+        let screen = monitor
+            .capture_image()
+            .map_err(|e| flow_like_types::anyhow!("Failed to capture screen: {}", e))?;
+
+        // xcap captures physical pixels, the region and the mouse use logical points
+        let (physical_w, physical_h) = screen.dimensions();
+        let (logical_w, logical_h) =
+            crate::types::screen_match::physical_to_logical(physical_w, physical_h);
+        let scale = (
+            logical_w as f64 / physical_w.max(1) as f64,
+            logical_h as f64 / physical_h.max(1) as f64,
+        );
+
+        let origin = (
+            ((region_x.max(0) as f64 / scale.0) as u32).min(physical_w),
+            ((region_y.max(0) as f64 / scale.1) as u32).min(physical_h),
+        );
+        let size = (
+            if region_width > 0 {
+                ((region_width as f64 / scale.0) as u32).min(physical_w - origin.0)
+            } else {
+                physical_w - origin.0
+            },
+            if region_height > 0 {
+                ((region_height as f64 / scale.1) as u32).min(physical_h - origin.1)
+            } else {
+                physical_h - origin.1
+            },
+        );
+        let capture = DynamicImage::ImageRgba8(screen).crop_imm(origin.0, origin.1, size.0, size.1);
+
+        let detected = {
+            let wrapper = detection_model.get_session(context).await?;
+            let mut guard = wrapper.lock().await;
+            detect_text(
+                &mut guard.session,
+                &capture,
+                DETECTION_THRESHOLD,
+                DETECTION_SIZE,
+            )?
+        };
+
+        let mut regions = Vec::with_capacity(detected.len());
+        {
+            let wrapper = recognition_model.get_session(context).await?;
+            let mut guard = wrapper.lock().await;
+            for region in detected {
+                let [x, y, width, height] = region.bbox;
+                if width < 1.0 || height < 1.0 {
+                    continue;
+                }
+                let crop = capture.crop_imm(x as u32, y as u32, width as u32, height as u32);
+                let text = recognize_text(
+                    &mut guard.session,
+                    &crop,
+                    DEFAULT_CHARSET,
+                    RECOGNITION_HEIGHT,
+                )?;
+                regions.push(OcrRegion { region, text });
+            }
+        }
+
+        let matches = find_text_matches(&regions, &query, exact, min_confidence as f32);
+        context
+            .set_pin_value("match_count", json!(matches.len() as i64))
+            .await?;
+
+        let Some(found) = matches.get(nth.max(0) as usize) else {
+            context.log_message(
+                &format!(
+                    "'{}' not found on screen ({} confident matches, wanted #{}), read: {:?}",
+                    query,
+                    matches.len(),
+                    nth,
+                    regions.iter().map(|ocr| &ocr.text.text).collect::<Vec<_>>()
+                ),
+                flow_like::flow::execution::LogLevel::Warn,
+            );
+            context.activate_exec_pin("not_found").await?;
+            return Ok(());
+        };
+
+        let (x, y) = click_point(found.bbox, origin, scale);
+
+        let button = match button_str.as_str() {
+            "right" => Button::Right,
+            "middle" => Button::Middle,
+            _ => Button::Left,
+        };
+
+        {
+            let mut enigo = session.create_enigo()?;
+            enigo
+                .move_mouse(x, y, Coordinate::Abs)
+                .map_err(|e| flow_like_types::anyhow!("Failed to move mouse: {}", e))?;
+
+            std::thread::sleep(std::time::Duration::from_millis(session.click_delay_ms));
+
+            enigo
+                .button(button, enigo::Direction::Click)
+                .map_err(|e| flow_like_types::anyhow!("Failed to click mouse: {}", e))?;
+        }
+
+        context.set_pin_value("match", json!(found)).await?;
+        context.set_pin_value("x", json!(x)).await?;
+        context.set_pin_value("y", json!(y)).await?;
+        context.activate_exec_pin("exec_out").await?;
+
+        Ok(())
+    }
+
+    #[cfg(not(feature = "execute"))]
+    async fn run(&self, _context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        Err(flow_like_types::anyhow!(
+            "Vision automation requires the 'execute' feature"
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flow_like_catalog_onnx::ocr::RecognizedText;
+
+    fn ocr(text: &str, confidence: f32, bbox: [f32; 4]) -> OcrRegion {
+        let [x, y, w, h] = bbox;
+        OcrRegion {
+            region: TextRegion {
+                bbox,
+                polygon: [[x, y], [x + w, y], [x + w, y + h], [x, y + h]],
+                confidence: 0.9,
+            },
+            text: RecognizedText {
+                text: text.to_string(),
+                confidence,
+                char_confidences: Vec::new(),
+            },
+        }
+    }
+
+    /// What OCR reads from a synthetic 800x600 dialog: a menu bar, two buttons
+    /// sharing a row with slightly different baselines, and a blurry footer
+    fn dialog() -> Vec<OcrRegion> {
+        vec![
+            ocr("Save As", 0.93, [500.0, 502.0, 140.0, 28.0]),
+            ocr("File", 0.97, [10.0, 8.0, 40.0, 20.0]),
+            ocr("Edit", 0.95, [70.0, 10.0, 40.0, 20.0]),
+            ocr("Save", 0.91, [360.0, 500.0, 80.0, 30.0]),
+            ocr("Save changes?", 0.88, [300.0, 250.0, 260.0, 40.0]),
+            ocr("Saved 2 min ago", 0.31, [10.0, 570.0, 150.0, 20.0]),
+        ]
+    }
+
+    #[test]
+    fn matches_in_reading_order() {
+        let matches = find_text_matches(&dialog(), "save", false, 0.6);
+        let texts: Vec<_> = matches.iter().map(|m| m.text.as_str()).collect();
+
+        assert_eq!(texts, vec!["Save changes?", "Save", "Save As"]);
+    }
+
+    #[test]
+    fn exact_match_clicks_button_center() {
+        let matches = find_text_matches(&dialog(), " SAVE ", true, 0.6);
+
+        assert_eq!(matches.len(), 1);
+        assert_eq!(click_point(matches[0].bbox, (0, 0), (1.0, 1.0)), (400, 515));
+    }
+
+    #[test]
+    fn nth_match_and_substring_box() {
+        let matches = find_text_matches(&dialog(), "as", false, 0.6);
+        assert_eq!(matches.len(), 1);
+
+        // "As" is the last 2 of 7 characters in "Save As"
+        assert_eq!(matches[0].bbox, [600.0, 502.0, 40.0, 28.0]);
+        assert_eq!(click_point(matches[0].bbox, (0, 0), (1.0, 1.0)), (620, 516));
+
+        let matches = find_text_matches(&dialog(), "save", false, 0.6);
+        assert_eq!(click_point(matches[2].bbox, (0, 0), (1.0, 1.0)), (520, 516));
+    }
+
+    #[test]
+    fn region_offset_and_display_scale() {
+        let matches = find_text_matches(&dialog(), "Edit", true, 0.6);
+
+        // Searched area started at physical (200, 100) on a 2x display
+        assert_eq!(
+            click_point(matches[0].bbox, (200, 100), (0.5, 0.5)),
+            (145, 60)
+        );
+    }
+
+    #[test]
+    fn low_confidence_text_is_not_matched() {
+        assert!(find_text_matches(&dialog(), "min ago", false, 0.6).is_empty());
+        assert_eq!(find_text_matches(&dialog(), "min ago", false, 0.3).len(), 1);
+        assert!(find_text_matches(&dialog(), "", false, 0.0).is_empty());
+    }
+}
@@ -1,4 +1,6 @@
 pub mod click_template;
+pub mod click_text;
 pub mod find_template;
 pub mod screen;
+pub mod screenshot_diff;
 pub mod wait_template;
@@ -0,0 +1,436 @@
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic},
+    pin::ValueType,
+    variable::VariableType,
+};
+use flow_like_catalog_core::{BoundingBox, FlowPath, NodeImage};
+use flow_like_types::{
+    async_trait,
+    image::{self, DynamicImage, GrayImage, Luma, Rgba, RgbaImage, imageops::FilterType},
+    imageproc::{
+        distance_transform::Norm,
+        drawing::draw_hollow_rect_mut,
+        morphology::dilate,
+        rect::Rect,
+        region_labelling::{Connectivity, connected_components},
+    },
+    json::json,
+};
+
+const CHANGED: Luma<u8> = Luma([255]);
+const HIGHLIGHT: Rgba<u8> = Rgba([255, 0, 0, 255]);
+
+pub struct DiffOptions {
+    /// Largest per-channel difference (0-255) that still counts as unchanged
+    pub threshold: u8,
+    /// Changed pixels closer than this many pixels end up in the same region
+    pub merge_distance: u8,
+    /// Areas of the baseline that are not compared
+    pub ignore_regions: Vec<BoundingBox>,
+}
+
+pub struct ScreenshotDiff {
+    /// Share of compared pixels that changed, 0-100
+    pub percent_changed: f64,
+    pub regions: Vec<BoundingBox>,
+    /// The current image with changed pixels tinted and regions outlined
+    pub annotated: RgbaImage,
+}
+
+/// Compares `current` against `baseline`. A current image of a different size, e.g. from a
+/// display with another scale factor, is resized to the baseline first.
+pub fn diff_images(
+    baseline: &RgbaImage,
+    current: &RgbaImage,
+    options: &DiffOptions,
+) -> ScreenshotDiff {
+    let (width, height) = baseline.dimensions();
+    let current = if current.dimensions() == (width, height) {
+        current.clone()
+    } else {
+        image::imageops::resize(current, width, height, FilterType::Triangle)
+    };
+
+    let ignored = |x: u32, y: u32| {
+        let (x, y) = (x as f32, y as f32);
+        options
+            .ignore_regions
+            .iter()
+            .any(|region| x >= region.x1 && x < region.x2 && y >= region.y1 && y < region.y2)
+    };
+
+    let mut mask = GrayImage::new(width, height);
+    let mut compared = 0u64;
+    let mut changed = 0u64;
+    for (x, y, before) in baseline.enumerate_pixels() {
+        if ignored(x, y) {
+            continue;
+        }
+        compared += 1;
+        let after = current.get_pixel(x, y);
+        let difference = before
+            .0
+            .iter()
+            .zip(after.0.iter())
+            .map(|(a, b)| a.abs_diff(*b))
+            .max()
+            .unwrap_or(0);
+        if difference > options.threshold {
+            mask.put_pixel(x, y, CHANGED);
+            changed += 1;
+        }
+    }
+
+    let regions = changed_regions(&mask, options.merge_distance);
+
+    let mut annotated = current;
+    for (x, y, pixel) in mask.enumerate_pixels() {
+        if *pixel == CHANGED {
+            let original = annotated.get_pixel_mut(x, y);
+            original.0[0] = ((original.0[0] as u16 + 255) / 2) as u8;
+            original.0[1] /= 2;
+            original.0[2] /= 2;
+        }
+    }
+    for region in &regions {
+        let (x, y, w, h) = region.x1y1wh();
+        draw_hollow_rect_mut(
+            &mut annotated,
+            Rect::at(x as i32, y as i32).of_size(w.max(1), h.max(1)),
+            HIGHLIGHT,
+        );
+    }
+
+    ScreenshotDiff {
+        percent_changed: if compared == 0 {
+            0.0
+        } else {
+            changed as f64 / compared as f64 * 100.0
+        },
+        regions,
+        annotated,
+    }
+}
+
+/// Bounding boxes of connected changed areas, scored by the share of changed pixels inside
+fn changed_regions(mask: &GrayImage, merge_distance: u8) -> Vec<BoundingBox> {
+    // Grouping runs on a dilated mask so nearby fragments (e.g. the letters of a changed word)
+    // form one region, while the boxes are fitted to the actually changed pixels
+    let grouped = if merge_distance > 0 {
+        dilate(mask, Norm::LInf, merge_distance)
+    } else {
+        mask.clone()
+    };
+    let labels = connected_components(&grouped, Connectivity::Eight, Luma([0u8]));
+
+    // label -> (min x, min y, max x, max y, changed pixels)
+    let mut bounds: Vec<Option<(u32, u32, u32, u32, u64)>> = Vec::new();
+    for (x, y, pixel) in mask.enumerate_pixels() {
+        if *pixel != CHANGED {
+            continue;
+        }
+        let label = labels.get_pixel(x, y).0[0] as usize;
+        if bounds.len() <= label {
+            bounds.resize(label + 1, None);
+        }
+        bounds[label] = Some(match bounds[label] {
+            None => (x, y, x, y, 1),
+            Some((x1, y1, x2, y2, count)) => {
+                (x1.min(x), y1.min(y), x2.max(x), y2.max(y), count + 1)
+            }
+        });
+    }
+
+    bounds
+        .into_iter()
+        .flatten()
+        .map(|(x1, y1, x2, y2, count)| {
+            let area = ((x2 - x1 + 1) * (y2 - y1 + 1)) as f32;
+            BoundingBox {
+                x1: x1 as f32,
+                y1: y1 as f32,
+                x2: (x2 + 1) as f32,
+                y2: (y2 + 1) as f32,
+                score: count as f32 / area,
+                class_idx: 0,
+                class_name: Some("changed".to_string()),
+            }
+        })
+        .collect()
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct ScreenshotDiffNode {}
+
+impl ScreenshotDiffNode {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for ScreenshotDiffNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "vision_screenshot_diff",
+            "Screenshot Diff",
+            "Compares a screenshot against a baseline and reports which regions changed, for visual regression checks",
+            "Automation/Vision",
+        );
+        node.add_icon("/flow/icons/vision.svg");
+
+        node.set_scores(
+            flow_like::flow::node::NodeScores::new()
+                .set_privacy(9)
+                .set_security(9)
+                .set_performance(6)
+                .set_governance(8)
+                .set_reliability(8)
+                .set_cost(10)
+                .build(),
+        );
+
+        node.add_input_pin("exec_in", "▶", "Trigger", VariableType::Execution);
+
+        node.add_input_pin(
+            "baseline_file",
+            "Baseline File",
+            "Stored baseline screenshot, used instead of Baseline when set",
+            VariableType::Struct,
+        )
+        .set_schema::<FlowPath>();
+
+        node.add_input_pin(
+            "baseline",
+            "Baseline",
+            "Baseline image",
+            VariableType::Struct,
+        )
+        .set_schema::<NodeImage>();
+
+        node.add_input_pin(
+            "current",
+            "Current",
+            "Fresh screenshot to compare",
+            VariableType::Struct,
+        )
+        .set_schema::<NodeImage>();
+
+        node.add_input_pin(
+            "threshold",
+            "Threshold",
+            "Per-channel difference (0-255) below which a pixel counts as unchanged, absorbs anti-aliasing and compression noise",
+            VariableType::Integer,
+        )
+        .set_default_value(Some(json!(32)));
+
+        node.add_input_pin(
+            "merge_distance",
+            "Merge Distance",
+            "Changes closer than this many pixels are reported as one region",
+            VariableType::Integer,
+        )
+        .set_default_value(Some(json!(8)));
+
+        node.add_input_pin(
+            "ignore_regions",
+            "Ignore Regions",
+            "Areas in baseline pixels to skip, like clocks or animated content",
+            VariableType::Struct,
+        )
+        .set_schema::<BoundingBox>()
+        .set_value_type(ValueType::Array)
+        .set_default_value(Some(json!([])));
+
+        node.add_output_pin("exec_out", "▶", "Continue", VariableType::Execution);
+
+        node.add_output_pin(
+            "changed",
+            "Changed",
+            "Whether any compared pixel changed",
+            VariableType::Boolean,
+        );
+
+        node.add_output_pin(
+            "percent_changed",
+            "Percent Changed",
+            "Share of compared pixels that changed, 0-100",
+            VariableType::Float,
+        );
+
+        node.add_output_pin(
+            "regions",
+            "Regions",
+            "Bounding boxes of the changed areas",
+            VariableType::Struct,
+        )
+        .set_schema::<BoundingBox>()
+        .set_value_type(ValueType::Array);
+
+        node.add_output_pin(
+            "diff_image",
+            "Diff Image",
+            "Current screenshot with changes tinted red and regions outlined",
+            VariableType::Struct,
+        )
+        .set_schema::<NodeImage>();
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+
+        let baseline_file: Option<FlowPath> = context.evaluate_pin("baseline_file").await.ok();
+        let baseline = match baseline_file {
+            Some(file) => {
+                let bytes = file.get(context, false).await?;
+                image::load_from_memory(&bytes)
+                    .map_err(|e| {
+                        flow_like_types::anyhow!("Failed to decode baseline image: {}", e)
+                    })?
+                    .to_rgba8()
+            }
+            None => {
+                let baseline: NodeImage = context.evaluate_pin("baseline").await?;
+                let baseline = baseline.get_image(context).await?;
+                baseline.lock().await.to_rgba8()
+            }
+        };
+
+        let current: NodeImage = context.evaluate_pin("current").await?;
+        let current = current.get_image(context).await?;
+        let current = current.lock().await.to_rgba8();
+
+        let threshold: i64 = context.evaluate_pin("threshold").await?;
+        let merge_distance: i64 = context.evaluate_pin("merge_distance").await?;
+        let ignore_regions: Vec<BoundingBox> = context.evaluate_pin("ignore_regions").await?;
+
+        let options = DiffOptions {
+            threshold: threshold.clamp(0, 255) as u8,
+            merge_distance: merge_distance.clamp(0, 255) as u8,
+            ignore_regions,
+        };
+        let diff = diff_images(&baseline, &current, &options);
+
+        let diff_image = NodeImage::new(context, DynamicImage::ImageRgba8(diff.annotated)).await;
+
+        context
+            .set_pin_value("changed", json!(diff.percent_changed > 0.0))
+            .await?;
+        context
+            .set_pin_value("percent_changed", json!(diff.percent_changed))
+            .await?;
+        context
+            .set_pin_value("regions", json!(diff.regions))
+            .await?;
+        context
+            .set_pin_value("diff_image", json!(diff_image))
+            .await?;
+        context.activate_exec_pin("exec_out").await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GRAY: Rgba<u8> = Rgba([200, 200, 200, 255]);
+    const BLACK: Rgba<u8> = Rgba([0, 0, 0, 255]);
+
+    fn screen() -> RgbaImage {
+        RgbaImage::from_pixel(100, 80, GRAY)
+    }
+
+    fn options(ignore_regions: Vec<BoundingBox>) -> DiffOptions {
+        DiffOptions {
+            threshold: 32,
+            merge_distance: 4,
+            ignore_regions,
+        }
+    }
+
+    fn paint(image: &mut RgbaImage, x: u32, y: u32, width: u32, height: u32) {
+        for py in y..y + height {
+            for px in x..x + width {
+                image.put_pixel(px, py, BLACK);
+            }
+        }
+    }
+
+    #[test]
+    fn identical_images_have_no_diff() {
+        let diff = diff_images(&screen(), &screen(), &options(vec![]));
+
+        assert_eq!(diff.percent_changed, 0.0);
+        assert!(diff.regions.is_empty());
+        assert_eq!(diff.annotated, screen());
+    }
+
+    #[test]
+    fn noise_below_threshold_is_ignored() {
+        let current = RgbaImage::from_pixel(100, 80, Rgba([210, 195, 200, 255]));
+        let diff = diff_images(&screen(), &current, &options(vec![]));
+
+        assert_eq!(diff.percent_changed, 0.0);
+    }
+
+    #[test]
+    fn single_change_has_tight_bounding_box() {
+        let mut current = screen();
+        paint(&mut current, 20, 10, 10, 5);
+
+        let diff = diff_images(&screen(), &current, &options(vec![]));
+
+        assert!((diff.percent_changed - 50.0 / 8000.0 * 100.0).abs() < 1e-9);
+        assert_eq!(diff.regions.len(), 1);
+        assert_eq!(diff.regions[0].x1y1wh(), (20, 10, 10, 5));
+        assert_eq!(diff.regions[0].score, 1.0);
+        assert_ne!(diff.annotated, current);
+    }
+
+    #[test]
+    fn distant_changes_are_separate_regions() {
+        let mut current = screen();
+        paint(&mut current, 5, 5, 3, 3);
+        paint(&mut current, 7, 10, 3, 3);
+        paint(&mut current, 80, 60, 4, 4);
+
+        let diff = diff_images(&screen(), &current, &options(vec![]));
+
+        let mut boxes: Vec<_> = diff.regions.iter().map(BoundingBox::x1y1wh).collect();
+        boxes.sort();
+        assert_eq!(boxes, vec![(5, 5, 5, 8), (80, 60, 4, 4)]);
+    }
+
+    #[test]
+    fn masked_change_is_ignored() {
+        let mut current = screen();
+        paint(&mut current, 90, 0, 10, 8);
+
+        let clock = BoundingBox {
+            x1: 85.0,
+            y1: 0.0,
+            x2: 100.0,
+            y2: 10.0,
+            ..Default::default()
+        };
+        let diff = diff_images(&screen(), &current, &options(vec![clock]));
+
+        assert_eq!(diff.percent_changed, 0.0);
+        assert!(diff.regions.is_empty());
+    }
+
+    #[test]
+    fn different_sizes_are_aligned_to_the_baseline() {
+        let current = RgbaImage::from_pixel(200, 160, GRAY);
+        let diff = diff_images(&screen(), &current, &options(vec![]));
+
+        assert_eq!(diff.annotated.dimensions(), (100, 80));
+        assert_eq!(diff.percent_changed, 0.0);
+    }
+}
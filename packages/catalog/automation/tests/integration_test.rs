@@ -1003,3 +1003,877 @@ mod template_matching_accuracy_tests {
         );
     }
 }
+
+mod network_intercept_tests {
+    use flow_like_catalog_automation::browser::intercept::{
+        InterceptAction, InterceptRule, glob_to_regex, install_script, wait_for_response_script,
+    };
+    use flow_like_types::regex::Regex;
+
+    fn matches(pattern: &str, url: &str) -> bool {
+        Regex::new(&glob_to_regex(pattern)).unwrap().is_match(url)
+    }
+
+    #[test]
+    fn test_glob_to_regex() {
+        assert!(matches(
+            "*/api/data*",
+            "http://localhost:8080/api/data?page=2"
+        ));
+        assert!(matches("https://example.com/*", "https://example.com/a/b"));
+        assert!(matches("*/v?/users", "https://x.io/v2/users"));
+        assert!(!matches("*/api/data", "http://localhost/api/data/extra"));
+        assert!(!matches(
+            "https://example.com/*",
+            "https://example.com.evil.io/"
+        ));
+        // Regex characters in URLs are taken literally
+        assert!(matches("*/search?q=(a+b)", "https://x.io/search?q=(a+b)"));
+        assert!(!matches("*/file.json", "https://x.io/fileXjson"));
+    }
+
+    #[test]
+    fn test_rule_defaults() {
+        let rule: InterceptRule =
+            serde_json::from_value(serde_json::json!({ "url_pattern": "*/api/*" })).unwrap();
+        assert_eq!(rule.action, InterceptAction::Capture);
+        assert_eq!(rule.status, 200);
+        assert!(rule.method.is_empty());
+
+        let rule: InterceptRule = serde_json::from_value(serde_json::json!({
+            "url_pattern": "*/api/*",
+            "action": "fulfill",
+            "status": 503,
+        }))
+        .unwrap();
+        assert_eq!(rule.action, InterceptAction::Fulfill);
+        assert_eq!(rule.status, 503);
+    }
+
+    #[test]
+    fn test_install_script_validates_rules() {
+        let rule = |url_pattern: &str, status: u16| InterceptRule {
+            url_pattern: url_pattern.to_string(),
+            method: "post".to_string(),
+            action: InterceptAction::Fulfill,
+            status,
+            headers: Default::default(),
+            body: "{}".to_string(),
+        };
+
+        let script = install_script(&[rule("*/api/*", 201)]).unwrap();
+        assert!(script.contains("\"method\":\"POST\""));
+        assert!(!script.contains("__MAX_RESPONSES__"));
+        assert!(install_script(&[]).is_ok());
+
+        assert!(install_script(&[rule("", 200)]).is_err());
+        assert!(install_script(&[rule("*", 0)]).is_err());
+        assert!(wait_for_response_script("", "").is_err());
+    }
+}
+
+/// Runs the interception scripts in a real browser against a local server.
+/// Needs a running chromedriver: `cargo test --features execute -- --ignored`
+#[cfg(feature = "execute")]
+mod network_intercept_live_tests {
+    use flow_like_catalog_automation::browser::intercept::{
+        InterceptAction, InterceptRule, InterceptedResponse, install_script, take_responses_script,
+        wait_for_response_script,
+    };
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+    use thirtyfour::prelude::*;
+
+    const PAGE: &str = "<html><body>intercept test</body></html>";
+
+    /// Serves `/` and `/api/data`, counting the requests that reach `/api/flaky`.
+    fn start_server() -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+        let flaky_hits = Arc::new(AtomicUsize::new(0));
+        let hits = flaky_hits.clone();
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { continue };
+                let mut buffer = [0u8; 4096];
+                let read = stream.read(&mut buffer).unwrap_or(0);
+                let request = String::from_utf8_lossy(&buffer[..read]);
+                let path = request.split_whitespace().nth(1).unwrap_or("/");
+
+                let (status, content_type, body) = match path {
+                    "/api/data" => ("200 OK", "application/json", r#"{"value":42}"#),
+                    "/api/flaky" => {
+                        hits.fetch_add(1, Ordering::SeqCst);
+                        ("500 Internal Server Error", "text/plain", "down")
+                    }
+                    _ => ("200 OK", "text/html", PAGE),
+                };
+                let response = format!(
+                    "HTTP/1.1 {}\r\nContent-Type: {}\r\nX-Test: yes\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    content_type,
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+
+        (address, flaky_hits)
+    }
+
+    async fn start_driver() -> WebDriver {
+        let url =
+            std::env::var("WEBDRIVER_URL").unwrap_or_else(|_| "http://localhost:9515".to_string());
+        let mut caps = DesiredCapabilities::chrome();
+        caps.set_headless().unwrap();
+        WebDriver::new(url, caps)
+            .await
+            .expect("chromedriver must be running")
+    }
+
+    async fn responses(driver: &WebDriver) -> Vec<InterceptedResponse> {
+        let result = driver
+            .execute(&take_responses_script(true), vec![])
+            .await
+            .unwrap();
+        serde_json::from_value(result.json().clone()).unwrap()
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_capture_and_stub_responses() {
+        let (address, flaky_hits) = start_server();
+        let driver = start_driver().await;
+        driver.goto(format!("{}/", address)).await.unwrap();
+
+        let rules = vec![
+            InterceptRule {
+                url_pattern: "*/api/data".to_string(),
+                method: String::new(),
+                action: InterceptAction::Capture,
+                status: 200,
+                headers: Default::default(),
+                body: String::new(),
+            },
+            InterceptRule {
+                url_pattern: "*/api/flaky".to_string(),
+                method: "GET".to_string(),
+                action: InterceptAction::Fulfill,
+                status: 200,
+                headers: [("Content-Type".to_string(), "application/json".to_string())].into(),
+                body: r#"{"stubbed":true}"#.to_string(),
+            },
+        ];
+        driver
+            .execute(&install_script(&rules).unwrap(), vec![])
+            .await
+            .unwrap();
+
+        let fetched = driver
+            .execute(
+                "return Promise.all(['/api/data', '/api/flaky'].map(u => fetch(u).then(r => r.text())));",
+                vec![],
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            fetched.json(),
+            &serde_json::json!([r#"{"value":42}"#, r#"{"stubbed":true}"#])
+        );
+
+        let xhr = driver
+            .execute(
+                r#"
+                return new Promise(resolve => {
+                    const xhr = new XMLHttpRequest();
+                    xhr.open('GET', '/api/flaky');
+                    xhr.onload = () => resolve([xhr.status, xhr.responseText, xhr.getResponseHeader('content-type')]);
+                    xhr.send();
+                });
+                "#,
+                vec![],
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            xhr.json(),
+            &serde_json::json!([200, r#"{"stubbed":true}"#, "application/json"])
+        );
+
+        // The captured body is read asynchronously after the fetch resolves
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        let recorded = responses(&driver).await;
+        assert_eq!(recorded.len(), 3);
+
+        let data = recorded
+            .iter()
+            .find(|r| r.url.ends_with("/api/data"))
+            .unwrap();
+        assert_eq!(data.status, 200);
+        assert_eq!(data.body, r#"{"value":42}"#);
+        assert_eq!(data.headers.get("x-test").map(String::as_str), Some("yes"));
+        assert!(!data.stubbed);
+
+        assert!(
+            recorded
+                .iter()
+                .filter(|r| r.url.ends_with("/api/flaky"))
+                .all(|r| r.stubbed)
+        );
+        assert_eq!(flaky_hits.load(Ordering::SeqCst), 0);
+        assert!(responses(&driver).await.is_empty());
+
+        // An empty rule list lets requests through again
+        driver
+            .execute(&install_script(&[]).unwrap(), vec![])
+            .await
+            .unwrap();
+        driver
+            .execute("return fetch('/api/flaky').then(r => r.status);", vec![])
+            .await
+            .unwrap();
+        assert_eq!(flaky_hits.load(Ordering::SeqCst), 1);
+
+        driver.quit().await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_wait_for_response() {
+        let (address, _) = start_server();
+        let driver = start_driver().await;
+        driver.goto(format!("{}/", address)).await.unwrap();
+
+        let script = wait_for_response_script("*/api/data*", "GET").unwrap();
+        let first = driver.execute(&script, vec![]).await.unwrap();
+        assert!(first.json().is_null());
+
+        driver
+            .execute(
+                "setTimeout(() => fetch('/api/data?delayed=1'), 300);",
+                vec![],
+            )
+            .await
+            .unwrap();
+
+        let response = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                let result = driver.execute(&script, vec![]).await.unwrap();
+                if let Ok(response) =
+                    serde_json::from_value::<InterceptedResponse>(result.json().clone())
+                {
+                    return response;
+                }
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+        })
+        .await
+        .expect("response should arrive before the timeout");
+
+        assert!(response.url.ends_with("/api/data?delayed=1"));
+        assert_eq!(response.method, "GET");
+        assert_eq!(response.body, r#"{"value":42}"#);
+
+        // A returned response is consumed
+        let again = driver.execute(&script, vec![]).await.unwrap();
+        assert!(again.json().is_null());
+
+        driver.quit().await.unwrap();
+    }
+}
+
+mod wait_for_element_tests {
+    use flow_like_catalog_automation::selector::wait::{ElementReadiness, to_locator};
+    use flow_like_catalog_automation::types::selectors::{Selector, SelectorKind};
+
+    fn selector(kind: SelectorKind, value: &str) -> Selector {
+        Selector {
+            kind,
+            value: value.to_string(),
+            confidence: None,
+            scope: None,
+        }
+    }
+
+    #[test]
+    fn test_readiness_states() {
+        assert!(ElementReadiness::Present.is_met(false, false, 0.0, 0.0));
+
+        assert!(ElementReadiness::Visible.is_met(true, false, 10.0, 10.0));
+        assert!(!ElementReadiness::Visible.is_met(true, true, 0.0, 10.0));
+        assert!(!ElementReadiness::Visible.is_met(false, true, 10.0, 10.0));
+
+        assert!(ElementReadiness::Clickable.is_met(true, true, 10.0, 10.0));
+        assert!(!ElementReadiness::Clickable.is_met(true, false, 10.0, 10.0));
+
+        assert_eq!(
+            ElementReadiness::parse("Clickable").unwrap(),
+            ElementReadiness::Clickable
+        );
+        assert!(ElementReadiness::parse("clickable").is_err());
+    }
+
+    #[test]
+    fn test_selector_locators() {
+        assert_eq!(
+            to_locator(&Selector::css("#late")),
+            Some((false, "#late".to_string()))
+        );
+        assert_eq!(
+            to_locator(&Selector::test_id("submit")),
+            Some((true, "//*[@data-testid='submit']".to_string()))
+        );
+        assert_eq!(
+            to_locator(&selector(SelectorKind::TextExact, "Sign in")),
+            Some((true, "//*[normalize-space(.)='Sign in']".to_string()))
+        );
+        assert_eq!(to_locator(&selector(SelectorKind::Image, "logo.png")), None);
+    }
+
+    #[test]
+    fn test_locators_quote_values() {
+        let (_, xpath) = to_locator(&selector(SelectorKind::AriaLabel, "Ada's page")).unwrap();
+        assert_eq!(xpath, "//*[@aria-label=\"Ada's page\"]");
+
+        let (_, xpath) = to_locator(&selector(SelectorKind::Title, "say \"it's\"")).unwrap();
+        assert_eq!(xpath, "//*[@title=concat('say \"it', \"'\", 's\"')]");
+    }
+}
+
+/// Waits for elements of a page that adds them after a delay.
+/// Needs a running chromedriver: `cargo test --features execute -- --ignored`
+#[cfg(feature = "execute")]
+mod wait_for_element_live_tests {
+    use flow_like_catalog_automation::selector::wait::{ElementReadiness, wait_for_element};
+    use flow_like_catalog_automation::types::selectors::Selector;
+    use std::time::{Duration, Instant};
+    use thirtyfour::prelude::*;
+
+    const DELAYED_PAGE: &str = r#"data:text/html,<html><body><script>
+        setTimeout(() => {
+            const button = document.createElement('button');
+            button.id = 'late';
+            button.textContent = 'Continue';
+            button.disabled = true;
+            document.body.appendChild(button);
+        }, 400);
+        setTimeout(() => { document.getElementById('late').disabled = false; }, 900);
+    </script></body></html>"#;
+
+    async fn start_driver() -> WebDriver {
+        let url =
+            std::env::var("WEBDRIVER_URL").unwrap_or_else(|_| "http://localhost:9515".to_string());
+        let mut caps = DesiredCapabilities::chrome();
+        caps.set_headless().unwrap();
+        WebDriver::new(url, caps)
+            .await
+            .expect("chromedriver must be running")
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_waits_for_delayed_element() {
+        let driver = start_driver().await;
+        let poll = Duration::from_millis(50);
+        let timeout = Duration::from_secs(5);
+
+        driver.goto(DELAYED_PAGE).await.unwrap();
+        let start = Instant::now();
+        let present = wait_for_element(
+            &driver,
+            &[Selector::css("#late")],
+            ElementReadiness::Present,
+            poll,
+            timeout,
+        )
+        .await
+        .expect("button should appear");
+        let appeared = start.elapsed();
+        assert!(appeared >= Duration::from_millis(300));
+        assert!(appeared < Duration::from_millis(900));
+        assert_eq!(present.resolved_selector.value, "#late");
+
+        // The button is disabled until later, so clickable takes longer
+        let clickable = wait_for_element(
+            &driver,
+            &[Selector::xpath("//button[@id='late']")],
+            ElementReadiness::Clickable,
+            poll,
+            timeout,
+        )
+        .await
+        .expect("button should become clickable");
+        assert!(start.elapsed() >= Duration::from_millis(800));
+        assert!(clickable.bounding_box.x2 > clickable.bounding_box.x1);
+
+        driver.quit().await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_missing_element_times_out() {
+        let driver = start_driver().await;
+        driver.goto(DELAYED_PAGE).await.unwrap();
+
+        let start = Instant::now();
+        let element = wait_for_element(
+            &driver,
+            &[Selector::css("#never"), Selector::test_id("never")],
+            ElementReadiness::Present,
+            Duration::from_millis(50),
+            Duration::from_millis(500),
+        )
+        .await;
+        assert!(element.is_none());
+        assert!(start.elapsed() >= Duration::from_millis(500));
+        assert!(start.elapsed() < Duration::from_secs(2));
+
+        driver.quit().await.unwrap();
+    }
+}
+
+mod browser_tab_tests {
+    use flow_like_catalog_automation::session::tabs::{
+        BrowserTab, find_tab, next_active_after_close,
+    };
+    use flow_like_catalog_automation::types::handles::TabList;
+
+    fn handles(list: &[&str]) -> Vec<String> {
+        list.iter().map(|handle| handle.to_string()).collect()
+    }
+
+    fn tabs(urls: &[&str]) -> Vec<BrowserTab> {
+        urls.iter()
+            .enumerate()
+            .map(|(index, url)| BrowserTab {
+                index,
+                handle: format!("H{}", index),
+                url: url.to_string(),
+                title: String::new(),
+                active: index == 0,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_sync_adopts_and_drops_tabs() {
+        let mut list = TabList::default();
+        assert_eq!(list.sync(&handles(&["A"])), handles(&["A"]));
+
+        // The page opened B with window.open, the browser may report handles in any order
+        assert_eq!(list.sync(&handles(&["B", "A"])), handles(&["B"]));
+        assert_eq!(list.handles(), handles(&["A", "B"]).as_slice());
+        assert!(list.sync(&handles(&["A", "B"])).is_empty());
+
+        list.push("C");
+        list.push("C");
+        assert_eq!(list.handles(), handles(&["A", "B", "C"]).as_slice());
+
+        // B closed itself
+        assert!(list.sync(&handles(&["C", "A"])).is_empty());
+        assert_eq!(list.handles(), handles(&["A", "C"]).as_slice());
+        assert_eq!(list.remove("C"), Some(1));
+        assert_eq!(list.remove("C"), None);
+    }
+
+    #[test]
+    fn test_find_tab() {
+        let open = tabs(&[
+            "https://shop.example.com/",
+            "https://shop.example.com/checkout?step=1",
+            "https://docs.example.com/help",
+            "https://shop.example.com/checkout?step=2",
+        ]);
+
+        assert_eq!(find_tab(&open, 0, ""), Some(0));
+        assert_eq!(find_tab(&open, 2, " "), Some(2));
+        assert_eq!(find_tab(&open, -1, ""), Some(3));
+        assert_eq!(find_tab(&open, -4, ""), Some(0));
+        assert_eq!(find_tab(&open, 4, ""), None);
+        assert_eq!(find_tab(&open, -5, ""), None);
+
+        assert_eq!(find_tab(&open, 0, "*/checkout*"), Some(1));
+        assert_eq!(find_tab(&open, -1, "*/checkout*"), Some(3));
+        assert_eq!(find_tab(&open, 0, "https://docs.example.com/*"), Some(2));
+        assert_eq!(find_tab(&open, 2, "*/checkout*"), None);
+        assert_eq!(find_tab(&open, 0, "*/cart*"), None);
+    }
+
+    #[test]
+    fn test_next_active_after_close() {
+        // Closing a popup returns to the tab before it
+        assert_eq!(next_active_after_close(2, 2), Some(1));
+        assert_eq!(next_active_after_close(1, 3), Some(0));
+        assert_eq!(next_active_after_close(0, 2), Some(0));
+        assert_eq!(next_active_after_close(0, 0), None);
+    }
+}
+
+/// Follows a page into a tab it opens with `window.open` and back.
+/// Needs a running chromedriver: `cargo test --features execute -- --ignored`
+#[cfg(feature = "execute")]
+mod browser_tab_live_tests {
+    use flow_like_catalog_automation::session::tabs::{describe_tabs, find_tab, sync_tabs};
+    use flow_like_catalog_automation::types::handles::TabList;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::time::Duration;
+    use thirtyfour::prelude::*;
+
+    const OPENER: &str = r#"<html><head><title>Opener</title></head><body>
+        <input id="name" value="">
+        <button id="open" onclick="window.open('/popup')">Open</button>
+    </body></html>"#;
+    const POPUP: &str = r#"<html><head><title>Popup</title></head><body>
+        <button id="confirm" onclick="document.title = 'Confirmed'">Confirm</button>
+    </body></html>"#;
+
+    fn start_server() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { continue };
+                let mut buffer = [0u8; 4096];
+                let read = stream.read(&mut buffer).unwrap_or(0);
+                let request = String::from_utf8_lossy(&buffer[..read]);
+                let body = match request.split_whitespace().nth(1) {
+                    Some("/popup") => POPUP,
+                    _ => OPENER,
+                };
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+
+        address
+    }
+
+    async fn start_driver() -> WebDriver {
+        let url =
+            std::env::var("WEBDRIVER_URL").unwrap_or_else(|_| "http://localhost:9515".to_string());
+        let mut caps = DesiredCapabilities::chrome();
+        caps.set_headless().unwrap();
+        WebDriver::new(url, caps)
+            .await
+            .expect("chromedriver must be running")
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_switch_to_opened_tab_and_back() {
+        let address = start_server();
+        let driver = start_driver().await;
+        driver.goto(format!("{}/", address)).await.unwrap();
+
+        let mut list = TabList::default();
+        sync_tabs(&driver, &mut list).await.unwrap();
+        let opener = list.handles()[0].clone();
+
+        driver
+            .find(By::Id("open"))
+            .await
+            .unwrap()
+            .click()
+            .await
+            .unwrap();
+
+        let popup = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                if let Some(handle) = sync_tabs(&driver, &mut list).await.unwrap().pop() {
+                    return handle;
+                }
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+        })
+        .await
+        .expect("the popup should open");
+        assert_eq!(list.handles(), [opener.clone(), popup.clone()].as_slice());
+
+        // Let the popup finish loading before reading its URL
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        let tabs = describe_tabs(&driver, &list, &opener).await.unwrap();
+        assert_eq!(tabs[1].title, "Popup");
+        assert!(tabs[0].active && !tabs[1].active);
+        assert_eq!(driver.window().await.unwrap().to_string(), opener);
+
+        let index = find_tab(&tabs, 0, "*/popup").unwrap();
+        assert_eq!(find_tab(&tabs, -1, ""), Some(index));
+        driver
+            .switch_to_window(WindowHandle::from(tabs[index].handle.clone()))
+            .await
+            .unwrap();
+        driver
+            .find(By::Id("confirm"))
+            .await
+            .unwrap()
+            .click()
+            .await
+            .unwrap();
+        assert_eq!(driver.title().await.unwrap(), "Confirmed");
+
+        driver
+            .switch_to_window(WindowHandle::from(opener.clone()))
+            .await
+            .unwrap();
+        let name = driver.find(By::Id("name")).await.unwrap();
+        name.send_keys("back").await.unwrap();
+        assert_eq!(name.value().await.unwrap().as_deref(), Some("back"));
+
+        // The popup closes itself and is dropped from the list
+        driver
+            .switch_to_window(WindowHandle::from(popup.clone()))
+            .await
+            .unwrap();
+        driver.close_window().await.unwrap();
+        driver
+            .switch_to_window(WindowHandle::from(opener.clone()))
+            .await
+            .unwrap();
+        assert!(sync_tabs(&driver, &mut list).await.unwrap().is_empty());
+        assert_eq!(list.handles(), [opener].as_slice());
+
+        driver.quit().await.unwrap();
+    }
+}
+
+mod cookie_tests {
+    use flow_like_catalog_automation::browser::auth::CookieData;
+    use flow_like_catalog_automation::browser::cookies::{
+        cookie_from_cdp, cookie_matches_domain, cookie_to_cdp,
+    };
+    use serde_json::json;
+
+    #[test]
+    fn test_cdp_cookie_round_trip() {
+        let reported = json!([
+            {
+                "name": "sid",
+                "value": "abc123",
+                "domain": ".example.com",
+                "path": "/",
+                "expires": 1893456000.25,
+                "size": 9,
+                "httpOnly": true,
+                "secure": true,
+                "session": false,
+                "sameSite": "Lax",
+                "priority": "Medium"
+            },
+            {
+                "name": "theme",
+                "value": "dark",
+                "domain": "app.example.com",
+                "path": "/settings",
+                "expires": -1,
+                "httpOnly": false,
+                "secure": false,
+                "session": true
+            }
+        ]);
+        let cookies: Vec<CookieData> = reported
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(cookie_from_cdp)
+            .collect();
+
+        assert_eq!(cookies.len(), 2);
+        assert_eq!(cookies[0].expiry, Some(1893456000));
+        assert_eq!(cookies[0].http_only, Some(true));
+        assert_eq!(cookies[0].same_site.as_deref(), Some("Lax"));
+        assert_eq!(cookies[1].expiry, None);
+        assert_eq!(cookies[1].same_site, None);
+
+        // A flow stores the list as JSON and restores it on the next run
+        let saved = serde_json::to_string(&cookies).unwrap();
+        let restored: Vec<CookieData> = serde_json::from_str(&saved).unwrap();
+        assert_eq!(restored, cookies);
+
+        assert_eq!(
+            cookie_to_cdp(&restored[0], "https://example.com/"),
+            json!({
+                "name": "sid",
+                "value": "abc123",
+                "domain": ".example.com",
+                "path": "/",
+                "secure": true,
+                "httpOnly": true,
+                "sameSite": "Lax",
+                "expires": 1893456000
+            })
+        );
+        assert_eq!(
+            cookie_to_cdp(&restored[1], "https://example.com/")["expires"],
+            json!(null)
+        );
+    }
+
+    #[test]
+    fn test_cookie_without_domain_uses_page_url() {
+        let cookie: CookieData =
+            serde_json::from_value(json!({ "name": "token", "value": "x", "same_site": "strict" }))
+                .unwrap();
+        let param = cookie_to_cdp(&cookie, "http://127.0.0.1:8080/login");
+        assert_eq!(param["url"], json!("http://127.0.0.1:8080/login"));
+        assert!(param.get("domain").is_none());
+        assert_eq!(param["sameSite"], json!("Strict"));
+    }
+
+    #[test]
+    fn test_cookie_domain_matching() {
+        assert!(cookie_matches_domain(".example.com", "example.com"));
+        assert!(cookie_matches_domain("app.example.com", "example.com"));
+        assert!(cookie_matches_domain("Example.com", ".example.com"));
+        assert!(!cookie_matches_domain("notexample.com", "example.com"));
+        assert!(!cookie_matches_domain("example.com", "app.example.com"));
+    }
+}
+
+/// Sets cookies and storage through CDP and checks they survive a reload.
+/// Needs a running chromedriver: `cargo test --features execute -- --ignored`
+#[cfg(feature = "execute")]
+mod cookie_live_tests {
+    use flow_like_catalog_automation::browser::auth::CookieData;
+    use flow_like_catalog_automation::browser::cookies::{
+        clear_cdp_cookies, get_cdp_cookies, set_cdp_cookies,
+    };
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use thirtyfour::prelude::*;
+
+    /// Serves a page that echoes the cookies the browser sent
+    fn start_server() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { continue };
+                let mut buffer = [0u8; 4096];
+                let read = stream.read(&mut buffer).unwrap_or(0);
+                let request = String::from_utf8_lossy(&buffer[..read]);
+                let cookies = request
+                    .lines()
+                    .find_map(|line| line.strip_prefix("Cookie: "))
+                    .unwrap_or_default()
+                    .to_string();
+                let body = format!(
+                    "<html><body><pre id=\"sent\">{}</pre></body></html>",
+                    cookies
+                );
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+
+        address
+    }
+
+    async fn start_driver() -> WebDriver {
+        let url =
+            std::env::var("WEBDRIVER_URL").unwrap_or_else(|_| "http://localhost:9515".to_string());
+        let mut caps = DesiredCapabilities::chrome();
+        caps.set_headless().unwrap();
+        WebDriver::new(url, caps)
+            .await
+            .expect("chromedriver must be running")
+    }
+
+    fn cookie(name: &str, value: &str, http_only: bool) -> CookieData {
+        CookieData {
+            name: name.to_string(),
+            value: value.to_string(),
+            domain: None,
+            path: Some("/".to_string()),
+            secure: Some(false),
+            http_only: Some(http_only),
+            same_site: Some("Lax".to_string()),
+            expiry: Some(4102444800),
+        }
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_cookie_persists_across_reload() {
+        let address = start_server();
+        let driver = start_driver().await;
+        driver.goto(format!("{}/", address)).await.unwrap();
+
+        set_cdp_cookies(
+            &driver,
+            &[
+                cookie("sid", "abc123", true),
+                cookie("theme", "dark", false),
+            ],
+        )
+        .await
+        .unwrap();
+
+        driver.refresh().await.unwrap();
+        let sent = driver
+            .find(By::Id("sent"))
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+        assert!(sent.contains("sid=abc123"));
+        assert!(sent.contains("theme=dark"));
+
+        // HttpOnly cookies are invisible to the page but still exported
+        let visible = driver
+            .execute("return document.cookie;", vec![])
+            .await
+            .unwrap();
+        assert_eq!(visible.json(), &serde_json::json!("theme=dark"));
+
+        let mut saved = get_cdp_cookies(&driver, true).await.unwrap();
+        saved.sort_by(|a, b| a.name.cmp(&b.name));
+        assert_eq!(saved.len(), 2);
+        assert_eq!(saved[0].name, "sid");
+        assert_eq!(saved[0].http_only, Some(true));
+        assert_eq!(saved[0].expiry, Some(4102444800));
+
+        // Restoring the export into a fresh browser logs the session back in
+        let json = serde_json::to_string(&saved).unwrap();
+        assert_eq!(clear_cdp_cookies(&driver, "127.0.0.1").await.unwrap(), 2);
+        driver.refresh().await.unwrap();
+        let sent = driver
+            .find(By::Id("sent"))
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+        assert!(sent.is_empty());
+
+        let restored: Vec<CookieData> = serde_json::from_str(&json).unwrap();
+        set_cdp_cookies(&driver, &restored).await.unwrap();
+        driver.refresh().await.unwrap();
+        let sent = driver
+            .find(By::Id("sent"))
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+        assert!(sent.contains("sid=abc123"));
+
+        driver.quit().await.unwrap();
+    }
+}
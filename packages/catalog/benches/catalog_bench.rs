@@ -0,0 +1,42 @@
+//! Startup benchmark for building the node catalog.
+//!
+//! Compares the parallel catalog build with the sequential one and with the previous
+//! build, where every package returned the whole node inventory and each node was
+//! constructed once per package.
+//!
+//! Run with:
+//!   cargo bench --bench catalog_bench
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use flow_like_catalog::{CatalogBuilder, CatalogPackage, NodeLogic, get_core_catalog};
+use std::collections::HashMap;
+use std::{hint::black_box, sync::Arc};
+
+/// The build before the packages were scoped to their own crate
+fn unscoped_build() -> Vec<Arc<dyn NodeLogic>> {
+    let mut node_map: HashMap<String, Arc<dyn NodeLogic>> = HashMap::new();
+    for _ in CatalogPackage::all() {
+        for node in get_core_catalog() {
+            node_map.insert(node.get_node().name, node);
+        }
+    }
+    node_map.into_values().collect()
+}
+
+fn bench_catalog_build(c: &mut Criterion) {
+    let mut group = c.benchmark_group("catalog_build");
+    group.sample_size(20);
+
+    group.bench_function("parallel", |b| {
+        b.iter(|| black_box(CatalogBuilder::new().build()))
+    });
+    group.bench_function("sequential", |b| {
+        b.iter(|| black_box(CatalogBuilder::new().sequential().build()))
+    });
+    group.bench_function("unscoped", |b| b.iter(|| black_box(unscoped_build())));
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_catalog_build);
+criterion_main!(benches);
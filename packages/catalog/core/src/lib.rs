@@ -5,7 +5,7 @@
 //! - FlowPath, FlowPathRuntime, FlowPathStore
 //! - NodeDBConnection, CachedDB
 //! - Attachment
-//! - NodeConstructor and get_catalog()
+//! - NodeConstructor, get_catalog() and get_package_catalog()
 
 use std::sync::Arc;
 
@@ -69,22 +69,52 @@ pub use types::segmentation_mask::{InstanceSegmentation, SegmentationMask};
 /// A node constructor function type
 pub struct NodeConstructor {
     constructor: fn() -> Arc<dyn NodeLogic>,
+    package: &'static str,
 }
 
 impl NodeConstructor {
     pub const fn new(constructor: fn() -> Arc<dyn NodeLogic>) -> Self {
-        Self { constructor }
+        Self {
+            constructor,
+            package: "",
+        }
+    }
+
+    /// Constructor registered by the crate `package`, `#[register_node]` passes the
+    /// name of the crate the node is defined in.
+    pub const fn for_package(
+        package: &'static str,
+        constructor: fn() -> Arc<dyn NodeLogic>,
+    ) -> Self {
+        Self {
+            constructor,
+            package,
+        }
     }
 
     pub fn construct(&self) -> Arc<dyn NodeLogic> {
         (self.constructor)()
     }
+
+    pub fn package(&self) -> &'static str {
+        self.package
+    }
 }
 
 inventory::collect!(NodeConstructor);
 
+/// All registered nodes of every linked catalog crate
 pub fn get_catalog() -> Vec<Arc<dyn NodeLogic>> {
     inventory::iter::<NodeConstructor>()
         .map(|nc| nc.construct())
         .collect()
 }
+
+/// The nodes registered by the crate `package` only. The inventory is global, so every
+/// sub-crate would otherwise return the nodes of all linked crates.
+pub fn get_package_catalog(package: &str) -> Vec<Arc<dyn NodeLogic>> {
+    inventory::iter::<NodeConstructor>()
+        .filter(|nc| nc.package == package)
+        .map(|nc| nc.construct())
+        .collect()
+}
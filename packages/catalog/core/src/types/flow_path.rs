@@ -4,13 +4,14 @@ use flow_like::{
 };
 use flow_like_storage::{
     Path,
-    files::store::{FlowLikeStore, local_store::LocalObjectStore},
+    files::store::{FlowLikeStore, StoragePage, local_store::LocalObjectStore},
     object_store::{GetResult, PutPayload},
 };
 use flow_like_types::{
     Bytes, Cacheable, JsonSchema, anyhow,
     json::{Deserialize, Serialize},
 };
+use futures::{Stream, stream::BoxStream};
 use std::{path::PathBuf, sync::Arc};
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -472,6 +473,37 @@ impl FlowPathRuntime {
             cache_store_ref: self.cache_hash.clone(),
         }
     }
+
+    /// Streams the file in chunks, for files too large to hold in memory.
+    pub async fn read_stream(
+        &self,
+    ) -> flow_like_types::Result<BoxStream<'static, flow_like_types::Result<Bytes>>> {
+        self.store.read_stream(&self.path).await
+    }
+
+    /// Uploads a stream as multipart upload and returns the bytes written. The cache layer
+    /// is bypassed, streamed files are usually too large to be worth caching.
+    pub async fn write_stream<S>(&self, stream: S) -> flow_like_types::Result<u64>
+    where
+        S: Stream<Item = flow_like_types::Result<Bytes>> + Send + Unpin,
+    {
+        self.store.write_stream(&self.path, stream).await
+    }
 }
 
 pub struct FlowPathStore;
+
+impl FlowPathStore {
+    /// Lists one page of the files and folders directly below `prefix`, in the store of the
+    /// flow path. Pass the continuation of the previous page to get the next one.
+    pub async fn list_page(
+        context: &mut ExecutionContext,
+        prefix: &FlowPath,
+        continuation: Option<&str>,
+        limit: usize,
+    ) -> flow_like_types::Result<StoragePage> {
+        let store = prefix.to_store(context).await?;
+        let path = Path::from(prefix.path.clone());
+        store.list_page(Some(&path), continuation, limit).await
+    }
+}
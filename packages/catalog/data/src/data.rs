@@ -1,3 +1,4 @@
+pub mod airtable;
 pub mod atlassian;
 pub mod databricks;
 pub mod datafusion;
@@ -8,5 +9,6 @@ pub mod google;
 pub mod linkedin;
 pub mod microsoft;
 pub mod notion;
+pub mod parquet;
 pub mod path;
 pub mod tdms;
@@ -0,0 +1,444 @@
+pub mod create_records;
+pub mod delete_records;
+pub mod list_records;
+pub mod provider;
+pub mod update_records;
+
+pub use provider::{AIRTABLE_PROVIDER_ID, AirtableProvider};
+
+use flow_like_types::{
+    JsonSchema, Value,
+    json::{Map, json},
+    reqwest::{Client, Method, RequestBuilder, StatusCode},
+    tokio::{
+        sync::Mutex,
+        time::{Duration, Instant, sleep},
+    },
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, future::Future, sync::LazyLock};
+
+pub const AIRTABLE_API_URL: &str = "https://api.airtable.com/v0";
+
+/// Airtable creates, updates or deletes at most this many records per request
+pub const MAX_RECORDS_PER_WRITE: usize = 10;
+
+/// Largest page Airtable returns when listing records
+pub const MAX_PAGE_SIZE: usize = 100;
+
+/// Airtable allows 5 requests per second and base
+const MIN_REQUEST_INTERVAL: Duration = Duration::from_millis(200);
+
+// =============================================================================
+// Airtable Types
+// =============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AirtableRecord {
+    /// Record ID (e.g., "recXXXXXXXXXXXXXX")
+    pub id: String,
+    /// Creation timestamp
+    #[serde(default)]
+    pub created_time: String,
+    /// Field values keyed by field name. Attachments become objects with id, url,
+    /// filename, size and mime_type, collaborators objects with id, email and name,
+    /// selects and linked records stay strings and arrays of strings.
+    #[serde(default)]
+    pub fields: Value,
+}
+
+// =============================================================================
+// Field mapping
+// =============================================================================
+
+fn is_attachment(value: &Map<String, Value>) -> bool {
+    value.contains_key("url") && value.contains_key("filename")
+}
+
+fn is_collaborator(value: &Map<String, Value>) -> bool {
+    value.contains_key("email") && !value.contains_key("url")
+}
+
+/// Flow-Like representation of a field value read from Airtable.
+pub fn field_from_airtable(value: &Value) -> Value {
+    match value {
+        Value::Array(items) => Value::Array(items.iter().map(field_from_airtable).collect()),
+        Value::Object(object) if is_attachment(object) => json!({
+            "id": object.get("id"),
+            "url": object.get("url"),
+            "filename": object.get("filename"),
+            "size": object.get("size"),
+            "mime_type": object.get("type"),
+        }),
+        Value::Object(object) if is_collaborator(object) => json!({
+            "id": object.get("id"),
+            "email": object.get("email"),
+            "name": object.get("name"),
+        }),
+        // Buttons and barcodes
+        Value::Object(object) if object.contains_key("label") && object.contains_key("url") => {
+            object["url"].clone()
+        }
+        Value::Object(object) if object.len() <= 2 && object.contains_key("text") => {
+            object["text"].clone()
+        }
+        other => other.clone(),
+    }
+}
+
+/// Airtable representation of a field value to write. Existing attachments and
+/// collaborators are referenced by id, new attachments by url.
+pub fn field_to_airtable(value: &Value) -> Value {
+    match value {
+        Value::Array(items) => Value::Array(items.iter().map(field_to_airtable).collect()),
+        Value::Object(object) if object.contains_key("url") || is_collaborator(object) => {
+            match object.get("id").filter(|id| id.is_string()) {
+                Some(id) => json!({ "id": id }),
+                None if is_collaborator(object) => json!({ "email": object["email"] }),
+                None => {
+                    let mut attachment = json!({ "url": object["url"] });
+                    if let Some(filename) = object.get("filename").filter(|name| name.is_string()) {
+                        attachment["filename"] = filename.clone();
+                    }
+                    attachment
+                }
+            }
+        }
+        other => other.clone(),
+    }
+}
+
+fn map_fields(fields: &Value, map: fn(&Value) -> Value) -> Value {
+    match fields.as_object() {
+        Some(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(name, value)| (name.clone(), map(value)))
+                .collect(),
+        ),
+        None => json!({}),
+    }
+}
+
+pub fn parse_airtable_record(value: &Value) -> Option<AirtableRecord> {
+    Some(AirtableRecord {
+        id: value["id"].as_str()?.to_string(),
+        created_time: value["createdTime"].as_str().unwrap_or("").to_string(),
+        fields: map_fields(&value["fields"], field_from_airtable),
+    })
+}
+
+pub fn parse_airtable_records(response: &Value) -> Vec<AirtableRecord> {
+    response["records"]
+        .as_array()
+        .map(|records| records.iter().filter_map(parse_airtable_record).collect())
+        .unwrap_or_default()
+}
+
+/// Request bodies for writing `records`, each with at most [`MAX_RECORDS_PER_WRITE`]
+/// records. `records` are objects with `fields` and, for updates, an `id`.
+pub fn write_batches(records: &[Value], typecast: bool) -> Vec<Value> {
+    records
+        .chunks(MAX_RECORDS_PER_WRITE)
+        .map(|batch| {
+            let records: Vec<Value> = batch
+                .iter()
+                .map(|record| {
+                    let mut written = json!({
+                        "fields": map_fields(&record["fields"], field_to_airtable)
+                    });
+                    if let Some(id) = record["id"].as_str() {
+                        written["id"] = json!(id);
+                    }
+                    written
+                })
+                .collect();
+            json!({ "records": records, "typecast": typecast })
+        })
+        .collect()
+}
+
+// =============================================================================
+// Requests
+// =============================================================================
+
+pub fn table_url(base_id: &str, table: &str) -> String {
+    format!(
+        "{}/{}/{}",
+        AIRTABLE_API_URL,
+        base_id,
+        urlencoding::encode(table)
+    )
+}
+
+/// Hands out request slots per base, spaced by [`MIN_REQUEST_INTERVAL`].
+#[derive(Default)]
+struct RequestThrottle {
+    next_slot: HashMap<String, Instant>,
+}
+
+impl RequestThrottle {
+    /// How long a request to `base_id` issued at `now` has to wait.
+    fn reserve(&mut self, base_id: &str, now: Instant) -> Duration {
+        let slot = self
+            .next_slot
+            .get(base_id)
+            .map_or(now, |next| (*next).max(now));
+        self.next_slot
+            .insert(base_id.to_string(), slot + MIN_REQUEST_INTERVAL);
+        slot - now
+    }
+}
+
+/// Shared by all nodes, parallel flows hit the same limit
+static THROTTLE: LazyLock<Mutex<RequestThrottle>> = LazyLock::new(Default::default);
+
+/// Waits for a free slot of the base, then sends the request and returns its JSON body.
+pub(crate) async fn send(base_id: &str, request: RequestBuilder) -> Result<Value, String> {
+    let wait = THROTTLE.lock().await.reserve(base_id, Instant::now());
+    if !wait.is_zero() {
+        sleep(wait).await;
+    }
+
+    let response = request
+        .send()
+        .await
+        .map_err(|e| format!("Network error: {}", e))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let error_text = response.text().await.unwrap_or_default();
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            return Err(format!(
+                "Airtable rate limit exceeded, retry in 30 seconds: {}",
+                error_text
+            ));
+        }
+        return Err(format!("Airtable API error {}: {}", status, error_text));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse response: {}", e))
+}
+
+/// Writes `records` in batches of [`MAX_RECORDS_PER_WRITE`] and returns the written records.
+/// `method` is POST to create, PATCH to update and PUT to replace records.
+pub(crate) async fn write_records(
+    client: &Client,
+    access_token: &str,
+    base_id: &str,
+    table: &str,
+    method: Method,
+    records: &[Value],
+    typecast: bool,
+) -> Result<Vec<AirtableRecord>, String> {
+    let url = table_url(base_id, table);
+    let mut written = Vec::with_capacity(records.len());
+    for batch in write_batches(records, typecast) {
+        let request = client
+            .request(method.clone(), &url)
+            .bearer_auth(access_token)
+            .json(&batch);
+        let response = send(base_id, request)
+            .await
+            .map_err(|e| match written.len() {
+                0 => e,
+                count => format!("{} ({} records were written before the error)", e, count),
+            })?;
+        written.extend(parse_airtable_records(&response));
+    }
+    Ok(written)
+}
+
+/// Follows the `offset` of each page until `max_records` are read or no page is left.
+/// `max_records` of 0 reads all records. Returns the records and the offset of the next
+/// page, if there is one.
+pub(crate) async fn collect_pages<F, Fut>(
+    max_records: usize,
+    mut fetch_page: F,
+) -> Result<(Vec<AirtableRecord>, Option<String>), String>
+where
+    F: FnMut(Option<String>, usize) -> Fut,
+    Fut: Future<Output = Result<Value, String>>,
+{
+    let mut records = Vec::new();
+    let mut offset: Option<String> = None;
+
+    loop {
+        let page_size = match max_records {
+            0 => MAX_PAGE_SIZE,
+            max => (max - records.len()).min(MAX_PAGE_SIZE),
+        };
+        let page = fetch_page(offset.take(), page_size).await?;
+        records.extend(parse_airtable_records(&page));
+        offset = page["offset"].as_str().map(String::from);
+
+        let full = max_records > 0 && records.len() >= max_records;
+        if full || offset.is_none() {
+            records.truncate(if max_records > 0 {
+                max_records
+            } else {
+                records.len()
+            });
+            return Ok((records, offset));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flow_like_types::json::from_str;
+
+    const LIST_PAGE_1: &str =
+        include_str!("../../tests/fixtures/airtable/list_records_page_1.json");
+    const LIST_PAGE_2: &str =
+        include_str!("../../tests/fixtures/airtable/list_records_page_2.json");
+    const CREATE_RESPONSE: &str = include_str!("../../tests/fixtures/airtable/create_records.json");
+
+    /// Serves the recorded pages like the list endpoint, keyed by the offset
+    fn recorded_page(offset: Option<String>) -> Result<Value, String> {
+        match offset.as_deref() {
+            None => Ok(from_str(LIST_PAGE_1).unwrap()),
+            Some("itrT2iEiPSFq4Qnbg/recW8Hi3aMf1cDbKF") => Ok(from_str(LIST_PAGE_2).unwrap()),
+            Some(other) => Err(format!("unexpected offset {}", other)),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_follows_pages() {
+        let mut requested = Vec::new();
+        let (records, offset) = collect_pages(0, |offset, page_size| {
+            requested.push((offset.clone(), page_size));
+            std::future::ready(recorded_page(offset))
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(records.len(), 5);
+        assert_eq!(offset, None);
+        assert_eq!(requested.len(), 2);
+        assert_eq!(
+            requested[1].0.as_deref(),
+            Some("itrT2iEiPSFq4Qnbg/recW8Hi3aMf1cDbKF")
+        );
+        let ids: Vec<&str> = records.iter().map(|record| record.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            [
+                "rec560UJdUtocSouk",
+                "recW8Hi3aMf1cDbKF",
+                "recDTsdW1xg2OB2rK",
+                "recK9yqQ4fYLmCZ1s",
+                "recPq3x0ZkG7vH2nB"
+            ]
+        );
+
+        // Stops early and hands out the offset of the next page
+        let (records, offset) =
+            collect_pages(2, |offset, _| std::future::ready(recorded_page(offset)))
+                .await
+                .unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(
+            offset.as_deref(),
+            Some("itrT2iEiPSFq4Qnbg/recW8Hi3aMf1cDbKF")
+        );
+    }
+
+    #[test]
+    fn test_field_types_are_mapped() {
+        let page: Value = from_str(LIST_PAGE_1).unwrap();
+        let record = &parse_airtable_records(&page)[0];
+        assert_eq!(record.created_time, "2024-02-12T08:31:04.000Z");
+
+        let fields = &record.fields;
+        assert_eq!(fields["Name"], json!("Launch plan"));
+        assert_eq!(fields["Status"], json!("In progress"));
+        assert_eq!(fields["Tags"], json!(["marketing", "q2"]));
+        assert_eq!(fields["Related"], json!(["recW8Hi3aMf1cDbKF"]));
+        assert_eq!(fields["Budget"], json!(12500.5));
+        assert_eq!(fields["Done"], json!(true));
+        assert_eq!(
+            fields["Files"],
+            json!([{
+                "id": "attYi4PMF2eMpXrbD",
+                "url": "https://v5.airtableusercontent.com/v3/u/plan.pdf",
+                "filename": "plan.pdf",
+                "size": 48211,
+                "mime_type": "application/pdf"
+            }])
+        );
+        assert_eq!(
+            fields["Owner"],
+            json!({ "id": "usrAj2gkPFtZ9bMlz", "email": "ada@example.com", "name": "Ada Lovelace" })
+        );
+        assert_eq!(fields["Ticket"], json!("LP-0042"));
+
+        let button = &parse_airtable_records(&page)[1].fields["Open"];
+        assert_eq!(button, &json!("https://example.com/press"));
+    }
+
+    #[test]
+    fn test_writes_are_chunked_and_mapped() {
+        let records: Vec<Value> = (0..23)
+            .map(|index| json!({ "fields": { "Name": format!("Row {}", index) } }))
+            .collect();
+        let batches = write_batches(&records, false);
+        let sizes: Vec<usize> = batches
+            .iter()
+            .map(|batch| batch["records"].as_array().unwrap().len())
+            .collect();
+        assert_eq!(sizes, [10, 10, 3]);
+        assert_eq!(batches[2]["records"][2]["fields"]["Name"], json!("Row 22"));
+
+        // A record read from Airtable can be written back as it is
+        let page: Value = from_str(LIST_PAGE_1).unwrap();
+        let record = json!(parse_airtable_records(&page)[0]);
+        let batch = &write_batches(&[record], true)[0];
+        assert_eq!(batch["typecast"], json!(true));
+        let written = &batch["records"][0];
+        assert_eq!(written["id"], json!("rec560UJdUtocSouk"));
+        assert_eq!(
+            written["fields"]["Files"],
+            json!([{ "id": "attYi4PMF2eMpXrbD" }])
+        );
+        assert_eq!(
+            written["fields"]["Owner"],
+            json!({ "id": "usrAj2gkPFtZ9bMlz" })
+        );
+
+        let new_file = field_to_airtable(&json!([{ "url": "https://example.com/a.png" }]));
+        assert_eq!(new_file, json!([{ "url": "https://example.com/a.png" }]));
+        let by_email = field_to_airtable(&json!({ "email": "ada@example.com" }));
+        assert_eq!(by_email, json!({ "email": "ada@example.com" }));
+    }
+
+    #[test]
+    fn test_create_response() {
+        let response: Value = from_str(CREATE_RESPONSE).unwrap();
+        let records = parse_airtable_records(&response);
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].id, "recN4uN2LwqtVWvF3");
+        assert_eq!(records[1].fields["Tags"], json!(["sales"]));
+    }
+
+    #[test]
+    fn test_throttle_spaces_requests_per_base() {
+        let mut throttle = RequestThrottle::default();
+        let now = Instant::now();
+
+        let waits: Vec<Duration> = (0..6).map(|_| throttle.reserve("appA", now)).collect();
+        assert_eq!(waits[0], Duration::ZERO);
+        assert_eq!(waits[5], MIN_REQUEST_INTERVAL * 5);
+
+        // Other bases have their own budget
+        assert_eq!(throttle.reserve("appB", now), Duration::ZERO);
+
+        // Slots in the past are not handed out
+        let later = now + Duration::from_secs(10);
+        assert_eq!(throttle.reserve("appA", later), Duration::ZERO);
+    }
+}
@@ -0,0 +1,166 @@
+use super::provider::AirtableProvider;
+use super::{AirtableRecord, write_records};
+use flow_like::flow::{
+    execution::{LogLevel, context::ExecutionContext},
+    node::{Node, NodeLogic, NodeScores},
+    pin::{PinOptions, ValueType},
+    variable::VariableType,
+};
+use flow_like_types::{Value, async_trait, json::json, reqwest};
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct CreateAirtableRecordsNode {}
+
+impl CreateAirtableRecordsNode {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for CreateAirtableRecordsNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "data_airtable_create_records",
+            "Create Airtable Records",
+            "Creates records in an Airtable table, 10 per request",
+            "Data/Airtable",
+        );
+        node.add_icon("/flow/icons/database.svg");
+
+        node.add_input_pin(
+            "exec_in",
+            "Input",
+            "Trigger the request",
+            VariableType::Execution,
+        );
+
+        node.add_input_pin(
+            "provider",
+            "Provider",
+            "Airtable provider (from Airtable node)",
+            VariableType::Struct,
+        )
+        .set_schema::<AirtableProvider>()
+        .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node.add_input_pin(
+            "base_id",
+            "Base ID",
+            "The ID of the base (starts with 'app')",
+            VariableType::String,
+        );
+
+        node.add_input_pin(
+            "table",
+            "Table",
+            "Name or ID of the table",
+            VariableType::String,
+        );
+
+        node.add_input_pin(
+            "fields",
+            "Fields",
+            "One object per record, mapping field names to values",
+            VariableType::Struct,
+        )
+        .set_value_type(ValueType::Array)
+        .set_default_value(Some(json!([])));
+
+        node.add_input_pin(
+            "typecast",
+            "Typecast",
+            "Convert values to the field types, e.g. create missing select options",
+            VariableType::Boolean,
+        )
+        .set_default_value(Some(json!(false)));
+
+        node.add_output_pin(
+            "exec_out",
+            "Success",
+            "Triggered when all records are created",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "error",
+            "Error",
+            "Triggered when an error occurs",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "records",
+            "Records",
+            "The written records as returned by Airtable",
+            VariableType::Struct,
+        )
+        .set_value_type(ValueType::Array)
+        .set_schema::<AirtableRecord>()
+        .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node.set_scores(
+            NodeScores::new()
+                .set_privacy(6)
+                .set_security(7)
+                .set_performance(7)
+                .set_governance(7)
+                .set_reliability(9)
+                .set_cost(10)
+                .build(),
+        );
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+        context.deactivate_exec_pin("error").await?;
+
+        let provider: AirtableProvider = context.evaluate_pin("provider").await?;
+        let base_id: String = context.evaluate_pin("base_id").await?;
+        let table: String = context.evaluate_pin("table").await?;
+        let fields: Vec<Value> = context.evaluate_pin("fields").await?;
+        let typecast: bool = context.evaluate_pin("typecast").await?;
+
+        let records: Vec<Value> = fields
+            .into_iter()
+            .map(|fields| json!({ "fields": fields }))
+            .collect();
+
+        if base_id.is_empty() || table.is_empty() {
+            context.log_message("Base ID and table cannot be empty", LogLevel::Error);
+            context.activate_exec_pin("error").await?;
+            return Ok(());
+        }
+
+        let client = reqwest::Client::new();
+        match write_records(
+            &client,
+            &provider.access_token,
+            &base_id,
+            &table,
+            reqwest::Method::POST,
+            &records,
+            typecast,
+        )
+        .await
+        {
+            Ok(written) => {
+                context.log_message(
+                    &format!("Created {} records in {}", written.len(), table),
+                    LogLevel::Info,
+                );
+                context.set_pin_value("records", json!(written)).await?;
+                context.activate_exec_pin("exec_out").await?;
+            }
+            Err(e) => {
+                context.log_message(&e, LogLevel::Error);
+                context.activate_exec_pin("error").await?;
+            }
+        }
+
+        Ok(())
+    }
+}
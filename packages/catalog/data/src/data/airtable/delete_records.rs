@@ -0,0 +1,172 @@
+use super::provider::AirtableProvider;
+use super::{MAX_RECORDS_PER_WRITE, send, table_url};
+use flow_like::flow::{
+    execution::{LogLevel, context::ExecutionContext},
+    node::{Node, NodeLogic, NodeScores},
+    pin::{PinOptions, ValueType},
+    variable::VariableType,
+};
+use flow_like_types::{async_trait, json::json, reqwest};
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct DeleteAirtableRecordsNode {}
+
+impl DeleteAirtableRecordsNode {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for DeleteAirtableRecordsNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "data_airtable_delete_records",
+            "Delete Airtable Records",
+            "Deletes records from an Airtable table, 10 per request",
+            "Data/Airtable",
+        );
+        node.add_icon("/flow/icons/database.svg");
+
+        node.add_input_pin(
+            "exec_in",
+            "Input",
+            "Trigger deleting the records",
+            VariableType::Execution,
+        );
+
+        node.add_input_pin(
+            "provider",
+            "Provider",
+            "Airtable provider (from Airtable node)",
+            VariableType::Struct,
+        )
+        .set_schema::<AirtableProvider>()
+        .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node.add_input_pin(
+            "base_id",
+            "Base ID",
+            "The ID of the base (starts with 'app')",
+            VariableType::String,
+        );
+
+        node.add_input_pin(
+            "table",
+            "Table",
+            "Name or ID of the table",
+            VariableType::String,
+        );
+
+        node.add_input_pin(
+            "record_ids",
+            "Record IDs",
+            "IDs of the records to delete",
+            VariableType::String,
+        )
+        .set_value_type(ValueType::Array)
+        .set_default_value(Some(json!([])));
+
+        node.add_output_pin(
+            "exec_out",
+            "Success",
+            "Triggered when all records are deleted",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "error",
+            "Error",
+            "Triggered when an error occurs",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "deleted_ids",
+            "Deleted IDs",
+            "IDs of the deleted records",
+            VariableType::String,
+        )
+        .set_value_type(ValueType::Array);
+
+        node.set_scores(
+            NodeScores::new()
+                .set_privacy(6)
+                .set_security(6)
+                .set_performance(7)
+                .set_governance(6)
+                .set_reliability(9)
+                .set_cost(10)
+                .build(),
+        );
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+        context.deactivate_exec_pin("error").await?;
+
+        let provider: AirtableProvider = context.evaluate_pin("provider").await?;
+        let base_id: String = context.evaluate_pin("base_id").await?;
+        let table: String = context.evaluate_pin("table").await?;
+        let record_ids: Vec<String> = context.evaluate_pin("record_ids").await?;
+
+        if base_id.is_empty() || table.is_empty() {
+            context.log_message("Base ID and table cannot be empty", LogLevel::Error);
+            context.activate_exec_pin("error").await?;
+            return Ok(());
+        }
+
+        let client = reqwest::Client::new();
+        let url = table_url(&base_id, &table);
+        let mut deleted_ids: Vec<String> = Vec::with_capacity(record_ids.len());
+
+        for batch in record_ids.chunks(MAX_RECORDS_PER_WRITE) {
+            let query: Vec<(&str, &str)> =
+                batch.iter().map(|id| ("records[]", id.as_str())).collect();
+            let request = client
+                .delete(&url)
+                .bearer_auth(&provider.access_token)
+                .query(&query);
+
+            match send(&base_id, request).await {
+                Ok(response) => {
+                    let deleted = response["records"].as_array().into_iter().flatten();
+                    deleted_ids.extend(
+                        deleted
+                            .filter(|record| record["deleted"].as_bool().unwrap_or(false))
+                            .filter_map(|record| record["id"].as_str().map(String::from)),
+                    );
+                }
+                Err(e) => {
+                    context.log_message(
+                        &format!(
+                            "{} ({} records were deleted before the error)",
+                            e,
+                            deleted_ids.len()
+                        ),
+                        LogLevel::Error,
+                    );
+                    context
+                        .set_pin_value("deleted_ids", json!(deleted_ids))
+                        .await?;
+                    context.activate_exec_pin("error").await?;
+                    return Ok(());
+                }
+            }
+        }
+
+        context.log_message(
+            &format!("Deleted {} records from {}", deleted_ids.len(), table),
+            LogLevel::Info,
+        );
+        context
+            .set_pin_value("deleted_ids", json!(deleted_ids))
+            .await?;
+        context.activate_exec_pin("exec_out").await?;
+
+        Ok(())
+    }
+}
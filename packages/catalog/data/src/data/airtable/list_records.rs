@@ -0,0 +1,220 @@
+use super::provider::AirtableProvider;
+use super::{AirtableRecord, collect_pages, send, table_url};
+use flow_like::flow::{
+    execution::{LogLevel, context::ExecutionContext},
+    node::{Node, NodeLogic, NodeScores},
+    pin::{PinOptions, ValueType},
+    variable::VariableType,
+};
+use flow_like_types::{async_trait, json::json, reqwest};
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct ListAirtableRecordsNode {}
+
+impl ListAirtableRecordsNode {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for ListAirtableRecordsNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "data_airtable_list_records",
+            "List Airtable Records",
+            "Lists the records of an Airtable table, following all pages",
+            "Data/Airtable",
+        );
+        node.add_icon("/flow/icons/database.svg");
+
+        node.add_input_pin(
+            "exec_in",
+            "Input",
+            "Trigger listing the records",
+            VariableType::Execution,
+        );
+
+        node.add_input_pin(
+            "provider",
+            "Provider",
+            "Airtable provider (from Airtable node)",
+            VariableType::Struct,
+        )
+        .set_schema::<AirtableProvider>()
+        .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node.add_input_pin(
+            "base_id",
+            "Base ID",
+            "The ID of the base (starts with 'app')",
+            VariableType::String,
+        );
+
+        node.add_input_pin(
+            "table",
+            "Table",
+            "Name or ID of the table",
+            VariableType::String,
+        );
+
+        node.add_input_pin(
+            "filter_formula",
+            "Filter Formula",
+            "Optional: Airtable formula records have to match, e.g. {Status} = 'Done'",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("")));
+
+        node.add_input_pin(
+            "sort_field",
+            "Sort Field",
+            "Optional: Field name to sort by",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("")));
+
+        node.add_input_pin(
+            "sort_direction",
+            "Sort Direction",
+            "Sort direction (asc or desc)",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("asc")))
+        .set_options(
+            PinOptions::new()
+                .set_valid_values(vec!["asc".to_string(), "desc".to_string()])
+                .build(),
+        );
+
+        node.add_input_pin(
+            "view",
+            "View",
+            "Optional: Name or ID of a view, its filters and order apply",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("")));
+
+        node.add_input_pin(
+            "max_records",
+            "Max Records",
+            "Maximum number of records to return, 0 returns all",
+            VariableType::Integer,
+        )
+        .set_default_value(Some(json!(0)));
+
+        node.add_output_pin(
+            "exec_out",
+            "Success",
+            "Triggered when the records are listed",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "error",
+            "Error",
+            "Triggered when an error occurs",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "records",
+            "Records",
+            "The records of the table",
+            VariableType::Struct,
+        )
+        .set_value_type(ValueType::Array)
+        .set_schema::<AirtableRecord>()
+        .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node.add_output_pin(
+            "count",
+            "Count",
+            "Number of returned records",
+            VariableType::Integer,
+        );
+
+        node.set_scores(
+            NodeScores::new()
+                .set_privacy(6)
+                .set_security(7)
+                .set_performance(6)
+                .set_governance(7)
+                .set_reliability(9)
+                .set_cost(10)
+                .build(),
+        );
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+        context.deactivate_exec_pin("error").await?;
+
+        let provider: AirtableProvider = context.evaluate_pin("provider").await?;
+        let base_id: String = context.evaluate_pin("base_id").await?;
+        let table: String = context.evaluate_pin("table").await?;
+        let filter_formula: String = context.evaluate_pin("filter_formula").await?;
+        let sort_field: String = context.evaluate_pin("sort_field").await?;
+        let sort_direction: String = context.evaluate_pin("sort_direction").await?;
+        let view: String = context.evaluate_pin("view").await?;
+        let max_records: i64 = context.evaluate_pin("max_records").await?;
+
+        if base_id.is_empty() || table.is_empty() {
+            context.log_message("Base ID and table cannot be empty", LogLevel::Error);
+            context.activate_exec_pin("error").await?;
+            return Ok(());
+        }
+
+        let mut query: Vec<(&str, String)> = Vec::new();
+        if !filter_formula.is_empty() {
+            query.push(("filterByFormula", filter_formula));
+        }
+        if !sort_field.is_empty() {
+            query.push(("sort[0][field]", sort_field));
+            query.push(("sort[0][direction]", sort_direction));
+        }
+        if !view.is_empty() {
+            query.push(("view", view));
+        }
+
+        let client = reqwest::Client::new();
+        let url = table_url(&base_id, &table);
+        let base = base_id.as_str();
+        let result = collect_pages(max_records.max(0) as usize, |offset, page_size| {
+            let mut request = client
+                .get(&url)
+                .bearer_auth(&provider.access_token)
+                .query(&query)
+                .query(&[("pageSize", page_size.to_string())]);
+            if let Some(offset) = offset {
+                request = request.query(&[("offset", offset)]);
+            }
+            send(base, request)
+        })
+        .await;
+
+        match result {
+            Ok((records, _)) => {
+                context.log_message(
+                    &format!("Listed {} records from {}", records.len(), table),
+                    LogLevel::Info,
+                );
+
+                context
+                    .set_pin_value("count", json!(records.len() as i64))
+                    .await?;
+                context.set_pin_value("records", json!(records)).await?;
+                context.activate_exec_pin("exec_out").await?;
+            }
+            Err(e) => {
+                context.log_message(&e, LogLevel::Error);
+                context.activate_exec_pin("error").await?;
+            }
+        }
+
+        Ok(())
+    }
+}
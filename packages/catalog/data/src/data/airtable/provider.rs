@@ -0,0 +1,89 @@
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic, NodeScores},
+    pin::PinOptions,
+    variable::VariableType,
+};
+use flow_like_types::{JsonSchema, async_trait, json::json};
+use serde::{Deserialize, Serialize};
+
+pub const AIRTABLE_PROVIDER_ID: &str = "airtable";
+
+/// Airtable provider - works with Personal Access Tokens
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+pub struct AirtableProvider {
+    pub provider_id: String,
+    pub access_token: String,
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct AirtableApiKeyProviderNode {}
+
+impl AirtableApiKeyProviderNode {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for AirtableApiKeyProviderNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "data_airtable_provider_api_key",
+            "Airtable (Token)",
+            "Connect to Airtable using a Personal Access Token. Create one at airtable.com/create/tokens with the data.records scopes and access to your bases.",
+            "Data/Airtable",
+        );
+        node.add_icon("/flow/icons/database.svg");
+
+        node.add_input_pin(
+            "access_token",
+            "Access Token",
+            "Your Airtable Personal Access Token (starts with 'pat')",
+            VariableType::String,
+        )
+        .set_options(PinOptions::new().set_sensitive(true).build());
+
+        node.add_output_pin(
+            "provider",
+            "Provider",
+            "Airtable provider with authentication token",
+            VariableType::Struct,
+        )
+        .set_schema::<AirtableProvider>()
+        .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node.set_scores(
+            NodeScores::new()
+                .set_privacy(6)
+                .set_security(7)
+                .set_performance(7)
+                .set_governance(7)
+                .set_reliability(9)
+                .set_cost(10)
+                .build(),
+        );
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        let token: String = context.evaluate_pin("access_token").await?;
+
+        if token.is_empty() {
+            return Err(flow_like_types::anyhow!(
+                "Access token is required. Create one at airtable.com/create/tokens"
+            ));
+        }
+
+        let provider = AirtableProvider {
+            provider_id: AIRTABLE_PROVIDER_ID.to_string(),
+            access_token: token,
+        };
+
+        context.set_pin_value("provider", json!(provider)).await?;
+
+        Ok(())
+    }
+}
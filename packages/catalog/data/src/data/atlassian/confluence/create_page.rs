@@ -1,4 +1,4 @@
-use super::{ConfluencePage, parse_confluence_page};
+use super::{ConfluencePage, create_page_request, parse_confluence_page, storage_body};
 use crate::data::atlassian::provider::{ATLASSIAN_PROVIDER_ID, AtlassianProvider};
 use flow_like::flow::{
     execution::{LogLevel, context::ExecutionContext},
@@ -28,6 +28,7 @@ impl NodeLogic for CreateConfluencePageNode {
             "Data/Atlassian/Confluence",
         );
         node.add_icon("/flow/icons/confluence.svg");
+        node.set_version(1);
 
         node.add_input_pin(
             "exec_in",
@@ -57,11 +58,24 @@ impl NodeLogic for CreateConfluencePageNode {
         node.add_input_pin(
             "body",
             "Body",
-            "Page body content (HTML/storage format)",
+            "Page body content, as plain text or storage format XHTML",
             VariableType::String,
         )
         .set_default_value(Some(json!("")));
 
+        node.add_input_pin(
+            "body_format",
+            "Body Format",
+            "'storage' sends the body as is, 'text' escapes it and turns blank lines into paragraphs",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("storage")))
+        .set_options(
+            PinOptions::new()
+                .set_valid_values(vec!["storage".to_string(), "text".to_string()])
+                .build(),
+        );
+
         node.add_input_pin(
             "parent_id",
             "Parent ID",
@@ -123,6 +137,7 @@ impl NodeLogic for CreateConfluencePageNode {
         let space_key: String = context.evaluate_pin("space_key").await?;
         let title: String = context.evaluate_pin("title").await?;
         let body: String = context.evaluate_pin("body").await?;
+        let body_format: String = context.evaluate_pin("body_format").await?;
         let parent_id: String = context.evaluate_pin("parent_id").await?;
 
         if space_key.is_empty() {
@@ -138,28 +153,14 @@ impl NodeLogic for CreateConfluencePageNode {
         }
 
         let client = reqwest::Client::new();
-        let base = provider.base_url.trim_end_matches('/');
-        let url = format!("{}/wiki/rest/api/content", base);
-
-        // Build the request body
-        let mut request_body = json!({
-            "type": "page",
-            "title": title,
-            "space": {
-                "key": space_key
-            },
-            "body": {
-                "storage": {
-                    "value": body,
-                    "representation": "storage"
-                }
-            }
-        });
+        let url = provider.confluence_content_url("");
 
-        // Add parent page if specified
-        if !parent_id.is_empty() {
-            request_body["ancestors"] = json!([{ "id": parent_id }]);
-        }
+        let request_body = create_page_request(
+            &space_key,
+            &title,
+            &storage_body(&body, &body_format),
+            &parent_id,
+        );
 
         context.log_message(
             &format!(
@@ -10,7 +10,7 @@ pub mod search_content;
 pub mod update_page;
 pub mod users;
 
-use flow_like_types::{JsonSchema, Value};
+use flow_like_types::{JsonSchema, Value, json::json};
 use serde::{Deserialize, Serialize};
 
 // =============================================================================
@@ -415,6 +415,112 @@ pub fn parse_confluence_content(value: &Value, base_url: &str) -> Option<Conflue
     })
 }
 
+pub fn parse_confluence_search(value: &Value, base_url: &str) -> ConfluenceSearchResult {
+    let results: Vec<ConfluenceContent> = value
+        .get("results")
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| parse_confluence_content(v, base_url))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    ConfluenceSearchResult {
+        total: value
+            .get("totalSize")
+            .or_else(|| value.get("size"))
+            .and_then(|v| v.as_i64())
+            .unwrap_or(results.len() as i64),
+        start: value.get("start").and_then(|v| v.as_i64()).unwrap_or(0),
+        limit: value
+            .get("limit")
+            .and_then(|v| v.as_i64())
+            .unwrap_or(results.len() as i64),
+        results,
+    }
+}
+
+// =============================================================================
+// Page bodies and request payloads
+// =============================================================================
+
+/// Converts plain text to storage format. Blank lines separate paragraphs,
+/// single line breaks are kept and markup characters are escaped.
+pub fn text_to_storage(text: &str) -> String {
+    let escaped = text
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;");
+
+    escaped
+        .replace("\r\n", "\n")
+        .split("\n\n")
+        .map(str::trim)
+        .filter(|paragraph| !paragraph.is_empty())
+        .map(|paragraph| format!("<p>{}</p>", paragraph.replace('\n', "<br />")))
+        .collect()
+}
+
+/// The storage format value for a body given as plain `text` or as raw `storage` XHTML
+pub fn storage_body(body: &str, body_format: &str) -> String {
+    match body_format {
+        "text" => text_to_storage(body),
+        _ => body.to_string(),
+    }
+}
+
+pub fn create_page_request(space_key: &str, title: &str, storage: &str, parent_id: &str) -> Value {
+    let mut request = json!({
+        "type": "page",
+        "title": title,
+        "space": { "key": space_key },
+        "body": {
+            "storage": {
+                "value": storage,
+                "representation": "storage"
+            }
+        }
+    });
+
+    if !parent_id.is_empty() {
+        request["ancestors"] = json!([{ "id": parent_id }]);
+    }
+
+    request
+}
+
+/// Confluence only accepts an update that carries the page's next version number
+pub fn update_page_request(
+    page_id: &str,
+    title: &str,
+    space_key: &str,
+    storage: &str,
+    current_version: i64,
+    version_message: &str,
+) -> Value {
+    let mut request = json!({
+        "id": page_id,
+        "type": "page",
+        "title": title,
+        "space": { "key": space_key },
+        "body": {
+            "storage": {
+                "value": storage,
+                "representation": "storage"
+            }
+        },
+        "version": { "number": current_version + 1 }
+    });
+
+    if !version_message.is_empty() {
+        request["version"]["message"] = json!(version_message);
+    }
+
+    request
+}
+
 // Re-export node implementations
 pub use add_comment::AddConfluenceCommentNode;
 pub use create_page::CreateConfluencePageNode;
@@ -424,3 +530,113 @@ pub use get_page::GetConfluencePageNode;
 pub use list_spaces::ListConfluenceSpacesNode;
 pub use search_content::SearchConfluenceContentNode;
 pub use update_page::UpdateConfluencePageNode;
+
+#[cfg(test)]
+mod tests {
+    use super::search_content::build_cql;
+    use super::*;
+    use flow_like_types::json::from_str;
+
+    const BASE_URL: &str = "https://example.atlassian.net";
+    const CREATE_FIXTURE: &str =
+        include_str!("../../../../tests/fixtures/confluence/create_page.json");
+    const SEARCH_FIXTURE: &str = include_str!("../../../../tests/fixtures/confluence/search.json");
+
+    #[test]
+    fn test_create_request_and_response() {
+        let request = create_page_request(
+            "OPS",
+            "Database failover runbook",
+            &storage_body(
+                "Check replication lag first.\n\nThen promote the replica.",
+                "text",
+            ),
+            "65601",
+        );
+        assert_eq!(
+            request,
+            json!({
+                "type": "page",
+                "title": "Database failover runbook",
+                "space": { "key": "OPS" },
+                "body": {
+                    "storage": {
+                        "value": "<p>Check replication lag first.</p><p>Then promote the replica.</p>",
+                        "representation": "storage"
+                    }
+                },
+                "ancestors": [{ "id": "65601" }]
+            })
+        );
+
+        let response: Value = from_str(CREATE_FIXTURE).unwrap();
+        let page = parse_confluence_page(&response, BASE_URL).unwrap();
+
+        assert_eq!(page.id, "98305");
+        assert_eq!(page.space_key, "OPS");
+        assert_eq!(page.parent_id.as_deref(), Some("65601"));
+        assert_eq!(page.version, 1);
+        assert_eq!(page.author.unwrap().display_name, "Ada Lovelace");
+        assert_eq!(page.created_at, "2024-06-03T08:15:42.117Z");
+        assert_eq!(
+            page.url,
+            "https://example.atlassian.net/wiki/spaces/OPS/pages/98305/Database+failover+runbook"
+        );
+        assert_eq!(
+            page.body.unwrap().storage.as_deref(),
+            Some(request["body"]["storage"]["value"].as_str().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_update_request_bumps_version() {
+        let request = update_page_request("98305", "Runbook", "OPS", "<p>v2</p>", 1, "");
+        assert_eq!(request["version"], json!({ "number": 2 }));
+
+        let request = update_page_request("98305", "Runbook", "OPS", "<p>v3</p>", 2, "Reviewed");
+        assert_eq!(
+            request["version"],
+            json!({ "number": 3, "message": "Reviewed" })
+        );
+    }
+
+    #[test]
+    fn test_text_body_is_escaped() {
+        assert_eq!(
+            text_to_storage("a < b & c\nnext line\n\n\n\"quoted\""),
+            "<p>a &lt; b &amp; c<br />next line</p><p>&quot;quoted&quot;</p>"
+        );
+        assert_eq!(storage_body("<p>raw</p>", "storage"), "<p>raw</p>");
+    }
+
+    #[test]
+    fn test_cql_search() {
+        assert_eq!(
+            build_cql("", "fail\"over", "OPS", "page"),
+            "text ~ \"fail\\\"over\" AND space = \"OPS\" AND type = \"page\""
+        );
+        assert_eq!(
+            build_cql("label = runbook", "ignored", "OPS", "page"),
+            "label = runbook"
+        );
+
+        let response: Value = from_str(SEARCH_FIXTURE).unwrap();
+        let search = parse_confluence_search(&response, BASE_URL);
+
+        assert_eq!(search.total, 5);
+        assert_eq!(search.limit, 2);
+        assert_eq!(
+            search
+                .results
+                .iter()
+                .map(|content| (content.id.as_str(), content.content_type.as_str()))
+                .collect::<Vec<_>>(),
+            vec![("98305", "page"), ("98417", "blogpost")]
+        );
+        assert_eq!(search.results[1].space_key.as_deref(), Some("OPS"));
+        assert_eq!(
+            search.results[1].updated_at.as_deref(),
+            Some("2024-06-12T09:30:00.000Z")
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use super::{ConfluenceContent, parse_confluence_content};
+use super::{ConfluenceContent, parse_confluence_search};
 use crate::data::atlassian::provider::{ATLASSIAN_PROVIDER_ID, AtlassianProvider};
 use flow_like::flow::{
     execution::{LogLevel, context::ExecutionContext},
@@ -8,6 +8,33 @@ use flow_like::flow::{
 };
 use flow_like_types::{Value, async_trait, json::json, reqwest};
 
+/// Uses `cql` as is when given, otherwise builds a query from the simple filters
+pub fn build_cql(cql: &str, text: &str, space_key: &str, content_type: &str) -> String {
+    if !cql.is_empty() {
+        return cql.to_string();
+    }
+
+    let mut parts = Vec::new();
+
+    if !text.is_empty() {
+        parts.push(format!("text ~ \"{}\"", text.replace('"', "\\\"")));
+    }
+
+    if !space_key.is_empty() {
+        parts.push(format!("space = \"{}\"", space_key));
+    }
+
+    if content_type != "all" {
+        parts.push(format!("type = \"{}\"", content_type));
+    }
+
+    if parts.is_empty() {
+        "type = page ORDER BY lastmodified DESC".to_string()
+    } else {
+        parts.join(" AND ")
+    }
+}
+
 #[crate::register_node]
 #[derive(Default)]
 pub struct SearchConfluenceContentNode {}
@@ -169,30 +196,7 @@ impl NodeLogic for SearchConfluenceContentNode {
         let limit: i64 = context.evaluate_pin("limit").await?;
         let start: i64 = context.evaluate_pin("start").await?;
 
-        // Build CQL query
-        let final_cql = if !cql.is_empty() {
-            cql
-        } else {
-            let mut parts = Vec::new();
-
-            if !text.is_empty() {
-                parts.push(format!("text ~ \"{}\"", text.replace('"', "\\\"")));
-            }
-
-            if !space_key.is_empty() {
-                parts.push(format!("space = \"{}\"", space_key));
-            }
-
-            if content_type != "all" {
-                parts.push(format!("type = \"{}\"", content_type));
-            }
-
-            if parts.is_empty() {
-                "type = page ORDER BY lastmodified DESC".to_string()
-            } else {
-                parts.join(" AND ")
-            }
-        };
+        let final_cql = build_cql(&cql, &text, &space_key, &content_type);
 
         let client = reqwest::Client::new();
 
@@ -256,21 +260,9 @@ impl NodeLogic for SearchConfluenceContentNode {
             }
         };
 
-        let total = data
-            .get("totalSize")
-            .or_else(|| data.get("size"))
-            .and_then(|v| v.as_i64())
-            .unwrap_or(0);
-
-        let results_data = data.get("results").and_then(|v| v.as_array());
-
-        let results: Vec<ConfluenceContent> = results_data
-            .map(|arr| {
-                arr.iter()
-                    .filter_map(|v| parse_confluence_content(v, &provider.base_url))
-                    .collect()
-            })
-            .unwrap_or_default();
+        let search = parse_confluence_search(&data, &provider.base_url);
+        let total = search.total;
+        let results = search.results;
 
         let returned_count = results.len() as i64;
         let has_more = start + returned_count < total;
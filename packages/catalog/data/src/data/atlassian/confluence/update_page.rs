@@ -1,4 +1,4 @@
-use super::{ConfluencePage, parse_confluence_page};
+use super::{ConfluencePage, parse_confluence_page, storage_body, update_page_request};
 use crate::data::atlassian::provider::{ATLASSIAN_PROVIDER_ID, AtlassianProvider};
 use flow_like::flow::{
     execution::{LogLevel, context::ExecutionContext},
@@ -6,7 +6,11 @@ use flow_like::flow::{
     pin::PinOptions,
     variable::VariableType,
 };
-use flow_like_types::{Value, async_trait, json::json, reqwest};
+use flow_like_types::{
+    Value, async_trait,
+    json::json,
+    reqwest::{self, StatusCode},
+};
 
 #[crate::register_node]
 #[derive(Default)]
@@ -28,6 +32,7 @@ impl NodeLogic for UpdateConfluencePageNode {
             "Data/Atlassian/Confluence",
         );
         node.add_icon("/flow/icons/confluence.svg");
+        node.set_version(1);
 
         node.add_input_pin(
             "exec_in",
@@ -63,11 +68,24 @@ impl NodeLogic for UpdateConfluencePageNode {
         node.add_input_pin(
             "body",
             "Body",
-            "New page body content (HTML/storage format, leave empty to keep current)",
+            "New page body content as plain text or storage format XHTML (leave empty to keep current)",
             VariableType::String,
         )
         .set_default_value(Some(json!("")));
 
+        node.add_input_pin(
+            "body_format",
+            "Body Format",
+            "'storage' sends the body as is, 'text' escapes it and turns blank lines into paragraphs",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("storage")))
+        .set_options(
+            PinOptions::new()
+                .set_valid_values(vec!["storage".to_string(), "text".to_string()])
+                .build(),
+        );
+
         node.add_input_pin(
             "version_message",
             "Version Message",
@@ -122,6 +140,7 @@ impl NodeLogic for UpdateConfluencePageNode {
         let page_id: String = context.evaluate_pin("page_id").await?;
         let title: String = context.evaluate_pin("title").await?;
         let body: String = context.evaluate_pin("body").await?;
+        let body_format: String = context.evaluate_pin("body_format").await?;
         let version_message: String = context.evaluate_pin("version_message").await?;
 
         if page_id.is_empty() {
@@ -131,137 +150,106 @@ impl NodeLogic for UpdateConfluencePageNode {
         }
 
         let client = reqwest::Client::new();
-        let base = provider.base_url.trim_end_matches('/');
+        let update_url = provider.confluence_content_url(&format!("/{}", page_id));
+        let body = if body.is_empty() {
+            None
+        } else {
+            Some(storage_body(&body, &body_format))
+        };
 
-        // First, get the current page to get version and current values
-        let get_url = format!(
-            "{}/wiki/rest/api/content/{}?expand=version,body.storage,space",
-            base, page_id
-        );
+        // Someone else may save the page between reading its version and writing the next one,
+        // Confluence answers that with a conflict and the update is retried once on the new version
+        let mut retried = false;
+        let response = loop {
+            let current_page = match fetch_current_page(&client, &provider, &page_id).await {
+                Ok(page) => page,
+                Err(error) => {
+                    context.log_message(&error, LogLevel::Error);
+                    context.activate_exec_pin("error").await?;
+                    return Ok(());
+                }
+            };
+
+            let current_version = current_page
+                .get("version")
+                .and_then(|v| v.get("number"))
+                .and_then(|n| n.as_i64())
+                .unwrap_or(1);
+
+            let current_title = current_page
+                .get("title")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default();
+
+            let current_body = current_page
+                .get("body")
+                .and_then(|b| b.get("storage"))
+                .and_then(|s| s.get("value"))
+                .and_then(|v| v.as_str())
+                .unwrap_or_default();
+
+            let space_key = current_page
+                .get("space")
+                .and_then(|s| s.get("key"))
+                .and_then(|v| v.as_str())
+                .unwrap_or_default();
+
+            let final_title = if title.is_empty() {
+                current_title
+            } else {
+                &title
+            };
+
+            let request_body = update_page_request(
+                &page_id,
+                final_title,
+                space_key,
+                body.as_deref().unwrap_or(current_body),
+                current_version,
+                &version_message,
+            );
 
-        let current_page = client
-            .get(&get_url)
-            .header("Authorization", provider.auth_header())
-            .header("Accept", "application/json")
-            .send()
-            .await;
+            context.log_message(
+                &format!(
+                    "Updating Confluence page {} (version {} -> {})",
+                    page_id,
+                    current_version,
+                    current_version + 1
+                ),
+                LogLevel::Debug,
+            );
 
-        let current_page = match current_page {
-            Ok(r) if r.status().is_success() => match r.json::<Value>().await {
-                Ok(d) => d,
+            let response = client
+                .put(&update_url)
+                .header("Authorization", provider.auth_header())
+                .header("Content-Type", "application/json")
+                .header("Accept", "application/json")
+                .json(&request_body)
+                .send()
+                .await;
+
+            let response = match response {
+                Ok(r) => r,
                 Err(e) => {
-                    context.log_message(
-                        &format!("Failed to parse current page: {}", e),
-                        LogLevel::Error,
-                    );
+                    context.log_message(&format!("Request failed: {}", e), LogLevel::Error);
                     context.activate_exec_pin("error").await?;
                     return Ok(());
                 }
-            },
-            Ok(r) => {
-                let error_text = r.text().await.unwrap_or_default();
+            };
+
+            if response.status() == StatusCode::CONFLICT && !retried {
                 context.log_message(
-                    &format!("Failed to get current page: {}", error_text),
-                    LogLevel::Error,
+                    &format!(
+                        "Page {} changed while updating, retrying on its latest version",
+                        page_id
+                    ),
+                    LogLevel::Warn,
                 );
-                context.activate_exec_pin("error").await?;
-                return Ok(());
-            }
-            Err(e) => {
-                context.log_message(&format!("Request failed: {}", e), LogLevel::Error);
-                context.activate_exec_pin("error").await?;
-                return Ok(());
+                retried = true;
+                continue;
             }
-        };
-
-        let current_version = current_page
-            .get("version")
-            .and_then(|v| v.get("number"))
-            .and_then(|n| n.as_i64())
-            .unwrap_or(1);
-
-        let current_title = current_page
-            .get("title")
-            .and_then(|v| v.as_str())
-            .unwrap_or_default();
-
-        let current_body = current_page
-            .get("body")
-            .and_then(|b| b.get("storage"))
-            .and_then(|s| s.get("value"))
-            .and_then(|v| v.as_str())
-            .unwrap_or_default();
-
-        let space_key = current_page
-            .get("space")
-            .and_then(|s| s.get("key"))
-            .and_then(|v| v.as_str())
-            .unwrap_or_default();
-
-        // Determine final values
-        let final_title = if title.is_empty() {
-            current_title.to_string()
-        } else {
-            title
-        };
 
-        let final_body = if body.is_empty() {
-            current_body.to_string()
-        } else {
-            body
-        };
-
-        // Build update request
-        let update_url = format!("{}/wiki/rest/api/content/{}", base, page_id);
-
-        let mut request_body = json!({
-            "id": page_id,
-            "type": "page",
-            "title": final_title,
-            "space": {
-                "key": space_key
-            },
-            "body": {
-                "storage": {
-                    "value": final_body,
-                    "representation": "storage"
-                }
-            },
-            "version": {
-                "number": current_version + 1
-            }
-        });
-
-        if !version_message.is_empty() {
-            request_body["version"]["message"] = json!(version_message);
-        }
-
-        context.log_message(
-            &format!(
-                "Updating Confluence page {} (version {} -> {})",
-                page_id,
-                current_version,
-                current_version + 1
-            ),
-            LogLevel::Debug,
-        );
-
-        let response = client
-            .put(&update_url)
-            .header("Authorization", provider.auth_header())
-            .header("Content-Type", "application/json")
-            .header("Accept", "application/json")
-            .json(&request_body)
-            .send()
-            .await;
-
-        let response = match response {
-            Ok(r) => r,
-            Err(e) => {
-                context.log_message(&format!("Request failed: {}", e), LogLevel::Error);
-                context.activate_exec_pin("error").await?;
-                return Ok(());
-            }
+            break response;
         };
 
         if !response.status().is_success() {
@@ -294,3 +282,31 @@ impl NodeLogic for UpdateConfluencePageNode {
         Ok(())
     }
 }
+
+/// Reads the page with the version and body an update has to build on
+async fn fetch_current_page(
+    client: &reqwest::Client,
+    provider: &AtlassianProvider,
+    page_id: &str,
+) -> Result<Value, String> {
+    let url =
+        provider.confluence_content_url(&format!("/{}?expand=version,body.storage,space", page_id));
+
+    let response = client
+        .get(&url)
+        .header("Authorization", provider.auth_header())
+        .header("Accept", "application/json")
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
+
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(format!("Failed to get current page: {}", error_text));
+    }
+
+    response
+        .json::<Value>()
+        .await
+        .map_err(|e| format!("Failed to parse current page: {}", e))
+}
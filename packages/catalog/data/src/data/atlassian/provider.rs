@@ -93,19 +93,25 @@ impl AtlassianProvider {
         }
     }
 
-    /// For Confluence search, we need to use the v1 REST API which has different path structure
-    pub fn confluence_search_url(&self) -> String {
+    /// URL on the v1 content API, which pages are created, updated and searched with.
+    /// `path` is appended to `/wiki/rest/api/content`, e.g. `/12345?expand=version`
+    pub fn confluence_content_url(&self, path: &str) -> String {
         if self.auth_type == "oauth"
             && let Some(cloud_id) = &self.cloud_id
         {
             return format!(
-                "https://api.atlassian.com/ex/confluence/{}/wiki/rest/api/content/search",
-                cloud_id
+                "https://api.atlassian.com/ex/confluence/{}/wiki/rest/api/content{}",
+                cloud_id, path
             );
         }
 
         let base = self.base_url.trim_end_matches('/');
-        format!("{}/wiki/rest/api/content/search", base)
+        format!("{}/wiki/rest/api/content{}", base, path)
+    }
+
+    /// For Confluence search, we need to use the v1 REST API which has different path structure
+    pub fn confluence_search_url(&self) -> String {
+        self.confluence_content_url("/search")
     }
 
     pub fn auth_header(&self) -> String {
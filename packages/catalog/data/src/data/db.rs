@@ -1 +1,3 @@
+pub mod mongo;
+pub mod postgres;
 pub mod vector;
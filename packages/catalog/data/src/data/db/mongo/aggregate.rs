@@ -0,0 +1,199 @@
+#[cfg(all(feature = "execute", feature = "mongodb"))]
+use flow_like::flow::execution::{LogLevel, internal_node::InternalNode};
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic, NodeScores},
+    pin::ValueType,
+    variable::VariableType,
+};
+use flow_like_types::{Value, async_trait, json::json};
+#[cfg(all(feature = "execute", feature = "mongodb"))]
+use futures::StreamExt;
+
+use super::add_collection_pins;
+#[cfg(all(feature = "execute", feature = "mongodb"))]
+use super::{aggregate_batches, evaluate_collection};
+
+/// Stages of a pipeline given either as an array or as JSON text of an array
+pub fn parse_pipeline(pipeline: Value) -> flow_like_types::Result<Vec<Value>> {
+    let pipeline = match pipeline {
+        Value::String(text) => flow_like_types::json::from_str(&text)
+            .map_err(|e| flow_like_types::anyhow!("Pipeline is not valid JSON: {}", e))?,
+        other => other,
+    };
+    match pipeline {
+        Value::Array(stages) => Ok(stages),
+        Value::Null => Ok(vec![]),
+        other => Err(flow_like_types::anyhow!(
+            "Pipeline must be an array of stages, got {}",
+            other
+        )),
+    }
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct MongoAggregateNode {}
+
+impl MongoAggregateNode {
+    pub fn new() -> Self {
+        MongoAggregateNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for MongoAggregateNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "db_mongo_aggregate",
+            "MongoDB Aggregate",
+            "Runs an aggregation pipeline and streams the results in batches. The next batch is fetched from the server only after the previous one was processed, so large results are never loaded at once",
+            "Data/Database/MongoDB",
+        );
+        node.add_icon("/flow/icons/database.svg");
+        node.set_long_running(true);
+
+        node.add_input_pin("exec_in", "Input", "", VariableType::Execution);
+        add_collection_pins(&mut node);
+        node.add_input_pin(
+            "pipeline",
+            "Pipeline",
+            "Pipeline stages as an array or JSON text, e.g. [{\"$match\": {\"status\": \"open\"}}, {\"$group\": {\"_id\": \"$owner\", \"total\": {\"$sum\": 1}}}]",
+            VariableType::Generic,
+        )
+        .set_default_value(Some(json!([])));
+        node.add_input_pin(
+            "batch_size",
+            "Batch Size",
+            "Maximum number of documents per batch",
+            VariableType::Integer,
+        )
+        .set_default_value(Some(json!(1000)));
+
+        node.add_output_pin(
+            "on_batch",
+            "For Batch",
+            "Executes for each batch of documents",
+            VariableType::Execution,
+        );
+        node.add_output_pin(
+            "batch",
+            "Batch",
+            "Documents of the current batch",
+            VariableType::Struct,
+        )
+        .set_value_type(ValueType::Array);
+        node.add_output_pin(
+            "batch_index",
+            "Batch Index",
+            "Zero-based index of the current batch",
+            VariableType::Integer,
+        );
+        node.add_output_pin(
+            "done",
+            "Done",
+            "Executes once the cursor is exhausted",
+            VariableType::Execution,
+        );
+        node.add_output_pin(
+            "total_documents",
+            "Total Documents",
+            "Number of documents the pipeline returned",
+            VariableType::Integer,
+        );
+
+        node.scores = Some(NodeScores {
+            privacy: 5,
+            security: 7,
+            performance: 8,
+            governance: 7,
+            reliability: 8,
+            cost: 7,
+        });
+
+        node
+    }
+
+    #[cfg(all(feature = "execute", feature = "mongodb"))]
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("done").await?;
+
+        let pipeline = parse_pipeline(context.evaluate_pin("pipeline").await?)?;
+        let batch_size: i64 = context.evaluate_pin("batch_size").await?;
+        let collection = evaluate_collection(context).await?;
+
+        let mut batches = aggregate_batches(
+            &collection,
+            &pipeline,
+            batch_size.clamp(1, u32::MAX as i64) as u32,
+        )
+        .await?;
+
+        let on_batch = context.get_pin_by_name("on_batch").await?;
+        context.activate_exec_pin_ref(&on_batch).await?;
+        let flow = on_batch.get_connected_nodes();
+
+        let mut total_documents: u64 = 0;
+        let mut batch_index: u64 = 0;
+
+        while let Some(batch) = batches.next().await {
+            context.check_cancelled()?;
+            let documents = batch?;
+            total_documents += documents.len() as u64;
+
+            context.set_pin_value("batch", json!(documents)).await?;
+            context
+                .set_pin_value("batch_index", json!(batch_index))
+                .await?;
+
+            for node in &flow {
+                let mut sub_context = context.create_sub_context(node).await;
+                let run = InternalNode::trigger(&mut sub_context, &mut None, true).await;
+                sub_context.end_trace();
+                context.push_sub_context(&mut sub_context);
+
+                if let Err(error) = run {
+                    context.log_message(&format!("Error: {:?}", error), LogLevel::Error);
+                }
+            }
+
+            batch_index += 1;
+        }
+
+        context.deactivate_exec_pin_ref(&on_batch).await?;
+        context
+            .set_pin_value("total_documents", json!(total_documents))
+            .await?;
+        context.activate_exec_pin("done").await?;
+
+        Ok(())
+    }
+
+    #[cfg(not(all(feature = "execute", feature = "mongodb")))]
+    async fn run(&self, _context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        Err(flow_like_types::anyhow!(
+            "MongoDB support is not enabled. Rebuild with the 'execute' and 'mongodb' feature flags."
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pipeline_accepts_array_or_json_text() {
+        let stages = json!([{"$match": {"status": "open"}}, {"$count": "open"}]);
+        assert_eq!(
+            parse_pipeline(stages.clone()).unwrap(),
+            stages.as_array().unwrap().clone()
+        );
+        assert_eq!(
+            parse_pipeline(json!(stages.to_string())).unwrap(),
+            stages.as_array().unwrap().clone()
+        );
+        assert!(parse_pipeline(json!(null)).unwrap().is_empty());
+        assert!(parse_pipeline(json!({"$match": {}})).is_err());
+        assert!(parse_pipeline(json!("[{")).is_err());
+    }
+}
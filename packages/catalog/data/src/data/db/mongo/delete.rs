@@ -0,0 +1,93 @@
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic, NodeScores},
+    variable::VariableType,
+};
+use flow_like_types::{async_trait, json::json};
+
+use super::add_collection_pins;
+#[cfg(all(feature = "execute", feature = "mongodb"))]
+use super::{delete_documents, evaluate_collection};
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct MongoDeleteNode {}
+
+impl MongoDeleteNode {
+    pub fn new() -> Self {
+        MongoDeleteNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for MongoDeleteNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "db_mongo_delete",
+            "MongoDB Delete",
+            "Deletes documents from a MongoDB collection",
+            "Data/Database/MongoDB",
+        );
+        node.add_icon("/flow/icons/database.svg");
+
+        node.add_input_pin("exec_in", "Input", "", VariableType::Execution);
+        add_collection_pins(&mut node);
+        node.add_input_pin(
+            "filter",
+            "Filter",
+            "Documents to delete. An empty filter with Many deletes the whole collection",
+            VariableType::Struct,
+        )
+        .set_default_value(Some(json!({})));
+        node.add_input_pin(
+            "many",
+            "Many",
+            "Delete every matching document instead of the first",
+            VariableType::Boolean,
+        )
+        .set_default_value(Some(json!(false)));
+
+        node.add_output_pin("exec_out", "Done", "", VariableType::Execution);
+        node.add_output_pin(
+            "deleted_count",
+            "Deleted",
+            "Number of deleted documents",
+            VariableType::Integer,
+        );
+
+        node.scores = Some(NodeScores {
+            privacy: 5,
+            security: 6,
+            performance: 8,
+            governance: 6,
+            reliability: 8,
+            cost: 8,
+        });
+
+        node
+    }
+
+    #[cfg(all(feature = "execute", feature = "mongodb"))]
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+
+        let filter = context.evaluate_pin("filter").await?;
+        let many: bool = context.evaluate_pin("many").await?;
+        let collection = evaluate_collection(context).await?;
+
+        let deleted = delete_documents(&collection, &filter, many).await?;
+
+        context
+            .set_pin_value("deleted_count", json!(deleted))
+            .await?;
+        context.activate_exec_pin("exec_out").await?;
+        Ok(())
+    }
+
+    #[cfg(not(all(feature = "execute", feature = "mongodb")))]
+    async fn run(&self, _context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        Err(flow_like_types::anyhow!(
+            "MongoDB support is not enabled. Rebuild with the 'execute' and 'mongodb' feature flags."
+        ))
+    }
+}
@@ -0,0 +1,118 @@
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic, NodeScores},
+    pin::ValueType,
+    variable::VariableType,
+};
+use flow_like_types::{async_trait, json::json};
+
+use super::add_collection_pins;
+#[cfg(all(feature = "execute", feature = "mongodb"))]
+use super::{evaluate_collection, find_documents};
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct MongoFindNode {}
+
+impl MongoFindNode {
+    pub fn new() -> Self {
+        MongoFindNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for MongoFindNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "db_mongo_find",
+            "MongoDB Find",
+            "Finds documents in a MongoDB collection. ObjectIds are returned as hex strings, strings under _id in the filter are matched as ObjectIds again",
+            "Data/Database/MongoDB",
+        );
+        node.add_icon("/flow/icons/database.svg");
+
+        node.add_input_pin("exec_in", "Input", "", VariableType::Execution);
+        add_collection_pins(&mut node);
+        node.add_input_pin(
+            "filter",
+            "Filter",
+            "Query filter, e.g. {\"status\": \"open\", \"age\": {\"$gt\": 30}}. Empty matches every document",
+            VariableType::Struct,
+        )
+        .set_default_value(Some(json!({})));
+        node.add_input_pin(
+            "projection",
+            "Projection",
+            "Fields to include or exclude, e.g. {\"name\": 1, \"_id\": 0}. Empty returns whole documents",
+            VariableType::Struct,
+        )
+        .set_default_value(Some(json!({})));
+        node.add_input_pin(
+            "sort",
+            "Sort",
+            "Sort order, e.g. {\"created\": -1}",
+            VariableType::Struct,
+        )
+        .set_default_value(Some(json!({})));
+        node.add_input_pin(
+            "limit",
+            "Limit",
+            "Maximum number of documents, 0 returns every match",
+            VariableType::Integer,
+        )
+        .set_default_value(Some(json!(100)));
+
+        node.add_output_pin("exec_out", "Done", "", VariableType::Execution);
+        node.add_output_pin(
+            "documents",
+            "Documents",
+            "Matching documents",
+            VariableType::Struct,
+        )
+        .set_value_type(ValueType::Array);
+        node.add_output_pin(
+            "count",
+            "Count",
+            "Number of returned documents",
+            VariableType::Integer,
+        );
+
+        node.scores = Some(NodeScores {
+            privacy: 5,
+            security: 7,
+            performance: 8,
+            governance: 7,
+            reliability: 8,
+            cost: 8,
+        });
+
+        node
+    }
+
+    #[cfg(all(feature = "execute", feature = "mongodb"))]
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+
+        let filter = context.evaluate_pin("filter").await?;
+        let projection = context.evaluate_pin("projection").await?;
+        let sort = context.evaluate_pin("sort").await?;
+        let limit: i64 = context.evaluate_pin("limit").await?;
+        let collection = evaluate_collection(context).await?;
+
+        let documents = find_documents(&collection, &filter, &projection, &sort, limit).await?;
+
+        context
+            .set_pin_value("count", json!(documents.len()))
+            .await?;
+        context.set_pin_value("documents", json!(documents)).await?;
+        context.activate_exec_pin("exec_out").await?;
+        Ok(())
+    }
+
+    #[cfg(not(all(feature = "execute", feature = "mongodb")))]
+    async fn run(&self, _context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        Err(flow_like_types::anyhow!(
+            "MongoDB support is not enabled. Rebuild with the 'execute' and 'mongodb' feature flags."
+        ))
+    }
+}
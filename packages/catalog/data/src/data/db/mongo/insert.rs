@@ -0,0 +1,88 @@
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic, NodeScores},
+    pin::ValueType,
+    variable::VariableType,
+};
+#[cfg(all(feature = "execute", feature = "mongodb"))]
+use flow_like_types::Value;
+use flow_like_types::{async_trait, json::json};
+
+use super::add_collection_pins;
+#[cfg(all(feature = "execute", feature = "mongodb"))]
+use super::{evaluate_collection, insert_documents};
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct MongoInsertNode {}
+
+impl MongoInsertNode {
+    pub fn new() -> Self {
+        MongoInsertNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for MongoInsertNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "db_mongo_insert",
+            "MongoDB Insert",
+            "Inserts documents into a MongoDB collection. Documents without _id get a new ObjectId",
+            "Data/Database/MongoDB",
+        );
+        node.add_icon("/flow/icons/database.svg");
+
+        node.add_input_pin("exec_in", "Input", "", VariableType::Execution);
+        add_collection_pins(&mut node);
+        node.add_input_pin(
+            "documents",
+            "Documents",
+            "Documents to insert",
+            VariableType::Struct,
+        )
+        .set_value_type(ValueType::Array)
+        .set_default_value(Some(json!([])));
+
+        node.add_output_pin("exec_out", "Done", "", VariableType::Execution);
+        node.add_output_pin(
+            "inserted_ids",
+            "Inserted Ids",
+            "Ids of the inserted documents in input order, ObjectIds as hex strings",
+            VariableType::String,
+        )
+        .set_value_type(ValueType::Array);
+
+        node.scores = Some(NodeScores {
+            privacy: 5,
+            security: 7,
+            performance: 8,
+            governance: 7,
+            reliability: 8,
+            cost: 8,
+        });
+
+        node
+    }
+
+    #[cfg(all(feature = "execute", feature = "mongodb"))]
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+
+        let documents: Vec<Value> = context.evaluate_pin("documents").await?;
+        let collection = evaluate_collection(context).await?;
+
+        let ids = insert_documents(&collection, &documents).await?;
+
+        context.set_pin_value("inserted_ids", json!(ids)).await?;
+        context.activate_exec_pin("exec_out").await?;
+        Ok(())
+    }
+
+    #[cfg(not(all(feature = "execute", feature = "mongodb")))]
+    async fn run(&self, _context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        Err(flow_like_types::anyhow!(
+            "MongoDB support is not enabled. Rebuild with the 'execute' and 'mongodb' feature flags."
+        ))
+    }
+}
@@ -0,0 +1,130 @@
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic, NodeScores},
+    variable::VariableType,
+};
+use flow_like_types::{async_trait, json::json};
+
+use super::add_collection_pins;
+#[cfg(all(feature = "execute", feature = "mongodb"))]
+use super::{evaluate_collection, update_documents};
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct MongoUpdateNode {}
+
+impl MongoUpdateNode {
+    pub fn new() -> Self {
+        MongoUpdateNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for MongoUpdateNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "db_mongo_update",
+            "MongoDB Update",
+            "Updates documents in a MongoDB collection. With Upsert a document is inserted if none matches the filter",
+            "Data/Database/MongoDB",
+        );
+        node.add_icon("/flow/icons/database.svg");
+
+        node.add_input_pin("exec_in", "Input", "", VariableType::Execution);
+        add_collection_pins(&mut node);
+        node.add_input_pin(
+            "filter",
+            "Filter",
+            "Documents to update, e.g. {\"_id\": \"65f1c0a2b3d4e5f601234567\"}",
+            VariableType::Struct,
+        )
+        .set_default_value(Some(json!({})));
+        node.add_input_pin(
+            "update",
+            "Update",
+            "Update operators, e.g. {\"$set\": {\"status\": \"done\"}}, or an aggregation pipeline",
+            VariableType::Generic,
+        )
+        .set_default_value(Some(json!({})));
+        node.add_input_pin(
+            "many",
+            "Many",
+            "Update every matching document instead of the first",
+            VariableType::Boolean,
+        )
+        .set_default_value(Some(json!(false)));
+        node.add_input_pin(
+            "upsert",
+            "Upsert",
+            "Insert a document built from the filter and update if nothing matches",
+            VariableType::Boolean,
+        )
+        .set_default_value(Some(json!(false)));
+
+        node.add_output_pin("exec_out", "Done", "", VariableType::Execution);
+        node.add_output_pin(
+            "matched_count",
+            "Matched",
+            "Number of documents that matched the filter",
+            VariableType::Integer,
+        );
+        node.add_output_pin(
+            "modified_count",
+            "Modified",
+            "Number of documents that were changed",
+            VariableType::Integer,
+        );
+        node.add_output_pin(
+            "upserted_id",
+            "Upserted Id",
+            "Id of the inserted document, empty if an existing document was updated",
+            VariableType::String,
+        );
+
+        node.scores = Some(NodeScores {
+            privacy: 5,
+            security: 7,
+            performance: 8,
+            governance: 7,
+            reliability: 8,
+            cost: 8,
+        });
+
+        node
+    }
+
+    #[cfg(all(feature = "execute", feature = "mongodb"))]
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+
+        let filter = context.evaluate_pin("filter").await?;
+        let update = context.evaluate_pin("update").await?;
+        let many: bool = context.evaluate_pin("many").await?;
+        let upsert: bool = context.evaluate_pin("upsert").await?;
+        let collection = evaluate_collection(context).await?;
+
+        let summary = update_documents(&collection, &filter, &update, many, upsert).await?;
+
+        context
+            .set_pin_value("matched_count", json!(summary.matched_count))
+            .await?;
+        context
+            .set_pin_value("modified_count", json!(summary.modified_count))
+            .await?;
+        context
+            .set_pin_value(
+                "upserted_id",
+                json!(summary.upserted_id.unwrap_or_default()),
+            )
+            .await?;
+        context.activate_exec_pin("exec_out").await?;
+        Ok(())
+    }
+
+    #[cfg(not(all(feature = "execute", feature = "mongodb")))]
+    async fn run(&self, _context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        Err(flow_like_types::anyhow!(
+            "MongoDB support is not enabled. Rebuild with the 'execute' and 'mongodb' feature flags."
+        ))
+    }
+}
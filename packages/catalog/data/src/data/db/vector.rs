@@ -25,6 +25,7 @@ pub mod purge;
 pub mod schema;
 pub mod upsert;
 pub mod vector_search;
+pub mod vector_store;
 
 #[derive(Default, Serialize, Deserialize, JsonSchema, Clone)]
 pub struct NodeDBConnection {
@@ -63,6 +64,85 @@ impl NodeDBConnection {
     }
 }
 
+/// Opens a table in the project (or user) database and caches the connection for the run.
+pub async fn open_database(
+    context: &mut ExecutionContext,
+    table: String,
+    user_scoped: bool,
+) -> flow_like_types::Result<NodeDBConnection> {
+    let cache_key = if user_scoped {
+        format!("db_user_{}", table)
+    } else {
+        format!("db_{}", table)
+    };
+    let cache_set = context.cache.read().await.contains_key(&cache_key);
+    if !cache_set {
+        let context_cache = context
+            .execution_cache
+            .clone()
+            .ok_or(flow_like_types::anyhow!("No execution cache found"))?;
+        let app_id = context_cache.app_id.clone();
+
+        let db = if let Some(credentials) = &context.credentials {
+            if user_scoped {
+                credentials.to_db_scoped(&app_id).await?
+            } else {
+                credentials.to_db(&app_id).await?
+            }
+        } else if user_scoped {
+            let user_dir = context_cache.get_user_dir(false)?;
+            let user_dir = user_dir.child("db");
+            context
+                .app_state
+                .config
+                .read()
+                .await
+                .callbacks
+                .build_user_database
+                .clone()
+                .ok_or(flow_like_types::anyhow!("No user database builder found"))?(
+                user_dir
+            )
+        } else {
+            let board_dir = context_cache.get_storage(false)?;
+            let board_dir = board_dir.child("db");
+            context
+                .app_state
+                .config
+                .read()
+                .await
+                .callbacks
+                .build_project_database
+                .clone()
+                .ok_or(flow_like_types::anyhow!("No database builder found"))?(board_dir)
+        };
+
+        let db = db.execute().await?;
+        let mut intermediate = LanceDBVectorStore::from_connection(db, table).await;
+        if let Some(opts) = &context
+            .app_state
+            .config
+            .read()
+            .await
+            .callbacks
+            .lance_write_options
+        {
+            intermediate.set_write_options(opts.clone());
+        }
+        let intermediate = CachedDB {
+            db: Arc::new(RwLock::new(intermediate)),
+        };
+        let cacheable: Arc<dyn Cacheable> = Arc::new(intermediate.clone());
+        context
+            .cache
+            .write()
+            .await
+            .insert(cache_key.clone(), cacheable);
+    }
+
+    Ok(NodeDBConnection { cache_key })
+}
+
 #[crate::register_node]
 #[derive(Default)]
 pub struct CreateLocalDatabaseNode {}
@@ -122,79 +202,7 @@ impl NodeLogic for CreateLocalDatabaseNode {
 
         let table: String = context.evaluate_pin("name").await?;
         let user_scoped: bool = context.evaluate_pin("user_scoped").await.unwrap_or(false);
-        let cache_key = if user_scoped {
-            format!("db_user_{}", table)
-        } else {
-            format!("db_{}", table)
-        };
-        let cache_set = context.cache.read().await.contains_key(&cache_key);
-        if !cache_set {
-            let context_cache = context
-                .execution_cache
-                .clone()
-                .ok_or(flow_like_types::anyhow!("No execution cache found"))?;
-            let app_id = context_cache.app_id.clone();
-
-            let db = if let Some(credentials) = &context.credentials {
-                if user_scoped {
-                    credentials.to_db_scoped(&app_id).await?
-                } else {
-                    credentials.to_db(&app_id).await?
-                }
-            } else if user_scoped {
-                let user_dir = context_cache.get_user_dir(false)?;
-                let user_dir = user_dir.child("db");
-                context
-                    .app_state
-                    .config
-                    .read()
-                    .await
-                    .callbacks
-                    .build_user_database
-                    .clone()
-                    .ok_or(flow_like_types::anyhow!("No user database builder found"))?(
-                    user_dir
-                )
-            } else {
-                let board_dir = context_cache.get_storage(false)?;
-                let board_dir = board_dir.child("db");
-                context
-                    .app_state
-                    .config
-                    .read()
-                    .await
-                    .callbacks
-                    .build_project_database
-                    .clone()
-                    .ok_or(flow_like_types::anyhow!("No database builder found"))?(
-                    board_dir
-                )
-            };
-
-            let db = db.execute().await?;
-            let mut intermediate = LanceDBVectorStore::from_connection(db, table).await;
-            if let Some(opts) = &context
-                .app_state
-                .config
-                .read()
-                .await
-                .callbacks
-                .lance_write_options
-            {
-                intermediate.set_write_options(opts.clone());
-            }
-            let intermediate = CachedDB {
-                db: Arc::new(RwLock::new(intermediate)),
-            };
-            let cacheable: Arc<dyn Cacheable> = Arc::new(intermediate.clone());
-            context
-                .cache
-                .write()
-                .await
-                .insert(cache_key.clone(), cacheable);
-        }
-
-        let db = NodeDBConnection { cache_key };
+        let db = open_database(context, table, user_scoped).await?;
 
         let db: Value = flow_like_types::json::to_value(&db)?;
 
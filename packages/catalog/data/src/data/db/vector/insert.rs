@@ -4,7 +4,13 @@ use flow_like::flow::{
     pin::{PinOptions, ValueType},
     variable::VariableType,
 };
-use flow_like_storage::{databases::vector::VectorStore, object_store::buffered::BufReader};
+use flow_like_storage::{
+    databases::vector::{
+        VectorStore,
+        lancedb::{IncrementalIndexStats, MIN_ROWS_FOR_VECTOR_INDEX},
+    },
+    object_store::buffered::BufReader,
+};
 use flow_like_types::{Value, async_trait, json::json};
 use futures::StreamExt;
 
@@ -128,6 +134,105 @@ impl NodeLogic for BatchInsertLocalDatabaseNode {
     }
 }
 
+#[crate::register_node]
+#[derive(Default)]
+pub struct AppendEmbeddingsLocalDatabaseNode {}
+
+impl AppendEmbeddingsLocalDatabaseNode {
+    pub fn new() -> Self {
+        AppendEmbeddingsLocalDatabaseNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for AppendEmbeddingsLocalDatabaseNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "append_embeddings_local_db",
+            "Append Embeddings",
+            "Inserts a batch of embedded items and updates the vector index incrementally. Call it for every batch while embedding a large corpus, the table stays searchable in between.",
+            "Data/Database/Insert",
+        );
+        node.add_icon("/flow/icons/database.svg");
+
+        node.add_input_pin("exec_in", "Input", "", VariableType::Execution);
+        node.add_input_pin(
+            "database",
+            "Database",
+            "Database Connection Reference",
+            VariableType::Struct,
+        )
+        .set_schema::<NodeDBConnection>()
+        .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node.add_input_pin(
+            "value",
+            "Value",
+            "Items of this batch, each with its embedding",
+            VariableType::Struct,
+        )
+        .set_value_type(ValueType::Array);
+
+        node.add_input_pin(
+            "column",
+            "Vector Column",
+            "Column holding the embeddings",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("vector")));
+
+        node.add_input_pin(
+            "min_rows",
+            "Min Rows",
+            "Rows needed before the index is trained, at least 256",
+            VariableType::Integer,
+        )
+        .set_default_value(Some(json!(MIN_ROWS_FOR_VECTOR_INDEX)));
+
+        node.add_output_pin(
+            "exec_out",
+            "Appended",
+            "Done appending the batch",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "stats",
+            "Index Stats",
+            "Row counts of the table and its vector index after this batch",
+            VariableType::Struct,
+        )
+        .set_schema::<IncrementalIndexStats>();
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+        let database: NodeDBConnection = context.evaluate_pin("database").await?;
+        let database = database.load(context).await?.db.clone();
+        let mut database = database.write().await;
+        let value: Vec<Value> = context.evaluate_pin("value").await?;
+        let column: String = context.evaluate_pin("column").await?;
+        let min_rows: i64 = context.evaluate_pin("min_rows").await?;
+
+        let stats = database
+            .append_indexed(value, &column, min_rows.max(0) as usize)
+            .await?;
+        if stats.index_created {
+            context.log_message(
+                &format!("Trained vector index on {} rows", stats.indexed_rows),
+                LogLevel::Info,
+            );
+        }
+
+        context.set_pin_value("stats", json!(stats)).await?;
+        context.activate_exec_pin("exec_out").await?;
+
+        Ok(())
+    }
+}
+
 #[crate::register_node]
 #[derive(Default)]
 pub struct BatchInsertCSVLocalDatabaseNode {}
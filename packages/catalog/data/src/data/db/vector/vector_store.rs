@@ -0,0 +1,389 @@
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic},
+    pin::{PinOptions, ValueType},
+    variable::VariableType,
+};
+use flow_like_storage::{
+    databases::vector::{VectorStore, lancedb::LanceDBVectorStore},
+    lancedb::DistanceType,
+};
+use flow_like_types::{
+    JsonSchema, Value, async_trait, bail,
+    json::{self, Deserialize, Map, Serialize, json},
+};
+
+use super::open_database;
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Copy, Debug, Default, PartialEq)]
+pub enum VectorDistance {
+    #[default]
+    Cosine,
+    L2,
+}
+
+impl VectorDistance {
+    fn distance_type(self) -> DistanceType {
+        match self {
+            VectorDistance::Cosine => DistanceType::Cosine,
+            VectorDistance::L2 => DistanceType::L2,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq)]
+pub struct VectorMatch {
+    pub id: String,
+    pub distance: f64,
+    pub metadata: Value,
+}
+
+/// Rows are stored as `id`, `vector` and the metadata serialized to JSON, so entries
+/// with differently shaped metadata can share a table.
+pub async fn upsert_vector(
+    store: &mut LanceDBVectorStore,
+    id: String,
+    vector: Vec<f32>,
+    metadata: &Value,
+) -> flow_like_types::Result<()> {
+    if id.is_empty() {
+        bail!("Vector id must not be empty");
+    }
+    if vector.is_empty() {
+        bail!("Vector must not be empty");
+    }
+
+    let row = json!({
+        "id": id,
+        "vector": vector,
+        "metadata": json::to_string(metadata)?,
+    });
+    store.upsert(vec![row], "id".to_string()).await
+}
+
+fn matches_filter(metadata: &Value, filter: &Map<String, Value>) -> bool {
+    filter
+        .iter()
+        .all(|(key, expected)| metadata.get(key) == Some(expected))
+}
+
+fn to_match(row: &Value) -> Option<VectorMatch> {
+    let metadata = row
+        .get("metadata")
+        .and_then(Value::as_str)
+        .and_then(|metadata| json::from_str(metadata).ok())
+        .unwrap_or(Value::Null);
+
+    Some(VectorMatch {
+        id: row.get("id")?.as_str()?.to_string(),
+        distance: row.get("_distance")?.as_f64()?,
+        metadata,
+    })
+}
+
+/// Returns the `top_k` closest entries whose metadata contains all `filter` fields.
+pub async fn search_vectors(
+    store: &LanceDBVectorStore,
+    vector: Vec<f64>,
+    top_k: usize,
+    distance: VectorDistance,
+    filter: &Map<String, Value>,
+) -> flow_like_types::Result<Vec<VectorMatch>> {
+    // Metadata is stored as JSON text, so filtering happens after ranking all rows
+    let limit = if filter.is_empty() {
+        top_k
+    } else {
+        store.count(None).await.unwrap_or(top_k)
+    };
+
+    let rows = store
+        .nearest_neighbors(vector, distance.distance_type(), None, limit.max(1))
+        .await?;
+
+    Ok(rows
+        .iter()
+        .filter_map(to_match)
+        .filter(|result| matches_filter(&result.metadata, filter))
+        .take(top_k)
+        .collect())
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct VectorUpsertNode {}
+
+impl VectorUpsertNode {
+    pub fn new() -> Self {
+        VectorUpsertNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for VectorUpsertNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "vector_store_upsert",
+            "Upsert Vector",
+            "Stores an embedding with metadata in a project vector table. The table is created on first use",
+            "Data/Database/Vector",
+        );
+        node.add_icon("/flow/icons/database.svg");
+
+        node.add_input_pin(
+            "exec_in",
+            "Input",
+            "Initiate Execution",
+            VariableType::Execution,
+        );
+
+        node.add_input_pin("table", "Table", "Vector table name", VariableType::String)
+            .set_default_value(Some(json!("vectors")));
+
+        node.add_input_pin(
+            "id",
+            "ID",
+            "Unique id, an existing entry with this id is replaced",
+            VariableType::String,
+        );
+
+        node.add_input_pin("vector", "Vector", "Embedding", VariableType::Float)
+            .set_value_type(ValueType::Array);
+
+        node.add_input_pin(
+            "metadata",
+            "Metadata",
+            "Metadata returned with search matches",
+            VariableType::Struct,
+        )
+        .set_default_value(Some(json!({})));
+
+        node.add_output_pin(
+            "exec_out",
+            "Output",
+            "Done with the Execution",
+            VariableType::Execution,
+        );
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+
+        let table: String = context.evaluate_pin("table").await?;
+        let id: String = context.evaluate_pin("id").await?;
+        let vector: Vec<f32> = context.evaluate_pin("vector").await?;
+        let metadata: Value = context.evaluate_pin("metadata").await.unwrap_or(json!({}));
+
+        let database = open_database(context, table, false).await?;
+        let database = database.load(context).await?.db.clone();
+        let mut database = database.write().await;
+        upsert_vector(&mut database, id, vector, &metadata).await?;
+
+        context.activate_exec_pin("exec_out").await?;
+        Ok(())
+    }
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct VectorSearchNode {}
+
+impl VectorSearchNode {
+    pub fn new() -> Self {
+        VectorSearchNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for VectorSearchNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "vector_store_search",
+            "Search Vectors",
+            "Finds the entries of a project vector table closest to a query vector",
+            "Data/Database/Vector",
+        );
+        node.add_icon("/flow/icons/database.svg");
+
+        node.add_input_pin(
+            "exec_in",
+            "Input",
+            "Initiate Execution",
+            VariableType::Execution,
+        );
+
+        node.add_input_pin("table", "Table", "Vector table name", VariableType::String)
+            .set_default_value(Some(json!("vectors")));
+
+        node.add_input_pin("vector", "Vector", "Query embedding", VariableType::Float)
+            .set_value_type(ValueType::Array);
+
+        node.add_input_pin(
+            "top_k",
+            "Top K",
+            "Number of matches to return",
+            VariableType::Integer,
+        )
+        .set_options(PinOptions::new().set_range((1., 1000.)).build())
+        .set_default_value(Some(json!(5)));
+
+        node.add_input_pin(
+            "distance",
+            "Distance",
+            "Distance metric",
+            VariableType::Struct,
+        )
+        .set_schema::<VectorDistance>()
+        .set_options(
+            PinOptions::new()
+                .set_valid_values(vec!["Cosine".to_string(), "L2".to_string()])
+                .build(),
+        )
+        .set_default_value(Some(json!(VectorDistance::Cosine)));
+
+        node.add_input_pin(
+            "filter",
+            "Metadata Filter",
+            "Only match entries whose metadata has these field values",
+            VariableType::Struct,
+        )
+        .set_default_value(Some(json!({})));
+
+        node.add_output_pin(
+            "exec_out",
+            "Output",
+            "Done with the Execution",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "matches",
+            "Matches",
+            "Closest entries with distance and metadata, closest first",
+            VariableType::Struct,
+        )
+        .set_schema::<VectorMatch>()
+        .set_value_type(ValueType::Array);
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+
+        let table: String = context.evaluate_pin("table").await?;
+        let vector: Vec<f64> = context.evaluate_pin("vector").await?;
+        let top_k: usize = context.evaluate_pin("top_k").await.unwrap_or(5);
+        let distance: VectorDistance = context.evaluate_pin("distance").await.unwrap_or_default();
+        let filter: Map<String, Value> = context.evaluate_pin("filter").await.unwrap_or_default();
+
+        let database = open_database(context, table, false).await?;
+        let database = database.load(context).await?.db.clone();
+        let database = database.read().await;
+        let matches = search_vectors(&database, vector, top_k, distance, &filter).await?;
+
+        context.set_pin_value("matches", json!(matches)).await?;
+        context.activate_exec_pin("exec_out").await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flow_like_types::create_id;
+
+    async fn store() -> (std::path::PathBuf, LanceDBVectorStore) {
+        let path = std::env::temp_dir().join(create_id());
+        std::fs::create_dir_all(&path).unwrap();
+        let mut store = LanceDBVectorStore::new(path.clone(), "vectors".to_string())
+            .await
+            .unwrap();
+
+        for (id, vector, kind) in [
+            ("a", [1.0, 0.0], "doc"),
+            ("b", [10.0, 1.0], "image"),
+            ("c", [0.0, 1.0], "doc"),
+            ("d", [-1.0, 0.0], "doc"),
+        ] {
+            upsert_vector(
+                &mut store,
+                id.to_string(),
+                vector.to_vec(),
+                &json!({ "kind": kind, "name": id }),
+            )
+            .await
+            .unwrap();
+        }
+
+        (path, store)
+    }
+
+    fn ids(matches: &[VectorMatch]) -> Vec<&str> {
+        matches.iter().map(|result| result.id.as_str()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_nearest_neighbor_ordering() {
+        let (path, store) = store().await;
+        let query = vec![1.0, 0.0];
+        let no_filter = Map::new();
+
+        let l2 = search_vectors(&store, query.clone(), 4, VectorDistance::L2, &no_filter)
+            .await
+            .unwrap();
+        assert_eq!(ids(&l2), vec!["a", "c", "d", "b"]);
+        assert_eq!(l2[0].distance, 0.0);
+        assert_eq!(l2[0].metadata, json!({ "kind": "doc", "name": "a" }));
+
+        let cosine = search_vectors(&store, query.clone(), 2, VectorDistance::Cosine, &no_filter)
+            .await
+            .unwrap();
+        assert_eq!(ids(&cosine), vec!["a", "b"]);
+
+        let mut filter = Map::new();
+        filter.insert("kind".to_string(), json!("doc"));
+        let filtered = search_vectors(&store, query, 2, VectorDistance::Cosine, &filter)
+            .await
+            .unwrap();
+        assert_eq!(ids(&filtered), vec!["a", "c"]);
+
+        std::fs::remove_dir_all(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_upsert_replaces_existing_id() {
+        let (path, mut store) = store().await;
+
+        upsert_vector(
+            &mut store,
+            "d".to_string(),
+            vec![1.0, 0.1],
+            &json!({ "kind": "doc", "updated": true }),
+        )
+        .await
+        .unwrap();
+
+        let matches = search_vectors(&store, vec![1.0, 0.0], 10, VectorDistance::L2, &Map::new())
+            .await
+            .unwrap();
+        assert_eq!(ids(&matches), vec!["a", "d", "c", "b"]);
+        assert_eq!(matches[1].metadata["updated"], json!(true));
+
+        std::fs::remove_dir_all(path).unwrap();
+    }
+
+    #[test]
+    fn test_metadata_filter() {
+        let metadata = json!({ "kind": "doc", "lang": "en" });
+        let mut filter = Map::new();
+        assert!(matches_filter(&metadata, &filter));
+
+        filter.insert("kind".to_string(), json!("doc"));
+        assert!(matches_filter(&metadata, &filter));
+
+        filter.insert("lang".to_string(), json!("de"));
+        assert!(!matches_filter(&metadata, &filter));
+    }
+}
@@ -1,3 +1,7 @@
+pub mod batch_update;
+pub mod range;
+pub mod read_records;
+
 use super::provider::{GOOGLE_PROVIDER_ID, GoogleProvider};
 use flow_like::flow::{
     execution::context::ExecutionContext,
@@ -6,6 +10,7 @@ use flow_like::flow::{
     variable::VariableType,
 };
 use flow_like_types::{JsonSchema, Value, async_trait, json::json, reqwest};
+use range::SheetRange;
 use serde::{Deserialize, Serialize};
 
 // =============================================================================
@@ -57,6 +62,145 @@ fn parse_spreadsheet(value: &Value) -> Option<GoogleSpreadsheet> {
     })
 }
 
+// =============================================================================
+// Values
+// =============================================================================
+
+/// Rows of a 2D values array, single values count as a row with one cell.
+pub(crate) fn as_rows(values: Value) -> Result<Vec<Vec<Value>>, String> {
+    match values {
+        Value::Array(rows) => Ok(rows
+            .into_iter()
+            .map(|row| match row {
+                Value::Array(cells) => cells,
+                cell => vec![cell],
+            })
+            .collect()),
+        Value::Null => Ok(Vec::new()),
+        _ => Err("Values must be a 2D array".to_string()),
+    }
+}
+
+/// Rows and columns `values` cover, `major_dimension` tells whether the inner arrays are
+/// rows (`ROWS`) or columns (`COLUMNS`).
+pub(crate) fn values_extent(values: &[Vec<Value>], major_dimension: &str) -> (u32, u32) {
+    let outer = values.len() as u32;
+    let inner = values.iter().map(Vec::len).max().unwrap_or(0) as u32;
+    match major_dimension {
+        "COLUMNS" => (inner, outer),
+        _ => (outer, inner),
+    }
+}
+
+/// `appendDimension` requests that grow the sheets until the last row and column of every
+/// range fit. Writes past the grid of a sheet are rejected by the values API.
+pub(crate) fn expansion_requests(
+    spreadsheet: &GoogleSpreadsheet,
+    needed: &[(SheetRange, u32, u32)],
+) -> Result<Vec<Value>, String> {
+    let mut sizes: Vec<(&GoogleSheet, u32, u32)> = Vec::new();
+    for (range, last_row, last_column) in needed {
+        let sheet = match &range.sheet {
+            Some(title) => spreadsheet
+                .sheets
+                .iter()
+                .find(|sheet| &sheet.title == title),
+            None => spreadsheet.sheets.iter().min_by_key(|sheet| sheet.index),
+        }
+        .ok_or_else(|| {
+            format!(
+                "Sheet \"{}\" not found",
+                range.sheet.as_deref().unwrap_or_default()
+            )
+        })?;
+
+        match sizes
+            .iter_mut()
+            .find(|(known, _, _)| known.sheet_id == sheet.sheet_id)
+        {
+            Some((_, rows, columns)) => {
+                *rows = (*rows).max(*last_row);
+                *columns = (*columns).max(*last_column);
+            }
+            None => sizes.push((sheet, *last_row, *last_column)),
+        }
+    }
+
+    let mut requests = Vec::new();
+    for (sheet, rows, columns) in sizes {
+        let grid = [
+            ("ROWS", rows, sheet.row_count.unwrap_or(0)),
+            ("COLUMNS", columns, sheet.column_count.unwrap_or(0)),
+        ];
+        for (dimension, size, current) in grid {
+            if i64::from(size) > current {
+                requests.push(json!({
+                    "appendDimension": {
+                        "sheetId": sheet.sheet_id,
+                        "dimension": dimension,
+                        "length": i64::from(size) - current
+                    }
+                }));
+            }
+        }
+    }
+    Ok(requests)
+}
+
+/// Body of `values:append`, the rows are sent row major.
+pub(crate) fn append_body(range: &str, rows: &[Vec<Value>]) -> Value {
+    json!({
+        "range": range,
+        "majorDimension": "ROWS",
+        "values": rows
+    })
+}
+
+/// Grows the sheets of `needed` so the ranges fit, see [`expansion_requests`].
+pub(crate) async fn expand_to_fit(
+    client: &reqwest::Client,
+    access_token: &str,
+    spreadsheet_id: &str,
+    needed: &[(SheetRange, u32, u32)],
+) -> Result<(), String> {
+    let response = client
+        .get(format!(
+            "https://sheets.googleapis.com/v4/spreadsheets/{}",
+            spreadsheet_id
+        ))
+        .header("Authorization", format!("Bearer {}", access_token))
+        .query(&[("fields", "spreadsheetId,properties,sheets.properties")])
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(response.text().await.unwrap_or_default());
+    }
+    let body: Value = response.json().await.map_err(|e| e.to_string())?;
+    let spreadsheet =
+        parse_spreadsheet(&body).ok_or_else(|| "Failed to parse spreadsheet".to_string())?;
+
+    let requests = expansion_requests(&spreadsheet, needed)?;
+    if requests.is_empty() {
+        return Ok(());
+    }
+
+    let response = client
+        .post(format!(
+            "https://sheets.googleapis.com/v4/spreadsheets/{}:batchUpdate",
+            spreadsheet_id
+        ))
+        .header("Authorization", format!("Bearer {}", access_token))
+        .json(&json!({ "requests": requests }))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(response.text().await.unwrap_or_default());
+    }
+    Ok(())
+}
+
 // =============================================================================
 // Create Spreadsheet Node
 // =============================================================================
@@ -552,7 +696,7 @@ impl NodeLogic for AppendGoogleSheetsRowsNode {
         let mut node = Node::new(
             "data_google_sheets_append_rows",
             "Append Rows",
-            "Append rows to the end of a Google Sheets range",
+            "Append rows after the last row of a table in a Google Sheets range. Columns the sheet is missing are added first",
             "Data/Google/Sheets",
         );
         node.add_icon("/flow/icons/google.svg");
@@ -570,7 +714,7 @@ impl NodeLogic for AppendGoogleSheetsRowsNode {
         node.add_input_pin(
             "range",
             "Range",
-            "A1 notation range (e.g., 'Sheet1!A:D')",
+            "A1 or R1C1 notation range of the table (e.g., 'Sheet1!A:D')",
             VariableType::String,
         );
         node.add_input_pin(
@@ -580,6 +724,37 @@ impl NodeLogic for AppendGoogleSheetsRowsNode {
             VariableType::Generic,
         )
         .set_value_type(ValueType::Array);
+        node.add_input_pin(
+            "value_input",
+            "Value Input Option",
+            "RAW stores values as they are, USER_ENTERED parses them like typed input (formulas, dates, numbers)",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("USER_ENTERED")))
+        .set_options(
+            PinOptions::new()
+                .set_valid_values(vec!["RAW".to_string(), "USER_ENTERED".to_string()])
+                .build(),
+        );
+        node.add_input_pin(
+            "insert_data",
+            "Insert Data Option",
+            "INSERT_ROWS inserts new rows for the data, OVERWRITE writes into the rows after the table",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("INSERT_ROWS")))
+        .set_options(
+            PinOptions::new()
+                .set_valid_values(vec!["INSERT_ROWS".to_string(), "OVERWRITE".to_string()])
+                .build(),
+        );
+        node.add_input_pin(
+            "expand_sheet",
+            "Expand Sheet",
+            "Add columns to the sheet if the rows are wider than it",
+            VariableType::Boolean,
+        )
+        .set_default_value(Some(json!(true)));
 
         node.add_output_pin("exec_out", "Success", "", VariableType::Execution);
         node.add_output_pin("error", "Error", "", VariableType::Execution);
@@ -613,12 +788,43 @@ impl NodeLogic for AppendGoogleSheetsRowsNode {
         let spreadsheet_id: String = context.evaluate_pin("spreadsheet_id").await?;
         let range: String = context.evaluate_pin("range").await?;
         let values: Value = context.evaluate_pin("values").await?;
+        let value_input: String = context
+            .evaluate_pin("value_input")
+            .await
+            .unwrap_or_else(|_| "USER_ENTERED".to_string());
+        let insert_data: String = context
+            .evaluate_pin("insert_data")
+            .await
+            .unwrap_or_else(|_| "INSERT_ROWS".to_string());
+        let expand_sheet: bool = context.evaluate_pin("expand_sheet").await.unwrap_or(true);
 
-        let body = json!({
-            "values": values
-        });
+        let parsed = SheetRange::parse(&range)
+            .and_then(|sheet_range| as_rows(values).map(|rows| (sheet_range, rows)));
+        let (sheet_range, rows) = match parsed {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                context.set_pin_value("error_message", json!(e)).await?;
+                context.activate_exec_pin("error").await?;
+                return Ok(());
+            }
+        };
 
         let client = reqwest::Client::new();
+        if expand_sheet {
+            // The API inserts the rows it needs, only missing columns make the append fail
+            let (_, width) = values_extent(&rows, "ROWS");
+            let last_column = sheet_range.first_column() + width.saturating_sub(1);
+            let needed = [(sheet_range, 0, last_column)];
+            if let Err(e) =
+                expand_to_fit(&client, &provider.access_token, &spreadsheet_id, &needed).await
+            {
+                context.set_pin_value("error_message", json!(e)).await?;
+                context.activate_exec_pin("error").await?;
+                return Ok(());
+            }
+        }
+
+        let body = append_body(&range, &rows);
         let response = client
             .post(format!(
                 "https://sheets.googleapis.com/v4/spreadsheets/{}/values/{}:append",
@@ -628,8 +834,8 @@ impl NodeLogic for AppendGoogleSheetsRowsNode {
             .header("Authorization", format!("Bearer {}", provider.access_token))
             .header("Content-Type", "application/json")
             .query(&[
-                ("valueInputOption", "USER_ENTERED"),
-                ("insertDataOption", "INSERT_ROWS"),
+                ("valueInputOption", value_input.as_str()),
+                ("insertDataOption", insert_data.as_str()),
             ])
             .json(&body)
             .send()
@@ -983,3 +1189,102 @@ impl NodeLogic for DeleteGoogleSheetNode {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flow_like_types::json::from_str;
+
+    const SPREADSHEET: &str =
+        include_str!("../../../tests/fixtures/google_sheets/spreadsheet.json");
+    const APPEND_RESPONSE: &str =
+        include_str!("../../../tests/fixtures/google_sheets/values_append.json");
+
+    pub(crate) fn spreadsheet() -> GoogleSpreadsheet {
+        parse_spreadsheet(&from_str(SPREADSHEET).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_as_rows() {
+        let rows = as_rows(json!([["a", 1], "single", []])).unwrap();
+        assert_eq!(
+            rows,
+            vec![vec![json!("a"), json!(1)], vec![json!("single")], vec![]]
+        );
+        assert!(as_rows(Value::Null).unwrap().is_empty());
+        assert!(as_rows(json!({ "a": 1 })).is_err());
+        assert_eq!(values_extent(&rows, "ROWS"), (3, 2));
+        assert_eq!(values_extent(&rows, "COLUMNS"), (2, 3));
+    }
+
+    #[test]
+    fn test_append_adds_missing_columns() {
+        let rows = as_rows(json!([
+            [
+                "Hooli",
+                "gavin@example.com",
+                90000,
+                "",
+                "Gavin",
+                "new",
+                "2024-03-01"
+            ],
+            [
+                "Pied Piper",
+                "richard@example.com",
+                150,
+                "",
+                "Richard",
+                "new",
+                "2024-03-02"
+            ]
+        ]))
+        .unwrap();
+        let range = "'Q1 ''24'!A:E";
+
+        assert_eq!(
+            append_body(range, &rows),
+            json!({
+                "range": "'Q1 ''24'!A:E",
+                "majorDimension": "ROWS",
+                "values": rows
+            })
+        );
+
+        let sheet_range = SheetRange::parse(range).unwrap();
+        let (_, width) = values_extent(&rows, "ROWS");
+        let last_column = sheet_range.first_column() + width - 1;
+        let requests =
+            expansion_requests(&spreadsheet(), &[(sheet_range, 0, last_column)]).unwrap();
+        assert_eq!(
+            requests,
+            vec![json!({
+                "appendDimension": {
+                    "sheetId": 1893471602,
+                    "dimension": "COLUMNS",
+                    "length": 2
+                }
+            })]
+        );
+
+        // The recorded append wrote all 7 columns after the expansion
+        let response: Value = from_str(APPEND_RESPONSE).unwrap();
+        let updated =
+            SheetRange::parse(response["updates"]["updatedRange"].as_str().unwrap()).unwrap();
+        assert_eq!(updated.end.column, Some(last_column));
+        assert_eq!(response["updates"]["updatedRows"], json!(rows.len()));
+    }
+
+    #[test]
+    fn test_expansion_needs_a_known_sheet() {
+        let fits = SheetRange::parse("A1:Z1000").unwrap();
+        assert!(
+            expansion_requests(&spreadsheet(), &[(fits, 1000, 26)])
+                .unwrap()
+                .is_empty()
+        );
+
+        let missing = SheetRange::parse("Archive!A1").unwrap();
+        assert!(expansion_requests(&spreadsheet(), &[(missing, 1, 1)]).is_err());
+    }
+}
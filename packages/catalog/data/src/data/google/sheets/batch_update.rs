@@ -0,0 +1,337 @@
+use super::range::SheetRange;
+use super::{expand_to_fit, values_extent};
+use crate::data::google::provider::{GOOGLE_PROVIDER_ID, GoogleProvider};
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic},
+    pin::{PinOptions, ValueType},
+    variable::VariableType,
+};
+use flow_like_types::{JsonSchema, Value, async_trait, json::json, reqwest};
+use serde::{Deserialize, Serialize};
+
+/// Values to write to one range
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SheetsValueRange {
+    /// A1 or R1C1 notation range (e.g., 'Sheet1!A1:C3')
+    pub range: String,
+    /// 2D array of values
+    pub values: Vec<Vec<Value>>,
+    /// ROWS or COLUMNS, the major dimension of the node if not set
+    #[serde(default)]
+    pub major_dimension: Option<String>,
+}
+
+pub(crate) fn batch_update_body(
+    updates: &[SheetsValueRange],
+    value_input: &str,
+    major_dimension: &str,
+) -> Value {
+    let data: Vec<Value> = updates
+        .iter()
+        .map(|update| {
+            json!({
+                "range": update.range,
+                "majorDimension": update.major_dimension.as_deref().unwrap_or(major_dimension),
+                "values": update.values
+            })
+        })
+        .collect();
+    json!({
+        "valueInputOption": value_input,
+        "includeValuesInResponse": false,
+        "data": data
+    })
+}
+
+/// Last row and column each update writes to, for [`expand_to_fit`].
+pub(crate) fn needed_extents(
+    updates: &[SheetsValueRange],
+    major_dimension: &str,
+) -> Result<Vec<(SheetRange, u32, u32)>, String> {
+    updates
+        .iter()
+        .map(|update| {
+            let range = SheetRange::parse(&update.range)?;
+            let dimension = update.major_dimension.as_deref().unwrap_or(major_dimension);
+            let (rows, columns) = values_extent(&update.values, dimension);
+            let last_row = range.first_row() + rows.saturating_sub(1);
+            let last_column = range.first_column() + columns.saturating_sub(1);
+            Ok((range, last_row, last_column))
+        })
+        .collect()
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct SheetsBatchUpdateNode {}
+
+impl SheetsBatchUpdateNode {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for SheetsBatchUpdateNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "data_google_sheets_batch_update",
+            "Batch Update Ranges",
+            "Write values to several Google Sheets ranges in one request, either all ranges are written or none",
+            "Data/Google/Sheets",
+        );
+        node.add_icon("/flow/icons/google.svg");
+
+        node.add_input_pin("exec_in", "Input", "Trigger", VariableType::Execution);
+        node.add_input_pin(
+            "provider",
+            "Provider",
+            "Google Drive provider",
+            VariableType::Struct,
+        )
+        .set_schema::<GoogleProvider>()
+        .set_options(PinOptions::new().set_enforce_schema(true).build());
+        node.add_input_pin("spreadsheet_id", "Spreadsheet ID", "", VariableType::String);
+        node.add_input_pin(
+            "updates",
+            "Updates",
+            "Ranges with the values to write",
+            VariableType::Struct,
+        )
+        .set_value_type(ValueType::Array)
+        .set_schema::<SheetsValueRange>();
+        node.add_input_pin(
+            "major_dimension",
+            "Major Dimension",
+            "Whether the inner arrays of the values are rows or columns, updates can override it",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("ROWS")))
+        .set_options(
+            PinOptions::new()
+                .set_valid_values(vec!["ROWS".to_string(), "COLUMNS".to_string()])
+                .build(),
+        );
+        node.add_input_pin(
+            "value_input",
+            "Value Input Option",
+            "How input should be interpreted",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("USER_ENTERED")))
+        .set_options(
+            PinOptions::new()
+                .set_valid_values(vec!["RAW".to_string(), "USER_ENTERED".to_string()])
+                .build(),
+        );
+        node.add_input_pin(
+            "expand_sheet",
+            "Expand Sheet",
+            "Add rows and columns to the sheets if a range reaches past them",
+            VariableType::Boolean,
+        )
+        .set_default_value(Some(json!(true)));
+
+        node.add_output_pin("exec_out", "Success", "", VariableType::Execution);
+        node.add_output_pin("error", "Error", "", VariableType::Execution);
+        node.add_output_pin(
+            "updated_cells",
+            "Updated Cells",
+            "Number of cells updated over all ranges",
+            VariableType::Integer,
+        );
+        node.add_output_pin(
+            "updated_ranges",
+            "Updated Ranges",
+            "A1 notation of every updated range",
+            VariableType::String,
+        )
+        .set_value_type(ValueType::Array);
+        node.add_output_pin("error_message", "Error Message", "", VariableType::String);
+
+        node.add_required_oauth_scopes(
+            GOOGLE_PROVIDER_ID,
+            vec!["https://www.googleapis.com/auth/spreadsheets"],
+        );
+        node.set_long_running(true);
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+        context.deactivate_exec_pin("error").await?;
+
+        let provider: GoogleProvider = context.evaluate_pin("provider").await?;
+        let spreadsheet_id: String = context.evaluate_pin("spreadsheet_id").await?;
+        let updates: Vec<SheetsValueRange> = context.evaluate_pin("updates").await?;
+        let major_dimension: String = context
+            .evaluate_pin("major_dimension")
+            .await
+            .unwrap_or_else(|_| "ROWS".to_string());
+        let value_input: String = context
+            .evaluate_pin("value_input")
+            .await
+            .unwrap_or_else(|_| "USER_ENTERED".to_string());
+        let expand_sheet: bool = context.evaluate_pin("expand_sheet").await.unwrap_or(true);
+
+        let client = reqwest::Client::new();
+        let prepared = match needed_extents(&updates, &major_dimension) {
+            Ok(needed) if expand_sheet => {
+                expand_to_fit(&client, &provider.access_token, &spreadsheet_id, &needed).await
+            }
+            Ok(_) => Ok(()),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = prepared {
+            context.set_pin_value("error_message", json!(e)).await?;
+            context.activate_exec_pin("error").await?;
+            return Ok(());
+        }
+
+        let body = batch_update_body(&updates, &value_input, &major_dimension);
+        let response = client
+            .post(format!(
+                "https://sheets.googleapis.com/v4/spreadsheets/{}/values:batchUpdate",
+                spreadsheet_id
+            ))
+            .header("Authorization", format!("Bearer {}", provider.access_token))
+            .header("Content-Type", "application/json")
+            .json(&body)
+            .send()
+            .await;
+
+        match response {
+            Ok(resp) if resp.status().is_success() => {
+                let body: Value = resp.json().await?;
+                let updated_cells = body["totalUpdatedCells"].as_i64().unwrap_or(0);
+                let updated_ranges: Vec<&str> = body["responses"]
+                    .as_array()
+                    .map(|responses| {
+                        responses
+                            .iter()
+                            .filter_map(|response| response["updatedRange"].as_str())
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                context
+                    .set_pin_value("updated_cells", json!(updated_cells))
+                    .await?;
+                context
+                    .set_pin_value("updated_ranges", json!(updated_ranges))
+                    .await?;
+                context.activate_exec_pin("exec_out").await?;
+            }
+            Ok(resp) => {
+                let error = resp.text().await.unwrap_or_default();
+                context.set_pin_value("error_message", json!(error)).await?;
+                context.activate_exec_pin("error").await?;
+            }
+            Err(e) => {
+                context
+                    .set_pin_value("error_message", json!(e.to_string()))
+                    .await?;
+                context.activate_exec_pin("error").await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::google::sheets::{expansion_requests, tests::spreadsheet};
+    use flow_like_types::json::from_str;
+
+    const BATCH_UPDATE_RESPONSE: &str =
+        include_str!("../../../../tests/fixtures/google_sheets/values_batch_update.json");
+
+    fn updates() -> Vec<SheetsValueRange> {
+        vec![
+            SheetsValueRange {
+                range: "Deals!A2:C3".to_string(),
+                values: vec![
+                    vec![json!("Hooli"), json!("gavin@example.com"), json!(90000)],
+                    vec![
+                        json!("Pied Piper"),
+                        json!("richard@example.com"),
+                        json!(150),
+                    ],
+                ],
+                major_dimension: None,
+            },
+            SheetsValueRange {
+                range: "'Q1 ''24'!R21C4".to_string(),
+                values: vec![vec![json!("=SUM(D2:D20)"), json!("=AVERAGE(D2:D20)")]],
+                major_dimension: Some("COLUMNS".to_string()),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_batch_update_body() {
+        assert_eq!(
+            batch_update_body(&updates(), "USER_ENTERED", "ROWS"),
+            json!({
+                "valueInputOption": "USER_ENTERED",
+                "includeValuesInResponse": false,
+                "data": [
+                    {
+                        "range": "Deals!A2:C3",
+                        "majorDimension": "ROWS",
+                        "values": [
+                            ["Hooli", "gavin@example.com", 90000],
+                            ["Pied Piper", "richard@example.com", 150]
+                        ]
+                    },
+                    {
+                        "range": "'Q1 ''24'!R21C4",
+                        "majorDimension": "COLUMNS",
+                        "values": [["=SUM(D2:D20)", "=AVERAGE(D2:D20)"]]
+                    }
+                ]
+            })
+        );
+
+        // The recorded response covers every cell of the request
+        let response: Value = from_str(BATCH_UPDATE_RESPONSE).unwrap();
+        let sent: usize = updates()
+            .iter()
+            .map(|update| update.values.concat().len())
+            .sum();
+        assert_eq!(response["totalUpdatedCells"], json!(sent));
+        assert_eq!(
+            response["responses"][1]["updatedRange"],
+            json!("'Q1 ''24'!D21:D22")
+        );
+    }
+
+    #[test]
+    fn test_ranges_past_the_grid_expand_the_sheet() {
+        let needed = needed_extents(&updates(), "ROWS").unwrap();
+        assert_eq!((needed[0].1, needed[0].2), (3, 3));
+        // A column of two values starting at row 21
+        assert_eq!((needed[1].1, needed[1].2), (22, 4));
+
+        let requests = expansion_requests(&spreadsheet(), &needed).unwrap();
+        assert_eq!(
+            requests,
+            vec![json!({
+                "appendDimension": {
+                    "sheetId": 1893471602,
+                    "dimension": "ROWS",
+                    "length": 2
+                }
+            })]
+        );
+
+        let invalid = SheetsValueRange {
+            range: "Deals!A0".to_string(),
+            values: Vec::new(),
+            major_dimension: None,
+        };
+        assert!(needed_extents(&[invalid], "ROWS").is_err());
+    }
+}
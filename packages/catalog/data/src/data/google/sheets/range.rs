@@ -0,0 +1,199 @@
+//! Ranges in A1 (`Sheet1!A1:D10`) and R1C1 (`Sheet1!R1C1:R10C4`) notation
+
+/// One end of a range, 1-based. Whole columns have no row, whole rows no column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CellRef {
+    pub row: Option<u32>,
+    pub column: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SheetRange {
+    /// Sheet title, `None` targets the first sheet
+    pub sheet: Option<String>,
+    pub start: CellRef,
+    pub end: CellRef,
+}
+
+/// Column number of letters like `AB`, `None` if there are none.
+pub fn column_number(letters: &str) -> Option<u32> {
+    if letters.is_empty() || !letters.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    letters.chars().try_fold(0u32, |number, c| {
+        let digit = c.to_ascii_uppercase() as u32 - 'A' as u32 + 1;
+        number.checked_mul(26)?.checked_add(digit)
+    })
+}
+
+/// Letters of a 1-based column number, e.g. 28 is `AB`.
+pub fn column_letters(mut column: u32) -> String {
+    let mut letters = Vec::new();
+    while column > 0 {
+        let remainder = (column - 1) % 26;
+        letters.push((b'A' + remainder as u8) as char);
+        column = (column - 1) / 26;
+    }
+    letters.iter().rev().collect()
+}
+
+fn parse_number(digits: &str) -> Option<u32> {
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse().ok().filter(|number| *number > 0)
+}
+
+/// `R2C3`. Only the full form is R1C1, `R1` alone is the A1 cell in column R.
+fn parse_r1c1(cell: &str) -> Option<CellRef> {
+    let rest = cell.strip_prefix(['R', 'r'])?;
+    let split = rest.find(['C', 'c'])?;
+    Some(CellRef {
+        row: Some(parse_number(&rest[..split])?),
+        column: Some(parse_number(&rest[split + 1..])?),
+    })
+}
+
+/// `B7`, `B` or `7`.
+fn parse_a1(cell: &str) -> Option<CellRef> {
+    let split = cell
+        .find(|c: char| c.is_ascii_digit())
+        .unwrap_or(cell.len());
+    let (letters, digits) = cell.split_at(split);
+    let cell_ref = CellRef {
+        column: if letters.is_empty() {
+            None
+        } else {
+            Some(column_number(letters)?)
+        },
+        row: if digits.is_empty() {
+            None
+        } else {
+            Some(parse_number(digits)?)
+        },
+    };
+    (cell_ref.row.is_some() || cell_ref.column.is_some()).then_some(cell_ref)
+}
+
+fn parse_cell(cell: &str) -> Option<CellRef> {
+    parse_r1c1(cell).or_else(|| parse_a1(cell))
+}
+
+/// Splits off the sheet title, quoted titles may contain `!` and escape `'` as `''`.
+fn split_sheet(range: &str) -> (Option<String>, &str) {
+    match range.rfind('!') {
+        Some(index) => {
+            let title = &range[..index];
+            let title = match title.strip_prefix('\'').and_then(|t| t.strip_suffix('\'')) {
+                Some(quoted) => quoted.replace("''", "'"),
+                None => title.to_string(),
+            };
+            (Some(title), &range[index + 1..])
+        }
+        None => (None, range),
+    }
+}
+
+impl SheetRange {
+    pub fn parse(range: &str) -> Result<Self, String> {
+        let range = range.trim();
+        if range.is_empty() {
+            return Err("Range cannot be empty".to_string());
+        }
+
+        let (sheet, cells) = split_sheet(range);
+        let whole = CellRef {
+            row: None,
+            column: None,
+        };
+
+        let parsed = match cells.split_once(':') {
+            Some((start, end)) => parse_cell(start).zip(parse_cell(end)),
+            // A single reference has to be a cell, `B` or `7` alone are not ranges
+            None => parse_cell(cells)
+                .filter(|cell| cell.row.is_some() && cell.column.is_some())
+                .map(|cell| (cell, cell)),
+        };
+
+        match (parsed, sheet) {
+            (Some((start, end)), sheet) => Ok(Self { sheet, start, end }),
+            // `Sheet1!` and `Sheet1` both target the whole sheet
+            (None, Some(sheet)) if cells.is_empty() => Ok(Self {
+                sheet: Some(sheet),
+                start: whole,
+                end: whole,
+            }),
+            (None, None) => Ok(Self {
+                sheet: Some(cells.to_string()),
+                start: whole,
+                end: whole,
+            }),
+            (None, Some(_)) => Err(format!(
+                "Invalid range \"{}\", use A1 or R1C1 notation",
+                range
+            )),
+        }
+    }
+
+    pub fn first_row(&self) -> u32 {
+        self.start.row.unwrap_or(1)
+    }
+
+    pub fn first_column(&self) -> u32 {
+        self.start.column.unwrap_or(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cell(row: Option<u32>, column: Option<u32>) -> CellRef {
+        CellRef { row, column }
+    }
+
+    #[test]
+    fn test_column_letters_roundtrip() {
+        for (number, letters) in [(1, "A"), (26, "Z"), (27, "AA"), (28, "AB"), (703, "AAA")] {
+            assert_eq!(column_letters(number), letters);
+            assert_eq!(column_number(letters), Some(number));
+        }
+        assert_eq!(column_number("ab"), Some(28));
+        assert_eq!(column_number(""), None);
+    }
+
+    #[test]
+    fn test_a1_and_r1c1_parse_to_the_same_range() {
+        let a1 = SheetRange::parse("Sheet1!B2:D10").unwrap();
+        let r1c1 = SheetRange::parse("Sheet1!R2C2:R10C4").unwrap();
+        assert_eq!(a1, r1c1);
+        assert_eq!(a1.sheet.as_deref(), Some("Sheet1"));
+        assert_eq!(a1.start, cell(Some(2), Some(2)));
+        assert_eq!(a1.end, cell(Some(10), Some(4)));
+    }
+
+    #[test]
+    fn test_partial_ranges() {
+        let columns = SheetRange::parse("A:D").unwrap();
+        assert_eq!(columns.sheet, None);
+        assert_eq!(columns.start, cell(None, Some(1)));
+        assert_eq!(columns.first_row(), 1);
+
+        let rows = SheetRange::parse("'Q1 ''24!'!3:5").unwrap();
+        assert_eq!(rows.sheet.as_deref(), Some("Q1 '24!"));
+        assert_eq!(rows.start, cell(Some(3), None));
+        assert_eq!(rows.first_column(), 1);
+
+        // R1 alone is column R in A1
+        let column_r = SheetRange::parse("R1").unwrap();
+        assert_eq!(column_r.start, cell(Some(1), Some(18)));
+
+        let whole = SheetRange::parse("Budget").unwrap();
+        assert_eq!(whole.sheet.as_deref(), Some("Budget"));
+        assert_eq!(whole.start, cell(None, None));
+
+        assert!(SheetRange::parse("").is_err());
+        assert!(SheetRange::parse("Sheet1!A0").is_err());
+        assert!(SheetRange::parse("Sheet1!1A:2").is_err());
+    }
+}
@@ -0,0 +1,271 @@
+use super::as_rows;
+use super::range::{SheetRange, column_letters};
+use crate::data::google::provider::{GOOGLE_PROVIDER_ID, GoogleProvider};
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic},
+    pin::{PinOptions, ValueType},
+    variable::VariableType,
+};
+use flow_like_types::{
+    Value, async_trait,
+    json::{Map, json},
+    reqwest,
+};
+
+/// Keys of the header row. Empty headers are named after their column letter, repeated
+/// ones get a counter, e.g. `Name`, `Name_2`.
+pub(crate) fn header_keys(header: &[Value], first_column: u32) -> Vec<String> {
+    let mut keys: Vec<String> = Vec::with_capacity(header.len());
+    for (index, cell) in header.iter().enumerate() {
+        let name = match cell {
+            Value::String(text) => text.trim().to_string(),
+            Value::Null => String::new(),
+            other => other.to_string(),
+        };
+        let name = match name.is_empty() {
+            true => column_letters(first_column + index as u32),
+            false => name,
+        };
+
+        let mut key = name.clone();
+        let mut counter = 1;
+        while keys.contains(&key) {
+            counter += 1;
+            key = format!("{}_{}", name, counter);
+        }
+        keys.push(key);
+    }
+    keys
+}
+
+/// One object per row below the header row. Sheets drops empty cells at the end of a row,
+/// they are filled with empty strings like the empty cells within it. Empty rows are skipped.
+pub(crate) fn rows_to_records(rows: &[Vec<Value>], first_column: u32) -> (Vec<String>, Vec<Value>) {
+    let Some((header, rows)) = rows.split_first() else {
+        return (Vec::new(), Vec::new());
+    };
+
+    // Cells right of the header row still get a key
+    let width = rows.iter().map(Vec::len).fold(header.len(), usize::max);
+    let mut header = header.to_vec();
+    header.resize(width, Value::Null);
+    let keys = header_keys(&header, first_column);
+
+    let records = rows
+        .iter()
+        .filter(|row| {
+            row.iter()
+                .any(|cell| !cell.is_null() && cell.as_str() != Some(""))
+        })
+        .map(|row| {
+            let record: Map<String, Value> = keys
+                .iter()
+                .enumerate()
+                .map(|(index, key)| (key.clone(), row.get(index).cloned().unwrap_or(json!(""))))
+                .collect();
+            Value::Object(record)
+        })
+        .collect();
+
+    (keys, records)
+}
+
+/// The response names the range in A1 notation, even if it was requested in R1C1, and
+/// starts it at the first column of the request.
+pub(crate) fn records_from_response(mut body: Value) -> Result<(Vec<String>, Vec<Value>), String> {
+    let first_column = body["range"]
+        .as_str()
+        .and_then(|range| SheetRange::parse(range).ok())
+        .map_or(1, |range| range.first_column());
+    let rows = as_rows(body["values"].take())?;
+    Ok(rows_to_records(&rows, first_column))
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct SheetsReadRangeNode {}
+
+impl SheetsReadRangeNode {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for SheetsReadRangeNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "data_google_sheets_read_records",
+            "Read Range as Records",
+            "Read a Google Sheets range as records, the first row holds the keys",
+            "Data/Google/Sheets",
+        );
+        node.add_icon("/flow/icons/google.svg");
+
+        node.add_input_pin("exec_in", "Input", "Trigger", VariableType::Execution);
+        node.add_input_pin(
+            "provider",
+            "Provider",
+            "Google Drive provider",
+            VariableType::Struct,
+        )
+        .set_schema::<GoogleProvider>()
+        .set_options(PinOptions::new().set_enforce_schema(true).build());
+        node.add_input_pin("spreadsheet_id", "Spreadsheet ID", "", VariableType::String);
+        node.add_input_pin(
+            "range",
+            "Range",
+            "A1 or R1C1 notation range starting at the header row (e.g., 'Sheet1!A1:D')",
+            VariableType::String,
+        );
+        node.add_input_pin(
+            "value_render",
+            "Value Render",
+            "How values should be rendered",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("UNFORMATTED_VALUE")))
+        .set_options(
+            PinOptions::new()
+                .set_valid_values(vec![
+                    "FORMATTED_VALUE".to_string(),
+                    "UNFORMATTED_VALUE".to_string(),
+                    "FORMULA".to_string(),
+                ])
+                .build(),
+        );
+
+        node.add_output_pin("exec_out", "Success", "", VariableType::Execution);
+        node.add_output_pin("error", "Error", "", VariableType::Execution);
+        node.add_output_pin(
+            "records",
+            "Records",
+            "One object per row, keyed by the header row",
+            VariableType::Struct,
+        )
+        .set_value_type(ValueType::Array);
+        node.add_output_pin(
+            "headers",
+            "Headers",
+            "Keys of the records in column order",
+            VariableType::String,
+        )
+        .set_value_type(ValueType::Array);
+        node.add_output_pin("row_count", "Row Count", "", VariableType::Integer);
+        node.add_output_pin("error_message", "Error Message", "", VariableType::String);
+
+        node.add_required_oauth_scopes(
+            GOOGLE_PROVIDER_ID,
+            vec!["https://www.googleapis.com/auth/spreadsheets.readonly"],
+        );
+        node.set_long_running(true);
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+        context.deactivate_exec_pin("error").await?;
+
+        let provider: GoogleProvider = context.evaluate_pin("provider").await?;
+        let spreadsheet_id: String = context.evaluate_pin("spreadsheet_id").await?;
+        let range: String = context.evaluate_pin("range").await?;
+        let value_render: String = context
+            .evaluate_pin("value_render")
+            .await
+            .unwrap_or_else(|_| "UNFORMATTED_VALUE".to_string());
+
+        let client = reqwest::Client::new();
+        let response = client
+            .get(format!(
+                "https://sheets.googleapis.com/v4/spreadsheets/{}/values/{}",
+                spreadsheet_id,
+                urlencoding::encode(&range)
+            ))
+            .header("Authorization", format!("Bearer {}", provider.access_token))
+            .query(&[
+                ("valueRenderOption", value_render.as_str()),
+                ("majorDimension", "ROWS"),
+            ])
+            .send()
+            .await;
+
+        match response {
+            Ok(resp) if resp.status().is_success() => {
+                let body: Value = resp.json().await?;
+                let (headers, records) = match records_from_response(body) {
+                    Ok(parsed) => parsed,
+                    Err(e) => {
+                        context.set_pin_value("error_message", json!(e)).await?;
+                        context.activate_exec_pin("error").await?;
+                        return Ok(());
+                    }
+                };
+                context
+                    .set_pin_value("row_count", json!(records.len() as i64))
+                    .await?;
+                context.set_pin_value("headers", json!(headers)).await?;
+                context.set_pin_value("records", json!(records)).await?;
+                context.activate_exec_pin("exec_out").await?;
+            }
+            Ok(resp) => {
+                let error = resp.text().await.unwrap_or_default();
+                context.set_pin_value("error_message", json!(error)).await?;
+                context.activate_exec_pin("error").await?;
+            }
+            Err(e) => {
+                context
+                    .set_pin_value("error_message", json!(e.to_string()))
+                    .await?;
+                context.activate_exec_pin("error").await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flow_like_types::json::from_str;
+
+    const VALUES_RESPONSE: &str =
+        include_str!("../../../../tests/fixtures/google_sheets/values_get.json");
+
+    #[test]
+    fn test_records_are_keyed_by_the_header_row() {
+        let (headers, records) = records_from_response(from_str(VALUES_RESPONSE).unwrap()).unwrap();
+
+        // The range starts at column B, so the empty header is E and the extra cell G
+        assert_eq!(headers, ["Company", "Owner", "Value", "E", "Owner_2", "G"]);
+        assert_eq!(records.len(), 4);
+        assert_eq!(
+            records[0],
+            json!({
+                "Company": "Acme",
+                "Owner": "ada@example.com",
+                "Value": 12000,
+                "E": "note",
+                "Owner_2": "Ada",
+                "G": ""
+            })
+        );
+        assert_eq!(records[1]["Value"], json!(8500));
+        assert_eq!(records[1]["Owner_2"], json!(""));
+        assert_eq!(records[2]["Company"], json!("Initech"));
+        assert_eq!(records[2]["G"], json!("late entry"));
+        assert_eq!(records[3]["Value"], json!(true));
+    }
+
+    #[test]
+    fn test_empty_and_header_only_ranges() {
+        let (headers, records) = records_from_response(json!({ "range": "Deals!A1:C1" })).unwrap();
+        assert!(headers.is_empty() && records.is_empty());
+
+        let body = json!({ "range": "Deals!A1:C1", "values": [["id", 2, null]] });
+        let (headers, records) = records_from_response(body).unwrap();
+        assert_eq!(headers, ["id", "2", "C"]);
+        assert!(records.is_empty());
+    }
+}
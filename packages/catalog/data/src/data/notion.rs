@@ -1,3 +1,4 @@
+pub mod append_blocks;
 pub mod create_page;
 pub mod get_database;
 pub mod get_page;
@@ -10,9 +11,617 @@ pub mod update_page;
 // Re-export types for external use
 pub use create_page::CreatedNotionPage;
 pub use get_database::{NotionDatabaseProperty, NotionDatabaseSchema};
-pub use get_page::{NotionBlock, NotionPageContent};
+pub use get_page::NotionPageContent;
 pub use list_databases::NotionDatabase;
 pub use provider::NotionProvider;
-pub use query_database::NotionPage;
 pub use search::NotionSearchResult;
 pub use update_page::UpdatedNotionPage;
+
+use flow_like_types::{
+    JsonSchema, Value,
+    json::{Map, json},
+    reqwest,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+pub const NOTION_API_VERSION: &str = "2022-06-28";
+
+/// Notion rejects text objects longer than this many characters
+const MAX_TEXT_LENGTH: usize = 2000;
+
+/// Notion accepts at most this many blocks per append request
+pub const MAX_BLOCKS_PER_REQUEST: usize = 100;
+
+// =============================================================================
+// Notion Types
+// =============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct NotionPage {
+    pub id: String,
+    pub url: String,
+    pub created_time: String,
+    pub last_edited_time: String,
+    pub archived: bool,
+    pub icon_emoji: Option<String>,
+    /// Properties in Notion format
+    pub properties: Value,
+    /// Properties flattened to plain values, keyed by property name
+    #[serde(default)]
+    pub values: Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct NotionBlock {
+    pub id: String,
+    pub block_type: String,
+    pub has_children: bool,
+    /// Block content in Notion format
+    pub content: Value,
+    /// Block content as plain text, headings and list items keep a markdown prefix
+    #[serde(default)]
+    pub text: String,
+}
+
+// =============================================================================
+// Reading: Notion format to plain values
+// =============================================================================
+
+pub fn rich_text_to_plain(rich_text: &Value) -> String {
+    rich_text
+        .as_array()
+        .map(|parts| {
+            parts
+                .iter()
+                .filter_map(|part| part["plain_text"].as_str())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn names(items: &Value, key: &str) -> Value {
+    let names: Vec<Value> = items
+        .as_array()
+        .map(|items| items.iter().map(|item| item[key].clone()).collect())
+        .unwrap_or_default();
+    Value::Array(names)
+}
+
+fn date_to_plain(date: &Value) -> Value {
+    match (date["start"].as_str(), date["end"].as_str()) {
+        (Some(start), Some(end)) => json!(format!("{}/{}", start, end)),
+        (Some(start), None) => json!(start),
+        _ => Value::Null,
+    }
+}
+
+/// Plain value of a property: text for text properties, names for selects and people,
+/// the value of the result type for formulas and rollups.
+pub fn property_to_plain(property: &Value) -> Value {
+    let kind = property["type"].as_str().unwrap_or_default();
+    let value = &property[kind];
+    match kind {
+        "title" | "rich_text" => json!(rich_text_to_plain(value)),
+        "number" | "checkbox" | "url" | "email" | "phone_number" | "created_time"
+        | "last_edited_time" => value.clone(),
+        "select" | "status" => value["name"].clone(),
+        "multi_select" => names(value, "name"),
+        "people" => names(value, "name"),
+        "relation" => names(value, "id"),
+        "files" => names(value, "name"),
+        "created_by" | "last_edited_by" => value["name"].clone(),
+        "date" => date_to_plain(value),
+        "unique_id" => match value["prefix"].as_str() {
+            Some(prefix) => json!(format!("{}-{}", prefix, value["number"])),
+            None => value["number"].clone(),
+        },
+        "formula" => {
+            let result = value["type"].as_str().unwrap_or_default();
+            match result {
+                "date" => date_to_plain(&value["date"]),
+                _ => value[result].clone(),
+            }
+        }
+        "rollup" => match value["type"].as_str().unwrap_or_default() {
+            "array" => Value::Array(
+                value["array"]
+                    .as_array()
+                    .map(|items| items.iter().map(property_to_plain).collect())
+                    .unwrap_or_default(),
+            ),
+            "date" => date_to_plain(&value["date"]),
+            result => value[result].clone(),
+        },
+        _ => property.clone(),
+    }
+}
+
+pub fn properties_to_plain(properties: &Value) -> Value {
+    let values: Map<String, Value> = properties
+        .as_object()
+        .map(|properties| {
+            properties
+                .iter()
+                .map(|(name, property)| (name.clone(), property_to_plain(property)))
+                .collect()
+        })
+        .unwrap_or_default();
+    Value::Object(values)
+}
+
+/// Text of the title property, whatever it is named.
+pub fn page_title(properties: &Value) -> Option<String> {
+    properties
+        .as_object()?
+        .values()
+        .find(|property| property["type"] == "title")
+        .map(|property| rich_text_to_plain(&property["title"]))
+}
+
+pub fn parse_notion_page(value: &Value) -> Option<NotionPage> {
+    Some(NotionPage {
+        id: value["id"].as_str()?.to_string(),
+        url: value["url"].as_str()?.to_string(),
+        created_time: value["created_time"].as_str()?.to_string(),
+        last_edited_time: value["last_edited_time"].as_str()?.to_string(),
+        archived: value["archived"].as_bool().unwrap_or(false),
+        icon_emoji: value["icon"]["emoji"].as_str().map(String::from),
+        properties: value["properties"].clone(),
+        values: properties_to_plain(&value["properties"]),
+    })
+}
+
+/// Plain text of a block in the format [`plain_text_to_blocks`] reads.
+pub fn block_to_plain(block: &Value) -> String {
+    let block_type = block["type"].as_str().unwrap_or("");
+    let text = rich_text_to_plain(&block[block_type]["rich_text"]);
+
+    match block_type {
+        "heading_1" => format!("# {}", text),
+        "heading_2" => format!("## {}", text),
+        "heading_3" => format!("### {}", text),
+        "bulleted_list_item" => format!("- {}", text),
+        "numbered_list_item" => format!("1. {}", text),
+        "quote" => format!("> {}", text),
+        "to_do" => {
+            let checked = block["to_do"]["checked"].as_bool().unwrap_or(false);
+            format!("[{}] {}", if checked { "x" } else { " " }, text)
+        }
+        "divider" => "---".to_string(),
+        _ => text,
+    }
+}
+
+pub fn parse_notion_block(value: &Value) -> NotionBlock {
+    let block_type = value["type"].as_str().unwrap_or("unknown").to_string();
+    NotionBlock {
+        id: value["id"].as_str().unwrap_or("").to_string(),
+        has_children: value["has_children"].as_bool().unwrap_or(false),
+        content: value[&block_type].clone(),
+        text: block_to_plain(value),
+        block_type,
+    }
+}
+
+// =============================================================================
+// Writing: plain values to Notion format
+// =============================================================================
+
+/// Rich text array of `text`, split into parts Notion accepts.
+pub fn plain_to_rich_text(text: &str) -> Value {
+    let chars: Vec<char> = text.chars().collect();
+    let parts: Vec<Value> = chars
+        .chunks(MAX_TEXT_LENGTH)
+        .map(|chunk| {
+            json!({
+                "type": "text",
+                "text": { "content": chunk.iter().collect::<String>() }
+            })
+        })
+        .collect();
+    Value::Array(parts)
+}
+
+fn text_block(block_type: &str, text: &str) -> Value {
+    json!({
+        "object": "block",
+        "type": block_type,
+        block_type: { "rich_text": plain_to_rich_text(text) }
+    })
+}
+
+/// One block per non-empty line. Markdown style prefixes create headings, list items,
+/// quotes, to-dos and dividers, everything else becomes a paragraph.
+pub fn plain_text_to_blocks(text: &str) -> Vec<Value> {
+    text.lines()
+        .map(str::trim_end)
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            if line == "---" {
+                return json!({ "object": "block", "type": "divider", "divider": {} });
+            }
+            for (prefix, block_type) in [
+                ("### ", "heading_3"),
+                ("## ", "heading_2"),
+                ("# ", "heading_1"),
+                ("- ", "bulleted_list_item"),
+                ("* ", "bulleted_list_item"),
+                ("> ", "quote"),
+            ] {
+                if let Some(text) = line.strip_prefix(prefix) {
+                    return text_block(block_type, text);
+                }
+            }
+            for (prefix, checked) in [("[ ] ", false), ("[x] ", true), ("[X] ", true)] {
+                if let Some(text) = line.strip_prefix(prefix) {
+                    let mut block = text_block("to_do", text);
+                    block["to_do"]["checked"] = json!(checked);
+                    return block;
+                }
+            }
+            if let Some((number, text)) = line.split_once(". ")
+                && !number.is_empty()
+                && number.chars().all(|c| c.is_ascii_digit())
+            {
+                return text_block("numbered_list_item", text);
+            }
+            text_block("paragraph", line)
+        })
+        .collect()
+}
+
+/// Types of the properties of a database schema or page, keyed by property name.
+pub fn property_types(properties: &Value) -> HashMap<String, String> {
+    properties
+        .as_object()
+        .map(|properties| {
+            properties
+                .iter()
+                .filter_map(|(name, property)| {
+                    Some((name.clone(), property["type"].as_str()?.to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn plain_string(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+/// Items of a list property, given as array or comma separated string.
+fn plain_list(value: &Value) -> Vec<String> {
+    match value {
+        Value::Array(items) => items.iter().map(plain_string).collect(),
+        Value::Null => Vec::new(),
+        other => plain_string(other)
+            .split(',')
+            .map(|item| item.trim().to_string())
+            .filter(|item| !item.is_empty())
+            .collect(),
+    }
+}
+
+/// Notion format of a plain value for a property of type `kind`. Objects are taken to
+/// be in Notion format already and pass through unchanged.
+pub fn plain_to_property(name: &str, kind: &str, value: &Value) -> Result<Value, String> {
+    if value.is_object() {
+        return Ok(value.clone());
+    }
+
+    let property = match kind {
+        "title" | "rich_text" => plain_to_rich_text(&plain_string(value)),
+        "number" => match value {
+            Value::Number(_) | Value::Null => value.clone(),
+            other => plain_string(other)
+                .trim()
+                .parse::<f64>()
+                .map(|number| json!(number))
+                .map_err(|_| format!("Property \"{}\" needs a number", name))?,
+        },
+        "checkbox" => match value {
+            Value::Bool(checked) => json!(checked),
+            other => match plain_string(other).trim().to_lowercase().as_str() {
+                "true" | "yes" | "1" => json!(true),
+                "false" | "no" | "0" | "" => json!(false),
+                _ => return Err(format!("Property \"{}\" needs true or false", name)),
+            },
+        },
+        "select" | "status" => match value {
+            Value::Null => Value::Null,
+            other => json!({ "name": plain_string(other) }),
+        },
+        "multi_select" => Value::Array(
+            plain_list(value)
+                .into_iter()
+                .map(|name| json!({ "name": name }))
+                .collect(),
+        ),
+        "relation" | "people" => Value::Array(
+            plain_list(value)
+                .into_iter()
+                .map(|id| json!({ "id": id }))
+                .collect(),
+        ),
+        "date" => match value {
+            Value::Null => Value::Null,
+            other => {
+                let date = plain_string(other);
+                match date.split_once('/') {
+                    Some((start, end)) => json!({ "start": start, "end": end }),
+                    None => json!({ "start": date }),
+                }
+            }
+        },
+        "url" | "email" | "phone_number" => match value {
+            Value::Null => Value::Null,
+            other => json!(plain_string(other)),
+        },
+        _ => {
+            return Err(format!(
+                "Property \"{}\" of type {} can not be set from a plain value, pass it in Notion format",
+                name, kind
+            ));
+        }
+    };
+
+    Ok(json!({ kind: property }))
+}
+
+/// Converts an object of plain values keyed by property name, `types` comes from
+/// [`property_types`].
+pub fn plain_to_properties(
+    values: &Value,
+    types: &HashMap<String, String>,
+) -> Result<Map<String, Value>, String> {
+    let Some(values) = values.as_object() else {
+        return Err("Values must be a JSON object keyed by property name".to_string());
+    };
+
+    values
+        .iter()
+        .map(|(name, value)| {
+            let kind = types
+                .get(name)
+                .ok_or_else(|| format!("Unknown property \"{}\"", name))?;
+            Ok((name.clone(), plain_to_property(name, kind, value)?))
+        })
+        .collect()
+}
+
+/// Reads the property types of a database or page, needed to write plain values.
+pub(crate) async fn fetch_property_types(
+    client: &reqwest::Client,
+    access_token: &str,
+    url: &str,
+) -> Result<HashMap<String, String>, String> {
+    let response = client
+        .get(url)
+        .header("Authorization", format!("Bearer {}", access_token))
+        .header("Notion-Version", NOTION_API_VERSION)
+        .send()
+        .await
+        .map_err(|e| format!("Network error: {}", e))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(format!("Notion API error {}: {}", status, error_text));
+    }
+
+    let data: Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse response: {}", e))?;
+    Ok(property_types(&data["properties"]))
+}
+
+/// Appends `children` to a page or block in batches Notion accepts, returns the created
+/// blocks.
+pub(crate) async fn append_children(
+    client: &reqwest::Client,
+    access_token: &str,
+    block_id: &str,
+    children: &[Value],
+) -> Result<Vec<Value>, String> {
+    let url = format!("https://api.notion.com/v1/blocks/{}/children", block_id);
+    let mut appended = Vec::with_capacity(children.len());
+
+    for batch in children.chunks(MAX_BLOCKS_PER_REQUEST) {
+        let response = client
+            .patch(&url)
+            .header("Authorization", format!("Bearer {}", access_token))
+            .header("Notion-Version", NOTION_API_VERSION)
+            .header("Content-Type", "application/json")
+            .json(&json!({ "children": batch }))
+            .send()
+            .await
+            .map_err(|e| format!("Network error: {}", e))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(format!(
+                "Notion API error {} after {} of {} blocks: {}",
+                status,
+                appended.len(),
+                children.len(),
+                error_text
+            ));
+        }
+
+        let data: Value = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {}", e))?;
+        if let Some(results) = data["results"].as_array() {
+            appended.extend(results.iter().cloned());
+        }
+    }
+
+    Ok(appended)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flow_like_types::json::from_str;
+
+    const QUERY_FIXTURE: &str = include_str!("../../tests/fixtures/notion/query_database.json");
+    const DATABASE_FIXTURE: &str = include_str!("../../tests/fixtures/notion/get_database.json");
+    const CREATE_FIXTURE: &str = include_str!("../../tests/fixtures/notion/create_page.json");
+
+    #[test]
+    fn test_query_fixture_flattens_properties() {
+        let response: Value = from_str(QUERY_FIXTURE).unwrap();
+        let pages: Vec<NotionPage> = response["results"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(parse_notion_page)
+            .collect();
+
+        assert_eq!(pages.len(), 2);
+        assert_eq!(response["has_more"], json!(true));
+
+        let page = &pages[0];
+        assert_eq!(page.id, "59833787-2cf9-4fdf-8782-e53db20768a5");
+        assert_eq!(page.icon_emoji.as_deref(), Some("📘"));
+        assert_eq!(
+            page.values,
+            json!({
+                "Name": "Onboarding guide",
+                "Summary": "How to get started, step by step",
+                "Status": "Published",
+                "Tags": ["docs", "onboarding"],
+                "Priority": "High",
+                "Views": 42,
+                "Reviewed": true,
+                "Due": "2024-05-01/2024-05-03",
+                "Owner": ["Ada Lovelace"],
+                "Website": "https://example.com/onboarding",
+                "Word Count": 1250
+            })
+        );
+        assert_eq!(
+            page_title(&page.properties).as_deref(),
+            Some("Onboarding guide")
+        );
+
+        // Empty properties flatten to empty values, not to errors
+        let draft = &pages[1];
+        assert_eq!(draft.values["Summary"], json!(""));
+        assert_eq!(draft.values["Priority"], Value::Null);
+        assert_eq!(draft.values["Due"], Value::Null);
+        assert_eq!(draft.values["Tags"], json!([]));
+    }
+
+    #[test]
+    fn test_create_from_plain_values() {
+        let database: Value = from_str(DATABASE_FIXTURE).unwrap();
+        let types = property_types(&database["properties"]);
+        assert_eq!(types["Name"], "title");
+
+        let values = json!({
+            "Name": "Release notes",
+            "Summary": "What changed",
+            "Status": "Draft",
+            "Tags": "docs, release",
+            "Views": "7",
+            "Reviewed": false,
+            "Due": "2024-06-01",
+            "Priority": { "select": { "name": "Low" } }
+        });
+        let properties = plain_to_properties(&values, &types).unwrap();
+        assert_eq!(
+            Value::Object(properties),
+            json!({
+                "Name": { "title": [{ "type": "text", "text": { "content": "Release notes" } }] },
+                "Summary": {
+                    "rich_text": [{ "type": "text", "text": { "content": "What changed" } }]
+                },
+                "Status": { "status": { "name": "Draft" } },
+                "Tags": { "multi_select": [{ "name": "docs" }, { "name": "release" }] },
+                "Views": { "number": 7.0 },
+                "Reviewed": { "checkbox": false },
+                "Due": { "date": { "start": "2024-06-01" } },
+                "Priority": { "select": { "name": "Low" } }
+            })
+        );
+
+        let created: Value = from_str(CREATE_FIXTURE).unwrap();
+        let page = parse_notion_page(&created).unwrap();
+        assert_eq!(page.id, "b55c9c91-384d-452b-81db-d1ef79372b75");
+        assert_eq!(page.values["Name"], json!("Release notes"));
+        assert_eq!(page.values["Tags"], json!(["docs", "release"]));
+    }
+
+    #[test]
+    fn test_invalid_plain_values() {
+        let database: Value = from_str(DATABASE_FIXTURE).unwrap();
+        let types = property_types(&database["properties"]);
+
+        let error = plain_to_properties(&json!({ "Missing": "x" }), &types).unwrap_err();
+        assert!(error.contains("Unknown property"));
+        let error = plain_to_properties(&json!({ "Views": "many" }), &types).unwrap_err();
+        assert!(error.contains("needs a number"));
+        let error = plain_to_properties(&json!({ "Word Count": 3 }), &types).unwrap_err();
+        assert!(error.contains("Notion format"));
+        assert!(plain_to_properties(&json!(["Name"]), &types).is_err());
+    }
+
+    #[test]
+    fn test_plain_text_blocks_roundtrip() {
+        let text = "# Title\nIntro paragraph\n\n- first\n1. step\n[x] done\n> quoted\n---";
+        let blocks = plain_text_to_blocks(text);
+        let types: Vec<&str> = blocks
+            .iter()
+            .map(|block| block["type"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            types,
+            [
+                "heading_1",
+                "paragraph",
+                "bulleted_list_item",
+                "numbered_list_item",
+                "to_do",
+                "quote",
+                "divider"
+            ]
+        );
+
+        // Notion adds plain_text when reading, mirror that for the roundtrip
+        let read: Vec<String> = blocks
+            .into_iter()
+            .map(|mut block| {
+                let block_type = block["type"].as_str().unwrap().to_string();
+                if let Some(parts) = block[&block_type]["rich_text"].as_array_mut() {
+                    for part in parts {
+                        part["plain_text"] = part["text"]["content"].clone();
+                    }
+                }
+                parse_notion_block(&block).text
+            })
+            .collect();
+        assert_eq!(read.join("\n"), text.replace("\n\n", "\n"));
+    }
+
+    #[test]
+    fn test_long_text_is_split() {
+        let text = "é".repeat(MAX_TEXT_LENGTH * 2 + 5);
+        let parts = plain_to_rich_text(&text);
+        let lengths: Vec<usize> = parts
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|part| part["text"]["content"].as_str().unwrap().chars().count())
+            .collect();
+        assert_eq!(lengths, [MAX_TEXT_LENGTH, MAX_TEXT_LENGTH, 5]);
+    }
+}
@@ -0,0 +1,182 @@
+use super::provider::{NOTION_PROVIDER_ID, NotionProvider};
+use super::{NotionBlock, append_children, parse_notion_block, plain_text_to_blocks};
+use flow_like::flow::{
+    execution::{LogLevel, context::ExecutionContext},
+    node::{Node, NodeLogic, NodeScores},
+    pin::{PinOptions, ValueType},
+    variable::VariableType,
+};
+use flow_like_types::{Value, async_trait, json::json, reqwest};
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct AppendNotionBlocksNode {}
+
+impl AppendNotionBlocksNode {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for AppendNotionBlocksNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "data_notion_append_blocks",
+            "Append Notion Blocks",
+            "Appends content to the end of a Notion page or block",
+            "Data/Notion",
+        );
+        node.add_icon("/flow/icons/notion.svg");
+
+        node.add_input_pin(
+            "exec_in",
+            "Input",
+            "Trigger appending the blocks",
+            VariableType::Execution,
+        );
+
+        node.add_input_pin(
+            "provider",
+            "Provider",
+            "Notion provider (from Notion node)",
+            VariableType::Struct,
+        )
+        .set_schema::<NotionProvider>()
+        .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node.add_input_pin(
+            "block_id",
+            "Page or Block ID",
+            "The ID of the page or block to append to",
+            VariableType::String,
+        );
+
+        node.add_input_pin(
+            "text",
+            "Text",
+            "Content as plain text, one block per line. Lines starting with #, -, 1., [ ], > or --- become headings, list items, to-dos, quotes and dividers",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("")));
+
+        node.add_input_pin(
+            "blocks",
+            "Blocks (JSON)",
+            "Optional: Array of block objects in Notion format, appended before the text",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("")));
+
+        node.add_output_pin(
+            "exec_out",
+            "Success",
+            "Triggered when all blocks are appended",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "error",
+            "Error",
+            "Triggered when an error occurs",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "appended",
+            "Appended Blocks",
+            "The created blocks",
+            VariableType::Struct,
+        )
+        .set_value_type(ValueType::Array)
+        .set_schema::<NotionBlock>()
+        .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node.add_output_pin(
+            "count",
+            "Count",
+            "Number of appended blocks",
+            VariableType::Integer,
+        );
+
+        node.add_required_oauth_scopes(NOTION_PROVIDER_ID, vec![]);
+        node.set_scores(
+            NodeScores::new()
+                .set_privacy(6)
+                .set_security(7)
+                .set_performance(7)
+                .set_governance(7)
+                .set_reliability(9)
+                .set_cost(10)
+                .build(),
+        );
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+        context.deactivate_exec_pin("error").await?;
+
+        let provider: NotionProvider = context.evaluate_pin("provider").await?;
+        let access_token = provider.access_token;
+
+        let block_id: String = context.evaluate_pin("block_id").await?;
+        let text: String = context.evaluate_pin("text").await?;
+        let blocks_str: String = context.evaluate_pin("blocks").await?;
+
+        if block_id.is_empty() {
+            context.log_message("Page or block ID cannot be empty", LogLevel::Error);
+            context.activate_exec_pin("error").await?;
+            return Ok(());
+        }
+
+        let mut children: Vec<Value> = Vec::new();
+        if !blocks_str.is_empty() {
+            match flow_like_types::json::from_str::<Vec<Value>>(&blocks_str) {
+                Ok(blocks) => children.extend(blocks),
+                Err(e) => {
+                    context.log_message(&format!("Invalid blocks JSON: {}", e), LogLevel::Error);
+                    context.activate_exec_pin("error").await?;
+                    return Ok(());
+                }
+            }
+        }
+        children.extend(plain_text_to_blocks(&text));
+
+        if children.is_empty() {
+            context.log_message("Nothing to append", LogLevel::Error);
+            context.activate_exec_pin("error").await?;
+            return Ok(());
+        }
+
+        context.log_message(
+            &format!("Appending {} blocks to {}", children.len(), block_id),
+            LogLevel::Debug,
+        );
+
+        let client = reqwest::Client::new();
+        match append_children(&client, &access_token, &block_id, &children).await {
+            Ok(appended) => {
+                let appended: Vec<NotionBlock> = appended.iter().map(parse_notion_block).collect();
+
+                context.log_message(
+                    &format!("Appended {} blocks", appended.len()),
+                    LogLevel::Info,
+                );
+
+                context
+                    .set_pin_value("count", json!(appended.len() as i64))
+                    .await?;
+                context.set_pin_value("appended", json!(appended)).await?;
+                context.activate_exec_pin("exec_out").await?;
+            }
+            Err(e) => {
+                context.log_message(&e, LogLevel::Error);
+                context.activate_exec_pin("error").await?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,4 +1,5 @@
 use super::provider::{NOTION_PROVIDER_ID, NotionProvider};
+use super::{NOTION_API_VERSION, fetch_property_types, plain_text_to_blocks, plain_to_properties};
 use flow_like::flow::{
     execution::{LogLevel, context::ExecutionContext},
     node::{Node, NodeLogic, NodeScores},
@@ -8,8 +9,6 @@ use flow_like::flow::{
 use flow_like_types::{JsonSchema, Value, async_trait, json::json, reqwest};
 use serde::{Deserialize, Serialize};
 
-const NOTION_API_VERSION: &str = "2022-06-28";
-
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CreatedNotionPage {
     pub id: String,
@@ -66,7 +65,16 @@ impl NodeLogic for CreateNotionPageNode {
             "Properties (JSON)",
             "Page properties in Notion format. Example: {\"Name\": {\"title\": [{\"text\": {\"content\": \"My Page\"}}]}}",
             VariableType::String,
-        );
+        )
+        .set_default_value(Some(json!("")));
+
+        node.add_input_pin(
+            "values",
+            "Values (JSON)",
+            "Optional: Properties as plain values keyed by name, converted using the database schema. Example: {\"Name\": \"My Page\", \"Tags\": [\"docs\"], \"Done\": true}",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("")));
 
         node.add_input_pin(
             "content",
@@ -76,6 +84,14 @@ impl NodeLogic for CreateNotionPageNode {
         )
         .set_default_value(Some(json!("")));
 
+        node.add_input_pin(
+            "content_text",
+            "Content (Text)",
+            "Optional: Page content as plain text, one block per line. Lines starting with #, -, 1., [ ], > or --- become headings, list items, to-dos, quotes and dividers",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("")));
+
         node.add_input_pin(
             "icon_emoji",
             "Icon Emoji",
@@ -145,7 +161,9 @@ impl NodeLogic for CreateNotionPageNode {
 
         let database_id: String = context.evaluate_pin("database_id").await?;
         let properties_str: String = context.evaluate_pin("properties").await?;
+        let values_str: String = context.evaluate_pin("values").await?;
         let content_str: String = context.evaluate_pin("content").await?;
+        let content_text: String = context.evaluate_pin("content_text").await?;
         let icon_emoji: String = context.evaluate_pin("icon_emoji").await?;
 
         if database_id.is_empty() {
@@ -154,31 +172,70 @@ impl NodeLogic for CreateNotionPageNode {
             return Ok(());
         }
 
-        if properties_str.is_empty() {
-            context.log_message("Properties cannot be empty", LogLevel::Error);
+        if properties_str.is_empty() && values_str.is_empty() {
+            context.log_message("Properties or values are required", LogLevel::Error);
             context.activate_exec_pin("error").await?;
             return Ok(());
         }
 
-        let properties: Value = match flow_like_types::json::from_str(&properties_str) {
-            Ok(p) => p,
-            Err(e) => {
-                context.log_message(&format!("Invalid properties JSON: {}", e), LogLevel::Error);
-                context.activate_exec_pin("error").await?;
-                return Ok(());
+        let mut properties: Value = if properties_str.is_empty() {
+            json!({})
+        } else {
+            match flow_like_types::json::from_str(&properties_str) {
+                Ok(p) => p,
+                Err(e) => {
+                    context
+                        .log_message(&format!("Invalid properties JSON: {}", e), LogLevel::Error);
+                    context.activate_exec_pin("error").await?;
+                    return Ok(());
+                }
             }
         };
 
+        let client = reqwest::Client::new();
+
+        if !values_str.is_empty() {
+            let values: Value = match flow_like_types::json::from_str(&values_str) {
+                Ok(v) => v,
+                Err(e) => {
+                    context.log_message(&format!("Invalid values JSON: {}", e), LogLevel::Error);
+                    context.activate_exec_pin("error").await?;
+                    return Ok(());
+                }
+            };
+            let url = format!("https://api.notion.com/v1/databases/{}", database_id);
+            let converted = fetch_property_types(&client, &access_token, &url)
+                .await
+                .and_then(|types| plain_to_properties(&values, &types));
+            match (converted, properties.as_object_mut()) {
+                (Ok(converted), Some(properties)) => {
+                    // Properties given in Notion format win over plain values
+                    for (name, property) in converted {
+                        properties.entry(name).or_insert(property);
+                    }
+                }
+                (Ok(_), None) => {
+                    context.log_message("Properties must be a JSON object", LogLevel::Error);
+                    context.activate_exec_pin("error").await?;
+                    return Ok(());
+                }
+                (Err(e), _) => {
+                    context.log_message(&e, LogLevel::Error);
+                    context.activate_exec_pin("error").await?;
+                    return Ok(());
+                }
+            }
+        }
+
         let mut body = json!({
             "parent": { "database_id": database_id },
             "properties": properties
         });
 
+        let mut children: Vec<Value> = Vec::new();
         if !content_str.is_empty() {
-            match flow_like_types::json::from_str::<Value>(&content_str) {
-                Ok(children) => {
-                    body["children"] = children;
-                }
+            match flow_like_types::json::from_str::<Vec<Value>>(&content_str) {
+                Ok(blocks) => children.extend(blocks),
                 Err(e) => {
                     context.log_message(&format!("Invalid content JSON: {}", e), LogLevel::Error);
                     context.activate_exec_pin("error").await?;
@@ -186,6 +243,12 @@ impl NodeLogic for CreateNotionPageNode {
                 }
             }
         }
+        children.extend(plain_text_to_blocks(&content_text));
+        // The rest is appended once the page exists
+        let remaining = children.split_off(children.len().min(MAX_BLOCKS_PER_REQUEST));
+        if !children.is_empty() {
+            body["children"] = json!(children);
+        }
 
         if !icon_emoji.is_empty() {
             body["icon"] = json!({
@@ -194,8 +257,6 @@ impl NodeLogic for CreateNotionPageNode {
             });
         }
 
-        let client = reqwest::Client::new();
-
         context.log_message("Creating Notion page...", LogLevel::Debug);
 
         let response = client
@@ -235,14 +296,29 @@ impl NodeLogic for CreateNotionPageNode {
                     created_time,
                 };
 
+                context.set_pin_value("page", json!(created_page)).await?;
+                context.set_pin_value("page_id", json!(page_id)).await?;
+                context.set_pin_value("page_url", json!(page_url)).await?;
+
+                if !remaining.is_empty()
+                    && let Err(e) =
+                        append_children(&client, &access_token, &page_id, &remaining).await
+                {
+                    context.log_message(
+                        &format!(
+                            "Created page {} but could not append all content: {}",
+                            page_id, e
+                        ),
+                        LogLevel::Error,
+                    );
+                    context.activate_exec_pin("error").await?;
+                    return Ok(());
+                }
+
                 context.log_message(
                     &format!("Successfully created page: {}", page_id),
                     LogLevel::Info,
                 );
-
-                context.set_pin_value("page", json!(created_page)).await?;
-                context.set_pin_value("page_id", json!(page_id)).await?;
-                context.set_pin_value("page_url", json!(page_url)).await?;
                 context.activate_exec_pin("exec_out").await?;
             }
             Err(e) => {
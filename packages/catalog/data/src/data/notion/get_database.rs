@@ -1,3 +1,4 @@
+use super::NOTION_API_VERSION;
 use super::provider::{NOTION_PROVIDER_ID, NotionProvider};
 use flow_like::flow::{
     execution::{LogLevel, context::ExecutionContext},
@@ -8,8 +9,6 @@ use flow_like::flow::{
 use flow_like_types::{JsonSchema, Value, async_trait, json::json, reqwest};
 use serde::{Deserialize, Serialize};
 
-const NOTION_API_VERSION: &str = "2022-06-28";
-
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct NotionDatabaseProperty {
     pub id: String,
@@ -1,4 +1,5 @@
 use super::provider::{NOTION_PROVIDER_ID, NotionProvider};
+use super::{NOTION_API_VERSION, NotionBlock, page_title, parse_notion_block, properties_to_plain};
 use flow_like::flow::{
     execution::{LogLevel, context::ExecutionContext},
     node::{Node, NodeLogic, NodeScores},
@@ -8,16 +9,6 @@ use flow_like::flow::{
 use flow_like_types::{JsonSchema, Value, async_trait, json::json, reqwest};
 use serde::{Deserialize, Serialize};
 
-const NOTION_API_VERSION: &str = "2022-06-28";
-
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
-pub struct NotionBlock {
-    pub id: String,
-    pub block_type: String,
-    pub has_children: bool,
-    pub content: Value,
-}
-
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct NotionPageContent {
     pub id: String,
@@ -28,6 +19,9 @@ pub struct NotionPageContent {
     pub archived: bool,
     pub icon_emoji: Option<String>,
     pub properties: Value,
+    /// Properties flattened to plain values, keyed by property name
+    #[serde(default)]
+    pub values: Value,
     pub blocks: Vec<NotionBlock>,
     pub plain_text: String,
 }
@@ -49,37 +43,6 @@ impl GetNotionPageNode {
     }
 }
 
-fn extract_plain_text_from_rich_text(rich_text: &Value) -> String {
-    rich_text
-        .as_array()
-        .map(|arr| {
-            arr.iter()
-                .filter_map(|t| t["plain_text"].as_str())
-                .collect::<Vec<_>>()
-                .join("")
-        })
-        .unwrap_or_default()
-}
-
-fn extract_block_text(block: &Value) -> String {
-    let block_type = block["type"].as_str().unwrap_or("");
-
-    match block_type {
-        "paragraph" | "heading_1" | "heading_2" | "heading_3" | "bulleted_list_item"
-        | "numbered_list_item" | "quote" | "callout" | "toggle" => {
-            extract_plain_text_from_rich_text(&block[block_type]["rich_text"])
-        }
-        "code" => extract_plain_text_from_rich_text(&block["code"]["rich_text"]),
-        "to_do" => {
-            let text = extract_plain_text_from_rich_text(&block["to_do"]["rich_text"]);
-            let checked = block["to_do"]["checked"].as_bool().unwrap_or(false);
-            format!("[{}] {}", if checked { "x" } else { " " }, text)
-        }
-        "divider" => "---".to_string(),
-        _ => String::new(),
-    }
-}
-
 #[async_trait]
 impl NodeLogic for GetNotionPageNode {
     fn get_node(&self) -> Node {
@@ -232,16 +195,7 @@ impl NodeLogic for GetNotionPageNode {
             }
         };
 
-        let title = page_data["properties"]["title"]["title"]
-            .as_array()
-            .or_else(|| page_data["properties"]["Name"]["title"].as_array())
-            .map(|arr| {
-                arr.iter()
-                    .filter_map(|t| t["plain_text"].as_str())
-                    .collect::<Vec<_>>()
-                    .join("")
-            })
-            .unwrap_or_else(|| "Untitled".to_string());
+        let title = page_title(&page_data["properties"]).unwrap_or_else(|| "Untitled".to_string());
 
         let mut blocks: Vec<NotionBlock> = Vec::new();
         let mut plain_text_parts: Vec<String> = Vec::new();
@@ -261,20 +215,11 @@ impl NodeLogic for GetNotionPageNode {
                 && resp.status().is_success()
                 && let Ok(blocks_data) = resp.json::<BlocksResponse>().await
             {
-                for block in blocks_data.results {
-                    let block_type = block["type"].as_str().unwrap_or("unknown").to_string();
-                    let text = extract_block_text(&block);
-
-                    if !text.is_empty() {
-                        plain_text_parts.push(text);
+                for block in blocks_data.results.iter().map(parse_notion_block) {
+                    if !block.text.is_empty() {
+                        plain_text_parts.push(block.text.clone());
                     }
-
-                    blocks.push(NotionBlock {
-                        id: block["id"].as_str().unwrap_or("").to_string(),
-                        block_type: block_type.clone(),
-                        has_children: block["has_children"].as_bool().unwrap_or(false),
-                        content: block[&block_type].clone(),
-                    });
+                    blocks.push(block);
                 }
             }
         }
@@ -293,6 +238,7 @@ impl NodeLogic for GetNotionPageNode {
             archived: page_data["archived"].as_bool().unwrap_or(false),
             icon_emoji: page_data["icon"]["emoji"].as_str().map(String::from),
             properties: page_data["properties"].clone(),
+            values: properties_to_plain(&page_data["properties"]),
             blocks: blocks.clone(),
             plain_text: plain_text.clone(),
         };
@@ -1,25 +1,13 @@
 use super::provider::{NOTION_PROVIDER_ID, NotionProvider};
+use super::{NOTION_API_VERSION, NotionPage, parse_notion_page};
 use flow_like::flow::{
     execution::{LogLevel, context::ExecutionContext},
     node::{Node, NodeLogic, NodeScores},
     pin::{PinOptions, ValueType},
     variable::VariableType,
 };
-use flow_like_types::{JsonSchema, Value, async_trait, json::json, reqwest};
-use serde::{Deserialize, Serialize};
-
-const NOTION_API_VERSION: &str = "2022-06-28";
-
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
-pub struct NotionPage {
-    pub id: String,
-    pub url: String,
-    pub created_time: String,
-    pub last_edited_time: String,
-    pub archived: bool,
-    pub icon_emoji: Option<String>,
-    pub properties: Value,
-}
+use flow_like_types::{Value, async_trait, json::json, reqwest};
+use serde::Deserialize;
 
 #[derive(Debug, Deserialize)]
 struct QueryResponse {
@@ -126,7 +114,7 @@ impl NodeLogic for QueryNotionDatabaseNode {
         node.add_output_pin(
             "pages",
             "Pages",
-            "Array of Notion pages matching the query",
+            "Array of Notion pages matching the query, with their properties also flattened to plain values",
             VariableType::Struct,
         )
         .set_value_type(ValueType::Array)
@@ -243,26 +231,8 @@ impl NodeLogic for QueryNotionDatabaseNode {
 
                 let pages: Vec<NotionPage> = query_response
                     .results
-                    .into_iter()
-                    .filter_map(|page| {
-                        let id = page["id"].as_str()?.to_string();
-                        let url = page["url"].as_str()?.to_string();
-                        let created_time = page["created_time"].as_str()?.to_string();
-                        let last_edited_time = page["last_edited_time"].as_str()?.to_string();
-                        let archived = page["archived"].as_bool().unwrap_or(false);
-                        let icon_emoji = page["icon"]["emoji"].as_str().map(String::from);
-                        let properties = page["properties"].clone();
-
-                        Some(NotionPage {
-                            id,
-                            url,
-                            created_time,
-                            last_edited_time,
-                            archived,
-                            icon_emoji,
-                            properties,
-                        })
-                    })
+                    .iter()
+                    .filter_map(parse_notion_page)
                     .collect();
 
                 let count = pages.len() as i64;
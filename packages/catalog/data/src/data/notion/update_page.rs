@@ -1,4 +1,5 @@
 use super::provider::{NOTION_PROVIDER_ID, NotionProvider};
+use super::{NOTION_API_VERSION, fetch_property_types, plain_to_properties};
 use flow_like::flow::{
     execution::{LogLevel, context::ExecutionContext},
     node::{Node, NodeLogic, NodeScores},
@@ -8,8 +9,6 @@ use flow_like::flow::{
 use flow_like_types::{JsonSchema, Value, async_trait, json::json, reqwest};
 use serde::{Deserialize, Serialize};
 
-const NOTION_API_VERSION: &str = "2022-06-28";
-
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct UpdatedNotionPage {
     pub id: String,
@@ -66,7 +65,16 @@ impl NodeLogic for UpdateNotionPageNode {
             "Properties (JSON)",
             "Page properties to update in Notion format. Example: {\"Status\": {\"status\": {\"name\": \"Done\"}}}",
             VariableType::String,
-        );
+        )
+        .set_default_value(Some(json!("")));
+
+        node.add_input_pin(
+            "values",
+            "Values (JSON)",
+            "Optional: Properties to update as plain values keyed by name, converted using the current property types. Example: {\"Status\": \"Done\", \"Tags\": [\"docs\"]}",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("")));
 
         node.add_input_pin(
             "icon_emoji",
@@ -131,6 +139,7 @@ impl NodeLogic for UpdateNotionPageNode {
 
         let page_id: String = context.evaluate_pin("page_id").await?;
         let properties_str: String = context.evaluate_pin("properties").await?;
+        let values_str: String = context.evaluate_pin("values").await?;
         let icon_emoji: String = context.evaluate_pin("icon_emoji").await?;
         let archived: bool = context.evaluate_pin("archived").await?;
 
@@ -156,6 +165,43 @@ impl NodeLogic for UpdateNotionPageNode {
             }
         }
 
+        let client = reqwest::Client::new();
+        let url = format!("https://api.notion.com/v1/pages/{}", page_id);
+
+        if !values_str.is_empty() {
+            let values: Value = match flow_like_types::json::from_str(&values_str) {
+                Ok(v) => v,
+                Err(e) => {
+                    context.log_message(&format!("Invalid values JSON: {}", e), LogLevel::Error);
+                    context.activate_exec_pin("error").await?;
+                    return Ok(());
+                }
+            };
+            let converted = fetch_property_types(&client, &access_token, &url)
+                .await
+                .and_then(|types| plain_to_properties(&values, &types));
+            let converted = match converted {
+                Ok(converted) => converted,
+                Err(e) => {
+                    context.log_message(&e, LogLevel::Error);
+                    context.activate_exec_pin("error").await?;
+                    return Ok(());
+                }
+            };
+            if body.get("properties").is_none() {
+                body["properties"] = json!({});
+            }
+            let Some(properties) = body["properties"].as_object_mut() else {
+                context.log_message("Properties must be a JSON object", LogLevel::Error);
+                context.activate_exec_pin("error").await?;
+                return Ok(());
+            };
+            // Properties given in Notion format win over plain values
+            for (name, property) in converted {
+                properties.entry(name).or_insert(property);
+            }
+        }
+
         if !icon_emoji.is_empty() {
             body["icon"] = json!({
                 "type": "emoji",
@@ -167,9 +213,6 @@ impl NodeLogic for UpdateNotionPageNode {
             body["archived"] = json!(true);
         }
 
-        let client = reqwest::Client::new();
-        let url = format!("https://api.notion.com/v1/pages/{}", page_id);
-
         context.log_message(
             &format!("Updating Notion page: {}", page_id),
             LogLevel::Debug,
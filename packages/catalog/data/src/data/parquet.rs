@@ -0,0 +1,459 @@
+//! Parquet support for the data catalog.
+//!
+//! Rows are exchanged as JSON objects (Flow-Like struct arrays) and converted to Arrow
+//! `RecordBatch`es with `serde_arrow`. Reading streams the file row group by row group
+//! through `ParquetObjectReader`, so only the current batch is kept in memory.
+
+use std::sync::Arc;
+
+use flow_like::flow::{pin::ValueType, variable::VariableType};
+use flow_like_storage::{
+    Path,
+    arrow::{
+        array::{ArrayRef, RecordBatch},
+        compute::cast,
+        datatypes::{DataType, Field, FieldRef, Schema},
+    },
+    datafusion::parquet::{
+        arrow::{
+            AsyncArrowWriter, ParquetRecordBatchStreamBuilder, ProjectionMask,
+            async_reader::{ParquetObjectReader, ParquetRecordBatchStream},
+            async_writer::ParquetObjectWriter,
+        },
+        basic::{Compression, ZstdLevel},
+        file::properties::WriterProperties,
+    },
+    object_store::ObjectStore,
+    serde_arrow::{
+        self,
+        schema::{SchemaLike, TracingOptions},
+    },
+};
+use flow_like_types::{Result, Value, anyhow, bail};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+pub mod read;
+pub mod write;
+
+/// A column of a Parquet file described in Flow-Like types.
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq)]
+pub struct ParquetColumn {
+    pub name: String,
+    pub arrow_type: String,
+    pub variable_type: VariableType,
+    pub value_type: ValueType,
+    pub nullable: bool,
+}
+
+pub fn parse_compression(compression: &str) -> Result<Compression> {
+    match compression.to_lowercase().as_str() {
+        "snappy" => Ok(Compression::SNAPPY),
+        "zstd" => Ok(Compression::ZSTD(ZstdLevel::default())),
+        "uncompressed" | "none" => Ok(Compression::UNCOMPRESSED),
+        other => bail!(
+            "Unsupported compression '{}', expected snappy, zstd or uncompressed",
+            other
+        ),
+    }
+}
+
+/// Maps an Arrow type to the Flow-Like pin type its values are exposed as.
+pub fn flow_type(data_type: &DataType) -> Option<(VariableType, ValueType)> {
+    let flow_type = match data_type {
+        DataType::Null => (VariableType::Generic, ValueType::Normal),
+        DataType::Boolean => (VariableType::Boolean, ValueType::Normal),
+        DataType::Int8
+        | DataType::Int16
+        | DataType::Int32
+        | DataType::Int64
+        | DataType::UInt8
+        | DataType::UInt16
+        | DataType::UInt32
+        | DataType::UInt64 => (VariableType::Integer, ValueType::Normal),
+        DataType::Float16
+        | DataType::Float32
+        | DataType::Float64
+        | DataType::Decimal128(_, _)
+        | DataType::Decimal256(_, _) => (VariableType::Float, ValueType::Normal),
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View => {
+            (VariableType::String, ValueType::Normal)
+        }
+        DataType::Date32 | DataType::Date64 | DataType::Timestamp(_, _) => {
+            (VariableType::Date, ValueType::Normal)
+        }
+        DataType::Time32(_) | DataType::Time64(_) => (VariableType::String, ValueType::Normal),
+        DataType::Binary
+        | DataType::LargeBinary
+        | DataType::BinaryView
+        | DataType::FixedSizeBinary(_) => (VariableType::Byte, ValueType::Array),
+        DataType::Dictionary(_, value) => return flow_type(value),
+        DataType::List(field) | DataType::LargeList(field) | DataType::FixedSizeList(field, _) => {
+            match flow_type(field.data_type())? {
+                (_, ValueType::Array) => (VariableType::Generic, ValueType::Array),
+                (variable_type, _) => (variable_type, ValueType::Array),
+            }
+        }
+        DataType::Struct(_) => (VariableType::Struct, ValueType::Normal),
+        _ => return None,
+    };
+
+    Some(flow_type)
+}
+
+/// Arrow type that `serde_arrow` turns into the JSON value Flow-Like expects
+/// (temporal values become ISO strings, decimals become floats).
+fn normalized_type(data_type: &DataType) -> Option<DataType> {
+    let normalize_field = |field: &FieldRef| -> Option<FieldRef> {
+        Some(Arc::new(
+            field
+                .as_ref()
+                .clone()
+                .with_data_type(normalized_type(field.data_type())?),
+        ))
+    };
+
+    let normalized = match data_type {
+        DataType::Float16 => DataType::Float32,
+        DataType::Decimal128(_, _) | DataType::Decimal256(_, _) => DataType::Float64,
+        DataType::Utf8View
+        | DataType::Date32
+        | DataType::Date64
+        | DataType::Timestamp(_, _)
+        | DataType::Time32(_)
+        | DataType::Time64(_) => DataType::Utf8,
+        DataType::BinaryView => DataType::Binary,
+        DataType::Dictionary(_, value) => normalized_type(value)?,
+        DataType::List(field) => DataType::List(normalize_field(field)?),
+        DataType::LargeList(field) => DataType::LargeList(normalize_field(field)?),
+        DataType::FixedSizeList(field, size) => {
+            DataType::FixedSizeList(normalize_field(field)?, *size)
+        }
+        DataType::Struct(fields) => DataType::Struct(
+            fields
+                .iter()
+                .map(normalize_field)
+                .collect::<Option<Vec<FieldRef>>>()?
+                .into(),
+        ),
+        other if flow_type(other).is_some() => other.clone(),
+        _ => return None,
+    };
+
+    Some(normalized)
+}
+
+/// Describes every column of `schema`, failing with the list of columns that can
+/// not be represented as Flow-Like values.
+pub fn describe_schema(schema: &Schema) -> Result<Vec<ParquetColumn>> {
+    let mut columns = Vec::with_capacity(schema.fields().len());
+    let mut unsupported = Vec::new();
+
+    for field in schema.fields() {
+        match (
+            flow_type(field.data_type()),
+            normalized_type(field.data_type()),
+        ) {
+            (Some((variable_type, value_type)), Some(_)) => columns.push(ParquetColumn {
+                name: field.name().clone(),
+                arrow_type: field.data_type().to_string(),
+                variable_type,
+                value_type,
+                nullable: field.is_nullable(),
+            }),
+            _ => unsupported.push(format!("'{}' ({})", field.name(), field.data_type())),
+        }
+    }
+
+    if !unsupported.is_empty() {
+        bail!(
+            "Unsupported Parquet column types: {}. Exclude them with the column projection",
+            unsupported.join(", ")
+        );
+    }
+
+    Ok(columns)
+}
+
+/// Converts a record batch into one JSON object per row.
+pub fn batch_to_records(batch: &RecordBatch) -> Result<Vec<Value>> {
+    let schema = batch.schema();
+    let mut fields = Vec::with_capacity(schema.fields().len());
+    let mut columns: Vec<ArrayRef> = Vec::with_capacity(schema.fields().len());
+
+    for (field, column) in schema.fields().iter().zip(batch.columns()) {
+        let data_type = normalized_type(field.data_type()).ok_or_else(|| {
+            anyhow!(
+                "Unsupported Parquet column type: '{}' ({})",
+                field.name(),
+                field.data_type()
+            )
+        })?;
+
+        if &data_type == field.data_type() {
+            columns.push(column.clone());
+        } else {
+            columns.push(cast(column, &data_type)?);
+        }
+        fields.push(Field::new(field.name(), data_type, field.is_nullable()));
+    }
+
+    let batch = RecordBatch::try_new(Arc::new(Schema::new(fields)), columns)?;
+    let records: Vec<Value> = serde_arrow::from_record_batch(&batch)?;
+    Ok(records)
+}
+
+/// Converts JSON objects into a record batch, inferring the Arrow schema from the rows.
+pub fn records_to_batch(records: &[Value]) -> Result<RecordBatch> {
+    if records.is_empty() {
+        bail!("No rows to write");
+    }
+
+    if let Some(index) = records.iter().position(|record| !record.is_object()) {
+        bail!("Row {} is not a struct", index);
+    }
+
+    let options = TracingOptions::default()
+        .allow_null_fields(true)
+        .coerce_numbers(true);
+    let fields = Vec::<FieldRef>::from_samples(records, options)?;
+    let batch = serde_arrow::to_record_batch(&fields, &records)?;
+    Ok(batch)
+}
+
+/// Writes `records` as a single Parquet file. Returns the number of written rows.
+pub async fn write_parquet(
+    store: Arc<dyn ObjectStore>,
+    path: Path,
+    records: &[Value],
+    compression: Compression,
+) -> Result<usize> {
+    let batch = records_to_batch(records)?;
+    let properties = WriterProperties::builder()
+        .set_compression(compression)
+        .build();
+
+    let mut writer = AsyncArrowWriter::try_new(
+        ParquetObjectWriter::new(store, path),
+        batch.schema(),
+        Some(properties),
+    )?;
+    writer.write(&batch).await?;
+    writer.close().await?;
+
+    Ok(batch.num_rows())
+}
+
+/// Opens a Parquet file as a stream of record batches. Only the `columns` are read,
+/// all columns if it is empty.
+pub async fn open_parquet_stream(
+    store: Arc<dyn ObjectStore>,
+    path: Path,
+    columns: &[String],
+    batch_size: usize,
+) -> Result<(
+    ParquetRecordBatchStream<ParquetObjectReader>,
+    Vec<ParquetColumn>,
+)> {
+    let meta = store.head(&path).await?;
+    let reader = ParquetObjectReader::new(store, path).with_file_size(meta.size);
+    let builder = ParquetRecordBatchStreamBuilder::new(reader).await?;
+    let schema = builder.schema().clone();
+
+    let mut indices = if columns.is_empty() {
+        (0..schema.fields().len()).collect::<Vec<_>>()
+    } else {
+        columns
+            .iter()
+            .map(|column| {
+                schema
+                    .index_of(column)
+                    .map_err(|_| anyhow!("Column '{}' not found in Parquet file", column))
+            })
+            .collect::<Result<Vec<_>>>()?
+    };
+    // Batches come back in file order, regardless of the requested order
+    indices.sort_unstable();
+    indices.dedup();
+
+    let described = describe_schema(&schema.project(&indices)?)?;
+    let mask = ProjectionMask::roots(builder.parquet_schema(), indices);
+    let stream = builder
+        .with_projection(mask)
+        .with_batch_size(batch_size.max(1))
+        .build()?;
+
+    Ok((stream, described))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flow_like_storage::{
+        arrow::datatypes::{IntervalUnit, TimeUnit},
+        object_store::memory::InMemory,
+    };
+    use flow_like_types::json::{from_value, json, to_value};
+    use futures::StreamExt;
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    struct Address {
+        city: String,
+        zip: i64,
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    struct Person {
+        id: i64,
+        name: String,
+        score: f64,
+        active: bool,
+        tags: Vec<String>,
+        address: Address,
+    }
+
+    fn people(count: usize) -> Vec<Person> {
+        (0..count)
+            .map(|index| Person {
+                id: index as i64,
+                name: format!("Person {}", index),
+                score: index as f64 * 1.5,
+                active: index % 2 == 0,
+                tags: (0..index % 3).map(|tag| format!("tag-{}", tag)).collect(),
+                address: Address {
+                    city: if index % 2 == 0 { "Berlin" } else { "Munich" }.to_string(),
+                    zip: 10_000 + index as i64,
+                },
+            })
+            .collect()
+    }
+
+    async fn read_all(
+        store: Arc<dyn ObjectStore>,
+        path: Path,
+        columns: &[String],
+        batch_size: usize,
+    ) -> (Vec<ParquetColumn>, Vec<Vec<Value>>) {
+        let (mut stream, described) = open_parquet_stream(store, path, columns, batch_size)
+            .await
+            .unwrap();
+        let mut batches = Vec::new();
+        while let Some(batch) = stream.next().await {
+            batches.push(batch_to_records(&batch.unwrap()).unwrap());
+        }
+        (described, batches)
+    }
+
+    #[tokio::test]
+    async fn test_round_trip_structs() {
+        let store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let path = Path::from("people.parquet");
+        let people = people(25);
+        let records: Vec<Value> = people.iter().map(|p| to_value(p).unwrap()).collect();
+
+        let written = write_parquet(
+            store.clone(),
+            path.clone(),
+            &records,
+            parse_compression("zstd").unwrap(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(written, 25);
+
+        let (described, batches) = read_all(store, path, &[], 10).await;
+        assert_eq!(
+            batches.iter().map(Vec::len).collect::<Vec<_>>(),
+            vec![10, 10, 5]
+        );
+
+        let read: Vec<Value> = batches.into_iter().flatten().collect();
+        assert_eq!(read, records);
+        let read: Vec<Person> = read.into_iter().map(|r| from_value(r).unwrap()).collect();
+        assert_eq!(read, people);
+
+        let mut types: Vec<(&str, VariableType, ValueType)> = described
+            .iter()
+            .map(|c| {
+                (
+                    c.name.as_str(),
+                    c.variable_type.clone(),
+                    c.value_type.clone(),
+                )
+            })
+            .collect();
+        types.sort_by_key(|(name, _, _)| *name);
+        assert_eq!(
+            types,
+            vec![
+                ("active", VariableType::Boolean, ValueType::Normal),
+                ("address", VariableType::Struct, ValueType::Normal),
+                ("id", VariableType::Integer, ValueType::Normal),
+                ("name", VariableType::String, ValueType::Normal),
+                ("score", VariableType::Float, ValueType::Normal),
+                ("tags", VariableType::String, ValueType::Array),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_projection_reads_selected_columns() {
+        let store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let path = Path::from("projection.parquet");
+        let records: Vec<Value> = people(3).iter().map(|p| to_value(p).unwrap()).collect();
+        write_parquet(store.clone(), path.clone(), &records, Compression::SNAPPY)
+            .await
+            .unwrap();
+
+        let columns = vec!["name".to_string(), "id".to_string()];
+        let (described, batches) = read_all(store.clone(), path.clone(), &columns, 100).await;
+        assert_eq!(described.len(), 2);
+        assert_eq!(batches[0][1], json!({ "id": 1, "name": "Person 1" }));
+
+        let missing = open_parquet_stream(store, path, &["unknown".to_string()], 100).await;
+        assert!(missing.is_err());
+    }
+
+    #[test]
+    fn test_temporal_columns_become_strings() {
+        let schema = Schema::new(vec![Field::new(
+            "at",
+            DataType::Timestamp(TimeUnit::Millisecond, None),
+            false,
+        )]);
+        let column: ArrayRef = Arc::new(
+            flow_like_storage::arrow::array::TimestampMillisecondArray::from(vec![
+                1_700_000_000_000,
+            ]),
+        );
+        let batch = RecordBatch::try_new(Arc::new(schema.clone()), vec![column]).unwrap();
+
+        let records = batch_to_records(&batch).unwrap();
+        assert_eq!(records, vec![json!({ "at": "2023-11-14T22:13:20" })]);
+        assert_eq!(
+            describe_schema(&schema).unwrap()[0].variable_type,
+            VariableType::Date
+        );
+    }
+
+    #[test]
+    fn test_unsupported_columns_are_reported() {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("span", DataType::Duration(TimeUnit::Second), true),
+            Field::new("window", DataType::Interval(IntervalUnit::DayTime), true),
+        ]);
+
+        let error = describe_schema(&schema).unwrap_err().to_string();
+        assert!(error.contains("'span'"));
+        assert!(error.contains("'window'"));
+        assert!(!error.contains("'id'"));
+    }
+
+    #[test]
+    fn test_records_must_be_structs() {
+        assert!(records_to_batch(&[]).is_err());
+        assert!(records_to_batch(&[json!({ "a": 1 }), json!(2)]).is_err());
+        assert!(parse_compression("lzo").is_err());
+    }
+}
@@ -0,0 +1,162 @@
+use flow_like::flow::{
+    execution::{LogLevel, context::ExecutionContext, internal_node::InternalNode},
+    node::{Node, NodeLogic},
+    pin::{PinOptions, ValueType},
+    variable::VariableType,
+};
+use flow_like_types::{async_trait, json::json};
+use futures::StreamExt;
+
+use super::{ParquetColumn, batch_to_records, open_parquet_stream};
+use crate::data::path::FlowPath;
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct ParquetReadNode {}
+
+impl ParquetReadNode {
+    pub fn new() -> Self {
+        ParquetReadNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for ParquetReadNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "parquet_read",
+            "Read Parquet",
+            "Streams a Parquet file in batches of rows. Row groups are read one after another, so large files are never loaded at once",
+            "Data/Parquet",
+        );
+        node.add_icon("/flow/icons/file-spreadsheet.svg");
+        node.set_long_running(true);
+
+        node.add_input_pin("exec_in", "Input", "", VariableType::Execution);
+
+        node.add_input_pin("path", "Path", "Parquet file to read", VariableType::Struct)
+            .set_schema::<FlowPath>()
+            .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node.add_input_pin(
+            "columns",
+            "Columns",
+            "Columns to read, all columns if empty",
+            VariableType::String,
+        )
+        .set_value_type(ValueType::Array)
+        .set_default_value(Some(json!([])));
+
+        node.add_input_pin(
+            "batch_size",
+            "Batch Size",
+            "Maximum number of rows per batch",
+            VariableType::Integer,
+        )
+        .set_default_value(Some(json!(8192)));
+
+        node.add_output_pin(
+            "on_batch",
+            "For Batch",
+            "Executes for each batch of rows",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "batch",
+            "Batch",
+            "Rows of the current batch",
+            VariableType::Struct,
+        )
+        .set_value_type(ValueType::Array);
+
+        node.add_output_pin(
+            "batch_index",
+            "Batch Index",
+            "Zero-based index of the current batch",
+            VariableType::Integer,
+        );
+
+        node.add_output_pin(
+            "schema",
+            "Schema",
+            "Columns of the file mapped to Flow-Like types",
+            VariableType::Struct,
+        )
+        .set_schema::<ParquetColumn>()
+        .set_value_type(ValueType::Array);
+
+        node.add_output_pin(
+            "done",
+            "Done",
+            "Executes once all rows were read",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "total_rows",
+            "Total Rows",
+            "Number of rows that were read",
+            VariableType::Integer,
+        );
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("done").await?;
+
+        let path: FlowPath = context.evaluate_pin("path").await?;
+        let columns: Vec<String> = context.evaluate_pin("columns").await?;
+        let batch_size: i64 = context.evaluate_pin("batch_size").await?;
+
+        let runtime = path.to_runtime(context).await?;
+        let (mut stream, schema) = open_parquet_stream(
+            runtime.store.as_generic(),
+            runtime.path.clone(),
+            &columns,
+            batch_size.max(1) as usize,
+        )
+        .await?;
+        context.set_pin_value("schema", json!(schema)).await?;
+
+        let on_batch = context.get_pin_by_name("on_batch").await?;
+        context.activate_exec_pin_ref(&on_batch).await?;
+        let flow = on_batch.get_connected_nodes();
+
+        let mut total_rows: u64 = 0;
+        let mut batch_index: u64 = 0;
+
+        while let Some(batch) = stream.next().await {
+            context.check_cancelled()?;
+            let records = batch_to_records(&batch?)?;
+            total_rows += records.len() as u64;
+
+            context.set_pin_value("batch", json!(records)).await?;
+            context
+                .set_pin_value("batch_index", json!(batch_index))
+                .await?;
+
+            for node in &flow {
+                let mut sub_context = context.create_sub_context(node).await;
+                let run = InternalNode::trigger(&mut sub_context, &mut None, true).await;
+                sub_context.end_trace();
+                context.push_sub_context(&mut sub_context);
+
+                if let Err(error) = run {
+                    context.log_message(&format!("Error: {:?}", error), LogLevel::Error);
+                }
+            }
+
+            batch_index += 1;
+        }
+
+        context.deactivate_exec_pin_ref(&on_batch).await?;
+        context
+            .set_pin_value("total_rows", json!(total_rows))
+            .await?;
+        context.activate_exec_pin("done").await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic},
+    pin::{PinOptions, ValueType},
+    variable::VariableType,
+};
+use flow_like_types::{Value, async_trait, json::json};
+
+use super::{parse_compression, write_parquet};
+use crate::data::path::FlowPath;
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct ParquetWriteNode {}
+
+impl ParquetWriteNode {
+    pub fn new() -> Self {
+        ParquetWriteNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for ParquetWriteNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "parquet_write",
+            "Write Parquet",
+            "Writes an array of structs to a Parquet file. The schema is inferred from the rows",
+            "Data/Parquet",
+        );
+        node.add_icon("/flow/icons/file-spreadsheet.svg");
+
+        node.add_input_pin("exec_in", "Input", "", VariableType::Execution);
+
+        node.add_input_pin("path", "Path", "Target file", VariableType::Struct)
+            .set_schema::<FlowPath>()
+            .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node.add_input_pin("rows", "Rows", "Rows to write", VariableType::Struct)
+            .set_value_type(ValueType::Array);
+
+        node.add_input_pin(
+            "compression",
+            "Compression",
+            "Compression codec for the column chunks",
+            VariableType::String,
+        )
+        .set_options(
+            PinOptions::new()
+                .set_valid_values(vec![
+                    "snappy".to_string(),
+                    "zstd".to_string(),
+                    "uncompressed".to_string(),
+                ])
+                .build(),
+        )
+        .set_default_value(Some(json!("snappy")));
+
+        node.add_output_pin("exec_out", "Output", "", VariableType::Execution);
+
+        node.add_output_pin(
+            "written",
+            "Written Rows",
+            "Number of rows written",
+            VariableType::Integer,
+        );
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+
+        let path: FlowPath = context.evaluate_pin("path").await?;
+        let rows: Vec<Value> = context.evaluate_pin("rows").await?;
+        let compression: String = context.evaluate_pin("compression").await?;
+        let compression = parse_compression(&compression)?;
+
+        let runtime = path.to_runtime(context).await?;
+        let written = write_parquet(
+            runtime.store.as_generic(),
+            runtime.path.clone(),
+            &rows,
+            compression,
+        )
+        .await?;
+
+        context.set_pin_value("written", json!(written)).await?;
+        context.activate_exec_pin("exec_out").await?;
+
+        Ok(())
+    }
+}
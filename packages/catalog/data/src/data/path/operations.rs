@@ -11,5 +11,6 @@ pub mod list_paths;
 pub mod list_with_offset;
 pub mod put;
 pub mod rename;
+pub mod sign_storage_request;
 pub mod sign_url;
 pub mod sign_urls;
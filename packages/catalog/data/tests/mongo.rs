@@ -0,0 +1,170 @@
+//! Integration tests for the MongoDB node helpers.
+//!
+//! Starts a MongoDB container through testcontainers, so Docker has to be available:
+//! ```sh
+//! cargo test --package flow-like-catalog-data --features mongodb --test mongo
+//! ```
+//! Tests are skipped when the container cannot be started.
+
+#![cfg(feature = "mongodb")]
+
+use flow_like_catalog_data::data::db::mongo::{
+    aggregate_batches, find_documents, insert_documents, update_documents,
+};
+use flow_like_types::json::json;
+use futures::TryStreamExt;
+use mongodb::{Client, Collection, bson::Document};
+use testcontainers_modules::{
+    mongo::Mongo,
+    testcontainers::{ContainerAsync, runners::AsyncRunner},
+};
+
+/// A fresh collection, the container has to stay alive as long as the collection is used
+async fn collection(name: &str) -> Option<(ContainerAsync<Mongo>, Collection<Document>)> {
+    let container = match Mongo::default().start().await {
+        Ok(container) => container,
+        Err(e) => {
+            eprintln!("Skipping test - MongoDB container not available: {}", e);
+            return None;
+        }
+    };
+    let host = container.get_host().await.unwrap();
+    let port = container.get_host_port_ipv4(27017).await.unwrap();
+
+    let client = Client::with_uri_str(format!("mongodb://{}:{}", host, port))
+        .await
+        .unwrap();
+    let collection = client.database("flow_like_test").collection(name);
+    Some((container, collection))
+}
+
+#[tokio::test]
+async fn test_find_with_projection() {
+    let Some((_container, people)) = collection("people").await else {
+        return;
+    };
+
+    let ids = insert_documents(
+        &people,
+        &[
+            json!({"name": "Alice", "age": 34, "city": "Berlin"}),
+            json!({"name": "Bob", "age": 27, "city": "Hamburg"}),
+            json!({"name": "Carol", "age": 41, "city": "Berlin"}),
+        ],
+    )
+    .await
+    .unwrap();
+    assert_eq!(ids.len(), 3);
+    assert!(ids.iter().all(|id| id.len() == 24));
+
+    let found = find_documents(
+        &people,
+        &json!({"city": "Berlin"}),
+        &json!({"name": 1, "_id": 0}),
+        &json!({"age": -1}),
+        0,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        found,
+        vec![json!({"name": "Carol"}), json!({"name": "Alice"})]
+    );
+
+    // Ids come back as hex strings and can be used as filters again
+    let by_id = find_documents(&people, &json!({"_id": ids[1]}), &json!({}), &json!({}), 1)
+        .await
+        .unwrap();
+    assert_eq!(by_id.len(), 1);
+    assert_eq!(by_id[0]["_id"], json!(ids[1]));
+    assert_eq!(by_id[0]["name"], json!("Bob"));
+}
+
+#[tokio::test]
+async fn test_upsert_inserts_then_updates() {
+    let Some((_container, counters)) = collection("counters").await else {
+        return;
+    };
+
+    let filter = json!({"name": "visits"});
+    let update = json!({"$inc": {"value": 1}});
+
+    let inserted = update_documents(&counters, &filter, &update, false, true)
+        .await
+        .unwrap();
+    assert_eq!(inserted.matched_count, 0);
+    let upserted_id = inserted
+        .upserted_id
+        .expect("upsert should insert a document");
+    assert_eq!(upserted_id.len(), 24);
+
+    let updated = update_documents(&counters, &filter, &update, false, true)
+        .await
+        .unwrap();
+    assert_eq!(updated.matched_count, 1);
+    assert_eq!(updated.modified_count, 1);
+    assert_eq!(updated.upserted_id, None);
+
+    let without_upsert = update_documents(
+        &counters,
+        &json!({"name": "missing"}),
+        &update,
+        false,
+        false,
+    )
+    .await
+    .unwrap();
+    assert_eq!(without_upsert.matched_count, 0);
+    assert_eq!(without_upsert.upserted_id, None);
+
+    let found = find_documents(&counters, &json!({}), &json!({}), &json!({}), 0)
+        .await
+        .unwrap();
+    assert_eq!(
+        found,
+        vec![json!({"_id": upserted_id, "name": "visits", "value": 2})]
+    );
+}
+
+#[tokio::test]
+async fn test_aggregation_pipeline_streams_batches() {
+    let Some((_container, orders)) = collection("orders").await else {
+        return;
+    };
+
+    let documents: Vec<_> = (0..25)
+        .map(|i| json!({"customer": if i % 2 == 0 { "a" } else { "b" }, "amount": i}))
+        .collect();
+    insert_documents(&orders, &documents).await.unwrap();
+
+    let totals: Vec<Vec<_>> = aggregate_batches(
+        &orders,
+        &[
+            json!({"$group": {"_id": "$customer", "total": {"$sum": "$amount"}, "orders": {"$sum": 1}}}),
+            json!({"$sort": {"_id": 1}}),
+        ],
+        10,
+    )
+    .await
+    .unwrap()
+    .try_collect()
+    .await
+    .unwrap();
+    assert_eq!(
+        totals,
+        vec![vec![
+            json!({"_id": "a", "total": 156, "orders": 13}),
+            json!({"_id": "b", "total": 144, "orders": 12}),
+        ]]
+    );
+
+    let batches: Vec<Vec<_>> = aggregate_batches(&orders, &[json!({"$sort": {"amount": 1}})], 10)
+        .await
+        .unwrap()
+        .try_collect()
+        .await
+        .unwrap();
+    let sizes: Vec<usize> = batches.iter().map(|batch| batch.len()).collect();
+    assert_eq!(sizes, vec![10, 10, 5]);
+    assert_eq!(batches[2][4]["amount"], json!(24));
+}
@@ -0,0 +1,125 @@
+//! Integration tests for the PostgreSQL query node helpers.
+//!
+//! Starts a PostgreSQL container through testcontainers, so Docker has to be available:
+//! ```sh
+//! cargo test --package flow-like-catalog-data --features postgres --test postgres_query
+//! ```
+//! Tests are skipped when the container cannot be started.
+
+#![cfg(feature = "postgres")]
+
+use flow_like_catalog_data::data::db::postgres::execute_query;
+use flow_like_types::json::json;
+use testcontainers_modules::{
+    postgres::Postgres,
+    testcontainers::{ContainerAsync, runners::AsyncRunner},
+};
+use tokio_postgres::{Client, NoTls};
+
+/// A client of a fresh database, the container has to stay alive as long as the client is used
+async fn get_client() -> Option<(ContainerAsync<Postgres>, Client)> {
+    let container = match Postgres::default().start().await {
+        Ok(container) => container,
+        Err(e) => {
+            eprintln!("Skipping test - PostgreSQL container not available: {}", e);
+            return None;
+        }
+    };
+    let host = container.get_host().await.unwrap();
+    let port = container.get_host_port_ipv4(5432).await.unwrap();
+
+    let (client, connection) = tokio_postgres::connect(
+        &format!(
+            "host={} port={} user=postgres password=postgres dbname=postgres",
+            host, port
+        ),
+        NoTls,
+    )
+    .await
+    .unwrap();
+    tokio::spawn(async move {
+        if let Err(e) = connection.await {
+            eprintln!("PostgreSQL connection error: {}", e);
+        }
+    });
+    Some((container, client))
+}
+
+#[tokio::test]
+async fn test_round_trip_supported_types() {
+    let Some((_container, client)) = get_client().await else {
+        return;
+    };
+
+    client
+        .batch_execute(
+            "CREATE TEMP TABLE flow_like_pg_types (
+                id INT,
+                name TEXT,
+                score FLOAT8,
+                active BOOL,
+                payload JSONB,
+                created TIMESTAMPTZ
+            )",
+        )
+        .await
+        .unwrap();
+
+    let inserted = execute_query(
+        &client,
+        "INSERT INTO flow_like_pg_types VALUES ($1, $2, $3, $4, $5, $6)",
+        &[
+            json!(1),
+            json!("Alice"),
+            json!(9.5),
+            json!(true),
+            json!({"tags": ["a", "b"]}),
+            json!("2024-03-01T12:00:00+00:00"),
+        ],
+    )
+    .await
+    .unwrap();
+    assert_eq!(inserted.affected_rows, 1);
+    assert!(inserted.rows.is_empty());
+
+    let selected = execute_query(
+        &client,
+        "SELECT * FROM flow_like_pg_types WHERE id = $1",
+        &[json!(1)],
+    )
+    .await
+    .unwrap();
+    assert_eq!(selected.rows.len(), 1);
+    let row = &selected.rows[0];
+    assert_eq!(row["name"], json!("Alice"));
+    assert_eq!(row["score"], json!(9.5));
+    assert_eq!(row["active"], json!(true));
+    assert_eq!(row["payload"], json!({"tags": ["a", "b"]}));
+    assert_eq!(row["created"], json!("2024-03-01T12:00:00+00:00"));
+}
+
+#[tokio::test]
+async fn test_parameters_are_not_interpolated() {
+    let Some((_container, client)) = get_client().await else {
+        return;
+    };
+
+    let result = execute_query(
+        &client,
+        "SELECT $1::text AS value",
+        &[json!("'; DROP TABLE users; --")],
+    )
+    .await
+    .unwrap();
+    assert_eq!(result.rows[0]["value"], json!("'; DROP TABLE users; --"));
+}
+
+#[tokio::test]
+async fn test_parameter_count_mismatch() {
+    let Some((_container, client)) = get_client().await else {
+        return;
+    };
+
+    let result = execute_query(&client, "SELECT $1::int AS value", &[]).await;
+    assert!(result.is_err());
+}
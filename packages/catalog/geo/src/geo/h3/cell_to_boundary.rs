@@ -44,6 +44,13 @@ impl NodeLogic for CellToBoundaryNode {
         )
         .set_schema::<GeoCoordinate>();
 
+        node.add_output_pin(
+            "geojson",
+            "GeoJSON",
+            "The boundary as a GeoJSON Polygon. Cells on the antimeridian keep continuous longitudes beyond ±180",
+            VariableType::Struct,
+        );
+
         node.add_output_pin(
             "vertex_count",
             "Vertex Count",
@@ -67,6 +74,7 @@ impl NodeLogic for CellToBoundaryNode {
 
     #[cfg(feature = "execute")]
     async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        use super::polyfill::unwrap_ring;
         use h3o::CellIndex;
         use std::str::FromStr;
 
@@ -83,7 +91,15 @@ impl NodeLogic for CellToBoundaryNode {
 
         let vertex_count = coords.len() as i64;
 
+        let mut ring: Vec<[f64; 2]> = boundary.iter().map(|ll| [ll.lng(), ll.lat()]).collect();
+        ring.push(ring[0]);
+        let geojson = json!({
+            "type": "Polygon",
+            "coordinates": [unwrap_ring(&ring)],
+        });
+
         context.set_pin_value("boundary", json!(coords)).await?;
+        context.set_pin_value("geojson", geojson).await?;
         context
             .set_pin_value("vertex_count", json!(vertex_count))
             .await?;
@@ -10,6 +10,7 @@ pub mod grid_disk;
 pub mod grid_distance;
 pub mod grid_path;
 pub mod latlng_to_cell;
+pub mod polyfill;
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,340 @@
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic, NodeScores},
+    pin::PinOptions,
+    variable::VariableType,
+};
+use flow_like_types::{Value, async_trait, json::json};
+
+/// Upper bound for the cells a single polyfill may return, to keep a continent at
+/// resolution 15 from exhausting memory.
+pub const MAX_POLYFILL_CELLS: usize = 1_000_000;
+
+/// Rings of one polygon as `[lng, lat]` positions, the exterior ring first and holes after.
+pub type PolygonRings = Vec<Vec<[f64; 2]>>;
+
+/// Reads the polygons of a GeoJSON `Polygon`, `MultiPolygon`, `Feature` or `FeatureCollection`.
+pub fn parse_geojson_polygons(value: &Value) -> flow_like_types::Result<Vec<PolygonRings>> {
+    let kind = value
+        .get("type")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    match kind {
+        "Polygon" => Ok(vec![parse_rings(coordinates(value)?)?]),
+        "MultiPolygon" => coordinates(value)?
+            .as_array()
+            .ok_or_else(|| flow_like_types::anyhow!("MultiPolygon coordinates must be an array"))?
+            .iter()
+            .map(parse_rings)
+            .collect(),
+        "Feature" => {
+            let geometry = value
+                .get("geometry")
+                .ok_or_else(|| flow_like_types::anyhow!("Feature has no geometry"))?;
+            parse_geojson_polygons(geometry)
+        }
+        "FeatureCollection" => {
+            let features = value
+                .get("features")
+                .and_then(Value::as_array)
+                .ok_or_else(|| flow_like_types::anyhow!("FeatureCollection has no features"))?;
+            let mut polygons = Vec::new();
+            for feature in features {
+                polygons.extend(parse_geojson_polygons(feature)?);
+            }
+            Ok(polygons)
+        }
+        "" => Err(flow_like_types::anyhow!(
+            "Expected a GeoJSON object with a \"type\" field"
+        )),
+        other => Err(flow_like_types::anyhow!(
+            "Unsupported GeoJSON type \"{}\", expected Polygon or MultiPolygon",
+            other
+        )),
+    }
+}
+
+fn coordinates(value: &Value) -> flow_like_types::Result<&Value> {
+    value
+        .get("coordinates")
+        .ok_or_else(|| flow_like_types::anyhow!("GeoJSON geometry has no coordinates"))
+}
+
+fn parse_rings(value: &Value) -> flow_like_types::Result<PolygonRings> {
+    let rings = value
+        .as_array()
+        .ok_or_else(|| flow_like_types::anyhow!("Polygon coordinates must be an array of rings"))?;
+    if rings.is_empty() {
+        return Err(flow_like_types::anyhow!("Polygon has no exterior ring"));
+    }
+
+    rings
+        .iter()
+        .map(|ring| {
+            let positions = ring
+                .as_array()
+                .ok_or_else(|| flow_like_types::anyhow!("Polygon ring must be an array"))?
+                .iter()
+                .map(parse_position)
+                .collect::<flow_like_types::Result<Vec<_>>>()?;
+            if positions.len() < 3 {
+                return Err(flow_like_types::anyhow!(
+                    "Polygon ring needs at least 3 positions, got {}",
+                    positions.len()
+                ));
+            }
+            Ok(positions)
+        })
+        .collect()
+}
+
+fn parse_position(value: &Value) -> flow_like_types::Result<[f64; 2]> {
+    let position = value.as_array().map(|p| p.as_slice()).unwrap_or_default();
+    let (Some(lng), Some(lat)) = (
+        position.first().and_then(Value::as_f64),
+        position.get(1).and_then(Value::as_f64),
+    ) else {
+        return Err(flow_like_types::anyhow!(
+            "Invalid position {}, expected [longitude, latitude]",
+            value
+        ));
+    };
+    if !(-90.0..=90.0).contains(&lat) || !lng.is_finite() {
+        return Err(flow_like_types::anyhow!(
+            "Position [{}, {}] is out of range",
+            lng,
+            lat
+        ));
+    }
+    Ok([lng, lat])
+}
+
+/// Shifts longitudes by whole turns so consecutive positions are never more than 180° apart.
+/// A ring crossing the antimeridian then runs past ±180 instead of jumping across the map.
+pub fn unwrap_ring(ring: &[[f64; 2]]) -> Vec<[f64; 2]> {
+    let mut unwrapped: Vec<[f64; 2]> = Vec::with_capacity(ring.len());
+    for &[lng, lat] in ring {
+        let lng = match unwrapped.last() {
+            Some(&[previous, _]) => lng + ((previous - lng) / 360.0).round() * 360.0,
+            None => lng,
+        };
+        unwrapped.push([lng, lat]);
+    }
+    unwrapped
+}
+
+#[cfg(feature = "execute")]
+pub fn parse_resolution(resolution: i64) -> flow_like_types::Result<h3o::Resolution> {
+    u8::try_from(resolution)
+        .ok()
+        .and_then(|resolution| h3o::Resolution::try_from(resolution).ok())
+        .ok_or_else(|| {
+            flow_like_types::anyhow!("Resolution must be between 0 and 15, got {}", resolution)
+        })
+}
+
+#[cfg(feature = "execute")]
+pub fn parse_containment(mode: &str) -> flow_like_types::Result<h3o::geom::ContainmentMode> {
+    use h3o::geom::ContainmentMode;
+
+    match mode {
+        "Centroid" => Ok(ContainmentMode::ContainsCentroid),
+        "Contained" => Ok(ContainmentMode::ContainsBoundary),
+        "Intersecting" => Ok(ContainmentMode::IntersectsBoundary),
+        other => Err(flow_like_types::anyhow!(
+            "Unknown containment mode \"{}\"",
+            other
+        )),
+    }
+}
+
+/// Builds planar polygons in the [-180, 180] longitude range. Polygons crossing the
+/// antimeridian are cut there and the pieces beyond it are moved back by a full turn.
+#[cfg(feature = "execute")]
+pub fn to_planar_polygons(rings: &PolygonRings) -> Vec<geo::Polygon<f64>> {
+    use geo::{BooleanOps, LineString, Polygon, Rect, Translate, coord};
+
+    let exterior = unwrap_ring(&rings[0]);
+    let anchor = exterior[0][0];
+    let to_line = |ring: Vec<[f64; 2]>| LineString::from(ring);
+
+    let holes: Vec<LineString<f64>> = rings[1..]
+        .iter()
+        .map(|ring| {
+            // Holes are unwrapped on their own, so move them next to the exterior first
+            let hole = unwrap_ring(ring);
+            let shift = ((anchor - hole[0][0]) / 360.0).round() * 360.0;
+            to_line(
+                hole.into_iter()
+                    .map(|[lng, lat]| [lng + shift, lat])
+                    .collect(),
+            )
+        })
+        .collect();
+
+    let (min_lng, max_lng) = exterior
+        .iter()
+        .fold((f64::MAX, f64::MIN), |(min, max), &[lng, _]| {
+            (min.min(lng), max.max(lng))
+        });
+    let polygon = Polygon::new(to_line(exterior), holes);
+
+    if min_lng >= -180.0 && max_lng <= 180.0 {
+        return vec![polygon];
+    }
+
+    let mut pieces = Vec::new();
+    let first_turn = ((min_lng + 180.0) / 360.0).floor() as i64;
+    let last_turn = ((max_lng + 180.0) / 360.0).floor() as i64;
+    for turn in first_turn..=last_turn {
+        let offset = turn as f64 * 360.0;
+        let window = Rect::new(
+            coord! { x: -180.0 + offset, y: -90.0 },
+            coord! { x: 180.0 + offset, y: 90.0 },
+        )
+        .to_polygon();
+        for piece in polygon.intersection(&window) {
+            pieces.push(piece.translate(-offset, 0.0));
+        }
+    }
+    pieces
+}
+
+/// Cells of the given resolution covering the polygons, in no particular order.
+#[cfg(feature = "execute")]
+pub fn polyfill(
+    polygons: &[PolygonRings],
+    resolution: h3o::Resolution,
+    containment: h3o::geom::ContainmentMode,
+) -> flow_like_types::Result<Vec<h3o::CellIndex>> {
+    use h3o::geom::TilerBuilder;
+
+    let mut tiler = TilerBuilder::new(resolution)
+        .containment_mode(containment)
+        .build();
+    for rings in polygons {
+        for polygon in to_planar_polygons(rings) {
+            tiler
+                .add(polygon)
+                .map_err(|e| flow_like_types::anyhow!("Invalid polygon: {}", e))?;
+        }
+    }
+
+    let cells: Vec<h3o::CellIndex> = tiler.into_coverage().take(MAX_POLYFILL_CELLS + 1).collect();
+    if cells.len() > MAX_POLYFILL_CELLS {
+        return Err(flow_like_types::anyhow!(
+            "Polygon covers more than {} cells at resolution {}, use a coarser resolution",
+            MAX_POLYFILL_CELLS,
+            u8::from(resolution)
+        ));
+    }
+    Ok(cells)
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct PolyfillNode {}
+
+impl PolyfillNode {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for PolyfillNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "h3_polyfill",
+            "H3 Polyfill",
+            "Returns the H3 cells covering a GeoJSON polygon, including holes and polygons crossing the antimeridian. Accepts Polygon, MultiPolygon, Feature and FeatureCollection objects.",
+            "Web/Geo/H3",
+        );
+        node.add_icon("/flow/icons/hexagon.svg");
+
+        node.add_input_pin(
+            "polygon",
+            "Polygon",
+            "GeoJSON polygon with [longitude, latitude] positions",
+            VariableType::Struct,
+        )
+        .set_default_value(Some(json!({ "type": "Polygon", "coordinates": [] })));
+
+        node.add_input_pin(
+            "resolution",
+            "Resolution",
+            "H3 resolution (0-15)",
+            VariableType::Integer,
+        )
+        .set_default_value(Some(json!(9)));
+
+        node.add_input_pin(
+            "containment",
+            "Containment",
+            "Which cells count as covered: center inside the polygon (H3 default), fully inside, or touching it",
+            VariableType::String,
+        )
+        .set_options(
+            PinOptions::new()
+                .set_valid_values(vec![
+                    "Centroid".to_string(),
+                    "Contained".to_string(),
+                    "Intersecting".to_string(),
+                ])
+                .build(),
+        )
+        .set_default_value(Some(json!("Centroid")));
+
+        node.add_output_pin(
+            "cells",
+            "Cells",
+            "H3 cell indices covering the polygon",
+            VariableType::String,
+        )
+        .set_value_type(flow_like::flow::pin::ValueType::Array);
+
+        node.add_output_pin("count", "Count", "Number of cells", VariableType::Integer);
+
+        node.set_long_running(false);
+        node.set_scores(
+            NodeScores::new()
+                .set_privacy(10)
+                .set_security(10)
+                .set_performance(6)
+                .set_reliability(9)
+                .set_cost(10)
+                .build(),
+        );
+
+        node
+    }
+
+    #[cfg(feature = "execute")]
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        let polygon: Value = context.evaluate_pin("polygon").await?;
+        let resolution: i64 = context.evaluate_pin("resolution").await?;
+        let containment: String = context.evaluate_pin("containment").await?;
+
+        let resolution = parse_resolution(resolution)?;
+        let containment = parse_containment(&containment)?;
+        let polygons = parse_geojson_polygons(&polygon)?;
+
+        let cells: Vec<String> = polyfill(&polygons, resolution, containment)?
+            .iter()
+            .map(|cell| cell.to_string())
+            .collect();
+        let count = cells.len() as i64;
+
+        context.set_pin_value("cells", json!(cells)).await?;
+        context.set_pin_value("count", json!(count)).await?;
+
+        Ok(())
+    }
+
+    #[cfg(not(feature = "execute"))]
+    async fn run(&self, _context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        Err(flow_like_types::anyhow!(
+            "This node requires the 'execute' feature"
+        ))
+    }
+}
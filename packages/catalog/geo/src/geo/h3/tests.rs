@@ -436,4 +436,146 @@ mod tests {
         assert!(result.is_ok());
         assert!(result.unwrap().0.is_empty());
     }
+
+    // Fixtures from the H3 library's polygonToCells tests, given there in radians as (lat, lng)
+    const SF_VERTS: [(f64, f64); 6] = [
+        (0.659966917655, -2.1364398519396),
+        (0.6595011102219, -2.1359434279405),
+        (0.6583348114025, -2.1354884206045),
+        (0.6581220034068, -2.1382437718946),
+        (0.6594479998527, -2.1384597563896),
+        (0.6599990002976, -2.1376771158464),
+    ];
+    const SF_HOLE_VERTS: [(f64, f64); 3] = [
+        (0.6595072188743, -2.1371053983433),
+        (0.6591482046471, -2.1373141048153),
+        (0.6592295020837, -2.1365222838402),
+    ];
+
+    fn geojson_ring(verts: &[(f64, f64)]) -> flow_like_types::Value {
+        let mut ring: Vec<[f64; 2]> = verts
+            .iter()
+            .map(|(lat, lng)| [lng.to_degrees(), lat.to_degrees()])
+            .collect();
+        ring.push(ring[0]);
+        flow_like_types::json::json!(ring)
+    }
+
+    fn polyfill_count(geojson: flow_like_types::Value, resolution: i64) -> usize {
+        use super::super::polyfill::{parse_geojson_polygons, parse_resolution, polyfill};
+        use h3o::geom::ContainmentMode;
+
+        let polygons = parse_geojson_polygons(&geojson).unwrap();
+        let resolution = parse_resolution(resolution).unwrap();
+        polyfill(&polygons, resolution, ContainmentMode::ContainsCentroid)
+            .unwrap()
+            .len()
+    }
+
+    #[test]
+    fn test_polyfill_matches_h3_reference() {
+        use flow_like_types::json::json;
+
+        let sf = json!({ "type": "Polygon", "coordinates": [geojson_ring(&SF_VERTS)] });
+        assert_eq!(polyfill_count(sf, 9), 1253);
+
+        let sf_with_hole = json!({
+            "type": "Polygon",
+            "coordinates": [geojson_ring(&SF_VERTS), geojson_ring(&SF_HOLE_VERTS)],
+        });
+        assert_eq!(polyfill_count(sf_with_hole, 9), 1214);
+    }
+
+    #[test]
+    fn test_polyfill_antimeridian_matches_h3_reference() {
+        use flow_like_types::json::json;
+        use std::f64::consts::PI;
+
+        let prime_meridian = [(0.01, 0.01), (0.01, -0.01), (-0.01, -0.01), (-0.01, 0.01)];
+        let prime_meridian =
+            json!({ "type": "Polygon", "coordinates": [geojson_ring(&prime_meridian)] });
+        assert_eq!(polyfill_count(prime_meridian, 7), 4228);
+
+        let trans_meridian = [
+            (0.01, -PI + 0.01),
+            (0.01, PI - 0.01),
+            (-0.01, PI - 0.01),
+            (-0.01, -PI + 0.01),
+        ];
+        let crossing = json!({ "type": "Polygon", "coordinates": [geojson_ring(&trans_meridian)] });
+        assert_eq!(polyfill_count(crossing, 7), 4238);
+
+        // The same square written with continuous longitudes past 180
+        let unwrapped =
+            trans_meridian.map(|(lat, lng)| (lat, if lng < 0.0 { lng + 2.0 * PI } else { lng }));
+        let unwrapped = json!({ "type": "Polygon", "coordinates": [geojson_ring(&unwrapped)] });
+        assert_eq!(polyfill_count(unwrapped, 7), 4238);
+    }
+
+    #[test]
+    fn test_polyfill_resolution_range() {
+        use super::super::polyfill::parse_resolution;
+
+        assert!(parse_resolution(-1).is_err());
+        assert!(parse_resolution(16).is_err());
+        assert_eq!(u8::from(parse_resolution(0).unwrap()), 0);
+        assert_eq!(u8::from(parse_resolution(15).unwrap()), 15);
+    }
+
+    #[test]
+    fn test_polyfill_geojson_inputs() {
+        use super::super::polyfill::parse_geojson_polygons;
+        use flow_like_types::json::json;
+
+        let polygon = json!({ "type": "Polygon", "coordinates": [geojson_ring(&SF_VERTS)] });
+        let feature = json!({ "type": "Feature", "properties": {}, "geometry": polygon });
+        let multi = json!({
+            "type": "MultiPolygon",
+            "coordinates": [[geojson_ring(&SF_VERTS)], [geojson_ring(&SF_HOLE_VERTS)]],
+        });
+
+        assert_eq!(parse_geojson_polygons(&feature).unwrap().len(), 1);
+        assert_eq!(parse_geojson_polygons(&multi).unwrap().len(), 2);
+        assert_eq!(polyfill_count(feature, 9), 1253);
+
+        let point = json!({ "type": "Point", "coordinates": [13.4, 52.5] });
+        assert!(parse_geojson_polygons(&point).is_err());
+        let open = json!({ "type": "Polygon", "coordinates": [[[0.0, 0.0], [1.0, 0.0]]] });
+        assert!(parse_geojson_polygons(&open).is_err());
+        let out_of_range = json!({
+            "type": "Polygon",
+            "coordinates": [[[0.0, 0.0], [1.0, 95.0], [1.0, 0.0]]],
+        });
+        assert!(parse_geojson_polygons(&out_of_range).is_err());
+    }
+
+    #[test]
+    fn test_boundary_polyfill_roundtrip() {
+        use super::super::polyfill::{
+            parse_geojson_polygons, parse_resolution, polyfill, unwrap_ring,
+        };
+        use flow_like_types::json::json;
+
+        let berlin = LatLng::new(BERLIN_LAT, BERLIN_LNG).unwrap();
+        let antimeridian = LatLng::new(0.0, 180.0).unwrap();
+
+        for (latlng, resolution) in [(berlin, 9_u8), (antimeridian, 5_u8)] {
+            let cell = latlng.to_cell(Resolution::try_from(resolution).unwrap());
+            let mut ring: Vec<[f64; 2]> = cell
+                .boundary()
+                .iter()
+                .map(|ll| [ll.lng(), ll.lat()])
+                .collect();
+            ring.push(ring[0]);
+            let geojson = json!({ "type": "Polygon", "coordinates": [unwrap_ring(&ring)] });
+
+            let cells = polyfill(
+                &parse_geojson_polygons(&geojson).unwrap(),
+                parse_resolution(resolution as i64).unwrap(),
+                h3o::geom::ContainmentMode::ContainsCentroid,
+            )
+            .unwrap();
+            assert_eq!(cells, vec![cell]);
+        }
+    }
 }
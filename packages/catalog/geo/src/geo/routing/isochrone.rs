@@ -0,0 +1,733 @@
+use std::{
+    collections::HashMap,
+    sync::{LazyLock, RwLock},
+    time::{Duration, Instant},
+};
+
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic, NodeScores},
+    pin::{PinOptions, ValueType},
+    variable::VariableType,
+};
+use flow_like_types::{Value, async_trait, json::json, reqwest};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::geo::{GeoCoordinate, routing::osrm::RouteProfile};
+
+/// Area reachable from the origin within `minutes`.
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema, Default)]
+pub struct IsochroneContour {
+    pub minutes: f64,
+    /// GeoJSON Polygon or MultiPolygon
+    pub geometry: Value,
+}
+
+/// A routing service that can compute travel-time polygons.
+#[async_trait]
+pub trait IsochroneBackend: Send + Sync {
+    /// Identifies the service instance, results are cached per id.
+    fn id(&self) -> String;
+
+    /// Most contours a single request can return. Larger threshold lists are split
+    /// into requests that run in parallel.
+    fn max_contours(&self) -> usize;
+
+    /// One contour per requested threshold, in any order.
+    async fn contours(
+        &self,
+        origin: &GeoCoordinate,
+        profile: &RouteProfile,
+        minutes: &[f64],
+    ) -> flow_like_types::Result<Vec<IsochroneContour>>;
+}
+
+/// Valhalla's `/isochrone` endpoint, up to four contours per request.
+pub struct ValhallaBackend {
+    pub client: reqwest::Client,
+    pub base_url: String,
+    pub api_key: Option<String>,
+}
+
+pub const VALHALLA_DEFAULT_URL: &str = "https://valhalla1.openstreetmap.de";
+
+#[async_trait]
+impl IsochroneBackend for ValhallaBackend {
+    fn id(&self) -> String {
+        format!("valhalla:{}", self.base_url)
+    }
+
+    fn max_contours(&self) -> usize {
+        4
+    }
+
+    async fn contours(
+        &self,
+        origin: &GeoCoordinate,
+        profile: &RouteProfile,
+        minutes: &[f64],
+    ) -> flow_like_types::Result<Vec<IsochroneContour>> {
+        let costing = match profile {
+            RouteProfile::Car => "auto",
+            RouteProfile::Bike => "bicycle",
+            RouteProfile::Foot => "pedestrian",
+        };
+        let body = json!({
+            "locations": [{ "lat": origin.latitude, "lon": origin.longitude }],
+            "costing": costing,
+            "contours": minutes.iter().map(|time| json!({ "time": time })).collect::<Vec<_>>(),
+            "polygons": true,
+        });
+
+        let mut request = self
+            .client
+            .post(format!("{}/isochrone", self.base_url))
+            .json(&body);
+        if let Some(api_key) = &self.api_key {
+            request = request.query(&[("api_key", api_key)]);
+        }
+        let response = request.send().await?;
+        if !response.status().is_success() {
+            return Err(flow_like_types::anyhow!(
+                "Valhalla returned status {}: {}",
+                response.status(),
+                response.text().await.unwrap_or_default()
+            ));
+        }
+
+        parse_valhalla_response(&response.json().await?)
+    }
+}
+
+/// Reads the contours of a Valhalla isochrone FeatureCollection.
+pub fn parse_valhalla_response(body: &Value) -> flow_like_types::Result<Vec<IsochroneContour>> {
+    let features = body
+        .get("features")
+        .and_then(Value::as_array)
+        .ok_or_else(|| flow_like_types::anyhow!("Valhalla response has no features"))?;
+
+    features
+        .iter()
+        .map(|feature| {
+            let minutes = feature
+                .pointer("/properties/contour")
+                .and_then(Value::as_f64)
+                .ok_or_else(|| flow_like_types::anyhow!("Valhalla feature has no contour"))?;
+            let geometry = feature
+                .get("geometry")
+                .cloned()
+                .ok_or_else(|| flow_like_types::anyhow!("Valhalla feature has no geometry"))?;
+            Ok(IsochroneContour { minutes, geometry })
+        })
+        .collect()
+}
+
+/// GraphHopper's `/isochrone` endpoint, which answers with a single time limit per request.
+pub struct GraphHopperBackend {
+    pub client: reqwest::Client,
+    pub base_url: String,
+    pub api_key: Option<String>,
+}
+
+pub const GRAPHHOPPER_DEFAULT_URL: &str = "https://graphhopper.com/api/1";
+
+#[async_trait]
+impl IsochroneBackend for GraphHopperBackend {
+    fn id(&self) -> String {
+        format!("graphhopper:{}", self.base_url)
+    }
+
+    fn max_contours(&self) -> usize {
+        1
+    }
+
+    async fn contours(
+        &self,
+        origin: &GeoCoordinate,
+        profile: &RouteProfile,
+        minutes: &[f64],
+    ) -> flow_like_types::Result<Vec<IsochroneContour>> {
+        let vehicle = match profile {
+            RouteProfile::Car => "car",
+            RouteProfile::Bike => "bike",
+            RouteProfile::Foot => "foot",
+        };
+
+        let mut contours = Vec::with_capacity(minutes.len());
+        for &limit in minutes {
+            let mut query = vec![
+                ("point", format!("{},{}", origin.latitude, origin.longitude)),
+                ("time_limit", ((limit * 60.0).round() as u64).to_string()),
+                ("profile", vehicle.to_string()),
+                ("buckets", "1".to_string()),
+            ];
+            if let Some(api_key) = &self.api_key {
+                query.push(("key", api_key.clone()));
+            }
+
+            let response = self
+                .client
+                .get(format!("{}/isochrone", self.base_url))
+                .query(&query)
+                .send()
+                .await?;
+            if !response.status().is_success() {
+                return Err(flow_like_types::anyhow!(
+                    "GraphHopper returned status {}: {}",
+                    response.status(),
+                    response.text().await.unwrap_or_default()
+                ));
+            }
+
+            contours.push(parse_graphhopper_response(&response.json().await?, limit)?);
+        }
+        Ok(contours)
+    }
+}
+
+/// Reads the single polygon of a GraphHopper isochrone response requested with one bucket.
+pub fn parse_graphhopper_response(
+    body: &Value,
+    minutes: f64,
+) -> flow_like_types::Result<IsochroneContour> {
+    let geometry = body
+        .pointer("/polygons/0/geometry")
+        .cloned()
+        .ok_or_else(|| flow_like_types::anyhow!("GraphHopper response has no polygon"))?;
+    Ok(IsochroneContour { minutes, geometry })
+}
+
+/// OSRM has no isochrone service. Travel times to a hexagonal grid of sample points are
+/// fetched with the table service and the reachable H3 cells are merged into polygons.
+pub struct OsrmBackend {
+    pub client: reqwest::Client,
+    pub base_url: String,
+}
+
+pub const OSRM_DEFAULT_URL: &str = "https://router.project-osrm.org";
+
+/// Coordinates per table request, the default `max-table-size` of osrm-routed.
+#[cfg(feature = "execute")]
+const OSRM_TABLE_SIZE: usize = 100;
+
+/// Rings of sample cells around the origin, 721 points at most.
+#[cfg(feature = "execute")]
+const OSRM_SAMPLE_RINGS: u32 = 15;
+
+#[cfg(feature = "execute")]
+impl OsrmBackend {
+    async fn durations(
+        &self,
+        profile: &RouteProfile,
+        origin: &GeoCoordinate,
+        destinations: &[GeoCoordinate],
+    ) -> flow_like_types::Result<Vec<Option<f64>>> {
+        use crate::geo::routing::osrm::build_coordinate_string;
+
+        let mut coordinates = vec![origin.clone()];
+        coordinates.extend_from_slice(destinations);
+        let url = format!(
+            "{}/table/v1/{}/{}?sources=0&annotations=duration",
+            self.base_url,
+            profile.as_str(),
+            build_coordinate_string(&coordinates)
+        );
+
+        let response = self.client.get(&url).send().await?;
+        if !response.status().is_success() {
+            return Err(flow_like_types::anyhow!(
+                "OSRM API returned status: {}",
+                response.status()
+            ));
+        }
+
+        let body: Value = response.json().await?;
+        let row = body
+            .pointer("/durations/0")
+            .and_then(Value::as_array)
+            .ok_or_else(|| flow_like_types::anyhow!("OSRM returned no durations"))?;
+        Ok(row.iter().skip(1).map(Value::as_f64).collect())
+    }
+}
+
+#[cfg(feature = "execute")]
+#[async_trait]
+impl IsochroneBackend for OsrmBackend {
+    fn id(&self) -> String {
+        format!("osrm:{}", self.base_url)
+    }
+
+    fn max_contours(&self) -> usize {
+        usize::MAX
+    }
+
+    async fn contours(
+        &self,
+        origin: &GeoCoordinate,
+        profile: &RouteProfile,
+        minutes: &[f64],
+    ) -> flow_like_types::Result<Vec<IsochroneContour>> {
+        use h3o::LatLng;
+
+        let center = LatLng::new(origin.latitude, origin.longitude)
+            .map_err(|e| flow_like_types::anyhow!("Invalid origin: {}", e))?;
+        let longest = minutes.iter().copied().fold(0.0, f64::max);
+        let cells = osrm_sample_cells(center, max_speed_mps(profile) * longest * 60.0);
+
+        let points: Vec<GeoCoordinate> = cells
+            .iter()
+            .map(|cell| {
+                let center = LatLng::from(*cell);
+                GeoCoordinate::new(center.lat(), center.lng())
+            })
+            .collect();
+        let chunks = points
+            .chunks(OSRM_TABLE_SIZE - 1)
+            .map(|chunk| self.durations(profile, origin, chunk));
+        let durations: Vec<Option<f64>> = futures::future::try_join_all(chunks)
+            .await?
+            .into_iter()
+            .flatten()
+            .collect();
+
+        minutes
+            .iter()
+            .map(|&limit| reachable_contour(&cells, &durations, limit))
+            .collect()
+    }
+}
+
+/// Upper speed estimate per profile, only used to size the sample grid.
+#[cfg(feature = "execute")]
+fn max_speed_mps(profile: &RouteProfile) -> f64 {
+    match profile {
+        RouteProfile::Car => 120.0 / 3.6,
+        RouteProfile::Bike => 25.0 / 3.6,
+        RouteProfile::Foot => 6.0 / 3.6,
+    }
+}
+
+/// Grid of cells covering `radius_m` around the center, at the finest resolution that
+/// needs no more than [`OSRM_SAMPLE_RINGS`] rings.
+#[cfg(feature = "execute")]
+pub fn osrm_sample_cells(center: h3o::LatLng, radius_m: f64) -> Vec<h3o::CellIndex> {
+    use h3o::Resolution;
+
+    let mut rings = OSRM_SAMPLE_RINGS;
+    let mut resolution = Resolution::Zero;
+    for candidate in (0..=10_u8).rev() {
+        let Ok(candidate) = Resolution::try_from(candidate) else {
+            continue;
+        };
+        let spacing = candidate.edge_length_m() * 3_f64.sqrt();
+        let needed = (radius_m / spacing).ceil() as u32;
+        if needed <= OSRM_SAMPLE_RINGS {
+            rings = needed.max(1);
+            resolution = candidate;
+            break;
+        }
+    }
+
+    center.to_cell(resolution).grid_disk::<Vec<_>>(rings)
+}
+
+/// Merges the sample cells reachable within `minutes` into a GeoJSON MultiPolygon.
+#[cfg(feature = "execute")]
+pub fn reachable_contour(
+    cells: &[h3o::CellIndex],
+    durations: &[Option<f64>],
+    minutes: f64,
+) -> flow_like_types::Result<IsochroneContour> {
+    use h3o::geom::SolventBuilder;
+
+    let limit = minutes * 60.0;
+    let reachable = cells
+        .iter()
+        .zip(durations)
+        .filter(|(_, duration)| duration.is_some_and(|duration| duration <= limit))
+        .map(|(cell, _)| *cell);
+
+    let polygons = SolventBuilder::new()
+        .build()
+        .dissolve(reachable)
+        .map_err(|e| flow_like_types::anyhow!("Failed to create polygon: {}", e))?;
+
+    let coordinates: Vec<Vec<Vec<[f64; 2]>>> = polygons
+        .0
+        .iter()
+        .map(|polygon| {
+            std::iter::once(polygon.exterior())
+                .chain(polygon.interiors())
+                .map(|ring| ring.coords().map(|c| [c.x, c.y]).collect())
+                .collect()
+        })
+        .collect();
+
+    Ok(IsochroneContour {
+        minutes,
+        geometry: json!({ "type": "MultiPolygon", "coordinates": coordinates }),
+    })
+}
+
+struct CachedContour {
+    contour: IsochroneContour,
+    cached_at: Instant,
+}
+
+/// Contours keyed by backend, origin, profile and threshold.
+#[derive(Default)]
+pub struct IsochroneCache {
+    entries: RwLock<HashMap<String, CachedContour>>,
+}
+
+static ISOCHRONE_CACHE: LazyLock<IsochroneCache> = LazyLock::new(IsochroneCache::default);
+
+impl IsochroneCache {
+    pub fn global() -> &'static IsochroneCache {
+        &ISOCHRONE_CACHE
+    }
+
+    fn key(backend: &str, origin: &GeoCoordinate, profile: &RouteProfile, minutes: f64) -> String {
+        format!(
+            "{}|{:.6},{:.6}|{}|{}",
+            backend,
+            origin.latitude,
+            origin.longitude,
+            profile.as_str(),
+            minutes
+        )
+    }
+
+    fn get(&self, key: &str, ttl: Duration) -> Option<IsochroneContour> {
+        let entries = self.entries.read().ok()?;
+        entries
+            .get(key)
+            .filter(|cached| cached.cached_at.elapsed() < ttl)
+            .map(|cached| cached.contour.clone())
+    }
+
+    fn insert(&self, key: String, contour: IsochroneContour, ttl: Duration) {
+        let Ok(mut entries) = self.entries.write() else {
+            return;
+        };
+        entries.retain(|_, cached| cached.cached_at.elapsed() < ttl);
+        entries.insert(
+            key,
+            CachedContour {
+                contour,
+                cached_at: Instant::now(),
+            },
+        );
+    }
+}
+
+/// Contours for all thresholds in the order they were given. Cached thresholds are reused,
+/// the others are requested in parallel batches of the backend's `max_contours`.
+pub async fn fetch_isochrones(
+    backend: &dyn IsochroneBackend,
+    cache: &IsochroneCache,
+    origin: &GeoCoordinate,
+    profile: &RouteProfile,
+    minutes: &[f64],
+    ttl: Duration,
+) -> flow_like_types::Result<Vec<IsochroneContour>> {
+    if let Some(invalid) = minutes.iter().find(|m| !m.is_finite() || **m <= 0.0) {
+        return Err(flow_like_types::anyhow!(
+            "Time thresholds must be positive, got {}",
+            invalid
+        ));
+    }
+
+    let backend_id = backend.id();
+    let keys: Vec<String> = minutes
+        .iter()
+        .map(|&limit| IsochroneCache::key(&backend_id, origin, profile, limit))
+        .collect();
+
+    let mut contours: Vec<Option<IsochroneContour>> =
+        keys.iter().map(|key| cache.get(key, ttl)).collect();
+
+    let mut missing: Vec<f64> = Vec::new();
+    for (limit, contour) in minutes.iter().zip(&contours) {
+        if contour.is_none() && !missing.contains(limit) {
+            missing.push(*limit);
+        }
+    }
+
+    if !missing.is_empty() {
+        let requests = missing
+            .chunks(backend.max_contours().max(1))
+            .map(|chunk| backend.contours(origin, profile, chunk));
+        let fetched: Vec<IsochroneContour> = futures::future::try_join_all(requests)
+            .await?
+            .into_iter()
+            .flatten()
+            .collect();
+
+        for (index, &limit) in minutes.iter().enumerate() {
+            if contours[index].is_some() {
+                continue;
+            }
+            let contour = fetched
+                .iter()
+                .find(|contour| (contour.minutes - limit).abs() < 1e-6)
+                .cloned()
+                .ok_or_else(|| {
+                    flow_like_types::anyhow!("Backend returned no contour for {} minutes", limit)
+                })?;
+            if !ttl.is_zero() {
+                cache.insert(keys[index].clone(), contour.clone(), ttl);
+            }
+            contours[index] = Some(contour);
+        }
+    }
+
+    Ok(contours.into_iter().flatten().collect())
+}
+
+/// The contours as a GeoJSON FeatureCollection with the threshold in `properties.minutes`.
+pub fn to_feature_collection(contours: &[IsochroneContour]) -> Value {
+    json!({
+        "type": "FeatureCollection",
+        "features": contours
+            .iter()
+            .map(|contour| json!({
+                "type": "Feature",
+                "properties": { "minutes": contour.minutes },
+                "geometry": contour.geometry,
+            }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct IsochroneNode {}
+
+impl IsochroneNode {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for IsochroneNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "geo_isochrone",
+            "Isochrone",
+            "Computes the areas reachable from an origin within the given travel times, as GeoJSON polygons. Works with Valhalla, GraphHopper or OSRM, including self-hosted instances.",
+            "Web/Geo/Routing",
+        );
+        node.add_icon("/flow/icons/map.svg");
+
+        node.add_input_pin(
+            "exec_in",
+            "Execute",
+            "Initiate the isochrone request",
+            VariableType::Execution,
+        );
+        node.add_input_pin(
+            "origin",
+            "Origin",
+            "Coordinate to measure travel times from",
+            VariableType::Struct,
+        )
+        .set_schema::<GeoCoordinate>()
+        .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node.add_input_pin(
+            "profile",
+            "Profile",
+            "Transportation mode: Car (driving), Bike (cycling), or Foot (walking)",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("Car")))
+        .set_options(
+            PinOptions::new()
+                .set_valid_values(vec![
+                    "Car".to_string(),
+                    "Bike".to_string(),
+                    "Foot".to_string(),
+                ])
+                .build(),
+        );
+
+        node.add_input_pin(
+            "minutes",
+            "Minutes",
+            "Travel time thresholds in minutes, one polygon each",
+            VariableType::Float,
+        )
+        .set_value_type(ValueType::Array)
+        .set_default_value(Some(json!([15.0])));
+
+        node.add_input_pin(
+            "backend",
+            "Backend",
+            "Routing service computing the isochrones. OSRM approximates them from a travel time matrix",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("Valhalla")))
+        .set_options(
+            PinOptions::new()
+                .set_valid_values(vec![
+                    "Valhalla".to_string(),
+                    "GraphHopper".to_string(),
+                    "OSRM".to_string(),
+                ])
+                .build(),
+        );
+
+        node.add_input_pin(
+            "base_url",
+            "Base URL",
+            "Server base URL, empty for the backend's public instance",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("")));
+
+        node.add_input_pin(
+            "api_key",
+            "API Key",
+            "API key, required by the hosted GraphHopper service",
+            VariableType::String,
+        )
+        .set_options(PinOptions::new().set_sensitive(true).build())
+        .set_default_value(Some(json!("")));
+
+        node.add_input_pin(
+            "cache_ttl",
+            "Cache TTL (s)",
+            "How long results are reused for the same origin, profile and threshold, 0 disables caching",
+            VariableType::Integer,
+        )
+        .set_default_value(Some(json!(3600)));
+
+        node.add_output_pin(
+            "exec_success",
+            "Success",
+            "Triggered when all isochrones were computed",
+            VariableType::Execution,
+        );
+        node.add_output_pin(
+            "exec_error",
+            "Error",
+            "Triggered when the request fails",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "isochrones",
+            "Isochrones",
+            "One contour per threshold, in input order",
+            VariableType::Struct,
+        )
+        .set_schema::<IsochroneContour>()
+        .set_value_type(ValueType::Array);
+
+        node.add_output_pin(
+            "geojson",
+            "GeoJSON",
+            "All contours as a FeatureCollection",
+            VariableType::Struct,
+        );
+
+        node.set_scores(
+            NodeScores::new()
+                .set_privacy(7)
+                .set_security(9)
+                .set_performance(6)
+                .set_reliability(8)
+                .set_cost(10)
+                .build(),
+        );
+
+        node
+    }
+
+    #[cfg(feature = "execute")]
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_success").await?;
+        context.activate_exec_pin("exec_error").await?;
+
+        let origin: GeoCoordinate = context.evaluate_pin("origin").await?;
+        let profile: String = context.evaluate_pin("profile").await?;
+        let minutes: Vec<f64> = context.evaluate_pin("minutes").await?;
+        let backend: String = context.evaluate_pin("backend").await?;
+        let base_url: String = context.evaluate_pin("base_url").await?;
+        let api_key: String = context.evaluate_pin("api_key").await?;
+        let cache_ttl: i64 = context.evaluate_pin("cache_ttl").await?;
+
+        let profile = match profile.as_str() {
+            "Car" | "car" | "driving" => RouteProfile::Car,
+            "Bike" | "bike" | "cycling" => RouteProfile::Bike,
+            "Foot" | "foot" | "walking" => RouteProfile::Foot,
+            _ => return Err(flow_like_types::anyhow!("Unsupported profile: {}", profile)),
+        };
+        if minutes.is_empty() {
+            return Err(flow_like_types::anyhow!(
+                "At least one time threshold is required"
+            ));
+        }
+
+        let client = reqwest::Client::builder()
+            .user_agent("FlowLike/1.0")
+            .build()?;
+        let base_url = |default: &str| {
+            let base_url = base_url.trim().trim_end_matches('/');
+            if base_url.is_empty() {
+                default.to_string()
+            } else {
+                base_url.to_string()
+            }
+        };
+        let api_key = (!api_key.is_empty()).then_some(api_key);
+
+        let backend: Box<dyn IsochroneBackend> = match backend.as_str() {
+            "Valhalla" => Box::new(ValhallaBackend {
+                client,
+                base_url: base_url(VALHALLA_DEFAULT_URL),
+                api_key,
+            }),
+            "GraphHopper" => Box::new(GraphHopperBackend {
+                client,
+                base_url: base_url(GRAPHHOPPER_DEFAULT_URL),
+                api_key,
+            }),
+            "OSRM" => Box::new(OsrmBackend {
+                client,
+                base_url: base_url(OSRM_DEFAULT_URL),
+            }),
+            _ => return Err(flow_like_types::anyhow!("Unsupported backend: {}", backend)),
+        };
+
+        let contours = fetch_isochrones(
+            backend.as_ref(),
+            IsochroneCache::global(),
+            &origin,
+            &profile,
+            &minutes,
+            Duration::from_secs(cache_ttl.max(0) as u64),
+        )
+        .await?;
+
+        context
+            .set_pin_value("geojson", to_feature_collection(&contours))
+            .await?;
+        context.set_pin_value("isochrones", json!(contours)).await?;
+
+        context.deactivate_exec_pin("exec_error").await?;
+        context.activate_exec_pin("exec_success").await?;
+
+        Ok(())
+    }
+
+    #[cfg(not(feature = "execute"))]
+    async fn run(&self, _context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        Err(flow_like_types::anyhow!(
+            "This node requires the 'execute' feature"
+        ))
+    }
+}
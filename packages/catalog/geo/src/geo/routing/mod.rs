@@ -1,3 +1,4 @@
+pub mod isochrone;
 pub mod match_trace;
 pub mod nearest;
 pub mod osrm;
@@ -164,4 +164,300 @@ mod tests {
         assert_eq!(geometry[2].latitude, 52.54);
         assert_eq!(geometry[2].longitude, 13.42);
     }
+
+    mod isochrone {
+        use std::{sync::Mutex, time::Duration};
+
+        use flow_like_types::{async_trait, json::json};
+
+        use crate::geo::{
+            GeoCoordinate,
+            routing::{
+                isochrone::{
+                    IsochroneBackend, IsochroneCache, IsochroneContour, fetch_isochrones,
+                    osrm_sample_cells, parse_graphhopper_response, parse_valhalla_response,
+                    reachable_contour, to_feature_collection,
+                },
+                osrm::RouteProfile,
+            },
+        };
+
+        const TTL: Duration = Duration::from_secs(60);
+
+        /// Answers with a square whose size grows with the threshold and records every request.
+        struct MockBackend {
+            max_contours: usize,
+            requests: Mutex<Vec<Vec<f64>>>,
+        }
+
+        impl MockBackend {
+            fn new(max_contours: usize) -> Self {
+                Self {
+                    max_contours,
+                    requests: Mutex::new(Vec::new()),
+                }
+            }
+
+            fn requests(&self) -> Vec<Vec<f64>> {
+                self.requests.lock().unwrap().clone()
+            }
+        }
+
+        #[async_trait]
+        impl IsochroneBackend for MockBackend {
+            fn id(&self) -> String {
+                "mock".to_string()
+            }
+
+            fn max_contours(&self) -> usize {
+                self.max_contours
+            }
+
+            async fn contours(
+                &self,
+                origin: &GeoCoordinate,
+                _profile: &RouteProfile,
+                minutes: &[f64],
+            ) -> flow_like_types::Result<Vec<IsochroneContour>> {
+                self.requests.lock().unwrap().push(minutes.to_vec());
+                // Reversed, callers must not rely on the backend's order
+                Ok(minutes
+                    .iter()
+                    .rev()
+                    .map(|&limit| {
+                        let d = limit / 1000.0;
+                        let (lat, lng) = (origin.latitude, origin.longitude);
+                        IsochroneContour {
+                            minutes: limit,
+                            geometry: json!({
+                                "type": "Polygon",
+                                "coordinates": [[
+                                    [lng - d, lat - d], [lng + d, lat - d],
+                                    [lng + d, lat + d], [lng - d, lat + d],
+                                    [lng - d, lat - d],
+                                ]],
+                            }),
+                        }
+                    })
+                    .collect())
+            }
+        }
+
+        fn berlin() -> GeoCoordinate {
+            GeoCoordinate::new(52.52, 13.405)
+        }
+
+        fn minutes(contours: &[IsochroneContour]) -> Vec<f64> {
+            contours.iter().map(|contour| contour.minutes).collect()
+        }
+
+        #[tokio::test]
+        async fn test_single_contour_backend_gets_parallel_requests() {
+            let backend = MockBackend::new(1);
+            let cache = IsochroneCache::default();
+            let contours = fetch_isochrones(
+                &backend,
+                &cache,
+                &berlin(),
+                &RouteProfile::Car,
+                &[15.0, 5.0, 10.0],
+                TTL,
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(minutes(&contours), vec![15.0, 5.0, 10.0]);
+            assert_eq!(backend.requests(), vec![vec![15.0], vec![5.0], vec![10.0]]);
+        }
+
+        #[tokio::test]
+        async fn test_thresholds_are_batched_per_request() {
+            let backend = MockBackend::new(2);
+            let cache = IsochroneCache::default();
+            let contours = fetch_isochrones(
+                &backend,
+                &cache,
+                &berlin(),
+                &RouteProfile::Foot,
+                &[5.0, 10.0, 15.0, 10.0],
+                TTL,
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(minutes(&contours), vec![5.0, 10.0, 15.0, 10.0]);
+            assert_eq!(backend.requests(), vec![vec![5.0, 10.0], vec![15.0]]);
+        }
+
+        #[tokio::test]
+        async fn test_results_are_cached_per_origin_profile_and_threshold() {
+            let backend = MockBackend::new(4);
+            let cache = IsochroneCache::default();
+            let fetch = |profile: RouteProfile, thresholds: Vec<f64>, ttl: Duration| {
+                let backend = &backend;
+                let cache = &cache;
+                async move {
+                    fetch_isochrones(backend, cache, &berlin(), &profile, &thresholds, ttl)
+                        .await
+                        .unwrap()
+                }
+            };
+
+            fetch(RouteProfile::Car, vec![5.0, 10.0], TTL).await;
+            let contours = fetch(RouteProfile::Car, vec![10.0, 20.0, 5.0], TTL).await;
+            assert_eq!(minutes(&contours), vec![10.0, 20.0, 5.0]);
+            assert_eq!(backend.requests(), vec![vec![5.0, 10.0], vec![20.0]]);
+
+            fetch(RouteProfile::Bike, vec![5.0], TTL).await;
+            assert_eq!(backend.requests().len(), 3);
+
+            // A zero TTL neither reads nor fills the cache
+            fetch(RouteProfile::Car, vec![5.0], Duration::ZERO).await;
+            fetch(RouteProfile::Foot, vec![5.0], Duration::ZERO).await;
+            fetch(RouteProfile::Foot, vec![5.0], TTL).await;
+            assert_eq!(backend.requests().len(), 6);
+        }
+
+        #[tokio::test]
+        async fn test_invalid_thresholds_and_missing_contours() {
+            let backend = MockBackend::new(1);
+            let cache = IsochroneCache::default();
+            for thresholds in [vec![0.0], vec![-5.0], vec![f64::NAN]] {
+                let result = fetch_isochrones(
+                    &backend,
+                    &cache,
+                    &berlin(),
+                    &RouteProfile::Car,
+                    &thresholds,
+                    TTL,
+                )
+                .await;
+                assert!(result.is_err());
+            }
+            assert!(backend.requests().is_empty());
+
+            struct EmptyBackend;
+
+            #[async_trait]
+            impl IsochroneBackend for EmptyBackend {
+                fn id(&self) -> String {
+                    "empty".to_string()
+                }
+
+                fn max_contours(&self) -> usize {
+                    4
+                }
+
+                async fn contours(
+                    &self,
+                    _origin: &GeoCoordinate,
+                    _profile: &RouteProfile,
+                    _minutes: &[f64],
+                ) -> flow_like_types::Result<Vec<IsochroneContour>> {
+                    Ok(vec![])
+                }
+            }
+
+            let result = fetch_isochrones(
+                &EmptyBackend,
+                &cache,
+                &berlin(),
+                &RouteProfile::Car,
+                &[10.0],
+                TTL,
+            )
+            .await;
+            assert!(result.is_err());
+        }
+
+        #[test]
+        fn test_parse_valhalla_response() {
+            let body = json!({
+                "type": "FeatureCollection",
+                "features": [
+                    {
+                        "type": "Feature",
+                        "properties": { "contour": 10, "metric": "time" },
+                        "geometry": { "type": "Polygon", "coordinates": [[[13.4, 52.5], [13.5, 52.5], [13.4, 52.6], [13.4, 52.5]]] }
+                    },
+                    {
+                        "type": "Feature",
+                        "properties": { "contour": 5, "metric": "time" },
+                        "geometry": { "type": "Polygon", "coordinates": [[[13.4, 52.5], [13.45, 52.5], [13.4, 52.55], [13.4, 52.5]]] }
+                    }
+                ]
+            });
+
+            let contours = parse_valhalla_response(&body).unwrap();
+            assert_eq!(minutes(&contours), vec![10.0, 5.0]);
+            assert_eq!(contours[0].geometry["type"], "Polygon");
+
+            assert!(parse_valhalla_response(&json!({ "error": "No suitable edges" })).is_err());
+        }
+
+        #[test]
+        fn test_parse_graphhopper_response() {
+            let body = json!({
+                "polygons": [{
+                    "type": "Feature",
+                    "properties": { "bucket": 0 },
+                    "geometry": { "type": "Polygon", "coordinates": [[[13.4, 52.5], [13.5, 52.5], [13.4, 52.6], [13.4, 52.5]]] }
+                }],
+                "info": { "copyrights": ["GraphHopper"] }
+            });
+
+            let contour = parse_graphhopper_response(&body, 15.0).unwrap();
+            assert_eq!(contour.minutes, 15.0);
+            assert_eq!(contour.geometry["type"], "Polygon");
+
+            assert!(parse_graphhopper_response(&json!({ "polygons": [] }), 15.0).is_err());
+        }
+
+        #[test]
+        fn test_osrm_contours_from_sample_durations() {
+            let center = h3o::LatLng::new(52.52, 13.405).unwrap();
+            let cells = osrm_sample_cells(center, 2_000.0);
+            assert!(cells.len() <= 721);
+            let origin = center.to_cell(cells[0].resolution());
+            assert!(cells.contains(&origin));
+
+            // Travel time grows by a minute per ring
+            let durations: Vec<Option<f64>> = cells
+                .iter()
+                .map(|cell| {
+                    let ring = origin.grid_distance(*cell).unwrap();
+                    (ring < 10).then_some(ring as f64 * 60.0)
+                })
+                .collect();
+
+            let near = reachable_contour(&cells, &durations, 2.0).unwrap();
+            let far = reachable_contour(&cells, &durations, 30.0).unwrap();
+            assert_eq!(near.geometry["type"], "MultiPolygon");
+            assert_eq!(near.geometry["coordinates"].as_array().unwrap().len(), 1);
+            assert_eq!(far.geometry["coordinates"].as_array().unwrap().len(), 1);
+
+            let ring_len = |contour: &IsochroneContour| {
+                contour.geometry["coordinates"][0][0]
+                    .as_array()
+                    .unwrap()
+                    .len()
+            };
+            assert!(ring_len(&far) > ring_len(&near));
+
+            let none = reachable_contour(&cells, &vec![None; cells.len()], 30.0).unwrap();
+            assert!(none.geometry["coordinates"].as_array().unwrap().is_empty());
+        }
+
+        #[test]
+        fn test_feature_collection() {
+            let contours = vec![IsochroneContour {
+                minutes: 15.0,
+                geometry: json!({ "type": "Polygon", "coordinates": [] }),
+            }];
+            let collection = to_feature_collection(&contours);
+            assert_eq!(collection["type"], "FeatureCollection");
+            assert_eq!(collection["features"][0]["properties"]["minutes"], 15.0);
+            assert_eq!(collection["features"][0]["geometry"]["type"], "Polygon");
+        }
+    }
 }
@@ -8,6 +8,7 @@ pub mod add_datafusion;
 pub mod from_model;
 pub mod helpers;
 pub mod invoke;
+pub mod mcp_connect;
 pub mod register_mcp_tools;
 pub mod register_thinking;
 pub mod register_tools;
@@ -25,6 +26,11 @@ pub struct McpServerConfig {
     /// If Some, only tools in this set are used
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tool_filter: Option<HashSet<String>>,
+
+    /// Run cache key of a connection opened by "MCP Connect".
+    /// If set, the agent uses that connection instead of connecting to `uri`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connection: Option<String>,
 }
 
 /// DataFusion session context for SQL-based data analysis
@@ -1,3 +1,5 @@
+#[cfg(feature = "execute")]
+use super::mcp_connect::{self, CachedMcpClient, McpToolError};
 use crate::generative::agent::{Agent, ContextManagementMode};
 /// # Agent Execution Helpers
 /// This module contains reusable logic for executing agents with tools and streaming.
@@ -32,13 +34,6 @@ use rig::message::{AssistantContent, ToolCall as RigToolCall};
 use rig::streaming::StreamedAssistantContent;
 #[cfg(feature = "execute")]
 use rig::tools::ThinkTool;
-#[cfg(feature = "execute")]
-use rmcp::{
-    ServiceExt,
-    model::{
-        CallToolRequestParam, ClientCapabilities, ClientInfo, Implementation, PaginatedRequestParam,
-    },
-};
 use std::{collections::HashMap, sync::Arc};
 
 const DEFAULT_MAX_CONTEXT_TOKENS: u32 = 32000;
@@ -783,63 +778,46 @@ pub async fn execute_agent_streaming(
         .await?
         .preamble(&system_prompt);
     let mut tool_servers: Vec<(Vec<rmcp::model::Tool>, rmcp::service::ServerSink)> = Vec::new();
-    let mut mcp_tool_clients: HashMap<String, rmcp::service::ServerSink> = HashMap::new();
+    let mut mcp_tool_clients: HashMap<String, (rmcp::service::ServerSink, String)> = HashMap::new();
     let mut _mcp_clients = Vec::new();
 
-    let client_info = ClientInfo {
-        protocol_version: Default::default(),
-        capabilities: ClientCapabilities::default(),
-        client_info: Implementation {
-            name: "Flow-Like".to_string(),
-            version: "alpha".to_string(),
-            title: None,
-            icons: None,
-            website_url: Some("https://flow-like.com".to_string()),
-        },
-    };
-
     for mcp_config in &agent.mcp_servers {
-        let transport =
-            rmcp::transport::StreamableHttpClientTransport::from_uri(mcp_config.uri.as_str());
-        let client = match client_info.clone().serve(transport).await {
-            Ok(c) => c,
-            Err(e) => {
-                let error = format!("Failed to connect to MCP server {}: {}", mcp_config.uri, e);
-                context.log_message(&error, LogLevel::Error);
-                continue;
-            }
-        };
-
-        // Fetch all tools with pagination support
-        let mut all_tools = Vec::new();
-        let mut cursor: Option<PaginatedRequestParam> = None;
-
-        loop {
-            let list_result = client.list_tools(cursor.clone()).await;
-
-            let response = match list_result {
-                Ok(r) => r,
-                Err(e) => {
+        let peer = match &mcp_config.connection {
+            Some(cache_key) => match context.get_cached::<CachedMcpClient>(cache_key).await {
+                Some(client) => client.peer(),
+                None => {
                     let error = format!(
-                        "Failed to fetch tools from MCP server {}: {}",
-                        mcp_config.uri, e
+                        "MCP connection to {} is not open in this run",
+                        mcp_config.uri
                     );
                     context.log_message(&error, LogLevel::Error);
-                    break;
+                    continue;
                 }
-            };
-
-            all_tools.extend(response.tools);
+            },
+            None => match mcp_connect::connect_http(&mcp_config.uri).await {
+                Ok(client) => {
+                    let peer = client.peer();
+                    _mcp_clients.push(client);
+                    peer
+                }
+                Err(e) => {
+                    context.log_message(&e.to_string(), LogLevel::Error);
+                    continue;
+                }
+            },
+        };
 
-            // Check if there are more pages
-            if let Some(next_cursor) = response.next_cursor {
-                cursor = Some(PaginatedRequestParam {
-                    cursor: Some(next_cursor),
-                });
-            } else {
-                break;
+        let all_tools = match mcp_connect::list_tools(&peer).await {
+            Ok(tools) => tools,
+            Err(e) => {
+                let error = format!(
+                    "Failed to fetch tools from MCP server {}: {}",
+                    mcp_config.uri, e
+                );
+                context.log_message(&error, LogLevel::Error);
+                Vec::new()
             }
-        }
+        };
 
         if all_tools.is_empty() {
             context.log_message(
@@ -869,12 +847,10 @@ pub async fn execute_agent_streaming(
             continue;
         }
 
-        let peer = client.peer().to_owned();
-
         for tool in &filtered_tools {
             let tool_name = tool.name.to_string();
             if mcp_tool_clients
-                .insert(tool_name.clone(), peer.clone())
+                .insert(tool_name.clone(), (peer.clone(), mcp_config.uri.clone()))
                 .is_some()
             {
                 context.log_message(
@@ -888,7 +864,6 @@ pub async fn execute_agent_streaming(
         }
 
         tool_servers.push((filtered_tools, peer));
-        _mcp_clients.push(client);
     }
 
     let mut tool_iter = tool_servers.into_iter();
@@ -1365,21 +1340,13 @@ pub async fn execute_agent_streaming(
                         Ok(value) => value,
                         Err(error) => json::json!(format!("Error: {:?}", error)),
                     }
-                } else if let Some(mcp_peer) = mcp_tool_clients.get(name) {
+                } else if let Some((mcp_peer, server)) = mcp_tool_clients.get(name) {
                     context.log_message(
                         &format!("Calling MCP tool '{}' with arguments {}", name, arguments),
                         LogLevel::Debug,
                     );
 
-                    let args_map = arguments.as_object().cloned();
-                    match mcp_peer
-                        .call_tool(CallToolRequestParam {
-                            name: name.clone().into(),
-                            arguments: args_map,
-                            task: None,
-                        })
-                        .await
-                    {
+                    match mcp_connect::call_mcp_tool(mcp_peer, server, name, arguments).await {
                         Ok(result) => {
                             context.log_message(
                                 &format!(
@@ -1388,15 +1355,15 @@ pub async fn execute_agent_streaming(
                                 ),
                                 LogLevel::Debug,
                             );
-                            json::to_value(result)
-                                .unwrap_or_else(|_| json::json!({"message": "Tool executed"}))
+                            result
                         }
-                        Err(error) => {
-                            context.log_message(
-                                &format!("MCP tool '{}' call failed: {}", name, error),
-                                LogLevel::Error,
-                            );
-                            json::json!({"error": format!("{}", error)})
+                        Err(McpToolError::Disconnected(error)) => {
+                            context.log_message(&error, LogLevel::Error);
+                            return Err(anyhow!(error));
+                        }
+                        Err(McpToolError::Failed(error)) => {
+                            context.log_message(&error, LogLevel::Error);
+                            json::json!({"error": error})
                         }
                     }
                 } else if name == "think" && agent.thinking_enabled {
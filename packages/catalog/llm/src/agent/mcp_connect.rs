@@ -0,0 +1,513 @@
+/// # MCP Connect Node
+/// Opens a connection to a Model Context Protocol (MCP) server and keeps it open for the
+/// rest of the run. Supports two transports:
+/// - HTTP: Streamable HTTP, responses are streamed as server sent events
+/// - Stdio: Spawns the server as a child process and talks to it over stdin/stdout
+///
+/// The connection handle can be passed to "Register MCP Tools", agents then call the tools
+/// through the open connection instead of connecting on their own.
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic, NodeScores},
+    pin::{PinOptions, ValueType},
+    variable::VariableType,
+};
+#[cfg(feature = "execute")]
+use flow_like_types::{Cacheable, Value, anyhow, create_id};
+use flow_like_types::{JsonSchema, async_trait, json};
+#[cfg(feature = "execute")]
+use rmcp::{
+    ServiceExt,
+    model::{
+        CallToolRequestParam, ClientCapabilities, ClientInfo, Implementation,
+        PaginatedRequestParam, Tool,
+    },
+    service::{Peer, RoleClient, RunningService, ServiceError},
+    transport::{StreamableHttpClientTransport, TokioChildProcess},
+};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "execute")]
+use std::{any::Any, sync::Arc};
+
+/// Handle to an open MCP connection, the client itself lives in the run cache
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct McpConnectionHandle {
+    /// Cache key of the connection in the run cache
+    pub cache_key: String,
+    /// The server URI or command, used in logs and errors
+    pub server: String,
+    pub tools: Vec<McpToolInfo>,
+    pub resources: Vec<McpResourceInfo>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct McpToolInfo {
+    pub name: String,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct McpResourceInfo {
+    pub uri: String,
+    pub name: String,
+    pub description: Option<String>,
+}
+
+/// An open MCP client, dropped (and for stdio servers terminated) when the run ends
+#[cfg(feature = "execute")]
+pub struct CachedMcpClient {
+    pub server: String,
+    pub client: RunningService<RoleClient, ClientInfo>,
+}
+
+#[cfg(feature = "execute")]
+impl CachedMcpClient {
+    pub fn new(server: impl Into<String>, client: RunningService<RoleClient, ClientInfo>) -> Self {
+        CachedMcpClient {
+            server: server.into(),
+            client,
+        }
+    }
+
+    pub fn peer(&self) -> Peer<RoleClient> {
+        self.client.peer().clone()
+    }
+}
+
+#[cfg(feature = "execute")]
+impl Cacheable for CachedMcpClient {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[cfg(feature = "execute")]
+pub fn client_info() -> ClientInfo {
+    ClientInfo {
+        protocol_version: Default::default(),
+        capabilities: ClientCapabilities::default(),
+        client_info: Implementation {
+            name: "Flow-Like".to_string(),
+            version: "alpha".to_string(),
+            title: None,
+            icons: None,
+            website_url: Some("https://flow-like.com".to_string()),
+        },
+    }
+}
+
+/// Connects to a server over streamable HTTP
+#[cfg(feature = "execute")]
+pub async fn connect_http(uri: &str) -> flow_like_types::Result<CachedMcpClient> {
+    let transport = StreamableHttpClientTransport::from_uri(uri);
+    let client = client_info()
+        .serve(transport)
+        .await
+        .map_err(|e| anyhow!("Failed to connect to MCP server {}: {}", uri, e))?;
+    Ok(CachedMcpClient::new(uri, client))
+}
+
+/// Spawns `command` and connects to it over stdio
+#[cfg(feature = "execute")]
+pub async fn connect_stdio(
+    command: &str,
+    args: &[String],
+) -> flow_like_types::Result<CachedMcpClient> {
+    let server = std::iter::once(command)
+        .chain(args.iter().map(String::as_str))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let mut process = flow_like_types::tokio::process::Command::new(command);
+    process.args(args);
+    let transport = TokioChildProcess::new(process)
+        .map_err(|e| anyhow!("Failed to start MCP server `{}`: {}", server, e))?;
+    let client = client_info()
+        .serve(transport)
+        .await
+        .map_err(|e| anyhow!("Failed to connect to MCP server `{}`: {}", server, e))?;
+    Ok(CachedMcpClient::new(server, client))
+}
+
+/// All tools of the server, following pagination
+#[cfg(feature = "execute")]
+pub async fn list_tools(peer: &Peer<RoleClient>) -> Result<Vec<Tool>, ServiceError> {
+    let mut tools = Vec::new();
+    let mut cursor: Option<PaginatedRequestParam> = None;
+    loop {
+        let response = peer.list_tools(cursor).await?;
+        tools.extend(response.tools);
+        match response.next_cursor {
+            Some(next_cursor) => {
+                cursor = Some(PaginatedRequestParam {
+                    cursor: Some(next_cursor),
+                })
+            }
+            None => return Ok(tools),
+        }
+    }
+}
+
+/// All resources of the server. Servers without resource support report none.
+#[cfg(feature = "execute")]
+pub async fn list_resources(peer: &Peer<RoleClient>) -> Vec<McpResourceInfo> {
+    let mut resources = Vec::new();
+    let mut cursor: Option<PaginatedRequestParam> = None;
+    loop {
+        let Ok(response) = peer.list_resources(cursor).await else {
+            return resources;
+        };
+        resources.extend(
+            response
+                .resources
+                .into_iter()
+                .map(|resource| McpResourceInfo {
+                    uri: resource.uri.clone(),
+                    name: resource.name.clone(),
+                    description: resource.description.clone(),
+                }),
+        );
+        match response.next_cursor {
+            Some(next_cursor) => {
+                cursor = Some(PaginatedRequestParam {
+                    cursor: Some(next_cursor),
+                })
+            }
+            None => return resources,
+        }
+    }
+}
+
+/// Why an MCP tool call did not produce a result
+#[derive(Debug, Clone, PartialEq)]
+pub enum McpToolError {
+    /// The connection to the server is gone, further calls will fail as well
+    Disconnected(String),
+    /// The server rejected or failed this call
+    Failed(String),
+}
+
+impl std::fmt::Display for McpToolError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            McpToolError::Disconnected(message) | McpToolError::Failed(message) => {
+                f.write_str(message)
+            }
+        }
+    }
+}
+
+/// Calls a tool on the server and returns the serialized `CallToolResult`
+#[cfg(feature = "execute")]
+pub async fn call_mcp_tool(
+    peer: &Peer<RoleClient>,
+    server: &str,
+    name: &str,
+    arguments: &Value,
+) -> Result<Value, McpToolError> {
+    let request = CallToolRequestParam {
+        name: name.to_string().into(),
+        arguments: arguments.as_object().cloned(),
+        task: None,
+    };
+
+    match peer.call_tool(request).await {
+        Ok(result) => Ok(
+            json::to_value(result).unwrap_or_else(|_| json::json!({"message": "Tool executed"}))
+        ),
+        Err(error @ (ServiceError::TransportClosed | ServiceError::TransportSend(_))) => {
+            Err(McpToolError::Disconnected(format!(
+                "MCP server {} disconnected while calling tool '{}': {}",
+                server, name, error
+            )))
+        }
+        Err(error) => Err(McpToolError::Failed(format!(
+            "MCP tool '{}' on {} failed: {}",
+            name, server, error
+        ))),
+    }
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct McpConnectNode {}
+
+#[async_trait]
+impl NodeLogic for McpConnectNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "mcp_connect",
+            "MCP Connect",
+            "Connects to a Model Context Protocol server and lists its tools and resources. The connection stays open until the run ends.",
+            "AI/Agents/Builder",
+        );
+        node.add_icon("/flow/icons/bot-invoke.svg");
+        node.set_version(1);
+        node.set_scores(
+            NodeScores::new()
+                .set_privacy(5)
+                .set_security(6)
+                .set_performance(7)
+                .set_governance(6)
+                .set_reliability(6)
+                .set_cost(3)
+                .build(),
+        );
+
+        node.add_input_pin("exec_in", "Input", "Trigger", VariableType::Execution);
+        node.add_input_pin(
+            "transport",
+            "Transport",
+            "How to reach the server (HTTP = streamable HTTP/SSE, Stdio = local process)",
+            VariableType::String,
+        )
+        .set_options(
+            PinOptions::new()
+                .set_valid_values(vec!["HTTP".to_string(), "Stdio".to_string()])
+                .build(),
+        )
+        .set_default_value(Some(json::json!("HTTP")));
+        node.add_input_pin(
+            "uri",
+            "Server URI",
+            "URI of the MCP server, used with the HTTP transport",
+            VariableType::String,
+        )
+        .set_default_value(Some(json::json!("")));
+        node.add_input_pin(
+            "command",
+            "Command",
+            "Command that starts the server, used with the Stdio transport (e.g., npx, uvx)",
+            VariableType::String,
+        )
+        .set_default_value(Some(json::json!("")));
+        node.add_input_pin(
+            "args",
+            "Arguments",
+            "Arguments of the command",
+            VariableType::String,
+        )
+        .set_value_type(ValueType::Array)
+        .set_default_value(Some(json::json!([])));
+
+        node.add_output_pin("exec_out", "Output", "Connected", VariableType::Execution);
+        node.add_output_pin(
+            "connection",
+            "Connection",
+            "Handle to the open connection, pass it to Register MCP Tools",
+            VariableType::Struct,
+        )
+        .set_schema::<McpConnectionHandle>()
+        .set_options(PinOptions::new().set_enforce_schema(true).build());
+        node.add_output_pin(
+            "tools",
+            "Tools",
+            "Tools offered by the server",
+            VariableType::Struct,
+        )
+        .set_value_type(ValueType::Array)
+        .set_schema::<McpToolInfo>();
+        node.add_output_pin(
+            "resources",
+            "Resources",
+            "Resources offered by the server",
+            VariableType::Struct,
+        )
+        .set_value_type(ValueType::Array)
+        .set_schema::<McpResourceInfo>();
+
+        node
+    }
+
+    #[cfg(feature = "execute")]
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+
+        let transport: String = context.evaluate_pin("transport").await?;
+        let client = if transport.eq_ignore_ascii_case("Stdio") {
+            let command: String = context.evaluate_pin("command").await?;
+            let args: Vec<String> = context.evaluate_pin("args").await?;
+            if command.trim().is_empty() {
+                return Err(anyhow!("A command is required for the Stdio transport"));
+            }
+            connect_stdio(&command, &args).await?
+        } else {
+            let uri: String = context.evaluate_pin("uri").await?;
+            if uri.trim().is_empty() {
+                return Err(anyhow!("A server URI is required for the HTTP transport"));
+            }
+            connect_http(&uri).await?
+        };
+
+        let peer = client.peer();
+        let tools = list_tools(&peer)
+            .await
+            .map_err(|e| {
+                anyhow!(
+                    "Failed to list tools of MCP server {}: {}",
+                    client.server,
+                    e
+                )
+            })?
+            .into_iter()
+            .map(|tool| McpToolInfo {
+                name: tool.name.to_string(),
+                description: tool.description.map(|description| description.into_owned()),
+            })
+            .collect::<Vec<_>>();
+        let resources = list_resources(&peer).await;
+
+        let handle = McpConnectionHandle {
+            cache_key: format!("mcp_connection_{}", create_id()),
+            server: client.server.clone(),
+            tools,
+            resources,
+        };
+        context.set_cache(&handle.cache_key, Arc::new(client)).await;
+
+        context
+            .set_pin_value("tools", json::json!(handle.tools))
+            .await?;
+        context
+            .set_pin_value("resources", json::json!(handle.resources))
+            .await?;
+        context
+            .set_pin_value("connection", json::json!(handle))
+            .await?;
+        context.activate_exec_pin("exec_out").await?;
+
+        Ok(())
+    }
+
+    #[cfg(not(feature = "execute"))]
+    async fn run(&self, _context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        Err(flow_like_types::anyhow!(
+            "LLM processing requires the 'execute' feature"
+        ))
+    }
+}
+
+#[cfg(all(test, feature = "execute"))]
+mod tests {
+    use super::*;
+    use flow_like::flow::execution::run_cache::{RunCache, get_cached};
+    use flow_like_types::sync::RwLock;
+    use flow_like_types::tokio;
+    use rmcp::{
+        ServerHandler,
+        model::{
+            CallToolResult, Content, ErrorData, ListToolsResult, ServerCapabilities, ServerInfo,
+        },
+        service::{RequestContext, RoleServer},
+    };
+
+    /// Server with a single `add` tool
+    #[derive(Clone)]
+    struct MockServer;
+
+    impl ServerHandler for MockServer {
+        fn get_info(&self) -> ServerInfo {
+            ServerInfo {
+                capabilities: ServerCapabilities::builder().enable_tools().build(),
+                ..Default::default()
+            }
+        }
+
+        async fn list_tools(
+            &self,
+            _request: Option<PaginatedRequestParam>,
+            _context: RequestContext<RoleServer>,
+        ) -> Result<ListToolsResult, ErrorData> {
+            let schema = json::json!({
+                "type": "object",
+                "properties": { "a": { "type": "number" }, "b": { "type": "number" } },
+                "required": ["a", "b"],
+            });
+            let schema = schema.as_object().cloned().unwrap();
+            Ok(ListToolsResult::with_all_items(vec![Tool::new(
+                "add",
+                "Adds two numbers",
+                Arc::new(schema),
+            )]))
+        }
+
+        async fn call_tool(
+            &self,
+            request: CallToolRequestParam,
+            _context: RequestContext<RoleServer>,
+        ) -> Result<CallToolResult, ErrorData> {
+            if request.name != "add" {
+                return Err(ErrorData::invalid_params("unknown tool", None));
+            }
+            let arguments = request.arguments.unwrap_or_default();
+            let number = |key: &str| arguments.get(key).and_then(Value::as_f64).unwrap_or(0.0);
+            let sum = number("a") + number("b");
+            Ok(CallToolResult::success(vec![Content::text(
+                sum.to_string(),
+            )]))
+        }
+    }
+
+    /// Connects a client to a mock server over an in-memory pipe
+    async fn connect_mock() -> (CachedMcpClient, RunningService<RoleServer, MockServer>) {
+        let (client_io, server_io) = tokio::io::duplex(16 * 1024);
+        let server = tokio::spawn(async move { MockServer.serve(server_io).await.unwrap() });
+        let client = client_info().serve(client_io).await.unwrap();
+        (CachedMcpClient::new("mock", client), server.await.unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_tool_round_trips_through_cached_connection() {
+        let (client, _server) = connect_mock().await;
+        let cache: RunCache = Arc::new(RwLock::new(Default::default()));
+        cache
+            .write()
+            .await
+            .insert("mcp".to_string(), Arc::new(client) as Arc<dyn Cacheable>);
+
+        // Another node of the run picks the connection up by its key
+        let client = get_cached::<CachedMcpClient>(&cache, "mcp").await.unwrap();
+        let peer = client.peer();
+
+        let tools = list_tools(&peer).await.unwrap();
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0].name, "add");
+        assert!(list_resources(&peer).await.is_empty());
+
+        let result = call_mcp_tool(&peer, &client.server, "add", &json::json!({"a": 2, "b": 3}))
+            .await
+            .unwrap();
+        assert_eq!(result["content"][0]["text"], json::json!("5"));
+    }
+
+    #[tokio::test]
+    async fn test_tool_errors_are_not_disconnects() {
+        let (client, _server) = connect_mock().await;
+
+        let error = call_mcp_tool(&client.peer(), "mock", "missing", &json::json!({}))
+            .await
+            .unwrap_err();
+        assert!(matches!(error, McpToolError::Failed(_)));
+    }
+
+    #[tokio::test]
+    async fn test_server_disconnect_is_reported() {
+        let (client, server) = connect_mock().await;
+        server.cancel().await.unwrap();
+
+        let error = call_mcp_tool(
+            &client.peer(),
+            "mock",
+            "add",
+            &json::json!({"a": 1, "b": 1}),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(error, McpToolError::Disconnected(_)));
+        assert!(error.to_string().contains("disconnected"));
+    }
+}
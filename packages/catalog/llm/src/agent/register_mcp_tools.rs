@@ -3,7 +3,9 @@
 /// Supports two modes:
 /// - Automatic: Uses all available tools from the MCP server
 /// - Manual: Lets the user enable individual tools via dynamic boolean pins
-use crate::generative::agent::Agent;
+///
+/// Instead of a URI it also accepts a connection opened by "MCP Connect".
+use crate::generative::agent::{Agent, mcp_connect::McpConnectionHandle};
 use flow_like::flow::{
     board::Board,
     execution::context::ExecutionContext,
@@ -13,11 +15,7 @@ use flow_like::flow::{
 };
 use flow_like_types::{async_trait, json, json::from_slice};
 #[cfg(feature = "execute")]
-use rmcp::{
-    ServiceExt,
-    model::{ClientCapabilities, ClientInfo, Implementation, PaginatedRequestParam, Tool},
-    transport::StreamableHttpClientTransport,
-};
+use rmcp::model::Tool;
 use std::{collections::HashSet, sync::Arc};
 
 const TOOL_PIN_PREFIX: &str = "mcp_tool_";
@@ -35,7 +33,11 @@ impl NodeLogic for RegisterMcpToolsNode {
     #[cfg(feature = "execute")]
     async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
         let mut agent: Agent = context.evaluate_pin("agent_in").await?;
-        let uri: String = context.evaluate_pin("uri").await?;
+        let connection: Option<McpConnectionHandle> = context.evaluate_pin("connection").await.ok();
+        let (uri, connection) = match connection {
+            Some(handle) => (handle.server, Some(handle.cache_key)),
+            None => (context.evaluate_pin("uri").await?, None),
+        };
         let mode = context
             .evaluate_pin::<String>("mode")
             .await
@@ -47,7 +49,11 @@ impl NodeLogic for RegisterMcpToolsNode {
             None
         };
 
-        agent.add_mcp_server(super::McpServerConfig { uri, tool_filter });
+        agent.add_mcp_server(super::McpServerConfig {
+            uri,
+            tool_filter,
+            connection,
+        });
 
         context
             .set_pin_value("agent_out", json::json!(agent))
@@ -135,6 +141,7 @@ fn build_register_mcp_tools_node() -> Node {
     let mut node = base_node();
     add_agent_pin(&mut node);
     add_uri_pin(&mut node);
+    add_connection_pin(&mut node);
     add_mode_pin(&mut node);
     add_agent_out_pin(&mut node);
     node
@@ -162,7 +169,7 @@ fn base_node() -> Node {
 }
 
 fn add_agent_pin(node: &mut Node) {
-    node.set_version(1);
+    node.set_version(2);
     node.add_input_pin(
         "agent_in",
         "Agent",
@@ -182,6 +189,17 @@ fn add_uri_pin(node: &mut Node) {
     );
 }
 
+fn add_connection_pin(node: &mut Node) {
+    node.add_input_pin(
+        "connection",
+        "Connection",
+        "Open connection from MCP Connect, used instead of the URI if set",
+        VariableType::Struct,
+    )
+    .set_schema::<McpConnectionHandle>()
+    .set_options(PinOptions::new().set_enforce_schema(true).build());
+}
+
 fn add_mode_pin(node: &mut Node) {
     node.add_input_pin(
         "mode",
@@ -257,45 +275,14 @@ async fn refresh_manual_tool_pins(node: &mut Node, uri: &str) -> Result<(), Stri
 
 #[cfg(feature = "execute")]
 async fn list_all_tools(uri: &str) -> Result<Vec<Tool>, String> {
-    let client_info = ClientInfo {
-        protocol_version: Default::default(),
-        capabilities: ClientCapabilities::default(),
-        client_info: Implementation {
-            name: "Flow-Like".to_string(),
-            version: "alpha".to_string(),
-            title: None,
-            icons: None,
-            website_url: Some("https://flow-like.com".to_string()),
-        },
-    };
+    use crate::generative::agent::mcp_connect;
 
-    let transport = StreamableHttpClientTransport::from_uri(uri);
-    let client = client_info
-        .serve(transport)
+    let client = mcp_connect::connect_http(uri)
         .await
-        .map_err(|error| format!("Failed to connect to MCP server: {}", error))?;
-
-    let mut tools = Vec::new();
-    let mut cursor: Option<PaginatedRequestParam> = None;
-
-    loop {
-        let response = client
-            .list_tools(cursor.clone())
-            .await
-            .map_err(|error| format!("Failed to fetch MCP tools: {}", error))?;
-
-        tools.extend(response.tools);
-
-        if let Some(next_cursor) = response.next_cursor {
-            cursor = Some(PaginatedRequestParam {
-                cursor: Some(next_cursor),
-            });
-        } else {
-            break;
-        }
-    }
-
-    Ok(tools)
+        .map_err(|error| error.to_string())?;
+    mcp_connect::list_tools(&client.peer())
+        .await
+        .map_err(|error| format!("Failed to fetch MCP tools: {}", error))
 }
 
 #[cfg(feature = "execute")]
@@ -1,6 +1,9 @@
+pub mod batch_embed;
+pub mod chunk_and_embed;
 pub mod chunk_text;
 pub mod chunk_text_char;
 pub mod embed_text_document;
 pub mod embed_text_query;
 pub mod embed_texts_document;
 pub mod embed_texts_query;
+pub mod semantic_search;
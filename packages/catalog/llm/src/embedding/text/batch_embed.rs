@@ -0,0 +1,471 @@
+use crate::generative::embedding::{CachedEmbeddingModel, CachedEmbeddingModelObject};
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic, NodeScores},
+    pin::{PinOptions, ValueType},
+    variable::VariableType,
+};
+use flow_like_types::{anyhow, async_trait, bail, json::json, tokio};
+use futures::{Stream, StreamExt};
+use std::{future::Future, ops::Range, time::Duration};
+
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+
+/// Splits `len` inputs into consecutive batches of at most `batch_size`, capped by the
+/// model's own limit
+pub(crate) fn plan_batches(
+    len: usize,
+    batch_size: usize,
+    model_limit: Option<usize>,
+) -> Vec<Range<usize>> {
+    let size = model_limit
+        .map_or(batch_size, |limit| batch_size.min(limit))
+        .max(1);
+    (0..len)
+        .step_by(size)
+        .map(|start| start..(start + size).min(len))
+        .collect()
+}
+
+/// Embeds the batches with at most `concurrency` requests in flight. Items are yielded in
+/// completion order together with the index of their batch. A failing batch is retried
+/// up to `max_retries` times with exponential backoff starting at `base_delay`.
+pub(crate) fn embed_batches<'a, F, Fut>(
+    texts: &'a [String],
+    batches: Vec<Range<usize>>,
+    concurrency: usize,
+    max_retries: u32,
+    base_delay: Duration,
+    embed: &'a F,
+) -> impl Stream<Item = (usize, flow_like_types::Result<Vec<Vec<f32>>>)> + 'a
+where
+    F: Fn(Vec<String>) -> Fut + Sync,
+    Fut: Future<Output = flow_like_types::Result<Vec<Vec<f32>>>> + Send + 'a,
+{
+    futures::stream::iter(batches.into_iter().enumerate())
+        .map(move |(index, range)| async move {
+            let batch = texts[range.clone()].to_vec();
+            let mut attempt = 0;
+            let result = loop {
+                match embed(batch.clone()).await {
+                    Ok(vectors) if vectors.len() == batch.len() => break Ok(vectors),
+                    Ok(vectors) => {
+                        break Err(anyhow!(
+                            "Model returned {} embeddings for {} texts",
+                            vectors.len(),
+                            batch.len()
+                        ));
+                    }
+                    Err(e) if attempt >= max_retries => {
+                        break Err(e.context(format!(
+                            "Batch {} ({}..{}) failed after {} attempts",
+                            index,
+                            range.start,
+                            range.end,
+                            attempt + 1
+                        )));
+                    }
+                    Err(_) => {
+                        tokio::time::sleep(base_delay * 2u32.saturating_pow(attempt)).await;
+                        attempt += 1;
+                    }
+                }
+            };
+            (index, result)
+        })
+        .buffer_unordered(concurrency.max(1))
+}
+
+/// Collects batch results that complete out of order back into input order
+pub(crate) struct OrderedBatches {
+    slots: Vec<Option<Vec<Vec<f32>>>>,
+}
+
+impl OrderedBatches {
+    pub(crate) fn new(batches: usize) -> Self {
+        OrderedBatches {
+            slots: vec![None; batches],
+        }
+    }
+
+    pub(crate) fn insert(&mut self, index: usize, vectors: Vec<Vec<f32>>) {
+        self.slots[index] = Some(vectors);
+    }
+
+    pub(crate) fn into_vectors(self) -> flow_like_types::Result<Vec<Vec<f32>>> {
+        let mut vectors = Vec::new();
+        for (index, slot) in self.slots.into_iter().enumerate() {
+            match slot {
+                Some(batch) => vectors.extend(batch),
+                None => bail!("Batch {} never completed", index),
+            }
+        }
+        Ok(vectors)
+    }
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct BatchEmbedNode {}
+
+impl BatchEmbedNode {
+    pub fn new() -> Self {
+        BatchEmbedNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for BatchEmbedNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "batch_embed",
+            "Batch Embed",
+            "Embeds many texts with batched, concurrent requests. Vectors are returned in the order of the input texts",
+            "AI/Embedding",
+        );
+
+        node.set_scores(
+            NodeScores::new()
+                .set_privacy(6)
+                .set_security(6)
+                .set_performance(6)
+                .set_governance(7)
+                .set_reliability(7)
+                .set_cost(5)
+                .build(),
+        );
+
+        node.set_long_running(true);
+        node.add_icon("/flow/icons/bot-invoke.svg");
+
+        node.add_input_pin(
+            "exec_in",
+            "Input",
+            "Execution trigger",
+            VariableType::Execution,
+        );
+
+        node.add_input_pin("texts", "Texts", "Texts to embed", VariableType::String)
+            .set_value_type(ValueType::Array);
+
+        node.add_input_pin(
+            "model",
+            "Model",
+            "Cached embedding Bit containing the provider",
+            VariableType::Struct,
+        )
+        .set_schema::<CachedEmbeddingModel>()
+        .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node.add_input_pin(
+            "query",
+            "Query",
+            "Embed the texts as search queries instead of documents",
+            VariableType::Boolean,
+        )
+        .set_default_value(Some(json!(false)));
+
+        node.add_input_pin(
+            "batch_size",
+            "Batch Size",
+            "Texts sent to the model per request, capped by the model's own limit",
+            VariableType::Integer,
+        )
+        .set_default_value(Some(json!(32)));
+
+        node.add_input_pin(
+            "concurrency",
+            "Concurrency",
+            "Maximum number of requests in flight",
+            VariableType::Integer,
+        )
+        .set_default_value(Some(json!(4)));
+
+        node.add_input_pin(
+            "max_retries",
+            "Max Retries",
+            "How often a failed batch is retried before the node fails",
+            VariableType::Integer,
+        )
+        .set_default_value(Some(json!(3)));
+
+        node.add_output_pin(
+            "exec_out",
+            "Output",
+            "Fires once all texts are embedded",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "vectors",
+            "Vectors",
+            "One embedding vector per input text, in input order",
+            VariableType::Generic,
+        )
+        .set_value_type(ValueType::Array);
+
+        node.add_output_pin("count", "Count", "Number of vectors", VariableType::Integer);
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+
+        let texts: Vec<String> = context.evaluate_pin("texts").await?;
+        let model: CachedEmbeddingModel = context.evaluate_pin("model").await?;
+        let query: bool = context.evaluate_pin("query").await?;
+        let batch_size: i64 = context.evaluate_pin("batch_size").await?;
+        let concurrency: i64 = context.evaluate_pin("concurrency").await?;
+        let max_retries: i64 = context.evaluate_pin("max_retries").await?;
+
+        let cached_model = context
+            .get_cache(&model.cache_key)
+            .await
+            .ok_or(anyhow!("Model not found in cache"))?;
+        let embedding_model = cached_model
+            .as_any()
+            .downcast_ref::<CachedEmbeddingModelObject>()
+            .ok_or(anyhow!("Failed to Downcast Model"))?;
+
+        let (text_model, image_model) = (
+            embedding_model.text_model.clone(),
+            embedding_model.image_model.clone(),
+        );
+        let model_limit = match (&text_model, &image_model) {
+            (Some(model), _) => model.max_batch_size(),
+            (None, Some(_)) => None,
+            (None, None) => bail!("No model found"),
+        };
+        let embed = move |batch: Vec<String>| {
+            let text_model = text_model.clone();
+            let image_model = image_model.clone();
+            async move {
+                match (text_model, image_model) {
+                    (Some(model), _) if query => model.text_embed_query(&batch).await,
+                    (Some(model), _) => model.text_embed_document(&batch).await,
+                    (None, Some(model)) if query => model.text_embed_query(&batch).await,
+                    (None, Some(model)) => model.text_embed_document(&batch).await,
+                    (None, None) => Err(anyhow!("No model found")),
+                }
+            }
+        };
+
+        let batches = plan_batches(texts.len(), batch_size.max(1) as usize, model_limit);
+        let total = batches.len();
+        let progress_id = context.id.clone();
+        let mut ordered = OrderedBatches::new(total);
+        let mut completed = 0;
+
+        let mut results = embed_batches(
+            &texts,
+            batches,
+            concurrency.max(1) as usize,
+            max_retries.max(0) as u32,
+            RETRY_BASE_DELAY,
+            &embed,
+        );
+        while let Some((index, result)) = results.next().await {
+            let vectors = match result {
+                Ok(vectors) => vectors,
+                Err(e) => {
+                    context
+                        .progress_done(&progress_id, "Embedding failed", false)
+                        .await?;
+                    return Err(e);
+                }
+            };
+            ordered.insert(index, vectors);
+            completed += 1;
+            context
+                .progress_message(
+                    &progress_id,
+                    &format!("Embedded {}/{} batches", completed, total),
+                    Some((completed * 100 / total) as u8),
+                )
+                .await?;
+        }
+        drop(results);
+
+        let vectors = ordered.into_vectors()?;
+        if total > 0 {
+            context
+                .progress_done(&progress_id, "Embedding finished", true)
+                .await?;
+        }
+
+        context
+            .set_pin_value("count", json!(vectors.len() as i64))
+            .await?;
+        context.set_pin_value("vectors", json!(vectors)).await?;
+        context.activate_exec_pin("exec_out").await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    };
+
+    /// Embeds a text as `[index]` where index is parsed from the text, and records how
+    /// many requests were in flight at once
+    #[derive(Default)]
+    struct MockEmbedder {
+        in_flight: AtomicUsize,
+        max_in_flight: AtomicUsize,
+        batches: Mutex<Vec<Vec<String>>>,
+        fail_once: Mutex<Vec<String>>,
+    }
+
+    impl MockEmbedder {
+        async fn embed(&self, batch: Vec<String>) -> flow_like_types::Result<Vec<Vec<f32>>> {
+            let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_in_flight.fetch_max(now, Ordering::SeqCst);
+
+            let first: usize = batch[0].parse().unwrap();
+            // Earlier batches take longer, so they complete after later ones
+            tokio::time::sleep(Duration::from_millis(40u64.saturating_sub(first as u64))).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+
+            {
+                let mut fail_once = self.fail_once.lock().unwrap();
+                if let Some(position) = fail_once.iter().position(|text| *text == batch[0]) {
+                    fail_once.remove(position);
+                    bail!("503 Service Unavailable");
+                }
+            }
+
+            self.batches.lock().unwrap().push(batch.clone());
+            Ok(batch
+                .iter()
+                .map(|text| vec![text.parse::<f32>().unwrap()])
+                .collect())
+        }
+    }
+
+    fn texts(count: usize) -> Vec<String> {
+        (0..count).map(|index| index.to_string()).collect()
+    }
+
+    async fn run(
+        embedder: &Arc<MockEmbedder>,
+        texts: &[String],
+        batch_size: usize,
+        model_limit: Option<usize>,
+        concurrency: usize,
+        max_retries: u32,
+    ) -> flow_like_types::Result<(Vec<Vec<f32>>, Vec<usize>)> {
+        let embed = |batch: Vec<String>| {
+            let embedder = embedder.clone();
+            async move { embedder.embed(batch).await }
+        };
+        let batches = plan_batches(texts.len(), batch_size, model_limit);
+        let mut ordered = OrderedBatches::new(batches.len());
+        let mut completion = Vec::new();
+        let mut results = embed_batches(
+            texts,
+            batches,
+            concurrency,
+            max_retries,
+            Duration::from_millis(1),
+            &embed,
+        );
+        while let Some((index, result)) = results.next().await {
+            completion.push(index);
+            ordered.insert(index, result?);
+        }
+        Ok((ordered.into_vectors()?, completion))
+    }
+
+    #[test]
+    fn test_plan_batches_respects_model_limit() {
+        assert_eq!(plan_batches(10, 4, None), vec![0..4, 4..8, 8..10]);
+        assert_eq!(plan_batches(10, 4, Some(3)), vec![0..3, 3..6, 6..9, 9..10]);
+        assert_eq!(plan_batches(4, 0, None), vec![0..1, 1..2, 2..3, 3..4]);
+        assert!(plan_batches(0, 4, None).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_order_is_preserved_and_concurrency_capped() {
+        let embedder = Arc::new(MockEmbedder::default());
+        let texts = texts(23);
+
+        let (vectors, completion) = run(&embedder, &texts, 5, None, 2, 0).await.unwrap();
+
+        assert!(
+            completion.windows(2).any(|pair| pair[0] > pair[1]),
+            "batches should complete out of order, got {:?}",
+            completion
+        );
+
+        let expected: Vec<Vec<f32>> = (0..23).map(|index| vec![index as f32]).collect();
+        assert_eq!(vectors, expected);
+        assert!(embedder.max_in_flight.load(Ordering::SeqCst) <= 2);
+        assert_eq!(embedder.max_in_flight.load(Ordering::SeqCst), 2);
+
+        let mut sizes: Vec<(String, usize)> = embedder
+            .batches
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|batch| (batch[0].clone(), batch.len()))
+            .collect();
+        sizes.sort_by_key(|(first, _)| first.parse::<usize>().unwrap());
+        let expected_sizes: Vec<(String, usize)> = vec![
+            ("0".to_string(), 5),
+            ("5".to_string(), 5),
+            ("10".to_string(), 5),
+            ("15".to_string(), 5),
+            ("20".to_string(), 3),
+        ];
+        assert_eq!(sizes, expected_sizes);
+    }
+
+    #[tokio::test]
+    async fn test_model_limit_caps_batch_size() {
+        let embedder = Arc::new(MockEmbedder::default());
+        let texts = texts(7);
+
+        let (vectors, _) = run(&embedder, &texts, 32, Some(3), 4, 0).await.unwrap();
+
+        assert_eq!(vectors.len(), 7);
+        let batches = embedder.batches.lock().unwrap();
+        assert_eq!(batches.len(), 3);
+        assert!(batches.iter().all(|batch| batch.len() <= 3));
+    }
+
+    #[tokio::test]
+    async fn test_transient_failure_is_retried_per_batch() {
+        let embedder = Arc::new(MockEmbedder::default());
+        embedder.fail_once.lock().unwrap().push("4".to_string());
+        let texts = texts(8);
+
+        let (vectors, _) = run(&embedder, &texts, 4, None, 2, 1).await.unwrap();
+
+        let expected: Vec<Vec<f32>> = (0..8).map(|index| vec![index as f32]).collect();
+        assert_eq!(vectors, expected);
+        // Only the failed batch was sent again
+        assert_eq!(embedder.batches.lock().unwrap().len(), 2);
+        assert!(embedder.fail_once.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_exhausted_retries_fail() {
+        let embedder = Arc::new(MockEmbedder::default());
+        embedder
+            .fail_once
+            .lock()
+            .unwrap()
+            .extend(["0".to_string(), "0".to_string()]);
+        let texts = texts(2);
+
+        let result = run(&embedder, &texts, 2, None, 1, 1).await;
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,275 @@
+use crate::generative::embedding::{CachedEmbeddingModel, CachedEmbeddingModelObject};
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic, NodeScores},
+    pin::{PinOptions, ValueType},
+    variable::VariableType,
+};
+use flow_like_types::{Value, anyhow, async_trait, bail, json::json, tokio};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// One chunk of a document with its embedding, shaped to be inserted into a vector table.
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq)]
+pub struct EmbeddedChunk {
+    pub text: String,
+    pub embedding: Vec<f32>,
+    /// Position of the chunk in the source text
+    pub chunk_index: usize,
+    /// Metadata of the source document, copied to every chunk
+    pub source_metadata: Value,
+}
+
+pub(crate) fn to_records(
+    chunks: Vec<String>,
+    embeddings: Vec<Vec<f32>>,
+    source_metadata: &Value,
+) -> flow_like_types::Result<Vec<EmbeddedChunk>> {
+    if chunks.len() != embeddings.len() {
+        bail!(
+            "Model returned {} embeddings for {} chunks",
+            embeddings.len(),
+            chunks.len()
+        );
+    }
+
+    Ok(chunks
+        .into_iter()
+        .zip(embeddings)
+        .enumerate()
+        .map(|(chunk_index, (text, embedding))| EmbeddedChunk {
+            text,
+            embedding,
+            chunk_index,
+            source_metadata: source_metadata.clone(),
+        })
+        .collect())
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct ChunkAndEmbedNode {}
+
+impl ChunkAndEmbedNode {
+    pub fn new() -> Self {
+        ChunkAndEmbedNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for ChunkAndEmbedNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "chunk_and_embed",
+            "Chunk & Embed",
+            "Splits text into overlapping chunks and embeds each one. Every record keeps the chunk index and the source metadata, ready to be inserted into a vector table",
+            "AI/Embedding",
+        );
+
+        node.set_scores(
+            NodeScores::new()
+                .set_privacy(6)
+                .set_security(6)
+                .set_performance(6)
+                .set_governance(7)
+                .set_reliability(7)
+                .set_cost(5)
+                .build(),
+        );
+
+        node.set_long_running(true);
+        node.add_icon("/flow/icons/bot-invoke.svg");
+
+        node.add_input_pin(
+            "exec_in",
+            "Input",
+            "Execution trigger",
+            VariableType::Execution,
+        );
+
+        node.add_input_pin(
+            "text",
+            "Text",
+            "Document text to chunk and embed",
+            VariableType::String,
+        );
+
+        node.add_input_pin(
+            "model",
+            "Model",
+            "Cached embedding Bit providing the splitter and the embeddings",
+            VariableType::Struct,
+        )
+        .set_schema::<CachedEmbeddingModel>()
+        .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node.add_input_pin(
+            "source_metadata",
+            "Source Metadata",
+            "Copied to every record, e.g. the file path, title or URL of the document",
+            VariableType::Struct,
+        )
+        .set_default_value(Some(json!({})));
+
+        node.add_input_pin(
+            "capacity",
+            "Capacity",
+            "Max characters/tokens in each chunk",
+            VariableType::Integer,
+        )
+        .set_default_value(Some(json!(512)));
+
+        node.add_input_pin(
+            "overlap",
+            "Overlap",
+            "How many characters/tokens overlap between consecutive chunks",
+            VariableType::Integer,
+        )
+        .set_default_value(Some(json!(20)));
+
+        node.add_input_pin(
+            "markdown",
+            "Markdown",
+            "Use a Markdown-aware splitter (true) or the plain splitter",
+            VariableType::Boolean,
+        )
+        .set_default_value(Some(json!(true)));
+
+        node.add_input_pin(
+            "batch_size",
+            "Batch Size",
+            "Chunks sent to the model per request",
+            VariableType::Integer,
+        )
+        .set_default_value(Some(json!(32)));
+
+        node.add_output_pin(
+            "exec_out",
+            "Output",
+            "Fires once all chunks are embedded",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "records",
+            "Records",
+            "One record per chunk with text, embedding, chunk_index and source_metadata",
+            VariableType::Struct,
+        )
+        .set_schema::<EmbeddedChunk>()
+        .set_value_type(ValueType::Array);
+
+        node.add_output_pin("count", "Count", "Number of chunks", VariableType::Integer);
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+
+        let text: String = context.evaluate_pin("text").await?;
+        let model: CachedEmbeddingModel = context.evaluate_pin("model").await?;
+        let source_metadata: Value = context.evaluate_pin("source_metadata").await?;
+        let capacity: i64 = context.evaluate_pin("capacity").await?;
+        let overlap: i64 = context.evaluate_pin("overlap").await?;
+        let markdown: bool = context.evaluate_pin("markdown").await?;
+        let batch_size: i64 = context.evaluate_pin("batch_size").await?;
+
+        if overlap >= capacity {
+            bail!(
+                "Overlap ({}) must be smaller than the capacity ({})",
+                overlap,
+                capacity
+            );
+        }
+
+        let cached_model = context
+            .get_cache(&model.cache_key)
+            .await
+            .ok_or(anyhow!("Model not found in cache"))?;
+        let embedding_model = cached_model
+            .as_any()
+            .downcast_ref::<CachedEmbeddingModelObject>()
+            .ok_or(anyhow!("Failed to Downcast Model"))?;
+
+        let capacity = Some(capacity.max(1) as usize);
+        let overlap = Some(overlap.max(0) as usize);
+        let (text_splitter, md_splitter) =
+            if let Some(text_model) = embedding_model.text_model.clone() {
+                text_model.get_splitter(capacity, overlap).await?
+            } else if let Some(image_model) = embedding_model.image_model.clone() {
+                image_model.get_splitter(capacity, overlap).await?
+            } else {
+                return Err(anyhow!("No model found"));
+            };
+
+        let chunks = tokio::task::spawn_blocking(move || {
+            if markdown {
+                md_splitter.chunks(&text)
+            } else {
+                text_splitter.chunks(&text)
+            }
+        })
+        .await
+        .map_err(|e| anyhow!("Blocking task failed: {}", e))??;
+
+        let mut embeddings = Vec::with_capacity(chunks.len());
+        for batch in chunks.chunks(batch_size.max(1) as usize) {
+            let batch = batch.to_vec();
+            let vectors = if let Some(text_model) = &embedding_model.text_model {
+                text_model.text_embed_document(&batch).await?
+            } else if let Some(image_model) = &embedding_model.image_model {
+                image_model.text_embed_document(&batch).await?
+            } else {
+                return Err(anyhow!("No model found"));
+            };
+            embeddings.extend(vectors);
+        }
+
+        let records = to_records(chunks, embeddings, &source_metadata)?;
+
+        context
+            .set_pin_value("count", json!(records.len() as i64))
+            .await?;
+        context.set_pin_value("records", json!(records)).await?;
+        context.activate_exec_pin("exec_out").await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_records_keep_order_and_metadata() {
+        let metadata = json!({ "path": "docs/intro.md", "title": "Intro" });
+        let records = to_records(
+            vec!["first".to_string(), "second".to_string()],
+            vec![vec![0.1, 0.2], vec![0.3, 0.4]],
+            &metadata,
+        )
+        .unwrap();
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].chunk_index, 1);
+        assert_eq!(records[1].text, "second");
+        assert_eq!(records[1].embedding, vec![0.3, 0.4]);
+        assert!(
+            records
+                .iter()
+                .all(|record| record.source_metadata == metadata)
+        );
+
+        let value = json!(records[0]);
+        assert_eq!(value["source_metadata"]["path"], "docs/intro.md");
+        assert_eq!(value["chunk_index"], 0);
+    }
+
+    #[test]
+    fn test_mismatched_embeddings_fail() {
+        let result = to_records(vec!["only".to_string()], vec![], &json!({}));
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,332 @@
+use crate::generative::embedding::{CachedEmbeddingModel, CachedEmbeddingModelObject};
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic, NodeScores},
+    pin::{PinOptions, ValueType},
+    variable::VariableType,
+};
+use flow_like_catalog_data::data::db::vector::NodeDBConnection;
+use flow_like_types::{Value, anyhow, async_trait, bail, json::json};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Columns LanceDB adds to search results.
+const SEARCH_COLUMNS: [&str; 4] = ["_distance", "_relevance_score", "_score", "_rowid"];
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq)]
+pub struct SemanticSearchResult {
+    /// Cosine similarity for vector search, RRF relevance for hybrid search. Higher is better
+    pub score: f64,
+    /// The stored row without its vector columns
+    pub record: Value,
+}
+
+/// Picks the vector column the query embedding fits. A requested column has to match the
+/// dimension, otherwise the only column with that dimension is used.
+pub(crate) fn pick_vector_column(
+    columns: &[(String, usize)],
+    requested: &str,
+    dimension: usize,
+) -> flow_like_types::Result<String> {
+    let described = || {
+        columns
+            .iter()
+            .map(|(name, size)| format!("{} ({})", name, size))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    if !requested.is_empty() {
+        return match columns.iter().find(|(name, _)| name == requested) {
+            Some((_, size)) if *size == dimension => Ok(requested.to_string()),
+            Some((_, size)) => Err(anyhow!(
+                "Column \"{}\" holds {} dimensional vectors but the model produces {}, the table was embedded with a different model",
+                requested,
+                size,
+                dimension
+            )),
+            None => Err(anyhow!(
+                "Table has no vector column \"{}\", found: {}",
+                requested,
+                described()
+            )),
+        };
+    }
+
+    let matching: Vec<&String> = columns
+        .iter()
+        .filter(|(_, size)| *size == dimension)
+        .map(|(name, _)| name)
+        .collect();
+    match matching.as_slice() {
+        [column] => Ok(column.to_string()),
+        [] if columns.is_empty() => Err(anyhow!("Table has no vector column")),
+        [] => Err(anyhow!(
+            "No vector column matches the {} dimensions of the model, found: {}",
+            dimension,
+            described()
+        )),
+        _ => Err(anyhow!(
+            "Several vector columns have {} dimensions, set the vector column: {}",
+            dimension,
+            described()
+        )),
+    }
+}
+
+pub(crate) fn to_results(
+    rows: Vec<Value>,
+    vector_columns: &[(String, usize)],
+    hybrid: bool,
+) -> Vec<SemanticSearchResult> {
+    rows.into_iter()
+        .map(|mut row| {
+            let score = if hybrid {
+                row.get("_relevance_score").and_then(Value::as_f64)
+            } else {
+                row.get("_distance")
+                    .and_then(Value::as_f64)
+                    .map(|distance| 1.0 - distance)
+            };
+
+            if let Some(record) = row.as_object_mut() {
+                for column in SEARCH_COLUMNS {
+                    record.remove(column);
+                }
+                for (column, _) in vector_columns {
+                    record.remove(column);
+                }
+            }
+
+            SemanticSearchResult {
+                score: score.unwrap_or_default(),
+                record: row,
+            }
+        })
+        .collect()
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct SemanticSearchNode {}
+
+impl SemanticSearchNode {
+    pub fn new() -> Self {
+        SemanticSearchNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for SemanticSearchNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "semantic_search",
+            "Semantic Search",
+            "Embeds a query and returns the most similar rows of a vector table with their scores. The vector column is chosen by the dimension of the model, hybrid search adds full text matches",
+            "AI/Embedding",
+        );
+
+        node.set_scores(
+            NodeScores::new()
+                .set_privacy(6)
+                .set_security(6)
+                .set_performance(7)
+                .set_governance(7)
+                .set_reliability(7)
+                .set_cost(7)
+                .build(),
+        );
+
+        node.set_long_running(true);
+        node.add_icon("/flow/icons/bot-invoke.svg");
+
+        node.add_input_pin(
+            "exec_in",
+            "Input",
+            "Execution trigger",
+            VariableType::Execution,
+        );
+
+        node.add_input_pin("query", "Query", "What to search for", VariableType::String)
+            .set_default_value(Some(json!("")));
+
+        node.add_input_pin(
+            "model",
+            "Model",
+            "Cached embedding Bit the table was embedded with",
+            VariableType::Struct,
+        )
+        .set_schema::<CachedEmbeddingModel>()
+        .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node.add_input_pin(
+            "database",
+            "Database",
+            "Database Connection Reference",
+            VariableType::Struct,
+        )
+        .set_schema::<NodeDBConnection>()
+        .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node.add_input_pin("top_k", "Top K", "Number of results", VariableType::Integer)
+            .set_default_value(Some(json!(5)));
+
+        node.add_input_pin(
+            "vector_column",
+            "Vector Column",
+            "Column to search, empty to pick the one matching the model",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("")));
+
+        node.add_input_pin(
+            "hybrid",
+            "Hybrid",
+            "Combine with full text search, requires a full text index",
+            VariableType::Boolean,
+        )
+        .set_default_value(Some(json!(false)));
+
+        node.add_input_pin(
+            "filter",
+            "SQL Filter",
+            "Optional SQL Filter",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("")));
+
+        node.add_output_pin(
+            "exec_out",
+            "Output",
+            "Fires when the search completes",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "results",
+            "Results",
+            "Best matches first, each with its score and stored columns",
+            VariableType::Struct,
+        )
+        .set_schema::<SemanticSearchResult>()
+        .set_value_type(ValueType::Array);
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+
+        let query: String = context.evaluate_pin("query").await?;
+        let model: CachedEmbeddingModel = context.evaluate_pin("model").await?;
+        let database: NodeDBConnection = context.evaluate_pin("database").await?;
+        let top_k: i64 = context.evaluate_pin("top_k").await?;
+        let vector_column: String = context.evaluate_pin("vector_column").await?;
+        let hybrid: bool = context.evaluate_pin("hybrid").await?;
+        let filter: String = context.evaluate_pin("filter").await?;
+
+        if query.trim().is_empty() {
+            bail!("Query is empty");
+        }
+
+        let cached_model = context
+            .get_cache(&model.cache_key)
+            .await
+            .ok_or(anyhow!("Model not found in cache"))?;
+        let embedding_model = cached_model
+            .as_any()
+            .downcast_ref::<CachedEmbeddingModelObject>()
+            .ok_or(anyhow!("Failed to Downcast Model"))?;
+
+        let queries = vec![query.clone()];
+        let embeddings = if let Some(text_model) = &embedding_model.text_model {
+            text_model.text_embed_query(&queries).await?
+        } else if let Some(image_model) = &embedding_model.image_model {
+            image_model.text_embed_query(&queries).await?
+        } else {
+            return Err(anyhow!("No model found"));
+        };
+        let embedding = embeddings
+            .into_iter()
+            .next()
+            .ok_or(anyhow!("Failed to embed the query"))?;
+
+        let database = database.load(context).await?.db.clone();
+        let database = database.read().await;
+        let columns = database.vector_columns().await?;
+        let column = pick_vector_column(&columns, vector_column.trim(), embedding.len())?;
+
+        let vector = embedding.into_iter().map(f64::from).collect();
+        let filter = (!filter.is_empty()).then_some(filter.as_str());
+        let text = hybrid.then_some(query.as_str());
+        let rows = database
+            .search_column(vector, &column, text, filter, top_k.max(1) as usize)
+            .await?;
+
+        let results = to_results(rows, &columns, hybrid);
+        context.set_pin_value("results", json!(results)).await?;
+        context.activate_exec_pin("exec_out").await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn columns() -> Vec<(String, usize)> {
+        vec![
+            ("title_vector".to_string(), 384),
+            ("vector".to_string(), 768),
+            ("image_vector".to_string(), 768),
+        ]
+    }
+
+    #[test]
+    fn test_pick_column_by_dimension() {
+        assert_eq!(
+            pick_vector_column(&columns(), "", 384).unwrap(),
+            "title_vector"
+        );
+        assert_eq!(
+            pick_vector_column(&columns(), "vector", 768).unwrap(),
+            "vector"
+        );
+
+        let ambiguous = pick_vector_column(&columns(), "", 768).unwrap_err();
+        assert!(ambiguous.to_string().contains("Several"));
+
+        let mismatch = pick_vector_column(&columns(), "vector", 384).unwrap_err();
+        assert!(mismatch.to_string().contains("different model"));
+
+        assert!(pick_vector_column(&columns(), "", 1536).is_err());
+        assert!(pick_vector_column(&columns(), "missing", 384).is_err());
+        assert!(pick_vector_column(&[], "", 384).is_err());
+    }
+
+    #[test]
+    fn test_results_strip_vectors_and_keep_metadata() {
+        let rows = vec![
+            json!({ "text": "a", "source": "doc.md", "vector": [0.1], "_distance": 0.25 }),
+            json!({ "text": "b", "source": "doc.md", "vector": [0.2], "_distance": 0.5 }),
+        ];
+        let results = to_results(rows, &[("vector".to_string(), 1)], false);
+
+        assert_eq!(results[0].score, 0.75);
+        assert_eq!(results[1].score, 0.5);
+        assert_eq!(
+            results[0].record,
+            json!({ "text": "a", "source": "doc.md" })
+        );
+    }
+
+    #[test]
+    fn test_hybrid_results_use_relevance() {
+        let rows = vec![json!({ "text": "a", "_relevance_score": 0.032, "_distance": 0.9 })];
+        let results = to_results(rows, &[], true);
+
+        assert_eq!(results[0].score, 0.032);
+        assert_eq!(results[0].record, json!({ "text": "a" }));
+    }
+}
@@ -12,5 +12,5 @@ pub mod generative;
 pub use generative::*;
 
 pub fn get_catalog() -> Vec<Arc<dyn NodeLogic>> {
-    flow_like_catalog_core::get_catalog()
+    flow_like_catalog_core::get_package_catalog(env!("CARGO_PKG_NAME"))
 }
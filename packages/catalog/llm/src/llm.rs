@@ -1,5 +1,8 @@
 pub mod add_headers;
 pub mod branch;
+pub mod conversation_memory;
+pub mod count_tokens;
+pub mod extract_entities;
 pub mod find_llm;
 pub mod history;
 pub mod invoke;
@@ -9,4 +12,7 @@ pub mod llm_extractor;
 pub mod llm_extractor_history;
 pub mod preferences;
 pub mod provider;
+pub mod provider_override;
+pub mod rerank;
 pub mod response;
+pub mod structured_output;
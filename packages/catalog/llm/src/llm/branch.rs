@@ -3,6 +3,7 @@
 /// Useful to route a execution flow between two possible downstream branches.
 /// Node execution might fail if the LLM-output cannot be parsed according to the decision data schema.
 use crate::generative::llm::invoke_with_tools::extract_tagged;
+use crate::generative::llm::provider_override::{add_provider_override_pins, evaluate_model};
 use flow_like::{
     bit::Bit,
     flow::{
@@ -137,6 +138,8 @@ impl NodeLogic for LLMBranchNode {
             VariableType::String,
         );
 
+        add_provider_override_pins(&mut node);
+
         node.add_output_pin(
             "true",
             "True",
@@ -160,7 +163,7 @@ impl NodeLogic for LLMBranchNode {
         context.deactivate_exec_pin("false").await?;
 
         // fetch inputs
-        let model = context.evaluate_pin::<Bit>("model").await?;
+        let model = evaluate_model(context).await?;
         let prompt: String = context.evaluate_pin::<String>("prompt").await?;
         let mut model_name = model.id.clone();
         if let Some(meta) = model.meta.get("en") {
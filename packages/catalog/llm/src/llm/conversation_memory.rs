@@ -0,0 +1,453 @@
+use flow_like::{
+    bit::Bit,
+    flow::{
+        execution::{LogLevel, context::ExecutionContext},
+        node::{Node, NodeLogic, NodeScores},
+        pin::PinOptions,
+        variable::VariableType,
+    },
+};
+use flow_like_catalog_core::FlowPath;
+use flow_like_model_provider::{
+    history::{History, HistoryMessage, Role},
+    tokenizer::estimate_token_count,
+};
+use flow_like_storage::{Path, object_store};
+use flow_like_types::{
+    async_trait, bail,
+    json::{self, json},
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Share of the budget reserved for the summary of older turns.
+const SUMMARY_SHARE: usize = 4;
+
+/// Persisted state of one conversation.
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, Default)]
+pub struct ConversationMemory {
+    /// Condensed content of the turns that no longer fit the budget
+    pub summary: String,
+    /// Turns kept verbatim, oldest first
+    pub turns: Vec<HistoryMessage>,
+}
+
+fn role_name(role: &Role) -> &'static str {
+    match role {
+        Role::System => "system",
+        Role::User => "user",
+        Role::Assistant => "assistant",
+        Role::Function => "function",
+        Role::Tool => "tool",
+    }
+}
+
+/// Index of the first turn kept verbatim. The newest `keep_recent` turns always stay,
+/// older ones stay as long as everything kept fits into `budget`.
+pub(crate) fn verbatim_start(turn_tokens: &[usize], budget: usize, keep_recent: usize) -> usize {
+    let mut used = 0;
+    let mut start = turn_tokens.len();
+    while start > 0 {
+        let tokens = turn_tokens[start - 1];
+        let kept = turn_tokens.len() - start;
+        if kept >= keep_recent && used + tokens > budget {
+            break;
+        }
+        used += tokens;
+        start -= 1;
+    }
+    start
+}
+
+pub(crate) fn summary_prompt(summary: &str, turns: &[HistoryMessage]) -> String {
+    let transcript = turns
+        .iter()
+        .map(|turn| format!("{}: {}", role_name(&turn.role), turn.as_str()))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    if summary.is_empty() {
+        format!("Conversation:\n{}", transcript)
+    } else {
+        format!(
+            "Summary so far:\n{}\n\nConversation since then:\n{}",
+            summary, transcript
+        )
+    }
+}
+
+/// The system prompt with the summary appended, followed by the verbatim turns.
+pub(crate) fn build_history(
+    model: String,
+    system_prompt: &str,
+    memory: &ConversationMemory,
+) -> History {
+    let mut history = History::new(model, memory.turns.clone());
+
+    let system_prompt = match (system_prompt.is_empty(), memory.summary.is_empty()) {
+        (_, true) => system_prompt.to_string(),
+        (true, false) => format!("Summary of the earlier conversation:\n{}", memory.summary),
+        (false, false) => format!(
+            "{}\n\nSummary of the earlier conversation:\n{}",
+            system_prompt, memory.summary
+        ),
+    };
+    if !system_prompt.is_empty() {
+        history.set_system_prompt(system_prompt);
+    }
+
+    history
+}
+
+fn count_history(history: &History) -> usize {
+    history
+        .messages
+        .iter()
+        .map(|message| estimate_token_count(&message.as_str()))
+        .sum()
+}
+
+async fn memory_path(
+    context: &mut ExecutionContext,
+    conversation_id: &str,
+) -> flow_like_types::Result<FlowPath> {
+    let storage = FlowPath::from_storage_dir(context, false).await?;
+    // `child` escapes the id, so arbitrary ids cannot leave the memory directory
+    let path = Path::from(storage.path.as_str())
+        .child("conversation_memory")
+        .child(format!("{}.json", conversation_id));
+    Ok(FlowPath::new(
+        path.to_string(),
+        storage.store_ref,
+        storage.cache_store_ref,
+    ))
+}
+
+async fn read_memory(
+    path: &FlowPath,
+    context: &mut ExecutionContext,
+) -> flow_like_types::Result<ConversationMemory> {
+    let store = path.to_store(context).await?;
+    match store
+        .as_generic()
+        .get(&Path::from(path.path.as_str()))
+        .await
+    {
+        Ok(result) => Ok(json::from_slice(&result.bytes().await?)?),
+        Err(object_store::Error::NotFound { .. }) => Ok(ConversationMemory::default()),
+        Err(error) => Err(error.into()),
+    }
+}
+
+async fn summarize(
+    context: &mut ExecutionContext,
+    model: &Bit,
+    summary: &str,
+    turns: &[HistoryMessage],
+    max_tokens: usize,
+) -> flow_like_types::Result<String> {
+    let model_factory = context.app_state.model_factory.clone();
+    let llm = model_factory
+        .lock()
+        .await
+        .build(model, context.app_state.clone(), context.token.clone())
+        .await?;
+
+    let mut history = History::new(model.id.clone(), vec![]);
+    history.set_system_prompt(format!(
+        "You maintain the memory of a conversation. Merge the summary so far and the new messages into one concise summary. Keep facts, decisions, names, numbers, open questions and stated preferences, drop small talk. Answer with the summary only, in at most {} words.",
+        max_tokens * 3 / 4
+    ));
+    history.push_message(HistoryMessage::from_string(
+        Role::User,
+        &summary_prompt(summary, turns),
+    ));
+
+    let response = llm.invoke(&history, None).await?;
+    let summary = response
+        .last_message()
+        .and_then(|message| message.content.clone())
+        .unwrap_or_default();
+    if summary.trim().is_empty() {
+        bail!("The model returned an empty summary");
+    }
+
+    Ok(summary.trim().to_string())
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct ConversationMemoryNode {}
+
+impl ConversationMemoryNode {
+    pub fn new() -> Self {
+        ConversationMemoryNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for ConversationMemoryNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "ai_generative_conversation_memory",
+            "Conversation Memory",
+            "Stores the turns of a conversation and returns a history that fits the token budget. Once the budget is exceeded, older turns are summarized by the model while recent ones stay verbatim. The memory persists per conversation id",
+            "AI/Generative/History",
+        );
+        node.add_icon("/flow/icons/history.svg");
+
+        node.set_scores(
+            NodeScores::new()
+                .set_privacy(5)
+                .set_security(6)
+                .set_performance(6)
+                .set_governance(6)
+                .set_reliability(7)
+                .set_cost(6)
+                .build(),
+        );
+
+        node.add_input_pin(
+            "exec_in",
+            "Input",
+            "Initiate Execution",
+            VariableType::Execution,
+        );
+
+        node.add_input_pin(
+            "conversation_id",
+            "Conversation ID",
+            "Identifies the conversation whose memory is used",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("")));
+
+        node.add_input_pin("role", "Role", "Role of the new turn", VariableType::String)
+            .set_options(
+                PinOptions::new()
+                    .set_valid_values(vec!["User".to_string(), "Assistant".to_string()])
+                    .build(),
+            )
+            .set_default_value(Some(json!("User")));
+
+        node.add_input_pin(
+            "content",
+            "Content",
+            "New turn to store, leave empty to only read the memory",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("")));
+
+        node.add_input_pin(
+            "model",
+            "Model",
+            "Model that summarizes older turns",
+            VariableType::Struct,
+        )
+        .set_schema::<Bit>()
+        .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node.add_input_pin(
+            "system_prompt",
+            "System Prompt",
+            "Instructions placed before the summary",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("")));
+
+        node.add_input_pin(
+            "token_budget",
+            "Token Budget",
+            "Estimated tokens the returned history may use",
+            VariableType::Integer,
+        )
+        .set_options(PinOptions::new().set_range((256., 1_000_000.)).build())
+        .set_default_value(Some(json!(4000)));
+
+        node.add_input_pin(
+            "keep_recent",
+            "Keep Recent",
+            "Newest turns that are never summarized",
+            VariableType::Integer,
+        )
+        .set_default_value(Some(json!(4)));
+
+        node.add_output_pin(
+            "exec_out",
+            "Output",
+            "Done with the Execution",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "history",
+            "History",
+            "System prompt with the summary, followed by the verbatim turns",
+            VariableType::Struct,
+        )
+        .set_schema::<History>();
+
+        node.add_output_pin(
+            "summary",
+            "Summary",
+            "Summary of the turns that no longer fit",
+            VariableType::String,
+        );
+
+        node.add_output_pin(
+            "summarized",
+            "Summarized",
+            "Whether older turns were summarized in this run",
+            VariableType::Boolean,
+        );
+
+        node.add_output_pin(
+            "tokens",
+            "Tokens",
+            "Estimated tokens of the returned history",
+            VariableType::Integer,
+        );
+
+        node.set_long_running(true);
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+
+        let conversation_id: String = context.evaluate_pin("conversation_id").await?;
+        let role: String = context.evaluate_pin("role").await?;
+        let content: String = context.evaluate_pin("content").await?;
+        let model: Bit = context.evaluate_pin("model").await?;
+        let system_prompt: String = context.evaluate_pin("system_prompt").await?;
+        let token_budget: i64 = context.evaluate_pin("token_budget").await?;
+        let keep_recent: i64 = context.evaluate_pin("keep_recent").await?;
+
+        let conversation_id = conversation_id.trim();
+        if conversation_id.is_empty() {
+            bail!("Conversation ID is empty");
+        }
+        let token_budget = token_budget.max(1) as usize;
+
+        let path = memory_path(context, conversation_id).await?;
+        let mut memory = read_memory(&path, context).await?;
+
+        let changed = !content.is_empty();
+        if changed {
+            let role = match role.as_str() {
+                "Assistant" => Role::Assistant,
+                _ => Role::User,
+            };
+            memory
+                .turns
+                .push(HistoryMessage::from_string(role, &content));
+        }
+
+        let mut summarized = false;
+        let mut history = build_history(model.id.clone(), &system_prompt, &memory);
+        if count_history(&history) > token_budget {
+            let summary_budget = token_budget / SUMMARY_SHARE;
+            let fixed = estimate_token_count(&system_prompt) + summary_budget;
+            let turn_tokens: Vec<usize> = memory
+                .turns
+                .iter()
+                .map(|turn| estimate_token_count(&turn.as_str()))
+                .collect();
+            let start = verbatim_start(
+                &turn_tokens,
+                token_budget.saturating_sub(fixed),
+                keep_recent.max(0) as usize,
+            );
+
+            if start > 0 {
+                let summary = summarize(
+                    context,
+                    &model,
+                    &memory.summary,
+                    &memory.turns[..start],
+                    summary_budget,
+                )
+                .await?;
+                memory.summary = summary;
+                memory.turns.drain(..start);
+                summarized = true;
+                history = build_history(model.id.clone(), &system_prompt, &memory);
+            } else {
+                context.log_message(
+                    "The most recent turns alone exceed the token budget",
+                    LogLevel::Warn,
+                );
+            }
+        }
+
+        if changed || summarized {
+            path.put(context, json::to_vec(&memory)?, true).await?;
+        }
+
+        context
+            .set_pin_value("tokens", json!(count_history(&history)))
+            .await?;
+        context
+            .set_pin_value("summary", json!(memory.summary))
+            .await?;
+        context
+            .set_pin_value("summarized", json!(summarized))
+            .await?;
+        context.set_pin_value("history", json!(history)).await?;
+        context.activate_exec_pin("exec_out").await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verbatim_start() {
+        let tokens = [100, 100, 100, 100, 100];
+        assert_eq!(verbatim_start(&tokens, 1000, 2), 0);
+        assert_eq!(verbatim_start(&tokens, 250, 2), 3);
+        assert_eq!(verbatim_start(&tokens, 250, 1), 3);
+        // Recent turns stay even if they alone exceed the budget
+        assert_eq!(verbatim_start(&tokens, 50, 2), 3);
+        assert_eq!(verbatim_start(&tokens, 50, 0), 5);
+        assert_eq!(verbatim_start(&[], 50, 2), 0);
+    }
+
+    #[test]
+    fn test_summary_prompt() {
+        let turns = vec![
+            HistoryMessage::from_string(Role::User, "My name is Ada"),
+            HistoryMessage::from_string(Role::Assistant, "Hi Ada"),
+        ];
+        assert_eq!(
+            summary_prompt("", &turns),
+            "Conversation:\nuser: My name is Ada\nassistant: Hi Ada"
+        );
+        assert!(
+            summary_prompt("Ada likes tea", &turns).starts_with("Summary so far:\nAda likes tea")
+        );
+    }
+
+    #[test]
+    fn test_build_history() {
+        let memory = ConversationMemory {
+            summary: "Ada likes tea".to_string(),
+            turns: vec![HistoryMessage::from_string(Role::User, "What do I like?")],
+        };
+
+        let history = build_history("model".to_string(), "Be brief", &memory);
+        assert_eq!(history.messages.len(), 2);
+        assert_eq!(
+            history.get_system_prompt().unwrap(),
+            "Be brief\n\nSummary of the earlier conversation:\nAda likes tea"
+        );
+        assert_eq!(history.messages[1].as_str(), "What do I like?");
+
+        let empty = build_history("model".to_string(), "", &ConversationMemory::default());
+        assert!(empty.messages.is_empty());
+    }
+}
@@ -0,0 +1,238 @@
+use flow_like::{
+    bit::{Bit, BitTypes},
+    flow::{
+        execution::{LogLevel, context::ExecutionContext},
+        node::{Node, NodeLogic, NodeScores},
+        pin::PinOptions,
+        variable::VariableType,
+    },
+    state::FlowLikeState,
+};
+use flow_like_model_provider::{
+    history::{History, Role},
+    tokenizer::{TokenCounter, cached_token_counter, estimate_token_count, tiktoken_counter},
+};
+use flow_like_storage::files::store::FlowLikeStore;
+use flow_like_types::{async_trait, json::json};
+use std::sync::Arc;
+
+/// Formatting overhead per chat message and for priming the reply, as used by OpenAI chat models.
+const TOKENS_PER_MESSAGE: usize = 3;
+const TOKENS_FOR_REPLY: usize = 3;
+
+fn role_name(role: &Role) -> &'static str {
+    match role {
+        Role::System => "system",
+        Role::User => "user",
+        Role::Assistant => "assistant",
+        Role::Function => "function",
+        Role::Tool => "tool",
+    }
+}
+
+fn count_history(history: &History, count: &impl Fn(&str) -> usize) -> usize {
+    let messages: usize = history
+        .messages
+        .iter()
+        .map(|message| {
+            TOKENS_PER_MESSAGE + count(role_name(&message.role)) + count(&message.as_str())
+        })
+        .sum();
+
+    if history.messages.is_empty() {
+        0
+    } else {
+        messages + TOKENS_FOR_REPLY
+    }
+}
+
+/// Loads the Hugging Face tokenizer shipped as a dependency of a local model Bit.
+async fn bit_token_counter(bit: &Bit, state: Arc<FlowLikeState>) -> Option<TokenCounter> {
+    if bit.dependencies.is_empty() {
+        return None;
+    }
+
+    let pack = bit.dependencies(state.clone()).await.ok()?;
+    let tokenizer_bit = pack
+        .bits
+        .iter()
+        .find(|bit| bit.bit_type == BitTypes::Tokenizer)?;
+
+    let FlowLikeStore::Local(store) = FlowLikeState::bit_store(&state).await.ok()? else {
+        return None;
+    };
+    let path = tokenizer_bit.to_path(&store)?;
+
+    cached_token_counter(&format!("bit:{}", tokenizer_bit.hash), || {
+        let bytes = std::fs::read(path).ok()?;
+        TokenCounter::from_tokenizer_bytes(&bytes).ok()
+    })
+}
+
+fn model_id(bit: &Bit) -> String {
+    bit.try_to_provider()
+        .and_then(|provider| provider.model_id)
+        .unwrap_or_else(|| bit.id.clone())
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct CountTokensNode {}
+
+impl CountTokensNode {
+    pub fn new() -> Self {
+        CountTokensNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for CountTokensNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "ai_generative_count_tokens",
+            "Count Tokens",
+            "Counts how many tokens a text or chat history uses for a model. Falls back to an estimate if the model's tokenizer is unknown",
+            "AI/Generative",
+        );
+        node.add_icon("/flow/icons/calculator.svg");
+
+        node.set_scores(
+            NodeScores::new()
+                .set_privacy(10)
+                .set_security(10)
+                .set_performance(8)
+                .set_governance(9)
+                .set_reliability(8)
+                .set_cost(10)
+                .build(),
+        );
+
+        node.add_input_pin(
+            "exec_in",
+            "Input",
+            "Initiate Execution",
+            VariableType::Execution,
+        );
+
+        node.add_input_pin(
+            "model",
+            "Model",
+            "Model whose tokenizer is used",
+            VariableType::Struct,
+        )
+        .set_schema::<Bit>()
+        .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node.add_input_pin("text", "Text", "Text to count", VariableType::String)
+            .set_default_value(Some(json!("")));
+
+        node.add_input_pin(
+            "history",
+            "History",
+            "Optional chat history, counted including the per-message overhead",
+            VariableType::Struct,
+        )
+        .set_schema::<History>()
+        .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node.add_output_pin(
+            "exec_out",
+            "Output",
+            "Done with the Execution",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "tokens",
+            "Tokens",
+            "Number of tokens",
+            VariableType::Integer,
+        );
+
+        node.add_output_pin(
+            "exact",
+            "Exact",
+            "False if the count is an estimate because no tokenizer was found",
+            VariableType::Boolean,
+        );
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+
+        let model: Bit = context.evaluate_pin("model").await?;
+        let text: String = context.evaluate_pin("text").await.unwrap_or_default();
+        let history: Option<History> = context.evaluate_pin("history").await.ok();
+
+        let model_id = model_id(&model);
+        let counter = match bit_token_counter(&model, context.app_state.clone()).await {
+            Some(counter) => Some(counter),
+            None => tiktoken_counter(&model_id),
+        };
+
+        if counter.is_none() {
+            context.log_message(
+                &format!(
+                    "No tokenizer available for model '{}', estimating about four characters per token",
+                    model_id
+                ),
+                LogLevel::Warn,
+            );
+        }
+
+        let count = |text: &str| match &counter {
+            Some(counter) => counter.count(text),
+            None => estimate_token_count(text),
+        };
+
+        let mut tokens = count(&text);
+        if let Some(history) = &history {
+            tokens += count_history(history, &count);
+        }
+
+        context.set_pin_value("tokens", json!(tokens)).await?;
+        context
+            .set_pin_value("exact", json!(counter.is_some()))
+            .await?;
+        context.activate_exec_pin("exec_out").await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flow_like_model_provider::history::HistoryMessage;
+
+    #[test]
+    fn test_history_counts_include_message_overhead() {
+        let history = History::new(
+            "gpt-4".to_string(),
+            vec![
+                HistoryMessage::from_string(Role::System, "You are helpful."),
+                HistoryMessage::from_string(Role::User, "hello world"),
+            ],
+        );
+        let counter = tiktoken_counter("gpt-4").unwrap();
+
+        // system(1) + "You are helpful."(4) + user(1) + "hello world"(2)
+        let expected = 2 * TOKENS_PER_MESSAGE + 1 + 4 + 1 + 2 + TOKENS_FOR_REPLY;
+        assert_eq!(
+            count_history(&history, &|text: &str| counter.count(text)),
+            expected
+        );
+
+        let empty = History::new("gpt-4".to_string(), vec![]);
+        assert_eq!(count_history(&empty, &estimate_token_count), 0);
+    }
+
+    #[test]
+    fn test_model_id_falls_back_to_bit_id() {
+        let mut bit = Bit::default();
+        bit.id = "bit-id".to_string();
+        assert_eq!(model_id(&bit), "bit-id");
+    }
+}
@@ -0,0 +1,292 @@
+use super::structured_output::{DEFAULT_MAX_ATTEMPTS, StructuredOutput, resolve_schema};
+use flow_like::{
+    bit::Bit,
+    flow::{
+        board::Board,
+        execution::{LogLevel, context::ExecutionContext},
+        node::{Node, NodeLogic, NodeScores},
+        pin::{PinOptions, ValueType},
+        variable::VariableType,
+    },
+};
+use flow_like_model_provider::history::{History, HistoryMessage, Role};
+use flow_like_types::{
+    Value, anyhow, async_trait,
+    json::{self, json},
+};
+use std::sync::Arc;
+
+const EXTRACTION_PROMPT: &str = "You are an information extraction assistant. Find every entity in the text that fits the record schema and return them in the `entities` array, in order of appearance. Only use information stated in the text, leave out fields that are not mentioned and return an empty array if nothing matches.";
+
+/// Wraps the schema of one record, structured output needs an object at the root.
+pub(crate) fn entities_schema(record: &Value) -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "entities": {
+                "type": "array",
+                "items": record
+            }
+        },
+        "required": ["entities"]
+    })
+}
+
+pub(crate) fn extraction_prompt(text: &str, hint: &str) -> String {
+    if hint.trim().is_empty() {
+        format!("Text:\n{}", text)
+    } else {
+        format!("Extraction hint: {}\n\nText:\n{}", hint.trim(), text)
+    }
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct ExtractEntitiesNode {}
+
+impl ExtractEntitiesNode {
+    pub fn new() -> Self {
+        ExtractEntitiesNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for ExtractEntitiesNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "ai_extract_entities",
+            "Extract Entities",
+            "Extracts every record matching a JSON Schema from free-form text, e.g. company, amount and date of each invoice. Uses the structured output mode of the model where available and validates each record against the schema",
+            "AI/Generative",
+        );
+        node.add_icon("/flow/icons/bot-invoke.svg");
+
+        node.set_scores(
+            NodeScores::new()
+                .set_privacy(4)
+                .set_security(4)
+                .set_performance(6)
+                .set_governance(6)
+                .set_reliability(7)
+                .set_cost(4)
+                .build(),
+        );
+
+        node.add_input_pin(
+            "exec_in",
+            "Input",
+            "Execution trigger to start the extraction",
+            VariableType::Execution,
+        );
+
+        node.add_input_pin(
+            "model",
+            "Model",
+            "Bit pointing to the LLM that performs the extraction",
+            VariableType::Struct,
+        )
+        .set_schema::<Bit>()
+        .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node.add_input_pin(
+            "schema",
+            "Schema",
+            "JSON Schema of a single record",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!(
+            json::to_string_pretty(&json!({
+                "type": "object",
+                "properties": {
+                    "company": { "type": "string" },
+                    "amount": { "type": "number" },
+                    "date": { "type": "string", "format": "date" }
+                },
+                "required": ["company", "amount"]
+            }))
+            .unwrap_or_default()
+        )));
+
+        node.add_input_pin(
+            "text",
+            "Text",
+            "Text to extract the records from",
+            VariableType::String,
+        );
+
+        node.add_input_pin(
+            "hint",
+            "Extraction Hint",
+            "Optional guidance, e.g. 'only the invoice totals, not the line items'",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("")));
+
+        node.add_input_pin(
+            "max_attempts",
+            "Max Attempts",
+            "How often the model may retry if its reply does not match the schema",
+            VariableType::Integer,
+        )
+        .set_options(PinOptions::new().set_range((1., 10.)).build())
+        .set_default_value(Some(json!(DEFAULT_MAX_ATTEMPTS)));
+
+        node.add_output_pin(
+            "exec_out",
+            "Output",
+            "Executes after the extraction succeeded",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "entities",
+            "Entities",
+            "Extracted records, each valid against the schema",
+            VariableType::Struct,
+        )
+        .set_value_type(ValueType::Array);
+
+        node.add_output_pin(
+            "count",
+            "Count",
+            "Number of extracted records",
+            VariableType::Integer,
+        );
+
+        node.set_long_running(true);
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+
+        let model_bit: Bit = context.evaluate_pin("model").await?;
+        let schema: Value = context.evaluate_pin("schema").await?;
+        let text: String = context.evaluate_pin("text").await?;
+        let hint: String = context.evaluate_pin("hint").await.unwrap_or_default();
+        let max_attempts = context
+            .evaluate_pin::<i64>("max_attempts")
+            .await
+            .map(|attempts| attempts.clamp(1, 10) as u32)
+            .unwrap_or(DEFAULT_MAX_ATTEMPTS);
+
+        let record_schema =
+            resolve_schema(schema, None)?.ok_or_else(|| anyhow!("Schema input cannot be empty"))?;
+        let structured =
+            StructuredOutput::for_model(entities_schema(&record_schema), &model_bit, max_attempts);
+
+        let model_factory = context.app_state.model_factory.clone();
+        let model = model_factory
+            .lock()
+            .await
+            .build(&model_bit, context.app_state.clone(), context.token.clone())
+            .await?;
+
+        let mut history = History::new(model_bit.id.clone(), vec![]);
+        history.set_system_prompt(EXTRACTION_PROMPT.to_string());
+        history.push_message(HistoryMessage::from_string(
+            Role::User,
+            &extraction_prompt(&text, &hint),
+        ));
+
+        let result = structured
+            .invoke(&history, |history| {
+                let model = model.clone();
+                async move { model.invoke(&history, None).await }
+            })
+            .await?;
+
+        if let Some(error) = result.error {
+            return Err(anyhow!(error));
+        }
+        let entities = result
+            .value
+            .and_then(|mut value| value.get_mut("entities").map(Value::take))
+            .unwrap_or_else(|| json!([]));
+        let count = entities.as_array().map_or(0, Vec::len);
+
+        context.log_message(
+            &format!(
+                "Extracted {} record(s) in {} attempt(s)",
+                count, result.attempts
+            ),
+            LogLevel::Debug,
+        );
+
+        context.set_pin_value("count", json!(count)).await?;
+        context.set_pin_value("entities", entities).await?;
+        context.activate_exec_pin("exec_out").await?;
+        Ok(())
+    }
+
+    async fn on_update(&self, node: &mut Node, _board: Arc<Board>) {
+        node.error = None;
+
+        let schema = node
+            .get_pin_by_name("schema")
+            .and_then(|pin| pin.default_value.as_ref())
+            .and_then(|bytes| json::from_slice::<Value>(bytes).ok())
+            .unwrap_or_default();
+
+        match resolve_schema(schema, None) {
+            Ok(Some(schema)) => {
+                if let Some(pin) = node.get_pin_mut_by_name("entities") {
+                    pin.schema = json::to_string(&schema).ok();
+                }
+            }
+            Ok(None) => node.error = Some("Schema input cannot be empty".to_string()),
+            Err(err) => node.error = Some(format!("Schema error: {}", err)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn invoice() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "company": { "type": "string" },
+                "amount": { "type": "number" }
+            },
+            "required": ["company", "amount"]
+        })
+    }
+
+    #[test]
+    fn test_entities_schema_wraps_record() {
+        let schema = entities_schema(&invoice());
+        assert_eq!(schema["type"], "object");
+        assert_eq!(schema["properties"]["entities"]["items"], invoice());
+        assert_eq!(schema["required"], json!(["entities"]));
+    }
+
+    #[test]
+    fn test_extraction_prompt() {
+        assert_eq!(extraction_prompt("ACME, 10 EUR", ""), "Text:\nACME, 10 EUR");
+        assert_eq!(
+            extraction_prompt("ACME, 10 EUR", " totals only "),
+            "Extraction hint: totals only\n\nText:\nACME, 10 EUR"
+        );
+    }
+
+    #[cfg(feature = "execute")]
+    #[test]
+    fn test_records_are_validated() {
+        let structured = StructuredOutput::new(entities_schema(&invoice()), false, 1);
+
+        let valid = structured.validate(
+            r#"{"entities": [{"company": "ACME", "amount": 120.5}, {"company": "Globex", "amount": 80}]}"#,
+        );
+        assert_eq!(valid.unwrap()["entities"][1]["company"], "Globex");
+
+        let missing_amount = structured
+            .validate(r#"{"entities": [{"company": "ACME", "amount": 1}, {"company": "Globex"}]}"#);
+        assert!(missing_amount.is_err());
+
+        assert!(structured.validate(r#"{"entities": []}"#).is_ok());
+    }
+}
@@ -1,7 +1,12 @@
+use crate::generative::llm::{
+    provider_override::{add_provider_override_pins, evaluate_model},
+    structured_output::{DEFAULT_MAX_ATTEMPTS, StructuredOutput, resolve_schema},
+};
 use ahash::AHashSet;
 use flow_like::{
     bit::Bit,
     flow::{
+        board::Board,
         execution::{
             LogLevel,
             context::ExecutionContext,
@@ -17,7 +22,7 @@ use flow_like_model_provider::{
     history::History, llm::LLMCallback, response::Response, response_chunk::ResponseChunk,
 };
 use flow_like_types::{
-    async_trait,
+    Value, async_trait,
     json::json,
     sync::{DashMap, Mutex},
 };
@@ -70,6 +75,25 @@ impl NodeLogic for InvokeLLM {
             .set_schema::<History>()
             .set_options(PinOptions::new().set_enforce_schema(true).build());
 
+        add_provider_override_pins(&mut node);
+
+        node.add_input_pin(
+            "response_format",
+            "Response Format",
+            "JSON Schema (or a connected struct) the reply has to match. Leave empty for plain text",
+            VariableType::Generic,
+        )
+        .set_default_value(Some(json!("")));
+
+        node.add_input_pin(
+            "max_attempts",
+            "Max Attempts",
+            "How often the model may repair a reply that does not match the response format",
+            VariableType::Integer,
+        )
+        .set_options(PinOptions::new().set_range((1., 10.)).build())
+        .set_default_value(Some(json!(DEFAULT_MAX_ATTEMPTS)));
+
         node.add_output_pin(
             "on_stream",
             "On Stream",
@@ -92,6 +116,27 @@ impl NodeLogic for InvokeLLM {
         .set_schema::<Response>()
         .set_options(PinOptions::new().set_enforce_schema(true).build());
 
+        node.add_output_pin(
+            "structured",
+            "Structured",
+            "Parsed reply matching the response format",
+            VariableType::Struct,
+        );
+
+        node.add_output_pin(
+            "error",
+            "Error",
+            "Triggers if the reply does not match the response format",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "error_message",
+            "Error Message",
+            "Why the reply did not match the response format",
+            VariableType::String,
+        );
+
         node.set_long_running(true);
 
         node
@@ -99,12 +144,34 @@ impl NodeLogic for InvokeLLM {
 
     async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
         context.deactivate_exec_pin("done").await?;
-        let model = context.evaluate_pin::<Bit>("model").await?;
+        context.deactivate_exec_pin("error").await?;
+        let model = evaluate_model(context).await?;
         let mut model_name = model.id.clone();
         if let Some(meta) = model.meta.get("en") {
             model_name = meta.name.clone();
         }
         let history = context.evaluate_pin::<History>("history").await?;
+
+        let response_format: Value = context
+            .evaluate_pin("response_format")
+            .await
+            .unwrap_or_default();
+        let struct_schema = context
+            .node
+            .node
+            .lock()
+            .await
+            .get_pin_by_name("response_format")
+            .filter(|pin| pin.data_type == VariableType::Struct)
+            .and_then(|pin| pin.schema.clone());
+        let max_attempts = context
+            .evaluate_pin::<i64>("max_attempts")
+            .await
+            .map(|attempts| attempts.clamp(1, 10) as u32)
+            .unwrap_or(DEFAULT_MAX_ATTEMPTS);
+        let structured = resolve_schema(response_format, struct_schema.as_deref())?
+            .map(|schema| StructuredOutput::for_model(schema, &model, max_attempts));
+
         let model_factory = context.app_state.model_factory.clone();
         let model = model_factory
             .lock()
@@ -180,7 +247,19 @@ impl NodeLogic for InvokeLLM {
             None,
         );
 
-        let res = model.invoke(&history, Some(callback)).await?;
+        let (res, structured_result) = match &structured {
+            Some(structured) => {
+                let result = structured
+                    .invoke(&history, |history| {
+                        let model = model.clone();
+                        let callback = callback.clone();
+                        async move { model.invoke(&history, Some(callback)).await }
+                    })
+                    .await?;
+                (result.response.clone(), Some(result))
+            }
+            None => (model.invoke(&history, Some(callback)).await?, None),
+        };
 
         message.end();
         message.put_stats(LogStat::new(
@@ -198,8 +277,44 @@ impl NodeLogic for InvokeLLM {
 
         context.set_pin_value("result", json!(res)).await?;
         context.deactivate_exec_pin("on_stream").await?;
+
+        if let Some(result) = structured_result {
+            if let Some(error) = result.error {
+                context.log_message(&error, LogLevel::Warn);
+                context.set_pin_value("error_message", json!(error)).await?;
+                context.activate_exec_pin("error").await?;
+                return Ok(());
+            }
+
+            context
+                .set_pin_value("structured", result.value.unwrap_or_default())
+                .await?;
+        }
+
         context.activate_exec_pin("done").await?;
 
         return Ok(());
     }
+
+    async fn on_update(&self, node: &mut Node, board: Arc<Board>) {
+        let match_type = node
+            .match_type("response_format", board, None, None)
+            .unwrap_or(VariableType::Generic);
+
+        // Without a struct connection the pin takes the schema as text
+        if match_type != VariableType::String
+            && match_type != VariableType::Struct
+            && let Some(pin) = node.get_pin_mut_by_name("response_format")
+        {
+            pin.depends_on.clear();
+            pin.data_type = VariableType::String;
+        }
+
+        let schema = node
+            .get_pin_by_name("response_format")
+            .and_then(|pin| pin.schema.clone());
+        if let Some(pin) = node.get_pin_mut_by_name("structured") {
+            pin.schema = schema;
+        }
+    }
 }
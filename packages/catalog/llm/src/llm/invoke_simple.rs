@@ -1,3 +1,4 @@
+use crate::generative::llm::provider_override::{add_provider_override_pins, evaluate_model};
 use ahash::AHashSet;
 use flow_like::{
     bit::Bit,
@@ -9,25 +10,110 @@ use flow_like::{
             log::{LogMessage, LogStat},
         },
         node::{Node, NodeLogic, NodeScores},
-        pin::PinOptions,
+        pin::{PinOptions, ValueType},
         variable::VariableType,
     },
 };
+use flow_like_catalog_core::{FlowPath, NodeImage};
 use flow_like_model_provider::{
-    history::{History, HistoryMessage, Role},
+    history::{Content, ContentType, History, HistoryMessage, ImageUrl, MessageContent, Role},
     llm::LLMCallback,
     response_chunk::ResponseChunk,
 };
 use flow_like_types::{
-    async_trait,
+    async_trait, bail,
+    image::{self, DynamicImage},
     json::json,
     sync::{DashMap, Mutex},
+    utils::{data_url::image_to_data_url, img::resize_image},
 };
+use serde::Deserialize;
 use std::sync::{
     Arc,
     atomic::{AtomicUsize, Ordering},
 };
 
+/// An image attached to the prompt, either already loaded in the run or a file in a store
+#[derive(Deserialize, Debug, Clone)]
+#[serde(untagged)]
+enum PromptImage {
+    Image(NodeImage),
+    Path(FlowPath),
+}
+
+impl PromptImage {
+    async fn load(&self, context: &mut ExecutionContext) -> flow_like_types::Result<DynamicImage> {
+        match self {
+            PromptImage::Image(image) => {
+                let image = image.get_image(context).await?;
+                let image = image.lock().await.clone();
+                Ok(image)
+            }
+            PromptImage::Path(path) => {
+                let bytes = path.get(context, false).await?;
+                image::load_from_memory(&bytes).map_err(|e| {
+                    flow_like_types::anyhow!("Failed to decode image '{}': {}", path.path, e)
+                })
+            }
+        }
+    }
+}
+
+/// Scales the image down to what the provider processes for the given detail level and
+/// re-encodes it as PNG (if it has transparency) or JPEG, which every vision provider accepts
+pub(crate) async fn encode_prompt_image(
+    image: &DynamicImage,
+    detail: &str,
+) -> flow_like_types::Result<String> {
+    let max_dimension = if detail == "low" { 512 } else { 2048 };
+    let image = resize_image(image, max_dimension).await;
+    if image.color().has_alpha() {
+        return image_to_data_url(&image, image::ImageFormat::Png).await;
+    }
+    let image = DynamicImage::ImageRgb8(image.to_rgb8());
+    image_to_data_url(&image, image::ImageFormat::Jpeg).await
+}
+
+/// The history for a single system + user turn. Images are attached to the user message
+/// and require a vision-capable model.
+pub(crate) fn build_history(
+    model: &Bit,
+    model_name: &str,
+    system_prompt: &str,
+    prompt: &str,
+    images: Vec<String>,
+    detail: &str,
+) -> flow_like_types::Result<History> {
+    if !images.is_empty() && !model.is_multimodal() {
+        bail!(
+            "Model '{}' does not support image input. Select a vision-capable model or remove the {} attached image(s)",
+            model_name,
+            images.len()
+        );
+    }
+
+    let mut message = HistoryMessage::from_string(Role::User, prompt);
+    if !images.is_empty() {
+        let mut content = vec![Content::Text {
+            content_type: ContentType::Text,
+            text: prompt.to_string(),
+        }];
+        content.extend(images.into_iter().map(|url| Content::Image {
+            content_type: ContentType::ImageUrl,
+            image_url: ImageUrl {
+                url,
+                detail: Some(detail.to_string()),
+            },
+        }));
+        message.content = MessageContent::Contents(content);
+    }
+
+    let mut history = History::new(model_name.to_string(), vec![]);
+    history.set_system_prompt(system_prompt.to_string());
+    history.push_message(message);
+    Ok(history)
+}
+
 #[crate::register_node]
 #[derive(Default)]
 pub struct InvokeLLMSimpleNode {}
@@ -92,6 +178,34 @@ impl NodeLogic for InvokeLLMSimpleNode {
         )
         .set_default_value(Some(json!("")));
 
+        node.add_input_pin(
+            "images",
+            "Images",
+            "Optional images (Image or Path) attached to the prompt, requires a vision-capable model",
+            VariableType::Struct,
+        )
+        .set_value_type(ValueType::Array)
+        .set_default_value(Some(json!([])));
+
+        node.add_input_pin(
+            "detail",
+            "Image Detail",
+            "How closely the model looks at the images. Low is faster and cheaper",
+            VariableType::String,
+        )
+        .set_options(
+            PinOptions::new()
+                .set_valid_values(vec![
+                    "auto".to_string(),
+                    "low".to_string(),
+                    "high".to_string(),
+                ])
+                .build(),
+        )
+        .set_default_value(Some(json!("auto")));
+
+        add_provider_override_pins(&mut node);
+
         node.add_output_pin(
             "on_stream",
             "On Stream",
@@ -127,13 +241,30 @@ impl NodeLogic for InvokeLLMSimpleNode {
 
     async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
         context.deactivate_exec_pin("done").await?;
-        let model = context.evaluate_pin::<Bit>("model").await?;
+        let model = evaluate_model(context).await?;
         let mut model_name = model.id.clone();
         if let Some(meta) = model.meta.get("en") {
             model_name = meta.name.clone();
         }
         let system_prompt = context.evaluate_pin::<String>("system_prompt").await?;
         let prompt = context.evaluate_pin::<String>("prompt").await?;
+        let images = context.evaluate_pin::<Vec<PromptImage>>("images").await?;
+        let detail = context.evaluate_pin::<String>("detail").await?;
+
+        let mut image_urls = Vec::with_capacity(images.len());
+        for image in &images {
+            let image = image.load(context).await?;
+            image_urls.push(encode_prompt_image(&image, &detail).await?);
+        }
+        let history = build_history(
+            &model,
+            &model_name,
+            &system_prompt,
+            &prompt,
+            image_urls,
+            &detail,
+        )?;
+
         let model_factory = context.app_state.model_factory.clone();
         let model = model_factory
             .lock()
@@ -141,10 +272,6 @@ impl NodeLogic for InvokeLLMSimpleNode {
             .build(&model, context.app_state.clone(), context.token.clone())
             .await?;
 
-        let mut history = History::new(model_name.clone(), vec![]);
-        history.set_system_prompt(system_prompt.clone());
-        history.push_message(HistoryMessage::from_string(Role::User, &prompt));
-
         let on_stream = context.get_pin_by_name("on_stream").await?;
         context.activate_exec_pin_ref(&on_stream).await?;
 
@@ -243,3 +370,138 @@ impl NodeLogic for InvokeLLMSimpleNode {
         return Ok(());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flow_like::bit::BitTypes;
+    use flow_like_model_provider::{
+        llm::{ModelConstructor, ModelLogic},
+        response::Response,
+    };
+    use flow_like_types::{
+        base64::{Engine as _, engine::general_purpose::STANDARD},
+        image::{GenericImageView, Rgb, RgbImage, Rgba, RgbaImage},
+        tokio,
+    };
+
+    /// Records the history it is invoked with instead of calling a provider
+    #[derive(Default)]
+    struct MockProvider {
+        invoked_with: std::sync::Mutex<Option<History>>,
+    }
+
+    #[async_trait]
+    impl ModelLogic for MockProvider {
+        async fn provider(&self) -> flow_like_types::Result<ModelConstructor> {
+            bail!("the mock provider has no client")
+        }
+
+        async fn default_model(&self) -> Option<String> {
+            None
+        }
+
+        async fn invoke(
+            &self,
+            history: &History,
+            _lambda: Option<LLMCallback>,
+        ) -> flow_like_types::Result<Response> {
+            *self.invoked_with.lock().unwrap() = Some(history.clone());
+            Ok(Response::new())
+        }
+    }
+
+    fn model(bit_type: BitTypes) -> Bit {
+        let mut bit = Bit::default();
+        bit.id = "model".to_string();
+        bit.bit_type = bit_type;
+        bit
+    }
+
+    fn decode(data_url: &str) -> DynamicImage {
+        let payload = data_url.split(',').next_back().unwrap();
+        image::load_from_memory(&STANDARD.decode(payload).unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_images_are_attached_to_the_user_message() {
+        let photo = DynamicImage::ImageRgb8(RgbImage::from_pixel(3000, 1500, Rgb([10, 20, 30])));
+        let url = encode_prompt_image(&photo, "high").await.unwrap();
+        let history = build_history(
+            &model(BitTypes::Vlm),
+            "vision",
+            "You describe images",
+            "What is in this picture?",
+            vec![url],
+            "high",
+        )
+        .unwrap();
+
+        let provider = MockProvider::default();
+        provider.invoke(&history, None).await.unwrap();
+
+        let sent = provider.invoked_with.lock().unwrap().take().unwrap();
+        let user = sent
+            .messages
+            .iter()
+            .find(|message| message.role == Role::User)
+            .unwrap();
+        let MessageContent::Contents(content) = &user.content else {
+            panic!("expected structured content");
+        };
+        assert_eq!(content.len(), 2);
+        assert!(matches!(
+            &content[0],
+            Content::Text { text, .. } if text == "What is in this picture?"
+        ));
+        let Content::Image { image_url, .. } = &content[1] else {
+            panic!("expected image content");
+        };
+        assert!(image_url.url.starts_with("data:image/jpeg;base64,"));
+        assert_eq!(image_url.detail.as_deref(), Some("high"));
+        assert_eq!(decode(&image_url.url).dimensions(), (2048, 1024));
+    }
+
+    #[tokio::test]
+    async fn test_transparent_images_stay_png() {
+        let icon = DynamicImage::ImageRgba8(RgbaImage::from_pixel(1024, 1024, Rgba([0, 0, 0, 0])));
+        let url = encode_prompt_image(&icon, "low").await.unwrap();
+
+        assert!(url.starts_with("data:image/png;base64,"));
+        assert_eq!(decode(&url).dimensions(), (512, 512));
+    }
+
+    #[test]
+    fn test_non_vision_model_rejects_images() {
+        let error = build_history(
+            &model(BitTypes::Llm),
+            "text-only",
+            "",
+            "Describe this",
+            vec!["data:image/png;base64,AAAA".to_string()],
+            "auto",
+        )
+        .unwrap_err()
+        .to_string();
+
+        assert!(error.contains("'text-only' does not support image input"));
+        assert!(error.contains("1 attached image"));
+    }
+
+    #[test]
+    fn test_text_only_prompt_works_with_any_model() {
+        let history = build_history(
+            &model(BitTypes::Llm),
+            "text-only",
+            "",
+            "Hello",
+            vec![],
+            "auto",
+        )
+        .unwrap();
+
+        let user = history.messages.last().unwrap();
+        assert_eq!(user.role, Role::User);
+        assert!(matches!(&user.content, MessageContent::Contents(content) if content.len() == 1));
+    }
+}
@@ -1,3 +1,4 @@
+use crate::generative::llm::provider_override::{add_provider_override_pins, evaluate_model};
 use flow_like::{
     bit::Bit,
     flow::{
@@ -258,6 +259,8 @@ impl NodeLogic for InvokeLLMWithToolsNode {
         )
         .set_default_value(Some(json::json!("Auto")));
 
+        add_provider_override_pins(&mut node);
+
         node.add_output_pin(
             "exec_done",
             "Done",
@@ -289,7 +292,7 @@ impl NodeLogic for InvokeLLMWithToolsNode {
     async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
         context.deactivate_exec_pin("exec_done").await?;
 
-        let model_bit = context.evaluate_pin::<Bit>("model").await?;
+        let model_bit = evaluate_model(context).await?;
         let tools_str: String = context.evaluate_pin("tools").await?;
         let tool_choice: String = context.evaluate_pin("tool_choice").await?;
         let tool_choice = match tool_choice.as_str() {
@@ -111,6 +111,7 @@ impl NodeLogic for BuildOllamaNode {
         let params_obj = VLMParameters {
             context_length: 20000,
             model_classification: BitModelClassification::default(),
+            pricing: None,
             provider: flow_like_model_provider::provider::ModelProvider {
                 provider_name: "custom:ollama".into(),
                 model_id: Some(model_id),
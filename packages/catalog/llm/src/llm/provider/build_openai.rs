@@ -145,6 +145,7 @@ impl NodeLogic for BuildOpenAiNode {
         let params = VLMParameters {
             context_length: 20000,
             model_classification: BitModelClassification::default(),
+            pricing: None,
             provider: flow_like_model_provider::provider::ModelProvider {
                 provider_name: "custom:openai".into(),
                 model_id: Some(
@@ -124,6 +124,7 @@ impl NodeLogic for BuildPerplexityNode {
         let params_obj = VLMParameters {
             context_length: 20000,
             model_classification: BitModelClassification::default(),
+            pricing: None,
             provider: flow_like_model_provider::provider::ModelProvider {
                 provider_name: "custom:perplexity".into(),
                 model_id: Some(model_id),
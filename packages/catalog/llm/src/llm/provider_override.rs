@@ -0,0 +1,368 @@
+//! Per-invocation provider override for the generative nodes.
+//!
+//! The override rewrites the model [`Bit`] into a `custom:<provider>` Bit before it is
+//! handed to the model factory, so the profile's provider selection is bypassed for a
+//! single node while model construction and caching still go through the factory.
+
+use super::add_headers::HttpHeader;
+use flow_like::{
+    bit::Bit,
+    flow::{
+        execution::context::ExecutionContext,
+        node::Node,
+        pin::{PinOptions, ValueType},
+        variable::VariableType,
+    },
+};
+use flow_like_model_provider::provider::ModelProvider;
+use flow_like_storage::blake3;
+use flow_like_types::{
+    anyhow,
+    json::{Map, from_value, json, to_value},
+};
+
+/// Value of the provider pin that keeps the provider family of the incoming model.
+pub const KEEP_PROVIDER: &str = "Model Default";
+
+const OVERRIDE_PROVIDERS: [&str; 18] = [
+    "openai",
+    "anthropic",
+    "gemini",
+    "groq",
+    "cohere",
+    "perplexity",
+    "xai",
+    "deepseek",
+    "mistral",
+    "ollama",
+    "huggingface",
+    "together",
+    "openrouter",
+    "voyageai",
+    "hyperbolic",
+    "moonshot",
+    "galadriel",
+    "mira",
+];
+
+#[derive(Clone, Debug, Default)]
+pub struct ProviderOverride {
+    pub provider: String,
+    pub base_url: String,
+    pub api_key: String,
+    pub headers: Vec<HttpHeader>,
+}
+
+impl ProviderOverride {
+    pub fn is_empty(&self) -> bool {
+        self.provider_name().is_none()
+            && self.base_url.trim().is_empty()
+            && self.api_key.is_empty()
+            && self.active_headers().next().is_none()
+    }
+
+    fn provider_name(&self) -> Option<String> {
+        let provider = self.provider.trim();
+        if provider.is_empty() || provider == KEEP_PROVIDER {
+            return None;
+        }
+        Some(provider.trim_start_matches("custom:").to_lowercase())
+    }
+
+    fn active_headers(&self) -> impl Iterator<Item = &HttpHeader> {
+        self.headers
+            .iter()
+            .filter(|header| !header.name.is_empty() && !header.value.is_empty())
+    }
+
+    /// Returns a copy of `bit` that the model factory resolves through its custom
+    /// provider path. Returns the Bit unchanged if no override is set.
+    pub fn apply(&self, bit: &Bit) -> flow_like_types::Result<Bit> {
+        if self.is_empty() {
+            return Ok(bit.clone());
+        }
+
+        let mut provider: ModelProvider = bit
+            .parameters
+            .get("provider")
+            .cloned()
+            .map(from_value::<ModelProvider>)
+            .transpose()?
+            .ok_or_else(|| anyhow!("Model does not define a provider that can be overridden"))?;
+
+        let provider_name = match self.provider_name() {
+            Some(name) => name,
+            None => base_provider_name(&provider.provider_name)?,
+        };
+
+        if !OVERRIDE_PROVIDERS.contains(&provider_name.as_str()) {
+            return Err(anyhow!(
+                "Provider '{}' can not be used as an override",
+                provider_name
+            ));
+        }
+
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(bit.id.as_bytes());
+        hasher.update(provider_name.as_bytes());
+
+        let mut params = provider.params.take().unwrap_or_default();
+
+        if !params.contains_key("model_id") {
+            let model_id = provider.model_id.clone().unwrap_or_else(|| bit.id.clone());
+            params.insert("model_id".to_string(), json!(model_id));
+        }
+
+        let base_url = self.base_url.trim();
+        if !base_url.is_empty() {
+            params.insert("endpoint".to_string(), json!(base_url));
+            hasher.update(base_url.as_bytes());
+        }
+
+        if !self.api_key.is_empty() {
+            params.insert("api_key".to_string(), json!(self.api_key));
+            hasher.update(self.api_key.as_bytes());
+        }
+
+        let mut headers = params
+            .get("headers")
+            .and_then(|headers| headers.as_object())
+            .cloned()
+            .unwrap_or_else(Map::new);
+        for header in self.active_headers() {
+            hasher.update(header.name.as_bytes());
+            hasher.update(header.value.as_bytes());
+            headers.insert(header.name.clone(), json!(header.value));
+        }
+        if !headers.is_empty() {
+            params.insert("headers".to_string(), json!(headers));
+        }
+
+        provider.provider_name = format!("custom:{}", provider_name);
+        provider.params = Some(params);
+
+        let mut overridden = bit.clone();
+        if let Some(parameters) = overridden.parameters.as_object_mut() {
+            parameters.insert("provider".to_string(), to_value(&provider)?);
+        }
+        overridden.id = hasher.finalize().to_hex().to_string();
+
+        Ok(overridden)
+    }
+}
+
+/// Maps the provider name of an existing Bit onto the matching custom provider.
+fn base_provider_name(provider_name: &str) -> flow_like_types::Result<String> {
+    let name = provider_name.to_lowercase();
+    if name == "local" {
+        return Err(anyhow!(
+            "Local models can not be routed through a provider override, select a provider"
+        ));
+    }
+
+    let name = name
+        .strip_prefix("custom:")
+        .or_else(|| name.strip_prefix("hosted:"))
+        .unwrap_or(&name);
+
+    Ok(match name {
+        "azure" => "openai".to_string(),
+        "hosted" | "vertex" => {
+            return Err(anyhow!(
+                "Hosted models can not be routed through a provider override, select a provider"
+            ));
+        }
+        name => name.to_string(),
+    })
+}
+
+pub fn add_provider_override_pins(node: &mut Node) {
+    let mut providers = vec![KEEP_PROVIDER.to_string()];
+    providers.extend(
+        OVERRIDE_PROVIDERS
+            .iter()
+            .map(|provider| provider.to_string()),
+    );
+
+    node.add_input_pin(
+        "override_provider",
+        "Override Provider",
+        "Provider used for this invocation instead of the one selected by the profile",
+        VariableType::String,
+    )
+    .set_options(PinOptions::new().set_valid_values(providers).build())
+    .set_default_value(Some(json!(KEEP_PROVIDER)));
+
+    node.add_input_pin(
+        "override_base_url",
+        "Override Base URL",
+        "Endpoint to send the request to, e.g. a self-hosted OpenAI compatible server",
+        VariableType::String,
+    )
+    .set_default_value(Some(json!("")));
+
+    node.add_input_pin(
+        "override_api_key",
+        "Override API Key",
+        "API key for the overridden endpoint",
+        VariableType::String,
+    )
+    .set_options(PinOptions::new().set_sensitive(true).build())
+    .set_default_value(Some(json!("")));
+
+    node.add_input_pin(
+        "override_headers",
+        "Override Headers",
+        "Additional HTTP headers sent with the request",
+        VariableType::Struct,
+    )
+    .set_schema::<HttpHeader>()
+    .set_value_type(ValueType::Array)
+    .set_default_value(Some(json!([])));
+}
+
+/// Reads the override pins. Nodes saved before the pins existed fall back to no override.
+pub async fn evaluate_provider_override(context: &mut ExecutionContext) -> ProviderOverride {
+    ProviderOverride {
+        provider: context
+            .evaluate_pin("override_provider")
+            .await
+            .unwrap_or_default(),
+        base_url: context
+            .evaluate_pin("override_base_url")
+            .await
+            .unwrap_or_default(),
+        api_key: context
+            .evaluate_pin("override_api_key")
+            .await
+            .unwrap_or_default(),
+        headers: context
+            .evaluate_pin("override_headers")
+            .await
+            .unwrap_or_default(),
+    }
+}
+
+/// Evaluates the `model` pin with the node's provider override applied.
+pub async fn evaluate_model(context: &mut ExecutionContext) -> flow_like_types::Result<Bit> {
+    let model = context.evaluate_pin::<Bit>("model").await?;
+    evaluate_provider_override(context).await.apply(&model)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model(provider_name: &str) -> Bit {
+        let mut bit = Bit::default();
+        bit.id = "gpt-4o-mini".to_string();
+        bit.parameters = json!({
+            "context_length": 128000,
+            "model_classification": {},
+            "provider": {
+                "provider_name": provider_name,
+                "model_id": "gpt-4o-mini",
+                "version": null,
+                "params": null
+            }
+        });
+        bit
+    }
+
+    fn provider(bit: &Bit) -> ModelProvider {
+        from_value(bit.parameters["provider"].clone()).unwrap()
+    }
+
+    #[test]
+    fn test_empty_override_keeps_model() {
+        let bit = model("openai");
+        let overridden = ProviderOverride {
+            provider: KEEP_PROVIDER.to_string(),
+            ..Default::default()
+        }
+        .apply(&bit)
+        .unwrap();
+
+        assert_eq!(overridden.id, bit.id);
+        assert_eq!(overridden.parameters, bit.parameters);
+    }
+
+    #[test]
+    fn test_base_url_routes_through_custom_provider() {
+        let overridden = ProviderOverride {
+            base_url: "http://localhost:8000/v1".to_string(),
+            headers: vec![HttpHeader {
+                name: "X-Tenant".to_string(),
+                value: "acme".to_string(),
+            }],
+            ..Default::default()
+        }
+        .apply(&model("openai"))
+        .unwrap();
+
+        let provider = provider(&overridden);
+        let params = provider.params.unwrap();
+        assert_eq!(provider.provider_name, "custom:openai");
+        assert_eq!(params["endpoint"], json!("http://localhost:8000/v1"));
+        assert_eq!(params["model_id"], json!("gpt-4o-mini"));
+        assert_eq!(params["headers"], json!({ "X-Tenant": "acme" }));
+        assert_ne!(overridden.id, "gpt-4o-mini");
+    }
+
+    #[test]
+    fn test_explicit_provider_replaces_profile_provider() {
+        let overridden = ProviderOverride {
+            provider: "ollama".to_string(),
+            ..Default::default()
+        }
+        .apply(&model("hosted:openai"))
+        .unwrap();
+
+        assert_eq!(provider(&overridden).provider_name, "custom:ollama");
+    }
+
+    #[test]
+    fn test_different_overrides_get_distinct_ids() {
+        let bit = model("openai");
+        let first = ProviderOverride {
+            base_url: "http://proxy-a/v1".to_string(),
+            ..Default::default()
+        };
+        let second = ProviderOverride {
+            base_url: "http://proxy-b/v1".to_string(),
+            ..Default::default()
+        };
+
+        assert_ne!(
+            first.apply(&bit).unwrap().id,
+            second.apply(&bit).unwrap().id
+        );
+        assert_eq!(first.apply(&bit).unwrap().id, first.apply(&bit).unwrap().id);
+    }
+
+    #[test]
+    fn test_local_model_requires_explicit_provider() {
+        let local = model("Local");
+        let base_url_only = ProviderOverride {
+            base_url: "http://localhost:8000/v1".to_string(),
+            ..Default::default()
+        };
+        assert!(base_url_only.apply(&local).is_err());
+
+        let with_provider = ProviderOverride {
+            provider: "openai".to_string(),
+            ..base_url_only
+        };
+        assert!(with_provider.apply(&local).is_ok());
+    }
+
+    #[test]
+    fn test_unknown_provider_is_rejected() {
+        let result = ProviderOverride {
+            provider: "bedrock".to_string(),
+            ..Default::default()
+        }
+        .apply(&model("openai"));
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,514 @@
+use flow_like::flow::{
+    board::Board,
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic, NodeScores, remove_pin_by_name},
+    pin::{PinOptions, ValueType},
+    variable::VariableType,
+};
+use flow_like_catalog_core::FlowPath;
+use flow_like_catalog_onnx::NodeOnnxSession;
+use flow_like_model_provider::rerank::{
+    ApiReranker, RerankProvider, RerankedDocument, Reranker, rerank,
+};
+use flow_like_types::{Value, async_trait, json::json};
+use std::sync::Arc;
+
+const API_PINS: [&str; 4] = ["provider", "model_id", "endpoint", "api_key"];
+const ONNX_PINS: [&str; 3] = ["model", "tokenizer", "max_length"];
+
+fn get_pin_string_value(node: &Node, name: &str) -> String {
+    node.get_pin_by_name(name)
+        .and_then(|pin| pin.default_value.clone())
+        .and_then(|bytes| flow_like_types::json::from_slice::<Value>(&bytes).ok())
+        .and_then(|json| json.as_str().map(ToOwned::to_owned))
+        .unwrap_or_default()
+}
+
+fn add_backend_pin(node: &mut Node, name: &str) {
+    if node.get_pin_by_name(name).is_some() {
+        return;
+    }
+
+    match name {
+        "provider" => {
+            node.add_input_pin(
+                "provider",
+                "Provider",
+                "Hosted reranking API",
+                VariableType::Struct,
+            )
+            .set_schema::<RerankProvider>()
+            .set_options(
+                PinOptions::new()
+                    .set_valid_values(vec!["Cohere".to_string(), "VoyageAI".to_string()])
+                    .build(),
+            )
+            .set_default_value(Some(json!(RerankProvider::Cohere)));
+        }
+        "model_id" => {
+            node.add_input_pin(
+                "model_id",
+                "Model ID",
+                "Reranking model, leave empty for the provider default (rerank-v3.5 / rerank-2)",
+                VariableType::String,
+            )
+            .set_default_value(Some(json!("")));
+        }
+        "endpoint" => {
+            node.add_input_pin(
+                "endpoint",
+                "Endpoint",
+                "Custom API base URL. Leave empty to use the configured provider",
+                VariableType::String,
+            )
+            .set_default_value(Some(json!("")));
+        }
+        "api_key" => {
+            node.add_input_pin(
+                "api_key",
+                "API Key",
+                "API key, leave empty to use the configured provider credentials",
+                VariableType::String,
+            )
+            .set_options(PinOptions::new().set_sensitive(true).build())
+            .set_default_value(Some(json!("")));
+        }
+        "model" => {
+            node.add_input_pin(
+                "model",
+                "Model",
+                "ONNX cross-encoder session, e.g. ms-marco-MiniLM-L-6-v2 or bge-reranker-base",
+                VariableType::Struct,
+            )
+            .set_schema::<NodeOnnxSession>()
+            .set_options(PinOptions::new().set_enforce_schema(true).build());
+        }
+        "tokenizer" => {
+            node.add_input_pin(
+                "tokenizer",
+                "Tokenizer",
+                "tokenizer.json from the same model repository",
+                VariableType::Struct,
+            )
+            .set_schema::<FlowPath>()
+            .set_options(PinOptions::new().set_enforce_schema(true).build());
+        }
+        "max_length" => {
+            node.add_input_pin(
+                "max_length",
+                "Max Length",
+                "Maximum tokens per query/document pair, longer documents are truncated",
+                VariableType::Integer,
+            )
+            .set_options(PinOptions::new().set_range((16., 8192.)).build())
+            .set_default_value(Some(json!(512)));
+        }
+        _ => {}
+    }
+}
+
+async fn api_reranker(context: &mut ExecutionContext) -> flow_like_types::Result<ApiReranker> {
+    let provider: RerankProvider = context.evaluate_pin("provider").await?;
+    let model_id: String = context.evaluate_pin("model_id").await.unwrap_or_default();
+    let endpoint: String = context.evaluate_pin("endpoint").await.unwrap_or_default();
+    let api_key: String = context.evaluate_pin("api_key").await.unwrap_or_default();
+
+    if !api_key.is_empty() {
+        return Ok(ApiReranker::new(
+            provider,
+            Some(model_id),
+            Some(endpoint),
+            api_key,
+        ));
+    }
+
+    ApiReranker::from_config(
+        provider,
+        Some(model_id),
+        &context.app_state.model_provider_config,
+    )
+}
+
+#[cfg(feature = "execute")]
+async fn onnx_reranker(
+    context: &mut ExecutionContext,
+) -> flow_like_types::Result<Box<dyn Reranker>> {
+    use flow_like_catalog_onnx::rerank::CrossEncoderReranker;
+
+    let model: NodeOnnxSession = context.evaluate_pin("model").await?;
+    let tokenizer: FlowPath = context.evaluate_pin("tokenizer").await?;
+    let max_length: usize = context.evaluate_pin("max_length").await.unwrap_or(512);
+
+    Ok(Box::new(
+        CrossEncoderReranker::new(context, &model, &tokenizer, max_length).await?,
+    ))
+}
+
+#[cfg(not(feature = "execute"))]
+async fn onnx_reranker(
+    _context: &mut ExecutionContext,
+) -> flow_like_types::Result<Box<dyn Reranker>> {
+    flow_like_types::bail!("ONNX reranking requires the 'execute' feature")
+}
+
+async fn backend_reranker(
+    context: &mut ExecutionContext,
+) -> flow_like_types::Result<Box<dyn Reranker>> {
+    let backend: String = context.evaluate_pin("backend").await?;
+    match backend.as_str() {
+        "ONNX" => onnx_reranker(context).await,
+        _ => Ok(Box::new(api_reranker(context).await?)),
+    }
+}
+
+fn update_backend_pins(node: &mut Node) {
+    let keep: &[&str] = match get_pin_string_value(node, "backend").as_str() {
+        "ONNX" => &ONNX_PINS,
+        _ => &API_PINS,
+    };
+
+    for name in API_PINS.iter().chain(&ONNX_PINS) {
+        if !keep.contains(name) {
+            remove_pin_by_name(node, name);
+        }
+    }
+
+    for name in keep {
+        add_backend_pin(node, name);
+    }
+}
+
+fn add_backend_selection(node: &mut Node) {
+    node.add_input_pin(
+        "backend",
+        "Backend",
+        "Score with a hosted API or a local ONNX cross-encoder",
+        VariableType::String,
+    )
+    .set_options(
+        PinOptions::new()
+            .set_valid_values(vec!["API".to_string(), "ONNX".to_string()])
+            .build(),
+    )
+    .set_default_value(Some(json!("API")));
+
+    for pin in API_PINS {
+        add_backend_pin(node, pin);
+    }
+}
+
+/// Reads the text to score from each record. `field` may be a dotted path such as
+/// `record.text` to reach into nested objects, e.g. Semantic Search results.
+pub(crate) fn record_texts(records: &[Value], field: &str) -> flow_like_types::Result<Vec<String>> {
+    records
+        .iter()
+        .enumerate()
+        .map(|(index, record)| {
+            field
+                .split('.')
+                .try_fold(record, |value, key| value.get(key))
+                .and_then(|value| match value {
+                    Value::String(text) => Some(text.clone()),
+                    Value::Null => None,
+                    other => Some(other.to_string()),
+                })
+                .ok_or_else(|| {
+                    flow_like_types::anyhow!("Record {} has no field \"{}\"", index, field)
+                })
+        })
+        .collect()
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct RerankNode {}
+
+impl RerankNode {
+    pub fn new() -> Self {
+        RerankNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for RerankNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "ai_rerank",
+            "Rerank",
+            "Reorders candidate documents by relevance to a query using a cross-encoder ONNX model or a hosted reranking API. Use it after retrieval to keep only the best matches",
+            "AI/Generative",
+        );
+        node.add_icon("/flow/icons/bot-search.svg");
+
+        node.set_scores(
+            NodeScores::new()
+                .set_privacy(6)
+                .set_security(7)
+                .set_performance(6)
+                .set_governance(7)
+                .set_reliability(8)
+                .set_cost(6)
+                .build(),
+        );
+
+        node.add_input_pin(
+            "exec_in",
+            "Input",
+            "Initiate Execution",
+            VariableType::Execution,
+        );
+
+        node.add_input_pin("query", "Query", "Search query", VariableType::String)
+            .set_default_value(Some(json!("")));
+
+        node.add_input_pin(
+            "documents",
+            "Documents",
+            "Candidate documents to rerank",
+            VariableType::String,
+        )
+        .set_value_type(ValueType::Array)
+        .set_default_value(Some(json!([])));
+
+        node.add_input_pin(
+            "top_k",
+            "Top K",
+            "Number of documents to keep, 0 keeps all",
+            VariableType::Integer,
+        )
+        .set_default_value(Some(json!(5)));
+
+        node.add_input_pin(
+            "batch_size",
+            "Batch Size",
+            "Documents scored per request or model run",
+            VariableType::Integer,
+        )
+        .set_options(PinOptions::new().set_range((1., 1000.)).build())
+        .set_default_value(Some(json!(32)));
+
+        add_backend_selection(&mut node);
+
+        node.add_output_pin(
+            "exec_out",
+            "Output",
+            "Done with the Execution",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "reranked",
+            "Documents",
+            "Documents ordered by descending relevance",
+            VariableType::String,
+        )
+        .set_value_type(ValueType::Array);
+
+        node.add_output_pin(
+            "scores",
+            "Scores",
+            "Relevance score of each returned document",
+            VariableType::Float,
+        )
+        .set_value_type(ValueType::Array);
+
+        node.add_output_pin(
+            "results",
+            "Results",
+            "Returned documents with their original index and score",
+            VariableType::Struct,
+        )
+        .set_schema::<RerankedDocument>()
+        .set_value_type(ValueType::Array);
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+
+        let query: String = context.evaluate_pin("query").await?;
+        let documents: Vec<String> = context.evaluate_pin("documents").await?;
+        let top_k: usize = context.evaluate_pin("top_k").await.unwrap_or(0);
+        let batch_size: usize = context.evaluate_pin("batch_size").await.unwrap_or(32);
+
+        let reranker = backend_reranker(context).await?;
+
+        let results = rerank(reranker.as_ref(), &query, &documents, top_k, batch_size).await?;
+        let reranked: Vec<&String> = results.iter().map(|result| &result.document).collect();
+        let scores: Vec<f32> = results.iter().map(|result| result.score).collect();
+
+        context.set_pin_value("reranked", json!(reranked)).await?;
+        context.set_pin_value("scores", json!(scores)).await?;
+        context.set_pin_value("results", json!(results)).await?;
+        context.activate_exec_pin("exec_out").await?;
+
+        Ok(())
+    }
+
+    async fn on_update(&self, node: &mut Node, _board: Arc<Board>) {
+        update_backend_pins(node);
+    }
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct RerankRecordsNode {}
+
+impl RerankRecordsNode {
+    pub fn new() -> Self {
+        RerankRecordsNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for RerankRecordsNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "ai_rerank_records",
+            "Rerank Records",
+            "Reorders structured search hits, e.g. from Semantic Search, by the relevance of one text field to the query. Typically narrows the top 20 vector hits down to the best 5 for an LLM context",
+            "AI/Generative",
+        );
+        node.add_icon("/flow/icons/bot-search.svg");
+
+        node.set_scores(
+            NodeScores::new()
+                .set_privacy(6)
+                .set_security(7)
+                .set_performance(6)
+                .set_governance(7)
+                .set_reliability(8)
+                .set_cost(6)
+                .build(),
+        );
+
+        node.add_input_pin(
+            "exec_in",
+            "Input",
+            "Initiate Execution",
+            VariableType::Execution,
+        );
+
+        node.add_input_pin("query", "Query", "Search query", VariableType::String)
+            .set_default_value(Some(json!("")));
+
+        node.add_input_pin(
+            "records",
+            "Records",
+            "Candidate records to rerank",
+            VariableType::Struct,
+        )
+        .set_value_type(ValueType::Array)
+        .set_default_value(Some(json!([])));
+
+        node.add_input_pin(
+            "text_field",
+            "Text Field",
+            "Field holding the text to score, dotted paths reach nested fields",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("record.text")));
+
+        node.add_input_pin(
+            "top_k",
+            "Top K",
+            "Number of records to keep, 0 keeps all",
+            VariableType::Integer,
+        )
+        .set_default_value(Some(json!(5)));
+
+        node.add_input_pin(
+            "batch_size",
+            "Batch Size",
+            "Records scored per request or model run",
+            VariableType::Integer,
+        )
+        .set_options(PinOptions::new().set_range((1., 1000.)).build())
+        .set_default_value(Some(json!(32)));
+
+        add_backend_selection(&mut node);
+
+        node.add_output_pin(
+            "exec_out",
+            "Output",
+            "Done with the Execution",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "reranked",
+            "Records",
+            "Records ordered by descending relevance, unchanged otherwise",
+            VariableType::Struct,
+        )
+        .set_value_type(ValueType::Array);
+
+        node.add_output_pin(
+            "scores",
+            "Scores",
+            "Relevance score of each returned record",
+            VariableType::Float,
+        )
+        .set_value_type(ValueType::Array);
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+
+        let query: String = context.evaluate_pin("query").await?;
+        let records: Vec<Value> = context.evaluate_pin("records").await?;
+        let text_field: String = context.evaluate_pin("text_field").await?;
+        let top_k: usize = context.evaluate_pin("top_k").await.unwrap_or(0);
+        let batch_size: usize = context.evaluate_pin("batch_size").await.unwrap_or(32);
+
+        let texts = record_texts(&records, text_field.trim())?;
+        let reranker = backend_reranker(context).await?;
+
+        let results = rerank(reranker.as_ref(), &query, &texts, top_k, batch_size).await?;
+        let reranked: Vec<&Value> = results
+            .iter()
+            .map(|result| &records[result.index])
+            .collect();
+        let scores: Vec<f32> = results.iter().map(|result| result.score).collect();
+
+        context.set_pin_value("reranked", json!(reranked)).await?;
+        context.set_pin_value("scores", json!(scores)).await?;
+        context.activate_exec_pin("exec_out").await?;
+
+        Ok(())
+    }
+
+    async fn on_update(&self, node: &mut Node, _board: Arc<Board>) {
+        update_backend_pins(node);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_texts() {
+        let records = vec![
+            json!({ "score": 0.9, "record": { "text": "first", "page": 3 } }),
+            json!({ "score": 0.8, "record": { "text": "second", "page": 4 } }),
+        ];
+        assert_eq!(
+            record_texts(&records, "record.text").unwrap(),
+            vec!["first", "second"]
+        );
+        assert_eq!(
+            record_texts(&records, "record.page").unwrap(),
+            vec!["3", "4"]
+        );
+
+        let error = record_texts(&records, "text").unwrap_err();
+        assert!(error.to_string().contains("Record 0"));
+
+        let missing = vec![json!({ "text": "a" }), json!({ "text": null })];
+        assert!(record_texts(&missing, "text").is_err());
+    }
+}
@@ -0,0 +1,433 @@
+//! Structured output for the generative invoke node.
+//!
+//! Providers with native JSON Schema support receive the schema as `response_format`.
+//! For all others the schema is written into the system prompt. In both cases the
+//! reply is validated and, if it does not match, the model is asked to repair it.
+
+use flow_like::bit::Bit;
+use flow_like_model_provider::{
+    history::{History, HistoryMessage, ResponseFormat, Role},
+    response::Response,
+};
+use flow_like_types::{
+    Value, anyhow,
+    json::{self, json},
+};
+use std::future::Future;
+
+/// Providers that accept an OpenAI style `json_schema` response format.
+const NATIVE_PROVIDERS: [&str; 7] = [
+    "openai",
+    "azure",
+    "openrouter",
+    "xai",
+    "groq",
+    "mistral",
+    "together",
+];
+
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+
+pub struct StructuredOutput {
+    schema: Value,
+    native: bool,
+    max_attempts: u32,
+}
+
+pub struct StructuredResult {
+    /// Response of the last attempt
+    pub response: Response,
+    pub value: Option<Value>,
+    pub error: Option<String>,
+    pub attempts: u32,
+}
+
+impl StructuredOutput {
+    pub fn new(schema: Value, native: bool, max_attempts: u32) -> Self {
+        StructuredOutput {
+            schema,
+            native,
+            max_attempts: max_attempts.max(1),
+        }
+    }
+
+    /// Builds the structured output for `model`, picking native mode if its provider supports it.
+    pub fn for_model(schema: Value, model: &Bit, max_attempts: u32) -> Self {
+        Self::new(schema, supports_native(model), max_attempts)
+    }
+
+    pub fn is_native(&self) -> bool {
+        self.native
+    }
+
+    /// Returns a copy of `history` that asks the model for JSON matching the schema.
+    pub fn prepare(&self, history: &History) -> History {
+        let mut history = history.clone();
+
+        if self.native {
+            history.response_format = Some(ResponseFormat::Object(json!({
+                "type": "json_schema",
+                "json_schema": {
+                    "name": "structured_output",
+                    "schema": self.schema,
+                    "strict": false
+                }
+            })));
+            return history;
+        }
+
+        let schema =
+            json::to_string_pretty(&self.schema).unwrap_or_else(|_| self.schema.to_string());
+        let instruction = format!(
+            "Respond only with a JSON value that matches the following JSON Schema. Do not add explanations or code fences.\n\n{}",
+            schema
+        );
+        let prompt = match history.get_system_prompt() {
+            Some(prompt) if !prompt.trim().is_empty() => format!("{}\n\n{}", prompt, instruction),
+            _ => instruction,
+        };
+        history.set_system_prompt(prompt);
+        history
+    }
+
+    /// Parses the model reply and checks it against the schema.
+    pub fn validate(&self, content: &str) -> Result<Value, String> {
+        let value = parse_json(content)?;
+        check_schema(&self.schema, &value)?;
+        Ok(value)
+    }
+
+    /// Invokes the model until it returns a value that matches the schema or the
+    /// attempts are used up. Provider errors are returned as is, validation errors
+    /// end up in [`StructuredResult::error`].
+    pub async fn invoke<F, Fut>(
+        &self,
+        history: &History,
+        mut invoke: F,
+    ) -> flow_like_types::Result<StructuredResult>
+    where
+        F: FnMut(History) -> Fut,
+        Fut: Future<Output = flow_like_types::Result<Response>>,
+    {
+        let mut history = self.prepare(history);
+        let mut attempt = 0;
+
+        loop {
+            attempt += 1;
+            let response = invoke(history.clone()).await?;
+            let content = response.content().unwrap_or_default();
+
+            let error = match self.validate(&content) {
+                Ok(value) => {
+                    return Ok(StructuredResult {
+                        response,
+                        value: Some(value),
+                        error: None,
+                        attempts: attempt,
+                    });
+                }
+                Err(error) => error,
+            };
+
+            if attempt >= self.max_attempts {
+                return Ok(StructuredResult {
+                    response,
+                    value: None,
+                    error: Some(format!(
+                        "No valid structured output after {} attempt(s): {}",
+                        attempt, error
+                    )),
+                    attempts: attempt,
+                });
+            }
+
+            history.push_message(HistoryMessage::from_response(response));
+            history.push_message(HistoryMessage::from_string(
+                Role::User,
+                &format!(
+                    "Your previous reply could not be used: {}\n\nReply again with only the corrected JSON value.",
+                    error
+                ),
+            ));
+        }
+    }
+}
+
+/// Resolves the schema from the `response_format` pin. `struct_schema` is the schema of a
+/// connected struct pin and wins over the pin value. Returns `None` if no format is set.
+pub fn resolve_schema(
+    value: Value,
+    struct_schema: Option<&str>,
+) -> flow_like_types::Result<Option<Value>> {
+    let schema = match (struct_schema, value) {
+        (Some(schema), _) => json::from_str::<Value>(schema)?,
+        (None, Value::Null) => return Ok(None),
+        (None, Value::String(raw)) if raw.trim().is_empty() => return Ok(None),
+        (None, Value::String(raw)) => json::from_str::<Value>(raw.trim())
+            .map_err(|e| anyhow!("Response format must be a JSON Schema: {}", e))?,
+        (None, value) => value,
+    };
+
+    if !schema.is_object() || !is_valid_schema(&schema) {
+        return Err(anyhow!("Response format is not a valid JSON Schema"));
+    }
+
+    Ok(Some(schema))
+}
+
+fn supports_native(model: &Bit) -> bool {
+    let Some(provider_name) = model
+        .parameters
+        .get("provider")
+        .and_then(|provider| provider.get("provider_name"))
+        .and_then(|name| name.as_str())
+    else {
+        return false;
+    };
+
+    let name = provider_name.to_lowercase();
+    let name = name
+        .strip_prefix("custom:")
+        .or_else(|| name.strip_prefix("hosted:"))
+        .unwrap_or(&name);
+
+    NATIVE_PROVIDERS.contains(&name)
+}
+
+/// Parses a reply as JSON, tolerating code fences and text around the value.
+fn parse_json(content: &str) -> Result<Value, String> {
+    let trimmed = content.trim();
+    if trimmed.is_empty() {
+        return Err("the reply was empty".to_string());
+    }
+
+    let unfenced = trimmed
+        .strip_prefix("```json")
+        .or_else(|| trimmed.strip_prefix("```"))
+        .and_then(|rest| rest.trim_end().strip_suffix("```"))
+        .map(str::trim)
+        .unwrap_or(trimmed);
+
+    let first_error = match json::from_str::<Value>(unfenced) {
+        Ok(value) => return Ok(value),
+        Err(error) => error,
+    };
+
+    let start = unfenced.find(['{', '[']);
+    let end = unfenced.rfind(['}', ']']);
+    if let (Some(start), Some(end)) = (start, end)
+        && start < end
+        && let Ok(value) = json::from_str::<Value>(&unfenced[start..=end])
+    {
+        return Ok(value);
+    }
+
+    Err(format!("the reply is not valid JSON ({})", first_error))
+}
+
+#[cfg(feature = "execute")]
+fn is_valid_schema(schema: &Value) -> bool {
+    jsonschema::meta::is_valid(schema)
+}
+
+#[cfg(not(feature = "execute"))]
+fn is_valid_schema(_schema: &Value) -> bool {
+    true
+}
+
+#[cfg(feature = "execute")]
+fn check_schema(schema: &Value, value: &Value) -> Result<(), String> {
+    jsonschema::validate(schema, value)
+        .map_err(|e| format!("the JSON does not match the schema ({})", e))
+}
+
+#[cfg(not(feature = "execute"))]
+fn check_schema(_schema: &Value, _value: &Value) -> Result<(), String> {
+    Err("Schema validation requires the 'execute' feature".to_string())
+}
+
+#[cfg(all(test, feature = "execute"))]
+mod tests {
+    use super::*;
+    use std::{
+        collections::VecDeque,
+        sync::{Arc, Mutex},
+    };
+
+    /// Replays canned replies and records the histories it was called with.
+    #[derive(Clone, Default)]
+    struct MockProvider {
+        replies: Arc<Mutex<VecDeque<String>>>,
+        requests: Arc<Mutex<Vec<History>>>,
+    }
+
+    impl MockProvider {
+        fn new(replies: &[&str]) -> Self {
+            MockProvider {
+                replies: Arc::new(Mutex::new(
+                    replies.iter().map(|reply| reply.to_string()).collect(),
+                )),
+                requests: Arc::default(),
+            }
+        }
+
+        fn invoke(
+            &self,
+            history: History,
+        ) -> impl Future<Output = flow_like_types::Result<Response>> {
+            self.requests.lock().unwrap().push(history);
+            let reply = self.replies.lock().unwrap().pop_front();
+            async move {
+                let reply = reply.ok_or_else(|| anyhow!("mock provider ran out of replies"))?;
+                Ok(Response::from_text(reply, "mock"))
+            }
+        }
+
+        fn requests(&self) -> Vec<History> {
+            self.requests.lock().unwrap().clone()
+        }
+    }
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "string" },
+                "age": { "type": "integer", "minimum": 0 }
+            },
+            "required": ["name", "age"]
+        })
+    }
+
+    fn history() -> History {
+        History::new(
+            "mock".to_string(),
+            vec![HistoryMessage::from_string(Role::User, "Who is Ada?")],
+        )
+    }
+
+    fn model(provider_name: &str) -> Bit {
+        let mut bit = Bit::default();
+        bit.parameters = json!({ "provider": { "provider_name": provider_name } });
+        bit
+    }
+
+    #[tokio::test]
+    async fn test_valid_reply_is_returned() {
+        let mock = MockProvider::new(&[r#"{"name": "Ada", "age": 36}"#]);
+        let output = StructuredOutput::new(schema(), false, 3);
+
+        let result = output
+            .invoke(&history(), |history| mock.invoke(history))
+            .await
+            .unwrap();
+
+        assert_eq!(result.value, Some(json!({ "name": "Ada", "age": 36 })));
+        assert_eq!(result.error, None);
+        assert_eq!(result.attempts, 1);
+        assert_eq!(mock.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_malformed_reply_is_repaired() {
+        let mock = MockProvider::new(&[
+            r#"{"name": "Ada", "age": 36"#,
+            "```json\n{\"name\": \"Ada\", \"age\": 36}\n```",
+        ]);
+        let output = StructuredOutput::new(schema(), false, 3);
+
+        let result = output
+            .invoke(&history(), |history| mock.invoke(history))
+            .await
+            .unwrap();
+
+        assert_eq!(result.value, Some(json!({ "name": "Ada", "age": 36 })));
+        assert_eq!(result.attempts, 2);
+
+        let repair = mock.requests()[1].messages.last().unwrap().as_str();
+        assert!(repair.contains("not valid JSON"));
+    }
+
+    #[tokio::test]
+    async fn test_schema_violation_exhausts_attempts() {
+        let mock = MockProvider::new(&[
+            r#"{"name": "Ada"}"#,
+            r#"{"name": "Ada", "age": -1}"#,
+            r#"{"name": "Ada", "age": "36"}"#,
+        ]);
+        let output = StructuredOutput::new(schema(), false, 3);
+
+        let result = output
+            .invoke(&history(), |history| mock.invoke(history))
+            .await
+            .unwrap();
+
+        assert_eq!(result.value, None);
+        assert_eq!(result.attempts, 3);
+        assert!(result.error.unwrap().contains("does not match the schema"));
+
+        let requests = mock.requests();
+        assert_eq!(requests.len(), 3);
+        // Every failed attempt adds the reply and a repair request
+        assert_eq!(requests[2].messages.len(), requests[0].messages.len() + 4);
+    }
+
+    #[tokio::test]
+    async fn test_provider_errors_are_returned() {
+        let mock = MockProvider::new(&[]);
+        let output = StructuredOutput::new(schema(), false, 3);
+
+        let result = output
+            .invoke(&history(), |history| mock.invoke(history))
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_prompt_mode_injects_schema() {
+        let mut base = history();
+        base.set_system_prompt("You are helpful.".to_string());
+
+        let prepared = StructuredOutput::new(schema(), false, 3).prepare(&base);
+        let prompt = prepared.get_system_prompt().unwrap();
+
+        assert!(prompt.starts_with("You are helpful."));
+        assert!(prompt.contains("\"required\""));
+        assert!(prepared.response_format.is_none());
+    }
+
+    #[test]
+    fn test_native_mode_sets_response_format() {
+        let output = StructuredOutput::for_model(schema(), &model("custom:openai"), 3);
+        assert!(output.is_native());
+
+        let prepared = output.prepare(&history());
+        assert!(prepared.get_system_prompt().is_none());
+        match prepared.response_format {
+            Some(ResponseFormat::Object(format)) => {
+                assert_eq!(format["type"], json!("json_schema"));
+                assert_eq!(format["json_schema"]["schema"], schema());
+            }
+            _ => panic!("expected a json_schema response format"),
+        }
+
+        assert!(!StructuredOutput::for_model(schema(), &model("anthropic"), 3).is_native());
+        assert!(!StructuredOutput::for_model(schema(), &Bit::default(), 3).is_native());
+    }
+
+    #[test]
+    fn test_resolve_schema() {
+        assert!(resolve_schema(json!(""), None).unwrap().is_none());
+        assert!(resolve_schema(Value::Null, None).unwrap().is_none());
+        assert_eq!(
+            resolve_schema(json!(schema().to_string()), None).unwrap(),
+            Some(schema())
+        );
+        assert_eq!(
+            resolve_schema(json!(""), Some(&schema().to_string())).unwrap(),
+            Some(schema())
+        );
+        assert!(resolve_schema(json!("not json"), None).is_err());
+        assert!(resolve_schema(json!({ "type": 5 }), None).is_err());
+    }
+}
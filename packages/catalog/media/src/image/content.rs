@@ -1,3 +1,4 @@
+pub mod generate_thumbnails;
 pub mod read_barcodes;
 pub mod read_from_path;
 pub mod read_from_url;
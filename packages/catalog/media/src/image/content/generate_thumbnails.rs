@@ -0,0 +1,385 @@
+use std::{collections::HashMap, sync::Arc};
+
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic},
+    pin::{PinOptions, ValueType},
+    variable::VariableType,
+};
+use flow_like_catalog_core::{FlowPath, NodeImage};
+use flow_like_types::{
+    anyhow, async_trait, bail,
+    image::{
+        DynamicImage, GenericImageView,
+        codecs::{
+            jpeg::JpegEncoder,
+            png::{CompressionType, FilterType as PngFilterType, PngEncoder},
+            webp::WebPEncoder,
+        },
+        imageops::FilterType,
+    },
+    json::json,
+    tokio,
+};
+use futures::future::try_join_all;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Copy, Debug, Default, PartialEq)]
+pub enum ThumbnailFit {
+    /// Scale down to fit inside the box, keeping the aspect ratio
+    #[default]
+    Contain,
+    /// Scale and crop to fill the box exactly, keeping the aspect ratio
+    Cover,
+    /// Stretch to the exact box size
+    Fill,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq)]
+pub struct ThumbnailSize {
+    pub label: String,
+    pub width: u32,
+    pub height: u32,
+    #[serde(default)]
+    pub fit: ThumbnailFit,
+}
+
+impl ThumbnailSize {
+    fn new(label: &str, size: u32) -> Self {
+        ThumbnailSize {
+            label: label.to_string(),
+            width: size,
+            height: size,
+            fit: ThumbnailFit::Contain,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ThumbnailFormat {
+    Jpeg(u8),
+    Png,
+    WebP,
+}
+
+impl ThumbnailFormat {
+    fn parse(format: &str, quality: u8) -> flow_like_types::Result<Self> {
+        match format {
+            "JPEG" => Ok(ThumbnailFormat::Jpeg(quality.clamp(1, 100))),
+            "PNG" => Ok(ThumbnailFormat::Png),
+            "WebP" => Ok(ThumbnailFormat::WebP),
+            other => Err(anyhow!("Unsupported thumbnail format: {}", other)),
+        }
+    }
+
+    fn extension(&self) -> &'static str {
+        match self {
+            ThumbnailFormat::Jpeg(_) => "jpeg",
+            ThumbnailFormat::Png => "png",
+            ThumbnailFormat::WebP => "webp",
+        }
+    }
+}
+
+fn validate_sizes(sizes: &[ThumbnailSize]) -> flow_like_types::Result<()> {
+    if sizes.is_empty() {
+        bail!("At least one thumbnail size is required");
+    }
+
+    let mut labels = std::collections::HashSet::new();
+    for size in sizes {
+        if size.label.trim().is_empty() || size.label.contains('/') {
+            bail!("Invalid thumbnail label '{}'", size.label);
+        }
+        if size.width == 0 || size.height == 0 {
+            bail!(
+                "Thumbnail '{}' needs a width and height above 0",
+                size.label
+            );
+        }
+        if !labels.insert(size.label.as_str()) {
+            bail!("Duplicate thumbnail label '{}'", size.label);
+        }
+    }
+
+    Ok(())
+}
+
+fn render_thumbnail(image: &DynamicImage, size: &ThumbnailSize) -> DynamicImage {
+    let (width, height) = image.dimensions();
+
+    match size.fit {
+        ThumbnailFit::Contain if width <= size.width && height <= size.height => image.clone(),
+        ThumbnailFit::Contain => image.resize(size.width, size.height, FilterType::Triangle),
+        ThumbnailFit::Cover => image.resize_to_fill(size.width, size.height, FilterType::Triangle),
+        ThumbnailFit::Fill => image.resize_exact(size.width, size.height, FilterType::Triangle),
+    }
+}
+
+fn encode_thumbnail(
+    image: &DynamicImage,
+    format: ThumbnailFormat,
+) -> flow_like_types::Result<Vec<u8>> {
+    let (width, height) = image.dimensions();
+    let mut encoded = Vec::with_capacity(width as usize * height as usize);
+
+    match format {
+        ThumbnailFormat::Jpeg(quality) => {
+            let encoder = JpegEncoder::new_with_quality(&mut encoded, quality);
+            DynamicImage::ImageRgb8(image.to_rgb8()).write_with_encoder(encoder)?;
+        }
+        ThumbnailFormat::Png => {
+            let encoder = PngEncoder::new_with_quality(
+                &mut encoded,
+                CompressionType::Fast,
+                PngFilterType::Adaptive,
+            );
+            image.write_with_encoder(encoder)?;
+        }
+        ThumbnailFormat::WebP => {
+            let encoder = WebPEncoder::new_lossless(&mut encoded);
+            DynamicImage::ImageRgba8(image.to_rgba8()).write_with_encoder(encoder)?;
+        }
+    }
+
+    Ok(encoded)
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct GenerateThumbnailsNode {}
+
+impl GenerateThumbnailsNode {
+    pub fn new() -> Self {
+        GenerateThumbnailsNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for GenerateThumbnailsNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "generate_thumbnails",
+            "Generate Thumbnails",
+            "Resizes an image to several thumbnail sizes, encodes them in parallel and writes them next to each other",
+            "Image/Content",
+        );
+        node.add_icon("/flow/icons/image.svg");
+
+        node.add_input_pin(
+            "exec_in",
+            "Input",
+            "Initiate Execution",
+            VariableType::Execution,
+        );
+
+        node.add_input_pin("image_in", "Image", "Source image", VariableType::Struct)
+            .set_schema::<NodeImage>()
+            .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node.add_input_pin(
+            "directory",
+            "Directory",
+            "Directory the thumbnails are written to",
+            VariableType::Struct,
+        )
+        .set_schema::<FlowPath>()
+        .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node.add_input_pin(
+            "name",
+            "Name",
+            "Base file name, thumbnails are stored as <name>_<label>.<extension>",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("thumbnail")));
+
+        node.add_input_pin(
+            "sizes",
+            "Sizes",
+            "Target sizes with label and fit mode (Contain, Cover or Fill)",
+            VariableType::Struct,
+        )
+        .set_schema::<ThumbnailSize>()
+        .set_value_type(ValueType::Array)
+        .set_options(PinOptions::new().set_enforce_schema(true).build())
+        .set_default_value(Some(json!([
+            ThumbnailSize::new("sm", 128),
+            ThumbnailSize::new("md", 512),
+            ThumbnailSize::new("lg", 1024),
+        ])));
+
+        node.add_input_pin("format", "Format", "Image Type", VariableType::String)
+            .set_options(
+                PinOptions::new()
+                    .set_valid_values(vec![
+                        "JPEG".to_string(),
+                        "PNG".to_string(),
+                        "WebP".to_string(),
+                    ])
+                    .build(),
+            )
+            .set_default_value(Some(json!("JPEG")));
+
+        node.add_input_pin(
+            "quality",
+            "Quality",
+            "JPEG Encoding Quality",
+            VariableType::Integer,
+        )
+        .set_options(PinOptions::new().set_range((1., 100.)).build())
+        .set_default_value(Some(json!(85)));
+
+        node.add_output_pin(
+            "exec_out",
+            "Output",
+            "Done with the Execution",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "thumbnails",
+            "Thumbnails",
+            "Written thumbnails by label",
+            VariableType::Struct,
+        )
+        .set_schema::<FlowPath>()
+        .set_value_type(ValueType::HashMap);
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+
+        let node_image: NodeImage = context.evaluate_pin("image_in").await?;
+        let directory: FlowPath = context.evaluate_pin("directory").await?;
+        let name: String = context.evaluate_pin("name").await?;
+        let sizes: Vec<ThumbnailSize> = context.evaluate_pin("sizes").await?;
+        let format: String = context.evaluate_pin("format").await?;
+        let quality: u8 = context.evaluate_pin("quality").await?;
+
+        validate_sizes(&sizes)?;
+        let format = ThumbnailFormat::parse(&format, quality)?;
+
+        let image = node_image.get_image(context).await?;
+        let source = Arc::new(image.lock().await.clone());
+
+        let encoded = try_join_all(sizes.into_iter().map(|size| {
+            let source = source.clone();
+            tokio::task::spawn_blocking(move || {
+                let thumbnail = render_thumbnail(&source, &size);
+                encode_thumbnail(&thumbnail, format).map(|bytes| (size.label, bytes))
+            })
+        }))
+        .await?;
+
+        let runtime = directory.to_runtime(context).await?;
+        let mut thumbnails = HashMap::with_capacity(encoded.len());
+
+        for result in encoded {
+            let (label, bytes) = result?;
+            let mut path = runtime.clone();
+            path.path = path
+                .path
+                .child(format!("{}_{}.{}", name, label, format.extension()));
+            let path = path.serialize().await;
+            path.put(context, bytes, false).await?;
+            thumbnails.insert(label, path);
+        }
+
+        context
+            .set_pin_value("thumbnails", json!(thumbnails))
+            .await?;
+        context.activate_exec_pin("exec_out").await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flow_like_types::image::{ImageFormat, RgbaImage, load_from_memory};
+
+    fn image(width: u32, height: u32) -> DynamicImage {
+        DynamicImage::ImageRgba8(RgbaImage::from_fn(width, height, |x, y| {
+            [(x % 255) as u8, (y % 255) as u8, 128, 255].into()
+        }))
+    }
+
+    fn size(fit: ThumbnailFit) -> ThumbnailSize {
+        ThumbnailSize {
+            label: "md".to_string(),
+            width: 100,
+            height: 100,
+            fit,
+        }
+    }
+
+    #[test]
+    fn test_fit_modes() {
+        let source = image(400, 200);
+
+        assert_eq!(
+            render_thumbnail(&source, &size(ThumbnailFit::Contain)).dimensions(),
+            (100, 50)
+        );
+        assert_eq!(
+            render_thumbnail(&source, &size(ThumbnailFit::Cover)).dimensions(),
+            (100, 100)
+        );
+        assert_eq!(
+            render_thumbnail(&source, &size(ThumbnailFit::Fill)).dimensions(),
+            (100, 100)
+        );
+    }
+
+    #[test]
+    fn test_contain_does_not_upscale() {
+        let source = image(40, 20);
+        assert_eq!(
+            render_thumbnail(&source, &size(ThumbnailFit::Contain)).dimensions(),
+            (40, 20)
+        );
+    }
+
+    #[test]
+    fn test_encoded_formats_decode() {
+        let thumbnail = image(32, 16);
+        for (format, expected) in [
+            (ThumbnailFormat::Jpeg(80), ImageFormat::Jpeg),
+            (ThumbnailFormat::Png, ImageFormat::Png),
+            (ThumbnailFormat::WebP, ImageFormat::WebP),
+        ] {
+            let bytes = encode_thumbnail(&thumbnail, format).unwrap();
+            assert_eq!(
+                flow_like_types::image::guess_format(&bytes).unwrap(),
+                expected
+            );
+            assert_eq!(load_from_memory(&bytes).unwrap().dimensions(), (32, 16));
+        }
+    }
+
+    #[test]
+    fn test_validate_sizes() {
+        assert!(validate_sizes(&[]).is_err());
+        assert!(validate_sizes(&[ThumbnailSize::new("sm", 0)]).is_err());
+        assert!(validate_sizes(&[ThumbnailSize::new("a/b", 10)]).is_err());
+        assert!(
+            validate_sizes(&[ThumbnailSize::new("sm", 10), ThumbnailSize::new("sm", 20)]).is_err()
+        );
+        assert!(
+            validate_sizes(&[ThumbnailSize::new("sm", 10), ThumbnailSize::new("lg", 20)]).is_ok()
+        );
+    }
+
+    #[test]
+    fn test_sizes_deserialize_with_default_fit() {
+        let sizes: Vec<ThumbnailSize> = flow_like_types::json::from_value(
+            json!([{ "label": "sm", "width": 64, "height": 64 }]),
+        )
+        .unwrap();
+        assert_eq!(sizes[0].fit, ThumbnailFit::Contain);
+    }
+}
@@ -0,0 +1,287 @@
+use flow_like::a2ui::Surface;
+use flow_like::flow::execution::context::ExecutionContext;
+use flow_like::flow::node::{Node, NodeLogic};
+use flow_like::flow::pin::PinOptions;
+use flow_like::flow::variable::VariableType;
+use flow_like_catalog_core::FlowPath;
+use flow_like_types::{async_trait, json::json};
+
+/// Paper sizes in inches, portrait
+const PAPER_SIZES: [(&str, f64, f64); 6] = [
+    ("A3", 11.69, 16.54),
+    ("A4", 8.27, 11.69),
+    ("A5", 5.83, 8.27),
+    ("Letter", 8.5, 11.0),
+    ("Legal", 8.5, 14.0),
+    ("Tabloid", 11.0, 17.0),
+];
+
+#[cfg(all(feature = "execute", feature = "html-to-pdf"))]
+const MM_PER_INCH: f64 = 25.4;
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct HtmlToPdfNode;
+
+impl HtmlToPdfNode {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl NodeLogic for HtmlToPdfNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "html_to_pdf",
+            "HTML to PDF",
+            "Renders an HTML document or an A2UI surface to a PDF file with a headless Chrome. Chrome or Chromium has to be installed where the flow runs",
+            "Image/PDF",
+        );
+        node.add_icon("/flow/icons/path.svg");
+
+        node.add_input_pin(
+            "exec_in",
+            "Input",
+            "Trigger execution",
+            VariableType::Execution,
+        );
+
+        node.add_input_pin(
+            "html",
+            "HTML",
+            "HTML document to render. Leave empty to render the surface instead",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("")));
+
+        node.add_input_pin(
+            "surface",
+            "Surface",
+            "A2UI surface to render when no HTML is given. Interactive components are left out",
+            VariableType::Struct,
+        )
+        .set_schema::<Surface>()
+        .set_options(PinOptions::new().set_enforce_schema(false).build())
+        .set_default_value(Some(json!(null)));
+
+        node.add_input_pin(
+            "data",
+            "Data",
+            "Data model of the surface, an object keyed by the first segment of the bound paths",
+            VariableType::Struct,
+        )
+        .set_default_value(Some(json!({})));
+
+        node.add_input_pin(
+            "path",
+            "Path",
+            "Where to write the PDF",
+            VariableType::Struct,
+        )
+        .set_schema::<FlowPath>()
+        .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node.add_input_pin("page_size", "Page Size", "Paper size", VariableType::String)
+            .set_options(
+                PinOptions::new()
+                    .set_valid_values(
+                        PAPER_SIZES
+                            .iter()
+                            .map(|(name, _, _)| name.to_string())
+                            .collect(),
+                    )
+                    .build(),
+            )
+            .set_default_value(Some(json!("A4")));
+
+        node.add_input_pin(
+            "landscape",
+            "Landscape",
+            "Print in landscape orientation",
+            VariableType::Boolean,
+        )
+        .set_default_value(Some(json!(false)));
+
+        node.add_input_pin(
+            "margin_mm",
+            "Margin (mm)",
+            "Margin on all sides of the page",
+            VariableType::Float,
+        )
+        .set_options(PinOptions::new().set_range((0., 100.)).build())
+        .set_default_value(Some(json!(10.0)));
+
+        node.add_input_pin(
+            "print_background",
+            "Print Background",
+            "Include background colors and images",
+            VariableType::Boolean,
+        )
+        .set_default_value(Some(json!(true)));
+
+        node.add_input_pin(
+            "header",
+            "Header",
+            "HTML template printed at the top of every page. Elements with the classes pageNumber, totalPages, title and date are filled in. Leave header and footer empty to print none",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("")));
+
+        node.add_input_pin(
+            "footer",
+            "Footer",
+            "HTML template printed at the bottom of every page, e.g. <div style=\"font-size:8px;margin:auto\"><span class=\"pageNumber\"></span> / <span class=\"totalPages\"></span></div>",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("")));
+
+        node.add_output_pin(
+            "exec_out",
+            "Output",
+            "Execution done",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin("pdf", "PDF", "The written PDF file", VariableType::Struct)
+            .set_schema::<FlowPath>();
+
+        node
+    }
+
+    #[cfg(all(feature = "execute", feature = "html-to-pdf"))]
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        use flow_like::a2ui::{DataModel, html::render_surface_html};
+        use flow_like_types::Value;
+
+        context.deactivate_exec_pin("exec_out").await?;
+
+        let html: String = context.evaluate_pin("html").await?;
+        let html = if html.trim().is_empty() {
+            let surface: Value = context.evaluate_pin("surface").await?;
+            if surface.is_null() {
+                return Err(flow_like_types::anyhow!(
+                    "Either HTML or a surface has to be given"
+                ));
+            }
+            let surface: Surface = flow_like_types::json::from_value(surface)
+                .map_err(|e| flow_like_types::anyhow!("Invalid surface: {}", e))?;
+
+            let data: Value = context.evaluate_pin("data").await?;
+            let mut model = DataModel::new();
+            if let Value::Object(entries) = data {
+                for (key, value) in entries {
+                    model.set(&key, value);
+                }
+            }
+            render_surface_html(&surface, &model)
+        } else {
+            html
+        };
+
+        let path: FlowPath = context.evaluate_pin("path").await?;
+        let page_size: String = context.evaluate_pin("page_size").await?;
+        let (_, width, height) = PAPER_SIZES
+            .iter()
+            .find(|(name, _, _)| name.eq_ignore_ascii_case(&page_size))
+            .ok_or_else(|| flow_like_types::anyhow!("Unknown page size '{}'", page_size))?;
+        let margin_mm: f64 = context.evaluate_pin("margin_mm").await?;
+        let header: String = context.evaluate_pin("header").await?;
+        let footer: String = context.evaluate_pin("footer").await?;
+
+        let options = PrintOptions {
+            paper_width: *width,
+            paper_height: *height,
+            landscape: context.evaluate_pin("landscape").await?,
+            margin: margin_mm.max(0.0) / MM_PER_INCH,
+            print_background: context.evaluate_pin("print_background").await?,
+            header,
+            footer,
+        };
+
+        let pdf = flow_like_types::tokio::task::spawn_blocking(move || print_pdf(&html, options))
+            .await??;
+
+        path.put(context, pdf, false).await?;
+        context.set_pin_value("pdf", json!(path)).await?;
+        context.activate_exec_pin("exec_out").await?;
+
+        Ok(())
+    }
+
+    #[cfg(not(all(feature = "execute", feature = "html-to-pdf")))]
+    async fn run(&self, _context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        Err(flow_like_types::anyhow!(
+            "HTML to PDF is not enabled. Rebuild with the 'html-to-pdf' feature"
+        ))
+    }
+}
+
+#[cfg(all(feature = "execute", feature = "html-to-pdf"))]
+struct PrintOptions {
+    paper_width: f64,
+    paper_height: f64,
+    landscape: bool,
+    /// Inches
+    margin: f64,
+    print_background: bool,
+    header: String,
+    footer: String,
+}
+
+/// Launches a headless Chrome, loads `html` from a temporary file and prints it.
+/// Blocking, the browser is closed when it is dropped at the end.
+#[cfg(all(feature = "execute", feature = "html-to-pdf"))]
+fn print_pdf(html: &str, options: PrintOptions) -> flow_like_types::Result<Vec<u8>> {
+    use headless_chrome::{Browser, LaunchOptions, types::PrintToPdfOptions};
+
+    let file =
+        std::env::temp_dir().join(format!("flow-like-{}.html", flow_like_types::create_id()));
+    std::fs::write(&file, html)?;
+    let url = format!("file://{}", file.to_string_lossy());
+
+    let printed = (|| {
+        let launch = LaunchOptions::default_builder()
+            .headless(true)
+            .build()
+            .map_err(|e| flow_like_types::anyhow!("Invalid browser options: {}", e))?;
+        let browser = Browser::new(launch).map_err(|e| {
+            flow_like_types::anyhow!("Failed to start Chrome, is it installed? {}", e)
+        })?;
+        let tab = browser
+            .new_tab()
+            .map_err(|e| flow_like_types::anyhow!("Failed to open a tab: {}", e))?;
+        tab.navigate_to(&url)
+            .and_then(|tab| tab.wait_until_navigated())
+            .map_err(|e| flow_like_types::anyhow!("Failed to load the HTML: {}", e))?;
+
+        let with_header_footer = !options.header.is_empty() || !options.footer.is_empty();
+        // Chrome prints its own default header and footer for empty templates
+        let template = |html: String| {
+            if html.is_empty() {
+                "<span></span>".to_string()
+            } else {
+                html
+            }
+        };
+
+        tab.print_to_pdf(Some(PrintToPdfOptions {
+            landscape: Some(options.landscape),
+            display_header_footer: Some(with_header_footer),
+            print_background: Some(options.print_background),
+            paper_width: Some(options.paper_width),
+            paper_height: Some(options.paper_height),
+            margin_top: Some(options.margin),
+            margin_bottom: Some(options.margin),
+            margin_left: Some(options.margin),
+            margin_right: Some(options.margin),
+            header_template: with_header_footer.then(|| template(options.header)),
+            footer_template: with_header_footer.then(|| template(options.footer)),
+            ..Default::default()
+        }))
+        .map_err(|e| flow_like_types::anyhow!("Failed to print the PDF: {}", e))
+    })();
+
+    let _ = std::fs::remove_file(&file);
+    printed
+}
@@ -9,6 +9,7 @@ use hayro::{Pdf, Pixmap};
 #[cfg(feature = "execute")]
 use std::sync::Arc;
 
+pub mod html_to_pdf;
 pub mod page_count;
 pub mod page_to_image;
 pub mod pdf_to_images;
@@ -0,0 +1,660 @@
+//! Node for **Time-Series Forecasting**
+//!
+//! `ForecastNode` extends an ordered series of `(timestamp, value)` points by `horizon` steps
+//! and returns the point forecast together with the bounds of a confidence interval.
+//!
+//! Methods:
+//! - **Naive**: repeats the last observation, or the last season if the series is seasonal
+//! - **Moving Average**: the mean of the last `window` values, a full season by default
+//! - **Holt-Winters**: additive exponential smoothing of level, trend and season. The
+//!   smoothing factors are chosen by a grid search minimizing the one-step-ahead error.
+//!
+//! Resampling: the forecast needs evenly spaced values, so the input is first moved onto a
+//! regular grid. The grid starts at the earliest timestamp and uses the given interval or,
+//! if none is given, the median distance between consecutive timestamps. Every point is
+//! assigned to its nearest grid slot, points sharing a slot are averaged and empty slots
+//! are filled by linear interpolation between their nearest filled neighbours.
+//!
+//! Intervals are derived from the standard deviation of the in-sample one-step errors,
+//! widened per horizon step as the respective method accumulates uncertainty.
+
+use crate::ml::TimeSeriesPoint;
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic, NodeScores},
+    pin::{PinOptions, ValueType},
+    variable::VariableType,
+};
+use flow_like_types::{Result, anyhow, async_trait, json::json};
+use std::str::FromStr;
+
+/// Upper bound for the resampled series, protects against a tiny interval
+const MAX_GRID_POINTS: usize = 100_000;
+/// Upper bound for the number of forecasted steps
+pub const MAX_HORIZON: usize = 10_000;
+/// Minimum autocorrelation a lag needs to be detected as season length
+const MIN_SEASONAL_AUTOCORRELATION: f64 = 0.3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForecastMethod {
+    Naive,
+    MovingAverage,
+    HoltWinters,
+}
+
+impl FromStr for ForecastMethod {
+    type Err = flow_like_types::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "Naive" => Ok(Self::Naive),
+            "Moving Average" => Ok(Self::MovingAverage),
+            "Holt-Winters" => Ok(Self::HoltWinters),
+            other => Err(anyhow!("Unknown forecast method `{other}`")),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ForecastOptions {
+    pub method: ForecastMethod,
+    /// Number of grid steps to forecast
+    pub horizon: usize,
+    /// Season length in grid steps, `None` detects it, `Some(1)` disables seasonality
+    pub season_length: Option<usize>,
+    /// Window of the moving average, `None` uses the season length
+    pub window: Option<usize>,
+    /// Grid spacing in timestamp units, `None` uses the median spacing of the input
+    pub interval: Option<i64>,
+    /// Coverage of the interval between the bounds, e.g. 0.95
+    pub confidence: f64,
+}
+
+/// Values of a series on an evenly spaced grid
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegularSeries {
+    pub start: i64,
+    pub step: i64,
+    pub values: Vec<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Forecast {
+    pub timestamps: Vec<i64>,
+    pub forecast: Vec<f64>,
+    pub lower: Vec<f64>,
+    pub upper: Vec<f64>,
+    /// Season length the forecast used, 1 if the series is not seasonal
+    pub season_length: usize,
+}
+
+/// Moves the points onto a regular grid as described in the module documentation.
+pub fn resample(points: &[TimeSeriesPoint], interval: Option<i64>) -> Result<RegularSeries> {
+    let mut points: Vec<TimeSeriesPoint> = points
+        .iter()
+        .filter(|point| point.value.is_finite())
+        .copied()
+        .collect();
+    if points.len() < 2 {
+        return Err(anyhow!(
+            "A forecast needs at least 2 points with a finite value"
+        ));
+    }
+    points.sort_by_key(|point| point.timestamp);
+
+    let step = match interval {
+        Some(step) if step > 0 => step,
+        Some(step) => return Err(anyhow!("Interval must be positive, got {step}")),
+        None => {
+            median_step(&points).ok_or_else(|| anyhow!("All points share the same timestamp"))?
+        }
+    };
+
+    let start = points[0].timestamp;
+    let slot_of = |timestamp: i64| {
+        let offset = (timestamp as i128 - start as i128) as u128;
+        ((offset + step as u128 / 2) / step as u128) as usize
+    };
+    let len = slot_of(points[points.len() - 1].timestamp) + 1;
+    if len > MAX_GRID_POINTS {
+        return Err(anyhow!(
+            "Resampling would create {len} points (max {MAX_GRID_POINTS}), use a larger interval"
+        ));
+    }
+
+    let mut sums = vec![0.0; len];
+    let mut counts = vec![0usize; len];
+    for point in &points {
+        let slot = slot_of(point.timestamp);
+        sums[slot] += point.value;
+        counts[slot] += 1;
+    }
+
+    // The first and the last slot always hold a point
+    let filled: Vec<usize> = (0..len).filter(|&i| counts[i] > 0).collect();
+    let mut values: Vec<f64> = sums
+        .iter()
+        .zip(&counts)
+        .map(|(sum, &count)| if count > 0 { sum / count as f64 } else { 0.0 })
+        .collect();
+    for pair in filled.windows(2) {
+        let (from, to) = (pair[0], pair[1]);
+        for slot in from + 1..to {
+            let t = (slot - from) as f64 / (to - from) as f64;
+            values[slot] = values[from] + t * (values[to] - values[from]);
+        }
+    }
+
+    Ok(RegularSeries {
+        start,
+        step,
+        values,
+    })
+}
+
+fn median_step(sorted: &[TimeSeriesPoint]) -> Option<i64> {
+    let mut steps: Vec<i64> = sorted
+        .windows(2)
+        .map(|pair| pair[1].timestamp - pair[0].timestamp)
+        .filter(|&step| step > 0)
+        .collect();
+    if steps.is_empty() {
+        return None;
+    }
+    steps.sort_unstable();
+    Some(steps[steps.len() / 2])
+}
+
+/// Detects the season length as the lag with the strongest autocorrelation of the
+/// differenced series. Returns 1 if no lag is correlated enough.
+pub fn detect_season_length(values: &[f64]) -> usize {
+    // Differencing removes a linear trend that would correlate every lag
+    let diffs: Vec<f64> = values.windows(2).map(|pair| pair[1] - pair[0]).collect();
+    let n = diffs.len();
+    if n < 4 {
+        return 1;
+    }
+    let mean = diffs.iter().sum::<f64>() / n as f64;
+    let variance: f64 = diffs.iter().map(|d| (d - mean).powi(2)).sum();
+    if variance <= f64::EPSILON {
+        return 1;
+    }
+
+    // Holt-Winters needs two full seasons to initialize
+    let max_lag = values.len() / 2;
+    let mut best = (1, MIN_SEASONAL_AUTOCORRELATION);
+    for lag in 2..=max_lag.min(n - 1) {
+        let covariance: f64 = (lag..n)
+            .map(|i| (diffs[i] - mean) * (diffs[i - lag] - mean))
+            .sum();
+        let correlation = covariance / variance;
+        if correlation > best.1 {
+            best = (lag, correlation);
+        }
+    }
+    best.0
+}
+
+/// Forecasts `options.horizon` steps past the end of the resampled series.
+pub fn forecast(points: &[TimeSeriesPoint], options: &ForecastOptions) -> Result<Forecast> {
+    if options.horizon == 0 || options.horizon > MAX_HORIZON {
+        return Err(anyhow!(
+            "Horizon must be between 1 and {MAX_HORIZON}, got {}",
+            options.horizon
+        ));
+    }
+    if !(options.confidence > 0.0 && options.confidence < 1.0) {
+        return Err(anyhow!(
+            "Confidence must be between 0 and 1, got {}",
+            options.confidence
+        ));
+    }
+
+    let series = resample(points, options.interval)?;
+    let values = &series.values;
+    let season = match options.season_length {
+        Some(0) | None => detect_season_length(values),
+        Some(season) => season,
+    };
+
+    let (forecast, deviations) = match options.method {
+        ForecastMethod::Naive => naive(values, season, options.horizon)?,
+        ForecastMethod::MovingAverage => {
+            let window = options.window.unwrap_or(season.max(2));
+            moving_average(values, window, options.horizon)?
+        }
+        ForecastMethod::HoltWinters => holt_winters(values, season, options.horizon)?,
+    };
+
+    let z = normal_quantile(0.5 + options.confidence / 2.0);
+    let last = series.start + series.step * (values.len() as i64 - 1);
+    Ok(Forecast {
+        timestamps: (1..=options.horizon as i64)
+            .map(|h| last + series.step * h)
+            .collect(),
+        lower: forecast
+            .iter()
+            .zip(&deviations)
+            .map(|(f, d)| f - z * d)
+            .collect(),
+        upper: forecast
+            .iter()
+            .zip(&deviations)
+            .map(|(f, d)| f + z * d)
+            .collect(),
+        forecast,
+        season_length: season,
+    })
+}
+
+/// Root mean square of the one-step errors
+fn residual_deviation(errors: impl Iterator<Item = f64>) -> f64 {
+    let (sum, count) = errors.fold((0.0, 0usize), |(sum, count), e| (sum + e * e, count + 1));
+    if count == 0 {
+        0.0
+    } else {
+        (sum / count as f64).sqrt()
+    }
+}
+
+/// Repeats the last season, the last value if `season` is 1. The error grows with every
+/// season the forecast reaches into.
+fn naive(values: &[f64], season: usize, horizon: usize) -> Result<(Vec<f64>, Vec<f64>)> {
+    let n = values.len();
+    if season >= n {
+        return Err(anyhow!(
+            "Naive forecast with season length {season} needs more than {season} points, got {n}"
+        ));
+    }
+    let sigma = residual_deviation((season..n).map(|t| values[t] - values[t - season]));
+    let forecast = (0..horizon)
+        .map(|h| values[n - season + h % season])
+        .collect();
+    let deviations = (0..horizon)
+        .map(|h| sigma * ((h / season + 1) as f64).sqrt())
+        .collect();
+    Ok((forecast, deviations))
+}
+
+/// Flat forecast at the mean of the last `window` values
+fn moving_average(values: &[f64], window: usize, horizon: usize) -> Result<(Vec<f64>, Vec<f64>)> {
+    let n = values.len();
+    if window == 0 || window >= n {
+        return Err(anyhow!(
+            "Moving average window must be between 1 and {}, got {window}",
+            n - 1
+        ));
+    }
+    let mean = |range: std::ops::Range<usize>| values[range].iter().sum::<f64>() / window as f64;
+    let sigma = residual_deviation((window..n).map(|t| values[t] - mean(t - window..t)));
+    Ok((vec![mean(n - window..n); horizon], vec![sigma; horizon]))
+}
+
+/// Smoothing factors tried for level, trend and season
+const SMOOTHING_GRID: [f64; 10] = [0.05, 0.15, 0.25, 0.35, 0.45, 0.55, 0.65, 0.75, 0.85, 0.95];
+
+struct HoltWintersFit {
+    alpha: f64,
+    beta: f64,
+    gamma: f64,
+    level: f64,
+    trend: f64,
+    /// Seasonal components of the last season, oldest first
+    seasonal: Vec<f64>,
+    sse: f64,
+    errors: usize,
+}
+
+/// Runs additive Holt-Winters over the series, `season` 1 reduces it to Holt's linear
+/// trend method.
+fn fit_holt_winters(
+    values: &[f64],
+    season: usize,
+    alpha: f64,
+    beta: f64,
+    gamma: f64,
+) -> HoltWintersFit {
+    let (mut level, mut trend, mut seasonal, first) = if season > 1 {
+        let first_mean = values[..season].iter().sum::<f64>() / season as f64;
+        let second_mean = values[season..2 * season].iter().sum::<f64>() / season as f64;
+        let seasonal: Vec<f64> = values[..season].iter().map(|v| v - first_mean).collect();
+        // The level is centered in the first season, move it to its last step
+        let trend = (second_mean - first_mean) / season as f64;
+        let level = first_mean + trend * (season - 1) as f64 / 2.0;
+        (level, trend, seasonal, season)
+    } else {
+        (values[0], values[1] - values[0], vec![0.0], 1)
+    };
+
+    let mut sse = 0.0;
+    for (t, &value) in values.iter().enumerate().skip(first) {
+        let index = t % season;
+        let previous_season = seasonal[index];
+        let error = value - (level + trend + previous_season);
+        sse += error * error;
+
+        let previous_level = level;
+        level = alpha * (value - previous_season) + (1.0 - alpha) * (level + trend);
+        trend = beta * (level - previous_level) + (1.0 - beta) * trend;
+        if season > 1 {
+            seasonal[index] = gamma * (value - level) + (1.0 - gamma) * previous_season;
+        }
+    }
+    seasonal.rotate_left(values.len() % season);
+
+    HoltWintersFit {
+        alpha,
+        beta,
+        gamma,
+        level,
+        trend,
+        seasonal,
+        sse,
+        errors: values.len() - first,
+    }
+}
+
+fn holt_winters(values: &[f64], season: usize, horizon: usize) -> Result<(Vec<f64>, Vec<f64>)> {
+    let n = values.len();
+    let required = if season > 1 { 2 * season } else { 3 };
+    if n < required {
+        return Err(anyhow!(
+            "Holt-Winters with season length {season} needs at least {required} points, got {n}"
+        ));
+    }
+
+    let gammas: &[f64] = if season > 1 { &SMOOTHING_GRID } else { &[0.0] };
+    let mut best: Option<HoltWintersFit> = None;
+    for &alpha in &SMOOTHING_GRID {
+        for &beta in &SMOOTHING_GRID {
+            for &gamma in gammas {
+                let fit = fit_holt_winters(values, season, alpha, beta, gamma);
+                if best.as_ref().is_none_or(|best| fit.sse < best.sse) {
+                    best = Some(fit);
+                }
+            }
+        }
+    }
+    let fit = best.ok_or_else(|| anyhow!("Holt-Winters could not be fitted"))?;
+    let sigma = if fit.errors > 0 {
+        (fit.sse / fit.errors as f64).sqrt()
+    } else {
+        0.0
+    };
+
+    let forecast = (1..=horizon)
+        .map(|h| fit.level + h as f64 * fit.trend + fit.seasonal[(h - 1) % fit.seasonal.len()])
+        .collect();
+
+    // Variance of the h-step error of the additive model: σ²(1 + Σ c_j²) over j < h
+    let mut accumulated: f64 = 1.0;
+    let mut deviations = Vec::with_capacity(horizon);
+    for h in 1..=horizon {
+        deviations.push(sigma * accumulated.sqrt());
+        let j = h as f64;
+        let seasonal = if season > 1 && h % season == 0 {
+            fit.gamma
+        } else {
+            0.0
+        };
+        accumulated += (fit.alpha * (1.0 + j * fit.beta) + seasonal).powi(2);
+    }
+
+    Ok((forecast, deviations))
+}
+
+/// Inverse of the standard normal CDF (Acklam's rational approximation)
+pub fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969683028665376e1,
+        2.209460984245205e2,
+        -2.759285104469687e2,
+        1.383577518672690e2,
+        -3.066479806614716e1,
+        2.506628277459239,
+    ];
+    const B: [f64; 5] = [
+        -5.447609879822406e1,
+        1.615858368580409e2,
+        -1.556989798598866e2,
+        6.680131188771972e1,
+        -1.328068155288572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-3,
+        -3.223964580411365e-1,
+        -2.400758277161838,
+        -2.549732539343734,
+        4.374664141464968,
+        2.938163982698783,
+    ];
+    const D: [f64; 4] = [
+        7.784695709041462e-3,
+        3.224671290700398e-1,
+        2.445134137142996,
+        3.754408661907416,
+    ];
+    const LOW: f64 = 0.02425;
+
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+    if p < LOW {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p > 1.0 - LOW {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    }
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct ForecastNode {}
+
+impl ForecastNode {
+    pub fn new() -> Self {
+        ForecastNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for ForecastNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "ml_forecast",
+            "Forecast Time Series",
+            "Forecasts the next values of a time series with confidence bounds. Irregular timestamps are resampled to a regular grid first",
+            "AI/ML/Forecasting",
+        );
+        node.add_icon("/flow/icons/chart-network.svg");
+
+        node.set_scores(
+            NodeScores::new()
+                .set_privacy(9)
+                .set_security(9)
+                .set_performance(8)
+                .set_governance(8)
+                .set_reliability(8)
+                .set_cost(10)
+                .build(),
+        );
+
+        node.add_input_pin(
+            "exec_in",
+            "Input",
+            "Execution trigger that starts the forecast",
+            VariableType::Execution,
+        );
+
+        node.add_input_pin(
+            "series",
+            "Series",
+            "Observed points, sorted or not. Points sharing a grid slot are averaged",
+            VariableType::Struct,
+        )
+        .set_schema::<TimeSeriesPoint>()
+        .set_value_type(ValueType::Array)
+        .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node.add_input_pin(
+            "horizon",
+            "Horizon",
+            "Number of grid steps to forecast",
+            VariableType::Integer,
+        )
+        .set_options(
+            PinOptions::new()
+                .set_range((1., MAX_HORIZON as f64))
+                .build(),
+        )
+        .set_default_value(Some(json!(10)));
+
+        node.add_input_pin(
+            "method",
+            "Method",
+            "Naive repeats the last value (or season), Moving Average the recent mean, Holt-Winters follows level, trend and season",
+            VariableType::String,
+        )
+        .set_options(
+            PinOptions::new()
+                .set_valid_values(vec![
+                    "Naive".to_string(),
+                    "Moving Average".to_string(),
+                    "Holt-Winters".to_string(),
+                ])
+                .build(),
+        )
+        .set_default_value(Some(json!("Holt-Winters")));
+
+        node.add_input_pin(
+            "season_length",
+            "Season Length",
+            "Length of a season in grid steps, 0 detects it from the data, 1 disables seasonality",
+            VariableType::Integer,
+        )
+        .set_default_value(Some(json!(0)));
+
+        node.add_input_pin(
+            "window",
+            "Window",
+            "Values averaged by the Moving Average method, 0 uses the season length",
+            VariableType::Integer,
+        )
+        .set_default_value(Some(json!(0)));
+
+        node.add_input_pin(
+            "interval",
+            "Interval",
+            "Spacing of the grid in timestamp units, 0 uses the median spacing of the series",
+            VariableType::Integer,
+        )
+        .set_default_value(Some(json!(0)));
+
+        node.add_input_pin(
+            "confidence",
+            "Confidence",
+            "Probability the true value lies between the lower and upper bound",
+            VariableType::Float,
+        )
+        .set_options(PinOptions::new().set_range((0.5, 0.999)).build())
+        .set_default_value(Some(json!(0.95)));
+
+        node.add_output_pin(
+            "exec_out",
+            "Done",
+            "Activated once the forecast is computed",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "timestamps",
+            "Timestamps",
+            "Timestamp of every forecasted step",
+            VariableType::Integer,
+        )
+        .set_value_type(ValueType::Array);
+
+        node.add_output_pin(
+            "forecast",
+            "Forecast",
+            "Point forecast of every step",
+            VariableType::Float,
+        )
+        .set_value_type(ValueType::Array);
+
+        node.add_output_pin(
+            "lower",
+            "Lower Bound",
+            "Lower bound of the confidence interval of every step",
+            VariableType::Float,
+        )
+        .set_value_type(ValueType::Array);
+
+        node.add_output_pin(
+            "upper",
+            "Upper Bound",
+            "Upper bound of the confidence interval of every step",
+            VariableType::Float,
+        )
+        .set_value_type(ValueType::Array);
+
+        node.add_output_pin(
+            "season_length",
+            "Season Length",
+            "Season length the forecast used, 1 if the series is not seasonal",
+            VariableType::Integer,
+        );
+
+        node
+    }
+
+    #[cfg(feature = "execute")]
+    async fn run(&self, context: &mut ExecutionContext) -> Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+
+        let series: Vec<TimeSeriesPoint> = context.evaluate_pin("series").await?;
+        let horizon: i64 = context.evaluate_pin("horizon").await?;
+        let method: String = context.evaluate_pin("method").await?;
+        let season_length: i64 = context.evaluate_pin("season_length").await?;
+        let window: i64 = context.evaluate_pin("window").await?;
+        let interval: i64 = context.evaluate_pin("interval").await?;
+        let confidence: f64 = context.evaluate_pin("confidence").await?;
+
+        let options = ForecastOptions {
+            method: method.parse()?,
+            horizon: horizon.max(0) as usize,
+            season_length: (season_length > 0).then_some(season_length as usize),
+            window: (window > 0).then_some(window as usize),
+            interval: (interval > 0).then_some(interval),
+            confidence,
+        };
+        let result = forecast(&series, &options)?;
+
+        context
+            .set_pin_value("timestamps", json!(result.timestamps))
+            .await?;
+        context
+            .set_pin_value("forecast", json!(result.forecast))
+            .await?;
+        context.set_pin_value("lower", json!(result.lower)).await?;
+        context.set_pin_value("upper", json!(result.upper)).await?;
+        context
+            .set_pin_value("season_length", json!(result.season_length))
+            .await?;
+
+        context.activate_exec_pin("exec_out").await?;
+        Ok(())
+    }
+
+    #[cfg(not(feature = "execute"))]
+    async fn run(&self, _context: &mut ExecutionContext) -> Result<()> {
+        Err(flow_like_types::anyhow!(
+            "ML execution requires the 'execute' feature. Rebuild with --features execute"
+        ))
+    }
+}
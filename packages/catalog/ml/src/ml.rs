@@ -42,6 +42,7 @@ use std::sync::Arc;
 pub mod classification;
 pub mod clustering;
 pub mod dataset;
+pub mod forecasting;
 pub mod load;
 pub mod load_binary;
 pub mod metrics;
@@ -79,6 +80,46 @@ pub struct LinearCoefficients {
     pub n_features: usize,
 }
 
+/// A fitted PCA that can be applied to new data of the same width
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct PcaModel {
+    /// Mean of every input feature, subtracted before projecting
+    pub mean: Vec<f64>,
+    /// Principal axes (n_components × n_features), strongest first
+    pub components: Vec<Vec<f64>>,
+    /// Variance of the training data along each component
+    pub explained_variance: Vec<f64>,
+    /// Share of the total variance each component explains
+    pub explained_variance_ratio: Vec<f64>,
+}
+
+impl PcaModel {
+    /// Projects `matrix` (rows × n_features) onto the components.
+    pub fn transform(&self, matrix: &Array2<f64>) -> Result<Array2<f64>> {
+        if matrix.ncols() != self.mean.len() {
+            return Err(anyhow!(
+                "PCA was fit on {} features, the input has {}",
+                self.mean.len(),
+                matrix.ncols()
+            ));
+        }
+        let components = Array2::from_shape_vec(
+            (self.components.len(), self.mean.len()),
+            self.components.iter().flatten().copied().collect(),
+        )?;
+        let centered = matrix - &Array1::from(self.mean.clone());
+        Ok(centered.dot(&components.t()))
+    }
+}
+
+/// A single observation of a time series
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct TimeSeriesPoint {
+    /// Time of the observation, any integer unit (e.g. Unix milliseconds) used consistently
+    pub timestamp: i64,
+    pub value: f64,
+}
+
 /// Confusion matrix result with classification metrics
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ConfusionMatrixResult {
@@ -693,6 +734,25 @@ pub fn values_to_array2_f64(values: &[Value], attr: &str) -> Result<Array2<f64>,
     Ok(Array2::from_shape_vec((rows, cols), flat)?)
 }
 
+/// Loads a matrix given as array of numeric rows, all rows need the same length
+pub fn rows_to_array2_f64(rows: &[Vec<f64>]) -> Result<Array2<f64>> {
+    let cols = rows
+        .first()
+        .map(Vec::len)
+        .ok_or_else(|| anyhow!("Matrix has no rows"))?;
+    if cols == 0 {
+        return Err(anyhow!("Row 0: expected at least one value"));
+    }
+    if let Some((r, row)) = rows.iter().enumerate().find(|(_, row)| row.len() != cols) {
+        return Err(anyhow!(
+            "Row {r}: inconsistent length (expected {cols}, got {})",
+            row.len()
+        ));
+    }
+    let flat = rows.iter().flatten().copied().collect();
+    Ok(Array2::from_shape_vec((rows.len(), cols), flat)?)
+}
+
 /// For a column `attr` in Vec<Values> attempt to load all rows as Array1<f64>
 pub fn values_to_array1_f64(values: &[Value], attr: &str) -> Result<Array1<f64>> {
     let mut flat = Vec::with_capacity(values.len());
@@ -1,15 +1,19 @@
-//! Node for **PCA Dimensionality Reduction**
+//! Nodes for **PCA Dimensionality Reduction**
 //!
-//! This node loads a dataset (currently from a database source), transforms it using
-//! Principal Component Analysis (PCA) to reduce dimensionality.
+//! `FitPcaNode` loads a dataset (currently from a database source), transforms it using
+//! Principal Component Analysis (PCA) to reduce dimensionality. `PcaNode` works on a matrix
+//! and returns the fitted model, which `PcaTransformNode` applies to new data.
 
+use crate::ml::PcaModel;
 #[cfg(feature = "execute")]
-use crate::ml::{MAX_ML_PREDICTION_RECORDS, make_new_field, values_to_array2_f64};
+use crate::ml::{
+    MAX_ML_PREDICTION_RECORDS, make_new_field, rows_to_array2_f64, values_to_array2_f64,
+};
 use flow_like::flow::{
     board::Board,
     execution::{LogLevel, context::ExecutionContext},
     node::{Node, NodeLogic, NodeScores},
-    pin::PinOptions,
+    pin::{PinOptions, ValueType},
     variable::VariableType,
 };
 #[cfg(feature = "execute")]
@@ -30,6 +34,8 @@ use linfa::traits::{Fit, Predict};
 #[cfg(feature = "execute")]
 use linfa_reduction::Pca;
 #[cfg(feature = "execute")]
+use ndarray::{Array2, Axis};
+#[cfg(feature = "execute")]
 use std::collections::HashSet;
 use std::sync::Arc;
 
@@ -107,7 +113,7 @@ impl NodeLogic for FitPcaNode {
             "Variance explained by each principal component",
             VariableType::Float,
         )
-        .set_value_type(flow_like::flow::pin::ValueType::Array);
+        .set_value_type(ValueType::Array);
 
         node
     }
@@ -301,3 +307,294 @@ impl NodeLogic for FitPcaNode {
         }
     }
 }
+
+/// Fits PCA on `matrix` (rows × features) and returns the model together with the
+/// transformed training data.
+#[cfg(feature = "execute")]
+pub fn fit_pca(matrix: &Array2<f64>, n_components: usize) -> Result<(PcaModel, Array2<f64>)> {
+    let (rows, features) = matrix.dim();
+    if rows < 2 {
+        return Err(anyhow!("PCA needs at least 2 rows, got {}", rows));
+    }
+    let max_components = rows.min(features);
+    if n_components == 0 || n_components > max_components {
+        return Err(anyhow!(
+            "Components must be between 1 and {} for {} rows of {} features, got {}",
+            max_components,
+            rows,
+            features,
+            n_components
+        ));
+    }
+
+    let dataset = DatasetBase::from(matrix.clone());
+    let pca: Pca<f64> = Pca::params(n_components).fit(&dataset)?;
+    let transformed: Array2<f64> = pca.predict(matrix);
+
+    let mean = matrix
+        .mean_axis(Axis(0))
+        .ok_or_else(|| anyhow!("Matrix has no rows"))?;
+
+    // linfa keeps the axes private. The projection is linear, so projecting the mean and
+    // the mean shifted by every unit vector recovers them.
+    let probes = Array2::from_shape_fn((features + 1, features), |(i, j)| {
+        mean[j] + if i == j { 1.0 } else { 0.0 }
+    });
+    let projected: Array2<f64> = pca.predict(&probes);
+    let origin = projected.row(features);
+    let components = (0..n_components)
+        .map(|c| {
+            (0..features)
+                .map(|j| projected[[j, c]] - origin[c])
+                .collect()
+        })
+        .collect();
+
+    let explained_variance = transformed.var_axis(Axis(0), 1.0).to_vec();
+    let total_variance = matrix.var_axis(Axis(0), 1.0).sum();
+    let explained_variance_ratio = explained_variance
+        .iter()
+        .map(|variance| {
+            if total_variance > 0.0 {
+                variance / total_variance
+            } else {
+                0.0
+            }
+        })
+        .collect();
+
+    let model = PcaModel {
+        mean: mean.to_vec(),
+        components,
+        explained_variance,
+        explained_variance_ratio,
+    };
+    Ok((model, transformed))
+}
+
+#[cfg(feature = "execute")]
+fn matrix_rows(matrix: &Array2<f64>) -> Vec<Vec<f64>> {
+    matrix.outer_iter().map(|row| row.to_vec()).collect()
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct PcaNode {}
+
+impl PcaNode {
+    pub fn new() -> Self {
+        PcaNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for PcaNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "pca_fit_transform",
+            "PCA (Matrix)",
+            "Fits a Principal Component Analysis on a matrix and reduces it to the chosen number of components",
+            "AI/ML/Reduction",
+        );
+        node.add_icon("/flow/icons/chart-network.svg");
+
+        node.set_scores(
+            NodeScores::new()
+                .set_privacy(9)
+                .set_security(9)
+                .set_performance(7)
+                .set_governance(8)
+                .set_reliability(9)
+                .set_cost(9)
+                .build(),
+        );
+
+        node.add_input_pin(
+            "exec_in",
+            "Input",
+            "Execution trigger that begins PCA fitting",
+            VariableType::Execution,
+        );
+
+        node.add_input_pin(
+            "matrix",
+            "Matrix",
+            "Array of numeric rows, e.g. embeddings. All rows need the same length",
+            VariableType::Generic,
+        )
+        .set_value_type(ValueType::Array);
+
+        node.add_input_pin(
+            "n_components",
+            "Components",
+            "Number of principal components to keep",
+            VariableType::Integer,
+        )
+        .set_options(PinOptions::new().set_range((1., 1000.)).build())
+        .set_default_value(Some(json!(2)));
+
+        node.add_output_pin(
+            "exec_out",
+            "Done",
+            "Activated once PCA fitting completes",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "transformed",
+            "Transformed",
+            "The input rows reduced to the chosen number of components",
+            VariableType::Generic,
+        )
+        .set_value_type(ValueType::Array);
+
+        node.add_output_pin(
+            "explained_variance_ratio",
+            "Explained Variance Ratio",
+            "Share of the total variance each component explains",
+            VariableType::Float,
+        )
+        .set_value_type(ValueType::Array);
+
+        node.add_output_pin(
+            "model",
+            "Model",
+            "Fitted PCA, apply it to new data with 'PCA Transform'",
+            VariableType::Struct,
+        )
+        .set_schema::<PcaModel>()
+        .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node
+    }
+
+    #[cfg(feature = "execute")]
+    async fn run(&self, context: &mut ExecutionContext) -> Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+        let rows: Vec<Vec<f64>> = context.evaluate_pin("matrix").await?;
+        let n_components: i64 = context.evaluate_pin("n_components").await?;
+
+        let matrix = rows_to_array2_f64(&rows)?;
+        let t0 = std::time::Instant::now();
+        let (model, transformed) = fit_pca(&matrix, n_components.max(0) as usize)?;
+        context.log_message(&format!("Fit PCA: {:?}", t0.elapsed()), LogLevel::Debug);
+
+        context
+            .set_pin_value("transformed", json!(matrix_rows(&transformed)))
+            .await?;
+        context
+            .set_pin_value(
+                "explained_variance_ratio",
+                json!(model.explained_variance_ratio),
+            )
+            .await?;
+        context.set_pin_value("model", json!(model)).await?;
+
+        context.activate_exec_pin("exec_out").await?;
+        Ok(())
+    }
+
+    #[cfg(not(feature = "execute"))]
+    async fn run(&self, _context: &mut ExecutionContext) -> Result<()> {
+        Err(flow_like_types::anyhow!(
+            "ML execution requires the 'execute' feature. Rebuild with --features execute"
+        ))
+    }
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct PcaTransformNode {}
+
+impl PcaTransformNode {
+    pub fn new() -> Self {
+        PcaTransformNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for PcaTransformNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "pca_transform",
+            "PCA Transform",
+            "Reduces a matrix with a PCA fitted earlier, possibly in another flow",
+            "AI/ML/Reduction",
+        );
+        node.add_icon("/flow/icons/chart-network.svg");
+
+        node.set_scores(
+            NodeScores::new()
+                .set_privacy(9)
+                .set_security(9)
+                .set_performance(9)
+                .set_governance(8)
+                .set_reliability(9)
+                .set_cost(10)
+                .build(),
+        );
+
+        node.add_input_pin(
+            "exec_in",
+            "Input",
+            "Execution trigger that begins the transformation",
+            VariableType::Execution,
+        );
+
+        node.add_input_pin(
+            "model",
+            "Model",
+            "PCA fitted by 'PCA (Matrix)'",
+            VariableType::Struct,
+        )
+        .set_schema::<PcaModel>()
+        .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node.add_input_pin(
+            "matrix",
+            "Matrix",
+            "Array of numeric rows with as many values as the data the model was fit on",
+            VariableType::Generic,
+        )
+        .set_value_type(ValueType::Array);
+
+        node.add_output_pin(
+            "exec_out",
+            "Done",
+            "Activated once the transformation completes",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "transformed",
+            "Transformed",
+            "The input rows reduced to the components of the model",
+            VariableType::Generic,
+        )
+        .set_value_type(ValueType::Array);
+
+        node
+    }
+
+    #[cfg(feature = "execute")]
+    async fn run(&self, context: &mut ExecutionContext) -> Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+        let model: PcaModel = context.evaluate_pin("model").await?;
+        let rows: Vec<Vec<f64>> = context.evaluate_pin("matrix").await?;
+
+        let transformed = model.transform(&rows_to_array2_f64(&rows)?)?;
+        context
+            .set_pin_value("transformed", json!(matrix_rows(&transformed)))
+            .await?;
+
+        context.activate_exec_pin("exec_out").await?;
+        Ok(())
+    }
+
+    #[cfg(not(feature = "execute"))]
+    async fn run(&self, _context: &mut ExecutionContext) -> Result<()> {
+        Err(flow_like_types::anyhow!(
+            "ML execution requires the 'execute' feature. Rebuild with --features execute"
+        ))
+    }
+}
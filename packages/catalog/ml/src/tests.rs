@@ -4,10 +4,14 @@
 
 #[cfg(test)]
 mod tests {
+    use crate::ml::forecasting::forecast::{
+        ForecastMethod, ForecastOptions, detect_season_length, forecast, normal_quantile, resample,
+    };
     use crate::ml::{
         AccuracyMetrics, ConfusionMatrixResult, GridSearchEntry, GridSearchResult, KMeansCentroids,
-        LinearCoefficients, ParameterSpec, RegressionMetrics, make_new_field, values_to_array1_f64,
-        values_to_array1_target, values_to_array1_usize, values_to_array2_f64,
+        LinearCoefficients, ParameterSpec, PcaModel, RegressionMetrics, TimeSeriesPoint,
+        make_new_field, rows_to_array2_f64, values_to_array1_f64, values_to_array1_target,
+        values_to_array1_usize, values_to_array2_f64,
     };
     use flow_like_types::Value;
     use flow_like_types::json::{self, json};
@@ -69,6 +73,40 @@ mod tests {
         assert!(result.is_err());
     }
 
+    // ============================================================================
+    // rows_to_array2_f64 tests
+    // ============================================================================
+
+    #[test]
+    fn test_rows_to_array2_f64() {
+        let result = rows_to_array2_f64(&[vec![1.0, 2.0], vec![3.0, 4.0], vec![5.0, 6.0]]).unwrap();
+        assert_eq!(result.shape(), &[3, 2]);
+        assert_eq!(result[[2, 1]], 6.0);
+
+        let ragged = rows_to_array2_f64(&[vec![1.0, 2.0], vec![3.0]]).unwrap_err();
+        assert!(ragged.to_string().contains("Row 1"));
+        assert!(rows_to_array2_f64(&[]).is_err());
+        assert!(rows_to_array2_f64(&[vec![], vec![]]).is_err());
+    }
+
+    #[test]
+    fn test_pca_model_transform() {
+        let model = PcaModel {
+            mean: vec![1.0, 2.0],
+            components: vec![vec![0.0, 1.0]],
+            explained_variance: vec![4.0],
+            explained_variance_ratio: vec![0.8],
+        };
+        let matrix = rows_to_array2_f64(&[vec![1.0, 5.0], vec![3.0, 0.0]]).unwrap();
+        let transformed = model.transform(&matrix).unwrap();
+        assert_eq!(transformed.shape(), &[2, 1]);
+        assert_eq!(transformed[[0, 0]], 3.0);
+        assert_eq!(transformed[[1, 0]], -2.0);
+
+        let too_wide = rows_to_array2_f64(&[vec![1.0, 2.0, 3.0]]).unwrap();
+        assert!(model.transform(&too_wide).is_err());
+    }
+
     // ============================================================================
     // values_to_array1_f64 tests
     // ============================================================================
@@ -377,6 +415,162 @@ mod tests {
         assert_eq!(parsed.name, "max_depth");
         assert_eq!(parsed.values.len(), 3);
     }
+
+    // ============================================================================
+    // Forecasting tests
+    // ============================================================================
+
+    const HOUR: i64 = 3_600_000;
+
+    /// Linear trend with a 12 step season
+    fn seasonal_trend(t: f64) -> f64 {
+        10.0 + 0.5 * t + 5.0 * (2.0 * std::f64::consts::PI * t / 12.0).sin()
+    }
+
+    /// 120 hourly points of `seasonal_trend`, shuffled, with gaps and jittered timestamps
+    fn seasonal_series() -> Vec<TimeSeriesPoint> {
+        (0..120)
+            .filter(|t| ![30, 31, 77].contains(t))
+            .rev()
+            .map(|t| TimeSeriesPoint {
+                timestamp: 1_700_000_000_000 + t * HOUR + if t % 5 == 0 { 60_000 } else { 0 },
+                value: seasonal_trend(t as f64),
+            })
+            .collect()
+    }
+
+    fn options(method: ForecastMethod) -> ForecastOptions {
+        ForecastOptions {
+            method,
+            horizon: 24,
+            season_length: None,
+            window: None,
+            interval: None,
+            confidence: 0.95,
+        }
+    }
+
+    #[test]
+    fn test_resample_to_regular_grid() {
+        let point = |timestamp, value| TimeSeriesPoint { timestamp, value };
+        let series = resample(
+            &[
+                point(40, 10.0),
+                point(0, 1.0),
+                point(10, 3.0),
+                point(11, 5.0),
+                point(20, f64::NAN),
+            ],
+            None,
+        )
+        .unwrap();
+
+        // Points 10 and 11 share a slot, 20 and 30 are interpolated
+        assert_eq!(series.start, 0);
+        assert_eq!(series.step, 10);
+        assert_eq!(series.values, vec![1.0, 4.0, 6.0, 8.0, 10.0]);
+
+        let coarse = resample(&[point(0, 1.0), point(9, 3.0), point(40, 5.0)], Some(20)).unwrap();
+        assert_eq!(coarse.values, vec![2.0, 3.5, 5.0]);
+
+        assert!(resample(&[point(0, 1.0)], None).is_err());
+        assert!(resample(&[point(5, 1.0), point(5, 2.0)], None).is_err());
+        assert!(resample(&[point(0, 1.0), point(1_000_000_000, 2.0)], Some(1)).is_err());
+    }
+
+    #[test]
+    fn test_detect_season_length() {
+        let values: Vec<f64> = (0..120).map(|t| seasonal_trend(t as f64)).collect();
+        assert_eq!(detect_season_length(&values), 12);
+
+        let linear: Vec<f64> = (0..50).map(|t| 2.0 * t as f64).collect();
+        assert_eq!(detect_season_length(&linear), 1);
+    }
+
+    #[test]
+    fn test_holt_winters_tracks_seasonal_trend() {
+        let result = forecast(&seasonal_series(), &options(ForecastMethod::HoltWinters)).unwrap();
+
+        assert_eq!(result.season_length, 12);
+        assert_eq!(result.timestamps.len(), 24);
+        assert_eq!(result.timestamps[0], 1_700_000_060_000 + 120 * HOUR);
+        assert_eq!(result.timestamps[1] - result.timestamps[0], HOUR);
+
+        for (h, value) in result.forecast.iter().enumerate() {
+            let expected = seasonal_trend(120.0 + h as f64);
+            assert!(
+                (value - expected).abs() < 0.5,
+                "step {h}: forecast {value}, expected {expected}"
+            );
+            assert!(result.lower[h] <= *value && *value <= result.upper[h]);
+        }
+
+        // Uncertainty grows with the horizon
+        let width = |h: usize| result.upper[h] - result.lower[h];
+        assert!(width(23) >= width(0));
+    }
+
+    #[test]
+    fn test_naive_and_moving_average() {
+        let series = seasonal_series();
+
+        let naive = forecast(&series, &options(ForecastMethod::Naive)).unwrap();
+        // Seasonal naive repeats the last season, missing the trend of one season
+        for (h, value) in naive.forecast.iter().enumerate().take(12) {
+            assert!((value - seasonal_trend(108.0 + h as f64)).abs() < 1e-9);
+        }
+        assert!(naive.upper[12] - naive.lower[12] > naive.upper[0] - naive.lower[0]);
+
+        let mut flat = options(ForecastMethod::Naive);
+        flat.season_length = Some(1);
+        let flat = forecast(&series, &flat).unwrap();
+        assert_eq!(flat.season_length, 1);
+        assert!(flat.forecast.iter().all(|v| *v == seasonal_trend(119.0)));
+
+        let mut average = options(ForecastMethod::MovingAverage);
+        average.window = Some(4);
+        let average = forecast(&series, &average).unwrap();
+        let expected = (116..120).map(|t| seasonal_trend(t as f64)).sum::<f64>() / 4.0;
+        assert!(average.forecast.iter().all(|v| (v - expected).abs() < 1e-9));
+    }
+
+    #[test]
+    fn test_forecast_rejects_invalid_options() {
+        let series = seasonal_series();
+        let invalid = [
+            ForecastOptions {
+                horizon: 0,
+                ..options(ForecastMethod::Naive)
+            },
+            ForecastOptions {
+                confidence: 1.0,
+                ..options(ForecastMethod::Naive)
+            },
+            ForecastOptions {
+                season_length: Some(100),
+                ..options(ForecastMethod::HoltWinters)
+            },
+            ForecastOptions {
+                window: Some(500),
+                ..options(ForecastMethod::MovingAverage)
+            },
+        ];
+        for options in invalid {
+            assert!(forecast(&series, &options).is_err(), "{options:?}");
+        }
+        assert!("Arima".parse::<ForecastMethod>().is_err());
+        assert_eq!(
+            "Holt-Winters".parse::<ForecastMethod>().unwrap(),
+            ForecastMethod::HoltWinters
+        );
+    }
+
+    #[test]
+    fn test_normal_quantile() {
+        assert!(normal_quantile(0.5).abs() < 1e-9);
+        assert!((normal_quantile(0.975) - 1.959964).abs() < 1e-5);
+        assert!((normal_quantile(0.005) + 2.575829).abs() < 1e-5);
+    }
 }
 
 // ============================================================================
@@ -385,7 +579,8 @@ mod tests {
 
 #[cfg(all(test, feature = "execute"))]
 mod execute_tests {
-    use crate::ml::{MLModel, ModelWithMeta, ParameterSpec};
+    use crate::ml::reduction::pca::fit_pca;
+    use crate::ml::{MLModel, ModelWithMeta, ParameterSpec, PcaModel, rows_to_array2_f64};
     use flow_like_types::json::{self, json};
     use linfa::prelude::*;
     use linfa_clustering::KMeans;
@@ -497,6 +692,67 @@ mod execute_tests {
         assert!(display.contains("KMeans"));
     }
 
+    // ============================================================================
+    // PCA tests
+    // ============================================================================
+
+    /// Points scattered along the diagonal of the first two axes with little noise in the
+    /// other ones
+    fn pca_rows() -> Vec<Vec<f64>> {
+        (0..12)
+            .map(|i| {
+                let t = i as f64 - 5.5;
+                let wobble = if i % 2 == 0 { 0.1 } else { -0.1 };
+                vec![t, t + wobble, wobble * 2.0, 0.3 * wobble + 1.0]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_pca_explained_variance() {
+        let matrix = rows_to_array2_f64(&pca_rows()).unwrap();
+        let (model, transformed) = fit_pca(&matrix, 2).unwrap();
+
+        assert_eq!(transformed.shape(), &[12, 2]);
+        assert_eq!(model.components.len(), 2);
+        assert_eq!(model.mean.len(), 4);
+
+        let ratios = &model.explained_variance_ratio;
+        let total: f64 = ratios.iter().sum();
+        assert!(total <= 1.0 + 1e-9, "ratios sum to {}", total);
+        assert!(ratios[0] > 0.95, "first component explains {}", ratios[0]);
+        assert!(ratios[0] >= ratios[1]);
+
+        // The first axis is the diagonal
+        let axis = &model.components[0];
+        assert!((axis[0].abs() - axis[1].abs()).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_pca_transform_reproduces_fit_transform() {
+        let matrix = rows_to_array2_f64(&pca_rows()).unwrap();
+        let (model, fit_transformed) = fit_pca(&matrix, 3).unwrap();
+
+        // The model travels between flows as JSON
+        let model: PcaModel = json::from_value(json::to_value(&model).unwrap()).unwrap();
+        let transformed = model.transform(&matrix).unwrap();
+
+        assert_eq!(transformed.shape(), fit_transformed.shape());
+        for (a, b) in transformed.iter().zip(fit_transformed.iter()) {
+            assert!((a - b).abs() < 1e-9, "{} != {}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_pca_component_count_is_validated() {
+        let matrix = rows_to_array2_f64(&pca_rows()).unwrap();
+        assert!(fit_pca(&matrix, 0).is_err());
+        assert!(fit_pca(&matrix, 5).is_err());
+
+        let single_row = rows_to_array2_f64(&[vec![1.0, 2.0]]).unwrap();
+        assert!(fit_pca(&single_row, 1).is_err());
+    }
+
     // ============================================================================
     // predict_on_values tests (public API)
     // ============================================================================
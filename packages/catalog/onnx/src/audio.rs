@@ -78,6 +78,77 @@ impl AudioData {
 
         Self::new(self.sample_rate, 1, mono)
     }
+
+    /// Decode a WAV, MP3 or FLAC file. The extension is only a hint, the container is
+    /// probed from the content.
+    #[cfg(feature = "execute")]
+    pub fn decode(bytes: Vec<u8>, extension: Option<&str>) -> Result<Self> {
+        use symphonia::core::{
+            audio::SampleBuffer, codecs::CODEC_TYPE_NULL, errors::Error as DecodeError,
+            io::MediaSourceStream, probe::Hint,
+        };
+
+        let stream =
+            MediaSourceStream::new(Box::new(std::io::Cursor::new(bytes)), Default::default());
+        let mut hint = Hint::new();
+        if let Some(extension) = extension {
+            hint.with_extension(extension);
+        }
+
+        let mut format = symphonia::default::get_probe()
+            .format(&hint, stream, &Default::default(), &Default::default())
+            .map_err(|e| anyhow!("Unsupported audio format: {}", e))?
+            .format;
+        let track = format
+            .tracks()
+            .iter()
+            .find(|track| track.codec_params.codec != CODEC_TYPE_NULL)
+            .ok_or_else(|| anyhow!("Audio file contains no audio track"))?;
+        let track_id = track.id;
+        let mut sample_rate = track.codec_params.sample_rate.unwrap_or(0);
+        let mut channels = track
+            .codec_params
+            .channels
+            .map(|channels| channels.count() as u16)
+            .unwrap_or(1);
+        let mut decoder = symphonia::default::get_codecs()
+            .make(&track.codec_params, &Default::default())
+            .map_err(|e| anyhow!("Unsupported audio codec: {}", e))?;
+
+        let mut samples = Vec::new();
+        loop {
+            let packet = match format.next_packet() {
+                Ok(packet) => packet,
+                Err(DecodeError::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+                    break;
+                }
+                Err(e) => return Err(anyhow!("Failed to read audio: {}", e)),
+            };
+            if packet.track_id() != track_id {
+                continue;
+            }
+
+            let decoded = match decoder.decode(&packet) {
+                Ok(decoded) => decoded,
+                // Corrupt frames are skipped, like players do
+                Err(DecodeError::DecodeError(_)) => continue,
+                Err(e) => return Err(anyhow!("Failed to decode audio: {}", e)),
+            };
+            let spec = *decoded.spec();
+            sample_rate = spec.rate;
+            channels = spec.channels.count() as u16;
+
+            let mut buffer = SampleBuffer::<f32>::new(decoded.capacity() as u64, spec);
+            buffer.copy_interleaved_ref(decoded);
+            samples.extend_from_slice(buffer.samples());
+        }
+
+        if sample_rate == 0 || samples.is_empty() {
+            return Err(anyhow!("Audio file contains no samples"));
+        }
+
+        Ok(Self::new(sample_rate, channels, samples))
+    }
 }
 
 /// Transcription result from speech-to-text
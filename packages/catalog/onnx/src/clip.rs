@@ -0,0 +1,562 @@
+/// # ONNX CLIP Embeddings
+/// Image and text embeddings in one shared space for semantic image search
+use crate::onnx::feature::FeatureVector;
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic},
+    pin::{PinOptions, ValueType},
+    variable::VariableType,
+};
+use flow_like_catalog_core::{FlowPath, NodeImage};
+#[cfg(feature = "execute")]
+use flow_like_model_provider::{
+    ml::{
+        ndarray::{Array2, Array4, ArrayViewD},
+        ort::{inputs, session::Session, value::Value},
+    },
+    tokenizers::{Tokenizer, TruncationParams},
+};
+#[cfg(feature = "execute")]
+use flow_like_types::{
+    Cacheable,
+    image::{DynamicImage, GenericImageView, imageops::FilterType},
+    sync::Mutex,
+};
+use flow_like_types::{Result, anyhow, async_trait, bail, json::json};
+#[cfg(feature = "execute")]
+use sha2::{Digest, Sha256};
+#[cfg(feature = "execute")]
+use std::sync::Arc;
+
+/// Per channel mean and standard deviation CLIP was trained with
+const CLIP_MEAN: [f32; 3] = [0.481_454_66, 0.457_827_5, 0.408_210_73];
+const CLIP_STD: [f32; 3] = [0.268_629_54, 0.261_302_58, 0.275_777_11];
+/// Context length of the CLIP text tower, including start and end tokens
+const MAX_TEXT_TOKENS: usize = 77;
+const DEFAULT_IMAGE_SIZE: u32 = 224;
+
+/// Resizes the shortest side to `size`, center crops and normalizes to a `[1, 3, size, size]`
+/// pixel tensor.
+#[cfg(feature = "execute")]
+pub fn preprocess_image(img: &DynamicImage, size: u32) -> Array4<f32> {
+    let (width, height) = img.dimensions();
+    let scale = size as f32 / width.min(height).max(1) as f32;
+    let resized_width = ((width as f32 * scale).round() as u32).max(size);
+    let resized_height = ((height as f32 * scale).round() as u32).max(size);
+
+    let rgb = img
+        .resize_exact(resized_width, resized_height, FilterType::CatmullRom)
+        .crop_imm(
+            (resized_width - size) / 2,
+            (resized_height - size) / 2,
+            size,
+            size,
+        )
+        .to_rgb8();
+
+    Array4::from_shape_fn(
+        (1, 3, size as usize, size as usize),
+        |(_, channel, y, x)| {
+            let value = rgb.get_pixel(x as u32, y as u32)[channel] as f32 / 255.0;
+            (value - CLIP_MEAN[channel]) / CLIP_STD[channel]
+        },
+    )
+}
+
+/// Cosine similarity of two embeddings. Unlike [`FeatureVector::cosine_similarity`] this fails
+/// if the dimensions differ, which usually means the vectors come from different models.
+pub fn embedding_similarity(a: &FeatureVector, b: &FeatureVector) -> Result<f32> {
+    if a.dimensions != b.dimensions {
+        bail!(
+            "Embedding dimensions differ ({} vs {}), were they created by the same model?",
+            a.dimensions,
+            b.dimensions
+        );
+    }
+    if a.dimensions == 0 {
+        bail!("Cannot compare empty embeddings");
+    }
+    Ok(a.cosine_similarity(b))
+}
+
+/// Both CLIP towers with the tokenizer for the text tower.
+#[cfg(feature = "execute")]
+pub struct ClipModel {
+    vision: Session,
+    text: Session,
+    tokenizer: Tokenizer,
+    image_size: u32,
+}
+
+/// Name of the projected embedding output, falling back to the first output.
+#[cfg(feature = "execute")]
+fn embedding_output(session: &Session, preferred: &str) -> Result<String> {
+    session
+        .outputs
+        .iter()
+        .find(|output| output.name == preferred)
+        .or_else(|| session.outputs.first())
+        .map(|output| output.name.clone())
+        .ok_or_else(|| anyhow!("CLIP model has no outputs"))
+}
+
+#[cfg(feature = "execute")]
+impl ClipModel {
+    pub fn from_bytes(vision: &[u8], text: &[u8], tokenizer: &[u8]) -> Result<Self> {
+        let vision = Session::builder()?.commit_from_memory(vision)?;
+        let text = Session::builder()?.commit_from_memory(text)?;
+
+        let mut tokenizer = Tokenizer::from_bytes(tokenizer)
+            .map_err(|e| anyhow!("Failed to load tokenizer: {}", e))?;
+        tokenizer
+            .with_truncation(Some(TruncationParams {
+                max_length: MAX_TEXT_TOKENS,
+                ..Default::default()
+            }))
+            .map_err(|e| anyhow!("Invalid truncation settings: {}", e))?;
+
+        let image_size = vision
+            .inputs
+            .first()
+            .and_then(|input| input.input_type.tensor_shape())
+            .and_then(|dims| dims.last().copied())
+            .filter(|&dim| dim > 0)
+            .map_or(DEFAULT_IMAGE_SIZE, |dim| dim as u32);
+
+        Ok(ClipModel {
+            vision,
+            text,
+            tokenizer,
+            image_size,
+        })
+    }
+
+    pub fn embed_image(&mut self, img: &DynamicImage) -> Result<FeatureVector> {
+        let input_name = self
+            .vision
+            .inputs
+            .first()
+            .map(|input| input.name.clone())
+            .unwrap_or_else(|| "pixel_values".to_string());
+        let output_name = embedding_output(&self.vision, "image_embeds")?;
+
+        let pixels = Value::from_array(preprocess_image(img, self.image_size))?;
+        let outputs = self.vision.run(inputs![input_name => pixels])?;
+        embedding_from_output(outputs[output_name.as_str()].try_extract_array::<f32>()?)
+    }
+
+    pub fn embed_text(&mut self, text: &str) -> Result<FeatureVector> {
+        let encoding = self
+            .tokenizer
+            .encode(text, true)
+            .map_err(|e| anyhow!("Tokenization failed: {}", e))?;
+        let ids: Vec<i64> = encoding.get_ids().iter().map(|&id| id as i64).collect();
+        let mask: Vec<i64> = encoding
+            .get_attention_mask()
+            .iter()
+            .map(|&m| m as i64)
+            .collect();
+        let len = ids.len();
+        let output_name = embedding_output(&self.text, "text_embeds")?;
+
+        let input_ids = Value::from_array(Array2::from_shape_vec((1, len), ids)?)?;
+        let has_attention_mask = self
+            .text
+            .inputs
+            .iter()
+            .any(|input| input.name == "attention_mask");
+        let outputs = if has_attention_mask {
+            let attention_mask = Value::from_array(Array2::from_shape_vec((1, len), mask)?)?;
+            self.text.run(inputs![
+                "input_ids" => input_ids,
+                "attention_mask" => attention_mask
+            ])?
+        } else {
+            self.text.run(inputs!["input_ids" => input_ids])?
+        };
+        embedding_from_output(outputs[output_name.as_str()].try_extract_array::<f32>()?)
+    }
+}
+
+#[cfg(feature = "execute")]
+fn embedding_from_output(output: ArrayViewD<f32>) -> Result<FeatureVector> {
+    // The unprojected last_hidden_state is [batch, tokens, hidden] and lives in another space
+    if output.ndim() != 2 {
+        bail!(
+            "Expected projected CLIP embeddings of shape [1, dim], got {:?}. Use an export with image_embeds / text_embeds outputs",
+            output.shape()
+        );
+    }
+    let mut embedding = FeatureVector::new(output.iter().copied().collect());
+    embedding.normalize();
+    Ok(embedding)
+}
+
+/// Loaded CLIP models are kept in the run cache, keyed by their files, so image and text
+/// embeddings share the same sessions.
+#[cfg(feature = "execute")]
+pub struct ClipModelCache {
+    pub model: Arc<Mutex<ClipModel>>,
+}
+
+#[cfg(feature = "execute")]
+impl Cacheable for ClipModelCache {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(feature = "execute")]
+pub fn clip_cache_key(vision: &FlowPath, text: &FlowPath, tokenizer: &FlowPath) -> String {
+    let mut hasher = Sha256::new();
+    for path in [vision, text, tokenizer] {
+        hasher.update(path.store_ref.as_bytes());
+        hasher.update([0]);
+        hasher.update(path.path.as_bytes());
+        hasher.update([0]);
+    }
+    format!("clip_{}", hex::encode(hasher.finalize()))
+}
+
+/// Returns the cached model for these files, loading it on first use.
+#[cfg(feature = "execute")]
+pub async fn cached_clip_model(
+    context: &mut ExecutionContext,
+    vision: &FlowPath,
+    text: &FlowPath,
+    tokenizer: &FlowPath,
+) -> Result<Arc<Mutex<ClipModel>>> {
+    let key = clip_cache_key(vision, text, tokenizer);
+    if let Some(cached) = context.get_cache(&key).await
+        && let Some(cached) = cached.as_any().downcast_ref::<ClipModelCache>()
+    {
+        return Ok(cached.model.clone());
+    }
+
+    let vision = vision.get(context, false).await?;
+    let text = text.get(context, false).await?;
+    let tokenizer = tokenizer.get(context, false).await?;
+    let model = Arc::new(Mutex::new(ClipModel::from_bytes(
+        &vision, &text, &tokenizer,
+    )?));
+
+    context
+        .set_cache(
+            &key,
+            Arc::new(ClipModelCache {
+                model: model.clone(),
+            }),
+        )
+        .await;
+    Ok(model)
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct ClipEmbedNode {}
+
+impl ClipEmbedNode {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for ClipEmbedNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "onnx_clip_embed",
+            "CLIP Embed",
+            "Embeds an image or a text with a CLIP model, so images can be searched by description. Use vision_model.onnx, text_model.onnx and tokenizer.json from e.g. https://huggingface.co/Xenova/clip-vit-base-patch32. The output is normalized to unit length",
+            "AI/ML/ONNX",
+        );
+
+        node.add_icon("/flow/icons/find_model.svg");
+
+        node.add_input_pin(
+            "exec_in",
+            "Input",
+            "Initiate Execution",
+            VariableType::Execution,
+        );
+
+        node.add_input_pin(
+            "vision_model",
+            "Vision Model",
+            "CLIP image tower ONNX file",
+            VariableType::Struct,
+        )
+        .set_schema::<FlowPath>()
+        .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node.add_input_pin(
+            "text_model",
+            "Text Model",
+            "CLIP text tower ONNX file",
+            VariableType::Struct,
+        )
+        .set_schema::<FlowPath>()
+        .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node.add_input_pin(
+            "tokenizer",
+            "Tokenizer",
+            "Path to tokenizer.json",
+            VariableType::Struct,
+        )
+        .set_schema::<FlowPath>()
+        .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node.add_input_pin(
+            "source",
+            "Source",
+            "Which input to embed",
+            VariableType::String,
+        )
+        .set_options(
+            PinOptions::new()
+                .set_valid_values(vec![
+                    "Text".to_string(),
+                    "Image".to_string(),
+                    "Image File".to_string(),
+                ])
+                .build(),
+        )
+        .set_default_value(Some(json!("Text")));
+
+        node.add_input_pin("text", "Text", "Text to embed", VariableType::String)
+            .set_default_value(Some(json!("")));
+
+        node.add_input_pin("image", "Image", "Image to embed", VariableType::Struct)
+            .set_schema::<NodeImage>()
+            .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node.add_input_pin(
+            "image_file",
+            "Image File",
+            "Image file to embed",
+            VariableType::Struct,
+        )
+        .set_schema::<FlowPath>()
+        .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node.add_output_pin(
+            "exec_out",
+            "Output",
+            "Done with the Execution",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "vector",
+            "Vector",
+            "Normalized embedding vector",
+            VariableType::Float,
+        )
+        .set_value_type(ValueType::Array);
+
+        node.add_output_pin(
+            "embedding",
+            "Embedding",
+            "Embedding with its dimensions",
+            VariableType::Struct,
+        )
+        .set_schema::<FeatureVector>();
+
+        node.add_output_pin(
+            "dimensions",
+            "Dimensions",
+            "Embedding size, e.g. 512 for ViT-B/32",
+            VariableType::Integer,
+        );
+
+        node
+    }
+
+    #[allow(unused_variables)]
+    async fn run(&self, context: &mut ExecutionContext) -> Result<()> {
+        #[cfg(feature = "execute")]
+        {
+            context.deactivate_exec_pin("exec_out").await?;
+
+            let vision: FlowPath = context.evaluate_pin("vision_model").await?;
+            let text_model: FlowPath = context.evaluate_pin("text_model").await?;
+            let tokenizer: FlowPath = context.evaluate_pin("tokenizer").await?;
+            let source: String = context
+                .evaluate_pin("source")
+                .await
+                .unwrap_or_else(|_| "Text".to_string());
+
+            let image = match source.as_str() {
+                "Text" => None,
+                "Image" => {
+                    let node_image: NodeImage = context.evaluate_pin("image").await?;
+                    let image = node_image.get_image(context).await?;
+                    let image = image.lock().await.clone();
+                    Some(image)
+                }
+                "Image File" => {
+                    let path: FlowPath = context.evaluate_pin("image_file").await?;
+                    let bytes = path.get(context, false).await?;
+                    Some(flow_like_types::image::load_from_memory(&bytes)?)
+                }
+                other => bail!("Unknown source '{}'", other),
+            };
+            let text: String = if image.is_none() {
+                context.evaluate_pin("text").await?
+            } else {
+                String::new()
+            };
+
+            let model = cached_clip_model(context, &vision, &text_model, &tokenizer).await?;
+            let embedding = {
+                let mut model = model.lock().await;
+                match &image {
+                    Some(image) => model.embed_image(image)?,
+                    None => model.embed_text(&text)?,
+                }
+            };
+
+            context
+                .set_pin_value("vector", json!(embedding.values))
+                .await?;
+            context
+                .set_pin_value("dimensions", json!(embedding.dimensions))
+                .await?;
+            context.set_pin_value("embedding", json!(embedding)).await?;
+            context.activate_exec_pin("exec_out").await?;
+            Ok(())
+        }
+
+        #[cfg(not(feature = "execute"))]
+        Err(anyhow!("Execute feature not enabled"))
+    }
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct ClipSimilarityNode {}
+
+impl ClipSimilarityNode {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for ClipSimilarityNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "onnx_clip_similarity",
+            "CLIP Similarity",
+            "Cosine similarity of two CLIP embeddings, e.g. an image and a search text. Fails if the vectors have different dimensions",
+            "AI/ML/ONNX",
+        );
+
+        node.add_icon("/flow/icons/find_model.svg");
+
+        node.add_input_pin(
+            "vector_a",
+            "Vector A",
+            "First embedding",
+            VariableType::Float,
+        )
+        .set_value_type(ValueType::Array);
+
+        node.add_input_pin(
+            "vector_b",
+            "Vector B",
+            "Second embedding",
+            VariableType::Float,
+        )
+        .set_value_type(ValueType::Array);
+
+        node.add_output_pin(
+            "similarity",
+            "Similarity",
+            "Cosine similarity (-1 to 1, higher is more similar)",
+            VariableType::Float,
+        );
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> Result<()> {
+        let vector_a: Vec<f32> = context.evaluate_pin("vector_a").await?;
+        let vector_b: Vec<f32> = context.evaluate_pin("vector_b").await?;
+
+        let similarity =
+            embedding_similarity(&FeatureVector::new(vector_a), &FeatureVector::new(vector_b))?;
+
+        context
+            .set_pin_value("similarity", json!(similarity))
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_similarity_requires_matching_dimensions() {
+        let a = FeatureVector::new(vec![1.0, 0.0]);
+        let b = FeatureVector::new(vec![0.0, 1.0, 0.0]);
+        assert!(embedding_similarity(&a, &b).is_err());
+        assert!(
+            embedding_similarity(&FeatureVector::new(vec![]), &FeatureVector::new(vec![])).is_err()
+        );
+
+        let c = FeatureVector::new(vec![2.0, 0.0]);
+        assert!((embedding_similarity(&a, &c).unwrap() - 1.0).abs() < 1e-6);
+        assert!(
+            embedding_similarity(&a, &FeatureVector::new(vec![0.0, 1.0]))
+                .unwrap()
+                .abs()
+                < 1e-6
+        );
+    }
+
+    #[cfg(feature = "execute")]
+    #[test]
+    fn test_preprocess_crops_and_normalizes() {
+        use flow_like_types::image::{Rgb, RgbImage};
+
+        // Wide image, white in the middle and black on the far left and right
+        let image = RgbImage::from_fn(448, 224, |x, _| {
+            if (112..336).contains(&x) {
+                Rgb([255, 255, 255])
+            } else {
+                Rgb([0, 0, 0])
+            }
+        });
+        let pixels = preprocess_image(&DynamicImage::ImageRgb8(image), 224);
+
+        assert_eq!(pixels.shape(), &[1, 3, 224, 224]);
+        for channel in 0..3 {
+            let white = (1.0 - CLIP_MEAN[channel]) / CLIP_STD[channel];
+            assert!((pixels[[0, channel, 100, 112]] - white).abs() < 1e-4);
+        }
+    }
+
+    #[cfg(feature = "execute")]
+    #[test]
+    fn test_cache_key_is_shared_per_model_files() {
+        let path = |path: &str| FlowPath::new(path.to_string(), "store".to_string(), None);
+        let key = clip_cache_key(&path("vision.onnx"), &path("text.onnx"), &path("tok.json"));
+
+        assert_eq!(
+            key,
+            clip_cache_key(&path("vision.onnx"), &path("text.onnx"), &path("tok.json"))
+        );
+        assert_ne!(
+            key,
+            clip_cache_key(&path("text.onnx"), &path("vision.onnx"), &path("tok.json"))
+        );
+        assert!(key.starts_with("clip_"));
+    }
+}
@@ -32,6 +32,12 @@
 //! 2. Registers all available EPs in order of preference
 //! 3. Falls back to CPU if no accelerators are available
 //!
+//! # Per-Session Selection
+//!
+//! Load nodes can ask for a specific provider with [`build_session`]. If it is not available
+//! or fails to register, the session runs on CPU and the returned [`ProviderSelection`]
+//! carries a warning instead of falling back silently.
+//!
 //! # Cross-Compilation Notes
 //!
 //! - All EP features compile on all platforms (they become no-ops where unsupported)
@@ -110,78 +116,212 @@ pub fn get_ep_info() -> Option<ExecutionProviderInfo> {
     EP_INFO.read().ok().and_then(|guard| guard.clone())
 }
 
-#[cfg(feature = "execute")]
-fn do_initialize_ort() -> ExecutionProviderInfo {
-    use flow_like_model_provider::ml::ort;
-    use tracing::info;
+/// Execution provider a session is asked to run on, as chosen on the load nodes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutionProviderKind {
+    /// Fastest available accelerator, CPU if there is none
+    Auto,
+    Cpu,
+    Cuda,
+    CoreML,
+    DirectML,
+    TensorRT,
+}
 
-    let mut active_providers = Vec::new();
-    let warnings = Vec::new();
-    let eps: Vec<ort::execution_providers::ExecutionProviderDispatch> = Vec::new();
+impl ExecutionProviderKind {
+    /// Values accepted by the `execution_provider` pin
+    pub const OPTIONS: [&'static str; 6] =
+        ["auto", "cpu", "cuda", "coreml", "directml", "tensorrt"];
+
+    /// Accelerators in order of preference
+    pub const ACCELERATORS: [ExecutionProviderKind; 4] = [
+        ExecutionProviderKind::TensorRT,
+        ExecutionProviderKind::Cuda,
+        ExecutionProviderKind::CoreML,
+        ExecutionProviderKind::DirectML,
+    ];
+
+    pub fn parse(value: &str) -> flow_like_types::Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "" | "auto" => Ok(ExecutionProviderKind::Auto),
+            "cpu" => Ok(ExecutionProviderKind::Cpu),
+            "cuda" => Ok(ExecutionProviderKind::Cuda),
+            "coreml" => Ok(ExecutionProviderKind::CoreML),
+            "directml" => Ok(ExecutionProviderKind::DirectML),
+            "tensorrt" => Ok(ExecutionProviderKind::TensorRT),
+            other => Err(flow_like_types::anyhow!(
+                "Unknown execution provider '{}', expected one of {}",
+                other,
+                Self::OPTIONS.join(", ")
+            )),
+        }
+    }
 
-    // Try to register EPs in order of preference
-    // TensorRT > CUDA > CoreML > DirectML > XNNPACK > CPU
+    pub fn name(&self) -> &'static str {
+        match self {
+            ExecutionProviderKind::Auto => "Auto",
+            ExecutionProviderKind::Cpu => "CPU",
+            ExecutionProviderKind::Cuda => "CUDA",
+            ExecutionProviderKind::CoreML => "CoreML",
+            ExecutionProviderKind::DirectML => "DirectML",
+            ExecutionProviderKind::TensorRT => "TensorRT",
+        }
+    }
 
-    // TensorRT (NVIDIA, fastest)
-    #[cfg(feature = "tensorrt")]
-    {
-        if ort::execution_providers::TensorRTExecutionProvider::is_available() {
-            info!("TensorRT execution provider available");
-            eps.push(ort::execution_providers::TensorRTExecutionProvider::default().build());
-            active_providers.push("TensorRT".to_string());
-        } else {
-            let msg = "TensorRT feature enabled but runtime not available";
-            warn!("{}", msg);
-            warnings.push(msg.to_string());
+    /// Whether the provider is compiled in and its runtime is present
+    pub fn is_available(&self) -> bool {
+        match self {
+            ExecutionProviderKind::Auto | ExecutionProviderKind::Cpu => true,
+            ExecutionProviderKind::Cuda => availability::cuda_available(),
+            ExecutionProviderKind::CoreML => availability::coreml_available(),
+            ExecutionProviderKind::DirectML => availability::directml_available(),
+            ExecutionProviderKind::TensorRT => availability::tensorrt_available(),
         }
     }
+}
 
-    // CUDA (NVIDIA)
-    #[cfg(feature = "cuda")]
-    {
-        if ort::execution_providers::CUDAExecutionProvider::is_available() {
-            info!("CUDA execution provider available");
-            eps.push(ort::execution_providers::CUDAExecutionProvider::default().build());
-            active_providers.push("CUDA".to_string());
-        } else {
-            let msg = "CUDA feature enabled but runtime not available";
-            warn!("{}", msg);
-            warnings.push(msg.to_string());
+/// The provider a session actually runs on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProviderSelection {
+    /// Never `Auto`
+    pub provider: ExecutionProviderKind,
+    /// Set when the requested accelerator could not be used and the session runs on CPU
+    pub fallback_warning: Option<String>,
+}
+
+impl ProviderSelection {
+    fn cpu_fallback(requested: ExecutionProviderKind, reason: &str) -> Self {
+        ProviderSelection {
+            provider: ExecutionProviderKind::Cpu,
+            fallback_warning: Some(format!(
+                "{} was requested but {}, falling back to CPU. Inference will be slower",
+                requested.name(),
+                reason
+            )),
         }
     }
 
-    // CoreML (Apple)
-    #[cfg(feature = "coreml")]
-    {
-        if ort::execution_providers::CoreMLExecutionProvider::is_available() {
-            info!("CoreML execution provider available");
-            eps.push(ort::execution_providers::CoreMLExecutionProvider::default().build());
-            active_providers.push("CoreML".to_string());
-        } else {
-            let msg = "CoreML feature enabled but not on Apple platform";
-            warn!("{}", msg);
-            warnings.push(msg.to_string());
+    pub fn accelerated(&self) -> bool {
+        self.provider != ExecutionProviderKind::Cpu
+    }
+}
+
+/// Resolves the requested provider against `is_available`.
+pub fn select_provider(
+    requested: ExecutionProviderKind,
+    is_available: impl Fn(ExecutionProviderKind) -> bool,
+) -> ProviderSelection {
+    let provider = match requested {
+        ExecutionProviderKind::Auto => ExecutionProviderKind::ACCELERATORS
+            .into_iter()
+            .find(|kind| is_available(*kind))
+            .unwrap_or(ExecutionProviderKind::Cpu),
+        ExecutionProviderKind::Cpu => ExecutionProviderKind::Cpu,
+        kind if is_available(kind) => kind,
+        kind => return ProviderSelection::cpu_fallback(kind, "it is not available on this system"),
+    };
+    ProviderSelection {
+        provider,
+        fallback_warning: None,
+    }
+}
+
+/// Execution provider registration for a compiled in accelerator.
+#[cfg(feature = "execute")]
+fn dispatch(
+    kind: ExecutionProviderKind,
+) -> Option<flow_like_model_provider::ml::ort::execution_providers::ExecutionProviderDispatch> {
+    #[allow(unused_imports)]
+    use flow_like_model_provider::ml::ort::execution_providers::*;
+
+    match kind {
+        #[cfg(feature = "tensorrt")]
+        ExecutionProviderKind::TensorRT => Some(TensorRTExecutionProvider::default().build()),
+        #[cfg(feature = "cuda")]
+        ExecutionProviderKind::Cuda => Some(CUDAExecutionProvider::default().build()),
+        #[cfg(feature = "coreml")]
+        ExecutionProviderKind::CoreML => Some(CoreMLExecutionProvider::default().build()),
+        #[cfg(feature = "directml")]
+        ExecutionProviderKind::DirectML => Some(DirectMLExecutionProvider::default().build()),
+        _ => None,
+    }
+}
+
+/// Builds a session on the requested provider. If it is unavailable or fails to register,
+/// the session is built on CPU and the selection carries the fallback warning.
+#[cfg(feature = "execute")]
+pub fn build_session(
+    model: &[u8],
+    requested: ExecutionProviderKind,
+) -> flow_like_types::Result<(
+    flow_like_model_provider::ml::ort::session::Session,
+    ProviderSelection,
+)> {
+    use flow_like_model_provider::ml::ort::{
+        execution_providers::CPUExecutionProvider, session::Session,
+    };
+
+    let mut selection = select_provider(requested, |kind| kind.is_available());
+    if let Some(provider) = dispatch(selection.provider) {
+        match Session::builder()?.with_execution_providers([provider.error_on_failure()]) {
+            Ok(builder) => {
+                tracing::info!("ONNX session bound to {}", selection.provider.name());
+                return Ok((builder.commit_from_memory(model)?, selection));
+            }
+            Err(e) => {
+                selection = ProviderSelection::cpu_fallback(
+                    selection.provider,
+                    &format!("it failed to register ({})", e),
+                );
+            }
         }
     }
 
-    // DirectML (Windows)
-    #[cfg(feature = "directml")]
-    {
-        if ort::execution_providers::DirectMLExecutionProvider::is_available() {
-            info!("DirectML execution provider available");
-            eps.push(ort::execution_providers::DirectMLExecutionProvider::default().build());
-            active_providers.push("DirectML".to_string());
+    if let Some(warning) = &selection.fallback_warning {
+        tracing::warn!("{}", warning);
+    }
+    // Auto keeps the globally registered defaults, which may include XNNPACK
+    let builder = if requested == ExecutionProviderKind::Auto {
+        Session::builder()?
+    } else {
+        Session::builder()?.with_execution_providers([CPUExecutionProvider::default().build()])?
+    };
+    let session = builder.commit_from_memory(model)?;
+    tracing::info!("ONNX session bound to CPU");
+    Ok((session, selection))
+}
+
+#[cfg(feature = "execute")]
+fn do_initialize_ort() -> ExecutionProviderInfo {
+    use flow_like_model_provider::ml::ort;
+    use tracing::{info, warn};
+
+    let mut active_providers = Vec::new();
+    let mut warnings = Vec::new();
+    #[allow(unused_mut)]
+    let mut eps: Vec<ort::execution_providers::ExecutionProviderDispatch> = Vec::new();
+
+    // Register the accelerators in order of preference, each at most once
+    // TensorRT > CUDA > CoreML > DirectML > XNNPACK > CPU
+    for kind in ExecutionProviderKind::ACCELERATORS {
+        let Some(provider) = dispatch(kind) else {
+            continue;
+        };
+        if kind.is_available() {
+            info!("{} execution provider available", kind.name());
+            eps.push(provider);
+            active_providers.push(kind.name().to_string());
         } else {
-            let msg = "DirectML feature enabled but not on Windows";
+            let msg = format!("{} feature enabled but runtime not available", kind.name());
             warn!("{}", msg);
-            warnings.push(msg.to_string());
+            warnings.push(msg);
         }
     }
 
     // XNNPACK (optimized CPU for ARM/x86)
     #[cfg(feature = "xnnpack")]
     {
-        if ort::execution_providers::XNNPACKExecutionProvider::is_available() {
+        if availability::xnnpack_available() {
             info!("XNNPACK execution provider available");
             eps.push(ort::execution_providers::XNNPACKExecutionProvider::default().build());
             active_providers.push("XNNPACK".to_string());
@@ -229,13 +369,15 @@ fn do_initialize_ort() -> ExecutionProviderInfo {
 #[cfg(feature = "execute")]
 pub mod availability {
     #[allow(unused_imports)]
-    use flow_like_model_provider::ml::ort;
+    use flow_like_model_provider::ml::ort::{self, execution_providers::ExecutionProvider};
 
     /// Check if CUDA is compiled in and available at runtime
     pub fn cuda_available() -> bool {
         #[cfg(feature = "cuda")]
         {
-            ort::execution_providers::CUDAExecutionProvider::is_available()
+            ort::execution_providers::CUDAExecutionProvider::default()
+                .is_available()
+                .unwrap_or(false)
         }
         #[cfg(not(feature = "cuda"))]
         {
@@ -247,7 +389,9 @@ pub mod availability {
     pub fn tensorrt_available() -> bool {
         #[cfg(feature = "tensorrt")]
         {
-            ort::execution_providers::TensorRTExecutionProvider::is_available()
+            ort::execution_providers::TensorRTExecutionProvider::default()
+                .is_available()
+                .unwrap_or(false)
         }
         #[cfg(not(feature = "tensorrt"))]
         {
@@ -259,7 +403,9 @@ pub mod availability {
     pub fn coreml_available() -> bool {
         #[cfg(feature = "coreml")]
         {
-            ort::execution_providers::CoreMLExecutionProvider::is_available()
+            ort::execution_providers::CoreMLExecutionProvider::default()
+                .is_available()
+                .unwrap_or(false)
         }
         #[cfg(not(feature = "coreml"))]
         {
@@ -271,7 +417,9 @@ pub mod availability {
     pub fn directml_available() -> bool {
         #[cfg(feature = "directml")]
         {
-            ort::execution_providers::DirectMLExecutionProvider::is_available()
+            ort::execution_providers::DirectMLExecutionProvider::default()
+                .is_available()
+                .unwrap_or(false)
         }
         #[cfg(not(feature = "directml"))]
         {
@@ -283,7 +431,9 @@ pub mod availability {
     pub fn xnnpack_available() -> bool {
         #[cfg(feature = "xnnpack")]
         {
-            ort::execution_providers::XNNPACKExecutionProvider::is_available()
+            ort::execution_providers::XNNPACKExecutionProvider::default()
+                .is_available()
+                .unwrap_or(false)
         }
         #[cfg(not(feature = "xnnpack"))]
         {
@@ -360,4 +510,65 @@ mod tests {
         let info = initialize_ort();
         assert!(info.active_providers.iter().any(|p| p.contains("CPU")));
     }
+
+    #[test]
+    fn test_parse_provider() {
+        assert_eq!(
+            ExecutionProviderKind::parse("").unwrap(),
+            ExecutionProviderKind::Auto
+        );
+        assert_eq!(
+            ExecutionProviderKind::parse(" CUDA ").unwrap(),
+            ExecutionProviderKind::Cuda
+        );
+        for option in ExecutionProviderKind::OPTIONS {
+            assert!(ExecutionProviderKind::parse(option).is_ok());
+        }
+        assert!(ExecutionProviderKind::parse("vulkan").is_err());
+    }
+
+    #[test]
+    fn test_select_provider() {
+        let only_cuda = |kind| kind == ExecutionProviderKind::Cuda;
+
+        let auto = select_provider(ExecutionProviderKind::Auto, only_cuda);
+        assert_eq!(auto.provider, ExecutionProviderKind::Cuda);
+        assert!(auto.accelerated());
+        assert_eq!(auto.fallback_warning, None);
+
+        let cuda = select_provider(ExecutionProviderKind::Cuda, only_cuda);
+        assert_eq!(cuda.provider, ExecutionProviderKind::Cuda);
+        assert_eq!(cuda.fallback_warning, None);
+
+        let cpu = select_provider(ExecutionProviderKind::Cpu, only_cuda);
+        assert_eq!(cpu.provider, ExecutionProviderKind::Cpu);
+        assert_eq!(cpu.fallback_warning, None);
+
+        // Auto without accelerators is not a fallback
+        let auto = select_provider(ExecutionProviderKind::Auto, |_| false);
+        assert_eq!(auto.provider, ExecutionProviderKind::Cpu);
+        assert_eq!(auto.fallback_warning, None);
+    }
+
+    #[test]
+    fn test_unavailable_provider_warns() {
+        let selection = select_provider(ExecutionProviderKind::TensorRT, |kind| {
+            kind == ExecutionProviderKind::Cuda
+        });
+        assert_eq!(selection.provider, ExecutionProviderKind::Cpu);
+        assert!(!selection.accelerated());
+        let warning = selection.fallback_warning.unwrap();
+        assert!(warning.starts_with("TensorRT was requested"));
+        assert!(warning.contains("falling back to CPU"));
+    }
+
+    /// CPU-only builds have no CUDA, so requesting it has to fall back with a warning
+    #[cfg(not(feature = "cuda"))]
+    #[test]
+    fn test_cuda_falls_back_without_cuda_feature() {
+        assert!(!ExecutionProviderKind::Cuda.is_available());
+        let selection = select_provider(ExecutionProviderKind::Cuda, |kind| kind.is_available());
+        assert_eq!(selection.provider, ExecutionProviderKind::Cpu);
+        assert!(selection.fallback_warning.is_some());
+    }
 }
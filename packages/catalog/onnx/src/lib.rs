@@ -4,13 +4,16 @@
 //! - Object detection
 //! - Image classification
 //! - Feature extraction
+//! - CLIP image/text embeddings
 //! - Teachable Machine models
 //! - Depth estimation
 //! - Face detection and recognition
 //! - OCR (text detection and recognition)
 //! - Audio processing (VAD)
+//! - Speech-to-text (Whisper)
 //! - Batch inference
 //! - Named Entity Recognition (NER)
+//! - Cross-encoder reranking
 
 use std::sync::Arc;
 
@@ -24,10 +27,10 @@ pub use onnx::*;
 
 // Re-export submodules for external access
 pub use onnx::{
-    audio, batch, classification, depth, detection, face, feature, load, ner, ocr, pose,
-    segmentation,
+    audio, batch, classification, clip, depth, detection, face, feature, load, ner, ocr, pose,
+    rerank, segmentation, whisper,
 };
 
 pub fn get_catalog() -> Vec<Arc<dyn NodeLogic>> {
-    flow_like_catalog_core::get_catalog()
+    flow_like_catalog_core::get_package_catalog(env!("CARGO_PKG_NAME"))
 }
@@ -1,9 +1,12 @@
 /// # ONNX Model Loader Nodes
 use crate::onnx::NodeOnnxSession;
+use crate::onnx::execution_providers::ExecutionProviderKind;
 #[cfg(feature = "execute")]
-use crate::onnx::execution_providers::{get_ep_info, is_initialized};
+use crate::onnx::execution_providers::{build_session, is_initialized};
 #[cfg(feature = "execute")]
 use crate::onnx::{Provider, SessionWithMeta, classification, detection};
+#[cfg(feature = "execute")]
+use flow_like::flow::execution::LogLevel;
 use flow_like::flow::{
     execution::context::ExecutionContext,
     node::{Node, NodeLogic},
@@ -116,6 +119,24 @@ impl NodeLogic for LoadOnnxNode {
             .set_schema::<FlowPath>()
             .set_options(PinOptions::new().set_enforce_schema(true).build());
 
+        node.add_input_pin(
+            "execution_provider",
+            "Execution Provider",
+            "Hardware to run the model on. Auto picks the fastest available accelerator",
+            VariableType::String,
+        )
+        .set_options(
+            PinOptions::new()
+                .set_valid_values(
+                    ExecutionProviderKind::OPTIONS
+                        .iter()
+                        .map(|option| option.to_string())
+                        .collect(),
+                )
+                .build(),
+        )
+        .set_default_value(Some(json!("auto")));
+
         // outputs
         node.add_output_pin(
             "exec_out",
@@ -138,7 +159,14 @@ impl NodeLogic for LoadOnnxNode {
         node.add_output_pin(
             "active_provider",
             "Active Provider",
-            "The execution provider(s) that are actually in use",
+            "The execution provider the model is bound to",
+            VariableType::String,
+        );
+
+        node.add_output_pin(
+            "provider_warning",
+            "Provider Warning",
+            "Why the model fell back to CPU, empty if the requested provider is used",
             VariableType::String,
         );
 
@@ -153,18 +181,28 @@ impl NodeLogic for LoadOnnxNode {
 
             // fetch inputs
             let path: FlowPath = context.evaluate_pin("path").await?;
+            let requested: String = context
+                .evaluate_pin("execution_provider")
+                .await
+                .unwrap_or_else(|_| "auto".to_string());
+            let requested = ExecutionProviderKind::parse(&requested)?;
             let bytes = path.get(context, false).await?;
 
-            // Get global EP info (ORT should be initialized at app startup)
-            let ep_info = get_ep_info().unwrap_or_default();
             if !is_initialized() {
                 tracing::warn!(
                     "ORT not initialized - call initialize_ort() at app startup for GPU acceleration"
                 );
             }
 
-            // Build session - it will use the globally configured EPs
-            let session = Session::builder()?.commit_from_memory(&bytes)?;
+            let (session, selection) = build_session(&bytes, requested)?;
+            let active_provider = selection.provider.name();
+            context.log_message(
+                &format!("ONNX model bound to {}", active_provider),
+                LogLevel::Info,
+            );
+            if let Some(warning) = &selection.fallback_warning {
+                context.log_message(warning, LogLevel::Warn);
+            }
 
             // wrap ONNX session with provider metadata
             // we try to determine the here to fail fast in case of incompatible ONNX assets
@@ -172,20 +210,23 @@ impl NodeLogic for LoadOnnxNode {
             let session_with_meta = SessionWithMeta {
                 session,
                 provider,
-                ep_active: ep_info.active_providers.clone(),
-                accelerated: ep_info.accelerated,
+                ep_active: vec![active_provider.to_string()],
+                accelerated: selection.accelerated(),
             };
             let node_session = NodeOnnxSession::new(context, session_with_meta).await;
 
             // set outputs
             context.set_pin_value("model", json!(node_session)).await?;
             context
-                .set_pin_value("accelerated", json!(ep_info.accelerated))
+                .set_pin_value("accelerated", json!(selection.accelerated()))
+                .await?;
+            context
+                .set_pin_value("active_provider", json!(active_provider))
                 .await?;
             context
                 .set_pin_value(
-                    "active_provider",
-                    json!(ep_info.active_providers.join(", ")),
+                    "provider_warning",
+                    json!(selection.fallback_warning.unwrap_or_default()),
                 )
                 .await?;
             context.activate_exec_pin("exec_out").await?;
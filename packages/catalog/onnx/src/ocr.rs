@@ -11,14 +11,17 @@ use flow_like_catalog_core::NodeImage;
 #[cfg(feature = "execute")]
 use flow_like_model_provider::ml::{
     ndarray::Array4,
-    ort::{inputs, value::Value},
+    ort::{inputs, session::Session, value::Value},
 };
 #[cfg(feature = "execute")]
-use flow_like_types::image::{GenericImageView, imageops::FilterType};
+use flow_like_types::image::{DynamicImage, GenericImageView, imageops::FilterType};
 use flow_like_types::{Result, async_trait, json::json};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+/// Characters of the default recognition models, in class order after the CTC blank
+pub const DEFAULT_CHARSET: &str = "0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ!\"#$%&'()*+,-./:;<=>?@[\\]^_`{|}~ ";
+
 /// Detected text region in an image
 #[derive(Serialize, Deserialize, JsonSchema, Clone, Debug)]
 pub struct TextRegion {
@@ -57,6 +60,225 @@ pub struct OcrRegion {
     pub text: RecognizedText,
 }
 
+/// Finds text regions with a detection model (CRAFT, DBNet, ...), in pixel coordinates of `dyn_image`
+#[cfg(feature = "execute")]
+pub fn detect_text(
+    session: &mut Session,
+    dyn_image: &DynamicImage,
+    threshold: f32,
+    input_size: u32,
+) -> Result<Vec<TextRegion>> {
+    let (orig_w, orig_h) = dyn_image.dimensions();
+
+    // Preprocess
+    let resized = dyn_image.resize_exact(input_size, input_size, FilterType::Triangle);
+    let rgb = resized.to_rgb8();
+
+    let mut input = Array4::<f32>::zeros((1, 3, input_size as usize, input_size as usize));
+    for y in 0..input_size {
+        for x in 0..input_size {
+            let pixel = rgb.get_pixel(x, y);
+            input[[0, 0, y as usize, x as usize]] = pixel[0] as f32 / 255.0;
+            input[[0, 1, y as usize, x as usize]] = pixel[1] as f32 / 255.0;
+            input[[0, 2, y as usize, x as usize]] = pixel[2] as f32 / 255.0;
+        }
+    }
+
+    let input_value = Value::from_array(input)?;
+    let outputs = session.run(inputs![input_value])?;
+
+    // Parse output - depends on model architecture
+    // Generic approach: look for score map and geometry
+    let mut regions: Vec<TextRegion> = Vec::new();
+
+    // Try to extract score map from first output
+    if let Some((_, tensor)) = outputs.iter().next()
+        && let Ok(scores) = tensor.try_extract_array::<f32>()
+    {
+        let shape = scores.shape();
+
+        // Simple connected component analysis on score map
+        if shape.len() >= 3 {
+            let h = if shape.len() == 4 { shape[2] } else { shape[1] };
+            let w = if shape.len() == 4 { shape[3] } else { shape[2] };
+
+            let scale_x = orig_w as f32 / w as f32;
+            let scale_y = orig_h as f32 / h as f32;
+
+            // Find connected regions above threshold
+            let mut visited = vec![false; h * w];
+            for y in 0..h {
+                for x in 0..w {
+                    let idx = y * w + x;
+                    if visited[idx] {
+                        continue;
+                    }
+
+                    let score = if shape.len() == 4 {
+                        scores[[0, 0, y, x]]
+                    } else {
+                        scores[[0, y, x]]
+                    };
+
+                    if score > threshold {
+                        // BFS to find connected region
+                        let mut min_x = x;
+                        let mut max_x = x;
+                        let mut min_y = y;
+                        let mut max_y = y;
+                        let mut sum_score = 0.0f32;
+                        let mut count = 0;
+
+                        let mut stack = vec![(x, y)];
+                        while let Some((cx, cy)) = stack.pop() {
+                            let cidx = cy * w + cx;
+                            if visited[cidx] {
+                                continue;
+                            }
+                            visited[cidx] = true;
+
+                            let s = if shape.len() == 4 {
+                                scores[[0, 0, cy, cx]]
+                            } else {
+                                scores[[0, cy, cx]]
+                            };
+
+                            if s > threshold {
+                                min_x = min_x.min(cx);
+                                max_x = max_x.max(cx);
+                                min_y = min_y.min(cy);
+                                max_y = max_y.max(cy);
+                                sum_score += s;
+                                count += 1;
+
+                                // Check neighbors
+                                if cx > 0 {
+                                    stack.push((cx - 1, cy));
+                                }
+                                if cx < w - 1 {
+                                    stack.push((cx + 1, cy));
+                                }
+                                if cy > 0 {
+                                    stack.push((cx, cy - 1));
+                                }
+                                if cy < h - 1 {
+                                    stack.push((cx, cy + 1));
+                                }
+                            }
+                        }
+
+                        if count > 4 {
+                            let x1 = min_x as f32 * scale_x;
+                            let y1 = min_y as f32 * scale_y;
+                            let x2 = (max_x + 1) as f32 * scale_x;
+                            let y2 = (max_y + 1) as f32 * scale_y;
+
+                            regions.push(TextRegion {
+                                bbox: [x1, y1, x2 - x1, y2 - y1],
+                                polygon: [[x1, y1], [x2, y1], [x2, y2], [x1, y2]],
+                                confidence: sum_score / count as f32,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(regions)
+}
+
+/// Reads the text of a cropped region with a CTC recognition model (CRNN, PaddleOCR, ...)
+#[cfg(feature = "execute")]
+pub fn recognize_text(
+    session: &mut Session,
+    dyn_image: &DynamicImage,
+    charset: &str,
+    input_height: u32,
+) -> Result<RecognizedText> {
+    let (orig_w, orig_h) = dyn_image.dimensions();
+
+    // Maintain aspect ratio, resize to fixed height
+    let aspect = orig_w as f32 / orig_h as f32;
+    let input_width = (input_height as f32 * aspect).round() as u32;
+    let resized = dyn_image.resize_exact(input_width, input_height, FilterType::Triangle);
+    let gray = resized.to_luma8();
+
+    // Create input tensor [1, 1, H, W] or [1, 3, H, W]
+    let mut input = Array4::<f32>::zeros((1, 1, input_height as usize, input_width as usize));
+    for y in 0..input_height {
+        for x in 0..input_width {
+            let pixel = gray.get_pixel(x, y);
+            input[[0, 0, y as usize, x as usize]] = pixel[0] as f32 / 255.0;
+        }
+    }
+
+    let input_value = Value::from_array(input)?;
+    let outputs = session.run(inputs![input_value])?;
+
+    // CTC decode the output
+    let chars: Vec<char> = charset.chars().collect();
+    let mut text = String::new();
+    let mut char_confidences = Vec::new();
+    let mut prev_idx: Option<usize> = None;
+
+    if let Some((_, tensor)) = outputs.iter().next()
+        && let Ok(logits) = tensor.try_extract_array::<f32>()
+    {
+        let shape = logits.shape();
+
+        // Expect shape [1, T, num_classes] or [T, num_classes]
+        let (seq_len, num_classes) = if shape.len() == 3 {
+            (shape[1], shape[2])
+        } else {
+            (shape[0], shape[1])
+        };
+
+        for t in 0..seq_len {
+            // Find max class
+            let mut max_idx = 0;
+            let mut max_val = f32::NEG_INFINITY;
+
+            for c in 0..num_classes {
+                let val = if shape.len() == 3 {
+                    logits[[0, t, c]]
+                } else {
+                    logits[[t, c]]
+                };
+                if val > max_val {
+                    max_val = val;
+                    max_idx = c;
+                }
+            }
+
+            // CTC blank is usually 0 or last class
+            let blank_idx = 0;
+            if max_idx != blank_idx
+                && Some(max_idx) != prev_idx
+                && let Some(ch) = chars.get(max_idx.saturating_sub(1))
+            {
+                text.push(*ch);
+                // Softmax for confidence
+                let conf = (max_val).exp();
+                char_confidences.push(conf);
+            }
+            prev_idx = Some(max_idx);
+        }
+    }
+
+    let avg_conf = if char_confidences.is_empty() {
+        0.0
+    } else {
+        char_confidences.iter().sum::<f32>() / char_confidences.len() as f32
+    };
+
+    Ok(RecognizedText {
+        text,
+        confidence: avg_conf,
+        char_confidences,
+    })
+}
+
 #[crate::register_node]
 #[derive(Default)]
 pub struct TextDetectionNode {}
@@ -152,122 +374,7 @@ impl NodeLogic for TextDetectionNode {
 
             let img_wrapper = image.get_image(context).await?;
             let dyn_image = img_wrapper.lock().await;
-            let (orig_w, orig_h) = dyn_image.dimensions();
-
-            // Preprocess
-            let resized = dyn_image.resize_exact(input_size, input_size, FilterType::Triangle);
-            let rgb = resized.to_rgb8();
-
-            let mut input = Array4::<f32>::zeros((1, 3, input_size as usize, input_size as usize));
-            for y in 0..input_size {
-                for x in 0..input_size {
-                    let pixel = rgb.get_pixel(x, y);
-                    input[[0, 0, y as usize, x as usize]] = pixel[0] as f32 / 255.0;
-                    input[[0, 1, y as usize, x as usize]] = pixel[1] as f32 / 255.0;
-                    input[[0, 2, y as usize, x as usize]] = pixel[2] as f32 / 255.0;
-                }
-            }
-
-            let input_value = Value::from_array(input)?;
-            let outputs = session.run(inputs![input_value])?;
-
-            // Parse output - depends on model architecture
-            // Generic approach: look for score map and geometry
-            let mut regions: Vec<TextRegion> = Vec::new();
-
-            // Try to extract score map from first output
-            if let Some((_, tensor)) = outputs.iter().next()
-                && let Ok(scores) = tensor.try_extract_array::<f32>()
-            {
-                let shape = scores.shape();
-
-                // Simple connected component analysis on score map
-                if shape.len() >= 3 {
-                    let h = if shape.len() == 4 { shape[2] } else { shape[1] };
-                    let w = if shape.len() == 4 { shape[3] } else { shape[2] };
-
-                    let scale_x = orig_w as f32 / w as f32;
-                    let scale_y = orig_h as f32 / h as f32;
-
-                    // Find connected regions above threshold
-                    let mut visited = vec![false; h * w];
-                    for y in 0..h {
-                        for x in 0..w {
-                            let idx = y * w + x;
-                            if visited[idx] {
-                                continue;
-                            }
-
-                            let score = if shape.len() == 4 {
-                                scores[[0, 0, y, x]]
-                            } else {
-                                scores[[0, y, x]]
-                            };
-
-                            if score > threshold as f32 {
-                                // BFS to find connected region
-                                let mut min_x = x;
-                                let mut max_x = x;
-                                let mut min_y = y;
-                                let mut max_y = y;
-                                let mut sum_score = 0.0f32;
-                                let mut count = 0;
-
-                                let mut stack = vec![(x, y)];
-                                while let Some((cx, cy)) = stack.pop() {
-                                    let cidx = cy * w + cx;
-                                    if visited[cidx] {
-                                        continue;
-                                    }
-                                    visited[cidx] = true;
-
-                                    let s = if shape.len() == 4 {
-                                        scores[[0, 0, cy, cx]]
-                                    } else {
-                                        scores[[0, cy, cx]]
-                                    };
-
-                                    if s > threshold as f32 {
-                                        min_x = min_x.min(cx);
-                                        max_x = max_x.max(cx);
-                                        min_y = min_y.min(cy);
-                                        max_y = max_y.max(cy);
-                                        sum_score += s;
-                                        count += 1;
-
-                                        // Check neighbors
-                                        if cx > 0 {
-                                            stack.push((cx - 1, cy));
-                                        }
-                                        if cx < w - 1 {
-                                            stack.push((cx + 1, cy));
-                                        }
-                                        if cy > 0 {
-                                            stack.push((cx, cy - 1));
-                                        }
-                                        if cy < h - 1 {
-                                            stack.push((cx, cy + 1));
-                                        }
-                                    }
-                                }
-
-                                if count > 4 {
-                                    let x1 = min_x as f32 * scale_x;
-                                    let y1 = min_y as f32 * scale_y;
-                                    let x2 = (max_x + 1) as f32 * scale_x;
-                                    let y2 = (max_y + 1) as f32 * scale_y;
-
-                                    regions.push(TextRegion {
-                                        bbox: [x1, y1, x2 - x1, y2 - y1],
-                                        polygon: [[x1, y1], [x2, y1], [x2, y2], [x1, y2]],
-                                        confidence: sum_score / count as f32,
-                                    });
-                                }
-                            }
-                        }
-                    }
-                }
-            }
+            let regions = detect_text(session, &dyn_image, threshold as f32, input_size)?;
 
             let count = regions.len() as i64;
             context.set_pin_value("regions", json!(regions)).await?;
@@ -328,8 +435,13 @@ impl NodeLogic for TextRecognitionNode {
         .set_schema::<NodeImage>()
         .set_options(PinOptions::new().set_enforce_schema(true).build());
 
-        node.add_input_pin("charset", "Charset", "Character set for decoding", VariableType::String)
-            .set_default_value(Some(json!("0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ!\"#$%&'()*+,-./:;<=>?@[\\]^_`{|}~ ")));
+        node.add_input_pin(
+            "charset",
+            "Charset",
+            "Character set for decoding",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!(DEFAULT_CHARSET)));
 
         node.add_input_pin(
             "input_height",
@@ -377,91 +489,10 @@ impl NodeLogic for TextRecognitionNode {
 
             let img_wrapper = image.get_image(context).await?;
             let dyn_image = img_wrapper.lock().await;
-            let (orig_w, orig_h) = dyn_image.dimensions();
-
-            // Maintain aspect ratio, resize to fixed height
-            let aspect = orig_w as f32 / orig_h as f32;
-            let input_width = (input_height as f32 * aspect).round() as u32;
-            let resized = dyn_image.resize_exact(input_width, input_height, FilterType::Triangle);
-            let gray = resized.to_luma8();
-
-            // Create input tensor [1, 1, H, W] or [1, 3, H, W]
-            let mut input =
-                Array4::<f32>::zeros((1, 1, input_height as usize, input_width as usize));
-            for y in 0..input_height {
-                for x in 0..input_width {
-                    let pixel = gray.get_pixel(x, y);
-                    input[[0, 0, y as usize, x as usize]] = pixel[0] as f32 / 255.0;
-                }
-            }
-
-            let input_value = Value::from_array(input)?;
-            let outputs = session.run(inputs![input_value])?;
-
-            // CTC decode the output
-            let chars: Vec<char> = charset.chars().collect();
-            let mut text = String::new();
-            let mut char_confidences = Vec::new();
-            let mut prev_idx: Option<usize> = None;
-
-            if let Some((_, tensor)) = outputs.iter().next()
-                && let Ok(logits) = tensor.try_extract_array::<f32>()
-            {
-                let shape = logits.shape();
-
-                // Expect shape [1, T, num_classes] or [T, num_classes]
-                let (seq_len, num_classes) = if shape.len() == 3 {
-                    (shape[1], shape[2])
-                } else {
-                    (shape[0], shape[1])
-                };
-
-                for t in 0..seq_len {
-                    // Find max class
-                    let mut max_idx = 0;
-                    let mut max_val = f32::NEG_INFINITY;
-
-                    for c in 0..num_classes {
-                        let val = if shape.len() == 3 {
-                            logits[[0, t, c]]
-                        } else {
-                            logits[[t, c]]
-                        };
-                        if val > max_val {
-                            max_val = val;
-                            max_idx = c;
-                        }
-                    }
-
-                    // CTC blank is usually 0 or last class
-                    let blank_idx = 0;
-                    if max_idx != blank_idx
-                        && Some(max_idx) != prev_idx
-                        && let Some(ch) = chars.get(max_idx.saturating_sub(1))
-                    {
-                        text.push(*ch);
-                        // Softmax for confidence
-                        let conf = (max_val).exp();
-                        char_confidences.push(conf);
-                    }
-                    prev_idx = Some(max_idx);
-                }
-            }
-
-            let avg_conf = if char_confidences.is_empty() {
-                0.0
-            } else {
-                char_confidences.iter().sum::<f32>() / char_confidences.len() as f32
-            };
-
-            let result = RecognizedText {
-                text: text.clone(),
-                confidence: avg_conf,
-                char_confidences,
-            };
+            let result = recognize_text(session, &dyn_image, &charset, input_height)?;
 
             context.set_pin_value("result", json!(result)).await?;
-            context.set_pin_value("text", json!(text)).await?;
+            context.set_pin_value("text", json!(result.text)).await?;
             context.activate_exec_pin("exec_out").await?;
             Ok(())
         }
@@ -17,6 +17,8 @@ pub mod audio;
 pub mod batch;
 /// ONNX Image Classification Nodes
 pub mod classification;
+/// ONNX CLIP Image/Text Embedding Nodes
+pub mod clip;
 /// ONNX Depth Estimation Nodes
 pub mod depth;
 /// ONNX Image Object Detection Nodes
@@ -35,12 +37,16 @@ pub mod ner;
 pub mod ocr;
 /// ONNX Pose Estimation Nodes
 pub mod pose;
+/// ONNX Cross-Encoder Reranking
+pub mod rerank;
 /// ONNX Semantic/Instance Segmentation Nodes
 pub mod segmentation;
 /// ONNX Model Utility Nodes
 pub mod utils;
+/// ONNX Whisper Speech-to-Text Nodes
+pub mod whisper;
 
-pub use execution_providers::{get_ep_info, initialize_ort, is_initialized};
+pub use execution_providers::{ExecutionProviderKind, get_ep_info, initialize_ort, is_initialized};
 
 /// Model provider type for automatic inference routing
 pub enum Provider {
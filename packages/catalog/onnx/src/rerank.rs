@@ -0,0 +1,160 @@
+/// # ONNX Cross-Encoder Reranking
+/// Scores query/document pairs with cross-encoder models such as ms-marco-MiniLM or bge-reranker.
+#[cfg(feature = "execute")]
+use crate::onnx::{NodeOnnxSession, SessionWithMeta};
+#[cfg(feature = "execute")]
+use flow_like::flow::execution::context::ExecutionContext;
+#[cfg(feature = "execute")]
+use flow_like_catalog_core::FlowPath;
+#[cfg(feature = "execute")]
+use flow_like_model_provider::{
+    ml::{
+        ndarray::Array2,
+        ort::{inputs, value::Value},
+    },
+    rerank::Reranker,
+};
+#[cfg(feature = "execute")]
+use flow_like_types::{Result, anyhow, async_trait, sync::Mutex};
+#[cfg(feature = "execute")]
+use std::{str::FromStr, sync::Arc};
+#[cfg(feature = "execute")]
+use tokenizers::{PaddingParams, PaddingStrategy, Tokenizer, TruncationParams};
+
+/// Converts a row of cross-encoder logits into a relevance score. Single-logit models are
+/// used as is, two-class models use the probability of the "relevant" class.
+pub fn relevance_from_logits(logits: &[f32]) -> f32 {
+    match logits {
+        [] => f32::NEG_INFINITY,
+        [score] => *score,
+        [.., irrelevant, relevant] => 1.0 / (1.0 + (irrelevant - relevant).exp()),
+    }
+}
+
+/// Cross-encoder that scores a whole batch of query/document pairs in one session run.
+#[cfg(feature = "execute")]
+pub struct CrossEncoderReranker {
+    session: Arc<Mutex<SessionWithMeta>>,
+    tokenizer: Tokenizer,
+}
+
+#[cfg(feature = "execute")]
+impl CrossEncoderReranker {
+    pub async fn new(
+        context: &mut ExecutionContext,
+        model: &NodeOnnxSession,
+        tokenizer: &FlowPath,
+        max_length: usize,
+    ) -> Result<Self> {
+        let tokenizer_bytes = tokenizer.get(context, false).await?;
+        let tokenizer_json = String::from_utf8(tokenizer_bytes)
+            .map_err(|e| anyhow!("Invalid tokenizer.json encoding: {}", e))?;
+        let mut tokenizer = Tokenizer::from_str(&tokenizer_json)
+            .map_err(|e| anyhow!("Failed to load tokenizer: {}", e))?;
+
+        tokenizer
+            .with_truncation(Some(TruncationParams {
+                max_length,
+                ..Default::default()
+            }))
+            .map_err(|e| anyhow!("Invalid truncation settings: {}", e))?
+            .with_padding(Some(PaddingParams {
+                strategy: PaddingStrategy::BatchLongest,
+                ..Default::default()
+            }));
+
+        Ok(CrossEncoderReranker {
+            session: model.get_session(context).await?,
+            tokenizer,
+        })
+    }
+}
+
+#[cfg(feature = "execute")]
+#[async_trait]
+impl Reranker for CrossEncoderReranker {
+    async fn score(&self, query: &str, documents: &[String]) -> Result<Vec<f32>> {
+        if documents.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let pairs: Vec<(&str, &str)> = documents
+            .iter()
+            .map(|document| (query, document.as_str()))
+            .collect();
+        let encodings = self
+            .tokenizer
+            .encode_batch(pairs, true)
+            .map_err(|e| anyhow!("Tokenization failed: {}", e))?;
+
+        let batch_size = encodings.len();
+        let seq_len = encodings[0].len();
+        let mut input_ids = Vec::with_capacity(batch_size * seq_len);
+        let mut attention_mask = Vec::with_capacity(batch_size * seq_len);
+        let mut token_type_ids = Vec::with_capacity(batch_size * seq_len);
+        for encoding in &encodings {
+            input_ids.extend(encoding.get_ids().iter().map(|&id| id as i64));
+            attention_mask.extend(encoding.get_attention_mask().iter().map(|&m| m as i64));
+            token_type_ids.extend(encoding.get_type_ids().iter().map(|&t| t as i64));
+        }
+
+        let input_ids =
+            Value::from_array(Array2::from_shape_vec((batch_size, seq_len), input_ids)?)?;
+        let attention_mask = Value::from_array(Array2::from_shape_vec(
+            (batch_size, seq_len),
+            attention_mask,
+        )?)?;
+
+        let mut session_guard = self.session.lock().await;
+        let session = &mut session_guard.session;
+
+        // BERT-style cross-encoders need the segment ids to tell query and document apart
+        let has_token_type_ids = session.inputs.iter().any(|i| i.name == "token_type_ids");
+        let outputs = if has_token_type_ids {
+            let token_type_ids = Value::from_array(Array2::from_shape_vec(
+                (batch_size, seq_len),
+                token_type_ids,
+            )?)?;
+            session.run(inputs![
+                "input_ids" => input_ids,
+                "attention_mask" => attention_mask,
+                "token_type_ids" => token_type_ids
+            ])?
+        } else {
+            session.run(inputs![
+                "input_ids" => input_ids,
+                "attention_mask" => attention_mask
+            ])?
+        };
+
+        let logits_key = outputs
+            .keys()
+            .find(|k| k.contains("logits") || k.contains("output"))
+            .or_else(|| outputs.keys().next())
+            .ok_or_else(|| anyhow!("No output from cross-encoder model"))?;
+        let logits = outputs[logits_key].try_extract_array::<f32>()?;
+
+        let num_labels = logits.len() / batch_size;
+        let logits = logits
+            .as_slice()
+            .ok_or_else(|| anyhow!("Cross-encoder logits are not contiguous"))?;
+
+        Ok(logits
+            .chunks(num_labels.max(1))
+            .map(relevance_from_logits)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relevance_from_logits() {
+        assert_eq!(relevance_from_logits(&[2.5]), 2.5);
+        assert!((relevance_from_logits(&[0.0, 0.0]) - 0.5).abs() < 1e-6);
+        assert!(relevance_from_logits(&[-3.0, 3.0]) > 0.99);
+        assert!(relevance_from_logits(&[3.0, -3.0]) < 0.01);
+    }
+}
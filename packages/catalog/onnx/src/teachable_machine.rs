@@ -5,17 +5,495 @@ use flow_like::flow::{
     variable::VariableType,
 };
 use flow_like_catalog_core::{ClassPrediction, FlowPath, NodeImage};
-use flow_like_types::{Result, async_trait, json::json};
+use flow_like_types::{
+    Cacheable, Result, Value, async_trait,
+    json::{Map, json},
+};
 #[cfg(feature = "execute")]
 use flow_like_types::{
     image::{RgbImage, imageops, imageops::FilterType},
     tokio,
 };
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 #[cfg(feature = "execute")]
 use std::io::Cursor;
+use std::sync::Arc;
 #[cfg(feature = "execute")]
 use tract_tflite::prelude::*;
 
+/// Input size of image models exported without metadata
+const DEFAULT_IMAGE_SIZE: u32 = 224;
+/// PoseNet resolution Teachable Machine uses if the export does not name one
+const DEFAULT_POSE_RESOLUTION: u32 = 257;
+/// Audio models take a spectrogram of 43 frames with 232 frequency bins each
+const AUDIO_FFT_BINS: u32 = 232;
+const AUDIO_FRAMES: u32 = 43;
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TeachableMachineModelType {
+    #[default]
+    Image,
+    Pose,
+    Audio,
+}
+
+impl TeachableMachineModelType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TeachableMachineModelType::Image => "image",
+            TeachableMachineModelType::Pose => "pose",
+            TeachableMachineModelType::Audio => "audio",
+        }
+    }
+}
+
+/// What a Teachable Machine export says about its model
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, Default, PartialEq)]
+pub struct TeachableMachineMetadata {
+    pub model_type: TeachableMachineModelType,
+    /// Class labels in the order of the model outputs
+    pub labels: Vec<String>,
+    /// Expected input width, for audio models the number of frequency bins
+    pub input_width: u32,
+    /// Expected input height, for audio models the number of frames
+    pub input_height: u32,
+    pub model_name: Option<String>,
+    /// Teachable Machine version the model was trained with
+    pub tm_version: Option<String>,
+}
+
+impl Cacheable for TeachableMachineMetadata {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl TeachableMachineMetadata {
+    /// Reads the `metadata.json` of a TensorFlow.js export or the `labels.txt` of a
+    /// TensorFlow Lite export, told apart by the file name
+    pub fn parse(file_name: &str, bytes: &[u8]) -> Result<Self> {
+        let text = std::str::from_utf8(bytes)
+            .map_err(|e| flow_like_types::anyhow!("Metadata is not UTF-8: {}", e))?;
+        if file_name.to_lowercase().ends_with(".json") {
+            Self::from_metadata_json(text)
+        } else {
+            Ok(Self::from_labels_txt(text))
+        }
+    }
+
+    pub fn from_metadata_json(text: &str) -> Result<Self> {
+        let metadata: Value = flow_like_types::json::from_str(text)
+            .map_err(|e| flow_like_types::anyhow!("Invalid metadata.json: {}", e))?;
+        let string = |key: &str| metadata[key].as_str().map(str::to_string);
+        let labels = |key: &str| {
+            metadata[key].as_array().map(|labels| {
+                labels
+                    .iter()
+                    .map(|label| match label {
+                        Value::String(label) => label.clone(),
+                        other => other.to_string(),
+                    })
+                    .collect::<Vec<_>>()
+            })
+        };
+
+        // Audio exports come from the speech commands library and name their labels differently
+        if let Some(labels) = labels("wordLabels") {
+            return Ok(Self {
+                model_type: TeachableMachineModelType::Audio,
+                labels,
+                input_width: AUDIO_FFT_BINS,
+                input_height: AUDIO_FRAMES,
+                model_name: string("modelName"),
+                tm_version: string("tmVersion"),
+            });
+        }
+
+        let labels = labels("labels")
+            .ok_or_else(|| flow_like_types::anyhow!("metadata.json does not list any labels"))?;
+        let is_pose = string("packageName").is_some_and(|name| name.contains("pose"));
+        let (model_type, size) = if is_pose {
+            let resolution = metadata["modelSettings"]["posenet"]["inputResolution"]
+                .as_u64()
+                .map(|size| size as u32)
+                .unwrap_or(DEFAULT_POSE_RESOLUTION);
+            (TeachableMachineModelType::Pose, resolution)
+        } else {
+            let size = metadata["imageSize"]
+                .as_u64()
+                .map(|size| size as u32)
+                .unwrap_or(DEFAULT_IMAGE_SIZE);
+            (TeachableMachineModelType::Image, size)
+        };
+
+        Ok(Self {
+            model_type,
+            labels,
+            input_width: size,
+            input_height: size,
+            model_name: string("modelName"),
+            tm_version: string("tmVersion"),
+        })
+    }
+
+    /// `labels.txt` only knows labels, TensorFlow Lite exports are always image models
+    pub fn from_labels_txt(text: &str) -> Self {
+        Self {
+            model_type: TeachableMachineModelType::Image,
+            labels: parse_labels(text),
+            input_width: DEFAULT_IMAGE_SIZE,
+            input_height: DEFAULT_IMAGE_SIZE,
+            model_name: None,
+            tm_version: None,
+        }
+    }
+}
+
+/// Labels of a `labels.txt`, without the class index Teachable Machine puts in front
+pub fn parse_labels(text: &str) -> Vec<String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| match line.split_once(' ') {
+            Some((index, label)) if index.parse::<u32>().is_ok() => label.trim().to_string(),
+            _ => line.to_string(),
+        })
+        .collect()
+}
+
+fn label_for(labels: &[String], class_idx: usize) -> String {
+    labels
+        .get(class_idx)
+        .cloned()
+        .unwrap_or_else(|| format!("class_{}", class_idx))
+}
+
+/// Predictions for every class, best first
+pub fn rank_predictions(scores: &[f32], labels: &[String]) -> Vec<ClassPrediction> {
+    let mut predictions: Vec<ClassPrediction> = scores
+        .iter()
+        .enumerate()
+        .map(|(class_idx, score)| ClassPrediction {
+            class_idx: class_idx as u32,
+            score: *score,
+            label: Some(label_for(labels, class_idx)),
+        })
+        .collect();
+    predictions.sort_by(|a, b| b.score.total_cmp(&a.score));
+    predictions
+}
+
+/// Scores keyed by label. Classes without a label are keyed `class_<index>`.
+pub fn label_scores(scores: &[f32], labels: &[String]) -> Map<String, Value> {
+    scores
+        .iter()
+        .enumerate()
+        .map(|(class_idx, score)| (label_for(labels, class_idx), json!(score)))
+        .collect()
+}
+
+/// Metadata of the export at `package`, read once per run
+pub async fn load_metadata(
+    context: &mut ExecutionContext,
+    package: &FlowPath,
+) -> Result<Arc<TeachableMachineMetadata>> {
+    let cache_key = format!(
+        "teachable_machine_metadata_{}_{}",
+        package.store_ref, package.path
+    );
+    if let Some(metadata) = context.get_cached(&cache_key).await {
+        return Ok(metadata);
+    }
+
+    let bytes = package.get(context, false).await?;
+    let metadata = Arc::new(TeachableMachineMetadata::parse(&package.path, &bytes)?);
+    context.set_cache(&cache_key, metadata.clone()).await;
+    Ok(metadata)
+}
+
+#[cfg(feature = "execute")]
+type Classifier = Box<dyn Fn(Tensor) -> Result<Vec<f32>> + Send + Sync>;
+
+/// A model prepared for one input size
+#[cfg(feature = "execute")]
+struct CachedTeachableMachineModel {
+    input_type: DatumType,
+    classify: Classifier,
+}
+
+#[cfg(feature = "execute")]
+impl Cacheable for CachedTeachableMachineModel {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(feature = "execute")]
+fn read_tflite(model_bytes: Vec<u8>) -> Result<TypedModel> {
+    let model_bytes = find_tflite_slice(&model_bytes)
+        .ok_or_else(|| flow_like_types::anyhow!("Could not locate TFLite buffer (TFL3 id)"))?
+        .to_vec();
+    let mut cursor = Cursor::new(model_bytes);
+    tract_tflite::tflite()
+        .model_for_read(&mut cursor)
+        .map_err(|e| flow_like_types::anyhow!("TFLite parse error: {e}"))
+}
+
+/// Input width and height the model declares, `None` if its input shape is dynamic
+#[cfg(feature = "execute")]
+fn model_input_size(model: &TypedModel) -> Result<Option<(u32, u32)>> {
+    let inlet = model.input_outlets()?[0];
+    let fact = model.outlet_fact(inlet)?;
+    Ok(match fact.shape.as_concrete() {
+        Some([_, height, width, _]) => Some((*width as u32, *height as u32)),
+        _ => None,
+    })
+}
+
+#[cfg(feature = "execute")]
+fn prepare_model(model: TypedModel, iw: u32, ih: u32) -> Result<CachedTeachableMachineModel> {
+    let inlet = model.input_outlets()?[0];
+    let input_type = model.outlet_fact(inlet)?.datum_type;
+    if !matches!(input_type, DatumType::F32 | DatumType::U8) {
+        return Err(flow_like_types::anyhow!(
+            "Unsupported input dtype: {input_type:?} (only F32 and U8 are supported)"
+        ));
+    }
+
+    let input_shape = tvec!(1, ih as usize, iw as usize, 3);
+    let runnable = model
+        .with_input_fact(0, TypedFact::dt_shape(input_type, input_shape))?
+        .into_optimized()?
+        .into_runnable()?;
+
+    let classify: Classifier = Box::new(move |tensor: Tensor| {
+        let outputs = runnable
+            .run(tvec!(tensor.into()))
+            .map_err(|e| flow_like_types::anyhow!("Failed to run TFLite model: {e}"))?;
+        let output = outputs
+            .first()
+            .ok_or_else(|| flow_like_types::anyhow!("Model produced no outputs"))?;
+        let scores = output
+            .to_array_view::<f32>()
+            .map_err(|e| flow_like_types::anyhow!("Output is not f32: {}", e))?;
+        Ok(scores.iter().copied().collect())
+    });
+
+    Ok(CachedTeachableMachineModel {
+        input_type,
+        classify,
+    })
+}
+
+/// Model at `path` prepared for `iw` x `ih` inputs, parsed once per run
+#[cfg(feature = "execute")]
+async fn load_model(
+    context: &mut ExecutionContext,
+    path: &FlowPath,
+    iw: u32,
+    ih: u32,
+) -> Result<Arc<CachedTeachableMachineModel>> {
+    let cache_key = format!(
+        "teachable_machine_model_{}_{}_{}x{}",
+        path.store_ref, path.path, iw, ih
+    );
+    if let Some(model) = context.get_cached(&cache_key).await {
+        return Ok(model);
+    }
+
+    let raw = path.get(context, false).await.map_err(|e| {
+        flow_like_types::anyhow!("Failed to load .tflite model '{}': {}", path.path, e)
+    })?;
+    if raw.is_empty() {
+        return Err(flow_like_types::anyhow!(
+            "Model file '{}' is empty",
+            path.path
+        ));
+    }
+
+    let model = tokio::task::spawn_blocking(move || prepare_model(read_tflite(raw)?, iw, ih))
+        .await
+        .map_err(|e| flow_like_types::anyhow!("TFLite load task join error: {}", e))??;
+    let model = Arc::new(model);
+    context.set_cache(&cache_key, model.clone()).await;
+    Ok(model)
+}
+
+/// Scales the image to the model input and normalizes it the way Teachable Machine does
+#[cfg(feature = "execute")]
+fn image_tensor(src: &RgbImage, input_type: DatumType, iw: u32, ih: u32) -> Result<Tensor> {
+    // Resize exactly with bicubic, like OpenCV INTER_CUBIC
+    let resized = imageops::resize(src, iw, ih, FilterType::CatmullRom);
+    let shape = (1, ih as usize, iw as usize, 3);
+    let tensor: Tensor = match input_type {
+        DatumType::F32 => tract_ndarray::Array4::<f32>::from_shape_fn(shape, |(_, y, x, c)| {
+            let p = resized.get_pixel(x as u32, y as u32);
+            p[c] as f32 / 127.5 - 1.0
+        })
+        .into(),
+        DatumType::U8 => tract_ndarray::Array4::<u8>::from_shape_fn(shape, |(_, y, x, c)| {
+            resized.get_pixel(x as u32, y as u32)[c]
+        })
+        .into(),
+        dt => {
+            return Err(flow_like_types::anyhow!(
+                "Unsupported input dtype: {dt:?} (only F32 and U8 are supported)"
+            ));
+        }
+    };
+    Ok(tensor)
+}
+
+/// Replaces the input size of `metadata` with the one the connected model declares
+#[cfg(feature = "execute")]
+async fn with_model_input_size(
+    context: &mut ExecutionContext,
+    mut metadata: TeachableMachineMetadata,
+) -> Result<TeachableMachineMetadata> {
+    let Ok(model_path) = context.evaluate_pin::<FlowPath>("model").await else {
+        return Ok(metadata);
+    };
+    let raw = model_path.get(context, false).await?;
+    let size = tokio::task::spawn_blocking(move || model_input_size(&read_tflite(raw)?))
+        .await
+        .map_err(|e| flow_like_types::anyhow!("TFLite load task join error: {}", e))??;
+    if let Some((width, height)) = size {
+        metadata.input_width = width;
+        metadata.input_height = height;
+    }
+    Ok(metadata)
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct TeachableMachineInfoNode {}
+
+impl TeachableMachineInfoNode {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for TeachableMachineInfoNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "ai_ml_teachable_machine_info",
+            "Teachable Machine Info",
+            "Reads the labels, input size and model type of a Teachable Machine export",
+            "AI/ML/Teachable Machine",
+        );
+
+        node.add_icon("/flow/icons/find_model.svg");
+
+        node.add_input_pin(
+            "exec_in",
+            "Input",
+            "Initiate Execution",
+            VariableType::Execution,
+        );
+
+        node.add_input_pin(
+            "package",
+            "Package",
+            "metadata.json of a TensorFlow.js export or labels.txt of a TensorFlow Lite export",
+            VariableType::Struct,
+        )
+        .set_schema::<FlowPath>()
+        .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node.add_input_pin(
+            "model",
+            "Model File",
+            "Optional *.tflite model, its input shape takes precedence over the package",
+            VariableType::Struct,
+        )
+        .set_schema::<FlowPath>()
+        .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node.add_output_pin(
+            "exec_out",
+            "Output",
+            "Done with the Execution",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "metadata",
+            "Metadata",
+            "Everything below in one struct, connect it to the Teachable Machine node",
+            VariableType::Struct,
+        )
+        .set_schema::<TeachableMachineMetadata>();
+
+        node.add_output_pin(
+            "labels",
+            "Labels",
+            "Class labels in the order of the model outputs",
+            VariableType::String,
+        )
+        .set_value_type(ValueType::Array);
+
+        node.add_output_pin(
+            "input_width",
+            "Input Width",
+            "Expected input width, frequency bins for audio models",
+            VariableType::Integer,
+        );
+
+        node.add_output_pin(
+            "input_height",
+            "Input Height",
+            "Expected input height, frames for audio models",
+            VariableType::Integer,
+        );
+
+        node.add_output_pin(
+            "model_type",
+            "Model Type",
+            "image, pose or audio",
+            VariableType::String,
+        );
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+
+        let package: FlowPath = context.evaluate_pin("package").await?;
+        let metadata = load_metadata(context, &package).await?.as_ref().clone();
+        #[cfg(feature = "execute")]
+        let metadata = with_model_input_size(context, metadata).await?;
+
+        context
+            .set_pin_value("labels", json!(metadata.labels))
+            .await?;
+        context
+            .set_pin_value("input_width", json!(metadata.input_width))
+            .await?;
+        context
+            .set_pin_value("input_height", json!(metadata.input_height))
+            .await?;
+        context
+            .set_pin_value("model_type", json!(metadata.model_type.as_str()))
+            .await?;
+        context.set_pin_value("metadata", json!(metadata)).await?;
+        context.activate_exec_pin("exec_out").await?;
+        Ok(())
+    }
+}
+
 #[crate::register_node]
 #[derive(Default)]
 pub struct TeachableMachineNode {}
@@ -58,10 +536,18 @@ impl NodeLogic for TeachableMachineNode {
             .set_schema::<NodeImage>()
             .set_options(PinOptions::new().set_enforce_schema(true).build());
 
+        node.add_input_pin(
+            "metadata",
+            "Metadata",
+            "Optional output of Teachable Machine Info, provides labels and input size",
+            VariableType::Struct,
+        )
+        .set_schema::<TeachableMachineMetadata>();
+
         node.add_input_pin(
             "labels",
             "Labels",
-            "Optional labels.txt",
+            "Optional labels.txt or metadata.json, used if no Metadata is connected",
             VariableType::Struct,
         )
         .set_schema::<FlowPath>()
@@ -70,14 +556,14 @@ impl NodeLogic for TeachableMachineNode {
         node.add_input_pin(
             "input_width",
             "Input Width",
-            "Model input width",
+            "Model input width, used if no Metadata is connected",
             VariableType::Integer,
         )
         .set_default_value(Some(json!(224)));
         node.add_input_pin(
             "input_height",
             "Input Height",
-            "Model input height",
+            "Model input height, used if no Metadata is connected",
             VariableType::Integer,
         )
         .set_default_value(Some(json!(224)));
@@ -91,10 +577,16 @@ impl NodeLogic for TeachableMachineNode {
         node.add_output_pin(
             "predictions",
             "Predictions",
-            "Class Predictions",
+            "Class Predictions for every class, best first",
             VariableType::Struct,
         )
         .set_value_type(ValueType::Array);
+        node.add_output_pin(
+            "scores",
+            "Scores",
+            "Score of every class keyed by its label",
+            VariableType::Struct,
+        );
 
         node
     }
@@ -105,136 +597,47 @@ impl NodeLogic for TeachableMachineNode {
 
         let model_path: FlowPath = context.evaluate_pin("model").await?;
         let node_img: NodeImage = context.evaluate_pin("image_in").await?;
-        let labels_path_opt: Option<FlowPath> = context.evaluate_pin("labels").await.ok();
-        let iw_pin: i64 = context.evaluate_pin("input_width").await.unwrap_or(224);
-        let ih_pin: i64 = context.evaluate_pin("input_height").await.unwrap_or(224);
-        let (iw, ih) = (iw_pin.max(1) as u32, ih_pin.max(1) as u32);
-
-        let labels_vec: Vec<String> = if let Some(labels_path) = labels_path_opt {
-            let lbs = labels_path.get(context, false).await?;
-            let s = String::from_utf8(lbs)
-                .map_err(|e| flow_like_types::anyhow!("Failed to parse labels file: {}", e))?;
-            s.lines()
-                .map(|l| l.trim().to_string())
-                .filter(|l| !l.is_empty())
-                .collect()
-        } else {
-            Vec::new()
-        };
 
-        let raw = model_path.get(context, false).await.map_err(|e| {
-            flow_like_types::anyhow!("Failed to load .tflite model '{}': {}", model_path.path, e)
-        })?;
-        if raw.is_empty() {
-            return Err(flow_like_types::anyhow!(
-                "Model file '{}' is empty",
-                model_path.path
-            ));
-        }
+        let metadata = match context
+            .evaluate_pin::<TeachableMachineMetadata>("metadata")
+            .await
+        {
+            Ok(metadata) if !metadata.labels.is_empty() => Some(metadata),
+            _ => None,
+        };
+        let (labels, iw, ih) = match metadata {
+            Some(metadata) => (metadata.labels, metadata.input_width, metadata.input_height),
+            None => {
+                let labels = match context.evaluate_pin::<FlowPath>("labels").await {
+                    Ok(labels_path) => load_metadata(context, &labels_path).await?.labels.clone(),
+                    Err(_) => Vec::new(),
+                };
+                let iw: i64 = context.evaluate_pin("input_width").await.unwrap_or(224);
+                let ih: i64 = context.evaluate_pin("input_height").await.unwrap_or(224);
+                (labels, iw as u32, ih as u32)
+            }
+        };
+        let (iw, ih) = (iw.max(1), ih.max(1));
 
-        let model_bytes: Vec<u8> = find_tflite_slice(&raw)
-            .ok_or_else(|| flow_like_types::anyhow!("Could not locate TFLite buffer (TFL3 id)"))?
-            .to_vec();
+        let model = load_model(context, &model_path, iw, ih).await?;
 
         let img = node_img.get_image(context).await?;
         let img_guard = img.lock().await;
         let rgb = img_guard.to_rgb8();
-        let (src_w, src_h) = rgb.dimensions();
-        let rgb_bytes = rgb.into_raw();
         drop(img_guard);
 
-        let (top_idx, top_score) =
-            tokio::task::spawn_blocking(move || -> flow_like_types::Result<(usize, f32)> {
-                use tract_data::prelude::DatumType;
-
-                // Load model
-                let mut cursor = Cursor::new(model_bytes);
-                let model = tract_tflite::tflite()
-                    .model_for_read(&mut cursor)
-                    .map_err(|e| flow_like_types::anyhow!("TFLite parse error: {e}"))?;
-
-                // Prepare image (resize exactly with bicubic, like OpenCV INTER_CUBIC)
-                let src = RgbImage::from_raw(src_w, src_h, rgb_bytes)
-                    .ok_or_else(|| flow_like_types::anyhow!("Invalid source image buffer"))?;
-                let resized = imageops::resize(&src, iw, ih, FilterType::CatmullRom);
-
-                // Inspect input dtype and set input fact/shape
-                let inlet = model.input_outlets()?[0];
-                let orig = model.outlet_fact(inlet)?;
-                let input_shape = tvec!(1, ih as usize, iw as usize, 3);
-                let (fact, tensor): (TypedFact, Tensor) = match orig.datum_type {
-                    DatumType::F32 => {
-                        let arr = tract_ndarray::Array4::<f32>::from_shape_fn(
-                            (1, ih as usize, iw as usize, 3),
-                            |(_, y, x, c)| {
-                                let p = resized.get_pixel(x as u32, y as u32);
-                                p[c] as f32 / 127.5 - 1.0
-                            },
-                        );
-                        (
-                            TypedFact::dt_shape(f32::datum_type(), input_shape),
-                            arr.into(),
-                        )
-                    }
-                    DatumType::U8 => {
-                        let arr = tract_ndarray::Array4::<u8>::from_shape_fn(
-                            (1, ih as usize, iw as usize, 3),
-                            |(_, y, x, c)| {
-                                let p = resized.get_pixel(x as u32, y as u32);
-                                p[c]
-                            },
-                        );
-                        (
-                            TypedFact::dt_shape(u8::datum_type(), input_shape),
-                            arr.into(),
-                        )
-                    }
-                    dt => {
-                        return Err(flow_like_types::anyhow!(
-                            "Unsupported input dtype: {dt:?} (only F32 and U8 are supported)"
-                        ));
-                    }
-                };
-
-                let runnable = model
-                    .with_input_fact(0, fact)?
-                    .into_optimized()?
-                    .into_runnable()?;
-
-                let outputs = runnable
-                    .run(tvec!(tensor.into()))
-                    .map_err(|e| flow_like_types::anyhow!("Failed to run TFLite model: {e}"))?;
-
-                if outputs.is_empty() {
-                    return Err(flow_like_types::anyhow!("Model produced no outputs"));
-                }
-
-                let out = outputs[0]
-                    .to_array_view::<f32>()
-                    .map_err(|e| flow_like_types::anyhow!("Output is not f32: {}", e))?;
-
-                let mut best_idx = 0usize;
-                let mut best_score = f32::MIN;
-                for (i, v) in out.iter().enumerate() {
-                    if *v > best_score {
-                        best_idx = i;
-                        best_score = *v;
-                    }
-                }
-                Ok((best_idx, best_score))
-            })
-            .await
-            .map_err(|e| flow_like_types::anyhow!("TFLite inference task join error: {}", e))??;
-
-        let label = labels_vec.get(top_idx).cloned();
-        let predictions = vec![ClassPrediction {
-            class_idx: top_idx as u32,
-            score: top_score,
-            label,
-        }];
+        let scores = tokio::task::spawn_blocking(move || -> Result<Vec<f32>> {
+            let tensor = image_tensor(&rgb, model.input_type, iw, ih)?;
+            (model.classify)(tensor)
+        })
+        .await
+        .map_err(|e| flow_like_types::anyhow!("TFLite inference task join error: {}", e))??;
 
         context
-            .set_pin_value("predictions", json!(predictions))
+            .set_pin_value("predictions", json!(rank_predictions(&scores, &labels)))
+            .await?;
+        context
+            .set_pin_value("scores", Value::Object(label_scores(&scores, &labels)))
             .await?;
         context.activate_exec_pin("exec_out").await?;
         Ok(())
@@ -0,0 +1,922 @@
+/// # ONNX Whisper Speech-to-Text
+/// Transcription and translation with Whisper encoder/decoder exports
+use crate::onnx::audio::{TranscriptionResult, TranscriptionSegment};
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic},
+    pin::{PinOptions, ValueType},
+    variable::VariableType,
+};
+use flow_like_catalog_core::FlowPath;
+#[cfg(feature = "execute")]
+use flow_like_model_provider::ml::{
+    ndarray::{Array2, Array3, ArrayD},
+    ort::{inputs, session::Session, value::Value},
+};
+#[cfg(feature = "execute")]
+use flow_like_types::{Cacheable, sync::Mutex};
+use flow_like_types::{Result, anyhow, async_trait, json::json};
+#[cfg(feature = "execute")]
+use sha2::{Digest, Sha256};
+#[cfg(feature = "execute")]
+use std::{str::FromStr, sync::Arc};
+#[cfg(feature = "execute")]
+use tokenizers::Tokenizer;
+
+pub const SAMPLE_RATE: u32 = 16_000;
+const N_FFT: usize = 400;
+const HOP_LENGTH: usize = 160;
+/// Whisper always sees 30 second windows, shorter audio is zero padded
+const CHUNK_SAMPLES: usize = 30 * SAMPLE_RATE as usize;
+const N_FRAMES: usize = CHUNK_SAMPLES / HOP_LENGTH;
+const SECONDS_PER_TIMESTAMP: f32 = 0.02;
+/// The first timestamp may be at most 1s into the window
+const MAX_INITIAL_TIMESTAMP: usize = 50;
+/// Half of the decoder context, the rest is reserved for the prompt
+const MAX_NEW_TOKENS: usize = 224;
+
+fn hz_to_mel(hz: f64) -> f64 {
+    // Slaney scale: linear below 1 kHz, logarithmic above
+    let f_sp = 200.0 / 3.0;
+    let min_log_mel = 1000.0 / f_sp;
+    let log_step = 6.4f64.ln() / 27.0;
+    if hz >= 1000.0 {
+        min_log_mel + (hz / 1000.0).ln() / log_step
+    } else {
+        hz / f_sp
+    }
+}
+
+fn mel_to_hz(mel: f64) -> f64 {
+    let f_sp = 200.0 / 3.0;
+    let min_log_mel = 1000.0 / f_sp;
+    let log_step = 6.4f64.ln() / 27.0;
+    if mel >= min_log_mel {
+        1000.0 * ((mel - min_log_mel) * log_step).exp()
+    } else {
+        mel * f_sp
+    }
+}
+
+/// Slaney-normalized mel filterbank as `[n_mels, N_FFT / 2 + 1]`, matching
+/// `librosa.filters.mel(sr=16000, n_fft=400)` used to train Whisper.
+pub fn mel_filters(n_mels: usize) -> Vec<f32> {
+    let n_freqs = N_FFT / 2 + 1;
+    let max_mel = hz_to_mel(SAMPLE_RATE as f64 / 2.0);
+    let mel_hz: Vec<f64> = (0..n_mels + 2)
+        .map(|i| mel_to_hz(max_mel * i as f64 / (n_mels + 1) as f64))
+        .collect();
+
+    let mut filters = vec![0.0f32; n_mels * n_freqs];
+    for mel in 0..n_mels {
+        let (lower, center, upper) = (mel_hz[mel], mel_hz[mel + 1], mel_hz[mel + 2]);
+        let norm = 2.0 / (upper - lower);
+        for bin in 0..n_freqs {
+            let hz = bin as f64 * SAMPLE_RATE as f64 / N_FFT as f64;
+            let weight = ((hz - lower) / (center - lower))
+                .min((upper - hz) / (upper - center))
+                .max(0.0);
+            filters[mel * n_freqs + bin] = (weight * norm) as f32;
+        }
+    }
+    filters
+}
+
+/// Log-mel spectrogram of one 30s window as `[n_mels, N_FRAMES]`, normalized the way
+/// Whisper expects its `input_features`.
+pub fn log_mel_spectrogram(samples: &[f32], filters: &[f32], n_mels: usize) -> Vec<f32> {
+    let n_freqs = N_FFT / 2 + 1;
+    let pad = N_FFT / 2;
+
+    let mut audio = vec![0.0f32; CHUNK_SAMPLES];
+    let len = samples.len().min(CHUNK_SAMPLES);
+    audio[..len].copy_from_slice(&samples[..len]);
+
+    // Centered frames with reflect padding, like `torch.stft(center=True)`
+    let mut padded = Vec::with_capacity(CHUNK_SAMPLES + 2 * pad);
+    padded.extend((1..=pad).rev().map(|i| audio[i]));
+    padded.extend_from_slice(&audio);
+    padded.extend((1..=pad).map(|i| audio[CHUNK_SAMPLES - 1 - i]));
+
+    let tau = 2.0 * std::f32::consts::PI / N_FFT as f32;
+    let window: Vec<f32> = (0..N_FFT)
+        .map(|n| 0.5 - 0.5 * (tau * n as f32).cos())
+        .collect();
+    let cos: Vec<f32> = (0..N_FFT).map(|n| (tau * n as f32).cos()).collect();
+    let sin: Vec<f32> = (0..N_FFT).map(|n| (tau * n as f32).sin()).collect();
+
+    let mut mel = vec![0.0f32; n_mels * N_FRAMES];
+    let mut frame = vec![0.0f32; N_FFT];
+    let mut power = vec![0.0f32; n_freqs];
+    for t in 0..N_FRAMES {
+        let start = t * HOP_LENGTH;
+        for (n, value) in frame.iter_mut().enumerate() {
+            *value = padded[start + n] * window[n];
+        }
+
+        for (bin, power) in power.iter_mut().enumerate() {
+            let (mut re, mut im) = (0.0f32, 0.0f32);
+            for (n, value) in frame.iter().enumerate() {
+                let index = (bin * n) % N_FFT;
+                re += value * cos[index];
+                im -= value * sin[index];
+            }
+            *power = re * re + im * im;
+        }
+
+        for m in 0..n_mels {
+            let energy: f32 = filters[m * n_freqs..(m + 1) * n_freqs]
+                .iter()
+                .zip(&power)
+                .map(|(weight, power)| weight * power)
+                .sum();
+            mel[m * N_FRAMES + t] = energy.max(1e-10).log10();
+        }
+    }
+
+    let max = mel.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    for value in &mut mel {
+        *value = (value.max(max - 8.0) + 4.0) / 4.0;
+    }
+    mel
+}
+
+/// Special token ids of a Whisper vocabulary.
+#[derive(Clone, Debug)]
+pub struct WhisperTokens {
+    pub start_of_transcript: u32,
+    pub end_of_text: u32,
+    pub transcribe: u32,
+    pub translate: u32,
+    pub no_timestamps: u32,
+    /// `<|0.00|>`, every following id adds 20ms
+    pub timestamp_begin: u32,
+    /// Language codes with their token ids, empty for English-only models
+    pub languages: Vec<(String, u32)>,
+}
+
+impl WhisperTokens {
+    #[cfg(feature = "execute")]
+    pub fn from_tokenizer(tokenizer: &Tokenizer) -> Result<Self> {
+        let id = |token: &str| {
+            tokenizer
+                .token_to_id(token)
+                .ok_or_else(|| anyhow!("Tokenizer is missing Whisper token {}", token))
+        };
+
+        let start_of_transcript = id("<|startoftranscript|>")?;
+        let translate = id("<|translate|>")?;
+        let no_timestamps = id("<|notimestamps|>")?;
+
+        // Multilingual vocabularies have one extra token, the language tokens sit
+        // between the start token and the task tokens
+        let multilingual = tokenizer.get_vocab_size(true) >= 51865;
+        let languages = if multilingual {
+            (start_of_transcript + 1..translate)
+                .filter_map(|id| {
+                    let token = tokenizer.id_to_token(id)?;
+                    let code = token.strip_prefix("<|")?.strip_suffix("|>")?.to_string();
+                    Some((code, id))
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        Ok(WhisperTokens {
+            start_of_transcript,
+            end_of_text: id("<|endoftext|>")?,
+            transcribe: id("<|transcribe|>")?,
+            translate,
+            no_timestamps,
+            timestamp_begin: no_timestamps + 1,
+            languages,
+        })
+    }
+
+    pub fn language(&self, code: &str) -> Result<u32> {
+        let code = code.trim().to_lowercase();
+        self.languages
+            .iter()
+            .find(|(language, _)| *language == code)
+            .map(|(_, id)| *id)
+            .ok_or_else(|| anyhow!("Language '{}' is not supported by this model", code))
+    }
+
+    fn is_timestamp(&self, token: u32) -> bool {
+        token >= self.timestamp_begin
+    }
+}
+
+fn suppress(logits: &mut [f32], from: usize, to: usize) {
+    let to = to.min(logits.len());
+    if from < to {
+        logits[from..to].fill(f32::NEG_INFINITY);
+    }
+}
+
+fn log_sum_exp(values: &[f32]) -> f32 {
+    let max = values.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    if max == f32::NEG_INFINITY {
+        return max;
+    }
+    max + values
+        .iter()
+        .map(|value| (value - max).exp())
+        .sum::<f32>()
+        .ln()
+}
+
+/// Masks tokens that may not follow `sampled`, the tokens generated after the prompt.
+/// Timestamp handling follows Whisper's reference decoder: timestamps come in pairs, never
+/// go backwards, and are forced when they are more likely than any text token.
+pub fn apply_token_rules(
+    logits: &mut [f32],
+    sampled: &[u32],
+    tokens: &WhisperTokens,
+    timestamps: bool,
+) {
+    let eot = tokens.end_of_text as usize;
+    let ts_begin = tokens.timestamp_begin as usize;
+
+    // Control tokens never appear in a transcript, and an empty one is not useful
+    suppress(logits, eot + 1, ts_begin);
+    if sampled.is_empty() {
+        suppress(logits, eot, eot + 1);
+    }
+
+    if !timestamps {
+        suppress(logits, ts_begin, logits.len());
+        return;
+    }
+
+    let last_was_timestamp = sampled.last().is_some_and(|&t| tokens.is_timestamp(t));
+    let penultimate_was_timestamp =
+        sampled.len() < 2 || tokens.is_timestamp(sampled[sampled.len() - 2]);
+
+    if last_was_timestamp {
+        if penultimate_was_timestamp {
+            suppress(logits, ts_begin, logits.len());
+        } else {
+            suppress(logits, 0, eot);
+        }
+    }
+
+    if let Some(&last) = sampled.iter().rev().find(|&&t| tokens.is_timestamp(t)) {
+        let floor = if last_was_timestamp && !penultimate_was_timestamp {
+            last as usize
+        } else {
+            last as usize + 1
+        };
+        suppress(logits, ts_begin, floor);
+    }
+
+    if sampled.is_empty() {
+        suppress(logits, 0, ts_begin);
+        suppress(logits, ts_begin + MAX_INITIAL_TIMESTAMP + 1, logits.len());
+    }
+
+    if ts_begin < logits.len() {
+        let normalizer = log_sum_exp(logits);
+        let timestamp_logprob = log_sum_exp(&logits[ts_begin..]) - normalizer;
+        let max_text_logprob = logits[..ts_begin]
+            .iter()
+            .copied()
+            .fold(f32::NEG_INFINITY, f32::max)
+            - normalizer;
+        if timestamp_logprob > max_text_logprob {
+            suppress(logits, 0, ts_begin);
+        }
+    }
+}
+
+/// A run of text tokens between two timestamps, times relative to the window.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecodedSegment {
+    pub start: f32,
+    pub end: f32,
+    pub tokens: Vec<u32>,
+    pub confidence: f32,
+}
+
+/// Splits generated `(token, probability)` pairs into segments at timestamp tokens.
+/// Without timestamps the whole window is one segment.
+pub fn group_segments(
+    sampled: &[(u32, f32)],
+    tokens: &WhisperTokens,
+    window_end: f32,
+) -> Vec<DecodedSegment> {
+    let mut segments = Vec::new();
+    let mut start: Option<f32> = None;
+    let mut last_time = 0.0;
+    let mut text: Vec<(u32, f32)> = Vec::new();
+
+    let mut close = |start: f32, end: f32, text: &mut Vec<(u32, f32)>| {
+        if !text.is_empty() {
+            segments.push(DecodedSegment {
+                start,
+                end: end.max(start),
+                tokens: text.iter().map(|(token, _)| *token).collect(),
+                confidence: text.iter().map(|(_, p)| p).sum::<f32>() / text.len() as f32,
+            });
+        }
+        text.clear();
+    };
+
+    for &(token, probability) in sampled {
+        if tokens.is_timestamp(token) {
+            let time = (token - tokens.timestamp_begin) as f32 * SECONDS_PER_TIMESTAMP;
+            match start.take() {
+                Some(begin) => close(begin, time, &mut text),
+                None if !text.is_empty() => close(last_time, time, &mut text),
+                None => start = Some(time),
+            }
+            last_time = time;
+        } else if token < tokens.end_of_text {
+            text.push((token, probability));
+        }
+    }
+
+    close(start.unwrap_or(last_time), window_end, &mut text);
+    segments
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct TranscribeOptions {
+    /// Language code such as `en` or `de`, `None` detects it from the first window
+    pub language: Option<String>,
+    /// Translate the speech to English instead of transcribing it
+    pub translate: bool,
+    pub timestamps: bool,
+}
+
+/// Whisper encoder and decoder sessions with their tokenizer. Expects the optimum export
+/// layout: the encoder takes `input_features`, the decoder `input_ids` and
+/// `encoder_hidden_states` and returns `logits`.
+#[cfg(feature = "execute")]
+pub struct WhisperModel {
+    encoder: Session,
+    decoder: Session,
+    tokenizer: Tokenizer,
+    tokens: WhisperTokens,
+    n_mels: usize,
+    filters: Vec<f32>,
+}
+
+#[cfg(feature = "execute")]
+impl WhisperModel {
+    pub fn from_bytes(encoder: &[u8], decoder: &[u8], tokenizer: &[u8]) -> Result<Self> {
+        let encoder = Session::builder()?.commit_from_memory(encoder)?;
+        let decoder = Session::builder()?.commit_from_memory(decoder)?;
+
+        let tokenizer_json = std::str::from_utf8(tokenizer)
+            .map_err(|e| anyhow!("Invalid tokenizer.json encoding: {}", e))?;
+        let tokenizer = Tokenizer::from_str(tokenizer_json)
+            .map_err(|e| anyhow!("Failed to load tokenizer: {}", e))?;
+        let tokens = WhisperTokens::from_tokenizer(&tokenizer)?;
+
+        // large-v3 uses 128 mel bins, every other size 80
+        let n_mels = encoder
+            .inputs
+            .iter()
+            .find(|input| input.name == "input_features")
+            .and_then(|input| input.input_type.tensor_shape())
+            .and_then(|dims| dims.get(1).copied())
+            .filter(|&dim| dim > 0)
+            .map_or(80, |dim| dim as usize);
+
+        Ok(WhisperModel {
+            encoder,
+            decoder,
+            tokenizer,
+            tokens,
+            n_mels,
+            filters: mel_filters(n_mels),
+        })
+    }
+
+    fn encode(&mut self, samples: &[f32]) -> Result<ArrayD<f32>> {
+        let mel = log_mel_spectrogram(samples, &self.filters, self.n_mels);
+        let features = Value::from_array(Array3::from_shape_vec((1, self.n_mels, N_FRAMES), mel)?)?;
+        let outputs = self.encoder.run(inputs!["input_features" => features])?;
+        let key = outputs
+            .keys()
+            .find(|key| key.contains("last_hidden_state"))
+            .or_else(|| outputs.keys().next())
+            .ok_or_else(|| anyhow!("No output from Whisper encoder"))?;
+        Ok(outputs[key].try_extract_array::<f32>()?.to_owned())
+    }
+
+    /// Logits for the token following `prompt`.
+    fn next_logits(&mut self, hidden: &ArrayD<f32>, prompt: &[u32]) -> Result<Vec<f32>> {
+        let ids: Vec<i64> = prompt.iter().map(|&id| id as i64).collect();
+        let input_ids = Value::from_array(Array2::from_shape_vec((1, ids.len()), ids)?)?;
+        let hidden = Value::from_array(hidden.clone())?;
+        let outputs = self.decoder.run(inputs![
+            "input_ids" => input_ids,
+            "encoder_hidden_states" => hidden
+        ])?;
+
+        let logits = outputs["logits"].try_extract_array::<f32>()?;
+        let vocab = *logits
+            .shape()
+            .last()
+            .ok_or_else(|| anyhow!("Whisper decoder returned scalar logits"))?;
+        let logits = logits
+            .as_slice()
+            .ok_or_else(|| anyhow!("Whisper logits are not contiguous"))?;
+        Ok(logits[logits.len() - vocab..].to_vec())
+    }
+
+    fn detect_language(&mut self, hidden: &ArrayD<f32>) -> Result<String> {
+        let logits = self.next_logits(hidden, &[self.tokens.start_of_transcript])?;
+        self.tokens
+            .languages
+            .iter()
+            .filter(|(_, id)| (*id as usize) < logits.len())
+            .max_by(|(_, a), (_, b)| logits[*a as usize].total_cmp(&logits[*b as usize]))
+            .map(|(code, _)| code.clone())
+            .ok_or_else(|| anyhow!("Model has no language tokens"))
+    }
+
+    fn decode(
+        &mut self,
+        hidden: &ArrayD<f32>,
+        prompt: &[u32],
+        timestamps: bool,
+    ) -> Result<Vec<(u32, f32)>> {
+        let mut sequence = prompt.to_vec();
+        let mut sampled: Vec<u32> = Vec::new();
+        let mut result = Vec::new();
+
+        while sampled.len() < MAX_NEW_TOKENS {
+            let mut logits = self.next_logits(hidden, &sequence)?;
+            apply_token_rules(&mut logits, &sampled, &self.tokens, timestamps);
+
+            let Some((token, &logit)) = logits
+                .iter()
+                .enumerate()
+                .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            else {
+                break;
+            };
+            let token = token as u32;
+            if token == self.tokens.end_of_text || logit == f32::NEG_INFINITY {
+                break;
+            }
+
+            let probability = (logit - log_sum_exp(&logits)).exp();
+            sequence.push(token);
+            sampled.push(token);
+            result.push((token, probability));
+        }
+
+        Ok(result)
+    }
+
+    pub fn transcribe(
+        &mut self,
+        audio: &crate::onnx::audio::AudioData,
+        options: &TranscribeOptions,
+    ) -> Result<TranscriptionResult> {
+        let audio = audio.to_mono().resample(SAMPLE_RATE);
+        let multilingual = !self.tokens.languages.is_empty();
+        let mut language = if multilingual {
+            options.language.clone()
+        } else {
+            Some("en".to_string())
+        };
+
+        let mut segments = Vec::new();
+        for (index, window) in audio.samples.chunks(CHUNK_SAMPLES).enumerate() {
+            let offset = index as f32 * (CHUNK_SAMPLES as f32 / SAMPLE_RATE as f32);
+            let hidden = self.encode(window)?;
+
+            let mut prompt = vec![self.tokens.start_of_transcript];
+            if multilingual {
+                let code = match &language {
+                    Some(code) => code.clone(),
+                    None => self.detect_language(&hidden)?,
+                };
+                prompt.push(self.tokens.language(&code)?);
+                prompt.push(if options.translate {
+                    self.tokens.translate
+                } else {
+                    self.tokens.transcribe
+                });
+                language = Some(code);
+            }
+            if !options.timestamps {
+                prompt.push(self.tokens.no_timestamps);
+            }
+
+            let sampled = self.decode(&hidden, &prompt, options.timestamps)?;
+            let window_end = window.len() as f32 / SAMPLE_RATE as f32;
+            for segment in group_segments(&sampled, &self.tokens, window_end) {
+                let text = self
+                    .tokenizer
+                    .decode(&segment.tokens, true)
+                    .map_err(|e| anyhow!("Failed to decode tokens: {}", e))?;
+                let text = text.trim();
+                if text.is_empty() {
+                    continue;
+                }
+                segments.push(TranscriptionSegment {
+                    text: text.to_string(),
+                    start: offset + segment.start,
+                    end: offset + segment.end,
+                    confidence: segment.confidence,
+                });
+            }
+        }
+
+        Ok(TranscriptionResult {
+            text: segments
+                .iter()
+                .map(|segment| segment.text.as_str())
+                .collect::<Vec<_>>()
+                .join(" "),
+            segments,
+            language,
+        })
+    }
+}
+
+/// Loaded Whisper models are kept in the run cache, keyed by their files.
+#[cfg(feature = "execute")]
+pub struct WhisperModelCache {
+    pub model: Arc<Mutex<WhisperModel>>,
+}
+
+#[cfg(feature = "execute")]
+impl Cacheable for WhisperModelCache {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(feature = "execute")]
+pub fn whisper_cache_key(encoder: &FlowPath, decoder: &FlowPath, tokenizer: &FlowPath) -> String {
+    let mut hasher = Sha256::new();
+    for path in [encoder, decoder, tokenizer] {
+        hasher.update(path.store_ref.as_bytes());
+        hasher.update([0]);
+        hasher.update(path.path.as_bytes());
+        hasher.update([0]);
+    }
+    format!("whisper_{}", hex::encode(hasher.finalize()))
+}
+
+/// Returns the cached model for these files, loading it on first use. The flag is `true`
+/// if the model was already loaded.
+#[cfg(feature = "execute")]
+pub async fn cached_whisper_model(
+    context: &mut ExecutionContext,
+    encoder: &FlowPath,
+    decoder: &FlowPath,
+    tokenizer: &FlowPath,
+) -> Result<(Arc<Mutex<WhisperModel>>, bool)> {
+    let key = whisper_cache_key(encoder, decoder, tokenizer);
+    if let Some(cached) = context.get_cache(&key).await
+        && let Some(cached) = cached.as_any().downcast_ref::<WhisperModelCache>()
+    {
+        return Ok((cached.model.clone(), true));
+    }
+
+    let encoder = encoder.get(context, false).await?;
+    let decoder = decoder.get(context, false).await?;
+    let tokenizer = tokenizer.get(context, false).await?;
+    let model = Arc::new(Mutex::new(WhisperModel::from_bytes(
+        &encoder, &decoder, &tokenizer,
+    )?));
+
+    context
+        .set_cache(
+            &key,
+            Arc::new(WhisperModelCache {
+                model: model.clone(),
+            }),
+        )
+        .await;
+    Ok((model, false))
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct SpeechToTextNode {}
+
+impl SpeechToTextNode {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for SpeechToTextNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "onnx_speech_to_text",
+            "Speech to Text (Whisper)",
+            "Transcribes a WAV, MP3 or FLAC file with a Whisper ONNX model. Use the encoder_model.onnx, decoder_model.onnx and tokenizer.json from e.g. https://huggingface.co/onnx-community/whisper-base. The model stays loaded for later calls",
+            "AI/ML/ONNX/Audio",
+        );
+
+        node.add_icon("/flow/icons/microphone.svg");
+
+        node.add_input_pin(
+            "exec_in",
+            "Input",
+            "Initiate Execution",
+            VariableType::Execution,
+        );
+
+        node.add_input_pin(
+            "encoder",
+            "Encoder",
+            "Whisper encoder ONNX file",
+            VariableType::Struct,
+        )
+        .set_schema::<FlowPath>()
+        .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node.add_input_pin(
+            "decoder",
+            "Decoder",
+            "Whisper decoder ONNX file (without past key values)",
+            VariableType::Struct,
+        )
+        .set_schema::<FlowPath>()
+        .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node.add_input_pin(
+            "tokenizer",
+            "Tokenizer",
+            "Path to tokenizer.json",
+            VariableType::Struct,
+        )
+        .set_schema::<FlowPath>()
+        .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node.add_input_pin(
+            "audio",
+            "Audio",
+            "Audio file (wav, mp3 or flac)",
+            VariableType::Struct,
+        )
+        .set_schema::<FlowPath>()
+        .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node.add_input_pin(
+            "language",
+            "Language",
+            "Spoken language code such as en or de, auto detects it",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("auto")));
+
+        node.add_input_pin(
+            "translate",
+            "Translate to English",
+            "Output an English translation instead of a transcript",
+            VariableType::Boolean,
+        )
+        .set_default_value(Some(json!(false)));
+
+        node.add_input_pin(
+            "timestamps",
+            "Timestamps",
+            "Split the transcript into timed segments",
+            VariableType::Boolean,
+        )
+        .set_default_value(Some(json!(true)));
+
+        node.add_output_pin("exec_out", "Output", "Done", VariableType::Execution);
+
+        node.add_output_pin("text", "Text", "Full transcript", VariableType::String);
+
+        node.add_output_pin(
+            "segments",
+            "Segments",
+            "Transcript segments with start and end in seconds",
+            VariableType::Struct,
+        )
+        .set_schema::<TranscriptionSegment>()
+        .set_value_type(ValueType::Array);
+
+        node.add_output_pin(
+            "result",
+            "Result",
+            "Transcript, segments and language",
+            VariableType::Struct,
+        )
+        .set_schema::<TranscriptionResult>();
+
+        node
+    }
+
+    #[allow(unused_variables)]
+    async fn run(&self, context: &mut ExecutionContext) -> Result<()> {
+        #[cfg(feature = "execute")]
+        {
+            use crate::onnx::audio::AudioData;
+
+            context.deactivate_exec_pin("exec_out").await?;
+
+            let encoder: FlowPath = context.evaluate_pin("encoder").await?;
+            let decoder: FlowPath = context.evaluate_pin("decoder").await?;
+            let tokenizer: FlowPath = context.evaluate_pin("tokenizer").await?;
+            let audio_path: FlowPath = context.evaluate_pin("audio").await?;
+            let language: String = context
+                .evaluate_pin("language")
+                .await
+                .unwrap_or_else(|_| "auto".to_string());
+            let options = TranscribeOptions {
+                language: match language.trim() {
+                    "" | "auto" => None,
+                    code => Some(code.to_lowercase()),
+                },
+                translate: context.evaluate_pin("translate").await.unwrap_or(false),
+                timestamps: context.evaluate_pin("timestamps").await.unwrap_or(true),
+            };
+
+            let extension = std::path::Path::new(&audio_path.path)
+                .extension()
+                .and_then(|extension| extension.to_str())
+                .map(str::to_lowercase);
+            let bytes = audio_path.get(context, false).await?;
+            let audio = AudioData::decode(bytes, extension.as_deref())?;
+
+            let (model, reused) =
+                cached_whisper_model(context, &encoder, &decoder, &tokenizer).await?;
+            if reused {
+                tracing::debug!("Reusing cached Whisper model");
+            }
+            let result = model.lock().await.transcribe(&audio, &options)?;
+
+            context.set_pin_value("text", json!(result.text)).await?;
+            context
+                .set_pin_value("segments", json!(result.segments))
+                .await?;
+            context.set_pin_value("result", json!(result)).await?;
+            context.activate_exec_pin("exec_out").await?;
+            Ok(())
+        }
+
+        #[cfg(not(feature = "execute"))]
+        Err(anyhow!("Execute feature not enabled"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Ids of the multilingual Whisper vocabulary
+    fn tokens() -> WhisperTokens {
+        WhisperTokens {
+            start_of_transcript: 50258,
+            end_of_text: 50257,
+            transcribe: 50359,
+            translate: 50358,
+            no_timestamps: 50363,
+            timestamp_begin: 50364,
+            languages: vec![("en".to_string(), 50259), ("de".to_string(), 50261)],
+        }
+    }
+
+    fn logits() -> Vec<f32> {
+        vec![0.0; 51865]
+    }
+
+    fn ts(seconds: f32) -> u32 {
+        50364 + (seconds / SECONDS_PER_TIMESTAMP).round() as u32
+    }
+
+    fn allowed(logits: &[f32], token: u32) -> bool {
+        logits[token as usize] > f32::NEG_INFINITY
+    }
+
+    #[test]
+    fn test_mel_filters_match_slaney_scale() {
+        assert!((hz_to_mel(1000.0) - 15.0).abs() < 1e-9);
+        assert!((mel_to_hz(hz_to_mel(4321.0)) - 4321.0).abs() < 1e-6);
+
+        let filters = mel_filters(80);
+        assert_eq!(filters.len(), 80 * 201);
+        assert!(filters.iter().all(|&w| w >= 0.0));
+        for mel in 0..80 {
+            assert!(filters[mel * 201..(mel + 1) * 201].iter().any(|&w| w > 0.0));
+        }
+    }
+
+    #[test]
+    fn test_log_mel_shape_and_range() {
+        let tone: Vec<f32> = (0..SAMPLE_RATE)
+            .map(|i| (2.0 * std::f32::consts::PI * 440.0 * i as f32 / SAMPLE_RATE as f32).sin())
+            .collect();
+        let mel = log_mel_spectrogram(&tone, &mel_filters(80), 80);
+        assert_eq!(mel.len(), 80 * N_FRAMES);
+
+        let max = mel.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        let min = mel.iter().copied().fold(f32::INFINITY, f32::min);
+        assert!(max - min <= 2.0 + 1e-5);
+
+        // A 440 Hz tone is loudest in a low mel band during the first second
+        let loudest = (0..80)
+            .max_by(|&a, &b| mel[a * N_FRAMES + 50].total_cmp(&mel[b * N_FRAMES + 50]))
+            .unwrap();
+        assert!(loudest < 20);
+    }
+
+    #[test]
+    fn test_token_rules_without_timestamps() {
+        let tokens = tokens();
+        let mut first = logits();
+        apply_token_rules(&mut first, &[], &tokens, false);
+        assert!(allowed(&first, 400));
+        assert!(!allowed(&first, tokens.end_of_text));
+        assert!(!allowed(&first, tokens.translate));
+        assert!(!allowed(&first, ts(0.0)));
+
+        let mut later = logits();
+        apply_token_rules(&mut later, &[400], &tokens, false);
+        assert!(allowed(&later, tokens.end_of_text));
+    }
+
+    #[test]
+    fn test_timestamp_rules() {
+        let tokens = tokens();
+
+        let mut first = logits();
+        apply_token_rules(&mut first, &[], &tokens, true);
+        assert!(!allowed(&first, 400));
+        assert!(allowed(&first, ts(0.0)) && allowed(&first, ts(1.0)));
+        assert!(!allowed(&first, ts(1.02)));
+
+        // An open segment is closed by a timestamp that does not go backwards
+        let mut after_open = logits();
+        apply_token_rules(&mut after_open, &[ts(0.0), 400, ts(2.0)], &tokens, true);
+        assert!(!allowed(&after_open, 400));
+        assert!(!allowed(&after_open, ts(1.0)));
+        assert!(allowed(&after_open, ts(2.0)));
+
+        // After a closed pair the next token is text
+        let mut after_pair = logits();
+        apply_token_rules(
+            &mut after_pair,
+            &[ts(0.0), 400, ts(2.0), ts(2.0)],
+            &tokens,
+            true,
+        );
+        assert!(allowed(&after_pair, 400));
+        assert!(!allowed(&after_pair, ts(3.0)));
+    }
+
+    #[test]
+    fn test_group_segments() {
+        let tokens = tokens();
+        let sampled = [
+            (ts(0.0), 1.0),
+            (100, 0.9),
+            (101, 0.7),
+            (ts(2.4), 1.0),
+            (ts(2.4), 1.0),
+            (102, 0.5),
+            (ts(4.0), 1.0),
+            (103, 0.6),
+        ];
+
+        let segments = group_segments(&sampled, &tokens, 5.5);
+        assert_eq!(segments.len(), 3);
+        assert_eq!(segments[0].tokens, vec![100, 101]);
+        assert!((segments[0].end - 2.4).abs() < 1e-6);
+        assert!((segments[0].confidence - 0.8).abs() < 1e-6);
+        assert!((segments[1].start - 2.4).abs() < 1e-6);
+        assert!((segments[1].end - 4.0).abs() < 1e-6);
+        assert_eq!(segments[2].tokens, vec![103]);
+        assert!((segments[2].start - 4.0).abs() < 1e-6);
+        assert_eq!(segments[2].end, 5.5);
+
+        let plain = group_segments(&[(100, 0.5), (101, 0.5)], &tokens, 3.0);
+        assert_eq!(plain.len(), 1);
+        assert_eq!((plain[0].start, plain[0].end), (0.0, 3.0));
+    }
+
+    #[cfg(feature = "execute")]
+    #[test]
+    fn test_cache_key_identifies_model_files() {
+        let path = |p: &str| FlowPath::new(p.to_string(), "store".to_string(), None);
+        let key = whisper_cache_key(&path("enc.onnx"), &path("dec.onnx"), &path("tok.json"));
+        assert_eq!(
+            key,
+            whisper_cache_key(&path("enc.onnx"), &path("dec.onnx"), &path("tok.json"))
+        );
+        assert_ne!(
+            key,
+            whisper_cache_key(&path("enc.onnx"), &path("dec_q.onnx"), &path("tok.json"))
+        );
+    }
+}
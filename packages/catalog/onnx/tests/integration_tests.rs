@@ -140,6 +140,23 @@ const ULTRAFACE_URL: &str = "https://github.com/onnx/models/raw/main/validated/v
 const SILERO_VAD_URL: &str =
     "https://github.com/snakers4/silero-vad/raw/master/src/silero_vad/data/silero_vad.onnx";
 
+// Speech-to-text - Whisper tiny (~40MB encoder + ~120MB decoder) and a 11s sample clip
+const WHISPER_TINY_ENCODER_URL: &str =
+    "https://huggingface.co/onnx-community/whisper-tiny/resolve/main/onnx/encoder_model.onnx";
+const WHISPER_TINY_DECODER_URL: &str =
+    "https://huggingface.co/onnx-community/whisper-tiny/resolve/main/onnx/decoder_model.onnx";
+const WHISPER_TINY_TOKENIZER_URL: &str =
+    "https://huggingface.co/onnx-community/whisper-tiny/resolve/main/tokenizer.json";
+const JFK_SAMPLE_URL: &str = "https://github.com/ggerganov/whisper.cpp/raw/master/samples/jfk.wav";
+
+// CLIP ViT-B/32 - image (~350MB) and text (~250MB) towers
+const CLIP_VISION_URL: &str =
+    "https://huggingface.co/Xenova/clip-vit-base-patch32/resolve/main/onnx/vision_model.onnx";
+const CLIP_TEXT_URL: &str =
+    "https://huggingface.co/Xenova/clip-vit-base-patch32/resolve/main/onnx/text_model.onnx";
+const CLIP_TOKENIZER_URL: &str =
+    "https://huggingface.co/Xenova/clip-vit-base-patch32/resolve/main/tokenizer.json";
+
 // Image classification - SqueezeNet is small (~5MB)
 const SQUEEZENET_URL: &str = "https://github.com/onnx/models/raw/main/validated/vision/classification/squeezenet/model/squeezenet1.0-12.onnx";
 
@@ -172,6 +189,7 @@ const BERT_NER_URL: &str =
 #[cfg(feature = "execute")]
 mod onnx_session_tests {
     use super::*;
+    use flow_like_catalog_onnx::onnx::execution_providers::{ExecutionProviderKind, build_session};
     use flow_like_model_provider::ml::ort::session::Session;
 
     #[test]
@@ -248,6 +266,35 @@ mod onnx_session_tests {
         assert!(!session.inputs.is_empty(), "Model should have inputs");
         assert!(!session.outputs.is_empty(), "Model should have outputs");
     }
+
+    #[test]
+    #[ignore]
+    fn test_build_session_on_cpu() {
+        let model_path = download_if_missing(SQUEEZENET_URL, "squeezenet1.0-12.onnx");
+        let bytes = fs::read(model_path).unwrap();
+
+        let (session, selection) = build_session(&bytes, ExecutionProviderKind::Cpu).unwrap();
+        assert_eq!(selection.provider, ExecutionProviderKind::Cpu);
+        assert_eq!(selection.fallback_warning, None);
+        assert!(!session.inputs.is_empty());
+    }
+
+    /// Only meaningful on CPU-only builds, where CUDA can never bind
+    #[cfg(not(feature = "cuda"))]
+    #[test]
+    #[ignore]
+    fn test_build_session_falls_back_to_cpu() {
+        let model_path = download_if_missing(SQUEEZENET_URL, "squeezenet1.0-12.onnx");
+        let bytes = fs::read(model_path).unwrap();
+
+        let (session, selection) = build_session(&bytes, ExecutionProviderKind::Cuda).unwrap();
+        assert_eq!(selection.provider, ExecutionProviderKind::Cpu);
+        assert!(!selection.accelerated());
+        let warning = selection.fallback_warning.expect("fallback should warn");
+        println!("Fallback warning: {}", warning);
+        assert!(warning.contains("CUDA"));
+        assert!(!session.outputs.is_empty());
+    }
 }
 // ============================================================================
 
@@ -410,6 +457,121 @@ mod vad_tests {
     }
 }
 
+#[cfg(feature = "execute")]
+mod whisper_tests {
+    use super::*;
+    use flow_like_catalog_onnx::{
+        audio::AudioData,
+        whisper::{TranscribeOptions, WhisperModel},
+    };
+
+    #[test]
+    #[ignore]
+    fn test_whisper_transcribes_known_clip() {
+        let encoder = download_if_missing(WHISPER_TINY_ENCODER_URL, "whisper_tiny_encoder.onnx");
+        let decoder = download_if_missing(WHISPER_TINY_DECODER_URL, "whisper_tiny_decoder.onnx");
+        let tokenizer =
+            download_if_missing(WHISPER_TINY_TOKENIZER_URL, "whisper_tiny_tokenizer.json");
+        let clip = download_if_missing(JFK_SAMPLE_URL, "jfk.wav");
+
+        let mut model = WhisperModel::from_bytes(
+            &fs::read(encoder).unwrap(),
+            &fs::read(decoder).unwrap(),
+            &fs::read(tokenizer).unwrap(),
+        )
+        .expect("Failed to load Whisper");
+        let audio = AudioData::decode(fs::read(clip).unwrap(), Some("wav")).unwrap();
+
+        let result = model
+            .transcribe(
+                &audio,
+                &TranscribeOptions {
+                    language: None,
+                    translate: false,
+                    timestamps: true,
+                },
+            )
+            .expect("Transcription failed");
+        println!("Transcript: {:?}", result);
+
+        let text = result.text.to_lowercase();
+        assert!(text.contains("ask not what your country can do for you"));
+        assert_eq!(result.language.as_deref(), Some("en"));
+        assert!(!result.segments.is_empty());
+        assert!(
+            result
+                .segments
+                .windows(2)
+                .all(|w| w[0].end <= w[1].start + 1e-3)
+        );
+        assert!(result.segments.last().unwrap().end <= audio.duration_secs + 0.5);
+
+        // The same loaded sessions serve further calls
+        let again = model
+            .transcribe(
+                &audio,
+                &TranscribeOptions {
+                    language: Some("en".to_string()),
+                    translate: false,
+                    timestamps: false,
+                },
+            )
+            .expect("Second transcription failed");
+        assert!(
+            again
+                .text
+                .to_lowercase()
+                .contains("ask what you can do for your country")
+        );
+        assert_eq!(again.segments.len(), 1);
+    }
+}
+
+#[cfg(feature = "execute")]
+mod clip_tests {
+    use super::*;
+    use flow_like_catalog_onnx::clip::{ClipModel, embedding_similarity};
+    use image::{DynamicImage, Rgb, RgbImage};
+
+    #[test]
+    #[ignore]
+    fn test_clip_matches_images_and_text() {
+        let vision = download_if_missing(CLIP_VISION_URL, "clip_vision.onnx");
+        let text = download_if_missing(CLIP_TEXT_URL, "clip_text.onnx");
+        let tokenizer = download_if_missing(CLIP_TOKENIZER_URL, "clip_tokenizer.json");
+
+        let mut model = ClipModel::from_bytes(
+            &fs::read(vision).unwrap(),
+            &fs::read(text).unwrap(),
+            &fs::read(tokenizer).unwrap(),
+        )
+        .expect("Failed to load CLIP");
+
+        let red = DynamicImage::ImageRgb8(RgbImage::from_pixel(256, 256, Rgb([220, 20, 20])));
+        let blue = DynamicImage::ImageRgb8(RgbImage::from_pixel(256, 256, Rgb([20, 40, 220])));
+        let red_image = model.embed_image(&red).unwrap();
+        let blue_image = model.embed_image(&blue).unwrap();
+        let red_text = model.embed_text("a plain red square").unwrap();
+        let blue_text = model.embed_text("a plain blue square").unwrap();
+
+        assert_eq!(red_image.dimensions, 512);
+        assert_eq!(red_text.dimensions, 512);
+        let norm: f32 = red_text.values.iter().map(|v| v * v).sum::<f32>().sqrt();
+        assert!((norm - 1.0).abs() < 1e-4);
+
+        let score = |a, b| embedding_similarity(a, b).unwrap();
+        println!(
+            "red/red {:.3}, red/blue {:.3}, blue/blue {:.3}, blue/red {:.3}",
+            score(&red_image, &red_text),
+            score(&red_image, &blue_text),
+            score(&blue_image, &blue_text),
+            score(&blue_image, &red_text)
+        );
+        assert!(score(&red_image, &red_text) > score(&red_image, &blue_text));
+        assert!(score(&blue_image, &blue_text) > score(&blue_image, &red_text));
+    }
+}
+
 // ============================================================================
 // Feature Extraction Tests
 // ============================================================================
@@ -517,3 +517,129 @@ mod node_metadata {
         assert_node_has_exec_pins(&node);
     }
 }
+
+// ============================================================================
+// Teachable Machine Tests
+// ============================================================================
+
+mod teachable_machine {
+    use super::*;
+    use flow_like_catalog_onnx::teachable_machine::{
+        TeachableMachineInfoNode, TeachableMachineMetadata, TeachableMachineModelType,
+        label_scores, rank_predictions,
+    };
+
+    fn fixture(name: &str) -> Vec<u8> {
+        let path = format!(
+            "{}/tests/fixtures/teachable_machine/{}",
+            env!("CARGO_MANIFEST_DIR"),
+            name
+        );
+        std::fs::read(&path).unwrap_or_else(|e| panic!("missing fixture {}: {}", path, e))
+    }
+
+    #[test]
+    fn image_export_metadata() {
+        let metadata =
+            TeachableMachineMetadata::parse("metadata.json", &fixture("image_metadata.json"))
+                .unwrap();
+
+        assert_eq!(metadata.model_type, TeachableMachineModelType::Image);
+        assert_eq!(metadata.labels, vec!["Cat", "Dog", "Background"]);
+        assert_eq!((metadata.input_width, metadata.input_height), (224, 224));
+        assert_eq!(metadata.model_name.as_deref(), Some("tm-my-image-model"));
+        assert_eq!(metadata.tm_version.as_deref(), Some("2.4.7"));
+    }
+
+    #[test]
+    fn pose_export_metadata() {
+        let metadata =
+            TeachableMachineMetadata::parse("metadata.json", &fixture("pose_metadata.json"))
+                .unwrap();
+
+        assert_eq!(metadata.model_type, TeachableMachineModelType::Pose);
+        assert_eq!(metadata.labels, vec!["Standing", "Sitting"]);
+        assert_eq!((metadata.input_width, metadata.input_height), (257, 257));
+    }
+
+    #[test]
+    fn audio_export_metadata() {
+        let metadata =
+            TeachableMachineMetadata::parse("metadata.json", &fixture("audio_metadata.json"))
+                .unwrap();
+
+        assert_eq!(metadata.model_type, TeachableMachineModelType::Audio);
+        assert_eq!(metadata.labels, vec!["Background Noise", "Clap", "Whistle"]);
+        assert_eq!((metadata.input_width, metadata.input_height), (232, 43));
+    }
+
+    #[test]
+    fn tflite_labels_drop_class_index() {
+        let metadata =
+            TeachableMachineMetadata::parse("labels.txt", &fixture("labels.txt")).unwrap();
+
+        assert_eq!(metadata.model_type, TeachableMachineModelType::Image);
+        assert_eq!(metadata.labels, vec!["Cat", "Dog", "Background"]);
+        assert_eq!((metadata.input_width, metadata.input_height), (224, 224));
+    }
+
+    #[test]
+    fn invalid_metadata_is_rejected() {
+        assert!(TeachableMachineMetadata::parse("metadata.json", b"{\"imageSize\": 224}").is_err());
+        assert!(TeachableMachineMetadata::parse("metadata.json", b"not json").is_err());
+    }
+
+    #[test]
+    fn classification_results_are_label_keyed() {
+        let metadata =
+            TeachableMachineMetadata::parse("metadata.json", &fixture("image_metadata.json"))
+                .unwrap();
+        let scores = [0.15, 0.8, 0.05];
+
+        let keyed = label_scores(&scores, &metadata.labels);
+        assert_eq!(keyed.len(), 3);
+        assert!((keyed["Dog"].as_f64().unwrap() - 0.8).abs() < 1e-6);
+        assert!((keyed["Cat"].as_f64().unwrap() - 0.15).abs() < 1e-6);
+        assert!((keyed["Background"].as_f64().unwrap() - 0.05).abs() < 1e-6);
+
+        let ranked = rank_predictions(&scores, &metadata.labels);
+        let order: Vec<_> = ranked
+            .iter()
+            .map(|prediction| (prediction.class_idx, prediction.label.clone().unwrap()))
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                (1, "Dog".to_string()),
+                (0, "Cat".to_string()),
+                (2, "Background".to_string())
+            ]
+        );
+
+        // Outputs without a label keep a stable key
+        let unlabeled = label_scores(&[0.5, 0.5], &metadata.labels[..1]);
+        assert!(unlabeled.contains_key("Cat"));
+        assert!(unlabeled.contains_key("class_1"));
+    }
+
+    #[test]
+    fn info_node_metadata() {
+        let node = TeachableMachineInfoNode::new().get_node();
+
+        assert_eq!(node.friendly_name, "Teachable Machine Info");
+        for pin in [
+            "package",
+            "model",
+            "metadata",
+            "labels",
+            "input_width",
+            "model_type",
+        ] {
+            assert!(
+                node.pins.values().any(|p| p.name == pin),
+                "missing pin {}",
+                pin
+            );
+        }
+    }
+}
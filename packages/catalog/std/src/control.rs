@@ -1,5 +1,9 @@
+pub mod assert;
+pub mod backoff;
 pub mod branch_node;
+pub mod call_board;
 pub mod call_ref;
+pub mod debounce;
 pub mod delay;
 pub mod do_n;
 pub mod do_once;
@@ -8,9 +12,14 @@ pub mod for_each;
 pub mod for_each_with_break;
 pub mod gate;
 pub mod gather;
+mod loop_break;
 pub mod par_execution;
 pub mod par_for_each;
 pub mod reroute;
+pub mod retry;
+pub mod round_robin;
 pub mod sequence;
+pub mod throttle;
 pub mod timeout;
+pub mod try_catch;
 pub mod while_loop;
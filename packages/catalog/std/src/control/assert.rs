@@ -0,0 +1,366 @@
+#[cfg(feature = "execute")]
+use flow_like::flow::execution::LogLevel;
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic},
+    pin::PinOptions,
+    variable::VariableType,
+};
+#[cfg(feature = "execute")]
+use flow_like_types::{Value, bail};
+use flow_like_types::{async_trait, json::json};
+#[cfg(feature = "execute")]
+use regex::{Captures, Regex};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "execute")]
+use std::collections::HashMap;
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq)]
+pub struct AssertionViolation {
+    pub expression: String,
+    pub message: String,
+    /// Values the expression was evaluated with, keyed as written in the expression
+    pub values: Vec<(String, f64)>,
+}
+
+#[cfg(feature = "execute")]
+fn resolve_path<'a>(data: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.')
+        .try_fold(data, |value, segment| value.get(segment))
+}
+
+#[cfg(feature = "execute")]
+fn as_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(number) => number.as_f64(),
+        Value::Bool(flag) => Some(if *flag { 1.0 } else { 0.0 }),
+        _ => None,
+    }
+}
+
+#[cfg(feature = "execute")]
+fn aggregate(function: &str, path: &str, items: &[Value]) -> flow_like_types::Result<f64> {
+    if function == "len" {
+        return Ok(items.len() as f64);
+    }
+
+    let Some(numbers) = items.iter().map(as_number).collect::<Option<Vec<_>>>() else {
+        bail!("{function}({path}) needs an array of numbers");
+    };
+
+    Ok(match function {
+        "sum" => numbers.iter().sum(),
+        "avg" if !numbers.is_empty() => numbers.iter().sum::<f64>() / numbers.len() as f64,
+        "min" => numbers.iter().copied().reduce(f64::min).unwrap_or(f64::NAN),
+        "max" => numbers.iter().copied().reduce(f64::max).unwrap_or(f64::NAN),
+        _ => f64::NAN,
+    })
+}
+
+/// Rewrites array aggregates and dotted paths into plain variables fasteval can look up.
+#[cfg(feature = "execute")]
+fn bind_expression(
+    expression: &str,
+    data: &Value,
+) -> flow_like_types::Result<(String, HashMap<String, (String, Option<f64>)>)> {
+    let aggregates =
+        Regex::new(r"\b(sum|len|avg|min|max)\(\s*([A-Za-z_]\w*(?:\.[A-Za-z_]\w*)*)\s*\)")?;
+    let paths = Regex::new(r"\b[A-Za-z_]\w*(?:\.[A-Za-z_]\w*)+")?;
+
+    let mut bindings = HashMap::new();
+    let mut error = None;
+
+    let rewritten = aggregates.replace_all(expression, |captures: &Captures| {
+        let call = captures[0].to_string();
+        let Some(Value::Array(items)) = resolve_path(data, &captures[2]) else {
+            return call;
+        };
+        match aggregate(&captures[1], &captures[2], items) {
+            Ok(value) => {
+                let name = format!("__assert_{}", bindings.len());
+                bindings.insert(name.clone(), (call, Some(value)));
+                name
+            }
+            Err(e) => {
+                error.get_or_insert(e);
+                call
+            }
+        }
+    });
+    if let Some(error) = error {
+        return Err(error);
+    }
+
+    // Unresolved paths are still bound, so they are reported as missing instead of
+    // failing to parse
+    let rewritten = paths.replace_all(&rewritten, |captures: &Captures| {
+        let path = captures[0].to_string();
+        let value = resolve_path(data, &path).and_then(as_number);
+        let name = format!("__assert_{}", bindings.len());
+        bindings.insert(name.clone(), (path, value));
+        name
+    });
+
+    Ok((rewritten.into_owned(), bindings))
+}
+
+/// Evaluates `expression` against the fields of `data`. Returns `None` if the invariant
+/// holds, otherwise the violation with the values it was evaluated with.
+#[cfg(feature = "execute")]
+pub fn check_assertion(
+    expression: &str,
+    data: &Value,
+    message: &str,
+) -> flow_like_types::Result<Option<AssertionViolation>> {
+    let (rewritten, bindings) = bind_expression(expression, data)?;
+    let mut values: Vec<(String, f64)> = Vec::new();
+    let mut missing: Vec<String> = Vec::new();
+
+    let mut namespace = |name: &str, args: Vec<f64>| -> Option<f64> {
+        if !args.is_empty() {
+            return None;
+        }
+        let (label, value) = match bindings.get(name) {
+            Some((label, value)) => (label.clone(), *value),
+            None => (name.to_string(), data.get(name).and_then(as_number)),
+        };
+        let Some(value) = value else {
+            if !missing.contains(&label) {
+                missing.push(label);
+            }
+            return None;
+        };
+        if !values.iter().any(|(existing, _)| existing == &label) {
+            values.push((label, value));
+        }
+        Some(value)
+    };
+
+    let result = fasteval::ez_eval(&rewritten, &mut namespace);
+    let reason = match result {
+        Ok(result) if result != 0.0 && !result.is_nan() => return Ok(None),
+        Ok(_) => None,
+        Err(_) if !missing.is_empty() => Some(format!("missing {}", missing.join(", "))),
+        Err(e) => bail!("Invalid assertion '{expression}': {e}"),
+    };
+
+    let mut details: Vec<String> = values
+        .iter()
+        .map(|(label, value)| format!("{label} = {value}"))
+        .collect();
+    details.extend(reason);
+
+    let summary = if message.trim().is_empty() {
+        format!("Assertion failed: {expression}")
+    } else {
+        format!("Assertion failed: {} ({expression})", message.trim())
+    };
+
+    Ok(Some(AssertionViolation {
+        expression: expression.to_string(),
+        message: if details.is_empty() {
+            summary
+        } else {
+            format!("{summary} with {}", details.join(", "))
+        },
+        values,
+    }))
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct AssertNode {}
+
+impl AssertNode {
+    pub fn new() -> Self {
+        AssertNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for AssertNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "control_assert",
+            "Assert",
+            "Checks an invariant against the data mid-flow. Fields are used as variables, nested fields with dots, and sum, len, avg, min and max work on arrays",
+            "Control",
+        );
+        node.add_icon("/flow/icons/check.svg");
+
+        node.add_input_pin("exec_in", "Input", "Trigger Pin", VariableType::Execution);
+
+        node.add_input_pin(
+            "data",
+            "Data",
+            "Struct whose fields the expression refers to",
+            VariableType::Struct,
+        )
+        .set_default_value(Some(json!({})));
+
+        node.add_input_pin(
+            "expression",
+            "Expression",
+            "Boolean expression, e.g. `abs(total - sum(items)) < 0.01 && len(items) > 0`",
+            VariableType::String,
+        );
+
+        node.add_input_pin(
+            "message",
+            "Message",
+            "Describes the invariant in the failure message",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("")));
+
+        node.add_input_pin(
+            "on_failure",
+            "On Failure",
+            "Fail the run, or continue on the Failed branch",
+            VariableType::String,
+        )
+        .set_options(
+            PinOptions::new()
+                .set_valid_values(vec!["fail".into(), "branch".into()])
+                .build(),
+        )
+        .set_default_value(Some(json!("fail")));
+
+        node.add_output_pin(
+            "exec_out",
+            "Passed",
+            "Triggers if the invariant holds",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "failed",
+            "Failed",
+            "Triggers if the invariant is violated and On Failure is branch",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "violation",
+            "Violation",
+            "The violated invariant and its values, null if it holds",
+            VariableType::Struct,
+        )
+        .set_schema::<AssertionViolation>();
+
+        node
+    }
+
+    #[cfg(feature = "execute")]
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+        context.deactivate_exec_pin("failed").await?;
+
+        let data: Value = context.evaluate_pin("data").await.unwrap_or(json!({}));
+        let expression: String = context.evaluate_pin("expression").await?;
+        let message: String = context.evaluate_pin("message").await.unwrap_or_default();
+        let on_failure: String = context
+            .evaluate_pin("on_failure")
+            .await
+            .unwrap_or_else(|_| "fail".to_string());
+
+        let Some(violation) = check_assertion(&expression, &data, &message)? else {
+            context.set_pin_value("violation", Value::Null).await?;
+            context.activate_exec_pin("exec_out").await?;
+            return Ok(());
+        };
+
+        context.log_message(&violation.message, LogLevel::Error);
+        if on_failure != "branch" {
+            bail!(violation.message);
+        }
+
+        context.set_pin_value("violation", json!(violation)).await?;
+        context.activate_exec_pin("failed").await?;
+        Ok(())
+    }
+
+    #[cfg(not(feature = "execute"))]
+    async fn run(&self, _context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        Err(flow_like_types::anyhow!(
+            "This feature requires the 'execute' feature"
+        ))
+    }
+}
+
+#[cfg(all(test, feature = "execute"))]
+mod tests {
+    use super::*;
+
+    fn order() -> Value {
+        json!({
+            "total": 12.5,
+            "items": [10, 2.5],
+            "paid": true,
+            "customer": { "age": 17, "name": "Ada" },
+        })
+    }
+
+    #[test]
+    fn test_invariant_holds() {
+        let data = order();
+        for expression in [
+            "total == sum(items)",
+            "len(items) == 2 && max(items) == 10 && avg(items) == 6.25",
+            "paid && customer.age >= 16",
+            "max(total, 3) == total",
+        ] {
+            assert_eq!(check_assertion(expression, &data, "").unwrap(), None);
+        }
+    }
+
+    #[test]
+    fn test_violation_reports_values() {
+        let violation = check_assertion(
+            "total == sum(items)",
+            &json!({ "total": 13, "items": [10, 2] }),
+            "Total matches items",
+        )
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(
+            violation.values,
+            vec![
+                ("total".to_string(), 13.0),
+                ("sum(items)".to_string(), 12.0)
+            ]
+        );
+        assert_eq!(
+            violation.message,
+            "Assertion failed: Total matches items (total == sum(items)) with total = 13, sum(items) = 12"
+        );
+    }
+
+    #[test]
+    fn test_missing_fields_are_violations() {
+        let violation = check_assertion("discount < total", &order(), "")
+            .unwrap()
+            .unwrap();
+        assert!(violation.message.ends_with("missing discount"));
+
+        let violation = check_assertion("customer.name == 1", &order(), "")
+            .unwrap()
+            .unwrap();
+        assert!(violation.message.ends_with("missing customer.name"));
+    }
+
+    #[test]
+    fn test_invalid_expressions_error() {
+        assert!(check_assertion("total ==", &order(), "").is_err());
+        assert!(
+            check_assertion(
+                "sum(customer.name) > 0",
+                &json!({ "customer": { "name": ["a"] } }),
+                ""
+            )
+            .is_err()
+        );
+    }
+}
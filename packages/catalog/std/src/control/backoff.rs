@@ -0,0 +1,158 @@
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic},
+    variable::VariableType,
+};
+use flow_like_types::{async_trait, json::json};
+
+use super::retry::{BackoffPolicy, cancellable_sleep};
+
+fn add_policy_pins(node: &mut Node) {
+    node.add_input_pin(
+        "attempt",
+        "Attempt",
+        "Retry number, 1 waits the initial delay",
+        VariableType::Integer,
+    )
+    .set_default_value(Some(json!(1)));
+
+    node.add_input_pin(
+        "initial_delay_ms",
+        "Initial Delay (ms)",
+        "Delay before the first retry",
+        VariableType::Float,
+    )
+    .set_default_value(Some(json!(500.0)));
+
+    node.add_input_pin(
+        "multiplier",
+        "Multiplier",
+        "Factor the delay grows by after each retry",
+        VariableType::Float,
+    )
+    .set_default_value(Some(json!(2.0)));
+
+    node.add_input_pin(
+        "max_delay_ms",
+        "Max Delay (ms)",
+        "Upper bound for the delay",
+        VariableType::Float,
+    )
+    .set_default_value(Some(json!(30000.0)));
+
+    node.add_input_pin(
+        "jitter",
+        "Jitter",
+        "Randomize the upper half of the delay to avoid synchronized retries",
+        VariableType::Boolean,
+    )
+    .set_default_value(Some(json!(false)));
+}
+
+async fn evaluate_policy(
+    context: &mut ExecutionContext,
+) -> flow_like_types::Result<(u32, BackoffPolicy)> {
+    let attempt: i64 = context.evaluate_pin("attempt").await?;
+    let policy = BackoffPolicy {
+        initial_delay_ms: context.evaluate_pin("initial_delay_ms").await?,
+        multiplier: context.evaluate_pin("multiplier").await?,
+        max_delay_ms: context.evaluate_pin("max_delay_ms").await?,
+        jitter: context.evaluate_pin("jitter").await?,
+    };
+    Ok((attempt.clamp(1, u32::MAX as i64) as u32, policy))
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct BackoffNode {}
+
+impl BackoffNode {
+    pub fn new() -> Self {
+        BackoffNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for BackoffNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "control_backoff",
+            "Backoff Delay",
+            "Computes the exponential backoff delay for a retry, for custom polling and retry loops",
+            "Control",
+        );
+        node.add_icon("/flow/icons/history.svg");
+
+        add_policy_pins(&mut node);
+
+        node.add_output_pin(
+            "delay_ms",
+            "Delay (ms)",
+            "How long to wait before this retry",
+            VariableType::Float,
+        );
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        let (attempt, policy) = evaluate_policy(context).await?;
+        let delay = policy.delay_for(attempt);
+        context
+            .set_pin_value("delay_ms", json!(delay.as_millis() as f64))
+            .await?;
+        Ok(())
+    }
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct BackoffWaitNode {}
+
+impl BackoffWaitNode {
+    pub fn new() -> Self {
+        BackoffWaitNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for BackoffWaitNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "control_backoff_wait",
+            "Backoff Wait",
+            "Waits the exponential backoff delay for a retry. Stops waiting as soon as the run is cancelled",
+            "Control",
+        );
+
+        node.set_long_running(true);
+        node.add_icon("/flow/icons/clock.svg");
+
+        node.add_input_pin("exec_in", "Execute", "Execution", VariableType::Execution);
+        add_policy_pins(&mut node);
+
+        node.add_output_pin("exec_out", "Done", "Execution", VariableType::Execution);
+        node.add_output_pin(
+            "delay_ms",
+            "Delay (ms)",
+            "How long was waited",
+            VariableType::Float,
+        );
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+
+        let (attempt, policy) = evaluate_policy(context).await?;
+        let delay = policy.delay_for(attempt);
+        cancellable_sleep(delay, context.get_cancellation_token()).await?;
+
+        context
+            .set_pin_value("delay_ms", json!(delay.as_millis() as f64))
+            .await?;
+        context.activate_exec_pin("exec_out").await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,463 @@
+use std::sync::{Arc, Mutex as StdMutex};
+
+use flow_like::flow::{
+    board::Board,
+    execution::{InternalRun, LogLevel, RunPayload, context::ExecutionContext},
+    node::{Node, NodeLogic},
+    pin::PinType,
+    variable::VariableType,
+};
+use flow_like_storage::Path;
+use flow_like_types::{
+    Cacheable, Value, anyhow, async_trait, bail, create_id,
+    intercom::{InterComCallback, InterComEvent},
+    json::{Map, json},
+};
+use futures::future::BoxFuture;
+
+/// Run cache key under which a nested run keeps the boards it was called through
+const CALL_STACK_KEY: &str = "__call_board_stack";
+/// How many boards deep a chain of Call Board nodes may go
+pub const MAX_CALL_DEPTH: usize = 16;
+/// The event a callee's "Return Generic Result" node emits, it becomes the node's result
+const RESULT_EVENT: &str = "generic_result";
+
+const CONFIG_PINS: [&str; 5] = ["exec_in", "app_id", "board_id", "version", "start_node"];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BoardRef {
+    pub app_id: String,
+    pub board_id: String,
+}
+
+impl std::fmt::Display for BoardRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.app_id, self.board_id)
+    }
+}
+
+/// The boards a nested run was called through, outermost first
+#[derive(Clone, Debug, Default)]
+pub struct BoardCallStack {
+    frames: Vec<BoardRef>,
+}
+
+impl Cacheable for BoardCallStack {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl BoardCallStack {
+    /// Number of nested calls, 0 for a run that was not started by a Call Board node
+    pub fn depth(&self) -> usize {
+        self.frames.len().saturating_sub(1)
+    }
+
+    /// The stack of the callee, rejecting calls back into a board that is already running
+    /// and chains deeper than `max_depth`
+    pub fn enter(
+        &self,
+        caller: BoardRef,
+        callee: BoardRef,
+        max_depth: usize,
+    ) -> flow_like_types::Result<Self> {
+        let mut frames = self.frames.clone();
+        if frames.is_empty() {
+            frames.push(caller);
+        }
+
+        if frames.contains(&callee) {
+            let chain = frames
+                .iter()
+                .chain(std::iter::once(&callee))
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(" -> ");
+            bail!("Cyclic board call: {}", chain);
+        }
+
+        frames.push(callee);
+        let stack = BoardCallStack { frames };
+        if stack.depth() > max_depth {
+            bail!("Board calls are nested deeper than {} levels", max_depth);
+        }
+        Ok(stack)
+    }
+}
+
+fn parse_version(version: &str) -> flow_like_types::Result<Option<(u32, u32, u32)>> {
+    let version = version.trim();
+    if version.is_empty() {
+        return Ok(None);
+    }
+    let parts = version
+        .split('.')
+        .map(str::parse::<u32>)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| {
+            anyhow!(
+                "Invalid board version '{}', expected major.minor.patch",
+                version
+            )
+        })?;
+    match parts.as_slice() {
+        [major, minor, patch] => Ok(Some((*major, *minor, *patch))),
+        _ => bail!(
+            "Invalid board version '{}', expected major.minor.patch",
+            version
+        ),
+    }
+}
+
+/// Forwards the callee's events to the caller, tagged with where they came from.
+/// The callee's result is captured instead of forwarded, the caller has its own.
+fn nested_callback(
+    parent: InterComCallback,
+    marker: Value,
+    result: Arc<StdMutex<Option<Value>>>,
+) -> InterComCallback {
+    Some(Arc::new(
+        move |mut event: InterComEvent| -> BoxFuture<'static, flow_like_types::Result<()>> {
+            if event.event_type == RESULT_EVENT {
+                *result.lock().unwrap() = Some(event.payload);
+                return Box::pin(async { Ok(()) });
+            }
+            if let Value::Object(payload) = &mut event.payload {
+                payload.insert("nested_run".to_string(), marker.clone());
+            }
+            let parent = parent.clone();
+            Box::pin(async move { event.call(&parent).await })
+        },
+    ))
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct CallBoardNode {}
+
+impl CallBoardNode {
+    pub fn new() -> Self {
+        CallBoardNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for CallBoardNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "control_call_board",
+            "Call Board",
+            "Runs another board like a function. The payload and any extra input pins are passed to the callee's start event, the callee's returned result comes back as Result",
+            "Control/Call",
+        );
+        node.add_icon("/flow/icons/workflow.svg");
+        node.set_long_running(true);
+
+        node.add_input_pin("exec_in", "Input", "Trigger Pin", VariableType::Execution);
+        node.add_input_pin(
+            "app_id",
+            "App",
+            "App of the board, empty for the current app",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("")));
+        node.add_input_pin(
+            "board_id",
+            "Board",
+            "The board to call",
+            VariableType::String,
+        );
+        node.add_input_pin(
+            "version",
+            "Version",
+            "Board version as major.minor.patch, empty for the latest",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("")));
+        node.add_input_pin(
+            "start_node",
+            "Start Event",
+            "Id of the event node in the called board to start from",
+            VariableType::String,
+        );
+        node.add_input_pin(
+            "payload",
+            "Payload",
+            "Object whose fields are mapped onto the start event's pins by name",
+            VariableType::Generic,
+        )
+        .set_default_value(Some(json!({})));
+
+        node.add_output_pin(
+            "exec_out",
+            "Done",
+            "Executes once the called board finished",
+            VariableType::Execution,
+        );
+        node.add_output_pin(
+            "result",
+            "Result",
+            "What the called board returned, null if it returned nothing",
+            VariableType::Generic,
+        );
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+
+        let app_id: String = context.evaluate_pin("app_id").await?;
+        let board_id: String = context.evaluate_pin("board_id").await?;
+        let version: String = context.evaluate_pin("version").await?;
+        let start_node: String = context.evaluate_pin("start_node").await?;
+        let version = parse_version(&version)?;
+
+        let mut payload = match context.evaluate_pin::<Value>("payload").await? {
+            Value::Object(map) => map,
+            Value::Null => Map::new(),
+            other => bail!("Payload must be an object, got {}", other),
+        };
+        let extra_inputs: Vec<_> = context
+            .node
+            .pins
+            .values()
+            .filter(|pin| {
+                pin.pin_type == PinType::Input
+                    && pin.data_type != VariableType::Execution
+                    && pin.name != "payload"
+                    && !CONFIG_PINS.contains(&pin.name.as_str())
+            })
+            .cloned()
+            .collect();
+        for pin in extra_inputs {
+            let name = pin.name.clone();
+            let value = context.evaluate_pin_ref::<Value>(pin).await?;
+            payload.insert(name, value);
+        }
+
+        let (caller, current_dir) = {
+            let run = context.try_get_run()?;
+            let run = run.lock().await;
+            (
+                BoardRef {
+                    app_id: run.app_id.clone(),
+                    board_id: run.board.id.clone(),
+                },
+                run.board.board_dir.clone(),
+            )
+        };
+        let app_id = if app_id.is_empty() {
+            caller.app_id.clone()
+        } else {
+            app_id
+        };
+        let callee = BoardRef { app_id, board_id };
+
+        let stack = context
+            .get_cached::<BoardCallStack>(CALL_STACK_KEY)
+            .await
+            .map(|stack| stack.as_ref().clone())
+            .unwrap_or_default()
+            .enter(caller.clone(), callee.clone(), MAX_CALL_DEPTH)?;
+
+        let board_dir = if callee.app_id == caller.app_id {
+            current_dir
+        } else {
+            Path::from("apps").child(callee.app_id.as_str())
+        };
+        let board = Board::load(
+            board_dir,
+            &callee.board_id,
+            context.app_state.clone(),
+            version,
+        )
+        .await?;
+
+        let run_id = create_id();
+        let result = Arc::new(StdMutex::new(None));
+        let marker = json!({
+            "run_id": run_id,
+            "parent_run_id": context.run_id(),
+            "parent_node_id": context.node.node.lock().await.id.clone(),
+            "app_id": callee.app_id,
+            "board_id": callee.board_id,
+            "depth": stack.depth(),
+        });
+        let callback = nested_callback(context.callback().clone(), marker, result.clone());
+
+        let run_payload = RunPayload {
+            id: start_node,
+            payload: Some(Value::Object(payload)),
+            runtime_variables: None,
+            filter_secrets: Some(true),
+        };
+        let mut run = InternalRun::new_with_run_id(
+            &callee.app_id,
+            Arc::new(board),
+            None,
+            &context.app_state,
+            &context.profile,
+            &run_payload,
+            context.stream_state,
+            callback,
+            context.credentials.as_deref().cloned(),
+            context.token.clone(),
+            context
+                .oauth_tokens
+                .iter()
+                .map(|(provider, token)| (provider.clone(), token.clone()))
+                .collect(),
+            Some(run_id.clone()),
+        )
+        .await?;
+        if let Some(user_context) = context.user_context.clone() {
+            run.set_user_context(user_context);
+        }
+        run.cache
+            .write()
+            .await
+            .insert(CALL_STACK_KEY.to_string(), Arc::new(stack));
+
+        context.log_message(
+            &format!("Calling board {} (run {})", callee, run_id),
+            LogLevel::Debug,
+        );
+
+        // Dropping the child run's future on cancellation stops it as well
+        let state = context.app_state.clone();
+        let report = context
+            .run_cancellable(async move {
+                run.execute(state).await;
+                run.node_report()
+            })
+            .await?;
+
+        if !report.failed.is_empty() {
+            bail!(
+                "Board {} failed in node(s): {}",
+                callee,
+                report.failed.join(", ")
+            );
+        }
+
+        let result = result.lock().unwrap().take().unwrap_or(Value::Null);
+        if let Value::Object(fields) = &result {
+            let outputs: Vec<String> = context
+                .node
+                .pins
+                .values()
+                .filter(|pin| {
+                    pin.pin_type == PinType::Output
+                        && pin.data_type != VariableType::Execution
+                        && pin.name != "result"
+                })
+                .map(|pin| pin.name.clone())
+                .collect();
+            for name in outputs {
+                if let Some(value) = fields.get(&name) {
+                    context.set_pin_value(&name, value.clone()).await?;
+                }
+            }
+        }
+        context.set_pin_value("result", result).await?;
+
+        context.activate_exec_pin("exec_out").await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board(board_id: &str) -> BoardRef {
+        BoardRef {
+            app_id: "app".to_string(),
+            board_id: board_id.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_first_call_records_caller_and_callee() {
+        let stack = BoardCallStack::default()
+            .enter(board("main"), board("child"), MAX_CALL_DEPTH)
+            .unwrap();
+
+        assert_eq!(stack.depth(), 1);
+        assert_eq!(stack.frames, vec![board("main"), board("child")]);
+    }
+
+    #[test]
+    fn test_cyclic_calls_are_rejected() {
+        let stack = BoardCallStack::default()
+            .enter(board("main"), board("child"), MAX_CALL_DEPTH)
+            .unwrap();
+
+        let error = stack
+            .enter(board("child"), board("main"), MAX_CALL_DEPTH)
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Cyclic board call: app/main -> app/child -> app/main"
+        );
+
+        let self_call = BoardCallStack::default().enter(board("main"), board("main"), 4);
+        assert!(self_call.is_err());
+    }
+
+    #[test]
+    fn test_depth_is_limited() {
+        let mut stack = BoardCallStack::default();
+        let mut caller = board("b0");
+        for level in 1..=3 {
+            let callee = board(&format!("b{}", level));
+            stack = stack.enter(caller, callee.clone(), 3).unwrap();
+            caller = callee;
+        }
+
+        assert!(stack.enter(caller, board("b4"), 3).is_err());
+    }
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(parse_version("").unwrap(), None);
+        assert_eq!(parse_version(" 1.2.3 ").unwrap(), Some((1, 2, 3)));
+        assert!(parse_version("1.2").is_err());
+        assert!(parse_version("latest").is_err());
+    }
+
+    #[flow_like_types::tokio::test]
+    async fn test_events_are_tagged_and_result_is_captured() {
+        let forwarded = Arc::new(StdMutex::new(Vec::new()));
+        let sink = forwarded.clone();
+        let parent: InterComCallback = Some(Arc::new(
+            move |event: InterComEvent| -> BoxFuture<'static, flow_like_types::Result<()>> {
+                sink.lock().unwrap().push(event);
+                Box::pin(async { Ok(()) })
+            },
+        ));
+        let result = Arc::new(StdMutex::new(None));
+        let callback = nested_callback(parent, json!({ "run_id": "child" }), result.clone());
+
+        InterComEvent::with_type("progress", json!({ "message": "halfway" }))
+            .call(&callback)
+            .await
+            .unwrap();
+        InterComEvent::with_type(RESULT_EVENT, json!({ "sum": 3 }))
+            .call(&callback)
+            .await
+            .unwrap();
+
+        let forwarded = forwarded.lock().unwrap();
+        assert_eq!(forwarded.len(), 1);
+        assert_eq!(forwarded[0].payload["nested_run"]["run_id"], "child");
+        assert_eq!(forwarded[0].payload["message"], "halfway");
+        assert_eq!(*result.lock().unwrap(), Some(json!({ "sum": 3 })));
+    }
+}
@@ -0,0 +1,226 @@
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic},
+    variable::VariableType,
+};
+use flow_like_types::{Cacheable, async_trait, json::json};
+use std::{any::Any, sync::Mutex, time::Duration};
+
+use super::retry::cancellable_sleep;
+
+/// Key of the app cache entry that holds the state of `key` for this node.
+/// Runs of the same board share it, other boards and nodes have their own.
+pub(crate) fn state_key(context: &ExecutionContext, prefix: &str, key: &str) -> String {
+    let (app_id, board_id) = context
+        .execution_cache
+        .as_ref()
+        .map(|cache| (cache.app_id.as_str(), cache.board_id.as_str()))
+        .unwrap_or_default();
+    format!(
+        "{}:{}:{}:{}:{}",
+        prefix,
+        app_id,
+        board_id,
+        context.node.node_id(),
+        key
+    )
+}
+
+/// How long the state of a key is kept after its last trigger, never shorter than `interval`
+pub(crate) async fn state_ttl(
+    context: &mut ExecutionContext,
+    interval: Duration,
+) -> flow_like_types::Result<Duration> {
+    let ttl_s: f64 = context.evaluate_pin("ttl_s").await?;
+    Ok(Duration::from_secs_f64(ttl_s.max(0.0)).max(interval))
+}
+
+pub(crate) fn add_ttl_pin(node: &mut Node) {
+    node.add_input_pin(
+        "ttl_s",
+        "State TTL (s)",
+        "How long a key is remembered after its last trigger. Keys that stay quiet longer start over",
+        VariableType::Float,
+    )
+    .set_default_value(Some(json!(3600.0)));
+}
+
+/// Trailing edge debounce: every trigger takes a ticket, only the newest one fires.
+#[derive(Debug, Default)]
+pub struct Debouncer {
+    generation: u64,
+    pending: u64,
+}
+
+impl Debouncer {
+    pub fn arm(&mut self) -> u64 {
+        self.generation += 1;
+        self.pending += 1;
+        self.generation
+    }
+
+    /// Number of triggers the fire stands for, `None` if a newer trigger superseded `ticket`
+    pub fn settle(&mut self, ticket: u64) -> Option<u64> {
+        if ticket != self.generation {
+            return None;
+        }
+        Some(std::mem::take(&mut self.pending))
+    }
+}
+
+struct DebounceState {
+    debouncer: Mutex<Debouncer>,
+}
+
+impl Cacheable for DebounceState {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct DebounceNode {}
+
+impl DebounceNode {
+    pub fn new() -> Self {
+        DebounceNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for DebounceNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "control_debounce",
+            "Debounce",
+            "Coalesces bursts of triggers: fires once the key was quiet for the wait time, triggers that were superseded take Skipped. State is kept across runs of this board in memory, a restart of the executor forgets it",
+            "Control",
+        );
+
+        node.set_long_running(true);
+        node.add_icon("/flow/icons/clock.svg");
+
+        node.add_input_pin("exec_in", "Input", "Trigger Pin", VariableType::Execution);
+
+        node.add_input_pin(
+            "key",
+            "Key",
+            "Triggers with different keys are debounced independently, e.g. a file path or topic",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("")));
+
+        node.add_input_pin(
+            "wait_ms",
+            "Wait (ms)",
+            "Quiet time after the last trigger before firing",
+            VariableType::Float,
+        )
+        .set_default_value(Some(json!(500.0)));
+
+        add_ttl_pin(&mut node);
+
+        node.add_output_pin(
+            "exec_out",
+            "Fire",
+            "Triggers in the run of the last trigger of a burst",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "skipped",
+            "Skipped",
+            "Triggers in runs that were superseded by a newer trigger",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "count",
+            "Count",
+            "Number of triggers coalesced into this fire, 0 if skipped",
+            VariableType::Integer,
+        );
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+        context.deactivate_exec_pin("skipped").await?;
+
+        let key: String = context.evaluate_pin("key").await?;
+        let wait_ms: f64 = context.evaluate_pin("wait_ms").await?;
+        let wait = Duration::from_secs_f64(wait_ms.max(0.0) / 1000.0);
+        let ttl = state_ttl(context, wait).await?;
+
+        let cache_key = state_key(context, "control_debounce", &key);
+        let state = context
+            .app_state
+            .app_cache
+            .get_or_insert_with(&cache_key, ttl, || DebounceState {
+                debouncer: Mutex::new(Debouncer::default()),
+            })?;
+
+        let ticket = state
+            .debouncer
+            .lock()
+            .map_err(|_| flow_like_types::anyhow!("Debounce state is poisoned"))?
+            .arm();
+        cancellable_sleep(wait, context.get_cancellation_token()).await?;
+
+        let settled = state
+            .debouncer
+            .lock()
+            .map_err(|_| flow_like_types::anyhow!("Debounce state is poisoned"))?
+            .settle(ticket);
+
+        match settled {
+            Some(count) => {
+                context.set_pin_value("count", json!(count)).await?;
+                context.activate_exec_pin("exec_out").await?;
+            }
+            None => {
+                context.set_pin_value("count", json!(0)).await?;
+                context.activate_exec_pin("skipped").await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_last_trigger_of_a_burst_fires() {
+        let mut debouncer = Debouncer::default();
+
+        let tickets: Vec<u64> = (0..4).map(|_| debouncer.arm()).collect();
+        let settled: Vec<Option<u64>> = tickets
+            .iter()
+            .map(|ticket| debouncer.settle(*ticket))
+            .collect();
+        assert_eq!(settled, vec![None, None, None, Some(4)]);
+
+        // The next burst counts from zero again
+        let single = debouncer.arm();
+        assert_eq!(debouncer.settle(single), Some(1));
+    }
+
+    #[test]
+    fn test_trigger_after_quiet_period_fires_on_its_own() {
+        let mut debouncer = Debouncer::default();
+
+        let first = debouncer.arm();
+        assert_eq!(debouncer.settle(first), Some(1));
+        let second = debouncer.arm();
+        assert_eq!(debouncer.settle(second), Some(1));
+        assert_eq!(debouncer.settle(first), None);
+    }
+}
@@ -1,3 +1,4 @@
+use super::loop_break::LoopBreak;
 use flow_like::flow::{
     board::Board,
     execution::{LogLevel, context::ExecutionContext, internal_node::InternalNode},
@@ -28,8 +29,15 @@ impl NodeLogic for LoopNode {
             "Control",
         );
         node.add_icon("/flow/icons/for-each.svg");
+        node.set_version(1);
 
         node.add_input_pin("exec_in", "Input", "Trigger Pin", VariableType::Execution);
+        node.add_input_pin(
+            "break",
+            "Break",
+            "Stops the loop after the current element, trigger it from inside the loop body",
+            VariableType::Execution,
+        );
         node.add_input_pin("array", "Array", "Array to Loop", VariableType::Generic)
             .set_value_type(ValueType::Array)
             .set_options(
@@ -64,10 +72,21 @@ impl NodeLogic for LoopNode {
             VariableType::Execution,
         );
 
+        node.add_output_pin(
+            "iterations",
+            "Iterations",
+            "How many elements the loop body ran for",
+            VariableType::Integer,
+        );
+
         node
     }
 
     async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        if LoopBreak::triggered(context) {
+            return LoopBreak::request(context).await;
+        }
+
         let done = context.get_pin_by_name("done").await?;
         context.deactivate_exec_pin_ref(&done).await?;
 
@@ -82,8 +101,12 @@ impl NodeLogic for LoopNode {
             .as_array()
             .ok_or(flow_like_types::anyhow!("Array value is not an array"))?;
 
+        let loop_break = LoopBreak::listen(context).await;
+        let mut iterations: usize = 0;
+
         context.activate_exec_pin_ref(&exec_item).await?;
-        for (i, item) in array_value.iter().enumerate() {
+        'outer: for (i, item) in array_value.iter().enumerate() {
+            iterations += 1;
             let item = item.to_owned();
             value.set_value(item).await;
             index.set_value(flow_like_types::Value::from(i)).await;
@@ -100,9 +123,20 @@ impl NodeLogic for LoopNode {
                         LogLevel::Error,
                     );
                 }
+
+                if loop_break.is_requested() {
+                    context.log_message(
+                        &format!("For Each: breaking at index {}", i),
+                        LogLevel::Debug,
+                    );
+                    break 'outer;
+                }
             }
         }
 
+        context
+            .set_pin_value("iterations", Value::from(iterations))
+            .await?;
         context.deactivate_exec_pin_ref(&exec_item).await?;
         context.activate_exec_pin_ref(&done).await?;
 
@@ -0,0 +1,62 @@
+//! The `Break` exec input of the loop nodes.
+//!
+//! Break is usually triggered from inside the loop body, which runs the loop node a second
+//! time while its first run is still looping. That second run only raises a flag in the run
+//! cache and deactivates the loop body, the looping run checks the flag after every body node.
+
+use flow_like::flow::execution::context::ExecutionContext;
+use flow_like_types::Cacheable;
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+
+pub(crate) struct LoopBreak {
+    requested: AtomicBool,
+}
+
+impl Cacheable for LoopBreak {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+fn cache_key(context: &ExecutionContext) -> String {
+    format!("loop_break_{}", context.id)
+}
+
+impl LoopBreak {
+    /// Whether this run of the loop node was started through its `break` pin
+    pub(crate) fn triggered(context: &ExecutionContext) -> bool {
+        context
+            .started_by
+            .as_ref()
+            .is_some_and(|pins| pins.iter().any(|pin| pin.name == "break"))
+    }
+
+    /// Starts listening for a break, clearing one left over from an earlier loop
+    pub(crate) async fn listen(context: &ExecutionContext) -> Arc<Self> {
+        let flag = Arc::new(LoopBreak {
+            requested: AtomicBool::new(false),
+        });
+        context.set_cache(&cache_key(context), flag.clone()).await;
+        flag
+    }
+
+    /// Asks the running loop of this node to stop after the current body node
+    pub(crate) async fn request(context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        if let Some(flag) = context.get_cached::<LoopBreak>(&cache_key(context)).await {
+            flag.requested.store(true, Ordering::SeqCst);
+        }
+        // Keeps the caller from following the still active loop body
+        context.deactivate_exec_pin("exec_out").await
+    }
+
+    pub(crate) fn is_requested(&self) -> bool {
+        self.requested.load(Ordering::SeqCst)
+    }
+}
@@ -0,0 +1,347 @@
+use flow_like::flow::{
+    execution::{
+        LogLevel, context::ExecutionContext, internal_node::InternalNode, internal_pin::InternalPin,
+    },
+    node::{Node, NodeLogic},
+    variable::VariableType,
+};
+use flow_like_types::{
+    Cacheable, async_trait,
+    json::json,
+    rand::{self, Rng},
+    tokio::{self, time},
+    tokio_util::sync::CancellationToken,
+};
+use std::{
+    any::Any,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+/// Exponential backoff schedule used between retry attempts.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BackoffPolicy {
+    pub initial_delay_ms: f64,
+    pub multiplier: f64,
+    pub max_delay_ms: f64,
+    pub jitter: bool,
+}
+
+impl BackoffPolicy {
+    /// Delay before the given retry (1-based: retry 1 waits `initial_delay_ms`).
+    /// Jitter is "equal jitter": half of the delay is kept, the other half is randomized.
+    pub fn delay_for(&self, retry: u32) -> time::Duration {
+        let exponent = retry.saturating_sub(1) as i32;
+        let base = self.initial_delay_ms.max(0.0) * self.multiplier.max(1.0).powi(exponent);
+        let mut delay = base.min(self.max_delay_ms.max(0.0));
+
+        if self.jitter && delay > 0.0 {
+            let half = delay / 2.0;
+            delay = half + rand::rng().random_range(0.0..=half);
+        }
+
+        time::Duration::from_millis(delay as u64)
+    }
+}
+
+/// Sleeps for `delay`, returning an error if the token is cancelled first.
+pub async fn cancellable_sleep(
+    delay: time::Duration,
+    token: Option<CancellationToken>,
+) -> flow_like_types::Result<()> {
+    let Some(token) = token else {
+        time::sleep(delay).await;
+        return Ok(());
+    };
+
+    tokio::select! {
+        biased;
+        _ = token.cancelled() => Err(flow_like_types::anyhow!("Execution was cancelled")),
+        _ = time::sleep(delay) => Ok(()),
+    }
+}
+
+/// Marker stored in the run cache while the retry loop is active, so that the
+/// `failed` signal routed back from the body can be told apart from a fresh trigger.
+struct RetryAttemptState {
+    failed: AtomicBool,
+}
+
+impl Cacheable for RetryAttemptState {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct RetryNode {}
+
+impl RetryNode {
+    pub fn new() -> Self {
+        RetryNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for RetryNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "control_retry",
+            "Retry",
+            "Runs the body and retries it with exponential backoff whenever it signals failure",
+            "Control",
+        );
+
+        node.set_long_running(true);
+        node.add_icon("/flow/icons/history.svg");
+
+        node.add_input_pin("exec_in", "Input", "Trigger Pin", VariableType::Execution);
+        node.add_input_pin(
+            "failed",
+            "Failed",
+            "Trigger from inside the body to mark the current attempt as failed",
+            VariableType::Execution,
+        );
+
+        node.add_input_pin(
+            "max_attempts",
+            "Max Attempts",
+            "Maximum number of attempts (including the first one)",
+            VariableType::Integer,
+        )
+        .set_default_value(Some(json!(3)));
+
+        node.add_input_pin(
+            "initial_delay_ms",
+            "Initial Delay (ms)",
+            "Delay before the first retry",
+            VariableType::Float,
+        )
+        .set_default_value(Some(json!(500.0)));
+
+        node.add_input_pin(
+            "multiplier",
+            "Multiplier",
+            "Factor the delay grows by after each retry",
+            VariableType::Float,
+        )
+        .set_default_value(Some(json!(2.0)));
+
+        node.add_input_pin(
+            "max_delay_ms",
+            "Max Delay (ms)",
+            "Upper bound for the delay between attempts",
+            VariableType::Float,
+        )
+        .set_default_value(Some(json!(30000.0)));
+
+        node.add_input_pin(
+            "jitter",
+            "Jitter",
+            "Randomize delays to avoid synchronized retries",
+            VariableType::Boolean,
+        )
+        .set_default_value(Some(json!(false)));
+
+        node.add_output_pin(
+            "body",
+            "Body",
+            "Execution path that is attempted (and retried)",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "done",
+            "Done",
+            "Executes once an attempt succeeded",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "exhausted",
+            "Exhausted",
+            "Executes when every attempt failed",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "attempts",
+            "Attempts",
+            "Number of attempts that were made",
+            VariableType::Integer,
+        );
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        let body = context.get_pin_by_name("body").await?;
+        let cache_key = format!("control_retry_{}", context.node.node_id());
+
+        // Re-entered through the `failed` pin while the loop below is running
+        if let Some(state) = context.get_cache(&cache_key).await {
+            if let Some(state) = state.downcast_ref::<RetryAttemptState>() {
+                state.failed.store(true, Ordering::SeqCst);
+            }
+            context.deactivate_exec_pin_ref(&body).await?;
+            return Ok(());
+        }
+
+        let done = context.get_pin_by_name("done").await?;
+        let exhausted = context.get_pin_by_name("exhausted").await?;
+        context.deactivate_exec_pin_ref(&done).await?;
+        context.deactivate_exec_pin_ref(&exhausted).await?;
+
+        let max_attempts: i64 = context.evaluate_pin("max_attempts").await?;
+        let max_attempts = max_attempts.max(1) as u32;
+        let policy = BackoffPolicy {
+            initial_delay_ms: context.evaluate_pin("initial_delay_ms").await?,
+            multiplier: context.evaluate_pin("multiplier").await?,
+            max_delay_ms: context.evaluate_pin("max_delay_ms").await?,
+            jitter: context.evaluate_pin("jitter").await?,
+        };
+
+        let state = Arc::new(RetryAttemptState {
+            failed: AtomicBool::new(false),
+        });
+        context.set_cache(&cache_key, state.clone()).await;
+
+        let result = run_attempts(context, &body, &state, max_attempts, policy).await;
+
+        context.cache.write().await.remove(&cache_key);
+        context.deactivate_exec_pin_ref(&body).await?;
+
+        let (attempts, succeeded) = result?;
+        context.set_pin_value("attempts", json!(attempts)).await?;
+
+        if succeeded {
+            context.activate_exec_pin_ref(&done).await?;
+        } else {
+            context.log_message(
+                &format!("Retry exhausted after {} attempts", attempts),
+                LogLevel::Warn,
+            );
+            context.activate_exec_pin_ref(&exhausted).await?;
+        }
+
+        Ok(())
+    }
+}
+
+async fn run_attempts(
+    context: &mut ExecutionContext,
+    body: &Arc<InternalPin>,
+    state: &RetryAttemptState,
+    max_attempts: u32,
+    policy: BackoffPolicy,
+) -> flow_like_types::Result<(u32, bool)> {
+    let nodes = body.get_connected_nodes();
+
+    for attempt in 1..=max_attempts {
+        context.check_cancelled()?;
+
+        if attempt > 1 {
+            let delay = policy.delay_for(attempt - 1);
+            context.log_message(
+                &format!("Retry: attempt {} in {} ms", attempt, delay.as_millis()),
+                LogLevel::Debug,
+            );
+            cancellable_sleep(delay, context.get_cancellation_token()).await?;
+        }
+
+        state.failed.store(false, Ordering::SeqCst);
+        context.activate_exec_pin_ref(body).await?;
+
+        let mut errored = false;
+        for node in &nodes {
+            let mut sub_context = context.create_sub_context(node).await;
+            let run = InternalNode::trigger(&mut sub_context, &mut None, true).await;
+            sub_context.end_trace();
+            context.push_sub_context(&mut sub_context);
+
+            if let Err(error) = run {
+                context.log_message(
+                    &format!("Error: {:?} in attempt {}", error, attempt),
+                    LogLevel::Error,
+                );
+                errored = true;
+            }
+        }
+
+        context.deactivate_exec_pin_ref(body).await?;
+
+        if !errored && !state.failed.load(Ordering::SeqCst) {
+            return Ok((attempt, true));
+        }
+    }
+
+    Ok((max_attempts, false))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_policy(jitter: bool) -> BackoffPolicy {
+        BackoffPolicy {
+            initial_delay_ms: 100.0,
+            multiplier: 2.0,
+            max_delay_ms: 1000.0,
+            jitter,
+        }
+    }
+
+    #[test]
+    fn test_delay_sequence() {
+        let policy = sample_policy(false);
+        let delays: Vec<u128> = (1..=6).map(|r| policy.delay_for(r).as_millis()).collect();
+        assert_eq!(delays, vec![100, 200, 400, 800, 1000, 1000]);
+    }
+
+    #[test]
+    fn test_jitter_stays_within_bounds() {
+        let policy = sample_policy(true);
+        for retry in 1..=6 {
+            let upper = sample_policy(false).delay_for(retry).as_millis();
+            let delay = policy.delay_for(retry).as_millis();
+            assert!(delay >= upper / 2 && delay <= upper);
+        }
+    }
+
+    #[test]
+    fn test_multiplier_below_one_does_not_shrink() {
+        let policy = BackoffPolicy {
+            multiplier: 0.5,
+            ..sample_policy(false)
+        };
+        assert_eq!(policy.delay_for(4).as_millis(), 100);
+    }
+
+    #[tokio::test]
+    async fn test_cancellation_aborts_pending_retry() {
+        let token = CancellationToken::new();
+        let cancel = token.clone();
+        tokio::spawn(async move {
+            time::sleep(time::Duration::from_millis(20)).await;
+            cancel.cancel();
+        });
+
+        let started = time::Instant::now();
+        let result = cancellable_sleep(time::Duration::from_secs(30), Some(token)).await;
+        assert!(result.is_err());
+        assert!(started.elapsed() < time::Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_sleep_without_token_completes() {
+        let result = cancellable_sleep(time::Duration::from_millis(1), None).await;
+        assert!(result.is_ok());
+    }
+}
@@ -0,0 +1,282 @@
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic},
+    pin::ValueType,
+    variable::VariableType,
+};
+use flow_like_types::{Cacheable, async_trait, json::json};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::{
+    any::Any,
+    collections::HashSet,
+    sync::{Arc, Mutex},
+};
+
+fn default_weight() -> u32 {
+    1
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq)]
+pub struct WeightedEndpoint {
+    pub endpoint: String,
+    /// Relative share of the selections, 0 disables the endpoint
+    #[serde(default = "default_weight")]
+    pub weight: u32,
+}
+
+/// Smooth weighted round-robin as used by nginx: endpoints are interleaved instead of
+/// picked in bursts, e.g. weights 5, 1, 1 select `a a b a c a a`.
+#[derive(Debug, Default)]
+pub struct WeightedRoundRobin {
+    endpoints: Vec<WeightedEndpoint>,
+    current: Vec<i64>,
+}
+
+impl WeightedRoundRobin {
+    /// Index of the next endpoint, skipping unhealthy ones. Starts over if the list changed.
+    pub fn next(
+        &mut self,
+        endpoints: &[WeightedEndpoint],
+        unhealthy: &HashSet<String>,
+    ) -> Option<usize> {
+        if self.endpoints != endpoints {
+            self.endpoints = endpoints.to_vec();
+            self.current = vec![0; endpoints.len()];
+        }
+
+        let mut total = 0;
+        let mut best: Option<usize> = None;
+        for (index, endpoint) in endpoints.iter().enumerate() {
+            if endpoint.weight == 0 || unhealthy.contains(&endpoint.endpoint) {
+                continue;
+            }
+            let weight = endpoint.weight as i64;
+            self.current[index] += weight;
+            total += weight;
+            if best.is_none_or(|best| self.current[index] > self.current[best]) {
+                best = Some(index);
+            }
+        }
+
+        let best = best?;
+        self.current[best] -= total;
+        Some(best)
+    }
+}
+
+/// Selection state of one Round Robin node, kept in the run cache between invocations.
+struct RoundRobinState {
+    balancer: Mutex<WeightedRoundRobin>,
+}
+
+impl Cacheable for RoundRobinState {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct RoundRobinNode {}
+
+impl RoundRobinNode {
+    pub fn new() -> Self {
+        RoundRobinNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for RoundRobinNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "control_round_robin",
+            "Round Robin",
+            "Picks the next endpoint from a weighted list on every trigger, to spread calls across backends. Unhealthy endpoints are skipped",
+            "Control",
+        );
+        node.add_icon("/flow/icons/workflow.svg");
+
+        node.add_input_pin("exec_in", "Input", "Trigger Pin", VariableType::Execution);
+
+        node.add_input_pin(
+            "endpoints",
+            "Endpoints",
+            "Endpoints with their weight, e.g. { \"endpoint\": \"https://a.example.com\", \"weight\": 3 }",
+            VariableType::Struct,
+        )
+        .set_schema::<WeightedEndpoint>()
+        .set_value_type(ValueType::Array)
+        .set_default_value(Some(json!([])));
+
+        node.add_input_pin(
+            "unhealthy",
+            "Unhealthy",
+            "Endpoints to skip until they recover",
+            VariableType::String,
+        )
+        .set_value_type(ValueType::Array)
+        .set_default_value(Some(json!([])));
+
+        node.add_output_pin(
+            "exec_out",
+            "Selected",
+            "Triggers with the selected endpoint",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "unavailable",
+            "Unavailable",
+            "Triggers if every endpoint is unhealthy or has no weight",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "endpoint",
+            "Endpoint",
+            "The selected endpoint",
+            VariableType::String,
+        );
+
+        node.add_output_pin(
+            "index",
+            "Index",
+            "Position of the selected endpoint in the list, -1 if none is available",
+            VariableType::Integer,
+        );
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+        context.deactivate_exec_pin("unavailable").await?;
+
+        let endpoints: Vec<WeightedEndpoint> = context.evaluate_pin("endpoints").await?;
+        let unhealthy: HashSet<String> = context
+            .evaluate_pin::<Vec<String>>("unhealthy")
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+
+        let cache_key = format!("control_round_robin_{}", context.node.node_id());
+        let state = match context.get_cache(&cache_key).await {
+            Some(state) if state.as_any().is::<RoundRobinState>() => state,
+            _ => {
+                let state: Arc<dyn Cacheable> = Arc::new(RoundRobinState {
+                    balancer: Mutex::new(WeightedRoundRobin::default()),
+                });
+                context.set_cache(&cache_key, state.clone()).await;
+                state
+            }
+        };
+
+        let selected = state
+            .as_any()
+            .downcast_ref::<RoundRobinState>()
+            .and_then(|state| state.balancer.lock().ok()?.next(&endpoints, &unhealthy));
+
+        let Some(index) = selected else {
+            context.set_pin_value("endpoint", json!("")).await?;
+            context.set_pin_value("index", json!(-1)).await?;
+            context.activate_exec_pin("unavailable").await?;
+            return Ok(());
+        };
+
+        context
+            .set_pin_value("endpoint", json!(endpoints[index].endpoint))
+            .await?;
+        context.set_pin_value("index", json!(index)).await?;
+        context.activate_exec_pin("exec_out").await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn endpoints(weights: &[(&str, u32)]) -> Vec<WeightedEndpoint> {
+        weights
+            .iter()
+            .map(|(endpoint, weight)| WeightedEndpoint {
+                endpoint: endpoint.to_string(),
+                weight: *weight,
+            })
+            .collect()
+    }
+
+    fn picks(
+        balancer: &mut WeightedRoundRobin,
+        endpoints: &[WeightedEndpoint],
+        unhealthy: &HashSet<String>,
+        count: usize,
+    ) -> String {
+        (0..count)
+            .map(|_| match balancer.next(endpoints, unhealthy) {
+                Some(index) => endpoints[index].endpoint.clone(),
+                None => "-".to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_smooth_weighted_order() {
+        let list = endpoints(&[("a", 5), ("b", 1), ("c", 1)]);
+        let mut balancer = WeightedRoundRobin::default();
+        let none = HashSet::new();
+
+        assert_eq!(picks(&mut balancer, &list, &none, 7), "aabacaa");
+        assert_eq!(picks(&mut balancer, &list, &none, 7), "aabacaa");
+
+        let even = endpoints(&[("a", 1), ("b", 1), ("c", 1)]);
+        assert_eq!(picks(&mut balancer, &even, &none, 6), "abcabc");
+    }
+
+    #[test]
+    fn test_weights_share_selections() {
+        let list = endpoints(&[("a", 3), ("b", 2), ("c", 1)]);
+        let mut balancer = WeightedRoundRobin::default();
+        let selections = picks(&mut balancer, &list, &HashSet::new(), 600);
+
+        assert_eq!(selections.matches('a').count(), 300);
+        assert_eq!(selections.matches('b').count(), 200);
+        assert_eq!(selections.matches('c').count(), 100);
+    }
+
+    #[test]
+    fn test_unhealthy_and_disabled_endpoints_are_skipped() {
+        let list = endpoints(&[("a", 2), ("b", 1), ("c", 0)]);
+        let mut balancer = WeightedRoundRobin::default();
+
+        let unhealthy = HashSet::from(["a".to_string()]);
+        assert_eq!(picks(&mut balancer, &list, &unhealthy, 3), "bbb");
+
+        // Recovered endpoints rejoin the rotation
+        let selections = picks(&mut balancer, &list, &HashSet::new(), 30);
+        assert_eq!(selections.matches('a').count(), 20);
+        assert!(!selections.contains('c'));
+
+        let all = HashSet::from(["a".to_string(), "b".to_string()]);
+        assert_eq!(picks(&mut balancer, &list, &all, 2), "--");
+        assert_eq!(balancer.next(&[], &HashSet::new()), None);
+    }
+
+    #[test]
+    fn test_changed_list_starts_over() {
+        let mut balancer = WeightedRoundRobin::default();
+        let none = HashSet::new();
+
+        let list = endpoints(&[("a", 5), ("b", 1), ("c", 1)]);
+        assert_eq!(picks(&mut balancer, &list, &none, 3), "aab");
+
+        let reweighted = endpoints(&[("a", 1), ("b", 5), ("c", 1)]);
+        assert_eq!(picks(&mut balancer, &reweighted, &none, 7), "bbabcbb");
+    }
+}
@@ -0,0 +1,199 @@
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic},
+    variable::VariableType,
+};
+use flow_like_types::{Cacheable, async_trait, json::json};
+use std::{
+    any::Any,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use super::debounce::{add_ttl_pin, state_key, state_ttl};
+
+/// Leading edge throttle: the first trigger passes, the ones within `interval` after it
+/// are suppressed.
+#[derive(Debug, Default)]
+pub struct Throttler {
+    last_pass: Option<Instant>,
+    suppressed: u64,
+}
+
+impl Throttler {
+    /// Number of triggers suppressed since the previous pass, `None` if this one is suppressed
+    pub fn pass(&mut self, now: Instant, interval: Duration) -> Option<u64> {
+        if self
+            .last_pass
+            .is_some_and(|last| now.duration_since(last) < interval)
+        {
+            self.suppressed += 1;
+            return None;
+        }
+        self.last_pass = Some(now);
+        Some(std::mem::take(&mut self.suppressed))
+    }
+}
+
+struct ThrottleState {
+    throttler: Mutex<Throttler>,
+}
+
+impl Cacheable for ThrottleState {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct ThrottleNode {}
+
+impl ThrottleNode {
+    pub fn new() -> Self {
+        ThrottleNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for ThrottleNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "control_throttle",
+            "Throttle",
+            "Lets at most one trigger per interval pass for each key, the rest take Throttled. State is kept across runs of this board in memory, a restart of the executor forgets it",
+            "Control",
+        );
+
+        node.add_icon("/flow/icons/clock.svg");
+
+        node.add_input_pin("exec_in", "Input", "Trigger Pin", VariableType::Execution);
+
+        node.add_input_pin(
+            "key",
+            "Key",
+            "Triggers with different keys are throttled independently, e.g. a device id or topic",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("")));
+
+        node.add_input_pin(
+            "interval_ms",
+            "Interval (ms)",
+            "Minimum time between two passing triggers",
+            VariableType::Float,
+        )
+        .set_default_value(Some(json!(1000.0)));
+
+        add_ttl_pin(&mut node);
+
+        node.add_output_pin(
+            "exec_out",
+            "Pass",
+            "Triggers if the interval since the last pass is over",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "throttled",
+            "Throttled",
+            "Triggers if the trigger was suppressed",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "suppressed",
+            "Suppressed",
+            "Number of triggers suppressed since the previous pass, 0 if throttled",
+            VariableType::Integer,
+        );
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+        context.deactivate_exec_pin("throttled").await?;
+
+        let key: String = context.evaluate_pin("key").await?;
+        let interval_ms: f64 = context.evaluate_pin("interval_ms").await?;
+        let interval = Duration::from_secs_f64(interval_ms.max(0.0) / 1000.0);
+        let ttl = state_ttl(context, interval).await?;
+
+        let cache_key = state_key(context, "control_throttle", &key);
+        let state = context
+            .app_state
+            .app_cache
+            .get_or_insert_with(&cache_key, ttl, || ThrottleState {
+                throttler: Mutex::new(Throttler::default()),
+            })?;
+
+        let passed = state
+            .throttler
+            .lock()
+            .map_err(|_| flow_like_types::anyhow!("Throttle state is poisoned"))?
+            .pass(Instant::now(), interval);
+
+        match passed {
+            Some(suppressed) => {
+                context
+                    .set_pin_value("suppressed", json!(suppressed))
+                    .await?;
+                context.activate_exec_pin("exec_out").await?;
+            }
+            None => {
+                context.set_pin_value("suppressed", json!(0)).await?;
+                context.activate_exec_pin("throttled").await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_burst_passes_once_per_interval() {
+        let mut throttler = Throttler::default();
+        let interval = Duration::from_millis(100);
+        let start = Instant::now();
+
+        // Triggers every 30 ms for 300 ms
+        let passed: Vec<Option<u64>> = (0..10)
+            .map(|i| throttler.pass(start + Duration::from_millis(i * 30), interval))
+            .collect();
+        assert_eq!(
+            passed,
+            vec![
+                Some(0),
+                None,
+                None,
+                None,
+                Some(3),
+                None,
+                None,
+                None,
+                Some(3),
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn test_spaced_triggers_all_pass() {
+        let mut throttler = Throttler::default();
+        let interval = Duration::from_millis(100);
+        let start = Instant::now();
+
+        for i in 0..3 {
+            let now = start + Duration::from_millis(i * 100);
+            assert_eq!(throttler.pass(now, interval), Some(0));
+        }
+    }
+}
@@ -0,0 +1,182 @@
+use flow_like::flow::{
+    execution::{
+        LogLevel,
+        context::{ExecutionContext, NodeFailure},
+        internal_node::InternalNode,
+        internal_pin::InternalPin,
+    },
+    node::{Node, NodeLogic},
+    variable::VariableType,
+};
+use flow_like_types::{Value, async_trait, json::json};
+use std::sync::Arc;
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct TryCatchNode {}
+
+impl TryCatchNode {
+    pub fn new() -> Self {
+        TryCatchNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for TryCatchNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "control_try_catch",
+            "Try Catch",
+            "Runs the Try branch and catches its errors instead of failing the board. Errors are routed to Catch, Finally runs in any case",
+            "Control",
+        );
+
+        node.set_long_running(true);
+        node.add_icon("/flow/icons/log-error.svg");
+
+        node.add_input_pin("exec_in", "Input", "Trigger Pin", VariableType::Execution);
+
+        node.add_output_pin(
+            "try",
+            "Try",
+            "Execution path whose errors are caught",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "catch",
+            "Catch",
+            "Executes when the Try branch failed. If nothing is connected the error is rethrown after Finally",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "finally",
+            "Finally",
+            "Executes after Try and Catch, whether they failed or not",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "message",
+            "Message",
+            "Error message of the failed node, empty if Try succeeded",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("")));
+
+        node.add_output_pin(
+            "error_type",
+            "Error Type",
+            "Type of the node that failed, e.g. http_request",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("")));
+
+        node.add_output_pin(
+            "error",
+            "Error",
+            "The caught error, null if Try succeeded",
+            VariableType::Struct,
+        )
+        .set_schema::<NodeFailure>();
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        let try_pin = context.get_pin_by_name("try").await?;
+        let catch_pin = context.get_pin_by_name("catch").await?;
+        let finally_pin = context.get_pin_by_name("finally").await?;
+
+        context.deactivate_exec_pin_ref(&catch_pin).await?;
+        context.deactivate_exec_pin_ref(&finally_pin).await?;
+        set_error_pins(context, None).await?;
+
+        let mut uncaught = None;
+        if let Some(failure) = run_branch(context, &try_pin).await? {
+            context.log_message(
+                &format!(
+                    "Caught error of {} [{}]: {}",
+                    failure.node_type, failure.node_id, failure.message
+                ),
+                LogLevel::Warn,
+            );
+            set_error_pins(context, Some(&failure)).await?;
+
+            uncaught = if catch_pin.get_connected_nodes().is_empty() {
+                Some(failure)
+            } else {
+                run_branch(context, &catch_pin).await?
+            };
+        }
+
+        let Some(failure) = uncaught else {
+            context.activate_exec_pin_ref(&finally_pin).await?;
+            return Ok(());
+        };
+
+        // The error leaves this node, so Finally cannot wait for the engine to pick it up
+        if let Some(finally_failure) = run_branch(context, &finally_pin).await? {
+            context.log_message(
+                &format!("Finally failed as well: {}", finally_failure.message),
+                LogLevel::Error,
+            );
+        }
+
+        let message = failure.message.clone();
+        context.failure = Some(failure);
+        Err(flow_like_types::anyhow!(message))
+    }
+}
+
+async fn set_error_pins(
+    context: &mut ExecutionContext,
+    failure: Option<&NodeFailure>,
+) -> flow_like_types::Result<()> {
+    let (message, error_type, error) = match failure {
+        Some(failure) => (
+            failure.message.clone(),
+            failure.node_type.clone(),
+            json!(failure),
+        ),
+        None => (String::new(), String::new(), Value::Null),
+    };
+    context.set_pin_value("message", json!(message)).await?;
+    context
+        .set_pin_value("error_type", json!(error_type))
+        .await?;
+    context.set_pin_value("error", error).await?;
+    Ok(())
+}
+
+/// Runs everything reachable from `pin` inline and returns the first error instead of
+/// failing this node. Cancellation is not an error of the branch and still ends the run.
+async fn run_branch(
+    context: &mut ExecutionContext,
+    pin: &Arc<InternalPin>,
+) -> flow_like_types::Result<Option<NodeFailure>> {
+    context.activate_exec_pin_ref(pin).await?;
+
+    let mut failure = None;
+    for node in pin.get_connected_nodes() {
+        let mut sub_context = context.create_sub_context(&node).await;
+        let run = InternalNode::trigger(&mut sub_context, &mut None, true).await;
+        sub_context.end_trace();
+        let sub_failure = sub_context.take_failure();
+        context.push_sub_context(&mut sub_context);
+
+        if run.is_err() {
+            failure = Some(sub_failure.unwrap_or_else(|| NodeFailure {
+                node_id: node.meta.id.clone(),
+                node_type: node.meta.name.clone(),
+                message: "Node failed".to_string(),
+            }));
+            break;
+        }
+    }
+
+    context.deactivate_exec_pin_ref(pin).await?;
+    context.check_cancelled()?;
+    Ok(failure)
+}
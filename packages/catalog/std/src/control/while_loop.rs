@@ -1,3 +1,4 @@
+use super::loop_break::LoopBreak;
 use flow_like::flow::{
     execution::{LogLevel, context::ExecutionContext, internal_node::InternalNode},
     node::{Node, NodeLogic},
@@ -5,6 +6,9 @@ use flow_like::flow::{
 };
 use flow_like_types::{async_trait, json::json};
 
+/// Upper bound for `max_iter`, so a misconfigured loop cannot keep the executor busy forever
+pub const ITERATION_CAP: u64 = 100_000;
+
 #[crate::register_node]
 #[derive(Default)]
 pub struct WhileLoopNode {}
@@ -25,9 +29,16 @@ impl NodeLogic for WhileLoopNode {
             "Control",
         );
         node.add_icon("/flow/icons/for-each.svg");
+        node.set_version(1);
 
         // inputs
         node.add_input_pin("exec_in", "Input", "Trigger Pin", VariableType::Execution);
+        node.add_input_pin(
+            "break",
+            "Break",
+            "Stops the loop after the current iteration, trigger it from inside the loop body",
+            VariableType::Execution,
+        );
 
         node.add_input_pin(
             "condition",
@@ -40,7 +51,7 @@ impl NodeLogic for WhileLoopNode {
         node.add_input_pin(
             "max_iter",
             "Max",
-            "Maximum number of iterations",
+            "Maximum number of iterations, capped at 100000",
             VariableType::Integer,
         )
         .set_default_value(Some(json!(15)));
@@ -67,21 +78,35 @@ impl NodeLogic for WhileLoopNode {
             VariableType::Execution,
         );
 
+        node.add_output_pin(
+            "iterations",
+            "Iterations",
+            "How many times the loop body ran",
+            VariableType::Integer,
+        );
+
         node
     }
 
     async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        if LoopBreak::triggered(context) {
+            return LoopBreak::request(context).await;
+        }
+
         let exec_item = context.get_pin_by_name("exec_out").await?;
         let iter = context.get_pin_by_name("iter").await?;
         let done = context.get_pin_by_name("done").await?;
         context.deactivate_exec_pin_ref(&done).await?;
         let max_iter: u64 = context.evaluate_pin("max_iter").await?;
+        let max_iter = max_iter.min(ITERATION_CAP);
         let condition_pin = context.get_pin_by_name("condition").await?;
+        let loop_break = LoopBreak::listen(context).await;
 
         context.activate_exec_pin_ref(&exec_item).await?;
         let flow = exec_item.get_connected_nodes();
 
-        for i in 0..max_iter {
+        let mut iterations: u64 = 0;
+        'outer: while iterations < max_iter {
             if !InternalNode::trigger_missing_dependencies(context, &mut None, false).await {
                 context.log_message(
                     "Failed to re-trigger condition dependencies",
@@ -96,7 +121,9 @@ impl NodeLogic for WhileLoopNode {
             if !condition {
                 break;
             }
-            iter.set_value(flow_like_types::json::json!(i)).await;
+            iter.set_value(flow_like_types::json::json!(iterations))
+                .await;
+            iterations += 1;
             for node in &flow {
                 let mut sub_context = context.create_sub_context(node).await;
                 let run = InternalNode::trigger(&mut sub_context, &mut None, true).await;
@@ -106,13 +133,34 @@ impl NodeLogic for WhileLoopNode {
                 if run.is_err() {
                     let error = run.err().unwrap();
                     context.log_message(
-                        &format!("Error: {:?} in iteration {}", error, i),
+                        &format!("Error: {:?} in iteration {}", error, iterations - 1),
                         LogLevel::Error,
                     );
                 }
+
+                if loop_break.is_requested() {
+                    context.log_message(
+                        &format!("While Loop: breaking after {} iterations", iterations),
+                        LogLevel::Debug,
+                    );
+                    break 'outer;
+                }
             }
         }
 
+        if iterations == max_iter && !loop_break.is_requested() {
+            context.log_message(
+                &format!(
+                    "While Loop: stopped at the maximum of {} iterations",
+                    max_iter
+                ),
+                LogLevel::Warn,
+            );
+        }
+
+        context
+            .set_pin_value("iterations", json!(iterations))
+            .await?;
         context.deactivate_exec_pin_ref(&exec_item).await?;
         context.activate_exec_pin_ref(&done).await?;
         Ok(())
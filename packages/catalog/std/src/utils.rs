@@ -1,10 +1,14 @@
 pub mod array;
 pub mod bool;
+pub mod command;
 pub mod csv;
 pub mod cuid;
 pub mod datetime;
+pub mod email;
+pub mod encoding;
 pub mod env;
 pub mod float;
+pub mod generate_id;
 pub mod hash;
 pub mod int;
 pub mod json;
@@ -15,4 +19,6 @@ pub mod set;
 pub mod string;
 pub mod types;
 pub mod user;
+pub mod user_agent;
+pub mod vcard;
 pub mod vector;
@@ -0,0 +1,529 @@
+use flow_like::flow::{
+    execution::{LogLevel, context::ExecutionContext},
+    node::{Node, NodeLogic, NodeScores},
+    pin::{PinOptions, ValueType},
+    variable::VariableType,
+};
+use flow_like_types::{
+    Result, async_trait, bail,
+    json::json,
+    tokio::{
+        self,
+        io::{AsyncRead, AsyncReadExt},
+    },
+    tokio_util::sync::CancellationToken,
+};
+use std::{process::Stdio, time::Duration};
+
+/// Programs the host allows flows to run and the arguments each may receive, e.g.
+/// `git:status,--short;/usr/bin/ffmpeg:-i,-y,--format=*`. Entries are separated by `;`,
+/// an argument ending in `*` allows any argument with that start. Programs listed without
+/// `:` may only run without arguments, several of them can be separated by `,`.
+/// Unset means the node refuses to run anything.
+pub const ALLOWED_COMMANDS_ENV: &str = "FLOW_LIKE_ALLOWED_COMMANDS";
+
+/// Stdout and stderr are each cut off after this many bytes, the process is killed once
+/// either grows beyond it.
+const MAX_OUTPUT: usize = 1024 * 1024;
+
+/// The only variables passed on to the process, so secrets of the host do not leak into it.
+const INHERITED_ENV: [&str; 2] = ["PATH", "SystemRoot"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct CommandOutput {
+    pub exit_code: i64,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct AllowedCommand {
+    pub program: String,
+    pub args: Vec<String>,
+}
+
+fn split_list(list: &str) -> impl Iterator<Item = String> + '_ {
+    list.split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+}
+
+/// Position of the `:` that ends the program, skipping the one of a Windows drive like `C:\`
+fn program_end(entry: &str) -> Option<usize> {
+    let bytes = entry.as_bytes();
+    let skip = if bytes.len() > 2
+        && bytes[0].is_ascii_alphabetic()
+        && bytes[1] == b':'
+        && matches!(bytes[2], b'\\' | b'/')
+    {
+        2
+    } else {
+        0
+    };
+    entry[skip..].find(':').map(|i| i + skip)
+}
+
+pub(crate) fn allowed_commands(value: Option<&str>) -> Vec<AllowedCommand> {
+    let mut commands = Vec::new();
+    for entry in value.unwrap_or_default().split(';').map(str::trim) {
+        match program_end(entry) {
+            Some(end) => {
+                let program = entry[..end].trim();
+                if !program.is_empty() {
+                    commands.push(AllowedCommand {
+                        program: program.to_string(),
+                        args: split_list(&entry[end + 1..]).collect(),
+                    });
+                }
+            }
+            None => commands.extend(split_list(entry).map(|program| AllowedCommand {
+                program,
+                args: Vec::new(),
+            })),
+        }
+    }
+    commands
+}
+
+/// The program has to be listed by the host and every argument has to match one of the
+/// arguments the host allows for it. Entries ending in `*` match any argument starting
+/// with the rest, e.g. `--format=*`.
+pub(crate) fn check_command(
+    program: &str,
+    args: &[String],
+    allowed: &[AllowedCommand],
+) -> Result<()> {
+    if allowed.is_empty() {
+        bail!(
+            "Running commands is disabled, list the allowed programs in {}",
+            ALLOWED_COMMANDS_ENV
+        );
+    }
+    let Some(command) = allowed.iter().find(|command| command.program == program) else {
+        bail!(
+            "\"{}\" is not in the allowed programs of {}",
+            program,
+            ALLOWED_COMMANDS_ENV
+        );
+    };
+
+    for arg in args {
+        let permitted = command
+            .args
+            .iter()
+            .any(|entry| match entry.strip_suffix('*') {
+                Some(prefix) => arg.starts_with(prefix),
+                None => entry == arg,
+            });
+        if !permitted {
+            bail!(
+                "Argument \"{}\" is not allowed for \"{}\" in {}",
+                arg,
+                program,
+                ALLOWED_COMMANDS_ENV
+            );
+        }
+    }
+    Ok(())
+}
+
+fn truncated(mut output: Vec<u8>) -> String {
+    output.truncate(MAX_OUTPUT);
+    String::from_utf8_lossy(&output).into_owned()
+}
+
+/// Reads one byte more than is kept, so an output that was cut off can be told apart
+async fn read_capped(reader: Option<impl AsyncRead + Unpin>) -> std::io::Result<Vec<u8>> {
+    let mut output = Vec::new();
+    if let Some(reader) = reader {
+        reader
+            .take(MAX_OUTPUT as u64 + 1)
+            .read_to_end(&mut output)
+            .await?;
+    }
+    Ok(output)
+}
+
+/// Runs the program directly without a shell, so arguments are never interpolated.
+/// Returns `None` if the timeout elapsed. Cancelling the run is an error. The process is
+/// killed in both cases, and as soon as its stdout or stderr exceeds [`MAX_OUTPUT`].
+pub(crate) async fn run_sandboxed(
+    program: &str,
+    args: &[String],
+    timeout: Duration,
+    cancel: Option<CancellationToken>,
+) -> Result<Option<CommandOutput>> {
+    let mut command = tokio::process::Command::new(program);
+    command
+        .args(args)
+        .env_clear()
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    for key in INHERITED_ENV {
+        if let Ok(value) = std::env::var(key) {
+            command.env(key, value);
+        }
+    }
+
+    let mut child = command.spawn()?;
+    let stdout = read_capped(child.stdout.take());
+    let stderr = read_capped(child.stderr.take());
+    let run = async {
+        tokio::pin!(stdout, stderr);
+        let (mut out, mut err) = (None, None);
+        let mut killed = false;
+        while out.is_none() || err.is_none() {
+            tokio::select! {
+                read = &mut stdout, if out.is_none() => out = Some(read?),
+                read = &mut stderr, if err.is_none() => err = Some(read?),
+            }
+            // Nothing reads the pipe beyond the cap, so the process would block on it
+            let over_cap = [&out, &err]
+                .into_iter()
+                .flatten()
+                .any(|output| output.len() > MAX_OUTPUT);
+            if over_cap && !killed {
+                child.start_kill()?;
+                killed = true;
+            }
+        }
+        let status = child.wait().await?;
+        std::io::Result::Ok((status, out.unwrap_or_default(), err.unwrap_or_default()))
+    };
+
+    let cancel = cancel.unwrap_or_default();
+    // Returning early drops the child, which kills it
+    let (status, stdout, stderr) = tokio::select! {
+        biased;
+        _ = cancel.cancelled() => bail!("Execution was cancelled"),
+        output = tokio::time::timeout(timeout, run) => match output {
+            Ok(output) => output?,
+            Err(_) => return Ok(None),
+        },
+    };
+
+    Ok(Some(CommandOutput {
+        // Processes killed by a signal have no exit code
+        exit_code: status.code().map_or(-1, i64::from),
+        stdout: truncated(stdout),
+        stderr: truncated(stderr),
+    }))
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct RunCommandNode {}
+
+impl RunCommandNode {
+    pub fn new() -> Self {
+        RunCommandNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for RunCommandNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "run_command",
+            "Run Command",
+            "Runs a program without a shell and captures its output. Only programs listed in the FLOW_LIKE_ALLOWED_COMMANDS environment variable of the host can run, with the arguments the host allows for them",
+            "Utils/Command",
+        );
+        node.add_icon("/flow/icons/computer.svg");
+
+        node.set_scores(
+            NodeScores::new()
+                .set_privacy(4)
+                .set_security(2)
+                .set_performance(6)
+                .set_governance(3)
+                .set_reliability(6)
+                .set_cost(9)
+                .build(),
+        );
+        node.set_only_offline(true);
+        node.set_long_running(true);
+
+        node.add_input_pin(
+            "exec_in",
+            "Input",
+            "Execution trigger",
+            VariableType::Execution,
+        );
+
+        node.add_input_pin(
+            "program",
+            "Program",
+            "Name or path of the program, exactly as listed in FLOW_LIKE_ALLOWED_COMMANDS",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("")));
+
+        node.add_input_pin(
+            "args",
+            "Arguments",
+            "Passed to the program as they are, without shell expansion. Each has to be allowed for the program in FLOW_LIKE_ALLOWED_COMMANDS",
+            VariableType::String,
+        )
+        .set_value_type(ValueType::Array)
+        .set_default_value(Some(json!([])));
+
+        node.add_input_pin(
+            "timeout_ms",
+            "Timeout (ms)",
+            "The process is killed after this time",
+            VariableType::Integer,
+        )
+        .set_options(PinOptions::new().set_range((1., 3_600_000.)).build())
+        .set_default_value(Some(json!(30000)));
+
+        node.add_output_pin(
+            "exec_out",
+            "Output",
+            "Fires once the program exited, whatever its exit code",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "timeout",
+            "Timeout",
+            "The program did not exit in time and was killed",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "exit_code",
+            "Exit Code",
+            "Exit code of the program, -1 if it was terminated by a signal",
+            VariableType::Integer,
+        );
+
+        node.add_output_pin(
+            "success",
+            "Success",
+            "Whether the exit code is 0",
+            VariableType::Boolean,
+        );
+
+        node.add_output_pin(
+            "stdout",
+            "Stdout",
+            "Standard output, cut off after 1 MiB",
+            VariableType::String,
+        );
+
+        node.add_output_pin(
+            "stderr",
+            "Stderr",
+            "Standard error, cut off after 1 MiB",
+            VariableType::String,
+        );
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+        context.deactivate_exec_pin("timeout").await?;
+
+        let program: String = context.evaluate_pin("program").await?;
+        let args: Vec<String> = context.evaluate_pin("args").await?;
+        let timeout_ms: i64 = context.evaluate_pin("timeout_ms").await?;
+
+        let allowed = allowed_commands(std::env::var(ALLOWED_COMMANDS_ENV).ok().as_deref());
+        check_command(&program, &args, &allowed)?;
+
+        let timeout = Duration::from_millis(timeout_ms.clamp(1, 3_600_000) as u64);
+        let Some(output) =
+            run_sandboxed(&program, &args, timeout, context.get_cancellation_token()).await?
+        else {
+            context.log_message(
+                &format!(
+                    "\"{}\" was killed after {} ms",
+                    program,
+                    timeout.as_millis()
+                ),
+                LogLevel::Warn,
+            );
+            context.activate_exec_pin("timeout").await?;
+            return Ok(());
+        };
+
+        context
+            .set_pin_value("exit_code", json!(output.exit_code))
+            .await?;
+        context
+            .set_pin_value("success", json!(output.exit_code == 0))
+            .await?;
+        context
+            .set_pin_value("stdout", json!(output.stdout))
+            .await?;
+        context
+            .set_pin_value("stderr", json!(output.stderr))
+            .await?;
+        context.activate_exec_pin("exec_out").await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn test_programs_need_the_capability() {
+        assert!(allowed_commands(None).is_empty());
+        assert!(check_command("git", &[], &allowed_commands(None)).is_err());
+
+        let allowed = allowed_commands(Some(" git, /usr/bin/ffmpeg ,,"));
+        assert_eq!(
+            allowed
+                .iter()
+                .map(|command| command.program.as_str())
+                .collect::<Vec<_>>(),
+            vec!["git", "/usr/bin/ffmpeg"]
+        );
+        assert!(check_command("git", &[], &allowed).is_ok());
+        assert!(check_command("/usr/bin/ffmpeg", &[], &allowed).is_ok());
+        assert!(check_command("ffmpeg", &[], &allowed).is_err());
+        assert!(check_command("git; rm -rf /", &[], &allowed).is_err());
+    }
+
+    #[test]
+    fn test_arguments_come_from_the_host() {
+        let allowed = allowed_commands(Some(
+            "git:status,--short,--format=*; C:\\tools\\ffmpeg.exe:-y; echo",
+        ));
+        assert_eq!(
+            allowed,
+            vec![
+                AllowedCommand {
+                    program: "git".to_string(),
+                    args: strings(&["status", "--short", "--format=*"]),
+                },
+                AllowedCommand {
+                    program: "C:\\tools\\ffmpeg.exe".to_string(),
+                    args: strings(&["-y"]),
+                },
+                AllowedCommand {
+                    program: "echo".to_string(),
+                    args: Vec::new(),
+                },
+            ]
+        );
+
+        assert!(check_command("git", &strings(&["status", "--short"]), &allowed).is_ok());
+        assert!(check_command("git", &strings(&["--format=json"]), &allowed).is_ok());
+        assert!(check_command("git", &strings(&["--force"]), &allowed).is_err());
+        assert!(check_command("git", &strings(&["status", "&&", "reboot"]), &allowed).is_err());
+        assert!(check_command("C:\\tools\\ffmpeg.exe", &strings(&["-y"]), &allowed).is_ok());
+        // Listed without arguments means none are allowed
+        assert!(check_command("echo", &strings(&["hi"]), &allowed).is_err());
+        // The allowlist of one program does not carry over to another
+        assert!(check_command("echo", &strings(&["status"]), &allowed).is_err());
+    }
+
+    #[test]
+    fn test_flow_cannot_widen_the_argument_list() {
+        // The allowlist is host config only, the node has no input to extend it
+        let node = RunCommandNode::new().get_node();
+        assert!(node.get_pin_by_name("allowed_args").is_none());
+        let inputs: Vec<&str> = node
+            .pins
+            .values()
+            .filter(|pin| pin.pin_type == flow_like::flow::pin::PinType::Input)
+            .map(|pin| pin.name.as_str())
+            .collect();
+        for input in inputs {
+            assert!(
+                ["exec_in", "program", "args", "timeout_ms"].contains(&input),
+                "unexpected input {}",
+                input
+            );
+        }
+
+        // Arguments that look like wildcards are matched literally
+        let allowed = allowed_commands(Some("git:status"));
+        assert!(check_command("git", &strings(&["*"]), &allowed).is_err());
+        assert!(check_command("git", &strings(&["-c", "core.pager=sh"]), &allowed).is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_run_without_shell_interpolation() {
+        let args = strings(&["$HOME", "a; echo b"]);
+        let output = run_sandboxed("echo", &args, Duration::from_secs(5), None)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(output.exit_code, 0);
+        assert_eq!(output.stdout, "$HOME a; echo b\n");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_run_captures_exit_code_and_timeout() {
+        let failed = run_sandboxed(
+            "ls",
+            &strings(&["/does/not/exist"]),
+            Duration::from_secs(5),
+            None,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert_ne!(failed.exit_code, 0);
+        assert!(!failed.stderr.is_empty());
+
+        let started = std::time::Instant::now();
+        let timed_out = run_sandboxed("sleep", &strings(&["10"]), Duration::from_millis(100), None)
+            .await
+            .unwrap();
+        assert!(timed_out.is_none());
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_endless_output_is_cut_off_and_kills_the_process() {
+        let started = std::time::Instant::now();
+        let output = run_sandboxed("yes", &[], Duration::from_secs(30), None)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(output.stdout.len(), MAX_OUTPUT);
+        assert!(output.stdout.starts_with("y\ny\n"));
+        assert_eq!(output.exit_code, -1);
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_cancelling_the_run_kills_the_process() {
+        let token = CancellationToken::new();
+        let cancel = token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            cancel.cancel();
+        });
+
+        let started = std::time::Instant::now();
+        let result = run_sandboxed(
+            "sleep",
+            &strings(&["10"]),
+            Duration::from_secs(30),
+            Some(token),
+        )
+        .await;
+        assert!(result.is_err());
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+}
@@ -1 +1,2 @@
 pub mod buffered_reader;
+pub mod stream_read;
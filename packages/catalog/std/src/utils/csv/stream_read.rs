@@ -0,0 +1,580 @@
+#[cfg(feature = "execute")]
+use flow_like::flow::execution::{LogLevel, internal_node::InternalNode};
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic},
+    pin::{PinOptions, ValueType},
+    variable::VariableType,
+};
+use flow_like_catalog_core::FlowPath;
+#[cfg(feature = "execute")]
+use flow_like_storage::object_store::buffered::BufReader;
+#[cfg(feature = "execute")]
+use flow_like_types::{Value, json::Map, tokio::io::AsyncRead};
+use flow_like_types::{async_trait, json::json};
+
+/// Bytes read from the start of the file to detect the delimiter and header row.
+const SAMPLE_SIZE: u64 = 64 * 1024;
+/// Number of sampled records considered for auto detection.
+const SAMPLE_RECORDS: usize = 20;
+const DELIMITER_CANDIDATES: [u8; 4] = [b',', b';', b'\t', b'|'];
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Parses the delimiter pin. `None` means the delimiter should be detected.
+pub fn parse_delimiter(delimiter: &str) -> flow_like_types::Result<Option<u8>> {
+    match delimiter {
+        "" | "auto" | "Auto" => Ok(None),
+        "\\t" | "\t" | "tab" | "Tab" => Ok(Some(b'\t')),
+        other if other.len() == 1 && other.is_ascii() => Ok(Some(other.as_bytes()[0])),
+        other => Err(flow_like_types::anyhow!(
+            "Invalid delimiter '{}', expected a single ASCII character or 'auto'",
+            other
+        )),
+    }
+}
+
+/// Splits a sample into records, honoring quoted fields with embedded delimiters,
+/// escaped quotes and line breaks. If the sample is not `complete` (it was cut off
+/// before the end of the file) the trailing partial record is dropped.
+pub fn sample_records(sample: &[u8], delimiter: u8, complete: bool) -> Vec<Vec<String>> {
+    let sample = sample.strip_prefix(UTF8_BOM).unwrap_or(sample);
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = Vec::new();
+    let mut in_quotes = false;
+    let mut bytes = sample.iter().copied().peekable();
+
+    while let Some(byte) = bytes.next() {
+        if in_quotes {
+            match byte {
+                b'"' if bytes.peek() == Some(&b'"') => {
+                    bytes.next();
+                    field.push(b'"');
+                }
+                b'"' => in_quotes = false,
+                byte => field.push(byte),
+            }
+            continue;
+        }
+
+        match byte {
+            b'"' => in_quotes = true,
+            b'\r' => {}
+            b'\n' => {
+                record.push(String::from_utf8_lossy(&field).to_string());
+                field.clear();
+                records.push(std::mem::take(&mut record));
+                if records.len() >= SAMPLE_RECORDS {
+                    return records;
+                }
+            }
+            byte if byte == delimiter => {
+                record.push(String::from_utf8_lossy(&field).to_string());
+                field.clear();
+            }
+            byte => field.push(byte),
+        }
+    }
+
+    if complete && !in_quotes && (!field.is_empty() || !record.is_empty()) {
+        record.push(String::from_utf8_lossy(&field).to_string());
+        records.push(record);
+    }
+
+    records
+}
+
+/// Picks the candidate delimiter that splits the sampled records into the same,
+/// largest number of fields. Falls back to a comma.
+pub fn detect_delimiter(sample: &[u8], complete: bool) -> u8 {
+    let mut best = (b',', false, 1);
+
+    for candidate in DELIMITER_CANDIDATES {
+        let records = sample_records(sample, candidate, complete);
+        let counts: Vec<usize> = records
+            .iter()
+            .filter(|record| !(record.len() == 1 && record[0].is_empty()))
+            .map(|record| record.len())
+            .collect();
+
+        let Some(&first) = counts.first() else {
+            continue;
+        };
+        let consistent = counts.iter().all(|count| *count == first);
+
+        if first > 1 && (consistent, first) > (best.1, best.2) {
+            best = (candidate, consistent, first);
+        }
+    }
+
+    best.0
+}
+
+/// A first row is treated as a header if every value is present, unique and not numeric.
+pub fn detect_header(records: &[Vec<String>]) -> bool {
+    let Some(first) = records.first() else {
+        return true;
+    };
+
+    let mut seen = std::collections::HashSet::new();
+    first.iter().all(|value| {
+        let value = value.trim();
+        !value.is_empty() && value.parse::<f64>().is_err() && seen.insert(value)
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CsvStreamOptions {
+    pub delimiter: u8,
+    pub has_header: bool,
+    pub batch_size: usize,
+}
+
+/// Reads a CSV source record by record and hands out rows in batches of JSON objects,
+/// keeping at most one batch in memory.
+#[cfg(feature = "execute")]
+pub struct CsvBatchReader<R> {
+    reader: csv_async::AsyncReader<R>,
+    headers: Vec<String>,
+    pending: Option<csv_async::ByteRecord>,
+    record: csv_async::ByteRecord,
+    batch_size: usize,
+}
+
+#[cfg(feature = "execute")]
+impl<R: AsyncRead + Unpin + Send> CsvBatchReader<R> {
+    pub async fn new(inner: R, options: CsvStreamOptions) -> flow_like_types::Result<Self> {
+        let mut reader = csv_async::AsyncReaderBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .delimiter(options.delimiter)
+            .buffer_capacity(1024 * 1024)
+            .create_reader(inner);
+
+        let mut first = csv_async::ByteRecord::new();
+        let mut headers = Vec::new();
+        let mut pending = None;
+
+        if reader.read_byte_record(&mut first).await? {
+            if options.has_header {
+                headers = first
+                    .iter()
+                    .map(|header| {
+                        let header = header.strip_prefix(UTF8_BOM).unwrap_or(header);
+                        String::from_utf8_lossy(header).trim().to_string()
+                    })
+                    .collect();
+            } else {
+                headers = (1..=first.len())
+                    .map(|index| format!("column_{}", index))
+                    .collect();
+                pending = Some(first);
+            }
+        }
+
+        Ok(Self {
+            reader,
+            headers,
+            pending,
+            record: csv_async::ByteRecord::new(),
+            batch_size: options.batch_size.max(1),
+        })
+    }
+
+    pub fn headers(&self) -> &[String] {
+        &self.headers
+    }
+
+    /// Returns the next batch of rows, or `None` once the source is exhausted.
+    pub async fn next_batch(&mut self) -> flow_like_types::Result<Option<Vec<Value>>> {
+        let mut batch = Vec::with_capacity(self.batch_size);
+
+        if let Some(record) = self.pending.take() {
+            batch.push(record_to_row(&mut self.headers, &record));
+        }
+
+        while batch.len() < self.batch_size {
+            if !self.reader.read_byte_record(&mut self.record).await? {
+                break;
+            }
+            batch.push(record_to_row(&mut self.headers, &self.record));
+        }
+
+        if batch.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(batch))
+    }
+}
+
+#[cfg(feature = "execute")]
+fn record_to_row(headers: &mut Vec<String>, record: &csv_async::ByteRecord) -> Value {
+    while headers.len() < record.len() {
+        headers.push(format!("column_{}", headers.len() + 1));
+    }
+
+    let row: Map<String, Value> = headers
+        .iter()
+        .zip(record.iter())
+        .map(|(header, value)| {
+            (
+                header.clone(),
+                json!(String::from_utf8_lossy(value).to_string()),
+            )
+        })
+        .collect();
+
+    Value::Object(row)
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct CsvStreamReadNode {}
+
+impl CsvStreamReadNode {
+    pub fn new() -> Self {
+        CsvStreamReadNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for CsvStreamReadNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "csv_stream_read",
+            "Stream Read CSV",
+            "Streams a CSV file in batches without loading it into memory. Delimiter and header row are detected automatically unless overridden.",
+            "Utils/CSV",
+        );
+        node.add_icon("/flow/icons/for-each.svg");
+        node.set_long_running(true);
+
+        node.add_input_pin(
+            "exec_in",
+            "Input",
+            "Initiate Execution",
+            VariableType::Execution,
+        );
+
+        node.add_input_pin("csv", "CSV", "CSV Path", VariableType::Struct)
+            .set_schema::<FlowPath>()
+            .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node.add_input_pin(
+            "batch_size",
+            "Batch Size",
+            "Number of rows per batch",
+            VariableType::Integer,
+        )
+        .set_default_value(Some(json!(1000)));
+
+        node.add_input_pin(
+            "delimiter",
+            "Delimiter",
+            "Field delimiter, 'auto' to detect it or '\\t' for tabs",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("auto")));
+
+        node.add_input_pin(
+            "header",
+            "Header Row",
+            "Whether the first row contains column names",
+            VariableType::String,
+        )
+        .set_options(
+            PinOptions::new()
+                .set_valid_values(vec![
+                    "Auto".to_string(),
+                    "Yes".to_string(),
+                    "No".to_string(),
+                ])
+                .build(),
+        )
+        .set_default_value(Some(json!("Auto")));
+
+        node.add_output_pin(
+            "on_batch",
+            "For Batch",
+            "Executes for each batch of rows",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "batch",
+            "Batch",
+            "Rows of the current batch, keyed by column name",
+            VariableType::Struct,
+        )
+        .set_value_type(ValueType::Array);
+
+        node.add_output_pin(
+            "batch_index",
+            "Batch Index",
+            "Zero-based index of the current batch",
+            VariableType::Integer,
+        );
+
+        node.add_output_pin(
+            "done",
+            "Done",
+            "Executes once the file is exhausted",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "columns",
+            "Columns",
+            "Column names used for the rows",
+            VariableType::String,
+        )
+        .set_value_type(ValueType::Array);
+
+        node.add_output_pin(
+            "total_rows",
+            "Total Rows",
+            "Number of rows that were read",
+            VariableType::Integer,
+        );
+
+        node
+    }
+
+    #[cfg(feature = "execute")]
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("done").await?;
+
+        let batch_size: i64 = context.evaluate_pin("batch_size").await?;
+        let delimiter: String = context.evaluate_pin("delimiter").await?;
+        let delimiter = parse_delimiter(&delimiter)?;
+        let header: String = context.evaluate_pin("header").await?;
+
+        let csv_path: FlowPath = context.evaluate_pin("csv").await?;
+        let runtime = csv_path.to_runtime(context).await?;
+        let store = runtime.store.as_generic();
+        let meta = store.head(&runtime.path).await?;
+
+        let (delimiter, has_header) = match (delimiter, header.as_str()) {
+            (Some(delimiter), "Yes") => (delimiter, true),
+            (Some(delimiter), "No") => (delimiter, false),
+            (delimiter, header) => {
+                let end = meta.size.min(SAMPLE_SIZE);
+                let complete = end == meta.size;
+                let sample = store.get_range(&runtime.path, 0..end).await?;
+                let delimiter = delimiter.unwrap_or_else(|| detect_delimiter(&sample, complete));
+                let has_header = match header {
+                    "Yes" => true,
+                    "No" => false,
+                    _ => detect_header(&sample_records(&sample, delimiter, complete)),
+                };
+                (delimiter, has_header)
+            }
+        };
+
+        context.log_message(
+            &format!(
+                "Streaming CSV with delimiter {:?}, header row: {}",
+                delimiter as char, has_header
+            ),
+            LogLevel::Debug,
+        );
+
+        let options = CsvStreamOptions {
+            delimiter,
+            has_header,
+            batch_size: batch_size.max(1) as usize,
+        };
+        let source = BufReader::new(store.clone(), &meta);
+        let mut reader = CsvBatchReader::new(source, options).await?;
+
+        let on_batch = context.get_pin_by_name("on_batch").await?;
+        context.activate_exec_pin_ref(&on_batch).await?;
+        let flow = on_batch.get_connected_nodes();
+
+        let mut total_rows: u64 = 0;
+        let mut batch_index: u64 = 0;
+
+        while let Some(batch) = reader.next_batch().await? {
+            context.check_cancelled()?;
+            total_rows += batch.len() as u64;
+
+            context.set_pin_value("batch", json!(batch)).await?;
+            context
+                .set_pin_value("batch_index", json!(batch_index))
+                .await?;
+
+            for node in &flow {
+                let mut sub_context = context.create_sub_context(node).await;
+                let run = InternalNode::trigger(&mut sub_context, &mut None, true).await;
+                sub_context.end_trace();
+                context.push_sub_context(&mut sub_context);
+
+                if let Err(error) = run {
+                    context.log_message(&format!("Error: {:?}", error), LogLevel::Error);
+                }
+            }
+
+            batch_index += 1;
+        }
+
+        context.deactivate_exec_pin_ref(&on_batch).await?;
+
+        context.log_message(
+            &format!(
+                "CSV stream completed: {} rows in {} batches",
+                total_rows, batch_index
+            ),
+            LogLevel::Info,
+        );
+
+        context
+            .set_pin_value("columns", json!(reader.headers()))
+            .await?;
+        context
+            .set_pin_value("total_rows", json!(total_rows))
+            .await?;
+        context.activate_exec_pin("done").await?;
+
+        Ok(())
+    }
+
+    #[cfg(not(feature = "execute"))]
+    async fn run(&self, _context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        Err(flow_like_types::anyhow!(
+            "This feature requires the 'execute' feature"
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRICKY: &[u8] = b"\xEF\xBB\xBFid;comment;amount\r\n\
+1;\"Hello; \"\"quoted\"\" world\";10.5\r\n\
+2;\"line one\nline two\";3\r\n\
+3;;0\r\n";
+
+    #[test]
+    fn test_parse_delimiter() {
+        assert_eq!(parse_delimiter("auto").unwrap(), None);
+        assert_eq!(parse_delimiter("").unwrap(), None);
+        assert_eq!(parse_delimiter("\\t").unwrap(), Some(b'\t'));
+        assert_eq!(parse_delimiter(";").unwrap(), Some(b';'));
+        assert!(parse_delimiter(";;").is_err());
+    }
+
+    #[test]
+    fn test_detect_delimiter() {
+        assert_eq!(detect_delimiter(b"a,b,c\n1,2,3\n", true), b',');
+        assert_eq!(detect_delimiter(b"a\tb\n1\t2\n", true), b'\t');
+        assert_eq!(detect_delimiter(b"a|b|c\n1|2|3\n", true), b'|');
+        assert_eq!(detect_delimiter(TRICKY, true), b';');
+        assert_eq!(detect_delimiter(b"single\nvalue\n", true), b',');
+    }
+
+    #[test]
+    fn test_detect_delimiter_ignores_truncated_record() {
+        let sample = b"a;b;c\n1;2;3\n4;\"5,6,7,8,9";
+        assert_eq!(detect_delimiter(sample, false), b';');
+    }
+
+    #[test]
+    fn test_sample_records_handles_quotes() {
+        let records = sample_records(TRICKY, b';', true);
+        assert_eq!(records.len(), 4);
+        assert_eq!(records[0], vec!["id", "comment", "amount"]);
+        assert_eq!(records[1][1], "Hello; \"quoted\" world");
+        assert_eq!(records[2][1], "line one\nline two");
+        assert_eq!(records[3], vec!["3", "", "0"]);
+    }
+
+    #[test]
+    fn test_detect_header() {
+        let with_header = sample_records(b"name,age\nAlice,30\n", b',', true);
+        assert!(detect_header(&with_header));
+
+        let numeric = sample_records(b"1,2\n3,4\n", b',', true);
+        assert!(!detect_header(&numeric));
+
+        let duplicate = sample_records(b"a,a\nb,c\n", b',', true);
+        assert!(!detect_header(&duplicate));
+    }
+
+    #[cfg(feature = "execute")]
+    async fn read_all(data: Vec<u8>, options: CsvStreamOptions) -> (Vec<String>, Vec<Vec<Value>>) {
+        let mut reader = CsvBatchReader::new(std::io::Cursor::new(data), options)
+            .await
+            .unwrap();
+        let mut batches = Vec::new();
+        while let Some(batch) = reader.next_batch().await.unwrap() {
+            batches.push(batch);
+        }
+        (reader.headers().to_vec(), batches)
+    }
+
+    #[cfg(feature = "execute")]
+    #[tokio::test]
+    async fn test_large_csv_is_read_in_batches() {
+        let rows = 250_003;
+        let mut data = b"id,name,score\n".to_vec();
+        for index in 0..rows {
+            data.extend_from_slice(
+                format!("{},\"user, {}\",{}\n", index, index, index % 97).as_bytes(),
+            );
+        }
+
+        let options = CsvStreamOptions {
+            delimiter: detect_delimiter(&data[..SAMPLE_SIZE as usize], false),
+            has_header: detect_header(&sample_records(&data[..SAMPLE_SIZE as usize], b',', false)),
+            batch_size: 10_000,
+        };
+        assert_eq!(options.delimiter, b',');
+        assert!(options.has_header);
+
+        let (headers, batches) = read_all(data, options).await;
+        assert_eq!(headers, vec!["id", "name", "score"]);
+        assert_eq!(batches.len(), 26);
+        assert!(batches[..25].iter().all(|batch| batch.len() == 10_000));
+        assert_eq!(batches[25].len(), 3);
+
+        let last = batches.last().unwrap().last().unwrap();
+        assert_eq!(last["id"], json!("250002"));
+        assert_eq!(last["name"], json!("user, 250002"));
+    }
+
+    #[cfg(feature = "execute")]
+    #[tokio::test]
+    async fn test_tricky_quoting() {
+        let options = CsvStreamOptions {
+            delimiter: b';',
+            has_header: true,
+            batch_size: 2,
+        };
+
+        let (headers, batches) = read_all(TRICKY.to_vec(), options).await;
+        assert_eq!(headers, vec!["id", "comment", "amount"]);
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[0][0]["comment"], json!("Hello; \"quoted\" world"));
+        assert_eq!(batches[0][1]["comment"], json!("line one\nline two"));
+        assert_eq!(batches[1][0]["comment"], json!(""));
+        assert_eq!(batches[1][0]["amount"], json!("0"));
+    }
+
+    #[cfg(feature = "execute")]
+    #[tokio::test]
+    async fn test_headerless_csv_generates_column_names() {
+        let options = CsvStreamOptions {
+            delimiter: b',',
+            has_header: false,
+            batch_size: 10,
+        };
+
+        let (headers, batches) = read_all(b"1,2\n3,4,5\n".to_vec(), options).await;
+        assert_eq!(headers, vec!["column_1", "column_2", "column_3"]);
+        assert_eq!(batches[0].len(), 2);
+        assert_eq!(batches[0][0]["column_1"], json!("1"));
+        assert_eq!(batches[0][1]["column_3"], json!("5"));
+    }
+}
@@ -1,7 +1,424 @@
+pub mod add;
 pub mod diff;
+pub mod diff_unit;
 pub mod duration;
 pub mod format;
+pub mod format_zoned;
 pub mod now;
 pub mod parse;
+pub mod parse_zoned;
 pub mod to_date;
 pub mod to_time;
+
+use chrono::{
+    DateTime, Datelike, Days, Duration, LocalResult, Months, NaiveDate, NaiveDateTime, NaiveTime,
+    Offset, TimeZone, Utc,
+    format::{Item, StrftimeItems},
+};
+use chrono_tz::Tz;
+use std::fmt::Write;
+
+/// Unit of [`add_to_date`] and [`diff_dates`]. Up to hours a unit is a fixed amount of
+/// time, from days on it follows the calendar of the timezone, so a day can have 23 or
+/// 25 hours around a DST change.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DateUnit {
+    Milliseconds,
+    Seconds,
+    Minutes,
+    Hours,
+    Days,
+    Weeks,
+    Months,
+    Years,
+}
+
+impl DateUnit {
+    pub const NAMES: [&'static str; 8] = [
+        "Milliseconds",
+        "Seconds",
+        "Minutes",
+        "Hours",
+        "Days",
+        "Weeks",
+        "Months",
+        "Years",
+    ];
+
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name.to_lowercase().as_str() {
+            "milliseconds" => Ok(Self::Milliseconds),
+            "seconds" => Ok(Self::Seconds),
+            "minutes" => Ok(Self::Minutes),
+            "hours" => Ok(Self::Hours),
+            "days" => Ok(Self::Days),
+            "weeks" => Ok(Self::Weeks),
+            "months" => Ok(Self::Months),
+            "years" => Ok(Self::Years),
+            _ => Err(format!("Unknown unit \"{}\"", name)),
+        }
+    }
+
+    fn fixed_millis(self) -> Option<i64> {
+        match self {
+            Self::Milliseconds => Some(1),
+            Self::Seconds => Some(1_000),
+            Self::Minutes => Some(60_000),
+            Self::Hours => Some(3_600_000),
+            _ => None,
+        }
+    }
+}
+
+/// IANA timezone like `Europe/Berlin`, empty means UTC.
+pub fn parse_timezone(name: &str) -> Result<Tz, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Ok(Tz::UTC);
+    }
+    name.parse::<Tz>()
+        .map_err(|_| format!("Unknown timezone \"{}\"", name))
+}
+
+/// Maps a wall clock time of `tz` to an instant. Times repeated when the clocks go back
+/// take the earlier instant, times skipped when they go forward are moved past the gap.
+pub fn resolve_local(tz: Tz, local: NaiveDateTime) -> Option<DateTime<Utc>> {
+    match tz.from_local_datetime(&local) {
+        LocalResult::Single(date) => Some(date.with_timezone(&Utc)),
+        LocalResult::Ambiguous(earliest, _) => Some(earliest.with_timezone(&Utc)),
+        LocalResult::None => {
+            // The offset before the gap, a skipped 02:30 then lands on 03:30
+            let before = local.checked_sub_signed(Duration::hours(6))?;
+            let offset = tz.offset_from_local_datetime(&before).earliest()?;
+            let seconds = offset.fix().local_minus_utc() as i64;
+            let utc = local.checked_sub_signed(Duration::seconds(seconds))?;
+            Some(utc.and_utc())
+        }
+    }
+}
+
+fn add_calendar(date: DateTime<Utc>, amount: i64, unit: DateUnit, tz: Tz) -> Option<DateTime<Utc>> {
+    let local = date.with_timezone(&tz).naive_local();
+    let magnitude = amount.unsigned_abs();
+    let shifted = match unit {
+        DateUnit::Days | DateUnit::Weeks => {
+            let days = if unit == DateUnit::Weeks {
+                magnitude.checked_mul(7)?
+            } else {
+                magnitude
+            };
+            if amount >= 0 {
+                local.checked_add_days(Days::new(days))?
+            } else {
+                local.checked_sub_days(Days::new(days))?
+            }
+        }
+        _ => {
+            let months = if unit == DateUnit::Years {
+                magnitude.checked_mul(12)?
+            } else {
+                magnitude
+            };
+            // Clamped to the end of shorter months, Jan 31 + 1 month is Feb 28 or 29
+            let months = Months::new(u32::try_from(months).ok()?);
+            if amount >= 0 {
+                local.checked_add_months(months)?
+            } else {
+                local.checked_sub_months(months)?
+            }
+        }
+    };
+    resolve_local(tz, shifted)
+}
+
+/// Adds `amount` units to `date`. Calendar units keep the wall clock time in `tz`, so one
+/// day after noon is noon again even across a DST change, unlike 24 hours.
+pub fn add_to_date(
+    date: DateTime<Utc>,
+    amount: i64,
+    unit: DateUnit,
+    tz: Tz,
+) -> Result<DateTime<Utc>, String> {
+    let result = match unit.fixed_millis() {
+        Some(millis) => amount
+            .checked_mul(millis)
+            .and_then(Duration::try_milliseconds)
+            .and_then(|duration| date.checked_add_signed(duration)),
+        None => add_calendar(date, amount, unit, tz),
+    };
+    result.ok_or_else(|| "The resulting date is out of range".to_string())
+}
+
+/// Complete units from `start` to `end`, negative if `end` is earlier. Calendar units
+/// count how often the unit can be added to `start` with [`add_to_date`] without
+/// passing `end`.
+pub fn diff_dates(
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    unit: DateUnit,
+    tz: Tz,
+) -> Result<i64, String> {
+    if let Some(millis) = unit.fixed_millis() {
+        return Ok((end - start).num_milliseconds() / millis);
+    }
+
+    let (step_unit, divisor) = match unit {
+        DateUnit::Weeks => (DateUnit::Days, 7),
+        DateUnit::Years => (DateUnit::Months, 12),
+        unit => (unit, 1),
+    };
+
+    let start_local = start.with_timezone(&tz).naive_local();
+    let end_local = end.with_timezone(&tz).naive_local();
+    // Off by at most one, corrected below
+    let mut count = if step_unit == DateUnit::Days {
+        (end_local.date() - start_local.date()).num_days()
+    } else {
+        (end_local.year() as i64 - start_local.year() as i64) * 12
+            + (end_local.month() as i64 - start_local.month() as i64)
+    };
+
+    let step = |count: i64| add_to_date(start, count, step_unit, tz);
+    if end >= start {
+        while count > 0 && step(count)? > end {
+            count -= 1;
+        }
+        while step(count + 1)? <= end {
+            count += 1;
+        }
+    } else {
+        while count < 0 && step(count)? < end {
+            count += 1;
+        }
+        while step(count - 1)? >= end {
+            count -= 1;
+        }
+    }
+
+    Ok(count / divisor)
+}
+
+fn check_pattern(pattern: &str) -> Result<Vec<Item<'_>>, String> {
+    let items: Vec<Item> = StrftimeItems::new(pattern).collect();
+    if items.contains(&Item::Error) {
+        return Err(format!("Invalid format pattern \"{}\"", pattern));
+    }
+    Ok(items)
+}
+
+/// Formats `date` as wall clock time of `tz` with a strftime pattern like
+/// `%Y-%m-%d %H:%M %Z`, or `rfc3339` and `rfc2822`.
+pub fn format_date(date: DateTime<Utc>, pattern: &str, tz: Tz) -> Result<String, String> {
+    let local = date.with_timezone(&tz);
+    match pattern.trim().to_lowercase().as_str() {
+        "" | "rfc3339" => return Ok(local.to_rfc3339()),
+        "rfc2822" => return Ok(local.to_rfc2822()),
+        _ => {}
+    }
+
+    let items = check_pattern(pattern)?;
+    let mut formatted = String::new();
+    // Fails instead of panicking if the pattern needs fields the date does not have
+    write!(formatted, "{}", local.format_with_items(items.iter()))
+        .map_err(|_| format!("The date can not be formatted with \"{}\"", pattern))?;
+    Ok(formatted)
+}
+
+/// Parses `input` with a strftime pattern, RFC 3339 or RFC 2822 if the pattern is empty.
+/// Input without an offset is read as wall clock time of `tz`.
+pub fn parse_date(input: &str, pattern: &str, tz: Tz) -> Result<DateTime<Utc>, String> {
+    let input = input.trim();
+    if pattern.trim().is_empty() {
+        return DateTime::parse_from_rfc3339(input)
+            .or_else(|_| DateTime::parse_from_rfc2822(input))
+            .map(|date| date.with_timezone(&Utc))
+            .map_err(|error| format!("\"{}\" is neither RFC 3339 nor RFC 2822: {}", input, error));
+    }
+
+    check_pattern(pattern)?;
+    if let Ok(date) = DateTime::parse_from_str(input, pattern) {
+        return Ok(date.with_timezone(&Utc));
+    }
+    let local = match NaiveDateTime::parse_from_str(input, pattern) {
+        Ok(local) => local,
+        Err(error) => match NaiveDate::parse_from_str(input, pattern) {
+            Ok(date) => date.and_time(NaiveTime::MIN),
+            Err(_) => {
+                return Err(format!(
+                    "Could not parse \"{}\" with \"{}\": {}",
+                    input, pattern, error
+                ));
+            }
+        },
+    };
+    resolve_local(tz, local).ok_or_else(|| format!("\"{}\" is out of range", input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(rfc3339: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(rfc3339)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn new_york() -> Tz {
+        parse_timezone("America/New_York").unwrap()
+    }
+
+    #[test]
+    fn test_day_and_24_hours_differ_across_dst() {
+        // Clocks in New York go forward on 2024-03-10 at 02:00
+        let noon = utc("2024-03-09T12:00:00-05:00");
+
+        let day = add_to_date(noon, 1, DateUnit::Days, new_york()).unwrap();
+        assert_eq!(day, utc("2024-03-10T12:00:00-04:00"));
+
+        let hours = add_to_date(noon, 24, DateUnit::Hours, new_york()).unwrap();
+        assert_eq!(hours, utc("2024-03-10T13:00:00-04:00"));
+
+        assert_eq!(diff_dates(noon, day, DateUnit::Hours, new_york()), Ok(23));
+        assert_eq!(diff_dates(noon, day, DateUnit::Days, new_york()), Ok(1));
+        assert_eq!(diff_dates(day, noon, DateUnit::Days, new_york()), Ok(-1));
+
+        // In UTC there is no DST, a day is 24 hours
+        let tz = parse_timezone("").unwrap();
+        assert_eq!(add_to_date(noon, 1, DateUnit::Days, tz).unwrap(), hours);
+    }
+
+    #[test]
+    fn test_skipped_and_repeated_wall_clock_times() {
+        // 02:30 does not exist on 2024-03-10, the result moves past the gap
+        let night = utc("2024-03-09T02:30:00-05:00");
+        let skipped = add_to_date(night, 1, DateUnit::Days, new_york()).unwrap();
+        assert_eq!(skipped, utc("2024-03-10T03:30:00-04:00"));
+
+        // 01:30 happens twice on 2024-11-03, the earlier one is used
+        let night = utc("2024-11-02T01:30:00-04:00");
+        let repeated = add_to_date(night, 1, DateUnit::Days, new_york()).unwrap();
+        assert_eq!(repeated, utc("2024-11-03T01:30:00-04:00"));
+        assert_eq!(
+            diff_dates(night, repeated, DateUnit::Hours, new_york()),
+            Ok(24)
+        );
+
+        let fall_back = utc("2024-11-02T12:00:00-04:00");
+        let next_day = add_to_date(fall_back, 1, DateUnit::Days, new_york()).unwrap();
+        assert_eq!(next_day, utc("2024-11-03T12:00:00-05:00"));
+        assert_eq!(
+            diff_dates(fall_back, next_day, DateUnit::Hours, new_york()),
+            Ok(25)
+        );
+    }
+
+    #[test]
+    fn test_leap_year_february() {
+        let tz = Tz::UTC;
+        let end_of_january = utc("2024-01-31T08:00:00Z");
+        let february = add_to_date(end_of_january, 1, DateUnit::Months, tz).unwrap();
+        assert_eq!(february, utc("2024-02-29T08:00:00Z"));
+        assert_eq!(
+            add_to_date(utc("2023-01-31T08:00:00Z"), 1, DateUnit::Months, tz).unwrap(),
+            utc("2023-02-28T08:00:00Z")
+        );
+        assert_eq!(
+            add_to_date(february, 1, DateUnit::Years, tz).unwrap(),
+            utc("2025-02-28T08:00:00Z")
+        );
+        assert_eq!(
+            add_to_date(february, -4, DateUnit::Years, tz).unwrap(),
+            utc("2020-02-29T08:00:00Z")
+        );
+
+        assert_eq!(
+            diff_dates(
+                utc("2024-02-28T00:00:00Z"),
+                utc("2024-03-01T00:00:00Z"),
+                DateUnit::Days,
+                tz
+            ),
+            Ok(2)
+        );
+        assert_eq!(
+            diff_dates(end_of_january, february, DateUnit::Months, tz),
+            Ok(1)
+        );
+        assert_eq!(
+            diff_dates(
+                utc("2024-02-29T00:00:00Z"),
+                utc("2025-02-28T00:00:00Z"),
+                DateUnit::Years,
+                tz
+            ),
+            Ok(1)
+        );
+        assert_eq!(
+            diff_dates(
+                utc("2024-01-01T00:00:00Z"),
+                utc("2024-01-14T23:59:59Z"),
+                DateUnit::Weeks,
+                tz
+            ),
+            Ok(1)
+        );
+    }
+
+    #[test]
+    fn test_format_and_parse_in_timezone() {
+        let berlin = parse_timezone("Europe/Berlin").unwrap();
+        let summer = utc("2024-07-01T10:00:00Z");
+        assert_eq!(
+            format_date(summer, "%Y-%m-%d %H:%M %Z", berlin).unwrap(),
+            "2024-07-01 12:00 CEST"
+        );
+        assert_eq!(
+            format_date(summer, "rfc3339", berlin).unwrap(),
+            "2024-07-01T12:00:00+02:00"
+        );
+        assert_eq!(
+            parse_date("2024-07-01 12:00", "%Y-%m-%d %H:%M", berlin).unwrap(),
+            summer
+        );
+        assert_eq!(
+            parse_date("2024-02-29", "%Y-%m-%d", berlin).unwrap(),
+            utc("2024-02-29T00:00:00+01:00")
+        );
+
+        // An offset in the input wins over the timezone
+        assert_eq!(
+            parse_date("2024-01-01 10:00 +0200", "%Y-%m-%d %H:%M %z", berlin).unwrap(),
+            utc("2024-01-01T08:00:00Z")
+        );
+        assert_eq!(
+            parse_date("2024-07-01T12:00:00+02:00", "", Tz::UTC).unwrap(),
+            summer
+        );
+
+        // A time skipped by the DST change is moved past the gap
+        assert_eq!(
+            parse_date("2024-03-10 02:30", "%Y-%m-%d %H:%M", new_york()).unwrap(),
+            utc("2024-03-10T03:30:00-04:00")
+        );
+    }
+
+    #[test]
+    fn test_invalid_patterns_and_input() {
+        let date = utc("2024-07-01T10:00:00Z");
+        assert!(format_date(date, "%Y-%Q", Tz::UTC).is_err());
+        assert!(parse_date("2024-07-01", "%Y-%Q", Tz::UTC).is_err());
+        assert!(parse_date("yesterday", "%Y-%m-%d", Tz::UTC).is_err());
+        assert!(parse_date("2024-02-30", "%Y-%m-%d", Tz::UTC).is_err());
+        assert!(parse_date("not a date", "", Tz::UTC).is_err());
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        assert!(parse_timezone("Mars/Olympus").is_err());
+        assert!(DateUnit::parse("fortnights").is_err());
+        assert_eq!(DateUnit::parse("days"), Ok(DateUnit::Days));
+        assert!(add_to_date(Utc::now(), i64::MAX, DateUnit::Hours, Tz::UTC).is_err());
+        assert!(add_to_date(Utc::now(), i64::MAX, DateUnit::Years, Tz::UTC).is_err());
+    }
+}
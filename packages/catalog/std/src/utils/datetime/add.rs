@@ -0,0 +1,100 @@
+use super::{DateUnit, add_to_date, parse_timezone};
+use chrono::{DateTime, Utc};
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic},
+    pin::PinOptions,
+    variable::VariableType,
+};
+use flow_like_types::{async_trait, json::json};
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct DateAddNode {}
+
+impl DateAddNode {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for DateAddNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "utils_datetime_add",
+            "Add To Date",
+            "Adds an amount of a unit to a date. Days, weeks, months and years follow the calendar of the timezone and keep the time of day across DST changes, hours and smaller units are fixed durations",
+            "Utils/DateTime",
+        );
+
+        node.add_input_pin("exec_in", "Input", "Trigger", VariableType::Execution);
+        node.add_input_pin("date", "Date", "Base date", VariableType::Date);
+        node.add_input_pin(
+            "amount",
+            "Amount",
+            "Amount to add, negative to subtract",
+            VariableType::Integer,
+        )
+        .set_default_value(Some(json!(1)));
+        node.add_input_pin("unit", "Unit", "Unit of the amount", VariableType::String)
+            .set_options(
+                PinOptions::new()
+                    .set_valid_values(DateUnit::NAMES.map(String::from).to_vec())
+                    .build(),
+            )
+            .set_default_value(Some(json!("Days")));
+        node.add_input_pin(
+            "timezone",
+            "Timezone",
+            "IANA timezone of the calendar, e.g. Europe/Berlin",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("UTC")));
+
+        node.add_output_pin("exec_out", "Output", "", VariableType::Execution);
+        node.add_output_pin("result", "Result", "Resulting date", VariableType::Date);
+        node.add_output_pin(
+            "error",
+            "Error",
+            "Triggers on an unknown unit or timezone, or if the result is out of range",
+            VariableType::Execution,
+        );
+        node.add_output_pin(
+            "error_message",
+            "Error Message",
+            "Why the amount could not be added",
+            VariableType::String,
+        );
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+        context.deactivate_exec_pin("error").await?;
+
+        let date: DateTime<Utc> = context.evaluate_pin("date").await?;
+        let amount: i64 = context.evaluate_pin("amount").await?;
+        let unit: String = context.evaluate_pin("unit").await?;
+        let timezone: String = context.evaluate_pin("timezone").await?;
+
+        let result = DateUnit::parse(&unit).and_then(|unit| {
+            let tz = parse_timezone(&timezone)?;
+            add_to_date(date, amount, unit, tz)
+        });
+
+        match result {
+            Ok(result) => {
+                context.set_pin_value("result", json!(result)).await?;
+                context.activate_exec_pin("exec_out").await?;
+            }
+            Err(error) => {
+                context.set_pin_value("error_message", json!(error)).await?;
+                context.activate_exec_pin("error").await?;
+            }
+        }
+
+        Ok(())
+    }
+}
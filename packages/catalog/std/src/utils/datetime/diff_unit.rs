@@ -0,0 +1,110 @@
+use super::{DateUnit, diff_dates, parse_timezone};
+use chrono::{DateTime, Utc};
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic},
+    pin::PinOptions,
+    variable::VariableType,
+};
+use flow_like_types::{async_trait, json::json};
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct DateDiffNode {}
+
+impl DateDiffNode {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for DateDiffNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "utils_datetime_diff_unit",
+            "Date Difference In Unit",
+            "Counts the complete units between two dates. Days, weeks, months and years are counted on the calendar of the timezone",
+            "Utils/DateTime",
+        );
+
+        node.add_input_pin("exec_in", "Input", "Trigger", VariableType::Execution);
+        node.add_input_pin("start", "Start", "Start date", VariableType::Date);
+        node.add_input_pin("end", "End", "End date", VariableType::Date);
+        node.add_input_pin("unit", "Unit", "Unit to count", VariableType::String)
+            .set_options(
+                PinOptions::new()
+                    .set_valid_values(DateUnit::NAMES.map(String::from).to_vec())
+                    .build(),
+            )
+            .set_default_value(Some(json!("Days")));
+        node.add_input_pin(
+            "timezone",
+            "Timezone",
+            "IANA timezone of the calendar, e.g. Europe/Berlin",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("UTC")));
+
+        node.add_output_pin("exec_out", "Output", "", VariableType::Execution);
+        node.add_output_pin(
+            "difference",
+            "Difference",
+            "Complete units from start to end, negative if end is earlier",
+            VariableType::Integer,
+        );
+        node.add_output_pin(
+            "milliseconds",
+            "Milliseconds",
+            "Exact difference in milliseconds",
+            VariableType::Integer,
+        );
+        node.add_output_pin(
+            "error",
+            "Error",
+            "Triggers on an unknown unit or timezone",
+            VariableType::Execution,
+        );
+        node.add_output_pin(
+            "error_message",
+            "Error Message",
+            "Why the difference could not be calculated",
+            VariableType::String,
+        );
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+        context.deactivate_exec_pin("error").await?;
+
+        let start: DateTime<Utc> = context.evaluate_pin("start").await?;
+        let end: DateTime<Utc> = context.evaluate_pin("end").await?;
+        let unit: String = context.evaluate_pin("unit").await?;
+        let timezone: String = context.evaluate_pin("timezone").await?;
+
+        let difference = DateUnit::parse(&unit).and_then(|unit| {
+            let tz = parse_timezone(&timezone)?;
+            diff_dates(start, end, unit, tz)
+        });
+
+        match difference {
+            Ok(difference) => {
+                context
+                    .set_pin_value("difference", json!(difference))
+                    .await?;
+                context
+                    .set_pin_value("milliseconds", json!((end - start).num_milliseconds()))
+                    .await?;
+                context.activate_exec_pin("exec_out").await?;
+            }
+            Err(error) => {
+                context.set_pin_value("error_message", json!(error)).await?;
+                context.activate_exec_pin("error").await?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+use super::{format_date, parse_timezone};
+use chrono::{DateTime, Utc};
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic},
+    variable::VariableType,
+};
+use flow_like_types::{async_trait, json::json};
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct DateFormatNode {}
+
+impl DateFormatNode {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for DateFormatNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "utils_datetime_format_zoned",
+            "Format Date In Timezone",
+            "Formats a date as the local time of a timezone",
+            "Utils/DateTime",
+        );
+
+        node.add_input_pin("exec_in", "Input", "Trigger", VariableType::Execution);
+        node.add_input_pin("date", "Date", "Date to format", VariableType::Date);
+        node.add_input_pin(
+            "pattern",
+            "Pattern",
+            "strftime pattern (e.g., '%Y-%m-%d %H:%M %Z'), 'rfc3339' or 'rfc2822'",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("%Y-%m-%d %H:%M:%S")));
+        node.add_input_pin(
+            "timezone",
+            "Timezone",
+            "IANA timezone, e.g. Europe/Berlin",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("UTC")));
+
+        node.add_output_pin("exec_out", "Output", "", VariableType::Execution);
+        node.add_output_pin(
+            "formatted",
+            "Formatted",
+            "Formatted string",
+            VariableType::String,
+        );
+        node.add_output_pin(
+            "error",
+            "Error",
+            "Triggers on an invalid pattern or unknown timezone",
+            VariableType::Execution,
+        );
+        node.add_output_pin(
+            "error_message",
+            "Error Message",
+            "Why the date could not be formatted",
+            VariableType::String,
+        );
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+        context.deactivate_exec_pin("error").await?;
+
+        let date: DateTime<Utc> = context.evaluate_pin("date").await?;
+        let pattern: String = context.evaluate_pin("pattern").await?;
+        let timezone: String = context.evaluate_pin("timezone").await?;
+
+        let formatted = parse_timezone(&timezone).and_then(|tz| format_date(date, &pattern, tz));
+
+        match formatted {
+            Ok(formatted) => {
+                context.set_pin_value("formatted", json!(formatted)).await?;
+                context.activate_exec_pin("exec_out").await?;
+            }
+            Err(error) => {
+                context.set_pin_value("error_message", json!(error)).await?;
+                context.activate_exec_pin("error").await?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,87 @@
+use super::{parse_date, parse_timezone};
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic},
+    variable::VariableType,
+};
+use flow_like_types::{async_trait, json::json};
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct DateParseNode {}
+
+impl DateParseNode {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for DateParseNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "utils_datetime_parse_zoned",
+            "Parse Date In Timezone",
+            "Parses a string with a pattern. Times without an offset are read as the local time of the timezone",
+            "Utils/DateTime",
+        );
+
+        node.add_input_pin("exec_in", "Input", "Trigger", VariableType::Execution);
+        node.add_input_pin("input", "Input", "String to parse", VariableType::String);
+        node.add_input_pin(
+            "pattern",
+            "Pattern",
+            "strftime pattern (e.g., '%d.%m.%Y %H:%M'). Leave empty for RFC 3339 or RFC 2822",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("%Y-%m-%d %H:%M:%S")));
+        node.add_input_pin(
+            "timezone",
+            "Timezone",
+            "IANA timezone, e.g. Europe/Berlin",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("UTC")));
+
+        node.add_output_pin("exec_out", "Output", "", VariableType::Execution);
+        node.add_output_pin("date", "Date", "Parsed date", VariableType::Date);
+        node.add_output_pin(
+            "error",
+            "Error",
+            "Triggers on an invalid pattern, unknown timezone or input that does not match",
+            VariableType::Execution,
+        );
+        node.add_output_pin(
+            "error_message",
+            "Error Message",
+            "Why the input could not be parsed",
+            VariableType::String,
+        );
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+        context.deactivate_exec_pin("error").await?;
+
+        let input: String = context.evaluate_pin("input").await?;
+        let pattern: String = context.evaluate_pin("pattern").await?;
+        let timezone: String = context.evaluate_pin("timezone").await?;
+
+        let parsed = parse_timezone(&timezone).and_then(|tz| parse_date(&input, &pattern, tz));
+
+        match parsed {
+            Ok(date) => {
+                context.set_pin_value("date", json!(date)).await?;
+                context.activate_exec_pin("exec_out").await?;
+            }
+            Err(error) => {
+                context.set_pin_value("error_message", json!(error)).await?;
+                context.activate_exec_pin("error").await?;
+            }
+        }
+
+        Ok(())
+    }
+}
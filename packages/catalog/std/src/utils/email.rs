@@ -0,0 +1,376 @@
+/// # Email
+/// Syntax validation and canonicalization of email addresses, with an optional MX lookup.
+/// The syntax check follows the dot-atom form of RFC 5322 and accepts internationalized
+/// addresses (RFC 6531). Quoted local parts and IP literal domains are rejected, since
+/// signup forms practically never see them legitimately.
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic},
+    variable::VariableType,
+};
+use flow_like_types::{
+    async_trait,
+    json::{Deserialize, Serialize, json},
+};
+use schemars::JsonSchema;
+
+const MAX_ADDRESS_LENGTH: usize = 254;
+const MAX_LOCAL_LENGTH: usize = 64;
+const MAX_DOMAIN_LENGTH: usize = 253;
+const MAX_LABEL_LENGTH: usize = 63;
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq)]
+pub struct EmailValidation {
+    pub valid: bool,
+    /// Canonical address with a lowercase domain, empty if the syntax is invalid
+    pub normalized: String,
+    pub local_part: String,
+    pub domain: String,
+    /// Why the address was rejected or flagged, empty for a valid address
+    pub reasons: Vec<String>,
+    /// Whether the domain accepts mail, `None` if not checked or the lookup failed
+    pub has_mx: Option<bool>,
+}
+
+fn is_atext(c: char) -> bool {
+    c.is_alphanumeric()
+        || "!#$%&'*+-/=?^_`{|}~".contains(c)
+        || (!c.is_ascii() && !c.is_control() && !c.is_whitespace())
+}
+
+fn check_local_part(local: &str, reasons: &mut Vec<String>) {
+    if local.is_empty() {
+        reasons.push("Local part before the @ is empty".to_string());
+        return;
+    }
+    if local.len() > MAX_LOCAL_LENGTH {
+        reasons.push(format!(
+            "Local part is longer than {} characters",
+            MAX_LOCAL_LENGTH
+        ));
+    }
+    if local.starts_with('"') {
+        reasons.push("Quoted local parts are not supported".to_string());
+        return;
+    }
+    if local.starts_with('.') || local.ends_with('.') || local.contains("..") {
+        reasons.push("Local part has a leading, trailing or repeated dot".to_string());
+    }
+    if let Some(c) = local.chars().find(|&c| c != '.' && !is_atext(c)) {
+        reasons.push(format!("Local part contains the invalid character '{}'", c));
+    }
+}
+
+fn check_domain(domain: &str, reasons: &mut Vec<String>) {
+    if domain.is_empty() {
+        reasons.push("Domain after the @ is empty".to_string());
+        return;
+    }
+    if domain.starts_with('[') {
+        reasons.push("IP address domains are not supported".to_string());
+        return;
+    }
+    if domain.len() > MAX_DOMAIN_LENGTH {
+        reasons.push(format!(
+            "Domain is longer than {} characters",
+            MAX_DOMAIN_LENGTH
+        ));
+    }
+
+    let labels: Vec<&str> = domain.split('.').collect();
+    if labels.len() < 2 {
+        reasons.push(format!("Domain \"{}\" has no top level domain", domain));
+    }
+    for label in &labels {
+        if label.is_empty() {
+            reasons.push("Domain has an empty label".to_string());
+            return;
+        }
+        if label.len() > MAX_LABEL_LENGTH {
+            reasons.push(format!(
+                "Domain label \"{}\" is longer than {} characters",
+                label, MAX_LABEL_LENGTH
+            ));
+        }
+        if label.starts_with('-') || label.ends_with('-') {
+            reasons.push(format!(
+                "Domain label \"{}\" starts or ends with a hyphen",
+                label
+            ));
+        }
+        if let Some(c) = label.chars().find(|&c| c != '-' && !c.is_alphanumeric()) {
+            reasons.push(format!("Domain contains the invalid character '{}'", c));
+        }
+    }
+    let tld = labels[labels.len() - 1];
+    if labels.len() > 1 && tld.chars().all(|c| c.is_ascii_digit()) {
+        reasons.push(format!("Top level domain \"{}\" is numeric", tld));
+    }
+}
+
+/// Checks the syntax and builds the canonical form. The local part keeps its case, as only
+/// the receiving server may treat it case-insensitively.
+pub fn validate_email(email: &str, strip_plus_tag: bool) -> EmailValidation {
+    let email = email.trim();
+    let mut reasons = Vec::new();
+
+    let Some((local, domain)) = email.rsplit_once('@') else {
+        return EmailValidation {
+            valid: false,
+            normalized: String::new(),
+            local_part: String::new(),
+            domain: String::new(),
+            reasons: vec!["Address has no @".to_string()],
+            has_mx: None,
+        };
+    };
+
+    if email.len() > MAX_ADDRESS_LENGTH {
+        reasons.push(format!(
+            "Address is longer than {} characters",
+            MAX_ADDRESS_LENGTH
+        ));
+    }
+    // Trailing dots mark a fully qualified name in DNS but are not part of the address
+    let domain = domain.strip_suffix('.').unwrap_or(domain).to_lowercase();
+    check_local_part(local, &mut reasons);
+    check_domain(&domain, &mut reasons);
+
+    let local = match local.split_once('+') {
+        Some((base, _)) if strip_plus_tag && !base.is_empty() => base,
+        _ => local,
+    };
+
+    let valid = reasons.is_empty();
+    EmailValidation {
+        valid,
+        normalized: if valid {
+            format!("{}@{}", local, domain)
+        } else {
+            String::new()
+        },
+        local_part: local.to_string(),
+        domain,
+        reasons,
+        has_mx: None,
+    }
+}
+
+/// Looks up whether the domain publishes a mail server. A null MX (RFC 7505) counts as none.
+#[cfg(feature = "execute")]
+async fn has_mail_server(domain: &str) -> flow_like_types::Result<bool> {
+    use hickory_resolver::TokioResolver;
+
+    let resolver = TokioResolver::builder_tokio()
+        .map_err(|e| flow_like_types::anyhow!("Failed to read the DNS configuration: {}", e))?
+        .build();
+    // The trailing dot keeps the system search domains from being appended
+    match resolver.mx_lookup(format!("{}.", domain)).await {
+        Ok(lookup) => Ok(lookup.iter().any(|mx| !mx.exchange().is_root())),
+        Err(e) if e.is_no_records_found() || e.is_nx_domain() => Ok(false),
+        Err(e) => Err(flow_like_types::anyhow!("MX lookup failed: {}", e)),
+    }
+}
+
+#[cfg(not(feature = "execute"))]
+async fn has_mail_server(_domain: &str) -> flow_like_types::Result<bool> {
+    Err(flow_like_types::anyhow!(
+        "MX lookups require the 'execute' feature"
+    ))
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct EmailValidateNode {}
+
+impl EmailValidateNode {
+    pub fn new() -> Self {
+        EmailValidateNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for EmailValidateNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "utils_email_validate",
+            "Validate Email",
+            "Checks the syntax of an email address and returns its canonical form. Optionally looks up the MX records of the domain to confirm it can receive mail. Failed lookups are reported in the reasons without rejecting the address",
+            "Utils/String",
+        );
+        node.add_icon("/flow/icons/mail.svg");
+
+        node.add_input_pin("exec_in", "Input", "Trigger Pin", VariableType::Execution);
+
+        node.add_input_pin(
+            "email",
+            "Email",
+            "Address to validate",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("")));
+
+        node.add_input_pin(
+            "strip_plus_tag",
+            "Strip Plus Tag",
+            "Remove plus addressing, e.g. ada+news@example.com becomes ada@example.com",
+            VariableType::Boolean,
+        )
+        .set_default_value(Some(json!(false)));
+
+        node.add_input_pin(
+            "check_mx",
+            "Check MX",
+            "Look up whether the domain has a mail server",
+            VariableType::Boolean,
+        )
+        .set_default_value(Some(json!(false)));
+
+        node.add_output_pin("exec_out", "Output", "Done", VariableType::Execution);
+
+        node.add_output_pin(
+            "valid",
+            "Valid",
+            "Whether the address is well formed and, if checked, its domain accepts mail",
+            VariableType::Boolean,
+        );
+
+        node.add_output_pin(
+            "normalized",
+            "Normalized",
+            "Canonical address, empty if invalid",
+            VariableType::String,
+        );
+
+        node.add_output_pin(
+            "result",
+            "Result",
+            "Validity, canonical parts and the reasons for rejecting or flagging the address",
+            VariableType::Struct,
+        )
+        .set_schema::<EmailValidation>();
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+
+        let email: String = context.evaluate_pin("email").await?;
+        let strip_plus_tag: bool = context.evaluate_pin("strip_plus_tag").await?;
+        let check_mx: bool = context.evaluate_pin("check_mx").await?;
+
+        let mut result = validate_email(&email, strip_plus_tag);
+        if check_mx && result.valid {
+            match has_mail_server(&result.domain).await {
+                Ok(true) => result.has_mx = Some(true),
+                Ok(false) => {
+                    result.has_mx = Some(false);
+                    result.valid = false;
+                    result
+                        .reasons
+                        .push(format!("Domain \"{}\" has no mail server", result.domain));
+                }
+                Err(e) => result.reasons.push(e.to_string()),
+            }
+        }
+
+        context.set_pin_value("valid", json!(result.valid)).await?;
+        context
+            .set_pin_value("normalized", json!(result.normalized))
+            .await?;
+        context.set_pin_value("result", json!(result)).await?;
+        context.activate_exec_pin("exec_out").await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reasons(email: &str) -> Vec<String> {
+        validate_email(email, false).reasons
+    }
+
+    #[test]
+    fn test_valid_addresses() {
+        for email in [
+            "ada@example.com",
+            "first.last@sub.example.co.uk",
+            "o'brien+tag@example.org",
+            "x@a.io",
+            "user_name-1@my-domain.dev",
+            "jörg@bücher.de",
+            "  padded@example.com  ",
+        ] {
+            let result = validate_email(email, false);
+            assert!(
+                result.valid,
+                "{} should be valid: {:?}",
+                email, result.reasons
+            );
+            assert!(result.reasons.is_empty());
+            assert_eq!(result.has_mx, None);
+        }
+    }
+
+    #[test]
+    fn test_invalid_addresses() {
+        for email in [
+            "",
+            "plainaddress",
+            "@example.com",
+            "ada@",
+            "ada@localhost",
+            ".ada@example.com",
+            "ada.@example.com",
+            "a..da@example.com",
+            "ada lovelace@example.com",
+            "ada@exa mple.com",
+            "ada@-example.com",
+            "ada@example-.com",
+            "ada@example..com",
+            "ada@example.123",
+            "ada@[192.168.0.1]",
+            "\"ada\"@example.com",
+            "ada@@example.com",
+        ] {
+            let result = validate_email(email, false);
+            assert!(!result.valid, "{} should be invalid", email);
+            assert!(!result.reasons.is_empty());
+            assert!(result.normalized.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_length_limits() {
+        let local = "a".repeat(65);
+        assert!(reasons(&format!("{}@example.com", local))[0].contains("64"));
+
+        let label = "b".repeat(64);
+        assert!(reasons(&format!("ada@{}.com", label))[0].contains("63"));
+
+        let long = format!("ada@{}.com", ["c".repeat(60); 5].join("."));
+        assert!(
+            reasons(&long)
+                .iter()
+                .any(|reason| reason.starts_with("Address is longer"))
+        );
+    }
+
+    #[test]
+    fn test_normalization() {
+        let result = validate_email("Ada.Lovelace+Newsletter@Example.COM.", false);
+        assert_eq!(result.normalized, "Ada.Lovelace+Newsletter@example.com");
+        assert_eq!(result.domain, "example.com");
+
+        let result = validate_email("Ada.Lovelace+Newsletter@Example.COM", true);
+        assert_eq!(result.normalized, "Ada.Lovelace@example.com");
+        assert_eq!(result.local_part, "Ada.Lovelace");
+
+        // A local part that is only a tag is kept as is
+        let result = validate_email("+tag@example.com", true);
+        assert_eq!(result.normalized, "+tag@example.com");
+    }
+}
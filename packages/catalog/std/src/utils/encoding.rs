@@ -0,0 +1,49 @@
+pub mod base64;
+pub mod hex;
+
+use flow_like_types::{Result, Value, bail, json::from_value};
+use std::borrow::Cow;
+
+/// Bytes of a string or byte array input, a string is taken as its UTF-8 bytes.
+pub fn input_bytes(value: Value) -> Result<Vec<u8>> {
+    match value {
+        Value::String(text) => Ok(text.into_bytes()),
+        Value::Array(_) => Ok(from_value(value)?),
+        other => bail!("Expected a string or a byte array, got {}", other),
+    }
+}
+
+/// Drops line breaks and spaces of wrapped encoded text, only copies if there are any.
+pub(crate) fn strip_whitespace(input: &[u8]) -> Cow<'_, [u8]> {
+    if input.iter().any(u8::is_ascii_whitespace) {
+        Cow::Owned(
+            input
+                .iter()
+                .copied()
+                .filter(|byte| !byte.is_ascii_whitespace())
+                .collect(),
+        )
+    } else {
+        Cow::Borrowed(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flow_like_types::json::json;
+
+    #[test]
+    fn test_input_bytes() {
+        assert_eq!(input_bytes(json!("hé")).unwrap(), "hé".as_bytes());
+        assert_eq!(input_bytes(json!([0, 255, 7])).unwrap(), vec![0, 255, 7]);
+        assert!(input_bytes(json!([256])).is_err());
+        assert!(input_bytes(json!({ "a": 1 })).is_err());
+    }
+
+    #[test]
+    fn test_strip_whitespace_borrows_clean_input() {
+        assert!(matches!(strip_whitespace(b"aGVsbG8="), Cow::Borrowed(_)));
+        assert_eq!(strip_whitespace(b"aGVs\r\nbG8=\n").as_ref(), b"aGVsbG8=");
+    }
+}
@@ -0,0 +1,245 @@
+use super::{input_bytes, strip_whitespace};
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic},
+    pin::{PinOptions, ValueType},
+    variable::VariableType,
+};
+use flow_like_types::{
+    Value, async_trait,
+    base64::{
+        Engine as _, alphabet,
+        engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig},
+    },
+    json::json,
+};
+
+const URL_SAFE: &str = "URL Safe";
+
+fn engine(name: &str, padding: bool) -> GeneralPurpose {
+    let alphabet = if name == URL_SAFE {
+        &alphabet::URL_SAFE
+    } else {
+        &alphabet::STANDARD
+    };
+    // Decoding accepts input with and without padding
+    let config = GeneralPurposeConfig::new()
+        .with_encode_padding(padding)
+        .with_decode_padding_mode(DecodePaddingMode::Indifferent);
+    GeneralPurpose::new(alphabet, config)
+}
+
+pub fn encode_base64(bytes: &[u8], alphabet: &str, padding: bool) -> String {
+    engine(alphabet, padding).encode(bytes)
+}
+
+/// Line breaks and spaces in the input are ignored.
+pub fn decode_base64(input: &[u8], alphabet: &str) -> Result<Vec<u8>, String> {
+    engine(alphabet, true)
+        .decode(strip_whitespace(input))
+        .map_err(|error| format!("Invalid base64: {}", error))
+}
+
+fn add_alphabet_pin(node: &mut Node) {
+    node.add_input_pin(
+        "alphabet",
+        "Alphabet",
+        "Standard uses + and /, URL Safe uses - and _",
+        VariableType::String,
+    )
+    .set_options(
+        PinOptions::new()
+            .set_valid_values(vec!["Standard".into(), URL_SAFE.into()])
+            .build(),
+    )
+    .set_default_value(Some(json!("Standard")));
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct Base64EncodeNode {}
+
+impl Base64EncodeNode {
+    pub fn new() -> Self {
+        Base64EncodeNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for Base64EncodeNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "utils_base64_encode",
+            "Base64 Encode",
+            "Encodes a string or byte array as base64",
+            "Utils/Encoding",
+        );
+        node.add_icon("/flow/icons/convert.svg");
+
+        node.add_input_pin(
+            "input",
+            "Input",
+            "String or byte array, strings are encoded as UTF-8",
+            VariableType::Generic,
+        );
+        add_alphabet_pin(&mut node);
+        node.add_input_pin(
+            "padding",
+            "Padding",
+            "Pad the output with = to a multiple of 4 characters",
+            VariableType::Boolean,
+        )
+        .set_default_value(Some(json!(true)));
+
+        node.add_output_pin("encoded", "Encoded", "Base64 text", VariableType::String);
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        let input: Value = context.evaluate_pin("input").await?;
+        let alphabet: String = context.evaluate_pin("alphabet").await?;
+        let padding: bool = context.evaluate_pin("padding").await?;
+
+        let bytes = input_bytes(input)?;
+        let encoded = encode_base64(&bytes, &alphabet, padding);
+
+        context.set_pin_value("encoded", json!(encoded)).await?;
+        Ok(())
+    }
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct Base64DecodeNode {}
+
+impl Base64DecodeNode {
+    pub fn new() -> Self {
+        Base64DecodeNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for Base64DecodeNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "utils_base64_decode",
+            "Base64 Decode",
+            "Decodes base64 text, padded or not",
+            "Utils/Encoding",
+        );
+        node.add_icon("/flow/icons/convert.svg");
+
+        node.add_input_pin("exec_in", "Input", "", VariableType::Execution);
+        node.add_input_pin(
+            "input",
+            "Input",
+            "Base64 text as string or byte array",
+            VariableType::Generic,
+        );
+        add_alphabet_pin(&mut node);
+
+        node.add_output_pin("exec_out", "Output", "", VariableType::Execution);
+        node.add_output_pin("bytes", "Bytes", "Decoded bytes", VariableType::Byte)
+            .set_value_type(ValueType::Array);
+        node.add_output_pin(
+            "text",
+            "Text",
+            "Decoded bytes as UTF-8, invalid sequences are replaced",
+            VariableType::String,
+        );
+        node.add_output_pin(
+            "error",
+            "Error",
+            "Triggers if the input is not valid base64",
+            VariableType::Execution,
+        );
+        node.add_output_pin(
+            "error_message",
+            "Error Message",
+            "Why the input could not be decoded",
+            VariableType::String,
+        );
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+        context.deactivate_exec_pin("error").await?;
+
+        let input: Value = context.evaluate_pin("input").await?;
+        let alphabet: String = context.evaluate_pin("alphabet").await?;
+
+        let decoded = input_bytes(input)
+            .map_err(|error| error.to_string())
+            .and_then(|input| decode_base64(&input, &alphabet));
+        let bytes = match decoded {
+            Ok(bytes) => bytes,
+            Err(error) => {
+                context.set_pin_value("error_message", json!(error)).await?;
+                context.activate_exec_pin("error").await?;
+                return Ok(());
+            }
+        };
+
+        context
+            .set_pin_value("text", json!(String::from_utf8_lossy(&bytes)))
+            .await?;
+        context.set_pin_value("bytes", json!(bytes)).await?;
+        context.activate_exec_pin("exec_out").await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        let bytes: Vec<u8> = (0..=255).collect();
+        for alphabet in ["Standard", URL_SAFE] {
+            for padding in [true, false] {
+                let encoded = encode_base64(&bytes, alphabet, padding);
+                assert_eq!(decode_base64(encoded.as_bytes(), alphabet).unwrap(), bytes);
+            }
+        }
+
+        let large = vec![0xA5u8; 3 * 1024 * 1024 + 1];
+        let encoded = encode_base64(&large, "Standard", true);
+        assert_eq!(
+            decode_base64(encoded.as_bytes(), "Standard").unwrap(),
+            large
+        );
+    }
+
+    #[test]
+    fn test_alphabets_and_padding() {
+        let bytes = [0xFB, 0xFF, 0xBF, 0x61];
+        assert_eq!(encode_base64(&bytes, "Standard", true), "+/+/YQ==");
+        assert_eq!(encode_base64(&bytes, URL_SAFE, true), "-_-_YQ==");
+        assert_eq!(encode_base64(&bytes, URL_SAFE, false), "-_-_YQ");
+        assert_eq!(encode_base64(b"hello", "Standard", true), "aGVsbG8=");
+
+        // Padding is optional when decoding
+        assert_eq!(decode_base64(b"aGVsbG8", "Standard").unwrap(), b"hello");
+        assert_eq!(
+            decode_base64(b"aGVs\nbG8=\n", "Standard").unwrap(),
+            b"hello"
+        );
+
+        // Each alphabet rejects the characters of the other
+        assert!(decode_base64(b"-_-_YQ==", "Standard").is_err());
+        assert!(decode_base64(b"+/+/YQ==", URL_SAFE).is_err());
+    }
+
+    #[test]
+    fn test_malformed_input() {
+        for input in [&b"a"[..], b"aGVsbG8===", b"aGV*bG8=", b"=aGV"] {
+            let error = decode_base64(input, "Standard").unwrap_err();
+            assert!(error.starts_with("Invalid base64"), "{}", error);
+        }
+        assert_eq!(decode_base64(b"", "Standard").unwrap(), Vec::<u8>::new());
+    }
+}
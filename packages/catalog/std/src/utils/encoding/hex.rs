@@ -0,0 +1,196 @@
+use super::{input_bytes, strip_whitespace};
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic},
+    pin::ValueType,
+    variable::VariableType,
+};
+use flow_like_types::{Value, async_trait, json::json};
+
+pub fn encode_hex(bytes: &[u8], uppercase: bool) -> String {
+    if uppercase {
+        hex::encode_upper(bytes)
+    } else {
+        hex::encode(bytes)
+    }
+}
+
+/// Accepts both cases, an optional `0x` prefix and whitespace between the digits.
+pub fn decode_hex(input: &[u8]) -> Result<Vec<u8>, String> {
+    let input = strip_whitespace(input);
+    let digits = input
+        .strip_prefix(b"0x")
+        .or_else(|| input.strip_prefix(b"0X"))
+        .unwrap_or(&input[..]);
+    hex::decode(digits).map_err(|error| format!("Invalid hex: {}", error))
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct HexEncodeNode {}
+
+impl HexEncodeNode {
+    pub fn new() -> Self {
+        HexEncodeNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for HexEncodeNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "utils_hex_encode",
+            "Hex Encode",
+            "Encodes a string or byte array as hexadecimal text",
+            "Utils/Encoding",
+        );
+        node.add_icon("/flow/icons/convert.svg");
+
+        node.add_input_pin(
+            "input",
+            "Input",
+            "String or byte array, strings are encoded as UTF-8",
+            VariableType::Generic,
+        );
+        node.add_input_pin(
+            "uppercase",
+            "Uppercase",
+            "Use A-F instead of a-f",
+            VariableType::Boolean,
+        )
+        .set_default_value(Some(json!(false)));
+
+        node.add_output_pin(
+            "encoded",
+            "Encoded",
+            "Two hex digits per byte",
+            VariableType::String,
+        );
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        let input: Value = context.evaluate_pin("input").await?;
+        let uppercase: bool = context.evaluate_pin("uppercase").await?;
+
+        let bytes = input_bytes(input)?;
+        let encoded = encode_hex(&bytes, uppercase);
+
+        context.set_pin_value("encoded", json!(encoded)).await?;
+        Ok(())
+    }
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct HexDecodeNode {}
+
+impl HexDecodeNode {
+    pub fn new() -> Self {
+        HexDecodeNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for HexDecodeNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "utils_hex_decode",
+            "Hex Decode",
+            "Decodes hexadecimal text, with or without a 0x prefix",
+            "Utils/Encoding",
+        );
+        node.add_icon("/flow/icons/convert.svg");
+
+        node.add_input_pin("exec_in", "Input", "", VariableType::Execution);
+        node.add_input_pin(
+            "input",
+            "Input",
+            "Hex text as string or byte array",
+            VariableType::Generic,
+        );
+
+        node.add_output_pin("exec_out", "Output", "", VariableType::Execution);
+        node.add_output_pin("bytes", "Bytes", "Decoded bytes", VariableType::Byte)
+            .set_value_type(ValueType::Array);
+        node.add_output_pin(
+            "text",
+            "Text",
+            "Decoded bytes as UTF-8, invalid sequences are replaced",
+            VariableType::String,
+        );
+        node.add_output_pin(
+            "error",
+            "Error",
+            "Triggers if the input is not valid hex",
+            VariableType::Execution,
+        );
+        node.add_output_pin(
+            "error_message",
+            "Error Message",
+            "Why the input could not be decoded",
+            VariableType::String,
+        );
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+        context.deactivate_exec_pin("error").await?;
+
+        let input: Value = context.evaluate_pin("input").await?;
+
+        let decoded = input_bytes(input)
+            .map_err(|error| error.to_string())
+            .and_then(|input| decode_hex(&input));
+        let bytes = match decoded {
+            Ok(bytes) => bytes,
+            Err(error) => {
+                context.set_pin_value("error_message", json!(error)).await?;
+                context.activate_exec_pin("error").await?;
+                return Ok(());
+            }
+        };
+
+        context
+            .set_pin_value("text", json!(String::from_utf8_lossy(&bytes)))
+            .await?;
+        context.set_pin_value("bytes", json!(bytes)).await?;
+        context.activate_exec_pin("exec_out").await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        let bytes: Vec<u8> = (0..=255).collect();
+        for uppercase in [false, true] {
+            let encoded = encode_hex(&bytes, uppercase);
+            assert_eq!(encoded.len(), 512);
+            assert_eq!(decode_hex(encoded.as_bytes()).unwrap(), bytes);
+        }
+        assert_eq!(encode_hex(b"Hi\xff", false), "4869ff");
+        assert_eq!(encode_hex(b"Hi\xff", true), "4869FF");
+    }
+
+    #[test]
+    fn test_prefix_and_whitespace() {
+        assert_eq!(decode_hex(b"0x4869").unwrap(), b"Hi");
+        assert_eq!(decode_hex(b"0X48 69\n").unwrap(), b"Hi");
+        assert_eq!(decode_hex(b"").unwrap(), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn test_malformed_input() {
+        for input in [&b"486"[..], b"48zz", b"0x0x48"] {
+            let error = decode_hex(input).unwrap_err();
+            assert!(error.starts_with("Invalid hex"), "{}", error);
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic},
+    pin::PinOptions,
+    variable::VariableType,
+};
+use flow_like_types::{
+    async_trait, bail,
+    json::json,
+    rand::{self, Rng},
+};
+
+const NANOID_ALPHABET: &str = "_-0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const NANOID_MAX_LENGTH: usize = 256;
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct GenerateIdNode {}
+
+impl GenerateIdNode {
+    pub fn new() -> Self {
+        GenerateIdNode {}
+    }
+}
+
+/// Checks the nanoid settings and returns the alphabet as characters
+fn nanoid_alphabet(length: i64, alphabet: &str) -> flow_like_types::Result<Vec<char>> {
+    if length < 1 || length as usize > NANOID_MAX_LENGTH {
+        bail!(
+            "Nanoid length must be between 1 and {}, got {}",
+            NANOID_MAX_LENGTH,
+            length
+        );
+    }
+
+    let chars: Vec<char> = alphabet.chars().collect();
+    if chars.len() < 2 {
+        bail!("Nanoid alphabet needs at least 2 characters");
+    }
+    let mut unique = chars.clone();
+    unique.sort_unstable();
+    unique.dedup();
+    if unique.len() != chars.len() {
+        bail!("Nanoid alphabet must not contain duplicate characters");
+    }
+
+    Ok(chars)
+}
+
+fn generate_id(format: &str, length: i64, alphabet: &str) -> flow_like_types::Result<String> {
+    let id = match format {
+        "cuid2" => flow_like_types::create_id(),
+        "uuid_v4" => uuid::Uuid::new_v4().to_string(),
+        "uuid_v7" => uuid::Uuid::now_v7().to_string(),
+        "nanoid" => {
+            let alphabet = nanoid_alphabet(length, alphabet)?;
+            let mut rng = rand::rng();
+            (0..length)
+                .map(|_| alphabet[rng.random_range(0..alphabet.len())])
+                .collect()
+        }
+        _ => bail!("Unknown id format: {}", format),
+    };
+    Ok(id)
+}
+
+#[async_trait]
+impl NodeLogic for GenerateIdNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "generate_id",
+            "Generate ID",
+            "Generates a unique id. UUID v7 ids sort by creation time, which makes them good database keys",
+            "Utils",
+        );
+        node.add_icon("/flow/icons/random.svg");
+
+        node.add_input_pin("exec_in", "Input", "", VariableType::Execution);
+
+        node.add_input_pin("format", "Format", "Format of the id", VariableType::String)
+            .set_default_value(Some(json!("uuid_v7")))
+            .set_options(
+                PinOptions::new()
+                    .set_valid_values(vec![
+                        "cuid2".into(),
+                        "uuid_v4".into(),
+                        "uuid_v7".into(),
+                        "nanoid".into(),
+                    ])
+                    .build(),
+            );
+
+        node.add_input_pin(
+            "length",
+            "Length",
+            "Length of a nanoid, between 1 and 256",
+            VariableType::Integer,
+        )
+        .set_default_value(Some(json!(21)));
+
+        node.add_input_pin(
+            "alphabet",
+            "Alphabet",
+            "Characters a nanoid is built from",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!(NANOID_ALPHABET)));
+
+        node.add_output_pin("exec_out", "Output", "", VariableType::Execution);
+
+        node.add_output_pin("id", "ID", "Generated id", VariableType::String);
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+
+        let format: String = context.evaluate_pin("format").await?;
+        let length: i64 = context.evaluate_pin("length").await?;
+        let alphabet: String = context.evaluate_pin("alphabet").await?;
+
+        let id = generate_id(&format, length, &alphabet)?;
+        context.set_pin_value("id", json!(id)).await?;
+        context.activate_exec_pin("exec_out").await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn id(format: &str) -> String {
+        generate_id(format, 21, NANOID_ALPHABET).unwrap()
+    }
+
+    #[test]
+    fn test_uuid_v4_is_random() {
+        let first = uuid::Uuid::parse_str(&id("uuid_v4")).unwrap();
+        let second = uuid::Uuid::parse_str(&id("uuid_v4")).unwrap();
+
+        assert_eq!(first.get_version_num(), 4);
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn test_uuid_v7_is_monotonic() {
+        let first = id("uuid_v7");
+        let second = id("uuid_v7");
+
+        assert_eq!(uuid::Uuid::parse_str(&first).unwrap().get_version_num(), 7);
+        // Also holds for the string form, which is what ends up as a database key
+        assert!(first < second, "{} should sort before {}", first, second);
+    }
+
+    #[test]
+    fn test_cuid2_shape() {
+        let cuid = id("cuid2");
+
+        assert_eq!(cuid.len(), 24);
+        assert!(cuid.chars().next().unwrap().is_ascii_lowercase());
+        assert!(
+            cuid.chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+        );
+    }
+
+    #[test]
+    fn test_nanoid_uses_length_and_alphabet() {
+        let nanoid = generate_id("nanoid", 10, "ab").unwrap();
+
+        assert_eq!(nanoid.len(), 10);
+        assert!(nanoid.chars().all(|c| c == 'a' || c == 'b'));
+    }
+
+    #[test]
+    fn test_nanoid_rejects_invalid_settings() {
+        assert!(generate_id("nanoid", 0, NANOID_ALPHABET).is_err());
+        assert!(generate_id("nanoid", 257, NANOID_ALPHABET).is_err());
+        assert!(generate_id("nanoid", 10, "a").is_err());
+        assert!(generate_id("nanoid", 10, "abca").is_err());
+    }
+
+    #[test]
+    fn test_unknown_format_fails() {
+        assert!(generate_id("snowflake", 21, NANOID_ALPHABET).is_err());
+    }
+}
@@ -1,2 +1,3 @@
 pub mod ahash;
 pub mod blake3;
+pub mod merkle;
@@ -0,0 +1,424 @@
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic},
+    pin::{PinOptions, ValueType},
+    variable::VariableType,
+};
+use flow_like_storage::blake3;
+use flow_like_types::{
+    Value, anyhow, async_trait, bail,
+    json::{json, to_vec},
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+// Leaves and inner nodes use different prefixes so an inner node can never pass as a leaf
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq)]
+pub struct MerkleProofStep {
+    /// Hex encoded hash of the sibling node
+    pub hash: String,
+    /// True if the sibling is the left child
+    pub left: bool,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq)]
+pub struct MerkleProof {
+    pub index: usize,
+    pub leaf_hash: String,
+    pub root: String,
+    /// Siblings from the leaf level up to the root
+    pub path: Vec<MerkleProofStep>,
+}
+
+/// Hashes a leaf value the same way the Blake3 node hashes its input, but domain separated.
+pub fn hash_leaf(value: &Value) -> flow_like_types::Result<blake3::Hash> {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&[LEAF_PREFIX]).update(&to_vec(value)?);
+    Ok(hasher.finalize())
+}
+
+fn hash_node(left: &blake3::Hash, right: &blake3::Hash) -> blake3::Hash {
+    let mut hasher = blake3::Hasher::new();
+    hasher
+        .update(&[NODE_PREFIX])
+        .update(left.as_bytes())
+        .update(right.as_bytes());
+    hasher.finalize()
+}
+
+/// Builds the next level. An unpaired last node is promoted as is instead of being
+/// duplicated, so `[a, b, c]` and `[a, b, c, c]` have different roots.
+fn next_level(level: &[blake3::Hash]) -> Vec<blake3::Hash> {
+    level
+        .chunks(2)
+        .map(|pair| match pair {
+            [left, right] => hash_node(left, right),
+            [single] => *single,
+            _ => unreachable!(),
+        })
+        .collect()
+}
+
+pub fn merkle_root(leaves: &[blake3::Hash]) -> Option<blake3::Hash> {
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        level = next_level(&level);
+    }
+    level.first().copied()
+}
+
+pub fn merkle_proof(leaves: &[blake3::Hash], index: usize) -> flow_like_types::Result<MerkleProof> {
+    if index >= leaves.len() {
+        bail!(
+            "Leaf index {} is out of range for {} leaves",
+            index,
+            leaves.len()
+        );
+    }
+
+    let mut path = Vec::new();
+    let mut level = leaves.to_vec();
+    let mut position = index;
+    while level.len() > 1 {
+        let sibling = position ^ 1;
+        if let Some(hash) = level.get(sibling) {
+            path.push(MerkleProofStep {
+                hash: hash.to_hex().to_string(),
+                left: sibling < position,
+            });
+        }
+        level = next_level(&level);
+        position /= 2;
+    }
+
+    Ok(MerkleProof {
+        index,
+        leaf_hash: leaves[index].to_hex().to_string(),
+        root: level[0].to_hex().to_string(),
+        path,
+    })
+}
+
+fn parse_hash(hex: &str) -> flow_like_types::Result<blake3::Hash> {
+    blake3::Hash::from_hex(hex.trim()).map_err(|e| anyhow!("Invalid hash '{}': {}", hex, e))
+}
+
+/// Recomputes the root from a leaf hash and its proof path and compares it to `root`.
+pub fn verify_proof(
+    leaf_hash: &blake3::Hash,
+    path: &[MerkleProofStep],
+    root: &str,
+) -> flow_like_types::Result<bool> {
+    let root = parse_hash(root)?;
+    let mut current = *leaf_hash;
+    for step in path {
+        let sibling = parse_hash(&step.hash)?;
+        current = match step.left {
+            true => hash_node(&sibling, &current),
+            false => hash_node(&current, &sibling),
+        };
+    }
+
+    // blake3::Hash compares in constant time
+    Ok(current == root)
+}
+
+fn hash_leaves(leaves: &[Value]) -> flow_like_types::Result<Vec<blake3::Hash>> {
+    leaves.iter().map(hash_leaf).collect()
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct MerkleTreeNode {}
+
+impl MerkleTreeNode {
+    pub fn new() -> Self {
+        MerkleTreeNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for MerkleTreeNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "utils_hash_merkle_root",
+            "Merkle Root",
+            "Builds a Blake3 Merkle tree over a batch of records and returns its root. Store the root to later prove that a record was part of the batch",
+            "Utils/Hash",
+        );
+        node.add_icon("/flow/icons/hash.svg");
+
+        node.add_input_pin("exec_in", "Execute", "", VariableType::Execution);
+
+        node.add_input_pin(
+            "leaves",
+            "Leaves",
+            "Records to commit to, in order",
+            VariableType::Generic,
+        )
+        .set_value_type(ValueType::Array)
+        .set_default_value(Some(json!([])));
+
+        node.add_output_pin(
+            "exec_out",
+            "Done",
+            "Execution output pin",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "root",
+            "Root (hex)",
+            "Merkle root of the leaves",
+            VariableType::String,
+        );
+
+        node.add_output_pin(
+            "leaf_hashes",
+            "Leaf Hashes",
+            "Hex encoded hash of every leaf",
+            VariableType::String,
+        )
+        .set_value_type(ValueType::Array);
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+
+        let leaves: Vec<Value> = context.evaluate_pin("leaves").await?;
+        let leaf_hashes = hash_leaves(&leaves)?;
+        let root = merkle_root(&leaf_hashes)
+            .ok_or_else(|| anyhow!("Cannot build a Merkle tree without leaves"))?;
+
+        let leaf_hashes: Vec<String> = leaf_hashes
+            .iter()
+            .map(|hash| hash.to_hex().to_string())
+            .collect();
+        context
+            .set_pin_value("root", json!(root.to_hex().to_string()))
+            .await?;
+        context
+            .set_pin_value("leaf_hashes", json!(leaf_hashes))
+            .await?;
+
+        context.activate_exec_pin("exec_out").await?;
+        Ok(())
+    }
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct MerkleProofNode {}
+
+impl MerkleProofNode {
+    pub fn new() -> Self {
+        MerkleProofNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for MerkleProofNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "utils_hash_merkle_proof",
+            "Merkle Proof",
+            "Creates an inclusion proof for one record of a batch. The proof can be verified against the Merkle root without the other records",
+            "Utils/Hash",
+        );
+        node.add_icon("/flow/icons/hash.svg");
+
+        node.add_input_pin("exec_in", "Execute", "", VariableType::Execution);
+
+        node.add_input_pin(
+            "leaves",
+            "Leaves",
+            "The same records, in the same order, the root was built from",
+            VariableType::Generic,
+        )
+        .set_value_type(ValueType::Array)
+        .set_default_value(Some(json!([])));
+
+        node.add_input_pin(
+            "index",
+            "Index",
+            "Index of the record to prove",
+            VariableType::Integer,
+        )
+        .set_default_value(Some(json!(0)));
+
+        node.add_output_pin(
+            "exec_out",
+            "Done",
+            "Execution output pin",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "proof",
+            "Proof",
+            "Inclusion proof with the sibling hashes up to the root",
+            VariableType::Struct,
+        )
+        .set_schema::<MerkleProof>();
+
+        node.add_output_pin(
+            "root",
+            "Root (hex)",
+            "Merkle root of the leaves",
+            VariableType::String,
+        );
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+
+        let leaves: Vec<Value> = context.evaluate_pin("leaves").await?;
+        let index: i64 = context.evaluate_pin("index").await?;
+        if index < 0 {
+            bail!("Leaf index must not be negative");
+        }
+
+        let proof = merkle_proof(&hash_leaves(&leaves)?, index as usize)?;
+        context.set_pin_value("root", json!(proof.root)).await?;
+        context.set_pin_value("proof", json!(proof)).await?;
+
+        context.activate_exec_pin("exec_out").await?;
+        Ok(())
+    }
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct MerkleVerifyNode {}
+
+impl MerkleVerifyNode {
+    pub fn new() -> Self {
+        MerkleVerifyNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for MerkleVerifyNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "utils_hash_merkle_verify",
+            "Verify Merkle Proof",
+            "Checks that a record is included under a trusted Merkle root",
+            "Utils/Hash",
+        );
+        node.add_icon("/flow/icons/shield.svg");
+
+        node.add_input_pin("leaf", "Leaf", "Record to check", VariableType::Generic);
+
+        node.add_input_pin(
+            "proof",
+            "Proof",
+            "Inclusion proof created by the Merkle Proof node",
+            VariableType::Struct,
+        )
+        .set_schema::<MerkleProof>()
+        .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node.add_input_pin(
+            "root",
+            "Root (hex)",
+            "Trusted Merkle root. The root stored in the proof is ignored",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("")));
+
+        node.add_output_pin(
+            "valid",
+            "Valid",
+            "True if the record is included under the root",
+            VariableType::Boolean,
+        );
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        let leaf: Value = context.evaluate_pin("leaf").await?;
+        let proof: MerkleProof = context.evaluate_pin("proof").await?;
+        let root: String = context.evaluate_pin("root").await?;
+
+        let valid = verify_proof(&hash_leaf(&leaf)?, &proof.path, &root)?;
+        context.set_pin_value("valid", json!(valid)).await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaves(count: usize) -> Vec<blake3::Hash> {
+        (0..count)
+            .map(|i| hash_leaf(&json!({ "id": i, "amount": i * 10 })).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_proofs_verify_for_every_leaf() {
+        for count in 1..=9 {
+            let leaves = leaves(count);
+            let root = merkle_root(&leaves).unwrap().to_hex().to_string();
+
+            for index in 0..count {
+                let proof = merkle_proof(&leaves, index).unwrap();
+                assert_eq!(proof.root, root);
+                assert!(verify_proof(&leaves[index], &proof.path, &root).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn test_tampered_record_fails() {
+        let leaves = leaves(5);
+        let root = merkle_root(&leaves).unwrap().to_hex().to_string();
+        let proof = merkle_proof(&leaves, 2).unwrap();
+
+        let tampered = hash_leaf(&json!({ "id": 2, "amount": 21 })).unwrap();
+        assert!(!verify_proof(&tampered, &proof.path, &root).unwrap());
+        assert!(!verify_proof(&leaves[3], &proof.path, &root).unwrap());
+    }
+
+    #[test]
+    fn test_root_depends_on_order_and_padding() {
+        let mut swapped = leaves(3);
+        let root = merkle_root(&swapped).unwrap();
+        swapped.swap(0, 1);
+        assert_ne!(merkle_root(&swapped).unwrap(), root);
+
+        let mut padded = leaves(3);
+        padded.push(padded[2]);
+        assert_ne!(merkle_root(&padded).unwrap(), root);
+    }
+
+    #[test]
+    fn test_leaf_and_node_hashes_are_separated() {
+        let leaves = leaves(2);
+        let root = merkle_root(&leaves).unwrap();
+        let mut concatenated = leaves[0].as_bytes().to_vec();
+        concatenated.extend_from_slice(leaves[1].as_bytes());
+
+        assert_ne!(blake3::hash(&concatenated), root);
+        assert_eq!(merkle_root(&leaves[..1]), Some(leaves[0]));
+        assert_eq!(merkle_root(&[]), None);
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        let leaves = leaves(3);
+        assert!(merkle_proof(&leaves, 3).is_err());
+        assert!(verify_proof(&leaves[0], &[], "not-a-hash").is_err());
+    }
+}
@@ -1,3 +1,5 @@
+pub mod detect_drift;
+pub mod guard;
 pub mod make_schema;
 pub mod parse_with_schema;
 pub mod repair_parse;
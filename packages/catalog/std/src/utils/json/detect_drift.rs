@@ -0,0 +1,375 @@
+use flow_like::flow::{
+    execution::{LogLevel, context::ExecutionContext},
+    node::{Node, NodeLogic},
+    pin::{PinOptions, ValueType},
+    variable::VariableType,
+};
+use flow_like_catalog_core::FlowPath;
+use flow_like_storage::{Path, object_store};
+use flow_like_types::{
+    Value, async_trait,
+    json::{self, json},
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Copy, Debug, PartialEq)]
+pub enum DriftKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq)]
+pub struct DriftChange {
+    /// JSON Pointer to the drifted value, e.g. `/server/port`
+    pub path: String,
+    pub kind: DriftKind,
+    pub baseline: Option<Value>,
+    pub current: Option<Value>,
+}
+
+fn escape_pointer(segment: &str) -> String {
+    segment.replace('~', "~0").replace('/', "~1")
+}
+
+fn is_ignored(path: &str, ignore: &[String]) -> bool {
+    ignore.iter().any(|ignored| {
+        let ignored = ignored.trim_end_matches('/');
+        path == ignored
+            || path
+                .strip_prefix(ignored)
+                .is_some_and(|rest| rest.starts_with('/'))
+    })
+}
+
+/// Collects all differences between `baseline` and `current` as JSON Pointer changes.
+/// Objects are compared by key and arrays by index, anything else by value.
+pub fn diff_values(baseline: &Value, current: &Value, ignore: &[String]) -> Vec<DriftChange> {
+    let mut changes = Vec::new();
+    diff_into(
+        String::new(),
+        Some(baseline),
+        Some(current),
+        ignore,
+        &mut changes,
+    );
+    changes
+}
+
+fn diff_into(
+    path: String,
+    baseline: Option<&Value>,
+    current: Option<&Value>,
+    ignore: &[String],
+    changes: &mut Vec<DriftChange>,
+) {
+    if !path.is_empty() && is_ignored(&path, ignore) {
+        return;
+    }
+
+    match (baseline, current) {
+        (Some(Value::Object(baseline)), Some(Value::Object(current))) => {
+            let keys: BTreeSet<&String> = baseline.keys().chain(current.keys()).collect();
+            for key in keys {
+                diff_into(
+                    format!("{}/{}", path, escape_pointer(key)),
+                    baseline.get(key),
+                    current.get(key),
+                    ignore,
+                    changes,
+                );
+            }
+        }
+        (Some(Value::Array(baseline)), Some(Value::Array(current))) => {
+            for index in 0..baseline.len().max(current.len()) {
+                diff_into(
+                    format!("{}/{}", path, index),
+                    baseline.get(index),
+                    current.get(index),
+                    ignore,
+                    changes,
+                );
+            }
+        }
+        (Some(baseline), Some(current)) if baseline == current => {}
+        (baseline, current) => {
+            let kind = match (baseline, current) {
+                (None, _) => DriftKind::Added,
+                (_, None) => DriftKind::Removed,
+                _ => DriftKind::Changed,
+            };
+            changes.push(DriftChange {
+                path,
+                kind,
+                baseline: baseline.cloned(),
+                current: current.cloned(),
+            });
+        }
+    }
+}
+
+async fn read_baseline(
+    baseline: &FlowPath,
+    context: &mut ExecutionContext,
+) -> flow_like_types::Result<Option<Value>> {
+    let store = baseline.to_store(context).await?;
+    match store
+        .as_generic()
+        .get(&Path::from(baseline.path.as_str()))
+        .await
+    {
+        Ok(result) => Ok(Some(json::from_slice(&result.bytes().await?)?)),
+        Err(object_store::Error::NotFound { .. }) => Ok(None),
+        Err(error) => Err(error.into()),
+    }
+}
+
+async fn write_baseline(
+    baseline: &FlowPath,
+    context: &mut ExecutionContext,
+    value: &Value,
+) -> flow_like_types::Result<()> {
+    baseline
+        .put(context, json::to_vec_pretty(value)?, true)
+        .await
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct DetectDriftNode {}
+
+impl DetectDriftNode {
+    pub fn new() -> Self {
+        DetectDriftNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for DetectDriftNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "detect_drift",
+            "Detect Drift",
+            "Compares a value against an approved baseline file. The baseline is only replaced when the drift is acknowledged, so ongoing drift keeps being reported",
+            "Utils/JSON",
+        );
+        node.add_icon("/flow/icons/history.svg");
+
+        node.add_input_pin(
+            "exec_in",
+            "Input",
+            "Initiate Execution",
+            VariableType::Execution,
+        );
+
+        node.add_input_pin(
+            "current",
+            "Current",
+            "Currently observed state",
+            VariableType::Struct,
+        );
+
+        node.add_input_pin(
+            "baseline",
+            "Baseline",
+            "JSON file holding the approved state. Created from the current state if it does not exist",
+            VariableType::Struct,
+        )
+        .set_schema::<FlowPath>()
+        .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node.add_input_pin(
+            "acknowledge",
+            "Acknowledge",
+            "Approve the current state and store it as the new baseline",
+            VariableType::Boolean,
+        )
+        .set_default_value(Some(json!(false)));
+
+        node.add_input_pin(
+            "ignore",
+            "Ignore",
+            "JSON Pointers that are excluded from the comparison, e.g. /metadata/updated_at",
+            VariableType::String,
+        )
+        .set_value_type(ValueType::Array)
+        .set_default_value(Some(json!([])));
+
+        node.add_output_pin(
+            "on_drift",
+            "Drift",
+            "Triggers if the current state differs from the baseline",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "no_drift",
+            "No Drift",
+            "Triggers if the current state matches the baseline or the drift was acknowledged",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "changes",
+            "Changes",
+            "Differences between baseline and current state",
+            VariableType::Struct,
+        )
+        .set_schema::<DriftChange>()
+        .set_value_type(ValueType::Array);
+
+        node.add_output_pin(
+            "baseline_updated",
+            "Baseline Updated",
+            "True if the baseline was created or replaced in this run",
+            VariableType::Boolean,
+        );
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("on_drift").await?;
+        context.deactivate_exec_pin("no_drift").await?;
+
+        let current: Value = context.evaluate_pin("current").await?;
+        let baseline_path: FlowPath = context.evaluate_pin("baseline").await?;
+        let acknowledge: bool = context.evaluate_pin("acknowledge").await.unwrap_or(false);
+        let ignore: Vec<String> = context.evaluate_pin("ignore").await.unwrap_or_default();
+
+        let (changes, baseline_updated) = match read_baseline(&baseline_path, context).await? {
+            Some(baseline) => {
+                let changes = diff_values(&baseline, &current, &ignore);
+                let update = acknowledge && !changes.is_empty();
+                if update {
+                    write_baseline(&baseline_path, context, &current).await?;
+                    context.log_message(
+                        &format!("Acknowledged {} drifted value(s)", changes.len()),
+                        LogLevel::Info,
+                    );
+                }
+                (changes, update)
+            }
+            None => {
+                write_baseline(&baseline_path, context, &current).await?;
+                context.log_message("Created drift baseline", LogLevel::Info);
+                (vec![], true)
+            }
+        };
+
+        let drifted = !changes.is_empty() && !baseline_updated;
+
+        context.set_pin_value("changes", json!(changes)).await?;
+        context
+            .set_pin_value("baseline_updated", json!(baseline_updated))
+            .await?;
+
+        if drifted {
+            context.activate_exec_pin("on_drift").await?;
+        } else {
+            context.activate_exec_pin("no_drift").await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn change(
+        path: &str,
+        kind: DriftKind,
+        baseline: Option<Value>,
+        current: Option<Value>,
+    ) -> DriftChange {
+        DriftChange {
+            path: path.to_string(),
+            kind,
+            baseline,
+            current,
+        }
+    }
+
+    #[test]
+    fn test_identical_values_have_no_drift() {
+        let value = json!({ "server": { "port": 80, "hosts": ["a", "b"] } });
+        assert!(diff_values(&value, &value, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_nested_changes() {
+        let baseline = json!({
+            "server": { "port": 80, "tls": true },
+            "hosts": ["a", "b"],
+            "owner": "ops"
+        });
+        let current = json!({
+            "server": { "port": 8080, "tls": true, "debug": true },
+            "hosts": ["a"],
+            "owner": "ops"
+        });
+
+        assert_eq!(
+            diff_values(&baseline, &current, &[]),
+            vec![
+                change("/hosts/1", DriftKind::Removed, Some(json!("b")), None),
+                change("/server/debug", DriftKind::Added, None, Some(json!(true))),
+                change(
+                    "/server/port",
+                    DriftKind::Changed,
+                    Some(json!(80)),
+                    Some(json!(8080))
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_type_change_and_root_value() {
+        assert_eq!(
+            diff_values(&json!({ "a": [1] }), &json!({ "a": { "0": 1 } }), &[]),
+            vec![change(
+                "/a",
+                DriftKind::Changed,
+                Some(json!([1])),
+                Some(json!({ "0": 1 }))
+            )]
+        );
+        assert_eq!(
+            diff_values(&json!(1), &json!(2), &[]),
+            vec![change(
+                "",
+                DriftKind::Changed,
+                Some(json!(1)),
+                Some(json!(2))
+            )]
+        );
+    }
+
+    #[test]
+    fn test_ignored_paths_and_escaping() {
+        let baseline = json!({
+            "metadata": { "updated_at": "monday", "version": 1 },
+            "a/b": { "c~d": 1 }
+        });
+        let current = json!({
+            "metadata": { "updated_at": "tuesday", "version": 1 },
+            "a/b": { "c~d": 2 }
+        });
+
+        let changes = diff_values(&baseline, &current, &["/metadata/updated_at".to_string()]);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].path, "/a~1b/c~0d");
+
+        let ignore_prefix = diff_values(&baseline, &current, &["/a~1b/".to_string()]);
+        assert_eq!(ignore_prefix.len(), 1);
+        assert_eq!(ignore_prefix[0].path, "/metadata/updated_at");
+
+        // A prefix only matches whole segments
+        assert!(!is_ignored("/metadata_old", &["/metadata".to_string()]));
+    }
+}
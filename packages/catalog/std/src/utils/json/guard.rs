@@ -0,0 +1,392 @@
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic},
+    pin::PinOptions,
+    variable::VariableType,
+};
+use flow_like_types::{
+    Value, async_trait,
+    json::{self, json},
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Copy, Debug, PartialEq)]
+pub enum JsonLimit {
+    Bytes,
+    Depth,
+    Keys,
+    ArrayLength,
+    Syntax,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq)]
+pub struct JsonGuardViolation {
+    pub limit: JsonLimit,
+    /// JSON Pointer to the offending object or array, empty for the whole payload
+    pub path: String,
+    pub max: usize,
+    pub actual: usize,
+    pub message: String,
+}
+
+/// Limits applied by [`guard_json`], `0` disables a limit.
+#[derive(Clone, Copy, Debug)]
+pub struct JsonLimits {
+    pub max_bytes: usize,
+    pub max_depth: usize,
+    pub max_keys: usize,
+    pub max_array_length: usize,
+}
+
+impl JsonGuardViolation {
+    fn new(limit: JsonLimit, path: String, max: usize, actual: usize) -> Self {
+        let what = match limit {
+            JsonLimit::Bytes => "Payload size in bytes",
+            JsonLimit::Depth => "Nesting depth",
+            JsonLimit::Keys => "Number of object keys",
+            JsonLimit::ArrayLength => "Array length",
+            JsonLimit::Syntax => "Invalid JSON",
+        };
+        let at = if path.is_empty() { "/" } else { &path };
+        JsonGuardViolation {
+            message: format!("{what} at {at} is {actual}, the limit is {max}"),
+            limit,
+            path,
+            max,
+            actual,
+        }
+    }
+}
+
+fn exceeds(max: usize, actual: usize) -> bool {
+    max > 0 && actual > max
+}
+
+/// Measures the nesting depth on the raw text, so deeply nested payloads are rejected
+/// before the recursive parser sees them.
+fn raw_depth(raw: &str) -> usize {
+    let mut depth = 0usize;
+    let mut max_depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for byte in raw.bytes() {
+        if in_string {
+            match byte {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+
+        match byte {
+            b'"' => in_string = true,
+            b'{' | b'[' => {
+                depth += 1;
+                max_depth = max_depth.max(depth);
+            }
+            b'}' | b']' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+
+    max_depth
+}
+
+fn escape_pointer(segment: &str) -> String {
+    segment.replace('~', "~0").replace('/', "~1")
+}
+
+/// Walks the parsed value without recursion and reports the first object or array that
+/// exceeds its limit.
+fn check_containers(value: &Value, limits: &JsonLimits) -> Option<JsonGuardViolation> {
+    let mut stack = vec![(String::new(), value)];
+
+    while let Some((path, value)) = stack.pop() {
+        match value {
+            Value::Object(map) => {
+                if exceeds(limits.max_keys, map.len()) {
+                    return Some(JsonGuardViolation::new(
+                        JsonLimit::Keys,
+                        path,
+                        limits.max_keys,
+                        map.len(),
+                    ));
+                }
+                for (key, child) in map.iter().rev() {
+                    stack.push((format!("{path}/{}", escape_pointer(key)), child));
+                }
+            }
+            Value::Array(items) => {
+                if exceeds(limits.max_array_length, items.len()) {
+                    return Some(JsonGuardViolation::new(
+                        JsonLimit::ArrayLength,
+                        path,
+                        limits.max_array_length,
+                        items.len(),
+                    ));
+                }
+                for (index, child) in items.iter().enumerate().rev() {
+                    stack.push((format!("{path}/{index}"), child));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Parses `raw` only if it stays within all limits.
+pub fn guard_json(raw: &str, limits: &JsonLimits) -> Result<Value, JsonGuardViolation> {
+    if exceeds(limits.max_bytes, raw.len()) {
+        return Err(JsonGuardViolation::new(
+            JsonLimit::Bytes,
+            String::new(),
+            limits.max_bytes,
+            raw.len(),
+        ));
+    }
+
+    let depth = raw_depth(raw);
+    if exceeds(limits.max_depth, depth) {
+        return Err(JsonGuardViolation::new(
+            JsonLimit::Depth,
+            String::new(),
+            limits.max_depth,
+            depth,
+        ));
+    }
+
+    let value: Value = json::from_str(raw).map_err(|e| JsonGuardViolation {
+        limit: JsonLimit::Syntax,
+        path: String::new(),
+        max: 0,
+        actual: 0,
+        message: format!("Invalid JSON: {e}"),
+    })?;
+
+    match check_containers(&value, limits) {
+        Some(violation) => Err(violation),
+        None => Ok(value),
+    }
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct GuardJsonNode {}
+
+impl GuardJsonNode {
+    pub fn new() -> Self {
+        GuardJsonNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for GuardJsonNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "guard_json",
+            "Guard JSON",
+            "Parses untrusted JSON only if it stays within size, depth, key and array limits. Use it on webhook payloads before they reach other nodes",
+            "Utils/JSON",
+        );
+        node.add_icon("/flow/icons/shield.svg");
+
+        node.add_input_pin(
+            "exec_in",
+            "Input",
+            "Initiate Execution",
+            VariableType::Execution,
+        );
+
+        node.add_input_pin(
+            "json_string",
+            "JSON String",
+            "Raw JSON payload",
+            VariableType::String,
+        );
+
+        node.add_input_pin(
+            "max_bytes",
+            "Max Bytes",
+            "Maximum payload size in bytes, 0 disables the limit",
+            VariableType::Integer,
+        )
+        .set_default_value(Some(json!(1_048_576)));
+
+        node.add_input_pin(
+            "max_depth",
+            "Max Depth",
+            "Maximum nesting of objects and arrays",
+            VariableType::Integer,
+        )
+        .set_options(PinOptions::new().set_range((1., 128.)).build())
+        .set_default_value(Some(json!(32)));
+
+        node.add_input_pin(
+            "max_keys",
+            "Max Keys",
+            "Maximum number of keys in a single object, 0 disables the limit",
+            VariableType::Integer,
+        )
+        .set_default_value(Some(json!(1000)));
+
+        node.add_input_pin(
+            "max_array_length",
+            "Max Array Length",
+            "Maximum number of items in a single array, 0 disables the limit",
+            VariableType::Integer,
+        )
+        .set_default_value(Some(json!(10_000)));
+
+        node.add_output_pin(
+            "exec_out",
+            "Accepted",
+            "Triggers if the payload is valid JSON within all limits",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "rejected",
+            "Rejected",
+            "Triggers if the payload is invalid or exceeds a limit",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "result",
+            "Result",
+            "The parsed JSON, null if rejected",
+            VariableType::Struct,
+        );
+
+        node.add_output_pin(
+            "reason",
+            "Reason",
+            "Which limit was exceeded and where, null if accepted",
+            VariableType::Struct,
+        )
+        .set_schema::<JsonGuardViolation>();
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+        context.deactivate_exec_pin("rejected").await?;
+
+        let json_string: String = context.evaluate_pin("json_string").await?;
+        let limits = JsonLimits {
+            max_bytes: context.evaluate_pin("max_bytes").await.unwrap_or(1_048_576),
+            // Zero is not allowed for the depth, the parser would otherwise recurse unbounded
+            max_depth: context
+                .evaluate_pin::<usize>("max_depth")
+                .await
+                .unwrap_or(32)
+                .clamp(1, 128),
+            max_keys: context.evaluate_pin("max_keys").await.unwrap_or(1000),
+            max_array_length: context
+                .evaluate_pin("max_array_length")
+                .await
+                .unwrap_or(10_000),
+        };
+
+        match guard_json(&json_string, &limits) {
+            Ok(value) => {
+                context.set_pin_value("result", value).await?;
+                context.set_pin_value("reason", Value::Null).await?;
+                context.activate_exec_pin("exec_out").await?;
+            }
+            Err(violation) => {
+                context.set_pin_value("result", Value::Null).await?;
+                context.set_pin_value("reason", json!(violation)).await?;
+                context.activate_exec_pin("rejected").await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIMITS: JsonLimits = JsonLimits {
+        max_bytes: 1024,
+        max_depth: 4,
+        max_keys: 3,
+        max_array_length: 5,
+    };
+
+    fn rejected(raw: &str) -> JsonGuardViolation {
+        guard_json(raw, &LIMITS).unwrap_err()
+    }
+
+    #[test]
+    fn test_accepts_payload_within_limits() {
+        let raw = r#"{"event":"push","commits":[{"id":"a"},{"id":"b"}],"ok":true}"#;
+        assert_eq!(
+            guard_json(raw, &LIMITS).unwrap(),
+            json!({ "event": "push", "commits": [{ "id": "a" }, { "id": "b" }], "ok": true })
+        );
+    }
+
+    #[test]
+    fn test_rejects_each_limit() {
+        let violation = rejected(&format!(r#""{}""#, "x".repeat(2000)));
+        assert_eq!(violation.limit, JsonLimit::Bytes);
+        assert_eq!(violation.actual, 2002);
+
+        let violation = rejected(r#"{"a":{"b":{"c":{"d":{"e":1}}}}}"#);
+        assert_eq!(violation.limit, JsonLimit::Depth);
+        assert_eq!(violation.actual, 5);
+
+        let violation = rejected(r#"{"user":{"a":1,"b":2,"c":3,"d":4}}"#);
+        assert_eq!(violation.limit, JsonLimit::Keys);
+        assert_eq!(violation.path, "/user");
+        assert_eq!(violation.actual, 4);
+
+        let violation = rejected(r#"{"items":[[1],[1,2,3,4,5,6]]}"#);
+        assert_eq!(violation.limit, JsonLimit::ArrayLength);
+        assert_eq!(violation.path, "/items/1");
+
+        assert_eq!(rejected(r#"{"a":"#).limit, JsonLimit::Syntax);
+    }
+
+    #[test]
+    fn test_depth_ignores_brackets_in_strings() {
+        assert_eq!(raw_depth(r#"{"a":"[[[[{{{{\"]]]"}"#), 1);
+        assert_eq!(raw_depth(r#"[[1],[[2]]]"#), 3);
+        assert_eq!(raw_depth("42"), 0);
+    }
+
+    #[test]
+    fn test_deep_nesting_is_rejected_before_parsing() {
+        let raw = format!("{}{}", "[".repeat(100_000), "]".repeat(100_000));
+        let limits = JsonLimits {
+            max_bytes: 0,
+            ..LIMITS
+        };
+        assert_eq!(
+            guard_json(&raw, &limits).unwrap_err().limit,
+            JsonLimit::Depth
+        );
+    }
+
+    #[test]
+    fn test_zero_disables_limits() {
+        let limits = JsonLimits {
+            max_bytes: 0,
+            max_depth: 0,
+            max_keys: 0,
+            max_array_length: 0,
+        };
+        let raw = json::to_string(&json!({ "items": (0..100).collect::<Vec<_>>() })).unwrap();
+        assert!(guard_json(&raw, &limits).is_ok());
+    }
+}
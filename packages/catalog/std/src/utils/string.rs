@@ -10,6 +10,7 @@ pub mod similarity;
 pub mod split;
 pub mod starts_with;
 pub mod template;
+pub mod template_context;
 pub mod to_lowercase;
 pub mod to_uppercase;
 pub mod trim;
@@ -1,6 +1,9 @@
 use flow_like::flow::{
     execution::context::ExecutionContext,
-    node::{Node, NodeLogic},
+    node::{
+        Node, NodeLogic,
+        validation::{ValidationContext, ValidationMessage},
+    },
     variable::VariableType,
 };
 use flow_like_types::{async_trait, json::json};
@@ -77,4 +80,101 @@ impl NodeLogic for StringReplaceNode {
             .await?;
         Ok(())
     }
+
+    async fn validate(
+        &self,
+        context: &ValidationContext<'_>,
+    ) -> flow_like_types::Result<Vec<ValidationMessage>> {
+        let mut messages: Vec<ValidationMessage> = ["string", "pattern"]
+            .into_iter()
+            .filter_map(|pin| context.require_input(pin))
+            .collect();
+
+        let is_regex = context.static_value::<bool>("is_regex").unwrap_or(false);
+        if let Some(pattern) = context.static_value::<String>("pattern")
+            && is_regex
+            && let Err(e) = Regex::new(&pattern)
+        {
+            messages.push(context.error(Some("pattern"), format!("Invalid regex pattern: {}", e)));
+        }
+
+        Ok(messages)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flow_like::{
+        flow::{board::Board, node::validation::ValidationSeverity},
+        state::{FlowLikeConfig, FlowLikeState},
+        utils::http::HTTPClient,
+    };
+    use flow_like_storage::object_store::path::Path;
+    use flow_like_types::tokio;
+    use std::sync::Arc;
+
+    fn board() -> Board {
+        let state = FlowLikeState::new(FlowLikeConfig::new(), HTTPClient::new_without_refetch());
+        Board::new(None, Path::from("boards"), Arc::new(state))
+    }
+
+    fn placed_node(pattern: &str, is_regex: bool) -> Node {
+        let mut node = StringReplaceNode::new().get_node();
+        for (name, value) in [
+            ("string", json!("hello world")),
+            ("pattern", json!(pattern)),
+            ("replacement", json!("")),
+            ("is_regex", json!(is_regex)),
+        ] {
+            node.get_pin_mut_by_name(name)
+                .unwrap()
+                .set_default_value(Some(value));
+        }
+        node
+    }
+
+    #[tokio::test]
+    async fn test_invalid_regex_is_reported_on_pattern_pin() {
+        let board = board();
+        let node = placed_node("(unclosed", true);
+        let messages = StringReplaceNode::new()
+            .validate(&ValidationContext::new(&node, &board))
+            .await
+            .unwrap();
+
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].severity, ValidationSeverity::Error);
+        assert_eq!(
+            messages[0].pin_id.as_deref(),
+            Some(node.get_pin_by_name("pattern").unwrap().id.as_str())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_plain_pattern_is_not_compiled() {
+        let board = board();
+        let node = placed_node("(unclosed", false);
+        let messages = StringReplaceNode::new()
+            .validate(&ValidationContext::new(&node, &board))
+            .await
+            .unwrap();
+
+        assert!(messages.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_unconnected_string_without_default_is_required() {
+        let board = board();
+        let mut node = placed_node("world", false);
+        node.get_pin_mut_by_name("string").unwrap().default_value = None;
+        let messages = StringReplaceNode::new()
+            .validate(&ValidationContext::new(&node, &board))
+            .await
+            .unwrap();
+
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].node_id, node.id);
+        assert!(messages[0].message.contains("String"));
+    }
 }
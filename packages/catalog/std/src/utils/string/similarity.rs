@@ -5,3 +5,4 @@ pub mod jaro_winkler;
 pub mod levenshtein;
 pub mod optimal_string_alignment;
 pub mod sorensen_dice;
+pub mod spell_correct;
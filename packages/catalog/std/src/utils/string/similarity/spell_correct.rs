@@ -0,0 +1,268 @@
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic},
+    pin::{PinOptions, ValueType},
+    variable::VariableType,
+};
+use flow_like_types::{async_trait, json::json};
+
+/// Common words per language, ordered by frequency so more common words win ties.
+const WORDS_EN: &str = include_str!("./assets/words_en.txt");
+const WORDS_DE: &str = include_str!("./assets/words_de.txt");
+const WORDS_FR: &str = include_str!("./assets/words_fr.txt");
+const WORDS_ES: &str = include_str!("./assets/words_es.txt");
+
+fn builtin_words(language: &str) -> Option<&'static str> {
+    match language {
+        "English" => Some(WORDS_EN),
+        "German" => Some(WORDS_DE),
+        "French" => Some(WORDS_FR),
+        "Spanish" => Some(WORDS_ES),
+        _ => None,
+    }
+}
+
+/// Finds the dictionary term closest to `word` within `max_distance` edits.
+/// Ties are resolved by dictionary order.
+pub fn closest_term<'a>(
+    word: &str,
+    dictionary: impl IntoIterator<Item = &'a str>,
+    max_distance: usize,
+    case_sensitive: bool,
+) -> Option<(&'a str, usize)> {
+    let normalize = |value: &str| match case_sensitive {
+        true => value.to_string(),
+        false => value.to_lowercase(),
+    };
+
+    let word = normalize(word.trim());
+    let word_len = word.chars().count();
+    let mut best: Option<(&'a str, usize)> = None;
+
+    for term in dictionary {
+        let candidate = normalize(term.trim());
+        if candidate.is_empty() || candidate.chars().count().abs_diff(word_len) > max_distance {
+            continue;
+        }
+
+        let distance = strsim::levenshtein(&word, &candidate);
+        if distance <= max_distance && best.is_none_or(|(_, best)| distance < best) {
+            best = Some((term.trim(), distance));
+            if distance == 0 {
+                break;
+            }
+        }
+    }
+
+    best
+}
+
+/// Applies the capitalization of `original` to a lowercase built-in word.
+fn match_case(original: &str, term: &str) -> String {
+    let mut letters = original.chars().filter(|c| c.is_alphabetic());
+    let first_upper = letters.next().is_some_and(char::is_uppercase);
+
+    if first_upper && original.chars().count() > 1 && letters.all(char::is_uppercase) {
+        return term.to_uppercase();
+    }
+
+    if first_upper {
+        let mut chars = term.chars();
+        return match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => String::new(),
+        };
+    }
+
+    term.to_string()
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct SpellCorrectNode {}
+
+impl SpellCorrectNode {
+    pub fn new() -> Self {
+        SpellCorrectNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for SpellCorrectNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "spell_correct",
+            "Spell Correct",
+            "Replaces a word with the closest term of a dictionary by Levenshtein distance. Falls back to a built-in list of common words if no dictionary is given",
+            "Utils/String/Similarity",
+        );
+        node.add_icon("/flow/icons/distance.svg");
+
+        node.add_input_pin("word", "Word", "Word to correct", VariableType::String)
+            .set_default_value(Some(json!("")));
+
+        node.add_input_pin(
+            "dictionary",
+            "Dictionary",
+            "Canonical terms, e.g. product names. Leave empty to use the built-in word list",
+            VariableType::String,
+        )
+        .set_value_type(ValueType::Array)
+        .set_default_value(Some(json!([])));
+
+        node.add_input_pin(
+            "language",
+            "Language",
+            "Built-in word list used when the dictionary is empty",
+            VariableType::String,
+        )
+        .set_options(
+            PinOptions::new()
+                .set_valid_values(vec![
+                    "English".to_string(),
+                    "German".to_string(),
+                    "French".to_string(),
+                    "Spanish".to_string(),
+                ])
+                .build(),
+        )
+        .set_default_value(Some(json!("English")));
+
+        node.add_input_pin(
+            "max_distance",
+            "Max Distance",
+            "Maximum number of edits between the word and a dictionary term",
+            VariableType::Integer,
+        )
+        .set_options(PinOptions::new().set_range((0., 10.)).build())
+        .set_default_value(Some(json!(2)));
+
+        node.add_input_pin(
+            "case_sensitive",
+            "Case Sensitive",
+            "Count differences in letter case as edits",
+            VariableType::Boolean,
+        )
+        .set_default_value(Some(json!(false)));
+
+        node.add_output_pin(
+            "corrected",
+            "Corrected",
+            "Closest dictionary term, or the original word if none is close enough",
+            VariableType::String,
+        );
+
+        node.add_output_pin(
+            "distance",
+            "Distance",
+            "Edits between the word and the returned term",
+            VariableType::Integer,
+        );
+
+        node.add_output_pin(
+            "found",
+            "Found",
+            "True if a dictionary term was within the maximum distance",
+            VariableType::Boolean,
+        );
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        let word: String = context.evaluate_pin("word").await?;
+        let dictionary: Vec<String> = context.evaluate_pin("dictionary").await.unwrap_or_default();
+        let language: String = context.evaluate_pin("language").await.unwrap_or_default();
+        let max_distance: usize = context.evaluate_pin("max_distance").await.unwrap_or(2);
+        let case_sensitive: bool = context
+            .evaluate_pin("case_sensitive")
+            .await
+            .unwrap_or(false);
+
+        let (corrected, distance) = if !dictionary.is_empty() {
+            closest_term(
+                &word,
+                dictionary.iter().map(String::as_str),
+                max_distance,
+                case_sensitive,
+            )
+            .map(|(term, distance)| (Some(term.to_string()), distance))
+            .unwrap_or((None, 0))
+        } else {
+            let words = builtin_words(&language).unwrap_or(WORDS_EN);
+            closest_term(&word, words.lines(), max_distance, false)
+                .map(|(term, distance)| (Some(match_case(word.trim(), term)), distance))
+                .unwrap_or((None, 0))
+        };
+
+        context
+            .set_pin_value("found", json!(corrected.is_some()))
+            .await?;
+        context
+            .set_pin_value("corrected", json!(corrected.unwrap_or(word)))
+            .await?;
+        context.set_pin_value("distance", json!(distance)).await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_closest_product_name() {
+        let catalog = ["iPhone 15 Pro", "iPad Air", "MacBook Pro"];
+
+        assert_eq!(
+            closest_term("iphone 15 pro", catalog, 2, false),
+            Some(("iPhone 15 Pro", 0))
+        );
+        assert_eq!(
+            closest_term("Macbok Pro", catalog, 2, false),
+            Some(("MacBook Pro", 1))
+        );
+        assert_eq!(closest_term("Surface Pro", catalog, 2, false), None);
+        assert_eq!(
+            closest_term("ipad air", catalog, 1, true),
+            None,
+            "case differences count as edits"
+        );
+    }
+
+    #[test]
+    fn test_ties_prefer_dictionary_order() {
+        assert_eq!(
+            closest_term("cat", ["bat", "hat"], 1, false),
+            Some(("bat", 1))
+        );
+    }
+
+    #[test]
+    fn test_builtin_word_lists() {
+        assert_eq!(
+            closest_term("teh", WORDS_EN.lines(), 2, false),
+            Some(("the", 2))
+        );
+        assert_eq!(
+            closest_term("recieve", WORDS_EN.lines(), 2, false),
+            Some(("receive", 2))
+        );
+        assert_eq!(
+            closest_term("vieleicht", WORDS_DE.lines(), 1, false),
+            Some(("vielleicht", 1))
+        );
+        for language in ["English", "German", "French", "Spanish"] {
+            assert!(builtin_words(language).is_some_and(|words| words.lines().count() > 100));
+        }
+    }
+
+    #[test]
+    fn test_match_case() {
+        assert_eq!(match_case("Teh", "the"), "The");
+        assert_eq!(match_case("TEH", "the"), "THE");
+        assert_eq!(match_case("teh", "the"), "the");
+        assert_eq!(match_case("I", "i"), "I");
+    }
+}
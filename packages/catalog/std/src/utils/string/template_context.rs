@@ -0,0 +1,241 @@
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic},
+    pin::PinOptions,
+    variable::VariableType,
+};
+use flow_like_types::{
+    Cacheable, Value, async_trait,
+    json::json,
+    minijinja::{self, AutoEscape, Environment, UndefinedBehavior},
+};
+use std::sync::Arc;
+
+const TEMPLATE_NAME: &str = "template";
+
+/// A parsed template together with the environment settings it was compiled with
+struct CompiledTemplate {
+    env: Environment<'static>,
+}
+
+impl Cacheable for CompiledTemplate {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl CompiledTemplate {
+    fn compile(template: &str, undefined: &str, escape_html: bool) -> Result<Self, String> {
+        let mut env = Environment::new();
+        env.set_undefined_behavior(match undefined {
+            "strict" => UndefinedBehavior::Strict,
+            "chainable" => UndefinedBehavior::Chainable,
+            _ => UndefinedBehavior::Lenient,
+        });
+        env.set_auto_escape_callback(move |_| {
+            if escape_html {
+                AutoEscape::Html
+            } else {
+                AutoEscape::None
+            }
+        });
+        env.add_template_owned(TEMPLATE_NAME, template.to_string())
+            .map_err(|error| error.to_string())?;
+        Ok(CompiledTemplate { env })
+    }
+
+    fn render(&self, data: &Value) -> Result<String, String> {
+        self.env
+            .get_template(TEMPLATE_NAME)
+            .and_then(|template| template.render(minijinja::Value::from_serialize(data)))
+            .map_err(|error| error.to_string())
+    }
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct RenderTemplateNode {}
+
+impl RenderTemplateNode {
+    pub fn new() -> Self {
+        RenderTemplateNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for RenderTemplateNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "string_render_template_context",
+            "Render Template With Context",
+            "Renders a Jinja template with the fields of a struct, including loops, conditions and filters like upper, default, join or tojson",
+            "Utils/String",
+        );
+        node.add_icon("/flow/icons/string.svg");
+
+        node.add_input_pin("exec_in", "Input", "Trigger", VariableType::Execution);
+        node.add_input_pin(
+            "template",
+            "Template",
+            "Jinja template, e.g. 'Hello {{ user.name }}'",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("")));
+        node.add_input_pin(
+            "context",
+            "Context",
+            "Data the template can access",
+            VariableType::Struct,
+        )
+        .set_default_value(Some(json!({})));
+        node.add_input_pin(
+            "undefined",
+            "Undefined",
+            "How missing variables render: lenient as empty, chainable also allows missing attributes, strict fails",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("lenient")))
+        .set_options(
+            PinOptions::new()
+                .set_valid_values(vec![
+                    "lenient".into(),
+                    "chainable".into(),
+                    "strict".into(),
+                ])
+                .build(),
+        );
+        node.add_input_pin(
+            "escape_html",
+            "Escape HTML",
+            "Escapes HTML in inserted values, mark trusted values with the 'safe' filter",
+            VariableType::Boolean,
+        )
+        .set_default_value(Some(json!(true)));
+
+        node.add_output_pin("exec_out", "Output", "", VariableType::Execution);
+        node.add_output_pin(
+            "rendered",
+            "Rendered",
+            "Rendered string",
+            VariableType::String,
+        );
+        node.add_output_pin(
+            "error",
+            "Error",
+            "Triggers on a template syntax error or, in strict mode, an undefined variable",
+            VariableType::Execution,
+        );
+        node.add_output_pin(
+            "error_message",
+            "Error Message",
+            "Why the template could not be rendered",
+            VariableType::String,
+        );
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+        context.deactivate_exec_pin("error").await?;
+
+        let template: String = context.evaluate_pin("template").await?;
+        let data: Value = context.evaluate_pin("context").await?;
+        let undefined: String = context.evaluate_pin("undefined").await?;
+        let escape_html: bool = context.evaluate_pin("escape_html").await?;
+
+        // Loops render the same template over and over, so it is parsed once per run
+        let cache_key = format!("render_template:{}:{}:{}", undefined, escape_html, template);
+        let compiled = match context.get_cached::<CompiledTemplate>(&cache_key).await {
+            Some(compiled) => Ok(compiled),
+            None => match CompiledTemplate::compile(&template, &undefined, escape_html) {
+                Ok(compiled) => {
+                    let compiled = Arc::new(compiled);
+                    context.set_cache(&cache_key, compiled.clone()).await;
+                    Ok(compiled)
+                }
+                Err(error) => Err(error),
+            },
+        };
+
+        let rendered = compiled.and_then(|compiled| compiled.render(&data));
+
+        match rendered {
+            Ok(rendered) => {
+                context.set_pin_value("rendered", json!(rendered)).await?;
+                context.activate_exec_pin("exec_out").await?;
+            }
+            Err(error) => {
+                context.set_pin_value("error_message", json!(error)).await?;
+                context.activate_exec_pin("error").await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(template: &str, data: Value) -> Result<String, String> {
+        CompiledTemplate::compile(template, "lenient", true)?.render(&data)
+    }
+
+    #[test]
+    fn test_substitutes_variables() {
+        let rendered = render(
+            "Hello {{ user.name }}, you are {{ user.age }}",
+            json!({ "user": { "name": "Ada", "age": 36 } }),
+        );
+        assert_eq!(rendered.unwrap(), "Hello Ada, you are 36");
+    }
+
+    #[test]
+    fn test_renders_loops() {
+        let rendered = render(
+            "{% for item in items %}{{ loop.index }}:{{ item }}{% if not loop.last %},{% endif %}{% endfor %}",
+            json!({ "items": ["a", "b", "c"] }),
+        );
+        assert_eq!(rendered.unwrap(), "1:a,2:b,3:c");
+    }
+
+    #[test]
+    fn test_applies_filters() {
+        let rendered = render(
+            "{{ name | upper }} {{ missing | default('none') }}",
+            json!({ "name": "ada" }),
+        );
+        assert_eq!(rendered.unwrap(), "ADA none");
+    }
+
+    #[test]
+    fn test_escapes_html_unless_disabled() {
+        let data = json!({ "html": "<b>" });
+        assert_eq!(render("{{ html }}", data.clone()).unwrap(), "&lt;b&gt;");
+
+        let raw = CompiledTemplate::compile("{{ html }}", "lenient", false).unwrap();
+        assert_eq!(raw.render(&data).unwrap(), "<b>");
+    }
+
+    #[test]
+    fn test_reports_syntax_errors() {
+        let error = CompiledTemplate::compile("{% for item in items %}", "lenient", true)
+            .err()
+            .unwrap();
+        assert!(error.contains("syntax error"), "{}", error);
+    }
+
+    #[test]
+    fn test_undefined_behavior() {
+        assert_eq!(render("[{{ missing }}]", json!({})).unwrap(), "[]");
+
+        let strict = CompiledTemplate::compile("[{{ missing }}]", "strict", true).unwrap();
+        assert!(strict.render(&json!({})).is_err());
+    }
+}
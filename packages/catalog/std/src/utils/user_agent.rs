@@ -0,0 +1,477 @@
+/// # User Agent
+/// Classifies HTTP `User-Agent` strings into browser, operating system and device type.
+/// Unknown agents are reported as `Other` instead of failing.
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic},
+    variable::VariableType,
+};
+use flow_like_types::{
+    async_trait,
+    json::{Deserialize, Serialize, json},
+};
+use schemars::JsonSchema;
+
+const OTHER: &str = "Other";
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq)]
+pub struct UserAgentInfo {
+    /// Browser or client name, e.g. `Chrome`, `Firefox` or `Googlebot`
+    pub browser: String,
+    pub browser_version: Option<String>,
+    /// Operating system, e.g. `Windows`, `macOS`, `iOS` or `Android`
+    pub os: String,
+    pub os_version: Option<String>,
+    /// One of `desktop`, `mobile`, `tablet`, `tv`, `console`, `bot` or `other`
+    pub device_type: String,
+    pub is_bot: bool,
+    pub is_mobile: bool,
+}
+
+/// Known crawlers and automated clients whose names don't contain `bot`, `crawler` or `spider`.
+const AUTOMATED_CLIENTS: [&str; 14] = [
+    "Slurp",
+    "facebookexternalhit",
+    "ia_archiver",
+    "HeadlessChrome",
+    "Lighthouse",
+    "curl",
+    "Wget",
+    "python-requests",
+    "python-urllib",
+    "Go-http-client",
+    "okhttp",
+    "axios",
+    "PostmanRuntime",
+    "Apache-HttpClient",
+];
+
+/// Browsers checked in order, as most agents also claim to be Chrome and Safari.
+/// Each entry is the name and the markers its version follows.
+const BROWSERS: [(&str, &[&str]); 11] = [
+    ("Edge", &["Edg/", "EdgA/", "EdgiOS/", "Edge/"]),
+    ("Opera", &["OPR/", "OPiOS/", "Opera/"]),
+    ("Samsung Internet", &["SamsungBrowser/"]),
+    ("Yandex Browser", &["YaBrowser/"]),
+    ("Vivaldi", &["Vivaldi/"]),
+    ("UC Browser", &["UCBrowser/"]),
+    ("Firefox", &["Firefox/", "FxiOS/"]),
+    ("Internet Explorer", &["MSIE ", "Trident/"]),
+    ("Chromium", &["Chromium/"]),
+    ("Chrome", &["CriOS/", "Chrome/"]),
+    ("Safari", &["Version/", "Safari/"]),
+];
+
+/// Digits and dots following `marker`, with `_` read as `.` as in `Mac OS X 10_15_7`.
+fn version_after(ua: &str, marker: &str) -> Option<String> {
+    let start = ua.find(marker)? + marker.len();
+    let version: String = ua[start..]
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || *c == '.' || *c == '_')
+        .map(|c| if c == '_' { '.' } else { c })
+        .collect();
+    let version = version.trim_end_matches('.');
+    if version.is_empty() {
+        None
+    } else {
+        Some(version.to_string())
+    }
+}
+
+/// Product token (`Name/1.0`) naming the bot, e.g. `Googlebot` in
+/// `Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)`.
+fn bot_name(ua: &str) -> Option<(String, Option<String>)> {
+    let lower = ua.to_ascii_lowercase();
+    if let Some((client, start)) = AUTOMATED_CLIENTS.iter().find_map(|client| {
+        lower
+            .find(&client.to_ascii_lowercase())
+            .map(|start| (client, start))
+    }) {
+        let version = version_after(&ua[start..], "/");
+        return Some((client.to_string(), version));
+    }
+
+    ua.split(|c: char| c.is_whitespace() || c == ';' || c == '(' || c == ')' || c == ',')
+        .filter(|token| !token.starts_with("http") && !token.starts_with('+'))
+        .find_map(|token| {
+            let (name, version) = token.split_once('/').unwrap_or((token, ""));
+            let lower = name.to_ascii_lowercase();
+            let is_bot = ["bot", "crawler", "spider"]
+                .iter()
+                .any(|marker| lower.contains(marker));
+            if !is_bot || name.is_empty() {
+                return None;
+            }
+            Some((name.to_string(), version_after(version, "")))
+        })
+}
+
+fn detect_browser(ua: &str) -> (String, Option<String>) {
+    for (name, markers) in BROWSERS {
+        for marker in markers {
+            if !ua.contains(marker) {
+                continue;
+            }
+            let version = match *marker {
+                // IE 11 dropped the MSIE token and reports its version as rv:11.0
+                "Trident/" => version_after(ua, "rv:"),
+                "Safari/" => None,
+                _ => version_after(ua, marker),
+            };
+            return (name.to_string(), version);
+        }
+    }
+    (OTHER.to_string(), None)
+}
+
+fn windows_version(nt: &str) -> String {
+    match nt {
+        "10.0" => "10",
+        "6.3" => "8.1",
+        "6.2" => "8",
+        "6.1" => "7",
+        "6.0" => "Vista",
+        "5.1" | "5.2" => "XP",
+        other => other,
+    }
+    .to_string()
+}
+
+fn detect_os(ua: &str) -> (String, Option<String>) {
+    if ua.contains("Windows Phone") {
+        return (
+            "Windows Phone".to_string(),
+            version_after(ua, "Windows Phone "),
+        );
+    }
+    if ua.contains("Windows") {
+        let version = version_after(ua, "Windows NT ").map(|nt| windows_version(&nt));
+        return ("Windows".to_string(), version);
+    }
+    if ua.contains("iPhone") || ua.contains("iPad") || ua.contains("iPod") {
+        let version = version_after(ua, "iPhone OS ").or_else(|| version_after(ua, "CPU OS "));
+        return ("iOS".to_string(), version);
+    }
+    if ua.contains("Android") {
+        return ("Android".to_string(), version_after(ua, "Android "));
+    }
+    if ua.contains("CrOS") {
+        return ("Chrome OS".to_string(), None);
+    }
+    if ua.contains("Mac OS X") || ua.contains("Macintosh") {
+        return ("macOS".to_string(), version_after(ua, "Mac OS X "));
+    }
+    if ua.contains("Linux") || ua.contains("X11") {
+        return ("Linux".to_string(), None);
+    }
+    (OTHER.to_string(), None)
+}
+
+fn detect_device(ua: &str, os: &str) -> &'static str {
+    const TV: [&str; 7] = [
+        "SmartTV", "SMART-TV", "AppleTV", "CrKey", "GoogleTV", "HbbTV", "BRAVIA",
+    ];
+    const CONSOLE: [&str; 3] = ["PlayStation", "Xbox", "Nintendo"];
+
+    if TV.iter().any(|marker| ua.contains(marker)) {
+        return "tv";
+    }
+    if CONSOLE.iter().any(|marker| ua.contains(marker)) {
+        return "console";
+    }
+    if ua.contains("iPad") || ua.contains("Tablet") || (os == "Android" && !ua.contains("Mobile")) {
+        return "tablet";
+    }
+    if ua.contains("Mobi") || ua.contains("iPhone") || ua.contains("iPod") || os == "Windows Phone"
+    {
+        return "mobile";
+    }
+    match os {
+        "Windows" | "macOS" | "Linux" | "Chrome OS" => "desktop",
+        _ => "other",
+    }
+}
+
+pub fn parse_user_agent(ua: &str) -> UserAgentInfo {
+    let ua = ua.trim();
+    let (os, os_version) = detect_os(ua);
+
+    if let Some((browser, browser_version)) = bot_name(ua) {
+        return UserAgentInfo {
+            browser,
+            browser_version,
+            os,
+            os_version,
+            device_type: "bot".to_string(),
+            is_bot: true,
+            is_mobile: false,
+        };
+    }
+
+    let (browser, browser_version) = detect_browser(ua);
+    let device_type = detect_device(ua, &os);
+    UserAgentInfo {
+        browser,
+        browser_version,
+        os,
+        os_version,
+        device_type: device_type.to_string(),
+        is_bot: false,
+        is_mobile: device_type == "mobile" || device_type == "tablet",
+    }
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct ParseUserAgentNode {}
+
+impl ParseUserAgentNode {
+    pub fn new() -> Self {
+        ParseUserAgentNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for ParseUserAgentNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "utils_parse_user_agent",
+            "Parse User Agent",
+            "Extracts browser, operating system, device type and bot status from a User-Agent header. Unknown agents are reported as Other",
+            "Utils/String",
+        );
+        node.add_icon("/flow/icons/browser.svg");
+
+        node.add_input_pin(
+            "user_agent",
+            "User Agent",
+            "Value of the User-Agent header",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("")));
+
+        node.add_output_pin(
+            "info",
+            "Info",
+            "Browser, OS and device with their versions",
+            VariableType::Struct,
+        )
+        .set_schema::<UserAgentInfo>();
+
+        node.add_output_pin(
+            "device_type",
+            "Device Type",
+            "desktop, mobile, tablet, tv, console, bot or other",
+            VariableType::String,
+        );
+
+        node.add_output_pin(
+            "is_bot",
+            "Is Bot",
+            "Whether the agent is a crawler or automated client",
+            VariableType::Boolean,
+        );
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        let user_agent: String = context.evaluate_pin("user_agent").await?;
+        let info = parse_user_agent(&user_agent);
+
+        context
+            .set_pin_value("device_type", json!(info.device_type))
+            .await?;
+        context.set_pin_value("is_bot", json!(info.is_bot)).await?;
+        context.set_pin_value("info", json!(info)).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(ua: &str) -> (String, Option<String>, String, Option<String>, String) {
+        let info = parse_user_agent(ua);
+        (
+            info.browser,
+            info.browser_version,
+            info.os,
+            info.os_version,
+            info.device_type,
+        )
+    }
+
+    fn expected(
+        browser: &str,
+        browser_version: Option<&str>,
+        os: &str,
+        os_version: Option<&str>,
+        device_type: &str,
+    ) -> (String, Option<String>, String, Option<String>, String) {
+        (
+            browser.to_string(),
+            browser_version.map(str::to_string),
+            os.to_string(),
+            os_version.map(str::to_string),
+            device_type.to_string(),
+        )
+    }
+
+    #[test]
+    fn test_desktop_browsers() {
+        assert_eq!(
+            summary(
+                "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/124.0.0.0 Safari/537.36"
+            ),
+            expected(
+                "Chrome",
+                Some("124.0.0.0"),
+                "Windows",
+                Some("10"),
+                "desktop"
+            )
+        );
+        assert_eq!(
+            summary(
+                "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/124.0.0.0 Safari/537.36 Edg/124.0.2478.51"
+            ),
+            expected(
+                "Edge",
+                Some("124.0.2478.51"),
+                "Windows",
+                Some("10"),
+                "desktop"
+            )
+        );
+        assert_eq!(
+            summary(
+                "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.4 Safari/605.1.15"
+            ),
+            expected("Safari", Some("17.4"), "macOS", Some("10.15.7"), "desktop")
+        );
+        assert_eq!(
+            summary(
+                "Mozilla/5.0 (X11; Ubuntu; Linux x86_64; rv:125.0) Gecko/20100101 Firefox/125.0"
+            ),
+            expected("Firefox", Some("125.0"), "Linux", None, "desktop")
+        );
+        assert_eq!(
+            summary("Mozilla/5.0 (Windows NT 6.1; WOW64; Trident/7.0; rv:11.0) like Gecko"),
+            expected(
+                "Internet Explorer",
+                Some("11.0"),
+                "Windows",
+                Some("7"),
+                "desktop"
+            )
+        );
+    }
+
+    #[test]
+    fn test_mobile_and_tablet() {
+        assert_eq!(
+            summary(
+                "Mozilla/5.0 (iPhone; CPU iPhone OS 17_4 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) CriOS/124.0.6367.88 Mobile/15E148 Safari/604.1"
+            ),
+            expected(
+                "Chrome",
+                Some("124.0.6367.88"),
+                "iOS",
+                Some("17.4"),
+                "mobile"
+            )
+        );
+        assert_eq!(
+            summary(
+                "Mozilla/5.0 (Linux; Android 14; SM-S918B) AppleWebKit/537.36 (KHTML, like Gecko) SamsungBrowser/24.0 Chrome/117.0.0.0 Mobile Safari/537.36"
+            ),
+            expected(
+                "Samsung Internet",
+                Some("24.0"),
+                "Android",
+                Some("14"),
+                "mobile"
+            )
+        );
+        assert_eq!(
+            summary(
+                "Mozilla/5.0 (iPad; CPU OS 16_6 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/16.6 Mobile/15E148 Safari/604.1"
+            ),
+            expected("Safari", Some("16.6"), "iOS", Some("16.6"), "tablet")
+        );
+        assert_eq!(
+            summary(
+                "Mozilla/5.0 (Linux; Android 13; SM-X700) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/124.0.0.0 Safari/537.36"
+            ),
+            expected("Chrome", Some("124.0.0.0"), "Android", Some("13"), "tablet")
+        );
+
+        let info = parse_user_agent(
+            "Mozilla/5.0 (Linux; Android 14) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/124.0.0.0 Mobile Safari/537.36",
+        );
+        assert!(info.is_mobile);
+        assert!(!info.is_bot);
+    }
+
+    #[test]
+    fn test_tv_and_console() {
+        assert_eq!(
+            parse_user_agent(
+                "Mozilla/5.0 (Windows NT 10.0; Win64; x64; Xbox; Xbox One) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/70.0.3538.102 Safari/537.36 Edge/18.19041"
+            )
+            .device_type,
+            "console"
+        );
+        assert_eq!(
+            parse_user_agent(
+                "Mozilla/5.0 (SMART-TV; Linux; Tizen 6.0) AppleWebKit/537.36 (KHTML, like Gecko) SamsungBrowser/4.0 Chrome/76.0.3809.146 TV Safari/537.36"
+            )
+            .device_type,
+            "tv"
+        );
+    }
+
+    #[test]
+    fn test_bots() {
+        let googlebot = parse_user_agent(
+            "Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)",
+        );
+        assert!(googlebot.is_bot);
+        assert_eq!(googlebot.browser, "Googlebot");
+        assert_eq!(googlebot.browser_version.as_deref(), Some("2.1"));
+        assert_eq!(googlebot.device_type, "bot");
+
+        let bing = parse_user_agent(
+            "Mozilla/5.0 AppleWebKit/537.36 (KHTML, like Gecko; compatible; bingbot/2.0; +http://www.bing.com/bingbot.htm) Chrome/116.0.1938.76 Safari/537.36",
+        );
+        assert_eq!(bing.browser, "bingbot");
+        assert!(bing.is_bot);
+
+        let curl = parse_user_agent("curl/8.4.0");
+        assert_eq!(curl.browser, "curl");
+        assert_eq!(curl.browser_version.as_deref(), Some("8.4.0"));
+        assert!(curl.is_bot);
+
+        let headless = parse_user_agent(
+            "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) HeadlessChrome/124.0.0.0 Safari/537.36",
+        );
+        assert_eq!(headless.browser, "HeadlessChrome");
+        assert_eq!(headless.os, "Linux");
+        assert!(headless.is_bot);
+    }
+
+    #[test]
+    fn test_unknown_agents_degrade_gracefully() {
+        for ua in ["", "   ", "SomethingNew", "Mozilla/5.0 (compatible)"] {
+            let info = parse_user_agent(ua);
+            assert_eq!(info.browser, OTHER);
+            assert_eq!(info.os, OTHER);
+            assert_eq!(info.device_type, "other");
+            assert_eq!(info.browser_version, None);
+            assert!(!info.is_bot);
+            assert!(!info.is_mobile);
+        }
+    }
+}
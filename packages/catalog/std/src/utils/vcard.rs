@@ -0,0 +1,601 @@
+/// # vCard
+/// Parsing and generation of vCard contact data (RFC 2426 / vCard 3.0 and RFC 6350 / vCard 4.0).
+/// The parser is lenient: unknown properties are ignored and vCard 2.1 style bare type
+/// parameters (e.g. `TEL;WORK;VOICE:`) are understood as well.
+use flow_like_types::{
+    anyhow,
+    json::{Deserialize, Serialize},
+};
+use schemars::JsonSchema;
+
+pub mod generate;
+pub mod parse;
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, Default, PartialEq)]
+pub struct VCardEntry {
+    pub value: String,
+    #[serde(default)]
+    pub types: Vec<String>,
+    #[serde(default)]
+    pub preferred: bool,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, Default, PartialEq)]
+pub struct VCardContact {
+    pub full_name: String,
+    #[serde(default)]
+    pub family_name: Option<String>,
+    #[serde(default)]
+    pub given_name: Option<String>,
+    #[serde(default)]
+    pub middle_name: Option<String>,
+    #[serde(default)]
+    pub prefix: Option<String>,
+    #[serde(default)]
+    pub suffix: Option<String>,
+    #[serde(default)]
+    pub emails: Vec<VCardEntry>,
+    #[serde(default)]
+    pub phones: Vec<VCardEntry>,
+    #[serde(default)]
+    pub organization: Option<String>,
+    #[serde(default)]
+    pub department: Option<String>,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub birthday: Option<String>,
+    #[serde(default)]
+    pub note: Option<String>,
+    #[serde(default)]
+    pub uid: Option<String>,
+    #[serde(default)]
+    pub version: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VCardVersion {
+    V3,
+    V4,
+}
+
+impl VCardVersion {
+    pub fn parse(version: &str) -> flow_like_types::Result<Self> {
+        match version.trim() {
+            "3.0" | "3" => Ok(VCardVersion::V3),
+            "4.0" | "4" => Ok(VCardVersion::V4),
+            other => Err(anyhow!(
+                "Unsupported vCard version '{}', expected 3.0 or 4.0",
+                other
+            )),
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            VCardVersion::V3 => "3.0",
+            VCardVersion::V4 => "4.0",
+        }
+    }
+}
+
+/// Joins folded lines (a line starting with a space or tab continues the previous one).
+fn unfold(input: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for raw in input.split('\n') {
+        let line = raw.strip_suffix('\r').unwrap_or(raw);
+        if let Some(continuation) = line.strip_prefix([' ', '\t'])
+            && let Some(last) = lines.last_mut()
+        {
+            last.push_str(continuation);
+            continue;
+        }
+        if !line.trim().is_empty() {
+            lines.push(line.to_string());
+        }
+    }
+    lines
+}
+
+fn unescape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+/// Splits on `separator` unless it is escaped with a backslash. Components stay escaped.
+fn split_unescaped(value: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut escaped = false;
+    for (idx, c) in value.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        if c == '\\' {
+            escaped = true;
+        } else if c == separator {
+            parts.push(&value[start..idx]);
+            start = idx + c.len_utf8();
+        }
+    }
+    parts.push(&value[start..]);
+    parts
+}
+
+/// Splits a content line into (property name, parameters, value).
+/// The name is upper-cased and stripped of any group prefix (`item1.EMAIL` -> `EMAIL`).
+fn split_content_line(line: &str) -> Option<(String, Vec<(String, String)>, &str)> {
+    let mut in_quotes = false;
+    let mut colon = None;
+    for (idx, c) in line.char_indices() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            ':' if !in_quotes => {
+                colon = Some(idx);
+                break;
+            }
+            _ => {}
+        }
+    }
+    let colon = colon?;
+    let (head, value) = (&line[..colon], &line[colon + 1..]);
+
+    let mut segments = head.split(';');
+    let name = segments.next()?.trim();
+    let name = name.rsplit('.').next().unwrap_or(name).to_ascii_uppercase();
+
+    let params = segments
+        .map(|segment| match segment.split_once('=') {
+            Some((key, value)) => (
+                key.trim().to_ascii_uppercase(),
+                value.trim().trim_matches('"').to_string(),
+            ),
+            // vCard 2.1 allows bare type parameters
+            None => ("TYPE".to_string(), segment.trim().to_string()),
+        })
+        .collect();
+
+    Some((name, params, value))
+}
+
+fn non_empty(value: &str) -> Option<String> {
+    let value = unescape(value).trim().to_string();
+    if value.is_empty() { None } else { Some(value) }
+}
+
+fn parse_entry(params: &[(String, String)], value: &str) -> VCardEntry {
+    let mut types = Vec::new();
+    let mut preferred = false;
+
+    for (key, param) in params {
+        match key.as_str() {
+            "TYPE" => {
+                for t in param.split(',') {
+                    let t = t.trim().to_ascii_lowercase();
+                    if t.is_empty() {
+                        continue;
+                    }
+                    if t == "pref" {
+                        preferred = true;
+                    } else if !types.contains(&t) {
+                        types.push(t);
+                    }
+                }
+            }
+            "PREF" => preferred = true,
+            _ => {}
+        }
+    }
+
+    let value = unescape(value);
+    let value = value
+        .strip_prefix("tel:")
+        .or_else(|| value.strip_prefix("mailto:"))
+        .unwrap_or(&value)
+        .trim()
+        .to_string();
+
+    VCardEntry {
+        value,
+        types,
+        preferred,
+    }
+}
+
+/// Parses every `BEGIN:VCARD ... END:VCARD` block in the input.
+pub fn parse_vcards(input: &str) -> flow_like_types::Result<Vec<VCardContact>> {
+    let mut contacts = Vec::new();
+    let mut current: Option<VCardContact> = None;
+
+    for line in unfold(input) {
+        let Some((name, params, value)) = split_content_line(&line) else {
+            continue;
+        };
+
+        match name.as_str() {
+            "BEGIN" if value.trim().eq_ignore_ascii_case("VCARD") => {
+                if current.is_some() {
+                    return Err(anyhow!("Nested BEGIN:VCARD is not allowed"));
+                }
+                current = Some(VCardContact::default());
+                continue;
+            }
+            "END" if value.trim().eq_ignore_ascii_case("VCARD") => {
+                let mut contact = current
+                    .take()
+                    .ok_or_else(|| anyhow!("END:VCARD without matching BEGIN:VCARD"))?;
+                if contact.full_name.is_empty() {
+                    contact.full_name = derive_full_name(&contact);
+                }
+                contacts.push(contact);
+                continue;
+            }
+            _ => {}
+        }
+
+        let Some(contact) = current.as_mut() else {
+            continue;
+        };
+
+        match name.as_str() {
+            "VERSION" => contact.version = non_empty(value),
+            "FN" => contact.full_name = non_empty(value).unwrap_or_default(),
+            "N" => {
+                let parts = split_unescaped(value, ';');
+                let part = |idx: usize| parts.get(idx).and_then(|p| non_empty(p));
+                contact.family_name = part(0);
+                contact.given_name = part(1);
+                contact.middle_name = part(2);
+                contact.prefix = part(3);
+                contact.suffix = part(4);
+            }
+            "EMAIL" => contact.emails.push(parse_entry(&params, value)),
+            "TEL" => contact.phones.push(parse_entry(&params, value)),
+            "ORG" => {
+                let parts = split_unescaped(value, ';');
+                contact.organization = parts.first().and_then(|p| non_empty(p));
+                contact.department = parts.get(1).and_then(|p| non_empty(p));
+            }
+            "TITLE" => contact.title = non_empty(value),
+            "URL" => contact.url = non_empty(value),
+            "BDAY" => contact.birthday = non_empty(value),
+            "NOTE" => contact.note = non_empty(value),
+            "UID" => contact.uid = non_empty(value),
+            _ => {}
+        }
+    }
+
+    if current.is_some() {
+        return Err(anyhow!("Unterminated vCard: missing END:VCARD"));
+    }
+
+    Ok(contacts)
+}
+
+fn derive_full_name(contact: &VCardContact) -> String {
+    [
+        &contact.prefix,
+        &contact.given_name,
+        &contact.middle_name,
+        &contact.family_name,
+        &contact.suffix,
+    ]
+    .iter()
+    .filter_map(|part| part.as_deref())
+    .collect::<Vec<_>>()
+    .join(" ")
+}
+
+fn escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            ',' => out.push_str("\\,"),
+            ';' => out.push_str("\\;"),
+            '\n' => out.push_str("\\n"),
+            '\r' => {}
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Folds a content line to at most 75 octets per line without splitting UTF-8 characters.
+fn fold(line: &str, out: &mut String) {
+    let mut width = 0;
+    for c in line.chars() {
+        let len = c.len_utf8();
+        if width + len > 75 {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += len;
+    }
+    out.push_str("\r\n");
+}
+
+fn entry_line(
+    property: &str,
+    entry: &VCardEntry,
+    version: VCardVersion,
+    uri_scheme: Option<&str>,
+) -> String {
+    let mut line = property.to_string();
+    let types: Vec<String> = entry
+        .types
+        .iter()
+        .map(|t| t.trim().to_ascii_lowercase())
+        .filter(|t| !t.is_empty() && t != "pref")
+        .collect();
+
+    match version {
+        VCardVersion::V3 => {
+            let mut types = types;
+            if entry.preferred {
+                types.push("pref".to_string());
+            }
+            if !types.is_empty() {
+                line.push_str(&format!(";TYPE={}", types.join(",")));
+            }
+            line.push(':');
+            line.push_str(&escape(&entry.value));
+        }
+        VCardVersion::V4 => {
+            if uri_scheme.is_some() {
+                line.push_str(";VALUE=uri");
+            }
+            if !types.is_empty() {
+                line.push_str(&format!(";TYPE={}", types.join(",")));
+            }
+            if entry.preferred {
+                line.push_str(";PREF=1");
+            }
+            line.push(':');
+            match uri_scheme {
+                Some(scheme) => {
+                    line.push_str(scheme);
+                    line.push_str(&entry.value.replace(' ', "-"));
+                }
+                None => line.push_str(&escape(&entry.value)),
+            }
+        }
+    }
+    line
+}
+
+/// Serializes contacts into a `.vcf` document using CRLF line endings.
+pub fn generate_vcards(
+    contacts: &[VCardContact],
+    version: VCardVersion,
+) -> flow_like_types::Result<String> {
+    let mut out = String::new();
+
+    for contact in contacts {
+        let full_name = if contact.full_name.trim().is_empty() {
+            derive_full_name(contact)
+        } else {
+            contact.full_name.clone()
+        };
+        if full_name.is_empty() {
+            return Err(anyhow!(
+                "Every contact needs a full name or at least one name component"
+            ));
+        }
+
+        let mut lines = vec![
+            "BEGIN:VCARD".to_string(),
+            format!("VERSION:{}", version.as_str()),
+            format!("FN:{}", escape(&full_name)),
+        ];
+
+        let has_name_parts = contact.family_name.is_some()
+            || contact.given_name.is_some()
+            || contact.middle_name.is_some()
+            || contact.prefix.is_some()
+            || contact.suffix.is_some();
+        // N is mandatory in 3.0 and optional in 4.0
+        if has_name_parts || version == VCardVersion::V3 {
+            let component = |part: &Option<String>| escape(part.as_deref().unwrap_or_default());
+            lines.push(format!(
+                "N:{};{};{};{};{}",
+                component(&contact.family_name),
+                component(&contact.given_name),
+                component(&contact.middle_name),
+                component(&contact.prefix),
+                component(&contact.suffix)
+            ));
+        }
+
+        for email in &contact.emails {
+            lines.push(entry_line("EMAIL", email, version, None));
+        }
+        for phone in &contact.phones {
+            lines.push(entry_line("TEL", phone, version, Some("tel:")));
+        }
+
+        if contact.organization.is_some() || contact.department.is_some() {
+            let mut org = escape(contact.organization.as_deref().unwrap_or_default());
+            if let Some(department) = &contact.department {
+                org.push(';');
+                org.push_str(&escape(department));
+            }
+            lines.push(format!("ORG:{}", org));
+        }
+
+        let optional = [
+            ("TITLE", &contact.title),
+            ("URL", &contact.url),
+            ("BDAY", &contact.birthday),
+            ("NOTE", &contact.note),
+            ("UID", &contact.uid),
+        ];
+        for (property, value) in optional {
+            if let Some(value) = value {
+                let value = if property == "URL" || property == "UID" {
+                    value.clone()
+                } else {
+                    escape(value)
+                };
+                lines.push(format!("{}:{}", property, value));
+            }
+        }
+
+        lines.push("END:VCARD".to_string());
+        for line in lines {
+            fold(&line, &mut out);
+        }
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const V3_CARD: &str = "BEGIN:VCARD\r\n\
+VERSION:3.0\r\n\
+N:Gump;Forrest;;Mr.;\r\n\
+FN:Forrest Gump\r\n\
+ORG:Bubba Gump Shrimp Co.;Sales\r\n\
+TITLE:Shrimp Man\r\n\
+TEL;TYPE=WORK,VOICE:(111) 555-1212\r\n\
+TEL;TYPE=HOME,VOICE,pref:(404) 555-1212\r\n\
+EMAIL;TYPE=PREF,INTERNET:forrestgump@example.com\r\n\
+NOTE:Line one\\nLine two\\, with comma\r\n\
+END:VCARD\r\n";
+
+    const V4_CARD: &str = "BEGIN:VCARD\r\n\
+VERSION:4.0\r\n\
+FN:Jane Doe\r\n\
+N:Doe;Jane;;;\r\n\
+item1.EMAIL;TYPE=work;PREF=1:jane@example.com\r\n\
+TEL;VALUE=uri;TYPE=cell:tel:+1-555-555-5555\r\n\
+NOTE:This note is folded acro\r\n ss two lines\r\n\
+END:VCARD\r\n";
+
+    #[test]
+    fn test_parse_v3() {
+        let contacts = parse_vcards(V3_CARD).unwrap();
+        assert_eq!(contacts.len(), 1);
+        let c = &contacts[0];
+        assert_eq!(c.full_name, "Forrest Gump");
+        assert_eq!(c.family_name.as_deref(), Some("Gump"));
+        assert_eq!(c.given_name.as_deref(), Some("Forrest"));
+        assert_eq!(c.prefix.as_deref(), Some("Mr."));
+        assert_eq!(c.organization.as_deref(), Some("Bubba Gump Shrimp Co."));
+        assert_eq!(c.department.as_deref(), Some("Sales"));
+        assert_eq!(c.phones.len(), 2);
+        assert_eq!(c.phones[0].types, vec!["work", "voice"]);
+        assert!(!c.phones[0].preferred);
+        assert!(c.phones[1].preferred);
+        assert_eq!(c.emails[0].value, "forrestgump@example.com");
+        assert!(c.emails[0].preferred);
+        assert_eq!(c.note.as_deref(), Some("Line one\nLine two, with comma"));
+    }
+
+    #[test]
+    fn test_parse_v4_with_groups_uris_and_folding() {
+        let contacts = parse_vcards(V4_CARD).unwrap();
+        let c = &contacts[0];
+        assert_eq!(c.version.as_deref(), Some("4.0"));
+        assert_eq!(c.emails[0].value, "jane@example.com");
+        assert_eq!(c.emails[0].types, vec!["work"]);
+        assert!(c.emails[0].preferred);
+        assert_eq!(c.phones[0].value, "+1-555-555-5555");
+        assert_eq!(
+            c.note.as_deref(),
+            Some("This note is folded across two lines")
+        );
+    }
+
+    #[test]
+    fn test_parse_multiple_and_v21_bare_types() {
+        let input = "BEGIN:VCARD\nVERSION:2.1\nN:Smith;John\nTEL;WORK;VOICE:123\nEND:VCARD\n\
+                     BEGIN:VCARD\nVERSION:3.0\nFN:Other\nEND:VCARD\n";
+        let contacts = parse_vcards(input).unwrap();
+        assert_eq!(contacts.len(), 2);
+        assert_eq!(contacts[0].full_name, "John Smith");
+        assert_eq!(contacts[0].phones[0].types, vec!["work", "voice"]);
+        assert_eq!(contacts[1].full_name, "Other");
+    }
+
+    #[test]
+    fn test_parse_unterminated_fails() {
+        assert!(parse_vcards("BEGIN:VCARD\nFN:Broken\n").is_err());
+    }
+
+    #[test]
+    fn test_round_trip_v3() {
+        let contacts = parse_vcards(V3_CARD).unwrap();
+        let generated = generate_vcards(&contacts, VCardVersion::V3).unwrap();
+        assert_eq!(parse_vcards(&generated).unwrap(), contacts);
+    }
+
+    #[test]
+    fn test_round_trip_v4() {
+        let contacts = parse_vcards(V3_CARD).unwrap();
+        let generated = generate_vcards(&contacts, VCardVersion::V4).unwrap();
+        let reparsed = parse_vcards(&generated).unwrap();
+        assert_eq!(reparsed[0].version.as_deref(), Some("4.0"));
+        assert_eq!(reparsed[0].full_name, contacts[0].full_name);
+        assert_eq!(reparsed[0].family_name, contacts[0].family_name);
+        assert_eq!(reparsed[0].emails, contacts[0].emails);
+        assert_eq!(reparsed[0].organization, contacts[0].organization);
+        assert_eq!(reparsed[0].note, contacts[0].note);
+        // tel: URIs replace spaces with visual separators
+        assert_eq!(reparsed[0].phones[0].value, "(111)-555-1212");
+        assert!(reparsed[0].phones[1].preferred);
+    }
+
+    #[test]
+    fn test_generate_version_specific_syntax() {
+        let contact = VCardContact {
+            full_name: "Jane Doe".to_string(),
+            phones: vec![VCardEntry {
+                value: "+1 555 0100".to_string(),
+                types: vec!["cell".to_string()],
+                preferred: true,
+            }],
+            ..Default::default()
+        };
+
+        let v3 = generate_vcards(std::slice::from_ref(&contact), VCardVersion::V3).unwrap();
+        assert!(v3.contains("N:;;;;\r\n"));
+        assert!(v3.contains("TEL;TYPE=cell,pref:+1 555 0100\r\n"));
+
+        let v4 = generate_vcards(&[contact], VCardVersion::V4).unwrap();
+        assert!(!v4.contains("\r\nN:"));
+        assert!(v4.contains("TEL;VALUE=uri;TYPE=cell;PREF=1:tel:+1-555-0100\r\n"));
+    }
+
+    #[test]
+    fn test_generate_folds_long_lines() {
+        let contact = VCardContact {
+            full_name: "Long".to_string(),
+            note: Some("ä".repeat(100)),
+            ..Default::default()
+        };
+        let generated = generate_vcards(&[contact.clone()], VCardVersion::V4).unwrap();
+        assert!(generated.split("\r\n").all(|line| line.len() <= 75));
+        let parsed = parse_vcards(&generated).unwrap();
+        assert_eq!(parsed[0].note, contact.note);
+    }
+}
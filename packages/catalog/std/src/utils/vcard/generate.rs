@@ -0,0 +1,76 @@
+use super::{VCardContact, VCardVersion, generate_vcards};
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic},
+    pin::{PinOptions, ValueType},
+    variable::VariableType,
+};
+use flow_like_types::{async_trait, json::json};
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct GenerateVCardNode {}
+
+impl GenerateVCardNode {
+    pub fn new() -> Self {
+        GenerateVCardNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for GenerateVCardNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "utils_vcard_generate",
+            "Generate vCard",
+            "Serializes contacts into vCard (.vcf) data",
+            "Utils/vCard",
+        );
+        node.add_icon("/flow/icons/struct.svg");
+
+        node.add_input_pin("exec_in", "Input", "", VariableType::Execution);
+        node.add_input_pin(
+            "contacts",
+            "Contacts",
+            "Contacts to serialize",
+            VariableType::Struct,
+        )
+        .set_schema::<VCardContact>()
+        .set_value_type(ValueType::Array)
+        .set_options(PinOptions::new().set_enforce_schema(true).build());
+        node.add_input_pin(
+            "version",
+            "Version",
+            "vCard version to generate",
+            VariableType::String,
+        )
+        .set_options(
+            PinOptions::new()
+                .set_valid_values(vec!["3.0".to_string(), "4.0".to_string()])
+                .build(),
+        )
+        .set_default_value(Some(json!("4.0")));
+
+        node.add_output_pin("exec_out", "Output", "", VariableType::Execution);
+        node.add_output_pin(
+            "vcard",
+            "vCard",
+            "Generated .vcf content",
+            VariableType::String,
+        );
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+
+        let contacts: Vec<VCardContact> = context.evaluate_pin("contacts").await?;
+        let version: String = context.evaluate_pin("version").await?;
+        let vcard = generate_vcards(&contacts, VCardVersion::parse(&version)?)?;
+
+        context.set_pin_value("vcard", json!(vcard)).await?;
+        context.activate_exec_pin("exec_out").await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,71 @@
+use super::{VCardContact, parse_vcards};
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic},
+    pin::ValueType,
+    variable::VariableType,
+};
+use flow_like_types::{async_trait, json::json};
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct ParseVCardNode {}
+
+impl ParseVCardNode {
+    pub fn new() -> Self {
+        ParseVCardNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for ParseVCardNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "utils_vcard_parse",
+            "Parse vCard",
+            "Parses vCard (.vcf) data into contacts. Supports vCard 2.1, 3.0 and 4.0 and files containing multiple cards.",
+            "Utils/vCard",
+        );
+        node.add_icon("/flow/icons/struct.svg");
+
+        node.add_input_pin("exec_in", "Input", "", VariableType::Execution);
+        node.add_input_pin(
+            "vcard",
+            "vCard",
+            "Content of a .vcf file",
+            VariableType::String,
+        );
+
+        node.add_output_pin("exec_out", "Output", "", VariableType::Execution);
+        node.add_output_pin(
+            "contacts",
+            "Contacts",
+            "Parsed contacts with name, emails, phones and organization",
+            VariableType::Struct,
+        )
+        .set_schema::<VCardContact>()
+        .set_value_type(ValueType::Array);
+        node.add_output_pin(
+            "count",
+            "Count",
+            "Number of parsed contacts",
+            VariableType::Integer,
+        );
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+
+        let vcard: String = context.evaluate_pin("vcard").await?;
+        let contacts = parse_vcards(&vcard)?;
+
+        context
+            .set_pin_value("count", json!(contacts.len()))
+            .await?;
+        context.set_pin_value("contacts", json!(contacts)).await?;
+        context.activate_exec_pin("exec_out").await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,99 @@
+//! Runs a board that calls another board through the Call Board node.
+
+#![cfg(feature = "execute")]
+
+mod common;
+
+use common::{connect, default_state, insert, new_board, results, run, set_default, template};
+use flow_like::{
+    flow::{board::Board, variable::VariableType},
+    state::FlowLikeState,
+};
+use flow_like_types::{Value, json::json, tokio};
+use std::sync::Arc;
+
+/// A board whose generic event has a `name` pin that is returned as the result.
+/// Returns the board id and the id of its start event.
+async fn save_child(state: &Arc<FlowLikeState>) -> (String, String) {
+    let mut board = new_board(state);
+
+    let mut event = template("events_generic");
+    event.add_output_pin("name", "Name", "", VariableType::String);
+    let event = insert(&mut board, event);
+    let ret = insert(&mut board, template("events_generic_return_result"));
+    connect(&mut board, (&event, "exec_out"), (&ret, "exec_in"));
+    connect(&mut board, (&event, "name"), (&ret, "response"));
+
+    board.save(None).await.unwrap();
+    (board.id.clone(), event)
+}
+
+/// A board that calls `callee` with `payload` and returns what the call returned.
+/// Returns the board and the id of its start event.
+async fn parent(
+    state: &Arc<FlowLikeState>,
+    callee: Option<(&str, &str)>,
+    payload: Value,
+) -> (Board, String) {
+    let mut board = new_board(state);
+
+    let event = insert(&mut board, template("events_simple"));
+    let mut call = template("control_call_board");
+    let (callee_board, callee_event) = match callee {
+        Some((callee_board, callee_event)) => (callee_board.to_string(), callee_event.to_string()),
+        None => (board.id.clone(), event.clone()),
+    };
+    set_default(&mut call, "board_id", json!(callee_board));
+    set_default(&mut call, "start_node", json!(callee_event));
+    set_default(&mut call, "payload", payload);
+    let call = insert(&mut board, call);
+    let ret = insert(&mut board, template("events_generic_return_result"));
+    connect(&mut board, (&event, "exec_out"), (&call, "exec_in"));
+    connect(&mut board, (&call, "exec_out"), (&ret, "exec_in"));
+    connect(&mut board, (&call, "result"), (&ret, "response"));
+
+    board.save(None).await.unwrap();
+    (board, event)
+}
+
+#[test]
+fn test_call_board_maps_inputs_and_result() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        let state = default_state().await;
+        let (child, child_event) = save_child(&state).await;
+        let (board, start) = parent(
+            &state,
+            Some((&child, &child_event)),
+            json!({ "name": "Ada" }),
+        )
+        .await;
+
+        let (events, failed) = run(&state, board, &start).await;
+
+        assert!(failed.is_empty(), "failed nodes: {:?}", failed);
+        // The child's result is consumed by the call, only the parent's own result is emitted
+        assert_eq!(results(&events), vec![json!("Ada")]);
+    });
+}
+
+#[test]
+fn test_call_board_rejects_cyclic_calls() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        let state = default_state().await;
+        let (board, start) = parent(&state, None, json!({})).await;
+        let call = board
+            .nodes
+            .values()
+            .find(|node| node.name == "control_call_board")
+            .unwrap()
+            .id
+            .clone();
+
+        let (events, failed) = run(&state, board, &start).await;
+
+        assert_eq!(failed, vec![call]);
+        assert!(results(&events).is_empty());
+    });
+}
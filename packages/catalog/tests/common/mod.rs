@@ -0,0 +1,176 @@
+//! Helpers to build boards in memory and run them.
+
+#![allow(dead_code)]
+
+use flow_like::{
+    flow::{
+        board::Board,
+        execution::{InternalRun, NodeErrorPolicy, RunPayload},
+        node::Node,
+    },
+    profile::Profile,
+    state::{FlowLikeConfig, FlowLikeState},
+    utils::http::HTTPClient,
+};
+use flow_like_storage::{Path, files::store::FlowLikeStore, object_store::memory::InMemory};
+use flow_like_types::{
+    Value,
+    intercom::{InterComCallback, InterComEvent},
+};
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+pub const APP_ID: &str = "test-app";
+
+type EventFuture = Pin<Box<dyn Future<Output = flow_like_types::Result<()>> + Send>>;
+
+pub async fn default_state() -> Arc<FlowLikeState> {
+    let mut config = FlowLikeConfig::new();
+    config.register_app_meta_store(FlowLikeStore::Other(Arc::new(InMemory::new())));
+    let state = Arc::new(FlowLikeState::new(
+        config,
+        HTTPClient::new_without_refetch(),
+    ));
+    let weak_ref = Arc::downgrade(&state);
+
+    {
+        let registry_guard = state.node_registry.clone();
+        let mut registry = registry_guard.write().await;
+        registry.initialize(weak_ref);
+        registry.push_nodes(flow_like_catalog::get_catalog());
+    }
+    state
+}
+
+pub fn template(name: &str) -> Node {
+    flow_like_catalog::get_catalog()
+        .into_iter()
+        .map(|logic| logic.get_node())
+        .find(|node| node.name == name)
+        .unwrap_or_else(|| panic!("node {} is not in the catalog", name))
+}
+
+pub fn set_default(node: &mut Node, pin: &str, value: Value) {
+    node.get_pin_mut_by_name(pin)
+        .unwrap()
+        .set_default_value(Some(value));
+}
+
+pub fn connect(board: &mut Board, from: (&str, &str), to: (&str, &str)) {
+    let from_pin = board.nodes[from.0]
+        .get_pin_by_name(from.1)
+        .unwrap()
+        .id
+        .clone();
+    let to_pin = board.nodes[to.0].get_pin_by_name(to.1).unwrap().id.clone();
+    board
+        .nodes
+        .get_mut(from.0)
+        .unwrap()
+        .get_pin_mut_by_name(from.1)
+        .unwrap()
+        .connected_to
+        .insert(to_pin.clone());
+    board
+        .nodes
+        .get_mut(to.0)
+        .unwrap()
+        .get_pin_mut_by_name(to.1)
+        .unwrap()
+        .depends_on
+        .insert(from_pin);
+}
+
+pub fn insert(board: &mut Board, node: Node) -> String {
+    let id = node.id.clone();
+    board.nodes.insert(id.clone(), node);
+    id
+}
+
+/// An empty board of the test app
+pub fn new_board(state: &Arc<FlowLikeState>) -> Board {
+    Board::new(None, Path::from("apps").child(APP_ID), state.clone())
+}
+
+/// The payloads of all `generic_result` events, in the order they were emitted
+pub fn results(events: &[InterComEvent]) -> Vec<Value> {
+    events
+        .iter()
+        .filter(|event| event.event_type == "generic_result")
+        .map(|event| event.payload.clone())
+        .collect()
+}
+
+/// Runs the board and returns the events it emitted and the nodes that failed
+pub async fn run(
+    state: &Arc<FlowLikeState>,
+    board: Board,
+    start: &str,
+) -> (Vec<InterComEvent>, Vec<String>) {
+    let (events, run) = run_to_end(state, board, start).await;
+    (events, run.node_report().failed)
+}
+
+/// Runs the board and returns the events it emitted and the finished run
+pub async fn run_to_end(
+    state: &Arc<FlowLikeState>,
+    board: Board,
+    start: &str,
+) -> (Vec<InterComEvent>, InternalRun) {
+    execute(state, board, start, None).await
+}
+
+/// Like [`run_to_end`], with `policy` deciding what happens after a node fails
+pub async fn run_with_policy(
+    state: &Arc<FlowLikeState>,
+    board: Board,
+    start: &str,
+    policy: NodeErrorPolicy,
+) -> (Vec<InterComEvent>, InternalRun) {
+    execute(state, board, start, Some(policy)).await
+}
+
+async fn execute(
+    state: &Arc<FlowLikeState>,
+    board: Board,
+    start: &str,
+    policy: Option<NodeErrorPolicy>,
+) -> (Vec<InterComEvent>, InternalRun) {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let sink = events.clone();
+    let callback: InterComCallback = Some(Arc::new(move |event: InterComEvent| -> EventFuture {
+        sink.lock().unwrap().push(event);
+        Box::pin(async { Ok(()) })
+    }));
+
+    let payload = RunPayload {
+        id: start.to_string(),
+        payload: None,
+        runtime_variables: None,
+        filter_secrets: Some(true),
+    };
+    let mut run = InternalRun::new(
+        APP_ID,
+        Arc::new(board),
+        None,
+        state,
+        &Profile::default(),
+        &payload,
+        false,
+        callback,
+        None,
+        None,
+        HashMap::new(),
+    )
+    .await
+    .unwrap();
+    if let Some(policy) = policy {
+        run.set_error_policy(policy);
+    }
+    run.execute(state.clone()).await;
+
+    let events = events.lock().unwrap().clone();
+    (events, run)
+}
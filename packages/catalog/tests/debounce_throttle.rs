@@ -0,0 +1,159 @@
+//! Triggers the same board in bursts of concurrent runs, the way a sink would, and checks
+//! which runs Debounce and Throttle let through for each key.
+
+#![cfg(feature = "execute")]
+
+mod common;
+
+use common::{connect, default_state, insert, new_board, results, run, set_default, template};
+use flow_like::{flow::board::Board, state::FlowLikeState};
+use flow_like_types::{Value, json::json, tokio};
+use std::{sync::Arc, time::Duration};
+
+/// A board of `event -> node -> return`, where the return emits `output` of the node.
+/// Returns the board, the start event and the node.
+fn gate_board(
+    state: &Arc<FlowLikeState>,
+    node: &str,
+    inputs: &[(&str, Value)],
+    output: &str,
+) -> (Board, String, String) {
+    let mut board = new_board(state);
+
+    let event = insert(&mut board, template("events_simple"));
+    let mut gate = template(node);
+    for (pin, value) in inputs {
+        set_default(&mut gate, pin, value.clone());
+    }
+    let gate = insert(&mut board, gate);
+    let ret = insert(&mut board, template("events_generic_return_result"));
+    connect(&mut board, (&event, "exec_out"), (&gate, "exec_in"));
+    connect(&mut board, (&gate, "exec_out"), (&ret, "exec_in"));
+    connect(&mut board, (&gate, output), (&ret, "response"));
+
+    (board, event, gate)
+}
+
+/// Starts one run per `(key, offset_ms)` at its offset and returns what every run emitted,
+/// in the order of `triggers`
+async fn burst(
+    state: &Arc<FlowLikeState>,
+    board: &Board,
+    start: &str,
+    gate: &str,
+    triggers: &[(&str, u64)],
+) -> Vec<Vec<Value>> {
+    let handles: Vec<_> = triggers
+        .iter()
+        .map(|(key, offset_ms)| {
+            let state = state.clone();
+            let start = start.to_string();
+            let offset = Duration::from_millis(*offset_ms);
+            let mut board = board.clone();
+            set_default(board.nodes.get_mut(gate).unwrap(), "key", json!(key));
+
+            tokio::spawn(async move {
+                tokio::time::sleep(offset).await;
+                let (events, failed) = run(&state, board, &start).await;
+                assert!(failed.is_empty(), "failed nodes: {:?}", failed);
+                results(&events)
+            })
+        })
+        .collect();
+
+    let mut emitted = Vec::new();
+    for handle in handles {
+        emitted.push(handle.await.unwrap());
+    }
+    emitted
+}
+
+#[test]
+fn test_debounce_fires_once_per_burst_and_key() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        let state = default_state().await;
+        let (board, start, debounce) = gate_board(
+            &state,
+            "control_debounce",
+            &[("wait_ms", json!(250.0))],
+            "count",
+        );
+
+        let emitted = burst(
+            &state,
+            &board,
+            &start,
+            &debounce,
+            &[("a", 0), ("b", 20), ("a", 40), ("a", 80), ("a", 700)],
+        )
+        .await;
+
+        // Only the last trigger of the burst on "a" fires, "b" is not held back by it
+        assert_eq!(
+            emitted,
+            vec![
+                vec![],
+                vec![json!(1)],
+                vec![],
+                vec![json!(3)],
+                vec![json!(1)],
+            ]
+        );
+    });
+}
+
+#[test]
+fn test_throttle_passes_once_per_interval_and_key() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        let state = default_state().await;
+        let (board, start, throttle) = gate_board(
+            &state,
+            "control_throttle",
+            &[("interval_ms", json!(250.0))],
+            "suppressed",
+        );
+
+        let emitted = burst(
+            &state,
+            &board,
+            &start,
+            &throttle,
+            &[("a", 0), ("a", 40), ("b", 60), ("a", 80), ("a", 400)],
+        )
+        .await;
+
+        // The first trigger per key passes, the next pass reports what was suppressed
+        assert_eq!(
+            emitted,
+            vec![
+                vec![json!(0)],
+                vec![],
+                vec![json!(0)],
+                vec![],
+                vec![json!(2)],
+            ]
+        );
+    });
+}
+
+#[test]
+fn test_state_is_separate_per_board() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        let state = default_state().await;
+        let inputs = [("interval_ms", json!(60000.0))];
+        let (first, first_start, _) = gate_board(&state, "control_throttle", &inputs, "suppressed");
+        let (second, second_start, _) =
+            gate_board(&state, "control_throttle", &inputs, "suppressed");
+
+        let (events, _) = run(&state, first.clone(), &first_start).await;
+        assert_eq!(results(&events), vec![json!(0)]);
+        let (events, _) = run(&state, first, &first_start).await;
+        assert!(results(&events).is_empty());
+
+        let (events, _) = run(&state, second, &second_start).await;
+        assert_eq!(results(&events), vec![json!(0)]);
+    });
+}
@@ -0,0 +1,141 @@
+//! Runs the fixture board of the flow benchmark with and without deterministic ordering.
+
+#![cfg(feature = "execute")]
+
+use flow_like::{
+    flow::{
+        board::Board,
+        execution::{InternalRun, RunPayload},
+    },
+    profile::Profile,
+    state::{FlowLikeConfig, FlowLikeState},
+    utils::http::HTTPClient,
+};
+use flow_like_storage::{
+    Path,
+    files::store::{FlowLikeStore, local_store::LocalObjectStore},
+};
+use flow_like_types::{intercom::BufferedInterComHandler, tokio};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+const BOARD_ID: &str = "o4wqrpzkx1cp4svxe91yordw";
+const START_ID: &str = "ek4tee4s3nufw3drfnwd20hw";
+const APP_ID: &str = "q99s8hb4z56mpwz8dscz7qmz";
+
+async fn default_state() -> Arc<FlowLikeState> {
+    let mut config = FlowLikeConfig::new();
+    let store = LocalObjectStore::new(PathBuf::from("../../tests")).unwrap();
+    let store = FlowLikeStore::Local(Arc::new(store));
+    config.register_bits_store(store.clone());
+    config.register_user_store(store.clone());
+    config.register_app_storage_store(store.clone());
+    config.register_app_meta_store(store);
+    let (http_client, _refetch_rx) = HTTPClient::new();
+    let state = Arc::new(FlowLikeState::new(config, http_client));
+    let weak_ref = Arc::downgrade(&state);
+
+    {
+        let registry_guard = state.node_registry.clone();
+        let mut registry = registry_guard.write().await;
+        registry.initialize(weak_ref);
+        registry.push_nodes(flow_like_catalog::get_catalog());
+    }
+    state
+}
+
+/// What a run produced: the emitted event types and the nodes in the order they executed
+#[derive(Debug, PartialEq)]
+struct Recording {
+    events: Vec<String>,
+    nodes: Vec<String>,
+}
+
+async fn run_board(state: Arc<FlowLikeState>, deterministic: bool) -> Recording {
+    let board = Board::load(
+        Path::from("flow").child(APP_ID),
+        BOARD_ID,
+        state.clone(),
+        None,
+    )
+    .await
+    .unwrap();
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let sink = events.clone();
+    let handler = BufferedInterComHandler::new(
+        Arc::new(move |batch| {
+            let sink = sink.clone();
+            Box::pin(async move {
+                let mut sink = sink.lock().unwrap();
+                sink.extend(batch.into_iter().map(|event| event.event_type));
+                Ok(())
+            })
+        }),
+        Some(100),
+        Some(400),
+        Some(true),
+    );
+
+    let payload = RunPayload {
+        id: START_ID.to_string(),
+        payload: None,
+        runtime_variables: None,
+        filter_secrets: Some(true),
+    };
+    let mut run = InternalRun::new(
+        "deterministic",
+        Arc::new(board),
+        None,
+        &state,
+        &Profile::default(),
+        &payload,
+        true,
+        handler.into_callback(),
+        None,
+        None,
+        HashMap::new(),
+    )
+    .await
+    .unwrap();
+    run.set_deterministic(deterministic);
+    run.execute(state).await;
+    handler.flush().await.unwrap();
+
+    let nodes = run
+        .get_traces()
+        .await
+        .into_iter()
+        .map(|trace| trace.node_id)
+        .collect();
+    let events = events.lock().unwrap().clone();
+    Recording { events, nodes }
+}
+
+#[test]
+fn test_deterministic_runs_are_reproducible() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let state = rt.block_on(default_state());
+
+    let first = rt.block_on(run_board(state.clone(), true));
+    let second = rt.block_on(run_board(state, true));
+
+    assert!(!first.nodes.is_empty());
+    assert_eq!(first, second);
+}
+
+#[test]
+fn test_parallel_run_executes_the_same_nodes() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let state = rt.block_on(default_state());
+
+    let deterministic = rt.block_on(run_board(state.clone(), true));
+    let parallel = rt.block_on(run_board(state, false));
+
+    let mut expected = deterministic.nodes;
+    let mut actual = parallel.nodes;
+    expected.sort();
+    actual.sort();
+    assert_eq!(expected, actual);
+}
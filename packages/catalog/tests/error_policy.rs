@@ -0,0 +1,152 @@
+//! Runs a diamond board with one failing branch under every node error policy.
+
+#![cfg(feature = "execute")]
+
+mod common;
+
+use common::{
+    connect, default_state, insert, new_board, results, run_to_end, run_with_policy, set_default,
+    template,
+};
+use flow_like::{
+    flow::{board::Board, execution::NodeErrorPolicy},
+    state::FlowLikeState,
+};
+use flow_like_types::{json::json, tokio};
+use std::sync::Arc;
+
+/// Node ids of the diamond: start -> b1 -> b2 -> join and start -> c1 -> c2 -> join
+struct Diamond {
+    start: String,
+    b1: String,
+    b2: String,
+    c1: String,
+    c2: String,
+    join: String,
+}
+
+/// Inserts an assert node that fails unless `holds`
+fn assertion(board: &mut Board, holds: bool) -> String {
+    let mut assert = template("control_assert");
+    set_default(
+        &mut assert,
+        "expression",
+        json!(if holds { "1 < 2" } else { "1 > 2" }),
+    );
+    set_default(&mut assert, "message", json!("branch failed"));
+    insert(board, assert)
+}
+
+/// A diamond whose `b1` node fails, the join returns "joined" when it runs
+fn diamond(state: &Arc<FlowLikeState>) -> (Board, Diamond) {
+    let mut board = new_board(state);
+
+    let start = insert(&mut board, template("events_simple"));
+    let b1 = assertion(&mut board, false);
+    let b2 = assertion(&mut board, true);
+    let c1 = assertion(&mut board, true);
+    let c2 = assertion(&mut board, true);
+    let mut ret = template("events_generic_return_result");
+    set_default(&mut ret, "response", json!("joined"));
+    let join = insert(&mut board, ret);
+
+    connect(&mut board, (&start, "exec_out"), (&b1, "exec_in"));
+    connect(&mut board, (&start, "exec_out"), (&c1, "exec_in"));
+    connect(&mut board, (&b1, "exec_out"), (&b2, "exec_in"));
+    connect(&mut board, (&c1, "exec_out"), (&c2, "exec_in"));
+    connect(&mut board, (&b2, "exec_out"), (&join, "exec_in"));
+    connect(&mut board, (&c2, "exec_out"), (&join, "exec_in"));
+
+    (
+        board,
+        Diamond {
+            start,
+            b1,
+            b2,
+            c1,
+            c2,
+            join,
+        },
+    )
+}
+
+fn sorted(ids: &[&String]) -> Vec<String> {
+    let mut ids: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+    ids.sort();
+    ids
+}
+
+#[test]
+fn test_abort_stops_the_healthy_branch() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        let state = default_state().await;
+        let (board, ids) = diamond(&state);
+
+        let (events, run) =
+            run_with_policy(&state, board, &ids.start, NodeErrorPolicy::Abort).await;
+        let report = run.node_report();
+
+        assert!(results(&events).is_empty(), "{:?}", results(&events));
+        assert_eq!(report.failed, vec![ids.b1.clone()]);
+        assert!(!report.succeeded.contains(&ids.c2));
+        for skipped in [&ids.b2, &ids.c2, &ids.join] {
+            assert!(report.skipped.contains(skipped), "{:?}", report);
+        }
+    });
+}
+
+#[test]
+fn test_continue_branch_skips_the_join() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        let state = default_state().await;
+        let (board, ids) = diamond(&state);
+
+        let (events, run) =
+            run_with_policy(&state, board, &ids.start, NodeErrorPolicy::ContinueBranch).await;
+        let report = run.node_report();
+
+        assert!(results(&events).is_empty(), "{:?}", results(&events));
+        assert_eq!(report.failed, vec![ids.b1.clone()]);
+        assert_eq!(report.succeeded, sorted(&[&ids.start, &ids.c1, &ids.c2]));
+        assert_eq!(report.skipped, sorted(&[&ids.b2, &ids.join]));
+    });
+}
+
+#[test]
+fn test_continue_all_runs_the_join_through_the_healthy_branch() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        let state = default_state().await;
+        let (board, ids) = diamond(&state);
+
+        let (events, run) =
+            run_with_policy(&state, board, &ids.start, NodeErrorPolicy::ContinueAll).await;
+        let report = run.node_report();
+
+        assert_eq!(results(&events), vec![json!("joined")]);
+        assert_eq!(report.failed, vec![ids.b1.clone()]);
+        assert_eq!(
+            report.succeeded,
+            sorted(&[&ids.start, &ids.c1, &ids.c2, &ids.join])
+        );
+        assert_eq!(report.skipped, vec![ids.b2.clone()]);
+    });
+}
+
+#[test]
+fn test_runs_default_to_continue_all() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        let state = default_state().await;
+        let (board, ids) = diamond(&state);
+
+        let (events, run) = run_to_end(&state, board, &ids.start).await;
+
+        assert_eq!(NodeErrorPolicy::default(), NodeErrorPolicy::ContinueAll);
+        assert_eq!(run.error_policy(), NodeErrorPolicy::ContinueAll);
+        assert_eq!(results(&events), vec![json!("joined")]);
+        assert_eq!(run.node_report().failed, vec![ids.b1.clone()]);
+    });
+}
@@ -0,0 +1,113 @@
+//! Runs While Loop and For Each boards to check how and when they stop.
+
+#![cfg(feature = "execute")]
+
+mod common;
+
+use common::{connect, default_state, insert, new_board, results, run, set_default, template};
+use flow_like::{
+    flow::{board::Board, node::Node},
+    state::FlowLikeState,
+};
+use flow_like_types::{json::json, tokio};
+use std::sync::Arc;
+
+/// A board that starts `looping` and returns its `iterations` once it is done.
+/// Returns the board, the start event and the id of the loop node.
+fn loop_board(state: &Arc<FlowLikeState>, looping: Node) -> (Board, String, String) {
+    let mut board = new_board(state);
+
+    let event = insert(&mut board, template("events_simple"));
+    let looping = insert(&mut board, looping);
+    let ret = insert(&mut board, template("events_generic_return_result"));
+    connect(&mut board, (&event, "exec_out"), (&looping, "exec_in"));
+    connect(&mut board, (&looping, "done"), (&ret, "exec_in"));
+    connect(&mut board, (&looping, "iterations"), (&ret, "response"));
+
+    (board, event, looping)
+}
+
+/// Lets the body of `looping` trigger its `break` pin once `index_pin` reaches `at`
+fn break_at(board: &mut Board, looping: &str, index_pin: &str, at: i64) {
+    let mut reached = template("int_greater_than_or_equal");
+    set_default(&mut reached, "integer2", json!(at));
+    let reached = insert(board, reached);
+    let branch = insert(board, template("control_branch"));
+    connect(board, (looping, index_pin), (&reached, "integer1"));
+    connect(
+        board,
+        (&reached, "greater_than_or_equal"),
+        (&branch, "condition"),
+    );
+    connect(board, (looping, "exec_out"), (&branch, "exec_in"));
+    connect(board, (&branch, "true"), (looping, "break"));
+}
+
+#[test]
+fn test_while_loop_stops_at_max_iterations() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        let state = default_state().await;
+        let mut looping = template("control_while_loop");
+        set_default(&mut looping, "condition", json!(true));
+        set_default(&mut looping, "max_iter", json!(5));
+        let (board, start, _) = loop_board(&state, looping);
+
+        let (events, failed) = run(&state, board, &start).await;
+
+        assert!(failed.is_empty(), "failed nodes: {:?}", failed);
+        assert_eq!(results(&events), vec![json!(5)]);
+    });
+}
+
+#[test]
+fn test_while_loop_breaks_early() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        let state = default_state().await;
+        let mut looping = template("control_while_loop");
+        set_default(&mut looping, "condition", json!(true));
+        set_default(&mut looping, "max_iter", json!(100));
+        let (mut board, start, looping) = loop_board(&state, looping);
+        break_at(&mut board, &looping, "iter", 2);
+
+        let (events, failed) = run(&state, board, &start).await;
+
+        assert!(failed.is_empty(), "failed nodes: {:?}", failed);
+        // The iteration that requested the break still counts
+        assert_eq!(results(&events), vec![json!(3)]);
+    });
+}
+
+#[test]
+fn test_for_each_breaks_early() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        let state = default_state().await;
+        let mut looping = template("control_for_each");
+        set_default(&mut looping, "array", json!(["a", "b", "c", "d"]));
+        let (mut board, start, looping) = loop_board(&state, looping);
+        break_at(&mut board, &looping, "index", 1);
+
+        let (events, failed) = run(&state, board, &start).await;
+
+        assert!(failed.is_empty(), "failed nodes: {:?}", failed);
+        assert_eq!(results(&events), vec![json!(2)]);
+    });
+}
+
+#[test]
+fn test_for_each_over_empty_array() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        let state = default_state().await;
+        let mut looping = template("control_for_each");
+        set_default(&mut looping, "array", json!([]));
+        let (board, start, _) = loop_board(&state, looping);
+
+        let (events, failed) = run(&state, board, &start).await;
+
+        assert!(failed.is_empty(), "failed nodes: {:?}", failed);
+        assert_eq!(results(&events), vec![json!(0)]);
+    });
+}
@@ -0,0 +1,73 @@
+//! Runs a board of delays and checks the per node timings the run collects.
+
+#![cfg(feature = "execute")]
+
+mod common;
+
+use common::{connect, default_state, insert, new_board, run_to_end, set_default, template};
+use flow_like_types::{json::json, tokio};
+use std::time::Instant;
+
+#[test]
+fn test_timings_cover_every_executed_node() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        let state = default_state().await;
+        let mut board = new_board(&state);
+
+        let event = insert(&mut board, template("events_simple"));
+        let mut slow = template("delay");
+        set_default(&mut slow, "time", json!(60.0));
+        let slow = insert(&mut board, slow);
+        let mut fast = template("delay");
+        set_default(&mut fast, "time", json!(10.0));
+        let fast = insert(&mut board, fast);
+        let mut ret = template("events_generic_return_result");
+        set_default(&mut ret, "response", json!("done"));
+        let ret = insert(&mut board, ret);
+        connect(&mut board, (&event, "exec_out"), (&slow, "exec_in"));
+        connect(&mut board, (&slow, "exec_out"), (&fast, "exec_in"));
+        connect(&mut board, (&fast, "exec_out"), (&ret, "exec_in"));
+
+        let started = Instant::now();
+        let (_, run) = run_to_end(&state, board, &event).await;
+        let wall_ms = started.elapsed().as_secs_f64() * 1000.0;
+
+        let report = run.node_report();
+        assert!(
+            report.failed.is_empty(),
+            "failed nodes: {:?}",
+            report.failed
+        );
+
+        let timings = run.node_timings();
+        let mut timed: Vec<String> = timings.keys().cloned().collect();
+        let mut executed = vec![event.clone(), slow.clone(), fast.clone(), ret.clone()];
+        timed.sort();
+        executed.sort();
+        assert_eq!(timed, executed);
+        assert_eq!(timed, report.succeeded);
+
+        for timing in timings.values() {
+            assert_eq!(
+                (timing.executions, timing.succeeded, timing.failed),
+                (1, 1, 0)
+            );
+            assert_eq!(timing.max_ms, timing.total_ms);
+            assert!(timing.wait_ms >= 0.0);
+        }
+
+        assert_eq!(timings[&slow].node_type, "delay");
+        assert!(timings[&slow].total_ms >= 60.0, "{:?}", timings[&slow]);
+        assert!(timings[&fast].total_ms >= 10.0, "{:?}", timings[&fast]);
+        assert!(timings[&slow].total_ms > timings[&fast].total_ms);
+
+        let total: f64 = timings.values().map(|timing| timing.total_ms).sum();
+        assert!(
+            total <= wall_ms,
+            "nodes took {}ms of a {}ms run",
+            total,
+            wall_ms
+        );
+    });
+}
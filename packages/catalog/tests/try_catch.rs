@@ -0,0 +1,145 @@
+//! Runs boards with Try Catch nodes whose Try branch succeeds, fails or rethrows.
+
+#![cfg(feature = "execute")]
+
+mod common;
+
+use common::{connect, default_state, insert, new_board, results, run, set_default, template};
+use flow_like::{flow::board::Board, state::FlowLikeState};
+use flow_like_types::{Value, json::json, tokio};
+use std::sync::Arc;
+
+/// Inserts an assert node that fails with `message` unless `holds`
+fn assertion(board: &mut Board, holds: bool, message: &str) -> String {
+    let mut assert = template("control_assert");
+    set_default(
+        &mut assert,
+        "expression",
+        json!(if holds { "1 < 2" } else { "1 > 2" }),
+    );
+    set_default(&mut assert, "message", json!(message));
+    insert(board, assert)
+}
+
+/// Inserts a return node that emits `value` when it runs
+fn emit(board: &mut Board, value: Value) -> String {
+    let mut ret = template("events_generic_return_result");
+    set_default(&mut ret, "response", value);
+    insert(board, ret)
+}
+
+/// A board that runs an assertion in a Try branch. Catch returns the caught message,
+/// Finally returns "finally". Returns the board, the start event and the Try Catch node.
+fn try_board(state: &Arc<FlowLikeState>, holds: bool, with_catch: bool) -> (Board, String, String) {
+    let mut board = new_board(state);
+
+    let event = insert(&mut board, template("events_simple"));
+    let try_catch = insert(&mut board, template("control_try_catch"));
+    let assert = assertion(&mut board, holds, "boom");
+    let try_done = emit(&mut board, json!("try"));
+    let finally = emit(&mut board, json!("finally"));
+    connect(&mut board, (&event, "exec_out"), (&try_catch, "exec_in"));
+    connect(&mut board, (&try_catch, "try"), (&assert, "exec_in"));
+    connect(&mut board, (&assert, "exec_out"), (&try_done, "exec_in"));
+    connect(&mut board, (&try_catch, "finally"), (&finally, "exec_in"));
+
+    if with_catch {
+        let caught = emit(&mut board, Value::Null);
+        connect(&mut board, (&try_catch, "catch"), (&caught, "exec_in"));
+        connect(&mut board, (&try_catch, "message"), (&caught, "response"));
+    }
+
+    (board, event, try_catch)
+}
+
+#[test]
+fn test_successful_try_skips_catch() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        let state = default_state().await;
+        let (board, start, _) = try_board(&state, true, true);
+
+        let (events, failed) = run(&state, board, &start).await;
+
+        assert!(failed.is_empty(), "failed nodes: {:?}", failed);
+        assert_eq!(results(&events), vec![json!("try"), json!("finally")]);
+    });
+}
+
+#[test]
+fn test_failed_try_takes_catch_with_message() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        let state = default_state().await;
+        let (board, start, _) = try_board(&state, false, true);
+
+        let (events, failed) = run(&state, board, &start).await;
+
+        assert!(failed.is_empty(), "failed nodes: {:?}", failed);
+        let results = results(&events);
+        assert_eq!(results.len(), 2, "{:?}", results);
+        assert!(
+            results[0]
+                .as_str()
+                .unwrap()
+                .contains("Assertion failed: boom"),
+            "{:?}",
+            results[0]
+        );
+        assert_eq!(results[1], json!("finally"));
+    });
+}
+
+#[test]
+fn test_uncaught_error_fails_board_after_finally() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        let state = default_state().await;
+        let (board, start, try_catch) = try_board(&state, false, false);
+
+        let (events, failed) = run(&state, board, &start).await;
+
+        assert_eq!(failed, vec![try_catch]);
+        assert_eq!(results(&events), vec![json!("finally")]);
+    });
+}
+
+#[test]
+fn test_nested_catch_rethrows_to_outer() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        let state = default_state().await;
+        let mut board = new_board(&state);
+
+        let event = insert(&mut board, template("events_simple"));
+        let outer = insert(&mut board, template("control_try_catch"));
+        let inner = insert(&mut board, template("control_try_catch"));
+        let failing = assertion(&mut board, false, "inner");
+        let rethrow = assertion(&mut board, false, "rethrown");
+        let inner_finally = emit(&mut board, json!("inner finally"));
+        let outer_caught = emit(&mut board, Value::Null);
+        let outer_finally = emit(&mut board, json!("outer finally"));
+
+        connect(&mut board, (&event, "exec_out"), (&outer, "exec_in"));
+        connect(&mut board, (&outer, "try"), (&inner, "exec_in"));
+        connect(&mut board, (&inner, "try"), (&failing, "exec_in"));
+        connect(&mut board, (&inner, "catch"), (&rethrow, "exec_in"));
+        connect(&mut board, (&inner, "finally"), (&inner_finally, "exec_in"));
+        connect(&mut board, (&outer, "catch"), (&outer_caught, "exec_in"));
+        connect(&mut board, (&outer, "message"), (&outer_caught, "response"));
+        connect(&mut board, (&outer, "finally"), (&outer_finally, "exec_in"));
+
+        let (events, failed) = run(&state, board, &event).await;
+
+        assert!(failed.is_empty(), "failed nodes: {:?}", failed);
+        let results = results(&events);
+        assert_eq!(results.len(), 3, "{:?}", results);
+        assert_eq!(results[0], json!("inner finally"));
+        assert!(
+            results[1].as_str().unwrap().contains("rethrown"),
+            "{:?}",
+            results[1]
+        );
+        assert_eq!(results[2], json!("outer finally"));
+    });
+}
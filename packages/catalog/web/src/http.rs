@@ -1 +1 @@
-
+pub mod graphql;
@@ -0,0 +1,651 @@
+use std::collections::HashMap;
+
+use flow_like::flow::{
+    execution::{LogLevel, context::ExecutionContext},
+    node::{Node, NodeLogic},
+    pin::{PinOptions, ValueType},
+    variable::VariableType,
+};
+use flow_like_types::{
+    Value, async_trait, bail,
+    json::{self, Map, json},
+    reqwest,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::web::api::{
+    HttpBody, HttpRequest,
+    graphql_paginate::{extract_page, find_connection, graphql_request},
+};
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq)]
+pub struct GraphQLError {
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<Vec<Value>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locations: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extensions: Option<Value>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct GraphQLResponse {
+    pub status: u16,
+    pub data: Value,
+    pub errors: Vec<GraphQLError>,
+    /// Aggregated connection nodes, only filled when following pagination
+    pub items: Vec<Value>,
+    pub pages: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct GraphQLOperation {
+    pub query: String,
+    pub variables: Map<String, Value>,
+    pub operation_name: Option<String>,
+    /// Send the query hash first and the full query only if the server does not know it
+    /// (Apollo automatic persisted queries).
+    pub persisted: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct GraphQLPagination {
+    pub connection_path: String,
+    pub cursor_variable: String,
+    pub max_pages: usize,
+}
+
+pub fn persisted_query_hash(query: &str) -> String {
+    format!("{:x}", Sha256::digest(query.as_bytes()))
+}
+
+fn is_persisted_query_not_found(errors: &[GraphQLError]) -> bool {
+    errors.iter().any(|error| {
+        error.message == "PersistedQueryNotFound"
+            || error
+                .extensions
+                .as_ref()
+                .and_then(|extensions| extensions.get("code"))
+                .and_then(Value::as_str)
+                == Some("PERSISTED_QUERY_NOT_FOUND")
+    })
+}
+
+impl GraphQLOperation {
+    fn body(&self, include_query: bool) -> Value {
+        let mut body = Map::new();
+        if include_query {
+            body.insert("query".to_string(), json!(self.query));
+        }
+        body.insert("variables".to_string(), json!(self.variables));
+        if let Some(operation_name) = &self.operation_name {
+            body.insert("operationName".to_string(), json!(operation_name));
+        }
+        if self.persisted {
+            body.insert(
+                "extensions".to_string(),
+                json!({
+                    "persistedQuery": {
+                        "version": 1,
+                        "sha256Hash": persisted_query_hash(&self.query),
+                    }
+                }),
+            );
+        }
+        Value::Object(body)
+    }
+}
+
+/// Sends one GraphQL request. GraphQL errors are returned alongside the data, only
+/// transport failures without a GraphQL response body are turned into an error.
+async fn send(
+    client: &reqwest::Client,
+    request: &HttpRequest,
+    body: Value,
+) -> flow_like_types::Result<GraphQLResponse> {
+    let mut request = request.clone();
+    request.set_body(HttpBody::Json(body));
+    let response = request.trigger(client).await?;
+
+    let body = response
+        .to_json()
+        .ok()
+        .filter(|body| body.get("data").is_some() || body.get("errors").is_some());
+
+    let Some(mut body) = body else {
+        if response.is_success() {
+            bail!("GraphQL response contains neither data nor errors");
+        }
+        bail!(
+            "GraphQL request failed with status {}: {}",
+            response.status_code,
+            response.to_text().unwrap_or_default()
+        );
+    };
+
+    let errors = match body.get_mut("errors").map(Value::take) {
+        Some(Value::Null) | None => vec![],
+        Some(errors) => json::from_value(errors)?,
+    };
+
+    Ok(GraphQLResponse {
+        status: response.status_code,
+        data: body.get_mut("data").map(Value::take).unwrap_or(Value::Null),
+        errors,
+        items: vec![],
+        pages: 1,
+    })
+}
+
+pub async fn execute(
+    client: &reqwest::Client,
+    request: &HttpRequest,
+    operation: &GraphQLOperation,
+) -> flow_like_types::Result<GraphQLResponse> {
+    if !operation.persisted {
+        return send(client, request, operation.body(true)).await;
+    }
+
+    let response = send(client, request, operation.body(false)).await?;
+    if !is_persisted_query_not_found(&response.errors) {
+        return Ok(response);
+    }
+
+    // The server registers the hash together with the full query on this request
+    send(client, request, operation.body(true)).await
+}
+
+/// Follows `pageInfo.hasNextPage` of a connection until the last page or `max_pages`.
+/// `data` holds the last page, `errors` the errors of all pages.
+pub async fn execute_paginated(
+    client: &reqwest::Client,
+    request: &HttpRequest,
+    operation: &GraphQLOperation,
+    pagination: &GraphQLPagination,
+) -> flow_like_types::Result<GraphQLResponse> {
+    let mut operation = operation.clone();
+    let mut result = GraphQLResponse::default();
+
+    loop {
+        let page = execute(client, request, &operation).await?;
+        result.pages += 1;
+        result.status = page.status;
+        result.errors.extend(page.errors);
+        result.data = page.data;
+
+        if result.data.is_null() {
+            break;
+        }
+
+        let connection = find_connection(&result.data, &pagination.connection_path)?;
+        let (nodes, next_cursor) = extract_page(connection)?;
+        result.items.extend(nodes);
+
+        let Some(next_cursor) = next_cursor else {
+            break;
+        };
+        if pagination.max_pages > 0 && result.pages >= pagination.max_pages {
+            break;
+        }
+
+        operation
+            .variables
+            .insert(pagination.cursor_variable.clone(), json!(next_cursor));
+    }
+
+    Ok(result)
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct GraphQLRequestNode {}
+
+impl GraphQLRequestNode {
+    pub fn new() -> Self {
+        GraphQLRequestNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for GraphQLRequestNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "graphql_request",
+            "GraphQL Request",
+            "Sends a GraphQL query or mutation. GraphQL errors are returned on their own pin, also for responses with status 200, and cursor pagination can be followed automatically",
+            "Web/API/GraphQL",
+        );
+
+        node.add_icon("/flow/icons/web.svg");
+
+        node.add_input_pin("exec_in", "Execute", "", VariableType::Execution);
+
+        node.add_input_pin(
+            "endpoint",
+            "Endpoint",
+            "GraphQL endpoint URL",
+            VariableType::String,
+        );
+
+        node.add_input_pin(
+            "query",
+            "Query",
+            "GraphQL query or mutation",
+            VariableType::String,
+        );
+
+        node.add_input_pin(
+            "variables",
+            "Variables",
+            "Query variables",
+            VariableType::Struct,
+        )
+        .set_default_value(Some(json!({})));
+
+        node.add_input_pin(
+            "operation_name",
+            "Operation Name",
+            "Operation to run if the document contains several, leave empty otherwise",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("")));
+
+        node.add_input_pin(
+            "headers",
+            "Headers",
+            "Additional HTTP headers",
+            VariableType::Struct,
+        )
+        .set_default_value(Some(json!({})));
+
+        node.add_input_pin(
+            "token",
+            "Bearer Token",
+            "Sent as 'Authorization: Bearer <token>' if set",
+            VariableType::String,
+        )
+        .set_options(PinOptions::new().set_sensitive(true).build())
+        .set_default_value(Some(json!("")));
+
+        node.add_input_pin(
+            "persisted_query",
+            "Persisted Query",
+            "Send the SHA-256 hash of the query instead of the query text, and the full query only if the server does not know the hash yet",
+            VariableType::Boolean,
+        )
+        .set_default_value(Some(json!(false)));
+
+        node.add_input_pin(
+            "paginate",
+            "Paginate",
+            "Follow pageInfo.hasNextPage and collect the nodes of all pages. The query has to accept the cursor variable",
+            VariableType::Boolean,
+        )
+        .set_default_value(Some(json!(false)));
+
+        node.add_input_pin(
+            "connection_path",
+            "Connection Path",
+            "Dot separated path to the paginated connection below 'data'. Leave empty to use the first connection that has pageInfo",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("")));
+
+        node.add_input_pin(
+            "cursor_variable",
+            "Cursor Variable",
+            "Name of the variable that receives the end cursor",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("after")));
+
+        node.add_input_pin(
+            "max_pages",
+            "Max Pages",
+            "Maximum number of pages to fetch when paginating, 0 for no limit",
+            VariableType::Integer,
+        )
+        .set_default_value(Some(json!(10)));
+
+        node.add_output_pin("exec_out", "Output", "", VariableType::Execution);
+
+        node.add_output_pin(
+            "data",
+            "Data",
+            "The 'data' object of the response, of the last page when paginating",
+            VariableType::Struct,
+        );
+
+        node.add_output_pin(
+            "errors",
+            "Errors",
+            "GraphQL errors, empty if the request fully succeeded",
+            VariableType::Struct,
+        )
+        .set_schema::<GraphQLError>()
+        .set_value_type(ValueType::Array);
+
+        node.add_output_pin(
+            "has_errors",
+            "Has Errors",
+            "True if the response contained GraphQL errors",
+            VariableType::Boolean,
+        );
+
+        node.add_output_pin(
+            "items",
+            "Items",
+            "Aggregated edges.node items of all pages, empty if pagination is disabled",
+            VariableType::Generic,
+        )
+        .set_value_type(ValueType::Array);
+
+        node.add_output_pin(
+            "status",
+            "Status",
+            "HTTP status code of the last response",
+            VariableType::Integer,
+        );
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+
+        let endpoint: String = context.evaluate_pin("endpoint").await?;
+        let query: String = context.evaluate_pin("query").await?;
+        let variables: Value = context.evaluate_pin("variables").await?;
+        let operation_name: String = context.evaluate_pin("operation_name").await?;
+        let headers: HashMap<String, String> = context.evaluate_pin("headers").await?;
+        let token: String = context.evaluate_pin("token").await?;
+        let persisted: bool = context.evaluate_pin("persisted_query").await?;
+        let paginate: bool = context.evaluate_pin("paginate").await?;
+
+        let variables = match variables {
+            Value::Object(variables) => variables,
+            Value::Null => Map::new(),
+            _ => bail!("Variables have to be an object"),
+        };
+
+        let operation = GraphQLOperation {
+            query,
+            variables,
+            operation_name: Some(operation_name).filter(|name| !name.trim().is_empty()),
+            persisted,
+        };
+        let request = graphql_request(endpoint, headers, &token);
+        let client = reqwest::Client::new();
+
+        let response = if paginate {
+            let pagination = GraphQLPagination {
+                connection_path: context.evaluate_pin("connection_path").await?,
+                cursor_variable: context.evaluate_pin("cursor_variable").await?,
+                max_pages: context.evaluate_pin::<i64>("max_pages").await?.max(0) as usize,
+            };
+            execute_paginated(&client, &request, &operation, &pagination).await?
+        } else {
+            execute(&client, &request, &operation).await?
+        };
+
+        if !response.errors.is_empty() {
+            let messages: Vec<&str> = response
+                .errors
+                .iter()
+                .map(|error| error.message.as_str())
+                .collect();
+            context.log_message(
+                &format!("GraphQL returned errors: {}", messages.join("; ")),
+                LogLevel::Warn,
+            );
+        }
+
+        context.set_pin_value("data", response.data).await?;
+        context
+            .set_pin_value("has_errors", json!(!response.errors.is_empty()))
+            .await?;
+        context
+            .set_pin_value("errors", json!(response.errors))
+            .await?;
+        context
+            .set_pin_value("items", json!(response.items))
+            .await?;
+        context
+            .set_pin_value("status", json!(response.status))
+            .await?;
+        context.activate_exec_pin("exec_out").await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::web::api::Method;
+    use std::sync::Arc;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+        sync::Mutex,
+    };
+
+    /// Answers one request per canned response, in order, and records the request bodies.
+    async fn mock_server(responses: Vec<(u16, &'static str)>) -> (String, Arc<Mutex<Vec<Value>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}/graphql", listener.local_addr().unwrap());
+        let bodies = Arc::new(Mutex::new(Vec::new()));
+        let captured = bodies.clone();
+
+        tokio::spawn(async move {
+            for (status, body) in responses {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buffer = Vec::new();
+                let mut chunk = [0u8; 4096];
+
+                let head_end = loop {
+                    let read = socket.read(&mut chunk).await.unwrap();
+                    buffer.extend_from_slice(&chunk[..read]);
+                    if let Some(position) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+                        break position + 4;
+                    }
+                };
+
+                let head = String::from_utf8_lossy(&buffer[..head_end]).to_lowercase();
+                let content_length = head
+                    .lines()
+                    .find_map(|line| line.strip_prefix("content-length:"))
+                    .and_then(|length| length.trim().parse::<usize>().ok())
+                    .unwrap_or(0);
+
+                while buffer.len() < head_end + content_length {
+                    let read = socket.read(&mut chunk).await.unwrap();
+                    buffer.extend_from_slice(&chunk[..read]);
+                }
+
+                captured
+                    .lock()
+                    .await
+                    .push(json::from_slice(&buffer[head_end..head_end + content_length]).unwrap());
+
+                let content_type = if body.starts_with('{') {
+                    "application/json"
+                } else {
+                    "text/html"
+                };
+                let response = format!(
+                    "HTTP/1.1 {} Mock\r\ncontent-type: {}\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                    status,
+                    content_type,
+                    body.len(),
+                    body
+                );
+                socket.write_all(response.as_bytes()).await.unwrap();
+                socket.shutdown().await.unwrap();
+            }
+        });
+
+        (endpoint, bodies)
+    }
+
+    fn operation(query: &str, persisted: bool) -> GraphQLOperation {
+        GraphQLOperation {
+            query: query.to_string(),
+            variables: Map::new(),
+            operation_name: None,
+            persisted,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_data_response() {
+        let (endpoint, bodies) =
+            mock_server(vec![(200, r#"{"data":{"viewer":{"login":"octocat"}}}"#)]).await;
+        let request = HttpRequest::new(endpoint, Method::POST);
+
+        let response = execute(
+            &reqwest::Client::new(),
+            &request,
+            &operation("{ viewer { login } }", false),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(response.status, 200);
+        assert_eq!(response.data, json!({ "viewer": { "login": "octocat" } }));
+        assert!(response.errors.is_empty());
+        assert_eq!(
+            bodies.lock().await[0],
+            json!({ "query": "{ viewer { login } }", "variables": {} })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_partial_error_response() {
+        let (endpoint, _) = mock_server(vec![(
+            200,
+            r#"{"data":{"user":{"name":"Ada"},"repo":null},"errors":[{"message":"Could not resolve to a Repository","path":["repo"],"locations":[{"line":1,"column":20}]}]}"#,
+        )])
+        .await;
+        let request = HttpRequest::new(endpoint, Method::POST);
+
+        let response = execute(&reqwest::Client::new(), &request, &operation("{}", false))
+            .await
+            .unwrap();
+
+        assert_eq!(response.data["user"]["name"], json!("Ada"));
+        assert_eq!(response.errors.len(), 1);
+        assert_eq!(
+            response.errors[0].message,
+            "Could not resolve to a Repository"
+        );
+        assert_eq!(response.errors[0].path, Some(vec![json!("repo")]));
+    }
+
+    #[tokio::test]
+    async fn test_transport_errors() {
+        let (endpoint, _) = mock_server(vec![
+            (502, "<html>Bad Gateway</html>"),
+            (
+                400,
+                r#"{"errors":[{"message":"Syntax Error: Unexpected '}'"}]}"#,
+            ),
+        ])
+        .await;
+        let request = HttpRequest::new(endpoint, Method::POST);
+        let client = reqwest::Client::new();
+
+        let error = execute(&client, &request, &operation("{", false))
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("502"));
+
+        // A GraphQL error body is reported on the errors pin even with a 4xx status
+        let response = execute(&client, &request, &operation("{", false))
+            .await
+            .unwrap();
+        assert_eq!(response.status, 400);
+        assert!(response.data.is_null());
+        assert_eq!(response.errors.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_persisted_query_retries_with_query() {
+        let (endpoint, bodies) = mock_server(vec![
+            (
+                200,
+                r#"{"errors":[{"message":"PersistedQueryNotFound","extensions":{"code":"PERSISTED_QUERY_NOT_FOUND"}}]}"#,
+            ),
+            (200, r#"{"data":{"ping":"pong"}}"#),
+        ])
+        .await;
+        let request = HttpRequest::new(endpoint, Method::POST);
+        let query = "{ ping }";
+
+        let response = execute(&reqwest::Client::new(), &request, &operation(query, true))
+            .await
+            .unwrap();
+        assert_eq!(response.data, json!({ "ping": "pong" }));
+        assert!(response.errors.is_empty());
+
+        let bodies = bodies.lock().await;
+        let hash = persisted_query_hash(query);
+        assert_eq!(
+            hash,
+            "6cd3bf61757c6bee6e943d50a381a002447236bf3f15d3730400b931e9cf323f"
+        );
+        assert!(bodies[0].get("query").is_none());
+        assert_eq!(
+            bodies[0]["extensions"]["persistedQuery"]["sha256Hash"],
+            json!(hash)
+        );
+        assert_eq!(bodies[1]["query"], json!(query));
+        assert_eq!(
+            bodies[1]["extensions"]["persistedQuery"]["sha256Hash"],
+            json!(hash)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pagination_follows_cursor_up_to_limit() {
+        let (endpoint, bodies) = mock_server(vec![
+            (
+                200,
+                r#"{"data":{"repository":{"issues":{"nodes":[{"id":1},{"id":2}],"pageInfo":{"endCursor":"c1","hasNextPage":true}}}}}"#,
+            ),
+            (
+                200,
+                r#"{"data":{"repository":{"issues":{"nodes":[{"id":3}],"pageInfo":{"endCursor":"c2","hasNextPage":true}}}}}"#,
+            ),
+        ])
+        .await;
+        let request = HttpRequest::new(endpoint, Method::POST);
+        let pagination = GraphQLPagination {
+            connection_path: "repository.issues".to_string(),
+            cursor_variable: "after".to_string(),
+            max_pages: 2,
+        };
+
+        let response = execute_paginated(
+            &reqwest::Client::new(),
+            &request,
+            &operation("query($after: String) { ... }", false),
+            &pagination,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(response.pages, 2);
+        assert_eq!(
+            response.items,
+            vec![json!({ "id": 1 }), json!({ "id": 2 }), json!({ "id": 3 })]
+        );
+
+        let bodies = bodies.lock().await;
+        assert!(bodies[0]["variables"].get("after").is_none());
+        assert_eq!(bodies[1]["variables"]["after"], json!("c1"));
+    }
+}
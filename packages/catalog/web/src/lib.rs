@@ -6,6 +6,8 @@
 //! - Mail (IMAP/SMTP)
 //! - Discord bot integration
 //! - Telegram bot integration
+//! - Slack messages and file uploads
+//! - Twilio SMS and MMS
 
 use std::sync::Arc;
 
@@ -15,10 +17,12 @@ pub use flow_like_catalog_core::{NodeConstructor, NodeLogic, inventory, register
 pub mod discord;
 pub mod http;
 pub mod mail;
+pub mod slack;
 #[cfg(feature = "execute")]
 pub mod telegram;
+pub mod twilio;
 pub mod web;
 
 pub fn get_catalog() -> Vec<Arc<dyn NodeLogic>> {
-    flow_like_catalog_core::get_catalog()
+    flow_like_catalog_core::get_package_catalog(env!("CARGO_PKG_NAME"))
 }
@@ -23,6 +23,7 @@ use std::{
 use tokio::{self, net::TcpStream, sync::Mutex};
 pub mod calendar;
 pub mod inbox;
+pub mod search;
 
 #[derive(Serialize, Deserialize, JsonSchema, Clone)]
 pub struct ImapConnection {
@@ -171,6 +171,7 @@ impl NodeLogic for ImapCreateDraftNode {
             &body_html,
             &message_id,
             &attachments,
+            &[],
         );
 
         let flags_str = if mark_seen {
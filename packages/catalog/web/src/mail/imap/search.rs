@@ -0,0 +1,431 @@
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic},
+    pin::{PinOptions, ValueType},
+    variable::VariableType,
+};
+#[cfg(feature = "execute")]
+use flow_like_types::{anyhow, bail};
+use flow_like_types::{async_trait, json::json};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::mail::imap::inbox::{ImapInbox, list::EmailRef};
+
+/// Structured IMAP search. Every set field must match, unset fields are ignored.
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, Default, PartialEq)]
+pub struct MailSearchCriteria {
+    /// Sender contains this text
+    pub from: Option<String>,
+    /// Recipient contains this text
+    pub to: Option<String>,
+    /// Subject contains this text
+    pub subject: Option<String>,
+    /// Received on or after this date (`YYYY-MM-DD`, RFC 3339 or `01-Jan-2024`)
+    pub since: Option<String>,
+    /// Received before this date
+    pub before: Option<String>,
+    pub seen: Option<bool>,
+    pub flagged: Option<bool>,
+}
+
+#[cfg(feature = "execute")]
+fn quote(field: &str, value: &str) -> flow_like_types::Result<String> {
+    if value.contains(['\r', '\n']) {
+        bail!("Search field '{field}' must not contain line breaks");
+    }
+    Ok(format!(
+        "\"{}\"",
+        value.replace('\\', "\\\\").replace('"', "\\\"")
+    ))
+}
+
+/// Converts a date into the `dd-Mon-yyyy` form IMAP expects.
+#[cfg(feature = "execute")]
+fn imap_date(field: &str, value: &str) -> flow_like_types::Result<String> {
+    use chrono::{DateTime, NaiveDate};
+
+    let value = value.trim();
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .or_else(|_| NaiveDate::parse_from_str(value, "%d-%b-%Y"))
+        .or_else(|_| DateTime::parse_from_rfc3339(value).map(|date| date.date_naive()))
+        .map_err(|_| anyhow!("Search field '{field}' is not a valid date: {value}"))?;
+    Ok(date.format("%d-%b-%Y").to_string())
+}
+
+impl MailSearchCriteria {
+    /// Translates the criteria into IMAP SEARCH keys, restricted to UIDs above `after_uid`.
+    #[cfg(feature = "execute")]
+    pub fn to_query(&self, after_uid: u32) -> flow_like_types::Result<String> {
+        let mut keys = Vec::new();
+        if after_uid > 0 {
+            keys.push(format!("UID {}:*", after_uid.saturating_add(1)));
+        }
+
+        for (key, value) in [
+            ("FROM", &self.from),
+            ("TO", &self.to),
+            ("SUBJECT", &self.subject),
+        ] {
+            if let Some(value) = value.as_deref().filter(|value| !value.is_empty()) {
+                keys.push(format!("{key} {}", quote(&key.to_lowercase(), value)?));
+            }
+        }
+
+        for (key, value) in [("SINCE", &self.since), ("BEFORE", &self.before)] {
+            if let Some(value) = value.as_deref().filter(|value| !value.trim().is_empty()) {
+                keys.push(format!("{key} {}", imap_date(&key.to_lowercase(), value)?));
+            }
+        }
+
+        match self.seen {
+            Some(true) => keys.push("SEEN".to_string()),
+            Some(false) => keys.push("UNSEEN".to_string()),
+            None => {}
+        }
+        match self.flagged {
+            Some(true) => keys.push("FLAGGED".to_string()),
+            Some(false) => keys.push("UNFLAGGED".to_string()),
+            None => {}
+        }
+
+        if keys.is_empty() {
+            return Ok("ALL".to_string());
+        }
+
+        let query = keys.join(" ");
+        // Quoted strings are 7-bit unless the charset is announced
+        if query.is_ascii() {
+            Ok(query)
+        } else {
+            Ok(format!("CHARSET UTF-8 {query}"))
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct MailSearchPage {
+    /// Matching UIDs in ascending order
+    pub uids: Vec<u32>,
+    /// Pass as `after_uid` to fetch the next page, `None` once all matches were returned
+    pub next_after_uid: Option<u32>,
+}
+
+/// Selects `mailbox` and searches it on an existing session, returning at most
+/// `page_size` UIDs above `after_uid`.
+#[cfg(feature = "execute")]
+pub async fn search_mailbox<T>(
+    session: &mut async_imap::Session<T>,
+    mailbox: &str,
+    criteria: &MailSearchCriteria,
+    after_uid: u32,
+    page_size: usize,
+) -> flow_like_types::Result<MailSearchPage>
+where
+    T: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin + std::fmt::Debug + Send,
+{
+    let query = criteria.to_query(after_uid)?;
+    session
+        .select(mailbox)
+        .await
+        .map_err(|e| anyhow!("IMAP SELECT {mailbox} failed: {e}"))?;
+
+    // `n:*` always includes the highest UID, even if it is below `n`
+    let mut uids: Vec<u32> = session
+        .uid_search(&query)
+        .await
+        .map_err(|e| anyhow!("IMAP UID SEARCH failed: {e}"))?
+        .into_iter()
+        .filter(|&uid| uid > after_uid)
+        .collect();
+    uids.sort_unstable();
+
+    let page_size = page_size.max(1);
+    let has_more = uids.len() > page_size;
+    uids.truncate(page_size);
+
+    Ok(MailSearchPage {
+        next_after_uid: if has_more { uids.last().copied() } else { None },
+        uids,
+    })
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct SearchMailNode;
+
+impl SearchMailNode {
+    pub fn new() -> Self {
+        SearchMailNode
+    }
+}
+
+#[async_trait]
+impl NodeLogic for SearchMailNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "mail_imap_search",
+            "Search Mails",
+            "Searches a mailbox by sender, recipient, subject, date range and flags on the cached IMAP session. Large result sets are paged by UID",
+            "Email/IMAP",
+        );
+        node.add_icon("/flow/icons/mail.svg");
+
+        node.add_input_pin("exec_in", "In", "Execution input", VariableType::Execution);
+        node.add_output_pin(
+            "exec_out",
+            "Out",
+            "Execution output",
+            VariableType::Execution,
+        );
+
+        node.add_input_pin("inbox", "Inbox", "Mailbox to search", VariableType::Struct)
+            .set_schema::<ImapInbox>()
+            .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node.add_input_pin(
+            "criteria",
+            "Criteria",
+            "Search criteria, all set fields must match",
+            VariableType::Struct,
+        )
+        .set_schema::<MailSearchCriteria>()
+        .set_default_value(Some(json!(MailSearchCriteria::default())));
+
+        node.add_input_pin(
+            "after_uid",
+            "After UID",
+            "Only return messages with a higher UID, 0 starts from the beginning",
+            VariableType::Integer,
+        )
+        .set_default_value(Some(json!(0)));
+
+        node.add_input_pin(
+            "page_size",
+            "Page Size",
+            "Maximum number of references to return",
+            VariableType::Integer,
+        )
+        .set_options(PinOptions::new().set_range((1., 10_000.)).build())
+        .set_default_value(Some(json!(100)));
+
+        node.add_output_pin(
+            "emails",
+            "Email References",
+            "Matching messages in ascending UID order",
+            VariableType::Struct,
+        )
+        .set_schema::<EmailRef>()
+        .set_value_type(ValueType::Array);
+
+        node.add_output_pin(
+            "next_after_uid",
+            "Next After UID",
+            "Feed back into After UID for the next page, 0 if there are no more matches",
+            VariableType::Integer,
+        );
+
+        node.add_output_pin(
+            "has_more",
+            "Has More",
+            "Whether more matches follow this page",
+            VariableType::Boolean,
+        );
+
+        node
+    }
+
+    #[cfg(feature = "execute")]
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+
+        let inbox: ImapInbox = context.evaluate_pin("inbox").await?;
+        let criteria: MailSearchCriteria =
+            context.evaluate_pin("criteria").await.unwrap_or_default();
+        let after_uid: u32 = context.evaluate_pin("after_uid").await.unwrap_or(0);
+        let page_size: usize = context.evaluate_pin("page_size").await.unwrap_or(100);
+
+        let connection = inbox.connection.clone();
+        let session_arc = connection.to_session(context).await?;
+        let page = {
+            let mut session = session_arc.lock().await;
+            search_mailbox(&mut session, &inbox.name, &criteria, after_uid, page_size).await?
+        };
+
+        let emails = page
+            .uids
+            .iter()
+            .map(|&uid| EmailRef::new(connection.clone(), inbox.clone(), uid))
+            .collect::<Vec<_>>();
+
+        context.set_pin_value("emails", json!(emails)).await?;
+        context
+            .set_pin_value("next_after_uid", json!(page.next_after_uid.unwrap_or(0)))
+            .await?;
+        context
+            .set_pin_value("has_more", json!(page.next_after_uid.is_some()))
+            .await?;
+        context.activate_exec_pin("exec_out").await?;
+        Ok(())
+    }
+
+    #[cfg(not(feature = "execute"))]
+    async fn run(&self, _context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        Err(flow_like_types::anyhow!(
+            "Web functionality requires the 'execute' feature"
+        ))
+    }
+}
+
+#[cfg(all(test, feature = "execute"))]
+mod tests {
+    use super::*;
+    use std::sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    };
+    use tokio::{
+        io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+        net::{TcpListener, TcpStream},
+        sync::Mutex,
+    };
+
+    /// Minimal IMAP server that records every command and answers searches with
+    /// a fixed set of UIDs.
+    async fn mock_server(
+        uids: &'static str,
+    ) -> (String, Arc<AtomicUsize>, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let connections = Arc::new(AtomicUsize::new(0));
+        let commands = Arc::new(Mutex::new(Vec::new()));
+
+        let (accepted, recorded) = (connections.clone(), commands.clone());
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                accepted.fetch_add(1, Ordering::SeqCst);
+                let recorded = recorded.clone();
+                tokio::spawn(async move {
+                    let (reader, mut writer) = stream.into_split();
+                    let mut lines = BufReader::new(reader).lines();
+                    writer.write_all(b"* OK mock ready\r\n").await.unwrap();
+
+                    while let Ok(Some(line)) = lines.next_line().await {
+                        let (tag, command) = line.split_once(' ').unwrap_or((&line, ""));
+                        recorded.lock().await.push(command.to_string());
+                        let response = match command.split(' ').next().unwrap_or_default() {
+                            "SELECT" => format!(
+                                "* 4 EXISTS\r\n* 0 RECENT\r\n* OK [UIDVALIDITY 1] ok\r\n{tag} OK [READ-WRITE] SELECT completed\r\n"
+                            ),
+                            "UID" => format!("* SEARCH {uids}\r\n{tag} OK SEARCH completed\r\n"),
+                            "LOGOUT" => format!("* BYE\r\n{tag} OK LOGOUT completed\r\n"),
+                            _ => format!("{tag} OK completed\r\n"),
+                        };
+                        writer.write_all(response.as_bytes()).await.unwrap();
+                    }
+                });
+            }
+        });
+
+        (addr, connections, commands)
+    }
+
+    async fn login(addr: &str) -> async_imap::Session<TcpStream> {
+        let stream = TcpStream::connect(addr).await.unwrap();
+        let mut client = async_imap::Client::new(stream);
+        let _ = client.read_response().await;
+        client
+            .login("user", "secret")
+            .await
+            .map_err(|(e, _)| e)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_criteria_translation() {
+        assert_eq!(MailSearchCriteria::default().to_query(0).unwrap(), "ALL");
+
+        let criteria = MailSearchCriteria {
+            from: Some("billing@example.com".to_string()),
+            subject: Some("Invoice \"Q3\"".to_string()),
+            since: Some("2024-03-01".to_string()),
+            before: Some("2024-04-01T12:00:00+02:00".to_string()),
+            seen: Some(false),
+            flagged: Some(true),
+            ..Default::default()
+        };
+        assert_eq!(
+            criteria.to_query(41).unwrap(),
+            "UID 42:* FROM \"billing@example.com\" SUBJECT \"Invoice \\\"Q3\\\"\" SINCE 01-Mar-2024 BEFORE 01-Apr-2024 UNSEEN FLAGGED"
+        );
+
+        let criteria = MailSearchCriteria {
+            to: Some("jürgen@example.com".to_string()),
+            since: Some("05-Jan-2024".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            criteria.to_query(0).unwrap(),
+            "CHARSET UTF-8 TO \"jürgen@example.com\" SINCE 05-Jan-2024"
+        );
+    }
+
+    #[test]
+    fn test_invalid_criteria_are_rejected() {
+        let criteria = MailSearchCriteria {
+            subject: Some("a\r\nA1 DELETE INBOX".to_string()),
+            ..Default::default()
+        };
+        assert!(criteria.to_query(0).is_err());
+
+        let criteria = MailSearchCriteria {
+            since: Some("last tuesday".to_string()),
+            ..Default::default()
+        };
+        assert!(criteria.to_query(0).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_search_pages_on_one_session() {
+        let (addr, connections, commands) = mock_server("3 7 12 15").await;
+        let session = Arc::new(Mutex::new(login(&addr).await));
+        let criteria = MailSearchCriteria {
+            from: Some("alerts@example.com".to_string()),
+            seen: Some(false),
+            ..Default::default()
+        };
+
+        let first = search_mailbox(&mut *session.lock().await, "INBOX", &criteria, 0, 2)
+            .await
+            .unwrap();
+        assert_eq!(first.uids, vec![3, 7]);
+        assert_eq!(first.next_after_uid, Some(7));
+
+        let second = search_mailbox(
+            &mut *session.lock().await,
+            "Alerts",
+            &criteria,
+            first.next_after_uid.unwrap(),
+            2,
+        )
+        .await
+        .unwrap();
+        assert_eq!(second.uids, vec![12, 15]);
+        assert_eq!(second.next_after_uid, None);
+
+        assert_eq!(connections.load(Ordering::SeqCst), 1);
+        let commands = commands.lock().await.clone();
+        assert_eq!(
+            commands
+                .iter()
+                .filter(|command| command.starts_with("LOGIN"))
+                .count(),
+            1
+        );
+        assert!(commands.contains(&"SELECT \"Alerts\"".to_string()));
+        assert!(commands.contains(&"UID SEARCH FROM \"alerts@example.com\" UNSEEN".to_string()));
+        assert!(
+            commands.contains(&"UID SEARCH UID 8:* FROM \"alerts@example.com\" UNSEEN".to_string())
+        );
+    }
+}
@@ -19,6 +19,9 @@ use std::{
 };
 #[cfg(feature = "execute")]
 use tokio::{self, io::BufStream, net::TcpStream, sync::Mutex};
+pub mod attachment;
+#[cfg(feature = "execute")]
+pub mod mime;
 pub mod send_mail;
 
 #[cfg(feature = "execute")]
@@ -0,0 +1,106 @@
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic},
+    pin::PinOptions,
+    variable::VariableType,
+};
+use flow_like_catalog_core::FlowPath;
+use flow_like_types::{async_trait, json::json};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// A file for the Send Mail node with optional overrides
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug)]
+pub struct MailAttachment {
+    pub file: FlowPath,
+    /// Name shown to the recipient, the file name of the path if not set
+    #[serde(default)]
+    pub filename: Option<String>,
+    /// MIME type, detected from the file name if not set
+    #[serde(default)]
+    pub content_type: Option<String>,
+    /// Sends the file inline, the HTML body shows it with `<img src="cid:<content_id>">`
+    #[serde(default)]
+    pub content_id: Option<String>,
+}
+
+fn non_empty(value: String) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct SmtpAttachmentNode;
+
+impl SmtpAttachmentNode {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl NodeLogic for SmtpAttachmentNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "email_smtp_attachment",
+            "Mail Attachment",
+            "Describes a file for Send Mail, with its filename, content type or a Content-ID to show it inline in the HTML body",
+            "Email/SMTP",
+        );
+        node.add_icon("/flow/icons/mail.svg");
+
+        node.add_input_pin("file", "File", "File to attach", VariableType::Struct)
+            .set_schema::<FlowPath>()
+            .set_options(PinOptions::new().set_enforce_schema(true).build());
+        node.add_input_pin(
+            "filename",
+            "Filename",
+            "Name shown to the recipient, empty keeps the name of the file",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("")));
+        node.add_input_pin(
+            "content_type",
+            "Content Type",
+            "MIME type like text/csv, empty detects it from the filename",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("")));
+        node.add_input_pin(
+            "content_id",
+            "Content-ID",
+            "Set to show the file inline, e.g. 'logo' for <img src=\"cid:logo\"> in the HTML body",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("")));
+
+        node.add_output_pin(
+            "attachment",
+            "Attachment",
+            "Attachment for the Send Mail node",
+            VariableType::Struct,
+        )
+        .set_schema::<MailAttachment>();
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        let file: FlowPath = context.evaluate_pin("file").await?;
+        let filename: String = context.evaluate_pin("filename").await?;
+        let content_type: String = context.evaluate_pin("content_type").await?;
+        let content_id: String = context.evaluate_pin("content_id").await?;
+
+        let attachment = MailAttachment {
+            file,
+            filename: non_empty(filename),
+            content_type: non_empty(content_type),
+            content_id: non_empty(content_id),
+        };
+        context
+            .set_pin_value("attachment", json!(attachment))
+            .await?;
+        Ok(())
+    }
+}
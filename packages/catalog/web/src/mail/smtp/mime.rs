@@ -0,0 +1,341 @@
+//! MIME parts of outgoing mails
+//!
+//! A mail with everything set is nested like this, parts that would be empty are left out:
+//!
+//! ```text
+//! multipart/mixed
+//! ├── multipart/related
+//! │   ├── multipart/alternative
+//! │   │   ├── text/plain
+//! │   │   └── text/html
+//! │   └── inline images, referenced from the HTML as cid:<id>
+//! └── attachments
+//! ```
+
+use base64::{Engine as _, engine::general_purpose::STANDARD};
+use flow_like_types::{Bytes, Result, bail};
+use futures::{Stream, StreamExt};
+
+const CRLF: &str = "\r\n";
+
+/// Raw bytes per encoded line, 57 bytes encode to the 76 characters RFC 2045 allows.
+const BYTES_PER_LINE: usize = 57;
+
+/// Base64 encodes chunks as they arrive into CRLF terminated lines, so a file never has to
+/// be held in memory next to its encoding.
+#[derive(Default)]
+pub struct Base64Lines {
+    pending: Vec<u8>,
+    encoded: String,
+}
+
+impl Base64Lines {
+    pub fn push(&mut self, chunk: &[u8]) {
+        self.pending.extend_from_slice(chunk);
+        let complete = self.pending.len() - self.pending.len() % BYTES_PER_LINE;
+        for line in self.pending[..complete].chunks(BYTES_PER_LINE) {
+            STANDARD.encode_string(line, &mut self.encoded);
+            self.encoded.push_str(CRLF);
+        }
+        self.pending.drain(..complete);
+    }
+
+    pub fn finish(mut self) -> String {
+        if !self.pending.is_empty() {
+            STANDARD.encode_string(&self.pending, &mut self.encoded);
+            self.encoded.push_str(CRLF);
+        }
+        self.encoded
+    }
+
+    pub fn encode(data: &[u8]) -> String {
+        let mut lines = Self::default();
+        lines.push(data);
+        lines.finish()
+    }
+
+    /// async-smtp sends the message from one buffer, streaming the file from storage keeps
+    /// the raw bytes out of memory at least.
+    pub async fn encode_stream<S>(mut stream: S) -> Result<String>
+    where
+        S: Stream<Item = Result<Bytes>> + Unpin,
+    {
+        let mut lines = Self::default();
+        while let Some(chunk) = stream.next().await {
+            lines.push(&chunk?);
+        }
+        Ok(lines.finish())
+    }
+}
+
+/// A file ready to be put into the message
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutgoingAttachment {
+    pub filename: String,
+    pub content_type: String,
+    /// Inline parts are shown in the HTML body where it references `cid:<content_id>`
+    pub content_id: Option<String>,
+    /// Base64 in lines, see [`Base64Lines`]
+    pub encoded: String,
+}
+
+/// Headers and body of one MIME part
+pub struct Part {
+    pub headers: Vec<String>,
+    pub body: String,
+}
+
+impl Part {
+    pub fn text(subtype: &str, body: String) -> Self {
+        Self {
+            headers: vec![
+                format!("Content-Type: text/{}; charset=utf-8", subtype),
+                "Content-Transfer-Encoding: 8bit".to_string(),
+            ],
+            body,
+        }
+    }
+
+    /// `boundary` has to differ between nesting levels.
+    pub fn multipart(subtype: &str, boundary: &str, parts: Vec<Part>) -> Self {
+        let mut body = String::new();
+        for part in parts {
+            body.push_str(&format!("--{}{}", boundary, CRLF));
+            body.push_str(&part.render());
+            if !body.ends_with(CRLF) {
+                body.push_str(CRLF);
+            }
+        }
+        body.push_str(&format!("--{}--{}", boundary, CRLF));
+
+        let content_type = match subtype {
+            // The root of a related part is the text, RFC 2387 asks to name its type
+            "related" => format!(
+                "Content-Type: multipart/related; type=\"multipart/alternative\"; boundary=\"{}\"",
+                boundary
+            ),
+            _ => format!(
+                "Content-Type: multipart/{}; boundary=\"{}\"",
+                subtype, boundary
+            ),
+        };
+        Self {
+            headers: vec![content_type],
+            body,
+        }
+    }
+
+    /// `inline` parts get a Content-ID so the HTML body can show them.
+    pub fn attachment(attachment: OutgoingAttachment, inline: bool) -> Self {
+        let disposition = if inline { "inline" } else { "attachment" };
+        let mut headers = vec![
+            format!("Content-Type: {}", attachment.content_type),
+            "Content-Transfer-Encoding: base64".to_string(),
+            format!(
+                "Content-Disposition: {}; {}",
+                disposition,
+                filename_parameter(&attachment.filename)
+            ),
+        ];
+        if let Some(content_id) = attachment.content_id.filter(|_| inline) {
+            headers.push(format!("Content-ID: <{}>", content_id));
+        }
+        Self {
+            headers,
+            body: attachment.encoded,
+        }
+    }
+
+    pub fn render(&self) -> String {
+        format!("{}{}{}{}", self.headers.join(CRLF), CRLF, CRLF, self.body)
+    }
+}
+
+/// The body parts of a mail, see the module docs for the nesting.
+pub fn body_part(
+    body_text: String,
+    body_html: Option<String>,
+    attachments: Vec<OutgoingAttachment>,
+) -> Part {
+    // Without an HTML body nothing references inline parts, they are sent as attachments
+    let has_html = body_html.is_some();
+    let (inline, attached): (Vec<_>, Vec<_>) = attachments
+        .into_iter()
+        .partition(|attachment| attachment.content_id.is_some() && has_html);
+
+    let text = Part::text("plain", body_text);
+    let mut body = match body_html {
+        Some(html) => Part::multipart(
+            "alternative",
+            "----=_FlowLikeBoundary_mpart_alternative_001",
+            vec![text, Part::text("html", html)],
+        ),
+        None => text,
+    };
+
+    if !inline.is_empty() {
+        let mut parts = vec![body];
+        parts.extend(
+            inline
+                .into_iter()
+                .map(|image| Part::attachment(image, true)),
+        );
+        body = Part::multipart("related", "----=_FlowLikeBoundary_mpart_related_001", parts);
+    }
+
+    if !attached.is_empty() {
+        let mut parts = vec![body];
+        parts.extend(
+            attached
+                .into_iter()
+                .map(|file| Part::attachment(file, false)),
+        );
+        body = Part::multipart("mixed", "----=_FlowLikeBoundary_mpart_mixed_001", parts);
+    }
+
+    body
+}
+
+/// `filename="..."`, or the RFC 2231 form for names that are not plain ASCII.
+fn filename_parameter(filename: &str) -> String {
+    let sanitized = filename.replace(['"', '\\', '\r', '\n'], "_");
+    if sanitized.is_ascii() {
+        format!("filename=\"{}\"", sanitized)
+    } else {
+        format!("filename*=UTF-8''{}", urlencoding::encode(&sanitized))
+    }
+}
+
+/// Checks a content type override like `text/csv; charset=utf-8`, it ends up in a header.
+pub fn check_content_type(content_type: &str) -> Result<String> {
+    let content_type = content_type.trim();
+    let essence = content_type.split(';').next().unwrap_or_default().trim();
+    let valid = essence
+        .split_once('/')
+        .is_some_and(|(kind, subtype)| !kind.is_empty() && !subtype.is_empty())
+        && content_type
+            .bytes()
+            .all(|b| b == b' ' || b.is_ascii_graphic());
+    if !valid {
+        bail!("Invalid content type '{}'", content_type);
+    }
+    Ok(content_type.to_string())
+}
+
+/// Content-ID without the angle brackets, usable as `cid:<id>` in the HTML body.
+pub fn check_content_id(content_id: &str) -> Result<String> {
+    let content_id = content_id
+        .trim()
+        .trim_start_matches('<')
+        .trim_end_matches('>');
+    if content_id.is_empty()
+        || !content_id
+            .bytes()
+            .all(|b| b.is_ascii_graphic() && b != b'<' && b != b'>')
+    {
+        bail!("Invalid Content-ID '{}'", content_id);
+    }
+    Ok(content_id.to_string())
+}
+
+pub fn detect_mime_type(filename: &str) -> String {
+    let extension = filename.split('.').next_back().unwrap_or("").to_lowercase();
+    match extension.as_str() {
+        "pdf" => "application/pdf",
+        "doc" => "application/msword",
+        "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        "xls" => "application/vnd.ms-excel",
+        "xlsx" => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        "ppt" => "application/vnd.ms-powerpoint",
+        "pptx" => "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+        "txt" => "text/plain",
+        "html" | "htm" => "text/html",
+        "csv" => "text/csv",
+        "json" => "application/json",
+        "xml" => "application/xml",
+        "zip" => "application/zip",
+        "tar" => "application/x-tar",
+        "gz" => "application/gzip",
+        "jpg" | "jpeg" => "image/jpeg",
+        "png" => "image/png",
+        "gif" => "image/gif",
+        "bmp" => "image/bmp",
+        "svg" => "image/svg+xml",
+        "webp" => "image/webp",
+        "mp3" => "audio/mpeg",
+        "wav" => "audio/wav",
+        "mp4" => "video/mp4",
+        "avi" => "video/x-msvideo",
+        "mov" => "video/quicktime",
+        _ => "application/octet-stream",
+    }
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::stream;
+
+    #[test]
+    fn test_base64_lines_do_not_depend_on_chunking() {
+        let data: Vec<u8> = (0..1000u32).map(|i| (i * 7 % 251) as u8).collect();
+        let encoded = Base64Lines::encode(&data);
+
+        let mut chunked = Base64Lines::default();
+        for chunk in data.chunks(13) {
+            chunked.push(chunk);
+        }
+        assert_eq!(chunked.finish(), encoded);
+
+        let lines: Vec<&str> = encoded.split_terminator(CRLF).collect();
+        assert!(lines.iter().all(|line| line.len() <= 76));
+        assert!(lines[..lines.len() - 1].iter().all(|line| line.len() == 76));
+        assert_eq!(STANDARD.decode(lines.concat()).unwrap(), data);
+
+        assert_eq!(Base64Lines::encode(b""), "");
+    }
+
+    #[tokio::test]
+    async fn test_encode_stream() {
+        let chunks = vec![
+            Ok(Bytes::from_static(b"%PDF-1.7\n")),
+            Ok(Bytes::from_static(&[0; 100])),
+        ];
+        let encoded = Base64Lines::encode_stream(stream::iter(chunks))
+            .await
+            .unwrap();
+        let mut expected = b"%PDF-1.7\n".to_vec();
+        expected.extend([0; 100]);
+        assert_eq!(encoded, Base64Lines::encode(&expected));
+
+        let failing = stream::iter(vec![
+            Ok(Bytes::from_static(b"abc")),
+            Err(flow_like_types::anyhow!("connection reset")),
+        ]);
+        assert!(Base64Lines::encode_stream(failing).await.is_err());
+    }
+
+    #[test]
+    fn test_overrides_are_checked() {
+        assert_eq!(
+            check_content_type(" text/csv; charset=utf-8 ").unwrap(),
+            "text/csv; charset=utf-8"
+        );
+        assert!(check_content_type("csv").is_err());
+        assert!(check_content_type("text/csv\r\nBcc: x@y.z").is_err());
+
+        assert_eq!(
+            check_content_id("<logo@flow-like>").unwrap(),
+            "logo@flow-like"
+        );
+        assert!(check_content_id("").is_err());
+        assert!(check_content_id("two words").is_err());
+
+        assert_eq!(filename_parameter("report.pdf"), "filename=\"report.pdf\"");
+        assert_eq!(
+            filename_parameter("März \"final\".pdf"),
+            "filename*=UTF-8''M%C3%A4rz%20_final_.pdf"
+        );
+    }
+}
@@ -12,12 +12,18 @@ use flow_like::flow::{
 };
 use flow_like_catalog_core::FlowPath;
 #[cfg(feature = "execute")]
-use flow_like_types::anyhow;
+use flow_like_types::{Value, anyhow, bail, json::Map};
 use flow_like_types::{async_trait, json::json};
 
-use crate::mail::smtp::SmtpConnection;
+use crate::mail::smtp::{SmtpConnection, attachment::MailAttachment};
 #[cfg(feature = "execute")]
-use crate::mail::{generate_mail_footer_html, generate_mail_footer_plain};
+use crate::mail::{
+    generate_mail_footer_html, generate_mail_footer_plain,
+    smtp::mime::{
+        Base64Lines, OutgoingAttachment, body_part, check_content_id, check_content_type,
+        detect_mime_type,
+    },
+};
 
 #[crate::register_node]
 #[derive(Default)]
@@ -90,6 +96,16 @@ impl NodeLogic for SmtpSendMailNode {
         .set_schema::<FlowPath>()
         .set_options(PinOptions::new().set_enforce_schema(true).build())
         .set_default_value(Some(json!([])));
+        node.add_input_pin(
+            "mail_attachments",
+            "Mail Attachments",
+            "Files with their own filename, content type or Content-ID for inline images, see the Mail Attachment node",
+            VariableType::Struct,
+        )
+        .set_value_type(flow_like::flow::pin::ValueType::Array)
+        .set_schema::<MailAttachment>()
+        .set_options(PinOptions::new().set_enforce_schema(true).build())
+        .set_default_value(Some(json!([])));
 
         // Options
         node.add_input_pin(
@@ -100,6 +116,14 @@ impl NodeLogic for SmtpSendMailNode {
         )
         .set_default_value(Some(json!(false)));
 
+        node.add_input_pin(
+            "headers",
+            "Headers",
+            "Additional headers, e.g. {\"Reply-To\": \"support@example.com\", \"X-Campaign\": \"weekly-report\"}",
+            VariableType::Struct,
+        )
+        .set_default_value(Some(json!({})));
+
         // Outputs
         node.add_output_pin(
             "exec_out",
@@ -130,19 +154,28 @@ impl NodeLogic for SmtpSendMailNode {
         let body_text: String = context.evaluate_pin("body_text").await?;
         let body_html: String = context.evaluate_pin("body_html").await?;
         let include_bcc_header: bool = context.evaluate_pin("include_bcc_header").await?;
+        let headers: Map<String, Value> = context.evaluate_pin("headers").await.unwrap_or_default();
+        let custom_headers = parse_custom_headers(&headers)?;
 
         let in_attachments = context.evaluate_pin::<Vec<FlowPath>>("attachments").await?;
+        let mail_attachments = context
+            .evaluate_pin::<Vec<MailAttachment>>("mail_attachments")
+            .await
+            .unwrap_or_default();
         let mut attachments = Vec::new();
-        for flow_path in in_attachments {
-            let content: Vec<u8> = flow_path.get(context, false).await?;
-            let filename = flow_path
-                .path
-                .split('/')
-                .next_back()
-                .unwrap_or("attachment")
-                .to_string();
-            attachments.push((filename, content));
+        let files = in_attachments
+            .into_iter()
+            .map(|file| MailAttachment {
+                file,
+                filename: None,
+                content_type: None,
+                content_id: None,
+            })
+            .chain(mail_attachments);
+        for attachment in files {
+            attachments.push(load_attachment(context, attachment).await?);
         }
+        let attachment_count = attachments.len();
 
         let mail_from = parse_first_address(&from)
             .ok_or_else(|| anyhow!("'From' must contain a valid email address"))?;
@@ -167,7 +200,8 @@ impl NodeLogic for SmtpSendMailNode {
             &body_text,
             &body_html,
             &message_id,
-            &attachments,
+            attachments,
+            &custom_headers,
         );
 
         let session = connection.to_session(context).await?;
@@ -201,7 +235,7 @@ impl NodeLogic for SmtpSendMailNode {
                 subject,
                 message_id,
                 rcpts.len(),
-                attachments.len()
+                attachment_count
             ),
             LogLevel::Debug,
         );
@@ -221,7 +255,49 @@ impl NodeLogic for SmtpSendMailNode {
     }
 }
 
+/// Reads the file from storage and base64 encodes it while streaming.
 #[cfg(feature = "execute")]
+async fn load_attachment(
+    context: &mut ExecutionContext,
+    attachment: MailAttachment,
+) -> flow_like_types::Result<OutgoingAttachment> {
+    let filename = match attachment.filename {
+        Some(filename) => filename,
+        None => attachment
+            .file
+            .path
+            .split('/')
+            .next_back()
+            .filter(|name| !name.is_empty())
+            .unwrap_or("attachment")
+            .to_string(),
+    };
+    let content_type = match &attachment.content_type {
+        Some(content_type) => check_content_type(content_type)?,
+        None => detect_mime_type(&filename),
+    };
+    let content_id = attachment
+        .content_id
+        .as_deref()
+        .map(check_content_id)
+        .transpose()?;
+
+    let runtime = attachment.file.to_runtime(context).await?;
+    let stream = runtime.read_stream().await?;
+    let encoded = Base64Lines::encode_stream(stream)
+        .await
+        .map_err(|e| anyhow!("Failed to read attachment '{}': {}", filename, e))?;
+
+    Ok(OutgoingAttachment {
+        filename,
+        content_type,
+        content_id,
+        encoded,
+    })
+}
+
+#[cfg(feature = "execute")]
+#[allow(clippy::too_many_arguments)]
 pub fn build_rfc5322_message_send(
     from: &str,
     to: &str,
@@ -231,7 +307,8 @@ pub fn build_rfc5322_message_send(
     body_text: &str,
     body_html: &str,
     message_id: &str,
-    attachments: &[(String, Vec<u8>)],
+    attachments: Vec<OutgoingAttachment>,
+    custom_headers: &[(String, String)],
 ) -> String {
     let crlf = "\r\n";
     let mut headers = Vec::new();
@@ -248,179 +325,101 @@ pub fn build_rfc5322_message_send(
     if !bcc_header.is_empty() {
         headers.push(format!("Bcc: {}", bcc_header));
     }
-    headers.push(format!("Subject: {}", subject));
+    headers.push(format!("Subject: {}", encode_header_value(subject)));
     headers.push(format!("Message-ID: {}", message_id));
     headers.push(format!("Date: {}", rfc2822_now()));
     headers.push("MIME-Version: 1.0".to_string());
+    for (name, value) in custom_headers {
+        headers.push(format!("{}: {}", name, encode_header_value(value)));
+    }
 
-    let mut message = String::new();
-
-    if attachments.is_empty() {
-        if body_html.trim().is_empty() {
-            headers.push("Content-Type: text/plain; charset=utf-8".to_string());
-            headers.push("Content-Transfer-Encoding: 8bit".to_string());
-            message.push_str(&headers.join(crlf));
-            message.push_str(crlf);
-            message.push_str(crlf);
-            message.push_str(body_text);
-            message.push_str(crlf);
-            message.push_str(crlf);
-            message.push_str(&generate_mail_footer_plain());
-            message.push_str(crlf);
-        } else {
-            let alt_boundary = "----=_FlowLikeBoundary_mpart_alternative_001";
-            headers.push(format!(
-                "Content-Type: multipart/alternative; boundary=\"{}\"",
-                alt_boundary
-            ));
+    let text = format!(
+        "{}{}{}{}{}",
+        body_text,
+        crlf,
+        crlf,
+        generate_mail_footer_plain(),
+        crlf
+    );
+    let html = (!body_html.trim().is_empty())
+        .then(|| format!("{}{}{}", body_html, generate_mail_footer_html(), crlf));
+
+    // The headers of the outermost part are the content headers of the message
+    let body = body_part(text, html, attachments);
+    headers.extend(body.headers);
+    format!("{}{}{}{}", headers.join(crlf), crlf, crlf, body.body)
+}
 
-            message.push_str(&headers.join(crlf));
-            message.push_str(crlf);
-            message.push_str(crlf);
-
-            message.push_str(&format!("--{}{}", alt_boundary, crlf));
-            message.push_str("Content-Type: text/plain; charset=utf-8");
-            message.push_str(crlf);
-            message.push_str("Content-Transfer-Encoding: 8bit");
-            message.push_str(crlf);
-            message.push_str(crlf);
-            message.push_str(body_text);
-            message.push_str(crlf);
-            message.push_str(&generate_mail_footer_plain());
-            message.push_str(crlf);
-
-            message.push_str(&format!("--{}{}", alt_boundary, crlf));
-            message.push_str("Content-Type: text/html; charset=utf-8");
-            message.push_str(crlf);
-            message.push_str("Content-Transfer-Encoding: 8bit");
-            message.push_str(crlf);
-            message.push_str(crlf);
-            message.push_str(body_html);
-            message.push_str(&generate_mail_footer_html());
-            message.push_str(crlf);
-
-            message.push_str(&format!("--{}--{}", alt_boundary, crlf));
+/// Headers the message builder sets itself and that must not be overridden.
+#[cfg(feature = "execute")]
+const RESERVED_HEADERS: [&str; 10] = [
+    "from",
+    "to",
+    "cc",
+    "bcc",
+    "subject",
+    "message-id",
+    "date",
+    "mime-version",
+    "content-type",
+    "content-transfer-encoding",
+];
+
+/// Validates user supplied headers. Names must be RFC 5322 field names and values must
+/// not contain line breaks, otherwise they could inject further headers or a body.
+#[cfg(feature = "execute")]
+pub fn parse_custom_headers(
+    headers: &Map<String, Value>,
+) -> flow_like_types::Result<Vec<(String, String)>> {
+    let mut parsed = Vec::with_capacity(headers.len());
+    for (name, value) in headers {
+        let name = name.trim();
+        if name.is_empty() || !name.bytes().all(|b| b.is_ascii_graphic() && b != b':') {
+            bail!("Invalid header name '{}'", name);
         }
-    } else {
-        let mixed_boundary = "----=_FlowLikeBoundary_mpart_mixed_001";
-        headers.push(format!(
-            "Content-Type: multipart/mixed; boundary=\"{}\"",
-            mixed_boundary
-        ));
-        message.push_str(&headers.join(crlf));
-        message.push_str(crlf);
-        message.push_str(crlf);
-
-        message.push_str(&format!("--{}{}", mixed_boundary, crlf));
-
-        if body_html.trim().is_empty() {
-            message.push_str("Content-Type: text/plain; charset=utf-8");
-            message.push_str(crlf);
-            message.push_str("Content-Transfer-Encoding: 8bit");
-            message.push_str(crlf);
-            message.push_str(crlf);
-            message.push_str(body_text);
-            message.push_str(crlf);
-            message.push_str(crlf);
-            message.push_str(&generate_mail_footer_plain());
-            message.push_str(crlf);
-        } else {
-            let alt_boundary = "----=_FlowLikeBoundary_mpart_alternative_001";
-            message.push_str(&format!(
-                "Content-Type: multipart/alternative; boundary=\"{}\"",
-                alt_boundary
-            ));
-            message.push_str(crlf);
-            message.push_str(crlf);
-
-            message.push_str(&format!("--{}{}", alt_boundary, crlf));
-            message.push_str("Content-Type: text/plain; charset=utf-8");
-            message.push_str(crlf);
-            message.push_str("Content-Transfer-Encoding: 8bit");
-            message.push_str(crlf);
-            message.push_str(crlf);
-            message.push_str(body_text);
-            message.push_str(crlf);
-
-            message.push_str(&format!("--{}{}", alt_boundary, crlf));
-            message.push_str("Content-Type: text/html; charset=utf-8");
-            message.push_str(crlf);
-            message.push_str("Content-Transfer-Encoding: 8bit");
-            message.push_str(crlf);
-            message.push_str(crlf);
-            message.push_str(body_html);
-            message.push_str(&generate_mail_footer_html());
-            message.push_str(crlf);
-
-            message.push_str(&format!("--{}--{}", alt_boundary, crlf));
+        if RESERVED_HEADERS.contains(&name.to_ascii_lowercase().as_str()) {
+            bail!(
+                "Header '{}' is set by the node, use the matching input instead",
+                name
+            );
         }
 
-        for (filename, content) in attachments {
-            message.push_str(&format!("--{}{}", mixed_boundary, crlf));
-
-            let mime_type = detect_mime_type(filename, content);
-            message.push_str(&format!("Content-Type: {}", mime_type));
-            message.push_str(crlf);
-            message.push_str("Content-Transfer-Encoding: base64");
-            message.push_str(crlf);
-            message.push_str(&format!(
-                "Content-Disposition: attachment; filename=\"{}\"",
-                sanitize_filename(filename)
-            ));
-            message.push_str(crlf);
-            message.push_str(crlf);
-
-            let encoded = base64_encode(content);
-            for chunk in encoded.as_bytes().chunks(76) {
-                message.push_str(&String::from_utf8_lossy(chunk));
-                message.push_str(crlf);
-            }
+        let value = match value {
+            Value::String(value) => value.clone(),
+            Value::Null => continue,
+            other => other.to_string(),
+        };
+        if value.contains(['\r', '\n']) {
+            bail!("Header '{}' must not contain line breaks", name);
         }
 
-        message.push_str(&format!("--{}--{}", mixed_boundary, crlf));
+        parsed.push((name.to_string(), value));
     }
-
-    message
+    Ok(parsed)
 }
 
+/// Encodes non-ASCII header values as RFC 2047 encoded words.
 #[cfg(feature = "execute")]
-fn detect_mime_type(filename: &str, _content: &[u8]) -> String {
-    let extension = filename.split('.').next_back().unwrap_or("").to_lowercase();
-    match extension.as_str() {
-        "pdf" => "application/pdf",
-        "doc" => "application/msword",
-        "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
-        "xls" => "application/vnd.ms-excel",
-        "xlsx" => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
-        "ppt" => "application/vnd.ms-powerpoint",
-        "pptx" => "application/vnd.openxmlformats-officedocument.presentationml.presentation",
-        "txt" => "text/plain",
-        "html" | "htm" => "text/html",
-        "csv" => "text/csv",
-        "json" => "application/json",
-        "xml" => "application/xml",
-        "zip" => "application/zip",
-        "tar" => "application/x-tar",
-        "gz" => "application/gzip",
-        "jpg" | "jpeg" => "image/jpeg",
-        "png" => "image/png",
-        "gif" => "image/gif",
-        "bmp" => "image/bmp",
-        "svg" => "image/svg+xml",
-        "webp" => "image/webp",
-        "mp3" => "audio/mpeg",
-        "wav" => "audio/wav",
-        "mp4" => "video/mp4",
-        "avi" => "video/x-msvideo",
-        "mov" => "video/quicktime",
-        _ => "application/octet-stream",
+fn encode_header_value(value: &str) -> String {
+    if value.is_ascii() {
+        return value.to_string();
     }
-    .to_string()
-}
 
-#[cfg(feature = "execute")]
-fn sanitize_filename(filename: &str) -> String {
-    filename.replace(['"', '\\', '\r', '\n'], "_")
+    // Encoded words are limited to 75 characters, so long values are split on char boundaries
+    let mut words = Vec::new();
+    let mut current = String::new();
+    for c in value.chars() {
+        if current.len() + c.len_utf8() > 45 {
+            words.push(format!("=?UTF-8?B?{}?=", base64_encode(current.as_bytes())));
+            current.clear();
+        }
+        current.push(c);
+    }
+    if !current.is_empty() {
+        words.push(format!("=?UTF-8?B?{}?=", base64_encode(current.as_bytes())));
+    }
+
+    words.join("\r\n ")
 }
 
 #[cfg(feature = "execute")]
@@ -479,3 +478,193 @@ fn base64_encode(data: &[u8]) -> String {
     use base64::{Engine as _, engine::general_purpose};
     general_purpose::STANDARD.encode(data)
 }
+
+#[cfg(all(test, feature = "execute"))]
+mod tests {
+    use super::*;
+    use mail_parser::{MessageParser, MimeHeaders};
+
+    fn attachment(
+        filename: &str,
+        content_type: &str,
+        content_id: Option<&str>,
+        data: &[u8],
+    ) -> OutgoingAttachment {
+        OutgoingAttachment {
+            filename: filename.to_string(),
+            content_type: content_type.to_string(),
+            content_id: content_id.map(String::from),
+            encoded: Base64Lines::encode(data),
+        }
+    }
+
+    fn build(body_html: &str, attachments: Vec<OutgoingAttachment>) -> String {
+        build_rfc5322_message_send(
+            "Reports <reports@example.com>",
+            "team@example.com",
+            "",
+            "",
+            "Weekly report",
+            "See attached.",
+            body_html,
+            "<id@example.com>",
+            attachments,
+            &[],
+        )
+    }
+
+    /// Content type of every part, depth first
+    fn structure(message: &mail_parser::Message<'_>) -> Vec<String> {
+        message
+            .parts
+            .iter()
+            .map(|part| {
+                let content_type = part.content_type().unwrap();
+                format!(
+                    "{}/{}",
+                    content_type.ctype(),
+                    content_type.subtype().unwrap_or_default()
+                )
+            })
+            .collect()
+    }
+
+    fn headers(value: Value) -> Map<String, Value> {
+        value.as_object().cloned().unwrap()
+    }
+
+    #[test]
+    fn test_custom_headers_are_validated() {
+        let parsed = parse_custom_headers(&headers(json!({
+            "Reply-To": "support@example.com",
+            "X-Priority": 1,
+            "X-Skipped": null
+        })))
+        .unwrap();
+        assert_eq!(
+            parsed,
+            vec![
+                ("Reply-To".to_string(), "support@example.com".to_string()),
+                ("X-Priority".to_string(), "1".to_string()),
+            ]
+        );
+
+        assert!(parse_custom_headers(&headers(json!({ "X-Bad": "a\r\nBcc: x@y.z" }))).is_err());
+        assert!(parse_custom_headers(&headers(json!({ "X Bad": "a" }))).is_err());
+        assert!(parse_custom_headers(&headers(json!({ "subject": "a" }))).is_err());
+    }
+
+    #[test]
+    fn test_message_with_headers_and_attachment() {
+        let message = build_rfc5322_message_send(
+            "Reports <reports@example.com>",
+            "team@example.com",
+            "",
+            "",
+            "Weekly report",
+            "See attached.",
+            "<p>See attached.</p>",
+            "<id@example.com>",
+            vec![attachment(
+                "report.pdf",
+                "application/pdf",
+                None,
+                b"%PDF-1.7",
+            )],
+            &[("Reply-To".to_string(), "support@example.com".to_string())],
+        );
+
+        let (head, body) = message.split_once("\r\n\r\n").unwrap();
+        assert!(head.contains("\r\nReply-To: support@example.com"));
+        assert!(head.contains("Content-Type: multipart/mixed"));
+        assert!(body.contains("Content-Type: text/html; charset=utf-8"));
+        assert!(body.contains("Content-Type: application/pdf"));
+        assert!(body.contains("filename=\"report.pdf\""));
+        assert!(body.contains(&base64_encode(b"%PDF-1.7")));
+    }
+
+    #[test]
+    fn test_non_ascii_subject_is_encoded() {
+        assert_eq!(encode_header_value("Report"), "Report");
+
+        let encoded = encode_header_value("Wöchentlicher Bericht für das Team – KW 42 ✓");
+        assert!(encoded.is_ascii());
+        for word in encoded.split("\r\n ") {
+            assert!(word.starts_with("=?UTF-8?B?") && word.ends_with("?="));
+            assert!(word.len() <= 75);
+        }
+    }
+
+    #[test]
+    fn test_mime_structure_parses_back() {
+        let logo: Vec<u8> = (0..=255u8).cycle().take(1000).collect();
+        let csv = "region;revenue\r\nnorth;12000\r\n".as_bytes();
+        let message = build(
+            "<p>See attached.</p><img src=\"cid:logo\">",
+            vec![
+                attachment("logo.png", "image/png", Some("logo"), &logo),
+                attachment("Bericht März.pdf", "application/pdf", None, b"%PDF-1.7"),
+                attachment("export.txt", "text/csv; charset=utf-8", None, csv),
+            ],
+        );
+        assert!(message.lines().all(|line| line.len() <= 998));
+
+        let parsed = MessageParser::default().parse(message.as_bytes()).unwrap();
+        assert_eq!(
+            structure(&parsed),
+            [
+                "multipart/mixed",
+                "multipart/related",
+                "multipart/alternative",
+                "text/plain",
+                "text/html",
+                "image/png",
+                "application/pdf",
+                "text/csv"
+            ]
+        );
+        assert!(parsed.body_text(0).unwrap().starts_with("See attached."));
+        assert!(parsed.body_html(0).unwrap().contains("cid:logo"));
+
+        let image = &parsed.parts[5];
+        assert_eq!(image.content_id(), Some("logo"));
+        assert_eq!(image.content_disposition().unwrap().ctype(), "inline");
+        assert_eq!(image.contents(), &logo[..]);
+
+        let pdf = &parsed.parts[6];
+        assert_eq!(pdf.content_disposition().unwrap().ctype(), "attachment");
+        assert_eq!(pdf.attachment_name(), Some("Bericht März.pdf"));
+        assert_eq!(pdf.contents(), b"%PDF-1.7");
+
+        let export = &parsed.parts[7];
+        assert_eq!(export.attachment_name(), Some("export.txt"));
+        assert_eq!(
+            export.content_type().unwrap().attribute("charset"),
+            Some("utf-8")
+        );
+        assert_eq!(export.contents(), csv);
+    }
+
+    #[test]
+    fn test_inline_images_without_html_are_attached() {
+        let message = build(
+            "",
+            vec![attachment("logo.png", "image/png", Some("logo"), b"png")],
+        );
+        let parsed = MessageParser::default().parse(message.as_bytes()).unwrap();
+        assert_eq!(
+            structure(&parsed),
+            ["multipart/mixed", "text/plain", "image/png"]
+        );
+
+        let image = &parsed.parts[2];
+        assert_eq!(image.content_disposition().unwrap().ctype(), "attachment");
+        assert_eq!(image.content_id(), None);
+        assert_eq!(image.contents(), b"png");
+
+        let plain = MessageParser::default()
+            .parse(build("", Vec::new()).as_bytes())
+            .unwrap();
+        assert_eq!(structure(&plain), ["text/plain"]);
+    }
+}
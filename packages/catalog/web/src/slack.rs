@@ -0,0 +1,246 @@
+//! Outbound Slack nodes, posting messages and files with a bot token
+
+pub mod file;
+pub mod message;
+
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic},
+    pin::PinOptions,
+    variable::VariableType,
+};
+use flow_like_types::{
+    Value, async_trait,
+    json::{self, json},
+    reqwest::{self, RequestBuilder, StatusCode},
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+pub const SLACK_API_URL: &str = "https://slack.com/api";
+
+/// Bot token (xoxb-...) of a Slack app installed to the workspace
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub struct SlackBot {
+    pub token: String,
+}
+
+impl SlackBot {
+    pub fn post(&self, client: &reqwest::Client, method: &str) -> RequestBuilder {
+        client
+            .post(format!("{}/{}", SLACK_API_URL, method))
+            .bearer_auth(&self.token)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SlackError {
+    /// HTTP 429, retry after the given seconds
+    RateLimited { retry_after: u64 },
+    /// `ok: false` in the response, e.g. `channel_not_found`
+    Api { error: String },
+    /// Any other failed request
+    Request(String),
+}
+
+impl SlackError {
+    pub fn retry_after(&self) -> u64 {
+        match self {
+            Self::RateLimited { retry_after } => *retry_after,
+            _ => 0,
+        }
+    }
+}
+
+impl fmt::Display for SlackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RateLimited { retry_after } => {
+                write!(f, "Slack rate limit hit, retry in {} seconds", retry_after)
+            }
+            Self::Api { error } => write!(f, "Slack API error: {}", error),
+            Self::Request(message) => write!(f, "Slack request failed: {}", message),
+        }
+    }
+}
+
+/// Slack answers most failures with status 200 and `{"ok": false, "error": "..."}`, so the
+/// status alone does not tell success.
+pub fn parse_response(
+    status: StatusCode,
+    retry_after: Option<&str>,
+    body: &str,
+) -> Result<Value, SlackError> {
+    if status == StatusCode::TOO_MANY_REQUESTS {
+        let retry_after = retry_after
+            .and_then(|seconds| seconds.trim().parse().ok())
+            .unwrap_or(1);
+        return Err(SlackError::RateLimited { retry_after });
+    }
+
+    let parsed: Option<Value> = json::from_str(body).ok();
+    match parsed {
+        Some(body) if body["ok"].as_bool() == Some(true) => Ok(body),
+        Some(body) if body["error"].is_string() => Err(SlackError::Api {
+            error: body["error"].as_str().unwrap_or_default().to_string(),
+        }),
+        _ if !status.is_success() => Err(SlackError::Request(format!("HTTP {}: {}", status, body))),
+        _ => Err(SlackError::Request(format!(
+            "Unexpected response: {}",
+            body
+        ))),
+    }
+}
+
+pub(crate) async fn send(request: RequestBuilder) -> Result<Value, SlackError> {
+    let response = request
+        .send()
+        .await
+        .map_err(|e| SlackError::Request(e.to_string()))?;
+    let status = response.status();
+    let retry_after = response
+        .headers()
+        .get("retry-after")
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let body = response
+        .text()
+        .await
+        .map_err(|e| SlackError::Request(e.to_string()))?;
+    parse_response(status, retry_after.as_deref(), &body)
+}
+
+/// Routes a failed call to the `error` pin of the node.
+pub(crate) async fn fail(
+    context: &mut ExecutionContext,
+    error: SlackError,
+) -> flow_like_types::Result<()> {
+    context
+        .set_pin_value("error_message", json!(error.to_string()))
+        .await?;
+    context
+        .set_pin_value("retry_after", json!(error.retry_after()))
+        .await?;
+    context.activate_exec_pin("error").await?;
+    Ok(())
+}
+
+/// Adds the `error`, `error_message` and `retry_after` outputs [`fail`] sets.
+pub(crate) fn add_error_pins(node: &mut Node) {
+    node.add_output_pin(
+        "error",
+        "Error",
+        "Triggered when Slack rejects the request",
+        VariableType::Execution,
+    );
+    node.add_output_pin(
+        "error_message",
+        "Error Message",
+        "Slack error code like channel_not_found, or what went wrong",
+        VariableType::String,
+    );
+    node.add_output_pin(
+        "retry_after",
+        "Retry After",
+        "Seconds to wait before retrying if rate limited, otherwise 0",
+        VariableType::Integer,
+    );
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct SlackBotNode;
+
+impl SlackBotNode {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl NodeLogic for SlackBotNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "slack_bot",
+            "Slack Bot",
+            "Slack bot for the Slack nodes. Create an app at api.slack.com/apps, add the chat:write and files:write scopes, install it and paste its Bot User OAuth Token",
+            "Web/Slack",
+        );
+        node.add_icon("/flow/icons/message.svg");
+
+        node.add_input_pin(
+            "token",
+            "Bot Token",
+            "Bot User OAuth Token, starts with 'xoxb-'",
+            VariableType::String,
+        )
+        .set_options(PinOptions::new().set_sensitive(true).build());
+
+        node.add_output_pin("bot", "Bot", "Slack bot", VariableType::Struct)
+            .set_schema::<SlackBot>()
+            .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        let token: String = context.evaluate_pin("token").await?;
+        let token = token.trim().to_string();
+        if token.is_empty() {
+            return Err(flow_like_types::anyhow!(
+                "Bot token is required, find it under OAuth & Permissions of your Slack app"
+            ));
+        }
+
+        context
+            .set_pin_value("bot", json!(SlackBot { token }))
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POST_MESSAGE: &str = include_str!("../tests/fixtures/slack/post_message.json");
+    const RATE_LIMITED: &str = include_str!("../tests/fixtures/slack/rate_limited.json");
+    const CHANNEL_NOT_FOUND: &str = include_str!("../tests/fixtures/slack/channel_not_found.json");
+
+    #[test]
+    fn test_success() {
+        let body = parse_response(StatusCode::OK, None, POST_MESSAGE).unwrap();
+        assert_eq!(body["ts"], json!("1712345678.123456"));
+        assert_eq!(body["channel"], json!("C0123456789"));
+    }
+
+    #[test]
+    fn test_rate_limit() {
+        let error = parse_response(StatusCode::TOO_MANY_REQUESTS, Some("30"), RATE_LIMITED);
+        assert_eq!(error, Err(SlackError::RateLimited { retry_after: 30 }));
+        assert_eq!(error.unwrap_err().retry_after(), 30);
+
+        // Without a usable header the caller still backs off
+        let error =
+            parse_response(StatusCode::TOO_MANY_REQUESTS, Some("soon"), RATE_LIMITED).unwrap_err();
+        assert_eq!(error.retry_after(), 1);
+    }
+
+    #[test]
+    fn test_error_envelope_with_status_200() {
+        let error = parse_response(StatusCode::OK, None, CHANNEL_NOT_FOUND).unwrap_err();
+        assert_eq!(
+            error,
+            SlackError::Api {
+                error: "channel_not_found".to_string()
+            }
+        );
+        assert_eq!(error.retry_after(), 0);
+        assert!(error.to_string().contains("channel_not_found"));
+
+        let error = parse_response(StatusCode::BAD_GATEWAY, None, "<html>").unwrap_err();
+        assert!(matches!(error, SlackError::Request(message) if message.contains("502")));
+        assert!(parse_response(StatusCode::OK, None, "{}").is_err());
+    }
+}
@@ -0,0 +1,244 @@
+use super::{SlackBot, SlackError, add_error_pins, fail, send};
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic},
+    pin::PinOptions,
+    variable::VariableType,
+};
+use flow_like_catalog_core::FlowPath;
+use flow_like_types::{Value, async_trait, json::json, reqwest};
+
+pub fn complete_upload_body(
+    file_id: &str,
+    title: &str,
+    channel: &str,
+    comment: &str,
+    thread_ts: &str,
+) -> Value {
+    let mut body = json!({
+        "files": [{ "id": file_id, "title": title }],
+        "channel_id": channel,
+    });
+    if !comment.trim().is_empty() {
+        body["initial_comment"] = json!(comment);
+    }
+    if !thread_ts.trim().is_empty() {
+        body["thread_ts"] = json!(thread_ts.trim());
+    }
+    body
+}
+
+/// Uploads through the external upload flow that replaced `files.upload`: reserve an upload
+/// URL, send the bytes there, then share the file to the channel.
+async fn upload(
+    bot: &SlackBot,
+    filename: &str,
+    content: Vec<u8>,
+    complete: impl FnOnce(&str) -> Value,
+) -> Result<Value, SlackError> {
+    let client = reqwest::Client::new();
+
+    let length = content.len().to_string();
+    let reserved = send(
+        bot.post(&client, "files.getUploadURLExternal")
+            .form(&[("filename", filename), ("length", length.as_str())]),
+    )
+    .await?;
+    let (Some(upload_url), Some(file_id)) = (
+        reserved["upload_url"].as_str(),
+        reserved["file_id"].as_str(),
+    ) else {
+        return Err(SlackError::Request(
+            "files.getUploadURLExternal returned no upload URL".to_string(),
+        ));
+    };
+
+    let uploaded = client
+        .post(upload_url)
+        .body(content)
+        .send()
+        .await
+        .map_err(|e| SlackError::Request(e.to_string()))?;
+    if !uploaded.status().is_success() {
+        return Err(SlackError::Request(format!(
+            "Uploading the file failed with HTTP {}",
+            uploaded.status()
+        )));
+    }
+
+    send(
+        bot.post(&client, "files.completeUploadExternal")
+            .json(&complete(file_id)),
+    )
+    .await
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct SlackUploadFileNode;
+
+impl SlackUploadFileNode {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl NodeLogic for SlackUploadFileNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "slack_upload_file",
+            "Upload File",
+            "Uploads a file and shares it in a Slack channel or thread",
+            "Web/Slack",
+        );
+        node.add_icon("/flow/icons/message.svg");
+
+        node.add_input_pin("exec_in", "In", "Trigger", VariableType::Execution);
+        node.add_input_pin("bot", "Bot", "Slack bot", VariableType::Struct)
+            .set_schema::<SlackBot>()
+            .set_options(PinOptions::new().set_enforce_schema(true).build());
+        node.add_input_pin(
+            "channel",
+            "Channel",
+            "Channel ID like C0123456789, the bot has to be a member",
+            VariableType::String,
+        );
+        node.add_input_pin("file", "File", "File to upload", VariableType::Struct)
+            .set_schema::<FlowPath>()
+            .set_options(PinOptions::new().set_enforce_schema(true).build());
+        node.add_input_pin(
+            "title",
+            "Title",
+            "Title of the file, empty uses the file name",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("")));
+        node.add_input_pin(
+            "comment",
+            "Comment",
+            "Optional message posted with the file",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("")));
+        node.add_input_pin(
+            "thread_ts",
+            "Thread TS",
+            "Optional ts of the message to share the file under",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("")));
+
+        node.add_output_pin(
+            "exec_out",
+            "Out",
+            "Triggered once the file is shared",
+            VariableType::Execution,
+        );
+        node.add_output_pin("file_id", "File ID", "ID of the file", VariableType::String);
+        node.add_output_pin(
+            "permalink",
+            "Permalink",
+            "Link to the file in Slack",
+            VariableType::String,
+        );
+        add_error_pins(&mut node);
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+        context.deactivate_exec_pin("error").await?;
+
+        let bot: SlackBot = context.evaluate_pin("bot").await?;
+        let channel: String = context.evaluate_pin("channel").await?;
+        let file: FlowPath = context.evaluate_pin("file").await?;
+        let title: String = context.evaluate_pin("title").await?;
+        let comment: String = context.evaluate_pin("comment").await?;
+        let thread_ts: String = context.evaluate_pin("thread_ts").await?;
+
+        let filename = file
+            .path
+            .split('/')
+            .next_back()
+            .filter(|name| !name.is_empty())
+            .unwrap_or("file")
+            .to_string();
+        let title = match title.trim() {
+            "" => filename.clone(),
+            title => title.to_string(),
+        };
+        let content = file.get(context, false).await?;
+
+        let complete =
+            |file_id: &str| complete_upload_body(file_id, &title, &channel, &comment, &thread_ts);
+        match upload(&bot, &filename, content, complete).await {
+            Ok(response) => {
+                let file = &response["files"][0];
+                let file_id = file["id"].as_str().unwrap_or_default();
+                let permalink = file["permalink"].as_str().unwrap_or_default();
+                context.set_pin_value("file_id", json!(file_id)).await?;
+                context.set_pin_value("permalink", json!(permalink)).await?;
+                context.activate_exec_pin("exec_out").await?;
+                Ok(())
+            }
+            Err(error) => fail(context, error).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::slack::parse_response;
+    use flow_like_types::reqwest::StatusCode;
+
+    const GET_UPLOAD_URL: &str = include_str!("../../tests/fixtures/slack/get_upload_url.json");
+    const COMPLETE_UPLOAD: &str = include_str!("../../tests/fixtures/slack/complete_upload.json");
+    const CHANNEL_NOT_FOUND: &str =
+        include_str!("../../tests/fixtures/slack/channel_not_found.json");
+
+    #[test]
+    fn test_upload_flow_responses() {
+        let reserved = parse_response(StatusCode::OK, None, GET_UPLOAD_URL).unwrap();
+        let file_id = reserved["file_id"].as_str().unwrap();
+        assert!(
+            reserved["upload_url"]
+                .as_str()
+                .unwrap()
+                .starts_with("https://")
+        );
+
+        assert_eq!(
+            complete_upload_body(file_id, "Nightly report", "C0123456789", "", ""),
+            json!({
+                "files": [{ "id": "F0FILE00001", "title": "Nightly report" }],
+                "channel_id": "C0123456789"
+            })
+        );
+        assert_eq!(
+            complete_upload_body(
+                file_id,
+                "Nightly report",
+                "C0123456789",
+                "Here it is",
+                "1712345678.123456"
+            ),
+            json!({
+                "files": [{ "id": "F0FILE00001", "title": "Nightly report" }],
+                "channel_id": "C0123456789",
+                "initial_comment": "Here it is",
+                "thread_ts": "1712345678.123456"
+            })
+        );
+
+        let completed = parse_response(StatusCode::OK, None, COMPLETE_UPLOAD).unwrap();
+        assert_eq!(completed["files"][0]["id"], json!(file_id));
+        assert!(completed["files"][0]["permalink"].is_string());
+
+        // Sharing to an unknown channel fails after the upload
+        let error = parse_response(StatusCode::OK, None, CHANNEL_NOT_FOUND).unwrap_err();
+        assert_eq!(error.to_string(), "Slack API error: channel_not_found");
+    }
+}
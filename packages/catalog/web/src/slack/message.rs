@@ -0,0 +1,202 @@
+use super::{SlackBot, SlackError, add_error_pins, fail, send};
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic},
+    pin::PinOptions,
+    variable::VariableType,
+};
+use flow_like_types::{
+    Value, async_trait,
+    json::{self, json},
+    reqwest,
+};
+
+/// Blocks as exported by the Block Kit Builder, either the array or `{"blocks": [...]}`.
+pub fn parse_blocks(blocks: &str) -> Result<Option<Value>, SlackError> {
+    if blocks.trim().is_empty() {
+        return Ok(None);
+    }
+    let invalid = |reason: String| SlackError::Request(format!("Invalid blocks JSON: {}", reason));
+    let mut parsed: Value = json::from_str(blocks).map_err(|e| invalid(e.to_string()))?;
+    if parsed.is_object() {
+        parsed = parsed["blocks"].take();
+    }
+    match parsed {
+        Value::Array(blocks) => Ok(Some(Value::Array(blocks))),
+        _ => Err(invalid("expected an array of blocks".to_string())),
+    }
+}
+
+pub fn post_message_body(
+    channel: &str,
+    text: &str,
+    blocks: Option<Value>,
+    thread_ts: &str,
+) -> Value {
+    let mut body = json!({
+        "channel": channel,
+        "text": text,
+    });
+    if let Some(blocks) = blocks {
+        body["blocks"] = blocks;
+    }
+    if !thread_ts.trim().is_empty() {
+        body["thread_ts"] = json!(thread_ts.trim());
+    }
+    body
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct SlackPostMessageNode;
+
+impl SlackPostMessageNode {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl NodeLogic for SlackPostMessageNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "slack_post_message",
+            "Post Message",
+            "Posts a message to a Slack channel, or replies in a thread",
+            "Web/Slack",
+        );
+        node.add_icon("/flow/icons/message.svg");
+
+        node.add_input_pin("exec_in", "In", "Trigger", VariableType::Execution);
+        node.add_input_pin("bot", "Bot", "Slack bot", VariableType::Struct)
+            .set_schema::<SlackBot>()
+            .set_options(PinOptions::new().set_enforce_schema(true).build());
+        node.add_input_pin(
+            "channel",
+            "Channel",
+            "Channel ID like C0123456789, the bot has to be a member",
+            VariableType::String,
+        );
+        node.add_input_pin(
+            "text",
+            "Text",
+            "Message text in mrkdwn, shown in notifications when blocks are set",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("")));
+        node.add_input_pin(
+            "blocks",
+            "Blocks (JSON)",
+            "Optional Block Kit layout, as an array or as exported by the Block Kit Builder",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("")));
+        node.add_input_pin(
+            "thread_ts",
+            "Thread TS",
+            "Optional ts of the message to reply to",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("")));
+
+        node.add_output_pin(
+            "exec_out",
+            "Out",
+            "Triggered once the message is posted",
+            VariableType::Execution,
+        );
+        node.add_output_pin(
+            "ts",
+            "TS",
+            "ts of the posted message, to reply in its thread or react to it",
+            VariableType::String,
+        );
+        node.add_output_pin(
+            "channel_id",
+            "Channel ID",
+            "ID of the channel the message was posted to",
+            VariableType::String,
+        );
+        add_error_pins(&mut node);
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+        context.deactivate_exec_pin("error").await?;
+
+        let bot: SlackBot = context.evaluate_pin("bot").await?;
+        let channel: String = context.evaluate_pin("channel").await?;
+        let text: String = context.evaluate_pin("text").await?;
+        let blocks: String = context.evaluate_pin("blocks").await?;
+        let thread_ts: String = context.evaluate_pin("thread_ts").await?;
+
+        let blocks = match parse_blocks(&blocks) {
+            Ok(blocks) => blocks,
+            Err(error) => return fail(context, error).await,
+        };
+        if text.trim().is_empty() && blocks.is_none() {
+            let error = SlackError::Request("Text or blocks are required".to_string());
+            return fail(context, error).await;
+        }
+
+        let client = reqwest::Client::new();
+        let request = bot
+            .post(&client, "chat.postMessage")
+            .json(&post_message_body(&channel, &text, blocks, &thread_ts));
+
+        match send(request).await {
+            Ok(response) => {
+                let ts = response["ts"].as_str().unwrap_or_default();
+                let channel_id = response["channel"].as_str().unwrap_or(&channel);
+                context.set_pin_value("ts", json!(ts)).await?;
+                context
+                    .set_pin_value("channel_id", json!(channel_id))
+                    .await?;
+                context.activate_exec_pin("exec_out").await?;
+                Ok(())
+            }
+            Err(error) => fail(context, error).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blocks_are_accepted_in_both_shapes() {
+        let section = json!([{ "type": "section", "text": { "type": "mrkdwn", "text": "*Hi*" } }]);
+        assert_eq!(
+            parse_blocks(&section.to_string()).unwrap(),
+            Some(section.clone())
+        );
+        let exported = json!({ "blocks": section });
+        assert_eq!(parse_blocks(&exported.to_string()).unwrap(), Some(section));
+
+        assert_eq!(parse_blocks("  ").unwrap(), None);
+        assert!(parse_blocks("{\"type\": \"section\"}").is_err());
+        assert!(parse_blocks("[{").is_err());
+    }
+
+    #[test]
+    fn test_post_message_body() {
+        assert_eq!(
+            post_message_body("C0123456789", "Nightly report is ready", None, ""),
+            json!({ "channel": "C0123456789", "text": "Nightly report is ready" })
+        );
+
+        let blocks = json!([{ "type": "divider" }]);
+        assert_eq!(
+            post_message_body("C0123456789", "Done", Some(blocks), " 1712345678.123456 "),
+            json!({
+                "channel": "C0123456789",
+                "text": "Done",
+                "blocks": [{ "type": "divider" }],
+                "thread_ts": "1712345678.123456"
+            })
+        );
+    }
+}
@@ -1,11 +1,12 @@
 //! Telegram user interaction - wait for replies, callbacks, and reactions
 
+use super::keyboard::{InlineButton, InlineKeyboard, inline_keyboard_markup};
 use super::message::SentMessage;
 use super::session::{TelegramSession, get_telegram_bot};
 use flow_like::flow::{
     execution::context::ExecutionContext,
     node::{Node, NodeLogic},
-    pin::PinOptions,
+    pin::{PinOptions, ValueType},
     variable::VariableType,
 };
 use flow_like_types::{async_trait, json::json};
@@ -72,6 +73,9 @@ pub struct CallbackResponse {
     pub chat_id: Option<String>,
     /// The message ID of the message with the button
     pub message_id: Option<String>,
+    /// The text of the message with the button
+    #[serde(default)]
+    pub message_text: Option<String>,
 }
 
 // ============================================================================
@@ -664,6 +668,10 @@ impl NodeLogic for WaitForCallbackNode {
                                 username: callback.from.username.clone(),
                                 chat_id: msg_chat_id.map(|id| id.to_string()),
                                 message_id: callback.message.as_ref().map(|m| m.id().0.to_string()),
+                                message_text: callback
+                                    .regular_message()
+                                    .and_then(|m| m.text())
+                                    .map(String::from),
                             };
 
                             if answer_callback {
@@ -774,6 +782,24 @@ impl NodeLogic for AnswerCallbackQueryNode {
         )
         .set_default_value(Some(json!(false)));
 
+        node.add_input_pin(
+            "edit_markup",
+            "Edit Buttons",
+            "Replace the buttons of the message that was clicked with the inline keyboard below",
+            VariableType::Boolean,
+        )
+        .set_default_value(Some(json!(false)));
+
+        node.add_input_pin(
+            "inline_keyboard",
+            "Inline Keyboard",
+            "New rows of buttons for the clicked message, leave empty to remove them",
+            VariableType::Struct,
+        )
+        .set_value_type(ValueType::Array)
+        .set_schema::<Vec<InlineButton>>()
+        .set_default_value(Some(json!([])));
+
         node.add_output_pin(
             "exec_out",
             "Output",
@@ -812,6 +838,30 @@ impl NodeLogic for AnswerCallbackQueryNode {
 
         request.await?;
 
+        let edit_markup: bool = context
+            .evaluate_pin::<bool>("edit_markup")
+            .await
+            .unwrap_or(false);
+        if edit_markup {
+            let keyboard: InlineKeyboard = context
+                .evaluate_pin("inline_keyboard")
+                .await
+                .unwrap_or_default();
+            let (Some(chat_id), Some(message_id)) = (&callback.chat_id, &callback.message_id)
+            else {
+                return Err(flow_like_types::anyhow!(
+                    "The clicked message is not available, its buttons cannot be edited"
+                ));
+            };
+            bot.bot
+                .edit_message_reply_markup(
+                    teloxide::types::ChatId(chat_id.parse()?),
+                    teloxide::types::MessageId(message_id.parse()?),
+                )
+                .reply_markup(inline_keyboard_markup(&keyboard)?)
+                .await?;
+        }
+
         context.activate_exec_pin("exec_out").await?;
 
         Ok(())
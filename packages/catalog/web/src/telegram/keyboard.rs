@@ -0,0 +1,334 @@
+//! Telegram inline keyboards and the callback queries their buttons send
+
+use super::interaction::CallbackResponse;
+use super::session::{CachedTelegramBot, TelegramSession, TelegramSessionData};
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic},
+    pin::PinOptions,
+    variable::VariableType,
+};
+use flow_like_types::{Cacheable, Value, async_trait, bail, json::json};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup};
+
+/// Telegram rejects callback data longer than this many bytes
+const MAX_CALLBACK_DATA: usize = 64;
+
+/// A button of an inline keyboard, either sending `callback_data` back to the bot or
+/// opening `url`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct InlineButton {
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub callback_data: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+/// Rows of buttons, top to bottom
+pub type InlineKeyboard = Vec<Vec<InlineButton>>;
+
+/// Converts the rows into the markup teloxide sends. Empty rows are dropped, an empty
+/// keyboard removes the buttons of a message.
+pub fn inline_keyboard_markup(
+    rows: &[Vec<InlineButton>],
+) -> flow_like_types::Result<InlineKeyboardMarkup> {
+    let mut keyboard = Vec::with_capacity(rows.len());
+    for row in rows.iter().filter(|row| !row.is_empty()) {
+        let mut buttons = Vec::with_capacity(row.len());
+        for button in row {
+            buttons.push(keyboard_button(button)?);
+        }
+        keyboard.push(buttons);
+    }
+    Ok(InlineKeyboardMarkup::new(keyboard))
+}
+
+fn keyboard_button(button: &InlineButton) -> flow_like_types::Result<InlineKeyboardButton> {
+    if button.text.trim().is_empty() {
+        bail!("Inline keyboard buttons need a text");
+    }
+    match (&button.callback_data, &button.url) {
+        (Some(data), None) => {
+            if data.is_empty() || data.len() > MAX_CALLBACK_DATA {
+                bail!(
+                    "Callback data of button \"{}\" must be 1 to {} bytes long",
+                    button.text,
+                    MAX_CALLBACK_DATA
+                );
+            }
+            Ok(InlineKeyboardButton::callback(&button.text, data))
+        }
+        (None, Some(url)) => {
+            let url = url.parse().map_err(|e| {
+                flow_like_types::anyhow!("Invalid url of button \"{}\": {}", button.text, e)
+            })?;
+            Ok(InlineKeyboardButton::url(&button.text, url))
+        }
+        _ => bail!(
+            "Button \"{}\" needs either callback_data or url",
+            button.text
+        ),
+    }
+}
+
+/// Callback query as the Telegram sink forwards it, next to the `local_session` of the
+/// message the button belongs to
+#[derive(Debug, Clone, Deserialize)]
+struct CallbackPayload {
+    local_session: TelegramSessionData,
+    callback_query: CallbackQueryPayload,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct CallbackQueryPayload {
+    id: String,
+    #[serde(default)]
+    data: Option<String>,
+    from_id: i64,
+    #[serde(default)]
+    from_username: Option<String>,
+    #[serde(default)]
+    chat_id: Option<i64>,
+    #[serde(default)]
+    message_id: Option<i32>,
+    #[serde(default)]
+    message_text: Option<String>,
+}
+
+/// Splits the event payload of a button click into the session of the chat and the
+/// callback to answer.
+pub fn parse_callback_payload(
+    payload: Value,
+) -> flow_like_types::Result<(TelegramSessionData, CallbackResponse)> {
+    let payload: CallbackPayload = flow_like_types::json::from_value(payload)
+        .map_err(|e| flow_like_types::anyhow!("Not a Telegram callback payload: {}", e))?;
+    let query = payload.callback_query;
+
+    let callback = CallbackResponse {
+        callback_id: query.id,
+        data: query.data.unwrap_or_default(),
+        user_id: query.from_id.to_string(),
+        username: query.from_username,
+        chat_id: query.chat_id.map(|id| id.to_string()),
+        message_id: query.message_id.map(|id| id.to_string()),
+        message_text: query.message_text,
+    };
+
+    Ok((payload.local_session, callback))
+}
+
+// ============================================================================
+// Callback Event Node
+// ============================================================================
+
+#[flow_like_catalog_macros::register_node]
+#[derive(Default)]
+pub struct CallbackEventNode;
+
+impl CallbackEventNode {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl NodeLogic for CallbackEventNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "telegram_callback_event",
+            "Callback Event",
+            "Reads the payload of a Telegram sink event fired by an inline keyboard button",
+            "Telegram/Interaction",
+        );
+        node.add_icon("/flow/icons/telegram.svg");
+
+        node.add_input_pin("exec_in", "Input", "Trigger", VariableType::Execution);
+
+        node.add_input_pin(
+            "payload",
+            "Payload",
+            "Payload of the event the Telegram sink fired for the button click",
+            VariableType::Struct,
+        );
+
+        node.add_output_pin(
+            "exec_out",
+            "Output",
+            "Continues with the clicked button",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "session",
+            "Session",
+            "Telegram session of the chat the button was clicked in",
+            VariableType::Struct,
+        )
+        .set_schema::<TelegramSession>()
+        .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node.add_output_pin(
+            "callback",
+            "Callback",
+            "The callback query, answer it with 'Answer Callback Query'",
+            VariableType::Struct,
+        )
+        .set_schema::<CallbackResponse>()
+        .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node.add_output_pin(
+            "data",
+            "Data",
+            "Callback data of the clicked button",
+            VariableType::String,
+        );
+
+        node.set_long_running(true);
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        let payload: Value = context.evaluate_pin("payload").await?;
+        let (session_data, callback) = parse_callback_payload(payload)?;
+
+        let ref_id = format!("telegram_bot_{}", flow_like_types::create_id());
+        let bot = CachedTelegramBot::with_bot_info(&session_data.bot_token)
+            .await
+            .unwrap_or_else(|_| CachedTelegramBot::new(&session_data.bot_token));
+        let cacheable: Arc<dyn Cacheable> = Arc::new(bot);
+        context.set_cache(&ref_id, cacheable).await;
+
+        let session = TelegramSession {
+            ref_id,
+            chat_id: session_data.chat_id,
+            message_id: session_data.message_id,
+            chat_type: session_data.chat_type,
+            chat_title: session_data.chat_title,
+            bot_username: session_data.bot_username,
+            user: session_data.user,
+        };
+
+        context.set_pin_value("session", json!(session)).await?;
+        context.set_pin_value("data", json!(callback.data)).await?;
+        context.set_pin_value("callback", json!(callback)).await?;
+
+        context.activate_exec_pin("exec_out").await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn button(text: &str, callback_data: Option<&str>, url: Option<&str>) -> InlineButton {
+        InlineButton {
+            text: text.to_string(),
+            callback_data: callback_data.map(str::to_string),
+            url: url.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_keyboard_serialization() {
+        let rows: InlineKeyboard = flow_like_types::json::from_value(json!([
+            [
+                { "text": "Yes", "callback_data": "vote:yes" },
+                { "text": "No", "callback_data": "vote:no" }
+            ],
+            [],
+            [{ "text": "Docs", "url": "https://docs.flow-like.com" }]
+        ]))
+        .unwrap();
+        assert_eq!(rows[0][0], button("Yes", Some("vote:yes"), None));
+
+        let markup = inline_keyboard_markup(&rows).unwrap();
+        assert_eq!(
+            flow_like_types::json::to_value(&markup).unwrap(),
+            json!({
+                "inline_keyboard": [
+                    [
+                        { "text": "Yes", "callback_data": "vote:yes" },
+                        { "text": "No", "callback_data": "vote:no" }
+                    ],
+                    [{ "text": "Docs", "url": "https://docs.flow-like.com/" }]
+                ]
+            })
+        );
+
+        let removed = inline_keyboard_markup(&[]).unwrap();
+        assert!(removed.inline_keyboard.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_buttons_are_rejected() {
+        let invalid = [
+            button("", Some("data"), None),
+            button("Both", Some("data"), Some("https://flow-like.com")),
+            button("Neither", None, None),
+            button("Too long", Some(&"x".repeat(65)), None),
+            button("Bad url", None, Some("not a url")),
+        ];
+        for button in invalid {
+            assert!(inline_keyboard_markup(&[vec![button]]).is_err());
+        }
+    }
+
+    #[test]
+    fn test_callback_payload_parsing() {
+        let payload = json!({
+            "source": "telegram",
+            "bot_id": "bot-1",
+            "event_id": "event-1",
+            "local_session": {
+                "bot_token": "123:abc",
+                "bot_username": "flow_bot",
+                "chat_id": "-100200",
+                "chat_type": "Public",
+                "message_id": "42",
+                "chat_title": "Team",
+                "user": { "id": "7", "name": "Ada", "username": "ada", "is_bot": false }
+            },
+            "callback_query": {
+                "id": "4382bfdwdsb323b2d9",
+                "data": "vote:yes",
+                "from_id": 7,
+                "from_username": "ada",
+                "chat_id": -100200,
+                "message_id": 42,
+                "message_text": "Ship it?"
+            }
+        });
+
+        let (session, callback) = parse_callback_payload(payload).unwrap();
+        assert_eq!(session.bot_token, "123:abc");
+        assert_eq!(session.message_id, "42");
+        assert_eq!(callback.callback_id, "4382bfdwdsb323b2d9");
+        assert_eq!(callback.data, "vote:yes");
+        assert_eq!(callback.user_id, "7");
+        assert_eq!(callback.chat_id.as_deref(), Some("-100200"));
+        assert_eq!(callback.message_id.as_deref(), Some("42"));
+        assert_eq!(callback.message_text.as_deref(), Some("Ship it?"));
+
+        // Messages sent through inline mode carry no chat
+        let inline = json!({
+            "local_session": {
+                "bot_token": "123:abc",
+                "chat_id": "",
+                "message_id": "",
+                "chat_type": ""
+            },
+            "callback_query": { "id": "1", "from_id": 7 }
+        });
+        let (_, callback) = parse_callback_payload(inline).unwrap();
+        assert_eq!(callback.data, "");
+        assert!(callback.chat_id.is_none());
+
+        assert!(parse_callback_payload(json!({ "message": {} })).is_err());
+    }
+}
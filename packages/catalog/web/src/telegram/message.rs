@@ -1,10 +1,11 @@
 //! Telegram message operations
 
+use super::keyboard::{InlineButton, InlineKeyboard, inline_keyboard_markup};
 use super::session::{TelegramSession, get_telegram_bot};
 use flow_like::flow::{
     execution::context::ExecutionContext,
     node::{Node, NodeLogic},
-    pin::PinOptions,
+    pin::{PinOptions, ValueType},
     variable::VariableType,
 };
 use flow_like_types::{async_trait, json::json};
@@ -83,6 +84,16 @@ impl NodeLogic for SendMessageNode {
             VariableType::Boolean,
         );
 
+        node.add_input_pin(
+            "inline_keyboard",
+            "Inline Keyboard",
+            "Optional rows of buttons below the message, each with a text and either callback_data or url. Clicks fire the Telegram sink as a callback event",
+            VariableType::Struct,
+        )
+        .set_value_type(ValueType::Array)
+        .set_schema::<Vec<InlineButton>>()
+        .set_default_value(Some(json!([])));
+
         node.add_output_pin(
             "exec_out",
             "Output",
@@ -113,6 +124,10 @@ impl NodeLogic for SendMessageNode {
             .evaluate_pin::<bool>("disable_notification")
             .await
             .unwrap_or(false);
+        let keyboard: InlineKeyboard = context
+            .evaluate_pin("inline_keyboard")
+            .await
+            .unwrap_or_default();
 
         let bot = get_telegram_bot(context, &session.ref_id).await?;
         let chat_id = session.chat_id()?;
@@ -123,6 +138,10 @@ impl NodeLogic for SendMessageNode {
             .parse_mode(ParseMode::MarkdownV2)
             .disable_notification(silent);
 
+        if keyboard.iter().any(|row| !row.is_empty()) {
+            request = request.reply_markup(inline_keyboard_markup(&keyboard)?);
+        }
+
         if let Some(reply_id) = reply_to
             && let Ok(msg_id) = reply_id.parse::<i32>()
         {
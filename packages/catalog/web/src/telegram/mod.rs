@@ -6,6 +6,8 @@
 //! 1. Receive a chat event from Telegram sink (via Chat Event node)
 //! 2. Use `To Telegram Session` node to create a session from `global_session`
 //! 3. Use various Telegram operation nodes with the session
+//!
+//! Inline keyboard buttons fire the sink again, `Callback Event` reads that payload.
 
 #[cfg(feature = "execute")]
 pub mod bot;
@@ -32,6 +34,8 @@ pub mod interactive;
 #[cfg(feature = "execute")]
 pub mod invite;
 #[cfg(feature = "execute")]
+pub mod keyboard;
+#[cfg(feature = "execute")]
 pub mod media;
 #[cfg(feature = "execute")]
 pub mod member;
@@ -71,6 +75,8 @@ pub use interaction::{CallbackResponse, UserReply};
 #[cfg(feature = "execute")]
 pub use invite::ChatInviteLink;
 #[cfg(feature = "execute")]
+pub use keyboard::{InlineButton, InlineKeyboard};
+#[cfg(feature = "execute")]
 pub use member::{AdminInfo, ChatMemberInfo};
 #[cfg(feature = "execute")]
 pub use payments::{InvoiceLink, LabeledPrice, StarTransaction};
@@ -0,0 +1,227 @@
+//! Outbound Twilio nodes, sending SMS and MMS from an account's phone numbers
+
+pub mod sms;
+
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic},
+    pin::PinOptions,
+    variable::VariableType,
+};
+use flow_like_types::{
+    Value, async_trait,
+    json::{self, json},
+    reqwest::{self, RequestBuilder, StatusCode},
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+pub const TWILIO_API_URL: &str = "https://api.twilio.com/2010-04-01";
+
+/// Account SID and auth token from the Twilio console
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub struct TwilioAccount {
+    pub account_sid: String,
+    pub auth_token: String,
+}
+
+impl TwilioAccount {
+    /// POST to a resource of the account, e.g. `Messages.json`
+    pub fn post(&self, client: &reqwest::Client, resource: &str) -> RequestBuilder {
+        client
+            .post(format!(
+                "{}/Accounts/{}/{}",
+                TWILIO_API_URL, self.account_sid, resource
+            ))
+            .basic_auth(&self.account_sid, Some(&self.auth_token))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TwilioError {
+    /// Error document of the REST API, `code` is listed at twilio.com/docs/errors
+    Api { code: i64, message: String },
+    /// Any other failed request
+    Request(String),
+}
+
+impl TwilioError {
+    pub fn code(&self) -> i64 {
+        match self {
+            Self::Api { code, .. } => *code,
+            Self::Request(_) => 0,
+        }
+    }
+}
+
+impl fmt::Display for TwilioError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Api { code, message } => write!(f, "Twilio error {}: {}", code, message),
+            Self::Request(message) => write!(f, "Twilio request failed: {}", message),
+        }
+    }
+}
+
+/// Failed calls come back as `{"code": 21211, "message": "...", "status": 400}`
+pub fn parse_response(status: StatusCode, body: &str) -> Result<Value, TwilioError> {
+    let parsed: Option<Value> = json::from_str(body).ok();
+    match parsed {
+        Some(body) if status.is_success() => Ok(body),
+        Some(body) if body["code"].is_i64() => Err(TwilioError::Api {
+            code: body["code"].as_i64().unwrap_or_default(),
+            message: body["message"].as_str().unwrap_or_default().to_string(),
+        }),
+        _ if status == StatusCode::TOO_MANY_REQUESTS => Err(TwilioError::Api {
+            code: 20429,
+            message: "Too Many Requests".to_string(),
+        }),
+        _ => Err(TwilioError::Request(format!("HTTP {}: {}", status, body))),
+    }
+}
+
+pub(crate) async fn send(request: RequestBuilder) -> Result<Value, TwilioError> {
+    let response = request
+        .send()
+        .await
+        .map_err(|e| TwilioError::Request(e.to_string()))?;
+    let status = response.status();
+    let body = response
+        .text()
+        .await
+        .map_err(|e| TwilioError::Request(e.to_string()))?;
+    parse_response(status, &body)
+}
+
+/// Routes a failed call to the `error` pin of the node.
+pub(crate) async fn fail(
+    context: &mut ExecutionContext,
+    error: TwilioError,
+) -> flow_like_types::Result<()> {
+    context
+        .set_pin_value("error_message", json!(error.to_string()))
+        .await?;
+    context
+        .set_pin_value("error_code", json!(error.code()))
+        .await?;
+    context.activate_exec_pin("error").await?;
+    Ok(())
+}
+
+/// Adds the `error`, `error_message` and `error_code` outputs [`fail`] sets.
+pub(crate) fn add_error_pins(node: &mut Node) {
+    node.add_output_pin(
+        "error",
+        "Error",
+        "Triggered when Twilio rejects the request",
+        VariableType::Execution,
+    );
+    node.add_output_pin(
+        "error_message",
+        "Error Message",
+        "What went wrong",
+        VariableType::String,
+    );
+    node.add_output_pin(
+        "error_code",
+        "Error Code",
+        "Twilio error code like 21211 for an invalid number, 0 if the request did not reach Twilio",
+        VariableType::Integer,
+    );
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct TwilioAccountNode;
+
+impl TwilioAccountNode {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl NodeLogic for TwilioAccountNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "twilio_account",
+            "Twilio Account",
+            "Twilio account for the Twilio nodes. Copy the Account SID and Auth Token from the Twilio console",
+            "Web/Twilio",
+        );
+        node.add_icon("/flow/icons/message.svg");
+
+        node.add_input_pin(
+            "account_sid",
+            "Account SID",
+            "Starts with 'AC'",
+            VariableType::String,
+        );
+        node.add_input_pin(
+            "auth_token",
+            "Auth Token",
+            "Auth token of the account",
+            VariableType::String,
+        )
+        .set_options(PinOptions::new().set_sensitive(true).build());
+
+        node.add_output_pin("account", "Account", "Twilio account", VariableType::Struct)
+            .set_schema::<TwilioAccount>()
+            .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        let account_sid: String = context.evaluate_pin("account_sid").await?;
+        let auth_token: String = context.evaluate_pin("auth_token").await?;
+        let account_sid = account_sid.trim().to_string();
+        let auth_token = auth_token.trim().to_string();
+        if !account_sid.starts_with("AC") || auth_token.is_empty() {
+            return Err(flow_like_types::anyhow!(
+                "Account SID and Auth Token are required, find them on the Twilio console dashboard"
+            ));
+        }
+
+        context
+            .set_pin_value(
+                "account",
+                json!(TwilioAccount {
+                    account_sid,
+                    auth_token
+                }),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MESSAGE: &str = include_str!("../tests/fixtures/twilio/message.json");
+    const INVALID_NUMBER: &str = include_str!("../tests/fixtures/twilio/invalid_number.json");
+
+    #[test]
+    fn test_success() {
+        let body = parse_response(StatusCode::CREATED, MESSAGE).unwrap();
+        assert_eq!(body["sid"], json!("SM1f0e8ae6ade43cb3c0ce4525424e404f"));
+        assert_eq!(body["status"], json!("queued"));
+    }
+
+    #[test]
+    fn test_error_codes() {
+        let error = parse_response(StatusCode::BAD_REQUEST, INVALID_NUMBER).unwrap_err();
+        assert_eq!(error.code(), 21211);
+        assert!(error.to_string().contains("Invalid 'To' Phone Number"));
+
+        let error = parse_response(StatusCode::TOO_MANY_REQUESTS, "").unwrap_err();
+        assert_eq!(error.code(), 20429);
+
+        let error = parse_response(StatusCode::BAD_GATEWAY, "<html>").unwrap_err();
+        assert!(matches!(error, TwilioError::Request(ref message) if message.contains("502")));
+        assert_eq!(error.code(), 0);
+    }
+}
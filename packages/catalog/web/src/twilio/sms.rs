@@ -0,0 +1,242 @@
+use super::{TwilioAccount, TwilioError, add_error_pins, fail, send};
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic},
+    pin::{PinOptions, ValueType},
+    variable::VariableType,
+};
+use flow_like_types::{async_trait, json::json, reqwest};
+
+/// Twilio accepts up to 10 media URLs per MMS
+const MAX_MEDIA: usize = 10;
+
+/// Form fields of a `Messages.json` request. Twilio takes one `MediaUrl` field per attachment.
+pub fn send_sms_form(
+    from: &str,
+    to: &str,
+    body: &str,
+    media_urls: &[String],
+    status_callback: &str,
+) -> Result<Vec<(&'static str, String)>, TwilioError> {
+    let (from, to) = (from.trim(), to.trim());
+    if from.is_empty() || to.is_empty() {
+        return Err(TwilioError::Request(
+            "From and To numbers are required".to_string(),
+        ));
+    }
+
+    let media_urls: Vec<&str> = media_urls
+        .iter()
+        .map(|url| url.trim())
+        .filter(|url| !url.is_empty())
+        .collect();
+    if body.trim().is_empty() && media_urls.is_empty() {
+        return Err(TwilioError::Request(
+            "Body or media URLs are required".to_string(),
+        ));
+    }
+    if media_urls.len() > MAX_MEDIA {
+        return Err(TwilioError::Request(format!(
+            "At most {} media URLs can be sent, got {}",
+            MAX_MEDIA,
+            media_urls.len()
+        )));
+    }
+    if let Some(url) = media_urls
+        .iter()
+        .find(|url| !url.starts_with("https://") && !url.starts_with("http://"))
+    {
+        return Err(TwilioError::Request(format!(
+            "Media URL must be publicly reachable over http(s): {}",
+            url
+        )));
+    }
+
+    let mut form = vec![("From", from.to_string()), ("To", to.to_string())];
+    if !body.trim().is_empty() {
+        form.push(("Body", body.to_string()));
+    }
+    form.extend(
+        media_urls
+            .into_iter()
+            .map(|url| ("MediaUrl", url.to_string())),
+    );
+    if !status_callback.trim().is_empty() {
+        form.push(("StatusCallback", status_callback.trim().to_string()));
+    }
+    Ok(form)
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct TwilioSendSmsNode;
+
+impl TwilioSendSmsNode {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl NodeLogic for TwilioSendSmsNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "twilio_send_sms",
+            "Send SMS",
+            "Sends an SMS, or an MMS when media URLs are set, from one of your Twilio numbers",
+            "Web/Twilio",
+        );
+        node.add_icon("/flow/icons/message.svg");
+
+        node.add_input_pin("exec_in", "In", "Trigger", VariableType::Execution);
+        node.add_input_pin("account", "Account", "Twilio account", VariableType::Struct)
+            .set_schema::<TwilioAccount>()
+            .set_options(PinOptions::new().set_enforce_schema(true).build());
+        node.add_input_pin(
+            "from",
+            "From",
+            "Twilio number in E.164 format like +15017122661, or a messaging service SID",
+            VariableType::String,
+        );
+        node.add_input_pin(
+            "to",
+            "To",
+            "Recipient in E.164 format like +15558675310",
+            VariableType::String,
+        );
+        node.add_input_pin("body", "Body", "Message text", VariableType::String)
+            .set_default_value(Some(json!("")));
+        node.add_input_pin(
+            "media_urls",
+            "Media URLs",
+            "Public URLs of images or files to attach, up to 10",
+            VariableType::String,
+        )
+        .set_value_type(ValueType::Array)
+        .set_default_value(Some(json!([])));
+        node.add_input_pin(
+            "status_callback",
+            "Status Callback",
+            "Optional URL Twilio posts delivery status updates to",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("")));
+
+        node.add_output_pin(
+            "exec_out",
+            "Out",
+            "Triggered once Twilio accepted the message",
+            VariableType::Execution,
+        );
+        node.add_output_pin(
+            "sid",
+            "Message SID",
+            "SID of the message, starts with 'SM' or 'MM'",
+            VariableType::String,
+        );
+        node.add_output_pin(
+            "status",
+            "Status",
+            "Initial status, usually queued or accepted",
+            VariableType::String,
+        );
+        add_error_pins(&mut node);
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+        context.deactivate_exec_pin("error").await?;
+
+        let account: TwilioAccount = context.evaluate_pin("account").await?;
+        let from: String = context.evaluate_pin("from").await?;
+        let to: String = context.evaluate_pin("to").await?;
+        let body: String = context.evaluate_pin("body").await?;
+        let media_urls: Vec<String> = context.evaluate_pin("media_urls").await?;
+        let status_callback: String = context.evaluate_pin("status_callback").await?;
+
+        let form = match send_sms_form(&from, &to, &body, &media_urls, &status_callback) {
+            Ok(form) => form,
+            Err(error) => return fail(context, error).await,
+        };
+
+        let client = reqwest::Client::new();
+        let request = account.post(&client, "Messages.json").form(&form);
+
+        match send(request).await {
+            Ok(message) => {
+                let sid = message["sid"].as_str().unwrap_or_default();
+                let status = message["status"].as_str().unwrap_or_default();
+                context.set_pin_value("sid", json!(sid)).await?;
+                context.set_pin_value("status", json!(status)).await?;
+                context.activate_exec_pin("exec_out").await?;
+                Ok(())
+            }
+            Err(error) => fail(context, error).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sms_form() {
+        let form = send_sms_form(" +15017122661 ", "+15558675310", "Hi there", &[], "").unwrap();
+        assert_eq!(
+            form,
+            vec![
+                ("From", "+15017122661".to_string()),
+                ("To", "+15558675310".to_string()),
+                ("Body", "Hi there".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mms_form_repeats_media_url() {
+        let media = vec![
+            "https://example.com/a.png".to_string(),
+            " ".to_string(),
+            "https://example.com/b.jpg".to_string(),
+        ];
+        let form = send_sms_form(
+            "+15017122661",
+            "+15558675310",
+            "",
+            &media,
+            "https://example.com/status",
+        )
+        .unwrap();
+        assert_eq!(
+            form,
+            vec![
+                ("From", "+15017122661".to_string()),
+                ("To", "+15558675310".to_string()),
+                ("MediaUrl", "https://example.com/a.png".to_string()),
+                ("MediaUrl", "https://example.com/b.jpg".to_string()),
+                ("StatusCallback", "https://example.com/status".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_requests() {
+        assert!(send_sms_form("", "+15558675310", "Hi", &[], "").is_err());
+        assert!(send_sms_form("+15017122661", "+15558675310", " ", &[], "").is_err());
+        assert!(
+            send_sms_form(
+                "+15017122661",
+                "+15558675310",
+                "",
+                &["file:///tmp/a.png".to_string()],
+                ""
+            )
+            .is_err()
+        );
+        let too_many = vec!["https://example.com/a.png".to_string(); 11];
+        assert!(send_sms_form("+15017122661", "+15558675310", "", &too_many, "").is_err());
+    }
+}
@@ -7,10 +7,13 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::{future::Future, pin::Pin, sync::Arc};
 
+pub mod batch_fetch;
 pub mod download;
 pub mod fetch;
+pub mod graphql_paginate;
 pub mod request;
 pub mod response;
+pub mod stream_ndjson;
 pub mod streaming_fetch;
 
 pub type StreamingCallback = Arc<
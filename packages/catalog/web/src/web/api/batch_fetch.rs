@@ -0,0 +1,373 @@
+use std::collections::HashMap;
+
+use flow_like::flow::{
+    execution::{LogLevel, context::ExecutionContext},
+    node::{Node, NodeLogic},
+    pin::{PinOptions, ValueType},
+    variable::VariableType,
+};
+use flow_like_types::{
+    Value, anyhow, async_trait,
+    json::{Map, json},
+    minijinja::{self, Environment, UndefinedBehavior},
+    reqwest, tokio,
+};
+use futures::StreamExt;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::{HttpBody, HttpRequest, HttpResponse};
+
+const MAX_CONCURRENCY: i64 = 100;
+
+/// Outcome of the request for one input item.
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
+pub struct BatchHttpResult {
+    /// Position of the item in the input array
+    pub index: usize,
+    pub success: bool,
+    /// Status code of the response, 0 if no response was received
+    pub status_code: u16,
+    pub response: Option<HttpResponse>,
+    pub error: Option<String>,
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct BatchHttpNode {}
+
+impl BatchHttpNode {
+    pub fn new() -> Self {
+        BatchHttpNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for BatchHttpNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "http_batch_fetch",
+            "Batch API Call",
+            "Performs one HTTP request per item, rendered from a request template, with bounded concurrency. Results keep the input order and failed items do not fail the batch",
+            "Web/API",
+        );
+
+        node.add_icon("/flow/icons/web.svg");
+        node.set_long_running(true);
+
+        node.add_input_pin(
+            "exec_in",
+            "Execute",
+            "Initiate the requests",
+            VariableType::Execution,
+        );
+
+        node.add_input_pin(
+            "items",
+            "Items",
+            "One request is made for each item",
+            VariableType::Generic,
+        )
+        .set_value_type(ValueType::Array)
+        .set_default_value(Some(json!([])));
+
+        node.add_input_pin(
+            "request",
+            "Request Template",
+            "URL, header values and body may use Jinja placeholders like {{ item.id }} and {{ index }}. A body value that is only a placeholder keeps the item's type",
+            VariableType::Struct,
+        )
+        .set_schema::<HttpRequest>()
+        .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node.add_input_pin(
+            "concurrency",
+            "Concurrency",
+            "Maximum number of requests in flight (1-100)",
+            VariableType::Integer,
+        )
+        .set_default_value(Some(json!(10)));
+
+        node.add_output_pin(
+            "exec_out",
+            "Done",
+            "All requests finished",
+            VariableType::Execution,
+        );
+
+        node.add_output_pin(
+            "results",
+            "Results",
+            "One result per item, in input order",
+            VariableType::Struct,
+        )
+        .set_schema::<BatchHttpResult>()
+        .set_value_type(ValueType::Array);
+
+        node.add_output_pin(
+            "succeeded",
+            "Succeeded",
+            "Number of requests with a 2xx response",
+            VariableType::Integer,
+        );
+
+        node.add_output_pin(
+            "failed",
+            "Failed",
+            "Number of requests that errored or returned a non 2xx status",
+            VariableType::Integer,
+        );
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+
+        let items: Vec<Value> = context.evaluate_pin("items").await?;
+        let template: HttpRequest = context.evaluate_pin("request").await?;
+        let concurrency: i64 = context.evaluate_pin("concurrency").await?;
+        let concurrency = concurrency.clamp(1, MAX_CONCURRENCY) as usize;
+
+        let env = template_environment();
+        let client = reqwest::Client::new();
+
+        let requests = items.iter().enumerate().map(|(index, item)| {
+            let request = render_request(&env, &template, item, index);
+            let client = client.clone();
+            async move {
+                let result = match request {
+                    Ok(request) => request.trigger(&client).await,
+                    Err(err) => Err(err),
+                };
+                to_result(index, result)
+            }
+        });
+        let batch = futures::stream::iter(requests)
+            .buffered(concurrency)
+            .collect::<Vec<_>>();
+
+        let results = match context.get_cancellation_token() {
+            Some(token) => tokio::select! {
+                biased;
+                _ = token.cancelled() => return Err(anyhow!("Execution was cancelled")),
+                results = batch => results,
+            },
+            None => batch.await,
+        };
+
+        let succeeded = results.iter().filter(|result| result.success).count();
+        let failed = results.len() - succeeded;
+        if failed > 0 {
+            context.log_message(
+                &format!("{} of {} requests failed", failed, results.len()),
+                LogLevel::Warn,
+            );
+        }
+
+        context.set_pin_value("results", json!(results)).await?;
+        context.set_pin_value("succeeded", json!(succeeded)).await?;
+        context.set_pin_value("failed", json!(failed)).await?;
+        context.activate_exec_pin("exec_out").await?;
+
+        Ok(())
+    }
+}
+
+fn template_environment() -> Environment<'static> {
+    let mut env = Environment::new();
+    // A misspelled field should fail the item instead of sending an empty value
+    env.set_undefined_behavior(UndefinedBehavior::Strict);
+    env
+}
+
+fn to_result(index: usize, result: flow_like_types::Result<HttpResponse>) -> BatchHttpResult {
+    match result {
+        Ok(response) => {
+            let success = response.is_success();
+            BatchHttpResult {
+                index,
+                success,
+                status_code: response.status_code,
+                error: (!success)
+                    .then(|| format!("Request failed with status {}", response.status_code)),
+                response: Some(response),
+            }
+        }
+        Err(err) => BatchHttpResult {
+            index,
+            success: false,
+            status_code: 0,
+            response: None,
+            error: Some(err.to_string()),
+        },
+    }
+}
+
+/// Fills the template's URL, header values and body for one item.
+pub(crate) fn render_request(
+    env: &Environment,
+    template: &HttpRequest,
+    item: &Value,
+    index: usize,
+) -> flow_like_types::Result<HttpRequest> {
+    let ctx = json!({ "item": item, "index": index });
+
+    let mut request = HttpRequest::new(
+        env.render_str(&template.url, &ctx)?,
+        template.method.clone(),
+    );
+    if let Some(headers) = &template.headers {
+        let headers = headers
+            .iter()
+            .map(|(key, value)| Ok((key.clone(), env.render_str(value, &ctx)?)))
+            .collect::<flow_like_types::Result<HashMap<_, _>>>()?;
+        request.set_headers(headers);
+    }
+
+    request.body = match &template.body {
+        Some(HttpBody::Json(body)) => Some(HttpBody::Json(render_value(env, body, &ctx)?)),
+        Some(HttpBody::String(body)) => Some(HttpBody::String(env.render_str(body, &ctx)?)),
+        other => other.clone(),
+    };
+
+    Ok(request)
+}
+
+fn render_value(env: &Environment, value: &Value, ctx: &Value) -> flow_like_types::Result<Value> {
+    Ok(match value {
+        Value::String(text) => match single_expression(text) {
+            Some(expression) => {
+                let value: minijinja::Value = env.compile_expression(expression)?.eval(ctx)?;
+                flow_like_types::json::to_value(value)?
+            }
+            None => Value::String(env.render_str(text, ctx)?),
+        },
+        Value::Array(values) => Value::Array(
+            values
+                .iter()
+                .map(|value| render_value(env, value, ctx))
+                .collect::<flow_like_types::Result<_>>()?,
+        ),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(key, value)| Ok((key.clone(), render_value(env, value, ctx)?)))
+                .collect::<flow_like_types::Result<Map<_, _>>>()?,
+        ),
+        other => other.clone(),
+    })
+}
+
+/// The expression of a string that consists of exactly one `{{ ... }}` placeholder.
+fn single_expression(text: &str) -> Option<&str> {
+    let inner = text.trim().strip_prefix("{{")?.strip_suffix("}}")?;
+    if inner.contains("{{") || inner.contains("}}") {
+        return None;
+    }
+    Some(inner.trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Method;
+    use super::*;
+
+    fn template(body: Option<HttpBody>) -> HttpRequest {
+        let mut request = HttpRequest::new(
+            "https://api.example.com/users/{{ item.id }}?page={{ index }}".to_string(),
+            Method::POST,
+        );
+        request.set_header(
+            "Authorization".to_string(),
+            "Bearer {{ item.token }}".to_string(),
+        );
+        request.body = body;
+        request
+    }
+
+    #[test]
+    fn test_render_url_and_headers() {
+        let env = template_environment();
+        let item = json!({ "id": 42, "token": "abc" });
+        let request = render_request(&env, &template(None), &item, 3).unwrap();
+
+        assert_eq!(request.url, "https://api.example.com/users/42?page=3");
+        assert_eq!(
+            request.headers.unwrap().get("Authorization").unwrap(),
+            "Bearer abc"
+        );
+        assert!(request.body.is_none());
+    }
+
+    #[test]
+    fn test_render_json_body_keeps_types() {
+        let env = template_environment();
+        let item = json!({ "id": 7, "token": "t", "tags": ["a", "b"], "name": "Ada" });
+        let body = HttpBody::Json(json!({
+            "id": "{{ item.id }}",
+            "tags": "{{ item.tags }}",
+            "greeting": "Hello {{ item.name }}",
+            "nested": [{ "position": "{{ index }}" }],
+            "fixed": true,
+        }));
+        let request = render_request(&env, &template(Some(body)), &item, 1).unwrap();
+
+        let Some(HttpBody::Json(body)) = request.body else {
+            panic!("expected a json body");
+        };
+        assert_eq!(
+            body,
+            json!({
+                "id": 7,
+                "tags": ["a", "b"],
+                "greeting": "Hello Ada",
+                "nested": [{ "position": 1 }],
+                "fixed": true,
+            })
+        );
+    }
+
+    #[test]
+    fn test_render_string_body() {
+        let env = template_environment();
+        let item = json!({ "id": 1, "token": "t" });
+        let body = HttpBody::String("id={{ item.id }}&n={{ index + 1 }}".to_string());
+        let request = render_request(&env, &template(Some(body)), &item, 4).unwrap();
+
+        assert!(matches!(request.body, Some(HttpBody::String(ref body)) if body == "id=1&n=5"));
+    }
+
+    #[test]
+    fn test_missing_field_fails_the_item() {
+        let env = template_environment();
+        let item = json!({ "id": 1 });
+        let err = render_request(&env, &template(None), &item, 0).unwrap_err();
+
+        let result = to_result(0, Err(err));
+        assert!(!result.success);
+        assert_eq!(result.status_code, 0);
+        assert!(result.error.is_some());
+    }
+
+    #[test]
+    fn test_non_success_status_is_reported() {
+        let response = HttpResponse {
+            status_code: 404,
+            headers: HashMap::new(),
+            body: None,
+        };
+        let result = to_result(2, Ok(response));
+        assert_eq!(result.index, 2);
+        assert!(!result.success);
+        assert_eq!(result.status_code, 404);
+        assert!(result.response.is_some());
+    }
+
+    #[test]
+    fn test_single_expression() {
+        assert_eq!(single_expression("{{ item.id }}"), Some("item.id"));
+        assert_eq!(single_expression("  {{item}} "), Some("item"));
+        assert_eq!(single_expression("id: {{ item.id }}"), None);
+        assert_eq!(single_expression("{{ a }}-{{ b }}"), None);
+    }
+}
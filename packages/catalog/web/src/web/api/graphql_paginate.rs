@@ -0,0 +1,591 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use flow_like::flow::{
+    execution::{LogLevel, context::ExecutionContext},
+    node::{Node, NodeLogic},
+    pin::{PinOptions, ValueType},
+    variable::VariableType,
+};
+use flow_like_types::{
+    Value, anyhow, async_trait, bail,
+    json::{Map, json},
+    reqwest, tokio,
+};
+
+use super::{HttpBody, HttpRequest, HttpResponse, Method};
+
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+/// Upper bound for waiting on a rate limit window to reset.
+const MAX_RATE_LIMIT_WAIT: Duration = Duration::from_secs(15 * 60);
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct GraphqlPaginateNode {}
+
+impl GraphqlPaginateNode {
+    pub fn new() -> Self {
+        GraphqlPaginateNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for GraphqlPaginateNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "graphql_paginate",
+            "GraphQL Paginate",
+            "Runs a GraphQL query and follows cursor pagination (pageInfo.endCursor / hasNextPage), waiting on rate limits, and collects all edges.node items",
+            "Web/API/GraphQL",
+        );
+
+        node.add_icon("/flow/icons/web.svg");
+        node.set_long_running(true);
+
+        node.add_input_pin("exec_in", "Execute", "", VariableType::Execution);
+
+        node.add_input_pin(
+            "endpoint",
+            "Endpoint",
+            "GraphQL endpoint URL",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("https://api.github.com/graphql")));
+
+        node.add_input_pin(
+            "query",
+            "Query",
+            "GraphQL query. It has to accept the cursor variable and select pageInfo { endCursor hasNextPage } on the connection",
+            VariableType::String,
+        );
+
+        node.add_input_pin(
+            "variables",
+            "Variables",
+            "Query variables",
+            VariableType::Struct,
+        )
+        .set_default_value(Some(json!({})));
+
+        node.add_input_pin(
+            "headers",
+            "Headers",
+            "Additional HTTP headers",
+            VariableType::Struct,
+        )
+        .set_default_value(Some(json!({})));
+
+        node.add_input_pin(
+            "token",
+            "Bearer Token",
+            "Sent as 'Authorization: Bearer <token>' if set",
+            VariableType::String,
+        )
+        .set_options(PinOptions::new().set_sensitive(true).build())
+        .set_default_value(Some(json!("")));
+
+        node.add_input_pin(
+            "connection_path",
+            "Connection Path",
+            "Dot separated path to the connection below 'data', e.g. 'repository.issues'. Leave empty to use the first connection that has pageInfo",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("")));
+
+        node.add_input_pin(
+            "cursor_variable",
+            "Cursor Variable",
+            "Name of the variable that receives the end cursor",
+            VariableType::String,
+        )
+        .set_default_value(Some(json!("after")));
+
+        node.add_input_pin(
+            "max_pages",
+            "Max Pages",
+            "Maximum number of pages to fetch, 0 for no limit",
+            VariableType::Integer,
+        )
+        .set_default_value(Some(json!(0)));
+
+        node.add_input_pin(
+            "max_retries",
+            "Max Retries",
+            "Retries per page when rate limited or on server errors",
+            VariableType::Integer,
+        )
+        .set_default_value(Some(json!(5)));
+
+        node.add_output_pin("exec_out", "Output", "", VariableType::Execution);
+
+        node.add_output_pin(
+            "items",
+            "Items",
+            "Aggregated edges.node items of all pages",
+            VariableType::Generic,
+        )
+        .set_value_type(ValueType::Array);
+
+        node.add_output_pin(
+            "pages",
+            "Pages",
+            "Number of fetched pages",
+            VariableType::Integer,
+        );
+
+        node.add_output_pin(
+            "end_cursor",
+            "End Cursor",
+            "Cursor the last page was requested with, can be used to resume",
+            VariableType::String,
+        );
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+
+        let endpoint: String = context.evaluate_pin("endpoint").await?;
+        let query: String = context.evaluate_pin("query").await?;
+        let variables: Value = context.evaluate_pin("variables").await?;
+        let headers: HashMap<String, String> = context.evaluate_pin("headers").await?;
+        let token: String = context.evaluate_pin("token").await?;
+        let connection_path: String = context.evaluate_pin("connection_path").await?;
+        let cursor_variable: String = context.evaluate_pin("cursor_variable").await?;
+        let max_pages: i64 = context.evaluate_pin("max_pages").await?;
+        let max_retries: i64 = context.evaluate_pin("max_retries").await?;
+
+        let mut variables = match variables {
+            Value::Object(variables) => variables,
+            Value::Null => Map::new(),
+            _ => bail!("Variables have to be an object"),
+        };
+
+        let mut request = graphql_request(endpoint, headers, &token);
+
+        let client = reqwest::Client::new();
+        let mut items = Vec::new();
+        let mut pages: i64 = 0;
+        let mut cursor = variables
+            .get(&cursor_variable)
+            .and_then(|cursor| cursor.as_str())
+            .map(ToString::to_string);
+
+        loop {
+            context.check_cancelled()?;
+
+            if max_pages > 0 && pages >= max_pages {
+                context.log_message(
+                    &format!("Stopped after {} pages (max pages reached)", pages),
+                    LogLevel::Info,
+                );
+                break;
+            }
+
+            variables.insert(cursor_variable.clone(), json!(cursor));
+            request.set_body(HttpBody::Json(json!({
+                "query": query,
+                "variables": variables,
+            })));
+
+            let (data, response) =
+                fetch_page(context, &client, &request, max_retries.max(0) as u32).await?;
+            pages += 1;
+
+            let connection = find_connection(&data, &connection_path)?;
+            let (nodes, next_cursor) = extract_page(connection)?;
+            items.extend(nodes);
+
+            let Some(next_cursor) = next_cursor else {
+                break;
+            };
+            cursor = Some(next_cursor);
+
+            if let Some(delay) = quota_exhausted_delay(&response.headers, unix_now()) {
+                context.log_message(
+                    &format!(
+                        "Rate limit quota exhausted, waiting {} s for the reset",
+                        delay.as_secs()
+                    ),
+                    LogLevel::Info,
+                );
+                wait(context, delay).await?;
+            }
+        }
+
+        context.set_pin_value("items", json!(items)).await?;
+        context.set_pin_value("pages", json!(pages)).await?;
+        context
+            .set_pin_value("end_cursor", json!(cursor.unwrap_or_default()))
+            .await?;
+        context.activate_exec_pin("exec_out").await?;
+
+        Ok(())
+    }
+}
+
+/// POST request with the given headers, bearer token and a default `User-Agent`,
+/// which APIs like GitHub require.
+pub(crate) fn graphql_request(
+    endpoint: String,
+    headers: HashMap<String, String>,
+    token: &str,
+) -> HttpRequest {
+    let mut request = HttpRequest::new(endpoint, Method::POST);
+    request.set_headers(headers);
+    if !token.is_empty() {
+        request.set_header("Authorization".to_string(), format!("Bearer {}", token));
+    }
+    if !request.headers.as_ref().is_some_and(|headers| {
+        headers
+            .keys()
+            .any(|key| key.eq_ignore_ascii_case("user-agent"))
+    }) {
+        request.set_header("User-Agent".to_string(), "flow-like".to_string());
+    }
+    request
+}
+
+/// Sends a single page request, retrying rate limited and failed requests with backoff.
+async fn fetch_page(
+    context: &mut ExecutionContext,
+    client: &reqwest::Client,
+    request: &HttpRequest,
+    max_retries: u32,
+) -> flow_like_types::Result<(Value, HttpResponse)> {
+    let mut attempt = 0;
+
+    loop {
+        context.check_cancelled()?;
+        let response = request.trigger(client).await?;
+        let body = response.to_json().unwrap_or(Value::Null);
+
+        let rate_limited = is_rate_limited(&response, &body);
+        let retryable = rate_limited || response.status_code >= 500;
+
+        if retryable {
+            if attempt >= max_retries {
+                bail!(
+                    "GraphQL request failed with status {} after {} retries: {}",
+                    response.status_code,
+                    attempt,
+                    response.to_text().unwrap_or_default()
+                );
+            }
+            attempt += 1;
+
+            let delay = rate_limit_delay(&response.headers, unix_now())
+                .filter(|_| rate_limited)
+                .unwrap_or_else(|| backoff_delay(attempt));
+            context.log_message(
+                &format!(
+                    "GraphQL request returned {}{}, retrying in {} ms",
+                    response.status_code,
+                    if rate_limited { " (rate limited)" } else { "" },
+                    delay.as_millis()
+                ),
+                LogLevel::Warn,
+            );
+            wait(context, delay).await?;
+            continue;
+        }
+
+        if !response.is_success() {
+            bail!(
+                "GraphQL request failed with status {}: {}",
+                response.status_code,
+                response.to_text().unwrap_or_default()
+            );
+        }
+
+        if let Some(errors) = body.get("errors").and_then(|errors| errors.as_array())
+            && !errors.is_empty()
+        {
+            let messages: Vec<&str> = errors
+                .iter()
+                .filter_map(|error| error.get("message").and_then(|message| message.as_str()))
+                .collect();
+            if body.get("data").is_none_or(|data| data.is_null()) {
+                bail!("GraphQL errors: {}", messages.join("; "));
+            }
+            context.log_message(
+                &format!("GraphQL returned partial data: {}", messages.join("; ")),
+                LogLevel::Warn,
+            );
+        }
+
+        let data = body
+            .get("data")
+            .cloned()
+            .ok_or_else(|| anyhow!("GraphQL response contains no data"))?;
+        return Ok((data, response));
+    }
+}
+
+async fn wait(context: &ExecutionContext, delay: Duration) -> flow_like_types::Result<()> {
+    let Some(token) = context.get_cancellation_token() else {
+        tokio::time::sleep(delay).await;
+        return Ok(());
+    };
+
+    tokio::select! {
+        biased;
+        _ = token.cancelled() => Err(anyhow!("Execution was cancelled")),
+        _ = tokio::time::sleep(delay) => Ok(()),
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+fn header<'a>(headers: &'a HashMap<String, String>, name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.trim())
+}
+
+fn backoff_delay(attempt: u32) -> Duration {
+    let delay =
+        Duration::from_secs(1).saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)));
+    delay.min(MAX_BACKOFF)
+}
+
+fn is_rate_limited(response: &HttpResponse, body: &Value) -> bool {
+    if response.status_code == 429 {
+        return true;
+    }
+
+    if response.status_code == 403
+        && (header(&response.headers, "retry-after").is_some()
+            || header(&response.headers, "x-ratelimit-remaining") == Some("0"))
+    {
+        return true;
+    }
+
+    body.get("errors")
+        .and_then(|errors| errors.as_array())
+        .is_some_and(|errors| {
+            errors.iter().any(|error| {
+                error.get("type").and_then(|kind| kind.as_str()) == Some("RATE_LIMITED")
+                    || error
+                        .get("extensions")
+                        .and_then(|extensions| extensions.get("code"))
+                        .and_then(|code| code.as_str())
+                        == Some("RATE_LIMITED")
+            })
+        })
+}
+
+/// Delay announced by the server, from `Retry-After` or the `X-RateLimit-Reset` epoch.
+fn rate_limit_delay(headers: &HashMap<String, String>, now: u64) -> Option<Duration> {
+    if let Some(seconds) =
+        header(headers, "retry-after").and_then(|value| value.parse::<u64>().ok())
+    {
+        return Some(Duration::from_secs(seconds).min(MAX_RATE_LIMIT_WAIT));
+    }
+
+    let reset = header(headers, "x-ratelimit-reset")?.parse::<u64>().ok()?;
+    Some(Duration::from_secs(reset.saturating_sub(now).max(1)).min(MAX_RATE_LIMIT_WAIT))
+}
+
+/// Delay before the next page if the last response used up the remaining quota.
+fn quota_exhausted_delay(headers: &HashMap<String, String>, now: u64) -> Option<Duration> {
+    if header(headers, "x-ratelimit-remaining")? != "0" {
+        return None;
+    }
+    rate_limit_delay(headers, now)
+}
+
+/// Resolves the connection below `data`, either by its dot separated path or by
+/// searching for the first object that exposes `pageInfo`.
+pub(crate) fn find_connection<'a>(
+    data: &'a Value,
+    path: &str,
+) -> flow_like_types::Result<&'a Value> {
+    let path = path.trim().trim_start_matches("data.");
+    if !path.is_empty() {
+        return path.split('.').try_fold(data, |value, segment| {
+            value.get(segment).ok_or_else(|| {
+                anyhow!(
+                    "Connection path segment '{}' not found in response",
+                    segment
+                )
+            })
+        });
+    }
+
+    fn search(value: &Value) -> Option<&Value> {
+        match value {
+            Value::Object(object) if object.contains_key("pageInfo") => Some(value),
+            Value::Object(object) => object.values().find_map(search),
+            _ => None,
+        }
+    }
+
+    search(data).ok_or_else(|| anyhow!("No connection with pageInfo found in response"))
+}
+
+/// Returns the nodes of a connection and the cursor of the next page, if there is one.
+pub(crate) fn extract_page(
+    connection: &Value,
+) -> flow_like_types::Result<(Vec<Value>, Option<String>)> {
+    let nodes = if let Some(edges) = connection.get("edges").and_then(|edges| edges.as_array()) {
+        edges
+            .iter()
+            .filter_map(|edge| edge.get("node").cloned())
+            .collect()
+    } else if let Some(nodes) = connection.get("nodes").and_then(|nodes| nodes.as_array()) {
+        nodes.clone()
+    } else {
+        bail!("Connection has neither edges nor nodes");
+    };
+
+    let page_info = connection
+        .get("pageInfo")
+        .ok_or_else(|| anyhow!("Connection has no pageInfo"))?;
+    let has_next_page = page_info
+        .get("hasNextPage")
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false);
+    let end_cursor = page_info
+        .get("endCursor")
+        .and_then(|value| value.as_str())
+        .map(ToString::to_string);
+
+    if has_next_page && end_cursor.is_none() {
+        bail!("pageInfo.hasNextPage is true but no endCursor was returned");
+    }
+
+    Ok((nodes, end_cursor.filter(|_| has_next_page)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    fn response(status_code: u16, pairs: &[(&str, &str)]) -> HttpResponse {
+        HttpResponse {
+            status_code,
+            headers: headers(pairs),
+            body: None,
+        }
+    }
+
+    #[test]
+    fn test_extract_page_from_edges() {
+        let connection = json!({
+            "edges": [{ "node": { "id": 1 } }, { "node": { "id": 2 } }],
+            "pageInfo": { "endCursor": "Y3Vyc29y", "hasNextPage": true }
+        });
+        let (nodes, cursor) = extract_page(&connection).unwrap();
+        assert_eq!(nodes, vec![json!({ "id": 1 }), json!({ "id": 2 })]);
+        assert_eq!(cursor.as_deref(), Some("Y3Vyc29y"));
+    }
+
+    #[test]
+    fn test_extract_last_page_from_nodes() {
+        let connection = json!({
+            "nodes": [{ "id": 3 }],
+            "pageInfo": { "endCursor": "Y3Vyc29y", "hasNextPage": false }
+        });
+        let (nodes, cursor) = extract_page(&connection).unwrap();
+        assert_eq!(nodes.len(), 1);
+        assert!(cursor.is_none());
+    }
+
+    #[test]
+    fn test_extract_page_requires_cursor() {
+        let connection = json!({
+            "edges": [],
+            "pageInfo": { "endCursor": null, "hasNextPage": true }
+        });
+        assert!(extract_page(&connection).is_err());
+    }
+
+    #[test]
+    fn test_find_connection() {
+        let data = json!({
+            "repository": {
+                "name": "flow-like",
+                "issues": { "edges": [], "pageInfo": { "hasNextPage": false } }
+            }
+        });
+        let by_path = find_connection(&data, "repository.issues").unwrap();
+        let by_search = find_connection(&data, "").unwrap();
+        assert_eq!(by_path, by_search);
+        assert!(find_connection(&data, "repository.pullRequests").is_err());
+    }
+
+    #[test]
+    fn test_rate_limit_detection() {
+        let body = Value::Null;
+        assert!(is_rate_limited(&response(429, &[]), &body));
+        assert!(is_rate_limited(
+            &response(403, &[("x-ratelimit-remaining", "0")]),
+            &body
+        ));
+        assert!(!is_rate_limited(&response(403, &[]), &body));
+
+        let graphql_error =
+            json!({ "errors": [{ "type": "RATE_LIMITED", "message": "API rate limit exceeded" }] });
+        assert!(is_rate_limited(&response(200, &[]), &graphql_error));
+    }
+
+    #[test]
+    fn test_rate_limit_delay() {
+        let now = 1_700_000_000;
+        assert_eq!(
+            rate_limit_delay(&headers(&[("Retry-After", "30")]), now),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            rate_limit_delay(&headers(&[("x-ratelimit-reset", "1700000042")]), now),
+            Some(Duration::from_secs(42))
+        );
+        assert_eq!(
+            rate_limit_delay(&headers(&[("x-ratelimit-reset", "1600000000")]), now),
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(rate_limit_delay(&headers(&[]), now), None);
+    }
+
+    #[test]
+    fn test_quota_exhausted_delay() {
+        let now = 1_700_000_000;
+        let remaining = headers(&[
+            ("x-ratelimit-remaining", "12"),
+            ("x-ratelimit-reset", "1700000060"),
+        ]);
+        let exhausted = headers(&[
+            ("x-ratelimit-remaining", "0"),
+            ("x-ratelimit-reset", "1700000060"),
+        ]);
+        assert_eq!(quota_exhausted_delay(&remaining, now), None);
+        assert_eq!(
+            quota_exhausted_delay(&exhausted, now),
+            Some(Duration::from_secs(60))
+        );
+    }
+
+    #[test]
+    fn test_backoff_delay() {
+        assert_eq!(backoff_delay(1), Duration::from_secs(1));
+        assert_eq!(backoff_delay(3), Duration::from_secs(4));
+        assert_eq!(backoff_delay(20), MAX_BACKOFF);
+    }
+}
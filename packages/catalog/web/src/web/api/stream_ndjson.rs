@@ -0,0 +1,396 @@
+use flow_like::flow::{
+    execution::{LogLevel, context::ExecutionContext, internal_node::InternalNode},
+    node::{Node, NodeLogic},
+    pin::PinOptions,
+    variable::VariableType,
+};
+use flow_like_types::{Bytes, Value, async_trait, json::json, reqwest};
+use futures::{Stream, StreamExt};
+use std::{collections::VecDeque, pin::Pin};
+
+use super::HttpRequest;
+
+/// A single line of an NDJSON body, either the parsed value or why it could not be parsed
+pub type NdjsonLine = Result<Value, String>;
+
+/// Splits a byte stream into NDJSON lines. Lines may be split across chunks at any byte,
+/// blank lines are skipped and a trailing `\r` is ignored.
+#[derive(Default)]
+pub struct NdjsonDecoder {
+    buffer: Vec<u8>,
+}
+
+impl NdjsonDecoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feeds a chunk and returns every line it completed
+    pub fn push(&mut self, chunk: &[u8]) -> Vec<NdjsonLine> {
+        self.buffer.extend_from_slice(chunk);
+
+        let mut lines = Vec::new();
+        let mut start = 0;
+        while let Some(offset) = self.buffer[start..].iter().position(|byte| *byte == b'\n') {
+            let end = start + offset;
+            if let Some(line) = parse_line(&self.buffer[start..end]) {
+                lines.push(line);
+            }
+            start = end + 1;
+        }
+        self.buffer.drain(..start);
+        lines
+    }
+
+    /// The last line if the stream did not end with a newline
+    pub fn finish(&mut self) -> Option<NdjsonLine> {
+        let rest = std::mem::take(&mut self.buffer);
+        parse_line(&rest)
+    }
+}
+
+fn parse_line(line: &[u8]) -> Option<NdjsonLine> {
+    let line = line.strip_suffix(b"\r").unwrap_or(line);
+    if line.iter().all(u8::is_ascii_whitespace) {
+        return None;
+    }
+
+    Some(
+        flow_like_types::json::from_slice::<Value>(line).map_err(|e| {
+            format!(
+                "Malformed NDJSON line ({}): {}",
+                e,
+                String::from_utf8_lossy(line)
+            )
+        }),
+    )
+}
+
+type ByteStream = Pin<Box<dyn Stream<Item = reqwest::Result<Bytes>> + Send>>;
+
+/// Reads NDJSON lines from a response body as they arrive
+pub struct NdjsonReader {
+    stream: ByteStream,
+    decoder: NdjsonDecoder,
+    pending: VecDeque<NdjsonLine>,
+    finished: bool,
+}
+
+impl NdjsonReader {
+    pub fn new(response: reqwest::Response) -> Self {
+        NdjsonReader {
+            stream: Box::pin(response.bytes_stream()),
+            decoder: NdjsonDecoder::new(),
+            pending: VecDeque::new(),
+            finished: false,
+        }
+    }
+
+    /// The next line, `None` at the end of the stream. Errors only on transport failures.
+    pub async fn next_line(&mut self) -> flow_like_types::Result<Option<NdjsonLine>> {
+        loop {
+            if let Some(line) = self.pending.pop_front() {
+                return Ok(Some(line));
+            }
+            if self.finished {
+                return Ok(None);
+            }
+
+            match self.stream.next().await {
+                Some(chunk) => self.pending.extend(self.decoder.push(&chunk?)),
+                None => {
+                    self.finished = true;
+                    self.pending.extend(self.decoder.finish());
+                }
+            }
+        }
+    }
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct StreamNdjsonNode {}
+
+impl StreamNdjsonNode {
+    pub fn new() -> Self {
+        StreamNdjsonNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for StreamNdjsonNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "stream_ndjson",
+            "Stream NDJSON",
+            "Performs an HTTP request and emits every JSON line of the response as soon as it arrives. Malformed lines trigger On Error and the stream continues.",
+            "Web/API",
+        );
+
+        node.add_icon("/flow/icons/web.svg");
+
+        node.add_input_pin(
+            "exec_in",
+            "Execute",
+            "Initiate the HTTP request",
+            VariableType::Execution,
+        );
+        node.add_input_pin(
+            "request",
+            "Request",
+            "The HTTP request to perform",
+            VariableType::Struct,
+        )
+        .set_schema::<HttpRequest>()
+        .set_options(PinOptions::new().set_enforce_schema(true).build());
+
+        node.add_output_pin(
+            "on_item",
+            "On Item",
+            "Executes for every parsed line",
+            VariableType::Execution,
+        );
+        node.add_output_pin(
+            "item",
+            "Item",
+            "The parsed JSON value of the current line",
+            VariableType::Generic,
+        );
+        node.add_output_pin(
+            "on_error",
+            "On Error",
+            "Executes for every line that is not valid JSON",
+            VariableType::Execution,
+        );
+        node.add_output_pin(
+            "error",
+            "Error",
+            "Why the current line could not be parsed",
+            VariableType::String,
+        );
+        node.add_output_pin(
+            "done",
+            "Done",
+            "Executes once the stream ended",
+            VariableType::Execution,
+        );
+        node.add_output_pin(
+            "count",
+            "Count",
+            "Number of parsed lines",
+            VariableType::Integer,
+        );
+
+        node
+    }
+
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        let done = context.get_pin_by_name("done").await?;
+        context.deactivate_exec_pin_ref(&done).await?;
+
+        let on_item = context.get_pin_by_name("on_item").await?;
+        let item = context.get_pin_by_name("item").await?;
+        let on_error = context.get_pin_by_name("on_error").await?;
+        let error = context.get_pin_by_name("error").await?;
+        let item_nodes = on_item.get_connected_nodes();
+        let error_nodes = on_error.get_connected_nodes();
+
+        let request: HttpRequest = context.evaluate_pin("request").await?;
+        let client = reqwest::Client::new();
+        let response = request.raw_request(&client).await?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(flow_like_types::anyhow!(
+                "NDJSON request failed with status {}",
+                status
+            ));
+        }
+
+        context.activate_exec_pin_ref(&on_item).await?;
+        context.activate_exec_pin_ref(&on_error).await?;
+
+        let mut reader = NdjsonReader::new(response);
+        let mut count: i64 = 0;
+        let mut line_number: usize = 0;
+        while let Some(line) = reader.next_line().await? {
+            line_number += 1;
+            let connected = match line {
+                Ok(value) => {
+                    count += 1;
+                    item.set_value(value).await;
+                    &item_nodes
+                }
+                Err(message) => {
+                    error.set_value(json!(message)).await;
+                    &error_nodes
+                }
+            };
+
+            for node in connected.iter() {
+                let mut sub_context = context.create_sub_context(node).await;
+                let run = InternalNode::trigger(&mut sub_context, &mut None, true).await;
+                sub_context.end_trace();
+                context.push_sub_context(&mut sub_context);
+
+                if let Err(err) = run {
+                    context.log_message(
+                        &format!("Error: {:?} handling line {}", err, line_number),
+                        LogLevel::Error,
+                    );
+                }
+            }
+        }
+
+        context.deactivate_exec_pin_ref(&on_item).await?;
+        context.deactivate_exec_pin_ref(&on_error).await?;
+        context.set_pin_value("count", json!(count)).await?;
+        context.activate_exec_pin_ref(&done).await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Method;
+    use super::*;
+    use std::time::Duration;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Serves a single chunked response, flushing every chunk separately with a pause in
+    /// between so the client sees them arrive one by one
+    async fn dribble_server(chunks: Vec<&'static str>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/stream", listener.local_addr().unwrap());
+
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = [0u8; 4096];
+            let _ = socket.read(&mut request).await.unwrap();
+
+            socket
+                .write_all(
+                    b"HTTP/1.1 200 OK\r\nContent-Type: application/x-ndjson\r\nTransfer-Encoding: chunked\r\n\r\n",
+                )
+                .await
+                .unwrap();
+            for chunk in chunks {
+                let frame = format!("{:x}\r\n{}\r\n", chunk.len(), chunk);
+                socket.write_all(frame.as_bytes()).await.unwrap();
+                socket.flush().await.unwrap();
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+            socket.write_all(b"0\r\n\r\n").await.unwrap();
+            socket.flush().await.unwrap();
+        });
+
+        url
+    }
+
+    async fn read_all(url: String) -> Vec<NdjsonLine> {
+        let request = HttpRequest::new(url, Method::GET);
+        let response = request.raw_request(&reqwest::Client::new()).await.unwrap();
+
+        let mut reader = NdjsonReader::new(response);
+        let mut lines = Vec::new();
+        while let Some(line) = reader.next_line().await.unwrap() {
+            lines.push(line);
+        }
+        lines
+    }
+
+    #[test]
+    fn test_decoder_joins_lines_split_across_chunks() {
+        let mut decoder = NdjsonDecoder::new();
+        assert!(decoder.push(b"{\"id\":").is_empty());
+        let lines = decoder.push(b"1}\n{\"id\":2}\r\n{\"id\"");
+        assert_eq!(lines, vec![Ok(json!({"id": 1})), Ok(json!({"id": 2}))]);
+        assert!(decoder.push(b":3}").is_empty());
+        assert_eq!(decoder.finish(), Some(Ok(json!({"id": 3}))));
+        assert_eq!(decoder.finish(), None);
+    }
+
+    #[test]
+    fn test_decoder_skips_blank_and_reports_malformed_lines() {
+        let mut decoder = NdjsonDecoder::new();
+        let lines = decoder.push(b"\n  \n{\"ok\":true}\nnot json\n\r\n[1,2]\n");
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], Ok(json!({"ok": true})));
+        assert!(lines[1].as_ref().unwrap_err().contains("not json"));
+        assert_eq!(lines[2], Ok(json!([1, 2])));
+        assert_eq!(decoder.finish(), None);
+    }
+
+    #[test]
+    fn test_decoder_handles_multibyte_split() {
+        let line = "{\"text\":\"größe\"}\n".as_bytes();
+        let split = line.iter().position(|byte| *byte > 127).unwrap() + 1;
+
+        let mut decoder = NdjsonDecoder::new();
+        assert!(decoder.push(&line[..split]).is_empty());
+        assert_eq!(
+            decoder.push(&line[split..]),
+            vec![Ok(json!({"text": "größe"}))]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reader_streams_dribbled_response() {
+        let url = dribble_server(vec![
+            "{\"event\":\"start\"}\n{\"ev",
+            "ent\":\"token\",\"value\":\"Hel",
+            "lo\"}\n\n",
+            "{broken\n",
+            "{\"event\":\"end\"}",
+        ])
+        .await;
+
+        let lines = read_all(url).await;
+
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], Ok(json!({"event": "start"})));
+        assert_eq!(lines[1], Ok(json!({"event": "token", "value": "Hello"})));
+        assert!(lines[2].is_err());
+        assert_eq!(lines[3], Ok(json!({"event": "end"})));
+    }
+
+    #[tokio::test]
+    async fn test_reader_yields_lines_before_stream_ends() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/stream", listener.local_addr().unwrap());
+        let (release, released) = tokio::sync::oneshot::channel::<()>();
+
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = [0u8; 4096];
+            let _ = socket.read(&mut request).await.unwrap();
+            socket
+                .write_all(b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n")
+                .await
+                .unwrap();
+            socket.write_all(b"7\r\n{\"a\":1}\r\n").await.unwrap();
+            socket.write_all(b"1\r\n\n\r\n").await.unwrap();
+            socket.flush().await.unwrap();
+            // Hold the rest back until the client saw the first line
+            let _ = released.await;
+            socket.write_all(b"0\r\n\r\n").await.unwrap();
+        });
+
+        let response = HttpRequest::new(url, Method::GET)
+            .raw_request(&reqwest::Client::new())
+            .await
+            .unwrap();
+        let mut reader = NdjsonReader::new(response);
+
+        let first = tokio::time::timeout(Duration::from_secs(5), reader.next_line())
+            .await
+            .expect("first line should arrive before the stream ends")
+            .unwrap();
+        assert_eq!(first, Some(Ok(json!({"a": 1}))));
+
+        release.send(()).unwrap();
+        assert_eq!(reader.next_line().await.unwrap(), None);
+    }
+}
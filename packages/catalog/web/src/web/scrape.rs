@@ -1,3 +1,4 @@
+pub mod extract;
 pub mod extract_links;
 
 // use deno_core::{JsRuntime, RuntimeOptions};
@@ -0,0 +1,411 @@
+use flow_like::flow::{
+    execution::context::ExecutionContext,
+    node::{Node, NodeLogic},
+    pin::ValueType,
+    variable::VariableType,
+};
+#[cfg(feature = "execute")]
+use flow_like_types::{
+    Value, async_trait, bail,
+    json::{Map, json},
+    reqwest,
+};
+#[cfg(not(feature = "execute"))]
+use flow_like_types::{async_trait, json::json};
+#[cfg(feature = "execute")]
+use scraper::{ElementRef, Html, Selector};
+#[cfg(feature = "execute")]
+use std::time::Duration;
+
+/// A field is either `"css selector"` for the element text, `"css selector@attribute"`
+/// or `{ "selector": "...", "attribute": "..." }`. An empty selector targets the scope
+/// element itself, which is handy for repeating items.
+#[cfg(feature = "execute")]
+struct FieldSelector {
+    selector: Option<Selector>,
+    attribute: Option<String>,
+}
+
+#[cfg(feature = "execute")]
+fn parse_selector(selector: &str) -> flow_like_types::Result<Selector> {
+    Selector::parse(selector)
+        .map_err(|e| flow_like_types::anyhow!("Invalid CSS selector '{selector}': {e}"))
+}
+
+#[cfg(feature = "execute")]
+impl FieldSelector {
+    fn parse(field: &str, spec: &Value) -> flow_like_types::Result<Self> {
+        let (selector, attribute) = match spec {
+            Value::String(spec) => match spec.rsplit_once('@') {
+                Some((selector, attribute)) => (selector.to_string(), Some(attribute.to_string())),
+                None => (spec.clone(), None),
+            },
+            Value::Object(spec) => (
+                spec.get("selector")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string(),
+                spec.get("attribute")
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+            ),
+            _ => bail!("Field '{field}' must be a CSS selector string"),
+        };
+
+        let selector = selector.trim();
+        Ok(FieldSelector {
+            selector: if selector.is_empty() {
+                None
+            } else {
+                Some(parse_selector(selector)?)
+            },
+            attribute: attribute
+                .map(|attribute| attribute.trim().to_string())
+                .filter(|attribute| !attribute.is_empty()),
+        })
+    }
+
+    fn extract(&self, scope: ElementRef) -> Value {
+        let element = match &self.selector {
+            Some(selector) => match scope.select(selector).next() {
+                Some(element) => element,
+                None => return Value::Null,
+            },
+            None => scope,
+        };
+
+        match &self.attribute {
+            Some(attribute) => element
+                .value()
+                .attr(attribute)
+                .map_or(Value::Null, |value| json!(value)),
+            None => json!(element_text(element)),
+        }
+    }
+}
+
+/// Text content with whitespace collapsed, the way a browser would render it.
+#[cfg(feature = "execute")]
+fn element_text(element: ElementRef) -> String {
+    element
+        .text()
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(feature = "execute")]
+fn parse_fields(
+    fields: &Map<String, Value>,
+) -> flow_like_types::Result<Vec<(String, FieldSelector)>> {
+    fields
+        .iter()
+        .map(|(field, spec)| Ok((field.clone(), FieldSelector::parse(field, spec)?)))
+        .collect()
+}
+
+#[cfg(feature = "execute")]
+fn extract_fields(scope: ElementRef, fields: &[(String, FieldSelector)]) -> Value {
+    let record: Map<String, Value> = fields
+        .iter()
+        .map(|(field, selector)| (field.clone(), selector.extract(scope)))
+        .collect();
+    Value::Object(record)
+}
+
+/// Extracts each field from the first matching element, missing matches become `null`.
+#[cfg(feature = "execute")]
+pub fn extract_html(html: &str, fields: &Map<String, Value>) -> flow_like_types::Result<Value> {
+    let fields = parse_fields(fields)?;
+    let document = Html::parse_document(html);
+    Ok(extract_fields(document.root_element(), &fields))
+}
+
+/// Extracts one record per element matching `item_selector`, with the fields resolved
+/// relative to that element. Without fields each record is the element's text.
+#[cfg(feature = "execute")]
+pub fn extract_html_all(
+    html: &str,
+    item_selector: &str,
+    fields: &Map<String, Value>,
+) -> flow_like_types::Result<Vec<Value>> {
+    let item_selector = parse_selector(item_selector.trim())?;
+    let fields = parse_fields(fields)?;
+
+    let document = Html::parse_document(html);
+    Ok(document
+        .select(&item_selector)
+        .map(|item| {
+            if fields.is_empty() {
+                json!(element_text(item))
+            } else {
+                extract_fields(item, &fields)
+            }
+        })
+        .collect())
+}
+
+/// Fetches `source` if it is an http(s) URL, otherwise treats it as HTML.
+#[cfg(feature = "execute")]
+async fn load_html(source: &str) -> flow_like_types::Result<String> {
+    let trimmed = source.trim();
+    if !(trimmed.starts_with("http://") || trimmed.starts_with("https://")) {
+        return Ok(source.to_string());
+    }
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(30))
+        .user_agent("FlowLike/0.1")
+        .build()?;
+    let response = client.get(trimmed).send().await?.error_for_status()?;
+    Ok(response.text().await?)
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct HtmlExtractNode {}
+
+impl HtmlExtractNode {
+    pub fn new() -> Self {
+        HtmlExtractNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for HtmlExtractNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "web_scrape_html_extract",
+            "Extract HTML",
+            "Extracts fields from HTML with CSS selectors. Each field takes the text of the first match, use `selector@attribute` to read an attribute instead",
+            "Web/Scraping",
+        );
+        node.add_icon("/flow/icons/spider-web.svg");
+
+        node.add_input_pin("exec_in", "Input", "Trigger Pin", VariableType::Execution);
+
+        node.add_input_pin(
+            "source",
+            "HTML or URL",
+            "HTML markup, or an http(s) URL to fetch it from",
+            VariableType::String,
+        );
+
+        node.add_input_pin(
+            "fields",
+            "Fields",
+            "Map of field name to CSS selector, e.g. { \"title\": \"h1\", \"link\": \"a.more@href\" }",
+            VariableType::Struct,
+        )
+        .set_default_value(Some(json!({})));
+
+        node.add_output_pin("exec_out", "", "", VariableType::Execution);
+
+        node.add_output_pin(
+            "result",
+            "Result",
+            "The extracted fields, null for selectors without a match",
+            VariableType::Struct,
+        );
+
+        node
+    }
+
+    #[cfg(feature = "execute")]
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+
+        let source: String = context.evaluate_pin("source").await?;
+        let fields: Map<String, Value> = context.evaluate_pin("fields").await.unwrap_or_default();
+
+        let html = load_html(&source).await?;
+        let result = extract_html(&html, &fields)?;
+
+        context.set_pin_value("result", result).await?;
+        context.activate_exec_pin("exec_out").await?;
+        Ok(())
+    }
+
+    #[cfg(not(feature = "execute"))]
+    async fn run(&self, _context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        Err(flow_like_types::anyhow!(
+            "Web functionality requires the 'execute' feature"
+        ))
+    }
+}
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct HtmlExtractAllNode {}
+
+impl HtmlExtractAllNode {
+    pub fn new() -> Self {
+        HtmlExtractAllNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for HtmlExtractAllNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "web_scrape_html_extract_all",
+            "Extract HTML (All)",
+            "Extracts one record per element matching a repeating selector, such as table rows or search results. Field selectors are relative to each element",
+            "Web/Scraping",
+        );
+        node.add_icon("/flow/icons/spider-web.svg");
+
+        node.add_input_pin("exec_in", "Input", "Trigger Pin", VariableType::Execution);
+
+        node.add_input_pin(
+            "source",
+            "HTML or URL",
+            "HTML markup, or an http(s) URL to fetch it from",
+            VariableType::String,
+        );
+
+        node.add_input_pin(
+            "item_selector",
+            "Item Selector",
+            "CSS selector for the repeating element, e.g. `ul.results > li`",
+            VariableType::String,
+        );
+
+        node.add_input_pin(
+            "fields",
+            "Fields",
+            "Map of field name to CSS selector within each item. Leave empty to return the item text",
+            VariableType::Struct,
+        )
+        .set_default_value(Some(json!({})));
+
+        node.add_output_pin("exec_out", "", "", VariableType::Execution);
+
+        node.add_output_pin(
+            "items",
+            "Items",
+            "One record per matching element, in document order",
+            VariableType::Struct,
+        )
+        .set_value_type(ValueType::Array);
+
+        node
+    }
+
+    #[cfg(feature = "execute")]
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        context.deactivate_exec_pin("exec_out").await?;
+
+        let source: String = context.evaluate_pin("source").await?;
+        let item_selector: String = context.evaluate_pin("item_selector").await?;
+        let fields: Map<String, Value> = context.evaluate_pin("fields").await.unwrap_or_default();
+
+        let html = load_html(&source).await?;
+        let items = extract_html_all(&html, &item_selector, &fields)?;
+
+        context.set_pin_value("items", json!(items)).await?;
+        context.activate_exec_pin("exec_out").await?;
+        Ok(())
+    }
+
+    #[cfg(not(feature = "execute"))]
+    async fn run(&self, _context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        Err(flow_like_types::anyhow!(
+            "Web functionality requires the 'execute' feature"
+        ))
+    }
+}
+
+#[cfg(all(test, feature = "execute"))]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = include_str!("fixtures/listing.html");
+
+    fn fields(fields: Value) -> Map<String, Value> {
+        fields.as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn test_extracts_text_and_attributes() {
+        let result = extract_html(
+            FIXTURE,
+            &fields(json!({
+                "heading": "h1.title",
+                "description": "meta[name=description]@content",
+                "first_link": { "selector": "#results a", "attribute": "href" },
+                "first_price": ".result .price",
+            })),
+        )
+        .unwrap();
+
+        assert_eq!(
+            result,
+            json!({
+                "heading": "Search Results",
+                "description": "Results for flow",
+                "first_link": "/docs/nodes",
+                "first_price": "12.50",
+            })
+        );
+    }
+
+    #[test]
+    fn test_missing_matches_are_null() {
+        let result = extract_html(
+            FIXTURE,
+            &fields(json!({
+                "missing": "table.prices td",
+                "missing_attribute": "h1.title@data-id",
+            })),
+        )
+        .unwrap();
+
+        assert_eq!(
+            result,
+            json!({ "missing": null, "missing_attribute": null })
+        );
+    }
+
+    #[test]
+    fn test_extract_all_repeating_items() {
+        let items = extract_html_all(
+            FIXTURE,
+            "li.result",
+            &fields(json!({
+                "name": "a",
+                "url": "a@href",
+                "price": ".price",
+            })),
+        )
+        .unwrap();
+
+        assert_eq!(
+            items,
+            vec![
+                json!({ "name": "Nodes", "url": "/docs/nodes", "price": "12.50" }),
+                json!({ "name": "Boards", "url": "/docs/boards", "price": "7.00" }),
+                json!({ "name": "Drafts", "url": null, "price": null }),
+            ]
+        );
+
+        let texts = extract_html_all(FIXTURE, "li.result a", &Map::new()).unwrap();
+        assert_eq!(
+            texts,
+            vec![json!("Nodes"), json!("Boards"), json!("Drafts")]
+        );
+
+        assert!(
+            extract_html_all(FIXTURE, "tr.row", &Map::new())
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_invalid_selector_is_an_error() {
+        assert!(extract_html(FIXTURE, &fields(json!({ "broken": "li[[" }))).is_err());
+        assert!(extract_html_all(FIXTURE, ">>", &Map::new()).is_err());
+    }
+}
@@ -5,6 +5,7 @@ use std::collections::HashMap;
 
 pub mod close;
 pub mod connect;
+pub mod receive;
 pub mod send;
 
 #[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
@@ -14,6 +15,23 @@ pub struct WebSocketConfig {
     pub headers: Option<HashMap<String, String>>,
     #[serde(default)]
     pub timeout_seconds: u64,
+    /// Offered in the `Sec-WebSocket-Protocol` header, in order of preference
+    #[serde(default)]
+    pub subprotocols: Vec<String>,
+    /// Sends a ping this often and drops the connection if the next one is due before
+    /// anything came back (0 = no keepalive)
+    #[serde(default)]
+    pub ping_interval_seconds: u64,
+    /// How often to reconnect after the connection dropped (0 = never)
+    #[serde(default)]
+    pub reconnect_attempts: u32,
+    /// Delay before the first reconnect, doubled for every further attempt
+    #[serde(default = "default_reconnect_backoff_ms")]
+    pub reconnect_backoff_ms: u64,
+}
+
+fn default_reconnect_backoff_ms() -> u64 {
+    500
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
@@ -25,30 +43,178 @@ pub struct WebSocketSession {
 #[cfg(feature = "execute")]
 use flow_like_types::Cacheable;
 #[cfg(feature = "execute")]
+use futures::{SinkExt, StreamExt};
+#[cfg(feature = "execute")]
 use std::any::Any;
 #[cfg(feature = "execute")]
 use std::sync::Arc;
 #[cfg(feature = "execute")]
-use tokio::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "execute")]
+use std::time::Duration;
+#[cfg(feature = "execute")]
+use tokio::sync::{Mutex, Notify, mpsc};
 #[cfg(feature = "execute")]
 use tokio_tungstenite::tungstenite::Message;
 
 #[cfg(feature = "execute")]
-type WsSink = futures::stream::SplitSink<
-    tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>,
-    Message,
->;
+type WsStreamInner =
+    tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>;
+
+#[cfg(feature = "execute")]
+type WsSink = futures::stream::SplitSink<WsStreamInner, Message>;
+
+#[cfg(feature = "execute")]
+type WsStream = futures::stream::SplitStream<WsStreamInner>;
 
+/// Longest wait between two reconnect attempts
 #[cfg(feature = "execute")]
-type WsStream = futures::stream::SplitStream<
-    tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>,
->;
+const MAX_RECONNECT_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Messages waiting for a handler or Receive node. Once full the connection stops reading,
+/// so a slow consumer slows down the server instead of growing the queue.
+#[cfg(feature = "execute")]
+const INCOMING_BUFFER: usize = 1024;
+
+#[cfg(feature = "execute")]
+impl WebSocketConfig {
+    fn reconnect_backoff(&self, attempt: u32) -> Duration {
+        Duration::from_millis(self.reconnect_backoff_ms)
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(MAX_RECONNECT_BACKOFF)
+    }
+
+    fn handshake_request(
+        &self,
+    ) -> flow_like_types::Result<tokio_tungstenite::tungstenite::handshake::client::Request> {
+        use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+        use tokio_tungstenite::tungstenite::http::header::SEC_WEBSOCKET_PROTOCOL;
+        use tokio_tungstenite::tungstenite::http::{HeaderName, HeaderValue};
+
+        let mut request = self
+            .url
+            .as_str()
+            .into_client_request()
+            .map_err(|e| flow_like_types::anyhow!("Invalid WebSocket URL: {}", e))?;
+
+        let headers = request.headers_mut();
+        for (key, value) in self.headers.iter().flatten() {
+            let name = HeaderName::from_bytes(key.as_bytes())
+                .map_err(|e| flow_like_types::anyhow!("Invalid header name {}: {}", key, e))?;
+            let value = HeaderValue::from_str(value)
+                .map_err(|e| flow_like_types::anyhow!("Invalid value of header {}: {}", key, e))?;
+            headers.insert(name, value);
+        }
+        if !self.subprotocols.is_empty() {
+            let value = HeaderValue::from_str(&self.subprotocols.join(", "))
+                .map_err(|e| flow_like_types::anyhow!("Invalid subprotocol: {}", e))?;
+            headers.insert(SEC_WEBSOCKET_PROTOCOL, value);
+        }
+
+        Ok(request)
+    }
+
+    async fn open(&self) -> flow_like_types::Result<(WsSink, WsStream, Option<String>)> {
+        let (ws_stream, response) = tokio_tungstenite::connect_async(self.handshake_request()?)
+            .await
+            .map_err(|e| flow_like_types::anyhow!("WebSocket connection failed: {}", e))?;
+        let protocol = response
+            .headers()
+            .get(tokio_tungstenite::tungstenite::http::header::SEC_WEBSOCKET_PROTOCOL)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let (sink, stream) = ws_stream.split();
+        Ok((sink, stream, protocol))
+    }
+}
+
+/// A text or binary frame received on a connection
+#[cfg(feature = "execute")]
+#[derive(Debug, Clone, PartialEq)]
+pub enum WsIncoming {
+    Text(String),
+    Binary(Vec<u8>),
+}
+
+/// Shared by every handle of a connection. Dropping the last one, e.g. when the cache of
+/// the run goes away after it ended or was cancelled, stops the connection task and closes
+/// the socket.
+#[cfg(feature = "execute")]
+struct ConnectionGuard {
+    sink: Arc<Mutex<WsSink>>,
+    closing: Arc<AtomicBool>,
+    close_notify: Arc<Notify>,
+    task: tokio::task::JoinHandle<()>,
+}
+
+#[cfg(feature = "execute")]
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        self.closing.store(true, Ordering::SeqCst);
+        self.task.abort();
+        // The aborted task cannot tell the Connect node anymore
+        self.close_notify.notify_one();
+        if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+            let sink = self.sink.clone();
+            runtime.spawn(async move {
+                let _ = sink.lock().await.close().await;
+            });
+        }
+    }
+}
 
 #[cfg(feature = "execute")]
 pub struct CachedWebSocketConnection {
     pub sink: Arc<Mutex<WsSink>>,
     pub close_notify: Arc<tokio::sync::Notify>,
     pub reader_handle: Mutex<Option<tokio::task::JoinHandle<()>>>,
+    /// Messages not yet taken by the on-message handler or a Receive node
+    pub incoming: Arc<Mutex<mpsc::Receiver<WsIncoming>>>,
+    /// Subprotocol the server picked
+    pub protocol: Option<String>,
+    guard: Arc<ConnectionGuard>,
+}
+
+#[cfg(feature = "execute")]
+impl CachedWebSocketConnection {
+    /// Connects and starts the task that reads the socket, keeps it alive and reconnects
+    /// as configured. `close_notify` fires once the connection is gone for good.
+    pub async fn connect(config: &WebSocketConfig) -> flow_like_types::Result<Self> {
+        let (sink, stream, protocol) = config.open().await?;
+        let sink = Arc::new(Mutex::new(sink));
+        let closing = Arc::new(AtomicBool::new(false));
+        let close_notify = Arc::new(Notify::new());
+        let (sender, receiver) = mpsc::channel(INCOMING_BUFFER);
+
+        let task = tokio::spawn(run_connection(
+            config.clone(),
+            stream,
+            sink.clone(),
+            sender,
+            closing.clone(),
+            close_notify.clone(),
+        ));
+
+        Ok(Self {
+            sink: sink.clone(),
+            close_notify: close_notify.clone(),
+            reader_handle: Mutex::new(None),
+            incoming: Arc::new(Mutex::new(receiver)),
+            protocol,
+            guard: Arc::new(ConnectionGuard {
+                sink,
+                closing,
+                close_notify,
+                task,
+            }),
+        })
+    }
+
+    /// Closes the socket without reconnecting.
+    pub async fn close(&self) -> Result<(), tokio_tungstenite::tungstenite::Error> {
+        self.guard.closing.store(true, Ordering::SeqCst);
+        self.sink.lock().await.close().await
+    }
 }
 
 #[cfg(feature = "execute")]
@@ -62,6 +228,109 @@ impl Cacheable for CachedWebSocketConnection {
     }
 }
 
+#[cfg(feature = "execute")]
+async fn run_connection(
+    config: WebSocketConfig,
+    mut stream: WsStream,
+    sink: Arc<Mutex<WsSink>>,
+    incoming: mpsc::Sender<WsIncoming>,
+    closing: Arc<AtomicBool>,
+    close_notify: Arc<Notify>,
+) {
+    let ping_interval = (config.ping_interval_seconds > 0)
+        .then(|| Duration::from_secs(config.ping_interval_seconds));
+
+    let mut attempt = 0;
+    'connection: loop {
+        // A connection that delivered something earns a fresh set of attempts, one that
+        // drops right after the handshake keeps using up the old ones
+        if read_until_dropped(&mut stream, &sink, &incoming, ping_interval).await {
+            attempt = 0;
+        }
+
+        while attempt < config.reconnect_attempts {
+            if closing.load(Ordering::SeqCst) || incoming.is_closed() {
+                break 'connection;
+            }
+            tokio::time::sleep(config.reconnect_backoff(attempt)).await;
+            attempt += 1;
+            match config.open().await {
+                Ok((new_sink, new_stream, _)) => {
+                    tracing::info!("WebSocket reconnected to {}", config.url);
+                    *sink.lock().await = new_sink;
+                    stream = new_stream;
+                    continue 'connection;
+                }
+                Err(e) => tracing::warn!("WebSocket reconnect attempt {} failed: {}", attempt, e),
+            }
+        }
+        break;
+    }
+
+    // Dropping the sender ends Receive nodes once they took the remaining messages
+    drop(incoming);
+    close_notify.notify_one();
+}
+
+/// Forwards text and binary frames until the socket closes, fails, or misses a keepalive.
+/// Returns whether the server sent anything but the closing frame.
+#[cfg(feature = "execute")]
+async fn read_until_dropped(
+    stream: &mut WsStream,
+    sink: &Mutex<WsSink>,
+    incoming: &mpsc::Sender<WsIncoming>,
+    ping_interval: Option<Duration>,
+) -> bool {
+    let mut ping = ping_interval
+        .map(|interval| tokio::time::interval_at(tokio::time::Instant::now() + interval, interval));
+    let mut awaiting_pong = false;
+    let mut received = false;
+
+    loop {
+        let ping_due = async {
+            match ping.as_mut() {
+                Some(ping) => ping.tick().await,
+                None => std::future::pending().await,
+            }
+        };
+
+        tokio::select! {
+            message = stream.next() => {
+                let message = match message {
+                    Some(Ok(Message::Text(text))) => WsIncoming::Text(text.as_str().to_string()),
+                    Some(Ok(Message::Binary(data))) => WsIncoming::Binary(data.to_vec()),
+                    Some(Ok(Message::Close(_))) | None => return received,
+                    Some(Ok(_)) => {
+                        // A pong, or any other frame, proves the connection is alive
+                        awaiting_pong = false;
+                        received = true;
+                        continue;
+                    }
+                    Some(Err(e)) => {
+                        tracing::warn!("WebSocket read error: {}", e);
+                        return received;
+                    }
+                };
+                awaiting_pong = false;
+                received = true;
+                if incoming.send(message).await.is_err() {
+                    return received;
+                }
+            }
+            _ = ping_due => {
+                if awaiting_pong {
+                    tracing::warn!("WebSocket keepalive timed out");
+                    return received;
+                }
+                if sink.lock().await.send(Message::Ping(Default::default())).await.is_err() {
+                    return received;
+                }
+                awaiting_pong = true;
+            }
+        }
+    }
+}
+
 #[cfg(feature = "execute")]
 pub async fn get_ws_connection(
     context: &ExecutionContext,
@@ -84,5 +353,256 @@ pub async fn get_ws_connection(
         sink: conn.sink.clone(),
         close_notify: conn.close_notify.clone(),
         reader_handle: Mutex::new(None),
+        incoming: conn.incoming.clone(),
+        protocol: conn.protocol.clone(),
+        guard: conn.guard.clone(),
     }))
 }
+
+#[cfg(all(test, feature = "execute"))]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::tungstenite::handshake::server::{Request, Response};
+    use tokio_tungstenite::tungstenite::http::header::SEC_WEBSOCKET_PROTOCOL;
+
+    fn config(url: String) -> WebSocketConfig {
+        WebSocketConfig {
+            url,
+            headers: None,
+            timeout_seconds: 0,
+            subprotocols: Vec::new(),
+            ping_interval_seconds: 0,
+            reconnect_attempts: 0,
+            reconnect_backoff_ms: 10,
+        }
+    }
+
+    async fn next(connection: &CachedWebSocketConnection) -> Option<WsIncoming> {
+        tokio::time::timeout(
+            Duration::from_secs(5),
+            connection.incoming.lock().await.recv(),
+        )
+        .await
+        .expect("no message within 5s")
+    }
+
+    /// Echo server answering every text and binary frame. Each accepted connection is
+    /// reported with the request headers; connections after `serve` ones are closed
+    /// right away.
+    async fn echo_server(
+        serve: usize,
+    ) -> (String, mpsc::UnboundedReceiver<HashMap<String, String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}/echo", listener.local_addr().unwrap());
+        let (connections, accepted) = mpsc::unbounded_channel();
+
+        tokio::spawn(async move {
+            let mut served = 0;
+            while let Ok((tcp, _)) = listener.accept().await {
+                let connections = connections.clone();
+                let callback = move |request: &Request, mut response: Response| {
+                    let headers = request
+                        .headers()
+                        .iter()
+                        .map(|(k, v)| (k.to_string(), v.to_str().unwrap().to_string()))
+                        .collect::<HashMap<_, _>>();
+                    if let Some(protocol) = headers.get("sec-websocket-protocol") {
+                        let first = protocol.split(',').next().unwrap().trim();
+                        response
+                            .headers_mut()
+                            .insert(SEC_WEBSOCKET_PROTOCOL, first.parse().unwrap());
+                    }
+                    let _ = connections.send(headers);
+                    Ok(response)
+                };
+                let mut ws = tokio_tungstenite::accept_hdr_async(tcp, callback)
+                    .await
+                    .unwrap();
+                served += 1;
+                if served > serve {
+                    let _ = ws.close(None).await;
+                    continue;
+                }
+                tokio::spawn(async move {
+                    while let Some(Ok(message)) = ws.next().await {
+                        match message {
+                            Message::Text(_) | Message::Binary(_) => {
+                                if ws.send(message).await.is_err() {
+                                    break;
+                                }
+                            }
+                            // The test asks the server to drop the connection
+                            Message::Close(_) => break,
+                            _ => {}
+                        }
+                    }
+                });
+            }
+        });
+
+        (url, accepted)
+    }
+
+    #[tokio::test]
+    async fn test_echo_with_headers_and_subprotocols() {
+        let (url, mut accepted) = echo_server(1).await;
+        let mut config = config(url);
+        config.headers = Some(HashMap::from([(
+            "Authorization".to_string(),
+            "Bearer secret".to_string(),
+        )]));
+        config.subprotocols = vec!["graphql-ws".to_string(), "json".to_string()];
+
+        let connection = CachedWebSocketConnection::connect(&config).await.unwrap();
+        let headers = accepted.recv().await.unwrap();
+        assert_eq!(headers["authorization"], "Bearer secret");
+        assert_eq!(headers["sec-websocket-protocol"], "graphql-ws, json");
+        assert_eq!(connection.protocol.as_deref(), Some("graphql-ws"));
+
+        {
+            let mut sink = connection.sink.lock().await;
+            sink.send(Message::Text("hello".into())).await.unwrap();
+            sink.send(Message::Binary(vec![0u8, 159, 255].into()))
+                .await
+                .unwrap();
+        }
+        assert_eq!(
+            next(&connection).await,
+            Some(WsIncoming::Text("hello".to_string()))
+        );
+        assert_eq!(
+            next(&connection).await,
+            Some(WsIncoming::Binary(vec![0, 159, 255]))
+        );
+
+        connection.close().await.unwrap();
+        assert_eq!(next(&connection).await, None);
+    }
+
+    #[tokio::test]
+    async fn test_reconnects_after_the_server_dropped() {
+        let (url, mut accepted) = echo_server(2).await;
+        let mut config = config(url);
+        config.reconnect_attempts = 3;
+
+        let connection = CachedWebSocketConnection::connect(&config).await.unwrap();
+        accepted.recv().await.unwrap();
+
+        // The echo task treats a close frame as the server going away
+        connection
+            .sink
+            .lock()
+            .await
+            .send(Message::Close(None))
+            .await
+            .unwrap();
+        tokio::time::timeout(Duration::from_secs(5), accepted.recv())
+            .await
+            .expect("did not reconnect")
+            .unwrap();
+
+        // Only the sink of the new connection accepts frames again
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+        loop {
+            let sent = connection
+                .sink
+                .lock()
+                .await
+                .send(Message::Text("again".into()))
+                .await;
+            if sent.is_ok() {
+                break;
+            }
+            assert!(tokio::time::Instant::now() < deadline);
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(
+            next(&connection).await,
+            Some(WsIncoming::Text("again".to_string()))
+        );
+
+        connection.close().await.unwrap();
+        assert_eq!(next(&connection).await, None);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_when_reconnects_fail() {
+        // The second connection is closed right after the handshake
+        let (url, _accepted) = echo_server(1).await;
+        let mut config = config(url);
+        config.reconnect_attempts = 2;
+
+        let connection = CachedWebSocketConnection::connect(&config).await.unwrap();
+        connection
+            .sink
+            .lock()
+            .await
+            .send(Message::Close(None))
+            .await
+            .unwrap();
+
+        let notified = connection.close_notify.clone();
+        tokio::time::timeout(Duration::from_secs(5), notified.notified())
+            .await
+            .expect("connection task did not end");
+        assert_eq!(next(&connection).await, None);
+    }
+
+    #[tokio::test]
+    async fn test_keepalive_pings_and_close_on_drop() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (tcp, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(tcp).await.unwrap();
+            let mut frames = Vec::new();
+            while let Some(Ok(message)) = ws.next().await {
+                let close = matches!(message, Message::Close(_));
+                frames.push(message);
+                if close {
+                    break;
+                }
+            }
+            frames
+        });
+
+        let mut config = config(url);
+        config.ping_interval_seconds = 1;
+        let connection = CachedWebSocketConnection::connect(&config).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+
+        // Dropping the last handle, as the end of the run does, closes the socket
+        drop(connection);
+        let frames = tokio::time::timeout(Duration::from_secs(5), server)
+            .await
+            .expect("socket was not closed")
+            .unwrap();
+        assert!(matches!(frames.first(), Some(Message::Ping(_))));
+        assert!(matches!(frames.last(), Some(Message::Close(_))));
+    }
+
+    #[test]
+    fn test_reconnect_backoff() {
+        let mut config = config(String::new());
+        config.reconnect_backoff_ms = 500;
+        let delays: Vec<u128> = (0..8)
+            .map(|attempt| config.reconnect_backoff(attempt).as_millis())
+            .collect();
+        assert_eq!(
+            delays,
+            vec![500, 1000, 2000, 4000, 8000, 16000, 30000, 30000]
+        );
+        assert_eq!(config.reconnect_backoff(u32::MAX), MAX_RECONNECT_BACKOFF);
+    }
+
+    #[test]
+    fn test_config_defaults() {
+        let config: WebSocketConfig =
+            flow_like_types::json::from_str(r#"{"url": "wss://stream.example.com"}"#).unwrap();
+        assert!(config.subprotocols.is_empty());
+        assert_eq!(config.ping_interval_seconds, 0);
+        assert_eq!(config.reconnect_attempts, 0);
+        assert_eq!(config.reconnect_backoff_ms, 500);
+    }
+}
@@ -70,8 +70,6 @@ impl NodeLogic for WebSocketCloseNode {
 
     #[cfg(feature = "execute")]
     async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
-        use futures::SinkExt;
-
         context.deactivate_exec_pin("exec_out").await?;
 
         let session: WebSocketSession = context.evaluate_pin("session").await?;
@@ -83,8 +81,7 @@ impl NodeLogic for WebSocketCloseNode {
                     .as_any()
                     .downcast_ref::<super::CachedWebSocketConnection>()
                 {
-                    let mut sink = conn.sink.lock().await;
-                    sink.close().await.err()
+                    conn.close().await.err()
                 } else {
                     None
                 }
@@ -22,13 +22,10 @@ use std::sync::Arc;
 #[cfg(feature = "execute")]
 use flow_like_types::Cacheable;
 
-#[cfg(feature = "execute")]
-use futures::StreamExt;
-
 use super::{WebSocketConfig, WebSocketSession};
 
 #[cfg(feature = "execute")]
-use super::CachedWebSocketConnection;
+use super::{CachedWebSocketConnection, WsIncoming};
 
 #[crate::register_node]
 #[derive(Default)]
@@ -47,8 +44,9 @@ impl NodeLogic for WebSocketConnectNode {
             "websocket_connect",
             "WebSocket Connect",
             "Opens a WebSocket connection. Immediately triggers on_connect with the session, \
-             then invokes on_message for each incoming message. Holds execution until the \
-             connection closes, then triggers on_close.",
+             then invokes the referenced on-message handler for each incoming message, or \
+             leaves them to a WebSocket Receive node if no handler is referenced. Holds \
+             execution until the connection closes, then triggers on_close.",
             "Web/WebSocket",
         );
         node.add_icon("/flow/icons/web.svg");
@@ -74,7 +72,7 @@ impl NodeLogic for WebSocketConnectNode {
         node.add_input_pin(
             "config",
             "Config",
-            "WebSocket connection configuration (URL, optional headers, optional timeout)",
+            "WebSocket connection configuration (URL, optional headers, subprotocols, timeout, keepalive and reconnect options)",
             VariableType::Struct,
         )
         .set_schema::<WebSocketConfig>()
@@ -119,44 +117,19 @@ impl NodeLogic for WebSocketConnectNode {
 
         let config: WebSocketConfig = context.evaluate_pin("config").await?;
         let referenced_fns = context.get_referenced_functions().await?;
-        let handler = referenced_fns
-            .first()
-            .ok_or_else(|| flow_like_types::anyhow!("No on-message handler function referenced"))?
-            .clone();
-
-        let mut request = tokio_tungstenite::tungstenite::http::Request::builder().uri(&config.url);
-
-        if let Some(headers) = &config.headers {
-            for (key, value) in headers {
-                request = request.header(key.as_str(), value.as_str());
-            }
-        }
+        let handler = referenced_fns.first().cloned();
 
-        let request = request
-            .body(())
-            .map_err(|e| flow_like_types::anyhow!("Failed to build WS request: {}", e))?;
-
-        let (ws_stream, _response) = match tokio_tungstenite::connect_async(request).await {
-            Ok(result) => result,
+        let cached = match CachedWebSocketConnection::connect(&config).await {
+            Ok(cached) => cached,
             Err(e) => {
-                context.log_message(
-                    &format!("WebSocket connection failed: {}", e),
-                    LogLevel::Error,
-                );
+                context.log_message(&e.to_string(), LogLevel::Error);
                 return Ok(());
             }
         };
 
-        let (sink, stream) = futures::StreamExt::split(ws_stream);
-
         let ref_id = format!("ws_{}", flow_like_types::create_id());
-        let close_notify = Arc::new(tokio::sync::Notify::new());
-
-        let cached = CachedWebSocketConnection {
-            sink: Arc::new(tokio::sync::Mutex::new(sink)),
-            close_notify: close_notify.clone(),
-            reader_handle: tokio::sync::Mutex::new(None),
-        };
+        let close_notify = cached.close_notify.clone();
+        let incoming = cached.incoming.clone();
         let cacheable: Arc<dyn Cacheable> = Arc::new(cached);
         context.set_cache(&ref_id, cacheable).await;
 
@@ -183,7 +156,9 @@ impl NodeLogic for WebSocketConnectNode {
             context.push_sub_context(&mut sub);
         }
 
-        spawn_message_reader(context, stream, &handler, &ref_id, close_notify.clone()).await?;
+        if let Some(handler) = &handler {
+            spawn_message_reader(context, incoming, handler, &ref_id).await?;
+        }
 
         let timeout = config.timeout_seconds;
         if timeout > 0 {
@@ -194,9 +169,7 @@ impl NodeLogic for WebSocketConnectNode {
                     let cache = context.cache.read().await;
                     if let Some(conn) = cache.get(&ref_id)
                         && let Some(conn) = conn.as_any().downcast_ref::<CachedWebSocketConnection>() {
-                            let mut sink = conn.sink.lock().await;
-                            use futures::SinkExt;
-                            let _ = sink.close().await;
+                            let _ = conn.close().await;
                         }
                 }
             }
@@ -226,14 +199,12 @@ impl NodeLogic for WebSocketConnectNode {
 #[cfg(feature = "execute")]
 async fn spawn_message_reader(
     context: &mut ExecutionContext,
-    mut stream: super::WsStream,
+    incoming: Arc<tokio::sync::Mutex<tokio::sync::mpsc::Receiver<WsIncoming>>>,
     handler: &Arc<InternalNode>,
     ref_id: &str,
-    close_notify: Arc<tokio::sync::Notify>,
 ) -> flow_like_types::Result<()> {
     use flow_like::flow::pin::PinType;
     use flow_like_types::sync::{DashMap, Mutex};
-    use tokio_tungstenite::tungstenite::Message;
 
     let reference_function = handler;
 
@@ -275,21 +246,9 @@ async fn spawn_message_reader(
     let parent_node_id = context.node.node.lock().await.id.clone();
 
     let handle = tokio::spawn(async move {
-        while let Some(msg_result) = stream.next().await {
-            let msg = match msg_result {
-                Ok(msg) => msg,
-                Err(e) => {
-                    tracing::warn!("WebSocket read error: {}", e);
-                    break;
-                }
-            };
-
-            match &msg {
-                Message::Close(_) => break,
-                Message::Ping(_) | Message::Pong(_) | Message::Frame(_) => continue,
-                Message::Text(_) | Message::Binary(_) => {}
-            }
-
+        // The connection task ends the queue once the socket is gone for good
+        let mut incoming = incoming.lock().await;
+        while let Some(msg) = incoming.recv().await {
             let mut recursion_guard = AHashSet::new();
             recursion_guard.insert(parent_node_id.clone());
 
@@ -298,7 +257,7 @@ async fn spawn_message_reader(
                 let mut ctx = ctx.lock().await;
 
                 match &msg {
-                    Message::Text(text) => {
+                    WsIncoming::Text(text) => {
                         if single_string {
                             let pins = &ctx.node.pins;
                             for (_, pin) in pins.iter() {
@@ -416,7 +375,7 @@ async fn spawn_message_reader(
                             }
                         }
                     }
-                    Message::Binary(data) => {
+                    WsIncoming::Binary(data) => {
                         if single_byte {
                             let pins: Vec<_> = ctx
                                 .node
@@ -448,7 +407,6 @@ async fn spawn_message_reader(
                             }
                         }
                     }
-                    _ => continue,
                 }
 
                 let mut log_message =
@@ -463,8 +421,6 @@ async fn spawn_message_reader(
                 }
             }
         }
-
-        close_notify.notify_waiters();
     });
 
     let cache = context.cache.read().await;
@@ -0,0 +1,172 @@
+#[cfg(not(feature = "execute"))]
+use flow_like::flow::execution::context::ExecutionContext;
+#[cfg(feature = "execute")]
+use flow_like::flow::execution::{
+    LogLevel, context::ExecutionContext, internal_node::InternalNode,
+};
+
+use flow_like::flow::{
+    node::{Node, NodeLogic},
+    pin::{PinOptions, ValueType},
+    variable::VariableType,
+};
+use flow_like_types::{async_trait, json::json};
+
+use super::WebSocketSession;
+
+#[crate::register_node]
+#[derive(Default)]
+pub struct WebSocketReceiveNode {}
+
+impl WebSocketReceiveNode {
+    pub fn new() -> Self {
+        WebSocketReceiveNode {}
+    }
+}
+
+#[async_trait]
+impl NodeLogic for WebSocketReceiveNode {
+    fn get_node(&self) -> Node {
+        let mut node = Node::new(
+            "websocket_receive",
+            "WebSocket Receive",
+            "Fires on_message for every message arriving on a connection opened without an \
+             on-message handler. Holds execution until the connection closes or the message \
+             limit is reached, then fires Done.",
+            "Web/WebSocket",
+        );
+        node.add_icon("/flow/icons/web.svg");
+        node.set_long_running(true);
+        node.scores = Some(
+            flow_like::flow::node::NodeScores::new()
+                .set_privacy(7)
+                .set_security(6)
+                .set_performance(7)
+                .set_governance(5)
+                .set_reliability(6)
+                .set_cost(9)
+                .build(),
+        );
+
+        node.add_input_pin(
+            "exec_in",
+            "Execute",
+            "Start receiving messages",
+            VariableType::Execution,
+        );
+        node.add_input_pin(
+            "session",
+            "Session",
+            "WebSocket session reference",
+            VariableType::Struct,
+        )
+        .set_schema::<WebSocketSession>()
+        .set_options(PinOptions::new().set_enforce_schema(true).build());
+        node.add_input_pin(
+            "max_messages",
+            "Max Messages",
+            "Stop after this many messages (0 = until the connection closes)",
+            VariableType::Integer,
+        )
+        .set_default_value(Some(json!(0)));
+
+        node.add_output_pin(
+            "on_message",
+            "On Message",
+            "Fires once per received message",
+            VariableType::Execution,
+        );
+        node.add_output_pin(
+            "message",
+            "Message",
+            "Content of a text message, empty for binary messages",
+            VariableType::String,
+        );
+        node.add_output_pin(
+            "data",
+            "Data",
+            "Raw bytes of the message",
+            VariableType::Byte,
+        )
+        .set_value_type(ValueType::Array);
+        node.add_output_pin(
+            "is_binary",
+            "Is Binary",
+            "Whether the message was sent as a binary frame",
+            VariableType::Boolean,
+        );
+        node.add_output_pin(
+            "exec_out",
+            "Done",
+            "Fires once no more messages are received",
+            VariableType::Execution,
+        );
+
+        node
+    }
+
+    #[cfg(feature = "execute")]
+    async fn run(&self, context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        use super::WsIncoming;
+
+        context.deactivate_exec_pin("exec_out").await?;
+
+        let session: WebSocketSession = context.evaluate_pin("session").await?;
+        let max_messages: i64 = context.evaluate_pin("max_messages").await?;
+
+        let conn = super::get_ws_connection(context, &session.ref_id).await?;
+        let on_message = context.get_pin_by_name("on_message").await?;
+        let connected = on_message.get_connected_nodes();
+        let message_pin = context.get_pin_by_name("message").await?;
+        let data_pin = context.get_pin_by_name("data").await?;
+        let is_binary_pin = context.get_pin_by_name("is_binary").await?;
+
+        let mut incoming = conn.incoming.lock().await;
+        let mut received: i64 = 0;
+
+        context.activate_exec_pin_ref(&on_message).await?;
+        while max_messages <= 0 || received < max_messages {
+            let Some(msg) = incoming.recv().await else {
+                break;
+            };
+            received += 1;
+
+            let (text, data, is_binary) = match msg {
+                WsIncoming::Text(text) => {
+                    let data = text.as_bytes().to_vec();
+                    (text, data, false)
+                }
+                WsIncoming::Binary(data) => (String::new(), data, true),
+            };
+            message_pin.set_value(json!(text)).await;
+            data_pin.set_value(json!(data)).await;
+            is_binary_pin.set_value(json!(is_binary)).await;
+
+            for node in connected.iter() {
+                let mut sub_context = context.create_sub_context(node).await;
+                let run = InternalNode::trigger(&mut sub_context, &mut None, true).await;
+                sub_context.end_trace();
+                context.push_sub_context(&mut sub_context);
+                if let Err(e) = run {
+                    context.log_message(
+                        &format!("Error handling WebSocket message {}: {:?}", received, e),
+                        LogLevel::Error,
+                    );
+                }
+            }
+        }
+        drop(incoming);
+
+        context.deactivate_exec_pin_ref(&on_message).await?;
+        context.activate_exec_pin("exec_out").await?;
+
+        Ok(())
+    }
+
+    #[cfg(not(feature = "execute"))]
+    async fn run(&self, _context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+        Err(flow_like_types::anyhow!(
+            "WebSocket requires the 'execute' feature"
+        ))
+    }
+}
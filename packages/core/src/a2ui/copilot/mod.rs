@@ -21,7 +21,7 @@ use futures::StreamExt;
 use rig::{
     OneOrMany,
     client::completion::CompletionClientDyn,
-    completion::Completion,
+    completion::{Completion, GetTokenUsage},
     message::{
         AssistantContent, DocumentSourceKind, Image, ImageDetail, ImageMediaType,
         ToolResult as RigToolResult, ToolResultContent, UserContent,
@@ -31,13 +31,17 @@ use rig::{
 };
 
 use crate::a2ui::SurfaceComponent;
-use crate::bit::{Bit, BitModelPreference, BitTypes, LLMParameters};
+use crate::bit::{Bit, BitModelPreference, BitTypes, LLMParameters, ModelPricing};
+use crate::flow::copilot::{ToolLoop, UsageMeter};
 use crate::profile::Profile;
 use crate::state::FlowLikeState;
 use flow_like_model_provider::provider::ModelProvider;
 
 // Note: Tool types are re-exported publicly from `pub use tools::*;` above
 
+/// Model iterations of a turn if the caller sets no limit
+pub const DEFAULT_MAX_ITERATIONS: u32 = 5;
+
 /// The main A2UI Copilot struct that provides AI-powered UI generation
 pub struct A2UICopilot {
     state: Arc<FlowLikeState>,
@@ -59,6 +63,7 @@ impl A2UICopilot {
         history: Vec<A2UIChatMessage>,
         model_id: Option<String>,
         token: Option<String>,
+        max_iterations: Option<u32>,
         on_token: Option<F>,
     ) -> Result<A2UICopilotResponse>
     where
@@ -67,7 +72,8 @@ impl A2UICopilot {
         let context = self.prepare_context(current_surface, selected_component_ids)?;
         let context_json = flow_like_types::json::to_string_pretty(&context)?;
 
-        let (model_name, completion_client) = self.get_model(model_id, token).await?;
+        let (model_name, pricing, completion_client) = self.get_model(model_id, token).await?;
+        let mut usage = UsageMeter::new(&model_name, pricing);
 
         let system_prompt = Self::build_system_prompt(&context_json);
 
@@ -133,10 +139,10 @@ impl A2UICopilot {
 
         let mut full_response = String::new();
         let mut generated_components: Vec<SurfaceComponent> = Vec::new();
-        let max_iterations = 5u64;
+        let mut tool_loop = ToolLoop::new(max_iterations.unwrap_or(DEFAULT_MAX_ITERATIONS));
         let mut plan_step_counter = 0u32;
 
-        for iteration in 0..max_iterations {
+        while let Some(iteration) = tool_loop.next_iteration() {
             // Send iteration start event
             if let Some(ref callback) = on_token {
                 plan_step_counter += 1;
@@ -170,6 +176,7 @@ impl A2UICopilot {
             let mut iteration_text = String::new();
             let mut current_reasoning = String::new();
             let mut reasoning_step_id: Option<String> = None;
+            let mut reported_usage = None;
 
             while let Some(item) = stream.next().await {
                 let content =
@@ -211,7 +218,8 @@ impl A2UICopilot {
                             ));
                         }
                     }
-                    StreamedAssistantContent::Final(_) => {
+                    StreamedAssistantContent::Final(final_response) => {
+                        reported_usage = final_response.token_usage();
                         if let (Some(callback), Some(step_id)) = (&on_token, &reasoning_step_id) {
                             let step_event = A2UIStreamEvent::PlanStep(A2UIPlanStep {
                                 id: step_id.clone(),
@@ -265,6 +273,27 @@ impl A2UICopilot {
                 ));
             }
 
+            let completion_text = serde_json::to_string(&response_contents).unwrap_or_default();
+            let running_usage = usage.record(
+                reported_usage,
+                || {
+                    format!(
+                        "{}\n{}\n{}",
+                        system_prompt,
+                        serde_json::to_string(&current_history).unwrap_or_default(),
+                        prompt
+                    )
+                },
+                &completion_text,
+            );
+            if let Some(ref callback) = on_token {
+                callback(format!(
+                    "<usage>{}</usage>",
+                    serde_json::to_string(&A2UIStreamEvent::Usage(running_usage.clone()))
+                        .unwrap_or_default()
+                ));
+            }
+
             // Mark iteration analysis as complete
             if let Some(ref callback) = on_token {
                 let step_event = A2UIStreamEvent::PlanStep(A2UIPlanStep {
@@ -297,6 +326,7 @@ impl A2UICopilot {
                 .collect();
 
             let tool_calls_found = !tool_calls.is_empty();
+            let continues = tool_loop.continues(tool_calls_found);
 
             if !tool_calls_found {
                 break;
@@ -460,12 +490,26 @@ impl A2UICopilot {
                     content: combined_tool_results,
                 });
             }
+
+            if !continues {
+                break;
+            }
+        }
+
+        if let Some(ref callback) = on_token {
+            callback(format!(
+                "<finished>{}</finished>",
+                serde_json::to_string(&A2UIStreamEvent::Finished(tool_loop.outcome()))
+                    .unwrap_or_default()
+            ));
         }
 
         Ok(A2UICopilotResponse {
-            message: full_response,
+            message: tool_loop.finish_message(full_response),
             components: generated_components,
             suggestions: vec![],
+            usage: usage.finish(),
+            stop_reason: tool_loop.stop_reason(),
         })
     }
 
@@ -498,7 +542,11 @@ impl A2UICopilot {
         &self,
         model_id: Option<String>,
         token: Option<String>,
-    ) -> Result<(String, Box<dyn CompletionClientDyn + Send + Sync + 'a>)> {
+    ) -> Result<(
+        String,
+        Option<ModelPricing>,
+        Box<dyn CompletionClientDyn + Send + Sync + 'a>,
+    )> {
         let bit = if let Some(profile) = &self.profile {
             if let Some(id) = model_id {
                 profile
@@ -526,6 +574,7 @@ impl A2UICopilot {
                         params: None,
                     },
                     model_classification: Default::default(),
+                    pricing: None,
                 })
                 .unwrap(),
                 ..Default::default()
@@ -542,7 +591,7 @@ impl A2UICopilot {
         let provider = model.provider().await?;
         let completion = provider.into_client();
 
-        Ok((default_model, completion))
+        Ok((default_model, bit.pricing(), completion))
     }
 
     async fn execute_tool(
@@ -1,6 +1,7 @@
 //! A2UI Copilot types
 
 use crate::a2ui::SurfaceComponent;
+use crate::flow::copilot::{CopilotUsage, LoopOutcome, StopReason};
 use serde::{Deserialize, Serialize};
 
 /// Chat message role for A2UI conversations
@@ -32,6 +33,10 @@ pub struct A2UICopilotResponse {
     pub message: String,
     pub components: Vec<SurfaceComponent>,
     pub suggestions: Vec<A2UISuggestion>,
+    #[serde(default)]
+    pub usage: CopilotUsage,
+    #[serde(default)]
+    pub stop_reason: StopReason,
 }
 
 /// Suggestion for follow-up actions
@@ -83,4 +88,8 @@ pub struct A2UIPlanStep {
 pub enum A2UIStreamEvent {
     PlanStep(A2UIPlanStep),
     ComponentPreview(Vec<SurfaceComponent>),
+    /// Running total of the turn, sent after every model iteration
+    Usage(CopilotUsage),
+    /// Last event of the turn, tells why the agent stopped
+    Finished(LoopOutcome),
 }
@@ -0,0 +1,376 @@
+//! Server-side HTML rendering of A2UI surfaces
+//!
+//! Produces a static, self contained HTML document for a surface, e.g. to print a report
+//! to PDF without a frontend. Only the display and layout components are rendered,
+//! interactive components, media players and game components are left out. Template
+//! children are not expanded.
+
+use flow_like_types::{Value, json::from_value};
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use super::{BoundValue, DataModel, Surface};
+
+/// Nesting deeper than this is cut off, it only happens for broken component trees
+const MAX_DEPTH: usize = 64;
+
+const BASE_CSS: &str = "\
+body{font-family:-apple-system,BlinkMacSystemFont,'Segoe UI',Helvetica,Arial,sans-serif;color:#111827;font-size:14px;line-height:1.5;margin:0}\
+.a2-row{display:flex;flex-direction:row;gap:8px}\
+.a2-column{display:flex;flex-direction:column;gap:8px}\
+.a2-grid{display:grid;gap:8px}\
+.a2-card{border:1px solid #e5e7eb;border-radius:8px;padding:16px;break-inside:avoid}\
+.a2-card-title{font-weight:600;font-size:16px}\
+.a2-card-description{color:#6b7280}\
+.a2-badge{display:inline-block;border:1px solid #d1d5db;border-radius:9999px;padding:0 8px;font-size:12px}\
+.a2-caption{color:#6b7280;font-size:12px}\
+.a2-markdown{white-space:pre-wrap}\
+table{border-collapse:collapse;width:100%}\
+th,td{border:1px solid #e5e7eb;padding:4px 8px;text-align:left}\
+th{background:#f9fafb}\
+img{max-width:100%}\
+hr{border:none;border-top:1px solid #e5e7eb;width:100%}";
+
+/// Renders `surface` with the values of `data` to a complete HTML document
+pub fn render_surface_html(surface: &Surface, data: &DataModel) -> String {
+    let mut renderer = HtmlRenderer {
+        surface,
+        data,
+        visiting: HashSet::new(),
+        out: String::new(),
+    };
+    renderer.component(&surface.root_component_id, 0);
+
+    format!(
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><title>{}</title><style>{}</style></head><body>{}</body></html>",
+        escape(&surface.id),
+        BASE_CSS,
+        renderer.out
+    )
+}
+
+struct HtmlRenderer<'a> {
+    surface: &'a Surface,
+    data: &'a DataModel,
+    visiting: HashSet<String>,
+    out: String,
+}
+
+impl HtmlRenderer<'_> {
+    fn component(&mut self, id: &str, depth: usize) {
+        if depth > MAX_DEPTH || self.visiting.contains(id) {
+            return;
+        }
+        let Some(component) = self.surface.get_component(id) else {
+            return;
+        };
+        self.visiting.insert(id.to_string());
+
+        let props = &component.component;
+        match component.get_component_type_name().as_str() {
+            "row" => self.container("div", "a2-row", props, depth),
+            "column" => self.container("div", "a2-column", props, depth),
+            "grid" => {
+                let columns = self.text(props, "columns");
+                let style = match columns.parse::<u32>() {
+                    Ok(n) if n > 0 => format!(" style=\"grid-template-columns:repeat({},1fr)\"", n),
+                    _ => String::new(),
+                };
+                let _ = write!(self.out, "<div class=\"a2-grid\"{}>", style);
+                self.children(props, depth);
+                self.out.push_str("</div>");
+            }
+            "card" => {
+                self.out.push_str("<div class=\"a2-card\">");
+                for (key, class) in [
+                    ("title", "a2-card-title"),
+                    ("description", "a2-card-description"),
+                ] {
+                    let text = self.text(props, key);
+                    if !text.is_empty() {
+                        let _ =
+                            write!(self.out, "<div class=\"{}\">{}</div>", class, escape(&text));
+                    }
+                }
+                self.children(props, depth);
+                let footer = self.text(props, "footer");
+                if !footer.is_empty() {
+                    let _ = write!(self.out, "<div>{}</div>", escape(&footer));
+                }
+                self.out.push_str("</div>");
+            }
+            "text" => {
+                let content = escape(&self.text(props, "content"));
+                match self.text(props, "variant").as_str() {
+                    tag @ ("h1" | "h2" | "h3" | "h4" | "h5" | "h6") => {
+                        let _ = write!(self.out, "<{tag}>{content}</{tag}>");
+                    }
+                    "code" => {
+                        let _ = write!(self.out, "<pre><code>{}</code></pre>", content);
+                    }
+                    "caption" => {
+                        let _ = write!(self.out, "<p class=\"a2-caption\">{}</p>", content);
+                    }
+                    _ => {
+                        let _ = write!(self.out, "<p>{}</p>", content);
+                    }
+                }
+            }
+            "markdown" => {
+                let content = escape(&self.text(props, "content"));
+                let _ = write!(self.out, "<div class=\"a2-markdown\">{}</div>", content);
+            }
+            "image" => {
+                let src = self.text(props, "src");
+                let alt = self.text(props, "alt");
+                let _ = write!(
+                    self.out,
+                    "<img src=\"{}\" alt=\"{}\">",
+                    escape(&src),
+                    escape(&alt)
+                );
+            }
+            "divider" => self.out.push_str("<hr>"),
+            "badge" => {
+                let content = escape(&self.text(props, "content"));
+                let _ = write!(self.out, "<span class=\"a2-badge\">{}</span>", content);
+            }
+            "link" => {
+                let href = self.text(props, "href");
+                let label = self.text(props, "label");
+                let label = if label.is_empty() { &href } else { &label };
+                let _ = write!(
+                    self.out,
+                    "<a href=\"{}\">{}</a>",
+                    escape(&href),
+                    escape(label)
+                );
+            }
+            "progress" => {
+                let value = self.text(props, "value");
+                let max = self.text(props, "max");
+                let max = if max.is_empty() {
+                    "100".to_string()
+                } else {
+                    max
+                };
+                let _ = write!(
+                    self.out,
+                    "<progress value=\"{}\" max=\"{}\"></progress>",
+                    escape(&value),
+                    escape(&max)
+                );
+            }
+            "table" => self.table(props),
+            // Interactive, media and game components have no static representation
+            "button" | "textField" | "select" | "slider" | "checkbox" | "switch" | "radioGroup"
+            | "dateTimeInput" | "fileInput" | "imageInput" | "imageLabeler" | "imageHotspot"
+            | "video" | "lottie" | "spinner" | "skeleton" | "modal" | "drawer" | "tooltip"
+            | "popover" | "iframe" | "canvas2d" | "sprite" | "shape" | "scene3d" | "model3d"
+            | "dialogue" | "characterPortrait" | "choiceMenu" | "inventoryGrid" | "healthBar"
+            | "miniMap" | "geoMap" | "plotlyChart" | "nivoChart" => {}
+            _ => self.container("div", "", props, depth),
+        }
+
+        self.visiting.remove(id);
+    }
+
+    fn container(&mut self, tag: &str, class: &str, props: &Value, depth: usize) {
+        if class.is_empty() {
+            let _ = write!(self.out, "<{}>", tag);
+        } else {
+            let _ = write!(self.out, "<{} class=\"{}\">", tag, class);
+        }
+        self.children(props, depth);
+        let _ = write!(self.out, "</{}>", tag);
+    }
+
+    fn children(&mut self, props: &Value, depth: usize) {
+        let ids: Vec<String> = props
+            .get("children")
+            .and_then(|children| children.get("explicitList"))
+            .and_then(|list| list.as_array())
+            .map(|list| {
+                list.iter()
+                    .filter_map(|id| id.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default();
+        for id in ids {
+            self.component(&id, depth + 1);
+        }
+    }
+
+    fn table(&mut self, props: &Value) {
+        let columns = self.resolve(props.get("columns"));
+        let rows = self.resolve(props.get("data"));
+        let columns: Vec<(String, String)> = columns
+            .as_array()
+            .map(|columns| {
+                columns
+                    .iter()
+                    .filter(|column| column.get("hidden").is_none_or(|h| !self.truthy(h)))
+                    .map(|column| {
+                        let id = text_of(column.get("id").unwrap_or(&Value::Null));
+                        let accessor = text_of(&self.resolve(column.get("accessor")));
+                        let header = text_of(&self.resolve(column.get("header")));
+                        let key = if accessor.is_empty() { id } else { accessor };
+                        let header = if header.is_empty() {
+                            key.clone()
+                        } else {
+                            header
+                        };
+                        (key, header)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        self.out.push_str("<table>");
+        let caption = self.text(props, "caption");
+        if !caption.is_empty() {
+            let _ = write!(self.out, "<caption>{}</caption>", escape(&caption));
+        }
+        self.out.push_str("<thead><tr>");
+        for (_, header) in &columns {
+            let _ = write!(self.out, "<th>{}</th>", escape(header));
+        }
+        self.out.push_str("</tr></thead><tbody>");
+        for row in rows.as_array().into_iter().flatten() {
+            self.out.push_str("<tr>");
+            for (key, _) in &columns {
+                let cell = row.get(key).map(text_of).unwrap_or_default();
+                let _ = write!(self.out, "<td>{}</td>", escape(&cell));
+            }
+            self.out.push_str("</tr>");
+        }
+        self.out.push_str("</tbody></table>");
+    }
+
+    /// Resolves a property that is either a bound value or a plain JSON value
+    fn resolve(&self, value: Option<&Value>) -> Value {
+        let Some(value) = value else {
+            return Value::Null;
+        };
+        match from_value::<BoundValue>(value.clone()) {
+            Ok(bound) => self.data.resolve_bound_value(&bound),
+            Err(_) => value.clone(),
+        }
+    }
+
+    fn text(&self, props: &Value, key: &str) -> String {
+        text_of(&self.resolve(props.get(key)))
+    }
+
+    fn truthy(&self, value: &Value) -> bool {
+        matches!(self.resolve(Some(value)), Value::Bool(true))
+    }
+}
+
+fn text_of(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        Value::Number(n) => n.to_string(),
+        Value::Bool(b) => b.to_string(),
+        other => other.to_string(),
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::a2ui::SurfaceComponent;
+    use flow_like_types::json::json;
+
+    fn surface(components: Vec<(&str, Value)>) -> Surface {
+        let mut surface = Surface::new("report", "root");
+        for (id, component) in components {
+            surface.add_component(SurfaceComponent::new(id, component));
+        }
+        surface
+    }
+
+    #[test]
+    fn test_renders_layout_text_and_bindings() {
+        let surface = surface(vec![
+            (
+                "root",
+                json!({"type": "column", "children": {"explicitList": ["title", "total"]}}),
+            ),
+            (
+                "title",
+                json!({"type": "text", "variant": {"literalString": "h1"}, "content": {"literalString": "Q1 <Report>"}}),
+            ),
+            (
+                "total",
+                json!({"type": "text", "content": {"path": "/summary/total"}}),
+            ),
+        ]);
+        let mut data = DataModel::new();
+        data.set("/summary/total", json!(42));
+
+        let html = render_surface_html(&surface, &data);
+        assert!(
+            html.contains("<div class=\"a2-column\"><h1>Q1 &lt;Report&gt;</h1><p>42</p></div>")
+        );
+    }
+
+    #[test]
+    fn test_renders_table_rows_from_data() {
+        let surface = surface(vec![(
+            "root",
+            json!({
+                "type": "table",
+                "columns": {"path": "/columns"},
+                "data": {"path": "/rows"},
+            }),
+        )]);
+        let mut data = DataModel::new();
+        data.set(
+            "/columns",
+            json!([
+                {"id": "name", "header": {"literalString": "Name"}},
+                {"id": "qty", "header": {"literalString": "Qty"}},
+            ]),
+        );
+        data.set("/rows", json!([{"name": "Bolt", "qty": 3}]));
+
+        let html = render_surface_html(&surface, &data);
+        assert!(html.contains("<th>Name</th><th>Qty</th>"));
+        assert!(html.contains("<tr><td>Bolt</td><td>3</td></tr>"));
+    }
+
+    #[test]
+    fn test_skips_interactive_components_and_cycles() {
+        let surface = surface(vec![
+            (
+                "root",
+                json!({"type": "row", "children": {"explicitList": ["button", "root"]}}),
+            ),
+            (
+                "button",
+                json!({"type": "button", "label": {"literalString": "Click"}}),
+            ),
+        ]);
+
+        let html = render_surface_html(&surface, &DataModel::new());
+        assert!(html.contains("<div class=\"a2-row\"></div>"));
+        assert!(!html.contains("Click"));
+    }
+}
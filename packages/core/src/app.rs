@@ -3,6 +3,7 @@ use crate::{
     flow::{
         board::{Board, VersionType, commands::nodes::copy_paste::CopyPasteCommand},
         event::Event,
+        node::validation::ValidationMessage,
     },
     state::FlowLikeState,
     utils::compression::{
@@ -494,11 +495,11 @@ impl App {
         &self,
         event_id: &str,
         version: Option<(u32, u32, u32)>,
-    ) -> flow_like_types::Result<()> {
+    ) -> flow_like_types::Result<Vec<ValidationMessage>> {
         let event = Event::load(event_id, self, version).await?;
         event.validate_event_references(self).await?;
 
-        Ok(())
+        event.validate_nodes(self).await
     }
 
     pub async fn delete_event(&mut self, event_id: &str) -> flow_like_types::Result<()> {
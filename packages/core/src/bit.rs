@@ -359,11 +359,28 @@ pub struct Bit {
     pub updated: String,
 }
 
+/// Price of a hosted model in USD per million tokens
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Copy, Debug, PartialEq)]
+pub struct ModelPricing {
+    pub input_per_million: f64,
+    pub output_per_million: f64,
+}
+
+impl ModelPricing {
+    pub fn cost(&self, input_tokens: u64, output_tokens: u64) -> f64 {
+        (input_tokens as f64 * self.input_per_million
+            + output_tokens as f64 * self.output_per_million)
+            / 1_000_000.0
+    }
+}
+
 #[derive(Serialize, Deserialize, JsonSchema, Clone, Debug)]
 pub struct LLMParameters {
     pub context_length: u32,
     pub provider: ModelProvider,
     pub model_classification: BitModelClassification,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pricing: Option<ModelPricing>,
 }
 
 #[derive(Serialize, Deserialize, JsonSchema, Clone, Debug)]
@@ -371,6 +388,8 @@ pub struct VLMParameters {
     pub context_length: u32,
     pub provider: ModelProvider,
     pub model_classification: BitModelClassification,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pricing: Option<ModelPricing>,
 }
 
 #[derive(Serialize, Deserialize, JsonSchema, Clone, Debug)]
@@ -622,6 +641,19 @@ impl Bit {
         None
     }
 
+    /// Pricing of LLM and VLM bits, `None` for local models or if the hub does not list it
+    pub fn pricing(&self) -> Option<ModelPricing> {
+        if let Some(parameters) = self.try_to_llm() {
+            return parameters.pricing;
+        }
+
+        if let Some(parameters) = self.try_to_vlm() {
+            return parameters.pricing;
+        }
+
+        None
+    }
+
     pub fn try_to_embedding_provider(&self) -> Option<ModelProvider> {
         if let Some(parameters) = self.try_to_embedding() {
             return Some(parameters.provider);
@@ -5,8 +5,9 @@ use serde::{Deserialize, Serialize};
 
 /// Re-export commonly used types from the board copilot
 pub use crate::flow::copilot::{
-    BoardCommand, ChatImage, ChatRole, NodeMetadata, NodePosition, PinMetadata, PlaceholderPinDef,
-    PlanStep, PlanStepStatus, RunContext, Suggestion, TemplateInfo,
+    BoardCommand, ChatImage, ChatRole, CopilotUsage, LoopOutcome, NodeMetadata, NodePosition,
+    PinMetadata, PlaceholderPinDef, PlanStep, PlanStepStatus, RunContext, StopReason, Suggestion,
+    TemplateInfo,
 };
 
 /// The scope of what the copilot agent can modify
@@ -88,6 +89,14 @@ pub struct UnifiedCopilotResponse {
 
     /// The actual scope that was used (agent may decide to focus on one area)
     pub active_scope: CopilotScope,
+
+    /// Tokens and estimated cost of the turn
+    #[serde(default)]
+    pub usage: CopilotUsage,
+
+    /// Why the agent stopped, `MaxIterationsReached` means the work is unfinished
+    #[serde(default)]
+    pub stop_reason: StopReason,
 }
 
 /// A suggestion for follow-up actions (works for both board and UI)
@@ -133,6 +142,10 @@ pub struct UnifiedContext {
     /// Action context for UI component actions (frontend mode)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub action_context: Option<UIActionContext>,
+
+    /// Limit for the model iterations of the turn, the copilot default if unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_iterations: Option<u32>,
 }
 
 /// Context for UI actions (pages, events, etc.)
@@ -183,4 +196,8 @@ pub enum UnifiedStreamEvent {
     ComponentPreview(Vec<SurfaceComponent>),
     /// Agent determined which scope to focus on
     ScopeDecision(CopilotScope),
+    /// Running token and cost total of the turn
+    Usage(CopilotUsage),
+    /// Why the agent stopped, always the last event of a turn
+    Finished(LoopOutcome),
 }
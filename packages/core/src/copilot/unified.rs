@@ -73,6 +73,8 @@ impl UnifiedCopilot {
             ));
         }
 
+        let max_iterations = context.as_ref().and_then(|c| c.max_iterations);
+
         match effective_scope {
             CopilotScope::Board => {
                 self.delegate_to_board(
@@ -85,6 +87,7 @@ impl UnifiedCopilot {
                     model_id,
                     token,
                     context.and_then(|c| c.run_context),
+                    max_iterations,
                     on_token,
                 )
                 .await
@@ -98,6 +101,7 @@ impl UnifiedCopilot {
                     model_id,
                     token,
                     context.and_then(|c| c.action_context),
+                    max_iterations,
                     on_token,
                 )
                 .await
@@ -159,6 +163,7 @@ impl UnifiedCopilot {
         model_id: Option<String>,
         token: Option<String>,
         run_context: Option<RunContext>,
+        max_iterations: Option<u32>,
         on_token: Option<F>,
     ) -> Result<UnifiedCopilotResponse>
     where
@@ -196,6 +201,7 @@ impl UnifiedCopilot {
                 model_id,
                 token,
                 run_context,
+                max_iterations,
                 on_token,
             )
             .await?;
@@ -217,6 +223,8 @@ impl UnifiedCopilot {
             active_scope: CopilotScope::Board,
             canvas_settings: None,
             root_component_id: None,
+            usage: response.usage,
+            stop_reason: response.stop_reason,
         })
     }
 
@@ -230,6 +238,7 @@ impl UnifiedCopilot {
         model_id: Option<String>,
         token: Option<String>,
         _action_context: Option<UIActionContext>,
+        max_iterations: Option<u32>,
         on_token: Option<F>,
     ) -> Result<UnifiedCopilotResponse>
     where
@@ -266,6 +275,7 @@ impl UnifiedCopilot {
                 ui_history,
                 model_id,
                 token,
+                max_iterations,
                 on_token,
             )
             .await?;
@@ -279,6 +289,8 @@ impl UnifiedCopilot {
             active_scope: CopilotScope::Frontend,
             canvas_settings: None,
             root_component_id: None,
+            usage: response.usage,
+            stop_reason: response.stop_reason,
         })
     }
 
@@ -299,6 +311,8 @@ impl UnifiedCopilot {
     where
         F: Fn(String) + Send + Sync + 'static + Clone,
     {
+        let max_iterations = context.as_ref().and_then(|c| c.max_iterations);
+
         // Analyze the prompt to determine primary focus
         let prompt_lower = user_prompt.to_lowercase();
         let is_ui_focused = prompt_lower.contains("ui")
@@ -326,6 +340,7 @@ impl UnifiedCopilot {
                     model_id,
                     token,
                     context.and_then(|c| c.action_context),
+                    max_iterations,
                     on_token,
                 )
                 .await;
@@ -344,6 +359,7 @@ impl UnifiedCopilot {
                     model_id,
                     token,
                     context.and_then(|c| c.run_context),
+                    max_iterations,
                     on_token,
                 )
                 .await;
@@ -362,6 +378,7 @@ impl UnifiedCopilot {
                     model_id,
                     token,
                     context.and_then(|c| c.run_context),
+                    max_iterations,
                     on_token,
                 )
                 .await;
@@ -375,6 +392,7 @@ impl UnifiedCopilot {
             model_id,
             token,
             context.and_then(|c| c.action_context),
+            max_iterations,
             on_token,
         )
         .await
@@ -1,6 +1,9 @@
 use super::{
     execution::LogLevel,
-    node::{Node, NodeLogic},
+    node::{
+        Node, NodeLogic,
+        validation::{ValidationContext, ValidationMessage},
+    },
     pin::Pin,
     variable::Variable,
 };
@@ -27,6 +30,7 @@ use tracing::instrument;
 
 pub mod cleanup;
 pub mod commands;
+pub mod diff;
 
 #[derive(Debug, Clone)]
 pub enum BoardParent {
@@ -286,6 +290,33 @@ impl Board {
         }
     }
 
+    /// Runs `NodeLogic::validate` for every node of the board. Nodes without registered logic
+    /// are skipped, a failing validation is reported as a warning on its node.
+    pub async fn validate_nodes(&self, state: Arc<FlowLikeState>) -> Vec<ValidationMessage> {
+        let registry = state.node_registry().clone();
+        let registry = registry.read().await;
+
+        let mut messages = Vec::new();
+        for node in self.nodes.values() {
+            let node_logic = match self.logic_nodes.get(&node.name) {
+                Some(logic) => Arc::clone(logic),
+                None => match registry.instantiate(node) {
+                    Ok(logic) => logic,
+                    Err(_) => continue,
+                },
+            };
+
+            let context = ValidationContext::new(node, self);
+            match node_logic.validate(&context).await {
+                Ok(found) => messages.extend(found),
+                Err(e) => messages.push(context.warning(None, format!("Validation failed: {}", e))),
+            }
+        }
+
+        messages.sort_by(|a, b| (&a.node_id, &a.pin_id).cmp(&(&b.node_id, &b.pin_id)));
+        messages
+    }
+
     pub async fn execute_command(
         &mut self,
         command: GenericCommand,
@@ -1121,6 +1152,14 @@ impl Comment {
 
 #[cfg(test)]
 mod tests {
+    use crate::flow::{
+        execution::context::ExecutionContext,
+        node::{
+            Node, NodeLogic,
+            validation::{ValidationContext, ValidationMessage, ValidationSeverity},
+        },
+        variable::VariableType,
+    };
     use crate::{state::FlowLikeConfig, utils::http::HTTPClient};
     use flow_like_storage::{
         files::store::FlowLikeStore,
@@ -1153,4 +1192,58 @@ mod tests {
 
         assert_eq!(board.id, deser_board.id);
     }
+
+    struct RequiresInputNode;
+
+    #[flow_like_types::async_trait]
+    impl NodeLogic for RequiresInputNode {
+        fn get_node(&self) -> Node {
+            let mut node = Node::new("requires_input", "Requires Input", "", "Test");
+            node.add_input_pin("value", "Value", "", VariableType::String);
+            node
+        }
+
+        async fn run(&self, _context: &mut ExecutionContext) -> flow_like_types::Result<()> {
+            Ok(())
+        }
+
+        async fn validate(
+            &self,
+            context: &ValidationContext<'_>,
+        ) -> flow_like_types::Result<Vec<ValidationMessage>> {
+            Ok(context.require_input("value").into_iter().collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn validate_nodes_collects_node_messages() {
+        let state = flow_state().await;
+        state
+            .node_registry()
+            .write()
+            .await
+            .push_node(Arc::new(RequiresInputNode));
+
+        let mut board = super::Board::new(None, Path::from("boards"), state.clone());
+        let unconnected = RequiresInputNode.get_node();
+        let mut with_default = RequiresInputNode.get_node();
+        with_default
+            .get_pin_mut_by_name("value")
+            .unwrap()
+            .set_default_value(Some(flow_like_types::json::json!("set")));
+        let unknown = Node::new("not_registered", "Unknown", "", "Test");
+        for node in [&unconnected, &with_default, &unknown] {
+            board.nodes.insert(node.id.clone(), node.clone());
+        }
+
+        let messages = board.validate_nodes(state).await;
+
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].node_id, unconnected.id);
+        assert_eq!(messages[0].severity, ValidationSeverity::Error);
+        assert_eq!(
+            messages[0].pin_id.as_deref(),
+            Some(unconnected.get_pin_by_name("value").unwrap().id.as_str())
+        );
+    }
 }
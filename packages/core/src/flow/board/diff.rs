@@ -0,0 +1,384 @@
+use super::Board;
+use crate::{
+    flow::{node::Node, pin::Pin},
+    state::FlowLikeState,
+};
+use flow_like_storage::object_store::path::Path;
+use flow_like_types::Value;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    sync::Arc,
+};
+
+/// What changed between two versions of a board.
+/// Nodes are matched by id, every list is sorted so equal boards always produce equal diffs.
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq, Default)]
+pub struct BoardDiff {
+    pub added_nodes: Vec<NodeSummary>,
+    pub removed_nodes: Vec<NodeSummary>,
+    pub modified_nodes: Vec<NodeModification>,
+    pub added_edges: Vec<Edge>,
+    pub removed_edges: Vec<Edge>,
+    pub changed_defaults: Vec<PinDefaultChange>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct NodeSummary {
+    pub node_id: String,
+    pub name: String,
+    pub friendly_name: String,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq, Eq)]
+pub struct NodeModification {
+    pub node_id: String,
+    pub name: String,
+    /// The changed node fields, e.g. `friendly_name`, `coordinates` or `pins`
+    pub fields: Vec<String>,
+}
+
+/// A connection from an output pin to an input pin, identified by node and pin ids
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Edge {
+    pub from_node: String,
+    pub from_pin: String,
+    pub to_node: String,
+    pub to_pin: String,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq)]
+pub struct PinDefaultChange {
+    pub node_id: String,
+    pub pin_id: String,
+    pub pin_name: String,
+    pub from: Option<Value>,
+    pub to: Option<Value>,
+}
+
+impl BoardDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added_nodes.is_empty()
+            && self.removed_nodes.is_empty()
+            && self.modified_nodes.is_empty()
+            && self.added_edges.is_empty()
+            && self.removed_edges.is_empty()
+            && self.changed_defaults.is_empty()
+    }
+}
+
+/// Loads two versions of a board and diffs them. `None` stands for the current, unversioned board.
+pub async fn diff_board_versions(
+    app_state: Arc<FlowLikeState>,
+    app_id: &str,
+    board_id: &str,
+    from: Option<(u32, u32, u32)>,
+    to: Option<(u32, u32, u32)>,
+) -> flow_like_types::Result<BoardDiff> {
+    let board_dir = Path::from("apps").child(app_id);
+    let from = Board::load(board_dir.clone(), board_id, app_state.clone(), from).await?;
+    let to = Board::load(board_dir, board_id, app_state, to).await?;
+    Ok(diff_boards(&from, &to))
+}
+
+pub fn diff_boards(from: &Board, to: &Board) -> BoardDiff {
+    let from_nodes: BTreeMap<&String, &Node> = from.nodes.iter().collect();
+    let to_nodes: BTreeMap<&String, &Node> = to.nodes.iter().collect();
+
+    let mut diff = BoardDiff::default();
+
+    for (id, node) in &to_nodes {
+        if !from_nodes.contains_key(id) {
+            diff.added_nodes.push(summary(node));
+        }
+    }
+
+    for (id, old) in &from_nodes {
+        let Some(new) = to_nodes.get(id) else {
+            diff.removed_nodes.push(summary(old));
+            continue;
+        };
+
+        let fields = changed_fields(old, new);
+        if !fields.is_empty() {
+            diff.modified_nodes.push(NodeModification {
+                node_id: new.id.clone(),
+                name: new.name.clone(),
+                fields,
+            });
+        }
+
+        let new_pins: BTreeMap<&String, &Pin> = new.pins.iter().collect();
+        let old_pins: BTreeMap<&String, &Pin> = old.pins.iter().collect();
+        for (pin_id, old_pin) in old_pins {
+            let Some(new_pin) = new_pins.get(pin_id) else {
+                continue;
+            };
+            if old_pin.default_value != new_pin.default_value {
+                diff.changed_defaults.push(PinDefaultChange {
+                    node_id: new.id.clone(),
+                    pin_id: pin_id.clone(),
+                    pin_name: new_pin.name.clone(),
+                    from: decode_default(old_pin),
+                    to: decode_default(new_pin),
+                });
+            }
+        }
+    }
+
+    let from_edges = edges(from);
+    let to_edges = edges(to);
+    diff.added_edges = to_edges.difference(&from_edges).cloned().collect();
+    diff.removed_edges = from_edges.difference(&to_edges).cloned().collect();
+
+    diff
+}
+
+fn summary(node: &Node) -> NodeSummary {
+    NodeSummary {
+        node_id: node.id.clone(),
+        name: node.name.clone(),
+        friendly_name: node.friendly_name.clone(),
+    }
+}
+
+/// Connections and defaults are reported as edges and default changes, not as node fields
+fn changed_fields(old: &Node, new: &Node) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut check = |field: &str, changed: bool| {
+        if changed {
+            fields.push(field.to_string());
+        }
+    };
+
+    check("name", old.name != new.name);
+    check("friendly_name", old.friendly_name != new.friendly_name);
+    check("description", old.description != new.description);
+    check("coordinates", old.coordinates != new.coordinates);
+    check("comment", old.comment != new.comment);
+    check("layer", old.layer != new.layer);
+    check("version", old.version != new.version);
+
+    let old_pins: BTreeSet<&String> = old.pins.keys().collect();
+    let new_pins: BTreeSet<&String> = new.pins.keys().collect();
+    check("pins", old_pins != new_pins);
+
+    fields.sort();
+    fields
+}
+
+fn decode_default(pin: &Pin) -> Option<Value> {
+    pin.default_value
+        .as_ref()
+        .map(|bytes| flow_like_types::json::from_slice(bytes).unwrap_or(Value::Null))
+}
+
+/// Every connection on the board, layer pins included so edges into collapsed layers show up too
+fn edges(board: &Board) -> BTreeSet<Edge> {
+    let mut owners: HashMap<&String, &String> = HashMap::new();
+    for node in board.nodes.values() {
+        for pin_id in node.pins.keys() {
+            owners.insert(pin_id, &node.id);
+        }
+    }
+    for layer in board.layers.values() {
+        for pin_id in layer.pins.keys() {
+            owners.insert(pin_id, &layer.id);
+        }
+    }
+
+    let mut edges = BTreeSet::new();
+    let pins = board
+        .nodes
+        .values()
+        .flat_map(|node| node.pins.values().map(move |pin| (&node.id, pin)))
+        .chain(
+            board
+                .layers
+                .values()
+                .flat_map(|layer| layer.pins.values().map(move |pin| (&layer.id, pin))),
+        );
+    for (owner, pin) in pins {
+        for target in &pin.connected_to {
+            let Some(to_node) = owners.get(target) else {
+                continue;
+            };
+            edges.insert(Edge {
+                from_node: owner.clone(),
+                from_pin: pin.id.clone(),
+                to_node: (*to_node).clone(),
+                to_pin: target.clone(),
+            });
+        }
+    }
+    edges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        flow::{board::VersionType, variable::VariableType},
+        state::FlowLikeConfig,
+        utils::http::HTTPClient,
+    };
+    use flow_like_storage::{files::store::FlowLikeStore, object_store};
+    use flow_like_types::{json::json, tokio};
+
+    async fn flow_state() -> Arc<FlowLikeState> {
+        let mut config: FlowLikeConfig = FlowLikeConfig::new();
+        config.register_app_meta_store(FlowLikeStore::Other(Arc::new(
+            object_store::memory::InMemory::new(),
+        )));
+        Arc::new(FlowLikeState::new(
+            config,
+            HTTPClient::new_without_refetch(),
+        ))
+    }
+
+    fn node(name: &str) -> Node {
+        let mut node = Node::new(name, name, "", "Test");
+        node.add_input_pin("exec_in", "Input", "", VariableType::Execution);
+        node.add_input_pin("value", "Value", "", VariableType::String);
+        node.add_output_pin("exec_out", "Output", "", VariableType::Execution);
+        node
+    }
+
+    fn pin_id(board: &Board, node: &str, pin: &str) -> String {
+        board.nodes[node].get_pin_by_name(pin).unwrap().id.clone()
+    }
+
+    fn connect(board: &mut Board, from: &str, to: &str) -> Edge {
+        let from_pin = pin_id(board, from, "exec_out");
+        let to_pin = pin_id(board, to, "exec_in");
+        let node = board.nodes.get_mut(from).unwrap();
+        node.pins
+            .get_mut(&from_pin)
+            .unwrap()
+            .connected_to
+            .insert(to_pin.clone());
+        let node = board.nodes.get_mut(to).unwrap();
+        node.pins
+            .get_mut(&to_pin)
+            .unwrap()
+            .depends_on
+            .insert(from_pin.clone());
+        Edge {
+            from_node: from.to_string(),
+            from_pin,
+            to_node: to.to_string(),
+            to_pin,
+        }
+    }
+
+    fn disconnect(board: &mut Board, edge: &Edge) {
+        let node = board.nodes.get_mut(&edge.from_node).unwrap();
+        node.pins
+            .get_mut(&edge.from_pin)
+            .unwrap()
+            .connected_to
+            .remove(&edge.to_pin);
+        let node = board.nodes.get_mut(&edge.to_node).unwrap();
+        node.pins
+            .get_mut(&edge.to_pin)
+            .unwrap()
+            .depends_on
+            .remove(&edge.from_pin);
+    }
+
+    fn insert(board: &mut Board, node: Node) -> String {
+        let id = node.id.clone();
+        board.nodes.insert(id.clone(), node);
+        id
+    }
+
+    #[tokio::test]
+    async fn diff_lists_exactly_the_changes_between_versions() {
+        let state = flow_state().await;
+        let mut board = Board::new(None, Path::from("apps").child("app"), state.clone());
+        let a = insert(&mut board, node("a"));
+        let b = insert(&mut board, node("b"));
+        let c = insert(&mut board, node("c"));
+        connect(&mut board, &a, &b);
+        let b_to_c = connect(&mut board, &b, &c);
+        let first = board.version;
+        board
+            .create_version(VersionType::Minor, None)
+            .await
+            .unwrap();
+
+        // Renamed and moved, keeps its id
+        let renamed = board.nodes.get_mut(&a).unwrap();
+        renamed.friendly_name = "Renamed".to_string();
+        renamed.coordinates = Some((10.0, 20.0, 0.0));
+        board
+            .nodes
+            .get_mut(&b)
+            .unwrap()
+            .get_pin_mut_by_name("value")
+            .unwrap()
+            .set_default_value(Some(json!("hello")));
+        disconnect(&mut board, &b_to_c);
+        board.nodes.remove(&c);
+        let d = insert(&mut board, node("d"));
+        let b_to_d = connect(&mut board, &b, &d);
+        let second = board.version;
+        board
+            .create_version(VersionType::Minor, None)
+            .await
+            .unwrap();
+
+        let diff = diff_board_versions(state, "app", &board.id, Some(first), Some(second))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            diff.added_nodes,
+            vec![NodeSummary {
+                node_id: d.clone(),
+                name: "d".to_string(),
+                friendly_name: "d".to_string(),
+            }]
+        );
+        assert_eq!(
+            diff.removed_nodes,
+            vec![NodeSummary {
+                node_id: c.clone(),
+                name: "c".to_string(),
+                friendly_name: "c".to_string(),
+            }]
+        );
+        assert_eq!(
+            diff.modified_nodes,
+            vec![NodeModification {
+                node_id: a.clone(),
+                name: "a".to_string(),
+                fields: vec!["coordinates".to_string(), "friendly_name".to_string()],
+            }]
+        );
+        assert_eq!(diff.added_edges, vec![b_to_d]);
+        assert_eq!(diff.removed_edges, vec![b_to_c]);
+        assert_eq!(
+            diff.changed_defaults,
+            vec![PinDefaultChange {
+                node_id: b.clone(),
+                pin_id: pin_id(&board, &b, "value"),
+                pin_name: "value".to_string(),
+                from: None,
+                to: Some(json!("hello")),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn diff_of_identical_boards_is_empty() {
+        let state = flow_state().await;
+        let mut board = Board::new(None, Path::from("apps").child("app"), state);
+        let a = insert(&mut board, node("a"));
+        let b = insert(&mut board, node("b"));
+        connect(&mut board, &a, &b);
+
+        assert!(diff_boards(&board, &board.clone()).is_empty());
+    }
+}
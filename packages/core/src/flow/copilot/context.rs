@@ -86,6 +86,19 @@ pub struct VariableContext {
     pub default_value: Option<String>,
 }
 
+/// Compact comment representation for context
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommentContext {
+    pub id: String,
+    #[serde(rename = "c")] // "content" abbreviated
+    pub content: String,
+    #[serde(rename = "p")] // "position" abbreviated
+    pub position: (i32, i32),
+    /// Layer the comment is placed in, None if at root
+    #[serde(rename = "l", skip_serializing_if = "Option::is_none")]
+    pub layer_id: Option<String>,
+}
+
 /// Complete graph context for the LLM
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GraphContext {
@@ -97,6 +110,9 @@ pub struct GraphContext {
     /// All variables defined in the board
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub variables: Vec<VariableContext>,
+    /// All comments on the board
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub comments: Vec<CommentContext>,
     pub selected_nodes: Vec<String>,
 }
 
@@ -274,11 +290,31 @@ pub fn prepare_context(board: &Board, selected_node_ids: &[String]) -> Result<Gr
         })
         .collect();
 
+    // Build comment contexts, comments live on the board and in layers
+    let comment_contexts: Vec<CommentContext> = board
+        .comments
+        .values()
+        .map(|comment| (comment, comment.layer.clone()))
+        .chain(board.layers.values().flat_map(|layer| {
+            layer
+                .comments
+                .values()
+                .map(|comment| (comment, Some(layer.id.clone())))
+        }))
+        .map(|(comment, layer_id)| CommentContext {
+            id: comment.id.clone(),
+            content: comment.content.clone(),
+            position: (comment.coordinates.0 as i32, comment.coordinates.1 as i32),
+            layer_id,
+        })
+        .collect();
+
     Ok(GraphContext {
         nodes: node_contexts,
         edges: edge_contexts,
         layers: layer_contexts,
         variables: variable_contexts,
+        comments: comment_contexts,
         selected_nodes: selected_node_ids.to_vec(),
     })
 }
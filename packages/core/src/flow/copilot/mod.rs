@@ -5,13 +5,18 @@
 
 mod context;
 mod provider;
+mod tool_loop;
 mod tools;
 mod types;
+mod usage;
 
 pub use context::{
-    EdgeContext, GraphContext, LayerContext, NodeContext, PinContext, prepare_context,
+    CommentContext, EdgeContext, GraphContext, LayerContext, NodeContext, PinContext,
+    prepare_context,
 };
 pub use provider::CatalogProvider;
+pub(crate) use tool_loop::ToolLoop;
+pub use tool_loop::{LoopOutcome, MAX_ITERATIONS_LIMIT, StopReason};
 pub use tools::{
     CatalogTool, EmitCommandsArgs, EmitCommandsTool, FilterCategoryArgs, FilterCategoryTool,
     GetNodeDetailsArgs, GetNodeDetailsTool, QueryLogsArgs, QueryLogsTool, SearchArgs,
@@ -23,6 +28,8 @@ pub use types::{
     NodeMetadata, NodePosition, PinMetadata, PlaceholderPinDef, PlanStep, PlanStepStatus,
     RunContext, StreamEvent, Suggestion, TemplateInfo,
 };
+pub use usage::CopilotUsage;
+pub(crate) use usage::UsageMeter;
 
 use std::sync::Arc;
 
@@ -31,7 +38,7 @@ use futures::StreamExt;
 use rig::{
     OneOrMany,
     client::completion::CompletionClientDyn,
-    completion::Completion,
+    completion::{Completion, GetTokenUsage},
     message::{
         AssistantContent, DocumentSourceKind, Image, ImageDetail, ImageMediaType,
         ToolResult as RigToolResult, ToolResultContent, UserContent,
@@ -42,10 +49,13 @@ use rig::{
 use serde_json::json;
 
 use crate::app::App;
-use crate::bit::{Bit, BitModelPreference, BitTypes, LLMParameters, Metadata};
+use crate::bit::{Bit, BitModelPreference, BitTypes, LLMParameters, Metadata, ModelPricing};
 use crate::flow::board::Board;
 use crate::profile::Profile;
 use crate::state::FlowLikeState;
+
+/// Model iterations of a turn if the caller sets no limit
+pub const DEFAULT_MAX_ITERATIONS: u32 = 10;
 use flow_like_model_provider::provider::ModelProvider;
 
 // Note: Tool args types are re-exported publicly from `pub use tools::{ ... }` above
@@ -160,6 +170,7 @@ impl Copilot {
         model_id: Option<String>,
         token: Option<String>,
         run_context: Option<RunContext>,
+        max_iterations: Option<u32>,
         on_token: Option<F>,
     ) -> Result<CopilotResponse>
     where
@@ -177,7 +188,8 @@ impl Copilot {
         let available_nodes = self.catalog_provider.get_all_nodes().await;
         let node_count = available_nodes.len();
 
-        let (model_name, completion_client) = self.get_model(model_id, token).await?;
+        let (model_name, pricing, completion_client) = self.get_model(model_id, token).await?;
+        let mut usage = UsageMeter::new(&model_name, pricing);
 
         // Build a compact system prompt
         let system_prompt = Self::build_system_prompt(
@@ -283,10 +295,10 @@ impl Copilot {
 
         let mut full_response = String::new();
         let mut all_commands: Vec<BoardCommand> = Vec::new();
-        let max_iterations = 10u64;
+        let mut tool_loop = ToolLoop::new(max_iterations.unwrap_or(DEFAULT_MAX_ITERATIONS));
         let mut plan_step_counter = 0u32;
 
-        for iteration in 0..max_iterations {
+        while let Some(iteration) = tool_loop.next_iteration() {
             // Send iteration start event
             if let Some(ref callback) = on_token {
                 plan_step_counter += 1;
@@ -322,6 +334,7 @@ impl Copilot {
             let mut iteration_text = String::new();
             let mut current_reasoning = String::new();
             let mut reasoning_step_id: Option<String> = None;
+            let mut reported_usage = None;
 
             while let Some(item) = stream.next().await {
                 let content =
@@ -367,7 +380,8 @@ impl Copilot {
                             ));
                         }
                     }
-                    StreamedAssistantContent::Final(_) => {
+                    StreamedAssistantContent::Final(final_response) => {
+                        reported_usage = final_response.token_usage();
                         // Mark reasoning step as completed
                         if let (Some(callback), Some(step_id)) = (&on_token, &reasoning_step_id) {
                             let step_event = StreamEvent::PlanStep(PlanStep {
@@ -423,6 +437,27 @@ impl Copilot {
                 ));
             }
 
+            let completion_text = serde_json::to_string(&response_contents).unwrap_or_default();
+            let running_usage = usage.record(
+                reported_usage,
+                || {
+                    format!(
+                        "{}\n{}\n{}",
+                        system_prompt,
+                        serde_json::to_string(&current_history).unwrap_or_default(),
+                        prompt
+                    )
+                },
+                &completion_text,
+            );
+            if let Some(ref callback) = on_token {
+                callback(format!(
+                    "<usage>{}</usage>",
+                    serde_json::to_string(&StreamEvent::Usage(running_usage.clone()))
+                        .unwrap_or_default()
+                ));
+            }
+
             // Mark iteration analysis as complete
             if let Some(ref callback) = on_token {
                 let step_event = StreamEvent::PlanStep(PlanStep {
@@ -456,6 +491,7 @@ impl Copilot {
                 .collect();
 
             let tool_calls_found = !tool_calls.is_empty();
+            let continues = tool_loop.continues(tool_calls_found);
 
             if tool_calls_found {
                 // Emit plan steps for all tool calls starting
@@ -605,11 +641,19 @@ impl Copilot {
             }
 
             // Continue to next iteration (agent will see tool results and continue)
-            if iteration == max_iterations - 1 {
+            if !continues {
                 break;
             }
         }
 
+        if let Some(ref callback) = on_token {
+            callback(format!(
+                "<finished>{}</finished>",
+                serde_json::to_string(&StreamEvent::Finished(tool_loop.outcome()))
+                    .unwrap_or_default()
+            ));
+        }
+
         let has_commands = !all_commands.is_empty();
         println!(
             "[Copilot] Final response: {} total commands, agent_type={:?}",
@@ -628,9 +672,11 @@ impl Copilot {
             } else {
                 AgentType::Explain
             },
-            message: Self::clean_message(&full_response),
+            message: tool_loop.finish_message(Self::clean_message(&full_response)),
             commands: all_commands,
             suggestions: vec![],
+            usage: usage.finish(),
+            stop_reason: tool_loop.stop_reason(),
         };
 
         if let Ok(json) = serde_json::to_string(&response) {
@@ -668,7 +714,7 @@ impl Copilot {
         format!(
             r#"You are an expert graph editor assistant. You help users understand and modify visual workflows.
 
-## Graph Context (abbreviated keys: t=type, n=name, i=inputs, o=outputs, p=position, s=size, f=from, fp=from_pin, tp=to_pin, v=value, p=parent)
+## Graph Context (abbreviated keys: t=type, n=name, i=inputs, o=outputs, p=position, s=size, f=from, fp=from_pin, tp=to_pin, v=value, p=parent, c=comment content, l=layer)
 {}
 
 ## Layers (also called Placeholders)
@@ -719,6 +765,7 @@ AddNode(node_type, ref_id, position, target_layer?, summary) | RemoveNode(node_i
 AddPlaceholder(name, ref_id, position, pins[], target_layer?, summary) - Create a placeholder node for process modeling
 ConnectPins(from_node, from_pin, to_node, to_pin, summary) | DisconnectPins(same)
 UpdateNodePin(node_id, pin_id, value, summary) | MoveNode(node_id, position, target_layer?, summary)
+RenameNode(node_id, new_name, summary) | UpdateComment(comment_id, content, summary) - Relabel existing nodes/comments (ids from context)
 CreateVariable(name, data_type, value_type, summary) | CreateComment(content, position, target_layer?, summary)
 CreateLayer(name, node_ids[], target_layer?, summary) - Create a layer, optionally nested inside target_layer
 
@@ -848,17 +895,34 @@ ALWAYS emit commands in this order:
             "emit_commands" => {
                 match serde_json::from_value::<EmitCommandsArgs>(arguments.clone()) {
                     Ok(args) => {
-                        let commands_json =
-                            serde_json::to_string(&args.commands).unwrap_or_default();
+                        let mut commands = Vec::new();
+                        let mut rejected = Vec::new();
+                        for command in args.commands {
+                            match Self::validate_command(&command, graph_context) {
+                                Ok(()) => commands.push(command),
+                                Err(error) => rejected.push(error),
+                            }
+                        }
+
+                        let commands_json = serde_json::to_string(&commands).unwrap_or_default();
                         println!(
-                            "[Copilot] emit_commands: {} commands, json length: {} chars",
-                            args.commands.len(),
+                            "[Copilot] emit_commands: {} commands, {} rejected, json length: {} chars",
+                            commands.len(),
+                            rejected.len(),
                             commands_json.len()
                         );
-                        format!(
+                        let mut output = format!(
                             "<commands>{}</commands>\n\n{}",
                             commands_json, args.explanation
-                        )
+                        );
+                        if !rejected.is_empty() {
+                            output.push_str(&format!(
+                                "\n\nRejected {} command(s), the others are queued:\n- {}",
+                                rejected.len(),
+                                rejected.join("\n- ")
+                            ));
+                        }
+                        output
                     }
                     Err(e) => {
                         println!("[Copilot] emit_commands: Failed to parse args: {:?}", e);
@@ -1024,6 +1088,46 @@ ALWAYS emit commands in this order:
         vec![]
     }
 
+    /// Check that a command targets an element that exists on the board
+    fn validate_command(
+        command: &BoardCommand,
+        graph_context: &GraphContext,
+    ) -> std::result::Result<(), String> {
+        match command {
+            BoardCommand::RenameNode {
+                node_id, new_name, ..
+            } => {
+                if new_name.trim().is_empty() {
+                    return Err(format!(
+                        "RenameNode: new_name for node '{}' is empty",
+                        node_id
+                    ));
+                }
+                if !graph_context.nodes.iter().any(|node| &node.id == node_id) {
+                    return Err(format!(
+                        "RenameNode: node '{}' does not exist on the board. Nodes added in this turn get their name from AddNode's friendly_name",
+                        node_id
+                    ));
+                }
+                Ok(())
+            }
+            BoardCommand::UpdateComment { comment_id, .. } => {
+                if !graph_context
+                    .comments
+                    .iter()
+                    .any(|comment| &comment.id == comment_id)
+                {
+                    return Err(format!(
+                        "UpdateComment: comment '{}' does not exist on the board",
+                        comment_id
+                    ));
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
     /// Check if two commands are duplicates (same type and key identifiers)
     fn commands_are_duplicate(a: &BoardCommand, b: &BoardCommand) -> bool {
         match (a, b) {
@@ -1071,6 +1175,30 @@ ALWAYS emit commands in this order:
                     ..
                 },
             ) => f1 == f2 && fp1 == fp2 && t1 == t2 && tp1 == tp2,
+            (
+                BoardCommand::RenameNode {
+                    node_id: id1,
+                    new_name: n1,
+                    ..
+                },
+                BoardCommand::RenameNode {
+                    node_id: id2,
+                    new_name: n2,
+                    ..
+                },
+            ) => id1 == id2 && n1 == n2,
+            (
+                BoardCommand::UpdateComment {
+                    comment_id: id1,
+                    content: c1,
+                    ..
+                },
+                BoardCommand::UpdateComment {
+                    comment_id: id2,
+                    content: c2,
+                    ..
+                },
+            ) => id1 == id2 && c1 == c2,
             _ => false,
         }
     }
@@ -1092,7 +1220,11 @@ ALWAYS emit commands in this order:
         &self,
         model_id: Option<String>,
         token: Option<String>,
-    ) -> Result<(String, Box<dyn CompletionClientDyn + Send + Sync + 'a>)> {
+    ) -> Result<(
+        String,
+        Option<ModelPricing>,
+        Box<dyn CompletionClientDyn + Send + Sync + 'a>,
+    )> {
         let bit = if let Some(profile) = &self.profile {
             if let Some(id) = model_id {
                 profile
@@ -1120,6 +1252,7 @@ ALWAYS emit commands in this order:
                         params: None,
                     },
                     model_classification: Default::default(),
+                    pricing: None,
                 })
                 .unwrap(),
                 ..Default::default()
@@ -1136,6 +1269,169 @@ ALWAYS emit commands in this order:
         let provider = model.provider().await?;
         let completion = provider.into_client();
 
-        Ok((default_model, completion))
+        Ok((default_model, bit.pricing(), completion))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::flow::board::{Comment, CommentType, Layer, LayerType};
+    use crate::flow::node::Node;
+    use crate::{state::FlowLikeConfig, utils::http::HTTPClient};
+    use flow_like_storage::{
+        files::store::FlowLikeStore,
+        object_store::{self, path::Path},
+    };
+    use flow_like_types::tokio;
+    use std::time::SystemTime;
+
+    fn comment(id: &str, content: &str) -> Comment {
+        Comment {
+            id: id.to_string(),
+            author: None,
+            content: content.to_string(),
+            comment_type: CommentType::Text,
+            timestamp: SystemTime::now(),
+            coordinates: (10.0, 20.0, 0.0),
+            width: None,
+            height: None,
+            layer: None,
+            color: None,
+            z_index: None,
+            hash: None,
+            is_locked: None,
+        }
+    }
+
+    /// Board with a node and a comment at the root and another node and comment in a layer
+    async fn sample_board() -> Board {
+        let mut config = FlowLikeConfig::new();
+        config.register_app_meta_store(FlowLikeStore::Other(Arc::new(
+            object_store::memory::InMemory::new(),
+        )));
+        let state = Arc::new(FlowLikeState::new(
+            config,
+            HTTPClient::new_without_refetch(),
+        ));
+        let mut board = Board::new(None, Path::from("boards"), state);
+
+        let mut node = Node::new("http_get", "HTTP GET", "", "Web");
+        node.id = "node_root".to_string();
+        board.nodes.insert(node.id.clone(), node);
+        board
+            .comments
+            .insert("comment_root".to_string(), comment("comment_root", "TODO"));
+
+        let mut layer = Layer::new(
+            "layer".to_string(),
+            "Fetch".to_string(),
+            LayerType::Collapsed,
+        );
+        let mut node = Node::new("parse_json", "Parse JSON", "", "Utils");
+        node.id = "node_layer".to_string();
+        layer.nodes.insert(node.id.clone(), node);
+        layer.comments.insert(
+            "comment_layer".to_string(),
+            comment("comment_layer", "Parses"),
+        );
+        board.layers.insert(layer.id.clone(), layer);
+
+        board
+    }
+
+    fn agent_output(commands: serde_json::Value) -> String {
+        format!("<commands>{}</commands>\n\nRelabelled the graph", commands)
+    }
+
+    #[tokio::test]
+    async fn test_rename_and_update_comment_apply_to_board() {
+        let board = sample_board().await;
+        let context = prepare_context(&board, &[]).unwrap();
+        assert_eq!(context.comments.len(), 2);
+        let layer_comment = context
+            .comments
+            .iter()
+            .find(|comment| comment.id == "comment_layer")
+            .unwrap();
+        assert_eq!(layer_comment.layer_id.as_deref(), Some("layer"));
+
+        let commands = Copilot::parse_commands(&agent_output(json!([
+            { "command_type": "RenameNode", "node_id": "node_root", "new_name": "Fetch Orders", "summary": "Rename request" },
+            { "command_type": "RenameNode", "node_id": "node_layer", "new_name": "Parse Orders" },
+            { "command_type": "UpdateComment", "comment_id": "comment_root", "content": "Fetches all open orders", "summary": "Describe" },
+            { "command_type": "UpdateComment", "comment_id": "comment_layer", "content": "Parses the orders", "color": "#FFD700" }
+        ])));
+        assert_eq!(commands.len(), 4);
+        assert!(matches!(
+            &commands[0],
+            BoardCommand::RenameNode { node_id, new_name, summary }
+                if node_id == "node_root" && new_name == "Fetch Orders" && summary.as_deref() == Some("Rename request")
+        ));
+        assert!(matches!(
+            &commands[3],
+            BoardCommand::UpdateComment { comment_id, content, color, .. }
+                if comment_id == "comment_layer" && content == "Parses the orders" && color.as_deref() == Some("#FFD700")
+        ));
+        for command in &commands {
+            assert_eq!(Copilot::validate_command(command, &context), Ok(()));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_commands_for_missing_targets_are_rejected() {
+        let board = sample_board().await;
+        let context = prepare_context(&board, &[]).unwrap();
+
+        let commands = Copilot::parse_commands(&agent_output(json!([
+            { "command_type": "RenameNode", "node_id": "$0", "new_name": "New Node" },
+            { "command_type": "RenameNode", "node_id": "node_root", "new_name": "  " },
+            { "command_type": "RenameNode", "node_id": "layer", "new_name": "Layers are not nodes" },
+            { "command_type": "UpdateComment", "comment_id": "missing", "content": "Text" }
+        ])));
+        assert_eq!(commands.len(), 4);
+
+        let errors: Vec<String> = commands
+            .iter()
+            .map(|command| Copilot::validate_command(command, &context).unwrap_err())
+            .collect();
+        assert!(errors[0].contains("node '$0' does not exist"));
+        assert!(errors[1].contains("empty"));
+        assert!(errors[2].contains("node 'layer' does not exist"));
+        assert!(errors[3].contains("comment 'missing' does not exist"));
+    }
+
+    #[test]
+    fn test_rename_and_update_comment_duplicates() {
+        let rename = |node_id: &str, new_name: &str| BoardCommand::RenameNode {
+            node_id: node_id.to_string(),
+            new_name: new_name.to_string(),
+            summary: None,
+        };
+        let update = |content: &str| BoardCommand::UpdateComment {
+            comment_id: "comment_root".to_string(),
+            content: content.to_string(),
+            color: None,
+            summary: Some("Describe".to_string()),
+        };
+
+        assert!(Copilot::commands_are_duplicate(
+            &rename("a", "Fetch"),
+            &rename("a", "Fetch")
+        ));
+        assert!(!Copilot::commands_are_duplicate(
+            &rename("a", "Fetch"),
+            &rename("a", "Load")
+        ));
+        assert!(!Copilot::commands_are_duplicate(
+            &rename("a", "Fetch"),
+            &rename("b", "Fetch")
+        ));
+        assert!(Copilot::commands_are_duplicate(&update("A"), &update("A")));
+        assert!(!Copilot::commands_are_duplicate(&update("A"), &update("B")));
+        assert!(!Copilot::commands_are_duplicate(
+            &rename("comment_root", "A"),
+            &update("A")
+        ));
     }
 }
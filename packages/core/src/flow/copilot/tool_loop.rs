@@ -0,0 +1,181 @@
+//! Iteration budget of the copilot tool loop
+
+use serde::{Deserialize, Serialize};
+
+/// Upper bound for caller supplied limits, every iteration is a full model request
+pub const MAX_ITERATIONS_LIMIT: u32 = 50;
+
+/// Why the tool loop of a copilot turn ended
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StopReason {
+    /// The model used tools and then answered without requesting more
+    #[default]
+    Completed,
+    /// The model still requested tools when the iteration limit was reached
+    MaxIterationsReached,
+    /// The model answered right away without using any tool
+    NoToolCalls,
+}
+
+/// Terminal event of a turn, sent once the tool loop ended
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoopOutcome {
+    pub reason: StopReason,
+    pub iterations: u32,
+    pub max_iterations: u32,
+}
+
+/// Counts the model iterations of a turn and records why the loop ended.
+pub(crate) struct ToolLoop {
+    max_iterations: u32,
+    iterations: u32,
+    used_tools: bool,
+    stop_reason: Option<StopReason>,
+}
+
+impl ToolLoop {
+    pub fn new(max_iterations: u32) -> Self {
+        Self {
+            max_iterations: max_iterations.clamp(1, MAX_ITERATIONS_LIMIT),
+            iterations: 0,
+            used_tools: false,
+            stop_reason: None,
+        }
+    }
+
+    /// Index of the next iteration, `None` once the loop ended
+    pub fn next_iteration(&mut self) -> Option<u32> {
+        if self.stop_reason.is_some() {
+            return None;
+        }
+        self.iterations += 1;
+        Some(self.iterations - 1)
+    }
+
+    /// Ends the current iteration, returns whether the model gets another one to see the
+    /// results of the requested tools.
+    pub fn continues(&mut self, tool_calls_found: bool) -> bool {
+        self.stop_reason = match tool_calls_found {
+            false if self.used_tools => Some(StopReason::Completed),
+            false => Some(StopReason::NoToolCalls),
+            true if self.iterations >= self.max_iterations => {
+                Some(StopReason::MaxIterationsReached)
+            }
+            true => None,
+        };
+        self.used_tools |= tool_calls_found;
+        self.stop_reason.is_none()
+    }
+
+    pub fn stop_reason(&self) -> StopReason {
+        // Only an aborted loop has no reason, it did not get to finish its work either
+        self.stop_reason.unwrap_or(StopReason::MaxIterationsReached)
+    }
+
+    pub fn outcome(&self) -> LoopOutcome {
+        LoopOutcome {
+            reason: self.stop_reason(),
+            iterations: self.iterations,
+            max_iterations: self.max_iterations,
+        }
+    }
+
+    /// Appended to the final message if the limit cut the turn short, so a partial result
+    /// is not mistaken for a finished one.
+    pub fn notice(&self) -> Option<String> {
+        (self.stop_reason() == StopReason::MaxIterationsReached).then(|| {
+            format!(
+                "I reached the limit of {} steps before finishing, so the result above is incomplete. Ask me to continue and I will pick up where I stopped.",
+                self.max_iterations
+            )
+        })
+    }
+
+    /// Appends the notice of [`Self::notice`] to `message`.
+    pub fn finish_message(&self, message: String) -> String {
+        match self.notice() {
+            Some(notice) if message.trim().is_empty() => notice,
+            Some(notice) => format!("{}\n\n{}", message.trim_end(), notice),
+            None => message,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Drives the loop like the copilots do, `tool_calls` plays the model and tells
+    /// whether iteration `n` requests tools.
+    fn run(max_iterations: u32, tool_calls: impl Fn(u32) -> bool) -> ToolLoop {
+        let mut tool_loop = ToolLoop::new(max_iterations);
+        while let Some(iteration) = tool_loop.next_iteration() {
+            if !tool_loop.continues(tool_calls(iteration)) {
+                break;
+            }
+        }
+        tool_loop
+    }
+
+    #[test]
+    fn test_model_that_always_calls_tools_hits_the_cap() {
+        let tool_loop = run(4, |_| true);
+        assert_eq!(
+            tool_loop.outcome(),
+            LoopOutcome {
+                reason: StopReason::MaxIterationsReached,
+                iterations: 4,
+                max_iterations: 4,
+            }
+        );
+
+        let message = tool_loop.finish_message("Added two nodes.".to_string());
+        assert!(message.starts_with("Added two nodes.\n\n"));
+        assert!(message.contains("limit of 4 steps"));
+        assert!(message.contains("incomplete"));
+        assert!(
+            tool_loop
+                .finish_message(String::new())
+                .contains("limit of 4")
+        );
+    }
+
+    #[test]
+    fn test_completed_and_no_tool_calls() {
+        let completed = run(10, |iteration| iteration < 2);
+        assert_eq!(completed.stop_reason(), StopReason::Completed);
+        assert_eq!(completed.outcome().iterations, 3);
+        assert_eq!(completed.finish_message("Done".to_string()), "Done");
+
+        let answered = run(10, |_| false);
+        assert_eq!(answered.stop_reason(), StopReason::NoToolCalls);
+        assert_eq!(answered.outcome().iterations, 1);
+        assert!(answered.notice().is_none());
+
+        // Answering in the last allowed iteration is not cut short
+        let last = run(3, |iteration| iteration < 2);
+        assert_eq!(last.stop_reason(), StopReason::Completed);
+    }
+
+    #[test]
+    fn test_limit_is_clamped() {
+        assert_eq!(run(0, |_| true).outcome().iterations, 1);
+        assert_eq!(
+            run(u32::MAX, |_| true).outcome().max_iterations,
+            MAX_ITERATIONS_LIMIT
+        );
+    }
+
+    #[test]
+    fn test_stop_reason_serialization() {
+        let outcome = run(2, |_| true).outcome();
+        assert_eq!(
+            serde_json::to_value(&outcome).unwrap(),
+            serde_json::json!({
+                "reason": "MaxIterationsReached",
+                "iterations": 2,
+                "max_iterations": 2
+            })
+        );
+    }
+}
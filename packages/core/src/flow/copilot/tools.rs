@@ -436,6 +436,7 @@ COMMAND TYPES:
 - ConnectPins: Connect two pins (use pin NAME, not ID)
 - UpdateNodePin: Set a pin's value
 - RemoveNode: Delete a node
+- RenameNode: Change the display name of an existing node
 - CreateVariable/UpdateVariable/DeleteVariable
 - CreateComment/UpdateComment/DeleteComment
 - CreateLayer/AddNodesToLayer/RemoveNodesFromLayer
@@ -549,6 +550,15 @@ REF_IDS: Use '$0', '$1', etc. to reference nodes in same batch"#.to_string(),
                                     },
                                     "required": ["command_type", "node_id", "position", "summary"]
                                 },
+                                {
+                                    "properties": {
+                                        "command_type": { "const": "RenameNode" },
+                                        "node_id": { "type": "string", "description": "ID of an existing node from context" },
+                                        "new_name": { "type": "string", "description": "New display name, e.g. 'Fetch Orders'" },
+                                        "summary": { "type": "string", "description": "Human-readable summary, e.g. 'Rename HTTP node to Fetch Orders'" }
+                                    },
+                                    "required": ["command_type", "node_id", "new_name", "summary"]
+                                },
                                 {
                                     "properties": {
                                         "command_type": { "const": "CreateVariable" },
@@ -598,11 +608,11 @@ REF_IDS: Use '$0', '$1', etc. to reference nodes in same batch"#.to_string(),
                                     "properties": {
                                         "command_type": { "const": "UpdateComment" },
                                         "comment_id": { "type": "string", "description": "Comment ID from context" },
-                                        "content": { "type": "string", "description": "New content" },
+                                        "content": { "type": "string", "description": "New text, replaces the whole comment" },
                                         "color": { "type": "string", "description": "New color" },
                                         "summary": { "type": "string", "description": "Human-readable summary" }
                                     },
-                                    "required": ["command_type", "comment_id", "summary"]
+                                    "required": ["command_type", "comment_id", "content", "summary"]
                                 },
                                 {
                                     "properties": {
@@ -700,6 +710,14 @@ REF_IDS: Use '$0', '$1', etc. to reference nodes in same batch"#.to_string(),
                 BoardCommand::RemoveNode { node_id, .. } => {
                     format!("  - RemoveNode: {}", node_id)
                 }
+                BoardCommand::RenameNode {
+                    node_id, new_name, ..
+                } => {
+                    format!("  - RenameNode: {} → \"{}\"", node_id, new_name)
+                }
+                BoardCommand::UpdateComment { comment_id, .. } => {
+                    format!("  - UpdateComment: {}", comment_id)
+                }
                 BoardCommand::UpdateNodePin {
                     node_id, pin_id, ..
                 } => {
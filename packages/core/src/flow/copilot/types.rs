@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
 
+use super::tool_loop::{LoopOutcome, StopReason};
+use super::usage::CopilotUsage;
+
 /// Metadata about a node in the catalog
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NodeMetadata {
@@ -159,6 +162,10 @@ pub enum StreamEvent {
         node_id: String,
         description: String,
     },
+    /// Running total of the turn, sent after every model iteration
+    Usage(CopilotUsage),
+    /// Last event of the turn, tells why the agent stopped
+    Finished(LoopOutcome),
 }
 
 /// Represents the type of response from the copilot
@@ -203,6 +210,10 @@ pub struct CopilotResponse {
     pub message: String,
     pub commands: Vec<BoardCommand>,
     pub suggestions: Vec<Suggestion>,
+    #[serde(default)]
+    pub usage: CopilotUsage,
+    #[serde(default)]
+    pub stop_reason: StopReason,
 }
 
 /// Context for a specific run (for log queries)
@@ -293,6 +304,13 @@ pub enum BoardCommand {
         #[serde(default)]
         summary: Option<String>,
     },
+    /// Change the display name of an existing node
+    RenameNode {
+        node_id: String,
+        new_name: String,
+        #[serde(default)]
+        summary: Option<String>,
+    },
     // Variable management
     CreateVariable {
         name: String,
@@ -323,6 +341,15 @@ pub enum BoardCommand {
         #[serde(default)]
         summary: Option<String>,
     },
+    /// Replace the text of an existing comment
+    UpdateComment {
+        comment_id: String,
+        content: String,
+        #[serde(default)]
+        color: Option<String>,
+        #[serde(default)]
+        summary: Option<String>,
+    },
     #[serde(rename = "DeleteComment")]
     RemoveComment {
         comment_id: String,
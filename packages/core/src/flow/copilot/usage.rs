@@ -0,0 +1,173 @@
+//! Token and cost accounting for copilot turns
+
+use flow_like_model_provider::tokenizer::{estimate_token_count, tiktoken_counter};
+use rig::completion::Usage;
+use serde::{Deserialize, Serialize};
+
+use crate::bit::ModelPricing;
+
+/// Tokens a copilot turn consumed, summed over all model iterations
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CopilotUsage {
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub total_tokens: u64,
+    /// Model iterations of the turn, each tool round trip adds one
+    pub iterations: u32,
+    /// Iterations the provider reported no usage for, their tokens are counted locally
+    pub estimated_iterations: u32,
+    /// Estimated cost in USD, `None` if the model bit has no pricing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost: Option<f64>,
+}
+
+/// Sums up the usage of the model iterations of one turn.
+pub(crate) struct UsageMeter {
+    model_name: String,
+    pricing: Option<ModelPricing>,
+    usage: CopilotUsage,
+}
+
+impl UsageMeter {
+    pub fn new(model_name: &str, pricing: Option<ModelPricing>) -> Self {
+        Self {
+            model_name: model_name.to_string(),
+            pricing,
+            usage: CopilotUsage {
+                cost: pricing.map(|_| 0.0),
+                ..Default::default()
+            },
+        }
+    }
+
+    fn count(&self, text: &str) -> u64 {
+        let tokens = match tiktoken_counter(&self.model_name) {
+            Some(counter) => counter.count(text),
+            None => estimate_token_count(text),
+        };
+        tokens as u64
+    }
+
+    /// Adds one iteration. Without usage from the provider the prompt and completion are
+    /// counted with the tokenizer of the model, `prompt` is only built in that case.
+    pub fn record(
+        &mut self,
+        reported: Option<Usage>,
+        prompt: impl FnOnce() -> String,
+        completion: &str,
+    ) -> &CopilotUsage {
+        // Some providers send an all zero usage block instead of none
+        let reported = reported.filter(|usage| usage.input_tokens + usage.output_tokens > 0);
+        let (prompt_tokens, completion_tokens) = match reported {
+            Some(usage) => (usage.input_tokens, usage.output_tokens),
+            None => {
+                self.usage.estimated_iterations += 1;
+                (self.count(&prompt()), self.count(completion))
+            }
+        };
+
+        self.usage.iterations += 1;
+        self.usage.prompt_tokens += prompt_tokens;
+        self.usage.completion_tokens += completion_tokens;
+        self.usage.total_tokens += prompt_tokens + completion_tokens;
+        if let Some(pricing) = &self.pricing {
+            self.usage.cost =
+                Some(pricing.cost(self.usage.prompt_tokens, self.usage.completion_tokens));
+        }
+        &self.usage
+    }
+
+    pub fn finish(self) -> CopilotUsage {
+        self.usage
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rig::completion::GetTokenUsage;
+
+    /// Final chunk of a mocked streaming provider
+    #[derive(Clone)]
+    struct MockFinalResponse {
+        usage: Option<(u64, u64)>,
+    }
+
+    impl GetTokenUsage for MockFinalResponse {
+        fn token_usage(&self) -> Option<Usage> {
+            self.usage.map(|(input_tokens, output_tokens)| Usage {
+                input_tokens,
+                output_tokens,
+                total_tokens: input_tokens + output_tokens,
+            })
+        }
+    }
+
+    const PRICING: ModelPricing = ModelPricing {
+        input_per_million: 2.5,
+        output_per_million: 10.0,
+    };
+
+    #[test]
+    fn test_usage_sums_over_tool_iterations() {
+        // A tool call round, a second tool call round and the final answer
+        let iterations = [
+            MockFinalResponse {
+                usage: Some((1_200, 80)),
+            },
+            MockFinalResponse {
+                usage: Some((1_500, 120)),
+            },
+            MockFinalResponse {
+                usage: Some((1_900, 400)),
+            },
+        ];
+
+        let mut meter = UsageMeter::new("gpt-4o", Some(PRICING));
+        let mut running = Vec::new();
+        for response in &iterations {
+            let usage = meter.record(
+                response.token_usage(),
+                || unreachable!("provider usage is used"),
+                "",
+            );
+            running.push(usage.total_tokens);
+        }
+        let usage = meter.finish();
+
+        assert_eq!(running, vec![1_280, 2_900, 5_200]);
+        assert_eq!(usage.prompt_tokens, 4_600);
+        assert_eq!(usage.completion_tokens, 600);
+        assert_eq!(usage.total_tokens, 5_200);
+        assert_eq!(usage.iterations, 3);
+        assert_eq!(usage.estimated_iterations, 0);
+        assert!((usage.cost.unwrap() - 0.0175).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_missing_usage_falls_back_to_tokenizer() {
+        let mut meter = UsageMeter::new("some-local-model", None);
+        let empty = Usage {
+            input_tokens: 0,
+            output_tokens: 0,
+            total_tokens: 0,
+        };
+        meter.record(Some(empty), || "a".repeat(400), &"b".repeat(40));
+        meter.record(
+            MockFinalResponse {
+                usage: Some((50, 5)),
+            }
+            .token_usage(),
+            String::new,
+            "",
+        );
+        let usage = meter.finish();
+
+        // 4 characters per token without a known tokenizer
+        assert_eq!(usage.prompt_tokens, 150);
+        assert_eq!(usage.completion_tokens, 15);
+        assert_eq!(usage.total_tokens, 165);
+        assert_eq!(usage.estimated_iterations, 1);
+        assert_eq!(usage.cost, None);
+    }
+}
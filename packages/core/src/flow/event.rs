@@ -12,7 +12,9 @@ use crate::{
     utils::compression::{compress_to_file, from_compressed},
 };
 
-use super::{board::VersionType, pin::PinType, variable::Variable};
+use super::{
+    board::VersionType, node::validation::ValidationMessage, pin::PinType, variable::Variable,
+};
 
 /// Simplified input pin metadata for events (used when board can't be fetched)
 #[derive(Serialize, Deserialize, JsonSchema, Clone, Debug)]
@@ -319,6 +321,35 @@ impl Event {
         Ok(())
     }
 
+    /// Node validation messages of the boards this event runs, including the canary board
+    pub async fn validate_nodes(
+        &self,
+        app: &App,
+    ) -> flow_like_types::Result<Vec<ValidationMessage>> {
+        if self.default_page_id.is_some() {
+            return Ok(vec![]);
+        }
+
+        let app_state = app
+            .app_state
+            .clone()
+            .ok_or(flow_like_types::anyhow!("App state not found"))?;
+
+        let mut targets = vec![(self.board_id.clone(), self.board_version)];
+        if let Some(canary) = &self.canary {
+            targets.push((canary.board_id.clone(), canary.board_version));
+        }
+
+        let mut messages = Vec::new();
+        for (board_id, board_version) in targets {
+            let board = app.open_board(board_id, Some(false), board_version).await?;
+            let board = board.lock().await;
+            messages.extend(board.validate_nodes(app_state.clone()).await);
+        }
+
+        Ok(messages)
+    }
+
     pub async fn load(
         id: &str,
         app: &App,
@@ -8,6 +8,7 @@ use crate::profile::Profile;
 use crate::state::FlowLikeState;
 use ahash::{AHashMap, AHashSet, AHasher};
 use context::ExecutionContext;
+use error_policy::ErrorIsolation;
 use flow_like_storage::arrow_array::{RecordBatch, RecordBatchIterator};
 use flow_like_storage::arrow_schema::{FieldRef, SchemaRef};
 use flow_like_storage::files::store::FlowLikeStore;
@@ -32,6 +33,7 @@ use num_cpus;
 use once_cell::sync::Lazy;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::hash::Hasher;
 use std::sync::atomic::Ordering;
 use std::time::{Duration, Instant};
@@ -39,15 +41,22 @@ use std::{
     sync::{Arc, Weak},
     time::SystemTime,
 };
+use timings::{NodeSample, NodeTimings};
 use trace::Trace;
 
+pub mod app_cache;
 pub mod context;
+pub mod error_policy;
 pub mod internal_node;
 pub mod internal_pin;
 pub mod log;
+pub mod run_cache;
+pub mod timings;
 pub mod trace;
 pub mod user_context;
 
+pub use error_policy::{NodeErrorPolicy, NodeReport};
+pub use timings::NodeTiming;
 pub use user_context::{RoleContext, UserExecutionContext};
 
 const USE_DEPENDENCY_GRAPH: bool = false;
@@ -546,6 +555,8 @@ struct RunStack {
     stack: Vec<ExecutionTarget>,
     deduplication: AHashSet<usize>,
     hash: u64,
+    /// When the nodes of this stack became ready, the start of their wait time
+    scheduled_at: Instant,
 }
 
 impl RunStack {
@@ -554,6 +565,7 @@ impl RunStack {
             stack: Vec::with_capacity(capacity),
             deduplication: AHashSet::with_capacity(capacity.saturating_mul(2)),
             hash: 0u64,
+            scheduled_at: Instant::now(),
         }
     }
 
@@ -631,6 +643,9 @@ pub struct InternalRun {
     cpus: usize,
     log_level: LogLevel,
     completion_callbacks: Arc<RwLock<Vec<EventTrigger>>>,
+    error_isolation: ErrorIsolation,
+    node_timings: NodeTimings,
+    deterministic: bool,
 
     // Cached immutable fields from Run to avoid locking
     pub meta: RunMeta,
@@ -929,6 +944,12 @@ impl InternalRun {
             }
         }
 
+        let successors = nodes
+            .iter()
+            .map(|(node_id, node)| (node_id.clone(), node.exec_successor_ids()))
+            .collect();
+        let error_isolation = ErrorIsolation::new(NodeErrorPolicy::default(), successors);
+
         if board.log_level <= LogLevel::Info {
             println!(
                 "InternalRun::new took {:?} on {} nodes and {} pins",
@@ -955,6 +976,9 @@ impl InternalRun {
             log_level: board.log_level,
             profile: Arc::new(profile.clone()),
             completion_callbacks: Arc::new(RwLock::new(vec![])),
+            error_isolation,
+            node_timings: NodeTimings::default(),
+            deterministic: false,
             user_context: None,
             // Cached immutable fields from Run
             meta: RunMeta {
@@ -985,6 +1009,39 @@ impl InternalRun {
         self.user_context.as_ref()
     }
 
+    /// Set how the run reacts to failing nodes
+    pub fn set_error_policy(&mut self, policy: NodeErrorPolicy) {
+        self.error_isolation.set_policy(policy);
+    }
+
+    pub fn error_policy(&self) -> NodeErrorPolicy {
+        self.error_isolation.policy()
+    }
+
+    /// Run ready nodes one after another, ordered by node id, instead of concurrently.
+    ///
+    /// Every step still executes the nodes that became ready in the previous step, so the
+    /// order stays topological, but logs, events and side effects happen in the same order on
+    /// every run with the same inputs. This trades the throughput of parallel branches for
+    /// reproducibility.
+    pub fn set_deterministic(&mut self, deterministic: bool) {
+        self.deterministic = deterministic;
+    }
+
+    pub fn is_deterministic(&self) -> bool {
+        self.deterministic
+    }
+
+    /// Succeeded, failed and skipped nodes of the run so far
+    pub fn node_report(&self) -> NodeReport {
+        self.error_isolation.report()
+    }
+
+    /// Duration, wait time and outcomes of every executed node, keyed by node id
+    pub fn node_timings(&self) -> BTreeMap<String, NodeTiming> {
+        self.node_timings.report()
+    }
+
     // Reuse the same run, but reset the states
     pub async fn fork(&mut self) -> flow_like_types::Result<()> {
         if self.stack.len() != 0 {
@@ -994,6 +1051,8 @@ impl InternalRun {
         }
 
         self.cache.write().await.clear();
+        self.error_isolation.reset();
+        self.node_timings.reset();
         self.stack = Arc::new(RunStack::with_capacity(self.stack.len()));
         self.concurrency_limit = 128_000;
         {
@@ -1037,7 +1096,7 @@ impl InternalRun {
         let meta = self.meta.clone();
         let user_context = self.user_context.clone();
 
-        let new_stack = futures::stream::iter(stack.stack.clone())
+        let results = futures::stream::iter(stack.stack.clone())
             .map(|target| {
                 // Clone per iteration as needed
                 let dependencies = dependencies.clone();
@@ -1054,9 +1113,12 @@ impl InternalRun {
                 let nodes = self.nodes.clone();
                 let oauth_tokens = self.oauth_tokens.clone();
                 let user_context = user_context.clone();
+                let scheduled_at = stack.scheduled_at;
 
                 async move {
-                    step_core(
+                    let started = Instant::now();
+                    let node = target.node.clone();
+                    let result = step_core(
                         nodes,
                         target,
                         concurrency_limit,
@@ -1076,24 +1138,15 @@ impl InternalRun {
                         oauth_tokens,
                         user_context,
                     )
-                    .await
+                    .await;
+                    (node_sample(&node, scheduled_at, started), result)
                 }
             })
             .buffer_unordered(self.cpus)
-            .fold(
-                RunStack::with_capacity(stack.stack.len()),
-                |mut acc: RunStack, result| async move {
-                    if let Ok(inner_iter) = result {
-                        for node in inner_iter {
-                            acc.push(node);
-                        }
-                    }
-                    acc
-                },
-            )
+            .collect::<Vec<_>>()
             .await;
 
-        self.stack = Arc::new(new_stack);
+        self.schedule(results, stack.stack.len());
     }
 
     async fn step_single(
@@ -1108,6 +1161,8 @@ impl InternalRun {
         let concurrency_limit = self.concurrency_limit;
 
         let target = stack.stack.first().cloned().unwrap();
+        let node = target.node.clone();
+        let started = Instant::now();
         let connected_nodes = step_core(
             self.nodes.clone(),
             target,
@@ -1130,10 +1185,72 @@ impl InternalRun {
         )
         .await;
 
-        let mut new_stack = RunStack::with_capacity(stack.len());
-        if let Ok(nodes) = connected_nodes {
-            for node in nodes {
-                new_stack.push(node);
+        let sample = node_sample(&node, stack.scheduled_at, started);
+        self.schedule(vec![(sample, connected_nodes)], stack.len());
+    }
+
+    async fn step_sequential(
+        &mut self,
+        stack: Arc<RunStack>,
+        handler: &Arc<FlowLikeState>,
+        log_level: LogLevel,
+        stage: ExecutionStage,
+    ) {
+        let mut results = Vec::with_capacity(stack.len());
+        for target in deterministic_order(&stack.stack) {
+            let node = target.node.clone();
+            let started = Instant::now();
+            let result = step_core(
+                self.nodes.clone(),
+                target,
+                self.concurrency_limit,
+                handler,
+                &self.run,
+                &self.meta,
+                &self.variables,
+                &self.cache,
+                log_level,
+                stage.clone(),
+                &self.dependencies,
+                &self.profile,
+                &self.callback,
+                &self.completion_callbacks,
+                self.credentials.clone(),
+                self.token.clone(),
+                self.oauth_tokens.clone(),
+                self.user_context.clone(),
+            )
+            .await;
+            let sample = node_sample(&node, stack.scheduled_at, started);
+            results.push((sample, result));
+        }
+
+        self.schedule(results, stack.len());
+    }
+
+    /// Records the outcome and timing of each executed node and builds the next stack from
+    /// the nodes the error policy still admits
+    fn schedule(
+        &mut self,
+        results: Vec<(NodeSample, flow_like_types::Result<Vec<ExecutionTarget>>)>,
+        capacity: usize,
+    ) {
+        let mut connected = Vec::with_capacity(capacity);
+        for (sample, result) in results {
+            self.node_timings.record(&sample, result.is_ok());
+            match result {
+                Ok(nodes) => {
+                    self.error_isolation.record(&sample.node_id, true);
+                    connected.extend(nodes);
+                }
+                Err(_) => self.error_isolation.record(&sample.node_id, false),
+            }
+        }
+
+        let mut new_stack = RunStack::with_capacity(capacity);
+        for target in connected {
+            if self.error_isolation.admit(&target.node.meta.id) {
+                new_stack.push(target);
             }
         }
 
@@ -1150,6 +1267,10 @@ impl InternalRun {
 
         match stack.len() {
             1 => self.step_single(stack, &handler, log_level, stage).await,
+            _ if self.deterministic => {
+                self.step_sequential(stack, &handler, log_level, stage)
+                    .await
+            }
             _ => self.step_parallel(stack, &handler, log_level, stage).await,
         };
 
@@ -1237,6 +1358,9 @@ impl InternalRun {
             }
         });
 
+        // The first nodes wait from here on, not from when the run was built
+        Arc::make_mut(&mut self.stack).scheduled_at = Instant::now();
+
         let mut stack_hash = self.stack.hash();
         let mut current_stack_len = self.stack.len();
         let mut errored = false;
@@ -1260,13 +1384,16 @@ impl InternalRun {
 
         self.trigger_completion_callbacks().await;
         self.drop_nodes().await;
+        self.cache.write().await.clear();
 
         let meta = {
             let prepared: Option<PreparedFlush> =
                 match lock_with_timeout(self.run.as_ref(), "run_finalize").await {
                     Ok(mut run) => {
                         run.end = SystemTime::now();
-                        run.status = if errored {
+                        let aborted = self.error_isolation.policy() == NodeErrorPolicy::Abort
+                            && self.error_isolation.has_failures();
+                        run.status = if errored || aborted {
                             RunStatus::Failed
                         } else {
                             RunStatus::Success
@@ -1470,6 +1597,22 @@ fn recursive_get_deps(
     found_dependencies
 }
 
+fn node_sample(node: &InternalNode, scheduled_at: Instant, started: Instant) -> NodeSample {
+    NodeSample {
+        node_id: node.meta.id.clone(),
+        node_type: node.meta.name.clone(),
+        wait: started.saturating_duration_since(scheduled_at),
+        duration: started.elapsed(),
+    }
+}
+
+/// Ready nodes sorted by node id, the order of a deterministic step
+fn deterministic_order(targets: &[ExecutionTarget]) -> Vec<ExecutionTarget> {
+    let mut ordered = targets.to_vec();
+    ordered.sort_by(|a, b| a.node.meta.id.cmp(&b.node.meta.id));
+    ordered
+}
+
 #[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
 pub enum RunStatus {
     Running,
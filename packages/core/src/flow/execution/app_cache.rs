@@ -0,0 +1,247 @@
+//! Cache that outlives a single run
+//!
+//! Unlike the [run cache](super::run_cache), entries here are shared by every run of the
+//! process, so nodes of event driven flows can remember what earlier runs saw (debounce
+//! timers, last emissions, ...). Every entry has a TTL that is refreshed whenever it is
+//! used, expired entries are dropped lazily so keys that stop coming in do not leak.
+//!
+//! The cache is held in memory only. It starts empty after a restart of the executor and
+//! every executor has its own, so anything built on it is best effort.
+
+use ahash::AHashMap;
+use flow_like_types::{Cacheable, anyhow};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Expired entries are swept at most this often, reading an expired entry drops it right away
+const PURGE_INTERVAL: Duration = Duration::from_secs(30);
+
+struct Entry {
+    value: Arc<dyn Cacheable>,
+    expires_at: Instant,
+}
+
+struct Entries {
+    entries: AHashMap<String, Entry>,
+    last_purge: Instant,
+}
+
+pub struct AppCache {
+    inner: Mutex<Entries>,
+}
+
+impl Default for AppCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AppCache {
+    pub fn new() -> Self {
+        AppCache {
+            inner: Mutex::new(Entries {
+                entries: AHashMap::new(),
+                last_purge: Instant::now(),
+            }),
+        }
+    }
+
+    /// The entry under `key` if it exists, has not expired and is a `T`.
+    /// Refreshes the TTL of the entry.
+    pub fn get<T: Cacheable>(&self, key: &str, ttl: Duration) -> Option<Arc<T>> {
+        self.get_at(key, ttl, Instant::now())
+    }
+
+    /// The entry under `key`, created with `init` if it is missing or expired.
+    /// Refreshes the TTL of the entry. Fails if the key holds a value of another type.
+    pub fn get_or_insert_with<T, F>(
+        &self,
+        key: &str,
+        ttl: Duration,
+        init: F,
+    ) -> flow_like_types::Result<Arc<T>>
+    where
+        T: Cacheable,
+        F: FnOnce() -> T,
+    {
+        self.get_or_insert_at(key, ttl, Instant::now(), init)
+    }
+
+    pub fn remove(&self, key: &str) {
+        self.lock().entries.remove(key);
+    }
+
+    /// Number of entries, including expired ones that were not swept yet
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drops every expired entry and returns how many were dropped
+    pub fn purge_expired(&self) -> usize {
+        let mut inner = self.lock();
+        purge(&mut inner, Instant::now())
+    }
+
+    fn get_at<T: Cacheable>(&self, key: &str, ttl: Duration, now: Instant) -> Option<Arc<T>> {
+        let mut inner = self.lock();
+        let entry = inner.entries.get_mut(key)?;
+        if entry.expires_at <= now {
+            inner.entries.remove(key);
+            return None;
+        }
+        entry.expires_at = now + ttl;
+        entry.value.clone().downcast_arc::<T>()
+    }
+
+    fn get_or_insert_at<T, F>(
+        &self,
+        key: &str,
+        ttl: Duration,
+        now: Instant,
+        init: F,
+    ) -> flow_like_types::Result<Arc<T>>
+    where
+        T: Cacheable,
+        F: FnOnce() -> T,
+    {
+        let mut inner = self.lock();
+        if now.duration_since(inner.last_purge) >= PURGE_INTERVAL {
+            purge(&mut inner, now);
+        }
+
+        let alive = inner
+            .entries
+            .get(key)
+            .is_some_and(|entry| entry.expires_at > now);
+        if !alive {
+            inner.entries.insert(
+                key.to_string(),
+                Entry {
+                    value: Arc::new(init()),
+                    expires_at: now,
+                },
+            );
+        }
+
+        let entry = inner
+            .entries
+            .get_mut(key)
+            .expect("entry exists or was inserted");
+        entry.expires_at = now + ttl;
+        entry
+            .value
+            .clone()
+            .downcast_arc::<T>()
+            .ok_or_else(|| anyhow!("App cache entry '{}' has a different type", key))
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Entries> {
+        // A panic while holding the lock cannot leave an entry half written
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn purge(inner: &mut Entries, now: Instant) -> usize {
+    let before = inner.entries.len();
+    inner.entries.retain(|_, entry| entry.expires_at > now);
+    inner.last_purge = now;
+    before - inner.entries.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::any::Any;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Default)]
+    struct Counter(AtomicUsize);
+
+    impl Cacheable for Counter {
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn as_any_mut(&mut self) -> &mut dyn Any {
+            self
+        }
+    }
+
+    #[test]
+    fn test_entries_expire_unless_used() {
+        let cache = AppCache::new();
+        let ttl = Duration::from_secs(10);
+        let start = Instant::now();
+
+        let counter = cache
+            .get_or_insert_at("a", ttl, start, Counter::default)
+            .unwrap();
+        counter.0.fetch_add(1, Ordering::SeqCst);
+
+        // Every use pushes the expiry out again
+        let later = start + Duration::from_secs(8);
+        let again = cache.get_at::<Counter>("a", ttl, later).unwrap();
+        assert!(Arc::ptr_eq(&counter, &again));
+        assert!(
+            cache
+                .get_at::<Counter>("a", ttl, later + Duration::from_secs(9))
+                .is_some()
+        );
+
+        let expired = later + Duration::from_secs(30);
+        assert!(cache.get_at::<Counter>("a", ttl, expired).is_none());
+        assert!(cache.is_empty());
+
+        let fresh = cache
+            .get_or_insert_at("a", ttl, expired, Counter::default)
+            .unwrap();
+        assert_eq!(fresh.0.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_expired_entries_are_swept_on_insert() {
+        let cache = AppCache::new();
+        let start = Instant::now();
+        for key in ["a", "b", "c"] {
+            cache
+                .get_or_insert_at(key, Duration::from_secs(1), start, Counter::default)
+                .unwrap();
+        }
+        assert_eq!(cache.len(), 3);
+
+        let later = start + PURGE_INTERVAL + Duration::from_secs(1);
+        cache
+            .get_or_insert_at("d", Duration::from_secs(1), later, Counter::default)
+            .unwrap();
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_other_type_is_an_error() {
+        struct Other;
+        impl Cacheable for Other {
+            fn as_any(&self) -> &dyn Any {
+                self
+            }
+
+            fn as_any_mut(&mut self) -> &mut dyn Any {
+                self
+            }
+        }
+
+        let cache = AppCache::new();
+        let ttl = Duration::from_secs(10);
+        cache.get_or_insert_with("a", ttl, || Other).unwrap();
+
+        assert!(cache.get::<Counter>("a", ttl).is_none());
+        assert!(
+            cache
+                .get_or_insert_with("a", ttl, Counter::default)
+                .is_err()
+        );
+    }
+}
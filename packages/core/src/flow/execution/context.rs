@@ -26,6 +26,7 @@ use flow_like_types::{
     json::from_value,
     sync::{Mutex, RwLock},
 };
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use std::{
     collections::BTreeMap,
@@ -151,6 +152,16 @@ struct RunUpdateEvent {
     method: RunUpdateEventMethod,
 }
 
+/// A node error, kept on the context of the failed node and handed up to the contexts that
+/// ran it, so nodes executing a branch inline can tell what went wrong in it
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct NodeFailure {
+    pub node_id: String,
+    /// Catalog name of the failed node, e.g. "http_request"
+    pub node_type: String,
+    pub message: String,
+}
+
 #[derive(Clone)]
 pub struct ExecutionContext {
     pub id: String,
@@ -178,6 +189,8 @@ pub struct ExecutionContext {
     pub oauth_tokens: Arc<AHashMap<String, OAuthToken>>,
     /// User context containing information about who triggered the execution
     pub user_context: Option<super::UserExecutionContext>,
+    /// First error of this node or of a sub context pushed into it
+    pub failure: Option<NodeFailure>,
     cancellation_token: Option<CancellationToken>,
     run_id: String,
     state: NodeState,
@@ -247,6 +260,7 @@ impl ExecutionContext {
             oauth_tokens,
             cancellation_token: None,
             user_context: None,
+            failure: None,
         }
     }
     pub fn run_id(&self) -> &str {
@@ -320,6 +334,7 @@ impl ExecutionContext {
             oauth_tokens,
             cancellation_token: None,
             user_context: None,
+            failure: None,
         }
     }
 
@@ -592,6 +607,26 @@ impl ExecutionContext {
         cache.insert(key.to_string(), value);
     }
 
+    /// Typed read of a run cache entry, `None` if it is missing or holds another type
+    pub async fn get_cached<T: Cacheable>(&self, key: &str) -> Option<Arc<T>> {
+        super::run_cache::get_cached(&self.cache, key).await
+    }
+
+    /// Typed run cache entry, created with `init` if missing, so nodes of the same run can
+    /// share connection handles. See [`super::run_cache`] for the lifetime of entries.
+    pub async fn get_or_insert_cached<T, F, Fut>(
+        &self,
+        key: &str,
+        init: F,
+    ) -> flow_like_types::Result<Arc<T>>
+    where
+        T: Cacheable,
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = flow_like_types::Result<T>>,
+    {
+        super::run_cache::get_or_insert_cached(&self.cache, key, init).await
+    }
+
     /// Get an OAuth token for a specific provider.
     /// Returns the token if found and not expired.
     pub fn get_oauth_token(&self, provider_id: &str) -> Option<&OAuthToken> {
@@ -818,6 +853,26 @@ impl ExecutionContext {
         if let Some(result) = &context.result {
             self.result = Some(result.clone());
         }
+        if self.failure.is_none() {
+            self.failure = context.failure.take();
+        }
+    }
+
+    /// Records the error of the node this context runs, unless an earlier one is known
+    pub fn record_failure(&mut self, message: impl Into<String>) {
+        if self.failure.is_some() {
+            return;
+        }
+        self.failure = Some(NodeFailure {
+            node_id: self.node.meta.id.clone(),
+            node_type: self.node.meta.name.clone(),
+            message: message.into(),
+        });
+    }
+
+    /// Removes and returns the recorded failure, e.g. once a branch's error was handled
+    pub fn take_failure(&mut self) -> Option<NodeFailure> {
+        self.failure.take()
     }
 
     pub fn end_trace(&mut self) {
@@ -0,0 +1,257 @@
+use ahash::{AHashMap, AHashSet};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+/// What a run does when a node fails.
+///
+/// Nodes with an error handler connected to `auto_handle_error` do not count as failed,
+/// the policy only applies to errors that would otherwise end the branch.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NodeErrorPolicy {
+    /// Stop the whole run, nothing that is still scheduled is executed
+    Abort,
+    /// Skip everything downstream of the failed node, including joins that are also reached
+    /// through healthy branches. Independent branches keep running.
+    ContinueBranch,
+    /// Only the failed node's own outputs are not followed. Nodes reached through other
+    /// branches still run. This is how runs behave unless a stricter policy is set.
+    #[default]
+    ContinueAll,
+}
+
+/// Per node outcome of a run
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Default, PartialEq, Eq)]
+pub struct NodeReport {
+    pub succeeded: Vec<String>,
+    pub failed: Vec<String>,
+    /// Nodes downstream of a failure that never executed
+    pub skipped: Vec<String>,
+}
+
+/// Tracks failed nodes during a run and decides which scheduled nodes may still execute
+#[derive(Clone)]
+pub struct ErrorIsolation {
+    policy: NodeErrorPolicy,
+    /// Node id -> ids of the nodes its execution outputs lead to
+    successors: Arc<AHashMap<String, Vec<String>>>,
+    succeeded: AHashSet<String>,
+    failed: AHashSet<String>,
+    dropped: AHashSet<String>,
+    tainted: AHashSet<String>,
+    aborted: bool,
+}
+
+impl ErrorIsolation {
+    pub fn new(policy: NodeErrorPolicy, successors: AHashMap<String, Vec<String>>) -> Self {
+        Self {
+            policy,
+            successors: Arc::new(successors),
+            succeeded: AHashSet::new(),
+            failed: AHashSet::new(),
+            dropped: AHashSet::new(),
+            tainted: AHashSet::new(),
+            aborted: false,
+        }
+    }
+
+    pub fn policy(&self) -> NodeErrorPolicy {
+        self.policy
+    }
+
+    pub fn set_policy(&mut self, policy: NodeErrorPolicy) {
+        self.policy = policy;
+    }
+
+    pub fn has_failures(&self) -> bool {
+        !self.failed.is_empty()
+    }
+
+    /// Forgets all outcomes, used when a run is forked
+    pub fn reset(&mut self) {
+        self.succeeded.clear();
+        self.failed.clear();
+        self.dropped.clear();
+        self.tainted.clear();
+        self.aborted = false;
+    }
+
+    /// Records the outcome of a node that was executed
+    pub fn record(&mut self, node_id: &str, succeeded: bool) {
+        if succeeded {
+            self.succeeded.insert(node_id.to_string());
+            return;
+        }
+
+        self.failed.insert(node_id.to_string());
+        match self.policy {
+            NodeErrorPolicy::Abort => self.aborted = true,
+            NodeErrorPolicy::ContinueBranch => {
+                let downstream = self.downstream([node_id]);
+                self.tainted.extend(downstream);
+            }
+            NodeErrorPolicy::ContinueAll => {}
+        }
+    }
+
+    /// Whether a node that was just scheduled may execute. Rejected nodes are reported as skipped.
+    pub fn admit(&mut self, node_id: &str) -> bool {
+        if self.aborted || self.tainted.contains(node_id) {
+            self.dropped.insert(node_id.to_string());
+            return false;
+        }
+        true
+    }
+
+    pub fn report(&self) -> NodeReport {
+        let executed = |id: &String| self.succeeded.contains(id) || self.failed.contains(id);
+
+        let roots = self.failed.iter().chain(self.dropped.iter());
+        let skipped: BTreeSet<String> = self
+            .downstream(roots.map(String::as_str))
+            .into_iter()
+            .chain(self.dropped.iter().cloned())
+            .filter(|id| !executed(id))
+            .collect();
+
+        let mut succeeded: Vec<String> = self
+            .succeeded
+            .iter()
+            .filter(|id| !self.failed.contains(*id))
+            .cloned()
+            .collect();
+        succeeded.sort();
+        let mut failed: Vec<String> = self.failed.iter().cloned().collect();
+        failed.sort();
+
+        NodeReport {
+            succeeded,
+            failed,
+            skipped: skipped.into_iter().collect(),
+        }
+    }
+
+    /// All nodes reachable from the execution outputs of `roots`
+    fn downstream<'a>(&self, roots: impl IntoIterator<Item = &'a str>) -> AHashSet<String> {
+        let mut seen = AHashSet::new();
+        let mut stack: Vec<&str> = roots
+            .into_iter()
+            .filter_map(|root| self.successors.get(root))
+            .flatten()
+            .map(String::as_str)
+            .collect();
+
+        while let Some(id) = stack.pop() {
+            if !seen.insert(id.to_string()) {
+                continue;
+            }
+            if let Some(next) = self.successors.get(id) {
+                stack.extend(next.iter().map(String::as_str));
+            }
+        }
+        seen
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A -> B1 -> B2 -> D and A -> C1 -> C2 -> D
+    fn diamond() -> AHashMap<String, Vec<String>> {
+        [
+            ("A", vec!["B1", "C1"]),
+            ("B1", vec!["B2"]),
+            ("B2", vec!["D"]),
+            ("C1", vec!["C2"]),
+            ("C2", vec!["D"]),
+        ]
+        .into_iter()
+        .map(|(node, next)| {
+            (
+                node.to_string(),
+                next.into_iter().map(str::to_string).collect(),
+            )
+        })
+        .collect()
+    }
+
+    /// Steps through the graph like `InternalRun`: every scheduled node of a step runs,
+    /// successful nodes schedule their successors for the next step.
+    fn run(policy: NodeErrorPolicy, failing: &[&str]) -> (NodeReport, Vec<String>) {
+        let graph = diamond();
+        let mut isolation = ErrorIsolation::new(policy, graph.clone());
+        let mut stack = vec!["A".to_string()];
+        let mut order = vec![];
+
+        while !stack.is_empty() {
+            let mut next = vec![];
+            for node in stack.drain(..) {
+                order.push(node.clone());
+                let ok = !failing.contains(&node.as_str());
+                isolation.record(&node, ok);
+                if ok {
+                    next.extend(graph.get(&node).cloned().unwrap_or_default());
+                }
+            }
+            for node in next {
+                if !stack.contains(&node) && isolation.admit(&node) {
+                    stack.push(node);
+                }
+            }
+        }
+
+        (isolation.report(), order)
+    }
+
+    fn ids(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[test]
+    fn test_abort_stops_sibling_branch() {
+        let (report, _) = run(NodeErrorPolicy::Abort, &["B1"]);
+        assert_eq!(report.succeeded, ids(&["A", "C1"]));
+        assert_eq!(report.failed, ids(&["B1"]));
+        assert_eq!(report.skipped, ids(&["B2", "C2", "D"]));
+    }
+
+    #[test]
+    fn test_continue_branch_skips_join_of_failed_branch() {
+        let (report, order) = run(NodeErrorPolicy::ContinueBranch, &["B1"]);
+        assert_eq!(report.succeeded, ids(&["A", "C1", "C2"]));
+        assert_eq!(report.failed, ids(&["B1"]));
+        assert_eq!(report.skipped, ids(&["B2", "D"]));
+        assert!(!order.contains(&"D".to_string()));
+    }
+
+    #[test]
+    fn test_continue_all_runs_join_through_healthy_branch() {
+        let (report, _) = run(NodeErrorPolicy::ContinueAll, &["B1"]);
+        assert_eq!(report.succeeded, ids(&["A", "C1", "C2", "D"]));
+        assert_eq!(report.failed, ids(&["B1"]));
+        assert_eq!(report.skipped, ids(&["B2"]));
+    }
+
+    #[test]
+    fn test_no_failures_skips_nothing() {
+        for policy in [
+            NodeErrorPolicy::Abort,
+            NodeErrorPolicy::ContinueBranch,
+            NodeErrorPolicy::ContinueAll,
+        ] {
+            let (report, _) = run(policy, &[]);
+            assert_eq!(report.succeeded, ids(&["A", "B1", "B2", "C1", "C2", "D"]));
+            assert!(report.failed.is_empty());
+            assert!(report.skipped.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_policy_deserializes_from_name() {
+        let policy: NodeErrorPolicy =
+            flow_like_types::json::from_str("\"ContinueBranch\"").unwrap();
+        assert_eq!(policy, NodeErrorPolicy::ContinueBranch);
+    }
+}
@@ -192,6 +192,7 @@ async fn run_node_logic_only(
             &format!("Failed to execute node: {}", &err_string),
             LogLevel::Error,
         );
+        ctx.record_failure(format!("{:#}", e));
         log_message.end();
         ctx.log(log_message);
         ctx.end_trace();
@@ -579,6 +580,42 @@ impl InternalNode {
         Ok(out)
     }
 
+    /// Ids of all nodes reachable through this node's execution outputs, whether or not the
+    /// outputs are active. Layer relay pins are followed.
+    pub fn exec_successor_ids(&self) -> Vec<String> {
+        let mut successors: AHashSet<String> = AHashSet::new();
+        let mut visited_pins: AHashSet<usize> = AHashSet::with_capacity(16);
+        let mut stack: Vec<Weak<InternalPin>> = Vec::with_capacity(16);
+
+        for pin in self.pins.values() {
+            if pin.pin_type != PinType::Output || pin.data_type != VariableType::Execution {
+                continue;
+            }
+            stack.extend(pin.connected_to().iter().cloned());
+        }
+
+        while let Some(next_weak) = stack.pop() {
+            let Some(pin_arc) = next_weak.upgrade() else {
+                continue;
+            };
+            if !visited_pins.insert(ptr_key(&pin_arc)) {
+                continue;
+            }
+
+            match pin_arc.node() {
+                Some(node_w) => {
+                    if let Some(parent) = node_w.upgrade() {
+                        successors.insert(parent.meta.id.clone());
+                    }
+                }
+                // relay pin; keep walking
+                None => stack.extend(pin_arc.connected_to().iter().cloned()),
+            }
+        }
+
+        successors.into_iter().collect()
+    }
+
     pub async fn get_error_handled_nodes(
         &self,
         context: &ExecutionContext,
@@ -971,6 +1008,7 @@ impl InternalNode {
         // deps
         if !InternalNode::trigger_missing_dependencies(context, recursion_guard, false).await {
             context.log_message("Failed to trigger missing dependencies", LogLevel::Error);
+            context.record_failure("Failed to trigger missing dependencies");
             context.end_trace();
             InternalNode::handle_error(
                 context,
@@ -1029,6 +1067,7 @@ impl InternalNode {
                     .await
                 {
                     let err_string = "Failed to trigger successor dependencies".to_string();
+                    sub.record_failure(err_string.clone());
                     InternalNode::handle_error(&mut sub, &err_string, &mut local_guard).await?;
                     sub.end_trace();
                     context.push_sub_context(&mut sub);
@@ -1119,6 +1158,7 @@ impl InternalNode {
                 &format!("Failed to execute node: {}", err_string),
                 LogLevel::Error,
             );
+            context.record_failure(format!("{:#}", e));
             log_message.end();
             context.log(log_message);
             context.end_trace();
@@ -0,0 +1,173 @@
+//! Typed access to the run cache
+//!
+//! The run cache is shared by every node of a run, so nodes can hand long lived handles
+//! (database pools, sockets, mail sessions, ...) to each other under a well known key.
+//! It lives as long as the run: `InternalRun::execute` clears it once the run ended and the
+//! completion callbacks had a chance to close what is still open, `fork` clears it as well.
+//!
+//! The helpers work on the same `RwLock` map as `ExecutionContext::cache`, which nodes
+//! already read and write directly, so typed and untyped entries share one store and one
+//! clear. Locks are only held for single map operations, never across `init`.
+
+use ahash::AHashMap;
+use flow_like_types::{Cacheable, anyhow, sync::RwLock};
+use std::future::Future;
+use std::sync::Arc;
+
+pub type RunCache = Arc<RwLock<AHashMap<String, Arc<dyn Cacheable>>>>;
+
+/// The entry under `key` if it exists and is a `T`
+pub async fn get_cached<T: Cacheable>(cache: &RunCache, key: &str) -> Option<Arc<T>> {
+    let value = cache.read().await.get(key).cloned()?;
+    value.downcast_arc::<T>()
+}
+
+/// The entry under `key`, created with `init` if it is missing.
+///
+/// `init` runs without holding the cache lock, so slow connects do not block other nodes.
+/// If two nodes race, the first insert wins and the other value is dropped.
+/// Fails if the key already holds a value of another type.
+pub async fn get_or_insert_cached<T, F, Fut>(
+    cache: &RunCache,
+    key: &str,
+    init: F,
+) -> flow_like_types::Result<Arc<T>>
+where
+    T: Cacheable,
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = flow_like_types::Result<T>>,
+{
+    if let Some(value) = cache.read().await.get(key).cloned() {
+        return value
+            .downcast_arc::<T>()
+            .ok_or_else(|| anyhow!("Cache entry '{}' has a different type", key));
+    }
+
+    let created: Arc<dyn Cacheable> = Arc::new(init().await?);
+    let value = cache
+        .write()
+        .await
+        .entry(key.to_string())
+        .or_insert(created)
+        .clone();
+    value
+        .downcast_arc::<T>()
+        .ok_or_else(|| anyhow!("Cache entry '{}' has a different type", key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flow_like_types::tokio;
+    use std::any::Any;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct Pool {
+        url: String,
+        connects: Arc<AtomicUsize>,
+    }
+
+    impl Cacheable for Pool {
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn as_any_mut(&mut self) -> &mut dyn Any {
+            self
+        }
+    }
+
+    struct Socket;
+
+    impl Cacheable for Socket {
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn as_any_mut(&mut self) -> &mut dyn Any {
+            self
+        }
+    }
+
+    fn connect(connects: &Arc<AtomicUsize>) -> flow_like_types::Result<Pool> {
+        connects.fetch_add(1, Ordering::SeqCst);
+        Ok(Pool {
+            url: "postgres://localhost/app".to_string(),
+            connects: connects.clone(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_value_inserted_by_one_node_is_read_typed_by_another() {
+        let cache: RunCache = Arc::new(RwLock::new(AHashMap::new()));
+        let connects = Arc::new(AtomicUsize::new(0));
+
+        let writer = {
+            let cache = cache.clone();
+            let connects = connects.clone();
+            tokio::spawn(async move {
+                get_or_insert_cached(&cache, "pg", || async { connect(&connects) })
+                    .await
+                    .unwrap()
+            })
+        };
+        let inserted = writer.await.unwrap();
+
+        let reader = {
+            let cache = cache.clone();
+            tokio::spawn(async move { get_cached::<Pool>(&cache, "pg").await })
+        };
+        let read = reader.await.unwrap().expect("pool should be cached");
+
+        assert!(Arc::ptr_eq(&inserted, &read));
+        assert_eq!(read.url, "postgres://localhost/app");
+        assert_eq!(read.connects.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_existing_entry_is_reused() {
+        let cache: RunCache = Arc::new(RwLock::new(AHashMap::new()));
+        let connects = Arc::new(AtomicUsize::new(0));
+
+        let first = get_or_insert_cached(&cache, "pg", || async { connect(&connects) })
+            .await
+            .unwrap();
+        let second = get_or_insert_cached(&cache, "pg", || async { connect(&connects) })
+            .await
+            .unwrap();
+
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(connects.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_wrong_type_is_not_returned() {
+        let cache: RunCache = Arc::new(RwLock::new(AHashMap::new()));
+        cache
+            .write()
+            .await
+            .insert("ws".to_string(), Arc::new(Socket) as Arc<dyn Cacheable>);
+
+        assert!(get_cached::<Pool>(&cache, "ws").await.is_none());
+        assert!(get_cached::<Socket>(&cache, "ws").await.is_some());
+        assert!(get_cached::<Socket>(&cache, "missing").await.is_none());
+
+        let connects = Arc::new(AtomicUsize::new(0));
+        let result = get_or_insert_cached(&cache, "ws", || async { connect(&connects) }).await;
+        assert!(result.is_err());
+        assert_eq!(connects.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_failed_init_inserts_nothing() {
+        let cache: RunCache = Arc::new(RwLock::new(AHashMap::new()));
+
+        let result = get_or_insert_cached::<Pool, _, _>(&cache, "pg", || async {
+            Err(anyhow!("connection refused"))
+        })
+        .await;
+
+        assert!(result.is_err());
+        assert!(cache.read().await.is_empty());
+    }
+}
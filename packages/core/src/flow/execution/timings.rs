@@ -0,0 +1,119 @@
+use ahash::AHashMap;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// Timing of one node, summed over all of its executions in a run.
+///
+/// Pure nodes are evaluated as part of the node that reads them, so their time is counted
+/// there and they do not get an entry of their own.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Default, PartialEq)]
+pub struct NodeTiming {
+    /// Name of the node in the catalog, e.g. "delay"
+    pub node_type: String,
+    pub executions: u64,
+    pub succeeded: u64,
+    pub failed: u64,
+    /// Time spent executing the node
+    pub total_ms: f64,
+    /// Longest single execution
+    pub max_ms: f64,
+    /// Time the node was ready to run but waited for a free worker or for the nodes ahead of
+    /// it in a deterministic step
+    pub wait_ms: f64,
+}
+
+impl NodeTiming {
+    pub fn average_ms(&self) -> f64 {
+        if self.executions == 0 {
+            return 0.0;
+        }
+        self.total_ms / self.executions as f64
+    }
+}
+
+/// One execution of a node
+#[derive(Debug, Clone)]
+pub struct NodeSample {
+    pub node_id: String,
+    pub node_type: String,
+    pub wait: Duration,
+    pub duration: Duration,
+}
+
+/// Collects [`NodeSample`]s during a run
+#[derive(Clone, Default)]
+pub struct NodeTimings {
+    timings: AHashMap<String, NodeTiming>,
+}
+
+impl NodeTimings {
+    pub fn record(&mut self, sample: &NodeSample, succeeded: bool) {
+        let timing = self
+            .timings
+            .entry(sample.node_id.clone())
+            .or_insert_with(|| NodeTiming {
+                node_type: sample.node_type.clone(),
+                ..Default::default()
+            });
+
+        let duration_ms = sample.duration.as_secs_f64() * 1000.0;
+        timing.executions += 1;
+        if succeeded {
+            timing.succeeded += 1;
+        } else {
+            timing.failed += 1;
+        }
+        timing.total_ms += duration_ms;
+        timing.max_ms = timing.max_ms.max(duration_ms);
+        timing.wait_ms += sample.wait.as_secs_f64() * 1000.0;
+    }
+
+    pub fn reset(&mut self) {
+        self.timings.clear();
+    }
+
+    /// Timings keyed by node id
+    pub fn report(&self) -> BTreeMap<String, NodeTiming> {
+        self.timings
+            .iter()
+            .map(|(id, timing)| (id.clone(), timing.clone()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(node_id: &str, wait_ms: u64, duration_ms: u64) -> NodeSample {
+        NodeSample {
+            node_id: node_id.to_string(),
+            node_type: "delay".to_string(),
+            wait: Duration::from_millis(wait_ms),
+            duration: Duration::from_millis(duration_ms),
+        }
+    }
+
+    #[test]
+    fn test_samples_are_summed_per_node() {
+        let mut timings = NodeTimings::default();
+        timings.record(&sample("a", 5, 10), true);
+        timings.record(&sample("a", 0, 30), false);
+        timings.record(&sample("b", 1, 2), true);
+
+        let report = timings.report();
+        let a = &report["a"];
+        assert_eq!(a.node_type, "delay");
+        assert_eq!((a.executions, a.succeeded, a.failed), (2, 1, 1));
+        assert_eq!(a.total_ms, 40.0);
+        assert_eq!(a.max_ms, 30.0);
+        assert_eq!(a.wait_ms, 5.0);
+        assert_eq!(a.average_ms(), 20.0);
+        assert_eq!(report["b"].executions, 1);
+
+        timings.reset();
+        assert!(timings.report().is_empty());
+    }
+}
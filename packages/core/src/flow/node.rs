@@ -14,6 +14,10 @@ use super::{
     variable::VariableType,
 };
 
+pub mod validation;
+
+use validation::{ValidationContext, ValidationMessage};
+
 #[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
 pub enum NodeState {
     Idle,
@@ -609,6 +613,15 @@ pub trait NodeLogic: Send + Sync {
 
     async fn on_update(&self, _node: &mut Node, _board: Arc<Board>) {}
     async fn on_delete(&self, _node: &mut Node, _board: Arc<Board>) {}
+
+    /// Reports problems with the placed node that can be found before a run, e.g. unconnected
+    /// required pins or invalid static values. The default finds nothing.
+    async fn validate(
+        &self,
+        _context: &ValidationContext<'_>,
+    ) -> flow_like_types::Result<Vec<ValidationMessage>> {
+        Ok(vec![])
+    }
 }
 
 /// Utility for .on_update()
@@ -0,0 +1,110 @@
+use super::Node;
+use crate::flow::{board::Board, pin::PinType};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationSeverity {
+    Error,
+    Warning,
+}
+
+/// A problem with a placed node that can be detected before the board runs
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq, Eq)]
+pub struct ValidationMessage {
+    pub node_id: String,
+    /// The pin the message is about, `None` if it concerns the whole node
+    pub pin_id: Option<String>,
+    pub severity: ValidationSeverity,
+    pub message: String,
+}
+
+/// What `NodeLogic::validate` gets to look at: the placed node and the board around it.
+/// Connected pins only get their value at runtime, so checks on values are limited to
+/// the defaults of unconnected pins.
+pub struct ValidationContext<'a> {
+    pub node: &'a Node,
+    pub board: &'a Board,
+}
+
+impl<'a> ValidationContext<'a> {
+    pub fn new(node: &'a Node, board: &'a Board) -> Self {
+        ValidationContext { node, board }
+    }
+
+    pub fn is_connected(&self, pin_name: &str) -> bool {
+        self.node.get_pin_by_name(pin_name).is_some_and(|pin| {
+            let links = match pin.pin_type {
+                PinType::Input => &pin.depends_on,
+                PinType::Output => &pin.connected_to,
+            };
+            !links.is_empty()
+        })
+    }
+
+    /// The default value of an unconnected pin, `None` if the pin is connected, has no
+    /// default or the default does not deserialize into `T`
+    pub fn static_value<T: DeserializeOwned>(&self, pin_name: &str) -> Option<T> {
+        if self.is_connected(pin_name) {
+            return None;
+        }
+        let pin = self.node.get_pin_by_name(pin_name)?;
+        let bytes = pin.default_value.as_ref()?;
+        flow_like_types::json::from_slice(bytes).ok()
+    }
+
+    pub fn error(&self, pin_name: Option<&str>, message: impl Into<String>) -> ValidationMessage {
+        self.message(pin_name, ValidationSeverity::Error, message.into())
+    }
+
+    pub fn warning(&self, pin_name: Option<&str>, message: impl Into<String>) -> ValidationMessage {
+        self.message(pin_name, ValidationSeverity::Warning, message.into())
+    }
+
+    /// An error if the input pin is neither connected nor has a default value
+    pub fn require_input(&self, pin_name: &str) -> Option<ValidationMessage> {
+        let pin = self.node.get_pin_by_name(pin_name)?;
+        if self.is_connected(pin_name) || pin.default_value.is_some() {
+            return None;
+        }
+        Some(self.error(
+            Some(pin_name),
+            format!("Required pin '{}' is not connected", pin.friendly_name),
+        ))
+    }
+
+    /// An error if the static value of the pin is not one of its `valid_values`
+    pub fn check_valid_values(&self, pin_name: &str) -> Option<ValidationMessage> {
+        let pin = self.node.get_pin_by_name(pin_name)?;
+        let valid_values = pin.options.as_ref()?.valid_values.as_ref()?;
+        let value: String = self.static_value(pin_name)?;
+        if valid_values.contains(&value) {
+            return None;
+        }
+        Some(self.error(
+            Some(pin_name),
+            format!(
+                "'{}' is not allowed for '{}', expected one of: {}",
+                value,
+                pin.friendly_name,
+                valid_values.join(", ")
+            ),
+        ))
+    }
+
+    fn message(
+        &self,
+        pin_name: Option<&str>,
+        severity: ValidationSeverity,
+        message: String,
+    ) -> ValidationMessage {
+        ValidationMessage {
+            node_id: self.node.id.clone(),
+            pin_id: pin_name
+                .and_then(|name| self.node.get_pin_by_name(name))
+                .map(|pin| pin.id.clone()),
+            severity,
+            message,
+        }
+    }
+}
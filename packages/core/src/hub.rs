@@ -379,6 +379,35 @@ pub struct BitSearchQuery {
     pub limit: Option<u64>,
     pub offset: Option<u64>,
     pub bit_types: Option<Vec<BitTypes>>,
+    /// `next_cursor` of the previous page
+    #[serde(default)]
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+pub struct BitSearchPage {
+    pub items: Vec<Bit>,
+    pub next_cursor: Option<String>,
+}
+
+/// Hubs before cursor pagination answer with a plain list.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+enum BitSearchResponse {
+    Page(BitSearchPage),
+    List(Vec<Bit>),
+}
+
+impl From<BitSearchResponse> for BitSearchPage {
+    fn from(response: BitSearchResponse) -> Self {
+        match response {
+            BitSearchResponse::Page(page) => page,
+            BitSearchResponse::List(items) => BitSearchPage {
+                items,
+                next_cursor: None,
+            },
+        }
+    }
 }
 
 impl BitSearchQuery {
@@ -388,6 +417,7 @@ impl BitSearchQuery {
             limit: None,
             offset: None,
             bit_types: None,
+            cursor: None,
         }
     }
 
@@ -411,6 +441,11 @@ impl BitSearchQuery {
         self
     }
 
+    pub fn with_cursor(mut self, cursor: &str) -> Self {
+        self.cursor = Some(cursor.to_string());
+        self
+    }
+
     pub fn build(self) -> Self {
         self
     }
@@ -539,7 +574,8 @@ impl Hub {
         }
     }
 
-    pub async fn search_bit(&self, query: &BitSearchQuery) -> Result<Vec<Bit>> {
+    /// One page of the bits of this hub, without its dependency hubs.
+    pub async fn search_bit_page(&self, query: &BitSearchQuery) -> Result<BitSearchPage> {
         let type_bits_url = self.construct_url("api/v1/bit")?;
 
         let request = self
@@ -548,10 +584,15 @@ impl Hub {
             .post(type_bits_url)
             .json(query)
             .build()?;
-        let mut bits = self
+        let page = self
             .http_client()
-            .hashed_request::<Vec<Bit>>(request)
+            .hashed_request::<BitSearchResponse>(request)
             .await?;
+        Ok(page.into())
+    }
+
+    pub async fn search_bit(&self, query: &BitSearchQuery) -> Result<Vec<Bit>> {
+        let mut bits = self.search_bit_page(query).await?.items;
         let dependency_hubs = self.get_dependency_hubs().await?;
 
         for hub in dependency_hubs {
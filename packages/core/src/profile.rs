@@ -5,7 +5,7 @@ use std::{
 
 use crate::{
     bit::{Bit, BitModelPreference, BitTypes},
-    hub::{BitSearchQuery, Hub},
+    hub::{BitSearchPage, BitSearchQuery, Hub},
     utils::http::HTTPClient,
 };
 use flow_like_types::{Result, Value, anyhow, tokio::task};
@@ -297,6 +297,17 @@ impl Profile {
         Ok(bits)
     }
 
+    /// One page of the bits of the home hub of the profile. Cursors are specific to a hub,
+    /// so unlike [`Profile::search_bits`] the other hubs are not queried.
+    pub async fn search_bits_page(
+        &self,
+        query: &BitSearchQuery,
+        http_client: Arc<HTTPClient>,
+    ) -> Result<BitSearchPage> {
+        let hub = Hub::new(&self.hub, http_client).await?;
+        hub.search_bit_page(query).await
+    }
+
     pub async fn get_bit(
         &self,
         bit: String,
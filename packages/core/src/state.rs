@@ -13,7 +13,7 @@ use std::time::Instant;
 
 use crate::flow::event::Event;
 #[cfg(feature = "flow-runtime")]
-use crate::flow::execution::{LogMeta, log::LogMessage};
+use crate::flow::execution::{LogMeta, app_cache::AppCache, log::LogMessage};
 
 #[cfg(feature = "flow-runtime")]
 use crate::flow::board::Board;
@@ -375,6 +375,10 @@ pub struct FlowLikeState {
     pub widget_registry: Arc<DashMap<String, crate::a2ui::widget::Widget>>,
     #[cfg(feature = "flow-runtime")]
     pub page_registry: Arc<DashMap<String, crate::a2ui::widget::Page>>,
+
+    /// State that nodes keep across runs, see [`AppCache`]
+    #[cfg(feature = "flow-runtime")]
+    pub app_cache: Arc<AppCache>,
 }
 
 impl FlowLikeState {
@@ -405,6 +409,9 @@ impl FlowLikeState {
             widget_registry: Arc::new(DashMap::new()),
             #[cfg(feature = "flow-runtime")]
             page_registry: Arc::new(DashMap::new()),
+
+            #[cfg(feature = "flow-runtime")]
+            app_cache: Arc::new(AppCache::new()),
         }
     }
 
@@ -436,6 +443,9 @@ impl FlowLikeState {
             widget_registry: Arc::new(DashMap::new()),
             #[cfg(feature = "flow-runtime")]
             page_registry: Arc::new(DashMap::new()),
+
+            #[cfg(feature = "flow-runtime")]
+            app_cache: Arc::new(AppCache::new()),
         }
     }
 
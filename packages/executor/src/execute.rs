@@ -9,7 +9,7 @@ use crate::types::{EventType, ExecutionEvent, ExecutionRequest, ExecutionResult,
 use flow_like::credentials::StoreType;
 use flow_like::flow::board::Board;
 use flow_like::flow::event::Event;
-use flow_like::flow::execution::{InternalRun, RunPayload};
+use flow_like::flow::execution::{InternalRun, NodeErrorPolicy, NodeReport, RunPayload};
 use flow_like::flow::oauth::OAuthToken;
 use flow_like::profile::Profile;
 use flow_like::state::{FlowLikeConfig, FlowLikeState, FlowNodeRegistryInner};
@@ -249,6 +249,8 @@ pub async fn execute(
     if let Some(user_context) = request.user_context.clone() {
         run.set_user_context(user_context);
     }
+    run.set_error_policy(request.on_node_error);
+    run.set_deterministic(request.deterministic);
 
     // Execute with timeout
     let execution_result = tokio::time::timeout(config.execution_timeout(), async {
@@ -262,6 +264,8 @@ pub async fn execute(
     }
 
     let duration_ms = start.elapsed().as_millis() as u64;
+    let nodes = run.node_report();
+    let node_timings = run.node_timings();
 
     let (status, output, error) = match &execution_result {
         Ok(log_meta) => {
@@ -308,14 +312,24 @@ pub async fn execute(
                 );
             }
 
-            send_event(
-                &event_tx,
-                &sequence,
-                &claims.run_id,
-                EventType::Log,
-                serde_json::json!({ "message": "Execution completed" }),
-            );
-            (ExecutionStatus::Completed, None, None)
+            let (status, error) = settle_status(request.on_node_error, &nodes);
+            match &error {
+                Some(message) => send_event(
+                    &event_tx,
+                    &sequence,
+                    &claims.run_id,
+                    EventType::Error,
+                    serde_json::json!({ "message": message, "failed_nodes": nodes.failed }),
+                ),
+                None => send_event(
+                    &event_tx,
+                    &sequence,
+                    &claims.run_id,
+                    EventType::Log,
+                    serde_json::json!({ "message": "Execution completed" }),
+                ),
+            }
+            (status, None, error)
         }
         Err(_) => {
             send_event(
@@ -333,6 +347,14 @@ pub async fn execute(
         }
     };
 
+    send_event(
+        &event_tx,
+        &sequence,
+        &claims.run_id,
+        EventType::Custom("timings".to_string()),
+        serde_json::json!({ "nodes": node_timings }),
+    );
+
     // Signal completion to callback batcher
     drop(event_tx);
 
@@ -360,9 +382,31 @@ pub async fn execute(
         output,
         error,
         duration_ms,
+        succeeded_nodes: nodes.succeeded,
+        failed_nodes: nodes.failed,
+        skipped_nodes: nodes.skipped,
+        node_timings,
     })
 }
 
+/// Final status of a run that finished in time. Under `Abort` any failed node fails the run,
+/// the continue policies complete and only list the failures.
+pub(crate) fn settle_status(
+    policy: NodeErrorPolicy,
+    nodes: &NodeReport,
+) -> (ExecutionStatus, Option<String>) {
+    if policy == NodeErrorPolicy::Abort && !nodes.failed.is_empty() {
+        return (
+            ExecutionStatus::Failed,
+            Some(format!(
+                "Execution aborted after node failure: {}",
+                nodes.failed.join(", ")
+            )),
+        );
+    }
+    (ExecutionStatus::Completed, None)
+}
+
 fn string_to_event_type(s: &str) -> EventType {
     match s {
         "log" => EventType::Log,
@@ -556,3 +600,39 @@ async fn send_progress(
         config.callback_retries
     )))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(failed: &[&str]) -> NodeReport {
+        NodeReport {
+            succeeded: vec!["a".to_string()],
+            failed: failed.iter().map(|id| id.to_string()).collect(),
+            skipped: vec![],
+        }
+    }
+
+    #[test]
+    fn test_abort_fails_run_on_node_failure() {
+        let (status, error) = settle_status(NodeErrorPolicy::Abort, &report(&["b"]));
+        assert_eq!(status, ExecutionStatus::Failed);
+        assert!(error.unwrap().contains("b"));
+
+        let (status, error) = settle_status(NodeErrorPolicy::Abort, &report(&[]));
+        assert_eq!(status, ExecutionStatus::Completed);
+        assert!(error.is_none());
+    }
+
+    #[test]
+    fn test_continue_policies_complete_with_failures() {
+        for policy in [
+            NodeErrorPolicy::ContinueBranch,
+            NodeErrorPolicy::ContinueAll,
+        ] {
+            let (status, error) = settle_status(policy, &report(&["b"]));
+            assert_eq!(status, ExecutionStatus::Completed);
+            assert!(error.is_none());
+        }
+    }
+}
@@ -0,0 +1,122 @@
+//! Liveness and readiness state of the executor
+//!
+//! Liveness only says the process is serving requests. Readiness additionally requires the
+//! catalog to be initialized and every registered dependency check (e.g. Redis for the queue
+//! worker) to pass, so orchestrators stop routing executions to a pod that cannot run them.
+
+use futures_util::future::{join_all, BoxFuture};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+/// How long a single dependency check may take before it counts as failed
+pub const CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Name of the built-in catalog check in [`ReadinessReport::checks`]
+pub const CATALOG_CHECK: &str = "catalog";
+
+type CheckFn = Arc<dyn Fn() -> BoxFuture<'static, Result<(), String>> + Send + Sync>;
+
+/// Shared readiness state, cheap to clone
+#[derive(Clone, Default)]
+pub struct Readiness {
+    catalog_initialized: Arc<AtomicBool>,
+    checks: Arc<RwLock<Vec<(String, CheckFn)>>>,
+}
+
+/// Outcome of a single dependency check
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct DependencyStatus {
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Outcome of all readiness checks
+#[derive(Debug, Clone, Serialize)]
+pub struct ReadinessReport {
+    pub ready: bool,
+    pub checks: BTreeMap<String, DependencyStatus>,
+}
+
+impl Readiness {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Marks the catalog runtime (ONNX execution providers, etc.) as initialized
+    pub fn mark_catalog_initialized(&self) {
+        self.catalog_initialized.store(true, Ordering::Release);
+    }
+
+    pub fn is_catalog_initialized(&self) -> bool {
+        self.catalog_initialized.load(Ordering::Acquire)
+    }
+
+    /// Registers a dependency check that runs on every readiness probe.
+    /// A check that errors or exceeds [`CHECK_TIMEOUT`] makes the executor not ready.
+    pub fn add_check<F, Fut>(&self, name: impl Into<String>, check: F)
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), String>> + Send + 'static,
+    {
+        let check: CheckFn = Arc::new(move || Box::pin(check()));
+        self.checks
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .push((name.into(), check));
+    }
+
+    /// Runs all dependency checks concurrently
+    pub async fn check(&self) -> ReadinessReport {
+        let checks: Vec<(String, CheckFn)> = self
+            .checks
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+
+        let results = join_all(checks.into_iter().map(|(name, check)| async move {
+            let status = match tokio::time::timeout(CHECK_TIMEOUT, check()).await {
+                Ok(Ok(())) => DependencyStatus {
+                    ok: true,
+                    error: None,
+                },
+                Ok(Err(e)) => DependencyStatus {
+                    ok: false,
+                    error: Some(e),
+                },
+                Err(_) => DependencyStatus {
+                    ok: false,
+                    error: Some(format!("timed out after {:?}", CHECK_TIMEOUT)),
+                },
+            };
+            (name, status)
+        }))
+        .await;
+
+        let mut report = BTreeMap::new();
+        report.insert(
+            CATALOG_CHECK.to_string(),
+            if self.is_catalog_initialized() {
+                DependencyStatus {
+                    ok: true,
+                    error: None,
+                }
+            } else {
+                DependencyStatus {
+                    ok: false,
+                    error: Some("initializing".to_string()),
+                }
+            },
+        );
+        report.extend(results);
+
+        ReadinessReport {
+            ready: report.values().all(|status| status.ok),
+            checks: report,
+        }
+    }
+}
@@ -18,6 +18,14 @@
 //! Events are streamed directly back to the caller via NDJSON or SSE.
 //! Perfect for Lambda streaming responses or direct API calls.
 //!
+//! ## Deterministic Runs
+//!
+//! Independent branches of a board normally run concurrently, so the order of logs, events
+//! and side effects can differ between runs. Setting `deterministic` on the
+//! `ExecutionRequest` runs the ready nodes one at a time, sorted by node id, which makes the
+//! event sequence reproducible for the same inputs (useful for snapshot tests and debugging).
+//! This trades the throughput of parallel branches for reproducibility.
+//!
 //! ## Usage
 //!
 //! ```rust,ignore
@@ -31,6 +39,7 @@
 pub mod config;
 pub mod error;
 pub mod execute;
+pub mod health;
 pub mod jwt;
 pub mod router;
 pub mod streaming;
@@ -39,7 +48,9 @@ pub mod types;
 pub use config::ExecutorConfig;
 pub use error::ExecutorError;
 pub use execute::execute;
+pub use flow_like::flow::execution::{NodeErrorPolicy, NodeReport, NodeTiming};
 pub use flow_like_types::OAuthTokenInput;
+pub use health::Readiness;
 pub use router::{executor_router, ExecutorState};
 pub use streaming::{execute_streaming, ExecutionStream, StreamEvent};
 pub use types::{BoardVersion, ExecutionEvent, ExecutionRequest, ExecutionResult, ExecutionStatus};
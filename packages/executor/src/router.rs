@@ -4,12 +4,13 @@
 
 use crate::config::ExecutorConfig;
 use crate::execute::execute;
+use crate::health::Readiness;
 use crate::streaming::{event_to_ndjson, execute_streaming};
 use crate::types::ExecutionRequest;
 use axum::body::Body;
 use axum::extract::State;
 use axum::http::StatusCode;
-use axum::response::{Response, Sse};
+use axum::response::{IntoResponse, Response, Sse};
 use axum::routing::{get, post};
 use axum::{Json, Router};
 use futures_util::stream::StreamExt;
@@ -21,11 +22,15 @@ use std::sync::Arc;
 #[derive(Clone)]
 pub struct ExecutorState {
     pub config: ExecutorConfig,
+    pub readiness: Readiness,
 }
 
 impl ExecutorState {
     pub fn new(config: ExecutorConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            readiness: Readiness::new(),
+        }
     }
 
     pub fn from_env() -> Self {
@@ -48,6 +53,8 @@ pub fn executor_router(state: ExecutorState) -> Router {
         .route("/execute/stream", post(execute_stream))
         .route("/execute/sse", post(execute_sse))
         .route("/health", get(health_check))
+        .route("/health/live", get(liveness))
+        .route("/health/ready", get(readiness))
         .with_state(Arc::new(state))
 }
 
@@ -60,6 +67,30 @@ async fn health_check() -> Json<HealthResponse> {
     })
 }
 
+/// Liveness probe, succeeds as long as the process serves requests
+///
+/// GET /health/live
+async fn liveness() -> Json<HealthResponse> {
+    Json(HealthResponse {
+        status: "alive".to_string(),
+        service: "flow-like-executor".to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+    })
+}
+
+/// Readiness probe, 503 until the catalog is initialized and all dependency checks pass
+///
+/// GET /health/ready
+async fn readiness(State(state): State<Arc<ExecutorState>>) -> Response {
+    let report = state.readiness.check().await;
+    let status = if report.ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(report)).into_response()
+}
+
 /// Execute with callback-based progress reporting
 ///
 /// POST /execute
@@ -150,3 +181,81 @@ async fn execute_sse(
             .text("ping"),
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::Request;
+    use tower::ServiceExt;
+
+    fn state() -> ExecutorState {
+        ExecutorState::new(ExecutorConfig::default())
+    }
+
+    async fn status(app: Router, path: &str) -> StatusCode {
+        app.oneshot(Request::get(path).body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[tokio::test]
+    async fn test_readiness_waits_for_catalog() {
+        let state = state();
+        let readiness = state.readiness.clone();
+        let app = executor_router(state);
+
+        assert_eq!(
+            status(app.clone(), "/health/ready").await,
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+        readiness.mark_catalog_initialized();
+        assert_eq!(status(app, "/health/ready").await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_readiness_fails_on_unreachable_dependency() {
+        let state = state();
+        state.readiness.mark_catalog_initialized();
+        state
+            .readiness
+            .add_check("redis", || async { Err("connection refused".to_string()) });
+        let report = state.readiness.check().await;
+        let app = executor_router(state);
+
+        assert!(!report.ready);
+        assert!(report.checks["catalog"].ok);
+        assert_eq!(
+            report.checks["redis"].error.as_deref(),
+            Some("connection refused")
+        );
+        assert_eq!(
+            status(app, "/health/ready").await,
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_readiness_times_out_slow_dependency() {
+        let state = state();
+        state.readiness.mark_catalog_initialized();
+        state.readiness.add_check("redis", || async {
+            tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+            Ok(())
+        });
+
+        assert!(!state.readiness.check().await.ready);
+    }
+
+    #[tokio::test]
+    async fn test_liveness_is_independent_of_readiness() {
+        let state = state();
+        state
+            .readiness
+            .add_check("redis", || async { Err("connection refused".to_string()) });
+        let app = executor_router(state);
+
+        assert_eq!(status(app.clone(), "/health/live").await, StatusCode::OK);
+        assert_eq!(status(app, "/health").await, StatusCode::OK);
+    }
+}
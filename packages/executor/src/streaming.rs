@@ -5,12 +5,13 @@
 
 use crate::config::{model_provider_config_from_env, ExecutorConfig};
 use crate::error::ExecutorError;
+use crate::execute::settle_status;
 use crate::jwt::verify_jwt_async;
 use crate::types::{ExecutionRequest, ExecutionStatus};
 use flow_like::credentials::StoreType;
 use flow_like::flow::board::Board;
 use flow_like::flow::event::Event;
-use flow_like::flow::execution::{InternalRun, RunPayload};
+use flow_like::flow::execution::{InternalRun, NodeTiming, RunPayload};
 use flow_like::flow::oauth::OAuthToken;
 use flow_like::profile::Profile;
 use flow_like::state::{FlowLikeConfig, FlowLikeState, FlowNodeRegistryInner};
@@ -19,7 +20,7 @@ use flow_like_catalog::get_catalog;
 use flow_like_storage::Path;
 use flow_like_types::intercom::{BufferedInterComHandler, InterComEvent};
 use futures_util::Stream;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
@@ -59,6 +60,14 @@ pub fn completed_event(
     )
 }
 
+/// Per node timings of a run, sent right before the `completed` event
+pub fn timings_event(run_id: &str, timings: &BTreeMap<String, NodeTiming>) -> StreamEvent {
+    InterComEvent::with_type(
+        "timings",
+        serde_json::json!({ "run_id": run_id, "nodes": timings }),
+    )
+}
+
 pub fn error_event(message: &str) -> StreamEvent {
     InterComEvent::with_type("error", serde_json::json!({ "message": message }))
 }
@@ -296,6 +305,8 @@ async fn execute_inner(
     if let Some(user_context) = request.user_context.clone() {
         run.set_user_context(user_context);
     }
+    run.set_error_policy(request.on_node_error);
+    run.set_deterministic(request.deterministic);
 
     let execution_result = tokio::time::timeout(config.execution_timeout(), async {
         run.execute(state.clone()).await
@@ -307,7 +318,7 @@ async fn execute_inner(
     let _ = intercom_handler.flush().await;
     tracing::debug!("Intercom flush completed");
 
-    match execution_result {
+    let outcome = match execution_result {
         Ok(log_meta) => {
             let log_level = log_meta.as_ref().map(|m| m.log_level);
 
@@ -339,12 +350,21 @@ async fn execute_inner(
                 }
             }
 
-            emit_event(
-                tx,
-                "log",
-                serde_json::json!({ "message": "Execution completed" }),
-            );
-            Ok((ExecutionStatus::Completed, log_level, None, None))
+            let nodes = run.node_report();
+            let (status, error) = settle_status(request.on_node_error, &nodes);
+            match &error {
+                Some(message) => emit_event(
+                    tx,
+                    "error",
+                    serde_json::json!({ "message": message, "nodes": nodes }),
+                ),
+                None => emit_event(
+                    tx,
+                    "log",
+                    serde_json::json!({ "message": "Execution completed", "nodes": nodes }),
+                ),
+            }
+            Ok((status, log_level, None, error))
         }
         Err(_) => {
             emit_event(
@@ -359,7 +379,10 @@ async fn execute_inner(
                 Some("Execution timeout".to_string()),
             ))
         }
-    }
+    };
+
+    let _ = tx.send(timings_event(run_id, &run.node_timings()));
+    outcome
 }
 
 fn emit_event(
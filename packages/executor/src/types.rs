@@ -1,9 +1,9 @@
 use flow_like::credentials::SharedCredentials;
-use flow_like::flow::execution::UserExecutionContext;
+use flow_like::flow::execution::{NodeErrorPolicy, NodeTiming, UserExecutionContext};
 use flow_like::flow::variable::Variable;
 use flow_like_types::OAuthTokenInput;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// Board version as a tuple (major, minor, patch)
 pub type BoardVersion = (u32, u32, u32);
@@ -46,6 +46,13 @@ pub struct ExecutionRequest {
     /// User profile (bits, hubs, settings) - pre-filtered for cloud deployments
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub profile: Option<serde_json::Value>,
+    /// How a failing node affects the rest of the board (defaults to `ContinueAll`)
+    #[serde(default)]
+    pub on_node_error: NodeErrorPolicy,
+    /// Run ready nodes one at a time in a stable order (by node id) instead of concurrently,
+    /// so the emitted events are reproducible. Slower for boards with parallel branches.
+    #[serde(default)]
+    pub deterministic: bool,
 }
 
 /// Result of an execution
@@ -63,6 +70,18 @@ pub struct ExecutionResult {
     pub error: Option<String>,
     /// Execution duration in milliseconds
     pub duration_ms: u64,
+    /// Nodes that executed successfully
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub succeeded_nodes: Vec<String>,
+    /// Nodes that failed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub failed_nodes: Vec<String>,
+    /// Nodes downstream of a failure that were never executed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped_nodes: Vec<String>,
+    /// Duration, wait time and outcomes per executed node, keyed by node id
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub node_timings: BTreeMap<String, NodeTiming>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
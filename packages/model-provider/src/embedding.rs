@@ -57,4 +57,9 @@ pub trait EmbeddingModelLogic: Send + Sync + Cacheable + 'static {
     async fn text_embed_query(&self, texts: &Vec<String>) -> Result<Vec<Vec<f32>>>;
     async fn text_embed_document(&self, texts: &Vec<String>) -> Result<Vec<Vec<f32>>>;
     fn as_cacheable(&self) -> Arc<dyn Cacheable>;
+
+    /// Most texts the provider accepts in a single request, `None` if it has no known limit
+    fn max_batch_size(&self) -> Option<usize> {
+        None
+    }
 }
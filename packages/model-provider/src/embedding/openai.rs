@@ -153,6 +153,11 @@ impl EmbeddingModelLogic for OpenAIEmbeddingModel {
     fn as_cacheable(&self) -> Arc<dyn Cacheable> {
         Arc::new(self.clone())
     }
+
+    /// The embeddings endpoint rejects requests with more than 2048 inputs
+    fn max_batch_size(&self) -> Option<usize> {
+        Some(2048)
+    }
 }
 
 #[cfg(test)]
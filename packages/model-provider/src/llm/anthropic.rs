@@ -1,12 +1,63 @@
-use std::any::Any;
+use std::{any::Any, sync::Arc};
 
-use super::{ModelLogic, extract_headers};
+use super::{LLMCallback, ModelLogic, extract_headers};
 use crate::provider::random_provider;
 use crate::{
+    history::{History, Role},
     llm::ModelConstructor,
     provider::{ModelProvider, ModelProviderConfiguration},
+    response::Response,
 };
-use flow_like_types::{Cacheable, Result, async_trait};
+use flow_like_types::json::{Map, json};
+use flow_like_types::{Cacheable, Result, Value, anyhow, async_trait};
+use rig::completion::CompletionModel;
+
+/// Used if neither the provider nor the history names a model.
+pub const DEFAULT_MODEL: &str = "claude-sonnet-4-5";
+
+/// Maps model ids used by routers and older configurations to Anthropic API ids,
+/// e.g. `anthropic/claude-3.5-sonnet` to `claude-3-5-sonnet-latest`.
+pub fn resolve_model_id(model_id: &str) -> String {
+    let model_id = model_id.trim();
+    let model_id = model_id
+        .strip_prefix("anthropic/")
+        .or_else(|| model_id.strip_prefix("anthropic:"))
+        .unwrap_or(model_id);
+
+    if model_id.is_empty() {
+        return DEFAULT_MODEL.to_string();
+    }
+
+    let model_id = model_id.to_lowercase().replace('.', "-");
+    let dated = model_id
+        .rsplit('-')
+        .next()
+        .is_some_and(|suffix| suffix.len() == 8 && suffix.chars().all(|c| c.is_ascii_digit()));
+
+    match model_id.as_str() {
+        "claude-sonnet-4" | "claude-opus-4" => format!("{}-0", model_id),
+        id if id.starts_with("claude-3") && !dated && !id.ends_with("-latest") => {
+            format!("{}-latest", model_id)
+        }
+        _ => model_id,
+    }
+}
+
+/// The messages API rejects requests without `max_tokens`, so every request gets the model's output limit.
+pub fn default_max_tokens(model_id: &str) -> u32 {
+    if model_id.starts_with("claude-opus-4") {
+        32_000
+    } else if model_id.starts_with("claude-sonnet-4")
+        || model_id.starts_with("claude-haiku-4")
+        || model_id.starts_with("claude-3-7-sonnet")
+    {
+        64_000
+    } else if model_id.starts_with("claude-3-5") {
+        8_192
+    } else {
+        4_096
+    }
+}
 
 pub struct AnthropicModel {
     client: rig::providers::anthropic::Client,
@@ -41,7 +92,7 @@ impl AnthropicModel {
         Ok(AnthropicModel {
             client,
             provider: provider.clone(),
-            default_model: model_id,
+            default_model: model_id.map(|id| resolve_model_id(&id)),
         })
     }
 
@@ -75,7 +126,7 @@ impl AnthropicModel {
 
         Ok(AnthropicModel {
             client,
-            default_model: model_id,
+            default_model: model_id.map(|id| resolve_model_id(&id)),
             provider: provider.clone(),
         })
     }
@@ -103,4 +154,285 @@ impl ModelLogic for AnthropicModel {
     async fn default_model(&self) -> Option<String> {
         self.default_model.clone()
     }
+
+    #[allow(deprecated)]
+    async fn invoke(&self, history: &History, lambda: Option<LLMCallback>) -> Result<Response> {
+        use crate::llm::{CompletionModelHandle, invoke_with_stream, invoke_without_stream};
+
+        let model_name = self
+            .default_model()
+            .await
+            .unwrap_or_else(|| resolve_model_id(&history.model));
+
+        let constructor = self.provider().await?;
+        let completion_model = constructor.inner.completion_model(&model_name);
+        let completion_handle = CompletionModelHandle::new(Arc::from(completion_model));
+
+        // The messages API takes the system prompt as a top-level block, not as a message
+        let mut history = history.clone();
+        let system_prompt = take_system_prompt(&mut history);
+
+        let (prompt, chat_history) = history
+            .extract_prompt_and_history()
+            .map_err(|e| anyhow!("Failed to convert history into rig messages: {e}"))?;
+
+        let max_tokens = history
+            .max_completion_tokens
+            .unwrap_or_else(|| default_max_tokens(&model_name));
+
+        let mut builder = completion_handle
+            .completion_request(prompt)
+            .messages(chat_history)
+            .max_tokens(max_tokens as u64);
+
+        if let Some(system_prompt) = system_prompt {
+            builder = builder.preamble(system_prompt);
+        }
+
+        if let Some(temp) = history.temperature {
+            builder = builder.temperature(temp as f64);
+        }
+
+        if history.tools.is_some() {
+            let tool_definitions = history.tools_to_rig()?;
+            if !tool_definitions.is_empty() {
+                builder = builder.tools(tool_definitions);
+            }
+        }
+
+        if let Some(choice) = history.tool_choice_to_rig() {
+            builder = builder.tool_choice(choice);
+        }
+
+        let additional_params = self.additional_params(&Some(history));
+
+        if let Some(callback) = lambda {
+            invoke_with_stream(builder, callback, &model_name, additional_params).await
+        } else {
+            invoke_without_stream(builder, &model_name, additional_params).await
+        }
+    }
+
+    /// Only forwards parameters the messages API accepts, unknown fields are rejected.
+    fn additional_params(&self, history: &Option<History>) -> Option<flow_like_types::Value> {
+        let history = history.as_ref()?;
+        let mut params = Map::new();
+
+        if let Some(top_p) = history.top_p {
+            params.insert("top_p".to_string(), json!(top_p));
+        }
+
+        if let Some(stop) = history.stop.as_ref().filter(|stop| !stop.is_empty()) {
+            params.insert("stop_sequences".to_string(), json!(stop));
+        }
+
+        if let Some(user) = history.user.as_ref() {
+            params.insert("metadata".to_string(), json!({ "user_id": user }));
+        }
+
+        (!params.is_empty()).then_some(Value::Object(params))
+    }
+}
+
+/// Removes all system messages from the history and joins them into one system prompt.
+fn take_system_prompt(history: &mut History) -> Option<String> {
+    let (system, messages): (Vec<_>, Vec<_>) = history
+        .messages
+        .drain(..)
+        .partition(|message| message.role == Role::System);
+    history.messages = messages;
+
+    let prompt = system
+        .iter()
+        .map(|message| message.as_str())
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+    (!prompt.is_empty()).then_some(prompt)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{history::HistoryMessage, response_chunk::ResponseChunk};
+    use flow_like_types::{
+        json::from_slice,
+        sync::Mutex,
+        tokio::{
+            self,
+            io::{AsyncReadExt, AsyncWriteExt},
+            net::TcpListener,
+        },
+    };
+    use std::collections::HashMap;
+
+    const STREAM: &[(&str, &str)] = &[
+        (
+            "message_start",
+            r#"{"type":"message_start","message":{"id":"msg_1","type":"message","role":"assistant","content":[],"model":"claude-sonnet-4-5","stop_reason":null,"stop_sequence":null,"usage":{"input_tokens":12,"output_tokens":1}}}"#,
+        ),
+        (
+            "content_block_start",
+            r#"{"type":"content_block_start","index":0,"content_block":{"type":"text","text":""}}"#,
+        ),
+        (
+            "content_block_delta",
+            r#"{"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Hello"}}"#,
+        ),
+        (
+            "content_block_delta",
+            r#"{"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":" world"}}"#,
+        ),
+        (
+            "content_block_stop",
+            r#"{"type":"content_block_stop","index":0}"#,
+        ),
+        (
+            "message_delta",
+            r#"{"type":"message_delta","delta":{"stop_reason":"end_turn","stop_sequence":null},"usage":{"output_tokens":5}}"#,
+        ),
+        ("message_stop", r#"{"type":"message_stop"}"#),
+    ];
+
+    struct CapturedRequest {
+        head: String,
+        body: Value,
+    }
+
+    /// Serves a single canned SSE response and captures the request it received.
+    async fn mock_server() -> (String, Arc<Mutex<Option<CapturedRequest>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let captured = Arc::new(Mutex::new(None));
+        let slot = captured.clone();
+
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buffer = Vec::new();
+            let mut chunk = [0u8; 4096];
+
+            let head_end = loop {
+                let read = socket.read(&mut chunk).await.unwrap();
+                buffer.extend_from_slice(&chunk[..read]);
+                if let Some(position) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+                    break position + 4;
+                }
+            };
+
+            let head = String::from_utf8_lossy(&buffer[..head_end]).to_lowercase();
+            let content_length = head
+                .lines()
+                .find_map(|line| line.strip_prefix("content-length:"))
+                .and_then(|length| length.trim().parse::<usize>().ok())
+                .unwrap_or(0);
+
+            while buffer.len() < head_end + content_length {
+                let read = socket.read(&mut chunk).await.unwrap();
+                buffer.extend_from_slice(&chunk[..read]);
+            }
+
+            *slot.lock().await = Some(CapturedRequest {
+                head,
+                body: from_slice(&buffer[head_end..head_end + content_length]).unwrap(),
+            });
+
+            let events: String = STREAM
+                .iter()
+                .map(|(event, data)| format!("event: {}\ndata: {}\n\n", event, data))
+                .collect();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\nconnection: close\r\n\r\n{}",
+                events
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+            socket.shutdown().await.unwrap();
+        });
+
+        (endpoint, captured)
+    }
+
+    #[test]
+    fn test_resolve_model_id() {
+        assert_eq!(resolve_model_id(""), DEFAULT_MODEL);
+        assert_eq!(
+            resolve_model_id("anthropic/claude-sonnet-4.5"),
+            "claude-sonnet-4-5"
+        );
+        assert_eq!(
+            resolve_model_id("anthropic/claude-3.5-sonnet"),
+            "claude-3-5-sonnet-latest"
+        );
+        assert_eq!(
+            resolve_model_id("claude-3-5-sonnet-20241022"),
+            "claude-3-5-sonnet-20241022"
+        );
+        assert_eq!(
+            resolve_model_id("anthropic/claude-opus-4"),
+            "claude-opus-4-0"
+        );
+        assert_eq!(resolve_model_id("claude-haiku-4-5"), "claude-haiku-4-5");
+    }
+
+    #[test]
+    fn test_default_max_tokens() {
+        assert_eq!(default_max_tokens("claude-opus-4-1"), 32_000);
+        assert_eq!(default_max_tokens("claude-sonnet-4-5"), 64_000);
+        assert_eq!(default_max_tokens("claude-3-5-haiku-latest"), 8_192);
+        assert_eq!(default_max_tokens("claude-3-haiku-20240307"), 4_096);
+    }
+
+    #[tokio::test]
+    async fn test_streaming_request_shape_and_chunks() {
+        let (endpoint, captured) = mock_server().await;
+
+        let mut params = HashMap::new();
+        params.insert("api_key".to_string(), json!("test-key"));
+        params.insert("endpoint".to_string(), json!(endpoint));
+        params.insert("model_id".to_string(), json!("anthropic/claude-sonnet-4.5"));
+        let provider = ModelProvider {
+            provider_name: "custom:anthropic".to_string(),
+            model_id: None,
+            version: None,
+            params: Some(params),
+        };
+        let model = AnthropicModel::from_provider(&provider).await.unwrap();
+
+        let mut history = History::new(
+            "ignored".to_string(),
+            vec![
+                HistoryMessage::from_string(Role::System, "You are terse."),
+                HistoryMessage::from_string(Role::User, "Say hello"),
+            ],
+        );
+        history.stop = Some(vec!["END".to_string()]);
+
+        let chunks = Arc::new(Mutex::new(Vec::new()));
+        let collected = chunks.clone();
+        let callback: LLMCallback = Arc::new(move |chunk: ResponseChunk| {
+            let collected = collected.clone();
+            Box::pin(async move {
+                collected.lock().await.push(chunk);
+                Ok(())
+            })
+        });
+
+        let response = model.invoke(&history, Some(callback)).await.unwrap();
+        assert_eq!(response.content().as_deref(), Some("Hello world"));
+        assert!(chunks.lock().await.len() >= 2);
+
+        let request = captured.lock().await.take().unwrap();
+        assert!(request.head.starts_with("post /v1/messages"));
+        assert!(request.head.contains("x-api-key: test-key"));
+        assert!(request.head.contains("anthropic-version:"));
+
+        let body = request.body;
+        assert_eq!(body["model"], json!("claude-sonnet-4-5"));
+        assert_eq!(body["system"], json!("You are terse."));
+        assert_eq!(body["max_tokens"], json!(64_000));
+        assert_eq!(body["stream"], json!(true));
+        assert_eq!(body["stop_sequences"], json!(["END"]));
+        assert_eq!(body["messages"].as_array().unwrap().len(), 1);
+        assert_eq!(body["messages"][0]["role"], json!("user"));
+    }
 }
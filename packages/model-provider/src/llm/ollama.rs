@@ -1,15 +1,324 @@
 use std::any::Any;
 
-use super::{ModelLogic, extract_headers};
+use super::{LLMCallback, ModelLogic, extract_headers};
 use crate::provider::random_provider;
 use crate::{
+    history::{Content, History, HistoryMessage, MessageContent, ResponseFormat, Role},
     llm::ModelConstructor,
     provider::{ModelProvider, ModelProviderConfiguration},
+    response::{Response, Usage},
+    response_chunk::{
+        Delta, DeltaFunctionCall, DeltaResponseFunction, ResponseChunk, ResponseChunkChoice,
+    },
 };
-use flow_like_types::{Cacheable, Result, async_trait};
+use flow_like_types::{
+    Cacheable, Result, Value, anyhow, async_trait, create_id,
+    json::{self, Map, json},
+    reqwest::{self, StatusCode},
+};
+use futures::StreamExt;
+use http::HeaderMap;
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_ENDPOINT: &str = "http://localhost:11434";
+
+/// A model installed on the Ollama server, as listed by `/api/tags`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct OllamaModelInfo {
+    pub name: String,
+    #[serde(default)]
+    pub model: String,
+    #[serde(default)]
+    pub size: u64,
+    #[serde(default)]
+    pub modified_at: Option<String>,
+    #[serde(default)]
+    pub details: Option<Value>,
+}
+
+#[derive(Deserialize)]
+struct OllamaTags {
+    #[serde(default)]
+    models: Vec<OllamaModelInfo>,
+}
+
+/// One line of the `/api/chat` NDJSON stream. Non-streaming requests return a single
+/// object of the same shape with `done` set.
+#[derive(Deserialize, Debug, Default)]
+struct OllamaChatChunk {
+    #[serde(default)]
+    message: Option<OllamaMessage>,
+    #[serde(default)]
+    done: bool,
+    #[serde(default)]
+    done_reason: Option<String>,
+    #[serde(default)]
+    prompt_eval_count: Option<u32>,
+    #[serde(default)]
+    eval_count: Option<u32>,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+struct OllamaMessage {
+    #[serde(default)]
+    content: String,
+    #[serde(default)]
+    thinking: Option<String>,
+    #[serde(default)]
+    tool_calls: Vec<OllamaToolCall>,
+}
+
+#[derive(Deserialize, Debug)]
+struct OllamaToolCall {
+    #[serde(default)]
+    id: Option<String>,
+    function: OllamaFunction,
+}
+
+#[derive(Deserialize, Debug)]
+struct OllamaFunction {
+    name: String,
+    #[serde(default)]
+    arguments: Value,
+}
+
+impl OllamaChatChunk {
+    /// Returns `None` for keep-alive lines that carry neither content nor the final stats.
+    fn into_response_chunk(self, model_name: &str) -> Option<ResponseChunk> {
+        let message = self.message.unwrap_or_default();
+        let tool_calls: Vec<DeltaFunctionCall> = message
+            .tool_calls
+            .into_iter()
+            .map(|call| DeltaFunctionCall {
+                index: None,
+                id: Some(call.id.unwrap_or_else(create_id)),
+                tool_type: Some("function".to_string()),
+                function: DeltaResponseFunction {
+                    name: Some(call.function.name),
+                    arguments: Some(json::to_string(&call.function.arguments).unwrap_or_default()),
+                },
+            })
+            .collect();
+
+        let delta = Delta {
+            role: Some("assistant".to_string()),
+            content: (!message.content.is_empty()).then_some(message.content),
+            tool_calls: (!tool_calls.is_empty()).then_some(tool_calls),
+            refusal: None,
+            reasoning: message.thinking.filter(|thinking| !thinking.is_empty()),
+        };
+        let has_delta =
+            delta.content.is_some() || delta.tool_calls.is_some() || delta.reasoning.is_some();
+
+        if !has_delta && !self.done {
+            return None;
+        }
+
+        let mut chunk = ResponseChunk {
+            model: Some(model_name.to_string()),
+            ..Default::default()
+        };
+        chunk.choices.push(ResponseChunkChoice {
+            index: 0,
+            delta: has_delta.then_some(delta),
+            finish_reason: self
+                .done
+                .then(|| self.done_reason.unwrap_or_else(|| "stop".to_string())),
+            logprobs: None,
+        });
+
+        if self.done {
+            let prompt_tokens = self.prompt_eval_count.unwrap_or_default();
+            let completion_tokens = self.eval_count.unwrap_or_default();
+            chunk.usage = Some(Usage {
+                prompt_tokens,
+                completion_tokens,
+                total_tokens: prompt_tokens + completion_tokens,
+                ..Default::default()
+            });
+        }
+
+        Some(chunk)
+    }
+}
+
+/// Ollama answers with 404 and "model ... not found" if the model was never pulled.
+fn ollama_error(
+    model_name: &str,
+    status: Option<StatusCode>,
+    message: &str,
+) -> flow_like_types::Error {
+    let not_pulled = message.contains("not found")
+        && (status == Some(StatusCode::NOT_FOUND) || message.contains("pull"));
+
+    if not_pulled {
+        return anyhow!(
+            "Ollama model '{}' is not available locally. Download it with `ollama pull {}`",
+            model_name,
+            model_name
+        );
+    }
+
+    match status {
+        Some(status) => anyhow!("Ollama request failed ({}): {}", status, message),
+        None => anyhow!("Ollama error: {}", message),
+    }
+}
+
+fn role_name(role: &Role) -> &'static str {
+    match role {
+        Role::System => "system",
+        Role::User => "user",
+        Role::Assistant => "assistant",
+        Role::Function | Role::Tool => "tool",
+    }
+}
+
+/// Ollama only accepts raw base64 images, so remote image URLs are dropped.
+fn message_images(content: &MessageContent) -> Vec<&str> {
+    let MessageContent::Contents(contents) = content else {
+        return vec![];
+    };
+
+    contents
+        .iter()
+        .filter_map(|content| match content {
+            Content::Image { image_url, .. } => image_url
+                .url
+                .strip_prefix("data:")
+                .and_then(|data| data.split_once("base64,"))
+                .map(|(_, payload)| payload),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Ollama expects tool call arguments as an object instead of a JSON string.
+fn message_tool_calls(message: &HistoryMessage) -> Vec<Value> {
+    let Some(tool_calls) = &message.tool_calls else {
+        return vec![];
+    };
+
+    tool_calls
+        .iter()
+        .map(|call| {
+            let arguments = json::from_str::<Value>(&call.function.arguments)
+                .unwrap_or_else(|_| json!(call.function.arguments));
+            json!({ "function": { "name": call.function.name, "arguments": arguments } })
+        })
+        .collect()
+}
+
+fn response_format(format: &ResponseFormat) -> Option<Value> {
+    match format {
+        ResponseFormat::String(format) if format == "json" || format == "json_object" => {
+            Some(json!("json"))
+        }
+        ResponseFormat::String(_) => None,
+        ResponseFormat::Object(format) => match format.get("type").and_then(Value::as_str) {
+            Some("json_object") => Some(json!("json")),
+            Some("json_schema") => format.pointer("/json_schema/schema").cloned(),
+            _ => Some(format.clone()),
+        },
+    }
+}
+
+fn chat_request(history: &History, model_name: &str, stream: bool) -> Value {
+    let messages: Vec<Value> = history
+        .messages
+        .iter()
+        .map(|message| {
+            let mut entry = Map::new();
+            entry.insert("role".to_string(), json!(role_name(&message.role)));
+            entry.insert("content".to_string(), json!(message.as_str()));
+
+            let images = message_images(&message.content);
+            if !images.is_empty() {
+                entry.insert("images".to_string(), json!(images));
+            }
+
+            let tool_calls = message_tool_calls(message);
+            if !tool_calls.is_empty() {
+                entry.insert("tool_calls".to_string(), json!(tool_calls));
+            }
+
+            if matches!(message.role, Role::Tool | Role::Function)
+                && let Some(name) = &message.name
+            {
+                entry.insert("tool_name".to_string(), json!(name));
+            }
+
+            Value::Object(entry)
+        })
+        .collect();
+
+    let mut options = Map::new();
+    if let Some(temperature) = history.temperature {
+        options.insert("temperature".to_string(), json!(temperature));
+    }
+    if let Some(top_p) = history.top_p {
+        options.insert("top_p".to_string(), json!(top_p));
+    }
+    if let Some(seed) = history.seed {
+        options.insert("seed".to_string(), json!(seed));
+    }
+    if let Some(max_tokens) = history.max_completion_tokens {
+        options.insert("num_predict".to_string(), json!(max_tokens));
+    }
+    if let Some(stop) = history.stop.as_ref().filter(|stop| !stop.is_empty()) {
+        options.insert("stop".to_string(), json!(stop));
+    }
+    if let Some(presence_penalty) = history.presence_penalty {
+        options.insert("presence_penalty".to_string(), json!(presence_penalty));
+    }
+    if let Some(frequency_penalty) = history.frequency_penalty {
+        options.insert("frequency_penalty".to_string(), json!(frequency_penalty));
+    }
+
+    let mut request = json!({
+        "model": model_name,
+        "messages": messages,
+        "stream": stream,
+    });
+
+    if !options.is_empty() {
+        request["options"] = Value::Object(options);
+    }
+
+    if let Some(tools) = history.tools.as_ref().filter(|tools| !tools.is_empty()) {
+        request["tools"] = json!(tools);
+    }
+
+    if let Some(format) = history.response_format.as_ref().and_then(response_format) {
+        request["format"] = format;
+    }
+
+    request
+}
+
+/// Parses one NDJSON line, surfacing errors the server reports mid-stream.
+fn parse_line(line: &[u8], model_name: &str) -> Result<Option<ResponseChunk>> {
+    let line = line.trim_ascii();
+    if line.is_empty() {
+        return Ok(None);
+    }
+
+    let chunk: OllamaChatChunk =
+        json::from_slice(line).map_err(|e| anyhow!("Invalid Ollama stream chunk: {e}"))?;
+    if let Some(error) = &chunk.error {
+        return Err(ollama_error(model_name, None, error));
+    }
+
+    Ok(chunk.into_response_chunk(model_name))
+}
 
 pub struct OllamaModel {
     client: rig::providers::ollama::Client,
+    http: reqwest::Client,
+    endpoint: String,
+    headers: HeaderMap,
     provider: ModelProvider,
     default_model: Option<String>,
 }
@@ -24,7 +333,7 @@ impl OllamaModel {
         let endpoint = ollama_config
             .endpoint
             .clone()
-            .unwrap_or_else(|| "http://localhost:11434".to_string());
+            .unwrap_or_else(|| DEFAULT_ENDPOINT.to_string());
 
         let mut builder = rig::providers::ollama::Client::builder().api_key(rig::client::Nothing);
         builder = builder.base_url(&endpoint);
@@ -33,6 +342,9 @@ impl OllamaModel {
 
         Ok(OllamaModel {
             client,
+            http: reqwest::Client::new(),
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            headers: HeaderMap::new(),
             provider: provider.clone(),
             default_model: model_id,
         })
@@ -47,23 +359,69 @@ impl OllamaModel {
         let endpoint = params
             .get("endpoint")
             .and_then(|v| v.as_str())
-            .unwrap_or("http://localhost:11434");
+            .unwrap_or(DEFAULT_ENDPOINT);
         let custom_headers = extract_headers(&params);
 
         let mut builder = rig::providers::ollama::Client::builder().api_key(rig::client::Nothing);
         builder = builder.base_url(endpoint);
         if !custom_headers.is_empty() {
-            builder = builder.http_headers(custom_headers);
+            builder = builder.http_headers(custom_headers.clone());
         }
 
         let client = builder.build()?;
 
         Ok(OllamaModel {
             client,
+            http: reqwest::Client::new(),
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            headers: custom_headers,
             default_model: model_id,
             provider: provider.clone(),
         })
     }
+
+    /// Lists the models that are pulled on the server.
+    pub async fn list_models(&self) -> Result<Vec<OllamaModelInfo>> {
+        let response = self
+            .http
+            .get(format!("{}/api/tags", self.endpoint))
+            .headers(self.headers.clone())
+            .send()
+            .await
+            .map_err(|e| anyhow!("Failed to reach Ollama at {}: {e}", self.endpoint))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow!("Ollama request failed ({}): {}", status, body));
+        }
+
+        let tags: OllamaTags = response.json().await?;
+        Ok(tags.models)
+    }
+
+    async fn send_chat(&self, body: &Value, model_name: &str) -> Result<reqwest::Response> {
+        let response = self
+            .http
+            .post(format!("{}/api/chat", self.endpoint))
+            .headers(self.headers.clone())
+            .json(body)
+            .send()
+            .await
+            .map_err(|e| anyhow!("Failed to reach Ollama at {}: {e}", self.endpoint))?;
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+
+        let body = response.text().await.unwrap_or_default();
+        let message = json::from_str::<Value>(&body)
+            .ok()
+            .and_then(|body| body.get("error")?.as_str().map(ToOwned::to_owned))
+            .unwrap_or(body);
+        Err(ollama_error(model_name, Some(status), &message))
+    }
 }
 
 impl Cacheable for OllamaModel {
@@ -88,4 +446,321 @@ impl ModelLogic for OllamaModel {
     async fn default_model(&self) -> Option<String> {
         self.default_model.clone()
     }
+
+    /// Talks to `/api/chat` directly, so thinking output, tool calls and the token counts of
+    /// the final NDJSON line all end up in the response chunks.
+    async fn invoke(&self, history: &History, lambda: Option<LLMCallback>) -> Result<Response> {
+        let model_name = self
+            .default_model()
+            .await
+            .unwrap_or_else(|| history.model.clone());
+
+        let body = chat_request(history, &model_name, lambda.is_some());
+        let response = self.send_chat(&body, &model_name).await?;
+
+        let mut result = Response::new();
+        result.model = Some(model_name.clone());
+
+        let Some(callback) = lambda else {
+            let bytes = response.bytes().await?;
+            if let Some(chunk) = parse_line(&bytes, &model_name)? {
+                result.push_chunk(chunk);
+            }
+            return Ok(result);
+        };
+
+        let mut stream = response.bytes_stream();
+        let mut buffer: Vec<u8> = Vec::new();
+        while let Some(bytes) = stream.next().await {
+            buffer.extend_from_slice(&bytes?);
+            while let Some(position) = buffer.iter().position(|&byte| byte == b'\n') {
+                let line: Vec<u8> = buffer.drain(..=position).collect();
+                if let Some(chunk) = parse_line(&line, &model_name)? {
+                    result.push_chunk(chunk.clone());
+                    callback(chunk).await?;
+                }
+            }
+        }
+
+        if let Some(chunk) = parse_line(&buffer, &model_name)? {
+            result.push_chunk(chunk.clone());
+            callback(chunk).await?;
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flow_like_types::{
+        sync::Mutex,
+        tokio::{
+            self,
+            io::{AsyncReadExt, AsyncWriteExt},
+            net::TcpListener,
+        },
+    };
+    use std::{collections::HashMap, sync::Arc};
+
+    const CHAT_STREAM: &str = include_str!("./fixtures/ollama_chat_stream.ndjson");
+    const CHAT: &str = include_str!("./fixtures/ollama_chat.json");
+    const TAGS: &str = include_str!("./fixtures/ollama_tags.json");
+
+    fn collect(fixture: &str, model_name: &str) -> (Response, Vec<ResponseChunk>) {
+        let mut response = Response::new();
+        let mut chunks = Vec::new();
+        for line in fixture.lines() {
+            if let Some(chunk) = parse_line(line.as_bytes(), model_name).unwrap() {
+                response.push_chunk(chunk.clone());
+                chunks.push(chunk);
+            }
+        }
+        (response, chunks)
+    }
+
+    /// Serves one canned response and returns the request line and JSON body it received.
+    async fn mock_server(
+        status: &'static str,
+        content_type: &'static str,
+        body: &'static str,
+    ) -> (String, Arc<Mutex<Option<(String, Value)>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let captured = Arc::new(Mutex::new(None));
+        let slot = captured.clone();
+
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buffer = Vec::new();
+            let mut chunk = [0u8; 4096];
+
+            let head_end = loop {
+                let read = socket.read(&mut chunk).await.unwrap();
+                buffer.extend_from_slice(&chunk[..read]);
+                if let Some(position) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+                    break position + 4;
+                }
+            };
+
+            let head = String::from_utf8_lossy(&buffer[..head_end]).to_lowercase();
+            let content_length = head
+                .lines()
+                .find_map(|line| line.strip_prefix("content-length:"))
+                .and_then(|length| length.trim().parse::<usize>().ok())
+                .unwrap_or(0);
+
+            while buffer.len() < head_end + content_length {
+                let read = socket.read(&mut chunk).await.unwrap();
+                buffer.extend_from_slice(&chunk[..read]);
+            }
+
+            let request_line = head.lines().next().unwrap_or_default().to_string();
+            let request_body = json::from_slice(&buffer[head_end..head_end + content_length])
+                .unwrap_or(Value::Null);
+            *slot.lock().await = Some((request_line, request_body));
+
+            let response = format!(
+                "HTTP/1.1 {}\r\ncontent-type: {}\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                status,
+                content_type,
+                body.len(),
+                body
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+            socket.shutdown().await.unwrap();
+        });
+
+        (endpoint, captured)
+    }
+
+    async fn model(endpoint: &str) -> OllamaModel {
+        let mut params = HashMap::new();
+        params.insert("endpoint".to_string(), json!(endpoint));
+        params.insert("model_id".to_string(), json!("llama3.2"));
+        OllamaModel::from_provider(&ModelProvider {
+            provider_name: "custom:ollama".to_string(),
+            model_id: None,
+            version: None,
+            params: Some(params),
+        })
+        .await
+        .unwrap()
+    }
+
+    #[test]
+    fn test_stream_fixture_to_chunks() {
+        let (response, chunks) = collect(CHAT_STREAM, "llama3.2");
+
+        assert_eq!(chunks.len(), 5);
+        assert_eq!(chunks[1].content_delta().as_deref(), Some(" sky"));
+        assert!(!chunks[3].is_finished());
+        assert_eq!(chunks[4].finish_reason().as_deref(), Some("stop"));
+
+        assert_eq!(response.content().as_deref(), Some("The sky is blue."));
+        assert_eq!(response.usage.prompt_tokens, 31);
+        assert_eq!(response.usage.completion_tokens, 5);
+        assert_eq!(response.usage.total_tokens, 36);
+    }
+
+    #[test]
+    fn test_non_streaming_fixture_with_tool_call() {
+        let (response, chunks) = collect(CHAT, "qwen3");
+        assert_eq!(chunks.len(), 1);
+
+        let message = response.last_message().unwrap();
+        assert_eq!(message.role, "assistant");
+        assert_eq!(message.content, None);
+        assert_eq!(
+            message.reasoning.as_deref(),
+            Some("The user wants the weather, I should call the tool.")
+        );
+        assert_eq!(message.tool_calls.len(), 1);
+        assert_eq!(message.tool_calls[0].function.name, "get_weather");
+        assert_eq!(
+            json::from_str::<Value>(&message.tool_calls[0].function.arguments).unwrap(),
+            json!({ "city": "Berlin", "unit": "celsius" })
+        );
+        assert!(!message.tool_calls[0].id.is_empty());
+        assert_eq!(response.usage.total_tokens, 210);
+    }
+
+    #[test]
+    fn test_tags_fixture() {
+        let tags: OllamaTags = json::from_str(TAGS).unwrap();
+        let names: Vec<&str> = tags
+            .models
+            .iter()
+            .map(|model| model.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["llama3.2:latest", "qwen3:8b"]);
+        assert_eq!(tags.models[1].size, 5_225_388_164);
+    }
+
+    #[test]
+    fn test_missing_model_suggests_pull() {
+        let error = ollama_error(
+            "llama3.2",
+            Some(StatusCode::NOT_FOUND),
+            "model \"llama3.2\" not found, try pulling it first",
+        );
+        assert!(error.to_string().contains("ollama pull llama3.2"));
+
+        let error = parse_line(
+            br#"{"error":"model 'llama3.2' not found, try pulling it first"}"#,
+            "llama3.2",
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("ollama pull llama3.2"));
+
+        let error = ollama_error("llama3.2", Some(StatusCode::BAD_REQUEST), "invalid options");
+        assert!(!error.to_string().contains("ollama pull"));
+    }
+
+    #[test]
+    fn test_chat_request_shape() {
+        let mut history = History::new(
+            "ignored".to_string(),
+            vec![
+                HistoryMessage::from_string(Role::System, "You are terse."),
+                HistoryMessage {
+                    role: Role::User,
+                    content: MessageContent::Contents(vec![
+                        Content::Text {
+                            content_type: crate::history::ContentType::Text,
+                            text: "What is this?".to_string(),
+                        },
+                        Content::Image {
+                            content_type: crate::history::ContentType::ImageUrl,
+                            image_url: crate::history::ImageUrl {
+                                url: "data:image/png;base64,iVBORw0KGgo=".to_string(),
+                                detail: None,
+                            },
+                        },
+                    ]),
+                    name: None,
+                    tool_calls: None,
+                    tool_call_id: None,
+                    annotations: None,
+                },
+            ],
+        );
+        history.temperature = Some(0.5);
+        history.max_completion_tokens = Some(128);
+        history.response_format = Some(ResponseFormat::String("json".to_string()));
+
+        let request = chat_request(&history, "llava", true);
+        assert_eq!(request["model"], json!("llava"));
+        assert_eq!(request["stream"], json!(true));
+        assert_eq!(request["format"], json!("json"));
+        assert_eq!(request["options"]["temperature"], json!(0.5));
+        assert_eq!(request["options"]["num_predict"], json!(128));
+        assert_eq!(request["messages"][0]["role"], json!("system"));
+        assert_eq!(request["messages"][1]["content"], json!("What is this?"));
+        assert_eq!(request["messages"][1]["images"], json!(["iVBORw0KGgo="]));
+    }
+
+    #[tokio::test]
+    async fn test_streaming_invoke() {
+        let (endpoint, captured) = mock_server("200 OK", "application/x-ndjson", CHAT_STREAM).await;
+        let model = model(&endpoint).await;
+
+        let history = History::new(
+            "ignored".to_string(),
+            vec![HistoryMessage::from_string(
+                Role::User,
+                "Why is the sky blue?",
+            )],
+        );
+
+        let chunks = Arc::new(Mutex::new(Vec::new()));
+        let collected = chunks.clone();
+        let callback: LLMCallback = Arc::new(move |chunk: ResponseChunk| {
+            let collected = collected.clone();
+            Box::pin(async move {
+                collected.lock().await.push(chunk);
+                Ok(())
+            })
+        });
+
+        let response = model.invoke(&history, Some(callback)).await.unwrap();
+        assert_eq!(response.content().as_deref(), Some("The sky is blue."));
+        assert_eq!(response.usage.total_tokens, 36);
+        assert_eq!(chunks.lock().await.len(), 5);
+
+        let (request_line, body) = captured.lock().await.take().unwrap();
+        assert!(request_line.starts_with("post /api/chat"));
+        assert_eq!(body["model"], json!("llama3.2"));
+        assert_eq!(body["stream"], json!(true));
+    }
+
+    #[tokio::test]
+    async fn test_invoke_missing_model() {
+        let (endpoint, _) = mock_server(
+            "404 Not Found",
+            "application/json",
+            r#"{"error":"model \"llama3.2\" not found, try pulling it first"}"#,
+        )
+        .await;
+        let model = model(&endpoint).await;
+
+        let history = History::new(
+            "ignored".to_string(),
+            vec![HistoryMessage::from_string(Role::User, "Hi")],
+        );
+        let error = model.invoke(&history, None).await.unwrap_err();
+        assert!(error.to_string().contains("ollama pull llama3.2"));
+    }
+
+    #[tokio::test]
+    async fn test_list_models() {
+        let (endpoint, captured) = mock_server("200 OK", "application/json", TAGS).await;
+        let models = model(&endpoint).await.list_models().await.unwrap();
+
+        assert_eq!(models.len(), 2);
+        assert_eq!(models[0].name, "llama3.2:latest");
+        let (request_line, _) = captured.lock().await.take().unwrap();
+        assert!(request_line.starts_with("get /api/tags"));
+    }
 }
@@ -0,0 +1,288 @@
+use crate::provider::{ModelProviderConfiguration, random_provider};
+use flow_like_types::{Result, anyhow, async_trait, bail, json::json, reqwest};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Scores documents against a query. Implementations receive a whole batch at once
+/// and return one relevance score per document, in input order.
+#[async_trait]
+pub trait Reranker: Send + Sync {
+    async fn score(&self, query: &str, documents: &[String]) -> Result<Vec<f32>>;
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq)]
+pub struct RerankedDocument {
+    /// Position of the document in the original candidate list
+    pub index: usize,
+    pub document: String,
+    pub score: f32,
+}
+
+/// Scores `documents` in batches of `batch_size` and returns them ordered by descending
+/// relevance. A `top_k` of 0 keeps all documents.
+pub async fn rerank(
+    reranker: &dyn Reranker,
+    query: &str,
+    documents: &[String],
+    top_k: usize,
+    batch_size: usize,
+) -> Result<Vec<RerankedDocument>> {
+    let mut scores = Vec::with_capacity(documents.len());
+    for batch in documents.chunks(batch_size.max(1)) {
+        let batch_scores = reranker.score(query, batch).await?;
+        if batch_scores.len() != batch.len() {
+            bail!(
+                "Reranker returned {} scores for {} documents",
+                batch_scores.len(),
+                batch.len()
+            );
+        }
+        scores.extend(batch_scores);
+    }
+
+    let mut ranked: Vec<RerankedDocument> = documents
+        .iter()
+        .zip(scores)
+        .enumerate()
+        .map(|(index, (document, score))| RerankedDocument {
+            index,
+            document: document.clone(),
+            score,
+        })
+        .collect();
+
+    ranked.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.index.cmp(&b.index)));
+    if top_k > 0 {
+        ranked.truncate(top_k);
+    }
+
+    Ok(ranked)
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Copy, Debug, PartialEq)]
+pub enum RerankProvider {
+    Cohere,
+    VoyageAI,
+}
+
+impl RerankProvider {
+    pub fn default_model(&self) -> &'static str {
+        match self {
+            RerankProvider::Cohere => "rerank-v3.5",
+            RerankProvider::VoyageAI => "rerank-2",
+        }
+    }
+
+    fn default_endpoint(&self) -> &'static str {
+        match self {
+            RerankProvider::Cohere => "https://api.cohere.com/v2",
+            RerankProvider::VoyageAI => "https://api.voyageai.com/v1",
+        }
+    }
+}
+
+/// Reranks through a hosted `/rerank` endpoint (Cohere or Voyage AI).
+pub struct ApiReranker {
+    provider: RerankProvider,
+    model: String,
+    endpoint: String,
+    api_key: String,
+    client: reqwest::Client,
+}
+
+impl ApiReranker {
+    pub fn new(
+        provider: RerankProvider,
+        model: Option<String>,
+        endpoint: Option<String>,
+        api_key: String,
+    ) -> Self {
+        let model = model
+            .filter(|model| !model.is_empty())
+            .unwrap_or_else(|| provider.default_model().to_string());
+        let endpoint = endpoint
+            .filter(|endpoint| !endpoint.is_empty())
+            .unwrap_or_else(|| provider.default_endpoint().to_string());
+
+        ApiReranker {
+            provider,
+            model,
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            api_key,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Uses the credentials from the model provider configuration.
+    pub fn from_config(
+        provider: RerankProvider,
+        model: Option<String>,
+        config: &ModelProviderConfiguration,
+    ) -> Result<Self> {
+        let (api_key, endpoint) = match provider {
+            RerankProvider::Cohere => {
+                let config = random_provider(&config.cohere_config)?;
+                (config.api_key, config.endpoint)
+            }
+            RerankProvider::VoyageAI => {
+                let config = random_provider(&config.voyageai_config)?;
+                (config.api_key, config.endpoint)
+            }
+        };
+
+        let api_key = api_key.ok_or_else(|| anyhow!("No API key configured for {:?}", provider))?;
+        Ok(Self::new(provider, model, endpoint, api_key))
+    }
+}
+
+#[derive(Deserialize)]
+struct ApiRerankResult {
+    index: usize,
+    relevance_score: f32,
+}
+
+/// Cohere answers with `results`, Voyage AI with `data`.
+#[derive(Deserialize)]
+struct ApiRerankResponse {
+    #[serde(alias = "data")]
+    results: Vec<ApiRerankResult>,
+}
+
+fn scores_from_response(response: ApiRerankResponse, len: usize) -> Result<Vec<f32>> {
+    let mut scores = vec![None; len];
+    for result in response.results {
+        let slot = scores
+            .get_mut(result.index)
+            .ok_or_else(|| anyhow!("Reranker returned unknown index {}", result.index))?;
+        *slot = Some(result.relevance_score);
+    }
+
+    scores
+        .into_iter()
+        .enumerate()
+        .map(|(index, score)| score.ok_or_else(|| anyhow!("Reranker skipped document {}", index)))
+        .collect()
+}
+
+#[async_trait]
+impl Reranker for ApiReranker {
+    async fn score(&self, query: &str, documents: &[String]) -> Result<Vec<f32>> {
+        let response = self
+            .client
+            .post(format!("{}/rerank", self.endpoint))
+            .bearer_auth(&self.api_key)
+            .json(&json!({
+                "model": self.model,
+                "query": query,
+                "documents": documents,
+            }))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            bail!(
+                "{:?} rerank request failed with {}: {}",
+                self.provider,
+                status,
+                body
+            );
+        }
+
+        scores_from_response(response.json().await?, documents.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flow_like_types::tokio;
+    use std::sync::Mutex;
+
+    /// Scores by the number of query words contained in the document.
+    struct MockScorer {
+        batches: Mutex<Vec<usize>>,
+    }
+
+    #[async_trait]
+    impl Reranker for MockScorer {
+        async fn score(&self, query: &str, documents: &[String]) -> Result<Vec<f32>> {
+            self.batches.lock().unwrap().push(documents.len());
+            Ok(documents
+                .iter()
+                .map(|document| {
+                    query
+                        .split_whitespace()
+                        .filter(|word| document.contains(word))
+                        .count() as f32
+                })
+                .collect())
+        }
+    }
+
+    fn documents() -> Vec<String> {
+        [
+            "rust is fast",
+            "the borrow checker in rust is strict",
+            "python is popular",
+            "rust borrow checker",
+            "cooking pasta",
+        ]
+        .iter()
+        .map(|document| document.to_string())
+        .collect()
+    }
+
+    #[tokio::test]
+    async fn test_rerank_orders_by_score_in_batches() {
+        let scorer = MockScorer {
+            batches: Mutex::new(vec![]),
+        };
+        let ranked = rerank(&scorer, "rust borrow checker", &documents(), 0, 2)
+            .await
+            .unwrap();
+
+        let indices: Vec<usize> = ranked.iter().map(|result| result.index).collect();
+        assert_eq!(indices, vec![1, 3, 0, 2, 4]);
+        assert_eq!(ranked[0].document, "the borrow checker in rust is strict");
+        assert_eq!(ranked[0].score, 3.0);
+        assert_eq!(*scorer.batches.lock().unwrap(), vec![2, 2, 1]);
+    }
+
+    #[tokio::test]
+    async fn test_rerank_top_k_truncates() {
+        let scorer = MockScorer {
+            batches: Mutex::new(vec![]),
+        };
+        let ranked = rerank(&scorer, "rust borrow checker", &documents(), 2, 32)
+            .await
+            .unwrap();
+
+        assert_eq!(ranked.len(), 2);
+        assert_eq!(ranked[0].index, 1);
+        assert_eq!(ranked[1].index, 3);
+        assert_eq!(*scorer.batches.lock().unwrap(), vec![5]);
+
+        let all = rerank(&scorer, "rust", &documents(), 10, 32).await.unwrap();
+        assert_eq!(all.len(), 5);
+    }
+
+    #[test]
+    fn test_scores_from_api_response() {
+        let cohere: ApiRerankResponse = flow_like_types::json::from_value(json!({
+            "results": [
+                { "index": 1, "relevance_score": 0.9 },
+                { "index": 0, "relevance_score": 0.1 }
+            ]
+        }))
+        .unwrap();
+        assert_eq!(scores_from_response(cohere, 2).unwrap(), vec![0.1, 0.9]);
+
+        let voyage: ApiRerankResponse = flow_like_types::json::from_value(json!({
+            "data": [{ "index": 0, "relevance_score": 0.5 }]
+        }))
+        .unwrap();
+        assert!(scores_from_response(voyage, 2).is_err());
+    }
+}
@@ -1,8 +1,12 @@
-use std::sync::Arc;
+use std::{
+    collections::HashMap,
+    sync::{Arc, LazyLock, RwLock},
+};
 
 #[cfg(feature = "local-ml")]
 use fastembed::TokenizerFiles;
 use text_splitter::ChunkSizer;
+use tiktoken_rs::CoreBPE;
 use tokenizers::{
     AddedToken, Encoding, PaddingParams, PaddingStrategy, Tokenizer, TruncationParams,
 };
@@ -51,6 +55,68 @@ impl From<Tokenizer> for TokenizerSizer {
     }
 }
 
+/// Counts tokens for a specific model, either through a tiktoken BPE or a Hugging Face tokenizer.
+#[derive(Clone)]
+pub enum TokenCounter {
+    Tiktoken(Arc<CoreBPE>),
+    HuggingFace(TokenizerSizer),
+}
+
+impl TokenCounter {
+    pub fn from_tokenizer_bytes(bytes: &[u8]) -> flow_like_types::Result<Self> {
+        let tokenizer = Tokenizer::from_bytes(bytes).map_err(flow_like_types::Error::msg)?;
+        Ok(TokenCounter::HuggingFace(TokenizerSizer::new(tokenizer)))
+    }
+
+    pub fn count(&self, text: &str) -> usize {
+        match self {
+            TokenCounter::Tiktoken(bpe) => bpe.encode_with_special_tokens(text).len(),
+            TokenCounter::HuggingFace(sizer) => sizer.size(text),
+        }
+    }
+}
+
+static TOKEN_COUNTERS: LazyLock<RwLock<HashMap<String, Option<TokenCounter>>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// Returns the cached counter stored under `key`, loading it with `load` on first use.
+/// Failed loads are cached as well, so models without a tokenizer are only looked up once.
+pub fn cached_token_counter(
+    key: &str,
+    load: impl FnOnce() -> Option<TokenCounter>,
+) -> Option<TokenCounter> {
+    if let Some(counter) = TOKEN_COUNTERS
+        .read()
+        .ok()
+        .and_then(|counters| counters.get(key).cloned())
+    {
+        return counter;
+    }
+
+    let counter = load();
+    if let Ok(mut counters) = TOKEN_COUNTERS.write() {
+        counters.insert(key.to_string(), counter.clone());
+    }
+    counter
+}
+
+/// Tiktoken counter for OpenAI style model ids, e.g. `gpt-4o` or `openai/gpt-4o-mini`.
+pub fn tiktoken_counter(model_id: &str) -> Option<TokenCounter> {
+    let model_id = model_id.trim().to_lowercase();
+    let model_id = model_id.rsplit('/').next().unwrap_or(&model_id).to_string();
+
+    cached_token_counter(&format!("tiktoken:{}", model_id), || {
+        tiktoken_rs::get_bpe_from_model(&model_id)
+            .ok()
+            .map(|bpe| TokenCounter::Tiktoken(Arc::new(bpe)))
+    })
+}
+
+/// Rough estimate used when no tokenizer is known for a model (about four characters per token).
+pub fn estimate_token_count(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
 #[cfg(feature = "local-ml")]
 pub fn load_tokenizer_from_file(
     tokenizer_files: Arc<TokenizerFiles>,
@@ -143,3 +209,83 @@ pub fn load_tokenizer_from_file(
     let tokenizer: Tokenizer = tokenizer;
     Ok(TokenizerSizer::new(tokenizer))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tiktoken_counts_match_reference() {
+        let gpt4 = tiktoken_counter("gpt-4").unwrap();
+        assert_eq!(gpt4.count("hello world"), 2);
+        assert_eq!(gpt4.count("tiktoken is great!"), 6);
+
+        let gpt4o = tiktoken_counter("gpt-4o").unwrap();
+        assert_eq!(gpt4o.count("tiktoken is great!"), 6);
+
+        if let TokenCounter::Tiktoken(bpe) = &gpt4 {
+            assert_eq!(
+                bpe.encode_with_special_tokens("hello world"),
+                vec![15339, 1917]
+            );
+        }
+    }
+
+    #[test]
+    fn test_tiktoken_counter_accepts_routed_ids() {
+        let routed = tiktoken_counter("openai/GPT-4o-mini").unwrap();
+        assert_eq!(routed.count("tiktoken is great!"), 6);
+        assert!(tiktoken_counter("claude-3-5-sonnet").is_none());
+    }
+
+    #[test]
+    fn test_counters_are_cached() {
+        let mut loads = 0;
+        for _ in 0..3 {
+            cached_token_counter("test:cached", || {
+                loads += 1;
+                None
+            });
+        }
+        assert_eq!(loads, 1);
+
+        let first = tiktoken_counter("gpt-4");
+        let second = tiktoken_counter("gpt-4");
+        match (first, second) {
+            (Some(TokenCounter::Tiktoken(a)), Some(TokenCounter::Tiktoken(b))) => {
+                assert!(Arc::ptr_eq(&a, &b))
+            }
+            _ => panic!("expected cached tiktoken counters"),
+        }
+    }
+
+    #[test]
+    fn test_hugging_face_tokenizer() {
+        let tokenizer = r#"{
+            "version": "1.0",
+            "truncation": null,
+            "padding": null,
+            "added_tokens": [],
+            "normalizer": null,
+            "pre_tokenizer": { "type": "Whitespace" },
+            "post_processor": null,
+            "decoder": null,
+            "model": {
+                "type": "WordLevel",
+                "vocab": { "[UNK]": 0, "hello": 1, "world": 2, "!": 3 },
+                "unk_token": "[UNK]"
+            }
+        }"#;
+
+        let counter = TokenCounter::from_tokenizer_bytes(tokenizer.as_bytes()).unwrap();
+        assert_eq!(counter.count("hello world!"), 3);
+        assert_eq!(counter.count("hello unknown"), 2);
+    }
+
+    #[test]
+    fn test_estimate_token_count() {
+        assert_eq!(estimate_token_count(""), 0);
+        assert_eq!(estimate_token_count("abcd"), 1);
+        assert_eq!(estimate_token_count("abcde"), 2);
+    }
+}
@@ -0,0 +1,212 @@
+//! Broker connection of the AMQP sink, built on `lapin`
+
+use super::{AmqpDelivery, AmqpSink, DeliveryAcker};
+use crate::{
+    config::{AmqpSinkConfig, AmqpTlsConfig},
+    traits::{Executor, SinkContext, SinkError, SinkResult},
+    types::SinkRegistration,
+};
+use base64::Engine;
+use flow_like_types::Value;
+use futures_util::StreamExt;
+use lapin::{
+    Connection, ConnectionProperties,
+    acker::Acker,
+    message::Delivery,
+    options::{
+        BasicAckOptions, BasicConsumeOptions, BasicNackOptions, BasicQosOptions, QueueBindOptions,
+    },
+    tcp::{OwnedIdentity, OwnedTLSConfig},
+    types::{AMQPValue, FieldTable},
+};
+use std::collections::BTreeMap;
+
+#[async_trait::async_trait]
+impl DeliveryAcker for Acker {
+    async fn ack(&self) -> SinkResult<()> {
+        Acker::ack(self, BasicAckOptions::default())
+            .await
+            .map_err(|e| SinkError::Internal(format!("Failed to ack delivery: {}", e)))
+    }
+
+    async fn nack(&self, requeue: bool) -> SinkResult<()> {
+        Acker::nack(
+            self,
+            BasicNackOptions {
+                requeue,
+                ..Default::default()
+            },
+        )
+        .await
+        .map_err(|e| SinkError::Internal(format!("Failed to nack delivery: {}", e)))
+    }
+}
+
+/// Consumes the configured queue until the connection closes. Deliveries are handled one at
+/// a time, `prefetch` only controls how many the broker buffers ahead.
+pub async fn consume<E: Executor>(
+    sink: &AmqpSink,
+    ctx: &SinkContext<E>,
+    registration: &SinkRegistration,
+) -> SinkResult<()> {
+    let config = AmqpSink::parse_config(&registration.config)?;
+
+    let connection = connect(&config).await?;
+    let channel = connection
+        .create_channel()
+        .await
+        .map_err(|e| SinkError::Internal(format!("Failed to open AMQP channel: {}", e)))?;
+    channel
+        .basic_qos(config.prefetch, BasicQosOptions::default())
+        .await
+        .map_err(|e| SinkError::Internal(format!("Failed to set prefetch: {}", e)))?;
+
+    if let Some(exchange) = &config.exchange {
+        channel
+            .queue_bind(
+                &config.queue,
+                exchange,
+                &config.routing_key,
+                QueueBindOptions::default(),
+                FieldTable::default(),
+            )
+            .await
+            .map_err(|e| {
+                SinkError::InvalidConfig(format!(
+                    "Failed to bind queue {} to exchange {}: {}",
+                    config.queue, exchange, e
+                ))
+            })?;
+    }
+
+    let mut consumer = channel
+        .basic_consume(
+            &config.queue,
+            &format!("flow-like-{}", registration.event_id),
+            BasicConsumeOptions::default(),
+            FieldTable::default(),
+        )
+        .await
+        .map_err(|e| {
+            SinkError::InvalidConfig(format!("Failed to consume queue {}: {}", config.queue, e))
+        })?;
+
+    tracing::info!(
+        "Consuming AMQP queue {} for event {} (app: {})",
+        config.queue,
+        registration.event_id,
+        registration.app_id
+    );
+
+    while let Some(delivery) = consumer.next().await {
+        let delivery =
+            delivery.map_err(|e| SinkError::Internal(format!("AMQP consumer failed: {}", e)))?;
+        let message = to_delivery(&delivery);
+        sink.handle_delivery(ctx, registration, &config, &message, &delivery.acker)
+            .await?;
+    }
+
+    Ok(())
+}
+
+async fn connect(config: &AmqpSinkConfig) -> SinkResult<Connection> {
+    let properties = ConnectionProperties::default();
+    let connection = match &config.tls {
+        Some(tls) => {
+            Connection::connect_with_config(&config.uri, properties, tls_config(tls)?).await
+        }
+        None => Connection::connect(&config.uri, properties).await,
+    };
+    connection.map_err(|e| SinkError::Internal(format!("Failed to connect to AMQP broker: {}", e)))
+}
+
+fn tls_config(tls: &AmqpTlsConfig) -> SinkResult<OwnedTLSConfig> {
+    let identity = tls
+        .client_identity
+        .as_deref()
+        .map(|identity| {
+            base64::engine::general_purpose::STANDARD
+                .decode(identity.trim())
+                .map(|der| OwnedIdentity {
+                    der,
+                    password: tls.client_identity_password.clone().unwrap_or_default(),
+                })
+                .map_err(|e| {
+                    SinkError::InvalidConfig(format!("Client identity is not valid base64: {}", e))
+                })
+        })
+        .transpose()?;
+
+    Ok(OwnedTLSConfig {
+        identity,
+        cert_chain: tls.ca_certificate.clone(),
+    })
+}
+
+fn to_delivery(delivery: &Delivery) -> AmqpDelivery {
+    let properties = &delivery.properties;
+    AmqpDelivery {
+        exchange: delivery.exchange.to_string(),
+        routing_key: delivery.routing_key.to_string(),
+        redelivered: delivery.redelivered,
+        headers: properties
+            .headers()
+            .as_ref()
+            .map(field_table_to_map)
+            .unwrap_or_default(),
+        content_type: properties
+            .content_type()
+            .as_ref()
+            .map(|value| value.to_string()),
+        message_id: properties
+            .message_id()
+            .as_ref()
+            .map(|value| value.to_string()),
+        correlation_id: properties
+            .correlation_id()
+            .as_ref()
+            .map(|value| value.to_string()),
+        body: delivery.data.clone(),
+    }
+}
+
+fn field_table_to_map(table: &FieldTable) -> BTreeMap<String, Value> {
+    table
+        .inner()
+        .iter()
+        .map(|(key, value)| (key.to_string(), amqp_value_to_json(value)))
+        .collect()
+}
+
+fn amqp_value_to_json(value: &AMQPValue) -> Value {
+    match value {
+        AMQPValue::Boolean(value) => Value::from(*value),
+        AMQPValue::ShortShortInt(value) => Value::from(*value),
+        AMQPValue::ShortShortUInt(value) => Value::from(*value),
+        AMQPValue::ShortInt(value) => Value::from(*value),
+        AMQPValue::ShortUInt(value) => Value::from(*value),
+        AMQPValue::LongInt(value) => Value::from(*value),
+        AMQPValue::LongUInt(value) => Value::from(*value),
+        AMQPValue::LongLongInt(value) => Value::from(*value),
+        AMQPValue::Float(value) => Value::from(*value),
+        AMQPValue::Double(value) => Value::from(*value),
+        AMQPValue::Timestamp(value) => Value::from(*value),
+        AMQPValue::ShortString(value) => Value::from(value.as_str()),
+        AMQPValue::LongString(value) => {
+            Value::from(String::from_utf8_lossy(value.as_bytes()).into_owned())
+        }
+        AMQPValue::FieldArray(values) => {
+            Value::Array(values.as_slice().iter().map(amqp_value_to_json).collect())
+        }
+        AMQPValue::FieldTable(table) => {
+            Value::Object(field_table_to_map(table).into_iter().collect())
+        }
+        AMQPValue::ByteArray(bytes) => {
+            Value::from(base64::engine::general_purpose::STANDARD.encode(bytes.as_slice()))
+        }
+        AMQPValue::DecimalValue(value) => {
+            Value::from(value.value as f64 / 10f64.powi(value.scale as i32))
+        }
+        AMQPValue::Void => Value::Null,
+    }
+}
@@ -0,0 +1,448 @@
+//! GitHub webhook sink
+//!
+//! Deliveries are verified against the `X-Hub-Signature-256` header, filtered by the
+//! `X-GitHub-Event` header and handed to the flow as a [`GitHubEvent`].
+
+use crate::{
+    config::GitHubSinkConfig,
+    traits::{Executor, SinkContext, SinkError, SinkResult, SinkTrait, TriggerResponse},
+    types::{SinkRegistration, SinkType},
+};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// GitHub sink for repository webhooks
+#[derive(Debug, Clone, Default)]
+pub struct GitHubSink;
+
+impl GitHubSink {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Parse and validate GitHub sink config from JSON
+    pub fn parse_config(config: &flow_like_types::Value) -> SinkResult<GitHubSinkConfig> {
+        serde_json::from_value(config.clone())
+            .map_err(|e| SinkError::InvalidConfig(format!("Invalid GitHub config: {}", e)))
+    }
+
+    /// Verify, filter and run a webhook delivery.
+    /// Deliveries for events outside the allowlist are acknowledged without running the flow.
+    ///
+    /// `secret` is the webhook secret of the sink, deliveries are refused when it is empty.
+    pub async fn handle_webhook<E: Executor>(
+        &self,
+        ctx: &SinkContext<E>,
+        registration: &SinkRegistration,
+        secret: &str,
+        request: &GitHubWebhookRequest,
+    ) -> SinkResult<TriggerResponse> {
+        if secret.is_empty() {
+            return Err(SinkError::AuthFailed(
+                "GitHub webhook has no secret configured".to_string(),
+            ));
+        }
+        let config = Self::parse_config(&registration.config)?;
+        request.verify_signature(secret)?;
+
+        if !config.events.is_empty() && !config.events.contains(&request.event) {
+            tracing::debug!(
+                "Ignoring GitHub '{}' event for event {} (app: {})",
+                request.event,
+                registration.event_id,
+                registration.app_id
+            );
+            return Ok(TriggerResponse {
+                triggered: false,
+                run_id: None,
+                response: None,
+                error: None,
+            });
+        }
+
+        let event = request.parse_event()?;
+
+        if let (Some(expected), Some(repository)) = (&config.repository, &event.repository)
+            && !repository.full_name.eq_ignore_ascii_case(expected)
+        {
+            return Err(SinkError::AuthFailed(format!(
+                "Delivery is for repository {}, expected {}",
+                repository.full_name, expected
+            )));
+        }
+
+        tracing::info!(
+            "GitHub sink triggered by '{}' for event {} (app: {})",
+            event.event,
+            registration.event_id,
+            registration.app_id
+        );
+
+        let payload = serde_json::to_value(&event)
+            .map_err(|e| SinkError::Internal(format!("Failed to serialize event: {}", e)))?;
+        self.handle_trigger(ctx, registration, Some(payload)).await
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkTrait for GitHubSink {
+    fn sink_type(&self) -> SinkType {
+        SinkType::GitHub
+    }
+
+    fn validate_config(&self, config: &flow_like_types::Value) -> SinkResult<()> {
+        let cfg = Self::parse_config(config)?;
+        if let Some(event) = cfg.events.iter().find(|event| {
+            event.is_empty() || !event.chars().all(|c| c.is_ascii_lowercase() || c == '_')
+        }) {
+            return Err(SinkError::InvalidConfig(format!(
+                "Invalid GitHub event name: '{}'",
+                event
+            )));
+        }
+        Ok(())
+    }
+
+    async fn register<E: Executor>(
+        &self,
+        _ctx: &SinkContext<E>,
+        registration: &SinkRegistration,
+    ) -> SinkResult<()> {
+        self.validate_config(&registration.config)?;
+
+        let config = Self::parse_config(&registration.config)?;
+
+        tracing::info!(
+            "Registered GitHub sink for {} ({}) -> event {} (app: {})",
+            config.repository.as_deref().unwrap_or("any repository"),
+            if config.events.is_empty() {
+                "all events".to_string()
+            } else {
+                config.events.join(", ")
+            },
+            registration.event_id,
+            registration.app_id
+        );
+
+        Ok(())
+    }
+
+    async fn unregister<E: Executor>(
+        &self,
+        _ctx: &SinkContext<E>,
+        registration: &SinkRegistration,
+    ) -> SinkResult<()> {
+        tracing::info!(
+            "Unregistered GitHub sink for event {} (app: {})",
+            registration.event_id,
+            registration.app_id
+        );
+        Ok(())
+    }
+
+    /// Runs the flow with `payload` as is, webhook deliveries go through [`GitHubSink::handle_webhook`]
+    async fn handle_trigger<E: Executor>(
+        &self,
+        ctx: &SinkContext<E>,
+        registration: &SinkRegistration,
+        payload: Option<flow_like_types::Value>,
+    ) -> SinkResult<TriggerResponse> {
+        let run_id = ctx.dispatch(registration, payload).await?;
+
+        Ok(TriggerResponse::success(Some(run_id)))
+    }
+}
+
+/// An incoming webhook delivery
+#[derive(Debug, Clone)]
+pub struct GitHubWebhookRequest {
+    /// `X-GitHub-Event` header
+    pub event: String,
+
+    /// `X-GitHub-Delivery` header
+    pub delivery: Option<String>,
+
+    /// `X-Hub-Signature-256` header, `sha256=<hex digest>`
+    pub signature: Option<String>,
+
+    /// Raw request body, the signature covers these exact bytes
+    pub body: Vec<u8>,
+}
+
+impl GitHubWebhookRequest {
+    /// Check the HMAC-SHA256 signature of the body against the webhook secret
+    pub fn verify_signature(&self, secret: &str) -> SinkResult<()> {
+        let signature = self
+            .signature
+            .as_deref()
+            .ok_or_else(|| SinkError::AuthFailed("Missing X-Hub-Signature-256".to_string()))?;
+        let digest = signature
+            .strip_prefix("sha256=")
+            .and_then(|digest| hex::decode(digest).ok())
+            .ok_or_else(|| SinkError::AuthFailed("Malformed X-Hub-Signature-256".to_string()))?;
+
+        let mut mac = HmacSha256::new_from_slice(secret.as_bytes())
+            .map_err(|e| SinkError::Internal(format!("Invalid webhook secret: {}", e)))?;
+        mac.update(&self.body);
+        mac.verify_slice(&digest)
+            .map_err(|_| SinkError::AuthFailed("Invalid webhook signature".to_string()))
+    }
+
+    pub fn parse_event(&self) -> SinkResult<GitHubEvent> {
+        let payload: flow_like_types::Value = serde_json::from_slice(&self.body)
+            .map_err(|e| SinkError::InvalidConfig(format!("Invalid webhook body: {}", e)))?;
+        let common: CommonFields = serde_json::from_value(payload.clone()).unwrap_or_default();
+
+        Ok(GitHubEvent {
+            event: self.event.clone(),
+            delivery: self.delivery.clone(),
+            action: common.action,
+            git_ref: common.git_ref,
+            repository: common.repository,
+            sender: common.sender,
+            payload,
+        })
+    }
+}
+
+/// The fields most GitHub events share, with the full delivery kept in `payload`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GitHubEvent {
+    /// Event name from the `X-GitHub-Event` header, e.g. "push"
+    pub event: String,
+
+    /// Unique id of the delivery
+    pub delivery: Option<String>,
+
+    /// What happened, e.g. "opened" for a pull request. Not set for push events
+    pub action: Option<String>,
+
+    /// Pushed ref, e.g. "refs/heads/main"
+    #[serde(rename = "ref")]
+    pub git_ref: Option<String>,
+
+    pub repository: Option<GitHubRepository>,
+
+    pub sender: Option<GitHubUser>,
+
+    /// The raw webhook body
+    pub payload: flow_like_types::Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GitHubRepository {
+    pub id: u64,
+    pub name: String,
+    /// "owner/repo"
+    pub full_name: String,
+    #[serde(default)]
+    pub private: bool,
+    pub html_url: Option<String>,
+    pub default_branch: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GitHubUser {
+    pub id: u64,
+    pub login: String,
+    pub html_url: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct CommonFields {
+    action: Option<String>,
+    #[serde(rename = "ref")]
+    git_ref: Option<String>,
+    repository: Option<GitHubRepository>,
+    sender: Option<GitHubUser>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::SinkExecution;
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+
+    const SECRET: &str = "It's a Secret to Everybody";
+
+    #[derive(Default)]
+    struct RecordingExecutor {
+        payloads: Mutex<Vec<Option<flow_like_types::Value>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl Executor for RecordingExecutor {
+        async fn execute_event(
+            &self,
+            _app_id: &str,
+            _board_id: &str,
+            _event_id: &str,
+            payload: Option<flow_like_types::Value>,
+            _personal_access_token: Option<&str>,
+        ) -> SinkResult<String> {
+            self.payloads.lock().unwrap().push(payload);
+            Ok("run-1".to_string())
+        }
+    }
+
+    fn registration(events: &[&str]) -> SinkRegistration {
+        SinkRegistration {
+            id: "sink-1".to_string(),
+            event_id: "event-1".to_string(),
+            board_id: "board-1".to_string(),
+            app_id: "app-1".to_string(),
+            sink_type: SinkType::GitHub,
+            config: json!({
+                "repository": "octo-org/hello-world",
+                "events": events,
+            }),
+            execution: SinkExecution::Remote,
+            active: true,
+            auth_token: None,
+            path: None,
+            method: None,
+            cron_expression: None,
+            default_payload: None,
+            personal_access_token: None,
+            oauth_tokens: None,
+        }
+    }
+
+    fn sign(body: &[u8], secret: &str) -> String {
+        let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(body);
+        format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+    }
+
+    fn push_request(secret: &str) -> GitHubWebhookRequest {
+        let body = serde_json::to_vec(&json!({
+            "ref": "refs/heads/main",
+            "before": "6113728f27ae82c7b1a177c8d03f9e96e0adf246",
+            "after": "0d1a26e67d8f5eaf1f6ba5c57fc3c7d91ac0fd1c",
+            "repository": {
+                "id": 1296269,
+                "name": "hello-world",
+                "full_name": "octo-org/hello-world",
+                "private": false,
+                "html_url": "https://github.com/octo-org/hello-world",
+                "default_branch": "main"
+            },
+            "sender": {
+                "id": 583231,
+                "login": "octocat",
+                "html_url": "https://github.com/octocat"
+            },
+            "commits": []
+        }))
+        .unwrap();
+
+        GitHubWebhookRequest {
+            event: "push".to_string(),
+            delivery: Some("72d3162e-cc78-11e3-81ab-4c9367dc0958".to_string()),
+            signature: Some(sign(&body, secret)),
+            body,
+        }
+    }
+
+    fn context() -> (SinkContext<RecordingExecutor>, Arc<RecordingExecutor>) {
+        let executor = Arc::new(RecordingExecutor::default());
+        (SinkContext::new(executor.clone()), executor)
+    }
+
+    #[tokio::test]
+    async fn push_event_runs_flow_with_typed_payload() {
+        let (ctx, executor) = context();
+
+        let response = GitHubSink::new()
+            .handle_webhook(
+                &ctx,
+                &registration(&["push"]),
+                SECRET,
+                &push_request(SECRET),
+            )
+            .await
+            .unwrap();
+
+        assert!(response.triggered);
+        let payloads = executor.payloads.lock().unwrap();
+        assert_eq!(payloads.len(), 1);
+        let event: GitHubEvent = serde_json::from_value(payloads[0].clone().unwrap()).unwrap();
+        assert_eq!(event.event, "push");
+        assert_eq!(event.git_ref.as_deref(), Some("refs/heads/main"));
+        assert_eq!(event.action, None);
+        assert_eq!(event.repository.unwrap().full_name, "octo-org/hello-world");
+        assert_eq!(event.sender.unwrap().login, "octocat");
+        assert_eq!(
+            event.payload["after"],
+            "0d1a26e67d8f5eaf1f6ba5c57fc3c7d91ac0fd1c"
+        );
+    }
+
+    #[tokio::test]
+    async fn filtered_event_does_not_run_flow() {
+        let (ctx, executor) = context();
+
+        let response = GitHubSink::new()
+            .handle_webhook(
+                &ctx,
+                &registration(&["pull_request", "issues"]),
+                SECRET,
+                &push_request(SECRET),
+            )
+            .await
+            .unwrap();
+
+        assert!(!response.triggered);
+        assert!(executor.payloads.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn bad_signature_is_rejected() {
+        let (ctx, executor) = context();
+
+        let error = GitHubSink::new()
+            .handle_webhook(
+                &ctx,
+                &registration(&[]),
+                SECRET,
+                &push_request("wrong secret"),
+            )
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, SinkError::AuthFailed(_)));
+        assert_eq!(error.status_code(), 401);
+        assert!(executor.payloads.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn empty_secret_is_rejected() {
+        let (ctx, executor) = context();
+
+        // Signed with the empty key, which would verify if the secret were not checked first
+        let error = GitHubSink::new()
+            .handle_webhook(&ctx, &registration(&[]), "", &push_request(""))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, SinkError::AuthFailed(_)));
+        assert!(executor.payloads.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn config_requires_valid_events() {
+        let sink = GitHubSink::new();
+        assert!(
+            sink.validate_config(&json!({ "events": ["Push!"] }))
+                .is_err()
+        );
+        assert!(
+            sink.validate_config(&json!({ "events": ["pull_request"] }))
+                .is_ok()
+        );
+    }
+}
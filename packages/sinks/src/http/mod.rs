@@ -131,16 +131,7 @@ impl SinkTrait for HttpSink {
         };
 
         // Execute the event
-        let run_id = ctx
-            .executor
-            .execute_event(
-                &registration.app_id,
-                &registration.board_id,
-                &registration.event_id,
-                final_payload,
-                registration.personal_access_token.as_deref(),
-            )
-            .await?;
+        let run_id = ctx.dispatch(registration, final_payload).await?;
 
         Ok(TriggerResponse::success(Some(run_id)))
     }
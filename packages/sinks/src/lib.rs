@@ -13,6 +13,9 @@
 //! | MQTT | ✅ | ⚠️ | IoT messaging |
 //! | GitHub | ✅ | ⚠️ | Repository webhooks |
 //! | RSS | ✅ | ✅ | Feed polling |
+//! | S3 | ✅ | ❌ | Bucket event notifications via SQS/SNS |
+//! | Twilio | ✅ | ⚠️ | Inbound SMS/MMS webhooks |
+//! | AMQP | ✅ | ❌ | RabbitMQ queue consumer |
 //! | Discord | ⚠️ | ✅ | Bot integration (requires persistent process) |
 //! | Slack | ⚠️ | ✅ | Bot integration |
 //! | Deeplink | ❌ | ✅ | Desktop app URL scheme |
@@ -20,16 +23,29 @@
 //! | Geolocation | ❌ | ✅ | Device GPS |
 //! | Shortcut | ❌ | ✅ | OS keyboard shortcuts |
 //! | File | ❌ | ✅ | Local filesystem watching |
+//!
+//! ## Throttling
+//!
+//! Any sink config may carry `rate_limit`, `max_concurrent` and `overflow` (see
+//! [`ThrottleConfig`]). Sinks run flows through [`SinkContext::dispatch`], which drops or
+//! queues events beyond that budget.
 
 mod config;
 mod traits;
 mod types;
 
+pub mod amqp;
+pub mod github;
 pub mod http;
+pub mod s3;
 pub mod scheduler;
+pub mod throttle;
+pub mod twilio;
 
 pub use config::{
-    CronSinkConfig, HttpSinkConfig, MqttSinkConfig, RssSinkConfig, SinkConfig, WebhookSinkConfig,
+    AmqpSinkConfig, AmqpTlsConfig, CronSinkConfig, GitHubSinkConfig, HttpSinkConfig,
+    MqttSinkConfig, OverflowPolicy, RateLimit, RssSinkConfig, S3EventSinkConfig, SinkConfig,
+    ThrottleConfig, TwilioSinkConfig, WebhookSinkConfig,
 };
 pub use scheduler::{ScheduleInfo, SchedulerBackend, SchedulerError, SchedulerResult};
 pub use traits::{Executor, SinkContext, SinkError, SinkResult, SinkTrait, TriggerResponse};
@@ -0,0 +1,488 @@
+//! S3 event notification sink
+//!
+//! Buckets publish object events to an SQS queue or an SNS topic. This sink unwraps
+//! either envelope, skips the `s3:TestEvent` S3 sends when a notification is set up,
+//! and runs the flow once per object with its metadata as an [`S3Object`].
+
+use crate::{
+    config::S3EventSinkConfig,
+    traits::{Executor, SinkContext, SinkError, SinkResult, SinkTrait, TriggerResponse},
+    types::{SinkRegistration, SinkType},
+};
+use flow_like_types::Value;
+use serde::{Deserialize, Serialize};
+
+/// S3 sink for bucket event notifications
+#[derive(Debug, Clone, Default)]
+pub struct S3EventSink;
+
+impl S3EventSink {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Parse and validate S3 sink config from JSON
+    pub fn parse_config(config: &Value) -> SinkResult<S3EventSinkConfig> {
+        serde_json::from_value(config.clone())
+            .map_err(|e| SinkError::InvalidConfig(format!("Invalid S3 config: {}", e)))
+    }
+
+    /// Extract the objects of a notification, as received from SQS, SNS or S3 directly.
+    /// Test events yield no objects.
+    pub fn parse_notification(notification: &Value) -> SinkResult<Vec<S3Object>> {
+        // SNS wraps the S3 event as a JSON string in "Message"
+        if notification.get("Type").and_then(Value::as_str) == Some("Notification") {
+            let message = notification
+                .get("Message")
+                .and_then(Value::as_str)
+                .ok_or_else(|| SinkError::InvalidConfig("SNS message is missing".to_string()))?;
+            return Self::parse_notification(&parse_json(message)?);
+        }
+
+        if notification.get("Event").and_then(Value::as_str) == Some("s3:TestEvent") {
+            return Ok(Vec::new());
+        }
+
+        let records = notification
+            .get("Records")
+            .and_then(Value::as_array)
+            .ok_or_else(|| {
+                SinkError::InvalidConfig("Notification has no Records array".to_string())
+            })?;
+
+        let mut objects = Vec::new();
+        for record in records {
+            // An SQS batch holds one message per record, each with its own S3 event (or SNS envelope)
+            if let Some(body) = record.get("body").and_then(Value::as_str) {
+                objects.extend(Self::parse_notification(&parse_json(body)?)?);
+                continue;
+            }
+
+            let record: S3Record = serde_json::from_value(record.clone())
+                .map_err(|e| SinkError::InvalidConfig(format!("Invalid S3 record: {}", e)))?;
+            objects.push(record.into_object());
+        }
+
+        Ok(objects)
+    }
+
+    /// Run the flow once for every object in the notification that passes the configured filters
+    pub async fn handle_notification<E: Executor>(
+        &self,
+        ctx: &SinkContext<E>,
+        registration: &SinkRegistration,
+        notification: &Value,
+    ) -> SinkResult<Vec<TriggerResponse>> {
+        let config = Self::parse_config(&registration.config)?;
+        let objects = Self::parse_notification(notification)?;
+
+        let mut responses = Vec::new();
+        for object in objects.into_iter().filter(|object| object.matches(&config)) {
+            tracing::info!(
+                "S3 sink triggered by {} on s3://{}/{} for event {} (app: {})",
+                object.event_name,
+                object.bucket,
+                object.key,
+                registration.event_id,
+                registration.app_id
+            );
+
+            let payload = serde_json::to_value(&object)
+                .map_err(|e| SinkError::Internal(format!("Failed to serialize object: {}", e)))?;
+            responses.push(
+                self.handle_trigger(ctx, registration, Some(payload))
+                    .await?,
+            );
+        }
+
+        Ok(responses)
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkTrait for S3EventSink {
+    fn sink_type(&self) -> SinkType {
+        SinkType::S3
+    }
+
+    fn validate_config(&self, config: &Value) -> SinkResult<()> {
+        let cfg = Self::parse_config(config)?;
+        if cfg.bucket.as_deref().is_some_and(str::is_empty) {
+            return Err(SinkError::InvalidConfig(
+                "Bucket name must not be empty".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn register<E: Executor>(
+        &self,
+        _ctx: &SinkContext<E>,
+        registration: &SinkRegistration,
+    ) -> SinkResult<()> {
+        self.validate_config(&registration.config)?;
+
+        let config = Self::parse_config(&registration.config)?;
+
+        tracing::info!(
+            "Registered S3 sink for {} (prefix: {}, suffix: {}) -> event {} (app: {})",
+            config.bucket.as_deref().unwrap_or("any bucket"),
+            config.prefix.as_deref().unwrap_or("-"),
+            config.suffix.as_deref().unwrap_or("-"),
+            registration.event_id,
+            registration.app_id
+        );
+
+        Ok(())
+    }
+
+    async fn unregister<E: Executor>(
+        &self,
+        _ctx: &SinkContext<E>,
+        registration: &SinkRegistration,
+    ) -> SinkResult<()> {
+        tracing::info!(
+            "Unregistered S3 sink for event {} (app: {})",
+            registration.event_id,
+            registration.app_id
+        );
+        Ok(())
+    }
+
+    /// Runs the flow with `payload` as is, notifications go through [`S3EventSink::handle_notification`]
+    async fn handle_trigger<E: Executor>(
+        &self,
+        ctx: &SinkContext<E>,
+        registration: &SinkRegistration,
+        payload: Option<Value>,
+    ) -> SinkResult<TriggerResponse> {
+        let run_id = ctx.dispatch(registration, payload).await?;
+
+        Ok(TriggerResponse::success(Some(run_id)))
+    }
+}
+
+/// An object a notification is about, passed to the flow as its payload
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct S3Object {
+    pub bucket: String,
+
+    /// Object key, already URL-decoded
+    pub key: String,
+
+    /// Size in bytes, not set for deletions
+    pub size: Option<u64>,
+
+    /// Not set for deletions
+    pub etag: Option<String>,
+
+    pub version_id: Option<String>,
+
+    /// e.g. "ObjectCreated:Put"
+    pub event_name: String,
+
+    /// ISO 8601 timestamp of the event
+    pub event_time: Option<String>,
+
+    /// e.g. "eu-central-1"
+    pub region: Option<String>,
+}
+
+impl S3Object {
+    /// Check the object against the bucket, prefix and suffix filters of a sink
+    pub fn matches(&self, config: &S3EventSinkConfig) -> bool {
+        config.bucket.as_deref().is_none_or(|b| b == self.bucket)
+            && config
+                .prefix
+                .as_deref()
+                .is_none_or(|prefix| self.key.starts_with(prefix))
+            && config
+                .suffix
+                .as_deref()
+                .is_none_or(|suffix| self.key.ends_with(suffix))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct S3Record {
+    event_name: String,
+    event_time: Option<String>,
+    aws_region: Option<String>,
+    s3: S3Entity,
+}
+
+#[derive(Debug, Deserialize)]
+struct S3Entity {
+    bucket: S3Bucket,
+    object: S3ObjectEntity,
+}
+
+#[derive(Debug, Deserialize)]
+struct S3Bucket {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct S3ObjectEntity {
+    key: String,
+    size: Option<u64>,
+    e_tag: Option<String>,
+    version_id: Option<String>,
+}
+
+impl S3Record {
+    fn into_object(self) -> S3Object {
+        S3Object {
+            bucket: self.s3.bucket.name,
+            key: decode_key(&self.s3.object.key),
+            size: self.s3.object.size,
+            etag: self.s3.object.e_tag,
+            version_id: self.s3.object.version_id,
+            event_name: self.event_name,
+            event_time: self.event_time,
+            region: self.aws_region,
+        }
+    }
+}
+
+fn parse_json(raw: &str) -> SinkResult<Value> {
+    serde_json::from_str(raw)
+        .map_err(|e| SinkError::InvalidConfig(format!("Invalid notification body: {}", e)))
+}
+
+/// S3 form-encodes keys in notifications: spaces become '+' and everything else is %-escaped
+fn decode_key(key: &str) -> String {
+    let bytes = key.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                match std::str::from_utf8(&bytes[i + 1..i + 3])
+                    .ok()
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                {
+                    Some(byte) => {
+                        decoded.push(byte);
+                        i += 2;
+                    }
+                    None => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::SinkExecution;
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Default)]
+    struct RecordingExecutor {
+        payloads: Mutex<Vec<Option<Value>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl Executor for RecordingExecutor {
+        async fn execute_event(
+            &self,
+            _app_id: &str,
+            _board_id: &str,
+            _event_id: &str,
+            payload: Option<Value>,
+            _personal_access_token: Option<&str>,
+        ) -> SinkResult<String> {
+            let mut payloads = self.payloads.lock().unwrap();
+            payloads.push(payload);
+            Ok(format!("run-{}", payloads.len()))
+        }
+    }
+
+    fn registration(config: Value) -> SinkRegistration {
+        SinkRegistration {
+            id: "sink-1".to_string(),
+            event_id: "event-1".to_string(),
+            board_id: "board-1".to_string(),
+            app_id: "app-1".to_string(),
+            sink_type: SinkType::S3,
+            config,
+            execution: SinkExecution::Remote,
+            active: true,
+            auth_token: None,
+            path: None,
+            method: None,
+            cron_expression: None,
+            default_payload: None,
+            personal_access_token: None,
+            oauth_tokens: None,
+        }
+    }
+
+    fn s3_event(keys: &[(&str, u64, &str)]) -> Value {
+        let records: Vec<Value> = keys
+            .iter()
+            .map(|(key, size, etag)| {
+                json!({
+                    "eventVersion": "2.1",
+                    "eventSource": "aws:s3",
+                    "awsRegion": "eu-central-1",
+                    "eventTime": "2024-05-01T12:00:00.000Z",
+                    "eventName": "ObjectCreated:Put",
+                    "s3": {
+                        "s3SchemaVersion": "1.0",
+                        "configurationId": "flow-like",
+                        "bucket": {
+                            "name": "reports",
+                            "arn": "arn:aws:s3:::reports"
+                        },
+                        "object": {
+                            "key": key,
+                            "size": size,
+                            "eTag": etag,
+                            "sequencer": "0055AED6DCD90281E5"
+                        }
+                    }
+                })
+            })
+            .collect();
+        json!({ "Records": records })
+    }
+
+    /// An SQS batch of two messages, the first holding two S3 records and the second a test event
+    fn sqs_batch() -> Value {
+        let event = s3_event(&[
+            (
+                "uploads/2024/q1+report.csv",
+                1024,
+                "d41d8cd98f00b204e9800998ecf8427e",
+            ),
+            ("archive/old.csv", 2048, "9e107d9d372bb6826bd81d3542a419d6"),
+        ]);
+        let test_event = json!({
+            "Service": "Amazon S3",
+            "Event": "s3:TestEvent",
+            "Time": "2024-05-01T11:59:00.000Z",
+            "Bucket": "reports"
+        });
+        json!({
+            "Records": [
+                {
+                    "messageId": "059f36b4-87a3-44ab-83d2-661975830a7d",
+                    "receiptHandle": "AQEBwJnKyrHigUMZj6rYigCgxlaS3SLy0a",
+                    "body": event.to_string(),
+                    "eventSource": "aws:sqs",
+                    "eventSourceARN": "arn:aws:sqs:eu-central-1:123456789012:reports"
+                },
+                {
+                    "messageId": "2e1424d4-f796-459a-8184-9c92662be6da",
+                    "receiptHandle": "AQEBzWwaftRI0KuVm4tP+/7q1rGgNqicHq",
+                    "body": test_event.to_string(),
+                    "eventSource": "aws:sqs",
+                    "eventSourceARN": "arn:aws:sqs:eu-central-1:123456789012:reports"
+                }
+            ]
+        })
+    }
+
+    #[tokio::test]
+    async fn sqs_batch_triggers_once_per_record() {
+        let executor = Arc::new(RecordingExecutor::default());
+        let ctx = SinkContext::new(executor.clone());
+
+        let responses = S3EventSink::new()
+            .handle_notification(&ctx, &registration(json!({})), &sqs_batch())
+            .await
+            .unwrap();
+
+        assert_eq!(responses.len(), 2);
+        assert!(responses.iter().all(|response| response.triggered));
+
+        let payloads = executor.payloads.lock().unwrap();
+        let objects: Vec<S3Object> = payloads
+            .iter()
+            .map(|payload| serde_json::from_value(payload.clone().unwrap()).unwrap())
+            .collect();
+        assert_eq!(
+            objects[0],
+            S3Object {
+                bucket: "reports".to_string(),
+                key: "uploads/2024/q1 report.csv".to_string(),
+                size: Some(1024),
+                etag: Some("d41d8cd98f00b204e9800998ecf8427e".to_string()),
+                version_id: None,
+                event_name: "ObjectCreated:Put".to_string(),
+                event_time: Some("2024-05-01T12:00:00.000Z".to_string()),
+                region: Some("eu-central-1".to_string()),
+            }
+        );
+        assert_eq!(objects[1].key, "archive/old.csv");
+        assert_eq!(objects[1].size, Some(2048));
+    }
+
+    #[tokio::test]
+    async fn prefix_and_suffix_filter_records() {
+        let executor = Arc::new(RecordingExecutor::default());
+        let ctx = SinkContext::new(executor.clone());
+
+        let responses = S3EventSink::new()
+            .handle_notification(
+                &ctx,
+                &registration(json!({ "prefix": "uploads/", "suffix": ".csv" })),
+                &sqs_batch(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(responses.len(), 1);
+        let payloads = executor.payloads.lock().unwrap();
+        assert_eq!(
+            payloads[0].as_ref().unwrap()["key"],
+            "uploads/2024/q1 report.csv"
+        );
+    }
+
+    #[test]
+    fn unwraps_sns_envelope() {
+        let notification = json!({
+            "Type": "Notification",
+            "MessageId": "22b80b92-fdea-4c2c-8f9d-bdfb0c7bf324",
+            "TopicArn": "arn:aws:sns:eu-central-1:123456789012:reports",
+            "Message": s3_event(&[("a%2Fb%20c.txt", 5, "etag")]).to_string(),
+        });
+
+        let objects = S3EventSink::parse_notification(&notification).unwrap();
+        assert_eq!(objects.len(), 1);
+        assert_eq!(objects[0].key, "a/b c.txt");
+    }
+
+    #[test]
+    fn test_event_has_no_objects() {
+        let test_event =
+            json!({ "Service": "Amazon S3", "Event": "s3:TestEvent", "Bucket": "reports" });
+        assert!(
+            S3EventSink::parse_notification(&test_event)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn bucket_filter_skips_other_buckets() {
+        let objects = S3EventSink::parse_notification(&s3_event(&[("a.csv", 1, "e")])).unwrap();
+        let config = S3EventSinkConfig {
+            bucket: Some("invoices".to_string()),
+            ..Default::default()
+        };
+        assert!(!objects[0].matches(&config));
+        assert!(objects[0].matches(&S3EventSinkConfig::default()));
+    }
+}
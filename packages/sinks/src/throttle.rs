@@ -0,0 +1,528 @@
+//! Rate limiting and concurrency caps for sink triggered flows
+//!
+//! Every registration with a [`ThrottleConfig`] gets its own [`SinkThrottle`], looked up by
+//! registration id in the [`Throttles`] of the [`crate::SinkContext`]. Events beyond the
+//! budget are dropped or queued depending on the [`OverflowPolicy`], either way
+//! [`THROTTLED_TOTAL`] is incremented.
+//!
+//! Runs of executors that return before the flow finished keep their concurrency slot in
+//! [`Throttles::hold`] until [`Throttles::release`] is called with the run id, or
+//! [`MAX_HELD_RUN`] passed.
+
+use crate::{
+    config::{OverflowPolicy, RateLimit, ThrottleConfig},
+    traits::{SinkError, SinkResult},
+};
+use parking_lot::Mutex;
+use std::{collections::HashMap, sync::Arc, time::Duration};
+use tokio::{
+    sync::{OwnedSemaphorePermit, Semaphore},
+    time::Instant,
+};
+
+/// Counter of throttled events, labeled by `sink` type and `action` (dropped, queued)
+pub const THROTTLED_TOTAL: &str = "flow_like_sink_throttled_total";
+
+/// Held slots of runs that never report back are freed after this long, the lifetime of the
+/// executor token of a run
+pub const MAX_HELD_RUN: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Registers the description of [`THROTTLED_TOTAL`], call after installing the recorder.
+pub fn describe_metrics() {
+    ::metrics::describe_counter!(
+        THROTTLED_TOTAL,
+        "Sink events that exceeded their rate limit or concurrency cap, by action (dropped, queued)"
+    );
+}
+
+fn record_throttled(sink: &str, action: &'static str) {
+    ::metrics::counter!(THROTTLED_TOTAL, "sink" => sink.to_string(), "action" => action)
+        .increment(1);
+}
+
+/// Token bucket that refills continuously. Tokens may go negative while events are queued,
+/// which hands out start times in arrival order.
+#[derive(Debug)]
+struct TokenBucket {
+    capacity: f64,
+    refill_per_second: f64,
+    tokens: f64,
+    updated: Instant,
+}
+
+impl TokenBucket {
+    fn new(limit: &RateLimit) -> Self {
+        let capacity = f64::from(limit.max_events.max(1));
+        Self {
+            capacity,
+            refill_per_second: capacity / limit.per_seconds.max(f64::EPSILON),
+            tokens: capacity,
+            updated: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_per_second).min(self.capacity);
+        self.updated = now;
+    }
+
+    /// Takes a token if one is available
+    fn try_take(&mut self, now: Instant) -> bool {
+        self.refill(now);
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Takes a token in any case and returns how long to wait until it is covered
+    fn reserve(&mut self, now: Instant) -> Duration {
+        self.refill(now);
+        self.tokens -= 1.0;
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / self.refill_per_second)
+        }
+    }
+}
+
+/// Budget of a single registration
+#[derive(Debug)]
+pub struct SinkThrottle {
+    config: ThrottleConfig,
+    bucket: Option<Mutex<TokenBucket>>,
+    slots: Option<Arc<Semaphore>>,
+}
+
+/// Held while a throttled flow runs, frees its concurrency slot on drop
+#[derive(Debug)]
+pub struct ThrottlePermit {
+    _slot: Option<OwnedSemaphorePermit>,
+}
+
+impl SinkThrottle {
+    pub fn new(config: ThrottleConfig) -> Self {
+        Self {
+            bucket: config
+                .rate_limit
+                .as_ref()
+                .map(|limit| Mutex::new(TokenBucket::new(limit))),
+            slots: config
+                .max_concurrent
+                .map(|max| Arc::new(Semaphore::new(max.max(1)))),
+            config,
+        }
+    }
+
+    pub fn config(&self) -> &ThrottleConfig {
+        &self.config
+    }
+
+    /// Waits for (or, with [`OverflowPolicy::Drop`], checks) the budget of one event.
+    /// `sink` is the metric label.
+    pub async fn acquire(&self, sink: &str) -> SinkResult<ThrottlePermit> {
+        match self.config.overflow {
+            OverflowPolicy::Drop => self.try_acquire(sink),
+            OverflowPolicy::Queue => Ok(self.acquire_queued(sink).await),
+        }
+    }
+
+    fn try_acquire(&self, sink: &str) -> SinkResult<ThrottlePermit> {
+        // Claim the slot first, a token taken for an event that is dropped anyway is lost
+        let slot = match &self.slots {
+            Some(slots) => match slots.clone().try_acquire_owned() {
+                Ok(permit) => Some(permit),
+                Err(_) => {
+                    record_throttled(sink, "dropped");
+                    return Err(SinkError::Throttled(format!(
+                        "{} triggered flows are already running",
+                        self.config.max_concurrent.unwrap_or_default()
+                    )));
+                }
+            },
+            None => None,
+        };
+
+        if let Some(bucket) = &self.bucket
+            && !bucket.lock().try_take(Instant::now())
+        {
+            record_throttled(sink, "dropped");
+            return Err(SinkError::Throttled("Rate limit exceeded".to_string()));
+        }
+
+        Ok(ThrottlePermit { _slot: slot })
+    }
+
+    async fn acquire_queued(&self, sink: &str) -> ThrottlePermit {
+        let mut queued = false;
+
+        if let Some(bucket) = &self.bucket {
+            let wait = bucket.lock().reserve(Instant::now());
+            if !wait.is_zero() {
+                queued = true;
+                tokio::time::sleep(wait).await;
+            }
+        }
+
+        let slot = match &self.slots {
+            Some(slots) => {
+                let permit = match slots.clone().try_acquire_owned() {
+                    Ok(permit) => permit,
+                    Err(_) => {
+                        queued = true;
+                        slots
+                            .clone()
+                            .acquire_owned()
+                            .await
+                            .expect("throttle semaphore is never closed")
+                    }
+                };
+                Some(permit)
+            }
+            None => None,
+        };
+
+        if queued {
+            record_throttled(sink, "queued");
+        }
+
+        ThrottlePermit { _slot: slot }
+    }
+}
+
+/// Throttles of all registrations, keyed by registration id
+#[derive(Debug, Default)]
+pub struct Throttles {
+    throttles: Mutex<HashMap<String, Arc<SinkThrottle>>>,
+    /// Permits of runs that are still going, keyed by run id
+    running: Mutex<HashMap<String, ThrottlePermit>>,
+}
+
+impl Throttles {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the throttle of a registration, `None` if it has no limits configured.
+    /// A changed config replaces the throttle, flows running under the old one keep their slot.
+    pub fn get(&self, registration_id: &str, config: &ThrottleConfig) -> Option<Arc<SinkThrottle>> {
+        let mut throttles = self.throttles.lock();
+        if !config.is_limited() {
+            throttles.remove(registration_id);
+            return None;
+        }
+
+        if let Some(throttle) = throttles.get(registration_id)
+            && throttle.config() == config
+        {
+            return Some(throttle.clone());
+        }
+
+        let throttle = Arc::new(SinkThrottle::new(config.clone()));
+        throttles.insert(registration_id.to_string(), throttle.clone());
+        Some(throttle)
+    }
+
+    /// Forgets the throttle of an unregistered sink
+    pub fn remove(&self, registration_id: &str) {
+        self.throttles.lock().remove(registration_id);
+    }
+
+    /// Keeps the permit of a run that is still going until [`Throttles::release`] is called
+    /// for it, at most for `ttl`
+    pub fn hold(self: &Arc<Self>, run_id: String, permit: ThrottlePermit, ttl: Duration) {
+        self.running.lock().insert(run_id.clone(), permit);
+
+        let throttles = Arc::downgrade(self);
+        tokio::spawn(async move {
+            tokio::time::sleep(ttl).await;
+            if let Some(throttles) = throttles.upgrade()
+                && throttles.release(&run_id)
+            {
+                tracing::warn!(
+                    "Freed the throttle slot of run {} that never finished",
+                    run_id
+                );
+            }
+        });
+    }
+
+    /// Frees the slot of a finished run, returns whether one was held
+    pub fn release(&self, run_id: &str) -> bool {
+        self.running.lock().remove(run_id).is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        http::HttpSink,
+        traits::{Executor, SinkContext, SinkTrait},
+        types::{SinkExecution, SinkRegistration, SinkType},
+    };
+    use serde_json::json;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Takes `delay` per run and tracks how many runs overlap
+    struct SlowExecutor {
+        delay: Duration,
+        running: AtomicUsize,
+        peak: AtomicUsize,
+        starts: Mutex<Vec<Instant>>,
+    }
+
+    impl SlowExecutor {
+        fn new(delay: Duration) -> Self {
+            Self {
+                delay,
+                running: AtomicUsize::new(0),
+                peak: AtomicUsize::new(0),
+                starts: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl Executor for SlowExecutor {
+        async fn execute_event(
+            &self,
+            _app_id: &str,
+            _board_id: &str,
+            _event_id: &str,
+            _payload: Option<flow_like_types::Value>,
+            _personal_access_token: Option<&str>,
+        ) -> SinkResult<String> {
+            self.starts.lock().push(Instant::now());
+            let running = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(running, Ordering::SeqCst);
+            tokio::time::sleep(self.delay).await;
+            self.running.fetch_sub(1, Ordering::SeqCst);
+            Ok("run-1".to_string())
+        }
+    }
+
+    fn registration(throttle: flow_like_types::Value) -> SinkRegistration {
+        let mut config = json!({ "path": "/hook", "method": "POST" });
+        config
+            .as_object_mut()
+            .unwrap()
+            .extend(throttle.as_object().unwrap().clone());
+
+        SinkRegistration {
+            id: "sink-1".to_string(),
+            event_id: "event-1".to_string(),
+            board_id: "board-1".to_string(),
+            app_id: "app-1".to_string(),
+            sink_type: SinkType::Http,
+            config,
+            execution: SinkExecution::Remote,
+            active: true,
+            auth_token: None,
+            path: Some("/hook".to_string()),
+            method: Some("POST".to_string()),
+            cron_expression: None,
+            default_payload: None,
+            personal_access_token: None,
+            oauth_tokens: None,
+        }
+    }
+
+    /// Fires `count` triggers at once and returns the result of each
+    async fn burst(
+        ctx: &Arc<SinkContext<SlowExecutor>>,
+        registration: &SinkRegistration,
+        count: usize,
+    ) -> Vec<SinkResult<crate::TriggerResponse>> {
+        let handles: Vec<_> = (0..count)
+            .map(|i| {
+                let ctx = ctx.clone();
+                let registration = registration.clone();
+                tokio::spawn(async move {
+                    HttpSink::new()
+                        .handle_trigger(&ctx, &registration, Some(json!({ "n": i })))
+                        .await
+                })
+            })
+            .collect();
+
+        let mut results = Vec::new();
+        for handle in handles {
+            results.push(handle.await.unwrap());
+        }
+        results
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_queued_burst_respects_concurrency_cap() {
+        let ctx = Arc::new(SinkContext::new(Arc::new(SlowExecutor::new(
+            Duration::from_millis(100),
+        ))));
+        let registration = registration(json!({ "max_concurrent": 3, "overflow": "queue" }));
+
+        let results = burst(&ctx, &registration, 20).await;
+
+        assert!(results.iter().all(|r| r.as_ref().unwrap().triggered));
+        assert_eq!(ctx.executor.peak.load(Ordering::SeqCst), 3);
+        assert_eq!(ctx.executor.running.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_dropped_burst_beyond_concurrency_cap() {
+        let ctx = Arc::new(SinkContext::new(Arc::new(SlowExecutor::new(
+            Duration::from_millis(100),
+        ))));
+        let registration = registration(json!({ "max_concurrent": 2 }));
+
+        let results = burst(&ctx, &registration, 10).await;
+
+        let triggered = results.iter().filter(|r| r.is_ok()).count();
+        assert_eq!(triggered, 2);
+        for error in results.iter().filter_map(|r| r.as_ref().err()) {
+            assert!(matches!(error, SinkError::Throttled(_)));
+            assert_eq!(error.status_code(), 429);
+        }
+        assert_eq!(ctx.executor.peak.load(Ordering::SeqCst), 2);
+
+        // Slots are free again once the runs finished
+        let results = burst(&ctx, &registration, 2).await;
+        assert!(results.iter().all(|r| r.is_ok()));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_queued_rate_limit_spaces_dispatches() {
+        let ctx = Arc::new(SinkContext::new(Arc::new(SlowExecutor::new(
+            Duration::ZERO,
+        ))));
+        let registration = registration(json!({
+            "rate_limit": { "max_events": 5, "per_seconds": 1.0 },
+            "overflow": "queue"
+        }));
+
+        let start = Instant::now();
+        let results = burst(&ctx, &registration, 15).await;
+        assert!(results.iter().all(|r| r.is_ok()));
+
+        let mut offsets: Vec<Duration> = ctx
+            .executor
+            .starts
+            .lock()
+            .iter()
+            .map(|at| at.duration_since(start))
+            .collect();
+        offsets.sort();
+
+        // The bucket allows a burst of 5, afterwards one event every 200ms
+        assert!(offsets[..5].iter().all(|offset| offset.is_zero()));
+        for (i, offset) in offsets.iter().enumerate().skip(5) {
+            let expected = Duration::from_millis(200 * (i as u64 - 4));
+            assert!(
+                offset.abs_diff(expected) < Duration::from_millis(5),
+                "event {} started after {:?}, expected {:?}",
+                i,
+                offset,
+                expected
+            );
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_dropped_events_beyond_rate_limit() {
+        let ctx = Arc::new(SinkContext::new(Arc::new(SlowExecutor::new(
+            Duration::ZERO,
+        ))));
+        let registration =
+            registration(json!({ "rate_limit": { "max_events": 5, "per_seconds": 1.0 } }));
+
+        let results = burst(&ctx, &registration, 8).await;
+        assert_eq!(results.iter().filter(|r| r.is_ok()).count(), 5);
+
+        tokio::time::advance(Duration::from_millis(400)).await;
+        let results = burst(&ctx, &registration, 3).await;
+        assert_eq!(results.iter().filter(|r| r.is_ok()).count(), 2);
+    }
+
+    /// Returns as soon as the run is queued, like the executors of the API
+    struct QueueingExecutor {
+        queued: AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl Executor for QueueingExecutor {
+        async fn execute_event(
+            &self,
+            _app_id: &str,
+            _board_id: &str,
+            _event_id: &str,
+            _payload: Option<flow_like_types::Value>,
+            _personal_access_token: Option<&str>,
+        ) -> SinkResult<String> {
+            let n = self.queued.fetch_add(1, Ordering::SeqCst);
+            Ok(format!("run-{}", n))
+        }
+
+        fn returns_before_completion(&self) -> bool {
+            true
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_queued_runs_keep_their_slot_until_released() {
+        let throttles = Arc::new(Throttles::new());
+        let executor = Arc::new(QueueingExecutor {
+            queued: AtomicUsize::new(0),
+        });
+        let registration = registration(json!({ "max_concurrent": 2 }));
+        // Every delivery builds its own context, the throttles are shared
+        let context = || SinkContext::with_throttles(executor.clone(), throttles.clone());
+
+        let first = context().dispatch(&registration, None).await.unwrap();
+        context().dispatch(&registration, None).await.unwrap();
+        let error = context().dispatch(&registration, None).await.unwrap_err();
+        assert!(matches!(error, SinkError::Throttled(_)));
+
+        assert!(throttles.release(&first));
+        assert!(!throttles.release(&first));
+        context().dispatch(&registration, None).await.unwrap();
+        assert!(context().dispatch(&registration, None).await.is_err());
+
+        // Runs that never report back give their slot up eventually
+        tokio::time::sleep(MAX_HELD_RUN + Duration::from_secs(1)).await;
+        context().dispatch(&registration, None).await.unwrap();
+        context().dispatch(&registration, None).await.unwrap();
+    }
+
+    #[test]
+    fn test_throttles_follow_config() {
+        let throttles = Throttles::new();
+        assert!(
+            throttles
+                .get("sink-1", &ThrottleConfig::default())
+                .is_none()
+        );
+
+        let config: ThrottleConfig =
+            serde_json::from_value(json!({ "max_concurrent": 2 })).unwrap();
+        let first = throttles.get("sink-1", &config).unwrap();
+        assert!(Arc::ptr_eq(
+            &first,
+            &throttles.get("sink-1", &config).unwrap()
+        ));
+
+        let changed: ThrottleConfig =
+            serde_json::from_value(json!({ "max_concurrent": 4, "overflow": "queue" })).unwrap();
+        let second = throttles.get("sink-1", &changed).unwrap();
+        assert!(!Arc::ptr_eq(&first, &second));
+        assert_eq!(second.config().overflow, OverflowPolicy::Queue);
+
+        assert!(
+            throttles
+                .get("sink-1", &ThrottleConfig::default())
+                .is_none()
+        );
+    }
+}
@@ -1,6 +1,10 @@
 //! Sink trait definitions
 
-use crate::types::{SinkRegistration, SinkType};
+use crate::{
+    config::ThrottleConfig,
+    throttle::{MAX_HELD_RUN, ThrottlePermit, Throttles},
+    types::{SinkRegistration, SinkType},
+};
 use std::sync::Arc;
 
 /// Result type for sink operations
@@ -24,6 +28,9 @@ pub enum SinkError {
     #[error("Execution failed: {0}")]
     ExecutionFailed(String),
 
+    #[error("Throttled: {0}")]
+    Throttled(String),
+
     #[error("Database error: {0}")]
     Database(String),
 
@@ -31,6 +38,20 @@ pub enum SinkError {
     Internal(String),
 }
 
+impl SinkError {
+    /// HTTP status code an endpoint should answer with for this error
+    pub fn status_code(&self) -> u16 {
+        match self {
+            Self::NotFound(_) => 404,
+            Self::AlreadyExists(_) => 409,
+            Self::InvalidConfig(_) => 400,
+            Self::AuthFailed(_) => 401,
+            Self::Throttled(_) => 429,
+            Self::ExecutionFailed(_) | Self::Database(_) | Self::Internal(_) => 500,
+        }
+    }
+}
+
 /// Response from triggering a sink
 #[derive(Debug, Clone)]
 pub struct TriggerResponse {
@@ -80,6 +101,79 @@ impl TriggerResponse {
 pub struct SinkContext<E: Executor> {
     /// The executor for running flows
     pub executor: Arc<E>,
+
+    /// Rate limits and concurrency caps of the registrations
+    pub throttles: Arc<Throttles>,
+}
+
+impl<E: Executor> SinkContext<E> {
+    pub fn new(executor: Arc<E>) -> Self {
+        Self::with_throttles(executor, Arc::new(Throttles::new()))
+    }
+
+    /// A context that shares its throttles with other contexts. Limits only hold across
+    /// events that go through the same [`Throttles`].
+    pub fn with_throttles(executor: Arc<E>, throttles: Arc<Throttles>) -> Self {
+        Self {
+            executor,
+            throttles,
+        }
+    }
+
+    /// Claims the budget of one event of a registration, `None` if it has no limits.
+    /// The concurrency slot is freed when the permit is dropped.
+    pub async fn acquire(
+        &self,
+        registration: &SinkRegistration,
+    ) -> SinkResult<Option<ThrottlePermit>> {
+        let config = ThrottleConfig::from_config(&registration.config)
+            .map_err(|e| SinkError::InvalidConfig(format!("Invalid throttle config: {}", e)))?;
+
+        let Some(throttle) = self.throttles.get(&registration.id, &config) else {
+            return Ok(None);
+        };
+        let permit = throttle
+            .acquire(registration.sink_type.as_str())
+            .await
+            .inspect_err(|e| {
+                tracing::warn!(
+                    "Dropped event {} of {} sink (app: {}): {}",
+                    registration.event_id,
+                    registration.sink_type,
+                    registration.app_id,
+                    e
+                )
+            })?;
+        Ok(Some(permit))
+    }
+
+    /// Runs the event of a registration within its [`ThrottleConfig`] and returns the run id.
+    /// Sinks trigger flows through here, so the limits apply to all of them alike.
+    pub async fn dispatch(
+        &self,
+        registration: &SinkRegistration,
+        payload: Option<flow_like_types::Value>,
+    ) -> SinkResult<String> {
+        let permit = self.acquire(registration).await?;
+
+        let run_id = self
+            .executor
+            .execute_event(
+                &registration.app_id,
+                &registration.board_id,
+                &registration.event_id,
+                payload,
+                registration.personal_access_token.as_deref(),
+            )
+            .await?;
+
+        if let Some(permit) = permit
+            && self.executor.returns_before_completion()
+        {
+            self.throttles.hold(run_id.clone(), permit, MAX_HELD_RUN);
+        }
+        Ok(run_id)
+    }
 }
 
 /// Executor trait that sinks use to trigger flow execution
@@ -94,6 +188,13 @@ pub trait Executor: Send + Sync {
         payload: Option<flow_like_types::Value>,
         personal_access_token: Option<&str>,
     ) -> SinkResult<String>;
+
+    /// Whether `execute_event` returns once the run is queued instead of finished.
+    /// [`SinkContext::dispatch`] then keeps the concurrency slot of the run until
+    /// [`Throttles::release`] is called with its run id.
+    fn returns_before_completion(&self) -> bool {
+        false
+    }
 }
 
 /// Trait for sink implementations
@@ -0,0 +1,452 @@
+//! Twilio inbound SMS sink
+//!
+//! Twilio posts incoming messages form-encoded to the webhook URL of a phone number.
+//! Deliveries are verified against the `X-Twilio-Signature` header and handed to the
+//! flow as a [`TwilioInboundMessage`].
+
+use crate::{
+    config::TwilioSinkConfig,
+    traits::{Executor, SinkContext, SinkError, SinkResult, SinkTrait, TriggerResponse},
+    types::{SinkRegistration, SinkType},
+};
+use base64::{Engine, engine::general_purpose::STANDARD};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha1::Sha1;
+use std::collections::BTreeMap;
+
+type HmacSha1 = Hmac<Sha1>;
+
+/// TwiML that acknowledges a message without replying to the sender
+pub const EMPTY_TWIML: &str = r#"<?xml version="1.0" encoding="UTF-8"?><Response></Response>"#;
+
+/// Twilio sink for inbound SMS and MMS
+#[derive(Debug, Clone, Default)]
+pub struct TwilioSink;
+
+impl TwilioSink {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Parse and validate Twilio sink config from JSON
+    pub fn parse_config(config: &flow_like_types::Value) -> SinkResult<TwilioSinkConfig> {
+        serde_json::from_value(config.clone())
+            .map_err(|e| SinkError::InvalidConfig(format!("Invalid Twilio config: {}", e)))
+    }
+
+    /// Verify a webhook delivery and run the flow with the parsed message
+    ///
+    /// `auth_token` is the auth token of the account, deliveries are refused when it is empty.
+    pub async fn handle_webhook<E: Executor>(
+        &self,
+        ctx: &SinkContext<E>,
+        registration: &SinkRegistration,
+        auth_token: &str,
+        request: &TwilioWebhookRequest,
+    ) -> SinkResult<TriggerResponse> {
+        if auth_token.is_empty() {
+            return Err(SinkError::AuthFailed(
+                "Twilio webhook has no auth token configured".to_string(),
+            ));
+        }
+        let config = Self::parse_config(&registration.config)?;
+        request.verify_signature(auth_token)?;
+
+        let message = request.parse_message()?;
+        if let Some(expected) = &config.account_sid
+            && &message.account_sid != expected
+        {
+            return Err(SinkError::AuthFailed(format!(
+                "Delivery is for account {}, expected {}",
+                message.account_sid, expected
+            )));
+        }
+
+        tracing::info!(
+            "Twilio sink triggered by message {} for event {} (app: {})",
+            message.message_sid,
+            registration.event_id,
+            registration.app_id
+        );
+
+        let payload = serde_json::to_value(&message)
+            .map_err(|e| SinkError::Internal(format!("Failed to serialize message: {}", e)))?;
+        self.handle_trigger(ctx, registration, Some(payload)).await
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkTrait for TwilioSink {
+    fn sink_type(&self) -> SinkType {
+        SinkType::Twilio
+    }
+
+    fn validate_config(&self, config: &flow_like_types::Value) -> SinkResult<()> {
+        let cfg = Self::parse_config(config)?;
+        if let Some(account_sid) = &cfg.account_sid
+            && !account_sid.starts_with("AC")
+        {
+            return Err(SinkError::InvalidConfig(format!(
+                "Invalid Twilio account SID: '{}'",
+                account_sid
+            )));
+        }
+        Ok(())
+    }
+
+    async fn register<E: Executor>(
+        &self,
+        _ctx: &SinkContext<E>,
+        registration: &SinkRegistration,
+    ) -> SinkResult<()> {
+        self.validate_config(&registration.config)?;
+
+        tracing::info!(
+            "Registered Twilio sink for event {} (app: {})",
+            registration.event_id,
+            registration.app_id
+        );
+
+        Ok(())
+    }
+
+    async fn unregister<E: Executor>(
+        &self,
+        _ctx: &SinkContext<E>,
+        registration: &SinkRegistration,
+    ) -> SinkResult<()> {
+        tracing::info!(
+            "Unregistered Twilio sink for event {} (app: {})",
+            registration.event_id,
+            registration.app_id
+        );
+        Ok(())
+    }
+
+    /// Runs the flow with `payload` as is, webhook deliveries go through [`TwilioSink::handle_webhook`]
+    async fn handle_trigger<E: Executor>(
+        &self,
+        ctx: &SinkContext<E>,
+        registration: &SinkRegistration,
+        payload: Option<flow_like_types::Value>,
+    ) -> SinkResult<TriggerResponse> {
+        let run_id = ctx.dispatch(registration, payload).await?;
+
+        Ok(TriggerResponse::success(Some(run_id)))
+    }
+}
+
+/// An incoming webhook delivery
+#[derive(Debug, Clone)]
+pub struct TwilioWebhookRequest {
+    /// Full URL Twilio called, including the query string. Behind a proxy this is
+    /// the public URL configured on the phone number, not the one the server sees
+    pub url: String,
+
+    /// `X-Twilio-Signature` header
+    pub signature: Option<String>,
+
+    /// Raw `application/x-www-form-urlencoded` body
+    pub body: Vec<u8>,
+}
+
+impl TwilioWebhookRequest {
+    pub fn params(&self) -> Vec<(String, String)> {
+        form_urlencoded::parse(&self.body).into_owned().collect()
+    }
+
+    /// Check the signature: base64 HMAC-SHA1 over the URL followed by every
+    /// parameter name and value, sorted by name, keyed with the auth token
+    pub fn verify_signature(&self, auth_token: &str) -> SinkResult<()> {
+        let signature = self
+            .signature
+            .as_deref()
+            .ok_or_else(|| SinkError::AuthFailed("Missing X-Twilio-Signature".to_string()))?;
+        let signature = STANDARD
+            .decode(signature)
+            .map_err(|_| SinkError::AuthFailed("Malformed X-Twilio-Signature".to_string()))?;
+
+        let mut params = self.params();
+        params.sort();
+
+        let mut mac = HmacSha1::new_from_slice(auth_token.as_bytes())
+            .map_err(|e| SinkError::Internal(format!("Invalid auth token: {}", e)))?;
+        mac.update(self.url.as_bytes());
+        for (name, value) in &params {
+            mac.update(name.as_bytes());
+            mac.update(value.as_bytes());
+        }
+        mac.verify_slice(&signature)
+            .map_err(|_| SinkError::AuthFailed("Invalid webhook signature".to_string()))
+    }
+
+    pub fn parse_message(&self) -> SinkResult<TwilioInboundMessage> {
+        let params: BTreeMap<String, String> = self.params().into_iter().collect();
+        let field = |name: &str| params.get(name).cloned();
+        let required = |name: &str| {
+            field(name).ok_or_else(|| {
+                SinkError::InvalidConfig(format!("Webhook body is missing {}", name))
+            })
+        };
+
+        // An MMS carries at most 10 attachments
+        let num_media: usize = field("NumMedia")
+            .and_then(|count| count.parse().ok())
+            .unwrap_or(0)
+            .min(10);
+        let media = (0..num_media)
+            .filter_map(|i| {
+                Some(TwilioMedia {
+                    url: field(&format!("MediaUrl{}", i))?,
+                    content_type: field(&format!("MediaContentType{}", i)),
+                })
+            })
+            .collect();
+
+        Ok(TwilioInboundMessage {
+            message_sid: field("MessageSid")
+                .or_else(|| field("SmsSid"))
+                .ok_or_else(|| {
+                    SinkError::InvalidConfig("Webhook body is missing MessageSid".to_string())
+                })?,
+            account_sid: required("AccountSid")?,
+            from: required("From")?,
+            to: required("To")?,
+            body: field("Body").unwrap_or_default(),
+            media,
+            status: field("SmsStatus").or_else(|| field("MessageStatus")),
+            error_code: field("ErrorCode").filter(|code| !code.is_empty()),
+            params,
+        })
+    }
+}
+
+/// A message sent to one of the account's numbers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TwilioInboundMessage {
+    pub message_sid: String,
+
+    pub account_sid: String,
+
+    /// Sender in E.164 format, e.g. "+15558675310"
+    pub from: String,
+
+    /// The Twilio number the message was sent to
+    pub to: String,
+
+    pub body: String,
+
+    /// Attachments of an MMS, Twilio hosts them until the message is deleted
+    pub media: Vec<TwilioMedia>,
+
+    /// e.g. "received", or the delivery status when used as a status callback
+    pub status: Option<String>,
+
+    /// Twilio error code of a failed delivery, e.g. "30003" for an unreachable handset
+    pub error_code: Option<String>,
+
+    /// Every parameter Twilio sent, including FromCity, FromCountry and similar
+    pub params: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TwilioMedia {
+    pub url: String,
+    pub content_type: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::SinkExecution;
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+
+    const AUTH_TOKEN: &str = "12345";
+    const URL: &str = "https://mycompany.com/myapp.php?foo=1&bar=2";
+
+    #[derive(Default)]
+    struct RecordingExecutor {
+        payloads: Mutex<Vec<Option<flow_like_types::Value>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl Executor for RecordingExecutor {
+        async fn execute_event(
+            &self,
+            _app_id: &str,
+            _board_id: &str,
+            _event_id: &str,
+            payload: Option<flow_like_types::Value>,
+            _personal_access_token: Option<&str>,
+        ) -> SinkResult<String> {
+            self.payloads.lock().unwrap().push(payload);
+            Ok("run-1".to_string())
+        }
+    }
+
+    fn registration() -> SinkRegistration {
+        SinkRegistration {
+            id: "sink-1".to_string(),
+            event_id: "event-1".to_string(),
+            board_id: "board-1".to_string(),
+            app_id: "app-1".to_string(),
+            sink_type: SinkType::Twilio,
+            config: json!({
+                "account_sid": "ACXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX",
+            }),
+            execution: SinkExecution::Remote,
+            active: true,
+            auth_token: None,
+            path: None,
+            method: None,
+            cron_expression: None,
+            default_payload: None,
+            personal_access_token: None,
+            oauth_tokens: None,
+        }
+    }
+
+    /// The example from Twilio's webhook security docs
+    fn docs_request(signature: &str) -> TwilioWebhookRequest {
+        TwilioWebhookRequest {
+            url: URL.to_string(),
+            signature: Some(signature.to_string()),
+            body: b"CallSid=CA1234567890ABCDE&Caller=%2B12349013030&Digits=1234&From=%2B12349013030&To=%2B18005551212"
+                .to_vec(),
+        }
+    }
+
+    fn inbound_mms(auth_token: &str) -> TwilioWebhookRequest {
+        let body = form_urlencoded::Serializer::new(String::new())
+            .append_pair("ToCountry", "US")
+            .append_pair("MediaContentType0", "image/jpeg")
+            .append_pair("SmsMessageSid", "MM09e9d3b8f0e74f0d8c1b1b2e4c3d2a10")
+            .append_pair("NumMedia", "1")
+            .append_pair("FromCity", "SAN FRANCISCO")
+            .append_pair("Body", "Here is the photo & the receipt")
+            .append_pair("FromCountry", "US")
+            .append_pair("To", "+15017122661")
+            .append_pair("MessageSid", "MM09e9d3b8f0e74f0d8c1b1b2e4c3d2a10")
+            .append_pair("AccountSid", "ACXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX")
+            .append_pair("From", "+15558675310")
+            .append_pair(
+                "MediaUrl0",
+                "https://api.twilio.com/2010-04-01/Accounts/ACXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX/Messages/MM09e9d3b8f0e74f0d8c1b1b2e4c3d2a10/Media/ME0123456789abcdef0123456789abcdef",
+            )
+            .append_pair("SmsStatus", "received")
+            .append_pair("ApiVersion", "2010-04-01")
+            .finish()
+            .into_bytes();
+
+        let mut request = TwilioWebhookRequest {
+            url: "https://api.flow-like.com/sink/trigger/twilio/event-1".to_string(),
+            signature: None,
+            body,
+        };
+        request.signature = Some(sign(&request, auth_token));
+        request
+    }
+
+    fn sign(request: &TwilioWebhookRequest, auth_token: &str) -> String {
+        let mut params = request.params();
+        params.sort();
+        let mut mac = HmacSha1::new_from_slice(auth_token.as_bytes()).unwrap();
+        mac.update(request.url.as_bytes());
+        for (name, value) in params {
+            mac.update(name.as_bytes());
+            mac.update(value.as_bytes());
+        }
+        STANDARD.encode(mac.finalize().into_bytes())
+    }
+
+    #[test]
+    fn verifies_signature_from_twilio_docs() {
+        let request = docs_request("0/KCTR6DLpKmkAf8muzZqo1nDgQ=");
+        assert!(request.verify_signature(AUTH_TOKEN).is_ok());
+    }
+
+    #[test]
+    fn rejects_invalid_signatures() {
+        let tampered = docs_request("GvWf1cFY/Q7PnoempGyD5oXAezc=");
+        let error = tampered.verify_signature(AUTH_TOKEN).unwrap_err();
+        assert!(matches!(error, SinkError::AuthFailed(_)));
+        assert_eq!(error.status_code(), 401);
+
+        assert!(
+            docs_request("0/KCTR6DLpKmkAf8muzZqo1nDgQ=")
+                .verify_signature("wrong token")
+                .is_err()
+        );
+
+        let mut unsigned = docs_request("");
+        unsigned.signature = None;
+        assert!(unsigned.verify_signature(AUTH_TOKEN).is_err());
+    }
+
+    #[test]
+    fn parses_form_encoded_message() {
+        let message = inbound_mms(AUTH_TOKEN).parse_message().unwrap();
+
+        assert_eq!(message.message_sid, "MM09e9d3b8f0e74f0d8c1b1b2e4c3d2a10");
+        assert_eq!(message.from, "+15558675310");
+        assert_eq!(message.to, "+15017122661");
+        assert_eq!(message.body, "Here is the photo & the receipt");
+        assert_eq!(message.status.as_deref(), Some("received"));
+        assert_eq!(message.error_code, None);
+        assert_eq!(message.media.len(), 1);
+        assert_eq!(message.media[0].content_type.as_deref(), Some("image/jpeg"));
+        assert!(
+            message.media[0]
+                .url
+                .ends_with("/Media/ME0123456789abcdef0123456789abcdef")
+        );
+        assert_eq!(message.params["FromCity"], "SAN FRANCISCO");
+    }
+
+    #[test]
+    fn missing_sender_is_rejected() {
+        let request = TwilioWebhookRequest {
+            url: URL.to_string(),
+            signature: None,
+            body: b"MessageSid=SM1&AccountSid=AC1&To=%2B15017122661".to_vec(),
+        };
+        assert!(request.parse_message().is_err());
+    }
+
+    #[tokio::test]
+    async fn webhook_runs_flow_with_message() {
+        let executor = Arc::new(RecordingExecutor::default());
+        let ctx = SinkContext::new(executor.clone());
+
+        let response = TwilioSink::new()
+            .handle_webhook(&ctx, &registration(), AUTH_TOKEN, &inbound_mms(AUTH_TOKEN))
+            .await
+            .unwrap();
+        assert!(response.triggered);
+
+        let error = TwilioSink::new()
+            .handle_webhook(
+                &ctx,
+                &registration(),
+                AUTH_TOKEN,
+                &inbound_mms("other token"),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(error, SinkError::AuthFailed(_)));
+
+        let error = TwilioSink::new()
+            .handle_webhook(&ctx, &registration(), "", &inbound_mms(""))
+            .await
+            .unwrap_err();
+        assert!(matches!(error, SinkError::AuthFailed(_)));
+
+        let payloads = executor.payloads.lock().unwrap();
+        assert_eq!(payloads.len(), 1);
+        let message: TwilioInboundMessage =
+            serde_json::from_value(payloads[0].clone().unwrap()).unwrap();
+        assert_eq!(message.body, "Here is the photo & the receipt");
+    }
+}
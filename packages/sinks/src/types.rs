@@ -42,6 +42,12 @@ pub enum SinkType {
     WebWatcher,
     /// Notion database polling
     Notion,
+    /// S3 event notifications, delivered through SQS or SNS
+    S3,
+    /// Twilio inbound SMS/MMS webhook
+    Twilio,
+    /// AMQP (RabbitMQ) queue consumer
+    Amqp,
 }
 
 impl SinkType {
@@ -66,6 +72,9 @@ impl SinkType {
             Self::Mcp => "mcp",
             Self::WebWatcher => "web_watcher",
             Self::Notion => "notion",
+            Self::S3 => "s3",
+            Self::Twilio => "twilio",
+            Self::Amqp => "amqp",
         }
     }
 
@@ -80,13 +89,17 @@ impl SinkType {
                 | Self::GitHub
                 | Self::Rss
                 | Self::Email
+                | Self::S3
+                | Self::Twilio
+                | Self::Amqp
         )
     }
 
     /// Check if this sink type is available on desktop
     pub fn is_desktop_available(&self) -> bool {
-        // All sink types are available on desktop
-        true
+        // Bucket notifications are delivered to cloud queues the desktop app can't subscribe to,
+        // and AMQP consumers need a connection that stays up while the app is closed
+        !matches!(self, Self::S3 | Self::Amqp)
     }
 
     /// Get the availability of this sink type
@@ -130,6 +143,9 @@ impl std::str::FromStr for SinkType {
             "mcp" => Ok(Self::Mcp),
             "web_watcher" => Ok(Self::WebWatcher),
             "notion" => Ok(Self::Notion),
+            "s3" => Ok(Self::S3),
+            "twilio" => Ok(Self::Twilio),
+            "amqp" | "rabbitmq" => Ok(Self::Amqp),
             _ => Err(format!("Unknown sink type: {}", s)),
         }
     }
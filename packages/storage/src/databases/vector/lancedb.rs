@@ -2,7 +2,7 @@ use arrow_array::{RecordBatch, RecordBatchIterator};
 use datafusion::prelude::*;
 use flow_like_types::Cacheable;
 use flow_like_types::async_trait;
-use flow_like_types::{Result, Value, anyhow};
+use flow_like_types::{JsonSchema, Result, Value, anyhow};
 use futures::TryStreamExt;
 use lancedb::index::IndexConfig;
 use lancedb::index::scalar::BTreeIndexBuilder;
@@ -278,6 +278,177 @@ impl LanceDBVectorStore {
             Err(err) => Err(anyhow!(err.to_string())),
         }
     }
+
+    /// Exact nearest neighbor search with the given distance metric.
+    /// Each row carries its distance in the `_distance` column, closest first.
+    /// Returns no rows if the table was not created yet.
+    pub async fn nearest_neighbors(
+        &self,
+        vector: Vec<f64>,
+        distance_type: lancedb::DistanceType,
+        filter: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<Value>> {
+        let Some(table) = self.table.clone() else {
+            return Ok(vec![]);
+        };
+
+        let mut query = table
+            .query()
+            .nearest_to(vector)?
+            .distance_type(distance_type)
+            .bypass_vector_index()
+            .limit(limit);
+
+        if let Some(filter) = filter {
+            query = query.only_if(filter);
+        }
+
+        let result = query.execute().await?;
+        let result = result.try_collect::<Vec<_>>().await.ok();
+        record_batches_to_vec(result)
+    }
+
+    /// Columns holding fixed size float vectors, with their dimension.
+    /// Returns no columns if the table was not created yet.
+    pub async fn vector_columns(&self) -> Result<Vec<(String, usize)>> {
+        let Some(table) = self.table.clone() else {
+            return Ok(vec![]);
+        };
+        let schema = table.schema().await?;
+        Ok(schema
+            .fields()
+            .iter()
+            .filter_map(|field| match field.data_type() {
+                arrow_schema::DataType::FixedSizeList(item, size)
+                    if item.data_type().is_floating() =>
+                {
+                    Some((field.name().clone(), *size as usize))
+                }
+                _ => None,
+            })
+            .collect())
+    }
+
+    /// Cosine vector search on the given column, for tables with more than one vector column.
+    /// With a `text` the results are combined with a full text search and re-ranked with RRF,
+    /// which then requires a full text index. Vector results carry `_distance`, hybrid
+    /// results `_relevance_score`.
+    pub async fn search_column(
+        &self,
+        vector: Vec<f64>,
+        column: &str,
+        text: Option<&str>,
+        filter: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<Value>> {
+        let table = self
+            .table
+            .clone()
+            .ok_or_else(|| anyhow!("Table not initialized"))?;
+
+        let mut query = table
+            .query()
+            .nearest_to(vector)?
+            .column(column)
+            .distance_type(lancedb::DistanceType::Cosine)
+            .limit(limit);
+
+        if let Some(filter) = filter {
+            query = query.only_if(filter);
+        }
+
+        let batches = match text {
+            Some(text) => {
+                query
+                    .full_text_search(FullTextSearchQuery::new(text.to_string()))
+                    .rerank(Arc::new(lancedb::rerankers::rrf::RRFReranker::new(60.0)))
+                    .execute_hybrid(QueryExecutionOptions::default())
+                    .await?
+                    .try_collect::<Vec<_>>()
+                    .await?
+            }
+            None => query.execute().await?.try_collect::<Vec<_>>().await?,
+        };
+        record_batches_to_vec(Some(batches))
+    }
+
+    /// Appends a batch of rows and keeps the ANN index on `column` up to date, so a corpus
+    /// can be indexed while its embeddings are still being computed.
+    ///
+    /// The index is trained once the table holds `min_rows_for_index` rows, but never on fewer
+    /// than [`MIN_ROWS_FOR_VECTOR_INDEX`]. After that every batch is added to it incrementally
+    /// instead of rebuilding it. Rows that are not indexed yet are still found by vector search
+    /// through a flat scan.
+    pub async fn append_indexed(
+        &mut self,
+        items: Vec<Value>,
+        column: &str,
+        min_rows_for_index: usize,
+    ) -> Result<IncrementalIndexStats> {
+        if !items.is_empty() {
+            self.insert(items).await?;
+        }
+
+        let table = self.table.clone().ok_or(anyhow!("Table not initialized"))?;
+        let rows = table.count_rows(None).await?;
+
+        let mut index_created = false;
+        let index_name = match vector_index_name(&table, column).await? {
+            Some(name) => {
+                table
+                    .optimize(lancedb::table::OptimizeAction::Index(
+                        OptimizeOptions::append(),
+                    ))
+                    .await?;
+                Some(name)
+            }
+            None if rows >= min_rows_for_index.max(MIN_ROWS_FOR_VECTOR_INDEX) => {
+                table.create_index(&[column], Index::Auto).execute().await?;
+                index_created = true;
+                vector_index_name(&table, column).await?
+            }
+            None => None,
+        };
+
+        let (indexed_rows, unindexed_rows) = match &index_name {
+            Some(name) => match table.index_stats(name).await? {
+                Some(stats) => (stats.num_indexed_rows, stats.num_unindexed_rows),
+                None => (0, rows),
+            },
+            None => (0, rows),
+        };
+
+        Ok(IncrementalIndexStats {
+            rows,
+            indexed_rows,
+            unindexed_rows,
+            index_created,
+        })
+    }
+}
+
+/// Fewest rows the product quantizer of an IVF_PQ index can be trained on.
+pub const MIN_ROWS_FOR_VECTOR_INDEX: usize = 256;
+
+/// Progress of an incremental index build after one [`LanceDBVectorStore::append_indexed`] call.
+#[derive(serde::Serialize, serde::Deserialize, JsonSchema, Clone, Debug, Default)]
+pub struct IncrementalIndexStats {
+    pub rows: usize,
+    pub indexed_rows: usize,
+    pub unindexed_rows: usize,
+    /// True for the batch that triggered the initial index training
+    pub index_created: bool,
+}
+
+async fn vector_index_name(table: &Table, column: &str) -> Result<Option<String>> {
+    let indices = table.list_indices().await?;
+    Ok(indices
+        .into_iter()
+        .find(|idx| {
+            idx.columns.iter().any(|c| c == column) && idx.index_type.to_string().starts_with("IVF")
+        })
+        .map(|idx| idx.name))
 }
 
 pub fn record_batches_to_vec(batches: Option<Vec<RecordBatch>>) -> Result<Vec<Value>> {
@@ -947,6 +1118,207 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_lance_nearest_neighbors_by_distance_type() -> Result<()> {
+        let test_path = format!("./tmp/{}", create_id());
+        std::fs::create_dir_all(&test_path).unwrap();
+        let mut db = LanceDBVectorStore::new(PathBuf::from(&test_path), "t".to_string()).await?;
+
+        let empty = db
+            .nearest_neighbors(vec![1.0, 0.0], lancedb::DistanceType::L2, None, 10)
+            .await?;
+        assert!(empty.is_empty());
+
+        let records = vec![
+            TestStruct {
+                id: 1,
+                name: "same".to_string(),
+                vector: vec![1.0, 0.0],
+            },
+            TestStruct {
+                id: 2,
+                name: "far_same_direction".to_string(),
+                vector: vec![10.0, 1.0],
+            },
+            TestStruct {
+                id: 3,
+                name: "near_orthogonal".to_string(),
+                vector: vec![0.0, 1.0],
+            },
+        ];
+        let json_records: Vec<Value> = records
+            .into_iter()
+            .map(to_value)
+            .collect::<Result<_, _>>()?;
+        db.upsert(json_records, "id".to_string()).await?;
+
+        let ids = |results: &[Value]| -> Vec<i64> {
+            results
+                .iter()
+                .map(|row| row["id"].as_i64().unwrap())
+                .collect()
+        };
+
+        let l2 = db
+            .nearest_neighbors(vec![1.0, 0.0], lancedb::DistanceType::L2, None, 10)
+            .await?;
+        assert_eq!(ids(&l2), vec![1, 3, 2]);
+        assert!(l2.iter().all(|row| row["_distance"].is_number()));
+
+        let cosine = db
+            .nearest_neighbors(vec![1.0, 0.0], lancedb::DistanceType::Cosine, None, 2)
+            .await?;
+        assert_eq!(ids(&cosine), vec![1, 2]);
+
+        let filtered = db
+            .nearest_neighbors(
+                vec![1.0, 0.0],
+                lancedb::DistanceType::L2,
+                Some("id > 1"),
+                10,
+            )
+            .await?;
+        assert_eq!(ids(&filtered), vec![3, 2]);
+
+        std::fs::remove_dir_all(&test_path).unwrap();
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_lance_search_column() -> Result<()> {
+        #[derive(Serialize, Deserialize)]
+        struct Chunk {
+            id: i32,
+            text: String,
+            title_vector: Vec<f32>,
+            body_vector: Vec<f32>,
+        }
+
+        let test_path = format!("./tmp/{}", create_id());
+        std::fs::create_dir_all(&test_path).unwrap();
+        let mut db = LanceDBVectorStore::new(PathBuf::from(&test_path), "t".to_string()).await?;
+
+        let texts = ["rust ownership", "python typing", "rust async"];
+        let items = texts
+            .iter()
+            .enumerate()
+            .map(|(id, text)| {
+                let mut title_vector = vec![0.0; 4];
+                title_vector[id] = 1.0;
+                let mut body_vector = vec![0.0; 8];
+                body_vector[7 - id] = 1.0;
+                to_value(Chunk {
+                    id: id as i32,
+                    text: text.to_string(),
+                    title_vector,
+                    body_vector,
+                })
+                .map_err(Into::into)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        db.insert(items).await?;
+
+        let mut columns = db.vector_columns().await?;
+        columns.sort();
+        assert_eq!(
+            columns,
+            vec![
+                ("body_vector".to_string(), 8),
+                ("title_vector".to_string(), 4)
+            ]
+        );
+
+        let mut query = vec![0.0; 8];
+        query[5] = 1.0;
+        let results = db
+            .search_column(query, "body_vector", None, None, 2)
+            .await?;
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0]["id"], 2);
+        assert!(results[0]["_distance"].as_f64().unwrap() < 1e-6);
+
+        let results = db
+            .search_column(
+                vec![0.0, 1.0, 0.0, 0.0],
+                "title_vector",
+                None,
+                Some("id > 1"),
+                3,
+            )
+            .await?;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0]["id"], 2);
+
+        db.index("text", Some("FULL TEXT")).await?;
+        let results = db
+            .search_column(
+                vec![1.0, 0.0, 0.0, 0.0],
+                "title_vector",
+                Some("async"),
+                None,
+                3,
+            )
+            .await?;
+        assert!(results[0].get("_relevance_score").is_some());
+        assert!(results.iter().any(|row| row["id"] == 2));
+
+        std::fs::remove_dir_all(&test_path).unwrap();
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_lance_append_indexed() -> Result<()> {
+        let test_path = format!("./tmp/{}", create_id());
+        std::fs::create_dir_all(&test_path).unwrap();
+        let mut db = LanceDBVectorStore::new(PathBuf::from(&test_path), "t".to_string()).await?;
+
+        let batch = |start: i32| -> Result<Vec<Value>> {
+            (start..start + 200)
+                .map(|id| {
+                    let vector = (0..16)
+                        .map(|dim| ((id * 31 + dim * 7) % 97) as f32 / 97.0)
+                        .collect();
+                    to_value(TestStruct {
+                        id,
+                        name: format!("item_{id}"),
+                        vector,
+                    })
+                    .map_err(Into::into)
+                })
+                .collect()
+        };
+
+        // Too few rows to train on, searchable through a flat scan
+        let stats = db.append_indexed(batch(0)?, "vector", 256).await?;
+        assert_eq!(stats.rows, 200);
+        assert_eq!(stats.indexed_rows, 0);
+        assert!(!stats.index_created);
+        assert_eq!(
+            db.vector_search(vec![0.0; 16], None, None, 5, 0)
+                .await?
+                .len(),
+            5
+        );
+
+        let stats = db.append_indexed(batch(200)?, "vector", 256).await?;
+        assert!(stats.index_created);
+        assert_eq!(stats.indexed_rows, 400);
+        assert_eq!(stats.unindexed_rows, 0);
+
+        // Later batches extend the existing index instead of training a new one
+        let stats = db.append_indexed(batch(400)?, "vector", 256).await?;
+        assert!(!stats.index_created);
+        assert_eq!(stats.rows, 600);
+        assert_eq!(stats.unindexed_rows, 0);
+        assert_eq!(db.list_indices().await?.len(), 1);
+
+        std::fs::remove_dir_all(&test_path).unwrap();
+
+        Ok(())
+    }
 }
 
 // impl VectorStoreIndex for LanceDBVectorStore {
@@ -23,6 +23,12 @@ impl dyn Cacheable {
     pub fn downcast_mut<T: Cacheable>(&mut self) -> Option<&mut T> {
         self.as_any_mut().downcast_mut::<T>()
     }
+
+    /// Downcasts a shared entry without cloning the value behind it
+    pub fn downcast_arc<T: Cacheable>(self: std::sync::Arc<Self>) -> Option<std::sync::Arc<T>> {
+        let any: std::sync::Arc<dyn Any + Send + Sync> = self;
+        any.downcast::<T>().ok()
+    }
 }
 
 pub type Timestamp = prost_types::Timestamp;
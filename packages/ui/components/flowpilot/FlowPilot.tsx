@@ -63,7 +63,11 @@ import type {
 	CopilotScope,
 	UnifiedChatMessage,
 } from "../../lib/schema/copilot";
-import type { BoardCommand, Suggestion } from "../../lib/schema/flow/copilot";
+import type {
+	BoardCommand,
+	CopilotUsage,
+	Suggestion,
+} from "../../lib/schema/flow/copilot";
 import type { SurfaceComponent } from "../a2ui/types";
 
 export function FlowPilot({
@@ -101,6 +105,7 @@ export function FlowPilot({
 	const [loadingStartTime, setLoadingStartTime] = useState<number | null>(null);
 	const [elapsedSeconds, setElapsedSeconds] = useState(0);
 	const [tokenCount, setTokenCount] = useState(0);
+	const [usage, setUsage] = useState<CopilotUsage | null>(null);
 	const [planSteps, setPlanSteps] = useState<UnifiedPlanStep[]>([]);
 	const [attachedImages, setAttachedImages] = useState<AttachedImage[]>([]);
 	const [userScrolledUp, setUserScrolledUp] = useState(false);
@@ -497,6 +502,7 @@ export function FlowPilot({
 			setLoadingPhase("initializing");
 			setLoadingStartTime(Date.now());
 			setTokenCount(0);
+			setUsage(null);
 			setPlanSteps([]);
 			setUserScrolledUp(false);
 
@@ -670,6 +676,28 @@ export function FlowPilot({
 						return;
 					}
 
+					// Parse the terminal event, the message already says if the step limit was hit
+					const finishedMatch = token.match(
+						/<finished>([\s\S]*?)<\/finished>/,
+					);
+					if (finishedMatch) {
+						return;
+					}
+
+					// Parse usage events, sent after every model iteration with the running total
+					const usageMatch = token.match(/<usage>([\s\S]*?)<\/usage>/);
+					if (usageMatch) {
+						try {
+							const eventData = JSON.parse(usageMatch[1]);
+							if (eventData.Usage) {
+								setUsage(eventData.Usage);
+							}
+						} catch {
+							// Invalid JSON
+						}
+						return;
+					}
+
 					// Parse plan step events
 					const planStepMatch = token.match(
 						/<plan_step>([\s\S]*?)<\/plan_step>/,
@@ -1167,6 +1195,7 @@ ${userMsg}`;
 				loading={loading}
 				loadingPhase={loadingPhase}
 				elapsedSeconds={elapsedSeconds}
+				usage={usage}
 				runContext={runContext}
 				onNewChat={handleNewChat}
 				onClose={onClose}
@@ -1429,12 +1458,17 @@ ${userMsg}`;
 	);
 }
 
+function formatTokens(tokens: number): string {
+	return tokens >= 1000 ? `${(tokens / 1000).toFixed(1)}k` : `${tokens}`;
+}
+
 // Header component
 interface HeaderProps {
 	title: string;
 	loading: boolean;
 	loadingPhase: LoadingPhase;
 	elapsedSeconds: number;
+	usage: CopilotUsage | null;
 	runContext?: { run_id: string };
 	onNewChat: () => void;
 	onClose?: () => void;
@@ -1459,6 +1493,7 @@ const Header = memo(function Header({
 	loading,
 	loadingPhase,
 	elapsedSeconds,
+	usage,
 	runContext,
 	onNewChat,
 	onClose,
@@ -1529,6 +1564,14 @@ const Header = memo(function Header({
 									<span className="relative inline-flex rounded-full h-1.5 w-1.5 bg-green-500" />
 								</span>
 								{runContext ? "Log context active" : "Ready"}
+								{usage && usage.total_tokens > 0 && (
+									<span
+										title={`${usage.prompt_tokens.toLocaleString()} prompt / ${usage.completion_tokens.toLocaleString()} completion tokens over ${usage.iterations} iteration(s)`}
+									>
+										· {formatTokens(usage.total_tokens)} tokens
+										{usage.cost != null && ` · ~$${usage.cost.toFixed(4)}`}
+									</span>
+								)}
 							</div>
 						)}
 					</div>
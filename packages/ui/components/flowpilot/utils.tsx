@@ -28,6 +28,7 @@ export function getCommandIcon(cmd: BoardCommand, size = "w-4 h-4") {
 		case "DisconnectPins":
 			return <Unlink2Icon className={size} />;
 		case "UpdateNodePin":
+		case "RenameNode":
 			return <PencilIcon className={size} />;
 		case "MoveNode":
 			return <MoveIcon className={size} />;
@@ -63,6 +64,8 @@ export function getCommandSummary(cmd: BoardCommand): string {
 			return `Set ${cmd.pin_id}`;
 		case "MoveNode":
 			return "Move node";
+		case "RenameNode":
+			return `Rename to ${cmd.new_name}`;
 		default:
 			return cmd.command_type.replace(/([A-Z])/g, " $1").trim();
 	}
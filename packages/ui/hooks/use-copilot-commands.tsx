@@ -593,6 +593,29 @@ export function useCopilotCommands({
 						break;
 					}
 
+					case "RenameNode": {
+						// Only real nodes, resolveNode also returns layers as nodes
+						const node = boardNodes[cmd.node_id];
+						if (!node) {
+							toastError(
+								`Cannot rename node: "${cmd.node_id}" not found`,
+								<XIcon />,
+							);
+							break;
+						}
+
+						console.log(
+							`[RenameNode] "${node.friendly_name}" → "${cmd.new_name}"`,
+						);
+						await executeCommand(
+							updateNodeCommand({
+								node: { ...node, friendly_name: cmd.new_name },
+								old_node: node,
+							}),
+						);
+						break;
+					}
+
 					case "MoveNode": {
 						const node = resolveNode(cmd.node_id);
 						if (!node) break;
@@ -717,8 +740,8 @@ export function useCopilotCommands({
 
 						const updatedComment: IComment = {
 							...existingComment,
-							content: cmd.content ?? existingComment.content,
-							color: cmd.color ?? existingComment.content,
+							content: cmd.content,
+							color: cmd.color ?? existingComment.color,
 						};
 
 						console.log(
@@ -727,7 +750,7 @@ export function useCopilotCommands({
 						await executeCommand(
 							upsertCommentCommand({
 								comment: updatedComment,
-								current_layer: currentLayer,
+								current_layer: existingComment.layer ?? currentLayer,
 								old_comment: existingComment,
 							}),
 						);
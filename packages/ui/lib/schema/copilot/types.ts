@@ -1,5 +1,10 @@
 import type { SurfaceComponent } from "../../../components/a2ui/types";
-import type { BoardCommand } from "../flow/copilot";
+import type {
+	BoardCommand,
+	CopilotUsage,
+	LoopOutcome,
+	StopReason,
+} from "../flow/copilot";
 
 /** The scope of what the copilot agent can modify */
 export type CopilotScope = "Board" | "Frontend" | "Both";
@@ -55,6 +60,8 @@ export interface UnifiedContext {
 	scope: CopilotScope;
 	run_context?: RunContext;
 	action_context?: UIActionContext;
+	/** Limit for the model iterations of the turn, the copilot default if unset */
+	max_iterations?: number;
 }
 
 /** A suggestion for follow-up actions (works for both board and UI) */
@@ -94,6 +101,12 @@ export interface UnifiedCopilotResponse {
 
 	/** The actual scope that was used (agent may decide to focus on one area) */
 	active_scope: CopilotScope;
+
+	/** Tokens and estimated cost of the turn */
+	usage?: CopilotUsage;
+
+	/** Why the agent stopped, `MaxIterationsReached` means the work is unfinished */
+	stop_reason?: StopReason;
 }
 
 /** Status of a plan step */
@@ -116,4 +129,6 @@ export type UnifiedStreamEvent =
 	| { Thinking: string }
 	| { FocusNode: { node_id: string; description: string } }
 	| { ComponentPreview: SurfaceComponent[] }
-	| { ScopeDecision: CopilotScope };
+	| { ScopeDecision: CopilotScope }
+	| { Usage: CopilotUsage }
+	| { Finished: LoopOutcome };
@@ -103,6 +103,12 @@ export type BoardCommand =
 			target_layer?: string;
 			summary?: string;
 	  }
+	| {
+			command_type: "RenameNode";
+			node_id: string;
+			new_name: string;
+			summary?: string;
+	  }
 	| {
 			command_type: "CreateVariable";
 			name: string;
@@ -133,7 +139,7 @@ export type BoardCommand =
 	| {
 			command_type: "UpdateComment";
 			comment_id: string;
-			content?: string;
+			content: string;
 			color?: string;
 			summary?: string;
 	  }
@@ -166,6 +172,30 @@ export interface CopilotResponse {
 	message: string;
 	commands: BoardCommand[];
 	suggestions: Suggestion[];
+	usage?: CopilotUsage;
+	stop_reason?: StopReason;
+}
+
+/** Why the tool loop of a copilot turn ended */
+export type StopReason = "Completed" | "MaxIterationsReached" | "NoToolCalls";
+
+/** Terminal stream event of a copilot turn */
+export interface LoopOutcome {
+	reason: StopReason;
+	iterations: number;
+	max_iterations: number;
+}
+
+/** Tokens a copilot turn consumed, summed over all model iterations */
+export interface CopilotUsage {
+	prompt_tokens: number;
+	completion_tokens: number;
+	total_tokens: number;
+	iterations: number;
+	/** Iterations the provider reported no usage for, counted locally */
+	estimated_iterations: number;
+	/** Estimated cost in USD, missing if the model has no pricing */
+	cost?: number;
 }
 
 export interface PlanStep {
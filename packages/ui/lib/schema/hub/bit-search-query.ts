@@ -1,5 +1,9 @@
 export interface IBitSearchQuery {
 	bit_types?: IBitTypes[] | null;
+	/**
+	 * `next_cursor` of the previous page
+	 */
+	cursor?: null | string;
 	limit?: number | null;
 	offset?: number | null;
 	search?: null | string;
@@ -7,6 +7,7 @@ import type {
 	IRunPayload,
 	IVersionType,
 } from "@tm9657/flow-like-ui";
+import type { IValidationMessage } from "../event-state";
 
 export class EmptyEventState implements IEventState {
 	getEvent(
@@ -41,7 +42,7 @@ export class EmptyEventState implements IEventState {
 		appId: string,
 		eventId: string,
 		version?: [number, number, number],
-	): Promise<void> {
+	): Promise<IValidationMessage[]> {
 		throw new Error("Method not implemented.");
 	}
 	upsertEventFeedback(
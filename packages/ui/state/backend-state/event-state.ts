@@ -14,6 +14,14 @@ export interface IOAuthCheckResult {
 	missingProviders: IOAuthProvider[];
 }
 
+export interface IValidationMessage {
+	node_id: string;
+	/** The pin the message is about, null if it concerns the whole node */
+	pin_id?: string | null;
+	severity: "Error" | "Warning";
+	message: string;
+}
+
 export interface IEventState {
 	/** Whether events always execute remotely (server-side). When true, secrets are handled server-side and don't need to be prompted or sent from the client. */
 	readonly alwaysRemote?: boolean;
@@ -38,11 +46,12 @@ export interface IEventState {
 	/** Check OAuth requirements for an event's board. Returns missing providers. */
 	checkEventOAuth?(appId: string, event: IEvent): Promise<IOAuthCheckResult>;
 	deleteEvent(appId: string, eventId: string): Promise<void>;
+	/** Checks the event references and returns the validation messages of its board nodes */
 	validateEvent(
 		appId: string,
 		eventId: string,
 		version?: [number, number, number],
-	): Promise<void>;
+	): Promise<IValidationMessage[]>;
 	upsertEventFeedback(
 		appId: string,
 		eventId: string,
@@ -5,11 +5,12 @@ use crate::engine::WasmEngine;
 use crate::error::{WasmError, WasmResult};
 use crate::host_functions::HostState;
 use crate::limits::WasmSecurityConfig;
+use crate::metrics::{self, ExecutionSample, FailureReason};
 use std::sync::Arc;
 use std::{
     fs,
     process::Command,
-    time::{SystemTime, UNIX_EPOCH},
+    time::{Instant, SystemTime, UNIX_EPOCH},
 };
 use wasmtime::component::{Instance, Linker};
 use wasmtime::{Engine, Store};
@@ -22,6 +23,7 @@ pub struct WasmComponentInstance {
     instance: Instance,
     component: Arc<WasmComponent>,
     fuel_limit: u64,
+    package_id: Option<String>,
 }
 
 impl WasmComponentInstance {
@@ -61,6 +63,7 @@ impl WasmComponentInstance {
             instance,
             component,
             fuel_limit,
+            package_id: None,
         })
     }
 
@@ -263,10 +266,45 @@ impl WasmComponentInstance {
         Ok(version)
     }
 
+    /// Set the registry package id reported with the execution metrics
+    pub fn set_package_id(&mut self, package_id: impl Into<String>) {
+        self.package_id = Some(package_id.into());
+    }
+
+    /// Call the run export and record its metrics. Components do not expose their linear
+    /// memory, so no memory is recorded for them.
     pub async fn call_run(
         &mut self,
         input: &WasmExecutionInput,
     ) -> WasmResult<WasmExecutionResult> {
+        let started = Instant::now();
+        let fuel_before = self.remaining_fuel();
+
+        let result = self.run_export(input).await;
+
+        let failure = match &result {
+            Ok(exec_result) if exec_result.error.is_some() => Some(FailureReason::Node),
+            Ok(_) => None,
+            Err(e) => Some(FailureReason::from_error(e)),
+        };
+        metrics::record_execution(&ExecutionSample {
+            node: &input.node_name,
+            package: self
+                .package_id
+                .as_deref()
+                .unwrap_or(metrics::UNKNOWN_PACKAGE),
+            duration: started.elapsed(),
+            memory_bytes: None,
+            fuel_consumed: fuel_before
+                .zip(self.remaining_fuel())
+                .map(|(before, after)| before.saturating_sub(after)),
+            failure,
+        });
+
+        result
+    }
+
+    async fn run_export(&mut self, input: &WasmExecutionInput) -> WasmResult<WasmExecutionResult> {
         let input_json = serde_json::to_string(input).map_err(WasmError::Json)?;
         let func = match self
             .instance
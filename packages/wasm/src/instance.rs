@@ -9,8 +9,10 @@ use crate::host_functions::linker::{register_host_functions, StoreData};
 use crate::host_functions::HostState;
 use crate::limits::WasmSecurityConfig;
 use crate::memory::{WasmAllocator, WasmMemory};
+use crate::metrics::{self, ExecutionSample, FailureReason};
 use crate::module::WasmModule;
 use std::sync::Arc;
+use std::time::Instant;
 use wasmtime::{Instance, Linker, Memory, Store, TypedFunc};
 
 /// An instantiated WASM module ready for execution
@@ -33,6 +35,8 @@ pub struct WasmInstance {
     dealloc_func: Option<TypedFunc<(i32, i32), ()>>,
     /// Fuel limit for tracking
     fuel_limit: u64,
+    /// Registry package the module belongs to, labels the execution metrics
+    package_id: Option<String>,
 }
 
 impl WasmInstance {
@@ -146,6 +150,7 @@ impl WasmInstance {
             alloc_func,
             dealloc_func,
             fuel_limit,
+            package_id: None,
         })
     }
 
@@ -257,11 +262,44 @@ impl WasmInstance {
         self.get_nodes_func.is_some()
     }
 
-    /// Call the run export with execution input
+    /// Set the registry package id reported with the execution metrics
+    pub fn set_package_id(&mut self, package_id: impl Into<String>) {
+        self.package_id = Some(package_id.into());
+    }
+
+    /// Call the run export with execution input and record its metrics
     pub async fn call_run(
         &mut self,
         input: &WasmExecutionInput,
     ) -> WasmResult<WasmExecutionResult> {
+        let started = Instant::now();
+        let fuel_before = self.remaining_fuel();
+
+        let result = self.run_export(input).await;
+
+        let failure = match &result {
+            Ok(exec_result) if exec_result.error.is_some() => Some(FailureReason::Node),
+            Ok(_) => None,
+            Err(e) => Some(FailureReason::from_error(e)),
+        };
+        metrics::record_execution(&ExecutionSample {
+            node: &input.node_name,
+            package: self
+                .package_id
+                .as_deref()
+                .unwrap_or(metrics::UNKNOWN_PACKAGE),
+            duration: started.elapsed(),
+            memory_bytes: Some(self.memory_size()),
+            fuel_consumed: fuel_before
+                .zip(self.remaining_fuel())
+                .map(|(before, after)| before.saturating_sub(after)),
+            failure,
+        });
+
+        result
+    }
+
+    async fn run_export(&mut self, input: &WasmExecutionInput) -> WasmResult<WasmExecutionResult> {
         // Serialize input to JSON
         let input_json = serde_json::to_vec(input).map_err(WasmError::Json)?;
         let input_len = input_json.len() as u32;
@@ -53,6 +53,7 @@ pub mod instance;
 pub mod limits;
 pub mod manifest;
 pub mod memory;
+pub mod metrics;
 pub mod module;
 pub mod node;
 pub mod registry;
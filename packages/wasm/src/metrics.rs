@@ -0,0 +1,183 @@
+//! Execution metrics for WASM nodes
+//!
+//! With the `metrics` feature every `run` call is recorded through the `metrics` facade, so
+//! the numbers show up in whatever recorder the host installed, e.g. the Prometheus exporter
+//! of the backend runtimes. Without the feature recording compiles to nothing.
+//!
+//! | Metric | Type | Labels |
+//! |--------|------|--------|
+//! | `wasm_node_executions_total` | counter | node, package, status |
+//! | `wasm_node_execution_duration_seconds` | histogram | node, package |
+//! | `wasm_node_memory_bytes` | histogram | node, package |
+//! | `wasm_node_fuel_consumed` | histogram | node, package |
+//! | `wasm_node_failures_total` | counter | node, package, reason |
+
+use crate::error::WasmError;
+use std::time::Duration;
+
+pub const EXECUTIONS_TOTAL: &str = "wasm_node_executions_total";
+pub const EXECUTION_DURATION_SECONDS: &str = "wasm_node_execution_duration_seconds";
+pub const MEMORY_BYTES: &str = "wasm_node_memory_bytes";
+pub const FUEL_CONSUMED: &str = "wasm_node_fuel_consumed";
+pub const FAILURES_TOTAL: &str = "wasm_node_failures_total";
+
+/// Buckets for [`EXECUTION_DURATION_SECONDS`], nodes mostly run in milliseconds
+pub const DURATION_BUCKETS: &[f64] = &[
+    0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 5.0, 30.0, 120.0,
+];
+
+/// Package label of instances that were not given a package id
+pub const UNKNOWN_PACKAGE: &str = "unknown";
+
+/// Why a run failed, the `reason` label of [`FAILURES_TOTAL`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureReason {
+    /// The epoch deadline was reached
+    Timeout,
+    /// The fuel limit was exhausted
+    Fuel,
+    /// The memory limit was exceeded
+    Memory,
+    /// The guest trapped, e.g. `unreachable` or an out of bounds access
+    Trap,
+    /// The run returned an error result
+    Node,
+    /// Anything else, like a malformed result
+    Error,
+}
+
+impl FailureReason {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FailureReason::Timeout => "timeout",
+            FailureReason::Fuel => "fuel",
+            FailureReason::Memory => "memory",
+            FailureReason::Trap => "trap",
+            FailureReason::Node => "node",
+            FailureReason::Error => "error",
+        }
+    }
+
+    pub fn from_error(error: &WasmError) -> Self {
+        match error {
+            WasmError::Timeout { .. } => FailureReason::Timeout,
+            WasmError::OutOfFuel { .. } => FailureReason::Fuel,
+            WasmError::OutOfMemory { .. } => FailureReason::Memory,
+            WasmError::Execution { message, .. } if message.starts_with("Call failed") => {
+                FailureReason::Trap
+            }
+            WasmError::MemoryAccess { .. } => FailureReason::Trap,
+            _ => FailureReason::Error,
+        }
+    }
+}
+
+/// Measurements of a single `run` call
+#[derive(Debug, Clone)]
+pub struct ExecutionSample<'a> {
+    pub node: &'a str,
+    pub package: &'a str,
+    pub duration: Duration,
+    /// Size of the linear memory after the run. It never shrinks, so this is the peak.
+    pub memory_bytes: Option<usize>,
+    pub fuel_consumed: Option<u64>,
+    pub failure: Option<FailureReason>,
+}
+
+/// Records a run with the installed metrics recorder.
+#[cfg(feature = "metrics")]
+pub fn record_execution(sample: &ExecutionSample<'_>) {
+    let node = sample.node.to_string();
+    let package = sample.package.to_string();
+    let status = if sample.failure.is_some() {
+        "failure"
+    } else {
+        "success"
+    };
+
+    ::metrics::counter!(EXECUTIONS_TOTAL,
+        "node" => node.clone(),
+        "package" => package.clone(),
+        "status" => status
+    )
+    .increment(1);
+    ::metrics::histogram!(EXECUTION_DURATION_SECONDS,
+        "node" => node.clone(),
+        "package" => package.clone()
+    )
+    .record(sample.duration.as_secs_f64());
+
+    if let Some(memory_bytes) = sample.memory_bytes {
+        ::metrics::histogram!(MEMORY_BYTES, "node" => node.clone(), "package" => package.clone())
+            .record(memory_bytes as f64);
+    }
+    if let Some(fuel) = sample.fuel_consumed {
+        ::metrics::histogram!(FUEL_CONSUMED, "node" => node.clone(), "package" => package.clone())
+            .record(fuel as f64);
+    }
+    if let Some(reason) = sample.failure {
+        ::metrics::counter!(FAILURES_TOTAL,
+            "node" => node,
+            "package" => package,
+            "reason" => reason.as_str()
+        )
+        .increment(1);
+    }
+}
+
+/// Records a run with the installed metrics recorder.
+#[cfg(not(feature = "metrics"))]
+pub fn record_execution(_sample: &ExecutionSample<'_>) {}
+
+/// Registers the descriptions of the WASM metrics, call after installing the recorder.
+#[cfg(feature = "metrics")]
+pub fn describe_metrics() {
+    ::metrics::describe_counter!(EXECUTIONS_TOTAL, "Total number of WASM node executions");
+    ::metrics::describe_histogram!(
+        EXECUTION_DURATION_SECONDS,
+        ::metrics::Unit::Seconds,
+        "WASM node execution duration in seconds"
+    );
+    ::metrics::describe_histogram!(
+        MEMORY_BYTES,
+        ::metrics::Unit::Bytes,
+        "Linear memory of a WASM node instance after its execution"
+    );
+    ::metrics::describe_histogram!(FUEL_CONSUMED, "Fuel a WASM node execution consumed");
+    ::metrics::describe_counter!(
+        FAILURES_TOTAL,
+        "Failed WASM node executions by reason (timeout, fuel, memory, trap, node, error)"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_failure_reason_from_error() {
+        let cases = [
+            (WasmError::Timeout { duration_ms: 10 }, "timeout"),
+            (WasmError::OutOfFuel { limit: 100 }, "fuel"),
+            (
+                WasmError::OutOfMemory {
+                    requested: 2,
+                    limit: 1,
+                },
+                "memory",
+            ),
+            (
+                WasmError::execution("run", "Call failed: wasm trap: unreachable"),
+                "trap",
+            ),
+            (
+                WasmError::execution("run", "Returned error code: -1"),
+                "error",
+            ),
+            (WasmError::Internal("boom".to_string()), "error"),
+        ];
+        for (error, reason) in cases {
+            assert_eq!(FailureReason::from_error(&error).as_str(), reason);
+        }
+    }
+}
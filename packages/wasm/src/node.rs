@@ -350,6 +350,9 @@ impl NodeLogic for WasmNodeLogic {
             .instantiate(&self.engine, self.security.clone())
             .await
             .map_err(|e| flow_like_types::anyhow!("Failed to create WASM instance: {}", e))?;
+        if let Some(package_id) = &self.package_id {
+            instance.set_package_id(package_id.as_str());
+        }
 
         let definition = self
             .get_definition()
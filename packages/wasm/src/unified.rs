@@ -82,6 +82,15 @@ impl UnifiedInstance {
         }
     }
 
+    /// Set the registry package id reported with the execution metrics
+    pub fn set_package_id(&mut self, package_id: impl Into<String>) {
+        match self {
+            UnifiedInstance::Module(i) => i.set_package_id(package_id),
+            #[cfg(feature = "component-model")]
+            UnifiedInstance::Component(i) => i.set_package_id(package_id),
+        }
+    }
+
     pub fn host_state(&self) -> &HostState {
         match self {
             UnifiedInstance::Module(i) => i.host_state(),
@@ -0,0 +1,214 @@
+//! Tests for the execution metrics recorded by `WasmInstance::call_run`
+
+#![cfg(feature = "metrics")]
+
+use flow_like_wasm::abi::WasmExecutionInput;
+use flow_like_wasm::engine::{WasmConfig, WasmEngine};
+use flow_like_wasm::instance::WasmInstance;
+use flow_like_wasm::limits::WasmSecurityConfig;
+use flow_like_wasm::metrics::{
+    EXECUTIONS_TOTAL, EXECUTION_DURATION_SECONDS, FAILURES_TOTAL, FUEL_CONSUMED, MEMORY_BYTES,
+};
+use flow_like_wasm::module::WasmModule;
+use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+use metrics_util::CompositeKey;
+use std::sync::Arc;
+
+const RESULT: &str = r#"{"outputs":{"sum":3},"activate_exec":["exec_out"]}"#;
+
+/// A node whose `run` returns a fixed result, or traps if `trap` is set
+fn node_wat(trap: bool) -> String {
+    let run_body = if trap {
+        "unreachable".to_string()
+    } else {
+        format!(
+            "(i64.or (i64.shl (i64.const 1024) (i64.const 32)) (i64.const {}))",
+            RESULT.len()
+        )
+    };
+    format!(
+        r#"
+        (module
+            (memory (export "memory") 1)
+            (global $next (mut i32) (i32.const 4096))
+            (data (i32.const 1024) "{data}")
+            (func (export "alloc") (param $size i32) (result i32)
+                (local $ptr i32)
+                (local.set $ptr (global.get $next))
+                (global.set $next (i32.add (global.get $next) (local.get $size)))
+                (local.get $ptr))
+            (func (export "get_node") (result i64)
+                (i64.const -1))
+            (func (export "run") (param i32 i32) (result i64)
+                {run_body})
+        )
+    "#,
+        data = RESULT.replace('"', "\\\""),
+    )
+}
+
+fn input(node_name: &str) -> WasmExecutionInput {
+    WasmExecutionInput {
+        inputs: serde_json::Map::new(),
+        node_id: "node_id".to_string(),
+        run_id: "run_id".to_string(),
+        app_id: "app".to_string(),
+        board_id: "board".to_string(),
+        user_id: "user".to_string(),
+        stream_state: false,
+        log_level: 0,
+        node_name: node_name.to_string(),
+    }
+}
+
+/// Runs `node_name` once on a fresh instance of the module
+async fn run_node(trap: bool, node_name: &str, package_id: Option<&str>) -> bool {
+    let config = WasmConfig {
+        cache_dir: None,
+        ..WasmConfig::default()
+    };
+    let engine = WasmEngine::new(config).expect("Failed to create engine");
+    let bytes = wat::parse_str(node_wat(trap)).expect("Failed to parse WAT");
+    let module = WasmModule::from_bytes(&engine, &bytes, format!("metrics_{trap}"))
+        .await
+        .expect("Failed to compile module");
+
+    let mut instance = WasmInstance::new(&engine, Arc::new(module), WasmSecurityConfig::default())
+        .await
+        .expect("Failed to instantiate module");
+    if let Some(package_id) = package_id {
+        instance.set_package_id(package_id);
+    }
+    instance.call_run(&input(node_name)).await.is_ok()
+}
+
+type Snapshot = Vec<(CompositeKey, DebugValue)>;
+
+/// Runs `f` on a single threaded runtime with a local debugging recorder and returns what
+/// was recorded. Histograms are drained by a snapshot, so it is taken once.
+fn record<F: std::future::Future<Output = ()>>(f: F) -> Snapshot {
+    let recorder = DebuggingRecorder::new();
+    let snapshotter = recorder.snapshotter();
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    metrics::with_local_recorder(&recorder, || runtime.block_on(f));
+    snapshotter
+        .snapshot()
+        .into_vec()
+        .into_iter()
+        .map(|(key, _, _, value)| (key, value))
+        .collect()
+}
+
+/// Values of the metric `name` whose labels contain all of `labels`
+fn values<'a>(
+    snapshot: &'a Snapshot,
+    name: &'a str,
+    labels: &'a [(&'a str, &'a str)],
+) -> impl Iterator<Item = &'a DebugValue> {
+    snapshot
+        .iter()
+        .filter(move |(key, _)| {
+            key.key().name() == name
+                && labels.iter().all(|(label, value)| {
+                    key.key()
+                        .labels()
+                        .any(|l| l.key() == *label && l.value() == *value)
+                })
+        })
+        .map(|(_, value)| value)
+}
+
+fn counter(snapshot: &Snapshot, name: &str, labels: &[(&str, &str)]) -> u64 {
+    values(snapshot, name, labels)
+        .map(|value| match value {
+            DebugValue::Counter(count) => *count,
+            other => panic!("{name} is not a counter: {other:?}"),
+        })
+        .sum()
+}
+
+fn histogram(snapshot: &Snapshot, name: &str, labels: &[(&str, &str)]) -> Vec<f64> {
+    values(snapshot, name, labels)
+        .flat_map(|value| match value {
+            DebugValue::Histogram(samples) => samples.iter().map(|s| s.into_inner()),
+            other => panic!("{name} is not a histogram: {other:?}"),
+        })
+        .collect()
+}
+
+#[test]
+fn test_successful_runs_increment_counters_with_labels() {
+    let snapshot = record(async {
+        assert!(run_node(false, "add", Some("math-nodes")).await);
+        assert!(run_node(false, "add", Some("math-nodes")).await);
+        assert!(run_node(false, "subtract", None).await);
+    });
+
+    assert_eq!(
+        counter(
+            &snapshot,
+            EXECUTIONS_TOTAL,
+            &[
+                ("node", "add"),
+                ("package", "math-nodes"),
+                ("status", "success")
+            ]
+        ),
+        2
+    );
+    assert_eq!(
+        counter(
+            &snapshot,
+            EXECUTIONS_TOTAL,
+            &[("node", "subtract"), ("package", "unknown")]
+        ),
+        1
+    );
+    assert_eq!(counter(&snapshot, FAILURES_TOTAL, &[]), 0);
+
+    let add = [("node", "add"), ("package", "math-nodes")];
+    assert_eq!(
+        histogram(&snapshot, EXECUTION_DURATION_SECONDS, &add).len(),
+        2
+    );
+    // One page of linear memory, the node never grows it
+    assert_eq!(histogram(&snapshot, MEMORY_BYTES, &add), vec![65536.0; 2]);
+    let fuel = histogram(&snapshot, FUEL_CONSUMED, &add);
+    assert_eq!(fuel.len(), 2);
+    assert!(fuel.iter().all(|fuel| *fuel > 0.0));
+}
+
+#[test]
+fn test_trap_is_recorded_as_failure() {
+    let snapshot = record(async {
+        assert!(!run_node(true, "divide", Some("math-nodes")).await);
+    });
+
+    assert_eq!(
+        counter(
+            &snapshot,
+            EXECUTIONS_TOTAL,
+            &[("node", "divide"), ("status", "failure")]
+        ),
+        1
+    );
+    assert_eq!(
+        counter(
+            &snapshot,
+            FAILURES_TOTAL,
+            &[
+                ("node", "divide"),
+                ("package", "math-nodes"),
+                ("reason", "trap")
+            ]
+        ),
+        1
+    );
+    assert_eq!(
+        counter(&snapshot, EXECUTIONS_TOTAL, &[("status", "success")]),
+        0
+    );
+}